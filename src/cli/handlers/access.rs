@@ -0,0 +1,98 @@
+//! Access handlers: `narra access ...`.
+
+use anyhow::Result;
+
+use crate::cli::output::schema::AccessRevoked;
+use crate::cli::output::{
+    output_json, output_json_list, print_hint, print_kv, print_success, print_table, OutputMode,
+};
+use crate::init::AppContext;
+use crate::services::access::{AccessService, Role};
+
+pub fn handle_whoami(ctx: &AppContext, mode: OutputMode) {
+    let policy = &ctx.access;
+    if mode == OutputMode::Json {
+        output_json(policy.as_ref());
+        return;
+    }
+    print_kv("User", policy.user.as_deref().unwrap_or("(unknown)"));
+    print_kv("Role", &policy.role.to_string());
+    if !policy.entity_types.is_empty() {
+        print_kv("May change", &policy.entity_types.join(", "));
+    }
+    if !policy.enforced {
+        print_hint("No grants in this world: everyone has full access.");
+    }
+}
+
+pub async fn handle_list(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let grants = AccessService::new(ctx.db.clone()).list().await?;
+    if mode == OutputMode::Json {
+        output_json_list(&grants);
+        return Ok(());
+    }
+    if grants.is_empty() {
+        println!("No grants: everyone has full access.");
+        print_hint("Claim the world with: narra access grant <you> --role owner");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = grants
+        .iter()
+        .map(|g| {
+            vec![
+                g.user.clone(),
+                g.role.to_string(),
+                if g.entity_types.is_empty() {
+                    "all".to_string()
+                } else {
+                    g.entity_types.join(", ")
+                },
+                g.granted_by.clone().unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+    print_table(&["User", "Role", "Entity Types", "Granted By"], rows);
+    Ok(())
+}
+
+pub async fn handle_grant(
+    ctx: &AppContext,
+    user: &str,
+    role: &str,
+    entity_types: &[String],
+    mode: OutputMode,
+) -> Result<()> {
+    let role: Role = role.parse()?;
+    let grant = AccessService::new(ctx.db.clone())
+        .grant(&ctx.access, user, role, entity_types.to_vec())
+        .await?;
+
+    if mode == OutputMode::Json {
+        output_json(&grant);
+    } else {
+        print_success(&format!("{} is now {}", grant.user, grant.role));
+        if !ctx.access.enforced {
+            print_hint("Access is now enforced: users without a grant can only read.");
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_revoke(ctx: &AppContext, user: &str, mode: OutputMode) -> Result<()> {
+    let removed = AccessService::new(ctx.db.clone())
+        .revoke(&ctx.access, user)
+        .await?;
+
+    if mode == OutputMode::Json {
+        output_json(&AccessRevoked {
+            user: user.to_string(),
+            revoked: removed,
+        });
+    } else if removed {
+        print_success(&format!("Revoked {}'s grant", user));
+    } else {
+        println!("{} has no grant.", user);
+    }
+    Ok(())
+}
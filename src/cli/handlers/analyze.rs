@@ -1,8 +1,12 @@
 //! CLI handlers for narrative analytics commands.
 
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
 use serde::Serialize;
 
+use crate::cli::output::schema::{CharacterFacets, FacetStatus, PhasesCleared, PluginCommandInfo};
 use crate::cli::output::{
     output_json, output_json_list, print_error, print_header, print_hint, print_kv, print_success,
     print_table, OutputMode,
@@ -10,19 +14,62 @@ use crate::cli::output::{
 use crate::cli::resolve::resolve_single;
 use crate::init::AppContext;
 use crate::repository::KnowledgeRepository;
+use crate::services::balance::{BalanceOptions, BalanceService};
+use crate::services::confusability::{ConfusabilityOptions, ConfusabilityService};
+use crate::services::ensemble::{EnsembleOptions, EnsembleService};
+use crate::services::fact_contradictions::{FactContradictionOptions, FactContradictionService};
+use crate::services::foreshadowing::ForeshadowingService;
+use crate::services::goal_conflicts::GoalConflictService;
+use crate::services::kmeans;
+use crate::services::knowledge_matrix::{symbol, KnowledgeMatrixService, UNAWARE};
+use crate::services::pressure::PressureService;
+use crate::services::reader_knowledge::ReaderKnowledgeService;
+use crate::services::reorder::ReorderService;
+use crate::services::report::{dossier_charts, render_html, situation_charts};
+use crate::services::scenario::{
+    self, Scenario, ScenarioDiff, ScenarioReport, ScenarioSandbox, WorldMetrics,
+};
+use crate::services::setups::SetupsService;
+use crate::services::spotlight::{SpotlightOptions, SpotlightService};
+use crate::services::templates::Templates;
+use crate::services::terminology::TerminologyService;
+use crate::services::thread_deadlines::ThreadDeadlineService;
 use crate::services::{
     generate_suggested_fix, CentralityMetric, ClusteringService, CompositeIntelligenceService,
     EntityType, GraphAnalyticsService, InfluenceService, IronyService, PhaseWeights,
     RoleInferenceService, TemporalService, TensionService, VectorOpsService,
 };
 
+/// Members of the `--phase` phase, announced in human output.
+async fn phase_members(
+    ctx: &AppContext,
+    phase: Option<usize>,
+    mode: OutputMode,
+) -> Result<Option<HashSet<String>>> {
+    let Some(phase_id) = phase else {
+        return Ok(None);
+    };
+    let phase = TemporalService::new(ctx.db.clone())
+        .find_phase(phase_id)
+        .await?;
+    if mode != OutputMode::Json {
+        print_hint(&format!(
+            "Phase {}: {} ({} members)",
+            phase.phase_id, phase.label, phase.member_count
+        ));
+    }
+    Ok(Some(phase.member_ids()))
+}
+
 pub async fn handle_centrality(
     ctx: &AppContext,
     scope: Option<&str>,
     limit: usize,
+    phase: Option<usize>,
     mode: OutputMode,
 ) -> Result<()> {
-    let service = GraphAnalyticsService::new(ctx.db.clone());
+    let members = phase_members(ctx, phase, mode).await?;
+    let service = GraphAnalyticsService::new(ctx.db.clone()).with_members(members);
     let metrics = vec![
         CentralityMetric::Degree,
         CentralityMetric::Betweenness,
@@ -72,7 +119,7 @@ pub async fn handle_influence(
     depth: usize,
     mode: OutputMode,
 ) -> Result<()> {
-    let service = InfluenceService::new(ctx.db.clone());
+    let service = InfluenceService::with_ontology(ctx.db.clone(), ctx.ontology.clone());
     let result = service
         .trace_propagation(character, depth)
         .await
@@ -109,9 +156,11 @@ pub async fn handle_irony(
     ctx: &AppContext,
     character: Option<&str>,
     threshold: usize,
+    phase: Option<usize>,
     mode: OutputMode,
 ) -> Result<()> {
-    let service = IronyService::new(ctx.db.clone());
+    let members = phase_members(ctx, phase, mode).await?;
+    let service = IronyService::new(ctx.db.clone()).with_members(members);
     let report = service
         .generate_report(character, threshold)
         .await
@@ -217,7 +266,17 @@ pub async fn handle_conflicts(
         .map_err(|e| anyhow::anyhow!("Knowledge conflicts query failed: {}", e))?;
 
     // Flatten and filter
-    let mut rows_data: Vec<(String, String, String, String)> = Vec::new();
+    #[derive(Serialize)]
+    struct ConflictRow {
+        character: String,
+        target: String,
+        certainty: String,
+        truth_value: String,
+        /// How the belief was passed on, nearest first
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        derivation: Vec<String>,
+    }
+    let mut rows: Vec<ConflictRow> = Vec::new();
     for conflict in &conflicts {
         for state in &conflict.conflicting_states {
             if let Some(filter) = character {
@@ -236,47 +295,130 @@ pub async fn handle_conflicts(
                 .as_deref()
                 .unwrap_or("unknown")
                 .to_string();
-            rows_data.push((
-                state.character_id.clone(),
-                conflict.target.clone(),
-                format!("{:?}", state.certainty),
-                truth,
-            ));
+            rows.push(ConflictRow {
+                character: state.character_id.clone(),
+                target: conflict.target.clone(),
+                certainty: format!("{:?}", state.certainty),
+                truth_value: truth,
+                derivation: state.derivation.clone(),
+            });
         }
     }
-    rows_data.truncate(limit);
+    rows.truncate(limit);
 
     if mode == OutputMode::Json {
-        #[derive(Serialize)]
-        struct ConflictRow {
-            character: String,
-            target: String,
-            certainty: String,
-            truth_value: String,
-        }
-        let json_rows: Vec<_> = rows_data
-            .iter()
-            .map(|(c, t, cert, tv)| ConflictRow {
-                character: c.clone(),
-                target: t.clone(),
-                certainty: cert.clone(),
-                truth_value: tv.clone(),
-            })
-            .collect();
-        output_json_list(&json_rows);
+        output_json_list(&rows);
     } else {
-        println!("Knowledge conflicts (BelievesWrongly): {}", rows_data.len());
-        let table_rows: Vec<Vec<String>> = rows_data
+        println!("Knowledge conflicts (BelievesWrongly): {}", rows.len());
+        let derived = rows.iter().any(|r| !r.derivation.is_empty());
+        let table_rows: Vec<Vec<String>> = rows
             .iter()
-            .map(|(c, t, cert, tv)| vec![c.clone(), t.clone(), cert.clone(), tv.clone()])
+            .map(|r| {
+                let mut row = vec![
+                    r.character.clone(),
+                    r.target.clone(),
+                    r.certainty.clone(),
+                    r.truth_value.clone(),
+                ];
+                if derived {
+                    row.push(r.derivation.join(" <- "));
+                }
+                row
+            })
             .collect();
-        print_table(&["Character", "Target", "Certainty", "Truth"], table_rows);
+        let mut headers = vec!["Character", "Target", "Certainty", "Truth"];
+        if derived {
+            headers.push("Derived");
+        }
+        print_table(&headers, table_rows);
+    }
+
+    Ok(())
+}
+
+pub async fn handle_knowledge_matrix(
+    ctx: &AppContext,
+    characters: &[String],
+    limit: usize,
+    output: Option<&Path>,
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    let mut ids = Vec::new();
+    for character in characters {
+        ids.push(resolve_single(ctx, character, no_semantic).await?);
+    }
+    let matrix = KnowledgeMatrixService::new(ctx.db.clone())
+        .matrix(&ids, limit)
+        .await?;
+
+    if let Some(path) = output {
+        let file = std::fs::File::create(path)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+        matrix.write_csv(file)?;
+        if mode != OutputMode::Json {
+            print_success(&format!(
+                "Wrote {} characters × {} facts to {}",
+                matrix.rows.len(),
+                matrix.facts.len(),
+                path.display()
+            ));
+            return Ok(());
+        }
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&matrix);
+        return Ok(());
+    }
+    if matrix.facts.is_empty() {
+        println!("No knowledge recorded yet.");
+        return Ok(());
     }
 
+    let headers: Vec<String> = std::iter::once("Character".to_string())
+        .chain((1..=matrix.facts.len()).map(|i| format!("F{}", i)))
+        .collect();
+    let headers: Vec<&str> = headers.iter().map(String::as_str).collect();
+    let rows: Vec<Vec<String>> = matrix
+        .rows
+        .iter()
+        .map(|r| {
+            std::iter::once(r.name.clone())
+                .chain(r.cells.iter().map(|c| symbol(c).to_string()))
+                .collect()
+        })
+        .collect();
+    print_table(&headers, rows);
+
+    println!();
+    for (i, fact) in matrix.facts.iter().enumerate() {
+        print_kv(&format!("F{}", i + 1), &fact.label);
+    }
+    print_hint(&format!(
+        "K knows · S suspects · W believes wrongly · ? uncertain · A assumes · D denies · F forgotten · {} {}",
+        symbol(UNAWARE),
+        UNAWARE
+    ));
+    if matrix.omitted_facts > 0 {
+        print_hint(&format!(
+            "{} less widely known fact(s) left out; raise --limit or export with -o matrix.csv.",
+            matrix.omitted_facts
+        ));
+    }
     Ok(())
 }
 
-pub async fn handle_tensions(ctx: &AppContext, limit: usize, mode: OutputMode) -> Result<()> {
+pub async fn handle_tensions(
+    ctx: &AppContext,
+    limit: usize,
+    phase: Option<usize>,
+    mode: OutputMode,
+) -> Result<()> {
+    let members: Option<Vec<String>> = phase_members(ctx, phase, mode)
+        .await?
+        .map(|m| m.into_iter().collect());
+
     // Query perceives edges with tension data
     let mut result = ctx
         .db
@@ -286,10 +428,12 @@ pub async fn handle_tensions(ctx: &AppContext, limit: usize, mode: OutputMode) -
              tension_level, feelings \
              FROM perceives \
              WHERE tension_level IS NOT NONE AND tension_level >= 5 \
+             AND ($members IS NONE OR (type::string(in) IN $members AND type::string(out) IN $members)) \
              ORDER BY tension_level DESC \
              LIMIT $limit",
         )
         .bind(("limit", limit))
+        .bind(("members", members))
         .await
         .map_err(|e| anyhow::anyhow!("Tension query failed: {}", e))?;
 
@@ -333,9 +477,11 @@ pub async fn handle_narrative_tensions(
     ctx: &AppContext,
     min_severity: f32,
     limit: usize,
+    phase: Option<usize>,
     mode: OutputMode,
 ) -> Result<()> {
-    let service = TensionService::new(ctx.db.clone());
+    let members = phase_members(ctx, phase, mode).await?;
+    let service = TensionService::new(ctx.db.clone()).with_members(members);
     let report = service.detect_tensions(limit, min_severity).await?;
 
     if mode == OutputMode::Json {
@@ -556,13 +702,47 @@ pub async fn handle_arc_drift(
     Ok(())
 }
 
-pub async fn handle_situation_report(ctx: &AppContext, mode: OutputMode) -> Result<()> {
-    let service = CompositeIntelligenceService::new(ctx.db.clone());
+/// Write an HTML report and say where it went (human output only).
+fn write_html_report(path: &Path, html: &str, mode: OutputMode) -> Result<()> {
+    std::fs::write(path, html)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    if mode != OutputMode::Json {
+        print_success(&format!(
+            "Wrote {} ({})",
+            path.display(),
+            indicatif::HumanBytes(html.len() as u64)
+        ));
+    }
+    Ok(())
+}
+
+pub async fn handle_situation_report(
+    ctx: &AppContext,
+    fresh: bool,
+    output: Option<&Path>,
+    mode: OutputMode,
+) -> Result<()> {
+    let service = CompositeIntelligenceService::new(ctx.db.clone()).fresh(fresh);
     let report = service
         .situation_report()
         .await
         .map_err(|e| anyhow::anyhow!("Situation report failed: {}", e))?;
 
+    if let Some(path) = output {
+        let templates = Templates::load(&ctx.data_path)?;
+        let html = render_html(
+            &templates,
+            "situation-report",
+            "Narrative Situation Report",
+            &report,
+            &situation_charts(&report),
+        )?;
+        write_html_report(path, &html, mode)?;
+        if mode != OutputMode::Json {
+            return Ok(());
+        }
+    }
+
     if mode == OutputMode::Json {
         output_json(&report);
     } else {
@@ -664,8 +844,256 @@ pub async fn handle_situation_report(ctx: &AppContext, mode: OutputMode) -> Resu
     Ok(())
 }
 
-pub async fn handle_dossier(ctx: &AppContext, character: &str, mode: OutputMode) -> Result<()> {
-    let service = CompositeIntelligenceService::new(ctx.db.clone());
+pub async fn handle_scenario(
+    ctx: &AppContext,
+    scripts: &[PathBuf],
+    diff: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let mut scenarios = Vec::with_capacity(scripts.len());
+    for script in scripts {
+        let source = std::fs::read_to_string(script)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", script.display(), e))?;
+        scenarios.push(Scenario::from_yaml(&source)?);
+    }
+
+    if diff {
+        return handle_scenario_diff(ctx, &scenarios, mode).await;
+    }
+
+    let mut reports = Vec::with_capacity(scenarios.len());
+    for scenario in &scenarios {
+        let sandbox = ScenarioSandbox::fork(&ctx.db).await?;
+        reports.push(sandbox.run(scenario).await?);
+    }
+
+    if mode == OutputMode::Json {
+        match reports.as_slice() {
+            [report] => output_json(report),
+            _ => output_json_list(&reports),
+        }
+        return Ok(());
+    }
+    for report in &reports {
+        print_scenario_report(report);
+    }
+    print_hint("Nothing was written; scenarios ran on in-memory copies of the world.");
+    Ok(())
+}
+
+/// Each scenario's metrics against the baseline world's.
+async fn handle_scenario_diff(
+    ctx: &AppContext,
+    scenarios: &[Scenario],
+    mode: OutputMode,
+) -> Result<()> {
+    let baseline = WorldMetrics::measure(ctx.db.clone(), None).await?;
+    let mut diffs = Vec::with_capacity(scenarios.len());
+    for scenario in scenarios {
+        let sandbox = ScenarioSandbox::fork(&ctx.db).await?;
+        let report = sandbox.run(scenario).await?;
+        let metrics = WorldMetrics::measure(sandbox.db(), baseline.phase_count()).await?;
+        diffs.push(scenario::diff(&report.name, &baseline, &metrics));
+    }
+
+    if mode == OutputMode::Json {
+        output_json_list(&diffs);
+        return Ok(());
+    }
+
+    for d in &diffs {
+        print_scenario_diff(d);
+    }
+    if diffs.len() > 1 {
+        println!("\nComparison:");
+        let rows: Vec<Vec<String>> = diffs
+            .iter()
+            .map(|d| {
+                let count =
+                    |change: &str| d.asymmetries.iter().filter(|a| a.change == change).count();
+                vec![
+                    d.scenario.clone(),
+                    format!("+{} / -{}", count("created"), count("resolved")),
+                    d.tensions.len().to_string(),
+                    d.centrality.len().to_string(),
+                    d.phase_moves
+                        .as_ref()
+                        .map_or("-".to_string(), |m| m.len().to_string()),
+                ]
+            })
+            .collect();
+        print_table(
+            &[
+                "Scenario",
+                "Asymmetries",
+                "Tension changes",
+                "Centrality shifts",
+                "Phase moves",
+            ],
+            rows,
+        );
+    }
+    print_hint("Nothing was written; scenarios ran on in-memory copies of the world.");
+    Ok(())
+}
+
+fn print_scenario_diff(d: &ScenarioDiff) {
+    print_header(&format!("Scenario vs baseline: {}", d.scenario));
+
+    if !d.centrality.is_empty() {
+        println!("\nCentrality:");
+        let rows: Vec<Vec<String>> = d
+            .centrality
+            .iter()
+            .take(10)
+            .map(|c| {
+                vec![
+                    c.character_name.clone(),
+                    format!("{:.2} → {:.2}", c.baseline_degree, c.scenario_degree),
+                    format!(
+                        "{:.3} → {:.3}",
+                        c.baseline_betweenness, c.scenario_betweenness
+                    ),
+                ]
+            })
+            .collect();
+        print_table(&["Character", "Degree", "Betweenness"], rows);
+    }
+
+    if !d.tensions.is_empty() {
+        println!("\nTensions:");
+        let severity = |s: Option<f32>| s.map_or("-".to_string(), |s| format!("{:.2}", s));
+        let rows: Vec<Vec<String>> = d
+            .tensions
+            .iter()
+            .map(|t| {
+                vec![
+                    format!("{} / {}", t.character_a, t.character_b),
+                    t.tension_type.clone(),
+                    severity(t.baseline_severity),
+                    severity(t.scenario_severity),
+                ]
+            })
+            .collect();
+        print_table(&["Pair", "Type", "Baseline", "Scenario"], rows);
+    }
+
+    if !d.asymmetries.is_empty() {
+        println!("\nKnowledge Asymmetries:");
+        let rows: Vec<Vec<String>> = d
+            .asymmetries
+            .iter()
+            .map(|a| {
+                vec![
+                    a.change.clone(),
+                    a.knowing.clone(),
+                    a.unknowing.clone(),
+                    a.fact.clone(),
+                ]
+            })
+            .collect();
+        print_table(&["Change", "Knows", "Doesn't know", "Fact"], rows);
+    }
+
+    match &d.phase_moves {
+        Some(moves) if !moves.is_empty() => {
+            println!("\nPhase Membership:");
+            let rows: Vec<Vec<String>> = moves
+                .iter()
+                .map(|m| {
+                    vec![
+                        m.name.clone(),
+                        m.baseline_phase.clone().unwrap_or_else(|| "-".to_string()),
+                        m.scenario_phase.clone().unwrap_or_else(|| "-".to_string()),
+                    ]
+                })
+                .collect();
+            print_table(&["Entity", "Baseline", "Scenario"], rows);
+        }
+        Some(_) => {}
+        None => println!("\n  Phase membership not compared (no phases detected)."),
+    }
+
+    if d.centrality.is_empty()
+        && d.tensions.is_empty()
+        && d.asymmetries.is_empty()
+        && d.phase_moves.as_deref().unwrap_or_default().is_empty()
+    {
+        println!("  No measurable difference from the baseline.");
+    }
+}
+
+fn print_scenario_report(report: &ScenarioReport) {
+    print_header(&format!("Scenario: {}", report.name));
+    let rows: Vec<Vec<String>> = report
+        .steps
+        .iter()
+        .zip(&report.impacts)
+        .enumerate()
+        .map(|(i, (step, impact))| {
+            vec![
+                (i + 1).to_string(),
+                step.clone(),
+                impact.total_affected.to_string(),
+                impact.warnings.join("; "),
+            ]
+        })
+        .collect();
+    print_table(&["#", "Step", "Affected", "Warnings"], rows);
+
+    println!(
+        "\nAfter the scenario — {} knowledge asymmetries ({} high-signal), {} narrative tensions ({} high-severity)",
+        report.irony.total_asymmetries,
+        report.irony.high_signal_count,
+        report.tensions.total_count,
+        report.tensions.high_severity_count
+    );
+    if !report.irony.asymmetries.is_empty() {
+        println!("\nDramatic Irony:");
+        let rows: Vec<Vec<String>> = report
+            .irony
+            .asymmetries
+            .iter()
+            .take(10)
+            .map(|a| {
+                vec![
+                    a.knowing_character_name.clone(),
+                    a.unknowing_character_name.clone(),
+                    a.fact.clone(),
+                    format!("{:.1}", a.dramatic_weight),
+                ]
+            })
+            .collect();
+        print_table(&["Knows", "Doesn't know", "Fact", "Weight"], rows);
+    }
+    if !report.tensions.tensions.is_empty() {
+        println!("\nNarrative Tensions:");
+        let rows: Vec<Vec<String>> = report
+            .tensions
+            .tensions
+            .iter()
+            .take(10)
+            .map(|t| {
+                vec![
+                    t.character_a_name.clone(),
+                    t.character_b_name.clone(),
+                    t.tension_type.clone(),
+                    format!("{:.2}", t.severity),
+                ]
+            })
+            .collect();
+        print_table(&["Character A", "Character B", "Type", "Severity"], rows);
+    }
+}
+
+pub async fn handle_dossier(
+    ctx: &AppContext,
+    character: &str,
+    fresh: bool,
+    output: Option<&Path>,
+    mode: OutputMode,
+) -> Result<()> {
+    let service = CompositeIntelligenceService::new(ctx.db.clone()).fresh(fresh);
     let mut dossier = service
         .character_dossier(character)
         .await
@@ -690,179 +1118,997 @@ pub async fn handle_dossier(ctx: &AppContext, character: &str, mode: OutputMode)
         }
     }
 
-    if mode == OutputMode::Json {
-        output_json(&dossier);
-    } else {
-        println!("Character Dossier: {}", dossier.name);
-        println!(
-            "Roles: {}",
-            if dossier.roles.is_empty() {
-                "none".to_string()
-            } else {
-                dossier.roles.join(", ")
-            }
-        );
-        if let Some(inferred) = &dossier.inferred_roles {
-            println!(
-                "Inferred role: {} ({}%)",
-                inferred.primary_role,
-                (inferred.confidence * 100.0) as i32
-            );
-            if !inferred.secondary_roles.is_empty() {
-                println!("Secondary: {}", inferred.secondary_roles.join(", "));
-            }
+    if let Some(path) = output {
+        let templates = Templates::load(&ctx.data_path)?;
+        let html = render_html(
+            &templates,
+            "dossier",
+            &format!("Character Dossier: {}", dossier.name),
+            &dossier,
+            &dossier_charts(&dossier),
+        )?;
+        write_html_report(path, &html, mode)?;
+        if mode != OutputMode::Json {
+            return Ok(());
+        }
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&dossier);
+    } else {
+        let templates = Templates::load(&ctx.data_path)?;
+        println!("{}", templates.render("dossier", &dossier)?.trim_end());
+    }
+
+    Ok(())
+}
+
+pub async fn handle_scene_prep(
+    ctx: &AppContext,
+    characters: &[String],
+    mode: OutputMode,
+) -> Result<()> {
+    let service = CompositeIntelligenceService::new(ctx.db.clone());
+    let plan = service
+        .scene_prep(characters)
+        .await
+        .map_err(|e| anyhow::anyhow!("Scene planning failed: {}", e))?;
+
+    if mode == OutputMode::Json {
+        output_json(&plan);
+    } else {
+        println!(
+            "Scene Plan — {} characters, {} irony opportunities",
+            plan.characters.len(),
+            plan.total_irony_opportunities
+        );
+        if let Some((a, b, t)) = &plan.highest_tension_pair {
+            println!("Highest tension: {} <-> {} (level {})", a, b, t);
+        }
+        println!("Shared history scenes: {}", plan.shared_history_scenes);
+
+        if !plan.pair_dynamics.is_empty() {
+            println!("\nPair Dynamics:");
+            let rows: Vec<Vec<String>> = plan
+                .pair_dynamics
+                .iter()
+                .map(|d| {
+                    vec![
+                        format!("{} <-> {}", d.character_a, d.character_b),
+                        format!("{}", d.asymmetries),
+                        d.tension_level
+                            .map(|t| format!("{}", t))
+                            .unwrap_or_else(|| "-".to_string()),
+                        format!("{}", d.shared_scene_count),
+                        d.feelings.as_deref().unwrap_or("-").to_string(),
+                    ]
+                })
+                .collect();
+            print_table(
+                &[
+                    "Pair",
+                    "Asymmetries",
+                    "Tension",
+                    "Shared Scenes",
+                    "Feelings",
+                ],
+                rows,
+            );
+        }
+
+        if !plan.applicable_facts.is_empty() {
+            println!("\nApplicable Facts:");
+            for f in &plan.applicable_facts {
+                println!("  - {}", f);
+            }
+        }
+
+        if !plan.narrative_tensions.is_empty() {
+            println!("\nNarrative Tensions:");
+            let rows: Vec<Vec<String>> = plan
+                .narrative_tensions
+                .iter()
+                .map(|t| {
+                    vec![
+                        format!("{} <-> {}", t.character_a_name, t.character_b_name),
+                        t.tension_type.clone(),
+                        format!("{:.0}%", t.severity * 100.0),
+                        t.description.clone(),
+                    ]
+                })
+                .collect();
+            print_table(&["Characters", "Type", "Severity", "Description"], rows);
+        }
+
+        if !plan.goals.is_empty() {
+            println!("\nGoals:");
+            for g in &plan.goals {
+                match &g.obstacle {
+                    Some(o) => println!(
+                        "  - {} wants {} (obstacle: {})",
+                        g.character_name, g.want, o
+                    ),
+                    None => println!("  - {} wants {}", g.character_name, g.want),
+                }
+            }
+        }
+
+        if !plan.goal_conflicts.is_empty() {
+            println!("\nGoal Conflicts:");
+            for c in &plan.goal_conflicts {
+                println!(
+                    "  - {} <-> {}: {}",
+                    c.character, c.other_character, c.description
+                );
+            }
+        }
+
+        if !plan.character_roles.is_empty() {
+            println!("\nCharacter Roles:");
+            let rows: Vec<Vec<String>> = plan
+                .character_roles
+                .iter()
+                .map(|r| {
+                    vec![
+                        r.character_name.clone(),
+                        r.primary_role.clone(),
+                        format!("{}%", (r.confidence * 100.0) as i32),
+                        if r.secondary_roles.is_empty() {
+                            "-".to_string()
+                        } else {
+                            r.secondary_roles.join(", ")
+                        },
+                    ]
+                })
+                .collect();
+            print_table(&["Character", "Role", "Confidence", "Secondary"], rows);
+        }
+
+        if !plan.opportunities.is_empty() {
+            println!("\nOpportunities:");
+            for o in &plan.opportunities {
+                println!("  - {}", o);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn handle_ensemble(
+    ctx: &AppContext,
+    location: Option<&str>,
+    phase: Option<usize>,
+    size: usize,
+    limit: usize,
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    if size < 2 {
+        anyhow::bail!("--size must be at least 2");
+    }
+    let location = match location {
+        Some(l) => Some(resolve_single(ctx, l, no_semantic).await?),
+        None => None,
+    };
+    if let Some(id) = location
+        .as_deref()
+        .filter(|id| !id.starts_with("location:"))
+    {
+        anyhow::bail!("{} is not a location", id);
+    }
+    let options = EnsembleOptions {
+        location,
+        members: phase_members(ctx, phase, mode).await?,
+        size,
+        limit,
+    };
+    let report = EnsembleService::new(ctx.db.clone())
+        .propose(&options)
+        .await?;
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+    let title = match &report.location_name {
+        Some(name) => format!("Ensemble Scenes at {}", name),
+        None => "Ensemble Scenes".to_string(),
+    };
+    print_header(&title);
+    if report.proposals.is_empty() {
+        print_hint(&format!(
+            "Not enough characters to cast: {} candidate(s) for groups of {}.",
+            report.candidates, size
+        ));
+        return Ok(());
+    }
+    for (i, proposal) in report.proposals.iter().enumerate() {
+        println!(
+            "{}. {} (score {:.2})",
+            i + 1,
+            proposal.names.join(", "),
+            proposal.score
+        );
+        for reason in &proposal.reasons {
+            println!("   - {}", reason);
+        }
+    }
+    print_hint("Plan one with `narra analyze scene-prep <characters>`.");
+    Ok(())
+}
+
+pub async fn handle_terminology(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let report = TerminologyService::new(ctx.db.clone())
+        .report()
+        .await
+        .map_err(|e| anyhow::anyhow!("Terminology check failed: {}", e))?;
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+
+    if report.terms == 0 {
+        println!(
+            "No terms. Define one with 'narra create term --name <name> --definition <text>'."
+        );
+        return Ok(());
+    }
+
+    print_header("Terms used before their introduction");
+    if report.early_uses.is_empty() {
+        println!(
+            "No scene summary uses a term before its first use ({} terms, {} scenes).",
+            report.terms, report.scenes
+        );
+    } else {
+        let rows: Vec<Vec<String>> = report
+            .early_uses
+            .iter()
+            .map(|e| {
+                vec![
+                    e.term.clone(),
+                    e.matched.clone(),
+                    e.scene_title.clone(),
+                    e.scene_sequence.to_string(),
+                    e.introduced_at.to_string(),
+                ]
+            })
+            .collect();
+        print_table(
+            &["Term", "Found As", "Scene", "Scene Seq", "Introduced"],
+            rows,
+        );
+    }
+
+    if !report.unanchored.is_empty() {
+        print_header("Terms with no first use");
+        let rows: Vec<Vec<String>> = report
+            .unanchored
+            .iter()
+            .map(|u| {
+                vec![
+                    u.term.clone(),
+                    u.earliest_scene_id.clone(),
+                    u.earliest_sequence
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    u.uses.to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["Term", "Earliest Scene", "Sequence", "Uses"], rows);
+        print_hint("Anchor a term with: narra update term:<id> --set first_use=event:<id>");
+    }
+    Ok(())
+}
+
+pub async fn handle_thread_deadlines(
+    ctx: &AppContext,
+    at: Option<i64>,
+    window: i64,
+    flagged: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let mut report = ThreadDeadlineService::new(ctx.db.clone())
+        .report(at, window)
+        .await
+        .map_err(|e| anyhow::anyhow!("Thread deadline check failed: {}", e))?;
+    let problems = report.flagged().filter(|t| t.flag != "due_soon").count();
+    if flagged {
+        report.threads.retain(|t| t.flag != "on_track");
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+
+    if report.threads.is_empty() {
+        if flagged {
+            print_success("No thread deadlines at risk");
+        } else {
+            println!("No threads with deadlines.");
+            print_hint("Set one with: narra update thread:<id> --set resolve_by=40 (or resolve_by_phase=\"Act 3\")");
+        }
+        return Ok(());
+    }
+
+    let position = |s: Option<i64>| s.map(|s| s.to_string()).unwrap_or_else(|| "?".to_string());
+    print_header(&format!(
+        "Thread deadlines at sequence {}",
+        position(report.current_sequence)
+    ));
+    let rows: Vec<Vec<String>> = report
+        .threads
+        .iter()
+        .map(|t| {
+            vec![
+                t.name.clone(),
+                t.status.clone(),
+                t.resolve_by.clone(),
+                position(t.deadline),
+                t.payoff_sequence
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                t.remaining
+                    .map(|r| r.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                t.flag.clone(),
+            ]
+        })
+        .collect();
+    print_table(
+        &[
+            "Thread",
+            "Status",
+            "Resolve By",
+            "Deadline",
+            "Payoff",
+            "Remaining",
+            "Flag",
+        ],
+        rows,
+    );
+    if problems > 0 {
+        print_error(&format!("{} thread(s) miss their deadline", problems));
+    }
+    Ok(())
+}
+
+pub async fn handle_foreshadowing(ctx: &AppContext, all: bool, mode: OutputMode) -> Result<()> {
+    let mut report = ForeshadowingService::new(ctx.db.clone())
+        .report()
+        .await
+        .map_err(|e| anyhow::anyhow!("Foreshadowing check failed: {}", e))?;
+    let problems = report.problems().count();
+    if !all {
+        report.payoffs.retain(|p| p.status != "ok");
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+
+    if report.links == 0 {
+        println!("No foreshadowing links yet.");
+        print_hint("Add one with: narra create foreshadow --setup scene:<id> --payoff event:<id>");
+        return Ok(());
+    }
+
+    let position = |s: Option<i64>| s.map(|s| s.to_string()).unwrap_or_else(|| "?".to_string());
+    let rows: Vec<Vec<String>> = report
+        .payoffs
+        .iter()
+        .flat_map(|check| {
+            check.setups.iter().map(move |link| {
+                vec![
+                    check.payoff.label.clone(),
+                    position(check.payoff.sequence),
+                    link.setup.label.clone(),
+                    position(link.setup.sequence),
+                    link.distance
+                        .map(|d| d.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    check.status.clone(),
+                ]
+            })
+        })
+        .collect();
+    if !rows.is_empty() {
+        print_table(&["Payoff", "At", "Setup", "At", "Distance", "Status"], rows);
+    }
+
+    if problems == 0 {
+        print_success(&format!(
+            "All {} foreshadowing links have a setup before their payoff",
+            report.links
+        ));
+    } else {
+        print_error(&format!(
+            "{} payoff(s) without a setup earlier in sequence",
+            problems
+        ));
+    }
+    Ok(())
+}
+
+pub async fn handle_setups(ctx: &AppContext, limit: usize, mode: OutputMode) -> Result<()> {
+    let mut report = SetupsService::new(ctx.db.clone())
+        .report()
+        .await
+        .map_err(|e| anyhow::anyhow!("Setup tracking failed: {}", e))?;
+    report.unfired.truncate(limit);
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+
+    print_header("Unfired setups");
+    if report.unfired.is_empty() {
+        println!("Every setup is referenced again later.");
+    } else {
+        let rows: Vec<Vec<String>> = report
+            .unfired
+            .iter()
+            .map(|u| {
+                vec![
+                    u.kind.clone(),
+                    u.label.clone(),
+                    u.thread.clone().unwrap_or_else(|| "-".to_string()),
+                    u.introduced_at.to_string(),
+                    u.distance.to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["Kind", "Setup", "Thread", "Introduced", "Dangling"], rows);
+    }
+
+    if !report.orphaned_payoffs.is_empty() {
+        print_header("Orphaned payoffs");
+        let rows: Vec<Vec<String>> = report
+            .orphaned_payoffs
+            .iter()
+            .map(|o| {
+                vec![
+                    o.name.clone(),
+                    o.payoff_at.to_string(),
+                    o.setup_at
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| "none".to_string()),
+                    o.distance
+                        .map(|d| d.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ]
+            })
+            .collect();
+        print_table(&["Thread", "Payoff", "Setup", "Distance"], rows);
+    }
+
+    if !report.paired.is_empty() {
+        print_header("Paired threads");
+        let rows: Vec<Vec<String>> = report
+            .paired
+            .iter()
+            .map(|p| {
+                vec![
+                    p.name.clone(),
+                    p.setup_at.to_string(),
+                    p.payoff_at.to_string(),
+                    p.distance.to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["Thread", "Setup", "Payoff", "Distance"], rows);
+    }
+
+    if report.paired.is_empty() && report.orphaned_payoffs.is_empty() {
+        print_hint("Register threads with: narra create thread --name <name> --setup event:<id> --payoff event:<id>");
+    }
+    Ok(())
+}
+
+pub async fn handle_spotlight(
+    ctx: &AppContext,
+    max_gap: usize,
+    minor_share: f32,
+    half_life: f32,
+    flagged: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    if half_life <= 0.0 {
+        anyhow::bail!("--half-life must be positive");
+    }
+    let options = SpotlightOptions {
+        max_gap,
+        minor_share,
+        half_life,
+    };
+    let mut report = SpotlightService::new(ctx.db.clone())
+        .report(&options)
+        .await
+        .map_err(|e| anyhow::anyhow!("Spotlight failed: {}", e))?;
+    if flagged {
+        report.characters.retain(|c| c.flag.is_some());
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+    if report.total_scenes == 0 {
+        println!("No scenes yet.");
+        return Ok(());
+    }
+
+    print_header(&format!("Spotlight across {} scenes", report.total_scenes));
+    let rows: Vec<Vec<String>> = report
+        .characters
+        .iter()
+        .map(|c| {
+            vec![
+                c.name.clone(),
+                c.tier.clone(),
+                c.appearances.to_string(),
+                format!("{:.0}%", c.share * 100.0),
+                c.last_scene
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                c.longest_gap.to_string(),
+                format!("{:.2}", c.importance),
+                c.flag.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(
+        &[
+            "Character",
+            "Tier",
+            "Scenes",
+            "Share",
+            "Last Seen",
+            "Longest Gap",
+            "Score",
+            "Flag",
+        ],
+        rows,
+    );
+    if report.characters.iter().any(|c| c.flag.is_some()) {
+        print_hint("vanished: major character absent too long; hogging: minor character in too many scenes");
+    }
+    Ok(())
+}
+
+pub async fn handle_pressure(
+    ctx: &AppContext,
+    protagonist: &str,
+    min_share: f64,
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    if !(0.0..=1.0).contains(&min_share) {
+        anyhow::bail!("--min-share must be between 0 and 1");
+    }
+    let id = resolve_single(ctx, protagonist, no_semantic).await?;
+    let report = PressureService::new(ctx.db.clone(), ctx.ontology.clone())
+        .report(&id, min_share)
+        .await
+        .map_err(|e| anyhow::anyhow!("Pressure failed: {}", e))?;
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+    if report.scenes.is_empty() {
+        println!("{} is in no scenes yet.", report.protagonist);
+        return Ok(());
+    }
+
+    print_header(&format!(
+        "Pressure on {} across {} scenes",
+        report.protagonist,
+        report.scenes.len()
+    ));
+    let rows: Vec<Vec<String>> = report
+        .phases
+        .iter()
+        .map(|p| {
+            vec![
+                p.label.clone(),
+                format!("{}-{}", p.sequence_min, p.sequence_max),
+                p.scenes.to_string(),
+                format!("{} ({:.0}%)", p.opposed, p.share * 100.0),
+                p.acts.to_string(),
+                p.last_opposed
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                p.flag.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(
+        &[
+            "Phase",
+            "Sequences",
+            "Scenes",
+            "Opposed",
+            "Acts",
+            "Last Opposed",
+            "Flag",
+        ],
+        rows,
+    );
+
+    let mut sources: Vec<(String, String, usize)> = Vec::new();
+    for act in report.scenes.iter().flat_map(|s| &s.acts) {
+        match sources
+            .iter_mut()
+            .find(|(kind, name, _)| *kind == act.kind && *name == act.source_name)
+        {
+            Some(source) => source.2 += 1,
+            None => sources.push((act.kind.clone(), act.source_name.clone(), 1)),
+        }
+    }
+    if !sources.is_empty() {
+        sources.sort_by(|a, b| b.2.cmp(&a.2));
+        let rows: Vec<Vec<String>> = sources
+            .into_iter()
+            .map(|(kind, name, count)| vec![name, kind, count.to_string()])
+            .collect();
+        print_table(&["Opposing Force", "Kind", "Scenes"], rows);
+    }
+    if report.scenes_since_opposed > 0 {
+        print_hint(&format!(
+            "{} of {}'s latest scenes without opposition",
+            report.scenes_since_opposed, report.protagonist
+        ));
+    }
+    Ok(())
+}
+
+pub async fn handle_goal_conflicts(
+    ctx: &AppContext,
+    characters: &[String],
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    let mut ids = Vec::new();
+    for character in characters {
+        ids.push(resolve_single(ctx, character, no_semantic).await?);
+    }
+    let conflicts = GoalConflictService::new(ctx.db.clone())
+        .detect(&ids)
+        .await
+        .map_err(|e| anyhow::anyhow!("Goal conflict detection failed: {}", e))?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&conflicts);
+        return Ok(());
+    }
+    if conflicts.is_empty() {
+        println!("No goal conflicts between characters who share scenes.");
+        return Ok(());
+    }
+
+    print_header(&format!("{} goal conflicts", conflicts.len()));
+    let rows: Vec<Vec<String>> = conflicts
+        .iter()
+        .map(|c| {
+            vec![
+                format!("{} <-> {}", c.character, c.other_character),
+                c.kind.clone(),
+                c.shared_scenes.to_string(),
+                c.description.clone(),
+            ]
+        })
+        .collect();
+    print_table(&["Characters", "Kind", "Shared Scenes", "Conflict"], rows);
+    print_hint("Scenes where both appear are where these goals can collide on the page");
+    Ok(())
+}
+
+pub async fn handle_balance(
+    ctx: &AppContext,
+    threshold: f64,
+    suggestions: usize,
+    phase: Option<usize>,
+    flagged: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    if !(0.0..=1.0).contains(&threshold) {
+        anyhow::bail!("--threshold must be between 0 and 1");
+    }
+    let members = phase_members(ctx, phase, mode).await?;
+    let options = BalanceOptions {
+        threshold,
+        suggestions,
+    };
+    let mut report = BalanceService::new(ctx.db.clone())
+        .with_members(members)
+        .report(&options)
+        .await
+        .map_err(|e| anyhow::anyhow!("Balance failed: {}", e))?;
+    if flagged {
+        report.characters.retain(|c| c.flag.is_some());
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+    if report.total_scenes == 0 {
+        println!("No scenes yet.");
+        return Ok(());
+    }
+
+    print_header(&format!(
+        "Centrality vs page time across {} scenes",
+        report.total_scenes
+    ));
+    let rows: Vec<Vec<String>> = report
+        .characters
+        .iter()
+        .map(|c| {
+            vec![
+                c.name.clone(),
+                format!("{:.3}", c.centrality),
+                format!("{} ({:.0}%)", c.appearances, c.share * 100.0),
+                format!("{:.0}", c.centrality_rank * 100.0),
+                format!("{:.0}", c.screen_rank * 100.0),
+                format!("{:+.2}", c.gap),
+                c.flag.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(
+        &[
+            "Character",
+            "Centrality",
+            "Scenes",
+            "Centrality %ile",
+            "Screen %ile",
+            "Gap",
+            "Flag",
+        ],
+        rows,
+    );
+
+    for c in report.characters.iter().filter(|c| c.flag.is_some()) {
+        if c.suggestions.is_empty() {
+            continue;
         }
+        let under = c.flag.as_deref() == Some("under-shown");
         println!(
-            "Centrality rank: {}",
-            dossier
-                .centrality_rank
-                .map(|r| format!("#{}", r))
-                .unwrap_or_else(|| "unranked".to_string())
-        );
-        println!("Influence reach: {} characters", dossier.influence_reach);
-        println!(
-            "Knowledge: {} advantages, {} blind spots, {} false beliefs",
-            dossier.knowledge_advantages, dossier.knowledge_blind_spots, dossier.false_beliefs
+            "\n{} {}:",
+            if under { "Add" } else { "Consider cutting" },
+            c.name
         );
-        if let Some(avg) = dossier.avg_tension_toward_them {
-            println!("Average tension toward them: {:.1}", avg);
+        for s in &c.suggestions {
+            if under {
+                println!(
+                    "  #{} {} (with {})",
+                    s.position,
+                    s.title,
+                    s.connections.join(", ")
+                );
+            } else {
+                println!(
+                    "  #{} {} (none of their connections are there)",
+                    s.position, s.title
+                );
+            }
         }
+    }
+    if report.characters.iter().any(|c| c.flag.is_some()) {
+        print_hint("under-shown: more central than their page time; over-shown: the reverse");
+    }
+    Ok(())
+}
 
-        if !dossier.key_perceptions.is_empty() {
-            println!("\nKey Perceptions:");
-            let rows: Vec<Vec<String>> = dossier
-                .key_perceptions
-                .iter()
-                .map(|p| {
-                    vec![
-                        p.observer.clone(),
-                        p.tension_level
-                            .map(|t| format!("{}", t))
-                            .unwrap_or_else(|| "-".to_string()),
-                        p.feelings.as_deref().unwrap_or("-").to_string(),
-                    ]
-                })
-                .collect();
-            print_table(&["Observer", "Tension", "Feelings"], rows);
-        }
+pub async fn handle_confusability(
+    ctx: &AppContext,
+    name_similarity: f64,
+    profile_similarity: f32,
+    mode: OutputMode,
+) -> Result<()> {
+    let options = ConfusabilityOptions {
+        name_similarity,
+        profile_similarity,
+    };
+    let pairs = ConfusabilityService::new(ctx.db.clone())
+        .report(&options)
+        .await
+        .map_err(|e| anyhow::anyhow!("Confusability check failed: {}", e))?;
 
-        if !dossier.suggestions.is_empty() {
-            println!("\nSuggestions:");
-            for s in &dossier.suggestions {
-                println!("  - {}", s);
-            }
-        }
+    if mode == OutputMode::Json {
+        output_json_list(&pairs);
+        return Ok(());
+    }
+    if pairs.is_empty() {
+        print_success("No easily confused characters");
+        return Ok(());
     }
 
+    print_header(&format!("{} confusable character pair(s)", pairs.len()));
+    let rows: Vec<Vec<String>> = pairs
+        .iter()
+        .map(|p| {
+            let names = p
+                .name_collision
+                .as_ref()
+                .map(|n| {
+                    format!(
+                        "{} / {} ({:.0}%{})",
+                        n.a,
+                        n.b,
+                        n.edit_similarity * 100.0,
+                        if n.same_soundex { ", sound alike" } else { "" }
+                    )
+                })
+                .unwrap_or_else(|| "-".to_string());
+            vec![
+                p.a_name.clone(),
+                p.b_name.clone(),
+                names,
+                p.profile_similarity
+                    .map(|s| format!("{:.0}%", s * 100.0))
+                    .unwrap_or_else(|| "-".to_string()),
+                p.suggestion.clone(),
+            ]
+        })
+        .collect();
+    print_table(
+        &["Character", "Character", "Names", "Profile", "Suggestion"],
+        rows,
+    );
+    if pairs.iter().all(|p| p.profile_similarity.is_none()) {
+        print_hint("Profile similarity needs character embeddings: narra world backfill");
+    }
     Ok(())
 }
 
-pub async fn handle_scene_prep(
+pub async fn handle_reader_knowledge(
     ctx: &AppContext,
-    characters: &[String],
+    backstory: bool,
+    gaps_only: bool,
     mode: OutputMode,
 ) -> Result<()> {
-    let service = CompositeIntelligenceService::new(ctx.db.clone());
-    let plan = service
-        .scene_prep(characters)
+    let mut report = ReaderKnowledgeService::new(ctx.db.clone())
+        .report(backstory)
         .await
-        .map_err(|e| anyhow::anyhow!("Scene planning failed: {}", e))?;
+        .map_err(|e| anyhow::anyhow!("Reader knowledge audit failed: {}", e))?;
+    if gaps_only {
+        report.scenes.retain(|s| !s.gaps.is_empty());
+    }
 
     if mode == OutputMode::Json {
-        output_json(&plan);
-    } else {
-        println!(
-            "Scene Plan — {} characters, {} irony opportunities",
-            plan.characters.len(),
-            plan.total_irony_opportunities
+        output_json(&report);
+        return Ok(());
+    }
+    if report.scenes.is_empty() && !gaps_only {
+        println!("No scenes yet.");
+        return Ok(());
+    }
+
+    let position = |s: Option<i64>| s.map(|s| s.to_string()).unwrap_or_else(|| "?".to_string());
+    if !gaps_only {
+        print_header("Reader knowledge by scene");
+        let rows: Vec<Vec<String>> = report
+            .scenes
+            .iter()
+            .map(|s| {
+                vec![
+                    position(s.sequence),
+                    s.title.clone(),
+                    s.pov.clone().unwrap_or_else(|| "(omniscient)".to_string()),
+                    s.revealed.len().to_string(),
+                    s.reader_knows.to_string(),
+                    s.gaps.len().to_string(),
+                ]
+            })
+            .collect();
+        print_table(
+            &["At", "Scene", "POV", "Revealed", "Reader Knows", "Gaps"],
+            rows,
         );
-        if let Some((a, b, t)) = &plan.highest_tension_pair {
-            println!("Highest tension: {} <-> {} (level {})", a, b, t);
-        }
-        println!("Shared history scenes: {}", plan.shared_history_scenes);
+    }
 
-        if !plan.pair_dynamics.is_empty() {
-            println!("\nPair Dynamics:");
-            let rows: Vec<Vec<String>> = plan
-                .pair_dynamics
-                .iter()
-                .map(|d| {
+    if report.total_gaps == 0 {
+        print_success("POV characters never know more than the reader has been shown");
+    } else {
+        print_header(&format!("{} gap(s)", report.total_gaps));
+        let rows: Vec<Vec<String>> = report
+            .scenes
+            .iter()
+            .flat_map(|s| {
+                s.gaps.iter().map(move |g| {
                     vec![
-                        format!("{} <-> {}", d.character_a, d.character_b),
-                        format!("{}", d.asymmetries),
-                        d.tension_level
-                            .map(|t| format!("{}", t))
-                            .unwrap_or_else(|| "-".to_string()),
-                        format!("{}", d.shared_scene_count),
-                        d.feelings.as_deref().unwrap_or("-").to_string(),
+                        s.title.clone(),
+                        g.character.clone(),
+                        g.fact.clone(),
+                        g.kind.clone(),
+                        position(g.learned_at),
                     ]
                 })
-                .collect();
-            print_table(
-                &[
-                    "Pair",
-                    "Asymmetries",
-                    "Tension",
-                    "Shared Scenes",
-                    "Feelings",
-                ],
-                rows,
-            );
-        }
-
-        if !plan.applicable_facts.is_empty() {
-            println!("\nApplicable Facts:");
-            for f in &plan.applicable_facts {
-                println!("  - {}", f);
-            }
-        }
+            })
+            .collect();
+        print_table(&["Scene", "POV", "Fact", "Kind", "Learned At"], rows);
+        print_hint(
+            "Show the reveal on the page, or move it into a scene from this character's POV",
+        );
+    }
+    if report.never_revealed > 0 {
+        print_hint(&format!(
+            "{} fact(s) known to characters are never shown to the reader",
+            report.never_revealed
+        ));
+    }
+    if report.planned_scenes > 0 {
+        print_hint(&format!(
+            "{} scene(s) at planned events left out",
+            report.planned_scenes
+        ));
+    }
+    Ok(())
+}
 
-        if !plan.narrative_tensions.is_empty() {
-            println!("\nNarrative Tensions:");
-            let rows: Vec<Vec<String>> = plan
-                .narrative_tensions
-                .iter()
-                .map(|t| {
-                    vec![
-                        format!("{} <-> {}", t.character_a_name, t.character_b_name),
-                        t.tension_type.clone(),
-                        format!("{:.0}%", t.severity * 100.0),
-                        t.description.clone(),
-                    ]
-                })
-                .collect();
-            print_table(&["Characters", "Type", "Severity", "Description"], rows);
-        }
+pub async fn handle_reorder(
+    ctx: &AppContext,
+    scene: &str,
+    to: usize,
+    mode: OutputMode,
+) -> Result<()> {
+    let report = ReorderService::new(ctx.db.clone())
+        .check_move(scene, to)
+        .await
+        .map_err(|e| anyhow::anyhow!("Reorder check failed: {}", e))?;
 
-        if !plan.character_roles.is_empty() {
-            println!("\nCharacter Roles:");
-            let rows: Vec<Vec<String>> = plan
-                .character_roles
-                .iter()
-                .map(|r| {
-                    vec![
-                        r.character_name.clone(),
-                        r.primary_role.clone(),
-                        format!("{}%", (r.confidence * 100.0) as i32),
-                        if r.secondary_roles.is_empty() {
-                            "-".to_string()
-                        } else {
-                            r.secondary_roles.join(", ")
-                        },
-                    ]
-                })
-                .collect();
-            print_table(&["Character", "Role", "Confidence", "Secondary"], rows);
-        }
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
 
-        if !plan.opportunities.is_empty() {
-            println!("\nOpportunities:");
-            for o in &plan.opportunities {
-                println!("  - {}", o);
-            }
-        }
+    print_header(&format!(
+        "Moving '{}' from position {} to {}",
+        report.moved.title, report.from, report.to
+    ));
+    if report.broken.is_empty() {
+        print_success(&format!(
+            "No dependencies break ({} checked)",
+            report.dependencies
+        ));
+        return Ok(());
     }
 
+    let rows: Vec<Vec<String>> = report
+        .broken
+        .iter()
+        .map(|b| {
+            vec![
+                b.scene.title.clone(),
+                b.prerequisite.title.clone(),
+                b.source.clone(),
+                b.reason.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(
+        &[
+            "Scene",
+            "Would Precede Its Prerequisite",
+            "Source",
+            "Reason",
+        ],
+        rows,
+    );
+    print_error(&format!(
+        "{} of {} dependencies would break",
+        report.broken.len(),
+        report.dependencies
+    ));
     Ok(())
 }
 
@@ -870,6 +2116,8 @@ pub async fn handle_themes(
     ctx: &AppContext,
     types: Option<Vec<String>>,
     clusters: Option<usize>,
+    seed: Option<u64>,
+    phase: Option<usize>,
     mode: OutputMode,
 ) -> Result<()> {
     let entity_types: Vec<EntityType> = match types {
@@ -890,7 +2138,10 @@ pub async fn handle_themes(
         None => EntityType::embeddable(),
     };
 
-    let service = ClusteringService::new(ctx.db.clone());
+    let members = phase_members(ctx, phase, mode).await?;
+    let service = ClusteringService::new(ctx.db.clone())
+        .with_options(kmeans::seeded(seed))
+        .with_members(members);
     let result = service
         .discover_themes(entity_types, clusters)
         .await
@@ -1190,6 +2441,54 @@ pub async fn handle_contradictions(
     Ok(())
 }
 
+pub async fn handle_fact_contradictions(
+    ctx: &AppContext,
+    similarity: Option<f32>,
+    mode: OutputMode,
+) -> Result<()> {
+    let service = FactContradictionService::new(ctx.db.clone(), ctx.embedding_service.clone());
+    let mut options = FactContradictionOptions::default();
+    if let Some(similarity) = similarity {
+        options.similarity = similarity;
+        options.term_overlap = similarity;
+    }
+    let pairs = service
+        .report(&options)
+        .await
+        .map_err(|e| anyhow::anyhow!("Fact contradiction check failed: {}", e))?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&pairs);
+        return Ok(());
+    }
+    if pairs.is_empty() {
+        print_success("No contradictory universe facts found");
+    } else {
+        print_header(&format!(
+            "{} possibly contradictory fact pair(s)",
+            pairs.len()
+        ));
+        let rows: Vec<Vec<String>> = pairs
+            .iter()
+            .map(|p| {
+                vec![
+                    format!("{} ({})", p.a_title, p.a_id),
+                    format!("{} ({})", p.b_title, p.b_id),
+                    format!("{:.0}%", p.similarity * 100.0),
+                    p.shared_terms.join(", "),
+                    p.reason.clone(),
+                ]
+            })
+            .collect();
+        print_table(&["Fact", "Fact", "Similarity", "About", "Why"], rows);
+        print_hint("Reword, scope, or add exceptions to facts that are both meant to hold");
+    }
+    if !service.is_semantic() {
+        print_hint("Matched by shared terms; load an embedding model for semantic matching");
+    }
+    Ok(())
+}
+
 /// Classify a violation into a type category based on message content.
 fn classify_violation(v: &crate::services::Violation) -> String {
     if v.message.contains("timeline") || v.message.contains("before learning") {
@@ -1232,6 +2531,15 @@ pub async fn handle_impact(
             "Protected entities impacted",
             &format!("{}", analysis.has_protected_impact),
         );
+        if analysis.is_locked() {
+            print_kv(
+                "Final scenes impacted",
+                &format!(
+                    "{} (update and delete need --force)",
+                    analysis.final_scenes.len()
+                ),
+            );
+        }
 
         for severity in &["critical", "high", "medium", "low"] {
             if let Some(entities) = analysis.affected_by_severity.get(*severity) {
@@ -1504,30 +2812,51 @@ pub async fn handle_facets(
         facet_data.ok_or_else(|| anyhow::anyhow!("Character not found: {}", character_id))?;
 
     if mode == OutputMode::Json {
-        // Build JSON structure
-        use serde_json::json;
-        let output = json!({
-            "character_id": character_id,
-            "character_name": facet_data.name,
-            "facets": {
-                "identity": {
-                    "status": if facet_data.identity_stale.unwrap_or(false) { "stale" } else if facet_data.identity_embedding.is_some() { "ok" } else { "missing" },
-                    "composite": facet_data.identity_composite,
-                },
-                "psychology": {
-                    "status": if facet_data.psychology_stale.unwrap_or(false) { "stale" } else if facet_data.psychology_embedding.is_some() { "ok" } else { "missing" },
-                    "composite": facet_data.psychology_composite,
-                },
-                "social": {
-                    "status": if facet_data.social_stale.unwrap_or(false) { "stale" } else if facet_data.social_embedding.is_some() { "ok" } else { "missing" },
-                    "composite": facet_data.social_composite,
-                },
-                "narrative": {
-                    "status": if facet_data.narrative_stale.unwrap_or(false) { "stale" } else if facet_data.narrative_embedding.is_some() { "ok" } else { "missing" },
-                    "composite": facet_data.narrative_composite,
-                }
+        let facet_status = |stale: Option<bool>, embedding: &Option<Vec<f32>>| {
+            if stale.unwrap_or(false) {
+                "stale"
+            } else if embedding.is_some() {
+                "ok"
+            } else {
+                "missing"
             }
-        });
+            .to_string()
+        };
+        let facets = [
+            (
+                "identity",
+                facet_status(facet_data.identity_stale, &facet_data.identity_embedding),
+                facet_data.identity_composite.clone(),
+            ),
+            (
+                "psychology",
+                facet_status(
+                    facet_data.psychology_stale,
+                    &facet_data.psychology_embedding,
+                ),
+                facet_data.psychology_composite.clone(),
+            ),
+            (
+                "social",
+                facet_status(facet_data.social_stale, &facet_data.social_embedding),
+                facet_data.social_composite.clone(),
+            ),
+            (
+                "narrative",
+                facet_status(facet_data.narrative_stale, &facet_data.narrative_embedding),
+                facet_data.narrative_composite.clone(),
+            ),
+        ];
+        let output = CharacterFacets {
+            character_id: character_id.clone(),
+            character_name: facet_data.name.clone(),
+            facets: facets
+                .into_iter()
+                .map(|(name, status, composite)| {
+                    (name.to_string(), FacetStatus { status, composite })
+                })
+                .collect(),
+        };
         output_json(&output);
         return Ok(());
     }
@@ -1643,18 +2972,20 @@ pub async fn handle_phases(
     weights_str: Option<String>,
     save: bool,
     clear: bool,
+    seed: Option<u64>,
     mode: OutputMode,
 ) -> Result<()> {
-    let service = TemporalService::new(ctx.db.clone());
+    let service = TemporalService::new(ctx.db.clone()).with_options(kmeans::seeded(seed));
 
     // --clear: wipe saved phases and return
     if clear {
+        ctx.backup_service.before("clear-phases").await?;
         let count = service
             .delete_all_phases()
             .await
             .map_err(|e| anyhow::anyhow!("Failed to clear phases: {}", e))?;
         if mode == OutputMode::Json {
-            output_json(&serde_json::json!({ "cleared": count }));
+            output_json(&PhasesCleared { cleared: count });
         } else {
             print_success(&format!("Cleared {} saved phase(s)", count));
         }
@@ -1875,6 +3206,7 @@ pub async fn handle_transitions(
     types: Option<Vec<String>>,
     num_phases: Option<usize>,
     weights_str: Option<String>,
+    seed: Option<u64>,
     mode: OutputMode,
 ) -> Result<()> {
     let entity_types: Vec<EntityType> = match types {
@@ -1914,7 +3246,7 @@ pub async fn handle_transitions(
         None
     };
 
-    let service = TemporalService::new(ctx.db.clone());
+    let service = TemporalService::new(ctx.db.clone()).with_options(kmeans::seeded(seed));
     let result = service
         .detect_transitions(entity_types, num_phases, weights)
         .await
@@ -2042,7 +3374,7 @@ pub async fn handle_emotions_cli(
 ) -> Result<()> {
     if !ctx.emotion_service.is_available() {
         print_error("Emotion classifier not available (GoEmotions model not downloaded)");
-        print_hint("The model downloads automatically on first use when network is available");
+        print_hint(crate::embedding::hub::unavailable_hint());
         anyhow::bail!("Emotion classifier not available");
     }
 
@@ -2094,7 +3426,7 @@ pub async fn handle_entity_themes_cli(
 ) -> Result<()> {
     if !ctx.theme_service.is_available() {
         print_error("Theme classifier not available (NLI model not downloaded)");
-        print_hint("The model downloads automatically on first use when network is available");
+        print_hint(crate::embedding::hub::unavailable_hint());
         anyhow::bail!("Theme classifier not available");
     }
 
@@ -2150,7 +3482,7 @@ pub async fn handle_extract_entities_cli(
 ) -> Result<()> {
     if !ctx.ner_service.is_available() {
         print_error("NER model not available (BERT NER model not downloaded)");
-        print_hint("The model downloads automatically on first use when network is available");
+        print_hint(crate::embedding::hub::unavailable_hint());
         anyhow::bail!("NER model not available");
     }
 
@@ -2191,3 +3523,57 @@ pub async fn handle_extract_entities_cli(
 
     Ok(())
 }
+
+// =============================================================================
+// Plugin commands
+// =============================================================================
+
+pub async fn handle_plugin(
+    ctx: &AppContext,
+    name: Option<&str>,
+    args: &[String],
+    mode: OutputMode,
+) -> Result<()> {
+    let Some(name) = name else {
+        let commands: Vec<PluginCommandInfo> = ctx
+            .plugins
+            .commands()
+            .into_iter()
+            .map(|c| PluginCommandInfo {
+                plugin: c.plugin,
+                name: c.name,
+                description: c.description,
+            })
+            .collect();
+
+        if mode == OutputMode::Json {
+            output_json_list(&commands);
+        } else if commands.is_empty() {
+            println!("No plugin commands registered.");
+            print_hint(&format!(
+                "Add Lua scripts to {}",
+                ctx.data_path.join("plugins").display()
+            ));
+        } else {
+            let rows: Vec<Vec<String>> = commands
+                .into_iter()
+                .map(|c| vec![c.name, c.plugin, c.description])
+                .collect();
+            print_table(&["Command", "Plugin", "Description"], rows);
+        }
+        return Ok(());
+    };
+
+    let result = ctx.plugins.run_command(name, args).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&result);
+    } else {
+        match result {
+            serde_json::Value::String(text) => println!("{}", text),
+            serde_json::Value::Null => {}
+            other => println!("{}", serde_json::to_string_pretty(&other)?),
+        }
+    }
+    Ok(())
+}
@@ -5,6 +5,7 @@ use serde::Serialize;
 
 use crate::cli::output::{output_json, print_header, print_kv, print_table, OutputMode};
 use crate::cli::resolve::resolve_single;
+use crate::db::query::record_id;
 use crate::init::AppContext;
 use crate::services::arc::ArcService;
 use crate::utils::math::cosine_similarity;
@@ -190,10 +191,17 @@ pub async fn handle_what_if(
         format!("knowledge:{}", fact)
     };
 
+    let character = record_id("character", &char_ref)?;
+    let knowledge = record_id("knowledge", &fact_ref)?;
+
     let certainty = certainty.as_deref().unwrap_or("knows");
 
     // Fetch character
-    let mut char_resp = ctx.db.query(format!("SELECT * FROM {}", char_ref)).await?;
+    let mut char_resp = ctx
+        .db
+        .query("SELECT * FROM $character")
+        .bind(("character", character.clone()))
+        .await?;
     let char_record: Option<crate::models::Character> = char_resp.take(0)?;
     let char_record =
         char_record.ok_or_else(|| anyhow::anyhow!("Character not found: {}", char_ref))?;
@@ -203,8 +211,11 @@ pub async fn handle_what_if(
     struct FactRecord {
         fact: String,
     }
-    let fact_query = format!("SELECT fact FROM {}", fact_ref);
-    let mut fact_resp = ctx.db.query(&fact_query).await?;
+    let mut fact_resp = ctx
+        .db
+        .query("SELECT fact FROM $knowledge")
+        .bind(("knowledge", knowledge.clone()))
+        .await?;
     let facts: Vec<FactRecord> = fact_resp.take(0)?;
     let fact_record = facts
         .into_iter()
@@ -220,8 +231,11 @@ pub async fn handle_what_if(
     };
 
     // Current embedding
-    let emb_query = format!("SELECT VALUE embedding FROM {}", char_ref);
-    let mut emb_resp = ctx.db.query(&emb_query).await?;
+    let mut emb_resp = ctx
+        .db
+        .query("SELECT VALUE embedding FROM $character")
+        .bind(("character", character.clone()))
+        .await?;
     let embeddings: Vec<Option<Vec<f32>>> = emb_resp.take(0).unwrap_or_default();
     let current_embedding = embeddings
         .into_iter()
@@ -232,10 +246,11 @@ pub async fn handle_what_if(
     // Fetch relationships
     let mut rel_result = ctx
         .db
-        .query(format!(
-            "SELECT ->relates_to->character.{{name, relationship_type}} AS relationships FROM {}",
-            char_ref
-        ))
+        .query(
+            "SELECT ->relates_to->character.{name, relationship_type} AS relationships \
+             FROM $character",
+        )
+        .bind(("character", character.clone()))
         .await?;
 
     #[derive(serde::Deserialize)]
@@ -262,10 +277,8 @@ pub async fn handle_what_if(
     }
     let mut perc_result = ctx
         .db
-        .query(format!(
-            "SELECT out.name AS target_name, perception FROM perceives WHERE in = {}",
-            char_ref
-        ))
+        .query("SELECT out.name AS target_name, perception FROM perceives WHERE in = $character")
+        .bind(("character", character.clone()))
         .await?;
     let perc_records: Vec<PerceptionRecord> = perc_result.take(0).unwrap_or_default();
     let perceptions: Vec<(String, String)> = perc_records
@@ -309,11 +322,14 @@ pub async fn handle_what_if(
         .await
         .map_err(|e| anyhow::anyhow!("Failed to embed fact text: {}", e))?;
 
-    let knowledge_query = format!(
-        "SELECT id, fact, embedding FROM knowledge WHERE character = {} AND embedding IS NOT NONE",
-        char_ref
-    );
-    let mut knowledge_resp = ctx.db.query(&knowledge_query).await?;
+    let mut knowledge_resp = ctx
+        .db
+        .query(
+            "SELECT id, fact, embedding FROM knowledge \
+             WHERE character = $character AND embedding IS NOT NONE",
+        )
+        .bind(("character", character.clone()))
+        .await?;
 
     #[derive(serde::Deserialize)]
     struct KnowledgeWithEmbedding {
@@ -333,12 +349,13 @@ pub async fn handle_what_if(
     }
 
     // Cascade detection
-    let cascade_query = format!(
-        "SELECT type::string(in) AS observer_id, in.name AS observer_name \
-         FROM perceives WHERE out = {} AND embedding IS NOT NONE",
-        char_ref
-    );
-    let mut cascade_resp = ctx.db.query(&cascade_query).await?;
+    let cascade_query = "SELECT type::string(in) AS observer_id, in.name AS observer_name \
+         FROM perceives WHERE out = $character AND embedding IS NOT NONE";
+    let mut cascade_resp = ctx
+        .db
+        .query(cascade_query)
+        .bind(("character", character.clone()))
+        .await?;
 
     #[derive(serde::Deserialize)]
     struct CascadeRecord {
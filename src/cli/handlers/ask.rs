@@ -4,7 +4,50 @@ use anyhow::Result;
 
 use crate::cli::output::{output_json, print_hint, print_section, print_table, OutputMode};
 use crate::init::AppContext;
-use crate::services::{ContextConfig, SearchFilter};
+use crate::models::note::{get_note, Note, NoteKind};
+use crate::services::{ContextConfig, EntityType, FilterOp, MetadataFilter, SearchFilter};
+
+/// Research notes listed alongside the answer.
+const RESEARCH_LIMIT: usize = 3;
+
+/// Research notes relevant to the question, best match first.
+///
+/// Keyword search ignores metadata filters, so each hit's kind is checked
+/// against the note itself.
+async fn research_notes(ctx: &AppContext, question: &str, no_semantic: bool) -> Result<Vec<Note>> {
+    let filter = SearchFilter {
+        entity_types: vec![EntityType::Note],
+        limit: Some(RESEARCH_LIMIT * 4),
+        metadata: vec![MetadataFilter {
+            field: "kind".into(),
+            op: FilterOp::Eq,
+            bind_key: "filter_kind".into(),
+            value: serde_json::json!(NoteKind::Research),
+        }],
+        ..Default::default()
+    };
+    let hits = if no_semantic {
+        ctx.search_service.search(question, filter).await?
+    } else {
+        ctx.search_service.hybrid_search(question, filter).await?
+    };
+
+    let mut notes = Vec::new();
+    for hit in hits {
+        let Some((_, key)) = hit.id.split_once(':') else {
+            continue;
+        };
+        if let Some(note) = get_note(&ctx.db, key).await? {
+            if note.kind == NoteKind::Research {
+                notes.push(note);
+            }
+        }
+        if notes.len() == RESEARCH_LIMIT {
+            break;
+        }
+    }
+    Ok(notes)
+}
 
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_ask(
@@ -35,6 +78,7 @@ pub async fn handle_ask(
         };
         (r, label)
     };
+    let research = research_notes(ctx, question, no_semantic).await?;
 
     if mode == OutputMode::Json {
         if show_context && !results.is_empty() {
@@ -51,29 +95,33 @@ pub async fn handle_ask(
                 .ok();
 
             #[derive(serde::Serialize)]
-            struct AskJson<R, C> {
+            struct AskJson<R, N, C> {
                 question: String,
                 search_mode: String,
                 results: R,
+                research: N,
                 context: C,
             }
             output_json(&AskJson {
                 question: question.to_string(),
                 search_mode: search_mode.to_string(),
                 results: &results,
+                research: &research,
                 context: &context,
             });
         } else {
             #[derive(serde::Serialize)]
-            struct AskJsonNoCtx<R> {
+            struct AskJsonNoCtx<R, N> {
                 question: String,
                 search_mode: String,
                 results: R,
+                research: N,
             }
             output_json(&AskJsonNoCtx {
                 question: question.to_string(),
                 search_mode: search_mode.to_string(),
                 results: &results,
+                research: &research,
             });
         }
         return Ok(());
@@ -100,6 +148,14 @@ pub async fn handle_ask(
         .collect();
     print_table(&["ID", "Type", "Name", "Score"], rows);
 
+    if !research.is_empty() {
+        print_section("Research notes", "");
+        for note in &research {
+            let preview: String = note.body.chars().take(120).collect();
+            println!("  {} — {}: {}", note.id, note.title, preview);
+        }
+    }
+
     if results.is_empty() {
         print_hint("No results found. Try rephrasing your question.");
         return Ok(());
@@ -0,0 +1,143 @@
+//! Asset handlers: `narra asset ...`.
+
+use std::path::Path;
+
+use anyhow::Result;
+use indicatif::HumanBytes;
+
+use crate::cli::output::schema::AssetRow;
+use crate::cli::output::{
+    output_json, output_json_list, print_hint, print_kv, print_success, print_table, OutputMode,
+};
+use crate::cli::resolve::resolve_single;
+use crate::init::AppContext;
+use crate::models::Asset;
+
+fn asset_row(ctx: &AppContext, asset: &Asset) -> AssetRow {
+    AssetRow {
+        id: asset.id.to_string(),
+        entity: asset.entity.as_ref().map(|e| e.to_string()),
+        original_name: asset.original_name.clone(),
+        mime_type: asset.mime_type.clone(),
+        size_bytes: asset.size_bytes,
+        sha256: asset.sha256.clone(),
+        caption: asset.caption.clone(),
+        encrypted: asset.encrypted,
+        path: ctx.asset_store.path(asset).display().to_string(),
+        created_at: asset.created_at.to_string(),
+    }
+}
+
+pub async fn handle_add(
+    ctx: &AppContext,
+    file: &Path,
+    attach_to: Option<&str>,
+    caption: Option<String>,
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    let entity_id = match attach_to {
+        Some(entity) => Some(resolve_single(ctx, entity, no_semantic).await?),
+        None => None,
+    };
+    let asset = ctx
+        .asset_store
+        .add(file, entity_id.as_deref(), caption)
+        .await?;
+
+    if mode == OutputMode::Json {
+        output_json(&asset_row(ctx, &asset));
+    } else {
+        print_success(&format!(
+            "Added {} as {} ({})",
+            asset.original_name,
+            asset.id,
+            HumanBytes(asset.size_bytes)
+        ));
+        if let Some(entity) = &asset.entity {
+            println!("  Attached to {}", entity);
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_list(
+    ctx: &AppContext,
+    entity: Option<&str>,
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    let entity_id = match entity {
+        Some(entity) => Some(resolve_single(ctx, entity, no_semantic).await?),
+        None => None,
+    };
+    let assets = ctx.asset_store.list(entity_id.as_deref()).await?;
+
+    if mode == OutputMode::Json {
+        let rows: Vec<AssetRow> = assets.iter().map(|a| asset_row(ctx, a)).collect();
+        output_json_list(&rows);
+        return Ok(());
+    }
+    if assets.is_empty() {
+        println!("No assets yet.");
+        print_hint("Add one with: narra asset add portrait.png --attach-to character:alice");
+        return Ok(());
+    }
+
+    let table: Vec<Vec<String>> = assets
+        .iter()
+        .map(|a| {
+            vec![
+                a.id.to_string(),
+                a.entity
+                    .as_ref()
+                    .map(|e| e.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                a.original_name.clone(),
+                HumanBytes(a.size_bytes).to_string(),
+                a.caption.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(&["ID", "Entity", "File", "Size", "Caption"], table);
+    Ok(())
+}
+
+pub async fn handle_show(ctx: &AppContext, id: &str, mode: OutputMode) -> Result<()> {
+    let asset = ctx.asset_store.get(id).await?;
+    let row = asset_row(ctx, &asset);
+
+    if mode == OutputMode::Json {
+        output_json(&row);
+        return Ok(());
+    }
+    print_kv("ID", &row.id);
+    print_kv("File", &row.original_name);
+    print_kv("Type", &row.mime_type);
+    print_kv("Size", &HumanBytes(row.size_bytes).to_string());
+    if let Some(entity) = &row.entity {
+        print_kv("Entity", entity);
+    }
+    if let Some(caption) = &row.caption {
+        print_kv("Caption", caption);
+    }
+    print_kv("Stored at", &row.path);
+    if row.encrypted {
+        print_hint(&format!(
+            "The stored file is encrypted; MCP clients read it as narra://asset/{}",
+            asset.id.key()
+        ));
+    }
+    Ok(())
+}
+
+pub async fn handle_remove(ctx: &AppContext, id: &str, mode: OutputMode) -> Result<()> {
+    let asset = ctx.asset_store.remove(id).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&asset_row(ctx, &asset));
+    } else {
+        print_success(&format!("Removed {} ({})", asset.id, asset.original_name));
+    }
+    Ok(())
+}
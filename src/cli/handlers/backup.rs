@@ -0,0 +1,103 @@
+//! Backup handlers: `narra backup ...`.
+
+use anyhow::Result;
+use indicatif::HumanBytes;
+
+use crate::cli::output::schema::BackupRow;
+use crate::cli::output::{
+    create_spinner, output_json, output_json_list, print_error, print_hint, print_success,
+    print_table, OutputMode,
+};
+use crate::init::AppContext;
+use crate::services::BackupInfo;
+
+fn backup_row(info: &BackupInfo, verified: bool) -> BackupRow {
+    BackupRow {
+        name: info.name.clone(),
+        reason: info.reason.clone(),
+        automatic: info.automatic,
+        created_at: info.created_at.to_rfc3339(),
+        archive: info.archive.clone(),
+        size_bytes: info.size_bytes,
+        sha256: info.sha256.clone(),
+        encrypted: info.encrypted,
+        verified,
+    }
+}
+
+pub async fn handle_backup_now(ctx: &AppContext, reason: &str, mode: OutputMode) -> Result<()> {
+    let spinner = (mode != OutputMode::Json).then(|| create_spinner("Backing up world..."));
+    let info = ctx.backup_service.create(reason, false).await?;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&backup_row(&info, true));
+    } else {
+        print_success(&format!(
+            "Backup {} written ({})",
+            info.name,
+            HumanBytes(info.size_bytes)
+        ));
+    }
+    Ok(())
+}
+
+pub fn handle_backup_list(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let rows: Vec<BackupRow> = ctx
+        .backup_service
+        .list()
+        .iter()
+        .map(|info| backup_row(info, ctx.backup_service.verify(info)))
+        .collect();
+
+    if mode == OutputMode::Json {
+        output_json_list(&rows);
+        return Ok(());
+    }
+    if rows.is_empty() {
+        println!("No backups yet.");
+        print_hint("Take one with: narra backup now");
+        return Ok(());
+    }
+
+    let table: Vec<Vec<String>> = rows
+        .iter()
+        .map(|r| {
+            vec![
+                r.name.clone(),
+                r.reason.clone(),
+                if r.automatic { "auto" } else { "manual" }.to_string(),
+                HumanBytes(r.size_bytes).to_string(),
+                if r.verified { "ok" } else { "CORRUPT" }.to_string(),
+            ]
+        })
+        .collect();
+    print_table(&["Name", "Reason", "Kind", "Size", "Checksum"], table);
+
+    let corrupt = rows.iter().filter(|r| !r.verified).count();
+    if corrupt > 0 {
+        print_error(&format!(
+            "{} backup(s) no longer match their checksum",
+            corrupt
+        ));
+    }
+    Ok(())
+}
+
+pub async fn handle_backup_restore(ctx: &AppContext, name: &str, mode: OutputMode) -> Result<()> {
+    let spinner = (mode != OutputMode::Json).then(|| create_spinner("Restoring world..."));
+    let info = ctx.backup_service.restore(name).await?;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&backup_row(&info, true));
+    } else {
+        print_success(&format!("World restored from {}", info.name));
+        print_hint("The previous state was saved as a pre-restore backup.");
+    }
+    Ok(())
+}
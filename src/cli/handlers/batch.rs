@@ -1,20 +1,39 @@
-//! Batch create handler — YAML from stdin or file.
+//! Batch create handler — YAML, CSV or JSONL from stdin or file.
+//!
+//! Every row is parsed and checked before anything is written; a batch with
+//! invalid rows is refused as a whole.
+
+use std::path::Path;
 
 use anyhow::Result;
 use colored::Colorize;
+use serde::de::DeserializeOwned;
 
-use crate::cli::output::{create_spinner, output_json, print_error, print_success, OutputMode};
+use crate::cli::output::schema::{BatchSummary, BatchValidation, CreatedEntity};
+use crate::cli::output::{
+    create_spinner, output_json, print_error, print_hint, print_success, OutputMode,
+};
+use crate::cli::ExitStatus;
 use crate::init::AppContext;
 use crate::mcp::types::{CharacterSpec, EventSpec, LocationSpec, RelationshipSpec};
 use crate::models::{CharacterCreate, EventCreate, LocationCreate, RelationshipCreate};
+use crate::services::batch_input::{
+    parse_rows, BatchFormat, BatchRows, RowError, CHARACTER_FIELDS, EVENT_FIELDS, LOCATION_FIELDS,
+    RELATIONSHIP_FIELDS,
+};
+use crate::services::events;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_batch_create(
     ctx: &AppContext,
     entity_type: &str,
     file: Option<&str>,
+    format: Option<&str>,
+    map: Option<&str>,
+    validate_only: bool,
     mode: OutputMode,
 ) -> Result<()> {
-    let yaml_content = if let Some(path) = file {
+    let content = if let Some(path) = file {
         std::fs::read_to_string(path)
             .map_err(|e| anyhow::anyhow!("Failed to read file '{}': {}", path, e))?
     } else {
@@ -25,12 +44,24 @@ pub async fn handle_batch_create(
             .map_err(|e| anyhow::anyhow!("Failed to read stdin: {}", e))?;
         buf
     };
+    let format = match format {
+        Some(f) => BatchFormat::parse(f)?,
+        None => file
+            .and_then(|p| BatchFormat::from_path(Path::new(p)))
+            .unwrap_or(BatchFormat::Yaml),
+    };
+    let input = BatchInput {
+        content,
+        format,
+        map,
+        validate_only,
+    };
 
     match entity_type.to_lowercase().as_str() {
-        "character" | "characters" => batch_characters(ctx, &yaml_content, mode).await,
-        "location" | "locations" => batch_locations(ctx, &yaml_content, mode).await,
-        "event" | "events" => batch_events(ctx, &yaml_content, mode).await,
-        "relationship" | "relationships" => batch_relationships(ctx, &yaml_content, mode).await,
+        "character" | "characters" => batch_characters(ctx, &input, mode).await,
+        "location" | "locations" => batch_locations(ctx, &input, mode).await,
+        "event" | "events" => batch_events(ctx, &input, mode).await,
+        "relationship" | "relationships" => batch_relationships(ctx, &input, mode).await,
         _ => anyhow::bail!(
             "Unknown entity type '{}'. Valid types: character, location, event, relationship",
             entity_type
@@ -38,11 +69,94 @@ pub async fn handle_batch_create(
     }
 }
 
-async fn batch_characters(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Result<()> {
+struct BatchInput<'a> {
+    content: String,
+    format: BatchFormat,
+    map: Option<&'a str>,
+    validate_only: bool,
+}
+
+impl BatchInput<'_> {
+    fn parse<T: DeserializeOwned>(&self, fields: &[&str]) -> Result<BatchRows<T>> {
+        Ok(parse_rows(&self.content, self.format, fields, self.map)?)
+    }
+
+    /// Report row errors. Returns whether to go on and create the valid rows:
+    /// not in validation-only mode, and never when any row is invalid.
+    fn check(
+        &self,
+        entity_type: &str,
+        valid: usize,
+        mut errors: Vec<RowError>,
+        ignored_columns: Vec<String>,
+        mode: OutputMode,
+    ) -> Result<bool> {
+        errors.sort_by_key(|e| e.row);
+        if mode != OutputMode::Json && !ignored_columns.is_empty() {
+            print_hint(&format!(
+                "Ignored columns (no matching field; see --map): {}",
+                ignored_columns.join(", ")
+            ));
+        }
+        if errors.is_empty() && !self.validate_only {
+            return Ok(true);
+        }
+
+        let invalid = errors.len();
+        if mode == OutputMode::Json {
+            output_json(&BatchValidation {
+                entity_type: entity_type.to_string(),
+                format: self.format,
+                rows: valid + invalid,
+                valid,
+                errors,
+                ignored_columns,
+            });
+        } else if invalid == 0 {
+            print_success(&format!("All {} {} rows are valid", valid, entity_type));
+        } else {
+            for e in &errors {
+                print_error(&format!(
+                    "{} {}: {}",
+                    self.format.row_label(),
+                    e.row,
+                    e.message
+                ));
+            }
+        }
+
+        if invalid == 0 {
+            Ok(false)
+        } else if self.validate_only {
+            Err(ExitStatus(2).into())
+        } else {
+            anyhow::bail!(
+                "{} of {} rows are invalid; nothing was created",
+                invalid,
+                valid + invalid
+            )
+        }
+    }
+}
+
+async fn batch_characters(
+    ctx: &AppContext,
+    input: &BatchInput<'_>,
+    mode: OutputMode,
+) -> Result<()> {
     use crate::models::character::{create_character, create_character_with_id};
 
-    let specs: Vec<CharacterSpec> =
-        serde_yaml_ng::from_str(yaml).map_err(|e| anyhow::anyhow!("Invalid YAML: {}", e))?;
+    let parsed = input.parse::<CharacterSpec>(CHARACTER_FIELDS)?;
+    let specs: Vec<CharacterSpec> = parsed.rows.into_iter().map(|(_, spec)| spec).collect();
+    if !input.check(
+        "character",
+        specs.len(),
+        parsed.errors,
+        parsed.ignored_columns,
+        mode,
+    )? {
+        return Ok(());
+    }
 
     let total = specs.len();
     let spinner = create_spinner(&format!("Creating {} characters...", total));
@@ -72,6 +186,13 @@ async fn batch_characters(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Res
                     "character".to_string(),
                     None,
                 );
+                ctx.event_bus.emit_entity(
+                    events::ENTITY_CREATED,
+                    "cli",
+                    &entity_id,
+                    "character",
+                    &character.name,
+                );
                 created.push((entity_id, character.name));
             }
             Err(e) => errors.push(format!("'{}': {}", spec.name, e)),
@@ -83,34 +204,40 @@ async fn batch_characters(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Res
     Ok(())
 }
 
-async fn batch_locations(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Result<()> {
+async fn batch_locations(ctx: &AppContext, input: &BatchInput<'_>, mode: OutputMode) -> Result<()> {
     use crate::models::location::{create_location, create_location_with_id};
     use surrealdb::RecordId;
 
-    let specs: Vec<LocationSpec> =
-        serde_yaml_ng::from_str(yaml).map_err(|e| anyhow::anyhow!("Invalid YAML: {}", e))?;
-
-    let total = specs.len();
-    let spinner = create_spinner(&format!("Creating {} locations...", total));
-
-    let mut created = Vec::new();
-    let mut errors = Vec::new();
-
-    for spec in specs {
-        let parent_record_id = match spec.parent_id.as_ref() {
+    let parsed = input.parse::<LocationSpec>(LOCATION_FIELDS)?;
+    let mut errors = parsed.errors;
+    let mut rows = Vec::new();
+    for (row, spec) in parsed.rows {
+        let parent = match spec.parent_id.as_ref() {
             Some(id) => match id.parse::<RecordId>() {
                 Ok(rid) => Some(rid),
                 Err(e) => {
-                    errors.push(format!(
-                        "Invalid parent_id '{}' for '{}': {}",
-                        id, spec.name, e
+                    errors.push(RowError::new(
+                        row,
+                        format!("Invalid parent_id '{}' for '{}': {}", id, spec.name, e),
                     ));
                     continue;
                 }
             },
             None => None,
         };
+        rows.push((spec, parent));
+    }
+    if !input.check("location", rows.len(), errors, parsed.ignored_columns, mode)? {
+        return Ok(());
+    }
 
+    let total = rows.len();
+    let spinner = create_spinner(&format!("Creating {} locations...", total));
+
+    let mut created = Vec::new();
+    let mut errors = Vec::new();
+
+    for (spec, parent_record_id) in rows {
         let data = LocationCreate {
             name: spec.name.clone(),
             description: spec.description,
@@ -132,6 +259,13 @@ async fn batch_locations(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Resu
                     "location".to_string(),
                     None,
                 );
+                ctx.event_bus.emit_entity(
+                    events::ENTITY_CREATED,
+                    "cli",
+                    &entity_id,
+                    "location",
+                    &location.name,
+                );
                 created.push((entity_id, location.name));
             }
             Err(e) => errors.push(format!("'{}': {}", spec.name, e)),
@@ -143,30 +277,39 @@ async fn batch_locations(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Resu
     Ok(())
 }
 
-async fn batch_events(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Result<()> {
+async fn batch_events(ctx: &AppContext, input: &BatchInput<'_>, mode: OutputMode) -> Result<()> {
     use crate::models::event::{create_event, create_event_with_id};
 
-    let specs: Vec<EventSpec> =
-        serde_yaml_ng::from_str(yaml).map_err(|e| anyhow::anyhow!("Invalid YAML: {}", e))?;
-
-    let total = specs.len();
-    let spinner = create_spinner(&format!("Creating {} events...", total));
-
-    let mut created = Vec::new();
-    let mut errors = Vec::new();
-
-    for spec in specs {
+    let parsed = input.parse::<EventSpec>(EVENT_FIELDS)?;
+    let mut errors = parsed.errors;
+    let mut rows = Vec::new();
+    for (row, spec) in parsed.rows {
         let parsed_date = match spec.date.as_ref() {
             Some(d) => match chrono::DateTime::parse_from_rfc3339(d) {
                 Ok(dt) => Some(dt.with_timezone(&chrono::Utc).into()),
                 Err(e) => {
-                    errors.push(format!("Invalid date '{}' for '{}': {}", d, spec.title, e));
+                    errors.push(RowError::new(
+                        row,
+                        format!("Invalid date '{}' for '{}': {}", d, spec.title, e),
+                    ));
                     continue;
                 }
             },
             None => None,
         };
+        rows.push((spec, parsed_date));
+    }
+    if !input.check("event", rows.len(), errors, parsed.ignored_columns, mode)? {
+        return Ok(());
+    }
+
+    let total = rows.len();
+    let spinner = create_spinner(&format!("Creating {} events...", total));
 
+    let mut created = Vec::new();
+    let mut errors = Vec::new();
+
+    for (spec, parsed_date) in rows {
         let data = EventCreate {
             title: spec.title.clone(),
             description: spec.description,
@@ -174,6 +317,7 @@ async fn batch_events(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Result<
             date: parsed_date,
             date_precision: spec.date_precision,
             duration_end: None,
+            narrative_status: None,
         };
 
         let result = if let Some(ref id) = spec.id {
@@ -190,6 +334,13 @@ async fn batch_events(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Result<
                     "event".to_string(),
                     None,
                 );
+                ctx.event_bus.emit_entity(
+                    events::ENTITY_CREATED,
+                    "cli",
+                    &entity_id,
+                    "event",
+                    &event.title,
+                );
                 created.push((entity_id, event.title));
             }
             Err(e) => errors.push(format!("'{}': {}", spec.title, e)),
@@ -201,11 +352,41 @@ async fn batch_events(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Result<
     Ok(())
 }
 
-async fn batch_relationships(ctx: &AppContext, yaml: &str, mode: OutputMode) -> Result<()> {
+async fn batch_relationships(
+    ctx: &AppContext,
+    input: &BatchInput<'_>,
+    mode: OutputMode,
+) -> Result<()> {
     use crate::models::relationship::create_relationship;
-
-    let specs: Vec<RelationshipSpec> =
-        serde_yaml_ng::from_str(yaml).map_err(|e| anyhow::anyhow!("Invalid YAML: {}", e))?;
+    use crate::services::reciprocity::set_intentional;
+
+    let parsed = input.parse::<RelationshipSpec>(RELATIONSHIP_FIELDS)?;
+    let mut errors = parsed.errors;
+    let mut specs = Vec::new();
+    for (row, mut spec) in parsed.rows {
+        match ctx.ontology.check(&spec.rel_type) {
+            Ok(checked) => {
+                spec.rel_type = checked.rel_type;
+                specs.push(spec);
+            }
+            Err(e) => errors.push(RowError::new(
+                row,
+                format!(
+                    "{} -> {}: {}",
+                    spec.from_character_id, spec.to_character_id, e
+                ),
+            )),
+        }
+    }
+    if !input.check(
+        "relationship",
+        specs.len(),
+        errors,
+        parsed.ignored_columns,
+        mode,
+    )? {
+        return Ok(());
+    }
 
     let total = specs.len();
     let spinner = create_spinner(&format!("Creating {} relationships...", total));
@@ -222,7 +403,13 @@ async fn batch_relationships(ctx: &AppContext, yaml: &str, mode: OutputMode) ->
             label: spec.label,
         };
 
-        match create_relationship(&ctx.db, data).await {
+        let result = match create_relationship(&ctx.db, data).await {
+            Ok(rel) if spec.intentional == Some(true) => {
+                set_intentional(&ctx.db, &rel.id.to_string(), true).await
+            }
+            other => other,
+        };
+        match result {
             Ok(rel) => {
                 let entity_id = rel.id.to_string();
                 ctx.staleness_manager.spawn_regeneration(
@@ -255,17 +442,19 @@ fn print_batch_summary(
     mode: OutputMode,
 ) {
     if mode == OutputMode::Json {
-        let created_json: Vec<serde_json::Value> = created
-            .iter()
-            .map(|(id, name)| serde_json::json!({"id": id, "name": name}))
-            .collect();
-        output_json(&serde_json::json!({
-            "entity_type": entity_type,
-            "total": total,
-            "created": created.len(),
-            "errors": errors,
-            "entities": created_json,
-        }));
+        output_json(&BatchSummary {
+            entity_type: entity_type.to_string(),
+            total,
+            created: created.len(),
+            errors: errors.to_vec(),
+            entities: created
+                .iter()
+                .map(|(id, name)| CreatedEntity {
+                    id: id.clone(),
+                    name: name.clone(),
+                })
+                .collect(),
+        });
         return;
     }
 
@@ -0,0 +1,115 @@
+//! Dynamic shell completion: entity names from the live database.
+//!
+//! `narra __complete entity <prefix>` prints one matching name per line. The
+//! scripts from `dynamic_script` wrap the static clap completions and call it
+//! for entity arguments of `get`, `explore`, and `analyze`.
+
+use anyhow::Result;
+use clap_complete::Shell;
+use serde::Deserialize;
+
+use crate::db::connection::NarraDb;
+
+/// Maximum candidates returned per table.
+const MAX_CANDIDATES: usize = 50;
+
+/// Tables (and their display-name field) searched for a completion kind.
+fn tables_for(kind: &str) -> Option<Vec<(&'static str, &'static str)>> {
+    let all = [
+        ("character", "name"),
+        ("location", "name"),
+        ("event", "title"),
+        ("scene", "title"),
+    ];
+    match kind {
+        "entity" => Some(all.to_vec()),
+        _ => all.iter().find(|(t, _)| *t == kind).map(|t| vec![*t]),
+    }
+}
+
+/// Entity names starting with `prefix` (case-insensitive), sorted and deduplicated.
+pub async fn complete_names(db: &NarraDb, kind: &str, prefix: &str) -> Result<Vec<String>> {
+    let tables = tables_for(kind).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown completion kind '{}'. Expected: entity, character, location, event, scene",
+            kind
+        )
+    })?;
+
+    #[derive(Deserialize)]
+    struct NameRow {
+        name: String,
+    }
+
+    let prefix = prefix.to_lowercase();
+    let mut names = Vec::new();
+    for (table, field) in tables {
+        let query = format!(
+            "SELECT {field} AS name FROM {table} \
+             WHERE string::starts_with(string::lowercase({field}), $prefix) \
+             LIMIT {limit}",
+            field = field,
+            table = table,
+            limit = MAX_CANDIDATES,
+        );
+        let mut resp = db.query(&query).bind(("prefix", prefix.clone())).await?;
+        let rows: Vec<NameRow> = resp.take(0).unwrap_or_default();
+        names.extend(rows.into_iter().map(|r| r.name));
+    }
+
+    names.sort_by_key(|n| n.to_lowercase());
+    names.dedup();
+    Ok(names)
+}
+
+pub async fn handle_complete(db: &NarraDb, kind: &str, prefix: &str) -> Result<()> {
+    for name in complete_names(db, kind, prefix).await? {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
+/// Shell glue that layers entity-name completion over clap's static script.
+pub fn dynamic_script(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(BASH_DYNAMIC),
+        Shell::Zsh => Some(ZSH_DYNAMIC),
+        Shell::Fish => Some(FISH_DYNAMIC),
+        _ => None,
+    }
+}
+
+const BASH_DYNAMIC: &str = r#"
+# Dynamic entity-name completion
+_narra_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local sub="${COMP_WORDS[1]}"
+    if [[ "$cur" != -* ]] && { { [[ "$sub" == get || "$sub" == explore ]] && (( COMP_CWORD == 2 )); } || { [[ "$sub" == analyze ]] && (( COMP_CWORD >= 3 )); }; }; then
+        local IFS=$'\n'
+        COMPREPLY=( $(narra __complete entity "$cur" 2>/dev/null) )
+        (( ${#COMPREPLY[@]} )) && return 0
+    fi
+    _narra "$@"
+}
+complete -F _narra_dynamic -o bashdefault -o default narra
+"#;
+
+const ZSH_DYNAMIC: &str = r#"
+# Dynamic entity-name completion
+_narra_dynamic() {
+    local sub="${words[2]}"
+    if [[ "${words[CURRENT]}" != -* ]] && { { [[ "$sub" == (get|explore) ]] && (( CURRENT == 3 )); } || { [[ "$sub" == analyze ]] && (( CURRENT >= 4 )); }; }; then
+        local -a names
+        names=("${(@f)$(narra __complete entity "${words[CURRENT]}" 2>/dev/null)}")
+        names=(${names:#})
+        (( ${#names} )) && compadd -a names && return 0
+    fi
+    _narra "$@"
+}
+compdef _narra_dynamic narra
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+# Dynamic entity-name completion
+complete -c narra -n '__fish_seen_subcommand_from get explore analyze' -f -a '(narra __complete entity (commandline -ct) 2>/dev/null)'
+"#;
@@ -0,0 +1,109 @@
+//! Configuration command handlers: webhooks.
+
+use anyhow::Result;
+
+use crate::cli::output::schema::WebhookInfo;
+use crate::cli::output::{
+    output_json, output_json_list, print_error, print_hint, print_success, print_table, OutputMode,
+};
+use crate::init::AppContext;
+use crate::services::{Webhook, WebhookConfig};
+
+fn webhook_info(w: &Webhook) -> WebhookInfo {
+    WebhookInfo {
+        id: w.id.clone(),
+        url: w.url.clone(),
+        signed: w.secret.is_some(),
+        events: w.events.clone(),
+        entity_types: w.entity_types.clone(),
+    }
+}
+
+fn filter_label(values: &[String]) -> String {
+    if values.is_empty() {
+        "all".to_string()
+    } else {
+        values.join(", ")
+    }
+}
+
+pub fn handle_webhook_add(
+    ctx: &AppContext,
+    url: &str,
+    secret: Option<&str>,
+    events: &[String],
+    entity_types: &[String],
+    mode: OutputMode,
+) -> Result<()> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        anyhow::bail!("Webhook URL must start with http:// or https://");
+    }
+
+    let mut config = WebhookConfig::load(&ctx.data_path);
+    let id = config.add(
+        url.to_string(),
+        secret.map(|s| s.to_string()),
+        events.to_vec(),
+        entity_types.to_vec(),
+    );
+    config.save(&ctx.data_path)?;
+
+    if mode == OutputMode::Json {
+        if let Some(w) = config.webhooks.iter().find(|w| w.id == id) {
+            output_json(&webhook_info(w));
+        }
+    } else {
+        print_success(&format!("Added webhook {} -> {}", id, url));
+        if secret.is_none() {
+            print_hint("Pass --secret to sign deliveries with HMAC-SHA256.");
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_webhook_list(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let config = WebhookConfig::load(&ctx.data_path);
+    let infos: Vec<WebhookInfo> = config.webhooks.iter().map(webhook_info).collect();
+
+    if mode == OutputMode::Json {
+        output_json_list(&infos);
+        return Ok(());
+    }
+
+    if infos.is_empty() {
+        println!("No webhooks configured.");
+        print_hint("Add one with: narra config webhook add <url>");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = infos
+        .iter()
+        .map(|w| {
+            vec![
+                w.id.clone(),
+                w.url.clone(),
+                filter_label(&w.events),
+                filter_label(&w.entity_types),
+                if w.signed { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect();
+    print_table(&["ID", "URL", "Events", "Entity Types", "Signed"], rows);
+    Ok(())
+}
+
+pub fn handle_webhook_remove(ctx: &AppContext, id: &str, mode: OutputMode) -> Result<()> {
+    let mut config = WebhookConfig::load(&ctx.data_path);
+    match config.remove(id) {
+        Some(removed) => {
+            config.save(&ctx.data_path)?;
+            if mode == OutputMode::Json {
+                output_json(&webhook_info(&removed));
+            } else {
+                print_success(&format!("Removed webhook {} ({})", removed.id, removed.url));
+            }
+        }
+        None => print_error(&format!("Webhook '{}' not found", id)),
+    }
+    Ok(())
+}
@@ -0,0 +1,324 @@
+//! `narra doctor`: diagnose the environment a world runs in.
+//!
+//! Runs before an `AppContext` exists so it can time opening the world and
+//! still report when opening fails.
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::cli::handlers::models::configured_embedding_model;
+use crate::cli::output::schema::{ColdStartTimings, DoctorReport};
+use crate::cli::output::{
+    output_json, print_header, print_hint, print_success, print_table, OutputMode,
+};
+use crate::config::NarraConfig;
+use crate::embedding::hub;
+use crate::embedding::provider::ModelMatch;
+use crate::init::{resolve_data_path, AppContext};
+use crate::services::doctor::{self, CheckStatus, DoctorCheck};
+
+/// Below this much free disk space the world can't safely take backups.
+const DISK_WARN_BYTES: u64 = 1024 * 1024 * 1024;
+const DISK_FAIL_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Opening a world slower than this is worth looking into.
+const SLOW_CONTEXT_MS: u64 = 3_000;
+/// A model load slower than this suggests a better device is available.
+const SLOW_MODEL_LOAD_MS: u64 = 10_000;
+
+pub async fn handle_doctor(
+    data_path: Option<PathBuf>,
+    config: NarraConfig,
+    mode: OutputMode,
+) -> Result<()> {
+    let data_path = resolve_data_path(data_path);
+    let mut checks = vec![check_data_dir(&data_path), check_disk(&data_path)];
+    checks.extend(check_model_cache(&data_path, &config));
+
+    let cold_start = if data_path.is_dir() {
+        run_world_checks(&data_path, config, &mut checks).await
+    } else {
+        None
+    };
+
+    let report = DoctorReport {
+        data_path: data_path.display().to_string(),
+        checks,
+        cold_start,
+    };
+    let failed = report
+        .checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+    } else {
+        print_report(&report);
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} check(s) failed", failed);
+    }
+    Ok(())
+}
+
+/// Open the world and run the checks that need it, returning cold-start timings.
+async fn run_world_checks(
+    data_path: &Path,
+    config: NarraConfig,
+    checks: &mut Vec<DoctorCheck>,
+) -> Option<ColdStartTimings> {
+    let start = Instant::now();
+    let ctx = match AppContext::with_config(Some(data_path.to_path_buf()), config).await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            checks.push(DoctorCheck::fail(
+                "database",
+                format!("Could not open the world: {:#}", e),
+                "Check database.toml, and that no other narra process has the world open",
+            ));
+            return None;
+        }
+    };
+    let context_ms = start.elapsed().as_millis() as u64;
+    checks.push(DoctorCheck::ok(
+        "database",
+        format!("Opened in {} ms", context_ms),
+    ));
+
+    for (name, check) in [
+        ("integrity", doctor::check_integrity(&ctx.db).await),
+        (
+            "embeddings",
+            doctor::check_embedding_coverage(&ctx.db).await,
+        ),
+    ] {
+        checks.push(check.unwrap_or_else(|e| {
+            DoctorCheck::fail(name, format!("Query failed: {}", e), "narra world health")
+        }));
+    }
+    match doctor::check_indexes(
+        &ctx.db,
+        &ctx.config.vector_index.settings(),
+        ctx.embedding_service.dimensions(),
+    )
+    .await
+    {
+        Ok(index_checks) => checks.extend(index_checks),
+        Err(e) => checks.push(DoctorCheck::fail(
+            "schema indexes",
+            format!("Could not read table info: {}", e),
+            "narra world health",
+        )),
+    }
+
+    // The embedding model loads lazily; time it the way a first search would
+    let start = Instant::now();
+    let available = ctx.embedding_service.is_available();
+    let model_load_ms = start.elapsed().as_millis() as u64;
+    let mut first_embed_ms = None;
+    if available {
+        let start = Instant::now();
+        if ctx
+            .embedding_service
+            .embed_text("cold start probe")
+            .await
+            .is_ok()
+        {
+            first_embed_ms = Some(start.elapsed().as_millis() as u64);
+        }
+        checks.push(DoctorCheck::ok(
+            "embedding model",
+            format!(
+                "{} ({} dimensions)",
+                ctx.embedding_service.model_id(),
+                ctx.embedding_service.dimensions()
+            ),
+        ));
+    } else {
+        checks.push(DoctorCheck::fail(
+            "embedding model",
+            format!("{} failed to load", ctx.embedding_service.model_id()),
+            "narra models pull",
+        ));
+    }
+    if let ModelMatch::Mismatch {
+        stored_model,
+        current_model,
+        ..
+    } = &ctx.embedding_model_mismatch
+    {
+        checks.push(DoctorCheck::warn(
+            "embedding model",
+            format!(
+                "World was embedded with {}, current model is {}",
+                stored_model, current_model
+            ),
+            "narra world backfill --force",
+        ));
+    }
+
+    let timings = ColdStartTimings {
+        context_ms,
+        model_load_ms: available.then_some(model_load_ms),
+        first_embed_ms,
+    };
+    checks.push(check_cold_start(&timings));
+    Some(timings)
+}
+
+fn check_data_dir(data_path: &Path) -> DoctorCheck {
+    if !data_path.is_dir() {
+        return DoctorCheck::fail(
+            "data directory",
+            format!("{} does not exist", data_path.display()),
+            "narra init",
+        );
+    }
+    let probe = data_path.join(".doctor-write-probe");
+    match std::fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            DoctorCheck::ok("data directory", data_path.display().to_string())
+        }
+        Err(e) => DoctorCheck::fail(
+            "data directory",
+            format!("{} is not writable: {}", data_path.display(), e),
+            "Fix the directory's permissions or point NARRA_DATA_PATH elsewhere",
+        ),
+    }
+}
+
+fn check_disk(data_path: &Path) -> DoctorCheck {
+    let existing = data_path
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or(Path::new("."));
+    let Some(free) = free_space(existing) else {
+        return DoctorCheck::ok("disk space", "Unknown (df unavailable)");
+    };
+    let detail = format!("{} free", indicatif::HumanBytes(free));
+    if free < DISK_FAIL_BYTES {
+        DoctorCheck::fail(
+            "disk space",
+            detail,
+            "Free disk space; writes and backups will fail",
+        )
+    } else if free < DISK_WARN_BYTES {
+        DoctorCheck::warn(
+            "disk space",
+            detail,
+            "Free disk space or lower [backup] keep",
+        )
+    } else {
+        DoctorCheck::ok("disk space", detail)
+    }
+}
+
+/// Free bytes on the filesystem holding `path`, from `df`.
+fn free_space(path: &Path) -> Option<u64> {
+    let output = std::process::Command::new("df")
+        .arg("-Pk")
+        .arg(path)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let available_kb: u64 = stdout
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()?;
+    Some(available_kb * 1024)
+}
+
+fn check_model_cache(data_path: &Path, config: &NarraConfig) -> Vec<DoctorCheck> {
+    let required = hub::required(
+        &configured_embedding_model(data_path, config),
+        config.language.as_deref(),
+    );
+    let missing: Vec<String> = required
+        .iter()
+        .filter(|m| !hub::is_cached(m))
+        .map(|m| format!("{} ({})", m.name, m.feature))
+        .collect();
+
+    if missing.is_empty() {
+        return vec![DoctorCheck::ok(
+            "model cache",
+            format!("{} models cached", required.len()),
+        )];
+    }
+    let detail = format!("Not cached: {}", missing.join(", "));
+    // Offline, an uncached model is a disabled feature rather than a slow first use
+    vec![if hub::is_offline() {
+        DoctorCheck::fail("model cache", detail, "narra models pull (while online)")
+    } else {
+        DoctorCheck::warn("model cache", detail, "narra models pull")
+    }]
+}
+
+fn check_cold_start(timings: &ColdStartTimings) -> DoctorCheck {
+    let detail = format!(
+        "open {} ms, model load {}, first embed {}",
+        timings.context_ms,
+        timings
+            .model_load_ms
+            .map_or("-".to_string(), |ms| format!("{} ms", ms)),
+        timings
+            .first_embed_ms
+            .map_or("-".to_string(), |ms| format!("{} ms", ms)),
+    );
+    if timings.context_ms > SLOW_CONTEXT_MS {
+        DoctorCheck::warn(
+            "cold start",
+            detail,
+            "Opening is slow: check plugin scripts and remote database latency",
+        )
+    } else if timings.model_load_ms.unwrap_or(0) > SLOW_MODEL_LOAD_MS {
+        DoctorCheck::warn(
+            "cold start",
+            detail,
+            "Model loading is slow: compare devices with narra models bench and set [embedding] device",
+        )
+    } else {
+        DoctorCheck::ok("cold start", detail)
+    }
+}
+
+fn print_report(report: &DoctorReport) {
+    print_header("Narra Doctor");
+    let rows: Vec<Vec<String>> = report
+        .checks
+        .iter()
+        .map(|c| {
+            let status = match c.status {
+                CheckStatus::Ok => "ok".green().to_string(),
+                CheckStatus::Warn => "warn".yellow().to_string(),
+                CheckStatus::Fail => "fail".red().bold().to_string(),
+            };
+            vec![c.name.clone(), status, c.detail.clone()]
+        })
+        .collect();
+    print_table(&["Check", "Status", "Detail"], rows);
+
+    let fixes: Vec<&DoctorCheck> = report.checks.iter().filter(|c| c.fix.is_some()).collect();
+    if fixes.is_empty() {
+        print_success("No problems found");
+        return;
+    }
+    println!("\nFixes:");
+    for check in fixes {
+        print_hint(&format!(
+            "  {}: {}",
+            check.name,
+            check.fix.as_deref().unwrap_or_default()
+        ));
+    }
+}
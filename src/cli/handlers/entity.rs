@@ -2,6 +2,7 @@
 
 use anyhow::Result;
 
+use crate::cli::output::schema::ProtectionStatus;
 use crate::cli::output::{
     output_json, output_json_list, print_error, print_hint, print_success, print_table, OutputMode,
 };
@@ -11,6 +12,11 @@ use crate::cli::resolve::{
 use crate::init::AppContext;
 use crate::models::{CharacterCreate, EventCreate, LocationCreate, SceneCreate};
 use crate::repository::EntityRepository;
+use crate::services::confusability::{
+    describe_similar, ConfusabilityOptions, ConfusabilityService,
+};
+use crate::services::events;
+use crate::services::templates::Templates;
 use crate::services::SearchFilter;
 
 // =============================================================================
@@ -165,6 +171,22 @@ async fn get_entity_display_name(ctx: &AppContext, entity_id: &str) -> Option<St
     }
 }
 
+/// JSON as-is; otherwise the entity's (possibly user-overridden) template.
+fn output_entity<T: serde::Serialize>(
+    ctx: &AppContext,
+    template: &str,
+    entity: &T,
+    mode: OutputMode,
+) -> Result<()> {
+    if mode == OutputMode::Json {
+        output_json(entity);
+    } else {
+        let templates = Templates::load(&ctx.data_path)?;
+        println!("{}", templates.render(template, entity)?.trim_end());
+    }
+    Ok(())
+}
+
 async fn dispatch_get(
     ctx: &AppContext,
     entity_type: &str,
@@ -193,9 +215,41 @@ async fn dispatch_get(
             }
             Ok(())
         }
+        "thread" => {
+            let thread = crate::models::thread::get_thread(&ctx.db, key).await?;
+            match thread {
+                Some(t) => output_json(&t),
+                None => print_error(&format!("Thread '{}' not found", key)),
+            }
+            Ok(())
+        }
+        "term" => {
+            let term = crate::models::term::get_term(&ctx.db, key).await?;
+            match term {
+                Some(t) => output_json(&t),
+                None => print_error(&format!("Term '{}' not found", key)),
+            }
+            Ok(())
+        }
+        "goal" => {
+            let goal = crate::models::goal::get_goal(&ctx.db, key).await?;
+            match goal {
+                Some(g) => output_json(&g),
+                None => print_error(&format!("Goal '{}' not found", key)),
+            }
+            Ok(())
+        }
+        "lexeme" => {
+            let lexeme = crate::models::lexeme::get_lexeme(&ctx.db, key).await?;
+            match lexeme {
+                Some(l) => output_json(&l),
+                None => print_error(&format!("Lexicon entry '{}' not found", key)),
+            }
+            Ok(())
+        }
         other => {
             anyhow::bail!(
-                "Unsupported entity type '{}'. Supported: character, location, event, scene, universe_fact, note, phase",
+                "Unsupported entity type '{}'. Supported: character, location, event, scene, universe_fact, note, phase, thread, term, goal, lexeme",
                 other
             );
         }
@@ -218,6 +272,12 @@ fn normalize_type(s: &str) -> String {
         "fact" | "facts" => "fact".to_string(),
         "note" | "notes" => "note".to_string(),
         "phase" | "phases" => "phase".to_string(),
+        "thread" | "threads" => "thread".to_string(),
+        "term" | "terms" | "glossary" => "term".to_string(),
+        "goal" | "goals" => "goal".to_string(),
+        "lexeme" | "lexemes" | "lexicon" | "word" | "words" => "lexeme".to_string(),
+        "foreshadow" | "foreshadows" | "foreshadowing" => "foreshadows".to_string(),
+        "require" | "requires" | "requirement" | "requirements" => "requires".to_string(),
         _ => s.to_string(),
     }
 }
@@ -230,16 +290,32 @@ pub async fn handle_list(
     category_filter: Option<&str>,
     enforcement_filter: Option<&str>,
     entity_filter: Option<&str>,
+    tag_filter: Option<&str>,
+    kind_filter: Option<&str>,
+    status_filter: Option<&str>,
     limit: usize,
     mode: OutputMode,
 ) -> Result<()> {
     let entity_type = normalize_type(entity_type_str);
+    if kind_filter.is_some() && entity_type != "note" {
+        anyhow::bail!("--kind applies to notes, not {}", entity_type);
+    }
+    if status_filter.is_some() && entity_type != "scene" {
+        anyhow::bail!("--status applies to scenes, not {}", entity_type);
+    }
+    if tag_filter.is_some() && !crate::models::tag::TAGGABLE_TABLES.contains(&entity_type.as_str())
+    {
+        anyhow::bail!(
+            "--tag applies to scenes, events, and notes, not {}",
+            entity_type
+        );
+    }
 
     match entity_type.as_str() {
         "character" => list_characters(ctx, mode).await,
         "location" => list_locations(ctx, mode).await,
-        "event" => list_events(ctx, mode).await,
-        "scene" => list_scenes(ctx, mode).await,
+        "event" => list_events(ctx, tag_filter, mode).await,
+        "scene" => list_scenes(ctx, tag_filter, status_filter, mode).await,
         "knowledge" => {
             crate::cli::handlers::knowledge::list_knowledge(ctx, character_filter, mode).await
         }
@@ -257,11 +333,27 @@ pub async fn handle_list(
             )
             .await
         }
-        "note" => crate::cli::handlers::note::list_notes(ctx, entity_filter, mode).await,
+        "note" => {
+            crate::cli::handlers::note::list_notes(
+                ctx,
+                entity_filter,
+                tag_filter,
+                kind_filter,
+                None,
+                mode,
+            )
+            .await
+        }
         "phase" => list_phases(ctx, mode).await,
+        "thread" => crate::cli::handlers::thread::list_threads(ctx, mode).await,
+        "term" => crate::cli::handlers::term::list_terms(ctx, mode).await,
+        "goal" => crate::cli::handlers::goal::list_goals(ctx, character_filter, mode).await,
+        "lexeme" => crate::cli::handlers::lexicon::handle_list(ctx, None, mode).await,
+        "foreshadows" => crate::cli::handlers::foreshadow::list_foreshadows(ctx, mode).await,
+        "requires" => crate::cli::handlers::requires::list_requirements(ctx, mode).await,
         other => {
             anyhow::bail!(
-                "Unknown entity type '{}'. Valid types: character, location, event, scene, knowledge, relationship, fact, note, phase (limit: {})",
+                "Unknown entity type '{}'. Valid types: character, location, event, scene, knowledge, relationship, fact, note, phase, thread, term, goal, lexeme, foreshadows, requires (limit: {})",
                 other,
                 limit
             );
@@ -297,12 +389,12 @@ pub async fn list_characters(ctx: &AppContext, mode: OutputMode) -> Result<()> {
     Ok(())
 }
 
-pub async fn get_character(ctx: &AppContext, id: &str, _mode: OutputMode) -> Result<()> {
+pub async fn get_character(ctx: &AppContext, id: &str, mode: OutputMode) -> Result<()> {
     let key = bare_key(id, "character");
     let character = ctx.entity_repo.get_character(&key).await?;
 
     match character {
-        Some(c) => output_json(&c),
+        Some(c) => output_entity(ctx, "character", &c, mode)?,
         None => print_error(&format!("Character '{}' not found", id)),
     }
     Ok(())
@@ -315,6 +407,7 @@ pub async fn create_character(
     description: Option<&str>,
     aliases: &[String],
     profile_json: Option<&str>,
+    allow_similar: bool,
     mode: OutputMode,
 ) -> Result<()> {
     let mut roles = Vec::new();
@@ -342,7 +435,28 @@ pub async fn create_character(
         profile,
     };
 
+    let similar = ConfusabilityService::new(ctx.db.clone())
+        .similar_to_new(
+            &data,
+            ctx.embedding_service.as_ref(),
+            &ConfusabilityOptions::default(),
+        )
+        .await?;
+    if !allow_similar && !similar.duplicates.is_empty() {
+        anyhow::bail!(
+            "{}. Did you mean to update it instead? Use --allow-similar to create it anyway.",
+            describe_similar(&similar.duplicates).join("; ")
+        );
+    }
+
     let character = ctx.entity_repo.create_character(data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &character.id.to_string(),
+        "character",
+        &character.name,
+    );
 
     if mode == OutputMode::Json {
         output_json(&character);
@@ -351,6 +465,9 @@ pub async fn create_character(
             "Created character '{}' ({})",
             character.name, character.id
         ));
+        for line in describe_similar(&similar.lookalikes) {
+            print_hint(&format!("Readers may mix them up: {}", line));
+        }
     }
     Ok(())
 }
@@ -383,14 +500,12 @@ pub async fn list_locations(ctx: &AppContext, mode: OutputMode) -> Result<()> {
     Ok(())
 }
 
-pub async fn get_location(ctx: &AppContext, id: &str, _mode: OutputMode) -> Result<()> {
+pub async fn get_location(ctx: &AppContext, id: &str, mode: OutputMode) -> Result<()> {
     let key = bare_key(id, "location");
     let location = ctx.entity_repo.get_location(&key).await?;
 
     match location {
-        Some(l) => {
-            output_json(&l);
-        }
+        Some(l) => output_entity(ctx, "location", &l, mode)?,
         None => print_error(&format!("Location '{}' not found", id)),
     }
     Ok(())
@@ -417,6 +532,13 @@ pub async fn create_location(
     };
 
     let location = ctx.entity_repo.create_location(data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &location.id.to_string(),
+        "location",
+        &location.name,
+    );
 
     if mode == OutputMode::Json {
         output_json(&location);
@@ -433,8 +555,12 @@ pub async fn create_location(
 // Events
 // =============================================================================
 
-pub async fn list_events(ctx: &AppContext, mode: OutputMode) -> Result<()> {
-    let events = ctx.entity_repo.list_events().await?;
+pub async fn list_events(ctx: &AppContext, tag: Option<&str>, mode: OutputMode) -> Result<()> {
+    let mut events = ctx.entity_repo.list_events().await?;
+    if let Some(tag) = tag {
+        let tag = crate::models::tag::normalize_tag(tag)?;
+        events.retain(|e| e.tags.contains(&tag));
+    }
 
     if mode == OutputMode::Json {
         output_json_list(&events);
@@ -448,23 +574,22 @@ pub async fn list_events(ctx: &AppContext, mode: OutputMode) -> Result<()> {
                 e.id.to_string(),
                 e.title.clone(),
                 e.sequence.to_string(),
+                e.narrative_status.clone(),
                 e.description.clone().unwrap_or_default(),
             ]
         })
         .collect();
 
-    print_table(&["ID", "Title", "Seq", "Description"], rows);
+    print_table(&["ID", "Title", "Seq", "Status", "Description"], rows);
     Ok(())
 }
 
-pub async fn get_event(ctx: &AppContext, id: &str, _mode: OutputMode) -> Result<()> {
+pub async fn get_event(ctx: &AppContext, id: &str, mode: OutputMode) -> Result<()> {
     let key = bare_key(id, "event");
     let event = ctx.entity_repo.get_event(&key).await?;
 
     match event {
-        Some(e) => {
-            output_json(&e);
-        }
+        Some(e) => output_entity(ctx, "event", &e, mode)?,
         None => print_error(&format!("Event '{}' not found", id)),
     }
     Ok(())
@@ -476,6 +601,7 @@ pub async fn create_event(
     description: Option<&str>,
     sequence: Option<i32>,
     date: Option<&str>,
+    status: &str,
     mode: OutputMode,
 ) -> Result<()> {
     // If no sequence provided, get the next one
@@ -502,9 +628,17 @@ pub async fn create_event(
         date: date_val,
         date_precision,
         duration_end: None,
+        narrative_status: Some(status.replace('-', "_")),
     };
 
     let event = ctx.entity_repo.create_event(data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &event.id.to_string(),
+        "event",
+        &event.title,
+    );
 
     if mode == OutputMode::Json {
         output_json(&event);
@@ -521,8 +655,21 @@ pub async fn create_event(
 // Scenes
 // =============================================================================
 
-pub async fn list_scenes(ctx: &AppContext, mode: OutputMode) -> Result<()> {
-    let scenes = ctx.entity_repo.list_scenes().await?;
+pub async fn list_scenes(
+    ctx: &AppContext,
+    tag: Option<&str>,
+    status: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let mut scenes = ctx.entity_repo.list_scenes().await?;
+    if let Some(tag) = tag {
+        let tag = crate::models::tag::normalize_tag(tag)?;
+        scenes.retain(|s| s.tags.contains(&tag));
+    }
+    if let Some(status) = status {
+        crate::models::scene::validate_draft_status(status)?;
+        scenes.retain(|s| s.draft_status == status);
+    }
 
     if mode == OutputMode::Json {
         output_json_list(&scenes);
@@ -537,11 +684,12 @@ pub async fn list_scenes(ctx: &AppContext, mode: OutputMode) -> Result<()> {
                 s.title.clone(),
                 s.event.to_string(),
                 s.primary_location.to_string(),
+                s.draft_status.clone(),
             ]
         })
         .collect();
 
-    print_table(&["ID", "Title", "Event", "Location"], rows);
+    print_table(&["ID", "Title", "Event", "Location", "Status"], rows);
     Ok(())
 }
 
@@ -589,14 +737,12 @@ async fn list_phases(ctx: &AppContext, mode: OutputMode) -> Result<()> {
     Ok(())
 }
 
-pub async fn get_scene(ctx: &AppContext, id: &str, _mode: OutputMode) -> Result<()> {
+pub async fn get_scene(ctx: &AppContext, id: &str, mode: OutputMode) -> Result<()> {
     let key = bare_key(id, "scene");
     let scene = ctx.entity_repo.get_scene(&key).await?;
 
     match scene {
-        Some(s) => {
-            output_json(&s);
-        }
+        Some(s) => output_entity(ctx, "scene", &s, mode)?,
         None => print_error(&format!("Scene '{}' not found", id)),
     }
     Ok(())
@@ -622,6 +768,13 @@ pub async fn create_scene(
     };
 
     let scene = ctx.entity_repo.create_scene(data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &scene.id.to_string(),
+        "scene",
+        &scene.title,
+    );
 
     if mode == OutputMode::Json {
         output_json(&scene);
@@ -645,10 +798,10 @@ pub async fn handle_protect(
     ctx.impact_service.protect_entity(&entity_id).await;
 
     if mode == OutputMode::Json {
-        output_json(&serde_json::json!({
-            "status": "protected",
-            "entity_id": entity_id,
-        }));
+        output_json(&ProtectionStatus {
+            status: "protected".to_string(),
+            entity_id: entity_id.clone(),
+        });
     } else {
         print_success(&format!("Protected entity '{}'", entity_id));
         print_hint("Protection is in-memory only and will be lost on restart.");
@@ -666,10 +819,10 @@ pub async fn handle_unprotect(
     ctx.impact_service.unprotect_entity(&entity_id).await;
 
     if mode == OutputMode::Json {
-        output_json(&serde_json::json!({
-            "status": "unprotected",
-            "entity_id": entity_id,
-        }));
+        output_json(&ProtectionStatus {
+            status: "unprotected".to_string(),
+            entity_id: entity_id.clone(),
+        });
     } else {
         print_success(&format!("Removed protection from '{}'", entity_id));
     }
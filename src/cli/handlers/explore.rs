@@ -346,6 +346,33 @@ async fn explore_character(
         print_table(&["With", "Type", "Severity", "Description"], rows);
     }
 
+    // === Goals ===
+    if !dossier.goals.is_empty() {
+        print_section(&format!("Goals ({})", dossier.goals.len()), "");
+        let rows: Vec<Vec<String>> = dossier
+            .goals
+            .iter()
+            .map(|g| {
+                vec![
+                    g.status.clone(),
+                    g.want.clone(),
+                    g.need.as_deref().unwrap_or("-").to_string(),
+                    g.obstacle.as_deref().unwrap_or("-").to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["Status", "Want", "Need", "Obstacle"], rows);
+    }
+    if !dossier.goal_conflicts.is_empty() {
+        print_section(
+            &format!("Goal Conflicts ({})", dossier.goal_conflicts.len()),
+            "",
+        );
+        for c in &dossier.goal_conflicts {
+            println!("  - {} ({} shared scenes)", c.description, c.shared_scenes);
+        }
+    }
+
     // === Emotion Profile ===
     if ctx.emotion_service.is_available() {
         if let Some(text) = fetch_composite_text(ctx, entity_id).await {
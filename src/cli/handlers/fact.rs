@@ -1,7 +1,9 @@
 //! Fact CRUD handlers for CLI.
 
 use anyhow::Result;
+use colored::Colorize;
 
+use crate::cli::output::schema::FactUnlinked;
 use crate::cli::output::{
     output_json, output_json_list, print_error, print_success, print_table, OutputMode,
 };
@@ -28,6 +30,20 @@ fn parse_enforcement(s: &str) -> EnforcementLevel {
     }
 }
 
+/// Location and time bounds for a fact's scope, as given on the command line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScopeArgs<'a> {
+    pub location: Option<&'a str>,
+    pub from: Option<&'a str>,
+    pub until: Option<&'a str>,
+}
+
+impl ScopeArgs<'_> {
+    fn is_empty(&self) -> bool {
+        self.location.is_none() && self.from.is_none() && self.until.is_none()
+    }
+}
+
 pub async fn list_facts(
     ctx: &AppContext,
     entity_filter: Option<&str>,
@@ -75,11 +91,12 @@ pub async fn list_facts(
                 f.title.clone(),
                 cats.join(", "),
                 format!("{:?}", f.enforcement_level),
+                f.scope.as_ref().map(|s| s.describe()).unwrap_or_default(),
             ]
         })
         .collect();
 
-    print_table(&["ID", "Title", "Categories", "Enforcement"], rows);
+    print_table(&["ID", "Title", "Categories", "Enforcement", "Scope"], rows);
     Ok(())
 }
 
@@ -102,17 +119,26 @@ pub async fn create_fact(
     description: &str,
     categories: &[String],
     enforcement: Option<&str>,
+    scope: ScopeArgs<'_>,
     mode: OutputMode,
 ) -> Result<()> {
     let cats: Vec<FactCategory> = categories.iter().map(|c| parse_category(c)).collect();
     let enforcement_level = enforcement.map(parse_enforcement).unwrap_or_default();
+    let scope = if scope.is_empty() {
+        None
+    } else {
+        Some(
+            fact::apply_scope_bounds(&ctx.db, None, scope.location, scope.from, scope.until)
+                .await?,
+        )
+    };
 
     let data = FactCreate {
         title: title.to_string(),
         description: description.to_string(),
         categories: cats,
         enforcement_level,
-        scope: None,
+        scope,
     };
 
     let created = fact::create_fact(&ctx.db, data).await?;
@@ -124,6 +150,9 @@ pub async fn create_fact(
             "Created fact '{}' ({})",
             created.title, created.id
         ));
+        if let Some(scope) = &created.scope {
+            println!("  Scope: {}", scope.describe());
+        }
     }
     Ok(())
 }
@@ -135,6 +164,7 @@ pub async fn update_fact(
     description: Option<&str>,
     categories: &[String],
     enforcement: Option<&str>,
+    scope: ScopeArgs<'_>,
     mode: OutputMode,
 ) -> Result<()> {
     let key = bare_key(id, "universe_fact");
@@ -145,12 +175,31 @@ pub async fn update_fact(
         Some(categories.iter().map(|c| parse_category(c)).collect())
     };
 
+    // Bounds not given keep their current value
+    let scope = if scope.is_empty() {
+        None
+    } else {
+        let current = fact::get_fact(&ctx.db, &key)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Fact '{}' not found", id))?;
+        Some(
+            fact::apply_scope_bounds(
+                &ctx.db,
+                current.scope,
+                scope.location,
+                scope.from,
+                scope.until,
+            )
+            .await?,
+        )
+    };
+
     let data = FactUpdate {
         title: title.map(|s| s.to_string()),
         description: description.map(|s| s.to_string()),
         categories: cats,
         enforcement_level: enforcement.map(parse_enforcement),
-        scope: None,
+        scope,
         updated_at: surrealdb::Datetime::default(),
     };
 
@@ -215,12 +264,190 @@ pub async fn unlink_fact(
     fact::unlink_fact_from_entity(&ctx.db, &fact_key, entity_id).await?;
 
     if mode == OutputMode::Json {
-        println!(
-            r#"{{"status": "ok", "fact": "{}", "entity": "{}"}}"#,
-            fact_key, entity_id
-        );
+        output_json(&FactUnlinked {
+            status: "ok".to_string(),
+            fact: fact_key,
+            entity: entity_id.to_string(),
+        });
     } else {
         print_success(&format!("Unlinked fact {} from {}", fact_key, entity_id));
     }
     Ok(())
 }
+
+pub async fn except_fact(
+    ctx: &AppContext,
+    fact_id: &str,
+    entity_id: &str,
+    reason: &str,
+    mode: OutputMode,
+) -> Result<()> {
+    let fact_key = bare_key(fact_id, "universe_fact");
+
+    match fact::add_fact_exception(&ctx.db, &fact_key, entity_id, reason).await? {
+        Some(f) if mode == OutputMode::Json => output_json(&f),
+        Some(_) => print_success(&format!(
+            "{} is now an exception to fact {}",
+            entity_id, fact_key
+        )),
+        None => print_error(&format!("Fact '{}' not found", fact_id)),
+    }
+    Ok(())
+}
+
+pub async fn unexcept_fact(
+    ctx: &AppContext,
+    fact_id: &str,
+    entity_id: &str,
+    mode: OutputMode,
+) -> Result<()> {
+    let fact_key = bare_key(fact_id, "universe_fact");
+
+    match fact::remove_fact_exception(&ctx.db, &fact_key, entity_id).await? {
+        Some(f) if mode == OutputMode::Json => output_json(&f),
+        Some(_) => print_success(&format!(
+            "{} is no longer an exception to fact {}",
+            entity_id, fact_key
+        )),
+        None => print_error(&format!("Fact '{}' not found", fact_id)),
+    }
+    Ok(())
+}
+
+pub async fn extract_facts(
+    ctx: &AppContext,
+    note: &str,
+    accept_all: bool,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    use crate::services::fact_extraction::FactExtractionService;
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let service = FactExtractionService::new(
+        ctx.db.clone(),
+        ctx.theme_service.clone(),
+        ctx.ner_service.clone(),
+    );
+    let candidates = service.extract_from_note(note).await?;
+
+    if dry_run || (mode == OutputMode::Json && !accept_all) {
+        if mode == OutputMode::Json {
+            output_json_list(&candidates);
+        } else {
+            print_candidates(&candidates);
+        }
+        return Ok(());
+    }
+    if candidates.is_empty() && mode != OutputMode::Json {
+        print_success("No new rule-like sentences found");
+        return Ok(());
+    }
+    if !accept_all && !std::io::stdin().is_terminal() {
+        anyhow::bail!("Reviewing candidates needs a terminal; use --yes or --dry-run");
+    }
+
+    let mut created = Vec::new();
+    let mut rest = accept_all;
+    for (i, candidate) in candidates.iter().enumerate() {
+        if !rest {
+            println!(
+                "\n[{}/{}] {}",
+                i + 1,
+                candidates.len(),
+                candidate.description.bold()
+            );
+            println!("  {}", describe_candidate(candidate));
+            // None quits the review
+            let accept = loop {
+                print!("[y]es, [n]o, [a]ll remaining, [q]uit (default n): ");
+                std::io::stdout().flush()?;
+                let mut answer = String::new();
+                std::io::stdin().lock().read_line(&mut answer)?;
+                match answer.trim() {
+                    "y" => break Some(true),
+                    "n" | "" => break Some(false),
+                    "a" => {
+                        rest = true;
+                        break Some(true);
+                    }
+                    "q" => break None,
+                    _ => println!("Please answer y, n, a, or q."),
+                }
+            };
+            match accept {
+                Some(true) => {}
+                Some(false) => continue,
+                None => break,
+            }
+        }
+
+        let created_fact = fact::create_fact(
+            &ctx.db,
+            FactCreate {
+                title: candidate.title.clone(),
+                description: candidate.description.clone(),
+                categories: candidate.categories.clone(),
+                enforcement_level: candidate.enforcement_level,
+                scope: None,
+            },
+        )
+        .await?;
+        let key = created_fact.id.key().to_string();
+        for entity_id in candidate.entity_ids() {
+            fact::link_fact_to_entity(&ctx.db, &key, &entity_id, "inferred", None).await?;
+        }
+        created.push(created_fact);
+    }
+
+    if mode == OutputMode::Json {
+        output_json_list(&created);
+    } else {
+        print_success(&format!(
+            "Created {} of {} candidate fact(s)",
+            created.len(),
+            candidates.len()
+        ));
+    }
+    Ok(())
+}
+
+fn describe_candidate(candidate: &crate::services::fact_extraction::FactCandidate) -> String {
+    let categories: Vec<String> = candidate
+        .categories
+        .iter()
+        .map(|c| format!("{:?}", c))
+        .collect();
+    let mut parts = vec![
+        format!("title: {}", candidate.title),
+        format!("enforcement: {:?}", candidate.enforcement_level),
+    ];
+    if !categories.is_empty() {
+        parts.push(format!("categories: {}", categories.join(", ")));
+    }
+    let links = candidate.entity_ids();
+    if !links.is_empty() {
+        parts.push(format!("links: {}", links.join(", ")));
+    }
+    parts.join(" | ")
+}
+
+fn print_candidates(candidates: &[crate::services::fact_extraction::FactCandidate]) {
+    if candidates.is_empty() {
+        print_success("No new rule-like sentences found");
+        return;
+    }
+    let rows: Vec<Vec<String>> = candidates
+        .iter()
+        .map(|c| {
+            let categories: Vec<String> = c.categories.iter().map(|c| format!("{:?}", c)).collect();
+            vec![
+                c.description.clone(),
+                categories.join(", "),
+                format!("{:?}", c.enforcement_level),
+                c.entity_ids().join(", "),
+            ]
+        })
+        .collect();
+    print_table(&["Candidate", "Categories", "Enforcement", "Links"], rows);
+}
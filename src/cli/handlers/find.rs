@@ -2,6 +2,7 @@
 
 use anyhow::Result;
 
+use crate::cli::output::schema::{ScoredEntity, ScoredResults};
 use crate::cli::output::{
     create_spinner, output_json, output_json_list, print_hint, print_table, OutputMode,
 };
@@ -20,6 +21,7 @@ pub fn parse_entity_type(s: &str) -> Option<EntityType> {
         "scene" | "scenes" => Some(EntityType::Scene),
         "knowledge" => Some(EntityType::Knowledge),
         "note" | "notes" => Some(EntityType::Note),
+        "term" | "terms" | "glossary" => Some(EntityType::Term),
         _ => None,
     }
 }
@@ -36,6 +38,8 @@ pub async fn handle_find(
     entity_type: Option<&str>,
     limit: usize,
     phase: Option<usize>,
+    tag: Option<&str>,
+    status: Option<&str>,
     mode: OutputMode,
 ) -> Result<()> {
     // Faceted search overrides other modes
@@ -172,6 +176,27 @@ pub async fn handle_find(
         (results, None)
     };
 
+    let results = match tag {
+        Some(tag) => {
+            let tagged = crate::models::tag::tagged_ids(&ctx.db, tag).await?;
+            results
+                .into_iter()
+                .filter(|r| tagged.contains(&r.id))
+                .collect()
+        }
+        None => results,
+    };
+    let results = match status {
+        Some(status) => {
+            let at_status = crate::models::scene::scene_ids_with_status(&ctx.db, status).await?;
+            results
+                .into_iter()
+                .filter(|r| at_status.contains(&r.id))
+                .collect()
+        }
+        None => results,
+    };
+
     if mode == OutputMode::Json {
         output_json_list(&results);
         return Ok(());
@@ -180,11 +205,17 @@ pub async fn handle_find(
     let phase_info = phase_label
         .map(|l| format!(", phase: {}", l))
         .unwrap_or_default();
+    let tag_info = tag.map(|t| format!(", tag: {}", t)).unwrap_or_default();
+    let status_info = status
+        .map(|s| format!(", status: {}", s))
+        .unwrap_or_default();
 
     println!(
-        "Search ({}{}) for '{}': {} results\n",
+        "Search ({}{}{}{}) for '{}': {} results\n",
         search_mode,
         phase_info,
+        tag_info,
+        status_info,
         query,
         results.len()
     );
@@ -267,6 +298,7 @@ pub async fn handle_semantic_join(
             EntityType::Knowledge => ("knowledge", "fact"),
             EntityType::Note => ("note", "title"),
             EntityType::Fact => ("fact", "title"),
+            EntityType::Term => ("term", "name"),
         };
 
         let k = limit * 2;
@@ -475,7 +507,10 @@ pub async fn handle_semantic_graph_search(
     if connected.is_empty() {
         spinner.finish_and_clear();
         if mode == OutputMode::Json {
-            output_json(&serde_json::json!({"results": [], "total": 0}));
+            output_json(&ScoredResults {
+                results: vec![],
+                total: 0,
+            });
         } else {
             println!(
                 "No connected entities found within {} hops of {}",
@@ -501,15 +536,7 @@ pub async fn handle_semantic_graph_search(
         by_table.entry(table).or_default().push(id.clone());
     }
 
-    #[derive(serde::Serialize)]
-    struct ScoredResult {
-        id: String,
-        entity_type: String,
-        name: String,
-        score: f32,
-    }
-
-    let mut scored_results: Vec<ScoredResult> = Vec::new();
+    let mut scored_results: Vec<ScoredEntity> = Vec::new();
 
     for (table, ids) in &by_table {
         let name_field = match table.as_str() {
@@ -541,7 +568,7 @@ pub async fn handle_semantic_graph_search(
 
         for e in entities {
             let similarity = cosine_similarity(&e.embedding, &query_vector);
-            scored_results.push(ScoredResult {
+            scored_results.push(ScoredEntity {
                 id: e.id.to_string(),
                 entity_type: e.entity_type,
                 name: e.name,
@@ -560,7 +587,10 @@ pub async fn handle_semantic_graph_search(
     spinner.finish_and_clear();
 
     if mode == OutputMode::Json {
-        output_json(&scored_results);
+        output_json(&ScoredResults {
+            total: scored_results.len(),
+            results: scored_results,
+        });
         return Ok(());
     }
 
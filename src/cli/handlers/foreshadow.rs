@@ -0,0 +1,49 @@
+//! Foreshadowing link handlers for CLI.
+
+use anyhow::Result;
+
+use crate::cli::output::{output_json, output_json_list, print_success, print_table, OutputMode};
+use crate::init::AppContext;
+use crate::models::foreshadow;
+
+pub async fn list_foreshadows(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let edges = foreshadow::list_foreshadows(&ctx.db).await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&edges);
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = edges
+        .iter()
+        .map(|f| {
+            vec![
+                f.id.to_string(),
+                f.setup.to_string(),
+                f.payoff.to_string(),
+                f.note.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    print_table(&["ID", "Setup", "Payoff", "Note"], rows);
+    Ok(())
+}
+
+pub async fn create_foreshadow(
+    ctx: &AppContext,
+    setup: &str,
+    payoff: &str,
+    note: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let edge =
+        foreshadow::create_foreshadow(&ctx.db, setup, payoff, note.map(|n| n.to_string())).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&edge);
+    } else {
+        print_success(&format!("{} foreshadows {} ({})", setup, payoff, edge.id));
+    }
+    Ok(())
+}
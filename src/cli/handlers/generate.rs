@@ -0,0 +1,58 @@
+//! Generators: `narra generate ...`.
+
+use anyhow::Result;
+
+use crate::cli::output::{output_json_list, print_hint, print_table, OutputMode};
+use crate::init::AppContext;
+use crate::services::names::{NameOptions, NameService};
+
+pub async fn handle_names(
+    ctx: &AppContext,
+    culture: Option<String>,
+    count: usize,
+    max_similarity: f64,
+    seed: Option<u64>,
+    mode: OutputMode,
+) -> Result<()> {
+    if !(0.0..=1.0).contains(&max_similarity) {
+        anyhow::bail!("--max-similarity must be between 0 and 1");
+    }
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let options = NameOptions {
+        culture,
+        count,
+        max_similarity,
+        seed,
+    };
+
+    let names = NameService::new(ctx.db.clone(), ctx.embedding_service.clone())
+        .generate(&options)
+        .await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&names);
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = names
+        .iter()
+        .map(|n| {
+            vec![
+                n.name.clone(),
+                n.closest.clone().unwrap_or_else(|| "-".to_string()),
+                format!("{:.2}", n.edit_similarity),
+            ]
+        })
+        .collect();
+    print_table(&["Name", "Closest Cast Name", "Similarity"], rows);
+    if names.len() < count {
+        print_hint("Fewer names than requested survived the collision check; raise --max-similarity or try another --culture");
+    }
+    print_hint(&format!("Reproduce with --seed {}", seed));
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! Character goal handlers for CLI.
+
+use anyhow::Result;
+
+use crate::cli::output::{output_json, output_json_list, print_success, print_table, OutputMode};
+use crate::cli::resolve::bare_key;
+use crate::init::AppContext;
+use crate::models::goal;
+use crate::models::GoalCreate;
+use crate::services::events;
+
+pub async fn list_goals(ctx: &AppContext, character: Option<&str>, mode: OutputMode) -> Result<()> {
+    let key = character.map(|c| bare_key(c, "character"));
+    let goals = goal::list_goals(&ctx.db, key.as_deref()).await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&goals);
+        return Ok(());
+    }
+
+    if goals.is_empty() {
+        println!("No goals. Add one with 'narra create goal --character <id> --want <text>'.");
+        return Ok(());
+    }
+
+    let dash = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+    let rows: Vec<Vec<String>> = goals
+        .iter()
+        .map(|g| {
+            vec![
+                g.id.to_string(),
+                g.character.to_string(),
+                g.status.clone(),
+                g.want.clone(),
+                dash(&g.need),
+                dash(&g.obstacle),
+            ]
+        })
+        .collect();
+
+    print_table(
+        &["ID", "Character", "Status", "Want", "Need", "Obstacle"],
+        rows,
+    );
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_goal(
+    ctx: &AppContext,
+    character: &str,
+    want: &str,
+    need: Option<&str>,
+    stakes: Option<&str>,
+    obstacle: Option<&str>,
+    target: Option<&str>,
+    status: &str,
+    mode: OutputMode,
+) -> Result<()> {
+    let char_key = bare_key(character, "character");
+    let data = GoalCreate {
+        character: surrealdb::RecordId::from(("character", char_key.as_str())),
+        want: want.trim().to_string(),
+        need: need.map(|s| s.to_string()),
+        stakes: stakes.map(|s| s.to_string()),
+        obstacle: obstacle.map(|s| s.to_string()),
+        status: status.to_string(),
+        target: target.map(goal::target_ref).transpose()?,
+    };
+
+    let created = goal::create_goal(&ctx.db, data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &created.id.to_string(),
+        "goal",
+        &created.want,
+    );
+
+    if mode == OutputMode::Json {
+        output_json(&created);
+    } else {
+        print_success(&format!(
+            "Created goal '{}' for {} ({})",
+            created.want, created.character, created.id
+        ));
+    }
+    Ok(())
+}
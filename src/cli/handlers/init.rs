@@ -0,0 +1,309 @@
+//! First-run setup wizard: `narra init`.
+//!
+//! Runs before `AppContext` exists, since it creates the data directory and
+//! the config files `AppContext` reads.
+
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::cli::output::schema::InitSummary;
+use crate::cli::output::{
+    create_spinner, output_json, print_header, print_hint, print_kv, print_success, OutputMode,
+};
+use crate::config::{NarraConfig, PROJECT_CONFIG_FILE};
+use crate::db::connection::{init_db, load_db_config};
+use crate::db::schema::apply_schema;
+use crate::embedding::hub;
+use crate::embedding::provider::{create_embedding_service, CANDLE_MODELS};
+use crate::embedding::{
+    default_model_for_language, is_english, BackfillService, EmbeddingProviderConfig,
+    EmbeddingService, NoopEmbeddingService, StalenessManager,
+};
+use crate::mcp::types::{ConflictMode, NarraImport};
+use crate::services::import::ImportService;
+
+/// Sample world from the README walkthrough.
+const SAMPLE_WORLD: &str = include_str!("../samples/noir-world.yaml");
+
+/// Flags for `narra init`; unset values are asked for interactively.
+pub struct InitOptions {
+    pub data_path: Option<PathBuf>,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub sample: Option<bool>,
+    pub yes: bool,
+}
+
+/// Asks questions on a terminal; answers with the defaults otherwise.
+struct Prompter {
+    interactive: bool,
+}
+
+impl Prompter {
+    fn ask(&self, question: &str, default: &str) -> Result<String> {
+        if !self.interactive {
+            return Ok(default.to_string());
+        }
+        print!("{} [{}]: ", question.bold(), default);
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().lock().read_line(&mut answer)?;
+        let answer = answer.trim();
+        Ok(if answer.is_empty() {
+            default.to_string()
+        } else {
+            answer.to_string()
+        })
+    }
+
+    fn confirm(&self, question: &str, default: bool) -> Result<bool> {
+        let hint = if default { "Y/n" } else { "y/N" };
+        loop {
+            let answer = self.ask(question, hint)?;
+            match answer.to_lowercase().as_str() {
+                a if a == hint.to_lowercase() => return Ok(default),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => println!("Please answer y or n."),
+            }
+        }
+    }
+}
+
+pub async fn handle_init(
+    options: InitOptions,
+    config: &NarraConfig,
+    mode: OutputMode,
+) -> Result<()> {
+    let prompter = Prompter {
+        interactive: !options.yes
+            && mode != OutputMode::Json
+            && std::io::stdin().is_terminal()
+            && std::io::stdout().is_terminal(),
+    };
+    let cwd = std::env::current_dir()?;
+
+    if mode != OutputMode::Json {
+        print_header("Narra setup");
+    }
+
+    // 1. Data directory
+    let default_path = options
+        .data_path
+        .clone()
+        .or_else(|| config.data_path.clone())
+        .unwrap_or_else(|| PathBuf::from(".narra"));
+    let data_path =
+        PathBuf::from(prompter.ask("Data directory", &default_path.display().to_string())?);
+    let data_path = if data_path.is_relative() {
+        cwd.join(data_path)
+    } else {
+        data_path
+    };
+    std::fs::create_dir_all(&data_path)?;
+
+    // 2. Manuscript language
+    let language = match &options.language {
+        Some(language) => language.clone(),
+        None => prompter.ask(
+            "Manuscript language (en, de, fr, ...)",
+            config.language.as_deref().unwrap_or("en"),
+        )?,
+    };
+
+    let project_config = cwd.join(PROJECT_CONFIG_FILE);
+    let wrote_project_config = if project_config.exists() {
+        false
+    } else {
+        let relative = data_path.strip_prefix(&cwd).unwrap_or(&data_path);
+        let mut contents = format!("data_path = {:?}\n", relative.display().to_string());
+        if !is_english(Some(&language)) {
+            contents.push_str(&format!("language = {:?}\n", language));
+        }
+        std::fs::write(&project_config, contents)?;
+        true
+    };
+
+    // 3. Embedding model
+    let supported: Vec<&str> = CANDLE_MODELS.iter().map(|(name, _, _)| *name).collect();
+    let default_model = options
+        .model
+        .clone()
+        .or_else(|| config.embedding.model.clone())
+        .unwrap_or_else(|| default_model_for_language(Some(&language)).to_string());
+    let model = loop {
+        let answer = prompter.ask(
+            &format!("Embedding model ({})", supported.join(", ")),
+            &default_model,
+        )?;
+        if supported.contains(&answer.as_str()) {
+            break answer;
+        }
+        if !prompter.interactive {
+            anyhow::bail!(
+                "Unknown embedding model '{}'. Supported: {}",
+                answer,
+                supported.join(", ")
+            );
+        }
+        println!("Unknown model '{}'.", answer);
+    };
+
+    let embedding_config = data_path.join("embedding.toml");
+    if !embedding_config.exists() {
+        std::fs::write(
+            &embedding_config,
+            format!("provider = \"candle\"\nmodel = \"{}\"\n", model),
+        )?;
+    }
+
+    // Offline, this only succeeds if the model is already cached
+    let spinner = (mode != OutputMode::Json).then(|| {
+        let verb = if hub::is_offline() {
+            "Loading"
+        } else {
+            "Downloading"
+        };
+        create_spinner(&format!("{} {}...", verb, model))
+    });
+    let provider = EmbeddingProviderConfig::Candle {
+        model: model.clone(),
+        cache_dir: None,
+        show_download_progress: false,
+        device: config.embedding.device,
+        threads: config.embedding.threads,
+    };
+    let embedding_service: Arc<dyn EmbeddingService + Send + Sync> =
+        create_embedding_service(&provider)
+            .unwrap_or_else(|_| Arc::new(NoopEmbeddingService::new()));
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+    let model_ready = embedding_service.is_available();
+
+    // 4. Sample world
+    let sample = match options.sample {
+        Some(sample) => sample,
+        None => prompter.confirm("Import the sample noir world?", false)?,
+    };
+    let mut sample_entities = 0;
+    if sample {
+        sample_entities = import_sample(&data_path, embedding_service.clone()).await?;
+    }
+
+    // 5. MCP client snippets
+    let snippet = mcp_snippet(&data_path);
+
+    let summary = InitSummary {
+        data_path: data_path.display().to_string(),
+        project_config: wrote_project_config.then(|| project_config.display().to_string()),
+        language: language.clone(),
+        model: model.clone(),
+        model_ready,
+        sample_entities,
+        claude_desktop_config: desktop_config_path().map(|p| p.display().to_string()),
+        claude_desktop_snippet: snippet.clone(),
+        claude_code_command: claude_code_command(&data_path),
+    };
+
+    if mode == OutputMode::Json {
+        output_json(&summary);
+        return Ok(());
+    }
+
+    println!();
+    print_kv("Data directory", &summary.data_path);
+    match &summary.project_config {
+        Some(path) => print_kv("Project config", path),
+        None => print_kv("Project config", "kept existing narra.toml"),
+    }
+    print_kv("Language", &summary.language);
+    print_kv("Embedding model", &model);
+    if model_ready {
+        print_success(&format!("Model {} is ready", model));
+    } else {
+        println!(
+            "{} Model {} is not available; keyword search still works.",
+            "!".yellow().bold(),
+            model
+        );
+        print_hint("Download it later with 'narra models pull', then run 'narra world backfill'.");
+    }
+    if sample {
+        print_success(&format!(
+            "Imported sample world ({} entities)",
+            sample_entities
+        ));
+    }
+
+    print_header("Connect an MCP client");
+    println!("Claude Code:");
+    println!("  {}", summary.claude_code_command);
+    println!();
+    match &summary.claude_desktop_config {
+        Some(path) => println!("Claude Desktop ({}):", path),
+        None => println!("Claude Desktop:"),
+    }
+    println!("{}", serde_json::to_string_pretty(&snippet)?);
+
+    println!();
+    print_success("Narra is set up. Try: narra world status");
+    Ok(())
+}
+
+/// Import the bundled sample world, embedding it when the model is available.
+async fn import_sample(
+    data_path: &Path,
+    embedding_service: Arc<dyn EmbeddingService + Send + Sync>,
+) -> Result<usize> {
+    let db = init_db(&load_db_config(data_path), data_path).await?;
+    apply_schema(&db).await?;
+    let db = Arc::new(db);
+
+    let import: NarraImport = serde_yaml_ng::from_str(SAMPLE_WORLD)?;
+    let staleness = Arc::new(StalenessManager::new(db.clone(), embedding_service.clone()));
+    let result = ImportService::new(db.clone(), staleness)
+        .execute_import(import, ConflictMode::Skip)
+        .await?;
+
+    if embedding_service.is_available() {
+        BackfillService::new(db, embedding_service)
+            .backfill_all()
+            .await?;
+    }
+    Ok(result.total_created + result.total_updated)
+}
+
+/// `mcpServers` entry for Claude Desktop's config file.
+fn mcp_snippet(data_path: &Path) -> serde_json::Value {
+    serde_json::json!({
+        "mcpServers": {
+            "narra": {
+                "command": "narra",
+                "args": ["mcp"],
+                "env": { "NARRA_DATA_PATH": data_path.display().to_string() }
+            }
+        }
+    })
+}
+
+fn claude_code_command(data_path: &Path) -> String {
+    format!(
+        "claude mcp add narra -e NARRA_DATA_PATH={} -- narra mcp",
+        data_path.display()
+    )
+}
+
+/// Where Claude Desktop reads its config on this platform.
+fn desktop_config_path() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        dirs::home_dir()
+            .map(|h| h.join("Library/Application Support/Claude/claude_desktop_config.json"))
+    } else {
+        dirs::config_dir().map(|c| c.join("Claude").join("claude_desktop_config.json"))
+    }
+}
@@ -3,12 +3,14 @@
 use anyhow::Result;
 
 use crate::cli::output::{
-    output_json, output_json_list, print_error, print_success, print_table, OutputMode,
+    output_json, output_json_list, print_error, print_hint, print_success, print_table, OutputMode,
 };
 use crate::cli::resolve::bare_key;
 use crate::init::AppContext;
+use crate::models::knowledge::record_derivation;
 use crate::models::{CertaintyLevel, KnowledgeCreate, KnowledgeStateCreate, LearningMethod};
 use crate::repository::KnowledgeRepository;
+use crate::services::transmission;
 
 pub async fn list_knowledge(
     ctx: &AppContext,
@@ -74,7 +76,7 @@ pub async fn record_knowledge(
     ctx: &AppContext,
     character: &str,
     fact: &str,
-    certainty: &str,
+    certainty: Option<&str>,
     method: Option<&str>,
     source: Option<&str>,
     event: Option<&str>,
@@ -82,6 +84,23 @@ pub async fn record_knowledge(
 ) -> Result<()> {
     let char_key = bare_key(character, "character");
 
+    // Passed-on knowledge derives its certainty unless one is given
+    let derivation = match (certainty, source) {
+        (None | Some("derive"), Some(source)) => Some(
+            transmission::derive_certainty(
+                &ctx.db,
+                &ctx.ontology,
+                &ctx.transmission_rules,
+                &char_key,
+                &bare_key(source, "character"),
+                fact,
+            )
+            .await?,
+        ),
+        (Some("derive"), None) => anyhow::bail!("--certainty derive needs --source"),
+        _ => None,
+    };
+
     let knowledge = ctx
         .knowledge_repo
         .create_knowledge(KnowledgeCreate {
@@ -90,18 +109,26 @@ pub async fn record_knowledge(
         })
         .await?;
 
-    let certainty_level = match certainty.to_lowercase().as_str() {
-        "knows" => CertaintyLevel::Knows,
-        "suspects" => CertaintyLevel::Suspects,
-        "believes_wrongly" => CertaintyLevel::BelievesWrongly,
-        "uncertain" => CertaintyLevel::Uncertain,
-        "assumes" => CertaintyLevel::Assumes,
-        "denies" => CertaintyLevel::Denies,
-        "forgotten" => CertaintyLevel::Forgotten,
-        _ => CertaintyLevel::Knows,
+    let certainty_level = match &derivation {
+        Some(d) => d.certainty,
+        None => match certainty.unwrap_or("knows").to_lowercase().as_str() {
+            "knows" => CertaintyLevel::Knows,
+            "suspects" => CertaintyLevel::Suspects,
+            "believes_wrongly" => CertaintyLevel::BelievesWrongly,
+            "uncertain" => CertaintyLevel::Uncertain,
+            "assumes" => CertaintyLevel::Assumes,
+            "denies" => CertaintyLevel::Denies,
+            "forgotten" => CertaintyLevel::Forgotten,
+            _ => CertaintyLevel::Knows,
+        },
     };
 
-    let learning_method = match method.unwrap_or("initial").to_lowercase().as_str() {
+    // Derived knowledge was told, which needs the event it was told at
+    let default_method = match (&derivation, event) {
+        (Some(_), Some(_)) => "told",
+        _ => "initial",
+    };
+    let learning_method = match method.unwrap_or(default_method).to_lowercase().as_str() {
         "told" => LearningMethod::Told,
         "overheard" => LearningMethod::Overheard,
         "witnessed" => LearningMethod::Witnessed,
@@ -120,14 +147,22 @@ pub async fn record_knowledge(
         learning_method,
         source_character: source.map(|s| bare_key(s, "character")),
         event: event.map(|e| bare_key(e, "event")),
+        truth_value: derivation.as_ref().and_then(|d| d.truth_value.clone()),
         ..Default::default()
     };
 
-    match ctx
+    let created = match ctx
         .knowledge_repo
         .create_knowledge_state(&char_key, &target, state_data)
         .await
     {
+        Ok(state) => match &derivation {
+            Some(d) => record_derivation(&ctx.db, &state.id, &d.source_state, &d.explanation).await,
+            None => Ok(state),
+        },
+        Err(e) => Err(e),
+    };
+    match created {
         Ok(state) => {
             if mode == OutputMode::Json {
                 output_json(&state);
@@ -136,6 +171,9 @@ pub async fn record_knowledge(
                     "Recorded knowledge: {} {:?} '{}' ({})",
                     char_key, certainty_level, fact, state.id
                 ));
+                if let Some(d) = &derivation {
+                    print_hint(&format!("Certainty derived: {}", d.explanation));
+                }
             }
         }
         Err(e) => {
@@ -0,0 +1,222 @@
+//! Lexicon handlers: `narra lexicon ...`.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cli::output::{
+    output_json, output_json_list, print_header, print_hint, print_kv, print_success, print_table,
+    OutputMode,
+};
+use crate::init::AppContext;
+use crate::models::lexeme::{self, Lexeme};
+use crate::models::LexemeCreate;
+use crate::services::events;
+use crate::services::lexicon::{read_lexicon_csv, LexiconService};
+
+/// Find an entry by `lexeme:<id>` or by word.
+async fn resolve_lexeme(ctx: &AppContext, word: &str, language: Option<&str>) -> Result<Lexeme> {
+    let found = match word.strip_prefix("lexeme:") {
+        Some(key) => lexeme::get_lexeme(&ctx.db, key).await?,
+        None => lexeme::find_lexeme(&ctx.db, word, language).await?,
+    };
+    found.ok_or_else(|| match language {
+        Some(language) => anyhow::anyhow!("'{}' is not in the {} lexicon", word, language),
+        None => anyhow::anyhow!("'{}' is not in the lexicon", word),
+    })
+}
+
+fn lexeme_rows(lexemes: &[Lexeme]) -> Vec<Vec<String>> {
+    let dash = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+    lexemes
+        .iter()
+        .map(|l| {
+            vec![
+                l.word.clone(),
+                l.gloss.clone(),
+                dash(&l.part_of_speech),
+                dash(&l.language),
+                dash(&l.etymology),
+            ]
+        })
+        .collect()
+}
+
+pub async fn handle_add(ctx: &AppContext, data: LexemeCreate, mode: OutputMode) -> Result<()> {
+    let created = lexeme::create_lexeme(&ctx.db, data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &created.id.to_string(),
+        "lexeme",
+        &created.word,
+    );
+
+    if mode == OutputMode::Json {
+        output_json(&created);
+    } else {
+        print_success(&format!(
+            "Added '{}' ({}) as {}",
+            created.word, created.gloss, created.id
+        ));
+    }
+    Ok(())
+}
+
+pub async fn handle_list(ctx: &AppContext, language: Option<&str>, mode: OutputMode) -> Result<()> {
+    let lexemes = lexeme::list_lexemes(&ctx.db, language).await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&lexemes);
+        return Ok(());
+    }
+
+    if lexemes.is_empty() {
+        println!(
+            "The lexicon is empty. Add a word with 'narra lexicon add <word> --gloss <meaning>'."
+        );
+        return Ok(());
+    }
+    print_table(
+        &["Word", "Gloss", "Part of Speech", "Language", "Etymology"],
+        lexeme_rows(&lexemes),
+    );
+    Ok(())
+}
+
+pub async fn handle_show(
+    ctx: &AppContext,
+    word: &str,
+    language: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let entry = resolve_lexeme(ctx, word, language).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&entry);
+        return Ok(());
+    }
+
+    print_header(&entry.word);
+    print_kv("ID", &entry.id.to_string());
+    print_kv("Gloss", &entry.gloss);
+    if let Some(pos) = &entry.part_of_speech {
+        print_kv("Part of speech", pos);
+    }
+    if let Some(language) = &entry.language {
+        print_kv("Language", language);
+    }
+    if let Some(etymology) = &entry.etymology {
+        print_kv("Etymology", etymology);
+    }
+    Ok(())
+}
+
+pub async fn handle_remove(
+    ctx: &AppContext,
+    word: &str,
+    language: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let entry = resolve_lexeme(ctx, word, language).await?;
+    let key = entry.id.key().to_string();
+    lexeme::delete_lexeme(&ctx.db, &key).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_DELETED,
+        "cli",
+        &entry.id.to_string(),
+        "lexeme",
+        &entry.word,
+    );
+
+    if mode == OutputMode::Json {
+        output_json(&entry);
+    } else {
+        print_success(&format!("Removed '{}' from the lexicon", entry.word));
+    }
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct ImportSummary {
+    created: usize,
+    updated: usize,
+}
+
+pub async fn handle_import(
+    ctx: &AppContext,
+    file: &Path,
+    language: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let reader = std::fs::File::open(file)
+        .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", file.display(), e))?;
+    let entries = read_lexicon_csv(reader, language)?;
+
+    let mut summary = ImportSummary {
+        created: 0,
+        updated: 0,
+    };
+    for entry in entries {
+        let (saved, created) = lexeme::upsert_lexeme(&ctx.db, entry).await?;
+        let event = if created {
+            summary.created += 1;
+            events::ENTITY_CREATED
+        } else {
+            summary.updated += 1;
+            events::ENTITY_UPDATED
+        };
+        ctx.event_bus
+            .emit_entity(event, "cli", &saved.id.to_string(), "lexeme", &saved.word);
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&summary);
+    } else {
+        print_success(&format!(
+            "Imported {} words ({} new, {} updated)",
+            summary.created + summary.updated,
+            summary.created,
+            summary.updated
+        ));
+    }
+    Ok(())
+}
+
+pub async fn handle_check(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let drift = LexiconService::new(ctx.db.clone())
+        .gloss_drift()
+        .await
+        .map_err(|e| anyhow::anyhow!("Lexicon check failed: {}", e))?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&drift);
+        return Ok(());
+    }
+
+    if drift.is_empty() {
+        println!("Every gloss in the notes agrees with the lexicon.");
+        return Ok(());
+    }
+
+    for word in &drift {
+        print_header(&format!("{} — lexicon: {}", word.word, word.gloss));
+        let rows: Vec<Vec<String>> = word
+            .usages
+            .iter()
+            .map(|u| {
+                vec![
+                    if u.agrees { "ok" } else { "DRIFT" }.to_string(),
+                    u.gloss.clone(),
+                    u.note_title.clone(),
+                    u.note_id.clone(),
+                ]
+            })
+            .collect();
+        print_table(&["", "Gloss in Note", "Note", "ID"], rows);
+    }
+    print_hint(
+        "Fix the note, or change the lexicon with: narra update lexeme:<id> --set gloss=<meaning>",
+    );
+    Ok(())
+}
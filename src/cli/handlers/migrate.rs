@@ -0,0 +1,181 @@
+//! Schema migration handlers: `narra migrate status` and `narra migrate up`.
+//!
+//! These open the database directly: `AppContext` applies pending migrations
+//! while opening the world, which would leave nothing to report.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::cli::output::schema::{MigrateStatus, MigrateUp, MigrationEntry};
+use crate::cli::output::{
+    output_json, print_hint, print_kv, print_success, print_table, OutputMode,
+};
+use crate::db::connection::{load_db_config, DbConfig};
+use crate::db::migrate::{self, Migration, MigrationState};
+use crate::db::schema::MIGRATIONS;
+use crate::init::{connect_db, resolve_data_path};
+
+fn latest_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+fn entry(
+    migration: &Migration,
+    state: MigrationState,
+    applied_at: Option<String>,
+) -> MigrationEntry {
+    MigrationEntry {
+        version: migration.version,
+        name: migration.name.to_string(),
+        summary: migration.summary.to_string(),
+        state: state.as_str().to_string(),
+        applied_at,
+    }
+}
+
+pub async fn handle_migrate_status(data_path: Option<PathBuf>, mode: OutputMode) -> Result<()> {
+    let db = connect_db(data_path).await?;
+    let status = migrate::status(&db).await?;
+    let report = MigrateStatus {
+        current_version: status.current_version(),
+        latest_version: latest_version(),
+        migrations: status
+            .migrations
+            .iter()
+            .map(|(m, state, at)| entry(m, *state, at.as_ref().map(|at| at.to_string())))
+            .collect(),
+        unknown_versions: status.unknown.iter().map(|a| a.version).collect(),
+    };
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+
+    print_kv("Schema version", &report.current_version.to_string());
+    print_kv("Latest known", &report.latest_version.to_string());
+    // Applied migrations are the common case; list only what needs attention
+    let rows: Vec<Vec<String>> = report
+        .migrations
+        .iter()
+        .filter(|m| m.state != MigrationState::Applied.as_str())
+        .map(|m| {
+            vec![
+                format!("{:03}", m.version),
+                m.name.clone(),
+                m.state.clone(),
+                m.summary.clone(),
+            ]
+        })
+        .collect();
+    if rows.is_empty() {
+        print_success("Schema is up to date");
+    } else {
+        println!();
+        print_table(&["Version", "Name", "State", "Summary"], rows);
+    }
+    if !report.unknown_versions.is_empty() {
+        println!(
+            "\n{} This world was migrated by a newer narra (versions {}). Upgrade narra before writing to it.",
+            "Warning:".yellow().bold(),
+            report
+                .unknown_versions
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    if report
+        .migrations
+        .iter()
+        .any(|m| m.state == MigrationState::Pending.as_str())
+    {
+        print_hint("Run 'narra migrate up' to apply pending migrations.");
+    }
+    Ok(())
+}
+
+pub async fn handle_migrate_up(
+    data_path: Option<PathBuf>,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let resolved = resolve_data_path(data_path.clone());
+    if !dry_run && matches!(load_db_config(&resolved), DbConfig::Encrypted) {
+        anyhow::bail!(
+            "Encrypted worlds are migrated when opened, so the vault is sealed with the result. \
+             Run any narra command (e.g. 'narra world status') to upgrade it."
+        );
+    }
+
+    let db = connect_db(data_path).await?;
+    let status = migrate::status(&db).await?;
+    let from_version = status.current_version();
+    let pending = status.pending();
+
+    let applied = if dry_run {
+        pending
+    } else {
+        migrate::up(&db).await?
+    };
+    let report = MigrateUp {
+        dry_run,
+        from_version,
+        to_version: if dry_run {
+            from_version
+        } else {
+            applied.last().map_or(from_version, |m| m.version)
+        },
+        migrations: applied
+            .iter()
+            .map(|m| {
+                let state = if dry_run {
+                    MigrationState::Pending
+                } else {
+                    MigrationState::Applied
+                };
+                entry(m, state, None)
+            })
+            .collect(),
+    };
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+
+    if report.migrations.is_empty() {
+        print_success(&format!(
+            "Schema is up to date (version {})",
+            report.from_version
+        ));
+        return Ok(());
+    }
+    let rows = report
+        .migrations
+        .iter()
+        .map(|m| {
+            vec![
+                format!("{:03}", m.version),
+                m.name.clone(),
+                m.summary.clone(),
+            ]
+        })
+        .collect();
+    print_table(&["Version", "Name", "Summary"], rows);
+    if dry_run {
+        print_hint(&format!(
+            "{} migration(s) would be applied. Run without --dry-run to apply them.",
+            report.migrations.len()
+        ));
+    } else {
+        print_success(&format!(
+            "Migrated schema from version {} to {}",
+            report.from_version, report.to_version
+        ));
+    }
+    Ok(())
+}
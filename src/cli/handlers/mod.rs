@@ -1,18 +1,40 @@
 //! CLI command handlers.
 
+pub mod access;
 pub mod analyze;
 pub mod arc;
 pub mod ask;
+pub mod asset;
+pub mod backup;
 pub mod batch;
+pub mod complete;
+pub mod config;
+pub mod doctor;
 pub mod entity;
 pub mod explore;
 pub mod fact;
 pub mod find;
+pub mod foreshadow;
+pub mod generate;
+pub mod goal;
+pub mod init;
 pub mod knowledge;
+pub mod lexicon;
+pub mod migrate;
+pub mod models;
 pub mod note;
 pub mod path;
 pub mod perception;
+pub mod phase;
 pub mod relationship;
+pub mod requires;
+pub mod scenes;
 pub mod session;
+pub mod style;
+pub mod tag;
+pub mod template;
+pub mod term;
+pub mod thread;
 pub mod utility;
+pub mod vault;
 pub mod world;
@@ -0,0 +1,373 @@
+//! Model management handlers: `narra models ...`.
+//!
+//! Like `init`, these run without an `AppContext`: they manage model files
+//! rather than a world.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use indicatif::HumanBytes;
+
+use crate::cli::output::schema::{ModelBenchResult, ModelInfo, ModelPullResult, ModelsRemoved};
+use crate::cli::output::{
+    create_spinner, output_json, output_json_list, print_error, print_hint, print_success,
+    print_table, OutputMode,
+};
+use crate::config::NarraConfig;
+use crate::embedding::hub::{self, ModelSpec};
+use crate::embedding::EmbeddingProviderConfig;
+use crate::init::resolve_provider_config;
+
+/// Embedding model the world at `data_path` would load.
+pub(crate) fn configured_embedding_model(data_path: &Path, config: &NarraConfig) -> String {
+    match resolve_provider_config(data_path, config) {
+        EmbeddingProviderConfig::Candle { model, .. } => model,
+    }
+}
+
+pub fn handle_models_pull(
+    data_path: &Path,
+    config: &NarraConfig,
+    all: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    if hub::is_offline() {
+        anyhow::bail!("'narra models pull' needs network access; drop --offline / NARRA_OFFLINE");
+    }
+
+    let models: Vec<ModelSpec> = if all {
+        hub::catalog()
+    } else {
+        hub::required(
+            &configured_embedding_model(data_path, config),
+            config.language.as_deref(),
+        )
+    };
+
+    let mut results = Vec::new();
+    for model in &models {
+        let (status, error) = if hub::is_cached(model) {
+            ("cached", None)
+        } else {
+            let spinner = (mode != OutputMode::Json).then(|| {
+                create_spinner(&format!("Downloading {} ({})...", model.name, model.repo))
+            });
+            let outcome = hub::pull(model);
+            if let Some(spinner) = spinner {
+                spinner.finish_and_clear();
+            }
+            match outcome {
+                Ok(()) => ("downloaded", None),
+                Err(e) => ("failed", Some(format!("{:#}", e))),
+            }
+        };
+        results.push(ModelPullResult {
+            name: model.name.to_string(),
+            repo: model.repo.to_string(),
+            feature: model.feature.to_string(),
+            status: status.to_string(),
+            error,
+        });
+    }
+
+    if mode == OutputMode::Json {
+        output_json_list(&results);
+    } else {
+        let rows: Vec<Vec<String>> = results
+            .iter()
+            .map(|r| {
+                vec![
+                    r.name.clone(),
+                    r.feature.clone(),
+                    r.status.clone(),
+                    r.error.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(&["Model", "Needed For", "Status", "Error"], rows);
+    }
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    if failed > 0 {
+        if mode != OutputMode::Json {
+            print_error(&format!("{} model(s) failed to download", failed));
+        }
+        anyhow::bail!("Model download incomplete");
+    }
+    if mode != OutputMode::Json {
+        print_success("All models are available offline");
+        print_hint("Run with --offline (or NARRA_OFFLINE=1) to never touch the network.");
+    }
+    Ok(())
+}
+
+pub fn handle_models_list(data_path: &Path, config: &NarraConfig, mode: OutputMode) -> Result<()> {
+    let required = hub::required(
+        &configured_embedding_model(data_path, config),
+        config.language.as_deref(),
+    );
+    let infos: Vec<ModelInfo> = hub::catalog()
+        .iter()
+        .map(|model| ModelInfo {
+            name: model.name.to_string(),
+            repo: model.repo.to_string(),
+            feature: model.feature.to_string(),
+            cached: hub::is_cached(model),
+            size_bytes: hub::cached_size(model),
+            in_use: required.contains(model),
+        })
+        .collect();
+
+    if mode == OutputMode::Json {
+        output_json_list(&infos);
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = infos
+        .iter()
+        .map(|m| {
+            let cached = match (m.cached, m.size_bytes) {
+                (true, _) => "yes",
+                (false, 0) => "no",
+                (false, _) => "partial",
+            };
+            vec![
+                m.name.clone(),
+                m.feature.clone(),
+                cached.to_string(),
+                if m.size_bytes > 0 {
+                    HumanBytes(m.size_bytes).to_string()
+                } else {
+                    "-".to_string()
+                },
+                if m.in_use { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect();
+    print_table(&["Model", "Needed For", "Cached", "Size", "In Use"], rows);
+
+    let total: u64 = infos.iter().map(|m| m.size_bytes).sum();
+    let reclaimable: u64 = infos
+        .iter()
+        .filter(|m| !m.in_use)
+        .map(|m| m.size_bytes)
+        .sum();
+    println!("\nTotal: {}", HumanBytes(total));
+    if reclaimable > 0 {
+        print_hint(&format!(
+            "{} is used by models the current configuration doesn't load: narra models remove --unused",
+            HumanBytes(reclaimable)
+        ));
+    }
+    Ok(())
+}
+
+pub fn handle_models_remove(
+    data_path: &Path,
+    config: &NarraConfig,
+    names: &[String],
+    unused: bool,
+    force: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let required = hub::required(
+        &configured_embedding_model(data_path, config),
+        config.language.as_deref(),
+    );
+
+    let targets: Vec<ModelSpec> = if unused {
+        hub::catalog()
+            .into_iter()
+            .filter(|m| !required.contains(m) && hub::cached_size(m) > 0)
+            .collect()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                hub::find(name).ok_or_else(|| {
+                    anyhow::anyhow!("Unknown model '{}'. See: narra models list", name)
+                })
+            })
+            .collect::<Result<_>>()?
+    };
+
+    if !force {
+        if let Some(model) = targets.iter().find(|m| required.contains(m)) {
+            anyhow::bail!(
+                "{} is used for {}; pass --force to remove it anyway (it will download again on next start)",
+                model.name,
+                model.feature
+            );
+        }
+    }
+
+    let mut removed = Vec::new();
+    let mut freed_bytes = 0;
+    for model in &targets {
+        let freed = hub::remove(model)?;
+        if freed > 0 {
+            removed.push(model.name.to_string());
+            freed_bytes += freed;
+        }
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&ModelsRemoved {
+            removed,
+            freed_bytes,
+        });
+    } else if removed.is_empty() {
+        println!("Nothing to remove.");
+    } else {
+        print_success(&format!(
+            "Removed {} ({} freed)",
+            removed.join(", "),
+            HumanBytes(freed_bytes)
+        ));
+    }
+    Ok(())
+}
+
+/// Flags for `narra models bench`.
+pub struct BenchOptions {
+    pub texts: usize,
+    pub batch: usize,
+    pub threads: Option<usize>,
+}
+
+/// Varied prose-like sentences so tokenized lengths resemble real entities.
+fn bench_texts(count: usize) -> Vec<String> {
+    const SUBJECTS: &[&str] = &["The detective", "Her brother", "The old sailor", "Mara"];
+    const ACTIONS: &[&str] = &[
+        "walks the harbor at night, turning over the letter in her pocket",
+        "distrusts the mayor since the fire at the warehouse",
+        "knows the ledger was forged but cannot prove who forged it",
+        "waits at the station for a train that never arrives",
+    ];
+    (0..count)
+        .map(|i| {
+            format!(
+                "{} {} (note {}).",
+                SUBJECTS[i % SUBJECTS.len()],
+                ACTIONS[(i / SUBJECTS.len()) % ACTIONS.len()],
+                i
+            )
+        })
+        .collect()
+}
+
+pub fn handle_models_bench(
+    data_path: &Path,
+    config: &NarraConfig,
+    devices: &[String],
+    options: BenchOptions,
+    mode: OutputMode,
+) -> Result<()> {
+    use std::time::Instant;
+
+    use crate::embedding::candle_backend::{
+        device_for, download_model, set_cpu_threads, BertEmbedder, ExecutionProvider,
+    };
+
+    anyhow::ensure!(
+        options.texts > 0 && options.batch > 0,
+        "--texts and --batch must be positive"
+    );
+    let providers: Vec<ExecutionProvider> = if devices.is_empty() {
+        ExecutionProvider::CONCRETE
+            .into_iter()
+            .filter(|p| p.is_compiled())
+            .collect()
+    } else {
+        devices.iter().map(|d| d.parse()).collect::<Result<_>>()?
+    };
+    if let Some(threads) = options.threads.or(config.embedding.threads) {
+        set_cpu_threads(threads);
+    }
+
+    let model = configured_embedding_model(data_path, config);
+    let spec =
+        hub::find(&model).ok_or_else(|| anyhow::anyhow!("Unknown embedding model '{}'", model))?;
+    let files = download_model(spec.repo, None)?;
+    let texts = bench_texts(options.texts);
+
+    let mut results = Vec::new();
+    for provider in providers {
+        let spinner = (mode != OutputMode::Json)
+            .then(|| create_spinner(&format!("Benchmarking {} on {}...", model, provider)));
+        let outcome = (|| -> Result<(u64, f64)> {
+            let started = Instant::now();
+            let embedder = BertEmbedder::new(&files, device_for(provider)?)?;
+            let load_ms = started.elapsed().as_millis() as u64;
+            // Warm-up batch: kernel compilation and allocation aren't throughput
+            embedder.embed(&texts[..options.batch.min(texts.len())])?;
+            let started = Instant::now();
+            for chunk in texts.chunks(options.batch) {
+                embedder.embed(chunk)?;
+            }
+            let secs = started.elapsed().as_secs_f64().max(f64::EPSILON);
+            Ok((load_ms, texts.len() as f64 / secs))
+        })();
+        if let Some(spinner) = spinner {
+            spinner.finish_and_clear();
+        }
+        results.push(match outcome {
+            Ok((load_ms, texts_per_sec)) => ModelBenchResult {
+                device: provider.to_string(),
+                model: model.clone(),
+                status: "ok".to_string(),
+                load_ms,
+                texts_per_sec,
+                error: None,
+            },
+            Err(e) => ModelBenchResult {
+                device: provider.to_string(),
+                model: model.clone(),
+                status: "unavailable".to_string(),
+                load_ms: 0,
+                texts_per_sec: 0.0,
+                error: Some(format!("{:#}", e)),
+            },
+        });
+    }
+
+    if mode == OutputMode::Json {
+        output_json_list(&results);
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|r| {
+            vec![
+                r.device.clone(),
+                r.status.clone(),
+                if r.error.is_none() {
+                    format!("{} ms", r.load_ms)
+                } else {
+                    "-".to_string()
+                },
+                if r.error.is_none() {
+                    format!("{:.1}", r.texts_per_sec)
+                } else {
+                    "-".to_string()
+                },
+                r.error.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(&["Device", "Status", "Load", "Texts/s", "Error"], rows);
+
+    if let Some(best) = results
+        .iter()
+        .filter(|r| r.error.is_none())
+        .max_by(|a, b| a.texts_per_sec.total_cmp(&b.texts_per_sec))
+    {
+        print_hint(&format!(
+            "Fastest: {}. Use it with [embedding] device = \"{}\" in narra.toml or NARRA_EMBEDDING_DEVICE={}",
+            best.device, best.device, best.device
+        ));
+    }
+    Ok(())
+}
@@ -2,19 +2,40 @@
 
 use anyhow::Result;
 
+use crate::cli::output::schema::NoteDetached;
 use crate::cli::output::{
-    output_json, output_json_list, print_error, print_success, print_table, OutputMode,
+    output_json, output_json_list, print_error, print_hint, print_success, print_table, OutputMode,
 };
-use crate::cli::resolve::bare_key;
+use crate::cli::resolve::{bare_key, resolve_single};
 use crate::init::AppContext;
-use crate::models::note;
+use crate::models::note::{self, NoteKind, NoteStatus};
 use crate::models::NoteCreate;
+use crate::services::note_links::{self, LinkSync};
 
-pub async fn list_notes(ctx: &AppContext, entity: Option<&str>, mode: OutputMode) -> Result<()> {
-    let notes = match entity {
+pub async fn list_notes(
+    ctx: &AppContext,
+    entity: Option<&str>,
+    tag: Option<&str>,
+    kind: Option<&str>,
+    status: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let mut notes = match entity {
         Some(entity_id) => note::get_entity_notes(&ctx.db, entity_id).await?,
         None => note::list_notes(&ctx.db, 100, 0).await?,
     };
+    if let Some(tag) = tag {
+        let tag = crate::models::tag::normalize_tag(tag)?;
+        notes.retain(|n| n.tags.contains(&tag));
+    }
+    if let Some(kind) = kind {
+        let kind: NoteKind = kind.parse()?;
+        notes.retain(|n| n.kind == kind);
+    }
+    if let Some(status) = status {
+        let status: NoteStatus = status.parse()?;
+        notes.retain(|n| n.kind.has_status() && n.status == status);
+    }
 
     if mode == OutputMode::Json {
         output_json_list(&notes);
@@ -29,11 +50,16 @@ pub async fn list_notes(ctx: &AppContext, entity: Option<&str>, mode: OutputMode
             } else {
                 n.body.clone()
             };
-            vec![n.id.to_string(), n.title.clone(), body_preview]
+            let kind = if n.kind.has_status() {
+                format!("{} ({})", n.kind, n.status)
+            } else {
+                n.kind.to_string()
+            };
+            vec![n.id.to_string(), kind, n.title.clone(), body_preview]
         })
         .collect();
 
-    print_table(&["ID", "Title", "Body"], rows);
+    print_table(&["ID", "Kind", "Title", "Body"], rows);
     Ok(())
 }
 
@@ -41,12 +67,21 @@ pub async fn create_note(
     ctx: &AppContext,
     title: &str,
     body: &str,
+    kind: Option<&str>,
     attach_to: &[String],
     mode: OutputMode,
 ) -> Result<()> {
+    let kind = kind
+        .map(str::parse::<NoteKind>)
+        .transpose()?
+        .unwrap_or_default();
+    note::check_critique_targets(kind, attach_to)?;
+
     let data = NoteCreate {
         title: title.to_string(),
         body: body.to_string(),
+        kind,
+        ..Default::default()
     };
 
     let created = note::create_note(&ctx.db, data).await?;
@@ -65,6 +100,20 @@ pub async fn create_note(
         }
     }
 
+    let links = note_links::sync_note_links(&ctx.db, &note_key).await?;
+
+    // Embed research now so `ask` can surface it without a backfill
+    let mut embed_error = None;
+    if kind == NoteKind::Research && ctx.embedding_service.is_available() {
+        if let Err(e) = ctx
+            .staleness_manager
+            .regenerate_embedding(&created.id.to_string(), None)
+            .await
+        {
+            embed_error = Some(e);
+        }
+    }
+
     if mode == OutputMode::Json {
         output_json(&created);
     } else {
@@ -72,6 +121,99 @@ pub async fn create_note(
             "Created note '{}' ({})",
             created.title, created.id
         ));
+        print_link_sync(&links);
+        if let Some(e) = embed_error {
+            print_hint(&format!(
+                "Could not embed research note ({}); run narra world backfill",
+                e
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Report what a note's [[links]] resolved to.
+pub fn print_link_sync(links: &LinkSync) {
+    if !links.linked.is_empty() {
+        print_success(&format!("Linked to {}", links.linked.join(", ")));
+    }
+    if !links.unresolved.is_empty() {
+        print_hint(&format!(
+            "No entity named {}",
+            links
+                .unresolved
+                .iter()
+                .map(|l| format!("[[{}]]", l))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    if !links.ambiguous.is_empty() {
+        print_hint(&format!(
+            "Several entities match {}; link by ID instead, e.g. [[character:alice]]",
+            links
+                .ambiguous
+                .iter()
+                .map(|l| format!("[[{}]]", l))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+}
+
+pub async fn backlinks(
+    ctx: &AppContext,
+    entity: &str,
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    let entity_id = resolve_single(ctx, entity, no_semantic).await?;
+    let links = note_links::backlinks(&ctx.db, &entity_id).await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&links);
+        return Ok(());
+    }
+
+    if links.is_empty() {
+        println!("No notes link to {}", entity_id);
+        print_hint("Write [[Entity Name]] in a note body to link it");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = links
+        .iter()
+        .map(|b| {
+            vec![
+                b.note.id.to_string(),
+                b.note.title.clone(),
+                format!("[[{}]]", b.link),
+            ]
+        })
+        .collect();
+    print_table(&["ID", "Title", "Link"], rows);
+    Ok(())
+}
+
+/// Set a todo or critique's status (`note resolve`, `note reopen`).
+pub async fn set_status(
+    ctx: &AppContext,
+    note_id: &str,
+    status: NoteStatus,
+    mode: OutputMode,
+) -> Result<()> {
+    let key = bare_key(note_id, "note");
+    let Some(updated) = note::set_note_status(&ctx.db, &key, status).await? else {
+        anyhow::bail!("Note '{}' not found", key);
+    };
+
+    if mode == OutputMode::Json {
+        output_json(&updated);
+    } else {
+        print_success(&format!(
+            "Marked {} '{}' {}",
+            updated.kind, updated.title, updated.status
+        ));
     }
     Ok(())
 }
@@ -103,10 +245,11 @@ pub async fn detach_note(
     note::detach_note(&ctx.db, &key, entity_id).await?;
 
     if mode == OutputMode::Json {
-        println!(
-            r#"{{"status": "ok", "note": "{}", "entity": "{}"}}"#,
-            key, entity_id
-        );
+        output_json(&NoteDetached {
+            status: "ok".to_string(),
+            note: key,
+            entity: entity_id.to_string(),
+        });
     } else {
         print_success(&format!("Detached note {} from {}", key, entity_id));
     }
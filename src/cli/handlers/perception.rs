@@ -9,9 +9,11 @@ use crate::cli::output::{
 };
 use crate::cli::resolve::resolve_by_name;
 use crate::init::AppContext;
+use crate::models::perception::set_trust;
 use crate::models::{Perception, PerceptionCreate};
 use crate::repository::relationship::RelationshipRepository;
 use crate::services::perception::PerceptionService;
+use crate::services::sentiment::RelationshipSentimentService;
 
 /// Resolve a single entity to a character ID.
 async fn resolve_character(ctx: &AppContext, input: &str, no_semantic: bool) -> Result<String> {
@@ -258,6 +260,88 @@ pub async fn handle_perception_shift(
     Ok(())
 }
 
+pub async fn handle_relationship_sentiment(
+    ctx: &AppContext,
+    observer: &str,
+    target: &str,
+    min_change: f32,
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    let observer_id = resolve_character(ctx, observer, no_semantic).await?;
+    let target_id = resolve_character(ctx, target, no_semantic).await?;
+
+    let service = RelationshipSentimentService::new(
+        ctx.db.clone(),
+        ctx.embedding_service.clone(),
+        ctx.emotion_service.clone(),
+    );
+    let result = service
+        .analyze(&observer_id, &target_id, min_change)
+        .await?;
+
+    if mode == OutputMode::Json {
+        output_json(&result);
+        return Ok(());
+    }
+
+    print_header(&format!(
+        "Relationship Sentiment: {} -> {}",
+        result.observer_name, result.target_name
+    ));
+    if result.points.is_empty() {
+        print_hint(
+            "No sentiment readings. Tie perception updates to events and load the embedding \
+             or emotion model, or run `narra world baseline-arcs` first.",
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = result
+        .points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let bar_len = (p.valence.abs() * 10.0).round() as usize;
+            let bar = if p.valence < 0.0 {
+                format!("{:>10}|", "-".repeat(bar_len))
+            } else {
+                format!("{:>10}|{}", "", "+".repeat(bar_len))
+            };
+            vec![
+                format!("{}", i + 1),
+                p.sequence
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                p.label.clone(),
+                format!("{:+.2}", p.valence),
+                bar,
+                p.emotion.clone().unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+    print_table(&["#", "Seq", "Event", "Valence", "", "Emotion"], rows);
+
+    print_kv("Trend", &result.trend);
+    if !result.inflections.is_empty() {
+        println!("\nInflections:");
+        for inflection in &result.inflections {
+            println!(
+                "  {} at {} ({:+.2})",
+                inflection.kind, inflection.label, inflection.change
+            );
+        }
+    }
+    if result.unanchored > 0 {
+        print_hint(&format!(
+            "{} snapshot(s) not tied to an event were left out of the curve.",
+            result.unanchored
+        ));
+    }
+
+    Ok(())
+}
+
 // =============================================================================
 // Creation — record perceptions
 // =============================================================================
@@ -273,8 +357,12 @@ pub async fn create_perception(
     rel_types: &[String],
     subtype: Option<&str>,
     history: Option<&str>,
+    trust: Option<f32>,
     mode: OutputMode,
 ) -> Result<()> {
+    if trust.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+        anyhow::bail!("--trust must be between 0.0 and 1.0");
+    }
     let observer_id = resolve_character(ctx, observer, false).await?;
     let target_id = resolve_character(ctx, target, false).await?;
 
@@ -295,10 +383,15 @@ pub async fn create_perception(
         history_notes: history.map(String::from),
     };
 
-    let result: Perception = ctx
+    let mut result: Perception = ctx
         .relationship_repo
         .create_perception(observer_key, target_key, data)
         .await?;
+    if trust.is_some() {
+        if let Some(updated) = set_trust(&ctx.db, observer_key, target_key, trust).await? {
+            result = updated;
+        }
+    }
 
     if mode == OutputMode::Json {
         output_json(&result);
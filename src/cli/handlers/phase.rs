@@ -0,0 +1,30 @@
+//! Saved narrative phase handlers for CLI.
+
+use anyhow::Result;
+
+use crate::cli::output::{output_json, print_hint, print_success, OutputMode};
+use crate::init::AppContext;
+use crate::services::TemporalService;
+
+pub async fn handle_rename(
+    ctx: &AppContext,
+    id: &str,
+    label: &str,
+    mode: OutputMode,
+) -> Result<()> {
+    let service = TemporalService::new(ctx.db.clone());
+    let phase = service.rename_phase(id, label).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&phase);
+    } else {
+        print_success(&format!(
+            "Phase {} is now \"{}\" (generated: {})",
+            phase.phase_id,
+            phase.label,
+            phase.auto_label.as_deref().unwrap_or_default()
+        ));
+        print_hint("The label follows this phase's members when phases are re-detected");
+    }
+    Ok(())
+}
@@ -2,14 +2,19 @@
 
 use anyhow::Result;
 
+use crate::cli::output::schema::RelationshipCreated;
 use crate::cli::output::{
     create_spinner, output_json, output_json_list, print_hint, print_success, print_table,
     OutputMode,
 };
 use crate::cli::resolve::{bare_key, resolve_single};
 use crate::init::AppContext;
-use crate::models::RelationshipCreate;
-use crate::repository::RelationshipRepository;
+use crate::models::{perception, RelationshipCreate};
+use crate::repository::{EntityRepository, RelationshipRepository};
+use crate::services::family::{self, FAMILY};
+use crate::services::ontology;
+use crate::services::reciprocity;
+use crate::services::trust;
 
 pub async fn list_relationships(
     ctx: &AppContext,
@@ -57,6 +62,7 @@ pub async fn list_relationships(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create_relationship(
     ctx: &AppContext,
     from: &str,
@@ -64,15 +70,20 @@ pub async fn create_relationship(
     rel_type: &str,
     subtype: Option<&str>,
     label: Option<&str>,
+    with_perceptions: bool,
     mode: OutputMode,
 ) -> Result<()> {
     let from_key = bare_key(from, "character");
     let to_key = bare_key(to, "character");
+    let checked = ctx.ontology.check(rel_type)?;
+    if with_perceptions {
+        ctx.access.check("perceives")?;
+    }
 
     let data = RelationshipCreate {
         from_character_id: from_key.clone(),
         to_character_id: to_key.clone(),
-        rel_type: rel_type.to_string(),
+        rel_type: checked.rel_type,
         subtype: subtype.map(|s| s.to_string()),
         label: label.map(|s| s.to_string()),
     };
@@ -81,16 +92,271 @@ pub async fn create_relationship(
         .relationship_repo
         .create_relationship(&from_key, &to_key, data)
         .await?;
+    let perceptions = if with_perceptions {
+        Some(
+            ontology::create_baseline_perceptions(
+                &ctx.db,
+                &ctx.ontology,
+                &from_key,
+                &to_key,
+                &rel.rel_type,
+                subtype,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
 
     if mode == OutputMode::Json {
-        output_json(&rel);
+        match perceptions {
+            Some(seeded) => output_json(&RelationshipCreated {
+                relationship: rel,
+                perceptions: seeded,
+            }),
+            None => output_json(&rel),
+        }
     } else {
         print_success(&format!(
             "Created {} relationship: {} -> {} ({})",
             rel.rel_type, rel.from_character, rel.to_character, rel.id,
         ));
+        if let Some(note) = &checked.note {
+            print_hint(note);
+        }
+        if let Some(seeded) = &perceptions {
+            for p in &seeded.created {
+                println!(
+                    "  Seeded perception {} -> {}: {}",
+                    p.from_character,
+                    p.to_character,
+                    p.perception.as_deref().unwrap_or_default()
+                );
+            }
+            if seeded.existing > 0 {
+                print_hint(&format!(
+                    "{} existing perception(s) left as they were.",
+                    seeded.existing
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Show trust from one character in another, after setting (`Some(Some)`)
+/// or clearing (`Some(None)`) it.
+pub async fn show_trust(
+    ctx: &AppContext,
+    from: &str,
+    to: &str,
+    change: Option<Option<f32>>,
+    mode: OutputMode,
+) -> Result<()> {
+    let from_key = bare_key(from, "character");
+    let to_key = bare_key(to, "character");
+
+    if let Some(value) = change {
+        if perception::set_trust(&ctx.db, &from_key, &to_key, value)
+            .await?
+            .is_none()
+        {
+            anyhow::bail!(
+                "character:{} has no perception of character:{}; record one first with `narra create perception`",
+                from_key,
+                to_key
+            );
+        }
+    }
+    let estimate = trust::estimate_trust(&ctx.db, &ctx.ontology, &from_key, &to_key).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&estimate);
+        return Ok(());
+    }
+    let source = if estimate.explicit { "set" } else { "inferred" };
+    println!(
+        "{} trusts {}: {:.2} ({}, {})",
+        estimate.from,
+        estimate.to,
+        estimate.value,
+        estimate.level(),
+        source
+    );
+    if !estimate.reasons.is_empty() {
+        println!(
+            "History suggests {:.2}: {}",
+            estimate.inferred,
+            estimate.reasons.join(", ")
+        );
+    }
+    match change {
+        Some(Some(_)) => print_success("Trust set"),
+        Some(None) => print_success("Trust cleared; it is inferred again"),
+        None => {}
+    }
+    Ok(())
+}
+
+pub async fn list_types(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let types = ctx.ontology.types();
+
+    if mode == OutputMode::Json {
+        output_json_list(types);
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = types
+        .iter()
+        .map(|t| {
+            vec![
+                t.name.clone(),
+                t.polarity.to_string(),
+                t.reciprocal.clone().unwrap_or_default(),
+                t.synonyms.join(", "),
+                if t.user_defined { "config" } else { "built-in" }.to_string(),
+            ]
+        })
+        .collect();
+    print_table(
+        &["Type", "Polarity", "Reciprocal", "Synonyms", "Source"],
+        rows,
+    );
+    if ctx.ontology.is_strict() {
+        print_hint("Strict: relationships with other types are rejected.");
+    }
+    Ok(())
+}
+
+pub async fn normalize_types(ctx: &AppContext, dry_run: bool, mode: OutputMode) -> Result<()> {
+    if !dry_run {
+        ctx.backup_service.before("normalize-relationships").await?;
+    }
+    let report = ontology::normalize_relationship_types(&ctx.db, &ctx.ontology, dry_run).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+    if report.rewrites.is_empty() {
+        println!("Every relationship type is already canonical.");
+    } else {
+        let rows: Vec<Vec<String>> = report
+            .rewrites
+            .iter()
+            .map(|r| vec![r.from.clone(), r.to.clone(), r.count.to_string()])
+            .collect();
+        print_table(&["Type", "Becomes", "Edges"], rows);
+    }
+    if !report.unknown.is_empty() {
+        println!();
+        println!("Unknown types (left unchanged):");
+        let rows: Vec<Vec<String>> = report
+            .unknown
+            .iter()
+            .map(|u| {
+                vec![
+                    u.rel_type.clone(),
+                    u.count.to_string(),
+                    u.suggestions.join(", "),
+                ]
+            })
+            .collect();
+        print_table(&["Type", "Edges", "Did you mean"], rows);
+        print_hint("Define them under [relationships.types] in narra.toml, or recreate the edges with a known type.");
+    }
+    if !report.rewrites.is_empty() {
+        let edges: usize = report.rewrites.iter().map(|r| r.count).sum();
+        if dry_run {
+            print_hint(
+                "Dry run: nothing was changed. Run again without --dry-run to rewrite these.",
+            );
+        } else {
+            print_success(&format!("Normalized {} relationships", edges));
+        }
+    }
+    Ok(())
+}
+
+pub async fn create_family(
+    ctx: &AppContext,
+    parents: &[String],
+    children: &[String],
+    spouses: bool,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let parents: Vec<String> = parents.iter().map(|p| bare_key(p, "character")).collect();
+    let children: Vec<String> = children.iter().map(|c| bare_key(c, "character")).collect();
+    for key in parents.iter().chain(&children) {
+        if ctx.entity_repo.get_character(key).await?.is_none() {
+            anyhow::bail!("Character not found: character:{}", key);
+        }
     }
 
+    let edges = family::plan_family(&ctx.db, &parents, &children, spouses).await?;
+    if !dry_run {
+        for edge in &edges {
+            let data = RelationshipCreate {
+                from_character_id: edge.from.clone(),
+                to_character_id: edge.to.clone(),
+                rel_type: FAMILY.to_string(),
+                subtype: Some(edge.subtype.clone()),
+                label: None,
+            };
+            ctx.relationship_repo
+                .create_relationship(&edge.from, &edge.to, data)
+                .await?;
+        }
+    }
+
+    if mode == OutputMode::Json {
+        output_json_list(&edges);
+        return Ok(());
+    }
+    if edges.is_empty() {
+        println!("All of these family relationships already exist.");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = edges
+        .iter()
+        .map(|e| {
+            vec![
+                format!("character:{}", e.from),
+                e.subtype.clone(),
+                format!("character:{}", e.to),
+                if e.derived { "derived" } else { "" }.to_string(),
+            ]
+        })
+        .collect();
+    print_table(&["From", "Is", "Of", ""], rows);
+    if dry_run {
+        print_hint("Dry run: nothing was created. Run again without --dry-run to create these.");
+    } else {
+        print_success(&format!("Created {} family relationships", edges.len()));
+    }
+    Ok(())
+}
+
+pub async fn set_intentional(
+    ctx: &AppContext,
+    id: &str,
+    intentional: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let rel = reciprocity::set_intentional(&ctx.db, id, intentional).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&rel);
+    } else if intentional {
+        print_success(&format!(
+            "{} marked intentional; its reverse edge won't be flagged",
+            rel.id
+        ));
+    } else {
+        print_success(&format!("{} is checked for reciprocity again", rel.id));
+    }
     Ok(())
 }
 
@@ -0,0 +1,53 @@
+//! Scene dependency handlers for CLI.
+
+use anyhow::Result;
+
+use crate::cli::output::{output_json, output_json_list, print_success, print_table, OutputMode};
+use crate::init::AppContext;
+use crate::models::requires;
+
+pub async fn list_requirements(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let edges = requires::list_requirements(&ctx.db).await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&edges);
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = edges
+        .iter()
+        .map(|r| {
+            vec![
+                r.id.to_string(),
+                r.scene.to_string(),
+                r.prerequisite.to_string(),
+                r.note.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    print_table(&["ID", "Scene", "Requires", "Note"], rows);
+    Ok(())
+}
+
+pub async fn create_requirement(
+    ctx: &AppContext,
+    scene: &str,
+    prerequisite: &str,
+    note: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let edge =
+        requires::create_requirement(&ctx.db, scene, prerequisite, note.map(|n| n.to_string()))
+            .await?;
+
+    if mode == OutputMode::Json {
+        output_json(&edge);
+    } else {
+        print_success(&format!(
+            "{} requires {} ({})",
+            scene, prerequisite, edge.id
+        ));
+    }
+    Ok(())
+}
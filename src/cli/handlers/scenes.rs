@@ -0,0 +1,279 @@
+//! Scene reading-order and draft-status handlers for CLI.
+
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cli::output::schema::{DraftStatusChange, SceneReorder};
+use crate::cli::output::{
+    output_json, print_error, print_header, print_hint, print_success, print_table, OutputMode,
+};
+use crate::cli::resolve::bare_key;
+use crate::init::AppContext;
+use crate::models::scene;
+use crate::models::tag::tagged_ids;
+use crate::services::events;
+use crate::services::reorder::{
+    format_order, parse_order, ReorderOutcome, ReorderPlan, ReorderService,
+};
+
+pub async fn handle_reorder(
+    ctx: &AppContext,
+    file: Option<&Path>,
+    print: bool,
+    dry_run: bool,
+    force: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let service = ReorderService::new(ctx.db.clone());
+    if print {
+        print!("{}", format_order(&service.timeline().await?));
+        return Ok(());
+    }
+
+    let text = match file {
+        Some(path) if path == Path::new("-") => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text)?;
+            text
+        }
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path.display(), e))?,
+        None => edit_order(&format_order(&service.timeline().await?))?,
+    };
+    let entries = parse_order(&text);
+    if entries.is_empty() {
+        if mode != OutputMode::Json {
+            println!("Order is empty; nothing changed.");
+        }
+        return Ok(());
+    }
+
+    let plan = service.plan(&entries).await?;
+    let blocked = !plan.broken.is_empty() && !force;
+    if mode == OutputMode::Json {
+        let outcome = if dry_run || blocked {
+            None
+        } else {
+            Some(apply(ctx, &service, &plan).await?)
+        };
+        output_json(&SceneReorder {
+            plan,
+            applied: outcome.is_some(),
+            outcome,
+        });
+        return Ok(());
+    }
+
+    if plan.changes.is_empty() {
+        print_success("Already in this order; nothing changed");
+        return Ok(());
+    }
+
+    print_header(&format!("{} events move", plan.changes.len()));
+    let rows: Vec<Vec<String>> = plan
+        .changes
+        .iter()
+        .map(|c| {
+            vec![
+                c.title.clone(),
+                c.from.to_string(),
+                c.to.to_string(),
+                c.event_id.clone(),
+            ]
+        })
+        .collect();
+    print_table(&["Event", "From", "To", "ID"], rows);
+
+    if !plan.broken.is_empty() {
+        let rows: Vec<Vec<String>> = plan
+            .broken
+            .iter()
+            .map(|b| {
+                vec![
+                    b.scene.title.clone(),
+                    b.prerequisite.title.clone(),
+                    b.source.clone(),
+                    b.reason.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(
+            &[
+                "Scene",
+                "Would Precede Its Prerequisite",
+                "Source",
+                "Reason",
+            ],
+            rows,
+        );
+    }
+    if plan.ties > 0 {
+        print_hint(&format!(
+            "{} moved event(s) share a sequence number with a neighbour; their order falls back to creation time",
+            plan.ties
+        ));
+    }
+
+    if dry_run {
+        print_hint("Dry run; nothing was written");
+        return Ok(());
+    }
+    if blocked {
+        print_error(&format!(
+            "{} dependencies would break; nothing was written (use --force to apply anyway)",
+            plan.broken.len()
+        ));
+        return Ok(());
+    }
+
+    let outcome = apply(ctx, &service, &plan).await?;
+    print_success(&format!("Renumbered {} events", outcome.events));
+    if outcome.arc_snapshots > 0 || outcome.phases > 0 {
+        print_hint(&format!(
+            "Recomputed drift on {} arc snapshot(s) and sequence ranges of {} saved phase(s)",
+            outcome.arc_snapshots, outcome.phases
+        ));
+    }
+    Ok(())
+}
+
+async fn apply(
+    ctx: &AppContext,
+    service: &ReorderService,
+    plan: &ReorderPlan,
+) -> Result<ReorderOutcome> {
+    let outcome = service.apply(plan).await?;
+    for change in &plan.changes {
+        ctx.event_bus.emit_entity(
+            events::ENTITY_UPDATED,
+            "cli",
+            &change.event_id,
+            "event",
+            &change.title,
+        );
+    }
+    Ok(outcome)
+}
+
+/// Let the user edit `order` in $VISUAL / $EDITOR and return the result.
+fn edit_order(order: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("narra-order-{}.txt", std::process::id()));
+    std::fs::write(&path, order)?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to start editor '{}': {}", editor, e));
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    let status = status?;
+    if !status.success() {
+        anyhow::bail!("Editor exited with {}", status);
+    }
+    Ok(edited?)
+}
+
+/// Move scenes to a draft status. Scenes are picked by ID, by their current
+/// status (`--from`), by tag, or any mix of these.
+pub async fn handle_status(
+    ctx: &AppContext,
+    status: &str,
+    ids: &[String],
+    from: Option<&str>,
+    tag: Option<&str>,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    scene::validate_draft_status(status)?;
+    if ids.is_empty() && from.is_none() && tag.is_none() {
+        anyhow::bail!("Name scenes by ID, or select them with --from or --tag");
+    }
+
+    let mut selected: BTreeSet<String> = ids
+        .iter()
+        .map(|id| format!("scene:{}", bare_key(id, "scene")))
+        .collect();
+    if let Some(from) = from {
+        selected.extend(scene::scene_ids_with_status(&ctx.db, from).await?);
+    }
+    if let Some(tag) = tag {
+        selected.extend(
+            tagged_ids(&ctx.db, tag)
+                .await?
+                .into_iter()
+                .filter(|id| id.starts_with("scene:")),
+        );
+    }
+    let selected: Vec<String> = selected.into_iter().collect();
+
+    if dry_run {
+        let mut scenes = Vec::new();
+        for id in &selected {
+            match scene::get_scene(&ctx.db, &bare_key(id, "scene")).await? {
+                Some(s) if s.draft_status != status => scenes.push(s),
+                Some(_) => {}
+                None => anyhow::bail!("Scene not found: {}", id),
+            }
+        }
+        if mode == OutputMode::Json {
+            output_json(&DraftStatusChange {
+                status: status.to_string(),
+                applied: false,
+                scenes,
+            });
+            return Ok(());
+        }
+        if scenes.is_empty() {
+            print_success(&format!("All selected scenes are already {}", status));
+            return Ok(());
+        }
+        print_header(&format!("{} scenes would move to {}", scenes.len(), status));
+        let rows: Vec<Vec<String>> = scenes
+            .iter()
+            .map(|s| vec![s.title.clone(), s.draft_status.clone(), s.id.to_string()])
+            .collect();
+        print_table(&["Scene", "Current", "ID"], rows);
+        print_hint("Dry run; nothing was written");
+        return Ok(());
+    }
+
+    let changed = scene::set_draft_status(&ctx.db, &selected, status).await?;
+    for s in &changed {
+        ctx.event_bus.emit_entity(
+            events::ENTITY_UPDATED,
+            "cli",
+            &s.id.to_string(),
+            "scene",
+            &s.title,
+        );
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&DraftStatusChange {
+            status: status.to_string(),
+            applied: true,
+            scenes: changed,
+        });
+        return Ok(());
+    }
+    if changed.is_empty() {
+        print_success(&format!("All selected scenes are already {}", status));
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = changed
+        .iter()
+        .map(|s| vec![s.title.clone(), s.id.to_string()])
+        .collect();
+    print_table(&["Scene", "ID"], rows);
+    print_success(&format!("Moved {} scenes to {}", changed.len(), status));
+    Ok(())
+}
@@ -1,4 +1,4 @@
-//! Session management command handlers: context, pin, unpin.
+//! Session management command handlers: context, pin, unpin, log, history.
 
 use anyhow::Result;
 use serde::Serialize;
@@ -8,10 +8,11 @@ use crate::cli::output::{
 };
 use crate::cli::resolve::resolve_single;
 use crate::init::AppContext;
-use crate::session::generate_startup_context;
+use crate::models::event::get_event;
+use crate::session::{generate_startup_context, session_stats, FocusEntity, PinPriority};
 
 pub async fn handle_context(ctx: &AppContext, mode: OutputMode) -> Result<()> {
-    let info = generate_startup_context(&ctx.session_manager, &ctx.db)
+    let info = generate_startup_context(&ctx.session_manager, &ctx.db, ctx.token_counter.as_ref())
         .await
         .map_err(|e| anyhow::anyhow!("Failed to generate session context: {}", e))?;
 
@@ -26,6 +27,18 @@ pub async fn handle_context(ctx: &AppContext, mode: OutputMode) -> Result<()> {
     }
     println!("  {}", info.summary);
 
+    // Always-included entities, from project config
+    if !info.always_included.is_empty() {
+        println!();
+        println!("Always Included:");
+        let rows: Vec<Vec<String>> = info
+            .always_included
+            .iter()
+            .map(|e| vec![e.id.clone(), e.name.clone(), e.entity_type.clone()])
+            .collect();
+        print_table(&["ID", "Name", "Type"], rows);
+    }
+
     // Hot entities
     if !info.hot_entities.is_empty() {
         println!();
@@ -38,14 +51,36 @@ pub async fn handle_context(ctx: &AppContext, mode: OutputMode) -> Result<()> {
         print_table(&["ID", "Name", "Type"], rows);
     }
 
-    // Pinned entities
-    let pinned = ctx.session_manager.get_pinned().await;
-    if !pinned.is_empty() {
+    // Pinned entities, highest priority first
+    if !info.pinned.is_empty() {
         println!();
         println!("Pinned Entities:");
-        for id in &pinned {
-            println!("  - {}", id);
-        }
+        let rows: Vec<Vec<String>> = info
+            .pinned
+            .iter()
+            .map(|p| {
+                vec![
+                    p.id.clone(),
+                    p.name.clone().unwrap_or_default(),
+                    p.priority.to_string(),
+                    p.until.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(&["ID", "Name", "Priority", "Until"], rows);
+    }
+    if !info.expired_pins.is_empty() {
+        print_hint(&format!(
+            "Unpinned (moved past their event): {}",
+            info.expired_pins.join(", ")
+        ));
+    }
+    if !info.suggested_pins.is_empty() {
+        let ids: Vec<&str> = info.suggested_pins.iter().map(|e| e.id.as_str()).collect();
+        print_hint(&format!(
+            "Often used, worth pinning: {} (narra session pin <id>)",
+            ids.join(", ")
+        ));
     }
 
     // Pending decisions
@@ -67,6 +102,28 @@ pub async fn handle_context(ctx: &AppContext, mode: OutputMode) -> Result<()> {
         print_table(&["ID", "Description", "Age", "Affected"], rows);
     }
 
+    // Journal
+    if !info.recent_journal.is_empty() {
+        println!();
+        println!("Journal:");
+        for entry in &info.recent_journal {
+            println!("  - {} ({})", entry.message, entry.age);
+        }
+    }
+
+    // Open todos
+    if !info.open_todos.is_empty() {
+        println!();
+        println!("Open Todos:");
+        let rows: Vec<Vec<String>> = info
+            .open_todos
+            .iter()
+            .map(|t| vec![t.id.clone(), t.title.clone(), t.age.clone()])
+            .collect();
+        print_table(&["ID", "Title", "Age"], rows);
+        print_hint("Mark one done with: narra note resolve <id>");
+    }
+
     // World overview
     if let Some(overview) = &info.world_overview {
         println!();
@@ -77,17 +134,56 @@ pub async fn handle_context(ctx: &AppContext, mode: OutputMode) -> Result<()> {
         print_kv("Relationships", &overview.relationship_count.to_string());
     }
 
+    // Neglected entities
+    if !info.neglected.is_empty() {
+        let ids: Vec<&str> = info.neglected.iter().map(|e| e.id.as_str()).collect();
+        print_hint(&format!(
+            "Cooled off since you last worked on them: {}",
+            ids.join(", ")
+        ));
+    }
+    if let Some(run) = &info.scheduled_run {
+        print_hint(&format!(
+            "Background annotation ({}, {}): {} annotated, {} unchanged, {} errors, {} arc snapshots",
+            run.trigger, run.age, run.annotated, run.unchanged, run.errors, run.arc_snapshots
+        ));
+    }
+    if info.trimmed > 0 {
+        print_hint(&format!(
+            "{} item(s) left out to stay under startup.token_ceiling",
+            info.trimmed
+        ));
+    }
+
     Ok(())
 }
 
 pub async fn handle_pin(
     ctx: &AppContext,
     entity: &str,
+    priority: &str,
+    until: Option<&str>,
     mode: OutputMode,
     no_semantic: bool,
 ) -> Result<()> {
+    let priority: PinPriority = priority.parse()?;
     let entity_id = resolve_single(ctx, entity, no_semantic).await?;
-    ctx.session_manager.pin_entity(&entity_id).await;
+    let until = match until {
+        Some(event) => {
+            let event_id = resolve_single(ctx, event, no_semantic).await?;
+            let Some(key) = event_id.strip_prefix("event:") else {
+                anyhow::bail!("--until must be an event, got {}", event_id);
+            };
+            if get_event(&ctx.db, key).await?.is_none() {
+                anyhow::bail!("Event not found: {}", event_id);
+            }
+            Some(event_id)
+        }
+        None => None,
+    };
+    ctx.session_manager
+        .pin_entity_with(&entity_id, priority, until.clone())
+        .await;
     ctx.session_manager
         .save()
         .await
@@ -100,17 +196,26 @@ pub async fn handle_pin(
         struct PinResult {
             status: String,
             entity_id: String,
+            priority: PinPriority,
+            until: Option<String>,
             total_pinned: usize,
         }
         output_json(&PinResult {
             status: "pinned".to_string(),
             entity_id: entity_id.clone(),
+            priority,
+            until,
             total_pinned: pinned.len(),
         });
     } else {
+        let until = until
+            .map(|event| format!(", until {}", event))
+            .unwrap_or_default();
         print_success(&format!(
-            "Pinned '{}' ({} total pinned)",
+            "Pinned '{}' ({} priority{}; {} total pinned)",
             entity_id,
+            priority,
+            until,
             pinned.len()
         ));
     }
@@ -158,3 +263,133 @@ pub async fn handle_unpin(
 
     Ok(())
 }
+
+/// Entities a journal entry touches when none are given.
+const DEFAULT_TOUCHED: usize = 5;
+
+pub async fn handle_log(
+    ctx: &AppContext,
+    message: &str,
+    entities: &[String],
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    let message = message.trim();
+    if message.is_empty() {
+        anyhow::bail!("Journal entry cannot be empty");
+    }
+
+    let touched = if entities.is_empty() {
+        ctx.session_manager.get_recent(DEFAULT_TOUCHED).await
+    } else {
+        let mut ids = Vec::with_capacity(entities.len());
+        for entity in entities {
+            ids.push(resolve_single(ctx, entity, no_semantic).await?);
+        }
+        ids
+    };
+
+    let entry = ctx.session_manager.log_entry(message, touched).await;
+    ctx.session_manager
+        .save()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to save session: {}", e))?;
+
+    if mode == OutputMode::Json {
+        output_json(&entry);
+    } else {
+        print_success(&format!("Logged: {}", entry.message));
+        if !entry.entities.is_empty() {
+            print_hint(&format!("Touched: {}", entry.entities.join(", ")));
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn handle_history(ctx: &AppContext, limit: usize, mode: OutputMode) -> Result<()> {
+    let entries = ctx.session_manager.get_journal(limit).await;
+
+    if mode == OutputMode::Json {
+        output_json(&entries);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No journal entries yet.");
+        print_hint("Log one with: narra session log \"drafted ch 7 confrontation\"");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|e| {
+            vec![
+                e.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                e.message.clone(),
+                e.entities.join(", "),
+            ]
+        })
+        .collect();
+    print_table(&["When", "Entry", "Touched"], rows);
+    Ok(())
+}
+
+pub async fn handle_stats(ctx: &AppContext, limit: usize, mode: OutputMode) -> Result<()> {
+    let stats = session_stats(&ctx.session_manager, &ctx.db, limit)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to compute session stats: {}", e))?;
+
+    if mode == OutputMode::Json {
+        output_json(&stats);
+        return Ok(());
+    }
+
+    print_header("Session Stats");
+    print_kv("Sessions", &stats.session_count.to_string());
+    if let Some(ago) = &stats.last_session_ago {
+        print_kv("Last session", ago);
+    }
+    print_kv("Journal entries", &stats.journal_entries.to_string());
+    print_kv("Entities touched", &stats.tracked_entities.to_string());
+
+    let focus_rows = |entities: &[FocusEntity]| -> Vec<Vec<String>> {
+        entities
+            .iter()
+            .map(|e| {
+                vec![
+                    e.id.clone(),
+                    e.name.clone().unwrap_or_default(),
+                    format!("{:.2}", e.heat),
+                    e.accesses.to_string(),
+                    e.last_accessed.clone().unwrap_or_default(),
+                ]
+            })
+            .collect()
+    };
+    let headers = ["ID", "Name", "Heat", "Accesses", "Last"];
+
+    println!();
+    println!("Focus:");
+    if stats.focus.is_empty() {
+        println!("  Nothing hot right now.");
+    } else {
+        print_table(&headers, focus_rows(&stats.focus));
+    }
+    if !stats.neglected.is_empty() {
+        println!();
+        println!("Neglected (cooled off):");
+        print_table(&headers, focus_rows(&stats.neglected));
+    }
+    if !stats.untouched.is_empty() {
+        println!();
+        println!("Untouched:");
+        let rows: Vec<Vec<String>> = stats
+            .untouched
+            .iter()
+            .map(|e| vec![e.id.clone(), e.name.clone(), e.entity_type.clone()])
+            .collect();
+        print_table(&["ID", "Name", "Type"], rows);
+    }
+    Ok(())
+}
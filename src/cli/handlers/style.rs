@@ -0,0 +1,157 @@
+//! Style sheet and lint handlers: `narra style ...`, `narra lint`.
+
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cli::output::{
+    output_json, output_json_list, print_hint, print_success, print_table, OutputMode,
+};
+use crate::cli::ExitStatus;
+use crate::init::AppContext;
+use crate::models::style::{self, StyleRuleCreate, StyleRuleKind};
+use crate::services::events;
+use crate::services::style::lint_text;
+
+pub async fn handle_add(
+    ctx: &AppContext,
+    kind: &str,
+    preferred: &str,
+    avoid: &[String],
+    note: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let kind: StyleRuleKind = kind.parse()?;
+    let data = StyleRuleCreate {
+        kind,
+        preferred: preferred.to_string(),
+        avoid: avoid.iter().filter(|a| !a.is_empty()).cloned().collect(),
+        note: note.map(String::from),
+    };
+    let created = style::create_style_rule(&ctx.db, data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &created.id.to_string(),
+        "style_rule",
+        &created.preferred,
+    );
+
+    if mode == OutputMode::Json {
+        output_json(&created);
+    } else {
+        print_success(&format!(
+            "Added {} rule '{}' ({})",
+            created.kind, created.preferred, created.id
+        ));
+    }
+    Ok(())
+}
+
+pub async fn handle_list(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let rules = style::list_style_rules(&ctx.db).await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&rules);
+        return Ok(());
+    }
+
+    if rules.is_empty() {
+        println!("The style sheet is empty. Add a rule with 'narra style add spelling grey --avoid gray'.");
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = rules
+        .iter()
+        .map(|r| {
+            vec![
+                r.id.to_string(),
+                r.kind.to_string(),
+                r.preferred.clone(),
+                if r.avoid.is_empty() {
+                    "-".to_string()
+                } else {
+                    r.avoid.join(", ")
+                },
+                r.note.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(&["ID", "Kind", "Preferred", "Avoid", "Note"], rows);
+    Ok(())
+}
+
+pub async fn handle_remove(ctx: &AppContext, id: &str, mode: OutputMode) -> Result<()> {
+    let key = id.strip_prefix("style_rule:").unwrap_or(id);
+    let removed = style::delete_style_rule(&ctx.db, key)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Style rule '{}' not found", id))?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_DELETED,
+        "cli",
+        &removed.id.to_string(),
+        "style_rule",
+        &removed.preferred,
+    );
+
+    if mode == OutputMode::Json {
+        output_json(&removed);
+    } else {
+        print_success(&format!(
+            "Removed {} rule '{}'",
+            removed.kind, removed.preferred
+        ));
+    }
+    Ok(())
+}
+
+/// Check a manuscript file (or `-` for stdin) against the style sheet.
+/// Exits with code 2 when anything is flagged.
+pub async fn handle_lint(ctx: &AppContext, file: &Path, mode: OutputMode) -> Result<()> {
+    let text = if file == Path::new("-") {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(file)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", file.display(), e))?
+    };
+    let rules = style::list_style_rules(&ctx.db).await?;
+    let findings = lint_text(&text, &rules);
+
+    if mode == OutputMode::Json {
+        output_json_list(&findings);
+    } else if rules.is_empty() {
+        println!("The style sheet is empty; nothing to check.");
+        print_hint(
+            "Add rules with: narra style add <spelling|capitalization|punctuation> <preferred>",
+        );
+    } else if findings.is_empty() {
+        print_success(&format!(
+            "{}: no style issues ({} rules)",
+            file.display(),
+            rules.len()
+        ));
+    } else {
+        let rows: Vec<Vec<String>> = findings
+            .iter()
+            .map(|f| {
+                vec![
+                    format!("{}:{}", f.line, f.column),
+                    f.kind.to_string(),
+                    f.found.clone(),
+                    f.suggestion.clone(),
+                    f.note.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(&["Line:Col", "Rule", "Found", "Use", "Note"], rows);
+        println!("{} style issues in {}", findings.len(), file.display());
+    }
+
+    if findings.is_empty() {
+        Ok(())
+    } else {
+        Err(ExitStatus(2).into())
+    }
+}
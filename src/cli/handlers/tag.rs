@@ -0,0 +1,94 @@
+//! Tag handlers for CLI: add, remove, list.
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cli::output::{
+    output_json, output_json_list, print_hint, print_success, print_table, OutputMode,
+};
+use crate::init::AppContext;
+use crate::models::tag;
+
+#[derive(Serialize)]
+struct EntityTags<'a> {
+    entity_id: &'a str,
+    tags: Vec<String>,
+}
+
+fn print_entity_tags(entity_id: &str, tags: Vec<String>, mode: OutputMode) {
+    if mode == OutputMode::Json {
+        output_json(&EntityTags { entity_id, tags });
+    } else if tags.is_empty() {
+        println!("{} has no tags", entity_id);
+    } else {
+        println!("{}: {}", entity_id, tags.join(", "));
+    }
+}
+
+pub async fn handle_add(
+    ctx: &AppContext,
+    entity_id: &str,
+    tags: &[String],
+    mode: OutputMode,
+) -> Result<()> {
+    let tags = tag::add_tags(&ctx.db, entity_id, tags).await?;
+    if mode != OutputMode::Json {
+        print_success(&format!("Tagged {}", entity_id));
+    }
+    print_entity_tags(entity_id, tags, mode);
+    Ok(())
+}
+
+pub async fn handle_remove(
+    ctx: &AppContext,
+    entity_id: &str,
+    tags: &[String],
+    mode: OutputMode,
+) -> Result<()> {
+    let tags = tag::remove_tags(&ctx.db, entity_id, tags).await?;
+    if mode != OutputMode::Json {
+        print_success(&format!("Untagged {}", entity_id));
+    }
+    print_entity_tags(entity_id, tags, mode);
+    Ok(())
+}
+
+pub async fn handle_list(
+    ctx: &AppContext,
+    entity_id: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    if let Some(entity_id) = entity_id {
+        let tags = tag::get_tags(&ctx.db, entity_id).await?;
+        print_entity_tags(entity_id, tags, mode);
+        return Ok(());
+    }
+
+    let counts = tag::tag_counts(&ctx.db).await?;
+    if mode == OutputMode::Json {
+        output_json_list(&counts);
+        return Ok(());
+    }
+
+    if counts.is_empty() {
+        println!("No tags yet.");
+        print_hint("Tag a scene with 'narra tag add scene:<id> action'");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = counts
+        .iter()
+        .map(|c| {
+            vec![
+                c.tag.clone(),
+                c.scenes.to_string(),
+                c.events.to_string(),
+                c.notes.to_string(),
+                c.total().to_string(),
+            ]
+        })
+        .collect();
+    print_table(&["Tag", "Scenes", "Events", "Notes", "Total"], rows);
+    print_hint("Filter with 'narra list scene --tag <tag>' or 'narra find <query> --tag <tag>'");
+    Ok(())
+}
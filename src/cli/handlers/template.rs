@@ -0,0 +1,111 @@
+//! Output template handlers: `narra template ...`.
+//!
+//! Templates are plain files, so these run without an `AppContext`.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cli::output::schema::TemplateRow;
+use crate::cli::output::{
+    output_json, output_json_list, print_error, print_hint, print_success, print_table, OutputMode,
+};
+use crate::services::templates::{self, TemplateSource, Templates};
+
+fn row(name: &str, used_by: &str, source: &TemplateSource) -> TemplateRow {
+    TemplateRow {
+        name: name.to_string(),
+        used_by: used_by.to_string(),
+        source: source.label().to_string(),
+        path: source.path().map(|p| p.display().to_string()),
+    }
+}
+
+pub fn handle_template_list(data_path: &Path, mode: OutputMode) -> Result<()> {
+    let rows: Vec<TemplateRow> = templates::list(data_path)
+        .iter()
+        .map(|t| row(t.name, t.used_by, &t.source))
+        .collect();
+
+    if mode == OutputMode::Json {
+        output_json_list(&rows);
+        return Ok(());
+    }
+    let table: Vec<Vec<String>> = rows
+        .iter()
+        .map(|r| {
+            vec![
+                r.name.clone(),
+                r.used_by.clone(),
+                r.path.clone().unwrap_or_else(|| r.source.clone()),
+            ]
+        })
+        .collect();
+    print_table(&["Template", "Used By", "Source"], table);
+    print_hint("Customize one with: narra template edit <name>");
+    Ok(())
+}
+
+pub fn handle_template_edit(
+    data_path: &Path,
+    name: &str,
+    user: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let builtin = templates::builtin(name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown template '{}'. See: narra template list", name))?;
+    let dir = if user {
+        templates::user_dir().ok_or_else(|| anyhow::anyhow!("No home directory"))?
+    } else {
+        templates::world_dir(data_path)
+    };
+    let path = dir.join(format!("{}.{}", name, templates::TEMPLATE_EXTENSION));
+
+    // Start from whatever renders today, so editing never silently resets a user override
+    if !path.exists() {
+        let current = match templates::resolve(data_path, name).path() {
+            Some(existing) => std::fs::read_to_string(existing)?,
+            None => builtin.source.to_string(),
+        };
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&path, current)?;
+    }
+
+    let source = if user {
+        TemplateSource::User(path.clone())
+    } else {
+        TemplateSource::World(path.clone())
+    };
+    if mode == OutputMode::Json {
+        // Scripts get the path to edit themselves
+        output_json(&row(builtin.name, builtin.used_by, &source));
+        return Ok(());
+    }
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to start editor '{}': {}", editor, e))?;
+    if !status.success() {
+        anyhow::bail!("Editor exited with {}", status);
+    }
+
+    match Templates::load(data_path) {
+        Ok(_) => print_success(&format!("Saved {}", path.display())),
+        Err(e) => {
+            print_error(&e.to_string());
+            print_hint(&format!(
+                "Fix it with 'narra template edit {}', or delete {} to use the built-in",
+                name,
+                path.display()
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Glossary term handlers for CLI.
+
+use anyhow::Result;
+
+use crate::cli::output::{
+    output_json, output_json_list, print_hint, print_success, print_table, OutputMode,
+};
+use crate::init::AppContext;
+use crate::models::term;
+use crate::models::TermCreate;
+use crate::services::events;
+
+pub async fn list_terms(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let terms = term::list_terms(&ctx.db).await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&terms);
+        return Ok(());
+    }
+
+    if terms.is_empty() {
+        println!(
+            "No terms. Define one with 'narra create term --name <name> --definition <text>'."
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = terms
+        .iter()
+        .map(|t| {
+            vec![
+                t.id.to_string(),
+                t.name.clone(),
+                t.kind.clone().unwrap_or_else(|| "-".to_string()),
+                if t.aliases.is_empty() {
+                    "-".to_string()
+                } else {
+                    t.aliases.join(", ")
+                },
+                t.first_use
+                    .as_ref()
+                    .map(|e| e.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                t.definition.clone(),
+            ]
+        })
+        .collect();
+
+    print_table(
+        &["ID", "Name", "Kind", "Aliases", "First Use", "Definition"],
+        rows,
+    );
+    Ok(())
+}
+
+pub async fn create_term(
+    ctx: &AppContext,
+    name: &str,
+    definition: &str,
+    kind: Option<&str>,
+    aliases: &[String],
+    first_use: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let data = TermCreate {
+        name: name.to_string(),
+        definition: definition.to_string(),
+        kind: kind.map(|k| k.to_string()),
+        aliases: aliases
+            .iter()
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect(),
+        first_use: first_use.map(term::first_use_ref).transpose()?,
+    };
+
+    let created = term::create_term(&ctx.db, data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &created.id.to_string(),
+        "term",
+        &created.name,
+    );
+
+    // Embed now so the term shows up in semantic search without a backfill
+    let mut embed_error = None;
+    if ctx.embedding_service.is_available() {
+        if let Err(e) = ctx
+            .staleness_manager
+            .regenerate_embedding(&created.id.to_string(), None)
+            .await
+        {
+            embed_error = Some(e);
+        }
+    }
+
+    if mode == OutputMode::Json {
+        output_json(&created);
+    } else {
+        print_success(&format!("Created term '{}' ({})", created.name, created.id));
+        if let Some(e) = embed_error {
+            print_hint(&format!(
+                "Could not embed term ({}); run narra world backfill",
+                e
+            ));
+        }
+    }
+    Ok(())
+}
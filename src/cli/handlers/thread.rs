@@ -0,0 +1,107 @@
+//! Thread registry handlers for CLI.
+
+use anyhow::Result;
+
+use crate::cli::output::{output_json, output_json_list, print_success, print_table, OutputMode};
+use crate::init::AppContext;
+use crate::models::thread;
+use crate::models::ThreadCreate;
+use crate::services::events;
+
+pub async fn list_threads(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let threads = thread::list_threads(&ctx.db, None).await?;
+
+    if mode == OutputMode::Json {
+        output_json_list(&threads);
+        return Ok(());
+    }
+
+    if threads.is_empty() {
+        println!("No threads. Register one with 'narra create thread --name <name>'.");
+        return Ok(());
+    }
+
+    let anchor = |a: &Option<surrealdb::RecordId>| {
+        a.as_ref()
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let rows: Vec<Vec<String>> = threads
+        .iter()
+        .map(|t| {
+            vec![
+                t.id.to_string(),
+                t.name.clone(),
+                t.status.clone(),
+                anchor(&t.setup),
+                anchor(&t.payoff),
+                t.elements.len().to_string(),
+                t.resolve_by_phase
+                    .clone()
+                    .or_else(|| t.resolve_by.map(|s| s.to_string()))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    print_table(
+        &[
+            "ID",
+            "Name",
+            "Status",
+            "Setup",
+            "Payoff",
+            "Elements",
+            "Resolve By",
+        ],
+        rows,
+    );
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_thread(
+    ctx: &AppContext,
+    name: &str,
+    description: Option<&str>,
+    status: &str,
+    setup: Option<&str>,
+    payoff: Option<&str>,
+    elements: &[String],
+    resolve_by: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let (resolve_by, resolve_by_phase) = resolve_by.map(thread::parse_deadline).unwrap_or_default();
+    let data = ThreadCreate {
+        name: name.to_string(),
+        description: description.map(|d| d.to_string()),
+        status: status.to_string(),
+        setup: setup.map(thread::anchor_ref).transpose()?,
+        payoff: payoff.map(thread::anchor_ref).transpose()?,
+        elements: elements
+            .iter()
+            .map(|e| thread::element_ref(e))
+            .collect::<Result<_, _>>()?,
+        resolve_by,
+        resolve_by_phase,
+    };
+
+    let created = thread::create_thread(&ctx.db, data).await?;
+    ctx.event_bus.emit_entity(
+        events::ENTITY_CREATED,
+        "cli",
+        &created.id.to_string(),
+        "thread",
+        &created.name,
+    );
+
+    if mode == OutputMode::Json {
+        output_json(&created);
+    } else {
+        print_success(&format!(
+            "Created thread '{}' ({})",
+            created.name, created.id
+        ));
+    }
+    Ok(())
+}
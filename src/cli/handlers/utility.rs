@@ -1,17 +1,72 @@
-//! Utility command handlers: update and delete.
+//! Utility command handlers: update, rename and delete.
 
 use anyhow::Result;
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
 
-use crate::cli::output::{print_error, print_success, OutputMode};
-use crate::cli::resolve::{bare_key, entity_type_from_id};
+use crate::cli::output::schema::{DeleteResult, UpdateResult};
+use crate::cli::output::{
+    output_json, print_error, print_header, print_hint, print_success, OutputMode,
+};
+use crate::cli::resolve::{bare_key, entity_type_from_id, resolve_single};
 use crate::init::AppContext;
+use crate::models::scene::{remove_event_involvement, set_event_involvement, InvolvementCreate};
 use crate::repository::EntityRepository;
+use crate::services::events;
+use crate::services::impact::final_scenes_referencing;
+use crate::services::note_links;
+use crate::services::rename::{RenamePlan, RenameService};
+
+// =============================================================================
+// Revision lock
+// =============================================================================
+
+/// Refuse to change an entity that scenes marked final reference, unless
+/// `--force` is given.
+async fn check_revision_lock(
+    ctx: &AppContext,
+    entity_id: &str,
+    action: &str,
+    force: bool,
+) -> Result<()> {
+    if force {
+        return Ok(());
+    }
+    let locked = final_scenes_referencing(&ctx.db, entity_id).await?;
+    if locked.is_empty() {
+        return Ok(());
+    }
+    let scenes: Vec<String> = locked
+        .iter()
+        .map(|s| format!("'{}' ({})", s.title, s.id))
+        .collect();
+    anyhow::bail!(
+        "{} {} would change {} scene(s) marked final: {}. Use --force to proceed.",
+        action,
+        entity_id,
+        locked.len(),
+        scenes.join(", ")
+    )
+}
 
 // =============================================================================
 // Update (with optional --link / --unlink)
 // =============================================================================
 
+/// A change to who took part in an event (`--add-participant` /
+/// `--remove-participant`).
+pub enum ParticipantUpdate<'a> {
+    Add {
+        character: &'a str,
+        role: Option<&'a str>,
+        impact: Option<&'a str>,
+    },
+    Remove {
+        character: &'a str,
+    },
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_update(
     ctx: &AppContext,
     entity_id: &str,
@@ -19,7 +74,10 @@ pub async fn handle_update(
     set_pairs: &[(String, String)],
     link: Option<&str>,
     unlink: Option<&str>,
+    participant: Option<ParticipantUpdate<'_>>,
+    force: bool,
     mode: OutputMode,
+    no_semantic: bool,
 ) -> Result<()> {
     let entity_type = entity_type_from_id(entity_id).ok_or_else(|| {
         anyhow::anyhow!(
@@ -28,6 +86,7 @@ pub async fn handle_update(
         )
     })?;
     let key = bare_key(entity_id, entity_type);
+    check_revision_lock(ctx, entity_id, "Updating", force).await?;
 
     // Handle field updates if provided
     let has_field_updates = fields_json.is_some() || !set_pairs.is_empty();
@@ -42,6 +101,7 @@ pub async fn handle_update(
             }
             serde_json::Value::Object(map)
         };
+        let renames = note_links::renames(&fields);
 
         let record_ref = surrealdb::RecordId::from((entity_type, key.as_str()));
         let mut response = ctx
@@ -65,13 +125,32 @@ pub async fn handle_update(
         match updated {
             Some(u) => {
                 let display_name = u.name.or(u.title).unwrap_or_else(|| key.clone());
+                ctx.event_bus.emit_entity(
+                    events::ENTITY_UPDATED,
+                    "cli",
+                    &u.id.to_string(),
+                    entity_type,
+                    &display_name,
+                );
+                let links = if entity_type == "note" {
+                    Some(note_links::sync_note_links(&ctx.db, &key).await?)
+                } else {
+                    if renames {
+                        note_links::resync_all_note_links(&ctx.db).await?;
+                    }
+                    None
+                };
                 if mode == OutputMode::Json {
-                    println!(
-                        r#"{{"status": "ok", "id": "{}", "name": "{}"}}"#,
-                        u.id, display_name
-                    );
+                    output_json(&UpdateResult {
+                        status: "ok".to_string(),
+                        id: u.id.to_string(),
+                        name: display_name,
+                    });
                 } else {
                     print_success(&format!("Updated {} '{}'", entity_type, display_name));
+                    if let Some(links) = &links {
+                        crate::cli::handlers::note::print_link_sync(links);
+                    }
                 }
             }
             None => {
@@ -103,9 +182,15 @@ pub async fn handle_update(
                     print_success(&format!("Attached {} to {}", entity_id, link_target));
                 }
             }
+            "thread" => {
+                crate::models::thread::add_thread_element(&ctx.db, &key, link_target).await?;
+                if mode != OutputMode::Json {
+                    print_success(&format!("Added {} to {}", link_target, entity_id));
+                }
+            }
             _ => {
                 anyhow::bail!(
-                    "--link is only supported for universe_fact and note entities, got '{}'",
+                    "--link is only supported for universe_fact, note and thread entities, got '{}'",
                     entity_type
                 );
             }
@@ -127,23 +212,235 @@ pub async fn handle_update(
                     print_success(&format!("Detached {} from {}", entity_id, unlink_target));
                 }
             }
+            "thread" => {
+                crate::models::thread::remove_thread_element(&ctx.db, &key, unlink_target).await?;
+                if mode != OutputMode::Json {
+                    print_success(&format!("Removed {} from {}", unlink_target, entity_id));
+                }
+            }
             _ => {
                 anyhow::bail!(
-                    "--unlink is only supported for universe_fact and note entities, got '{}'",
+                    "--unlink is only supported for universe_fact, note and thread entities, got '{}'",
                     entity_type
                 );
             }
         }
     }
 
+    // Handle --add-participant / --remove-participant
+    if let Some(participant) = &participant {
+        if entity_type != "event" {
+            anyhow::bail!(
+                "Participants can only be managed on events, got '{}'",
+                entity_type
+            );
+        }
+        ctx.access.check("involved_in")?;
+        let character = match participant {
+            ParticipantUpdate::Add { character, .. } | ParticipantUpdate::Remove { character } => {
+                resolve_single(ctx, character, no_semantic).await?
+            }
+        };
+        if entity_type_from_id(&character) != Some("character") {
+            anyhow::bail!("Participants must be characters, got '{}'", character);
+        }
+        let character_key = bare_key(&character, "character");
+        match participant {
+            ParticipantUpdate::Add { role, impact, .. } => {
+                let involvement = set_event_involvement(
+                    &ctx.db,
+                    InvolvementCreate {
+                        character_id: character_key,
+                        event_id: key.clone(),
+                        role: role.map(str::to_string),
+                        impact: impact.map(str::to_string),
+                    },
+                )
+                .await?;
+                if mode != OutputMode::Json {
+                    let role = involvement
+                        .role
+                        .map(|r| format!(" as {}", r))
+                        .unwrap_or_default();
+                    print_success(&format!("Added {} to {}{}", character, entity_id, role));
+                }
+            }
+            ParticipantUpdate::Remove { .. } => {
+                let removed = remove_event_involvement(&ctx.db, &character_key, &key).await?;
+                if removed.is_empty() {
+                    anyhow::bail!("{} is not a participant in {}", character, entity_id);
+                }
+                if mode != OutputMode::Json {
+                    print_success(&format!("Removed {} from {}", character, entity_id));
+                }
+            }
+        }
+        ctx.event_bus.emit_entity(
+            events::ENTITY_UPDATED,
+            "cli",
+            &format!("event:{}", key),
+            "event",
+            &key,
+        );
+    }
+
     // Ensure at least one operation was requested
-    if !has_field_updates && link.is_none() && unlink.is_none() {
-        anyhow::bail!("Provide --fields, --set, --link, or --unlink to specify update operation");
+    if !has_field_updates && link.is_none() && unlink.is_none() && participant.is_none() {
+        anyhow::bail!(
+            "Provide --fields, --set, --link, --unlink, --add-participant, or --remove-participant to specify update operation"
+        );
     }
 
     Ok(())
 }
 
+// =============================================================================
+// Rename (with the old name replaced in other texts)
+// =============================================================================
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_rename(
+    ctx: &AppContext,
+    entity: &str,
+    new_name: &str,
+    replace: bool,
+    keep_text: bool,
+    dry_run: bool,
+    force: bool,
+    mode: OutputMode,
+    no_semantic: bool,
+) -> Result<()> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let entity_id = resolve_single(ctx, entity, no_semantic).await?;
+    let service = RenameService::new(ctx.db.clone());
+    let plan = service.plan(&entity_id, new_name).await?;
+
+    if dry_run {
+        if mode == OutputMode::Json {
+            output_json(&plan);
+        } else {
+            print_rename_plan(&plan);
+        }
+        return Ok(());
+    }
+    check_revision_lock(ctx, &plan.entity_id, "Renaming", force).await?;
+
+    let replace_text = if replace {
+        true
+    } else if keep_text
+        || plan.occurrences.is_empty()
+        || mode == OutputMode::Json
+        || !std::io::stdin().is_terminal()
+    {
+        false
+    } else {
+        print_rename_plan(&plan);
+        print!(
+            "Replace {} occurrence(s) of '{}' in {} text(s)? [y/N]: ",
+            plan.total(),
+            plan.old_name,
+            plan.occurrences.len()
+        );
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().lock().read_line(&mut answer)?;
+        matches!(answer.trim(), "y" | "yes")
+    };
+
+    let outcome = service.apply(plan, replace_text, force).await?;
+    let plan = &outcome.plan;
+    let entity_type = entity_type_from_id(&plan.entity_id).unwrap_or_default();
+    ctx.event_bus.emit_entity(
+        events::ENTITY_UPDATED,
+        "cli",
+        &plan.entity_id,
+        entity_type,
+        &plan.new_name,
+    );
+    for id in &outcome.rewritten {
+        if *id == plan.entity_id {
+            continue;
+        }
+        let label = plan
+            .occurrences
+            .iter()
+            .find(|o| &o.entity_id == id)
+            .map_or(id.as_str(), |o| o.label.as_str());
+        ctx.event_bus.emit_entity(
+            events::ENTITY_UPDATED,
+            "cli",
+            id,
+            entity_type_from_id(id).unwrap_or_default(),
+            label,
+        );
+    }
+    note_links::resync_all_note_links(&ctx.db).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&outcome);
+        return Ok(());
+    }
+    print_success(&format!(
+        "Renamed {} '{}' to '{}'",
+        entity_type, plan.old_name, plan.new_name
+    ));
+    if let Some(alias) = &plan.alias {
+        print_hint(&format!("'{}' is kept as an alias", alias));
+    }
+    if !outcome.rewritten.is_empty() {
+        print_success(&format!(
+            "Replaced '{}' in {} text(s)",
+            plan.old_name,
+            outcome.rewritten.len()
+        ));
+    }
+    if !outcome.skipped_final.is_empty() {
+        print_hint(&format!(
+            "Left {} scene(s) marked final unchanged: {}. Use --force to rewrite them.",
+            outcome.skipped_final.len(),
+            outcome.skipped_final.join(", ")
+        ));
+    } else if outcome.rewritten.is_empty() && !plan.occurrences.is_empty() {
+        print_hint(&format!(
+            "'{}' still appears in {} text(s); list them with: narra find \"{}\" --keyword-only",
+            plan.old_name,
+            plan.occurrences.len(),
+            plan.old_name
+        ));
+    }
+    Ok(())
+}
+
+fn print_rename_plan(plan: &RenamePlan) {
+    print_header(&format!(
+        "Rename {}: '{}' -> '{}'",
+        plan.entity_id, plan.old_name, plan.new_name
+    ));
+    if plan.occurrences.is_empty() {
+        println!("  No other text names '{}'.", plan.old_name);
+        return;
+    }
+    for occurrence in &plan.occurrences {
+        println!(
+            "  {} ({}) {} x{}",
+            occurrence.label.bold(),
+            occurrence.entity_id,
+            occurrence.field,
+            occurrence.count
+        );
+        if occurrence.locked {
+            println!(
+                "    {}",
+                "scene marked final: only replaced with --force".yellow()
+            );
+        }
+        println!("    - {}", occurrence.before.red());
+        println!("    + {}", occurrence.after.green());
+    }
+    println!();
+}
+
 // =============================================================================
 // Delete
 // =============================================================================
@@ -152,6 +449,7 @@ pub async fn handle_delete(
     ctx: &AppContext,
     entity_id: &str,
     _hard: bool,
+    force: bool,
     mode: OutputMode,
 ) -> Result<()> {
     let entity_type = entity_type_from_id(entity_id).ok_or_else(|| {
@@ -161,6 +459,7 @@ pub async fn handle_delete(
         )
     })?;
     let key = bare_key(entity_id, entity_type);
+    check_revision_lock(ctx, entity_id, "Deleting", force).await?;
 
     let deleted_name: Option<String> = match entity_type {
         "character" => {
@@ -187,16 +486,49 @@ pub async fn handle_delete(
             let r = crate::models::note::delete_note(&ctx.db, &key).await?;
             r.map(|n| n.title)
         }
+        "thread" => {
+            let r = crate::models::thread::delete_thread(&ctx.db, &key).await?;
+            r.map(|t| t.name)
+        }
+        "term" => {
+            let r = crate::models::term::delete_term(&ctx.db, &key).await?;
+            r.map(|t| t.name)
+        }
+        "goal" => {
+            let r = crate::models::goal::delete_goal(&ctx.db, &key).await?;
+            r.map(|g| g.want)
+        }
+        "lexeme" => {
+            let r = crate::models::lexeme::delete_lexeme(&ctx.db, &key).await?;
+            r.map(|l| l.word)
+        }
+        "style_rule" => {
+            let r = crate::models::style::delete_style_rule(&ctx.db, &key).await?;
+            r.map(|r| r.preferred)
+        }
+        "foreshadows" => {
+            let r: Option<crate::models::Foreshadow> =
+                ctx.db.delete(("foreshadows", key.as_str())).await?;
+            r.map(|f| format!("{} -> {}", f.setup, f.payoff))
+        }
+        "requires" => {
+            let r: Option<crate::models::Requirement> =
+                ctx.db.delete(("requires", key.as_str())).await?;
+            r.map(|r| format!("{} -> {}", r.scene, r.prerequisite))
+        }
         _ => anyhow::bail!("Unsupported entity type '{}' for delete", entity_type),
     };
 
     match deleted_name {
         Some(name) => {
+            ctx.event_bus
+                .emit_entity(events::ENTITY_DELETED, "cli", entity_id, entity_type, &name);
             if mode == OutputMode::Json {
-                println!(
-                    r#"{{"status": "ok", "deleted": "{}", "name": "{}"}}"#,
-                    entity_id, name
-                );
+                output_json(&DeleteResult {
+                    status: "ok".to_string(),
+                    deleted: entity_id.to_string(),
+                    name,
+                });
             } else {
                 print_success(&format!("Deleted {} '{}'", entity_type, name));
             }
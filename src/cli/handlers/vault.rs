@@ -0,0 +1,172 @@
+//! Encryption-at-rest handlers: `narra vault ...`.
+//!
+//! These convert worlds between RocksDB and the encrypted `world.age` form, so
+//! they open the database directly instead of through `AppContext`.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cli::output::schema::VaultStatus;
+use crate::cli::output::{output_json, print_hint, print_kv, print_success, OutputMode};
+use crate::db::connection::{export_surql, init_db, load_db_config, DbConfig};
+use crate::db::vault::{self, Vault};
+
+const DB_CONFIG_FILE: &str = "database.toml";
+
+/// RocksDB's own files in a data directory; everything else (session state,
+/// plugins, config) is left alone.
+fn is_rocksdb_file(name: &str) -> bool {
+    matches!(name, "CURRENT" | "IDENTITY" | "LOCK" | "LOG")
+        || name.starts_with("LOG.old.")
+        || name.starts_with("MANIFEST-")
+        || name.starts_with("OPTIONS-")
+        || [".sst", ".log", ".blob"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+}
+
+fn remove_rocksdb_files(dir: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.file_type()?.is_file() && is_rocksdb_file(&name) {
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_vault_status(data_path: &Path, mode: OutputMode) -> Result<()> {
+    let encrypted = matches!(load_db_config(data_path), DbConfig::Encrypted);
+    let vault_path = Vault::path(data_path);
+    let status = VaultStatus {
+        encrypted,
+        vault_file: vault_path.display().to_string(),
+        vault_bytes: std::fs::metadata(&vault_path).map(|m| m.len()).ok(),
+        keyring: encrypted && vault::has_keyring_entry(data_path),
+    };
+
+    if mode == OutputMode::Json {
+        output_json(&status);
+        return Ok(());
+    }
+    print_kv("Encrypted", if encrypted { "yes" } else { "no" });
+    if encrypted {
+        print_kv("Vault file", &status.vault_file);
+        if let Some(bytes) = status.vault_bytes {
+            print_kv("Size", &indicatif::HumanBytes(bytes).to_string());
+        }
+        print_kv(
+            "Keyring",
+            if status.keyring {
+                "stored"
+            } else {
+                "not stored"
+            },
+        );
+    } else {
+        print_hint("Encrypt this world with: narra vault encrypt");
+    }
+    Ok(())
+}
+
+pub async fn handle_vault_encrypt(data_path: &Path, keyring: bool, mode: OutputMode) -> Result<()> {
+    let config = load_db_config(data_path);
+    let rocksdb_dir = match &config {
+        DbConfig::Embedded { path } => path
+            .as_deref()
+            .map(Path::new)
+            .unwrap_or(data_path)
+            .to_path_buf(),
+        DbConfig::Encrypted => anyhow::bail!("World is already encrypted"),
+        DbConfig::Remote { .. } => {
+            anyhow::bail!("Encryption at rest applies to embedded worlds, not remote servers")
+        }
+    };
+
+    let passphrase = match std::env::var("NARRA_PASSPHRASE") {
+        Ok(p) => p.into(),
+        Err(_) => vault::prompt_passphrase(true)?,
+    };
+    let vault = Vault::with_passphrase(data_path, passphrase);
+
+    let db = init_db(&config, data_path).await?;
+    vault.seal(&db).await?;
+    drop(db);
+
+    // Never delete the plaintext until the vault provably opens
+    vault.load().await?;
+    std::fs::write(data_path.join(DB_CONFIG_FILE), "mode = \"encrypted\"\n")?;
+    if rocksdb_dir == data_path {
+        remove_rocksdb_files(data_path)?;
+    } else {
+        std::fs::remove_dir_all(&rocksdb_dir)?;
+    }
+    if keyring {
+        vault::store_in_keyring(data_path, vault.passphrase())?;
+    }
+
+    if mode == OutputMode::Json {
+        handle_vault_status(data_path, mode)?;
+    } else {
+        print_success(&format!(
+            "World encrypted to {}",
+            Vault::path(data_path).display()
+        ));
+        print_hint(
+            "Without the passphrase this world cannot be recovered. Keep it somewhere safe.",
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_vault_decrypt(data_path: &Path, mode: OutputMode) -> Result<()> {
+    if !matches!(load_db_config(data_path), DbConfig::Encrypted) {
+        anyhow::bail!("World is not encrypted");
+    }
+    let vault = Vault::unlock(data_path, true)?;
+    let memory = vault.load().await?;
+
+    let dump = export_surql(&memory).await?;
+
+    let plain = DbConfig::Embedded { path: None };
+    let db = init_db(&plain, data_path).await?;
+    db.query(String::from_utf8(dump)?).await?.check()?;
+    drop(db);
+
+    std::fs::remove_file(data_path.join(DB_CONFIG_FILE))?;
+    std::fs::remove_file(Vault::path(data_path))?;
+    vault::forget_keyring(data_path)?;
+    drop(vault);
+    let _ = std::fs::remove_file(data_path.join(vault::LOCK_FILE));
+
+    if mode == OutputMode::Json {
+        handle_vault_status(data_path, mode)?;
+    } else {
+        print_success("World decrypted; data is stored unencrypted again");
+    }
+    Ok(())
+}
+
+pub fn handle_vault_keyring(data_path: &Path, forget: bool, mode: OutputMode) -> Result<()> {
+    if forget {
+        vault::forget_keyring(data_path)?;
+        if mode != OutputMode::Json {
+            print_success("Passphrase removed from the OS keyring");
+        }
+    } else {
+        if !Vault::exists(data_path) {
+            anyhow::bail!("No encrypted world at {}", data_path.display());
+        }
+        let vault = Vault::with_passphrase(data_path, vault::prompt_passphrase(false)?);
+        vault.verify()?;
+        vault::store_in_keyring(data_path, vault.passphrase())?;
+        if mode != OutputMode::Json {
+            print_success("Passphrase stored in the OS keyring");
+        }
+    }
+    if mode == OutputMode::Json {
+        handle_vault_status(data_path, mode)?;
+    }
+    Ok(())
+}
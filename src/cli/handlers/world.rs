@@ -1,4 +1,4 @@
-//! World management command handlers: status, health, backfill, export, import, validate, graph.
+//! World management command handlers: status, health, backfill, export, import, progress, oplog, audit, validate, graph.
 
 use std::path::Path;
 
@@ -6,12 +6,29 @@ use anyhow::Result;
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 
+use crate::cli::output::schema::{
+    BaselineArcs, CompileSummary, CsvExportSummary, EmbeddingComparison,
+    EmbeddingComparisonSummary, EmbeddingQueryComparison, EntityStatus, ExportSummary, GraphOutput,
+    ImportDryRun, OplogExportSummary, ValidationSweep, VectorReindex, WorldStatus,
+};
 use crate::cli::output::{
-    create_spinner, output_json, print_error, print_header, print_kv, print_success, print_table,
-    OutputMode,
+    create_spinner, output_json, print_error, print_header, print_hint, print_kv, print_success,
+    print_table, OutputMode,
 };
 use crate::cli::resolve::bare_key;
+use crate::cli::ExitStatus;
 use crate::init::AppContext;
+use crate::services::arc_compaction::{ArcCompactionService, RetentionPolicy};
+use crate::services::audit::{audit_world, AuditCheck};
+use crate::services::draft_progress::DraftProgressService;
+use crate::services::event_inference::{EventInferenceService, EventProposal};
+use crate::services::events;
+use crate::services::fsck;
+use crate::services::mentions::MentionService;
+use crate::services::oplog::{OpRecord, OplogService};
+use crate::services::reciprocity::{self, ReciprocityIssueKind};
+use crate::services::renumber::RenumberService;
+use crate::services::ConsistencySeverity;
 
 // =============================================================================
 // Status — world overview dashboard
@@ -31,16 +48,9 @@ pub async fn handle_status(ctx: &AppContext, mode: OutputMode) -> Result<()> {
         ("knowledge", "Knowledge"),
         ("relates_to", "Relationships"),
         ("note", "Notes"),
+        ("term", "Terms"),
     ];
 
-    #[derive(Serialize)]
-    struct EntityStatus {
-        entity_type: String,
-        total: usize,
-        embedded: usize,
-        coverage: String,
-    }
-
     let mut statuses = Vec::new();
     let mut total_stale = 0usize;
 
@@ -84,6 +94,8 @@ pub async fn handle_status(ctx: &AppContext, mode: OutputMode) -> Result<()> {
         });
     }
 
+    let tags = crate::models::tag::tag_counts(&ctx.db).await?;
+
     let embedding_available = ctx.embedding_service.is_available();
     let dimensions = ctx.embedding_service.dimensions();
     let model_id = ctx.embedding_service.model_id();
@@ -95,17 +107,17 @@ pub async fn handle_status(ctx: &AppContext, mode: OutputMode) -> Result<()> {
     );
 
     if mode == OutputMode::Json {
-        let json = serde_json::json!({
-            "data_path": ctx.data_path.display().to_string(),
-            "entities": statuses,
-            "embedding_available": embedding_available,
-            "embedding_model": model_id,
-            "embedding_provider": provider,
-            "embedding_dimensions": dimensions,
-            "embedding_model_mismatch": model_mismatch,
-            "stale_count": total_stale,
+        output_json(&WorldStatus {
+            data_path: ctx.data_path.display().to_string(),
+            entities: statuses,
+            embedding_available,
+            embedding_model: model_id.to_string(),
+            embedding_provider: provider.to_string(),
+            embedding_dimensions: dimensions,
+            embedding_model_mismatch: model_mismatch,
+            stale_count: total_stale,
+            tags,
         });
-        output_json(&json);
         return Ok(());
     }
 
@@ -127,6 +139,19 @@ pub async fn handle_status(ctx: &AppContext, mode: OutputMode) -> Result<()> {
     print_table(&["Entities", "Count", "Embedded", "Coverage"], rows);
 
     println!();
+    if !tags.is_empty() {
+        let top: Vec<String> = tags
+            .iter()
+            .take(10)
+            .map(|t| format!("{} ({})", t.tag, t.total()))
+            .collect();
+        let more = if tags.len() > top.len() {
+            format!(", +{} more", tags.len() - top.len())
+        } else {
+            String::new()
+        };
+        println!("  Tags: {}{}", top.join(", "), more);
+    }
     if embedding_available {
         println!(
             "  Embedding Model: {} via {} ({} dims) {}",
@@ -192,6 +217,7 @@ pub async fn handle_health(ctx: &AppContext, mode: OutputMode) -> Result<()> {
         ("knowledge", true),
         ("relates_to", true),
         ("perceives", true),
+        ("term", true),
     ];
 
     let mut health_rows: Vec<HealthTable> = Vec::new();
@@ -268,6 +294,7 @@ pub async fn handle_backfill(
 
     // If --force, mark all entities as needing re-embedding
     if force {
+        ctx.backup_service.before("re-embed").await?;
         let tables = [
             "character",
             "location",
@@ -276,6 +303,7 @@ pub async fn handle_backfill(
             "knowledge",
             "perceives",
             "relates_to",
+            "term",
         ];
         for table in &tables {
             let query = format!(
@@ -296,6 +324,10 @@ pub async fn handle_backfill(
 
     spinner.finish_and_clear();
 
+    let mut payload = serde_json::to_value(&stats)?;
+    payload["source"] = serde_json::json!("cli");
+    ctx.event_bus.emit(events::EMBEDDING_BACKFILLED, payload);
+
     if mode == OutputMode::Json {
         output_json(&stats);
     } else {
@@ -315,6 +347,57 @@ pub async fn handle_backfill(
     Ok(())
 }
 
+pub async fn handle_reindex_vectors(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    use crate::services::vector_index;
+
+    let settings = ctx.config.vector_index.settings();
+    let dimensions = ctx.embedding_service.dimensions();
+    let previous = vector_index::built_index(&ctx.db).await?;
+
+    let spinner = create_spinner(&format!("Rebuilding vector indexes ({})...", settings.kind));
+    let tables = vector_index::reindex(&ctx.db, &settings, previous, dimensions).await?;
+    spinner.finish_and_clear();
+
+    if mode == OutputMode::Json {
+        output_json(&VectorReindex {
+            kind: settings.kind.to_string(),
+            dimensions,
+            tables,
+        });
+        return Ok(());
+    }
+
+    let ms = |v: Option<f64>| v.map_or("-".to_string(), |ms| format!("{:.1}", ms));
+    let rows = tables
+        .iter()
+        .map(|t| {
+            let speedup = match (t.before_ms, t.after_ms) {
+                (Some(before), Some(after)) if after > 0.0 => format!("{:.1}x", before / after),
+                _ => "-".to_string(),
+            };
+            vec![
+                t.table.clone(),
+                t.rows.to_string(),
+                ms(t.before_ms),
+                ms(t.after_ms),
+                speedup,
+            ]
+        })
+        .collect();
+    print_header(&format!(
+        "Vector indexes: {} ({} dimensions)",
+        settings.kind, dimensions
+    ));
+    print_table(
+        &["Table", "Vectors", "Before ms", "After ms", "Speedup"],
+        rows,
+    );
+    if settings.kind == vector_index::VectorIndexKind::None {
+        print_hint("Set [vector_index] kind = \"hnsw\" in narra.toml to index embeddings.");
+    }
+    Ok(())
+}
+
 // =============================================================================
 // Export
 // =============================================================================
@@ -322,24 +405,43 @@ pub async fn handle_backfill(
 pub async fn handle_export(
     ctx: &AppContext,
     output: Option<&Path>,
+    include_derived: bool,
+    scope: Option<(&str, usize)>,
+    redact: &[String],
     mode: OutputMode,
 ) -> Result<()> {
-    use crate::services::export::ExportService;
+    use crate::services::export::{redact as redact_world, scope_filter, ExportService, Redaction};
+
+    let redactions = redact
+        .iter()
+        .map(|r| r.parse::<Redaction>())
+        .collect::<Result<Vec<_>, _>>()?;
 
     let spinner = create_spinner("Exporting world data...");
 
     let export_service = ExportService::new(ctx.db.clone());
-    let import = export_service.export_world().await?;
+    let mut import = match scope {
+        Some((root, depth)) => export_service.export_scoped(root, depth).await?,
+        None => export_service.export_world().await?,
+    };
+    redact_world(&mut import, &redactions, &ctx.config.export.secret_keys());
 
     spinner.finish_and_clear();
 
     let yaml = serde_yaml_ng::to_string(&import)?;
 
-    let header = format!(
+    let mut header = format!(
         "# Narra world export\n# Version: {}\n# Exported: {}\n",
         env!("CARGO_PKG_VERSION"),
         chrono::Utc::now().to_rfc3339()
     );
+    if let Some((root, depth)) = scope {
+        header.push_str(&format!("# Scope: {} (depth {})\n", root, depth));
+    }
+    if !redactions.is_empty() {
+        let names: Vec<String> = redactions.iter().map(|r| r.to_string()).collect();
+        header.push_str(&format!("# Redacted: {}\n", names.join(", ")));
+    }
     let content = format!("{}{}", header, yaml);
 
     let default_path = format!(
@@ -352,17 +454,57 @@ pub async fn handle_export(
 
     std::fs::write(&output_path, &content)?;
 
-    let summary = serde_json::json!({
-        "output_path": output_path.display().to_string(),
-        "characters": import.characters.len(),
-        "locations": import.locations.len(),
-        "events": import.events.len(),
-        "scenes": import.scenes.len(),
-        "relationships": import.relationships.len(),
-        "knowledge": import.knowledge.len(),
-        "notes": import.notes.len(),
-        "facts": import.facts.len(),
-    });
+    let (derived_path, derived) = if include_derived {
+        use crate::mcp::types::DerivedStats;
+        use crate::services::derived::{sidecar_path, write_sidecar, DerivedDataService};
+
+        let spinner = create_spinner("Exporting embeddings and annotations...");
+        let mut data = DerivedDataService::new(ctx.db.clone())
+            .export(ctx.embedding_service.model_id())
+            .await?;
+        if scope.is_some() {
+            data.retain_entities(scope_filter(&import));
+        }
+        let redacted_tables: Vec<&str> = redactions
+            .iter()
+            .filter_map(|r| match r {
+                Redaction::Notes => Some("note:"),
+                Redaction::Knowledge => Some("knowledge:"),
+                Redaction::Secrets => None,
+            })
+            .collect();
+        if !redacted_tables.is_empty() {
+            data.retain_entities(|id| !redacted_tables.iter().any(|t| id.starts_with(t)));
+        }
+        let path = sidecar_path(&output_path);
+        write_sidecar(&path, &data)?;
+        spinner.finish_and_clear();
+
+        let stats = DerivedStats {
+            embeddings: data.embeddings.len(),
+            arc_snapshots: data.arc_snapshots.len(),
+            annotations: data.annotations.len(),
+            ..Default::default()
+        };
+        (Some(path.display().to_string()), Some(stats))
+    } else {
+        (None, None)
+    };
+
+    let summary = ExportSummary {
+        output_path: output_path.display().to_string(),
+        characters: import.characters.len(),
+        locations: import.locations.len(),
+        events: import.events.len(),
+        scenes: import.scenes.len(),
+        relationships: import.relationships.len(),
+        knowledge: import.knowledge.len(),
+        notes: import.notes.len(),
+        facts: import.facts.len(),
+        redacted: redactions.iter().map(|r| r.to_string()).collect(),
+        derived_path,
+        derived,
+    };
 
     if mode == OutputMode::Json {
         output_json(&summary);
@@ -376,6 +518,15 @@ pub async fn handle_export(
         println!("  Knowledge:     {}", import.knowledge.len());
         println!("  Notes:         {}", import.notes.len());
         println!("  Facts:         {}", import.facts.len());
+        if !summary.redacted.is_empty() {
+            println!("  Redacted:      {}", summary.redacted.join(", "));
+        }
+        if let (Some(path), Some(stats)) = (&summary.derived_path, &summary.derived) {
+            print_success(&format!("Exported derived data to {}", path));
+            println!("  Embeddings:    {}", stats.embeddings);
+            println!("  Arc snapshots: {}", stats.arc_snapshots);
+            println!("  Annotations:   {}", stats.annotations);
+        }
     }
 
     Ok(())
@@ -385,21 +536,176 @@ pub async fn handle_export(
 // Import
 // =============================================================================
 
+/// Flags for `narra world import`.
+pub struct ImportOptions<'a> {
+    pub on_conflict: &'a str,
+    pub dry_run: bool,
+    pub include_derived: bool,
+    /// Root character and depth for a scoped import
+    pub scope: Option<(&'a str, usize)>,
+    /// Ask how to resolve each conflicting entity
+    pub interactive: bool,
+    /// Resolution log to replay
+    pub replay: Option<&'a Path>,
+    /// Where to write the resolution log
+    pub resolution_log: Option<&'a Path>,
+}
+
+/// Compact single-line rendering of a field value for diffs.
+fn diff_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "(none)".to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn print_conflicts(conflicts: &[crate::mcp::types::EntityConflict]) {
+    let rows: Vec<Vec<String>> = conflicts
+        .iter()
+        .flat_map(|c| {
+            c.fields.iter().map(|f| {
+                vec![
+                    c.entity_id.clone(),
+                    f.field.clone(),
+                    diff_value(&f.old),
+                    diff_value(&f.new),
+                ]
+            })
+        })
+        .collect();
+    if !rows.is_empty() {
+        print_table(&["Entity", "Field", "Current", "Incoming"], rows);
+    }
+    let unchanged = conflicts.iter().filter(|c| c.fields.is_empty()).count();
+    if unchanged > 0 {
+        println!(
+            "{} existing entities are unchanged by this import.",
+            unchanged
+        );
+    }
+}
+
+/// Ask how to resolve each conflict with field changes. Unchanged entities
+/// are skipped without asking.
+fn prompt_resolutions(
+    conflicts: &[crate::mcp::types::EntityConflict],
+    policy: &mut crate::services::import::ConflictPolicy,
+) -> Result<()> {
+    use crate::mcp::types::ConflictMode;
+    use std::io::{BufRead, Write};
+
+    let mut for_rest: Option<ConflictMode> = None;
+    for conflict in conflicts {
+        if policy.overrides.contains_key(&conflict.entity_id) {
+            continue;
+        }
+        if conflict.fields.is_empty() {
+            policy
+                .overrides
+                .insert(conflict.entity_id.clone(), ConflictMode::Skip);
+            continue;
+        }
+        let resolution = match for_rest {
+            Some(mode) => mode,
+            None => {
+                println!("\n{}", conflict.entity_id.bold());
+                for diff in &conflict.fields {
+                    println!(
+                        "  {}: {} -> {}",
+                        diff.field,
+                        diff_value(&diff.old).red(),
+                        diff_value(&diff.new).green()
+                    );
+                }
+                loop {
+                    print!("[u]pdate, [s]kip, [U]pdate all, [S]kip all (default s): ");
+                    std::io::stdout().flush()?;
+                    let mut answer = String::new();
+                    std::io::stdin().lock().read_line(&mut answer)?;
+                    match answer.trim() {
+                        "u" => break ConflictMode::Update,
+                        "s" | "" => break ConflictMode::Skip,
+                        "U" => {
+                            for_rest = Some(ConflictMode::Update);
+                            break ConflictMode::Update;
+                        }
+                        "S" => {
+                            for_rest = Some(ConflictMode::Skip);
+                            break ConflictMode::Skip;
+                        }
+                        _ => println!("Please answer u, s, U, or S."),
+                    }
+                }
+            }
+        };
+        policy
+            .overrides
+            .insert(conflict.entity_id.clone(), resolution);
+    }
+    Ok(())
+}
+
 pub async fn handle_import(
     ctx: &AppContext,
     file: &Path,
-    on_conflict: &str,
-    dry_run: bool,
+    options: ImportOptions<'_>,
     mode: OutputMode,
 ) -> Result<()> {
-    use crate::mcp::types::{ConflictMode, NarraImport};
-    use crate::services::import::ImportService;
+    use crate::mcp::types::NarraImport;
 
     let content = std::fs::read_to_string(file)
         .map_err(|e| anyhow::anyhow!("Failed to read file '{}': {}", file.display(), e))?;
 
     let import: NarraImport = serde_yaml_ng::from_str(&content)
         .map_err(|e| anyhow::anyhow!("Failed to parse YAML: {}", e))?;
+    import_world(ctx, file, import, options, mode).await
+}
+
+/// Shared by YAML and CSV imports once the file has been parsed.
+async fn import_world(
+    ctx: &AppContext,
+    file: &Path,
+    mut import: crate::mcp::types::NarraImport,
+    options: ImportOptions<'_>,
+    mode: OutputMode,
+) -> Result<()> {
+    use crate::mcp::types::ConflictMode;
+    use crate::services::derived::{read_sidecar, sidecar_path, DerivedDataService};
+    use crate::services::export::{scope_filter, scope_import};
+    use crate::services::import::{ConflictPolicy, ImportService, ResolutionLog};
+    use std::io::IsTerminal;
+
+    let ImportOptions {
+        on_conflict,
+        dry_run,
+        include_derived,
+        scope,
+        interactive,
+        replay,
+        resolution_log,
+    } = options;
+    if interactive && (mode == OutputMode::Json || !std::io::stdin().is_terminal()) {
+        anyhow::bail!("--interactive needs a terminal and human output");
+    }
+
+    if let Some((root, depth)) = scope {
+        import = scope_import(import, root, depth, &[])?;
+    }
+
+    // Read the sidecar up front so a missing or corrupt one fails before any writes
+    let derived = if include_derived {
+        let path = sidecar_path(file);
+        let mut data = read_sidecar(&path).map_err(|e| {
+            anyhow::anyhow!("Failed to read derived data '{}': {}", path.display(), e)
+        })?;
+        if scope.is_some() {
+            data.retain_entities(scope_filter(&import));
+        }
+        Some(data)
+    } else {
+        None
+    };
 
     let conflict_mode = match on_conflict.to_lowercase().as_str() {
         "skip" => ConflictMode::Skip,
@@ -407,6 +713,12 @@ pub async fn handle_import(
         _ => ConflictMode::Error,
     };
 
+    let import_service = ImportService::new(ctx.db.clone(), ctx.staleness_manager.clone());
+    let conflicts = import_service
+        .conflicts(&import)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to compare with the world: {}", e))?;
+
     if dry_run {
         let counts = [
             ("characters", import.characters.len()),
@@ -423,14 +735,13 @@ pub async fn handle_import(
 
         match mode {
             OutputMode::Json => {
-                let json_counts: std::collections::HashMap<&str, usize> =
-                    counts.iter().copied().collect();
-                output_json(&serde_json::json!({
-                    "dry_run": true,
-                    "total": total,
-                    "by_type": json_counts,
-                    "on_conflict": on_conflict,
-                }));
+                output_json(&ImportDryRun {
+                    dry_run: true,
+                    total,
+                    by_type: counts.iter().map(|(t, c)| (t.to_string(), *c)).collect(),
+                    on_conflict: on_conflict.to_string(),
+                    conflicts,
+                });
             }
             OutputMode::Human | OutputMode::Markdown => {
                 println!("Dry run — no changes will be made\n");
@@ -442,21 +753,54 @@ pub async fn handle_import(
                 print_table(&["Entity Type", "Count"], rows);
                 println!("\nTotal: {} entities", total);
                 println!("Conflict mode: {}", on_conflict);
+                if !conflicts.is_empty() {
+                    println!("\n{} entities already exist:\n", conflicts.len());
+                    print_conflicts(&conflicts);
+                }
             }
         }
         return Ok(());
     }
 
+    let mut policy = ConflictPolicy::uniform(conflict_mode);
+    if let Some(path) = replay {
+        let log: ResolutionLog = serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| anyhow::anyhow!("Invalid resolution log '{}': {}", path.display(), e))?;
+        policy.overrides.extend(log.overrides());
+    }
+    if interactive && !conflicts.is_empty() {
+        prompt_resolutions(&conflicts, &mut policy)?;
+    }
+
+    ctx.backup_service.before("import").await?;
+
     let spinner = create_spinner("Importing world data...");
 
-    let import_service = ImportService::new(ctx.db.clone(), ctx.staleness_manager.clone());
-    let result = import_service
-        .execute_import(import, conflict_mode)
+    let mut result = import_service
+        .execute_import_with(import, &policy)
         .await
         .map_err(|e| anyhow::anyhow!("Import failed: {}", e))?;
 
+    if let Some(data) = derived {
+        spinner.set_message("Restoring embeddings and annotations...");
+        let stats = DerivedDataService::new(ctx.db.clone())
+            .import(data, ctx.embedding_service.model_id())
+            .await
+            .map_err(|e| anyhow::anyhow!("Derived data import failed: {}", e))?;
+        result.derived = Some(stats);
+    }
+
     spinner.finish_and_clear();
 
+    if let Some(path) = resolution_log {
+        let log = ResolutionLog {
+            source: file.display().to_string(),
+            created_at: chrono::Utc::now(),
+            resolutions: result.conflicts.clone(),
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&log)?)?;
+    }
+
     match mode {
         OutputMode::Json => {
             output_json(&result);
@@ -496,7 +840,31 @@ pub async fn handle_import(
                 }
             }
 
-            if result.total_created > 0 {
+            if let Some(derived) = &result.derived {
+                print_success(&format!(
+                    "Derived data restored: {} embeddings, {} arc snapshots, {} annotations",
+                    derived.embeddings, derived.arc_snapshots, derived.annotations,
+                ));
+                if derived.unmatched > 0 {
+                    print_error(&format!(
+                        "{} derived item(s) refer to entities that were not imported",
+                        derived.unmatched
+                    ));
+                }
+                if let Some(model) = &derived.model_mismatch {
+                    print_error(&format!(
+                        "Vectors were made with {} but this world uses {}; they were skipped",
+                        model,
+                        ctx.embedding_service.model_id()
+                    ));
+                }
+            }
+
+            let needs_backfill = result
+                .derived
+                .as_ref()
+                .is_none_or(|d| d.model_mismatch.is_some());
+            if result.total_created > 0 && needs_backfill {
                 println!(
                     "\nRun 'narra world backfill' to generate embeddings for imported entities."
                 );
@@ -507,6 +875,587 @@ pub async fn handle_import(
     Ok(())
 }
 
+pub async fn handle_compile(
+    ctx: &AppContext,
+    format: &str,
+    output: Option<&Path>,
+    title: &str,
+    mode: OutputMode,
+) -> Result<()> {
+    use crate::services::bible::{BibleFormat, WorldBible};
+    use crate::services::export::ExportService;
+    use crate::services::templates::Templates;
+
+    let format = BibleFormat::parse(format)?;
+    let templates = Templates::load(&ctx.data_path)?;
+
+    let spinner = create_spinner("Compiling world bible...");
+    let world = ExportService::new(ctx.db.clone()).export_world().await?;
+    let bible = WorldBible::build(title, &world, &templates)?;
+    let bytes = bible.render(format)?;
+    spinner.finish_and_clear();
+
+    let output_path = output.map(|p| p.to_path_buf()).unwrap_or_else(|| {
+        std::path::PathBuf::from(format!("./world-bible.{}", format.extension()))
+    });
+    std::fs::write(&output_path, &bytes)?;
+
+    let summary = CompileSummary {
+        output_path: output_path.display().to_string(),
+        format: format.extension().to_string(),
+        chapters: bible.chapters.iter().map(|c| c.title.clone()).collect(),
+        size_bytes: bytes.len() as u64,
+    };
+    if mode == OutputMode::Json {
+        output_json(&summary);
+    } else {
+        print_success(&format!(
+            "Compiled {} to {} ({})",
+            title,
+            summary.output_path,
+            indicatif::HumanBytes(summary.size_bytes)
+        ));
+        for chapter in &summary.chapters {
+            println!("  {}", chapter);
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_import_csv(
+    ctx: &AppContext,
+    file: &Path,
+    entity_type: Option<&str>,
+    map: Option<&str>,
+    options: ImportOptions<'_>,
+    mode: OutputMode,
+) -> Result<()> {
+    use crate::services::tabular::{read_csv, ColumnMap, TabularType};
+
+    let kind = match entity_type {
+        Some(t) => TabularType::parse(t)?,
+        None => TabularType::from_path(file).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot tell the entity type from '{}'; pass --type characters|locations|events",
+                file.display()
+            )
+        })?,
+    };
+    let map = ColumnMap::parse(map.unwrap_or_default(), kind)?;
+
+    let reader = std::fs::File::open(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read file '{}': {}", file.display(), e))?;
+    let import = read_csv(reader, kind, &map)
+        .map_err(|e| anyhow::anyhow!("Failed to parse '{}': {}", file.display(), e))?;
+    import_world(ctx, file, import, options, mode).await
+}
+
+pub async fn handle_import_script(
+    ctx: &AppContext,
+    file: &Path,
+    format: Option<&str>,
+    on_conflict: &str,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    use crate::services::export::ExportService;
+    use crate::services::ingest::{
+        adapter, adapter_for_path, plan_import, script_prefix, store_dialogue,
+    };
+
+    let reader = match format {
+        Some(f) => adapter(f)?,
+        None => adapter_for_path(file).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot tell the script format from '{}'; pass --format fountain|fdx",
+                file.display()
+            )
+        })?,
+    };
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read file '{}': {}", file.display(), e))?;
+    let script = reader
+        .parse(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse '{}': {}", file.display(), e))?;
+    if script.scenes.is_empty() {
+        anyhow::bail!("No scene headings found in '{}'", file.display());
+    }
+
+    let world = ExportService::new(ctx.db.clone()).export_world().await?;
+    let plan = plan_import(&script, &script_prefix(file), &world);
+    import_world(
+        ctx,
+        file,
+        plan.import.clone(),
+        ImportOptions {
+            on_conflict,
+            dry_run,
+            include_derived: false,
+            scope: None,
+            interactive: false,
+            replay: None,
+            resolution_log: None,
+        },
+        mode,
+    )
+    .await?;
+
+    if dry_run {
+        if mode != OutputMode::Json {
+            println!(
+                "  {} lines of dialogue ({} characters and locations matched existing ones)",
+                plan.dialogue_lines, plan.reused
+            );
+        }
+        return Ok(());
+    }
+    // Scenes that were already there keep their dialogue unless updated
+    let skip = if on_conflict == "update" {
+        std::collections::HashSet::new()
+    } else {
+        world
+            .scenes
+            .iter()
+            .filter_map(|s| s.id.as_deref())
+            .map(|id| id.split_once(':').map_or(id, |(_, key)| key).to_string())
+            .collect()
+    };
+    let stored = store_dialogue(&ctx.db, &script, &plan, &skip).await?;
+    if mode != OutputMode::Json {
+        print_success(&format!("Stored {} lines of dialogue", stored));
+    }
+    Ok(())
+}
+
+pub async fn handle_export_csv(
+    ctx: &AppContext,
+    entity_type: &str,
+    map: Option<&str>,
+    output: Option<&Path>,
+    mode: OutputMode,
+) -> Result<()> {
+    use crate::services::export::ExportService;
+    use crate::services::tabular::{write_csv, ColumnMap, TabularType};
+
+    let kind = TabularType::parse(entity_type)?;
+    let map = ColumnMap::parse(map.unwrap_or_default(), kind)?;
+
+    let spinner = create_spinner("Exporting world data...");
+    let import = ExportService::new(ctx.db.clone()).export_world().await?;
+    spinner.finish_and_clear();
+
+    let output_path = output
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| std::path::PathBuf::from(format!("./{}.csv", kind.plural())));
+    let rows = write_csv(std::fs::File::create(&output_path)?, kind, &import, &map)?;
+
+    if mode == OutputMode::Json {
+        output_json(&CsvExportSummary {
+            output_path: output_path.display().to_string(),
+            entity_type: kind.plural().to_string(),
+            rows,
+        });
+    } else {
+        print_success(&format!(
+            "Exported {} {} to {}",
+            rows,
+            kind.plural(),
+            output_path.display()
+        ));
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Progress — word counts against a target
+// =============================================================================
+
+pub async fn handle_progress(
+    ctx: &AppContext,
+    target: Option<i64>,
+    clear_target: bool,
+    ingest: Option<&Path>,
+    record: bool,
+    by_status: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let service = DraftProgressService::new(ctx.db.clone());
+    if let Some(t) = target {
+        if t <= 0 {
+            anyhow::bail!("--target must be positive");
+        }
+        service.set_target(Some(t)).await?;
+    } else if clear_target {
+        service.set_target(None).await?;
+    }
+
+    let ingested = match ingest {
+        Some(dir) => Some(service.ingest(dir).await?),
+        None => None,
+    };
+    let progress = service.report(record).await?;
+
+    if mode == OutputMode::Json {
+        output_json(&progress);
+        return Ok(());
+    }
+
+    if let Some(result) = &ingested {
+        print_success(&format!(
+            "Counted {} manuscript file(s)",
+            result.counted.len()
+        ));
+        for path in &result.unmatched {
+            print_error(&format!("No scene matches {}", path.display()));
+        }
+    }
+
+    print_header("Draft Progress");
+    print_kv("Words", &progress.total_words.to_string());
+    print_kv(
+        "Scenes counted",
+        &format!("{} of {}", progress.counted_scenes, progress.total_scenes),
+    );
+    if let (Some(t), Some(percent), Some(remaining)) =
+        (progress.target, progress.percent, progress.remaining)
+    {
+        print_kv(
+            "Target",
+            &format!("{} ({:.1}%, {} to go)", t, percent, remaining),
+        );
+    }
+
+    if !progress.acts.is_empty() {
+        println!();
+        let rows: Vec<Vec<String>> = progress
+            .acts
+            .iter()
+            .map(|a| {
+                vec![
+                    a.act.clone(),
+                    format!("{}/{}", a.counted_scenes, a.scenes),
+                    a.words.to_string(),
+                    format!("{:.0}%", a.share * 100.0),
+                ]
+            })
+            .collect();
+        print_table(&["Act", "Scenes Counted", "Words", "Share"], rows);
+    }
+
+    if by_status && progress.total_scenes > 0 {
+        println!();
+        let rows: Vec<Vec<String>> = progress
+            .by_status
+            .iter()
+            .map(|s| {
+                vec![
+                    s.status.clone(),
+                    s.scenes.to_string(),
+                    s.words.to_string(),
+                    format!("{:.0}%", s.share * 100.0),
+                ]
+            })
+            .collect();
+        print_table(&["Status", "Scenes", "Words", "Share"], rows);
+    }
+
+    if progress.history.len() > 1 {
+        println!();
+        let rows: Vec<Vec<String>> = progress
+            .history
+            .iter()
+            .rev()
+            .take(10)
+            .map(|h| {
+                vec![
+                    h.recorded_at.to_string(),
+                    h.total_words.to_string(),
+                    format!("{}/{}", h.counted_scenes, h.total_scenes),
+                ]
+            })
+            .collect();
+        print_table(&["Recorded", "Words", "Scenes Counted"], rows);
+    }
+
+    if progress.counted_scenes == 0 {
+        print_hint(
+            "Set counts with: narra update scene:<id> --set word_count=1200, or --ingest <dir>",
+        );
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Oplog — export/import authored operations between writers
+// =============================================================================
+
+pub async fn handle_oplog_export(
+    ctx: &AppContext,
+    since: Option<&str>,
+    output: Option<&Path>,
+    mode: OutputMode,
+) -> Result<()> {
+    let since = since
+        .map(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|_| anyhow::anyhow!("--since must be an RFC 3339 timestamp, got '{}'", s))
+        })
+        .transpose()?;
+    let ops = OplogService::new(ctx.db.clone()).export(since).await?;
+
+    let mut lines = String::new();
+    for op in &ops {
+        lines.push_str(&serde_json::to_string(op)?);
+        lines.push('\n');
+    }
+    match output {
+        Some(path) => {
+            std::fs::write(path, lines)?;
+            if mode == OutputMode::Json {
+                output_json(&OplogExportSummary {
+                    operations: ops.len(),
+                    path: path.display().to_string(),
+                });
+            } else {
+                print_success(&format!(
+                    "Exported {} operation(s) to {}",
+                    ops.len(),
+                    path.display()
+                ));
+            }
+        }
+        None => print!("{}", lines),
+    }
+    Ok(())
+}
+
+pub async fn handle_oplog_import(
+    ctx: &AppContext,
+    file: &Path,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?;
+    let ops = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str::<OpRecord>(line)
+                .map_err(|e| anyhow::anyhow!("{}:{}: {}", file.display(), i + 1, e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if !dry_run {
+        ctx.backup_service.before("oplog-import").await?;
+    }
+    let summary = OplogService::new(ctx.db.clone())
+        .import(ops, dry_run)
+        .await?;
+
+    if mode == OutputMode::Json {
+        output_json(&summary);
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would apply" } else { "Applied" };
+    print_success(&format!(
+        "{} {} entity change(s) from {} new operation(s) ({} already present)",
+        verb, summary.applied, summary.recorded, summary.duplicates
+    ));
+    if !summary.superseded.is_empty() {
+        println!();
+        let rows: Vec<Vec<String>> = summary
+            .superseded
+            .iter()
+            .map(|op| {
+                vec![
+                    op.entity_id.clone(),
+                    op.author.clone().unwrap_or_else(|| "-".to_string()),
+                    op.event.clone(),
+                    op.timestamp.clone(),
+                ]
+            })
+            .collect();
+        print_header("Superseded by local changes");
+        print_table(&["Entity", "Author", "Event", "When"], rows);
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Audit
+// =============================================================================
+
+pub async fn handle_audit(
+    ctx: &AppContext,
+    checks: &[String],
+    fail_on: &str,
+    mode: OutputMode,
+) -> Result<()> {
+    let fail_on = match fail_on.to_lowercase().as_str() {
+        "critical" => Some(ConsistencySeverity::Critical),
+        "warning" => Some(ConsistencySeverity::Warning),
+        "info" => Some(ConsistencySeverity::Info),
+        "never" => None,
+        other => anyhow::bail!(
+            "Unknown --fail-on '{}'. Expected: critical, warning, info, never",
+            other
+        ),
+    };
+    let checks = if checks.is_empty() {
+        AuditCheck::ALL.to_vec()
+    } else {
+        checks
+            .iter()
+            .map(|c| c.parse::<AuditCheck>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(anyhow::Error::msg)?
+    };
+
+    let spinner = create_spinner("Auditing world...");
+    let report = audit_world(&ctx.db, ctx.consistency_service.as_ref(), &checks).await?;
+    spinner.finish_and_clear();
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+    } else {
+        for group in &report.groups {
+            print_header(&format!(
+                "{:?}: {} ({})",
+                group.severity,
+                group.fact_title,
+                group.findings.len()
+            ));
+            let rows: Vec<Vec<String>> = group
+                .findings
+                .iter()
+                .map(|f| {
+                    let message = if f.intentional {
+                        format!("{} (likely intentional)", f.message)
+                    } else {
+                        f.message.clone()
+                    };
+                    vec![f.entity_id.clone(), f.check.to_string(), message]
+                })
+                .collect();
+            print_table(&["Entity", "Check", "Violation"], rows);
+            println!();
+        }
+        let checks: Vec<String> = report.checks.iter().map(|c| c.to_string()).collect();
+        let summary = format!(
+            "Audited {} entities ({}): {} critical, {} warning, {} info",
+            report.checked,
+            checks.join(", "),
+            report.critical,
+            report.warning,
+            report.info
+        );
+        if report.total_violations == 0 {
+            print_success(&summary);
+        } else {
+            println!("{}", summary);
+        }
+    }
+
+    // 3: critical violations, 2: others at or above --fail-on
+    match (report.worst(), fail_on) {
+        (Some(worst), Some(threshold)) if worst >= threshold => {
+            let code = if worst == ConsistencySeverity::Critical {
+                3
+            } else {
+                2
+            };
+            Err(ExitStatus(code).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+// =============================================================================
+// Fsck
+// =============================================================================
+
+pub async fn handle_fsck(ctx: &AppContext, fix: bool, mode: OutputMode) -> Result<()> {
+    let spinner = create_spinner("Checking database integrity...");
+    let report = fsck::fsck(&ctx.db, ctx.embedding_service.dimensions(), fix).await;
+    spinner.finish_and_clear();
+    let report = report?;
+
+    if mode == OutputMode::Json {
+        output_json(&report);
+    } else {
+        print_header(if fix {
+            "Database Check (fix)"
+        } else {
+            "Database Check"
+        });
+        let rows = report
+            .checks
+            .iter()
+            .map(|c| {
+                vec![
+                    c.name.clone(),
+                    c.scanned.to_string(),
+                    c.problems.to_string(),
+                    c.repaired.to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["Check", "Scanned", "Problems", "Repaired"], rows);
+
+        if !report.findings.is_empty() {
+            println!();
+            let rows = report
+                .findings
+                .iter()
+                .map(|f| {
+                    let status = if f.repaired {
+                        "repaired".green().to_string()
+                    } else if f.fixable {
+                        "fixable".yellow().to_string()
+                    } else {
+                        "manual".red().to_string()
+                    };
+                    vec![f.check.clone(), f.record.clone(), f.detail.clone(), status]
+                })
+                .collect();
+            print_table(&["Check", "Record", "Problem", "Status"], rows);
+        }
+
+        let repaired = report.findings.len() - report.unresolved();
+        if repaired > 0 {
+            print_success(&format!(
+                "Repaired {} problem(s), reclaimed ~{}",
+                repaired,
+                indicatif::HumanBytes(report.reclaimed_bytes)
+            ));
+            if report
+                .checks
+                .iter()
+                .any(|c| c.name == "embeddings" && c.repaired > 0)
+            {
+                print_hint("Run 'narra world backfill' to re-embed the cleared vectors.");
+            }
+        } else if report.findings.is_empty() {
+            print_success("No problems found");
+        }
+        if report.fixable() > 0 {
+            print_hint(&format!(
+                "Run 'narra world fsck --fix' to repair {} of them.",
+                report.fixable()
+            ));
+        }
+    }
+
+    if report.unresolved() > 0 {
+        return Err(ExitStatus(2).into());
+    }
+    Ok(())
+}
+
 // =============================================================================
 // Validate
 // =============================================================================
@@ -514,6 +1463,7 @@ pub async fn handle_import(
 pub async fn handle_validate(
     ctx: &AppContext,
     entity_id: Option<&str>,
+    fix_reciprocals: bool,
     mode: OutputMode,
 ) -> Result<()> {
     match entity_id {
@@ -522,6 +1472,7 @@ pub async fn handle_validate(
                 .consistency_service
                 .check_entity_mutation(eid, &serde_json::json!({}))
                 .await?;
+            ctx.event_bus.emit_violations("cli", eid, &result);
 
             if mode == OutputMode::Json {
                 output_json(&result);
@@ -557,6 +1508,7 @@ pub async fn handle_validate(
                     .consistency_service
                     .check_entity_mutation(&char_id, &serde_json::json!({}))
                     .await?;
+                ctx.event_bus.emit_violations("cli", &char_id, &result);
                 total_violations += result.total_violations;
                 checked += 1;
 
@@ -565,16 +1517,47 @@ pub async fn handle_validate(
                 }
             }
 
+            let mut reciprocity = reciprocity::check_reciprocity(&ctx.db, None).await?;
+            let mut reciprocals_created = 0;
+            if fix_reciprocals {
+                reciprocals_created =
+                    reciprocity::create_missing_reciprocals(&ctx.db, &reciprocity).await?;
+                reciprocity.retain(|i| i.kind != ReciprocityIssueKind::Missing);
+            }
+            total_violations += reciprocity.len();
+
             if mode == OutputMode::Json {
-                println!(
-                    r#"{{"checked": {}, "total_violations": {}}}"#,
-                    checked, total_violations
-                );
+                output_json(&ValidationSweep {
+                    checked,
+                    total_violations,
+                    reciprocity,
+                    reciprocals_created,
+                });
             } else {
+                for issue in &reciprocity {
+                    println!("  {} ({})", issue.message(), issue.relationship_id);
+                }
+                if reciprocals_created > 0 {
+                    print_success(&format!(
+                        "Created {} missing reciprocal relationships",
+                        reciprocals_created
+                    ));
+                }
                 println!(
                     "\nValidation complete: checked {} entities, {} total violations",
                     checked, total_violations
                 );
+                if reciprocity
+                    .iter()
+                    .any(|i| i.kind == ReciprocityIssueKind::Missing)
+                {
+                    print_hint(
+                        "Create missing reverse edges with: narra world validate --fix-reciprocals",
+                    );
+                }
+                if !reciprocity.is_empty() {
+                    print_hint("Mark deliberate ones with: narra relationship intentional <id>");
+                }
             }
         }
     }
@@ -648,12 +1631,11 @@ pub async fn handle_graph(
     }
 
     if mode == OutputMode::Json {
-        let result = serde_json::json!({
-            "format": "mermaid",
-            "content": mermaid,
-            "output_path": output.map(|p| p.display().to_string()),
+        output_json(&GraphOutput {
+            format: "mermaid".to_string(),
+            content: mermaid,
+            output_path: output.map(|p| p.display().to_string()),
         });
-        output_json(&result);
     } else if output.is_none() {
         println!("{}", mermaid);
     }
@@ -741,90 +1723,322 @@ pub async fn handle_baseline_arcs(
         None => vec!["character", "knowledge"],
     };
 
-    let mut total_created = 0usize;
-    let mut total_skipped = 0usize;
+    let counts = crate::services::arc::baseline_arc_snapshots(&ctx.db, &types_to_process).await?;
+    let (total_created, total_skipped) = (counts.created, counts.skipped);
 
-    for etype in &types_to_process {
-        // Fetch entities that have embeddings
-        let fetch_query = format!(
-            "SELECT id, embedding FROM {} WHERE embedding IS NOT NONE",
-            etype
+    if mode == OutputMode::Json {
+        output_json(&BaselineArcs {
+            created: total_created,
+            skipped: total_skipped,
+            entity_types: types_to_process.iter().map(|t| t.to_string()).collect(),
+        });
+    } else {
+        print_header("Baseline Arc Snapshots");
+        print_kv("Created", &total_created.to_string());
+        print_kv(
+            "Skipped (already had snapshots)",
+            &total_skipped.to_string(),
         );
+        if total_created > 0 {
+            print_success(&format!("Created {} baseline snapshots", total_created));
+        } else if total_skipped > 0 {
+            println!("  All entities already have snapshots.");
+        } else {
+            println!("  No entities with embeddings found. Run 'narra world backfill' first.");
+        }
+    }
 
-        let mut response = ctx.db.query(&fetch_query).await?;
+    Ok(())
+}
 
-        #[derive(Deserialize)]
-        struct EntityWithEmbedding {
-            id: surrealdb::RecordId,
-            embedding: Vec<f32>,
-        }
+// =============================================================================
+// Arc Snapshot Compaction
+// =============================================================================
 
-        let entities: Vec<EntityWithEmbedding> = response.take(0).unwrap_or_default();
+pub async fn handle_compact_arcs(
+    ctx: &AppContext,
+    keep_every: Option<usize>,
+    min_drift: Option<f32>,
+    keep_anchors: bool,
+    entity_type: Option<String>,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    if keep_every.is_none() && min_drift.is_none() {
+        anyhow::bail!("Choose a retention policy: --keep-every N and/or --min-drift D");
+    }
+    let policy = RetentionPolicy {
+        keep_every,
+        keep_anchors,
+        min_drift,
+        entity_type,
+    };
 
-        for entity in &entities {
-            let entity_id = entity.id.to_string();
+    let spinner = create_spinner("Compacting arc snapshots...");
+    let report = ArcCompactionService::new(ctx.db.clone())
+        .compact(&policy, dry_run)
+        .await;
+    spinner.finish_and_clear();
+    let report = report?;
 
-            // Check if snapshot already exists
-            let mut count_response = ctx
-                .db
-                .query("SELECT count() AS cnt FROM arc_snapshot WHERE entity_id = $eid GROUP ALL")
-                .bind(("eid", entity.id.clone()))
-                .await?;
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
 
-            #[derive(Deserialize)]
-            struct ArcCount {
-                cnt: i64,
-            }
+    print_header(if dry_run {
+        "Arc Compaction (dry run)"
+    } else {
+        "Arc Compaction"
+    });
+    print_kv("Entities", &report.entities.to_string());
+    print_kv("Examined", &report.examined.to_string());
+    print_kv("Kept", &report.kept.to_string());
+    print_kv("Removed", &report.removed.to_string());
+    print_kv(
+        "Reclaimed (est.)",
+        &indicatif::HumanBytes(report.reclaimed_bytes).to_string(),
+    );
+    if report.removed == 0 {
+        println!("  Nothing to remove under this policy.");
+    } else if dry_run {
+        println!("  Run without --dry-run to remove them.");
+    } else {
+        print_success(&format!("Removed {} arc snapshots", report.removed));
+    }
+    Ok(())
+}
 
-            let count: Option<ArcCount> = count_response.take(0).unwrap_or(None);
-            let existing = count.map(|c| c.cnt).unwrap_or(0);
+// =============================================================================
+// Infer events — group scenes imported one event apiece
+// =============================================================================
 
-            if existing > 0 {
-                total_skipped += 1;
-                continue;
+pub async fn handle_infer_events(
+    ctx: &AppContext,
+    threshold: f32,
+    accept_all: bool,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    if !(0.0..=1.0).contains(&threshold) {
+        anyhow::bail!("--threshold must be between 0 and 1");
+    }
+    let service = EventInferenceService::new(ctx.db.clone());
+    let spinner = create_spinner("Comparing scenes...");
+    let report = service.propose(threshold).await;
+    spinner.finish_and_clear();
+    let report = report?;
+
+    if dry_run || (mode == OutputMode::Json && !accept_all) {
+        if mode == OutputMode::Json {
+            output_json(&report);
+        } else {
+            print_header(&format!(
+                "{} event proposals ({} scenes on stand-in events)",
+                report.proposals.len(),
+                report.unanchored
+            ));
+            for proposal in &report.proposals {
+                print_proposal(proposal);
+            }
+            if report.singles > 0 {
+                print_hint(&format!(
+                    "{} scene(s) resemble neither neighbour and keep their own event",
+                    report.singles
+                ));
             }
+        }
+        return Ok(());
+    }
+    if report.proposals.is_empty() && mode != OutputMode::Json {
+        print_success("No scenes to group into events");
+        return Ok(());
+    }
+    if !accept_all && !std::io::stdin().is_terminal() {
+        anyhow::bail!("Reviewing proposals needs a terminal; use --yes or --dry-run");
+    }
 
-            // Create baseline snapshot
-            if let Err(e) = ctx
-                .db
-                .query(
-                    "CREATE arc_snapshot SET entity_id = $eid, entity_type = $etype, embedding = $embedding",
-                )
-                .bind(("eid", entity.id.clone()))
-                .bind(("etype", etype.to_string()))
-                .bind(("embedding", entity.embedding.clone()))
-                .await
-            {
-                eprintln!("Warning: Failed to create snapshot for {}: {}", entity_id, e);
-                continue;
+    let mut merged = Vec::new();
+    let mut rest = accept_all;
+    for (i, proposal) in report.proposals.iter().enumerate() {
+        let mut title = proposal.title.clone();
+        if !rest {
+            println!("\n[{}/{}]", i + 1, report.proposals.len());
+            print_proposal(proposal);
+            // None quits the review
+            let accept = loop {
+                print!("[y]es, [r]ename and accept, [n]o, [a]ll remaining, [q]uit (default n): ");
+                std::io::stdout().flush()?;
+                let mut answer = String::new();
+                std::io::stdin().lock().read_line(&mut answer)?;
+                match answer.trim() {
+                    "y" => break Some(true),
+                    "r" => {
+                        print!("Event title: ");
+                        std::io::stdout().flush()?;
+                        let mut answer = String::new();
+                        std::io::stdin().lock().read_line(&mut answer)?;
+                        if !answer.trim().is_empty() {
+                            title = answer.trim().to_string();
+                        }
+                        break Some(true);
+                    }
+                    "n" | "" => break Some(false),
+                    "a" => {
+                        rest = true;
+                        break Some(true);
+                    }
+                    "q" => break None,
+                    _ => println!("Please answer y, r, n, a, or q."),
+                }
+            };
+            match accept {
+                Some(true) => {}
+                Some(false) => continue,
+                None => break,
             }
+        }
 
-            total_created += 1;
+        let event_id = service.accept(proposal, &title).await?;
+        ctx.event_bus
+            .emit_entity(events::ENTITY_UPDATED, "cli", &event_id, "event", &title);
+        for scene in &proposal.scenes {
+            if scene.event_id != proposal.event_id {
+                ctx.event_bus.emit_entity(
+                    events::ENTITY_DELETED,
+                    "cli",
+                    &scene.event_id,
+                    "event",
+                    &scene.title,
+                );
+            }
         }
+        merged.push(serde_json::json!({
+            "event_id": event_id,
+            "title": title,
+            "scenes": proposal.scenes.len(),
+        }));
     }
 
     if mode == OutputMode::Json {
-        output_json(&serde_json::json!({
-            "created": total_created,
-            "skipped": total_skipped,
-            "entity_types": types_to_process,
-        }));
+        output_json(&merged);
     } else {
-        print_header("Baseline Arc Snapshots");
-        print_kv("Created", &total_created.to_string());
-        print_kv(
-            "Skipped (already had snapshots)",
-            &total_skipped.to_string(),
-        );
-        if total_created > 0 {
-            print_success(&format!("Created {} baseline snapshots", total_created));
-        } else if total_skipped > 0 {
-            println!("  All entities already have snapshots.");
-        } else {
-            println!("  No entities with embeddings found. Run 'narra world backfill' first.");
+        print_success(&format!(
+            "Merged {} of {} proposal(s) into events",
+            merged.len(),
+            report.proposals.len()
+        ));
+    }
+    Ok(())
+}
+
+fn print_proposal(proposal: &EventProposal) {
+    println!(
+        "  {} (sequence {}, cohesion {:.2})",
+        proposal.title.bold(),
+        proposal.sequence,
+        proposal.cohesion
+    );
+    if let Some(location) = &proposal.location {
+        println!("    at {}", location);
+    }
+    if !proposal.participants.is_empty() {
+        println!("    with {}", proposal.participants.join(", "));
+    }
+    for scene in &proposal.scenes {
+        println!("    - {} ({})", scene.title, scene.id);
+    }
+}
+
+// =============================================================================
+// Renumber — even out event sequence gaps
+// =============================================================================
+
+pub async fn handle_mentions(ctx: &AppContext, mode: OutputMode) -> Result<()> {
+    let spinner = create_spinner("Scanning prose for mentions...");
+    let outcome = MentionService::new(ctx.db.clone(), ctx.ner_service.clone())
+        .rebuild()
+        .await;
+    spinner.finish_and_clear();
+    let outcome = outcome?;
+
+    if mode == OutputMode::Json {
+        output_json(&outcome);
+    } else {
+        print_success(&format!(
+            "Found {} mention(s) in {} text(s)",
+            outcome.mentions, outcome.scanned
+        ));
+        if !ctx.ner_service.is_available() {
+            print_hint("NER model not loaded: only exact names and aliases were matched.");
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_renumber(
+    ctx: &AppContext,
+    spacing: i64,
+    dry_run: bool,
+    mode: OutputMode,
+) -> Result<()> {
+    let spinner = create_spinner("Renumbering events...");
+    let report = RenumberService::new(ctx.db.clone())
+        .renumber(spacing, dry_run)
+        .await;
+    spinner.finish_and_clear();
+    let report = report?;
+
+    if report.applied {
+        for change in &report.changes {
+            ctx.event_bus.emit_entity(
+                events::ENTITY_UPDATED,
+                "cli",
+                &change.event_id,
+                "event",
+                &change.title,
+            );
         }
     }
 
+    if mode == OutputMode::Json {
+        output_json(&report);
+        return Ok(());
+    }
+
+    print_header(if dry_run {
+        "Renumber (dry run)"
+    } else {
+        "Renumber"
+    });
+    if report.is_empty() {
+        println!("  Sequences are already {} apart.", spacing);
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = report
+        .changes
+        .iter()
+        .map(|c| {
+            vec![
+                c.title.clone(),
+                c.from.to_string(),
+                c.to.to_string(),
+                c.event_id.clone(),
+            ]
+        })
+        .collect();
+    print_table(&["Event", "From", "To", "ID"], rows);
+    print_kv("Thread deadlines", &report.threads.to_string());
+    print_kv("Fact bounds", &report.facts.to_string());
+    print_kv("Phases", &report.phases.to_string());
+    print_kv("Phase members", &report.phase_members.to_string());
+    if dry_run {
+        println!("  Run without --dry-run to apply.");
+    } else {
+        print_success(&format!("Renumbered {} events", report.changes.len()));
+    }
     Ok(())
 }
 
@@ -887,6 +2101,8 @@ pub async fn handle_benchmark(
         model: comparison_model.to_string(),
         cache_dir: None,
         show_download_progress: true,
+        device: ctx.config.embedding.device,
+        threads: ctx.config.embedding.threads,
     };
     let comp_service = create_embedding_service(&comp_config)
         .map_err(|e| anyhow::anyhow!("Failed to load comparison model: {}", e))?;
@@ -976,18 +2192,7 @@ pub async fn handle_benchmark(
     }
 
     // Per-query comparison
-    #[derive(Serialize)]
-    struct QueryResult {
-        query: String,
-        current_top_ids: Vec<String>,
-        comparison_top_ids: Vec<String>,
-        current_avg_score: f64,
-        comparison_avg_score: f64,
-        score_delta: f64,
-        rank_correlation: f64,
-    }
-
-    let mut results: Vec<QueryResult> = Vec::new();
+    let mut results: Vec<EmbeddingQueryComparison> = Vec::new();
 
     for q in &test_queries {
         // Embed query with both models
@@ -1055,7 +2260,7 @@ pub async fn handle_benchmark(
 
         let rank_corr = spearman_correlation(&curr_ranks, &comp_ranks);
 
-        results.push(QueryResult {
+        results.push(EmbeddingQueryComparison {
             query: q.clone(),
             current_top_ids: curr_top
                 .iter()
@@ -1085,22 +2290,22 @@ pub async fn handle_benchmark(
     let comp_latency_ms = comp_elapsed.as_millis() as f64 / entity_count as f64;
 
     if mode == OutputMode::Json {
-        output_json(&serde_json::json!({
-            "current_model": current_model,
-            "current_dimensions": current_dims,
-            "comparison_model": comparison_model,
-            "comparison_dimensions": comp_dims,
-            "entities_sampled": entity_count,
-            "queries": results,
-            "summary": {
-                "avg_current_score": avg_curr_score,
-                "avg_comparison_score": avg_comp_score,
-                "avg_score_delta": avg_score_delta,
-                "avg_rank_correlation": avg_rank_corr,
-                "current_latency_ms_per_entity": curr_latency_ms,
-                "comparison_latency_ms_per_entity": comp_latency_ms,
-            }
-        }));
+        output_json(&EmbeddingComparison {
+            current_model,
+            current_dimensions: current_dims,
+            comparison_model: comparison_model.to_string(),
+            comparison_dimensions: comp_dims,
+            entities_sampled: entity_count,
+            queries: results,
+            summary: EmbeddingComparisonSummary {
+                avg_current_score: avg_curr_score,
+                avg_comparison_score: avg_comp_score,
+                avg_score_delta,
+                avg_rank_correlation: avg_rank_corr,
+                current_latency_ms_per_entity: curr_latency_ms,
+                comparison_latency_ms_per_entity: comp_latency_ms,
+            },
+        });
         return Ok(());
     }
 
@@ -1192,6 +2397,7 @@ pub async fn handle_annotate(
     skip_themes: bool,
     skip_ner: bool,
     concurrency: usize,
+    incremental: bool,
     mode: crate::cli::OutputMode,
 ) -> Result<(), crate::NarraError> {
     use crate::services::{AnnotationPipeline, PipelineConfig};
@@ -1212,6 +2418,7 @@ pub async fn handle_annotate(
         run_themes: !skip_themes,
         run_ner: !skip_ner,
         concurrency,
+        incremental,
     };
 
     let pipeline = AnnotationPipeline::new(
@@ -1229,10 +2436,7 @@ pub async fn handle_annotate(
 
     match mode {
         crate::cli::OutputMode::Json => {
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&report).unwrap_or_default()
-            );
+            output_json(&report);
         }
         _ => {
             println!("\nAnnotation Pipeline Report");
@@ -1241,6 +2445,9 @@ pub async fn handle_annotate(
             println!("Emotion successes: {}", report.emotion_successes);
             println!("Theme successes: {}", report.theme_successes);
             println!("NER successes: {}", report.ner_successes);
+            if incremental {
+                println!("Unchanged (not re-run): {}", report.unchanged);
+            }
             if report.errors > 0 {
                 println!("Errors: {}", report.errors);
             }
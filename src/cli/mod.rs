@@ -2,13 +2,29 @@
 
 pub mod handlers;
 pub mod output;
+pub mod pipe;
 pub mod resolve;
+pub mod tui;
+pub mod watch;
 
 use clap::{CommandFactory, Parser, Subcommand};
 use std::path::PathBuf;
 
 use output::{DetailLevel, OutputMode};
 
+/// Exit code a command asks for without it being an error, e.g. `world
+/// audit` finding violations. `main` exits with it and prints nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitStatus(pub i32);
+
+impl std::fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "exit status {}", self.0)
+    }
+}
+
+impl std::error::Error for ExitStatus {}
+
 /// Narra - Narrative intelligence engine for fiction writing
 #[derive(Parser)]
 #[command(name = "narra", version, about, long_about = None)]
@@ -37,15 +53,68 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub no_semantic: bool,
 
+    /// Emit JSON-lines events to a Unix socket or file (appended)
+    #[arg(long, env = "NARRA_EVENTS", global = true, value_name = "SOCKET|FILE")]
+    pub events: Option<String>,
+
+    /// Never download models; features whose models aren't cached are disabled
+    #[arg(long, env = "NARRA_OFFLINE", global = true)]
+    pub offline: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+/// Print the static clap completion script plus dynamic entity-name glue.
+pub fn print_completions(shell: clap_complete::Shell) {
+    clap_complete::generate(shell, &mut Cli::command(), "narra", &mut std::io::stdout());
+    if let Some(script) = handlers::complete::dynamic_script(shell) {
+        print!("{}", script);
+    }
+}
+
+/// Space-separated subcommand path of parsed arguments (e.g., "world status").
+pub fn command_path(matches: &clap::ArgMatches) -> String {
+    let mut parts = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        parts.push(name);
+        current = sub;
+    }
+    parts.join(" ")
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Set up a project: data directory, embedding model, sample world, MCP client config
+    Init {
+        /// Embedding model (bge-small-en-v1.5, bge-base-en-v1.5, bge-large-en-v1.5,
+        /// multilingual-e5-small)
+        #[arg(long)]
+        model: Option<String>,
+        /// Manuscript language (ISO 639-1, e.g. de); non-English picks multilingual models
+        #[arg(long)]
+        language: Option<String>,
+        /// Import the sample noir world
+        #[arg(long, conflicts_with = "no_sample")]
+        sample: bool,
+        /// Skip the sample world
+        #[arg(long)]
+        no_sample: bool,
+        /// Accept defaults without prompting
+        #[arg(short, long)]
+        yes: bool,
+    },
+
     /// Start MCP server (stdio transport for Claude Code integration)
     Mcp,
 
+    /// Start language server (stdio) for manuscript editing: mentions, hovers, quick fixes
+    Lsp,
+
+    /// Interactive terminal dashboard (browse, search, edit descriptions)
+    Tui,
+
     /// Deep entity exploration (relationships, knowledge, perceptions, similar)
     Explore {
         /// Entity ID or name
@@ -99,6 +168,12 @@ pub enum Commands {
         /// Filter results to a specific narrative phase (run phase detection first)
         #[arg(long)]
         phase: Option<usize>,
+        /// Only return scenes, events, and notes carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Only return scenes at this draft status (outline, drafted, revised, final)
+        #[arg(long)]
+        status: Option<String>,
         #[command(subcommand)]
         subcommand: Option<FindCommands>,
     },
@@ -149,9 +224,9 @@ pub enum Commands {
 
     /// List entities of a given type
     List {
-        /// Entity type (character, location, event, scene, knowledge, relationship, fact, note)
+        /// Entity type (character, location, event, scene, knowledge, relationship, fact, note, phase, thread, term, goal, lexeme, foreshadows, requires)
         entity_type: String,
-        /// Filter by character (for knowledge, relationship)
+        /// Filter by character (for knowledge, relationship, goal)
         #[arg(long)]
         character: Option<String>,
         /// Filter by category (for facts)
@@ -163,6 +238,15 @@ pub enum Commands {
         /// Filter by attached entity (for notes)
         #[arg(long)]
         entity: Option<String>,
+        /// Filter by tag (for scenes, events, notes)
+        #[arg(long)]
+        tag: Option<String>,
+        /// Filter by kind (for notes: general, research, todo, critique)
+        #[arg(long)]
+        kind: Option<String>,
+        /// Filter by draft status (for scenes: outline, drafted, revised, final)
+        #[arg(long)]
+        status: Option<String>,
         /// Maximum results
         #[arg(long, default_value = "100")]
         limit: usize,
@@ -182,12 +266,47 @@ pub enum Commands {
         /// Set single field (key=value, repeatable)
         #[arg(long, value_parser = parse_key_val, action = clap::ArgAction::Append)]
         set: Vec<(String, String)>,
-        /// Link to entity (for facts: link fact to entity; for notes: attach note to entity)
+        /// Link to entity (for facts: link fact to entity; for notes: attach note to entity; for threads: add knowledge/fact element)
         #[arg(long)]
         link: Option<String>,
-        /// Unlink from entity (for facts: unlink; for notes: detach)
+        /// Unlink from entity (for facts: unlink; for notes: detach; for threads: remove element)
         #[arg(long)]
         unlink: Option<String>,
+        /// Add a character as a participant (events only)
+        #[arg(long)]
+        add_participant: Option<String>,
+        /// Remove a participating character (events only)
+        #[arg(long, conflicts_with = "add_participant")]
+        remove_participant: Option<String>,
+        /// Participant's role in the event (e.g., witness, instigator)
+        #[arg(long, requires = "add_participant")]
+        role: Option<String>,
+        /// How the event affected the participant
+        #[arg(long, requires = "add_participant")]
+        impact: Option<String>,
+        /// Change it even though scenes marked final reference it
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Rename an entity and find its old name in other entities' text
+    Rename {
+        /// Entity ID or name
+        entity: String,
+        /// The new name (title, for events and scenes)
+        new_name: String,
+        /// Replace the old name in the texts found without asking
+        #[arg(long, conflicts_with = "keep_text")]
+        replace: bool,
+        /// Only rename, leaving the texts as they are
+        #[arg(long)]
+        keep_text: bool,
+        /// Show the texts that name it without writing
+        #[arg(long)]
+        dry_run: bool,
+        /// Rename it even though scenes marked final reference it
+        #[arg(long)]
+        force: bool,
     },
 
     /// Delete entity
@@ -197,6 +316,9 @@ pub enum Commands {
         /// Hard delete (bypass protection)
         #[arg(long)]
         hard: bool,
+        /// Delete it even though scenes marked final reference it
+        #[arg(long)]
+        force: bool,
     },
 
     /// Narrative analytics and intelligence
@@ -207,17 +329,93 @@ pub enum Commands {
     #[command(subcommand)]
     World(WorldCommands),
 
-    /// Session management (context, pin, unpin)
+    /// Session management (context, pin, unpin, stats)
     #[command(subcommand)]
     Session(SessionCommands),
 
-    /// Batch-create entities from YAML (stdin or --file)
+    /// Narra configuration (webhooks)
+    #[command(subcommand)]
+    Config(ConfigCommands),
+
+    /// Encryption at rest for the embedded database
+    #[command(subcommand)]
+    Vault(VaultCommands),
+
+    /// Manage downloaded ML models (pull, list, remove)
+    #[command(subcommand)]
+    Models(ModelsCommands),
+
+    /// Diagnose the environment: model cache, database integrity, embeddings,
+    /// indexes, disk space, cold-start latency
+    Doctor,
+
+    /// Database schema version and upgrades (status, up)
+    #[command(subcommand)]
+    Migrate(MigrateCommands),
+
+    /// World backups (now, list, restore)
+    #[command(subcommand)]
+    Backup(BackupCommands),
+
+    /// Images attached to entities: portraits, maps (add, list, show, remove)
+    #[command(subcommand)]
+    Asset(AssetCommands),
+
+    /// Roles for shared worlds (whoami, list, grant, revoke)
+    #[command(subcommand)]
+    Access(AccessCommands),
+
+    /// Output templates for get, dossier, and compile (list, edit)
+    #[command(subcommand)]
+    Template(TemplateCommands),
+
+    /// Generate material for the world (names)
+    #[command(subcommand)]
+    Generate(GenerateCommands),
+
+    /// Saved narrative phases (rename)
+    #[command(subcommand)]
+    Phases(PhaseCommands),
+
+    /// Reading order and draft status of scenes (reorder, status)
+    #[command(subcommand)]
+    Scenes(ScenesCommands),
+
+    /// Freeform tags on scenes, events, and notes (add, remove, list)
+    #[command(subcommand)]
+    Tag(TagCommands),
+
+    /// Constructed-language lexicon (add, list, show, remove, import, check)
+    #[command(subcommand)]
+    Lexicon(LexiconCommands),
+
+    /// Project style sheet: spelling, capitalization, punctuation (add, list, remove)
+    #[command(subcommand)]
+    Style(StyleCommands),
+
+    /// Check a manuscript chunk against the style sheet (exit code 2 on issues)
+    Lint {
+        /// Markdown or text file; `-` reads stdin
+        file: PathBuf,
+    },
+
+    /// Batch-create entities from YAML, CSV, or JSONL (stdin or --file)
     Batch {
         /// Entity type: character, location, event, relationship
         entity_type: String,
         /// Read from file instead of stdin
         #[arg(long)]
         file: Option<String>,
+        /// Input format: yaml, csv, or jsonl (default: from the file extension, else yaml)
+        #[arg(long)]
+        format: Option<String>,
+        /// CSV column mapping, e.g. "name=Full Name,profile.wound=Backstory".
+        /// Unmapped fields use a header of the same name.
+        #[arg(long)]
+        map: Option<String>,
+        /// Check every row and report errors without creating anything
+        #[arg(long)]
+        validate: bool,
     },
 
     /// Generate shell completions
@@ -226,6 +424,16 @@ pub enum Commands {
         shell: clap_complete::Shell,
     },
 
+    /// Dynamic completion helper used by the generated shell scripts
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// What to complete: entity, character, location, event, scene
+        kind: String,
+        /// Prefix typed so far
+        #[arg(default_value = "")]
+        prefix: String,
+    },
+
     // =========================================================================
     // Legacy subcommands kept for backward compatibility (hidden)
     // =========================================================================
@@ -254,7 +462,7 @@ pub enum Commands {
     Relationship(RelationshipCommands),
 
     /// Universe fact management
-    #[command(subcommand, hide = true)]
+    #[command(subcommand, hide = true, alias = "facts")]
     Fact(FactCommands),
 
     /// Note management
@@ -277,11 +485,17 @@ pub enum Commands {
     Export {
         #[arg(long, short)]
         output: Option<PathBuf>,
+        #[arg(long)]
+        include_derived: bool,
     },
 
     /// Validate entity consistency
     #[command(hide = true)]
-    Validate { entity_id: Option<String> },
+    Validate {
+        entity_id: Option<String>,
+        #[arg(long, conflicts_with = "entity_id")]
+        fix_reciprocals: bool,
+    },
 
     /// Import world data from YAML
     #[command(hide = true)]
@@ -291,6 +505,8 @@ pub enum Commands {
         on_conflict: String,
         #[arg(long)]
         dry_run: bool,
+        #[arg(long)]
+        include_derived: bool,
     },
 
     /// Generate relationship graph
@@ -324,6 +540,9 @@ pub enum CreateCommands {
         /// Profile as JSON object (e.g. '{"wound": ["..."], "secret": ["..."]}')
         #[arg(long)]
         profile: Option<String>,
+        /// Create it even if an existing character looks like the same one
+        #[arg(long)]
+        allow_similar: bool,
     },
     /// Create a new location
     Location {
@@ -346,6 +565,9 @@ pub enum CreateCommands {
         sequence: Option<i32>,
         #[arg(long)]
         date: Option<String>,
+        /// backstory (before the story), on_page or planned
+        #[arg(long, default_value = "on_page")]
+        status: String,
     },
     /// Create a new scene
     Scene {
@@ -364,8 +586,10 @@ pub enum CreateCommands {
         character: String,
         #[arg(long)]
         fact: String,
-        #[arg(long, default_value = "knows")]
-        certainty: String,
+        /// knows, suspects, believes_wrongly, ..., or derive (the default with
+        /// --source: from the source's certainty and the character's trust in them)
+        #[arg(long)]
+        certainty: Option<String>,
         #[arg(long)]
         method: Option<String>,
         #[arg(long)]
@@ -385,6 +609,25 @@ pub enum CreateCommands {
         subtype: Option<String>,
         #[arg(long)]
         label: Option<String>,
+        /// Also seed both characters' perceptions of each other from the type
+        #[arg(long)]
+        with_perceptions: bool,
+    },
+    /// Create all kinship relationships for parents and their children
+    /// (spouses, parent/child, siblings, and in-laws from existing spouses)
+    Family {
+        /// Parent character IDs (comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        parents: Vec<String>,
+        /// Child character IDs (comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        children: Vec<String>,
+        /// Don't make the parents each other's spouses
+        #[arg(long)]
+        no_spouse: bool,
+        /// Show the relationships that would be created without creating them
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Record a character's perception of another
     Perception {
@@ -404,6 +647,9 @@ pub enum CreateCommands {
         subtype: Option<String>,
         #[arg(long)]
         history: Option<String>,
+        /// How far the observer trusts the target (0.0-1.0)
+        #[arg(long)]
+        trust: Option<f32>,
     },
     /// Create a universe fact
     Fact {
@@ -415,6 +661,15 @@ pub enum CreateCommands {
         categories: Vec<String>,
         #[arg(long)]
         enforcement: Option<String>,
+        /// Only enforce within this location and the locations inside it
+        #[arg(long)]
+        location: Option<String>,
+        /// Only enforce from this event (ID or sequence number) on
+        #[arg(long)]
+        from: Option<String>,
+        /// Only enforce up to this event (ID or sequence number)
+        #[arg(long)]
+        until: Option<String>,
     },
     /// Create a note
     Note {
@@ -422,9 +677,95 @@ pub enum CreateCommands {
         title: String,
         #[arg(long)]
         body: String,
+        /// general (default), research, todo, or critique (needs a scene in --attach-to)
+        #[arg(long)]
+        kind: Option<String>,
         #[arg(long, value_delimiter = ',')]
         attach_to: Vec<String>,
     },
+    /// Record that a scene, event or knowledge entry foreshadows a later payoff
+    Foreshadow {
+        /// The earlier hint (scene:<id>, event:<id> or knowledge:<id>)
+        #[arg(long)]
+        setup: String,
+        /// What it pays off
+        #[arg(long)]
+        payoff: String,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Record that a scene relies on an earlier scene
+    Requires {
+        /// The dependent scene (scene:<id>)
+        #[arg(long)]
+        scene: String,
+        /// The scene that must come before it
+        #[arg(long)]
+        prerequisite: String,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Register a plot thread
+    Thread {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        description: Option<String>,
+        /// open, resolved or abandoned
+        #[arg(long, default_value = "open")]
+        status: String,
+        /// Where the thread is set up (event:<id> or scene:<id>)
+        #[arg(long)]
+        setup: Option<String>,
+        /// Where the thread pays off (event:<id> or scene:<id>)
+        #[arg(long)]
+        payoff: Option<String>,
+        /// Knowledge or facts the thread is about (comma-separated IDs)
+        #[arg(long, value_delimiter = ',')]
+        elements: Vec<String>,
+        /// Deadline: an event sequence (40) or a phase label ("Act 3")
+        #[arg(long)]
+        resolve_by: Option<String>,
+    },
+    /// Define a glossary term (jargon, spell, slang)
+    Term {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        definition: String,
+        /// What sort of term: jargon, spell, slang, ...
+        #[arg(long)]
+        kind: Option<String>,
+        /// Other names for the term (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        aliases: Vec<String>,
+        /// Event where the term is introduced (event:<id>)
+        #[arg(long)]
+        first_use: Option<String>,
+    },
+    /// Give a character a goal (what they want, need, risk and face)
+    Goal {
+        #[arg(long)]
+        character: String,
+        /// What the character consciously wants
+        #[arg(long)]
+        want: String,
+        /// What they actually need
+        #[arg(long)]
+        need: Option<String>,
+        /// What they lose if they fail
+        #[arg(long)]
+        stakes: Option<String>,
+        /// What stands in their way
+        #[arg(long)]
+        obstacle: Option<String>,
+        /// What the goal is about (character, location, knowledge or event ID)
+        #[arg(long)]
+        target: Option<String>,
+        /// active, achieved, failed or abandoned
+        #[arg(long, default_value = "active")]
+        status: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -503,7 +844,11 @@ pub enum FindCommands {
 #[derive(Subcommand)]
 pub enum WorldCommands {
     /// World overview dashboard (entity counts, embedding coverage)
-    Status,
+    Status {
+        /// Re-render whenever the world changes (remote databases only)
+        #[arg(long)]
+        watch: bool,
+    },
     /// Embedding health report
     Health,
     /// Backfill embeddings for all or specific entity types
@@ -515,11 +860,27 @@ pub enum WorldCommands {
         #[arg(long)]
         force: bool,
     },
+    /// Rebuild ANN indexes on embedding fields per `[vector_index]` config and report query latency before and after
+    ReindexVectors,
     /// Export world data to YAML
     Export {
         /// Output file path (defaults to ./narra-export-{date}.yaml)
         #[arg(long, short)]
         output: Option<PathBuf>,
+        /// Also write embeddings, arc snapshots, and annotations to a
+        /// `<name>.derived.gz` sidecar
+        #[arg(long)]
+        include_derived: bool,
+        /// Export only this character's neighborhood (e.g., character:alice)
+        #[arg(long)]
+        scope: Option<String>,
+        /// Relationship hops from --scope to include
+        #[arg(long, default_value = "1", requires = "scope")]
+        depth: usize,
+        /// Strip content before sharing: notes, secrets (profile keys from
+        /// `[export] secret_keys`), knowledge
+        #[arg(long, value_delimiter = ',')]
+        redact: Vec<String>,
     },
     /// Import world data from a YAML file
     Import {
@@ -531,11 +892,135 @@ pub enum WorldCommands {
         /// Parse and show entity counts without writing to database
         #[arg(long)]
         dry_run: bool,
+        /// Also restore the `<name>.derived.gz` sidecar written by
+        /// `export --include-derived`
+        #[arg(long)]
+        include_derived: bool,
+        /// Import only this character's neighborhood from the file
+        #[arg(long)]
+        scope: Option<String>,
+        /// Relationship hops from --scope to include
+        #[arg(long, default_value = "1", requires = "scope")]
+        depth: usize,
+        /// Ask how to resolve each entity that already exists with different fields
+        #[arg(long, conflicts_with = "dry_run")]
+        interactive: bool,
+        /// Apply the resolutions recorded in a resolution log
+        #[arg(long, value_name = "LOG")]
+        replay: Option<PathBuf>,
+        /// Write how each conflict was resolved to this file (JSON), for --replay
+        #[arg(long, value_name = "LOG")]
+        resolution_log: Option<PathBuf>,
+    },
+    /// Import characters, locations, or events from a CSV file
+    ImportCsv {
+        /// Path to CSV file (header row required)
+        file: PathBuf,
+        /// Entity type: characters, locations, or events (guessed from the file name if omitted)
+        #[arg(long, name = "type")]
+        entity_type: Option<String>,
+        /// Column mapping, e.g. "name=Name,role=Role,profile.wound=Backstory".
+        /// Unmapped fields use a column of the same name.
+        #[arg(long)]
+        map: Option<String>,
+        /// Conflict resolution: error, skip, or update
+        #[arg(long, default_value = "error")]
+        on_conflict: String,
+        /// Parse and show entity counts without writing to database
+        #[arg(long)]
+        dry_run: bool,
+        /// Ask how to resolve each entity that already exists with different fields
+        #[arg(long, conflicts_with = "dry_run")]
+        interactive: bool,
+    },
+    /// Import a screenplay: scene headings become locations and scenes,
+    /// character cues become characters, and dialogue is stored per scene
+    ImportScript {
+        /// Path to the script
+        file: PathBuf,
+        /// Script format: fountain or fdx (guessed from the extension if omitted)
+        #[arg(long)]
+        format: Option<String>,
+        /// Conflict resolution: error, skip, or update
+        #[arg(long, default_value = "error")]
+        on_conflict: String,
+        /// Parse and show entity counts without writing to database
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export characters, locations, or events to a CSV file
+    ExportCsv {
+        /// Entity type: characters, locations, or events
+        #[arg(long, name = "type")]
+        entity_type: String,
+        /// Column mapping (same syntax as import-csv) to rename headers
+        #[arg(long)]
+        map: Option<String>,
+        /// Output file path (defaults to ./{type}.csv)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Compile a formatted world bible (characters, locations, timeline, facts)
+    Compile {
+        /// Output format: epub, pdf, html, or md
+        #[arg(long, default_value = "html")]
+        format: String,
+        /// Output file path (defaults to ./world-bible.{format})
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Title on the cover page
+        #[arg(long, default_value = "World Bible")]
+        title: String,
+    },
+    /// Draft progress: word counts, per-act distribution, target and history
+    Progress {
+        /// Set the word target for the whole draft
+        #[arg(long)]
+        target: Option<i64>,
+        /// Remove the word target
+        #[arg(long, conflicts_with = "target")]
+        clear_target: bool,
+        /// Count words in manuscript files (named after scene keys or titles) first
+        #[arg(long, value_name = "DIR")]
+        ingest: Option<PathBuf>,
+        /// Don't record a history snapshot for this run
+        #[arg(long)]
+        no_record: bool,
+        /// Break scenes down by draft status (outline, drafted, revised, final)
+        #[arg(long)]
+        by_status: bool,
+    },
+    /// Authored operation log for reconciling offline co-authors (experimental)
+    #[command(subcommand)]
+    Oplog(OplogCommands),
+    /// Audit the whole world for consistency violations (exit code 2 on
+    /// violations at or above --fail-on, 3 on critical ones)
+    Audit {
+        /// Checks to run: facts, timeline, relationships (default: all)
+        #[arg(long = "check", value_name = "CHECK")]
+        checks: Vec<String>,
+        /// Lowest severity that fails the audit: critical, warning, info, or never
+        #[arg(long, default_value = "warning")]
+        fail_on: String,
+    },
+    /// Check database integrity: ID formats, edge endpoints, embedding sizes,
+    /// orphaned annotations and snapshots (exit code 2 on unresolved problems)
+    Fsck {
+        /// Repair what is safe to repair: delete dangling edges and orphaned
+        /// derived data, clear mis-sized embeddings for backfill
+        #[arg(long)]
+        fix: bool,
     },
     /// Validate entity consistency
     Validate {
         /// Entity ID (omit for general check)
         entity_id: Option<String>,
+        /// Create missing reverse relationships (mentor -> student, ...)
+        #[arg(long, conflicts_with = "entity_id")]
+        fix_reciprocals: bool,
+        /// Re-check whenever the world changes (remote databases only)
+        #[arg(long, conflicts_with = "fix_reciprocals")]
+        watch: bool,
     },
     /// Generate relationship graph (Mermaid format)
     Graph {
@@ -558,6 +1043,47 @@ pub enum WorldCommands {
         #[arg(long, name = "type")]
         entity_type: Option<String>,
     },
+    /// Prune arc snapshots with a retention policy (first and latest are always kept)
+    CompactArcs {
+        /// Keep every Nth snapshot of each entity and drop the rest
+        #[arg(long)]
+        keep_every: Option<usize>,
+        /// Drop snapshots that drifted less than this from the last kept one
+        #[arg(long)]
+        min_drift: Option<f32>,
+        /// Always keep snapshots anchored to an event
+        #[arg(long)]
+        keep_anchors: bool,
+        /// Snapshot type filter (character, knowledge, perspective, relationship)
+        #[arg(long, name = "type")]
+        entity_type: Option<String>,
+        /// Report what would be removed without deleting
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Propose events for scenes imported one event apiece, grouping alike neighbours
+    InferEvents {
+        /// Similarity (0-1) a scene needs to join its neighbours
+        #[arg(long, default_value = "0.5")]
+        threshold: f32,
+        /// Accept every proposal without asking
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// List proposals without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Renumber events evenly while keeping their order (updates every stored sequence)
+    Renumber {
+        /// Gap between consecutive event sequences
+        #[arg(long, default_value = "10")]
+        spacing: i64,
+        /// Report what would change without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rebuild prose mention edges from profiles, descriptions and summaries
+    Mentions,
     /// Compare embedding model quality (re-embeds a sample with a comparison model)
     Benchmark {
         /// Comparison model name (default: bge-large-en-v1.5)
@@ -590,6 +1116,9 @@ pub enum WorldCommands {
         /// Max concurrency
         #[arg(long, default_value = "4")]
         concurrency: usize,
+        /// Re-run only the models whose input text changed since their last run
+        #[arg(long)]
+        incremental: bool,
     },
 }
 
@@ -601,56 +1130,466 @@ pub enum SessionCommands {
     Pin {
         /// Entity ID or name
         entity: String,
+        /// low, normal, or high; higher pins rank first in context
+        #[arg(long, default_value = "normal")]
+        priority: String,
+        /// Event the pin lasts until (e.g., event:duel); it expires once
+        /// work moves past that event
+        #[arg(long)]
+        until: Option<String>,
     },
     /// Unpin an entity from session context
     Unpin {
         /// Entity ID or name
         entity: String,
     },
+    /// Journal what happened this session (e.g., "drafted ch 7 confrontation")
+    Log {
+        message: String,
+        /// Entities the work touched (IDs or names; defaults to recently accessed ones)
+        #[arg(long, value_delimiter = ',')]
+        entities: Vec<String>,
+    },
+    /// Show session journal entries, most recent first
+    History {
+        /// Maximum entries to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Show what you've focused on lately and what you've neglected
+    Stats {
+        /// Maximum entities per list
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
 }
 
-// =============================================================================
-// Analyze commands (unchanged from original)
-// =============================================================================
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Manage webhooks fired after successful mutations
+    #[command(subcommand)]
+    Webhook(WebhookCommands),
+}
 
 #[derive(Subcommand)]
-pub enum AnalyzeCommands {
-    /// Network centrality metrics (degree, betweenness)
-    Centrality {
+pub enum VaultCommands {
+    /// Show whether this world is encrypted
+    Status,
+    /// Encrypt the world with a passphrase and delete the unencrypted database
+    Encrypt {
+        /// Also store the passphrase in the OS keyring
         #[arg(long)]
-        scope: Option<String>,
-        #[arg(long, default_value = "20")]
-        limit: usize,
+        keyring: bool,
     },
-    /// Trace influence propagation from a character
-    Influence {
-        character: String,
-        #[arg(long, default_value = "3")]
-        depth: usize,
+    /// Decrypt the world back to a regular database
+    Decrypt,
+    /// Store the passphrase in the OS keyring so commands don't prompt
+    Keyring {
+        /// Remove the stored passphrase instead
+        #[arg(long)]
+        forget: bool,
     },
-    /// Dramatic irony report (knowledge asymmetries)
-    Irony {
+}
+
+#[derive(Subcommand)]
+pub enum MigrateCommands {
+    /// Show the schema version and any pending migrations
+    Status,
+    /// Apply pending migrations
+    Up {
+        /// List the migrations that would run without applying them
         #[arg(long)]
-        character: Option<String>,
-        #[arg(long, default_value = "3")]
-        threshold: usize,
+        dry_run: bool,
     },
-    /// Knowledge asymmetries between a specific pair
-    Asymmetries {
-        character_a: String,
-        character_b: String,
+}
+
+#[derive(Subcommand)]
+pub enum BackupCommands {
+    /// Take a backup of the world now
+    Now {
+        /// Why the backup is being taken (shown in `narra backup list`)
+        #[arg(long, default_value = "manual")]
+        reason: String,
     },
-    /// BelievesWrongly knowledge conflicts
-    Conflicts {
+    /// List backups, newest first, with checksum verification
+    List,
+    /// Replace the world with a backup (a pre-restore backup is taken first)
+    Restore {
+        /// Backup name, unique name prefix, or "latest"
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AssetCommands {
+    /// Copy an image (png, jpg, gif, webp, svg) into the world
+    Add {
+        /// Image file to add
+        file: PathBuf,
+        /// Entity the image belongs to (ID or name)
         #[arg(long)]
-        character: Option<String>,
-        #[arg(long, default_value = "50")]
-        limit: usize,
+        attach_to: Option<String>,
+        #[arg(long)]
+        caption: Option<String>,
     },
-    /// Unresolved perception tensions
-    Tensions {
+    /// List assets, optionally only those attached to one entity
+    List {
+        /// Entity ID or name
+        #[arg(long)]
+        entity: Option<String>,
+    },
+    /// Show an asset's metadata and where its file is stored
+    Show { id: String },
+    /// Delete an asset (its file goes once no other asset shares it)
+    Remove { id: String },
+}
+
+#[derive(Subcommand)]
+pub enum AccessCommands {
+    /// Show the current user and role
+    Whoami,
+    /// List access grants
+    List,
+    /// Give a user a role (owner, editor, reader), replacing any earlier grant
+    Grant {
+        /// Database username (remote worlds) or author name
+        user: String,
+        #[arg(long)]
+        role: String,
+        /// Limit an editor to these entity types (e.g., character, note); repeatable
+        #[arg(long = "type")]
+        entity_types: Vec<String>,
+    },
+    /// Remove a user's grant
+    Revoke { user: String },
+}
+
+#[derive(Subcommand)]
+pub enum GenerateCommands {
+    /// Character names that fit the world and don't collide with the cast
+    Names {
+        /// Built-in corpus to learn from (nordic, latin, slavic, celtic, japanese, greek);
+        /// without it, names are learned from the existing cast only
+        #[arg(long)]
+        culture: Option<String>,
+        #[arg(long, default_value = "10")]
+        count: usize,
+        /// Reject names at or above this edit similarity (0-1) to any cast name
+        #[arg(long, default_value = "0.75")]
+        max_similarity: f64,
+        /// Seed for reproducible output
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PhaseCommands {
+    /// Give a saved phase a custom label, kept across re-detection
+    Rename {
+        /// Phase ID (phase:phase_2) or number (see `narra list phase`)
+        id: String,
+        /// New label, e.g. "Act II: The Siege"
+        label: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ScenesCommands {
+    /// Rewrite event sequences to a new reading order, edited in $EDITOR or
+    /// read from a file (one scene or event ID per line)
+    Reorder {
+        /// Order file; `-` reads stdin (default: edit the current order in $EDITOR)
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Print the current order in the file format and exit
+        #[arg(long, conflicts_with = "file")]
+        print: bool,
+        /// Show the new sequences without writing them
+        #[arg(long)]
+        dry_run: bool,
+        /// Apply even if scenes would come before scenes they depend on
+        #[arg(long)]
+        force: bool,
+    },
+    /// Move scenes to a draft status (outline, drafted, revised, final)
+    Status {
+        /// New draft status
+        status: String,
+        /// Scene IDs to move
+        scenes: Vec<String>,
+        /// Also move every scene currently at this status
+        #[arg(long)]
+        from: Option<String>,
+        /// Also move every scene with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// List the scenes that would move without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// Tag a scene, event, or note
+    Add {
+        /// Entity ID (scene:opening, event:betrayal, note:abc)
+        entity: String,
+        /// Tags to add, e.g. action flashback b-plot
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove tags from a scene, event, or note
+    Remove {
+        /// Entity ID (scene:opening, event:betrayal, note:abc)
+        entity: String,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Tags on one entity, or usage counts for every tag
+    List {
+        /// Entity ID; omit for world-wide tag statistics
+        entity: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LexiconCommands {
+    /// Add a word with its gloss
+    Add {
+        word: String,
+        /// What the word means
+        #[arg(long)]
+        gloss: String,
+        /// Part of speech (noun, verb, ...)
+        #[arg(long)]
+        pos: Option<String>,
+        #[arg(long)]
+        etymology: Option<String>,
+        /// Constructed language the word belongs to
+        #[arg(long)]
+        language: Option<String>,
+    },
+    /// List words, optionally for one language
+    List {
+        #[arg(long)]
+        language: Option<String>,
+    },
+    /// Show a word (by word or lexeme:<id>)
+    Show {
+        word: String,
+        #[arg(long)]
+        language: Option<String>,
+    },
+    /// Remove a word (by word or lexeme:<id>)
+    Remove {
+        word: String,
+        #[arg(long)]
+        language: Option<String>,
+    },
+    /// Import words from CSV (word, gloss, part_of_speech, etymology, language);
+    /// existing words are updated
+    Import {
+        file: PathBuf,
+        /// Language for rows without a language column
+        #[arg(long)]
+        language: Option<String>,
+    },
+    /// Flag words whose glosses in notes drift from the lexicon
+    Check,
+}
+
+#[derive(Subcommand)]
+pub enum StyleCommands {
+    /// Add a rule
+    Add {
+        /// spelling, capitalization, or punctuation
+        kind: String,
+        /// The form to use (grey, the Weave, …)
+        preferred: String,
+        /// Forms to flag (comma-separated); required for spelling and punctuation
+        #[arg(long, value_delimiter = ',')]
+        avoid: Vec<String>,
+        /// Why, shown with each finding
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// List the style sheet
+    List,
+    /// Remove a rule
+    Remove { id: String },
+}
+
+#[derive(Subcommand)]
+pub enum TemplateCommands {
+    /// List templates and whether a built-in or an override is in use
+    List,
+    /// Open a template in $EDITOR, copying the built-in first if needed
+    Edit {
+        /// Template name (see `narra template list`)
+        name: String,
+        /// Edit the override for every world (~/.narra/templates) instead of this one
+        #[arg(long)]
+        user: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ModelsCommands {
+    /// Download the models narra loads at startup, for offline use
+    Pull {
+        /// Also download the embedding models not currently configured
+        #[arg(long)]
+        all: bool,
+    },
+    /// Show known models, whether they're cached, their size, and what needs them
+    List,
+    /// Delete cached models to free disk space
+    Remove {
+        /// Model names (see `narra models list`)
+        #[arg(required_unless_present = "unused")]
+        names: Vec<String>,
+        /// Remove every cached model the current configuration doesn't load
+        #[arg(long, conflicts_with = "names")]
+        unused: bool,
+        /// Allow removing models in use (they re-download on next start)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Measure embedding throughput on each device (cpu, cuda, metal)
+    Bench {
+        /// Devices to try (defaults to every one this build supports)
+        #[arg(long, value_delimiter = ',')]
+        device: Vec<String>,
+        /// Texts embedded per device
+        #[arg(long, default_value = "256")]
+        texts: usize,
+        /// Texts per batch
+        #[arg(long, default_value = "32")]
+        batch: usize,
+        /// CPU inference threads
+        #[arg(long)]
+        threads: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum OplogCommands {
+    /// Write logged operations as JSON lines
+    Export {
+        /// Only operations after this RFC 3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+        /// Output file (defaults to stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Merge another writer's exported operations (last writer wins per entity)
+    Import {
+        /// JSON-lines file written by `oplog export`
+        file: PathBuf,
+        /// Show what would change without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WebhookCommands {
+    /// Register a webhook
+    Add {
+        /// Endpoint URL receiving POSTed JSON events
+        url: String,
+        /// Secret for HMAC-SHA256 signing (X-Narra-Signature header)
+        #[arg(long)]
+        secret: Option<String>,
+        /// Only deliver these events (e.g., entity.created); repeatable
+        #[arg(long = "event")]
+        events: Vec<String>,
+        /// Only deliver events for these entity types (e.g., character); repeatable
+        #[arg(long = "entity-type")]
+        entity_types: Vec<String>,
+    },
+    /// List registered webhooks
+    List,
+    /// Remove a webhook by ID or URL
+    Remove {
+        /// Webhook ID or URL
+        id: String,
+    },
+}
+
+// =============================================================================
+// Analyze commands (unchanged from original)
+// =============================================================================
+
+#[derive(Subcommand)]
+pub enum AnalyzeCommands {
+    /// Network centrality metrics (degree, betweenness)
+    Centrality {
+        #[arg(long)]
+        scope: Option<String>,
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Only consider members of this narrative phase (see `narra list phase`)
+        #[arg(long)]
+        phase: Option<usize>,
+    },
+    /// Trace influence propagation from a character
+    Influence {
+        #[arg(required_unless_present = "stdin")]
+        character: Option<String>,
+        #[arg(long, default_value = "3")]
+        depth: usize,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "character")]
+        stdin: bool,
+    },
+    /// Dramatic irony report (knowledge asymmetries)
+    Irony {
+        #[arg(long)]
+        character: Option<String>,
+        #[arg(long, default_value = "3")]
+        threshold: usize,
+        /// Only consider members of this narrative phase (see `narra list phase`)
+        #[arg(long)]
+        phase: Option<usize>,
+    },
+    /// Knowledge asymmetries between a specific pair
+    Asymmetries {
+        character_a: String,
+        character_b: String,
+    },
+    /// BelievesWrongly knowledge conflicts
+    Conflicts {
+        #[arg(long)]
+        character: Option<String>,
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// Who knows what: characters × facts grid of certainties
+    KnowledgeMatrix {
+        /// Characters to include (comma-separated IDs or names; default: all)
+        #[arg(long, value_delimiter = ',')]
+        characters: Vec<String>,
+        /// Maximum facts (the most widely known first)
+        #[arg(long, default_value = "30")]
+        limit: usize,
+        /// Write the grid as CSV to this file
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Unresolved perception tensions
+    Tensions {
         #[arg(long, default_value = "20")]
         limit: usize,
+        /// Only consider members of this narrative phase (see `narra list phase`)
+        #[arg(long)]
+        phase: Option<usize>,
+        /// Re-run whenever the world changes (remote databases only)
+        #[arg(long)]
+        watch: bool,
     },
     /// Most-changed entities by arc drift
     ArcDrift {
@@ -667,6 +1606,109 @@ pub enum AnalyzeCommands {
         /// Number of clusters (auto if not specified)
         #[arg(long)]
         clusters: Option<usize>,
+        /// RNG seed; the same seed always gives the same clusters
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Only consider members of this narrative phase (see `narra list phase`)
+        #[arg(long)]
+        phase: Option<usize>,
+    },
+    /// Character page time: who vanishes, who hogs scenes
+    Spotlight {
+        /// Flag major characters absent for this many consecutive scenes
+        #[arg(long, default_value = "8")]
+        max_gap: usize,
+        /// Flag minor characters appearing in more than this share of scenes
+        #[arg(long, default_value = "0.3")]
+        minor_share: f32,
+        /// Scenes after which an appearance counts half as much
+        #[arg(long, default_value = "10")]
+        half_life: f32,
+        /// Only show flagged characters
+        #[arg(long)]
+        flagged: bool,
+    },
+    /// Opposition a protagonist faces per phase: rivals, antagonists, strict facts
+    Pressure {
+        /// Protagonist (ID or name)
+        protagonist: String,
+        /// Flag phases where fewer of the protagonist's scenes carry opposition (0-1)
+        #[arg(long, default_value = "0.25")]
+        min_share: f64,
+    },
+    /// Centrality against page time: who is under- or over-shown, with scenes to adjust
+    Balance {
+        /// Flag characters whose centrality and screen-time ranks differ by this much (0-1)
+        #[arg(long, default_value = "0.3")]
+        threshold: f64,
+        /// Scene suggestions per flagged character
+        #[arg(long, default_value = "3")]
+        suggestions: usize,
+        /// Only consider members of this narrative phase (see `narra list phase`)
+        #[arg(long)]
+        phase: Option<usize>,
+        /// Only show flagged characters
+        #[arg(long)]
+        flagged: bool,
+    },
+    /// Active goals at odds between characters who share scenes
+    GoalConflicts {
+        /// Only conflicts among these characters (IDs or names, comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        characters: Vec<String>,
+    },
+    /// Characters readers may mix up: look-alike names or near-duplicate profiles
+    Confusability {
+        /// Flag names at or above this edit similarity (0.0-1.0)
+        #[arg(long, default_value = "0.75")]
+        name_similarity: f64,
+        /// Flag profiles at or above this embedding similarity (0.0-1.0)
+        #[arg(long, default_value = "0.9")]
+        profile_similarity: f32,
+    },
+    /// What the reader knows at each scene versus what POV characters know
+    ReaderKnowledge {
+        /// Also flag POV characters' backstory knowledge never shown on the page
+        #[arg(long)]
+        backstory: bool,
+        /// Only list the gaps, not the scene-by-scene walk
+        #[arg(long)]
+        gaps_only: bool,
+    },
+    /// What breaks if a scene is moved to another position in reading order
+    Reorder {
+        /// Scene to move (scene:<id>)
+        #[arg(long = "move")]
+        scene: String,
+        /// Target position in reading order (1-based)
+        #[arg(long)]
+        to: usize,
+    },
+    /// Chekhov's gun: unfired setups and orphaned payoffs
+    Setups {
+        /// Maximum unfired setups to show
+        #[arg(long, default_value = "30")]
+        limit: usize,
+    },
+    /// Glossary terms used in scene summaries before their first use
+    Terminology,
+    /// Threads past (or near) their resolve-by deadline
+    ThreadDeadlines {
+        /// Check at this event sequence instead of the current writing position
+        #[arg(long)]
+        at: Option<i64>,
+        /// Flag open threads due within this many sequence steps
+        #[arg(long, default_value = "5")]
+        window: i64,
+        /// Only show flagged threads
+        #[arg(long)]
+        flagged: bool,
+    },
+    /// Foreshadowing: payoffs without an earlier setup
+    Foreshadowing {
+        /// Include payoffs whose setups check out
+        #[arg(long)]
+        all: bool,
     },
     /// Missing entity types in thematic clusters
     ThematicGaps {
@@ -685,13 +1727,22 @@ pub enum AnalyzeCommands {
         #[arg(long)]
         event: Option<String>,
     },
-    /// Investigate contradictions across connected entities
+    /// Investigate contradictions across connected entities, or between
+    /// universe facts themselves (--facts)
     Contradictions {
         /// Entity (ID or name)
-        entity: String,
+        #[arg(required_unless_present = "facts")]
+        entity: Option<String>,
         /// Graph traversal depth
         #[arg(long, default_value = "3")]
         depth: usize,
+        /// Check universe facts against each other instead of an entity
+        #[arg(long, conflicts_with = "entity")]
+        facts: bool,
+        /// Similarity at or above which two facts share a topic (default:
+        /// 0.7 by embedding, 0.25 term overlap without a model)
+        #[arg(long)]
+        similarity: Option<f32>,
     },
     /// Perception gap: how wrong is observer about target
     PerceptionGap {
@@ -715,13 +1766,27 @@ pub enum AnalyzeCommands {
         /// Target character (ID or name)
         target: String,
     },
+    /// Relationship sentiment: how observer feels about target over the story
+    RelationshipSentiment {
+        /// Observer character (ID or name)
+        observer: String,
+        /// Target character (ID or name)
+        target: String,
+        /// Valence change between neighbouring points that counts as a swing
+        #[arg(long, default_value = "0.2")]
+        min_change: f32,
+    },
     /// Arc history: entity embedding evolution timeline
     ArcHistory {
         /// Entity (ID or name)
-        entity: String,
+        #[arg(required_unless_present = "stdin")]
+        entity: Option<String>,
         /// Maximum snapshots
         #[arg(long, default_value = "50")]
         limit: usize,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "entity")]
+        stdin: bool,
     },
     /// Arc comparison: compare two entity trajectories
     ArcCompare {
@@ -751,30 +1816,85 @@ pub enum AnalyzeCommands {
         #[arg(long)]
         certainty: Option<String>,
     },
+    /// What-if sandbox: apply a multi-step scenario script to a throwaway copy of the world
+    Scenario {
+        /// Scenario YAML (steps: learns, forgets, perceives, relates, unrelates, update, delete).
+        /// Repeat to compare several plot directions.
+        #[arg(long, required = true)]
+        script: Vec<PathBuf>,
+        /// Diff centrality, tensions, knowledge asymmetries and phases against the baseline
+        #[arg(long)]
+        diff: bool,
+    },
     /// Impact cascade analysis for entity changes
     Impact {
         /// Entity (ID or name)
-        entity: String,
+        #[arg(required_unless_present = "stdin")]
+        entity: Option<String>,
         /// Description of the change
         #[arg(long)]
         description: Option<String>,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "entity")]
+        stdin: bool,
     },
     /// Narrative situation report (irony, conflicts, tensions, themes)
-    SituationReport,
+    SituationReport {
+        /// Recompute instead of using the cached report
+        #[arg(long)]
+        fresh: bool,
+        /// Write a standalone HTML report with charts to this file
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
     /// Character dossier (network, knowledge, perceptions)
-    Dossier { character: String },
+    Dossier {
+        #[arg(required_unless_present = "stdin")]
+        character: Option<String>,
+        /// Recompute instead of using the cached dossier
+        #[arg(long)]
+        fresh: bool,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "character")]
+        stdin: bool,
+        /// Write a standalone HTML report with charts to this file
+        #[arg(long, short, conflicts_with = "stdin")]
+        output: Option<PathBuf>,
+    },
     /// Scene planning for a set of characters
     ScenePrep {
-        #[arg(value_delimiter = ',')]
+        #[arg(value_delimiter = ',', required_unless_present = "stdin")]
         characters: Vec<String>,
+        /// Add the character IDs piped in (e.g. from `narra find --json`)
+        #[arg(long)]
+        stdin: bool,
+    },
+    /// Ensemble scenes: character groups with tension, secrets, or time apart
+    Ensemble {
+        /// Location the scene is set at (ID or name)
+        #[arg(long)]
+        location: Option<String>,
+        /// Only cast members of this narrative phase (see `narra list phase`)
+        #[arg(long)]
+        phase: Option<usize>,
+        /// Characters per scene
+        #[arg(long, default_value = "3")]
+        size: usize,
+        /// Maximum proposals
+        #[arg(long, default_value = "5")]
+        limit: usize,
     },
     /// Growth vector: where is an entity heading based on arc snapshots
     GrowthVector {
         /// Entity (ID or name)
-        entity: String,
+        #[arg(required_unless_present = "stdin")]
+        entity: Option<String>,
         /// Maximum trajectory neighbors
         #[arg(long, default_value = "5")]
         limit: usize,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "entity")]
+        stdin: bool,
     },
     /// Misperception vector: what does observer get wrong about target
     Misperception {
@@ -809,7 +1929,11 @@ pub enum AnalyzeCommands {
     /// Character facet diagnostics: view all facet statuses and inter-facet similarities
     Facets {
         /// Character ID or name
-        character: String,
+        #[arg(required_unless_present = "stdin")]
+        character: Option<String>,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "character")]
+        stdin: bool,
     },
     /// Auto-detect narrative phases via temporal-semantic clustering
     Phases {
@@ -828,6 +1952,9 @@ pub enum AnalyzeCommands {
         /// Clear all saved phases from database
         #[arg(long)]
         clear: bool,
+        /// RNG seed; the same seed always gives the same phases
+        #[arg(long)]
+        seed: Option<u64>,
     },
     /// Find entities narratively close to an anchor
     Around {
@@ -848,6 +1975,12 @@ pub enum AnalyzeCommands {
         /// Max results
         #[arg(long, default_value = "20")]
         limit: usize,
+        /// Only consider members of this narrative phase (see `narra list phase`)
+        #[arg(long)]
+        phase: Option<usize>,
+        /// Re-run whenever the world changes (remote databases only)
+        #[arg(long)]
+        watch: bool,
     },
     /// Infer structural narrative roles from graph topology and knowledge patterns
     Roles {
@@ -858,20 +1991,32 @@ pub enum AnalyzeCommands {
     /// Classify emotional tone of an entity (ML-based, GoEmotions 28 labels)
     Emotions {
         /// Entity (ID or name)
-        entity: String,
+        #[arg(required_unless_present = "stdin")]
+        entity: Option<String>,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "entity")]
+        stdin: bool,
     },
     /// Classify narrative themes for an entity (ML zero-shot NLI)
     EntityThemes {
         /// Entity (ID or name)
-        entity: String,
+        #[arg(required_unless_present = "stdin")]
+        entity: Option<String>,
         /// Custom themes to test (comma-separated; defaults to 20 narrative themes)
         #[arg(long, value_delimiter = ',')]
         themes: Option<Vec<String>>,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "entity")]
+        stdin: bool,
     },
     /// Extract named entities (people, locations, organizations) from entity text (ML-based NER)
     ExtractEntities {
         /// Entity (ID or name)
-        entity: String,
+        #[arg(required_unless_present = "stdin")]
+        entity: Option<String>,
+        /// Analyze each ID piped in (e.g. from `narra find --json`)
+        #[arg(long, conflicts_with = "entity")]
+        stdin: bool,
     },
     /// Identify entities that bridge narrative phases (arc transition points)
     Transitions {
@@ -884,6 +2029,17 @@ pub enum AnalyzeCommands {
         /// Content/neighborhood/temporal weights (comma-separated, e.g. "0.6,0.25,0.15")
         #[arg(long)]
         weights: Option<String>,
+        /// RNG seed; the same seed always gives the same phases
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Run a custom analysis command provided by a Lua plugin (lists commands if omitted)
+    Plugin {
+        /// Plugin command name
+        name: Option<String>,
+        /// Arguments passed to the command
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
     },
 }
 
@@ -908,6 +2064,9 @@ pub enum CharacterCommands {
         aliases: Vec<String>,
         #[arg(long)]
         profile: Option<String>,
+        /// Create it even if an existing character looks like the same one
+        #[arg(long)]
+        allow_similar: bool,
     },
 }
 
@@ -944,6 +2103,9 @@ pub enum EventCommands {
         sequence: Option<i32>,
         #[arg(long)]
         date: Option<String>,
+        /// backstory (before the story), on_page or planned
+        #[arg(long, default_value = "on_page")]
+        status: String,
     },
 }
 
@@ -976,8 +2138,10 @@ pub enum KnowledgeCommands {
         character: String,
         #[arg(long)]
         fact: String,
-        #[arg(long, default_value = "knows")]
-        certainty: String,
+        /// knows, suspects, believes_wrongly, ..., or derive (the default with
+        /// --source: from the source's certainty and the character's trust in them)
+        #[arg(long)]
+        certainty: Option<String>,
         #[arg(long)]
         method: Option<String>,
         #[arg(long)]
@@ -1004,6 +2168,39 @@ pub enum RelationshipCommands {
         subtype: Option<String>,
         #[arg(long)]
         label: Option<String>,
+        /// Also seed both characters' perceptions of each other from the type
+        #[arg(long)]
+        with_perceptions: bool,
+    },
+    /// Mark a relationship as deliberately one-sided or contradictory, so
+    /// `world validate` doesn't flag its reverse edge
+    Intentional {
+        /// Relationship ID (relates_to:...)
+        id: String,
+        /// Remove the mark instead
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Show, set or clear how far one character trusts another
+    Trust {
+        #[arg(long)]
+        from: String,
+        #[arg(long)]
+        to: String,
+        /// Trust from 0.0 (none) to 1.0 (complete)
+        #[arg(long, conflicts_with = "clear")]
+        set: Option<f32>,
+        /// Remove the set value so trust is inferred again
+        #[arg(long)]
+        clear: bool,
+    },
+    /// List canonical relationship types, their synonyms and reciprocals
+    Types,
+    /// Rewrite existing relationships to canonical types (synonyms, case)
+    Normalize {
+        /// Show what would change without changing it
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
@@ -1029,6 +2226,15 @@ pub enum FactCommands {
         categories: Vec<String>,
         #[arg(long)]
         enforcement: Option<String>,
+        /// Only enforce within this location and the locations inside it
+        #[arg(long)]
+        location: Option<String>,
+        /// Only enforce from this event (ID or sequence number) on
+        #[arg(long)]
+        from: Option<String>,
+        /// Only enforce up to this event (ID or sequence number)
+        #[arg(long)]
+        until: Option<String>,
     },
     Update {
         id: String,
@@ -1040,6 +2246,15 @@ pub enum FactCommands {
         categories: Vec<String>,
         #[arg(long)]
         enforcement: Option<String>,
+        /// Location subtree to limit the fact to ('none' to clear)
+        #[arg(long)]
+        location: Option<String>,
+        /// Event (ID or sequence number) the fact holds from ('none' to clear)
+        #[arg(long)]
+        from: Option<String>,
+        /// Event (ID or sequence number) the fact holds until ('none' to clear)
+        #[arg(long)]
+        until: Option<String>,
     },
     Delete {
         id: String,
@@ -1056,6 +2271,35 @@ pub enum FactCommands {
         #[arg(long)]
         entity: String,
     },
+    /// Exempt an entity from a fact, with a justification
+    Except {
+        #[arg(long)]
+        fact: String,
+        #[arg(long)]
+        entity: String,
+        /// Why the fact doesn't hold for this entity
+        #[arg(long)]
+        reason: String,
+    },
+    /// Remove an entity's exemption from a fact
+    Unexcept {
+        #[arg(long)]
+        fact: String,
+        #[arg(long)]
+        entity: String,
+    },
+    /// Propose facts from the rule-like sentences of a note
+    Extract {
+        /// Note to read (e.g., note:worldbuilding-dump)
+        #[arg(long)]
+        from: String,
+        /// Accept every candidate without asking
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// List candidates without creating anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1063,15 +2307,33 @@ pub enum NoteCommands {
     List {
         #[arg(long)]
         entity: Option<String>,
+        /// general, research, todo, or critique
+        #[arg(long)]
+        kind: Option<String>,
+        /// open, resolved, or dismissed (todos and critiques)
+        #[arg(long)]
+        status: Option<String>,
     },
     Create {
         #[arg(long)]
         title: String,
         #[arg(long)]
         body: String,
+        /// general (default), research, todo, or critique (needs a scene in --attach-to)
+        #[arg(long)]
+        kind: Option<String>,
         #[arg(long, value_delimiter = ',')]
         attach_to: Vec<String>,
     },
+    /// Mark a todo or critique resolved
+    Resolve {
+        note: String,
+        /// Close it without acting on it instead
+        #[arg(long)]
+        dismiss: bool,
+    },
+    /// Reopen a resolved or dismissed todo or critique
+    Reopen { note: String },
     Attach {
         #[arg(long)]
         note: String,
@@ -1084,6 +2346,11 @@ pub enum NoteCommands {
         #[arg(long)]
         entity: String,
     },
+    /// Notes that link to an entity with [[Entity Name]]
+    Backlinks {
+        /// Entity ID or name
+        entity: String,
+    },
 }
 
 /// Parse key=value pairs for --set flag
@@ -1094,6 +2361,169 @@ fn parse_key_val(s: &str) -> Result<(String, String), String> {
     Ok((s[..pos].to_string(), s[pos + 1..].to_string()))
 }
 
+/// Entity type a command changes, for access checks. Commands count as
+/// world-wide writes (`ANY_TYPE`) unless they are on the read-only list
+/// below or change a single entity type; only read-only ones return `None`.
+fn write_target(command: &Commands) -> Option<&str> {
+    use crate::services::access::ANY_TYPE;
+    fn table_of(id: &str) -> &str {
+        id.split_once(':').map_or(ANY_TYPE, |(t, _)| t)
+    }
+    match command {
+        // Handled in main before access is loaded
+        Commands::Init { .. }
+        | Commands::Mcp
+        | Commands::Lsp
+        | Commands::Models(_)
+        | Commands::Doctor
+        | Commands::Migrate(_)
+        | Commands::Vault(_)
+        | Commands::Template(_) => None,
+        // Read-only. The TUI saves edits through the access-checked entity
+        // repositories; session state and webhooks are local files, not world data.
+        Commands::Tui
+        | Commands::Explore { .. }
+        | Commands::Ask { .. }
+        | Commands::Find { .. }
+        | Commands::Path { .. }
+        | Commands::References { .. }
+        | Commands::Get { .. }
+        | Commands::List { .. }
+        | Commands::Rename { dry_run: true, .. }
+        | Commands::Lint { .. }
+        | Commands::Batch { validate: true, .. }
+        | Commands::Completions { .. }
+        | Commands::Complete { .. }
+        | Commands::Session(_)
+        | Commands::Config(_)
+        | Commands::Generate(_)
+        | Commands::Health
+        | Commands::Export { .. }
+        | Commands::Graph { .. }
+        | Commands::Validate {
+            fix_reciprocals: false,
+            ..
+        }
+        | Commands::Import { dry_run: true, .. }
+        | Commands::Backup(BackupCommands::Now { .. } | BackupCommands::List)
+        | Commands::Asset(AssetCommands::List { .. } | AssetCommands::Show { .. })
+        | Commands::Access(AccessCommands::Whoami | AccessCommands::List)
+        | Commands::Tag(TagCommands::List { .. })
+        | Commands::Lexicon(
+            LexiconCommands::List { .. } | LexiconCommands::Show { .. } | LexiconCommands::Check,
+        )
+        | Commands::Style(StyleCommands::List)
+        | Commands::Scenes(
+            ScenesCommands::Reorder { print: true, .. }
+            | ScenesCommands::Reorder { dry_run: true, .. }
+            | ScenesCommands::Status { dry_run: true, .. },
+        )
+        | Commands::World(
+            WorldCommands::Status { .. }
+            | WorldCommands::Health
+            | WorldCommands::Export { .. }
+            | WorldCommands::ExportCsv { .. }
+            | WorldCommands::Compile { .. }
+            | WorldCommands::Audit { .. }
+            | WorldCommands::Graph { .. }
+            | WorldCommands::Benchmark { .. }
+            | WorldCommands::Oplog(OplogCommands::Export { .. })
+            | WorldCommands::Fsck { fix: false }
+            | WorldCommands::Validate {
+                fix_reciprocals: false,
+                ..
+            }
+            | WorldCommands::Progress {
+                target: None,
+                clear_target: false,
+                no_record: true,
+                ..
+            }
+            | WorldCommands::Import { dry_run: true, .. }
+            | WorldCommands::ImportCsv { dry_run: true, .. }
+            | WorldCommands::ImportScript { dry_run: true, .. }
+            | WorldCommands::CompactArcs { dry_run: true, .. }
+            | WorldCommands::InferEvents { dry_run: true, .. }
+            | WorldCommands::Renumber { dry_run: true, .. },
+        ) => None,
+        // Analyses only read, except saving or clearing phases
+        Commands::Analyze(
+            AnalyzeCommands::Phases { save: true, .. }
+            | AnalyzeCommands::Phases { clear: true, .. },
+        ) => Some(ANY_TYPE),
+        Commands::Analyze(_) => None,
+        Commands::Character(CharacterCommands::List | CharacterCommands::Get { .. })
+        | Commands::Location(LocationCommands::List | LocationCommands::Get { .. })
+        | Commands::Event(EventCommands::List | EventCommands::Get { .. })
+        | Commands::Scene(SceneCommands::List | SceneCommands::Get { .. })
+        | Commands::Knowledge(KnowledgeCommands::List { .. })
+        | Commands::Relationship(
+            RelationshipCommands::List { .. }
+            | RelationshipCommands::Types
+            | RelationshipCommands::Normalize { dry_run: true }
+            | RelationshipCommands::Trust {
+                set: None,
+                clear: false,
+                ..
+            },
+        )
+        | Commands::Fact(
+            FactCommands::List { .. }
+            | FactCommands::Get { .. }
+            | FactCommands::Extract { dry_run: true, .. },
+        )
+        | Commands::Note(NoteCommands::List { .. } | NoteCommands::Backlinks { .. }) => None,
+
+        // Writes to one entity type
+        Commands::Create(create) => Some(match create {
+            CreateCommands::Character { .. } => "character",
+            CreateCommands::Location { .. } => "location",
+            CreateCommands::Event { .. } => "event",
+            CreateCommands::Scene { .. } => "scene",
+            CreateCommands::Knowledge { .. } => "knows",
+            CreateCommands::Relationship { .. } | CreateCommands::Family { .. } => "relates_to",
+            CreateCommands::Perception { .. } => "perceives",
+            CreateCommands::Fact { .. } => "universe_fact",
+            CreateCommands::Note { .. } => "note",
+            CreateCommands::Foreshadow { .. } => "foreshadows",
+            CreateCommands::Requires { .. } => "requires",
+            CreateCommands::Thread { .. } => "thread",
+            CreateCommands::Term { .. } => "term",
+            CreateCommands::Goal { .. } => "goal",
+        }),
+        Commands::Update { entity_id, .. } | Commands::Delete { entity_id, .. } => {
+            Some(table_of(entity_id))
+        }
+        Commands::Protect { entity } | Commands::Unprotect { entity } => Some(table_of(entity)),
+        Commands::Batch { entity_type, .. } => Some(match entity_type.as_str() {
+            "relationship" | "relationships" => "relates_to",
+            t => t.trim_end_matches('s'),
+        }),
+        Commands::Character(_) => Some("character"),
+        Commands::Location(_) => Some("location"),
+        Commands::Event(_) => Some("event"),
+        Commands::Scene(_) => Some("scene"),
+        Commands::Knowledge(_) => Some("knows"),
+        Commands::Relationship(RelationshipCommands::Trust { .. }) => Some("perceives"),
+        Commands::Relationship(_) => Some("relates_to"),
+        Commands::Fact(_) => Some("universe_fact"),
+        Commands::Note(_) => Some("note"),
+        Commands::Phases(PhaseCommands::Rename { .. }) => Some("phase"),
+        Commands::Scenes(ScenesCommands::Reorder { .. }) => Some("event"),
+        Commands::Scenes(ScenesCommands::Status { .. }) => Some("scene"),
+        Commands::Asset(_) => Some("asset"),
+        Commands::Lexicon(_) => Some("lexeme"),
+        Commands::Style(_) => Some("style_rule"),
+        Commands::Tag(TagCommands::Add { entity, .. } | TagCommands::Remove { entity, .. }) => {
+            Some(table_of(entity))
+        }
+
+        // Everything else writes across the world: imports, restores, grants,
+        // renames, and derived data (embeddings, arcs, phases, annotations)
+        _ => Some(ANY_TYPE),
+    }
+}
+
 /// Execute a CLI command, dispatching to the appropriate handler.
 pub async fn execute(
     command: &Commands,
@@ -1104,8 +2534,126 @@ pub async fn execute(
 ) -> anyhow::Result<()> {
     let _ = detail; // Used in future sessions for controlling output verbosity
 
+    if let Some(target) = write_target(command) {
+        ctx.access.check(target)?;
+    }
+
     match command {
+        Commands::Init { .. } => unreachable!("init handled in main"),
+        Commands::Models(_) => unreachable!("models handled in main"),
+        Commands::Doctor => unreachable!("doctor handled in main"),
+        Commands::Migrate(_) => unreachable!("migrate handled in main"),
+        Commands::Vault(_) => unreachable!("vault handled in main"),
+        Commands::Template(_) => unreachable!("template handled in main"),
         Commands::Mcp => unreachable!("MCP handled in main"),
+        Commands::Lsp => unreachable!("LSP handled in main"),
+
+        Commands::Tui => tui::run(ctx).await?,
+
+        Commands::Phases(PhaseCommands::Rename { id, label }) => {
+            handlers::phase::handle_rename(ctx, id, label, mode).await?
+        }
+        Commands::Scenes(ScenesCommands::Reorder {
+            file,
+            print,
+            dry_run,
+            force,
+        }) => {
+            handlers::scenes::handle_reorder(ctx, file.as_deref(), *print, *dry_run, *force, mode)
+                .await?
+        }
+        Commands::Scenes(ScenesCommands::Status {
+            status,
+            scenes,
+            from,
+            tag,
+            dry_run,
+        }) => {
+            handlers::scenes::handle_status(
+                ctx,
+                status,
+                scenes,
+                from.as_deref(),
+                tag.as_deref(),
+                *dry_run,
+                mode,
+            )
+            .await?
+        }
+
+        Commands::Tag(TagCommands::Add { entity, tags }) => {
+            handlers::tag::handle_add(ctx, entity, tags, mode).await?
+        }
+        Commands::Tag(TagCommands::Remove { entity, tags }) => {
+            handlers::tag::handle_remove(ctx, entity, tags, mode).await?
+        }
+        Commands::Tag(TagCommands::List { entity }) => {
+            handlers::tag::handle_list(ctx, entity.as_deref(), mode).await?
+        }
+
+        Commands::Lexicon(cmd) => match cmd {
+            LexiconCommands::Add {
+                word,
+                gloss,
+                pos,
+                etymology,
+                language,
+            } => {
+                let data = crate::models::LexemeCreate {
+                    word: word.clone(),
+                    gloss: gloss.clone(),
+                    part_of_speech: pos.clone(),
+                    etymology: etymology.clone(),
+                    language: language.clone(),
+                };
+                handlers::lexicon::handle_add(ctx, data, mode).await?
+            }
+            LexiconCommands::List { language } => {
+                handlers::lexicon::handle_list(ctx, language.as_deref(), mode).await?
+            }
+            LexiconCommands::Show { word, language } => {
+                handlers::lexicon::handle_show(ctx, word, language.as_deref(), mode).await?
+            }
+            LexiconCommands::Remove { word, language } => {
+                handlers::lexicon::handle_remove(ctx, word, language.as_deref(), mode).await?
+            }
+            LexiconCommands::Import { file, language } => {
+                handlers::lexicon::handle_import(ctx, file, language.as_deref(), mode).await?
+            }
+            LexiconCommands::Check => handlers::lexicon::handle_check(ctx, mode).await?,
+        },
+
+        Commands::Style(cmd) => match cmd {
+            StyleCommands::Add {
+                kind,
+                preferred,
+                avoid,
+                note,
+            } => {
+                handlers::style::handle_add(ctx, kind, preferred, avoid, note.as_deref(), mode)
+                    .await?
+            }
+            StyleCommands::List => handlers::style::handle_list(ctx, mode).await?,
+            StyleCommands::Remove { id } => handlers::style::handle_remove(ctx, id, mode).await?,
+        },
+        Commands::Lint { file } => handlers::style::handle_lint(ctx, file, mode).await?,
+
+        Commands::Generate(GenerateCommands::Names {
+            culture,
+            count,
+            max_similarity,
+            seed,
+        }) => {
+            handlers::generate::handle_names(
+                ctx,
+                culture.clone(),
+                *count,
+                *max_similarity,
+                *seed,
+                mode,
+            )
+            .await?
+        }
 
         // =====================================================================
         // New intent-based commands
@@ -1154,6 +2702,8 @@ pub async fn execute(
             entity_type,
             limit,
             phase,
+            tag,
+            status,
             subcommand,
         } => match subcommand {
             Some(FindCommands::Join {
@@ -1247,6 +2797,8 @@ pub async fn execute(
                     entity_type.as_deref(),
                     *limit,
                     *phase,
+                    tag.as_deref(),
+                    status.as_deref(),
                     mode,
                 )
                 .await?
@@ -1282,6 +2834,9 @@ pub async fn execute(
             category,
             enforcement,
             entity,
+            tag,
+            kind,
+            status,
             limit,
         } => {
             handlers::entity::handle_list(
@@ -1291,6 +2846,9 @@ pub async fn execute(
                 category.as_deref(),
                 enforcement.as_deref(),
                 entity.as_deref(),
+                tag.as_deref(),
+                kind.as_deref(),
+                status.as_deref(),
                 *limit,
                 mode,
             )
@@ -1305,7 +2863,23 @@ pub async fn execute(
             set,
             link,
             unlink,
+            add_participant,
+            remove_participant,
+            role,
+            impact,
+            force,
         } => {
+            let participant = match (add_participant, remove_participant) {
+                (Some(character), _) => Some(handlers::utility::ParticipantUpdate::Add {
+                    character,
+                    role: role.as_deref(),
+                    impact: impact.as_deref(),
+                }),
+                (None, Some(character)) => {
+                    Some(handlers::utility::ParticipantUpdate::Remove { character })
+                }
+                (None, None) => None,
+            };
             handlers::utility::handle_update(
                 ctx,
                 entity_id,
@@ -1313,34 +2887,218 @@ pub async fn execute(
                 set,
                 link.as_deref(),
                 unlink.as_deref(),
+                participant,
+                *force,
                 mode,
+                no_semantic,
             )
             .await?
         }
 
-        Commands::Delete { entity_id, hard } => {
-            handlers::utility::handle_delete(ctx, entity_id, *hard, mode).await?
+        Commands::Rename {
+            entity,
+            new_name,
+            replace,
+            keep_text,
+            dry_run,
+            force,
+        } => {
+            handlers::utility::handle_rename(
+                ctx,
+                entity,
+                new_name,
+                *replace,
+                *keep_text,
+                *dry_run,
+                *force,
+                mode,
+                no_semantic,
+            )
+            .await?
         }
 
+        Commands::Delete {
+            entity_id,
+            hard,
+            force,
+        } => handlers::utility::handle_delete(ctx, entity_id, *hard, *force, mode).await?,
+
         // =====================================================================
         // World commands
         // =====================================================================
         Commands::World(cmd) => match cmd {
-            WorldCommands::Status => handlers::world::handle_status(ctx, mode).await?,
+            WorldCommands::Status { watch } => {
+                let run = move || handlers::world::handle_status(ctx, mode);
+                if *watch {
+                    watch::watch(ctx, mode, run).await?
+                } else {
+                    run().await?
+                }
+            }
             WorldCommands::Health => handlers::world::handle_health(ctx, mode).await?,
             WorldCommands::Backfill { entity_type, force } => {
                 handlers::world::handle_backfill(ctx, entity_type.as_deref(), *force, mode).await?
             }
-            WorldCommands::Export { output } => {
-                handlers::world::handle_export(ctx, output.as_deref(), mode).await?
+            WorldCommands::ReindexVectors => {
+                handlers::world::handle_reindex_vectors(ctx, mode).await?
+            }
+            WorldCommands::Export {
+                output,
+                include_derived,
+                scope,
+                depth,
+                redact,
+            } => {
+                handlers::world::handle_export(
+                    ctx,
+                    output.as_deref(),
+                    *include_derived,
+                    scope.as_deref().map(|s| (s, *depth)),
+                    redact,
+                    mode,
+                )
+                .await?
             }
             WorldCommands::Import {
                 file,
                 on_conflict,
                 dry_run,
-            } => handlers::world::handle_import(ctx, file, on_conflict, *dry_run, mode).await?,
-            WorldCommands::Validate { entity_id } => {
-                handlers::world::handle_validate(ctx, entity_id.as_deref(), mode).await?
+                include_derived,
+                scope,
+                depth,
+                interactive,
+                replay,
+                resolution_log,
+            } => {
+                let options = handlers::world::ImportOptions {
+                    on_conflict,
+                    dry_run: *dry_run,
+                    include_derived: *include_derived,
+                    scope: scope.as_deref().map(|s| (s, *depth)),
+                    interactive: *interactive,
+                    replay: replay.as_deref(),
+                    resolution_log: resolution_log.as_deref(),
+                };
+                handlers::world::handle_import(ctx, file, options, mode).await?
+            }
+            WorldCommands::ImportCsv {
+                file,
+                entity_type,
+                map,
+                on_conflict,
+                dry_run,
+                interactive,
+            } => {
+                let options = handlers::world::ImportOptions {
+                    on_conflict,
+                    dry_run: *dry_run,
+                    include_derived: false,
+                    scope: None,
+                    interactive: *interactive,
+                    replay: None,
+                    resolution_log: None,
+                };
+                handlers::world::handle_import_csv(
+                    ctx,
+                    file,
+                    entity_type.as_deref(),
+                    map.as_deref(),
+                    options,
+                    mode,
+                )
+                .await?
+            }
+            WorldCommands::ImportScript {
+                file,
+                format,
+                on_conflict,
+                dry_run,
+            } => {
+                handlers::world::handle_import_script(
+                    ctx,
+                    file,
+                    format.as_deref(),
+                    on_conflict,
+                    *dry_run,
+                    mode,
+                )
+                .await?
+            }
+            WorldCommands::ExportCsv {
+                entity_type,
+                map,
+                output,
+            } => {
+                handlers::world::handle_export_csv(
+                    ctx,
+                    entity_type,
+                    map.as_deref(),
+                    output.as_deref(),
+                    mode,
+                )
+                .await?
+            }
+            WorldCommands::Compile {
+                format,
+                output,
+                title,
+            } => {
+                handlers::world::handle_compile(ctx, format, output.as_deref(), title, mode).await?
+            }
+            WorldCommands::Progress {
+                target,
+                clear_target,
+                ingest,
+                no_record,
+                by_status,
+            } => {
+                handlers::world::handle_progress(
+                    ctx,
+                    *target,
+                    *clear_target,
+                    ingest.as_deref(),
+                    !*no_record,
+                    *by_status,
+                    mode,
+                )
+                .await?
+            }
+            WorldCommands::Oplog(cmd) => match cmd {
+                OplogCommands::Export { since, output } => {
+                    handlers::world::handle_oplog_export(
+                        ctx,
+                        since.as_deref(),
+                        output.as_deref(),
+                        mode,
+                    )
+                    .await?
+                }
+                OplogCommands::Import { file, dry_run } => {
+                    handlers::world::handle_oplog_import(ctx, file, *dry_run, mode).await?
+                }
+            },
+            WorldCommands::Audit { checks, fail_on } => {
+                handlers::world::handle_audit(ctx, checks, fail_on, mode).await?
+            }
+            WorldCommands::Fsck { fix } => handlers::world::handle_fsck(ctx, *fix, mode).await?,
+            WorldCommands::Validate {
+                entity_id,
+                fix_reciprocals,
+                watch,
+            } => {
+                let run = move || {
+                    handlers::world::handle_validate(
+                        ctx,
+                        entity_id.as_deref(),
+                        *fix_reciprocals,
+                        mode,
+                    )
+                };
+                if *watch {
+                    watch::watch(ctx, mode, run).await?
+                } else {
+                    run().await?
+                }
             }
             WorldCommands::Graph {
                 scope,
@@ -1354,77 +3112,236 @@ pub async fn execute(
             WorldCommands::BaselineArcs { entity_type } => {
                 handlers::world::handle_baseline_arcs(ctx, entity_type.as_deref(), mode).await?
             }
-            WorldCommands::Benchmark {
-                model,
-                queries,
-                sample,
-                limit,
-            } => {
-                handlers::world::handle_benchmark(ctx, model, queries, *sample, *limit, mode)
-                    .await?
+            WorldCommands::CompactArcs {
+                keep_every,
+                min_drift,
+                keep_anchors,
+                entity_type,
+                dry_run,
+            } => {
+                handlers::world::handle_compact_arcs(
+                    ctx,
+                    *keep_every,
+                    *min_drift,
+                    *keep_anchors,
+                    entity_type.clone(),
+                    *dry_run,
+                    mode,
+                )
+                .await?
+            }
+            WorldCommands::InferEvents {
+                threshold,
+                yes,
+                dry_run,
+            } => {
+                handlers::world::handle_infer_events(ctx, *threshold, *yes, *dry_run, mode).await?
+            }
+            WorldCommands::Renumber { spacing, dry_run } => {
+                handlers::world::handle_renumber(ctx, *spacing, *dry_run, mode).await?
+            }
+            WorldCommands::Mentions => handlers::world::handle_mentions(ctx, mode).await?,
+            WorldCommands::Benchmark {
+                model,
+                queries,
+                sample,
+                limit,
+            } => {
+                handlers::world::handle_benchmark(ctx, model, queries, *sample, *limit, mode)
+                    .await?
+            }
+            WorldCommands::Annotate {
+                entity_types,
+                skip_emotions,
+                skip_themes,
+                skip_ner,
+                concurrency,
+                incremental,
+            } => {
+                handlers::world::handle_annotate(
+                    ctx,
+                    entity_types,
+                    *skip_emotions,
+                    *skip_themes,
+                    *skip_ner,
+                    *concurrency,
+                    *incremental,
+                    mode,
+                )
+                .await?
+            }
+        },
+
+        // =====================================================================
+        // Session commands
+        // =====================================================================
+        Commands::Session(cmd) => match cmd {
+            SessionCommands::Context => handlers::session::handle_context(ctx, mode).await?,
+            SessionCommands::Pin {
+                entity,
+                priority,
+                until,
+            } => {
+                handlers::session::handle_pin(
+                    ctx,
+                    entity,
+                    priority,
+                    until.as_deref(),
+                    mode,
+                    no_semantic,
+                )
+                .await?
+            }
+            SessionCommands::Unpin { entity } => {
+                handlers::session::handle_unpin(ctx, entity, mode, no_semantic).await?
+            }
+            SessionCommands::Log { message, entities } => {
+                handlers::session::handle_log(ctx, message, entities, mode, no_semantic).await?
+            }
+            SessionCommands::History { limit } => {
+                handlers::session::handle_history(ctx, *limit, mode).await?
+            }
+            SessionCommands::Stats { limit } => {
+                handlers::session::handle_stats(ctx, *limit, mode).await?
+            }
+        },
+
+        // =====================================================================
+        // Config
+        // =====================================================================
+        Commands::Config(ConfigCommands::Webhook(cmd)) => match cmd {
+            WebhookCommands::Add {
+                url,
+                secret,
+                events,
+                entity_types,
+            } => handlers::config::handle_webhook_add(
+                ctx,
+                url,
+                secret.as_deref(),
+                events,
+                entity_types,
+                mode,
+            )?,
+            WebhookCommands::List => handlers::config::handle_webhook_list(ctx, mode)?,
+            WebhookCommands::Remove { id } => {
+                handlers::config::handle_webhook_remove(ctx, id, mode)?
+            }
+        },
+
+        // =====================================================================
+        // Backups
+        // =====================================================================
+        Commands::Backup(cmd) => match cmd {
+            BackupCommands::Now { reason } => {
+                handlers::backup::handle_backup_now(ctx, reason, mode).await?
+            }
+            BackupCommands::List => handlers::backup::handle_backup_list(ctx, mode)?,
+            BackupCommands::Restore { name } => {
+                handlers::backup::handle_backup_restore(ctx, name, mode).await?
             }
-            WorldCommands::Annotate {
-                entity_types,
-                skip_emotions,
-                skip_themes,
-                skip_ner,
-                concurrency,
+        },
+
+        // =====================================================================
+        // Assets
+        // =====================================================================
+        Commands::Asset(cmd) => match cmd {
+            AssetCommands::Add {
+                file,
+                attach_to,
+                caption,
             } => {
-                handlers::world::handle_annotate(
+                handlers::asset::handle_add(
                     ctx,
-                    entity_types,
-                    *skip_emotions,
-                    *skip_themes,
-                    *skip_ner,
-                    *concurrency,
+                    file,
+                    attach_to.as_deref(),
+                    caption.clone(),
                     mode,
+                    no_semantic,
                 )
                 .await?
             }
+            AssetCommands::List { entity } => {
+                handlers::asset::handle_list(ctx, entity.as_deref(), mode, no_semantic).await?
+            }
+            AssetCommands::Show { id } => handlers::asset::handle_show(ctx, id, mode).await?,
+            AssetCommands::Remove { id } => handlers::asset::handle_remove(ctx, id, mode).await?,
         },
 
         // =====================================================================
-        // Session commands
+        // Access
         // =====================================================================
-        Commands::Session(cmd) => match cmd {
-            SessionCommands::Context => handlers::session::handle_context(ctx, mode).await?,
-            SessionCommands::Pin { entity } => {
-                handlers::session::handle_pin(ctx, entity, mode, no_semantic).await?
-            }
-            SessionCommands::Unpin { entity } => {
-                handlers::session::handle_unpin(ctx, entity, mode, no_semantic).await?
+        Commands::Access(cmd) => match cmd {
+            AccessCommands::Whoami => handlers::access::handle_whoami(ctx, mode),
+            AccessCommands::List => handlers::access::handle_list(ctx, mode).await?,
+            AccessCommands::Grant {
+                user,
+                role,
+                entity_types,
+            } => handlers::access::handle_grant(ctx, user, role, entity_types, mode).await?,
+            AccessCommands::Revoke { user } => {
+                handlers::access::handle_revoke(ctx, user, mode).await?
             }
         },
 
         // =====================================================================
         // Batch create
         // =====================================================================
-        Commands::Batch { entity_type, file } => {
-            handlers::batch::handle_batch_create(ctx, entity_type, file.as_deref(), mode).await?
+        Commands::Batch {
+            entity_type,
+            file,
+            format,
+            map,
+            validate,
+        } => {
+            handlers::batch::handle_batch_create(
+                ctx,
+                entity_type,
+                file.as_deref(),
+                format.as_deref(),
+                map.as_deref(),
+                *validate,
+                mode,
+            )
+            .await?
         }
 
         // =====================================================================
         // Shell completions (no AppContext needed, but we have it here)
         // =====================================================================
-        Commands::Completions { shell } => {
-            clap_complete::generate(*shell, &mut Cli::command(), "narra", &mut std::io::stdout());
+        Commands::Completions { shell } => print_completions(*shell),
+        Commands::Complete { kind, prefix } => {
+            handlers::complete::handle_complete(&ctx.db, kind, prefix).await?
         }
 
         // =====================================================================
         // Analyze commands (unchanged)
         // =====================================================================
         Commands::Analyze(cmd) => match cmd {
-            AnalyzeCommands::Centrality { scope, limit } => {
-                handlers::analyze::handle_centrality(ctx, scope.as_deref(), *limit, mode).await?
+            AnalyzeCommands::Centrality {
+                scope,
+                limit,
+                phase,
+            } => {
+                handlers::analyze::handle_centrality(ctx, scope.as_deref(), *limit, *phase, mode)
+                    .await?
             }
-            AnalyzeCommands::Influence { character, depth } => {
-                handlers::analyze::handle_influence(ctx, character, *depth, mode).await?
+            AnalyzeCommands::Influence {
+                character,
+                depth,
+                stdin,
+            } => {
+                for character in pipe::targets(character.as_deref(), *stdin, Some("character"))? {
+                    handlers::analyze::handle_influence(ctx, &character, *depth, mode).await?
+                }
             }
             AnalyzeCommands::Irony {
                 character,
                 threshold,
+                phase,
             } => {
-                handlers::analyze::handle_irony(ctx, character.as_deref(), *threshold, mode).await?
+                handlers::analyze::handle_irony(ctx, character.as_deref(), *threshold, *phase, mode)
+                    .await?
             }
             AnalyzeCommands::Asymmetries {
                 character_a,
@@ -1433,15 +3350,126 @@ pub async fn execute(
             AnalyzeCommands::Conflicts { character, limit } => {
                 handlers::analyze::handle_conflicts(ctx, character.as_deref(), *limit, mode).await?
             }
-            AnalyzeCommands::Tensions { limit } => {
-                handlers::analyze::handle_tensions(ctx, *limit, mode).await?
+            AnalyzeCommands::KnowledgeMatrix {
+                characters,
+                limit,
+                output,
+            } => {
+                handlers::analyze::handle_knowledge_matrix(
+                    ctx,
+                    characters,
+                    *limit,
+                    output.as_deref(),
+                    mode,
+                    no_semantic,
+                )
+                .await?
+            }
+            AnalyzeCommands::Tensions {
+                limit,
+                phase,
+                watch,
+            } => {
+                let run = move || handlers::analyze::handle_tensions(ctx, *limit, *phase, mode);
+                if *watch {
+                    watch::watch(ctx, mode, run).await?
+                } else {
+                    run().await?
+                }
             }
             AnalyzeCommands::ArcDrift { entity_type, limit } => {
                 handlers::analyze::handle_arc_drift(ctx, entity_type.as_deref(), *limit, mode)
                     .await?
             }
-            AnalyzeCommands::Themes { types, clusters } => {
-                handlers::analyze::handle_themes(ctx, types.clone(), *clusters, mode).await?
+            AnalyzeCommands::Themes {
+                types,
+                clusters,
+                seed,
+                phase,
+            } => {
+                handlers::analyze::handle_themes(ctx, types.clone(), *clusters, *seed, *phase, mode)
+                    .await?
+            }
+            AnalyzeCommands::Spotlight {
+                max_gap,
+                minor_share,
+                half_life,
+                flagged,
+            } => {
+                handlers::analyze::handle_spotlight(
+                    ctx,
+                    *max_gap,
+                    *minor_share,
+                    *half_life,
+                    *flagged,
+                    mode,
+                )
+                .await?
+            }
+            AnalyzeCommands::Pressure {
+                protagonist,
+                min_share,
+            } => {
+                handlers::analyze::handle_pressure(ctx, protagonist, *min_share, mode, no_semantic)
+                    .await?
+            }
+            AnalyzeCommands::Balance {
+                threshold,
+                suggestions,
+                phase,
+                flagged,
+            } => {
+                handlers::analyze::handle_balance(
+                    ctx,
+                    *threshold,
+                    *suggestions,
+                    *phase,
+                    *flagged,
+                    mode,
+                )
+                .await?
+            }
+            AnalyzeCommands::GoalConflicts { characters } => {
+                handlers::analyze::handle_goal_conflicts(ctx, characters, mode, no_semantic).await?
+            }
+            AnalyzeCommands::Confusability {
+                name_similarity,
+                profile_similarity,
+            } => {
+                handlers::analyze::handle_confusability(
+                    ctx,
+                    *name_similarity,
+                    *profile_similarity,
+                    mode,
+                )
+                .await?
+            }
+            AnalyzeCommands::ReaderKnowledge {
+                backstory,
+                gaps_only,
+            } => {
+                handlers::analyze::handle_reader_knowledge(ctx, *backstory, *gaps_only, mode)
+                    .await?
+            }
+            AnalyzeCommands::Reorder { scene, to } => {
+                handlers::analyze::handle_reorder(ctx, scene, *to, mode).await?
+            }
+            AnalyzeCommands::Setups { limit } => {
+                handlers::analyze::handle_setups(ctx, *limit, mode).await?
+            }
+            AnalyzeCommands::Terminology => {
+                handlers::analyze::handle_terminology(ctx, mode).await?
+            }
+            AnalyzeCommands::ThreadDeadlines {
+                at,
+                window,
+                flagged,
+            } => {
+                handlers::analyze::handle_thread_deadlines(ctx, *at, *window, *flagged, mode)
+                    .await?
+            }
+            AnalyzeCommands::Foreshadowing { all } => {
+                handlers::analyze::handle_foreshadowing(ctx, *all, mode).await?
             }
             AnalyzeCommands::ThematicGaps {
                 min_size,
@@ -1459,10 +3487,19 @@ pub async fn execute(
                 handlers::analyze::handle_temporal(ctx, character, event.clone(), mode, no_semantic)
                     .await?
             }
-            AnalyzeCommands::Contradictions { entity, depth } => {
+            AnalyzeCommands::Contradictions {
+                entity: Some(entity),
+                depth,
+                ..
+            } => {
                 handlers::analyze::handle_contradictions(ctx, entity, *depth, mode, no_semantic)
                     .await?
             }
+            AnalyzeCommands::Contradictions {
+                entity: None,
+                similarity,
+                ..
+            } => handlers::analyze::handle_fact_contradictions(ctx, *similarity, mode).await?,
             AnalyzeCommands::PerceptionGap { observer, target } => {
                 handlers::perception::handle_perception_gap(
                     ctx,
@@ -1493,8 +3530,30 @@ pub async fn execute(
                 )
                 .await?
             }
-            AnalyzeCommands::ArcHistory { entity, limit } => {
-                handlers::arc::handle_arc_history(ctx, entity, *limit, mode, no_semantic).await?
+            AnalyzeCommands::RelationshipSentiment {
+                observer,
+                target,
+                min_change,
+            } => {
+                handlers::perception::handle_relationship_sentiment(
+                    ctx,
+                    observer,
+                    target,
+                    *min_change,
+                    mode,
+                    no_semantic,
+                )
+                .await?
+            }
+            AnalyzeCommands::ArcHistory {
+                entity,
+                limit,
+                stdin,
+            } => {
+                for entity in pipe::targets(entity.as_deref(), *stdin, None)? {
+                    handlers::arc::handle_arc_history(ctx, &entity, *limit, mode, no_semantic)
+                        .await?
+                }
             }
             AnalyzeCommands::ArcCompare {
                 entity_a,
@@ -1515,6 +3574,9 @@ pub async fn execute(
                 handlers::arc::handle_arc_moment(ctx, entity, event.clone(), mode, no_semantic)
                     .await?
             }
+            AnalyzeCommands::Scenario { script, diff } => {
+                handlers::analyze::handle_scenario(ctx, script, *diff, mode).await?
+            }
             AnalyzeCommands::WhatIf {
                 character,
                 fact,
@@ -1533,28 +3595,77 @@ pub async fn execute(
             AnalyzeCommands::Impact {
                 entity,
                 description,
+                stdin,
             } => {
-                handlers::analyze::handle_impact(
+                for entity in pipe::targets(entity.as_deref(), *stdin, None)? {
+                    handlers::analyze::handle_impact(
+                        ctx,
+                        &entity,
+                        description.clone(),
+                        mode,
+                        no_semantic,
+                    )
+                    .await?
+                }
+            }
+            AnalyzeCommands::SituationReport { fresh, output } => {
+                handlers::analyze::handle_situation_report(ctx, *fresh, output.as_deref(), mode)
+                    .await?
+            }
+            AnalyzeCommands::Dossier {
+                character,
+                fresh,
+                stdin,
+                output,
+            } => {
+                for character in pipe::targets(character.as_deref(), *stdin, Some("character"))? {
+                    handlers::analyze::handle_dossier(
+                        ctx,
+                        &character,
+                        *fresh,
+                        output.as_deref(),
+                        mode,
+                    )
+                    .await?
+                }
+            }
+            AnalyzeCommands::ScenePrep { characters, stdin } => {
+                let mut characters = characters.clone();
+                if *stdin {
+                    for id in pipe::read_stdin_ids(Some("character"))? {
+                        if !characters.contains(&id) {
+                            characters.push(id);
+                        }
+                    }
+                }
+                handlers::analyze::handle_scene_prep(ctx, &characters, mode).await?
+            }
+            AnalyzeCommands::Ensemble {
+                location,
+                phase,
+                size,
+                limit,
+            } => {
+                handlers::analyze::handle_ensemble(
                     ctx,
-                    entity,
-                    description.clone(),
+                    location.as_deref(),
+                    *phase,
+                    *size,
+                    *limit,
                     mode,
                     no_semantic,
                 )
                 .await?
             }
-            AnalyzeCommands::SituationReport => {
-                handlers::analyze::handle_situation_report(ctx, mode).await?
-            }
-            AnalyzeCommands::Dossier { character } => {
-                handlers::analyze::handle_dossier(ctx, character, mode).await?
-            }
-            AnalyzeCommands::ScenePrep { characters } => {
-                handlers::analyze::handle_scene_prep(ctx, characters, mode).await?
-            }
-            AnalyzeCommands::GrowthVector { entity, limit } => {
-                handlers::analyze::handle_growth_vector(ctx, entity, *limit, mode, no_semantic)
-                    .await?
+            AnalyzeCommands::GrowthVector {
+                entity,
+                limit,
+                stdin,
+            } => {
+                for entity in pipe::targets(entity.as_deref(), *stdin, None)? {
+                    handlers::analyze::handle_growth_vector(ctx, &entity, *limit, mode, no_semantic)
+                        .await?
+                }
             }
             AnalyzeCommands::Misperception {
                 observer,
@@ -1601,8 +3712,10 @@ pub async fn execute(
                 )
                 .await?
             }
-            AnalyzeCommands::Facets { character } => {
-                handlers::analyze::handle_facets(ctx, character, mode, no_semantic).await?
+            AnalyzeCommands::Facets { character, stdin } => {
+                for character in pipe::targets(character.as_deref(), *stdin, Some("character"))? {
+                    handlers::analyze::handle_facets(ctx, &character, mode, no_semantic).await?
+                }
             }
             AnalyzeCommands::Phases {
                 types,
@@ -1610,6 +3723,7 @@ pub async fn execute(
                 weights,
                 save,
                 clear,
+                seed,
             } => {
                 handlers::analyze::handle_phases(
                     ctx,
@@ -1618,6 +3732,7 @@ pub async fn execute(
                     weights.clone(),
                     *save,
                     *clear,
+                    *seed,
                     mode,
                 )
                 .await?
@@ -1640,44 +3755,73 @@ pub async fn execute(
             AnalyzeCommands::NarrativeTensions {
                 min_severity,
                 limit,
+                phase,
+                watch,
             } => {
-                handlers::analyze::handle_narrative_tensions(ctx, *min_severity, *limit, mode)
-                    .await?
+                let run = move || {
+                    handlers::analyze::handle_narrative_tensions(
+                        ctx,
+                        *min_severity,
+                        *limit,
+                        *phase,
+                        mode,
+                    )
+                };
+                if *watch {
+                    watch::watch(ctx, mode, run).await?
+                } else {
+                    run().await?
+                }
             }
             AnalyzeCommands::Roles { limit } => {
                 handlers::analyze::handle_roles(ctx, *limit, mode).await?
             }
-            AnalyzeCommands::Emotions { entity } => {
-                handlers::analyze::handle_emotions_cli(ctx, entity, mode, no_semantic).await?
-            }
-            AnalyzeCommands::EntityThemes { entity, themes } => {
-                handlers::analyze::handle_entity_themes_cli(
-                    ctx,
-                    entity,
-                    themes.clone(),
-                    mode,
-                    no_semantic,
-                )
-                .await?
+            AnalyzeCommands::Emotions { entity, stdin } => {
+                for entity in pipe::targets(entity.as_deref(), *stdin, None)? {
+                    handlers::analyze::handle_emotions_cli(ctx, &entity, mode, no_semantic).await?
+                }
             }
-            AnalyzeCommands::ExtractEntities { entity } => {
-                handlers::analyze::handle_extract_entities_cli(ctx, entity, mode, no_semantic)
+            AnalyzeCommands::EntityThemes {
+                entity,
+                themes,
+                stdin,
+            } => {
+                for entity in pipe::targets(entity.as_deref(), *stdin, None)? {
+                    handlers::analyze::handle_entity_themes_cli(
+                        ctx,
+                        &entity,
+                        themes.clone(),
+                        mode,
+                        no_semantic,
+                    )
                     .await?
+                }
+            }
+            AnalyzeCommands::ExtractEntities { entity, stdin } => {
+                for entity in pipe::targets(entity.as_deref(), *stdin, None)? {
+                    handlers::analyze::handle_extract_entities_cli(ctx, &entity, mode, no_semantic)
+                        .await?
+                }
             }
             AnalyzeCommands::Transitions {
                 types,
                 num_phases,
                 weights,
+                seed,
             } => {
                 handlers::analyze::handle_transitions(
                     ctx,
                     types.clone(),
                     *num_phases,
                     weights.clone(),
+                    *seed,
                     mode,
                 )
                 .await?
             }
+            AnalyzeCommands::Plugin { name, args } => {
+                handlers::analyze::handle_plugin(ctx, name.as_deref(), args, mode).await?
+            }
         },
 
         // =====================================================================
@@ -1692,6 +3836,7 @@ pub async fn execute(
                 description,
                 aliases,
                 profile,
+                allow_similar,
             } => {
                 handlers::entity::create_character(
                     ctx,
@@ -1700,6 +3845,7 @@ pub async fn execute(
                     description.as_deref(),
                     aliases,
                     profile.as_deref(),
+                    *allow_similar,
                     mode,
                 )
                 .await?
@@ -1728,13 +3874,14 @@ pub async fn execute(
         },
 
         Commands::Event(cmd) => match cmd {
-            EventCommands::List => handlers::entity::list_events(ctx, mode).await?,
+            EventCommands::List => handlers::entity::list_events(ctx, None, mode).await?,
             EventCommands::Get { id } => handlers::entity::get_event(ctx, id, mode).await?,
             EventCommands::Create {
                 title,
                 description,
                 sequence,
                 date,
+                status,
             } => {
                 handlers::entity::create_event(
                     ctx,
@@ -1742,6 +3889,7 @@ pub async fn execute(
                     description.as_deref(),
                     *sequence,
                     date.as_deref(),
+                    status,
                     mode,
                 )
                 .await?
@@ -1749,7 +3897,7 @@ pub async fn execute(
         },
 
         Commands::Scene(cmd) => match cmd {
-            SceneCommands::List => handlers::entity::list_scenes(ctx, mode).await?,
+            SceneCommands::List => handlers::entity::list_scenes(ctx, None, None, mode).await?,
             SceneCommands::Get { id } => handlers::entity::get_scene(ctx, id, mode).await?,
             SceneCommands::Create {
                 title,
@@ -1785,7 +3933,7 @@ pub async fn execute(
                     ctx,
                     character,
                     fact,
-                    certainty,
+                    certainty.as_deref(),
                     method.as_deref(),
                     source.as_deref(),
                     event.as_deref(),
@@ -1805,6 +3953,7 @@ pub async fn execute(
                 rel_type,
                 subtype,
                 label,
+                with_perceptions,
             } => {
                 handlers::relationship::create_relationship(
                     ctx,
@@ -1813,10 +3962,27 @@ pub async fn execute(
                     rel_type,
                     subtype.as_deref(),
                     label.as_deref(),
+                    *with_perceptions,
                     mode,
                 )
                 .await?
             }
+            RelationshipCommands::Intentional { id, clear } => {
+                handlers::relationship::set_intentional(ctx, id, !clear, mode).await?
+            }
+            RelationshipCommands::Trust {
+                from,
+                to,
+                set,
+                clear,
+            } => {
+                let change = if *clear { Some(None) } else { set.map(Some) };
+                handlers::relationship::show_trust(ctx, from, to, change, mode).await?
+            }
+            RelationshipCommands::Types => handlers::relationship::list_types(ctx, mode).await?,
+            RelationshipCommands::Normalize { dry_run } => {
+                handlers::relationship::normalize_types(ctx, *dry_run, mode).await?
+            }
         },
 
         Commands::Fact(cmd) => match cmd {
@@ -1840,6 +4006,9 @@ pub async fn execute(
                 description,
                 categories,
                 enforcement,
+                location,
+                from,
+                until,
             } => {
                 handlers::fact::create_fact(
                     ctx,
@@ -1847,6 +4016,11 @@ pub async fn execute(
                     description,
                     categories,
                     enforcement.as_deref(),
+                    handlers::fact::ScopeArgs {
+                        location: location.as_deref(),
+                        from: from.as_deref(),
+                        until: until.as_deref(),
+                    },
                     mode,
                 )
                 .await?
@@ -1857,6 +4031,9 @@ pub async fn execute(
                 description,
                 categories,
                 enforcement,
+                location,
+                from,
+                until,
             } => {
                 handlers::fact::update_fact(
                     ctx,
@@ -1865,6 +4042,11 @@ pub async fn execute(
                     description.as_deref(),
                     categories,
                     enforcement.as_deref(),
+                    handlers::fact::ScopeArgs {
+                        location: location.as_deref(),
+                        from: from.as_deref(),
+                        until: until.as_deref(),
+                    },
                     mode,
                 )
                 .await?
@@ -1876,23 +4058,65 @@ pub async fn execute(
             FactCommands::Unlink { fact, entity } => {
                 handlers::fact::unlink_fact(ctx, fact, entity, mode).await?
             }
+            FactCommands::Except {
+                fact,
+                entity,
+                reason,
+            } => handlers::fact::except_fact(ctx, fact, entity, reason, mode).await?,
+            FactCommands::Unexcept { fact, entity } => {
+                handlers::fact::unexcept_fact(ctx, fact, entity, mode).await?
+            }
+            FactCommands::Extract { from, yes, dry_run } => {
+                handlers::fact::extract_facts(ctx, from, *yes, *dry_run, mode).await?
+            }
         },
 
         Commands::Note(cmd) => match cmd {
-            NoteCommands::List { entity } => {
-                handlers::note::list_notes(ctx, entity.as_deref(), mode).await?
+            NoteCommands::List {
+                entity,
+                kind,
+                status,
+            } => {
+                handlers::note::list_notes(
+                    ctx,
+                    entity.as_deref(),
+                    None,
+                    kind.as_deref(),
+                    status.as_deref(),
+                    mode,
+                )
+                .await?
             }
             NoteCommands::Create {
                 title,
                 body,
+                kind,
                 attach_to,
-            } => handlers::note::create_note(ctx, title, body, attach_to, mode).await?,
+            } => {
+                handlers::note::create_note(ctx, title, body, kind.as_deref(), attach_to, mode)
+                    .await?
+            }
+            NoteCommands::Resolve { note, dismiss } => {
+                let status = if *dismiss {
+                    crate::models::note::NoteStatus::Dismissed
+                } else {
+                    crate::models::note::NoteStatus::Resolved
+                };
+                handlers::note::set_status(ctx, note, status, mode).await?
+            }
+            NoteCommands::Reopen { note } => {
+                handlers::note::set_status(ctx, note, crate::models::note::NoteStatus::Open, mode)
+                    .await?
+            }
             NoteCommands::Attach { note, entity } => {
                 handlers::note::attach_note(ctx, note, entity, mode).await?
             }
             NoteCommands::Detach { note, entity } => {
                 handlers::note::detach_note(ctx, note, entity, mode).await?
             }
+            NoteCommands::Backlinks { entity } => {
+                handlers::note::backlinks(ctx, entity, mode, no_semantic).await?
+            }
         },
 
         // Legacy top-level aliases → world commands
@@ -1900,17 +4124,44 @@ pub async fn execute(
         Commands::Backfill { entity_type } => {
             handlers::world::handle_backfill(ctx, entity_type.as_deref(), false, mode).await?
         }
-        Commands::Export { output } => {
-            handlers::world::handle_export(ctx, output.as_deref(), mode).await?
+        Commands::Export {
+            output,
+            include_derived,
+        } => {
+            handlers::world::handle_export(
+                ctx,
+                output.as_deref(),
+                *include_derived,
+                None,
+                &[],
+                mode,
+            )
+            .await?
         }
-        Commands::Validate { entity_id } => {
-            handlers::world::handle_validate(ctx, entity_id.as_deref(), mode).await?
+        Commands::Validate {
+            entity_id,
+            fix_reciprocals,
+        } => {
+            handlers::world::handle_validate(ctx, entity_id.as_deref(), *fix_reciprocals, mode)
+                .await?
         }
         Commands::Import {
             file,
             on_conflict,
             dry_run,
-        } => handlers::world::handle_import(ctx, file, on_conflict, *dry_run, mode).await?,
+            include_derived,
+        } => {
+            let options = handlers::world::ImportOptions {
+                on_conflict,
+                dry_run: *dry_run,
+                include_derived: *include_derived,
+                scope: None,
+                interactive: false,
+                replay: None,
+                resolution_log: None,
+            };
+            handlers::world::handle_import(ctx, file, options, mode).await?
+        }
         Commands::Graph {
             scope,
             depth,
@@ -1937,6 +4188,7 @@ async fn handle_create(
             description,
             aliases,
             profile,
+            allow_similar,
         } => {
             handlers::entity::create_character(
                 ctx,
@@ -1945,6 +4197,7 @@ async fn handle_create(
                 description.as_deref(),
                 aliases,
                 profile.as_deref(),
+                *allow_similar,
                 mode,
             )
             .await
@@ -1970,6 +4223,7 @@ async fn handle_create(
             description,
             sequence,
             date,
+            status,
         } => {
             handlers::entity::create_event(
                 ctx,
@@ -1977,6 +4231,7 @@ async fn handle_create(
                 description.as_deref(),
                 *sequence,
                 date.as_deref(),
+                status,
                 mode,
             )
             .await
@@ -2002,7 +4257,7 @@ async fn handle_create(
                 ctx,
                 character,
                 fact,
-                certainty,
+                certainty.as_deref(),
                 method.as_deref(),
                 source.as_deref(),
                 event.as_deref(),
@@ -2016,6 +4271,7 @@ async fn handle_create(
             rel_type,
             subtype,
             label,
+            with_perceptions,
         } => {
             handlers::relationship::create_relationship(
                 ctx,
@@ -2024,10 +4280,22 @@ async fn handle_create(
                 rel_type,
                 subtype.as_deref(),
                 label.as_deref(),
+                *with_perceptions,
                 mode,
             )
             .await
         }
+        CreateCommands::Family {
+            parents,
+            children,
+            no_spouse,
+            dry_run,
+        } => {
+            handlers::relationship::create_family(
+                ctx, parents, children, !no_spouse, *dry_run, mode,
+            )
+            .await
+        }
         CreateCommands::Perception {
             observer,
             target,
@@ -2037,6 +4305,7 @@ async fn handle_create(
             rel_types,
             subtype,
             history,
+            trust,
         } => {
             handlers::perception::create_perception(
                 ctx,
@@ -2048,6 +4317,7 @@ async fn handle_create(
                 rel_types,
                 subtype.as_deref(),
                 history.as_deref(),
+                *trust,
                 mode,
             )
             .await
@@ -2057,6 +4327,9 @@ async fn handle_create(
             description,
             categories,
             enforcement,
+            location,
+            from,
+            until,
         } => {
             handlers::fact::create_fact(
                 ctx,
@@ -2064,6 +4337,11 @@ async fn handle_create(
                 description,
                 categories,
                 enforcement.as_deref(),
+                handlers::fact::ScopeArgs {
+                    location: location.as_deref(),
+                    from: from.as_deref(),
+                    until: until.as_deref(),
+                },
                 mode,
             )
             .await
@@ -2071,7 +4349,85 @@ async fn handle_create(
         CreateCommands::Note {
             title,
             body,
+            kind,
             attach_to,
-        } => handlers::note::create_note(ctx, title, body, attach_to, mode).await,
+        } => handlers::note::create_note(ctx, title, body, kind.as_deref(), attach_to, mode).await,
+        CreateCommands::Foreshadow {
+            setup,
+            payoff,
+            note,
+        } => {
+            handlers::foreshadow::create_foreshadow(ctx, setup, payoff, note.as_deref(), mode).await
+        }
+        CreateCommands::Requires {
+            scene,
+            prerequisite,
+            note,
+        } => {
+            handlers::requires::create_requirement(ctx, scene, prerequisite, note.as_deref(), mode)
+                .await
+        }
+        CreateCommands::Thread {
+            name,
+            description,
+            status,
+            setup,
+            payoff,
+            elements,
+            resolve_by,
+        } => {
+            handlers::thread::create_thread(
+                ctx,
+                name,
+                description.as_deref(),
+                status,
+                setup.as_deref(),
+                payoff.as_deref(),
+                elements,
+                resolve_by.as_deref(),
+                mode,
+            )
+            .await
+        }
+        CreateCommands::Term {
+            name,
+            definition,
+            kind,
+            aliases,
+            first_use,
+        } => {
+            handlers::term::create_term(
+                ctx,
+                name,
+                definition,
+                kind.as_deref(),
+                aliases,
+                first_use.as_deref(),
+                mode,
+            )
+            .await
+        }
+        CreateCommands::Goal {
+            character,
+            want,
+            need,
+            stakes,
+            obstacle,
+            target,
+            status,
+        } => {
+            handlers::goal::create_goal(
+                ctx,
+                character,
+                want,
+                need.as_deref(),
+                stakes.as_deref(),
+                obstacle.as_deref(),
+                target.as_deref(),
+                status,
+                mode,
+            )
+            .await
+        }
     }
 }
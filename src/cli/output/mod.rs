@@ -1,5 +1,7 @@
 //! Output formatting infrastructure for CLI commands.
 
+pub mod schema;
+
 use colored::Colorize;
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
 use serde::Serialize;
@@ -30,6 +32,16 @@ impl OutputMode {
             OutputMode::Human
         }
     }
+
+    /// Parse a configured format name ("human", "json", "md"/"markdown").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(OutputMode::Human),
+            "json" => Some(OutputMode::Json),
+            "md" | "markdown" => Some(OutputMode::Markdown),
+            _ => None,
+        }
+    }
 }
 
 /// Detail level for CLI output.
@@ -50,19 +62,29 @@ impl DetailLevel {
             DetailLevel::Standard
         }
     }
+
+    /// Parse a configured detail name ("brief", "standard", "full").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "brief" => Some(DetailLevel::Brief),
+            "standard" => Some(DetailLevel::Standard),
+            "full" => Some(DetailLevel::Full),
+            _ => None,
+        }
+    }
 }
 
-/// Print a single item as pretty-printed JSON.
-pub fn output_json<T: Serialize>(item: &T) {
-    match serde_json::to_string_pretty(item) {
+/// Print a single item as pretty-printed JSON, wrapped in the versioned envelope.
+pub fn output_json<T: Serialize + ?Sized>(item: &T) {
+    match serde_json::to_string_pretty(&schema::Envelope::item(item)) {
         Ok(json) => println!("{}", json),
         Err(e) => print_error(&format!("Failed to serialize to JSON: {}", e)),
     }
 }
 
-/// Print a list of items as a JSON array.
+/// Print a list of items as a JSON array, wrapped in the versioned envelope.
 pub fn output_json_list<T: Serialize>(items: &[T]) {
-    match serde_json::to_string_pretty(items) {
+    match serde_json::to_string_pretty(&schema::Envelope::list(items)) {
         Ok(json) => println!("{}", json),
         Err(e) => print_error(&format!("Failed to serialize to JSON: {}", e)),
     }
@@ -0,0 +1,593 @@
+//! Versioned JSON output schema for `--json` mode.
+//!
+//! Every JSON document the CLI prints is wrapped in an [`Envelope`] carrying
+//! `schema_version` and the command path that produced it, so scripts can
+//! detect breaking changes instead of guessing from field layout.
+//!
+//! Bump [`SCHEMA_VERSION`] whenever a field is renamed, removed, or changes
+//! type. Adding optional fields is not a breaking change.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mcp::types::{DerivedStats, EntityConflict};
+use crate::models::{Relationship, Scene, TagCount};
+use crate::services::batch_input::{BatchFormat, RowError};
+use crate::services::doctor::DoctorCheck;
+use crate::services::ontology::BaselinePerceptions;
+use crate::services::reorder::{ReorderOutcome, ReorderPlan};
+use crate::services::vector_index::ReindexTiming;
+
+/// Current version of the CLI JSON output schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+static COMMAND: OnceLock<String> = OnceLock::new();
+
+/// Record the command path (e.g., "world status") reported in every envelope.
+///
+/// Called once from `main` after argument parsing; later calls are ignored.
+pub fn set_command(path: impl Into<String>) {
+    let _ = COMMAND.set(path.into());
+}
+
+/// Command path recorded by [`set_command`], or empty when unset (e.g., in tests).
+pub fn current_command() -> &'static str {
+    COMMAND.get().map(String::as_str).unwrap_or("")
+}
+
+/// Whether the payload is a single item or a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadKind {
+    Item,
+    List,
+}
+
+/// Top-level wrapper for all `--json` output.
+#[derive(Debug, Serialize)]
+pub struct Envelope<'a, T: Serialize + ?Sized> {
+    pub schema_version: u32,
+    pub command: &'a str,
+    pub kind: PayloadKind,
+    pub data: &'a T,
+}
+
+impl<'a, T: Serialize + ?Sized> Envelope<'a, T> {
+    pub fn item(data: &'a T) -> Self {
+        Self::new(PayloadKind::Item, data)
+    }
+
+    pub fn list(data: &'a T) -> Self {
+        Self::new(PayloadKind::List, data)
+    }
+
+    fn new(kind: PayloadKind, data: &'a T) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            command: current_command(),
+            kind,
+            data,
+        }
+    }
+}
+
+// =============================================================================
+// Command payloads
+// =============================================================================
+
+/// `narra find graph` result row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoredEntity {
+    pub id: String,
+    pub entity_type: String,
+    pub name: String,
+    pub score: f32,
+}
+
+/// `narra find graph` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoredResults {
+    pub results: Vec<ScoredEntity>,
+    pub total: usize,
+}
+
+/// Entity created by a batch command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedEntity {
+    pub id: String,
+    pub name: String,
+}
+
+/// `narra batch` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSummary {
+    pub entity_type: String,
+    pub total: usize,
+    pub created: usize,
+    pub errors: Vec<String>,
+    pub entities: Vec<CreatedEntity>,
+}
+
+/// `narra batch --validate` payload, also printed when a batch is refused.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchValidation {
+    pub entity_type: String,
+    pub format: BatchFormat,
+    pub rows: usize,
+    pub valid: usize,
+    pub errors: Vec<RowError>,
+    /// CSV headers that match no field
+    pub ignored_columns: Vec<String>,
+}
+
+/// Per-table embedding coverage in `narra world status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityStatus {
+    pub entity_type: String,
+    pub total: usize,
+    pub embedded: usize,
+    pub coverage: String,
+}
+
+/// `narra world status` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldStatus {
+    pub data_path: String,
+    pub entities: Vec<EntityStatus>,
+    pub embedding_available: bool,
+    pub embedding_model: String,
+    pub embedding_provider: String,
+    pub embedding_dimensions: usize,
+    pub embedding_model_mismatch: bool,
+    pub stale_count: usize,
+    /// Tag usage on scenes, events, and notes, most used first
+    #[serde(default)]
+    pub tags: Vec<TagCount>,
+}
+
+/// `narra world export` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub output_path: String,
+    pub characters: usize,
+    pub locations: usize,
+    pub events: usize,
+    pub scenes: usize,
+    pub relationships: usize,
+    pub knowledge: usize,
+    pub notes: usize,
+    pub facts: usize,
+    /// Categories stripped with `--redact`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redacted: Vec<String>,
+    /// Sidecar written with `--include-derived`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derived_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derived: Option<DerivedStats>,
+}
+
+/// `narra world export-csv` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvExportSummary {
+    pub output_path: String,
+    pub entity_type: String,
+    pub rows: usize,
+}
+
+/// `narra world oplog export --output` payload. Without `--output` the
+/// operations themselves go to stdout as JSON lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OplogExportSummary {
+    pub operations: usize,
+    pub path: String,
+}
+
+/// `narra world compile` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompileSummary {
+    pub output_path: String,
+    pub format: String,
+    pub chapters: Vec<String>,
+    pub size_bytes: u64,
+}
+
+/// `narra world import --dry-run` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportDryRun {
+    pub dry_run: bool,
+    pub total: usize,
+    pub by_type: BTreeMap<String, usize>,
+    pub on_conflict: String,
+    /// Entities that already exist, with field-level differences
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<EntityConflict>,
+}
+
+/// `narra world validate` (all characters) payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationSweep {
+    pub checked: usize,
+    pub total_violations: usize,
+    /// Relationships whose reverse edge is missing or contradicts them
+    #[serde(default)]
+    pub reciprocity: Vec<crate::services::reciprocity::ReciprocityIssue>,
+    /// Reverse edges created by `--fix-reciprocals`
+    #[serde(default)]
+    pub reciprocals_created: usize,
+}
+
+/// `narra world graph` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphOutput {
+    pub format: String,
+    pub content: String,
+    pub output_path: Option<String>,
+}
+
+/// `narra world baseline-arcs` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineArcs {
+    pub created: usize,
+    pub skipped: usize,
+    pub entity_types: Vec<String>,
+}
+
+/// `narra world reindex-vectors` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorReindex {
+    /// Index kind now in place ("none" removes indexes)
+    pub kind: String,
+    pub dimensions: usize,
+    pub tables: Vec<ReindexTiming>,
+}
+
+/// Per-query result in `narra world benchmark`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingQueryComparison {
+    pub query: String,
+    pub current_top_ids: Vec<String>,
+    pub comparison_top_ids: Vec<String>,
+    pub current_avg_score: f64,
+    pub comparison_avg_score: f64,
+    pub score_delta: f64,
+    pub rank_correlation: f64,
+}
+
+/// Aggregates in `narra world benchmark`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingComparisonSummary {
+    pub avg_current_score: f64,
+    pub avg_comparison_score: f64,
+    pub avg_score_delta: f64,
+    pub avg_rank_correlation: f64,
+    pub current_latency_ms_per_entity: f64,
+    pub comparison_latency_ms_per_entity: f64,
+}
+
+/// `narra world benchmark` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingComparison {
+    pub current_model: String,
+    pub current_dimensions: usize,
+    pub comparison_model: String,
+    pub comparison_dimensions: usize,
+    pub entities_sampled: usize,
+    pub queries: Vec<EmbeddingQueryComparison>,
+    pub summary: EmbeddingComparisonSummary,
+}
+
+/// Status of one embedding facet: "ok", "stale", or "missing".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FacetStatus {
+    pub status: String,
+    pub composite: Option<String>,
+}
+
+/// `narra analyze facets` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterFacets {
+    pub character_id: String,
+    pub character_name: String,
+    pub facets: BTreeMap<String, FacetStatus>,
+}
+
+/// `narra analyze phases --clear` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhasesCleared {
+    pub cleared: usize,
+}
+
+/// `narra protect` / `narra unprotect` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtectionStatus {
+    pub status: String,
+    pub entity_id: String,
+}
+
+/// `narra update` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateResult {
+    pub status: String,
+    pub id: String,
+    pub name: String,
+}
+
+/// `narra delete` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteResult {
+    pub status: String,
+    pub deleted: String,
+    pub name: String,
+}
+
+/// `narra fact unlink` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactUnlinked {
+    pub status: String,
+    pub fact: String,
+    pub entity: String,
+}
+
+/// `narra note detach` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteDetached {
+    pub status: String,
+    pub note: String,
+    pub entity: String,
+}
+
+/// One row of `narra analyze plugin` (no command given).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginCommandInfo {
+    pub plugin: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// One row of `narra config webhook list` (secret is never echoed).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookInfo {
+    pub id: String,
+    pub url: String,
+    pub signed: bool,
+    pub events: Vec<String>,
+    pub entity_types: Vec<String>,
+}
+
+/// `narra init` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitSummary {
+    pub data_path: String,
+    /// Path of the `narra.toml` written, if one was created
+    pub project_config: Option<String>,
+    /// Manuscript language (ISO 639-1)
+    pub language: String,
+    pub model: String,
+    /// Whether the embedding model downloaded and loaded
+    pub model_ready: bool,
+    pub sample_entities: usize,
+    pub claude_desktop_config: Option<String>,
+    pub claude_desktop_snippet: serde_json::Value,
+    pub claude_code_command: String,
+}
+
+/// `narra vault status` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultStatus {
+    pub encrypted: bool,
+    pub vault_file: String,
+    pub vault_bytes: Option<u64>,
+    /// Passphrase stored in the OS keyring
+    pub keyring: bool,
+}
+
+/// One row of `narra template list` (also the `template edit` payload).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateRow {
+    pub name: String,
+    pub used_by: String,
+    /// "builtin", "user", or "world"
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// One row of `narra backup list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupRow {
+    pub name: String,
+    pub reason: String,
+    pub automatic: bool,
+    pub created_at: String,
+    pub archive: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+    pub encrypted: bool,
+    /// Archive still matches its recorded checksum
+    pub verified: bool,
+}
+
+/// One row of `narra asset list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetRow {
+    pub id: String,
+    pub entity: Option<String>,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+    pub caption: Option<String>,
+    pub encrypted: bool,
+    pub path: String,
+    pub created_at: String,
+}
+
+/// One row of `narra models list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInfo {
+    pub name: String,
+    pub repo: String,
+    pub feature: String,
+    pub cached: bool,
+    pub size_bytes: u64,
+    /// Loaded by the current configuration
+    pub in_use: bool,
+}
+
+/// One row of `narra models bench`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelBenchResult {
+    pub device: String,
+    pub model: String,
+    /// "ok" or "unavailable"
+    pub status: String,
+    pub load_ms: u64,
+    pub texts_per_sec: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `narra models remove` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelsRemoved {
+    pub removed: Vec<String>,
+    pub freed_bytes: u64,
+}
+
+/// One row of `narra models pull`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelPullResult {
+    pub name: String,
+    pub repo: String,
+    pub feature: String,
+    /// "cached", "downloaded", or "failed"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Cold-start latency measured by `narra doctor`, in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColdStartTimings {
+    /// Opening the world: database, schema, services
+    pub context_ms: u64,
+    /// Loading the embedding model on first use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_load_ms: Option<u64>,
+    /// First embedding after the model loaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_embed_ms: Option<u64>,
+}
+
+/// `narra doctor` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DoctorReport {
+    pub data_path: String,
+    pub checks: Vec<DoctorCheck>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cold_start: Option<ColdStartTimings>,
+}
+
+/// One schema migration in `narra migrate` output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationEntry {
+    pub version: u32,
+    pub name: String,
+    pub summary: String,
+    /// "applied", "pending", or "modified"
+    pub state: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub applied_at: Option<String>,
+}
+
+/// `narra migrate status` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrateStatus {
+    pub current_version: u32,
+    pub latest_version: u32,
+    pub migrations: Vec<MigrationEntry>,
+    /// Versions recorded by a newer narra
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unknown_versions: Vec<u32>,
+}
+
+/// `narra migrate up` payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrateUp {
+    pub dry_run: bool,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub migrations: Vec<MigrationEntry>,
+}
+
+/// `narra scenes status` payload. With `--dry-run`, `applied` is false and
+/// `scenes` lists the selected scenes not yet at `status`, as they are now.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftStatusChange {
+    pub status: String,
+    pub applied: bool,
+    pub scenes: Vec<Scene>,
+}
+
+/// `narra scenes reorder` payload. `outcome` is absent on a dry run or when
+/// broken dependencies blocked the reorder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneReorder {
+    pub plan: ReorderPlan,
+    pub applied: bool,
+    pub outcome: Option<ReorderOutcome>,
+}
+
+/// `narra relationship create --with-perceptions` payload. Without the flag
+/// the relationship is printed on its own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipCreated {
+    pub relationship: Relationship,
+    pub perceptions: BaselinePerceptions,
+}
+
+/// `narra access revoke` payload. `revoked` is false when the user had no
+/// grant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessRevoked {
+    pub user: String,
+    pub revoked: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_envelope_shape() {
+        let payload = PhasesCleared { cleared: 3 };
+        let json = serde_json::to_value(Envelope::item(&payload)).unwrap();
+
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["kind"], "item");
+        assert_eq!(json["data"]["cleared"], 3);
+        assert!(json["command"].is_string());
+    }
+
+    #[test]
+    fn test_list_envelope_wraps_slice() {
+        let items = vec![
+            CreatedEntity {
+                id: "character:alice".into(),
+                name: "Alice".into(),
+            },
+            CreatedEntity {
+                id: "character:bob".into(),
+                name: "Bob".into(),
+            },
+        ];
+        let json = serde_json::to_value(Envelope::list(items.as_slice())).unwrap();
+
+        assert_eq!(json["kind"], "list");
+        assert_eq!(json["data"].as_array().unwrap().len(), 2);
+        assert_eq!(json["data"][1]["name"], "Bob");
+    }
+}
@@ -0,0 +1,156 @@
+//! Entity IDs piped between commands.
+//!
+//! `--stdin` on analysis commands reads the IDs another command printed:
+//! a `--json` envelope (`narra find "betrayal" --json`), a bare JSON array,
+//! JSONL, or plain text with one ID per line. Only top-level results are
+//! taken, so IDs nested inside a result (its relationships, say) are not
+//! analyzed by accident.
+
+use std::io::{IsTerminal, Read};
+
+use anyhow::Result;
+use serde_json::Value;
+
+/// Fields that name an entity in JSON results, in order of preference.
+const ID_FIELDS: &[&str] = &["id", "entity_id"];
+
+fn is_entity_id(s: &str) -> bool {
+    let mut parts = s.splitn(2, ':');
+    matches!(
+        (parts.next(), parts.next()),
+        (Some(table), Some(key)) if !table.is_empty() && !key.is_empty() && !s.contains(char::is_whitespace)
+    )
+}
+
+/// The entity a JSON result stands for.
+fn item_id(item: &Value) -> Option<String> {
+    match item {
+        Value::String(s) if is_entity_id(s) => Some(s.clone()),
+        Value::Object(map) => ID_FIELDS
+            .iter()
+            .filter_map(|f| map.get(*f)?.as_str())
+            .find(|s| is_entity_id(s))
+            .map(String::from),
+        _ => None,
+    }
+}
+
+/// IDs in one JSON document: an envelope's `data`, a list of results, a
+/// single result, or an object holding a list (`{"results": [...]}`).
+fn document_ids(doc: &Value, ids: &mut Vec<String>) {
+    let doc = match doc.get("schema_version").and(doc.get("data")) {
+        Some(data) => data,
+        None => doc,
+    };
+    match doc {
+        Value::Array(items) => ids.extend(items.iter().filter_map(item_id)),
+        Value::Object(map) => match item_id(doc) {
+            Some(id) => ids.push(id),
+            None => {
+                if let Some(items) = map.values().find_map(|v| v.as_array()) {
+                    ids.extend(items.iter().filter_map(item_id));
+                }
+            }
+        },
+        other => ids.extend(item_id(other)),
+    }
+}
+
+/// Entity IDs in piped input, first occurrence order, without duplicates.
+pub fn entity_ids_from_input(input: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut json_ok = true;
+    for doc in serde_json::Deserializer::from_str(input).into_iter::<Value>() {
+        match doc {
+            Ok(doc) => document_ids(&doc, &mut ids),
+            Err(_) => {
+                json_ok = false;
+                break;
+            }
+        }
+    }
+    // Plain text, or JSON too broken to walk: take anything shaped like an ID
+    if !json_ok {
+        ids = input
+            .split_whitespace()
+            .map(|token| {
+                token.trim_matches(|c: char| matches!(c, '"' | ',' | '[' | ']' | '{' | '}'))
+            })
+            .filter(|token| is_entity_id(token))
+            .map(String::from)
+            .collect();
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+    ids
+}
+
+/// Read entity IDs from stdin, keeping those in `table` when given.
+pub fn read_stdin_ids(table: Option<&str>) -> Result<Vec<String>> {
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        anyhow::bail!("--stdin expects entity IDs piped in, e.g. narra find \"betrayal\" --json | narra analyze impact --stdin");
+    }
+    let mut input = String::new();
+    stdin.lock().read_to_string(&mut input)?;
+
+    let mut ids = entity_ids_from_input(&input);
+    if let Some(table) = table {
+        ids.retain(|id| id.split(':').next() == Some(table));
+    }
+    if ids.is_empty() {
+        match table {
+            Some(table) => anyhow::bail!("No {} IDs in the input", table),
+            None => anyhow::bail!("No entity IDs in the input"),
+        }
+    }
+    Ok(ids)
+}
+
+/// The entities a command runs on: the one given, or every ID piped in.
+pub fn targets(entity: Option<&str>, stdin: bool, table: Option<&str>) -> Result<Vec<String>> {
+    match entity {
+        Some(entity) if !stdin => Ok(vec![entity.to_string()]),
+        _ => read_stdin_ids(table),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_ids_from_envelopes_jsonl_and_text() {
+        let envelope = r#"{
+            "schema_version": 1,
+            "command": "find",
+            "kind": "list",
+            "data": [
+                {"id": "character:alice", "name": "Alice", "links": [{"id": "event:x"}]},
+                {"id": "event:betrayal", "name": "Betrayal"},
+                {"id": "character:alice", "name": "Alice"}
+            ]
+        }"#;
+        assert_eq!(
+            entity_ids_from_input(envelope),
+            vec!["character:alice", "event:betrayal"]
+        );
+
+        let scored =
+            r#"{"schema_version": 1, "data": {"results": [{"id": "location:keep"}], "total": 1}}"#;
+        assert_eq!(entity_ids_from_input(scored), vec!["location:keep"]);
+
+        let jsonl = "{\"entity_id\": \"character:bob\"}\n{\"entity_id\": \"scene:s1\"}\n";
+        assert_eq!(
+            entity_ids_from_input(jsonl),
+            vec!["character:bob", "scene:s1"]
+        );
+
+        let text = "character:alice\n  character:bob\nnot an id\n";
+        assert_eq!(
+            entity_ids_from_input(text),
+            vec!["character:alice", "character:bob"]
+        );
+    }
+}
@@ -6,6 +6,7 @@ use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::init::AppContext;
+use crate::services::names::fold_name;
 use crate::services::{SearchFilter, SearchService};
 
 /// Strip a known table prefix from an entity ID, returning the bare key.
@@ -93,8 +94,10 @@ struct NameResult {
 ///
 /// Fallback chain:
 /// 1. Exact case-insensitive name/title match across character, location, event, scene
-/// 2. Fuzzy search (Levenshtein) if exact returns 0 and search_service provided
-/// 3. Semantic search if fuzzy also returns 0 and search_service provided
+/// 2. Folded match ignoring diacritics and script ("Osten" finds "Östen"),
+///    including character aliases; reported as exact
+/// 3. Fuzzy search (Levenshtein) if nothing matched and search_service provided
+/// 4. Semantic search if fuzzy also returns 0 and search_service provided
 pub async fn resolve_by_name(
     db: &Arc<NarraDb>,
     input: &str,
@@ -106,7 +109,13 @@ pub async fn resolve_by_name(
         return Ok(resolved);
     }
 
-    // 2. Fuzzy fallback
+    // 2. Transliteration-aware match
+    let resolved = folded_name_match(db, input).await?;
+    if !resolved.is_empty() {
+        return Ok(resolved);
+    }
+
+    // 3. Fuzzy fallback
     if let Some(search) = search_service {
         let filter = SearchFilter {
             limit: Some(5),
@@ -129,7 +138,7 @@ pub async fn resolve_by_name(
             }
         }
 
-        // 3. Semantic fallback
+        // 4. Semantic fallback
         let filter = SearchFilter {
             limit: Some(3),
             ..Default::default()
@@ -233,3 +242,49 @@ async fn exact_name_match(db: &Arc<NarraDb>, input: &str) -> Result<Vec<Resolved
 
     Ok(resolved)
 }
+
+#[derive(Deserialize)]
+struct AliasedNameResult {
+    id: surrealdb::RecordId,
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+/// Names (and character aliases) equal to `input` once folded to ASCII.
+async fn folded_name_match(db: &Arc<NarraDb>, input: &str) -> Result<Vec<ResolvedEntity>> {
+    let folded = fold_name(input);
+    if folded.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut resp = db
+        .query("SELECT id, name, aliases FROM character")
+        .query("SELECT id, name FROM location")
+        .query("SELECT id, title AS name FROM event")
+        .query("SELECT id, title AS name FROM scene")
+        .await?;
+
+    let mut resolved = Vec::new();
+    for (index, entity_type) in ["character", "location", "event", "scene"]
+        .into_iter()
+        .enumerate()
+    {
+        let rows: Vec<AliasedNameResult> = resp.take(index).unwrap_or_default();
+        for r in rows {
+            if std::iter::once(&r.name)
+                .chain(&r.aliases)
+                .any(|n| fold_name(n) == folded)
+            {
+                resolved.push(ResolvedEntity {
+                    entity_type: entity_type.to_string(),
+                    id: r.id.to_string(),
+                    name: r.name,
+                    method: ResolutionMethod::Exact,
+                });
+            }
+        }
+    }
+
+    Ok(resolved)
+}
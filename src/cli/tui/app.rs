@@ -0,0 +1,517 @@
+//! Dashboard state and data loading.
+
+use anyhow::Result;
+
+use crate::cli::resolve::{bare_key, entity_type_from_id};
+use crate::init::AppContext;
+use crate::models::event::EventUpdate;
+use crate::models::location::LocationUpdate;
+use crate::models::scene::SceneUpdate;
+use crate::repository::{EntityRepository, RelationshipRepository};
+use crate::services::events;
+use crate::services::impact::final_scenes_referencing;
+use crate::services::{EntityType, SearchFilter};
+
+/// Maximum search hits shown in the browser.
+const SEARCH_LIMIT: usize = 50;
+
+/// Number of recent entities shown in the session pane.
+const RECENT_LIMIT: usize = 10;
+
+/// What keystrokes are currently routed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    Normal,
+    Search,
+    Edit,
+}
+
+/// One row in the entity browser.
+#[derive(Debug, Clone)]
+pub struct EntityRow {
+    pub id: String,
+    pub entity_type: String,
+    pub name: String,
+}
+
+/// One edge in the relationship mini-graph, relative to the selected entity.
+#[derive(Debug, Clone)]
+pub struct Edge {
+    pub label: String,
+    pub other: String,
+    pub outgoing: bool,
+}
+
+/// Dashboard state.
+pub struct App {
+    /// Every browsable entity, loaded at startup and on reload.
+    all_entities: Vec<EntityRow>,
+    /// Rows currently shown in the browser (all entities or search hits).
+    pub rows: Vec<EntityRow>,
+    pub selected: usize,
+    pub mode: InputMode,
+    /// Text being typed in search or edit mode.
+    pub input: String,
+    /// Query that produced `rows`, if the browser shows search results.
+    pub active_search: Option<String>,
+    pub details: Vec<String>,
+    pub detail_scroll: u16,
+    pub edges: Vec<Edge>,
+    pub pinned: Vec<String>,
+    pub recent: Vec<String>,
+    pub status: String,
+    pub should_quit: bool,
+}
+
+impl App {
+    pub fn new() -> Self {
+        Self {
+            all_entities: Vec::new(),
+            rows: Vec::new(),
+            selected: 0,
+            mode: InputMode::Normal,
+            input: String::new(),
+            active_search: None,
+            details: Vec::new(),
+            detail_scroll: 0,
+            edges: Vec::new(),
+            pinned: Vec::new(),
+            recent: Vec::new(),
+            status: String::new(),
+            should_quit: false,
+        }
+    }
+
+    /// Load (or reload) all entities and session state.
+    pub async fn load(&mut self, ctx: &AppContext) -> Result<()> {
+        let mut rows = Vec::new();
+        for c in ctx.entity_repo.list_characters().await? {
+            rows.push(EntityRow {
+                id: c.id.to_string(),
+                entity_type: "character".into(),
+                name: c.name,
+            });
+        }
+        for l in ctx.entity_repo.list_locations().await? {
+            rows.push(EntityRow {
+                id: l.id.to_string(),
+                entity_type: "location".into(),
+                name: l.name,
+            });
+        }
+        for e in ctx.entity_repo.list_events().await? {
+            rows.push(EntityRow {
+                id: e.id.to_string(),
+                entity_type: "event".into(),
+                name: e.title,
+            });
+        }
+        for s in ctx.entity_repo.list_scenes().await? {
+            rows.push(EntityRow {
+                id: s.id.to_string(),
+                entity_type: "scene".into(),
+                name: s.title,
+            });
+        }
+        rows.sort_by(|a, b| {
+            a.entity_type
+                .cmp(&b.entity_type)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
+        self.all_entities = rows;
+        if self.active_search.is_none() {
+            self.rows = self.all_entities.clone();
+        }
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+        self.refresh_session(ctx).await;
+        self.refresh_selection(ctx).await
+    }
+
+    pub fn selected_row(&self) -> Option<&EntityRow> {
+        self.rows.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.rows.is_empty() {
+            self.selected = (self.selected + 1) % self.rows.len();
+            self.detail_scroll = 0;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.rows.is_empty() {
+            self.selected = (self.selected + self.rows.len() - 1) % self.rows.len();
+            self.detail_scroll = 0;
+        }
+    }
+
+    /// Display name for an entity ID, falling back to the ID itself.
+    pub fn display_name(&self, id: &str) -> String {
+        self.all_entities
+            .iter()
+            .find(|r| r.id == id)
+            .map(|r| r.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    /// Reload details and mini-graph for the selected entity.
+    pub async fn refresh_selection(&mut self, ctx: &AppContext) -> Result<()> {
+        self.details.clear();
+        self.edges.clear();
+
+        let Some(row) = self.selected_row().cloned() else {
+            return Ok(());
+        };
+        ctx.session_manager.record_access(&row.id).await;
+
+        let key = bare_key(&row.id, &row.entity_type);
+        self.details = match row.entity_type.as_str() {
+            "character" => match ctx.entity_repo.get_character(&key).await? {
+                Some(c) => {
+                    let mut lines = vec![format!("Character: {}", c.name)];
+                    if !c.aliases.is_empty() {
+                        lines.push(format!("Aliases: {}", c.aliases.join(", ")));
+                    }
+                    if !c.roles.is_empty() {
+                        lines.push(format!("Roles: {}", c.roles.join(", ")));
+                    }
+                    let mut categories: Vec<_> = c.profile.iter().collect();
+                    categories.sort_by_key(|(k, _)| k.as_str());
+                    for (category, entries) in categories {
+                        lines.push(String::new());
+                        lines.push(format!("{}:", category));
+                        lines.extend(entries.iter().map(|e| format!("  - {}", e)));
+                    }
+                    lines
+                }
+                None => vec![format!("{} not found", row.id)],
+            },
+            "location" => match ctx.entity_repo.get_location(&key).await? {
+                Some(l) => vec![
+                    format!("Location: {}", l.name),
+                    format!("Type: {}", l.loc_type),
+                    String::new(),
+                    l.description.unwrap_or_default(),
+                ],
+                None => vec![format!("{} not found", row.id)],
+            },
+            "event" => match ctx.entity_repo.get_event(&key).await? {
+                Some(e) => vec![
+                    format!("Event: {}", e.title),
+                    format!("Sequence: {}", e.sequence),
+                    String::new(),
+                    e.description.unwrap_or_default(),
+                ],
+                None => vec![format!("{} not found", row.id)],
+            },
+            "scene" => match ctx.entity_repo.get_scene(&key).await? {
+                Some(s) => vec![
+                    format!("Scene: {}", s.title),
+                    format!("Event: {}", self.display_name(&s.event.to_string())),
+                    format!(
+                        "Location: {}",
+                        self.display_name(&s.primary_location.to_string())
+                    ),
+                    String::new(),
+                    s.summary.unwrap_or_default(),
+                ],
+                None => vec![format!("{} not found", row.id)],
+            },
+            _ => vec![format!("{} ({})", row.name, row.id)],
+        };
+
+        if row.entity_type == "character" {
+            for rel in ctx
+                .relationship_repo
+                .get_character_relationships(&key)
+                .await?
+            {
+                let from = rel.from_character.to_string();
+                let to = rel.to_character.to_string();
+                let outgoing = from == row.id;
+                let other = if outgoing { to } else { from };
+                let label = match rel.subtype {
+                    Some(sub) => format!("{}/{}", rel.rel_type, sub),
+                    None => rel.rel_type,
+                };
+                self.edges.push(Edge {
+                    label,
+                    other: self.display_name(&other),
+                    outgoing,
+                });
+            }
+        } else {
+            for other in ctx
+                .relationship_repo
+                .get_connected_entities(&row.id, 1)
+                .await?
+            {
+                let label = entity_type_from_id(&other).unwrap_or("linked").to_string();
+                self.edges.push(Edge {
+                    label,
+                    other: self.display_name(&other),
+                    outgoing: true,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reload pinned and recent entities from the session.
+    pub async fn refresh_session(&mut self, ctx: &AppContext) {
+        self.pinned = ctx.session_manager.get_pinned().await;
+        self.recent = ctx.session_manager.get_recent(RECENT_LIMIT).await;
+    }
+
+    /// Replace the browser contents with search hits.
+    pub async fn run_search(&mut self, ctx: &AppContext, query: &str) -> Result<()> {
+        let filter = SearchFilter {
+            entity_types: vec![
+                EntityType::Character,
+                EntityType::Location,
+                EntityType::Event,
+                EntityType::Scene,
+            ],
+            limit: Some(SEARCH_LIMIT),
+            ..Default::default()
+        };
+        let results = ctx.search_service.hybrid_search(query, filter).await?;
+
+        self.rows = results
+            .into_iter()
+            .map(|r| EntityRow {
+                id: r.id,
+                entity_type: r.entity_type,
+                name: r.name,
+            })
+            .collect();
+        self.status = format!("{} result(s) for '{}'", self.rows.len(), query);
+        self.active_search = Some(query.to_string());
+        self.selected = 0;
+        self.refresh_selection(ctx).await
+    }
+
+    /// Return from search results to the full entity list.
+    pub async fn clear_search(&mut self, ctx: &AppContext) -> Result<()> {
+        self.active_search = None;
+        self.rows = self.all_entities.clone();
+        self.selected = 0;
+        self.status.clear();
+        self.refresh_selection(ctx).await
+    }
+
+    /// Begin editing the selected entity's description, if it has one.
+    pub async fn begin_edit(&mut self, ctx: &AppContext) -> Result<()> {
+        let Some(row) = self.selected_row().cloned() else {
+            return Ok(());
+        };
+        let Some(field) = editable_field(&row.entity_type) else {
+            self.status = format!(
+                "{}s have no description field; use `narra update` for profile edits",
+                row.entity_type
+            );
+            return Ok(());
+        };
+
+        let key = bare_key(&row.id, &row.entity_type);
+        let current = match row.entity_type.as_str() {
+            "location" => ctx
+                .entity_repo
+                .get_location(&key)
+                .await?
+                .and_then(|l| l.description),
+            "event" => ctx
+                .entity_repo
+                .get_event(&key)
+                .await?
+                .and_then(|e| e.description),
+            "scene" => ctx
+                .entity_repo
+                .get_scene(&key)
+                .await?
+                .and_then(|s| s.summary),
+            _ => None,
+        };
+
+        self.input = current.unwrap_or_default();
+        self.mode = InputMode::Edit;
+        self.status = format!(
+            "Editing {} of {} (Enter saves, Esc cancels)",
+            field, row.name
+        );
+        Ok(())
+    }
+
+    /// Persist the edited description and schedule re-embedding.
+    ///
+    /// Like `narra update`, an entity that scenes marked final reference is
+    /// only changed when `force` is set.
+    pub async fn save_edit(&mut self, ctx: &AppContext, force: bool) -> Result<()> {
+        let Some(row) = self.selected_row().cloned() else {
+            return Ok(());
+        };
+        let Some(field) = editable_field(&row.entity_type) else {
+            return Ok(());
+        };
+
+        if !force {
+            let locked = final_scenes_referencing(&ctx.db, &row.id).await?;
+            if !locked.is_empty() {
+                let scenes: Vec<&str> = locked.iter().map(|s| s.title.as_str()).collect();
+                self.status = format!(
+                    "{} scene(s) marked final reference {}: {}. Ctrl+F saves anyway",
+                    locked.len(),
+                    row.name,
+                    scenes.join(", ")
+                );
+                return Ok(());
+            }
+        }
+
+        let key = bare_key(&row.id, &row.entity_type);
+        let text = self.input.trim().to_string();
+        let text = (!text.is_empty()).then_some(text);
+        let updated_at: surrealdb::Datetime = chrono::Utc::now().into();
+        let found = match row.entity_type.as_str() {
+            "location" => ctx
+                .entity_repo
+                .update_location(
+                    &key,
+                    LocationUpdate {
+                        name: None,
+                        description: Some(text),
+                        loc_type: None,
+                        parent: None,
+                        updated_at,
+                    },
+                )
+                .await?
+                .is_some(),
+            "event" => ctx
+                .entity_repo
+                .update_event(
+                    &key,
+                    EventUpdate {
+                        title: None,
+                        description: Some(text),
+                        sequence: None,
+                        date: None,
+                        date_precision: None,
+                        duration_end: None,
+                        narrative_status: None,
+                        updated_at,
+                    },
+                )
+                .await?
+                .is_some(),
+            "scene" => ctx
+                .entity_repo
+                .update_scene(
+                    &key,
+                    SceneUpdate {
+                        title: None,
+                        summary: Some(text),
+                        event: None,
+                        primary_location: None,
+                        secondary_locations: None,
+                        updated_at,
+                    },
+                )
+                .await?
+                .is_some(),
+            _ => false,
+        };
+        if !found {
+            self.status = format!("{} no longer exists", row.id);
+            return Ok(());
+        }
+
+        ctx.event_bus.emit_entity(
+            events::ENTITY_UPDATED,
+            "cli",
+            &row.id,
+            &row.entity_type,
+            &row.name,
+        );
+        ctx.staleness_manager
+            .spawn_regeneration(row.id.clone(), row.entity_type.clone(), None);
+
+        self.status = format!("Saved {} of {}", field, row.name);
+        self.input.clear();
+        self.mode = InputMode::Normal;
+        self.refresh_selection(ctx).await
+    }
+
+    /// Pin the selected entity, or unpin it if already pinned.
+    pub async fn toggle_pin(&mut self, ctx: &AppContext) {
+        let Some(row) = self.selected_row().cloned() else {
+            return;
+        };
+        if self.pinned.contains(&row.id) {
+            ctx.session_manager.unpin_entity(&row.id).await;
+            self.status = format!("Unpinned {}", row.name);
+        } else {
+            ctx.session_manager.pin_entity(&row.id).await;
+            self.status = format!("Pinned {}", row.name);
+        }
+        self.refresh_session(ctx).await;
+    }
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Field holding free-text description for an entity type, if any.
+pub fn editable_field(entity_type: &str) -> Option<&'static str> {
+    match entity_type {
+        "location" | "event" => Some("description"),
+        "scene" => Some("summary"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: &str) -> EntityRow {
+        EntityRow {
+            id: id.to_string(),
+            entity_type: "character".to_string(),
+            name: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_selection_wraps() {
+        let mut app = App::new();
+        app.rows = vec![row("character:a"), row("character:b")];
+
+        app.select_prev();
+        assert_eq!(app.selected, 1);
+        app.select_next();
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn test_selection_on_empty_list() {
+        let mut app = App::new();
+        app.select_next();
+        app.select_prev();
+        assert_eq!(app.selected, 0);
+        assert!(app.selected_row().is_none());
+    }
+
+    #[test]
+    fn test_editable_field() {
+        assert_eq!(editable_field("location"), Some("description"));
+        assert_eq!(editable_field("event"), Some("description"));
+        assert_eq!(editable_field("scene"), Some("summary"));
+        assert_eq!(editable_field("character"), None);
+    }
+}
@@ -0,0 +1,118 @@
+//! Terminal dashboard (`narra tui`).
+//!
+//! Panes: entity browser, details, relationship mini-graph, and session
+//! context. Data access goes through the same `AppContext` services as the
+//! rest of the CLI.
+
+mod app;
+mod ui;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::DefaultTerminal;
+
+use crate::init::AppContext;
+use app::{App, InputMode};
+
+/// How long to wait for a key before redrawing.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Run the dashboard until the user quits.
+pub async fn run(ctx: &AppContext) -> Result<()> {
+    let mut app = App::new();
+    app.load(ctx).await?;
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app, ctx).await;
+    ratatui::restore();
+
+    // Persist pins and recent access from this session
+    ctx.session_manager.save().await?;
+    result
+}
+
+async fn event_loop(terminal: &mut DefaultTerminal, app: &mut App, ctx: &AppContext) -> Result<()> {
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        if !event::poll(POLL_INTERVAL)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            // Surface failures in the status line instead of tearing down the UI
+            if let Err(e) = handle_key(app, ctx, key).await {
+                app.status = format!("Error: {}", e);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_key(app: &mut App, ctx: &AppContext, key: KeyEvent) -> Result<()> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.should_quit = true;
+        return Ok(());
+    }
+
+    match app.mode {
+        InputMode::Normal => match key.code {
+            KeyCode::Char('q') => app.should_quit = true,
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.select_next();
+                app.refresh_selection(ctx).await?;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.select_prev();
+                app.refresh_selection(ctx).await?;
+            }
+            KeyCode::PageDown => app.detail_scroll = app.detail_scroll.saturating_add(5),
+            KeyCode::PageUp => app.detail_scroll = app.detail_scroll.saturating_sub(5),
+            KeyCode::Char('/') => {
+                app.input.clear();
+                app.mode = InputMode::Search;
+            }
+            KeyCode::Char('e') => app.begin_edit(ctx).await?,
+            KeyCode::Char('p') => app.toggle_pin(ctx).await,
+            KeyCode::Char('r') => {
+                app.load(ctx).await?;
+                app.status = "Reloaded".to_string();
+            }
+            KeyCode::Esc if app.active_search.is_some() => app.clear_search(ctx).await?,
+            _ => {}
+        },
+        InputMode::Search | InputMode::Edit => match key.code {
+            KeyCode::Esc => {
+                app.input.clear();
+                app.mode = InputMode::Normal;
+                app.status.clear();
+            }
+            KeyCode::Enter if app.mode == InputMode::Search => {
+                let query = app.input.trim().to_string();
+                app.mode = InputMode::Normal;
+                app.input.clear();
+                if query.is_empty() {
+                    app.clear_search(ctx).await?;
+                } else {
+                    app.run_search(ctx, &query).await?;
+                }
+            }
+            KeyCode::Enter => app.save_edit(ctx, false).await?,
+            KeyCode::Char('f')
+                if app.mode == InputMode::Edit && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                app.save_edit(ctx, true).await?
+            }
+            KeyCode::Backspace => {
+                app.input.pop();
+            }
+            KeyCode::Char(c) => app.input.push(c),
+            _ => {}
+        },
+    }
+    Ok(())
+}
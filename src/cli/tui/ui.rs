@@ -0,0 +1,230 @@
+//! Dashboard layout and rendering.
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use super::app::{App, InputMode};
+
+const HELP: &str =
+    "j/k move  / search  e edit  p pin  r reload  Esc clear  PgUp/PgDn scroll  q quit";
+
+/// Draw the whole dashboard.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+
+    draw_input(frame, app, outer[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(45),
+            Constraint::Percentage(25),
+        ])
+        .split(outer[1]);
+
+    draw_browser(frame, app, columns[0]);
+
+    let middle = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(columns[1]);
+    draw_details(frame, app, middle[0]);
+    draw_graph(frame, app, middle[1]);
+
+    draw_session(frame, app, columns[2]);
+
+    let status = if app.status.is_empty() {
+        HELP.to_string()
+    } else {
+        app.status.clone()
+    };
+    frame.render_widget(
+        Paragraph::new(status).style(Style::default().fg(Color::DarkGray)),
+        outer[2],
+    );
+}
+
+fn draw_input(frame: &mut Frame, app: &App, area: Rect) {
+    let (title, text, style) = match app.mode {
+        InputMode::Search => (
+            " Search ",
+            app.input.as_str(),
+            Style::default().fg(Color::Yellow),
+        ),
+        InputMode::Edit => (
+            " Edit description ",
+            app.input.as_str(),
+            Style::default().fg(Color::Green),
+        ),
+        InputMode::Normal => (
+            " Search (press /) ",
+            app.active_search.as_deref().unwrap_or(""),
+            Style::default(),
+        ),
+    };
+
+    let block = Block::default().borders(Borders::ALL).title(title);
+    frame.render_widget(
+        Paragraph::new(text)
+            .style(style)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+
+    if app.mode != InputMode::Normal {
+        let cursor_x = area.x + 1 + (text.chars().count() as u16).min(area.width.saturating_sub(3));
+        frame.set_cursor_position((cursor_x, area.y + 1));
+    }
+}
+
+fn draw_browser(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .rows
+        .iter()
+        .map(|row| {
+            let marker = if app.pinned.contains(&row.id) {
+                "* "
+            } else {
+                "  "
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(marker),
+                Span::styled(
+                    format!("{:<9}", row.entity_type),
+                    Style::default().fg(type_color(&row.entity_type)),
+                ),
+                Span::raw(row.name.clone()),
+            ]))
+        })
+        .collect();
+
+    let title = match &app.active_search {
+        Some(_) => format!(" Results ({}) ", app.rows.len()),
+        None => format!(" Entities ({}) ", app.rows.len()),
+    };
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = ListState::default();
+    if !app.rows.is_empty() {
+        state.select(Some(app.selected));
+    }
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_details(frame: &mut Frame, app: &App, area: Rect) {
+    let text: Vec<Line> = app.details.iter().map(|l| Line::raw(l.clone())).collect();
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(Block::default().borders(Borders::ALL).title(" Details "))
+            .wrap(Wrap { trim: false })
+            .scroll((app.detail_scroll, 0)),
+        area,
+    );
+}
+
+fn draw_graph(frame: &mut Frame, app: &App, area: Rect) {
+    let center = app
+        .selected_row()
+        .map(|r| r.name.clone())
+        .unwrap_or_default();
+
+    let mut lines = vec![Line::styled(
+        center,
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    if app.edges.is_empty() {
+        lines.push(Line::styled(
+            "  (no connections)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let last = app.edges.len().saturating_sub(1);
+    for (i, edge) in app.edges.iter().enumerate() {
+        let branch = if i == last { "└" } else { "├" };
+        let arrow = if edge.outgoing {
+            format!("─{}──▶ ", edge.label)
+        } else {
+            format!("◀─{}── ", edge.label)
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {}", branch)),
+            Span::styled(arrow, Style::default().fg(Color::Cyan)),
+            Span::raw(edge.other.clone()),
+        ]));
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Connections "),
+        ),
+        area,
+    );
+}
+
+fn draw_session(frame: &mut Frame, app: &App, area: Rect) {
+    let mut lines = vec![Line::styled(
+        "Pinned",
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    if app.pinned.is_empty() {
+        lines.push(Line::styled(
+            "  (none)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines.extend(
+        app.pinned
+            .iter()
+            .map(|id| Line::raw(format!("  {}", app.display_name(id)))),
+    );
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Recent",
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    if app.recent.is_empty() {
+        lines.push(Line::styled(
+            "  (none)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines.extend(
+        app.recent
+            .iter()
+            .map(|id| Line::raw(format!("  {}", app.display_name(id)))),
+    );
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(" Session "))
+            .wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
+fn type_color(entity_type: &str) -> Color {
+    match entity_type {
+        "character" => Color::Magenta,
+        "location" => Color::Green,
+        "event" => Color::Yellow,
+        "scene" => Color::Blue,
+        _ => Color::Gray,
+    }
+}
@@ -0,0 +1,87 @@
+//! `--watch`: re-run a report whenever the world changes.
+//!
+//! Changes arrive through SurrealDB live queries (see `db::live`), so
+//! edits from another terminal or an MCP client show up too. Bursts (an
+//! import, or an entity followed by its embedding) are coalesced into one
+//! re-run.
+//!
+//! Only remote worlds can be watched. An embedded world is locked by the
+//! process that opens it, and an encrypted one lives in that process's
+//! memory, so no other process could change it while the watch runs.
+
+use std::future::Future;
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::Colorize;
+use futures::StreamExt;
+
+use crate::cli::output::{print_error, OutputMode};
+use crate::cli::ExitStatus;
+use crate::db::connection::DbConfig;
+use crate::db::live;
+use crate::init::AppContext;
+
+/// Quiet time after a change before re-running.
+const DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// Run `render` now and again after every change, until Ctrl-C.
+///
+/// Errors from `render` are shown and watching goes on; losing the live
+/// query ends the watch with an error.
+pub async fn watch<F, Fut>(ctx: &AppContext, mode: OutputMode, mut render: F) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    if !matches!(ctx.db_config, DbConfig::Remote { .. }) {
+        anyhow::bail!(
+            "--watch needs a remote database. A local world is locked by the process that \
+             opens it, so nothing else can change it while this one watches. Connect to a \
+             SurrealDB server with NARRA_DB_URL or mode = \"remote\" in database.toml."
+        );
+    }
+    let mut changes = live::subscribe(&ctx.db)
+        .await
+        .map_err(|e| anyhow::anyhow!("Cannot watch for changes: {}", e))?;
+    let clear = mode != OutputMode::Json && std::io::stdout().is_terminal();
+
+    loop {
+        if clear {
+            print!("\x1b[2J\x1b[H");
+        }
+        if let Err(e) = render().await {
+            // A report's own exit status (e.g. validation issues) is not a failure here
+            if e.downcast_ref::<ExitStatus>().is_none() {
+                print_error(&e.to_string());
+            }
+        }
+        if mode != OutputMode::Json {
+            println!(
+                "\n{}",
+                format!(
+                    "Updated {} · watching for changes (Ctrl-C to stop)",
+                    chrono::Local::now().format("%H:%M:%S")
+                )
+                .dimmed()
+            );
+        }
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            next = changes.next() => match next {
+                Some(Ok(_)) => {}
+                Some(Err(e)) => anyhow::bail!("Live query failed: {}", e),
+                None => anyhow::bail!("Live query ended"),
+            },
+        }
+        while let Ok(next) = tokio::time::timeout(DEBOUNCE, changes.next()).await {
+            match next {
+                Some(Ok(_)) => {}
+                Some(Err(e)) => anyhow::bail!("Live query failed: {}", e),
+                None => anyhow::bail!("Live query ended"),
+            }
+        }
+    }
+}
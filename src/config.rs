@@ -0,0 +1,810 @@
+//! Layered user configuration.
+//!
+//! Precedence, lowest to highest:
+//! 1. Built-in defaults
+//! 2. `~/.narra/config.toml` (user-wide)
+//! 3. `narra.toml` in the current directory or the nearest ancestor (per-project)
+//! 4. Environment variables (`NARRA_OUTPUT`, `NARRA_DETAIL`, `NARRA_LIMIT`, ...)
+//! 5. Command-line flags
+//!
+//! Flags win because config values only become clap defaults (see
+//! `apply_cli_defaults`) or apply when no flag was given.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::embedding::candle_backend::ExecutionProvider;
+use crate::embedding::ArcSnapshotPolicy;
+use crate::services::kmeans::KMeansOptions;
+use crate::services::ontology::{RelationshipOntology, RelationshipTypeDef};
+use crate::services::scheduler::{AnnotationSchedule, SchedulePolicy};
+use crate::services::transmission::{TransmissionRules, Trust};
+use crate::services::vector_index::{VectorIndexKind, VectorIndexSettings};
+use crate::services::ConsistencyStrictness;
+use crate::session::{HotDecay, StartupOptions, StartupSection};
+
+/// Per-project config file name, searched upward from the current directory.
+pub const PROJECT_CONFIG_FILE: &str = "narra.toml";
+
+/// Merged configuration. Every field is optional so layers can be overlaid.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NarraConfig {
+    /// Data directory; relative paths resolve against the file that set them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_path: Option<PathBuf>,
+    /// Name recorded as the author of every mutation (defaults to the OS user)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Manuscript language (ISO 639-1, e.g. "de"); picks multilingual models
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub output: OutputConfig,
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    #[serde(default)]
+    pub embedding: EmbeddingConfigSection,
+    #[serde(default)]
+    pub consistency: ConsistencyConfig,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    #[serde(default)]
+    pub arcs: ArcsConfig,
+    #[serde(default)]
+    pub annotate: AnnotateConfig,
+    #[serde(default)]
+    pub export: ExportConfig,
+    #[serde(default)]
+    pub vector_index: VectorIndexConfig,
+    #[serde(default)]
+    pub clustering: ClusteringConfig,
+    #[serde(default)]
+    pub relationships: RelationshipsConfig,
+    #[serde(default)]
+    pub transmission: TransmissionConfig,
+    #[serde(default)]
+    pub session: SessionConfig,
+    #[serde(default)]
+    pub startup: StartupConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    /// "human", "json", or "md"
+    pub format: Option<String>,
+    /// "brief", "standard", or "full"
+    pub detail: Option<String>,
+    /// Use semantic search (false behaves like `--no-semantic`)
+    pub semantic: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Default for every `--limit` flag
+    pub results: Option<usize>,
+    /// Default for every `--budget` token budget flag
+    pub token_budget: Option<usize>,
+    /// Per-command `--limit` defaults keyed by command path (e.g., "find", "analyze centrality")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EmbeddingConfigSection {
+    /// Model used when the world has no `embedding.toml`
+    pub model: Option<String>,
+    /// Inference device: auto, cpu, cuda, or metal
+    pub device: Option<ExecutionProvider>,
+    /// CPU inference threads (default: one per core)
+    pub threads: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConsistencyConfig {
+    pub strictness: Option<ConsistencyStrictness>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BackupConfig {
+    /// Automatic backups kept before the oldest is deleted (0 disables them)
+    pub keep: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ArcsConfig {
+    /// Cosine distance from the last arc snapshot that triggers a new one
+    pub drift_threshold: Option<f32>,
+    /// Minimum seconds between automatic snapshots of one entity
+    pub debounce_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AnnotateConfig {
+    /// When the MCP server annotates in the background: manual, on_mutation, or nightly
+    pub schedule: Option<AnnotationSchedule>,
+    /// Seconds without mutations before a background run may start
+    pub idle_secs: Option<u64>,
+    /// Local hour (0-23) from which the nightly run is due
+    pub nightly_hour: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExportConfig {
+    /// Character profile keys removed by `world export --redact secrets`
+    pub secret_keys: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VectorIndexConfig {
+    /// ANN index on embedding fields: none (brute force), hnsw, or mtree
+    pub kind: Option<VectorIndexKind>,
+    /// HNSW neighbours per node
+    pub m: Option<u8>,
+    /// HNSW candidate list size while building
+    pub efc: Option<u16>,
+    /// HNSW candidate list size while searching
+    pub ef: Option<u16>,
+    /// MTREE node capacity
+    pub capacity: Option<u16>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ClusteringConfig {
+    /// Ceiling for the dense k-means matrix; larger worlds use mini-batch k-means
+    pub memory_limit_mb: Option<usize>,
+    /// RNG seed for reproducible theme and phase clusters
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RelationshipsConfig {
+    /// Reject relationship types the ontology doesn't know
+    pub strict: Option<bool>,
+    /// User-defined types keyed by canonical name; layers add to each other
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub types: BTreeMap<String, RelationshipTypeDef>,
+}
+
+impl RelationshipsConfig {
+    /// Built-in relationship types overlaid with the configured ones.
+    pub fn ontology(&self) -> Result<RelationshipOntology, crate::NarraError> {
+        RelationshipOntology::new(&self.types, self.strict.unwrap_or(false))
+    }
+}
+
+/// Overrides for derived certainty, as source certainty -> recipient certainty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TransmissionConfig {
+    /// When the recipient has a positive relationship towards the source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub trusted: BTreeMap<String, String>,
+    /// When the recipient has no positive or negative relationship towards the source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub neutral: BTreeMap<String, String>,
+    /// When the recipient has a negative relationship towards the source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub distrusted: BTreeMap<String, String>,
+}
+
+impl TransmissionConfig {
+    /// Built-in transmission rules with the configured overrides.
+    pub fn rules(&self) -> Result<TransmissionRules, crate::NarraError> {
+        TransmissionRules::with_overrides(&[
+            (Trust::Trusted, &self.trusted),
+            (Trust::Neutral, &self.neutral),
+            (Trust::Distrusted, &self.distrusted),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SessionConfig {
+    /// Days for an untouched entity's heat to halve
+    pub half_life_days: Option<f64>,
+    /// Share of heat kept when a session ends (1.0 disables session decay)
+    pub session_decay: Option<f64>,
+}
+
+impl SessionConfig {
+    /// Hot-entity decay, with unset values at their defaults.
+    pub fn decay(&self) -> HotDecay {
+        let defaults = HotDecay::default();
+        HotDecay {
+            half_life_days: self.half_life_days.unwrap_or(defaults.half_life_days),
+            per_session: self.session_decay.unwrap_or(defaults.per_session),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StartupConfig {
+    /// "minimal", "brief", "standard", or "full" (default: by time since last session)
+    pub verbosity: Option<String>,
+    /// Sections to include (default: all but "neglected")
+    pub sections: Option<Vec<String>>,
+    /// Approximate token ceiling for the whole startup context
+    pub token_ceiling: Option<usize>,
+    /// Entity IDs always included (e.g., "character:alice")
+    pub always_include: Option<Vec<String>>,
+}
+
+impl StartupConfig {
+    /// Startup options, with unset values at their defaults.
+    pub fn options(&self) -> Result<StartupOptions, crate::NarraError> {
+        let defaults = StartupOptions::default();
+        let verbosity = self
+            .verbosity
+            .as_deref()
+            .filter(|v| *v != "auto")
+            .map(str::parse)
+            .transpose()?;
+        let sections = match &self.sections {
+            Some(sections) => sections
+                .iter()
+                .map(|s| s.parse())
+                .collect::<Result<Vec<StartupSection>, _>>()?,
+            None => defaults.sections,
+        };
+        let always_include = self.always_include.clone().unwrap_or_default();
+        if let Some(id) = always_include.iter().find(|id| !id.contains(':')) {
+            return Err(crate::NarraError::Validation(format!(
+                "startup.always_include entries must be entity IDs such as character:alice (got '{}')",
+                id
+            )));
+        }
+        Ok(StartupOptions {
+            verbosity,
+            sections,
+            token_ceiling: self.token_ceiling,
+            always_include,
+        })
+    }
+}
+
+impl ClusteringConfig {
+    /// K-means options, with unset values at their defaults.
+    pub fn options(&self) -> KMeansOptions {
+        let defaults = KMeansOptions::default();
+        KMeansOptions {
+            memory_limit_bytes: self
+                .memory_limit_mb
+                .map_or(defaults.memory_limit_bytes, |mb| mb * 1024 * 1024),
+            seed: self.seed.unwrap_or(defaults.seed),
+        }
+    }
+}
+
+impl VectorIndexConfig {
+    /// Index parameters, with unset values at their defaults.
+    pub fn settings(&self) -> VectorIndexSettings {
+        let defaults = VectorIndexSettings::default();
+        VectorIndexSettings {
+            kind: self.kind.unwrap_or(defaults.kind),
+            m: self.m.unwrap_or(defaults.m),
+            efc: self.efc.unwrap_or(defaults.efc),
+            ef: self.ef.unwrap_or(defaults.ef),
+            capacity: self.capacity.unwrap_or(defaults.capacity),
+        }
+    }
+}
+
+impl ExportConfig {
+    /// Configured secret keys, or the built-in defaults.
+    pub fn secret_keys(&self) -> Vec<String> {
+        self.secret_keys.clone().unwrap_or_else(|| {
+            crate::services::export::DEFAULT_SECRET_KEYS
+                .iter()
+                .map(|k| k.to_string())
+                .collect()
+        })
+    }
+}
+
+impl ArcsConfig {
+    /// Automatic arc snapshot policy, with unset values at their defaults.
+    pub fn policy(&self) -> ArcSnapshotPolicy {
+        let defaults = ArcSnapshotPolicy::default();
+        ArcSnapshotPolicy {
+            drift_threshold: self.drift_threshold.unwrap_or(defaults.drift_threshold),
+            debounce: self
+                .debounce_secs
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.debounce),
+        }
+    }
+}
+
+impl AnnotateConfig {
+    /// Background annotation policy, with unset values at their defaults.
+    pub fn policy(&self) -> SchedulePolicy {
+        let defaults = SchedulePolicy::default();
+        SchedulePolicy {
+            schedule: self.schedule.unwrap_or(defaults.schedule),
+            idle: self
+                .idle_secs
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.idle),
+            nightly_hour: self.nightly_hour.unwrap_or(defaults.nightly_hour),
+        }
+    }
+}
+
+impl NarraConfig {
+    /// User-wide config path (`~/.narra/config.toml`).
+    pub fn user_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|h| h.join(".narra").join("config.toml"))
+    }
+
+    /// Nearest `narra.toml` from `start` upward.
+    pub fn project_path(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+            .find(|p| p.is_file())
+    }
+
+    /// Load all layers for the current process.
+    pub fn load() -> anyhow::Result<Self> {
+        let cwd = std::env::current_dir()?;
+        let mut config = Self::default();
+        for path in [Self::user_path(), Self::project_path(&cwd)]
+            .into_iter()
+            .flatten()
+        {
+            if path.is_file() {
+                config.merge(Self::from_file(&path)?);
+            }
+        }
+        config.apply_env(|key| std::env::var(key).ok())?;
+        Ok(config)
+    }
+
+    /// Parse one config file, resolving a relative `data_path` against its directory.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: Self = toml::from_str(&contents)
+            .map_err(|e| anyhow::anyhow!("Invalid config {}: {}", path.display(), e))?;
+        if let (Some(data_path), Some(dir)) = (&config.data_path, path.parent()) {
+            if data_path.is_relative() {
+                config.data_path = Some(dir.join(data_path));
+            }
+        }
+        tracing::debug!("Loaded config from {}", path.display());
+        Ok(config)
+    }
+
+    /// Overlay `other` on top of `self` (set values in `other` win).
+    pub fn merge(&mut self, other: Self) {
+        fn take<T>(slot: &mut Option<T>, value: Option<T>) {
+            if value.is_some() {
+                *slot = value;
+            }
+        }
+        take(&mut self.data_path, other.data_path);
+        take(&mut self.author, other.author);
+        take(&mut self.language, other.language);
+        take(&mut self.output.format, other.output.format);
+        take(&mut self.output.detail, other.output.detail);
+        take(&mut self.output.semantic, other.output.semantic);
+        take(&mut self.limits.results, other.limits.results);
+        take(&mut self.limits.token_budget, other.limits.token_budget);
+        self.limits.commands.extend(other.limits.commands);
+        take(&mut self.embedding.model, other.embedding.model);
+        take(&mut self.embedding.device, other.embedding.device);
+        take(&mut self.embedding.threads, other.embedding.threads);
+        take(
+            &mut self.consistency.strictness,
+            other.consistency.strictness,
+        );
+        take(&mut self.backup.keep, other.backup.keep);
+        take(&mut self.arcs.drift_threshold, other.arcs.drift_threshold);
+        take(&mut self.arcs.debounce_secs, other.arcs.debounce_secs);
+        take(&mut self.annotate.schedule, other.annotate.schedule);
+        take(&mut self.annotate.idle_secs, other.annotate.idle_secs);
+        take(&mut self.annotate.nightly_hour, other.annotate.nightly_hour);
+        take(&mut self.export.secret_keys, other.export.secret_keys);
+        take(&mut self.vector_index.kind, other.vector_index.kind);
+        take(&mut self.vector_index.m, other.vector_index.m);
+        take(&mut self.vector_index.efc, other.vector_index.efc);
+        take(&mut self.vector_index.ef, other.vector_index.ef);
+        take(&mut self.vector_index.capacity, other.vector_index.capacity);
+        take(
+            &mut self.clustering.memory_limit_mb,
+            other.clustering.memory_limit_mb,
+        );
+        take(&mut self.clustering.seed, other.clustering.seed);
+        take(&mut self.relationships.strict, other.relationships.strict);
+        self.relationships.types.extend(other.relationships.types);
+        self.transmission.trusted.extend(other.transmission.trusted);
+        self.transmission.neutral.extend(other.transmission.neutral);
+        self.transmission
+            .distrusted
+            .extend(other.transmission.distrusted);
+        take(
+            &mut self.session.half_life_days,
+            other.session.half_life_days,
+        );
+        take(&mut self.session.session_decay, other.session.session_decay);
+        take(&mut self.startup.verbosity, other.startup.verbosity);
+        take(&mut self.startup.sections, other.startup.sections);
+        take(&mut self.startup.token_ceiling, other.startup.token_ceiling);
+        take(
+            &mut self.startup.always_include,
+            other.startup.always_include,
+        );
+    }
+
+    /// Apply environment-variable overrides. `get` abstracts `std::env::var` for tests.
+    pub fn apply_env(&mut self, get: impl Fn(&str) -> Option<String>) -> anyhow::Result<()> {
+        fn parse<T: std::str::FromStr>(key: &str, value: String) -> anyhow::Result<T> {
+            value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid value for {}: '{}'", key, value))
+        }
+
+        if let Some(v) = get("NARRA_AUTHOR") {
+            self.author = Some(v);
+        }
+        if let Some(v) = get("NARRA_LANGUAGE") {
+            self.language = Some(v);
+        }
+        if let Some(v) = get("NARRA_OUTPUT") {
+            self.output.format = Some(v);
+        }
+        if let Some(v) = get("NARRA_DETAIL") {
+            self.output.detail = Some(v);
+        }
+        if let Some(v) = get("NARRA_SEMANTIC") {
+            self.output.semantic = Some(parse("NARRA_SEMANTIC", v)?);
+        }
+        if let Some(v) = get("NARRA_LIMIT") {
+            self.limits.results = Some(parse("NARRA_LIMIT", v)?);
+        }
+        if let Some(v) = get("NARRA_TOKEN_BUDGET") {
+            self.limits.token_budget = Some(parse("NARRA_TOKEN_BUDGET", v)?);
+        }
+        if let Some(v) = get("NARRA_EMBEDDING_MODEL") {
+            self.embedding.model = Some(v);
+        }
+        if let Some(v) = get("NARRA_EMBEDDING_DEVICE") {
+            self.embedding.device = Some(v.parse()?);
+        }
+        if let Some(v) = get("NARRA_CONSISTENCY") {
+            self.consistency.strictness = Some(parse("NARRA_CONSISTENCY", v)?);
+        }
+        if let Some(v) = get("NARRA_ARC_DRIFT") {
+            self.arcs.drift_threshold = Some(parse("NARRA_ARC_DRIFT", v)?);
+        }
+        if let Some(v) = get("NARRA_ANNOTATE_SCHEDULE") {
+            self.annotate.schedule = Some(parse("NARRA_ANNOTATE_SCHEDULE", v)?);
+        }
+        if let Some(v) = get("NARRA_VECTOR_INDEX") {
+            self.vector_index.kind = Some(parse("NARRA_VECTOR_INDEX", v)?);
+        }
+        if let Some(v) = get("NARRA_CLUSTERING_MEMORY_MB") {
+            self.clustering.memory_limit_mb = Some(parse("NARRA_CLUSTERING_MEMORY_MB", v)?);
+        }
+        self.validate()
+    }
+
+    /// Author recorded on mutations: configured name, else the OS user.
+    pub fn author(&self) -> Option<String> {
+        self.author
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .filter(|a| !a.trim().is_empty())
+    }
+
+    /// Reject unknown enum-like string values early with a clear message.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(format) = &self.output.format {
+            if !matches!(format.as_str(), "human" | "json" | "md" | "markdown") {
+                anyhow::bail!(
+                    "output.format must be human, json, or md (got '{}')",
+                    format
+                );
+            }
+        }
+        if let Some(detail) = &self.output.detail {
+            if !matches!(detail.as_str(), "brief" | "standard" | "full") {
+                anyhow::bail!(
+                    "output.detail must be brief, standard, or full (got '{}')",
+                    detail
+                );
+            }
+        }
+        if let Some(language) = &self.language {
+            if language.is_empty()
+                || !language
+                    .chars()
+                    .all(|c| c.is_ascii_alphabetic() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "language must be a language code such as en or de (got '{}')",
+                    language
+                );
+            }
+        }
+        if let Some(threshold) = self.arcs.drift_threshold {
+            if !(0.0..=2.0).contains(&threshold) {
+                anyhow::bail!(
+                    "arcs.drift_threshold must be between 0 and 2 (got {})",
+                    threshold
+                );
+            }
+        }
+        if let Some(hour) = self.annotate.nightly_hour {
+            if hour > 23 {
+                anyhow::bail!(
+                    "annotate.nightly_hour must be between 0 and 23 (got {})",
+                    hour
+                );
+            }
+        }
+        let index = self.vector_index.settings();
+        if !(4..=64).contains(&index.m) {
+            anyhow::bail!("vector_index.m must be between 4 and 64 (got {})", index.m);
+        }
+        if index.efc == 0 || index.ef == 0 {
+            anyhow::bail!("vector_index.efc and vector_index.ef must be positive");
+        }
+        if index.capacity < 2 {
+            anyhow::bail!(
+                "vector_index.capacity must be at least 2 (got {})",
+                index.capacity
+            );
+        }
+        if let Some(mb) = self.clustering.memory_limit_mb {
+            if mb < 16 {
+                anyhow::bail!(
+                    "clustering.memory_limit_mb must be at least 16 (got {})",
+                    mb
+                );
+            }
+        }
+        let decay = self.session.decay();
+        if !decay.half_life_days.is_finite() || decay.half_life_days <= 0.0 {
+            anyhow::bail!(
+                "session.half_life_days must be positive (got {})",
+                decay.half_life_days
+            );
+        }
+        if !(0.0..=1.0).contains(&decay.per_session) {
+            anyhow::bail!(
+                "session.session_decay must be between 0 and 1 (got {})",
+                decay.per_session
+            );
+        }
+        self.relationships.ontology()?;
+        self.transmission.rules()?;
+        self.startup.options()?;
+        Ok(())
+    }
+
+    /// Rewrite clap defaults for `--limit` and `--budget` so configured values
+    /// apply while explicit flags still win.
+    pub fn apply_cli_defaults(&self, command: clap::Command) -> clap::Command {
+        self.apply_defaults_at(command, "")
+    }
+
+    fn apply_defaults_at(&self, mut command: clap::Command, path: &str) -> clap::Command {
+        let has_arg = |cmd: &clap::Command, id: &str| cmd.get_arguments().any(|a| a.get_id() == id);
+
+        let limit = self
+            .limits
+            .commands
+            .get(path)
+            .copied()
+            .or(self.limits.results);
+        if let Some(limit) = limit {
+            if has_arg(&command, "limit") {
+                command = command.mut_arg("limit", |a| a.default_value(leak(limit)));
+            }
+        }
+        if let Some(budget) = self.limits.token_budget {
+            if has_arg(&command, "budget") {
+                command = command.mut_arg("budget", |a| a.default_value(leak(budget)));
+            }
+        }
+
+        let names: Vec<String> = command
+            .get_subcommands()
+            .map(|s| s.get_name().to_string())
+            .collect();
+        for name in names {
+            let sub_path = if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{} {}", path, name)
+            };
+            command = command.mut_subcommand(&name, |sub| self.apply_defaults_at(sub, &sub_path));
+        }
+        command
+    }
+}
+
+/// clap defaults must be `'static`; the CLI builds its command once per process.
+fn leak(value: usize) -> &'static str {
+    Box::leak(value.to_string().into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::StartupVerbosity;
+
+    #[test]
+    fn test_later_layers_win() {
+        let mut config: NarraConfig = toml::from_str(
+            r#"
+            [output]
+            format = "json"
+            [limits]
+            results = 30
+            "#,
+        )
+        .unwrap();
+        let project: NarraConfig = toml::from_str(
+            r#"
+            [limits]
+            results = 50
+            [consistency]
+            strictness = "lenient"
+            [backup]
+            keep = 3
+            "#,
+        )
+        .unwrap();
+        config.merge(project);
+        config
+            .apply_env(|k| (k == "NARRA_OUTPUT").then(|| "md".to_string()))
+            .unwrap();
+
+        assert_eq!(config.output.format.as_deref(), Some("md"));
+        assert_eq!(config.limits.results, Some(50));
+        assert_eq!(
+            config.consistency.strictness,
+            Some(ConsistencyStrictness::Lenient)
+        );
+        assert_eq!(config.backup.keep, Some(3));
+    }
+
+    #[test]
+    fn test_rejects_bad_values() {
+        assert!(toml::from_str::<NarraConfig>("[output]\nfromat = \"json\"").is_err());
+        let mut config = NarraConfig::default();
+        assert!(config
+            .apply_env(|k| (k == "NARRA_LIMIT").then(|| "lots".to_string()))
+            .is_err());
+        assert!(config
+            .apply_env(|k| (k == "NARRA_LANGUAGE").then(|| "de; drop".to_string()))
+            .is_err());
+        assert!(config
+            .apply_env(|k| (k == "NARRA_EMBEDDING_DEVICE").then(|| "directml".to_string()))
+            .is_err());
+        config
+            .apply_env(|k| (k == "NARRA_EMBEDDING_DEVICE").then(|| "CUDA".to_string()))
+            .unwrap();
+        assert_eq!(config.embedding.device, Some(ExecutionProvider::Cuda));
+        assert!(config
+            .apply_env(|k| (k == "NARRA_VECTOR_INDEX").then(|| "ivf".to_string()))
+            .is_err());
+        let mut config: NarraConfig =
+            toml::from_str("[vector_index]\nkind = \"hnsw\"\nm = 2").unwrap();
+        assert!(config.validate().is_err());
+        config.vector_index.m = Some(24);
+        config.validate().unwrap();
+        assert_eq!(config.vector_index.settings().kind, VectorIndexKind::Hnsw);
+        assert_eq!(config.vector_index.settings().ef, 40);
+
+        let mut config: NarraConfig =
+            toml::from_str("[clustering]\nmemory_limit_mb = 4\nseed = 7").unwrap();
+        assert!(config.validate().is_err());
+        config.clustering.memory_limit_mb = Some(64);
+        config.validate().unwrap();
+        assert_eq!(config.clustering.options().memory_limit_bytes, 64 << 20);
+        assert_eq!(config.clustering.options().seed, 7);
+
+        let config: NarraConfig = toml::from_str(
+            "[relationships.types.liege]\nsynonyms = [\"lord\"]\n[relationships.types.lord]\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+        let config: NarraConfig =
+            toml::from_str("[transmission.neutral]\nknows = \"certain\"").unwrap();
+        assert!(config.validate().is_err());
+
+        let mut config: NarraConfig = toml::from_str("[session]\nhalf_life_days = 0.0").unwrap();
+        assert!(config.validate().is_err());
+        config.session.half_life_days = Some(14.0);
+        config.session.session_decay = Some(1.5);
+        assert!(config.validate().is_err());
+        config.session.session_decay = Some(1.0);
+        config.validate().unwrap();
+        assert_eq!(config.session.decay().half_life_days, 14.0);
+
+        let mut config: NarraConfig =
+            toml::from_str("[annotate]\nschedule = \"nightly\"\nnightly_hour = 24").unwrap();
+        assert!(config.validate().is_err());
+        config.annotate.nightly_hour = Some(2);
+        config.validate().unwrap();
+        assert_eq!(
+            config.annotate.policy().schedule,
+            AnnotationSchedule::Nightly
+        );
+        assert!(toml::from_str::<NarraConfig>("[annotate]\nschedule = \"hourly\"").is_err());
+        config
+            .apply_env(|k| (k == "NARRA_ANNOTATE_SCHEDULE").then(|| "on_mutation".to_string()))
+            .unwrap();
+        assert_eq!(
+            config.annotate.policy().schedule,
+            AnnotationSchedule::OnMutation
+        );
+
+        let config: NarraConfig =
+            toml::from_str("[startup]\nsections = [\"journal\", \"gossip\"]").unwrap();
+        assert!(config.validate().is_err());
+        let config: NarraConfig = toml::from_str(
+            "[startup]\nverbosity = \"minimal\"\nsections = [\"pinned\", \"neglected\"]\nalways_include = [\"character:alice\"]",
+        )
+        .unwrap();
+        let options = config.startup.options().unwrap();
+        assert_eq!(options.verbosity, Some(StartupVerbosity::Minimal));
+        assert!(options.includes(StartupSection::Neglected));
+        assert!(!options.includes(StartupSection::Journal));
+    }
+
+    #[test]
+    fn test_relative_data_path_resolves_against_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PROJECT_CONFIG_FILE);
+        std::fs::write(&path, "data_path = \"world\"\n").unwrap();
+
+        let config = NarraConfig::from_file(&path).unwrap();
+        assert_eq!(config.data_path, Some(dir.path().join("world")));
+        assert_eq!(NarraConfig::project_path(dir.path()), Some(path));
+    }
+
+    #[test]
+    fn test_cli_defaults_respect_flags() {
+        let command = clap::Command::new("narra").subcommand(
+            clap::Command::new("find").arg(
+                clap::Arg::new("limit")
+                    .long("limit")
+                    .default_value("20")
+                    .value_parser(clap::value_parser!(usize)),
+            ),
+        );
+        let mut config = NarraConfig::default();
+        config.limits.commands.insert("find".to_string(), 7);
+        let command = config.apply_cli_defaults(command);
+
+        let matches = command.clone().get_matches_from(["narra", "find"]);
+        let find = matches.subcommand_matches("find").unwrap();
+        assert_eq!(find.get_one::<usize>("limit"), Some(&7));
+
+        let matches = command.get_matches_from(["narra", "find", "--limit", "3"]);
+        let find = matches.subcommand_matches("find").unwrap();
+        assert_eq!(find.get_one::<usize>("limit"), Some(&3));
+    }
+}
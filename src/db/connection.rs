@@ -46,6 +46,9 @@ pub enum DbConfig {
         #[serde(default = "default_database")]
         database: String,
     },
+    /// In-memory database sealed to an age-encrypted `world.age` in the data
+    /// directory. See [`crate::db::vault`].
+    Encrypted,
 }
 
 impl Default for DbConfig {
@@ -103,8 +106,10 @@ pub fn load_db_config(data_path: &Path) -> DbConfig {
 
 /// Initialize and connect to a SurrealDB database.
 ///
-/// Supports both embedded RocksDB (single-process) and remote WebSocket
-/// (concurrent access) modes, driven by `DbConfig`.
+/// Supports embedded RocksDB (single-process), remote WebSocket (concurrent
+/// access), and encrypted modes, driven by `DbConfig`. An encrypted world
+/// opened here is read-only in effect: sealing changes back to disk needs the
+/// [`Vault`](crate::db::vault::Vault) itself (see `AppContext`).
 ///
 /// # Arguments
 ///
@@ -125,6 +130,11 @@ pub async fn init_db(config: &DbConfig, data_path: &Path) -> Result<NarraDb, Nar
             db.use_ns("narra").use_db("world").await?;
             Ok(db)
         }
+        DbConfig::Encrypted => {
+            crate::db::vault::Vault::unlock(data_path, true)?
+                .load()
+                .await
+        }
         DbConfig::Remote {
             endpoint,
             username,
@@ -152,3 +162,34 @@ pub async fn init_db(config: &DbConfig, data_path: &Path) -> Result<NarraDb, Nar
         }
     }
 }
+
+/// Empty in-memory database with the same namespace and capabilities as an
+/// embedded world.
+pub async fn connect_memory() -> Result<NarraDb, NarraError> {
+    let surreal_config = surrealdb::opt::Config::new()
+        .capabilities(Capabilities::all().with_all_experimental_features_allowed());
+    let db = surrealdb::engine::any::connect(("mem://", surreal_config)).await?;
+    db.use_ns("narra").use_db("world").await?;
+    Ok(db)
+}
+
+/// In-memory copy of `db`. Writes to the copy never reach the original.
+pub async fn fork_in_memory(db: &NarraDb) -> Result<NarraDb, NarraError> {
+    let dump = String::from_utf8(export_surql(db).await?)
+        .map_err(|e| NarraError::Database(format!("Export is not UTF-8: {}", e)))?;
+    let fork = connect_memory().await?;
+    fork.query(dump).await?.check()?;
+    Ok(fork)
+}
+
+/// Full SurrealQL dump (definitions and records) of the current database.
+pub async fn export_surql(db: &NarraDb) -> Result<Vec<u8>, NarraError> {
+    use futures::StreamExt;
+
+    let mut dump = Vec::new();
+    let mut export = db.export(()).await?;
+    while let Some(chunk) = export.next().await {
+        dump.extend(chunk?);
+    }
+    Ok(dump)
+}
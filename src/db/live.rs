@@ -0,0 +1,90 @@
+//! Change feeds over the narrative tables, via SurrealDB live queries.
+//!
+//! Used by `--watch` on the CLI and by MCP resource subscriptions. Edits from
+//! any connection to the same database arrive here, not only this process's.
+
+use std::pin::Pin;
+
+use futures::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+use surrealdb::{Action, Notification, RecordId};
+
+use crate::db::connection::NarraDb;
+use crate::NarraError;
+
+/// Tables whose changes can alter a report or a resource.
+pub const NARRATIVE_TABLES: &[&str] = &[
+    "character",
+    "location",
+    "event",
+    "scene",
+    "knowledge",
+    "knows",
+    "perceives",
+    "relates_to",
+    "involved_in",
+    "participates_in",
+    "universe_fact",
+    "note",
+    "thread",
+    "goal",
+];
+
+/// The parts of a changed record that say what it concerns.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangedRecord {
+    pub id: RecordId,
+    /// Edge source, for relation tables
+    #[serde(default, rename = "in")]
+    pub from: Option<RecordId>,
+    /// Edge target, for relation tables
+    #[serde(default, rename = "out")]
+    pub to: Option<RecordId>,
+}
+
+/// One create, update or delete.
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub action: Action,
+    pub record: ChangedRecord,
+}
+
+impl Change {
+    /// Whether the change is to `entity_id` itself or an edge touching it.
+    pub fn touches(&self, entity_id: &str) -> bool {
+        std::iter::once(&self.record.id)
+            .chain(&self.record.from)
+            .chain(&self.record.to)
+            .any(|id| id.to_string() == entity_id)
+    }
+
+    /// Whether a record appeared or disappeared (counts change).
+    pub fn is_create_or_delete(&self) -> bool {
+        matches!(self.action, Action::Create | Action::Delete)
+    }
+}
+
+pub type ChangeFeed = Pin<Box<dyn Stream<Item = Result<Change, NarraError>> + Send>>;
+
+/// Subscribe to every change in [`NARRATIVE_TABLES`]. The feed ends when the
+/// connection is lost.
+pub async fn subscribe(db: &NarraDb) -> Result<ChangeFeed, NarraError> {
+    let mut feeds = Vec::with_capacity(NARRATIVE_TABLES.len());
+    for table in NARRATIVE_TABLES {
+        let mut response = db
+            .query(format!("LIVE SELECT id, in, out FROM {}", table))
+            .await?;
+        let feed = response
+            .stream::<Notification<ChangedRecord>>(0)?
+            .map(|notification| {
+                notification
+                    .map(|n| Change {
+                        action: n.action,
+                        record: n.data,
+                    })
+                    .map_err(NarraError::from)
+            });
+        feeds.push(feed.boxed());
+    }
+    Ok(stream::select_all(feeds).boxed())
+}
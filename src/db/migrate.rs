@@ -0,0 +1,232 @@
+//! Versioned schema migrations.
+//!
+//! Each migration is a numbered `.surql` file under `db/migrations/`, listed
+//! in [`crate::db::schema::MIGRATIONS`]. The versions a database has applied
+//! are recorded in its `schema_migration` table, so opening a world runs only
+//! the migrations it hasn't seen, in order.
+//!
+//! Migrations up to [`BASELINE_VERSION`] predate the table. They used to run
+//! on every start, so they only define things and tolerate being repeated:
+//! a database without records gets them again (ignoring "already exists"
+//! errors) and is tracked from then on. Later migrations run once, inside a
+//! transaction, and stop the upgrade if any statement fails.
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use surrealdb::Datetime;
+
+use crate::db::connection::NarraDb;
+use crate::db::schema::MIGRATIONS;
+use crate::NarraError;
+
+/// Last migration written before applied versions were recorded.
+pub const BASELINE_VERSION: u32 = 40;
+
+/// The table recording applied migrations. Defined outside the numbered
+/// migrations because it has to exist before any of them is recorded.
+const TRACKING_SCHEMA: &str = "
+DEFINE TABLE IF NOT EXISTS schema_migration SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS version ON schema_migration TYPE int;
+DEFINE FIELD IF NOT EXISTS name ON schema_migration TYPE string;
+DEFINE FIELD IF NOT EXISTS checksum ON schema_migration TYPE string;
+DEFINE FIELD IF NOT EXISTS applied_at ON schema_migration TYPE datetime DEFAULT time::now() READONLY;
+DEFINE INDEX IF NOT EXISTS schema_migration_version ON schema_migration FIELDS version UNIQUE;
+";
+
+const RECORD_MIGRATION: &str = "CREATE type::thing('schema_migration', $version) CONTENT {
+    version: $version,
+    name: $name,
+    checksum: $checksum
+}";
+
+/// One numbered schema change.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    /// File name without the number, e.g. `style_rules`
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub sql: &'static str,
+}
+
+impl Migration {
+    /// SHA-256 of the migration's text, to notice files edited after release.
+    pub fn checksum(&self) -> String {
+        format!("{:x}", Sha256::digest(self.sql.as_bytes()))
+    }
+
+    /// `041_name`, as the file is called.
+    pub fn label(&self) -> String {
+        format!("{:03}_{}", self.version, self.name)
+    }
+}
+
+/// A migration a database has recorded as applied.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: Datetime,
+}
+
+/// Where a database stands on one migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    /// Applied, but the file has changed since
+    Modified,
+}
+
+impl MigrationState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MigrationState::Applied => "applied",
+            MigrationState::Pending => "pending",
+            MigrationState::Modified => "modified",
+        }
+    }
+}
+
+/// A database's schema version compared to the migrations this build knows.
+#[derive(Debug, Clone)]
+pub struct SchemaStatus {
+    pub migrations: Vec<(&'static Migration, MigrationState, Option<Datetime>)>,
+    /// Versions recorded by a newer narra than this one
+    pub unknown: Vec<AppliedMigration>,
+}
+
+impl SchemaStatus {
+    pub fn pending(&self) -> Vec<&'static Migration> {
+        self.migrations
+            .iter()
+            .filter(|(_, state, _)| *state == MigrationState::Pending)
+            .map(|(m, _, _)| *m)
+            .collect()
+    }
+
+    /// Highest version applied, 0 for a database never migrated.
+    pub fn current_version(&self) -> u32 {
+        self.migrations
+            .iter()
+            .filter(|(_, state, _)| *state != MigrationState::Pending)
+            .map(|(m, _, _)| m.version)
+            .chain(self.unknown.iter().map(|a| a.version))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Migrations recorded in the database, by version.
+pub async fn applied(db: &NarraDb) -> Result<Vec<AppliedMigration>, NarraError> {
+    db.query(TRACKING_SCHEMA).await?.check()?;
+    let mut response = db
+        .query("SELECT version, name, checksum, applied_at FROM schema_migration ORDER BY version")
+        .await?;
+    Ok(response.take(0)?)
+}
+
+/// Compare the database's recorded migrations with [`MIGRATIONS`].
+pub async fn status(db: &NarraDb) -> Result<SchemaStatus, NarraError> {
+    let mut recorded = applied(db).await?;
+    let migrations = MIGRATIONS
+        .iter()
+        .map(
+            |m| match recorded.iter().position(|a| a.version == m.version) {
+                Some(i) => {
+                    let a = recorded.remove(i);
+                    let state = if a.checksum == m.checksum() {
+                        MigrationState::Applied
+                    } else {
+                        MigrationState::Modified
+                    };
+                    (m, state, Some(a.applied_at))
+                }
+                None => (m, MigrationState::Pending, None),
+            },
+        )
+        .collect();
+    Ok(SchemaStatus {
+        migrations,
+        unknown: recorded,
+    })
+}
+
+/// Apply every pending migration in version order, returning those applied.
+///
+/// Stops at the first failure; migrations before it stay applied and
+/// recorded, so fixing the cause and running again picks up where it left off.
+pub async fn up(db: &NarraDb) -> Result<Vec<&'static Migration>, NarraError> {
+    let status = status(db).await?;
+    if let Some(newest) = status.unknown.last() {
+        tracing::warn!(
+            "Database schema is at version {} ({}), newer than this narra knows; \
+             upgrade narra before writing to this world",
+            newest.version,
+            newest.name
+        );
+    }
+    for (m, state, _) in &status.migrations {
+        if *state == MigrationState::Modified {
+            tracing::warn!("Migration {} changed after it was applied", m.label());
+        }
+    }
+
+    let pending = status.pending();
+    for migration in &pending {
+        apply(db, migration).await?;
+        tracing::debug!("Applied migration {}", migration.label());
+    }
+    Ok(pending)
+}
+
+async fn apply(db: &NarraDb, migration: &Migration) -> Result<(), NarraError> {
+    let failed = |e: surrealdb::Error| {
+        NarraError::Database(format!("Migration {} failed: {}", migration.label(), e))
+    };
+
+    if migration.version <= BASELINE_VERSION {
+        // Statement errors here are definitions that already exist
+        db.query(migration.sql).await?;
+        db.query(RECORD_MIGRATION)
+            .bind(("version", migration.version))
+            .bind(("name", migration.name))
+            .bind(("checksum", migration.checksum()))
+            .await?
+            .check()
+            .map_err(failed)?;
+        return Ok(());
+    }
+
+    db.query(format!(
+        "BEGIN TRANSACTION;\n{}\n;\n{};\nCOMMIT TRANSACTION;",
+        migration.sql, RECORD_MIGRATION
+    ))
+    .bind(("version", migration.version))
+    .bind(("name", migration.name))
+    .bind(("checksum", migration.checksum()))
+    .await?
+    .check()
+    .map_err(failed)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_numbered_in_order() {
+        for (i, m) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(
+                m.version as usize,
+                i + 1,
+                "{} is out of sequence",
+                m.label()
+            );
+            assert!(!m.sql.trim().is_empty(), "{} is empty", m.label());
+        }
+        assert!(MIGRATIONS.len() as u32 >= BASELINE_VERSION);
+    }
+}
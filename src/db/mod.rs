@@ -1,2 +1,6 @@
 pub mod connection;
+pub mod live;
+pub mod migrate;
+pub mod query;
 pub mod schema;
+pub mod vault;
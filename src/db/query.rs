@@ -0,0 +1,117 @@
+//! Parameterised query helpers.
+//!
+//! Record IDs go into queries as bound [`RecordId`] parameters, never spliced
+//! into the SQL text, so a key containing quotes, spaces or `;` can't change
+//! the statement and every call with the same shape sends the same text.
+//!
+//! SQL whose shape varies (a set of facet fields, say) is built once per
+//! shape and kept in a process-wide statement cache. narra opens one
+//! connection per process, so this is the connection's cache in practice;
+//! the text holds no connection state either way.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use surrealdb::RecordId;
+
+use crate::NarraError;
+
+/// Record ID for `table` from either a bare key (`"alice"`) or a full ID
+/// (`"character:alice"`). A full ID of another table is rejected.
+pub fn record_id(table: &str, id: &str) -> Result<RecordId, NarraError> {
+    let key = match id.split_once(':') {
+        Some((t, key)) if t == table => key,
+        Some((t, _)) => {
+            return Err(NarraError::Validation(format!(
+                "Expected a {} ID, got a {} ID: {}",
+                table, t, id
+            )))
+        }
+        None => id,
+    };
+    Ok(RecordId::from((table, unquote(key))))
+}
+
+/// Record ID from a full `table:key` string, for references that can point
+/// at several tables (knowledge targets, fact applications).
+pub fn parse_record_id(id: &str) -> Result<RecordId, NarraError> {
+    match id.split_once(':') {
+        Some((table, key)) if is_identifier(table) && !key.is_empty() => {
+            Ok(RecordId::from((table, unquote(key))))
+        }
+        _ => Err(NarraError::Validation(format!(
+            "Invalid entity ID: {} (expected table:key)",
+            id
+        ))),
+    }
+}
+
+/// Strip the `⟨…⟩` or backtick quoting SurrealDB prints around complex keys.
+fn unquote(key: &str) -> &str {
+    key.strip_prefix('⟨')
+        .and_then(|k| k.strip_suffix('⟩'))
+        .or_else(|| key.strip_prefix('`').and_then(|k| k.strip_suffix('`')))
+        .unwrap_or(key)
+}
+
+/// Whether `name` is a plain SurrealQL identifier (safe to place in SQL text).
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+static STATEMENTS: OnceLock<Mutex<HashMap<String, Arc<str>>>> = OnceLock::new();
+
+/// Statement for `key`, built by `build` on first use and cached after.
+///
+/// `key` must identify the statement's shape completely; values belong in
+/// bound parameters, not in the key or the built text.
+pub fn statement(key: &str, build: impl FnOnce() -> String) -> Arc<str> {
+    let cache = STATEMENTS.get_or_init(Default::default);
+    let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(sql) = cache.get(key) {
+        return sql.clone();
+    }
+    let sql: Arc<str> = build().into();
+    cache.insert(key.to_string(), sql.clone());
+    sql
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_ids_are_parsed_not_spliced() {
+        assert_eq!(
+            record_id("character", "alice").unwrap(),
+            RecordId::from(("character", "alice"))
+        );
+        assert_eq!(
+            record_id("character", "character:alice").unwrap(),
+            RecordId::from(("character", "alice"))
+        );
+        assert_eq!(
+            record_id("character", "character:⟨o'brien; DELETE x⟩").unwrap(),
+            RecordId::from(("character", "o'brien; DELETE x"))
+        );
+        assert!(record_id("character", "location:alice").is_err());
+
+        assert_eq!(
+            parse_record_id("knowledge:secret").unwrap(),
+            RecordId::from(("knowledge", "secret"))
+        );
+        assert!(parse_record_id("secret").is_err());
+        assert!(parse_record_id("know ledge:secret").is_err());
+    }
+
+    #[test]
+    fn test_statement_builds_once_per_key() {
+        let first = statement("test:shape", || "SELECT 1".to_string());
+        let second = statement("test:shape", || unreachable!("cached"));
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+}
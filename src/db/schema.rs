@@ -1,89 +1,300 @@
 use crate::db::connection::NarraDb;
+use crate::db::migrate::{self, Migration};
 use crate::NarraError;
 
-/// Phase 1 schema: Foundation tables (character, location, event, relates_to, knowledge)
-const SCHEMA_001: &str = include_str!("migrations/001_initial_schema.surql");
-
-/// Phase 2 schema: Character psychology, asymmetric relationships, scenes
-const SCHEMA_002: &str = include_str!("migrations/002_phase2_schema.surql");
-
-/// Phase 3 schema: Knowledge states with certainty and provenance
-const SCHEMA_003: &str = include_str!("migrations/003_phase3_schema.surql");
-
-/// Phase 4 schema: Search infrastructure with FULLTEXT indexes
-const SCHEMA_004: &str = include_str!("migrations/004_phase4_search.surql");
-
-/// Phase 7 schema: Notes for freeform worldbuilding content
-const SCHEMA_005: &str = include_str!("migrations/005_phase7_notes.surql");
-
-/// Phase 11 schema: Universe facts for world rules and constraints
-const SCHEMA_006: &str = include_str!("migrations/006_phase11_facts.surql");
-
-/// Phase 16 schema: Embedding fields for semantic search
-const SCHEMA_007: &str = include_str!("migrations/007_phase16_embeddings.surql");
-
-/// Phase 17 schema: Referential integrity constraints
-const SCHEMA_008: &str = include_str!("migrations/008_phase17_referential_integrity.surql");
-
-/// HNSW vector indexes for semantic search
-const SCHEMA_009: &str = include_str!("migrations/009_hnsw_vector_indexes.surql");
-
-/// Knowledge embeddings: embedding fields + HNSW index for knowledge table
-const SCHEMA_010: &str = include_str!("migrations/010_knowledge_embeddings.surql");
-
-/// Arc snapshots: temporal embedding history for character arc tracking
-const SCHEMA_011: &str = include_str!("migrations/011_arc_snapshots.surql");
-
-/// Perspective embeddings on perceives edges + arc_snapshot type extension
-const SCHEMA_012: &str = include_str!("migrations/012_perspective_embeddings.surql");
-
-/// Embedding infrastructure: relates_to embeddings + composite_text storage
-const SCHEMA_013: &str = include_str!("migrations/013_embedding_infra.surql");
-
-/// Character profile: replace typed wounds/desires/contradictions with flexible profile HashMap
-const SCHEMA_014: &str = include_str!("migrations/014_character_profile.surql");
-
-/// Drop unused HNSW vector indexes (brute-force cosine is reliable + fast at narrative scale)
-const SCHEMA_015: &str = include_str!("migrations/015_drop_hnsw_indexes.surql");
-
-/// Embedding metadata: world_meta table for model/dimensions/provider tracking
-const SCHEMA_016: &str = include_str!("migrations/016_embedding_metadata.surql");
-
-/// Character facets: multi-vector embeddings for faceted search
-const SCHEMA_017: &str = include_str!("migrations/017_character_facets.surql");
-
-/// Phases: persisted narrative phase detection results + membership edges
-const SCHEMA_018: &str = include_str!("migrations/018_phases.surql");
-
-/// Annotations: generic ML model outputs cached per entity
-const SCHEMA_019: &str = include_str!("migrations/019_annotations.surql");
+/// Every schema migration, in version order.
+///
+/// To change the model, add the next numbered file under `migrations/` and
+/// append it here. Never edit or renumber a migration that has shipped:
+/// databases that already applied it won't run it again.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        summary: "Phase 1 schema: Foundation tables (character, location, event, relates_to, knowledge)",
+        sql: include_str!("migrations/001_initial_schema.surql"),
+    },
+    Migration {
+        version: 2,
+        name: "phase2_schema",
+        summary: "Phase 2 schema: Character psychology, asymmetric relationships, scenes",
+        sql: include_str!("migrations/002_phase2_schema.surql"),
+    },
+    Migration {
+        version: 3,
+        name: "phase3_schema",
+        summary: "Phase 3 schema: Knowledge states with certainty and provenance",
+        sql: include_str!("migrations/003_phase3_schema.surql"),
+    },
+    Migration {
+        version: 4,
+        name: "phase4_search",
+        summary: "Phase 4 schema: Search infrastructure with FULLTEXT indexes",
+        sql: include_str!("migrations/004_phase4_search.surql"),
+    },
+    Migration {
+        version: 5,
+        name: "phase7_notes",
+        summary: "Phase 7 schema: Notes for freeform worldbuilding content",
+        sql: include_str!("migrations/005_phase7_notes.surql"),
+    },
+    Migration {
+        version: 6,
+        name: "phase11_facts",
+        summary: "Phase 11 schema: Universe facts for world rules and constraints",
+        sql: include_str!("migrations/006_phase11_facts.surql"),
+    },
+    Migration {
+        version: 7,
+        name: "phase16_embeddings",
+        summary: "Phase 16 schema: Embedding fields for semantic search",
+        sql: include_str!("migrations/007_phase16_embeddings.surql"),
+    },
+    Migration {
+        version: 8,
+        name: "phase17_referential_integrity",
+        summary: "Phase 17 schema: Referential integrity constraints",
+        sql: include_str!("migrations/008_phase17_referential_integrity.surql"),
+    },
+    Migration {
+        version: 9,
+        name: "hnsw_vector_indexes",
+        summary: "HNSW vector indexes for semantic search",
+        sql: include_str!("migrations/009_hnsw_vector_indexes.surql"),
+    },
+    Migration {
+        version: 10,
+        name: "knowledge_embeddings",
+        summary: "Knowledge embeddings: embedding fields + HNSW index for knowledge table",
+        sql: include_str!("migrations/010_knowledge_embeddings.surql"),
+    },
+    Migration {
+        version: 11,
+        name: "arc_snapshots",
+        summary: "Arc snapshots: temporal embedding history for character arc tracking",
+        sql: include_str!("migrations/011_arc_snapshots.surql"),
+    },
+    Migration {
+        version: 12,
+        name: "perspective_embeddings",
+        summary: "Perspective embeddings on perceives edges + arc_snapshot type extension",
+        sql: include_str!("migrations/012_perspective_embeddings.surql"),
+    },
+    Migration {
+        version: 13,
+        name: "embedding_infra",
+        summary: "Embedding infrastructure: relates_to embeddings + composite_text storage",
+        sql: include_str!("migrations/013_embedding_infra.surql"),
+    },
+    Migration {
+        version: 14,
+        name: "character_profile",
+        summary: "Character profile: replace typed wounds/desires/contradictions with flexible profile HashMap",
+        sql: include_str!("migrations/014_character_profile.surql"),
+    },
+    Migration {
+        version: 15,
+        name: "drop_hnsw_indexes",
+        summary: "Drop unused HNSW vector indexes (brute-force cosine is reliable + fast at narrative scale)",
+        sql: include_str!("migrations/015_drop_hnsw_indexes.surql"),
+    },
+    Migration {
+        version: 16,
+        name: "embedding_metadata",
+        summary: "Embedding metadata: world_meta table for model/dimensions/provider tracking",
+        sql: include_str!("migrations/016_embedding_metadata.surql"),
+    },
+    Migration {
+        version: 17,
+        name: "character_facets",
+        summary: "Character facets: multi-vector embeddings for faceted search",
+        sql: include_str!("migrations/017_character_facets.surql"),
+    },
+    Migration {
+        version: 18,
+        name: "phases",
+        summary: "Phases: persisted narrative phase detection results + membership edges",
+        sql: include_str!("migrations/018_phases.surql"),
+    },
+    Migration {
+        version: 19,
+        name: "annotations",
+        summary: "Annotations: generic ML model outputs cached per entity",
+        sql: include_str!("migrations/019_annotations.surql"),
+    },
+    Migration {
+        version: 20,
+        name: "composite_cache",
+        summary: "Composite cache: dossiers and situation reports keyed by world revision",
+        sql: include_str!("migrations/020_composite_cache.surql"),
+    },
+    Migration {
+        version: 21,
+        name: "threads",
+        summary: "Threads: plot thread registry (setup, payoff, elements)",
+        sql: include_str!("migrations/021_threads.surql"),
+    },
+    Migration {
+        version: 22,
+        name: "foreshadows",
+        summary: "Foreshadows: setup -> payoff edges between scenes, events and knowledge",
+        sql: include_str!("migrations/022_foreshadows.surql"),
+    },
+    Migration {
+        version: 23,
+        name: "scene_requires",
+        summary: "Scene requires: scene -> prerequisite scene dependency edges",
+        sql: include_str!("migrations/023_scene_requires.surql"),
+    },
+    Migration {
+        version: 24,
+        name: "word_counts",
+        summary: "Word counts: scene word counts, world word target, progress snapshots",
+        sql: include_str!("migrations/024_word_counts.surql"),
+    },
+    Migration {
+        version: 25,
+        name: "thread_deadlines",
+        summary: "Thread deadlines: resolve-by sequence or phase on threads",
+        sql: include_str!("migrations/025_thread_deadlines.surql"),
+    },
+    Migration {
+        version: 26,
+        name: "oplog",
+        summary: "Oplog: per-mutation author and state log for offline sync",
+        sql: include_str!("migrations/026_oplog.surql"),
+    },
+    Migration {
+        version: 27,
+        name: "access",
+        summary: "Access: per-user roles for shared worlds",
+        sql: include_str!("migrations/027_access.surql"),
+    },
+    Migration {
+        version: 28,
+        name: "vector_index",
+        summary: "Vector index: which ANN index was built on embedding fields",
+        sql: include_str!("migrations/028_vector_index.surql"),
+    },
+    Migration {
+        version: 29,
+        name: "phase_labels",
+        summary: "Phase labels: custom names that survive phase re-detection",
+        sql: include_str!("migrations/029_phase_labels.surql"),
+    },
+    Migration {
+        version: 30,
+        name: "tags",
+        summary: "Tags: freeform labels on scenes, events, and notes",
+        sql: include_str!("migrations/030_tags.surql"),
+    },
+    Migration {
+        version: 31,
+        name: "note_links",
+        summary: "Note links: attachments made by `[[Entity Name]]` in a note body",
+        sql: include_str!("migrations/031_note_links.surql"),
+    },
+    Migration {
+        version: 32,
+        name: "note_kinds",
+        summary: "Note kinds: research, todo, and critique notes with a status",
+        sql: include_str!("migrations/032_note_kinds.surql"),
+    },
+    Migration {
+        version: 33,
+        name: "assets",
+        summary: "Assets: images attached to entities, stored under the data dir",
+        sql: include_str!("migrations/033_assets.surql"),
+    },
+    Migration {
+        version: 34,
+        name: "relationship_intent",
+        summary: "Relationship intent: one-sided edges reciprocity checks should skip",
+        sql: include_str!("migrations/034_relationship_intent.surql"),
+    },
+    Migration {
+        version: 35,
+        name: "knowledge_derivation",
+        summary: "Knowledge derivation: certainty derived when knowledge is passed on",
+        sql: include_str!("migrations/035_knowledge_derivation.surql"),
+    },
+    Migration {
+        version: 36,
+        name: "perception_trust",
+        summary: "Perception trust: how far an observer trusts the one they perceive",
+        sql: include_str!("migrations/036_perception_trust.surql"),
+    },
+    Migration {
+        version: 37,
+        name: "fact_exceptions",
+        summary: "Fact exceptions: entities a universe fact deliberately doesn't bind",
+        sql: include_str!("migrations/037_fact_exceptions.surql"),
+    },
+    Migration {
+        version: 38,
+        name: "terms",
+        summary: "Glossary: in-world terms with definitions, aliases and first use",
+        sql: include_str!("migrations/038_terms.surql"),
+    },
+    Migration {
+        version: 39,
+        name: "lexicon",
+        summary: "Lexicon: constructed-language words with glosses and etymology",
+        sql: include_str!("migrations/039_lexicon.surql"),
+    },
+    Migration {
+        version: 40,
+        name: "style_rules",
+        summary: "Style sheet: spelling, capitalization and punctuation rules for `lint`",
+        sql: include_str!("migrations/040_style_rules.surql"),
+    },
+    Migration {
+        version: 41,
+        name: "dialogue",
+        summary: "Dialogue: spoken lines from imported scripts, by scene and character",
+        sql: include_str!("migrations/041_dialogue.surql"),
+    },
+    Migration {
+        version: 42,
+        name: "annotation_input_hash",
+        summary: "Annotation input hashes for incremental re-annotation",
+        sql: include_str!("migrations/042_annotation_input_hash.surql"),
+    },
+    Migration {
+        version: 43,
+        name: "goals",
+        summary: "Character goals: want, need, stakes and obstacle, with a status",
+        sql: include_str!("migrations/043_goals.surql"),
+    },
+    Migration {
+        version: 44,
+        name: "event_narrative_status",
+        summary: "Event narrative status: backstory, on_page or planned",
+        sql: include_str!("migrations/044_event_narrative_status.surql"),
+    },
+    Migration {
+        version: 45,
+        name: "scene_draft_status",
+        summary: "Scene draft status: outline, drafted, revised or final",
+        sql: include_str!("migrations/045_scene_draft_status.surql"),
+    },
+    Migration {
+        version: 46,
+        name: "mentions",
+        summary: "Prose mentions: entities named in profiles, descriptions and summaries",
+        sql: include_str!("migrations/046_mentions.surql"),
+    },
+];
 
-/// Apply the database schema to an initialized database connection.
+/// Bring the database schema up to date on an initialized connection.
 ///
-/// This executes all DEFINE statements in the schema files, creating tables,
-/// fields, and indexes. Migrations are applied in order:
-/// - 001: Foundation tables (character, location, event, relates_to, knowledge)
-/// - 002: Phase 2 extensions (character psychology, perceives edge, scene, participates_in, involved_in)
-/// - 003: Phase 3 extensions (knows edge for temporal knowledge with certainty/provenance)
-/// - 004: Phase 4 extensions (FULLTEXT indexes for search with BM25 ranking)
-/// - 005: Phase 7 extensions (notes for freeform worldbuilding content)
-/// - 006: Phase 11 extensions (universe facts for world rules and constraints)
-/// - 007: Phase 16 extensions (embedding fields for semantic search)
-/// - 008: Phase 17 extensions (referential integrity with REFERENCE clauses)
-/// - 009: HNSW vector indexes for semantic search
-/// - 010: Knowledge embeddings (embedding fields + HNSW index for knowledge)
-/// - 011: Arc snapshots (temporal embedding history for character arc tracking)
-/// - 012: Perspective embeddings (per-observer view embeddings on perceives edges)
-/// - 013: Embedding infrastructure (relates_to embeddings + composite_text storage)
-/// - 014: Character profile (replace wounds/desires/contradictions with flexible profile)
-/// - 015: Drop unused HNSW vector indexes (brute-force cosine at narrative scale)
-/// - 016: Embedding metadata (world_meta table for model/dimensions/provider tracking)
-/// - 017: Character facets (multi-vector embeddings for faceted search)
-/// - 018: Phases (persisted narrative phase detection results + membership edges)
-/// - 019: Annotations (generic ML model outputs cached per entity)
+/// Runs every migration in [`MIGRATIONS`] the database hasn't recorded yet,
+/// in version order, and records each one in the `schema_migration` table
+/// (see [`crate::db::migrate`]). Databases created before migrations were
+/// tracked get the baseline migrations re-applied once; they only contain
+/// `DEFINE` statements and are safe to repeat.
 ///
-/// It's safe to call multiple times - SurrealDB will update existing definitions
-/// rather than fail.
+/// It's safe to call multiple times - an up-to-date database runs nothing.
 ///
 /// # Arguments
 ///
@@ -106,24 +317,9 @@ const SCHEMA_019: &str = include_str!("migrations/019_annotations.surql");
 /// # }
 /// ```
 pub async fn apply_schema(db: &NarraDb) -> Result<(), NarraError> {
-    db.query(SCHEMA_001).await?;
-    db.query(SCHEMA_002).await?;
-    db.query(SCHEMA_003).await?;
-    db.query(SCHEMA_004).await?;
-    db.query(SCHEMA_005).await?;
-    db.query(SCHEMA_006).await?;
-    db.query(SCHEMA_007).await?;
-    db.query(SCHEMA_008).await?;
-    db.query(SCHEMA_009).await?;
-    db.query(SCHEMA_010).await?;
-    db.query(SCHEMA_011).await?;
-    db.query(SCHEMA_012).await?;
-    db.query(SCHEMA_013).await?;
-    db.query(SCHEMA_014).await?;
-    db.query(SCHEMA_015).await?;
-    db.query(SCHEMA_016).await?;
-    db.query(SCHEMA_017).await?;
-    db.query(SCHEMA_018).await?;
-    db.query(SCHEMA_019).await?;
+    let applied = migrate::up(db).await?;
+    if !applied.is_empty() {
+        tracing::info!("Applied {} schema migration(s)", applied.len());
+    }
     Ok(())
 }
@@ -0,0 +1,292 @@
+//! Encryption at rest for embedded worlds.
+//!
+//! With `mode = "encrypted"` in `database.toml`, the world is stored as
+//! `<data_path>/world.age`: a SurrealQL export encrypted with an age
+//! passphrase (scrypt + ChaCha20-Poly1305). It is decrypted into an in-memory
+//! database on startup and re-sealed after mutations and on exit, so the
+//! plaintext never touches disk.
+//!
+//! Each process works on its own in-memory copy, so only one may have the
+//! world open at a time: `world.age.lock` is held for the whole session and
+//! a second process is refused, like a second process on RocksDB. A seal is
+//! skipped when the export hasn't changed since it was loaded or last
+//! sealed, so read-only commands never rewrite the file.
+//!
+//! The passphrase comes from `NARRA_PASSPHRASE`, then the OS keyring (see
+//! `narra vault keyring`), then an interactive prompt.
+
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use age::secrecy::{ExposeSecret, SecretString};
+use sha2::{Digest, Sha256};
+
+use crate::db::connection::{connect_memory, export_surql, NarraDb};
+use crate::services::events::{EventBus, EventListener};
+use crate::NarraError;
+
+/// Encrypted world file inside the data directory.
+pub const VAULT_FILE: &str = "world.age";
+/// Held while a process has the world open.
+pub const LOCK_FILE: &str = "world.age.lock";
+const PASSPHRASE_ENV: &str = "NARRA_PASSPHRASE";
+const KEYRING_SERVICE: &str = "narra";
+
+fn vault_error(msg: impl std::fmt::Display) -> NarraError {
+    NarraError::Database(format!("Vault: {}", msg))
+}
+
+/// An unlocked encrypted world.
+pub struct Vault {
+    path: PathBuf,
+    passphrase: SecretString,
+    /// Digest of the export last loaded or sealed. Also serializes seals so
+    /// concurrent mutations can't interleave file writes.
+    sealed: tokio::sync::Mutex<Option<[u8; 32]>>,
+    /// The session lock, taken by `load` and released on drop.
+    session: std::sync::Mutex<Option<std::fs::File>>,
+}
+
+impl Vault {
+    pub fn path(data_path: &Path) -> PathBuf {
+        data_path.join(VAULT_FILE)
+    }
+
+    pub fn exists(data_path: &Path) -> bool {
+        Self::path(data_path).is_file()
+    }
+
+    pub fn with_passphrase(data_path: &Path, passphrase: SecretString) -> Self {
+        Self {
+            path: Self::path(data_path),
+            passphrase,
+            sealed: tokio::sync::Mutex::new(None),
+            session: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Take the session lock, refusing when another process holds it.
+    fn lock_session(&self) -> Result<(), NarraError> {
+        let mut session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+        if session.is_some() {
+            return Ok(());
+        }
+        let dir = self.path.parent().unwrap_or(Path::new("."));
+        std::fs::create_dir_all(dir)?;
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join(LOCK_FILE))?;
+        match file.try_lock() {
+            Ok(()) => {
+                *session = Some(file);
+                Ok(())
+            }
+            Err(std::fs::TryLockError::WouldBlock) => Err(vault_error(
+                "the world is open in another narra process (e.g. 'narra mcp'); close it first",
+            )),
+            Err(std::fs::TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    /// Resolve the passphrase (env, keyring, then prompt if `interactive`).
+    pub fn unlock(data_path: &Path, interactive: bool) -> Result<Self, NarraError> {
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+            return Ok(Self::with_passphrase(data_path, passphrase.into()));
+        }
+        if let Some(passphrase) = keyring_passphrase(data_path) {
+            return Ok(Self::with_passphrase(data_path, passphrase));
+        }
+        if interactive && std::io::stdin().is_terminal() {
+            let confirm = !Self::exists(data_path);
+            return Ok(Self::with_passphrase(
+                data_path,
+                prompt_passphrase(confirm)?,
+            ));
+        }
+        Err(vault_error(format!(
+            "world is encrypted; set {} or store the passphrase with 'narra vault keyring'",
+            PASSPHRASE_ENV
+        )))
+    }
+
+    /// Decrypt the world into a fresh in-memory database.
+    ///
+    /// A missing vault file yields an empty world (first run after
+    /// `mode = "encrypted"` was configured).
+    ///
+    /// Takes the session lock first; fails when another process has the
+    /// world open.
+    pub async fn load(&self) -> Result<NarraDb, NarraError> {
+        self.lock_session()?;
+        let db = connect_memory().await?;
+
+        if self.path.is_file() {
+            let plaintext = self.decrypt(&std::fs::read(&self.path)?)?;
+            let dump = String::from_utf8(plaintext).map_err(vault_error)?;
+            db.query(dump).await?.check()?;
+            *self.sealed.lock().await = Some(Sha256::digest(export_surql(&db).await?).into());
+        }
+        Ok(db)
+    }
+
+    /// Export `db` and atomically replace the vault file with its encryption,
+    /// unless nothing changed since it was loaded or last sealed.
+    pub async fn seal(&self, db: &NarraDb) -> Result<(), NarraError> {
+        let mut sealed = self.sealed.lock().await;
+
+        let dump = export_surql(db).await?;
+        let digest: [u8; 32] = Sha256::digest(&dump).into();
+        if *sealed == Some(digest) {
+            return Ok(());
+        }
+        let ciphertext = self.encrypt(&dump)?;
+
+        // Unique per write, so nothing else can clobber it before the rename
+        let tmp = self
+            .path
+            .with_extension(format!("age.{}.tmp", uuid::Uuid::new_v4().simple()));
+        std::fs::write(&tmp, ciphertext)?;
+        if let Err(e) = std::fs::rename(&tmp, &self.path) {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+        *sealed = Some(digest);
+        tracing::debug!("Sealed {}", self.path.display());
+        Ok(())
+    }
+
+    /// Re-seal after every event so a long-running server loses nothing if
+    /// it is killed.
+    pub fn listen(self: &Arc<Self>, bus: &EventBus, db: Arc<NarraDb>) -> EventListener {
+        let vault = self.clone();
+        EventListener::spawn(bus, move |_event| {
+            let vault = vault.clone();
+            let db = db.clone();
+            async move {
+                if let Err(e) = vault.seal(&db).await {
+                    tracing::error!("Failed to seal encrypted world: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Whether the passphrase opens this vault.
+    pub fn verify(&self) -> Result<(), NarraError> {
+        self.decrypt(&std::fs::read(&self.path)?).map(|_| ())
+    }
+
+    /// Encrypt arbitrary bytes with this world's passphrase (also used for backups).
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, NarraError> {
+        let recipient = age::scrypt::Recipient::new(self.passphrase.clone());
+        age::encrypt(&recipient, plaintext).map_err(vault_error)
+    }
+
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, NarraError> {
+        let identity = age::scrypt::Identity::new(self.passphrase.clone());
+        age::decrypt(&identity, ciphertext)
+            .map_err(|_| vault_error("wrong passphrase or corrupted data"))
+    }
+
+    pub fn passphrase(&self) -> &SecretString {
+        &self.passphrase
+    }
+}
+
+/// Ask for the passphrase on the terminal; `confirm` asks twice.
+pub fn prompt_passphrase(confirm: bool) -> Result<SecretString, NarraError> {
+    let passphrase = rpassword::prompt_password("World passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err(vault_error("passphrase cannot be empty"));
+    }
+    if confirm && rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+        return Err(vault_error("passphrases do not match"));
+    }
+    Ok(passphrase.into())
+}
+
+/// Keyring entries are per data directory, so several encrypted worlds can
+/// have different passphrases.
+fn keyring_entry(data_path: &Path) -> keyring::Result<keyring::Entry> {
+    let account = data_path
+        .canonicalize()
+        .unwrap_or_else(|_| data_path.to_path_buf());
+    keyring::Entry::new(KEYRING_SERVICE, &account.to_string_lossy())
+}
+
+fn keyring_passphrase(data_path: &Path) -> Option<SecretString> {
+    keyring_entry(data_path)
+        .and_then(|entry| entry.get_password())
+        .ok()
+        .map(SecretString::from)
+}
+
+pub fn has_keyring_entry(data_path: &Path) -> bool {
+    keyring_passphrase(data_path).is_some()
+}
+
+pub fn store_in_keyring(data_path: &Path, passphrase: &SecretString) -> Result<(), NarraError> {
+    keyring_entry(data_path)
+        .and_then(|entry| entry.set_password(passphrase.expose_secret()))
+        .map_err(vault_error)
+}
+
+pub fn forget_keyring(data_path: &Path) -> Result<(), NarraError> {
+    match keyring_entry(data_path).and_then(|entry| entry.delete_credential()) {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(vault_error(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_seal_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = Vault::with_passphrase(dir.path(), "correct horse".to_string().into());
+
+        let db = vault.load().await.unwrap();
+        db.query("CREATE character:alice SET name = 'Alice'")
+            .await
+            .unwrap();
+        vault.seal(&db).await.unwrap();
+
+        let on_disk = std::fs::read(Vault::path(dir.path())).unwrap();
+        assert!(!on_disk.windows(5).any(|w| w == b"Alice"));
+
+        let reopened = vault.load().await.unwrap();
+        let mut resp = reopened
+            .query("SELECT VALUE name FROM character:alice")
+            .await
+            .unwrap();
+        let names: Vec<String> = resp.take(0).unwrap();
+        assert_eq!(names, vec!["Alice".to_string()]);
+
+        // Reading only leaves the file alone
+        vault.seal(&reopened).await.unwrap();
+        assert_eq!(std::fs::read(Vault::path(dir.path())).unwrap(), on_disk);
+
+        drop(vault);
+        let wrong = Vault::with_passphrase(dir.path(), "battery staple".to_string().into());
+        assert!(wrong.load().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_second_session_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = Vault::with_passphrase(dir.path(), "correct horse".to_string().into());
+        let db = first.load().await.unwrap();
+        first.seal(&db).await.unwrap();
+
+        let second = Vault::with_passphrase(dir.path(), "correct horse".to_string().into());
+        let err = second.load().await.err().expect("second session refused");
+        assert!(err.to_string().contains("another narra process"));
+
+        drop(first);
+        assert!(second.load().await.is_ok());
+    }
+}
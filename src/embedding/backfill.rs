@@ -15,9 +15,10 @@ use crate::embedding::composite::{
     character_composite, event_composite, fact_composite, identity_composite, knowledge_composite,
     location_composite, narrative_composite, note_composite, perspective_composite,
     psychology_composite, relationship_composite, scene_composite, social_composite,
+    term_composite,
 };
 use crate::embedding::EmbeddingService;
-use crate::models::{Character, Event, Location, Note, Scene, UniverseFact};
+use crate::models::{Character, Event, Location, Note, Scene, Term, UniverseFact};
 use crate::NarraError;
 
 /// Statistics from a backfill operation.
@@ -30,6 +31,20 @@ pub struct BackfillStats {
     pub entity_type_stats: HashMap<String, usize>,
 }
 
+/// Entities per backfill chunk: one `embed_batch` call and one write query.
+/// The embedding service splits each call into forward passes by token
+/// length, so this only bounds memory held for composites and results.
+const BACKFILL_CHUNK: usize = 256;
+
+/// Which facet embeddings of one character need regenerating.
+#[derive(Debug, Clone, Copy)]
+struct FacetNeeds {
+    identity: bool,
+    psychology: bool,
+    social: bool,
+    narrative: bool,
+}
+
 /// Service for backfilling embeddings across all entities.
 pub struct BackfillService {
     db: Arc<NarraDb>,
@@ -50,7 +65,8 @@ impl BackfillService {
 
     /// Embed a batch of texts and update their entities in the database.
     ///
-    /// Handles the repeated pattern of: embed_batch → UPDATE each entity with embedding + composite_text.
+    /// Handles the repeated pattern of: embed_batch → UPDATE every entity with
+    /// embedding + composite_text in a single multi-statement query.
     async fn embed_and_update_batch(
         &self,
         entity_ids: &[String],
@@ -60,35 +76,19 @@ impl BackfillService {
     ) -> Result<(), NarraError> {
         match self.embedding_service.embed_batch(texts).await {
             Ok(embeddings) => {
-                for (i, entity_id) in entity_ids.iter().enumerate() {
-                    let update_query = format!(
-                        "UPDATE {} SET embedding = $embedding, embedding_stale = false, composite_text = $composite_text",
-                        entity_id
-                    );
-
-                    match self
-                        .db
-                        .query(&update_query)
-                        .bind(("embedding", embeddings[i].clone()))
-                        .bind(("composite_text", texts[i].clone()))
-                        .await
-                    {
-                        Ok(_) => {
-                            stats.embedded += 1;
-                        }
-                        Err(e) => {
-                            tracing::warn!("Failed to update embedding for {}: {}", entity_id, e);
-                            stats.failed += 1;
-                        }
-                    }
-                }
+                self.write_embeddings(
+                    entity_ids,
+                    texts,
+                    &embeddings,
+                    ["embedding", "embedding_stale", "composite_text"],
+                    stats,
+                )
+                .await;
 
-                if stats.embedded.is_multiple_of(50) && stats.embedded > 0 {
-                    info!(
-                        "Backfilled {} {} entities so far",
-                        stats.embedded, entity_type
-                    );
-                }
+                info!(
+                    "Backfilled {} {} entities so far",
+                    stats.embedded, entity_type
+                );
             }
             Err(e) => {
                 tracing::warn!(
@@ -102,50 +102,56 @@ impl BackfillService {
         Ok(())
     }
 
-    /// Embed a single text and update its entity in the database.
-    ///
-    /// Handles the repeated pattern of: embed_text → UPDATE entity with embedding + composite_text.
-    async fn embed_and_update_single(
+    /// Write one chunk of embeddings back as a single query, one UPDATE per
+    /// entity. `columns` names the embedding, stale flag and composite text
+    /// fields. Returns which entities were written.
+    async fn write_embeddings(
         &self,
-        entity_id: &str,
-        text: &str,
-        entity_type: &str,
+        entity_ids: &[String],
+        texts: &[String],
+        embeddings: &[Vec<f32>],
+        columns: [&str; 3],
         stats: &mut BackfillStats,
-    ) -> Result<(), NarraError> {
-        match self.embedding_service.embed_text(text).await {
-            Ok(embedding) => {
-                let update_query = format!(
-                    "UPDATE {} SET embedding = $embedding, embedding_stale = false, composite_text = $composite_text",
-                    entity_id
-                );
+    ) -> Vec<bool> {
+        let [embedding_col, stale_col, composite_col] = columns;
+        let sql: String = entity_ids
+            .iter()
+            .enumerate()
+            .map(|(i, entity_id)| {
+                format!(
+                    "UPDATE {} SET {} = $embedding_{i}, {} = false, {} = $composite_{i};\n",
+                    entity_id, embedding_col, stale_col, composite_col
+                )
+            })
+            .collect();
+
+        let mut query = self.db.query(sql);
+        for (i, (embedding, text)) in embeddings.iter().zip(texts).enumerate() {
+            query = query
+                .bind((format!("embedding_{i}"), embedding.clone()))
+                .bind((format!("composite_{i}"), text.clone()));
+        }
 
-                match self
-                    .db
-                    .query(&update_query)
-                    .bind(("embedding", embedding))
-                    .bind(("composite_text", text.to_string()))
-                    .await
-                {
-                    Ok(_) => {
-                        stats.embedded += 1;
-                    }
-                    Err(e) => {
+        let mut written = vec![true; entity_ids.len()];
+        match query.await {
+            Ok(mut response) => {
+                for (i, e) in response.take_errors() {
+                    if let Some(entity_id) = entity_ids.get(i) {
                         tracing::warn!("Failed to update embedding for {}: {}", entity_id, e);
-                        stats.failed += 1;
+                        written[i] = false;
                     }
                 }
             }
             Err(e) => {
-                tracing::warn!(
-                    "Failed to generate embedding for {} {}: {}",
-                    entity_type,
-                    entity_id,
-                    e
-                );
-                stats.failed += 1;
+                tracing::warn!("Failed to write embedding batch: {}", e);
+                written.fill(false);
             }
         }
-        Ok(())
+
+        let ok = written.iter().filter(|&&w| w).count();
+        stats.embedded += ok;
+        stats.failed += written.len() - ok;
+        written
     }
 
     /// Backfill embeddings for all entity types.
@@ -179,6 +185,7 @@ impl BackfillService {
             "relationship",
             "note",
             "fact",
+            "term",
         ] {
             let type_stats = self.backfill_type(entity_type).await?;
             stats.total_entities += type_stats.total_entities;
@@ -249,6 +256,7 @@ impl BackfillService {
             "relationship" => self.backfill_relationships(&mut stats).await?,
             "note" => self.backfill_notes(&mut stats).await?,
             "fact" => self.backfill_facts(&mut stats).await?,
+            "term" => self.backfill_terms(&mut stats).await?,
             _ => {
                 return Err(NarraError::Database(format!(
                     "Unknown entity type for backfill: {}",
@@ -279,7 +287,7 @@ impl BackfillService {
             self.get_all_character_perceptions(),
         )?;
 
-        for chunk in characters.chunks(50) {
+        for chunk in characters.chunks(BACKFILL_CHUNK) {
             let mut texts = Vec::new();
             let mut ids = Vec::new();
 
@@ -307,7 +315,7 @@ impl BackfillService {
 
         stats.total_entities += locations.len();
 
-        for chunk in locations.chunks(50) {
+        for chunk in locations.chunks(BACKFILL_CHUNK) {
             let ids: Vec<String> = chunk.iter().map(|l| l.id.to_string()).collect();
             let texts: Vec<String> = chunk.iter().map(location_composite).collect();
 
@@ -326,7 +334,7 @@ impl BackfillService {
 
         stats.total_entities += events.len();
 
-        for chunk in events.chunks(50) {
+        for chunk in events.chunks(BACKFILL_CHUNK) {
             let ids: Vec<String> = chunk.iter().map(|e| e.id.to_string()).collect();
             let texts: Vec<String> = chunk.iter().map(event_composite).collect();
 
@@ -361,7 +369,7 @@ impl BackfillService {
 
         stats.total_entities += scenes.len();
 
-        for chunk in scenes.chunks(50) {
+        for chunk in scenes.chunks(BACKFILL_CHUNK) {
             let mut texts = Vec::new();
             let mut ids = Vec::new();
 
@@ -373,6 +381,9 @@ impl BackfillService {
                     event: surrealdb::RecordId::from(("event", "placeholder")),
                     primary_location: surrealdb::RecordId::from(("location", "placeholder")),
                     secondary_locations: vec![],
+                    word_count: None,
+                    tags: vec![],
+                    draft_status: "outline".to_string(),
                     created_at: surrealdb::Datetime::default(),
                     updated_at: surrealdb::Datetime::default(),
                 };
@@ -414,7 +425,7 @@ impl BackfillService {
         // Bulk pre-fetch all knows edges to avoid N+1
         let edge_map = self.get_all_knows_edges().await?;
 
-        for chunk in knowledge_entities.chunks(50) {
+        for chunk in knowledge_entities.chunks(BACKFILL_CHUNK) {
             let mut texts = Vec::new();
             let mut ids = Vec::new();
 
@@ -470,7 +481,7 @@ impl BackfillService {
         stats.total_entities += perceives_edges.len();
 
         // Use batch embedding instead of individual calls
-        for chunk in perceives_edges.chunks(50) {
+        for chunk in perceives_edges.chunks(BACKFILL_CHUNK) {
             let mut texts = Vec::new();
             let mut ids = Vec::new();
 
@@ -562,130 +573,54 @@ impl BackfillService {
         info!("Backfilling facets for {} characters", characters.len());
 
         // Bulk pre-fetch all data needed for facet composites in parallel
-        let (all_relationships, all_perceptions_in, all_scene_participation, all_knowledge) = tokio::try_join!(
+        let (
+            facet_needs,
+            all_relationships,
+            all_perceptions_in,
+            all_scene_participation,
+            all_knowledge,
+        ) = tokio::try_join!(
+            self.get_all_character_facet_needs(),
             self.get_all_character_relationships(),
             self.get_all_character_inbound_perceptions(),
             self.get_all_character_scenes(),
             self.get_all_character_knowledge(),
         )?;
 
-        // Process characters in chunks of 50
-        for chunk in characters.chunks(50) {
+        for chunk in characters.chunks(BACKFILL_CHUNK) {
             // Build composites for each facet
             let mut identity_texts = Vec::new();
             let mut identity_ids = Vec::new();
-            let mut identity_needs_update = Vec::new();
 
             let mut psychology_texts = Vec::new();
             let mut psychology_ids = Vec::new();
-            let mut psychology_needs_update = Vec::new();
 
             let mut social_texts = Vec::new();
             let mut social_ids = Vec::new();
-            let mut social_needs_update = Vec::new();
 
             let mut narrative_texts = Vec::new();
             let mut narrative_ids = Vec::new();
-            let mut narrative_needs_update = Vec::new();
 
             for character in chunk {
                 let char_id = character.id.to_string();
-
-                // Check which facets need updates
-                let needs_identity = {
-                    let query_result = self
-                        .db
-                        .query(&format!(
-                            "SELECT identity_embedding, identity_stale FROM {}",
-                            char_id
-                        ))
-                        .await;
-
-                    #[derive(serde::Deserialize)]
-                    struct FacetCheck {
-                        identity_embedding: Option<Vec<f32>>,
-                        identity_stale: Option<bool>,
-                    }
-
-                    match query_result {
-                        Ok(mut resp) => {
-                            let check: Option<FacetCheck> = resp.take(0).unwrap_or(None);
-                            check.is_none_or(|c| {
-                                c.identity_embedding.is_none() || c.identity_stale.unwrap_or(false)
-                            })
-                        }
-                        Err(_) => true,
-                    }
-                };
-
-                if needs_identity {
+                let needs = facet_needs.get(&char_id).copied().unwrap_or(FacetNeeds {
+                    identity: true,
+                    psychology: true,
+                    social: true,
+                    narrative: true,
+                });
+
+                if needs.identity {
                     identity_texts.push(identity_composite(character));
                     identity_ids.push(char_id.clone());
-                    identity_needs_update.push(char_id.clone());
                 }
 
-                // Psychology facet
-                let needs_psychology = {
-                    let query_result = self
-                        .db
-                        .query(&format!(
-                            "SELECT psychology_embedding, psychology_stale FROM {}",
-                            char_id
-                        ))
-                        .await;
-
-                    #[derive(serde::Deserialize)]
-                    struct FacetCheck {
-                        psychology_embedding: Option<Vec<f32>>,
-                        psychology_stale: Option<bool>,
-                    }
-
-                    match query_result {
-                        Ok(mut resp) => {
-                            let check: Option<FacetCheck> = resp.take(0).unwrap_or(None);
-                            check.is_none_or(|c| {
-                                c.psychology_embedding.is_none()
-                                    || c.psychology_stale.unwrap_or(false)
-                            })
-                        }
-                        Err(_) => true,
-                    }
-                };
-
-                if needs_psychology {
+                if needs.psychology {
                     psychology_texts.push(psychology_composite(character));
                     psychology_ids.push(char_id.clone());
-                    psychology_needs_update.push(char_id.clone());
                 }
 
-                // Social facet
-                let needs_social = {
-                    let query_result = self
-                        .db
-                        .query(&format!(
-                            "SELECT social_embedding, social_stale FROM {}",
-                            char_id
-                        ))
-                        .await;
-
-                    #[derive(serde::Deserialize)]
-                    struct FacetCheck {
-                        social_embedding: Option<Vec<f32>>,
-                        social_stale: Option<bool>,
-                    }
-
-                    match query_result {
-                        Ok(mut resp) => {
-                            let check: Option<FacetCheck> = resp.take(0).unwrap_or(None);
-                            check.is_none_or(|c| {
-                                c.social_embedding.is_none() || c.social_stale.unwrap_or(false)
-                            })
-                        }
-                        Err(_) => true,
-                    }
-                };
-
-                if needs_social {
+                if needs.social {
                     let relationships =
                         all_relationships.get(&char_id).cloned().unwrap_or_default();
                     let perceptions_in = all_perceptions_in
@@ -694,46 +629,16 @@ impl BackfillService {
                         .unwrap_or_default();
                     social_texts.push(social_composite(character, &relationships, &perceptions_in));
                     social_ids.push(char_id.clone());
-                    social_needs_update.push(char_id.clone());
                 }
 
-                // Narrative facet
-                let needs_narrative = {
-                    let query_result = self
-                        .db
-                        .query(&format!(
-                            "SELECT narrative_embedding, narrative_stale FROM {}",
-                            char_id
-                        ))
-                        .await;
-
-                    #[derive(serde::Deserialize)]
-                    struct FacetCheck {
-                        narrative_embedding: Option<Vec<f32>>,
-                        narrative_stale: Option<bool>,
-                    }
-
-                    match query_result {
-                        Ok(mut resp) => {
-                            let check: Option<FacetCheck> = resp.take(0).unwrap_or(None);
-                            check.is_none_or(|c| {
-                                c.narrative_embedding.is_none()
-                                    || c.narrative_stale.unwrap_or(false)
-                            })
-                        }
-                        Err(_) => true,
-                    }
-                };
-
-                if needs_narrative {
+                if needs.narrative {
                     let scenes = all_scene_participation
                         .get(&char_id)
                         .cloned()
                         .unwrap_or_default();
                     let knowledge = all_knowledge.get(&char_id).cloned().unwrap_or_default();
                     narrative_texts.push(narrative_composite(&character.name, &scenes, &knowledge));
-                    narrative_ids.push(char_id.clone());
-                    narrative_needs_update.push(char_id);
+                    narrative_ids.push(char_id);
                 }
             }
 
@@ -783,6 +688,8 @@ impl BackfillService {
     }
 
     /// Embed a batch of facet texts and update their entities in the database.
+    ///
+    /// Also records an arc snapshot per written facet, again as one query.
     async fn embed_and_update_facet_batch(
         &self,
         entity_ids: &[String],
@@ -792,63 +699,69 @@ impl BackfillService {
     ) -> Result<(), NarraError> {
         match self.embedding_service.embed_batch(texts).await {
             Ok(embeddings) => {
-                for (i, entity_id) in entity_ids.iter().enumerate() {
-                    let update_query = format!(
-                        "UPDATE {} SET {}_embedding = $embedding, {}_stale = false, {}_composite = $composite_text",
-                        entity_id, facet, facet, facet
-                    );
-
-                    match self
-                        .db
-                        .query(&update_query)
-                        .bind(("embedding", embeddings[i].clone()))
-                        .bind(("composite_text", texts[i].clone()))
-                        .await
-                    {
-                        Ok(_) => {
-                            stats.embedded += 1;
+                let embedding_col = format!("{}_embedding", facet);
+                let stale_col = format!("{}_stale", facet);
+                let composite_col = format!("{}_composite", facet);
+                let written = self
+                    .write_embeddings(
+                        entity_ids,
+                        texts,
+                        &embeddings,
+                        [
+                            embedding_col.as_str(),
+                            stale_col.as_str(),
+                            composite_col.as_str(),
+                        ],
+                        stats,
+                    )
+                    .await;
+
+                let snapshots: Vec<usize> = (0..entity_ids.len()).filter(|&i| written[i]).collect();
+                if !snapshots.is_empty() {
+                    let sql: String = snapshots
+                        .iter()
+                        .map(|i| {
+                            format!(
+                                "CREATE arc_snapshot SET entity_id = $eid_{i}, entity_type = 'character', \
+                                 facet = $facet, embedding = $snap_embedding_{i}, delta_magnitude = NONE;\n"
+                            )
+                        })
+                        .collect();
+                    let mut query = self.db.query(sql).bind(("facet", facet.to_string()));
+                    for &i in &snapshots {
+                        let entity_id = &entity_ids[i];
+                        query = query
+                            .bind((
+                                format!("eid_{i}"),
+                                surrealdb::RecordId::from((
+                                    entity_id.split(':').next().unwrap_or("character"),
+                                    entity_id.split(':').nth(1).unwrap_or("unknown"),
+                                )),
+                            ))
+                            .bind((format!("snap_embedding_{i}"), embeddings[i].clone()));
+                    }
+                    match query.await {
+                        Ok(mut response) => {
+                            for (n, e) in response.take_errors() {
+                                tracing::warn!(
+                                    "Failed to create arc snapshot for {} facet {}: {}",
+                                    facet,
+                                    entity_ids[snapshots[n]],
+                                    e
+                                );
+                            }
                         }
                         Err(e) => {
                             tracing::warn!(
-                                "Failed to update {} facet for {}: {}",
+                                "Failed to create arc snapshots for {} facet batch: {}",
                                 facet,
-                                entity_id,
                                 e
                             );
-                            stats.failed += 1;
                         }
                     }
-
-                    // Create arc snapshot for this facet
-                    if let Err(e) = self
-                        .db
-                        .query(
-                            "CREATE arc_snapshot SET entity_id = $eid, entity_type = 'character', \
-                             facet = $facet, embedding = $snap_embedding, delta_magnitude = NONE",
-                        )
-                        .bind((
-                            "eid",
-                            surrealdb::RecordId::from((
-                                entity_id.split(':').next().unwrap_or("character"),
-                                entity_id.split(':').nth(1).unwrap_or("unknown"),
-                            )),
-                        ))
-                        .bind(("facet", facet.to_string()))
-                        .bind(("snap_embedding", embeddings[i].clone()))
-                        .await
-                    {
-                        tracing::warn!(
-                            "Failed to create arc snapshot for {} facet {}: {}",
-                            facet,
-                            entity_id,
-                            e
-                        );
-                    }
                 }
 
-                if stats.embedded.is_multiple_of(50) && stats.embedded > 0 {
-                    info!("Backfilled {} character facets so far", stats.embedded);
-                }
+                info!("Backfilled {} character facets so far", stats.embedded);
             }
             Err(e) => {
                 tracing::warn!(
@@ -887,20 +800,24 @@ impl BackfillService {
 
         stats.total_entities += edges.len();
 
-        for edge in &edges {
-            let entity_id = edge.id.to_string();
-
-            let composite = relationship_composite(
-                edge.from_name.as_deref().unwrap_or("Unknown"),
-                &edge.from_roles.clone().unwrap_or_default(),
-                edge.to_name.as_deref().unwrap_or("Unknown"),
-                &edge.to_roles.clone().unwrap_or_default(),
-                &edge.rel_type,
-                edge.subtype.as_deref(),
-                edge.label.as_deref(),
-            );
-
-            self.embed_and_update_single(&entity_id, &composite, "relationship", stats)
+        for chunk in edges.chunks(BACKFILL_CHUNK) {
+            let ids: Vec<String> = chunk.iter().map(|e| e.id.to_string()).collect();
+            let texts: Vec<String> = chunk
+                .iter()
+                .map(|edge| {
+                    relationship_composite(
+                        edge.from_name.as_deref().unwrap_or("Unknown"),
+                        &edge.from_roles.clone().unwrap_or_default(),
+                        edge.to_name.as_deref().unwrap_or("Unknown"),
+                        &edge.to_roles.clone().unwrap_or_default(),
+                        &edge.rel_type,
+                        edge.subtype.as_deref(),
+                        edge.label.as_deref(),
+                    )
+                })
+                .collect();
+
+            self.embed_and_update_batch(&ids, &texts, "relationship", stats)
                 .await?;
         }
 
@@ -915,7 +832,7 @@ impl BackfillService {
 
         stats.total_entities += notes.len();
 
-        for chunk in notes.chunks(50) {
+        for chunk in notes.chunks(BACKFILL_CHUNK) {
             let ids: Vec<String> = chunk.iter().map(|n| n.id.to_string()).collect();
             let texts: Vec<String> = chunk.iter().map(note_composite).collect();
 
@@ -934,7 +851,7 @@ impl BackfillService {
 
         stats.total_entities += facts.len();
 
-        for chunk in facts.chunks(50) {
+        for chunk in facts.chunks(BACKFILL_CHUNK) {
             let ids: Vec<String> = chunk.iter().map(|f| f.id.to_string()).collect();
             let texts: Vec<String> = chunk.iter().map(fact_composite).collect();
 
@@ -945,6 +862,63 @@ impl BackfillService {
         Ok(())
     }
 
+    /// Backfill glossary term embeddings.
+    async fn backfill_terms(&self, stats: &mut BackfillStats) -> Result<(), NarraError> {
+        let query = "SELECT * FROM term WHERE embedding IS NONE OR embedding_stale = true";
+        let mut response = self.db.query(query).await?;
+        let terms: Vec<Term> = response.take(0)?;
+
+        stats.total_entities += terms.len();
+
+        for chunk in terms.chunks(BACKFILL_CHUNK) {
+            let ids: Vec<String> = chunk.iter().map(|t| t.id.to_string()).collect();
+            let texts: Vec<String> = chunk.iter().map(term_composite).collect();
+
+            self.embed_and_update_batch(&ids, &texts, "term", stats)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Bulk-fetch which facets of each character are missing or stale.
+    async fn get_all_character_facet_needs(
+        &self,
+    ) -> Result<HashMap<String, FacetNeeds>, NarraError> {
+        let query = r#"SELECT type::string(id) AS id,
+                              (identity_embedding IS NONE OR identity_stale = true) AS identity,
+                              (psychology_embedding IS NONE OR psychology_stale = true) AS psychology,
+                              (social_embedding IS NONE OR social_stale = true) AS social,
+                              (narrative_embedding IS NONE OR narrative_stale = true) AS narrative
+                       FROM character"#;
+        let mut response = self.db.query(query).await?;
+
+        #[derive(serde::Deserialize)]
+        struct FacetRecord {
+            id: String,
+            identity: bool,
+            psychology: bool,
+            social: bool,
+            narrative: bool,
+        }
+
+        let records: Vec<FacetRecord> = response.take(0)?;
+        Ok(records
+            .into_iter()
+            .map(|r| {
+                (
+                    r.id,
+                    FacetNeeds {
+                        identity: r.identity,
+                        psychology: r.psychology,
+                        social: r.social,
+                        narrative: r.narrative,
+                    },
+                )
+            })
+            .collect())
+    }
+
     /// Bulk-fetch all character relationships for composite text generation.
     async fn get_all_character_relationships(
         &self,
@@ -5,7 +5,9 @@
 //! [`CrossEncoderReranker`] for relevance scoring (BGE-reranker-base),
 //! and [`SequenceClassifier`] for multi-label classification (GoEmotions).
 
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use anyhow::{Context, Result};
 use candle_core::{DType, Device, Tensor};
@@ -16,7 +18,8 @@ use candle_transformers::models::bert::{BertModel, Config as BertConfig};
 use candle_transformers::models::xlm_roberta::{
     Config as XLMRobertaConfig, XLMRobertaForSequenceClassification,
 };
-use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer};
+use serde::{Deserialize, Serialize};
+use tokenizers::{Encoding, PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
 
 /// Paths to downloaded model files from HuggingFace Hub.
 pub struct ModelFiles {
@@ -27,21 +30,17 @@ pub struct ModelFiles {
 
 /// Download model files from HuggingFace Hub.
 ///
-/// Uses `hf_hub::api::sync::Api` which caches at `~/.cache/huggingface/hub/`.
+/// Goes through [`hub::fetch_file`](crate::embedding::hub::fetch_file), which
+/// caches at `~/.cache/huggingface/hub/` and only reads the cache when offline.
 /// Designed to be called from `spawn_blocking` since it performs synchronous I/O.
 pub fn download_model(repo_id: &str, _cache_dir: Option<&Path>) -> Result<ModelFiles> {
-    let api = hf_hub::api::sync::Api::new().context("Failed to initialize HuggingFace Hub API")?;
-    let repo = api.model(repo_id.to_string());
-
-    let config_path = repo
-        .get("config.json")
-        .context("Failed to download config.json")?;
-    let tokenizer_path = repo
-        .get("tokenizer.json")
-        .context("Failed to download tokenizer.json")?;
-    let weights_path = repo
-        .get("model.safetensors")
-        .context("Failed to download model.safetensors")?;
+    use crate::embedding::hub::fetch_file;
+
+    let config_path = fetch_file(repo_id, "config.json").context("Failed to get config.json")?;
+    let tokenizer_path =
+        fetch_file(repo_id, "tokenizer.json").context("Failed to get tokenizer.json")?;
+    let weights_path =
+        fetch_file(repo_id, "model.safetensors").context("Failed to get model.safetensors")?;
 
     Ok(ModelFiles {
         config_path,
@@ -80,6 +79,116 @@ pub fn select_device() -> Device {
     Device::Cpu
 }
 
+/// Where embedding inference runs (`[embedding] device`).
+///
+/// Candle has no DirectML or CoreML backend; on Windows use `cuda`, on
+/// macOS `metal` (what `auto` picks there).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionProvider {
+    /// Best available GPU, else CPU
+    #[default]
+    Auto,
+    Cpu,
+    Cuda,
+    Metal,
+}
+
+impl ExecutionProvider {
+    /// Concrete providers, in the order `narra models bench` tries them.
+    pub const CONCRETE: [ExecutionProvider; 3] = [Self::Cpu, Self::Cuda, Self::Metal];
+
+    /// Whether this build of narra can use the provider at all.
+    pub fn is_compiled(self) -> bool {
+        match self {
+            Self::Auto | Self::Cpu => true,
+            Self::Cuda => cfg!(feature = "cuda"),
+            Self::Metal => cfg!(target_os = "macos"),
+        }
+    }
+}
+
+impl fmt::Display for ExecutionProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Cpu => "cpu",
+            Self::Cuda => "cuda",
+            Self::Metal => "metal",
+        })
+    }
+}
+
+impl FromStr for ExecutionProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "cpu" => Ok(Self::Cpu),
+            "cuda" | "gpu" => Ok(Self::Cuda),
+            "metal" => Ok(Self::Metal),
+            "directml" | "coreml" => anyhow::bail!(
+                "{} isn't available in narra's candle backend; use cuda, metal, or cpu",
+                s
+            ),
+            _ => anyhow::bail!(
+                "Unknown device '{}' (expected auto, cpu, cuda, or metal)",
+                s
+            ),
+        }
+    }
+}
+
+/// Open the device for `provider`. Unlike `auto`, an explicit GPU that is
+/// missing, not compiled in, or lacks layer-norm is an error.
+pub fn device_for(provider: ExecutionProvider) -> Result<Device> {
+    match provider {
+        ExecutionProvider::Auto => Ok(select_device()),
+        ExecutionProvider::Cpu => Ok(Device::Cpu),
+        ExecutionProvider::Cuda => open_cuda(),
+        ExecutionProvider::Metal => open_metal(),
+    }
+}
+
+#[cfg(feature = "cuda")]
+fn open_cuda() -> Result<Device> {
+    let device = Device::new_cuda(0).context("No usable CUDA device")?;
+    anyhow::ensure!(
+        probe_layer_norm(&device),
+        "CUDA device lacks layer-norm support"
+    );
+    Ok(device)
+}
+
+#[cfg(not(feature = "cuda"))]
+fn open_cuda() -> Result<Device> {
+    anyhow::bail!("narra was built without CUDA support (rebuild with --features cuda)")
+}
+
+#[cfg(target_os = "macos")]
+fn open_metal() -> Result<Device> {
+    let device = Device::new_metal(0).context("No usable Metal device")?;
+    anyhow::ensure!(
+        probe_layer_norm(&device),
+        "Metal device lacks layer-norm support"
+    );
+    Ok(device)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn open_metal() -> Result<Device> {
+    anyhow::bail!("Metal is only available on macOS")
+}
+
+/// Cap CPU inference threads for the whole process. Takes effect only
+/// before the first model runs; an explicit `RAYON_NUM_THREADS` wins.
+pub fn set_cpu_threads(threads: usize) {
+    if threads > 0 && std::env::var_os("RAYON_NUM_THREADS").is_none() {
+        std::env::set_var("RAYON_NUM_THREADS", threads.to_string());
+    }
+}
+
 /// Probe whether a device supports layer-norm (required by BERT/RoBERTa).
 #[cfg(any(target_os = "macos", feature = "cuda"))]
 fn probe_layer_norm(device: &Device) -> bool {
@@ -119,6 +228,13 @@ impl BertEmbedder {
             strategy: PaddingStrategy::BatchLongest,
             ..Default::default()
         }));
+        // Longer inputs would overrun the position embeddings
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: config.max_position_embeddings,
+                ..Default::default()
+            }))
+            .map_err(|e| anyhow::anyhow!("Failed to configure tokenizer: {}", e))?;
 
         // SAFETY: mmap'd safetensors file — safe as long as the file is not modified
         // while the model is in use.
@@ -135,11 +251,27 @@ impl BertEmbedder {
         })
     }
 
-    /// Generate embeddings for a batch of texts.
+    /// Generate embeddings for a batch of texts in a single forward pass.
     ///
     /// Applies mean pooling over token hidden states (masked by attention mask)
     /// followed by L2 normalization. Returns one embedding vector per input text.
     pub fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.embed_with_budget(texts, usize::MAX)
+    }
+
+    /// Generate embeddings, splitting the input into forward passes of at
+    /// most `token_budget` padded tokens each.
+    ///
+    /// Texts are grouped by token length (see [`plan_batches`]) so short
+    /// texts aren't padded out to the longest one; results come back in
+    /// input order.
+    ///
+    /// [`plan_batches`]: crate::embedding::model::plan_batches
+    pub fn embed_with_budget(
+        &self,
+        texts: &[String],
+        token_budget: usize,
+    ) -> Result<Vec<Vec<f32>>> {
         if texts.is_empty() {
             return Ok(vec![]);
         }
@@ -150,24 +282,41 @@ impl BertEmbedder {
             .encode_batch(str_refs, true)
             .map_err(|e| anyhow::anyhow!("Tokenization failed: {}", e))?;
 
-        let batch_size = encodings.len();
-        let max_len = encodings
+        let lengths: Vec<usize> = encodings
             .iter()
-            .map(|e| e.get_ids().len())
-            .max()
-            .unwrap_or(0);
+            .map(|e| e.get_attention_mask().iter().filter(|&&m| m != 0).count())
+            .collect();
+
+        let mut embeddings = vec![Vec::new(); texts.len()];
+        for batch in crate::embedding::model::plan_batches(&lengths, token_budget) {
+            let seq_len = batch.iter().map(|&i| lengths[i]).max().unwrap_or(0);
+            let batch_encodings: Vec<&Encoding> = batch.iter().map(|&i| &encodings[i]).collect();
+            let vectors = self.forward(&batch_encodings, seq_len)?;
+            for (i, vector) in batch.into_iter().zip(vectors) {
+                embeddings[i] = vector;
+            }
+        }
+        Ok(embeddings)
+    }
+
+    /// Run one forward pass over `encodings`, trimmed to `max_len` tokens.
+    ///
+    /// Encodings come padded to the longest text of the whole call; padding
+    /// past `max_len` is masked out anyway, so dropping it is lossless.
+    fn forward(&self, encodings: &[&Encoding], max_len: usize) -> Result<Vec<Vec<f32>>> {
+        let batch_size = encodings.len();
 
         let input_ids: Vec<u32> = encodings
             .iter()
-            .flat_map(|e| e.get_ids().to_vec())
+            .flat_map(|e| e.get_ids()[..max_len].to_vec())
             .collect();
         let attention_mask: Vec<u32> = encodings
             .iter()
-            .flat_map(|e| e.get_attention_mask().to_vec())
+            .flat_map(|e| e.get_attention_mask()[..max_len].to_vec())
             .collect();
         let token_type_ids: Vec<u32> = encodings
             .iter()
-            .flat_map(|e| e.get_type_ids().to_vec())
+            .flat_map(|e| e.get_type_ids()[..max_len].to_vec())
             .collect();
 
         let input_ids = Tensor::from_vec(input_ids, (batch_size, max_len), &self.device)?;
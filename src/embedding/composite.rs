@@ -3,7 +3,7 @@
 //! Generates natural-language descriptions of entities for embedding.
 //! Composite text should be semantically rich but concise (50-200 words).
 
-use crate::models::{Character, Event, Location, Note, Scene, UniverseFact};
+use crate::models::{Character, Event, Location, Note, Scene, Term, UniverseFact};
 
 /// Generate composite text for a character.
 ///
@@ -618,6 +618,39 @@ pub fn fact_composite(fact: &UniverseFact) -> String {
     parts.join(". ") + "."
 }
 
+/// Generate composite text for a glossary term.
+///
+/// Combines name, kind, aliases, and definition.
+///
+/// # Arguments
+///
+/// * `term` - The term entity
+///
+/// # Returns
+///
+/// A natural-language composite text suitable for embedding.
+pub fn term_composite(term: &Term) -> String {
+    term_text(
+        &term.name,
+        term.kind.as_deref(),
+        &term.aliases,
+        &term.definition,
+    )
+}
+
+fn term_text(name: &str, kind: Option<&str>, aliases: &[String], definition: &str) -> String {
+    let mut parts = Vec::new();
+    match kind {
+        Some(kind) => parts.push(format!("{} is a {}", name, kind)),
+        None => parts.push(format!("{} is a term", name)),
+    }
+    if !aliases.is_empty() {
+        parts.push(format!("Also known as {}", aliases.join(", ")));
+    }
+    parts.push(definition.trim_end_matches('.').to_string());
+    parts.join(". ") + "."
+}
+
 /// Truncate a string to approximately `max_words` words.
 fn truncate_words(text: &str, max_words: usize) -> String {
     let words: Vec<&str> = text.split_whitespace().collect();
@@ -731,6 +764,20 @@ pub fn generate_composite_text(entity_type: &str, entity_json: &serde_json::Valu
                 )
             }
         }
+        "term" => {
+            let name = entity_json["name"].as_str().unwrap_or("Unknown");
+            let definition = entity_json["definition"].as_str().unwrap_or("");
+            let aliases: Vec<String> = entity_json["aliases"]
+                .as_array()
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default();
+            term_text(name, entity_json["kind"].as_str(), &aliases, definition)
+        }
         _ => format!("Unknown entity type: {}", entity_type),
     }
 }
@@ -912,6 +959,8 @@ mod tests {
             date: None,
             date_precision: None,
             duration_end: None,
+            tags: vec![],
+            narrative_status: "on_page".to_string(),
             created_at: surrealdb::Datetime::default(),
             updated_at: surrealdb::Datetime::default(),
         };
@@ -931,6 +980,9 @@ mod tests {
             event: RecordId::from(("event", "betrayal")),
             primary_location: RecordId::from(("location", "forest")),
             secondary_locations: vec![],
+            word_count: None,
+            tags: vec![],
+            draft_status: "outline".to_string(),
             created_at: surrealdb::Datetime::default(),
             updated_at: surrealdb::Datetime::default(),
         };
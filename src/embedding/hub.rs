@@ -0,0 +1,259 @@
+//! HuggingFace Hub access: model catalog, cache lookups, and offline mode.
+//!
+//! Every ML model narra uses is fetched through [`fetch_file`]. Online, files
+//! download on first use and are cached under `~/.cache/huggingface/hub/`
+//! (or `$HF_HOME/hub`). In offline mode only the cache is consulted, so a
+//! missing model makes its service unavailable instead of stalling on the
+//! network mid-query.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+
+use crate::embedding::is_english;
+use crate::embedding::provider::CANDLE_MODELS;
+use crate::embedding::reranker::RERANKER_REPO;
+use crate::services::emotion::EMOTION_MODEL_REPO;
+use crate::services::ner::{MULTILINGUAL_NER_MODEL_REPO, NER_MODEL_REPO};
+use crate::services::theme::THEME_MODEL_REPO;
+use crate::services::token_counter::DEFAULT_TOKENIZER_REPO;
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Files fetched for candle models.
+const MODEL_FILES: &[&str] = &["config.json", "tokenizer.json", "model.safetensors"];
+/// Files fetched for tokenizer-only repos.
+const TOKENIZER_FILES: &[&str] = &["tokenizer.json"];
+
+/// A downloadable model and the feature that needs it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelSpec {
+    /// Short name used on the command line (e.g., "bge-small-en-v1.5")
+    pub name: &'static str,
+    /// HuggingFace repo ID
+    pub repo: &'static str,
+    pub files: &'static [&'static str],
+    /// What stops working without it
+    pub feature: &'static str,
+}
+
+/// Switch offline mode on or off for the whole process.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// What to tell a user whose model-backed feature is unavailable.
+pub fn unavailable_hint() -> &'static str {
+    if is_offline() {
+        "Running offline: run 'narra models pull' while online, then retry."
+    } else {
+        "Models download on first use when the network is available; 'narra models pull' fetches them all up front."
+    }
+}
+
+/// Every model narra knows how to use.
+pub fn catalog() -> Vec<ModelSpec> {
+    let mut models: Vec<ModelSpec> = CANDLE_MODELS
+        .iter()
+        .map(|(name, repo, _)| ModelSpec {
+            name,
+            repo,
+            files: MODEL_FILES,
+            feature: "semantic search, embeddings",
+        })
+        .collect();
+    models.extend([
+        ModelSpec {
+            name: "bge-reranker-base",
+            repo: RERANKER_REPO,
+            files: MODEL_FILES,
+            feature: "search re-ranking",
+        },
+        ModelSpec {
+            name: "go-emotions",
+            repo: EMOTION_MODEL_REPO,
+            files: MODEL_FILES,
+            feature: "emotion analysis",
+        },
+        ModelSpec {
+            name: "nli-roberta-base",
+            repo: THEME_MODEL_REPO,
+            files: MODEL_FILES,
+            feature: "theme classification",
+        },
+        ModelSpec {
+            name: "bert-base-ner",
+            repo: NER_MODEL_REPO,
+            files: MODEL_FILES,
+            feature: "entity extraction",
+        },
+        ModelSpec {
+            name: "bert-multilingual-ner",
+            repo: MULTILINGUAL_NER_MODEL_REPO,
+            files: MODEL_FILES,
+            feature: "entity extraction (non-English)",
+        },
+        ModelSpec {
+            name: "claude-tokenizer",
+            repo: DEFAULT_TOKENIZER_REPO,
+            files: TOKENIZER_FILES,
+            feature: "token budgets",
+        },
+    ]);
+    models
+}
+
+/// Models loaded at startup: the configured embedding model plus the
+/// non-embedding models for `language`. The other embedding sizes are only
+/// needed on switch; emotion and theme models only load for English.
+pub fn required(embedding_model: &str, language: Option<&str>) -> Vec<ModelSpec> {
+    let english = is_english(language);
+    catalog()
+        .into_iter()
+        .filter(|m| {
+            if CANDLE_MODELS.iter().any(|(name, _, _)| *name == m.name) {
+                return m.name == embedding_model;
+            }
+            match m.repo {
+                NER_MODEL_REPO | EMOTION_MODEL_REPO | THEME_MODEL_REPO => english,
+                MULTILINGUAL_NER_MODEL_REPO => !english,
+                _ => true,
+            }
+        })
+        .collect()
+}
+
+/// Look up a catalog entry by short name or repo ID.
+pub fn find(name_or_repo: &str) -> Option<ModelSpec> {
+    catalog()
+        .into_iter()
+        .find(|m| m.name == name_or_repo || m.repo.eq_ignore_ascii_case(name_or_repo))
+}
+
+/// Path to one model file, downloading it unless offline.
+pub fn fetch_file(repo: &str, file: &str) -> Result<PathBuf> {
+    if is_offline() {
+        return hf_hub::Cache::from_env()
+            .model(repo.to_string())
+            .get(file)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{} is not cached and narra is offline (run 'narra models pull' while online)",
+                    repo
+                )
+            });
+    }
+    let api = hf_hub::api::sync::Api::new().context("Failed to initialize HuggingFace Hub API")?;
+    api.model(repo.to_string())
+        .get(file)
+        .with_context(|| format!("Failed to download {}", file))
+}
+
+/// Whether every file of `model` is already in the local cache.
+pub fn is_cached(model: &ModelSpec) -> bool {
+    let repo = hf_hub::Cache::from_env().model(model.repo.to_string());
+    model.files.iter().all(|file| repo.get(file).is_some())
+}
+
+/// Download every file of `model` into the cache.
+pub fn pull(model: &ModelSpec) -> Result<()> {
+    if is_offline() {
+        anyhow::bail!("Cannot download models in offline mode");
+    }
+    for file in model.files {
+        fetch_file(model.repo, file)?;
+    }
+    Ok(())
+}
+
+/// Cache directory holding every file of `model` (blobs and snapshots).
+pub fn cache_dir(model: &ModelSpec) -> PathBuf {
+    hf_hub::Cache::from_env()
+        .path()
+        .join(format!("models--{}", model.repo.replace('/', "--")))
+}
+
+/// Bytes `model` occupies in the cache (0 if absent).
+pub fn cached_size(model: &ModelSpec) -> u64 {
+    dir_size(&cache_dir(model))
+}
+
+/// Delete `model` from the cache, returning the bytes freed.
+pub fn remove(model: &ModelSpec) -> Result<u64> {
+    let dir = cache_dir(model);
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let size = dir_size(&dir);
+    std::fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+    Ok(size)
+}
+
+/// Total size of regular files under `dir`. Snapshot symlinks point into
+/// `blobs/`, so they are skipped rather than counted twice.
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(t) if t.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_keeps_only_configured_embedding_model() {
+        let required = required("bge-base-en-v1.5", None);
+        assert!(required.iter().any(|m| m.name == "bge-base-en-v1.5"));
+        assert!(!required.iter().any(|m| m.name == "bge-small-en-v1.5"));
+        assert!(required.iter().any(|m| m.repo == NER_MODEL_REPO));
+        assert_eq!(required.len(), catalog().len() - CANDLE_MODELS.len());
+    }
+
+    #[test]
+    fn test_required_follows_language() {
+        let required = required("multilingual-e5-small", Some("de"));
+        assert!(required
+            .iter()
+            .any(|m| m.repo == MULTILINGUAL_NER_MODEL_REPO));
+        assert!(!required.iter().any(|m| m.repo == NER_MODEL_REPO));
+        assert!(!required.iter().any(|m| m.repo == EMOTION_MODEL_REPO));
+        assert!(required.iter().any(|m| m.repo == RERANKER_REPO));
+    }
+
+    #[test]
+    fn test_offline_fetch_never_downloads() {
+        set_offline(true);
+        let err = fetch_file("narra/definitely-not-a-model", "config.json").unwrap_err();
+        set_offline(false);
+        assert!(err.to_string().contains("narra models pull"));
+    }
+
+    #[test]
+    fn test_dir_size_skips_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("blobs")).unwrap();
+        std::fs::create_dir_all(dir.path().join("snapshots/main")).unwrap();
+        let blob = dir.path().join("blobs/abc");
+        std::fs::write(&blob, vec![0u8; 1000]).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&blob, dir.path().join("snapshots/main/model.safetensors"))
+            .unwrap();
+
+        assert_eq!(dir_size(dir.path()), 1000);
+        assert_eq!(dir_size(&dir.path().join("missing")), 0);
+    }
+}
@@ -0,0 +1,231 @@
+//! Lazily loaded ML services.
+//!
+//! Loading the embedding, reranker and annotation models, or fetching a
+//! tokenizer, takes seconds and most commands never touch them. Each service
+//! is wrapped in a [`Lazy`] that builds it on first use — the first embed,
+//! classification, token count or `is_available()` check — so listing or
+//! editing entities starts instantly.
+
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use super::reranker::RerankerService;
+use super::EmbeddingService;
+use crate::models::annotation::{EmotionOutput, NerOutput, ThemeOutput};
+use crate::services::{EmotionService, NerService, ThemeService, TokenCounter};
+use crate::NarraError;
+
+type Loader<T> = Box<dyn Fn() -> Arc<T> + Send + Sync>;
+
+/// A service built on first use.
+pub struct Lazy<T: ?Sized> {
+    name: &'static str,
+    cell: OnceLock<Arc<T>>,
+    load: Loader<T>,
+}
+
+impl<T: ?Sized> Lazy<T> {
+    /// Wrap `load`, which runs at most once, on the first call to [`get`](Self::get).
+    pub fn new(name: &'static str, load: impl Fn() -> Arc<T> + Send + Sync + 'static) -> Self {
+        Self {
+            name,
+            cell: OnceLock::new(),
+            load: Box::new(load),
+        }
+    }
+
+    /// The service, loading it if this is the first use.
+    pub fn get(&self) -> &Arc<T> {
+        self.cell.get_or_init(|| {
+            let start = Instant::now();
+            let service = blocking(&self.load);
+            tracing::info!("Loaded {} in {:.1?}", self.name, start.elapsed());
+            service
+        })
+    }
+
+    /// Whether the service has been loaded yet.
+    pub fn is_loaded(&self) -> bool {
+        self.cell.get().is_some()
+    }
+}
+
+/// Run a synchronous model load without stalling other tasks on the
+/// worker thread, where the runtime allows it.
+fn blocking<R>(f: impl FnOnce() -> R) -> R {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// Embedding service that loads its model on the first embed.
+///
+/// Model ID, dimensions and provider are known from the provider config,
+/// so metadata checks and index resolution don't trigger a load.
+pub struct LazyEmbeddingService {
+    inner: Lazy<dyn EmbeddingService + Send + Sync>,
+    model_id: String,
+    dimensions: usize,
+    provider_name: &'static str,
+}
+
+impl LazyEmbeddingService {
+    pub fn new(
+        model_id: String,
+        dimensions: usize,
+        provider_name: &'static str,
+        load: impl Fn() -> Arc<dyn EmbeddingService + Send + Sync> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            inner: Lazy::new("embedding model", load),
+            model_id,
+            dimensions,
+            provider_name,
+        }
+    }
+
+    /// Whether the model has been loaded yet.
+    pub fn is_loaded(&self) -> bool {
+        self.inner.is_loaded()
+    }
+}
+
+#[async_trait]
+impl EmbeddingService for LazyEmbeddingService {
+    async fn embed_text(&self, text: &str) -> Result<Vec<f32>, NarraError> {
+        self.inner.get().embed_text(text).await
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, NarraError> {
+        self.inner.get().embed_batch(texts).await
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn is_available(&self) -> bool {
+        self.inner.get().is_available()
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn provider_name(&self) -> &str {
+        self.provider_name
+    }
+}
+
+#[async_trait]
+impl RerankerService for Lazy<dyn RerankerService + Send + Sync> {
+    async fn rerank(
+        &self,
+        query: &str,
+        candidates: &[String],
+    ) -> Result<Vec<(usize, f32)>, NarraError> {
+        self.get().rerank(query, candidates).await
+    }
+
+    fn is_available(&self) -> bool {
+        self.get().is_available()
+    }
+}
+
+#[async_trait]
+impl EmotionService for Lazy<dyn EmotionService + Send + Sync> {
+    async fn get_emotions(&self, entity_id: &str, text: &str) -> Result<EmotionOutput, NarraError> {
+        self.get().get_emotions(entity_id, text).await
+    }
+
+    async fn classify_text(&self, text: &str) -> Result<EmotionOutput, NarraError> {
+        self.get().classify_text(text).await
+    }
+
+    fn is_available(&self) -> bool {
+        self.get().is_available()
+    }
+}
+
+#[async_trait]
+impl ThemeService for Lazy<dyn ThemeService + Send + Sync> {
+    async fn get_themes(
+        &self,
+        entity_id: &str,
+        text: &str,
+        themes: Option<&[String]>,
+    ) -> Result<ThemeOutput, NarraError> {
+        self.get().get_themes(entity_id, text, themes).await
+    }
+
+    async fn classify_themes(
+        &self,
+        text: &str,
+        themes: Option<&[String]>,
+    ) -> Result<ThemeOutput, NarraError> {
+        self.get().classify_themes(text, themes).await
+    }
+
+    fn is_available(&self) -> bool {
+        self.get().is_available()
+    }
+}
+
+#[async_trait]
+impl NerService for Lazy<dyn NerService + Send + Sync> {
+    async fn get_entities(&self, entity_id: &str, text: &str) -> Result<NerOutput, NarraError> {
+        self.get().get_entities(entity_id, text).await
+    }
+
+    async fn extract_entities(&self, text: &str) -> Result<NerOutput, NarraError> {
+        self.get().extract_entities(text).await
+    }
+
+    fn is_available(&self) -> bool {
+        self.get().is_available()
+    }
+}
+
+impl TokenCounter for Lazy<dyn TokenCounter> {
+    fn count(&self, text: &str) -> usize {
+        self.get().count(text)
+    }
+
+    fn name(&self) -> &str {
+        self.get().name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::NoopEmbeddingService;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_lazy_embedding_loads_once_on_first_use() {
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = loads.clone();
+        let service =
+            LazyEmbeddingService::new("bge-small-en-v1.5".into(), 384, "candle", move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Arc::new(NoopEmbeddingService::new())
+            });
+
+        // Metadata comes from config without loading
+        assert_eq!(service.dimensions(), 384);
+        assert_eq!(service.model_id(), "bge-small-en-v1.5");
+        assert!(!service.is_loaded());
+        assert_eq!(loads.load(Ordering::SeqCst), 0);
+
+        assert!(!service.is_available());
+        assert!(!service.is_available());
+        assert!(service.is_loaded());
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+}
@@ -7,6 +7,8 @@
 pub mod backfill;
 pub mod candle_backend;
 pub mod composite;
+pub mod hub;
+pub mod lazy;
 pub mod model;
 pub mod provider;
 pub mod queries;
@@ -18,9 +20,12 @@ use async_trait::async_trait;
 use crate::NarraError;
 
 pub use backfill::{BackfillService, BackfillStats};
-pub use model::{EmbeddingConfig, LocalEmbeddingService};
+pub use model::{
+    default_model_for_language, is_english, EmbeddingConfig, LocalEmbeddingService,
+    MULTILINGUAL_EMBEDDING_MODEL,
+};
 pub use provider::EmbeddingProviderConfig;
-pub use staleness::StalenessManager;
+pub use staleness::{ArcSnapshotPolicy, StalenessManager};
 
 /// No-op embedding service for testing.
 ///
@@ -5,10 +5,96 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use tracing::warn;
 
-use crate::embedding::candle_backend::{download_model, select_device, BertEmbedder};
+use crate::embedding::candle_backend::{
+    device_for, download_model, set_cpu_threads, BertEmbedder, ExecutionProvider,
+};
 use crate::embedding::EmbeddingService;
 use crate::NarraError;
 
+/// Embedding model used by default for manuscripts not in English.
+pub const MULTILINGUAL_EMBEDDING_MODEL: &str = "multilingual-e5-small";
+
+/// Whether `language` (an ISO 639-1 code such as "en" or "en-GB", or the
+/// English name) is English. Unset means English.
+pub fn is_english(language: Option<&str>) -> bool {
+    match language.map(|l| l.trim().to_lowercase()) {
+        None => true,
+        Some(l) => {
+            l.is_empty()
+                || l == "en"
+                || l == "english"
+                || l.starts_with("en-")
+                || l.starts_with("en_")
+        }
+    }
+}
+
+/// Default embedding model for a manuscript language: BGE for English,
+/// multilingual E5 otherwise.
+pub fn default_model_for_language(language: Option<&str>) -> &'static str {
+    if is_english(language) {
+        "bge-small-en-v1.5"
+    } else {
+        MULTILINGUAL_EMBEDDING_MODEL
+    }
+}
+
+/// Token budget per forward pass when available memory can't be read.
+pub const DEFAULT_TOKEN_BUDGET: usize = 16_384;
+
+/// Bounds on the memory-derived token budget.
+const MIN_TOKEN_BUDGET: usize = 2_048;
+const MAX_TOKEN_BUDGET: usize = 65_536;
+
+/// Rough activation footprint of one padded token, in f32s per hidden unit,
+/// across a BERT forward pass (attention, intermediate layer, copies).
+const ACTIVATION_FLOATS_PER_DIM: usize = 48;
+
+/// Padded tokens one forward pass may hold for a model with `dimensions`
+/// hidden units: a quarter of available memory at the rough per-token
+/// activation cost, clamped to a sane range.
+pub fn token_budget(dimensions: usize) -> usize {
+    let Some(available) = available_memory_bytes() else {
+        return DEFAULT_TOKEN_BUDGET;
+    };
+    let per_token = dimensions.max(1) * ACTIVATION_FLOATS_PER_DIM * std::mem::size_of::<f32>();
+    (available / 4 / per_token).clamp(MIN_TOKEN_BUDGET, MAX_TOKEN_BUDGET)
+}
+
+/// `MemAvailable` from `/proc/meminfo`, where there is one.
+fn available_memory_bytes() -> Option<usize> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemAvailable:"))?;
+    let kib: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Split texts into forward passes by token length.
+///
+/// Indices are sorted longest first and packed greedily while
+/// `count × longest` stays within `token_budget`, so each pass pads only
+/// to texts of similar length. A text longer than the budget gets a pass
+/// of its own.
+pub fn plan_batches(lengths: &[usize], token_budget: usize) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..lengths.len()).collect();
+    order.sort_by(|&a, &b| lengths[b].cmp(&lengths[a]));
+
+    let mut batches: Vec<Vec<usize>> = Vec::new();
+    let mut current: Vec<usize> = Vec::new();
+    for i in order {
+        // Sorted descending, so the first entry is the batch's padded length
+        let longest = current.first().map_or(lengths[i], |&f| lengths[f]).max(1);
+        if !current.is_empty() && (current.len() + 1).saturating_mul(longest) > token_budget {
+            batches.push(std::mem::take(&mut current));
+        }
+        current.push(i);
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
 /// Configuration for embedding model initialization.
 #[derive(Debug, Clone)]
 pub struct EmbeddingConfig {
@@ -22,6 +108,10 @@ pub struct EmbeddingConfig {
     pub cache_dir: Option<String>,
     /// Show download progress (default: true)
     pub show_download_progress: bool,
+    /// Device to run on (default: best available)
+    pub execution_provider: ExecutionProvider,
+    /// CPU inference threads (default: one per core)
+    pub threads: Option<usize>,
 }
 
 impl Default for EmbeddingConfig {
@@ -32,6 +122,8 @@ impl Default for EmbeddingConfig {
             model_id: "bge-small-en-v1.5".to_string(),
             cache_dir: None,
             show_download_progress: true,
+            execution_provider: ExecutionProvider::Auto,
+            threads: None,
         }
     }
 }
@@ -45,6 +137,8 @@ pub struct LocalEmbeddingService {
     available: bool,
     dimensions: usize,
     model_id: String,
+    /// Padded tokens per forward pass in `embed_batch`
+    token_budget: usize,
 }
 
 impl LocalEmbeddingService {
@@ -72,11 +166,21 @@ impl LocalEmbeddingService {
                     available: false,
                     dimensions,
                     model_id,
+                    token_budget: DEFAULT_TOKEN_BUDGET,
                 });
             }
         };
 
-        let device = select_device();
+        if let Some(threads) = config.threads {
+            set_cpu_threads(threads);
+        }
+        let device = device_for(config.execution_provider).unwrap_or_else(|e| {
+            warn!(
+                "Embedding device '{}' unavailable ({:#}), using CPU",
+                config.execution_provider, e
+            );
+            candle_core::Device::Cpu
+        });
 
         match BertEmbedder::new(&files, device) {
             Ok(embedder) => Ok(Self {
@@ -84,6 +188,7 @@ impl LocalEmbeddingService {
                 available: true,
                 dimensions,
                 model_id,
+                token_budget: token_budget(dimensions),
             }),
             Err(e) => {
                 warn!(
@@ -95,6 +200,7 @@ impl LocalEmbeddingService {
                     available: false,
                     dimensions,
                     model_id,
+                    token_budget: DEFAULT_TOKEN_BUDGET,
                 })
             }
         }
@@ -147,10 +253,11 @@ impl EmbeddingService for LocalEmbeddingService {
             .clone();
 
         let texts = texts.to_vec();
+        let token_budget = self.token_budget;
 
         // Use spawn_blocking since candle operations are synchronous and CPU/GPU-bound
         let result = tokio::task::spawn_blocking(move || {
-            let embeddings = embedder.embed(&texts)?;
+            let embeddings = embedder.embed_with_budget(&texts, token_budget)?;
             Ok::<Vec<Vec<f32>>, anyhow::Error>(embeddings)
         })
         .await
@@ -176,3 +283,51 @@ impl EmbeddingService for LocalEmbeddingService {
         "candle"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_routing() {
+        assert!(is_english(None));
+        assert!(is_english(Some("en-GB")));
+        assert!(is_english(Some("English")));
+        assert!(!is_english(Some("de")));
+        assert!(!is_english(Some("es_MX")));
+        assert_eq!(default_model_for_language(Some("en")), "bge-small-en-v1.5");
+        assert_eq!(
+            default_model_for_language(Some("fr")),
+            MULTILINGUAL_EMBEDDING_MODEL
+        );
+    }
+
+    #[test]
+    fn test_plan_batches_groups_by_length_within_budget() {
+        let lengths = [10, 500, 12, 480, 11, 9];
+        let batches = plan_batches(&lengths, 1024);
+
+        // Every index exactly once
+        let mut seen: Vec<usize> = batches.iter().flatten().copied().collect();
+        seen.sort();
+        assert_eq!(seen, vec![0, 1, 2, 3, 4, 5]);
+
+        // Long texts share a pass; short ones aren't padded to 500
+        assert_eq!(batches[0], vec![1, 3]);
+        assert_eq!(batches[1], vec![2, 4, 0, 5]);
+        for batch in &batches {
+            let longest = batch.iter().map(|&i| lengths[i]).max().unwrap();
+            assert!(batch.len() * longest <= 1024);
+        }
+
+        // Oversized text still gets its own pass
+        assert_eq!(plan_batches(&[4096, 8], 1024), vec![vec![0], vec![1]]);
+        assert!(plan_batches(&[], 1024).is_empty());
+    }
+
+    #[test]
+    fn test_token_budget_is_clamped() {
+        let budget = token_budget(384);
+        assert!((MIN_TOKEN_BUDGET..=MAX_TOKEN_BUDGET).contains(&budget));
+    }
+}
@@ -10,7 +10,11 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use crate::embedding::{EmbeddingConfig, EmbeddingService, LocalEmbeddingService};
+use crate::embedding::candle_backend::ExecutionProvider;
+use crate::embedding::lazy::LazyEmbeddingService;
+use crate::embedding::{
+    EmbeddingConfig, EmbeddingService, LocalEmbeddingService, NoopEmbeddingService,
+};
 use crate::NarraError;
 
 /// Embedding provider configuration.
@@ -32,6 +36,12 @@ pub enum EmbeddingProviderConfig {
         /// Show download progress bar (default: true)
         #[serde(default = "default_true")]
         show_download_progress: bool,
+        /// Device to run on: auto, cpu, cuda, or metal (default: config, then auto)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        device: Option<ExecutionProvider>,
+        /// CPU inference threads (default: config, then one per core)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        threads: Option<usize>,
     },
     // Future: OpenAi, Cohere, Voyage, etc.
     // Gated behind `api-embeddings` feature flag.
@@ -51,6 +61,8 @@ impl Default for EmbeddingProviderConfig {
             model: default_candle_model(),
             cache_dir: None,
             show_download_progress: true,
+            device: None,
+            threads: None,
         }
     }
 }
@@ -80,17 +92,32 @@ pub enum ModelMatch {
     },
 }
 
+/// Supported local models: (short name, HuggingFace repo ID, dimensions).
+pub const CANDLE_MODELS: &[(&str, &str, usize)] = &[
+    ("bge-small-en-v1.5", "BAAI/bge-small-en-v1.5", 384),
+    ("bge-base-en-v1.5", "BAAI/bge-base-en-v1.5", 768),
+    ("bge-large-en-v1.5", "BAAI/bge-large-en-v1.5", 1024),
+    (
+        "multilingual-e5-small",
+        "intfloat/multilingual-e5-small",
+        384,
+    ),
+];
+
 /// Map model short name to (HuggingFace repo ID, dimensions).
 fn resolve_model(name: &str) -> Result<(&str, usize), NarraError> {
-    match name {
-        "bge-small-en-v1.5" => Ok(("BAAI/bge-small-en-v1.5", 384)),
-        "bge-base-en-v1.5" => Ok(("BAAI/bge-base-en-v1.5", 768)),
-        "bge-large-en-v1.5" => Ok(("BAAI/bge-large-en-v1.5", 1024)),
-        other => Err(NarraError::Database(format!(
-            "Unknown embedding model: '{}'. Supported: bge-small-en-v1.5, bge-base-en-v1.5, bge-large-en-v1.5",
-            other
-        ))),
-    }
+    CANDLE_MODELS
+        .iter()
+        .find(|(short, _, _)| *short == name)
+        .map(|(_, repo, dims)| (*repo, *dims))
+        .ok_or_else(|| {
+            let supported: Vec<&str> = CANDLE_MODELS.iter().map(|(short, _, _)| *short).collect();
+            NarraError::Database(format!(
+                "Unknown embedding model: '{}'. Supported: {}",
+                name,
+                supported.join(", ")
+            ))
+        })
 }
 
 /// Load embedding provider config with priority:
@@ -98,6 +125,15 @@ fn resolve_model(name: &str) -> Result<(&str, usize), NarraError> {
 /// 2. `NARRA_EMBEDDING_PROVIDER` env var (JSON)
 /// 3. Default (local BGE-small-en-v1.5)
 pub fn load_provider_config(data_path: &Path) -> EmbeddingProviderConfig {
+    load_provider_config_or(data_path, EmbeddingProviderConfig::default())
+}
+
+/// Like `load_provider_config`, but with a caller-supplied fallback (e.g., the
+/// model from `config.toml`) when neither the file nor the env var is set.
+pub fn load_provider_config_or(
+    data_path: &Path,
+    fallback: EmbeddingProviderConfig,
+) -> EmbeddingProviderConfig {
     // Try file first
     let config_path = data_path.join("embedding.toml");
     if config_path.exists() {
@@ -141,7 +177,30 @@ pub fn load_provider_config(data_path: &Path) -> EmbeddingProviderConfig {
         }
     }
 
-    EmbeddingProviderConfig::default()
+    fallback
+}
+
+/// Create an embedding service that loads its model on first use.
+///
+/// The model name is validated up front; download and loading are deferred
+/// until something embeds or checks availability.
+pub fn create_lazy_embedding_service(
+    config: &EmbeddingProviderConfig,
+) -> Result<Arc<dyn EmbeddingService + Send + Sync>, NarraError> {
+    let EmbeddingProviderConfig::Candle { model, .. } = config;
+    let (_, dimensions) = resolve_model(model)?;
+    let provider = config.clone();
+    Ok(Arc::new(LazyEmbeddingService::new(
+        model.clone(),
+        dimensions,
+        "candle",
+        move || {
+            create_embedding_service(&provider).unwrap_or_else(|e| {
+                tracing::error!("Failed to initialize embedding service: {}", e);
+                Arc::new(NoopEmbeddingService::new())
+            })
+        },
+    )))
 }
 
 /// Create an embedding service from provider configuration.
@@ -153,6 +212,8 @@ pub fn create_embedding_service(
             model,
             cache_dir,
             show_download_progress,
+            device,
+            threads,
         } => {
             let (repo_id, dimensions) = resolve_model(model)?;
 
@@ -162,6 +223,8 @@ pub fn create_embedding_service(
                 model_id: model.clone(),
                 cache_dir: cache_dir.clone(),
                 show_download_progress: *show_download_progress,
+                execution_provider: device.unwrap_or_default(),
+                threads: *threads,
             };
 
             let service = LocalEmbeddingService::new(embedding_config)?;
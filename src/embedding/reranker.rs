@@ -27,7 +27,7 @@ pub trait RerankerService: Send + Sync {
     fn is_available(&self) -> bool;
 }
 
-const RERANKER_REPO: &str = "BAAI/bge-reranker-base";
+pub(crate) const RERANKER_REPO: &str = "BAAI/bge-reranker-base";
 
 /// Local cross-encoder reranker using candle.
 pub struct LocalRerankerService {
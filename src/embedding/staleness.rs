@@ -6,17 +6,19 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::db::connection::NarraDb;
+use crate::db::query::{parse_record_id, record_id, statement};
 use tokio::time::Instant;
 use tracing::{error, info, warn};
 
 use crate::embedding::composite::{
     character_composite, event_composite, fact_composite, knowledge_composite, location_composite,
-    note_composite, perspective_composite, relationship_composite, scene_composite,
+    note_composite, perspective_composite, relationship_composite, scene_composite, term_composite,
 };
 use crate::embedding::EmbeddingService;
-use crate::models::{Character, Event, Location, Note, Scene, UniverseFact};
+use crate::models::{Character, Event, Location, Note, Scene, Term, UniverseFact};
 use crate::utils::math::cosine_similarity;
 use crate::NarraError;
 
@@ -32,6 +34,40 @@ pub struct StalenessManager {
     embedding_service: Arc<dyn EmbeddingService + Send + Sync>,
     /// Tracks in-flight regenerations to debounce rapid mutations on the same entity.
     in_flight: Arc<Mutex<HashMap<String, Instant>>>,
+    arc_policy: ArcSnapshotPolicy,
+}
+
+/// When a re-embedding records an arc snapshot.
+///
+/// Drift is measured against the entity's latest snapshot rather than its
+/// previous embedding, so many small edits still add up to a snapshot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArcSnapshotPolicy {
+    /// Minimum cosine distance from the latest snapshot.
+    pub drift_threshold: f32,
+    /// Minimum time between snapshots of one entity. Updates tied to an event ignore it.
+    pub debounce: Duration,
+}
+
+impl Default for ArcSnapshotPolicy {
+    fn default() -> Self {
+        Self {
+            drift_threshold: 0.05,
+            debounce: Duration::from_secs(300),
+        }
+    }
+}
+
+impl ArcSnapshotPolicy {
+    /// `last` is the drift from and age of the latest snapshot, if any.
+    pub fn should_snapshot(&self, last: Option<(f32, Duration)>, anchored: bool) -> bool {
+        match last {
+            None => true,
+            Some((drift, age)) => {
+                drift >= self.drift_threshold && (anchored || age >= self.debounce)
+            }
+        }
+    }
 }
 
 impl StalenessManager {
@@ -49,9 +85,16 @@ impl StalenessManager {
             db,
             embedding_service,
             in_flight: Arc::new(Mutex::new(HashMap::new())),
+            arc_policy: ArcSnapshotPolicy::default(),
         }
     }
 
+    /// Override when re-embeddings record arc snapshots.
+    pub fn with_arc_policy(mut self, policy: ArcSnapshotPolicy) -> Self {
+        self.arc_policy = policy;
+        self
+    }
+
     /// Mark an entity as stale (embedding needs regeneration).
     ///
     /// # Arguments
@@ -62,20 +105,18 @@ impl StalenessManager {
     ///
     /// Ok(()) if entity was marked stale, Err if operation failed.
     pub async fn mark_stale(&self, entity_id: &str) -> Result<(), NarraError> {
-        // Parse entity type from entity_id
-        let parts: Vec<&str> = entity_id.split(':').collect();
-        if parts.len() != 2 {
-            return Err(NarraError::Database(format!(
-                "Invalid entity_id format: {}",
-                entity_id
-            )));
-        }
+        let entity_ref = parse_record_id(entity_id).map_err(|_| {
+            NarraError::Database(format!("Invalid entity_id format: {}", entity_id))
+        })?;
 
         // Update the entity to mark it stale
-        let query = format!("UPDATE {} SET embedding_stale = true", entity_id);
-        self.db.query(query).await.map_err(|e| {
-            NarraError::Database(format!("Failed to mark {} stale: {}", entity_id, e))
-        })?;
+        self.db
+            .query("UPDATE $entity SET embedding_stale = true")
+            .bind(("entity", entity_ref))
+            .await
+            .map_err(|e| {
+                NarraError::Database(format!("Failed to mark {} stale: {}", entity_id, e))
+            })?;
 
         info!("Marked {} as stale", entity_id);
         Ok(())
@@ -117,19 +158,24 @@ impl StalenessManager {
             }
         }
 
-        // Build SET clauses for all facets
-        let set_clauses: Vec<String> = facets
-            .iter()
-            .map(|facet| format!("{}_stale = true", facet))
-            .collect();
-
-        let query = format!("UPDATE {} SET {}", entity_id, set_clauses.join(", "));
-        self.db.query(query).await.map_err(|e| {
-            NarraError::Database(format!(
-                "Failed to mark facets {:?} stale for {}: {}",
-                facets, entity_id, e
-            ))
-        })?;
+        // One statement per facet combination, built on first use
+        let query = statement(&format!("mark_facets_stale:{}", facets.join(",")), || {
+            let set_clauses: Vec<String> = facets
+                .iter()
+                .map(|facet| format!("{}_stale = true", facet))
+                .collect();
+            format!("UPDATE $entity SET {}", set_clauses.join(", "))
+        });
+        self.db
+            .query(&*query)
+            .bind(("entity", record_id("character", entity_id)?))
+            .await
+            .map_err(|e| {
+                NarraError::Database(format!(
+                    "Failed to mark facets {:?} stale for {}: {}",
+                    facets, entity_id, e
+                ))
+            })?;
 
         info!("Marked facets {:?} as stale for {}", facets, entity_id);
         Ok(())
@@ -154,15 +200,12 @@ impl StalenessManager {
         }
 
         // Find all connected characters via relates_to edges
-        let query = format!(
-            "SELECT ->relates_to->character.id AS related FROM {}",
-            entity_id
-        );
-
-        let mut result =
-            self.db.query(&query).await.map_err(|e| {
-                NarraError::Database(format!("Failed to find related entities: {}", e))
-            })?;
+        let mut result = self
+            .db
+            .query("SELECT ->relates_to->character.id AS related FROM $entity")
+            .bind(("entity", record_id("character", entity_id)?))
+            .await
+            .map_err(|e| NarraError::Database(format!("Failed to find related entities: {}", e)))?;
 
         #[derive(serde::Deserialize)]
         struct RelatedResult {
@@ -249,11 +292,13 @@ impl StalenessManager {
         let db = self.db.clone();
         let embedding_service = self.embedding_service.clone();
         let in_flight = Arc::clone(&self.in_flight);
+        let arc_policy = self.arc_policy;
 
         tokio::spawn(async move {
             let result = regenerate_embedding_internal(
                 db,
                 embedding_service,
+                arc_policy,
                 &entity_id,
                 &entity_type,
                 event_id,
@@ -302,6 +347,7 @@ impl StalenessManager {
         regenerate_embedding_internal(
             self.db.clone(),
             self.embedding_service.clone(),
+            self.arc_policy,
             entity_id,
             entity_type,
             event_id,
@@ -343,6 +389,7 @@ impl StalenessManager {
         regenerate_facet_embedding_internal(
             self.db.clone(),
             self.embedding_service.clone(),
+            self.arc_policy,
             entity_id,
             facet,
             event_id,
@@ -360,6 +407,7 @@ use crate::embedding::composite::{
 async fn regenerate_facet_embedding_internal(
     db: Arc<NarraDb>,
     embedding_service: Arc<dyn EmbeddingService + Send + Sync>,
+    arc_policy: ArcSnapshotPolicy,
     entity_id: &str,
     facet: &str,
     event_id: Option<String>,
@@ -565,38 +613,18 @@ async fn regenerate_facet_embedding_internal(
         .await
         .map_err(|e| NarraError::Database(format!("Failed to generate facet embedding: {}", e)))?;
 
-    // Create arc snapshot for this facet
-    let delta_magnitude = old_embedding
-        .as_ref()
-        .map(|old| 1.0 - crate::utils::math::cosine_similarity(old, &embedding));
-
-    let event_ref: Option<surrealdb::RecordId> = match &event_id {
-        Some(eid) if eid.starts_with("event:") => {
-            let key = eid.strip_prefix("event:").unwrap_or(eid);
-            Some(surrealdb::RecordId::from(("event", key)))
-        }
-        Some(eid) => Some(surrealdb::RecordId::from(("event", eid.as_str()))),
-        None => None,
-    };
-
-    if let Err(e) = db
-        .query(
-            "CREATE arc_snapshot SET entity_id = $eid, entity_type = 'character', \
-             facet = $facet, embedding = $snap_embedding, delta_magnitude = $delta_magnitude, \
-             event_id = $event_ref",
-        )
-        .bind(("eid", entity_ref.clone()))
-        .bind(("facet", facet.to_string()))
-        .bind(("snap_embedding", embedding.clone()))
-        .bind(("delta_magnitude", delta_magnitude))
-        .bind(("event_ref", event_ref))
-        .await
-    {
-        warn!(
-            "Failed to create arc snapshot for {} facet {}: {}",
-            entity_id, facet, e
-        );
-    }
+    // Record an arc snapshot for this facet if it drifted far enough
+    record_arc_snapshot(
+        &db,
+        arc_policy,
+        &entity_ref,
+        "character",
+        Some(facet),
+        &embedding,
+        old_embedding.as_deref(),
+        event_id.as_deref(),
+    )
+    .await;
 
     // Update character with new facet embedding + composite text
     let update_query = format!(
@@ -631,6 +659,7 @@ async fn regenerate_facet_embedding_internal(
 async fn regenerate_embedding_internal(
     db: Arc<NarraDb>,
     embedding_service: Arc<dyn EmbeddingService + Send + Sync>,
+    arc_policy: ArcSnapshotPolicy,
     entity_id: &str,
     entity_type: &str,
     event_id: Option<String>,
@@ -810,6 +839,9 @@ async fn regenerate_embedding_internal(
                 event: surrealdb::RecordId::from(("event", "placeholder")),
                 primary_location: surrealdb::RecordId::from(("location", "placeholder")),
                 secondary_locations: vec![],
+                word_count: None,
+                tags: vec![],
+                draft_status: "outline".to_string(),
                 created_at: surrealdb::Datetime::default(),
                 updated_at: surrealdb::Datetime::default(),
             };
@@ -1017,6 +1049,22 @@ async fn regenerate_embedding_internal(
 
             fact_composite(&fact)
         }
+        "term" => {
+            let mut result = db
+                .query("SELECT * FROM ONLY $ref")
+                .bind(("ref", entity_ref.clone()))
+                .await
+                .map_err(|e| NarraError::Database(format!("Failed to fetch term: {}", e)))?;
+
+            let term: Option<Term> = result
+                .take(0)
+                .map_err(|e| NarraError::Database(format!("Failed to parse term: {}", e)))?;
+
+            let term =
+                term.ok_or_else(|| NarraError::Database(format!("Term not found: {}", entity_id)))?;
+
+            term_composite(&term)
+        }
         _ => {
             return Err(NarraError::Database(format!(
                 "Unknown entity type: {}",
@@ -1058,24 +1106,11 @@ async fn regenerate_embedding_internal(
         .await
         .map_err(|e| NarraError::Database(format!("Failed to generate embedding: {}", e)))?;
 
-    // Create arc snapshot for trackable types (character, knowledge, perceives, relates_to)
+    // Record an arc snapshot for trackable types (character, knowledge, perceives, relates_to)
     if matches!(
         entity_type,
         "character" | "knowledge" | "perceives" | "relates_to"
     ) {
-        let delta_magnitude = old_embedding
-            .as_ref()
-            .map(|old| 1.0 - cosine_similarity(old, &embedding));
-
-        let event_ref: Option<surrealdb::RecordId> = match &event_id {
-            Some(eid) if eid.starts_with("event:") => {
-                let key = eid.strip_prefix("event:").unwrap_or(eid);
-                Some(surrealdb::RecordId::from(("event", key)))
-            }
-            Some(eid) => Some(surrealdb::RecordId::from(("event", eid.as_str()))),
-            None => None,
-        };
-
         // Use friendly names as arc_snapshot entity_type for edge types
         let snapshot_entity_type = match entity_type {
             "perceives" => "perspective",
@@ -1083,20 +1118,17 @@ async fn regenerate_embedding_internal(
             other => other,
         };
 
-        if let Err(e) = db
-            .query(
-                "CREATE arc_snapshot SET entity_id = $eid, entity_type = $entity_type, \
-                 embedding = $snap_embedding, delta_magnitude = $delta_magnitude, event_id = $event_ref",
-            )
-            .bind(("eid", entity_ref.clone()))
-            .bind(("entity_type", snapshot_entity_type.to_string()))
-            .bind(("snap_embedding", embedding.clone()))
-            .bind(("delta_magnitude", delta_magnitude))
-            .bind(("event_ref", event_ref))
-            .await
-        {
-            warn!("Failed to create arc snapshot for {}: {}", entity_id, e);
-        }
+        record_arc_snapshot(
+            &db,
+            arc_policy,
+            &entity_ref,
+            snapshot_entity_type,
+            None,
+            &embedding,
+            old_embedding.as_deref(),
+            event_id.as_deref(),
+        )
+        .await;
     }
 
     // Update entity with new embedding + composite text
@@ -1121,6 +1153,96 @@ async fn regenerate_embedding_internal(
     Ok(())
 }
 
+/// Create an arc snapshot if `embedding` drifted past the policy threshold
+/// since the entity's latest snapshot. Failures are logged, never fatal.
+#[allow(clippy::too_many_arguments)]
+async fn record_arc_snapshot(
+    db: &NarraDb,
+    policy: ArcSnapshotPolicy,
+    entity_ref: &surrealdb::RecordId,
+    entity_type: &str,
+    facet: Option<&str>,
+    embedding: &[f32],
+    old_embedding: Option<&[f32]>,
+    event_id: Option<&str>,
+) {
+    #[derive(serde::Deserialize)]
+    struct LastSnapshot {
+        embedding: Vec<f32>,
+        age_secs: i64,
+    }
+
+    let facet_filter = if facet.is_some() {
+        "facet = $facet"
+    } else {
+        "facet IS NONE"
+    };
+    let last: Option<LastSnapshot> = match db
+        .query(format!(
+            "SELECT embedding, time::unix(time::now()) - time::unix(created_at) AS age_secs \
+             FROM arc_snapshot WHERE entity_id = $eid AND {} \
+             ORDER BY created_at DESC LIMIT 1",
+            facet_filter
+        ))
+        .bind(("eid", entity_ref.clone()))
+        .bind(("facet", facet.map(str::to_string)))
+        .await
+    {
+        Ok(mut response) => response
+            .take::<Vec<LastSnapshot>>(0)
+            .unwrap_or_default()
+            .pop(),
+        Err(e) => {
+            warn!(
+                "Failed to fetch latest arc snapshot for {}: {}",
+                entity_ref, e
+            );
+            return;
+        }
+    };
+
+    // Without a snapshot yet, the first one is the baseline
+    let drift = last
+        .as_ref()
+        .map(|l| 1.0 - cosine_similarity(&l.embedding, embedding));
+    let age = last
+        .as_ref()
+        .map(|l| Duration::from_secs(l.age_secs.max(0) as u64));
+    if !policy.should_snapshot(drift.zip(age), event_id.is_some()) {
+        info!(
+            "Skipped arc snapshot for {} (drift {:.3} below {:.3} or within debounce)",
+            entity_ref,
+            drift.unwrap_or_default(),
+            policy.drift_threshold
+        );
+        return;
+    }
+
+    let delta_magnitude =
+        drift.or_else(|| old_embedding.map(|old| 1.0 - cosine_similarity(old, embedding)));
+    let event_ref = event_id.map(|eid| {
+        let key = eid.strip_prefix("event:").unwrap_or(eid);
+        surrealdb::RecordId::from(("event", key))
+    });
+
+    if let Err(e) = db
+        .query(
+            "CREATE arc_snapshot SET entity_id = $eid, entity_type = $entity_type, \
+             facet = $facet, embedding = $snap_embedding, delta_magnitude = $delta_magnitude, \
+             event_id = $event_ref",
+        )
+        .bind(("eid", entity_ref.clone()))
+        .bind(("entity_type", entity_type.to_string()))
+        .bind(("facet", facet.map(str::to_string)))
+        .bind(("snap_embedding", embedding.to_vec()))
+        .bind(("delta_magnitude", delta_magnitude))
+        .bind(("event_ref", event_ref))
+        .await
+    {
+        warn!("Failed to create arc snapshot for {}: {}", entity_ref, e);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1188,4 +1310,23 @@ mod tests {
         // Should proceed since the window has expired
         assert!(tracker.should_spawn("character:alice"));
     }
+
+    #[test]
+    fn test_arc_policy_threshold_and_debounce() {
+        let policy = ArcSnapshotPolicy {
+            drift_threshold: 0.1,
+            debounce: Duration::from_secs(60),
+        };
+        let recent = Duration::from_secs(5);
+        let old = Duration::from_secs(600);
+
+        // First snapshot is always the baseline
+        assert!(policy.should_snapshot(None, false));
+        // Small drift never snapshots
+        assert!(!policy.should_snapshot(Some((0.05, old)), true));
+        // Large drift waits out the debounce unless tied to an event
+        assert!(!policy.should_snapshot(Some((0.2, recent)), false));
+        assert!(policy.should_snapshot(Some((0.2, recent)), true));
+        assert!(policy.should_snapshot(Some((0.2, old)), false));
+    }
 }
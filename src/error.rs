@@ -31,6 +31,10 @@ pub enum NarraError {
         source: Option<Box<dyn std::error::Error + Send + Sync>>,
     },
 
+    /// The current user's access role does not allow the operation.
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
     /// Deletion blocked due to referential integrity constraint.
     #[error("Cannot delete {entity_type} '{entity_id}': {message}")]
     ReferentialIntegrityViolation {
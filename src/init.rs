@@ -4,19 +4,31 @@ use anyhow::Result;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use crate::config::NarraConfig;
 use crate::db::connection::{init_db, load_db_config, DbConfig, NarraDb};
 use crate::db::schema::apply_schema;
+use crate::db::vault::Vault;
+use crate::embedding::lazy::Lazy;
 use crate::embedding::provider::{
-    create_embedding_service, load_provider_config, EmbeddingMetadata, ModelMatch,
+    create_lazy_embedding_service, load_provider_config_or, EmbeddingMetadata,
+    EmbeddingProviderConfig, ModelMatch,
 };
+use crate::embedding::reranker::RerankerService;
 use crate::embedding::{EmbeddingService, StalenessManager};
+use crate::plugins::{PluginConsistencyService, PluginHost};
 use crate::repository::{
     SurrealEntityRepository, SurrealKnowledgeRepository, SurrealRelationshipRepository,
 };
+use crate::services::access::{AccessPolicy, AccessService};
+use crate::services::assets::AssetStore;
+use crate::services::backup::{BackupService, DEFAULT_KEEP};
+use crate::services::ontology::RelationshipOntology;
+use crate::services::transmission::TransmissionRules;
+use crate::services::vector_index::{self, VectorIndexKind, VectorIndexSettings};
 use crate::services::{
     CachedContextService, CachedSummaryService, ConsistencyChecker, ConsistencyService,
-    ContextService, EmotionService, ImpactAnalyzer, ImpactService, NerService, SearchService,
-    SummaryService, SurrealSearchService, ThemeService,
+    ContextService, EmotionService, EventBus, ImpactAnalyzer, ImpactService, NerService,
+    SearchService, SummaryService, SurrealSearchService, ThemeService, TokenCounter,
 };
 use crate::session::SessionStateManager;
 
@@ -40,30 +52,44 @@ pub struct AppContext {
     pub emotion_service: Arc<dyn EmotionService + Send + Sync>,
     pub theme_service: Arc<dyn ThemeService + Send + Sync>,
     pub ner_service: Arc<dyn NerService + Send + Sync>,
+    /// Token counter for budget enforcement (real tokenizer, or ~4 chars/token fallback).
+    pub token_counter: Arc<dyn TokenCounter>,
+    /// Event stream for external tooling (sinks attached via `--events`).
+    pub event_bus: Arc<EventBus>,
+    /// User Lua plugins from `<data_path>/plugins`.
+    pub plugins: Arc<PluginHost>,
     /// Whether the current embedding model mismatches stored world metadata.
     pub embedding_model_mismatch: ModelMatch,
+    /// Merged user/project/env configuration.
+    pub config: NarraConfig,
+    /// Where the world lives: embedded, encrypted, or on a remote server.
+    pub db_config: DbConfig,
+    /// Set for encrypted worlds; seal `db` through it to persist changes.
+    pub vault: Option<Arc<Vault>>,
+    /// Backups in `<data_path>/backups`, taken automatically before destructive operations.
+    pub backup_service: Arc<BackupService>,
+    /// Images attached to entities, stored in `<data_path>/assets`.
+    pub asset_store: Arc<AssetStore>,
+    /// Canonical relationship types, from built-ins and `[relationships]` config.
+    pub ontology: Arc<RelationshipOntology>,
+    /// How certainty carries over when knowledge is passed on.
+    pub transmission_rules: Arc<TransmissionRules>,
+    /// The current user's role in a shared world.
+    pub access: Arc<AccessPolicy>,
 }
 
 impl AppContext {
-    /// Initialize application context.
+    /// Initialize application context, loading layered config from disk and env.
     ///
-    /// Data path priority: explicit path > NARRA_DATA_PATH env > ./.narra (if exists) > ~/.narra
+    /// Data path priority: explicit path > NARRA_DATA_PATH env > config `data_path`
+    /// > ./.narra (if exists) > ~/.narra
     pub async fn new(explicit_path: Option<PathBuf>) -> Result<Self> {
-        let data_path = explicit_path
-            .or_else(|| std::env::var("NARRA_DATA_PATH").ok().map(PathBuf::from))
-            .or_else(|| {
-                let local_path = Path::new(".narra");
-                if local_path.exists() && local_path.is_dir() {
-                    Some(local_path.to_path_buf())
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_else(|| {
-                dirs::home_dir()
-                    .map(|h| h.join(".narra"))
-                    .unwrap_or_else(|| PathBuf::from(".narra"))
-            });
+        Self::with_config(explicit_path, NarraConfig::load()?).await
+    }
+
+    /// Initialize application context with an already-loaded config.
+    pub async fn with_config(explicit_path: Option<PathBuf>, config: NarraConfig) -> Result<Self> {
+        let data_path = resolve_data_path(explicit_path.or_else(|| config.data_path.clone()));
 
         tracing::info!("Using data path: {}", data_path.display());
 
@@ -71,12 +97,19 @@ impl AppContext {
         let db_config = load_db_config(&data_path);
         match &db_config {
             DbConfig::Embedded { .. } => tracing::info!("Using embedded database"),
+            DbConfig::Encrypted => tracing::info!("Using encrypted database"),
             DbConfig::Remote { endpoint, .. } => {
                 tracing::info!("Connecting to remote database: {}", endpoint)
             }
         }
 
-        let db = init_db(&db_config, &data_path).await?;
+        let (db, vault) = match &db_config {
+            DbConfig::Encrypted => {
+                let vault = Vault::unlock(&data_path, true)?;
+                (vault.load().await?, Some(Arc::new(vault)))
+            }
+            _ => (init_db(&db_config, &data_path).await?, None),
+        };
         tracing::info!("Database connected");
 
         apply_schema(&db).await?;
@@ -86,28 +119,25 @@ impl AppContext {
 
         // Session state
         let session_path = data_path.join("session.json");
-        let session_manager = Arc::new(SessionStateManager::load_or_create(&session_path)?);
+        let session_manager = Arc::new(
+            SessionStateManager::load_or_create(&session_path)?
+                .with_decay(config.session.decay())
+                .with_startup_options(config.startup.options()?),
+        );
         tracing::info!("Session state loaded");
 
-        // Embedding model — load provider config
-        tracing::info!("Initializing embedding model...");
-        let provider_config = load_provider_config(&data_path);
-        let embedding_service: Arc<dyn EmbeddingService + Send + Sync> =
-            create_embedding_service(&provider_config).unwrap_or_else(|e| {
-                tracing::error!("Failed to initialize embedding service: {}", e);
-                panic!("Embedding service initialization failed");
-            });
-
-        if embedding_service.is_available() {
-            tracing::info!(
-                "Embedding model loaded: {} via {} ({} dimensions)",
-                embedding_service.model_id(),
-                embedding_service.provider_name(),
-                embedding_service.dimensions()
-            );
-        } else {
-            tracing::warn!("Embedding model not available");
+        // Embedding model — resolved now, loaded on first use
+        if crate::embedding::hub::is_offline() {
+            tracing::info!("Offline mode: models load from the local cache only");
         }
+        let provider_config = resolve_provider_config(&data_path, &config);
+        let embedding_service = create_lazy_embedding_service(&provider_config)?;
+        tracing::info!(
+            "Embedding model: {} via {} ({} dimensions, loads on first use)",
+            embedding_service.model_id(),
+            embedding_service.provider_name(),
+            embedding_service.dimensions()
+        );
 
         // Check model match against stored world metadata
         let embedding_model_mismatch =
@@ -134,76 +164,164 @@ impl AppContext {
             }
         }
 
-        // Repositories
-        let entity_repo = Arc::new(SurrealEntityRepository::new(db.clone()));
-        let relationship_repo = Arc::new(SurrealRelationshipRepository::new(db.clone()));
-        let knowledge_repo = Arc::new(SurrealKnowledgeRepository::new(db.clone()));
-
-        // Reranker — loads model eagerly, degrades gracefully if unavailable.
-        let reranker: Option<Arc<dyn crate::embedding::reranker::RerankerService + Send + Sync>> = {
-            let reranker = crate::embedding::reranker::LocalRerankerService::new();
-            if crate::embedding::reranker::RerankerService::is_available(&reranker) {
-                Some(Arc::new(reranker))
-            } else {
-                None
-            }
+        let vector_index = resolve_vector_index(&db, &config, embedding_service.as_ref()).await;
+        crate::services::kmeans::configure(config.clustering.options());
+
+        // Access — remote worlds identify users by database login, others by author
+        let access_user = match &db_config {
+            DbConfig::Remote {
+                username: Some(user),
+                ..
+            } => Some(user.clone()),
+            _ => config.author(),
         };
+        let access = Arc::new(
+            AccessService::new(db.clone())
+                .policy_for(access_user.as_deref())
+                .await?,
+        );
+        if access.enforced {
+            tracing::info!(
+                "Access role: {} ({})",
+                access.role,
+                access.user.as_deref().unwrap_or("anonymous")
+            );
+        }
+
+        // Repositories
+        let entity_repo =
+            Arc::new(SurrealEntityRepository::new(db.clone()).with_access(access.clone()));
+        let relationship_repo =
+            Arc::new(SurrealRelationshipRepository::new(db.clone()).with_access(access.clone()));
+        let knowledge_repo =
+            Arc::new(SurrealKnowledgeRepository::new(db.clone()).with_access(access.clone()));
+
+        // Reranker — loads on the first rerank, degrades gracefully if unavailable.
+        let reranker: Arc<dyn RerankerService + Send + Sync> =
+            Arc::new(Lazy::<dyn RerankerService + Send + Sync>::new(
+                "reranker",
+                || Arc::new(crate::embedding::reranker::LocalRerankerService::new()),
+            ));
 
         // Services
         let mut search = SurrealSearchService::new(db.clone(), embedding_service.clone());
-        if let Some(ref r) = reranker {
-            search = search.with_reranker(r.clone());
-        }
+        search = search
+            .with_reranker(reranker)
+            .with_vector_index(vector_index);
         let search_service: Arc<dyn SearchService + Send + Sync> = Arc::new(search);
-        let summary_service: Arc<dyn SummaryService + Send + Sync> =
-            Arc::new(CachedSummaryService::with_defaults(db.clone()));
+        // Tokenizer — fetched on the first count, heuristic if unavailable.
+        let token_counter: Arc<dyn TokenCounter> = Arc::new(Lazy::<dyn TokenCounter>::new(
+            "tokenizer",
+            crate::services::create_token_counter,
+        ));
+        let summary_service: Arc<dyn SummaryService + Send + Sync> = Arc::new(
+            CachedSummaryService::with_defaults(db.clone())
+                .with_token_counter(token_counter.clone()),
+        );
         let context_service: Arc<dyn ContextService + Send + Sync> = Arc::new(
-            CachedContextService::new(db.clone(), session_manager.clone()),
+            CachedContextService::new(db.clone(), session_manager.clone())
+                .with_token_counter(token_counter.clone()),
         );
         let impact_service: Arc<dyn ImpactService + Send + Sync> =
             Arc::new(ImpactAnalyzer::new(db.clone()));
-        let consistency_service: Arc<dyn ConsistencyService> =
-            Arc::new(ConsistencyChecker::new(db.clone()));
-        let staleness_manager =
-            Arc::new(StalenessManager::new(db.clone(), embedding_service.clone()));
-
-        // Emotion classifier — loads model eagerly, degrades gracefully if unavailable.
-        let emotion_service: Arc<dyn EmotionService + Send + Sync> = {
-            let service = crate::services::LocalEmotionService::new(db.clone());
-            if service.is_available() {
-                Arc::new(service)
-            } else {
-                tracing::info!(
-                    "Emotion classifier not available, using noop (emotion queries will return errors)"
-                );
-                Arc::new(crate::services::NoopEmotionService::new())
-            }
+        // Plugins — a failing script is skipped, never fatal.
+        let plugins = Arc::new(PluginHost::load(&data_path, db.clone()).await);
+        let mut consistency_service: Arc<dyn ConsistencyService> = Arc::new(
+            ConsistencyChecker::new(db.clone())
+                .with_strictness(config.consistency.strictness.unwrap_or_default()),
+        );
+        if plugins.has_hook("on_validate") {
+            consistency_service = Arc::new(PluginConsistencyService::new(
+                consistency_service,
+                plugins.clone(),
+                db.clone(),
+            ));
+        }
+        let staleness_manager = Arc::new(
+            StalenessManager::new(db.clone(), embedding_service.clone())
+                .with_arc_policy(config.arcs.policy()),
+        );
+        let backup_service = Arc::new(BackupService::new(
+            db.clone(),
+            &data_path,
+            vault.clone(),
+            config.backup.keep.unwrap_or(DEFAULT_KEEP),
+        ));
+        let asset_store = Arc::new(AssetStore::new(db.clone(), &data_path, vault.clone()));
+        let ontology = Arc::new(config.relationships.ontology()?);
+        let transmission_rules = Arc::new(config.transmission.rules()?);
+
+        // Emotion and theme models are English-only; other languages get noops
+        // rather than confident nonsense.
+        let english = crate::embedding::is_english(config.language.as_deref());
+        if !english {
+            tracing::info!(
+                "Language '{}' is not English: emotion and theme classification disabled",
+                config.language.as_deref().unwrap_or_default()
+            );
+        }
+
+        // Annotation models load on first use and degrade gracefully if unavailable.
+        let emotion_service: Arc<dyn EmotionService + Send + Sync> = if english {
+            let db = db.clone();
+            Arc::new(Lazy::<dyn EmotionService + Send + Sync>::new(
+                "emotion classifier",
+                move || {
+                    let service = crate::services::LocalEmotionService::new(db.clone());
+                    if service.is_available() {
+                        Arc::new(service)
+                    } else {
+                        tracing::info!(
+                        "Emotion classifier not available, using noop (emotion queries will return errors)"
+                    );
+                        Arc::new(crate::services::NoopEmotionService::new())
+                    }
+                },
+            ))
+        } else {
+            Arc::new(crate::services::NoopEmotionService::new())
         };
 
-        // Theme classifier — loads model eagerly, degrades gracefully if unavailable.
-        let theme_service: Arc<dyn ThemeService + Send + Sync> = {
-            let service = crate::services::LocalThemeService::new(db.clone());
-            if service.is_available() {
-                Arc::new(service)
-            } else {
-                tracing::info!(
-                    "Theme classifier not available, using noop (theme queries will return errors)"
-                );
-                Arc::new(crate::services::NoopThemeService::new())
-            }
+        let theme_service: Arc<dyn ThemeService + Send + Sync> = if english {
+            let db = db.clone();
+            Arc::new(Lazy::<dyn ThemeService + Send + Sync>::new(
+                "theme classifier",
+                move || {
+                    let service = crate::services::LocalThemeService::new(db.clone());
+                    if service.is_available() {
+                        Arc::new(service)
+                    } else {
+                        tracing::info!(
+                        "Theme classifier not available, using noop (theme queries will return errors)"
+                    );
+                        Arc::new(crate::services::NoopThemeService::new())
+                    }
+                },
+            ))
+        } else {
+            Arc::new(crate::services::NoopThemeService::new())
         };
 
-        // NER classifier — loads model eagerly, degrades gracefully if unavailable.
         let ner_service: Arc<dyn NerService + Send + Sync> = {
-            let service = crate::services::LocalNerService::new(db.clone());
-            if service.is_available() {
-                Arc::new(service)
-            } else {
-                tracing::info!(
-                    "NER classifier not available, using noop (entity extraction will return errors)"
-                );
-                Arc::new(crate::services::NoopNerService::new())
-            }
+            let db = db.clone();
+            let language = config.language.clone();
+            Arc::new(Lazy::<dyn NerService + Send + Sync>::new(
+                "NER classifier",
+                move || {
+                    let service = crate::services::LocalNerService::for_language(
+                        db.clone(),
+                        language.as_deref(),
+                    );
+                    if service.is_available() {
+                        Arc::new(service)
+                    } else {
+                        tracing::info!(
+                        "NER classifier not available, using noop (entity extraction will return errors)"
+                    );
+                        Arc::new(crate::services::NoopNerService::new())
+                    }
+                },
+            ))
         };
 
         Ok(Self {
@@ -223,8 +341,135 @@ impl AppContext {
             emotion_service,
             theme_service,
             ner_service,
+            token_counter,
+            event_bus: Arc::new(EventBus::new().with_author(config.author())),
+            plugins,
             embedding_model_mismatch,
+            config,
+            db_config,
+            vault,
+            backup_service,
+            asset_store,
+            ontology,
+            transmission_rules,
+            access,
+        })
+    }
+}
+
+/// Resolve the data directory.
+///
+/// Priority: explicit path > NARRA_DATA_PATH env > ./.narra (if exists) > ~/.narra
+pub fn resolve_data_path(explicit_path: Option<PathBuf>) -> PathBuf {
+    explicit_path
+        .or_else(|| std::env::var("NARRA_DATA_PATH").ok().map(PathBuf::from))
+        .or_else(|| {
+            let local_path = Path::new(".narra");
+            if local_path.exists() && local_path.is_dir() {
+                Some(local_path.to_path_buf())
+            } else {
+                None
+            }
+        })
+        .unwrap_or_else(|| {
+            dirs::home_dir()
+                .map(|h| h.join(".narra"))
+                .unwrap_or_else(|| PathBuf::from(".narra"))
         })
+}
+
+/// Embedding provider for a world: its `embedding.toml`, else the configured
+/// model, else the default for the configured language. Device and threads
+/// not set in `embedding.toml` come from config.
+pub fn resolve_provider_config(data_path: &Path, config: &NarraConfig) -> EmbeddingProviderConfig {
+    let model = config.embedding.model.clone().unwrap_or_else(|| {
+        crate::embedding::default_model_for_language(config.language.as_deref()).to_string()
+    });
+    let fallback = EmbeddingProviderConfig::Candle {
+        model,
+        cache_dir: None,
+        show_download_progress: true,
+        device: None,
+        threads: None,
+    };
+    // The device is a property of the machine: config fills in what the
+    // world's embedding.toml leaves unset
+    let mut provider = load_provider_config_or(data_path, fallback);
+    let EmbeddingProviderConfig::Candle {
+        device, threads, ..
+    } = &mut provider;
+    if device.is_none() {
+        *device = config.embedding.device;
+    }
+    if threads.is_none() {
+        *threads = config.embedding.threads;
+    }
+    provider
+}
+
+/// Connect to the database without loading models or services.
+///
+/// For lightweight commands (e.g., shell completion) that only read entities.
+pub async fn connect_db(explicit_path: Option<PathBuf>) -> Result<NarraDb> {
+    let data_path = resolve_data_path(explicit_path);
+    let db_config = load_db_config(&data_path);
+    Ok(init_db(&db_config, &data_path).await?)
+}
+
+/// ANN index settings search should use: the configured parameters for
+/// the index actually built, or none. An index built for other dimensions
+/// would reject every embedding write, so it is dropped.
+async fn resolve_vector_index(
+    db: &NarraDb,
+    config: &NarraConfig,
+    embedding_service: &dyn EmbeddingService,
+) -> VectorIndexSettings {
+    let settings = config.vector_index.settings();
+    let built = match vector_index::built_index(db).await {
+        Ok(built) => built,
+        Err(e) => {
+            tracing::debug!("Could not read vector index state: {}", e);
+            None
+        }
+    };
+
+    match built {
+        Some(built) if built.dimensions != embedding_service.dimensions() => {
+            tracing::warn!(
+                "Vector index was built for {} dimensions but the embedding model has {}; \
+                 dropping it. Run 'narra world reindex-vectors' to rebuild.",
+                built.dimensions,
+                embedding_service.dimensions()
+            );
+            if let Err(e) = vector_index::drop_indexes(db).await {
+                tracing::warn!("Failed to drop vector indexes: {}", e);
+            }
+            VectorIndexSettings::default()
+        }
+        Some(built) => {
+            if built.kind != settings.kind {
+                tracing::warn!(
+                    "Vector index is {} but config asks for {}. \
+                     Run 'narra world reindex-vectors' to rebuild.",
+                    built.kind,
+                    settings.kind
+                );
+            }
+            VectorIndexSettings {
+                kind: built.kind,
+                ..settings
+            }
+        }
+        None => {
+            if settings.kind != VectorIndexKind::None {
+                tracing::warn!(
+                    "Vector index {} is configured but not built. \
+                     Run 'narra world reindex-vectors' to build it.",
+                    settings.kind
+                );
+            }
+            VectorIndexSettings::default()
+        }
     }
 }
 
@@ -1,10 +1,13 @@
 pub mod cli;
+pub mod config;
 pub mod db;
 pub mod embedding;
 pub mod error;
 pub mod init;
+pub mod lsp;
 pub mod mcp;
 pub mod models;
+pub mod plugins;
 pub mod repository;
 pub mod services;
 pub mod session;
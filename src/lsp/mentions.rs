@@ -0,0 +1,77 @@
+//! LSP position arithmetic.
+//!
+//! Pure functions over text so they can be tested without a client. LSP
+//! positions count UTF-16 code units; offsets here are byte offsets into the
+//! Rust `&str`.
+
+use tower_lsp::lsp_types::{Position, Range};
+
+/// Convert a byte offset into an LSP position (UTF-16 columns).
+pub fn offset_to_position(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let before = &text[..offset];
+    let line = before.matches('\n').count() as u32;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let character = text[line_start..offset].encode_utf16().count() as u32;
+    Position { line, character }
+}
+
+/// Convert an LSP position into a byte offset, clamped to the text.
+pub fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+
+    let line_end = text[line_start..]
+        .find('\n')
+        .map(|i| line_start + i)
+        .unwrap_or(text.len());
+    let mut units = 0u32;
+    for (i, c) in text[line_start..line_end].char_indices() {
+        if units >= position.character {
+            return line_start + i;
+        }
+        units += c.len_utf16() as u32;
+    }
+    line_end
+}
+
+/// LSP range covering a byte span.
+pub fn span_to_range(text: &str, start: usize, end: usize) -> Range {
+    Range {
+        start: offset_to_position(text, start),
+        end: offset_to_position(text, end),
+    }
+}
+
+/// Byte offset of the `char_index`-th character (NER reports character offsets).
+pub fn char_to_byte(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_roundtrip_utf16() {
+        let text = "line one\nZoë → Alice\n";
+        let offset = text.find("Alice").unwrap();
+        let pos = offset_to_position(text, offset);
+        assert_eq!(pos, Position::new(1, 6));
+        assert_eq!(position_to_offset(text, pos), offset);
+    }
+
+    #[test]
+    fn test_char_to_byte() {
+        assert_eq!(char_to_byte("Zoë Alice", 4), 5);
+        assert_eq!(char_to_byte("abc", 10), 3);
+    }
+}
@@ -0,0 +1,29 @@
+//! Editor integration over the Language Server Protocol (`narra lsp`).
+//!
+//! Watches manuscript buffers and, using the world database:
+//! - underlines mentions of known entities (as information diagnostics)
+//! - shows hover cards with an entity summary and, for characters, what they
+//!   know as of the most recently mentioned event
+//! - flags names NER finds that match no entity, with a quick fix to create it
+
+pub mod mentions;
+pub mod server;
+
+use std::sync::Arc;
+
+use tower_lsp::{LspService, Server};
+
+use crate::init::AppContext;
+use server::NarraLanguageServer;
+
+/// Run the language server on stdio until the client disconnects.
+pub async fn run_lsp_server(ctx: AppContext) -> anyhow::Result<()> {
+    let ctx = Arc::new(ctx);
+    let (service, socket) = LspService::new(|client| NarraLanguageServer::new(client, ctx));
+
+    tracing::info!("Starting Narra language server on stdio");
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+        .serve(service)
+        .await;
+    Ok(())
+}
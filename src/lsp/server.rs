@@ -0,0 +1,454 @@
+//! Language server backend: diagnostics, hover cards, and quick fixes.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tower_lsp::jsonrpc::{Error as RpcError, Result as RpcResult};
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer};
+
+use super::mentions::{char_to_byte, position_to_offset, span_to_range};
+use crate::init::AppContext;
+use crate::models::{CharacterCreate, LocationCreate};
+use crate::repository::{EntityRepository, KnowledgeRepository};
+use crate::services::mentions::{find_mentions, KnownEntity, Mention};
+use crate::services::names::same_name;
+use crate::services::DetailLevel;
+
+/// Command executed by the "create entity" quick fix.
+pub const CREATE_ENTITY_COMMAND: &str = "narra.createEntity";
+
+/// Diagnostic code for NER hits that match no known entity.
+const UNKNOWN_ENTITY_CODE: &str = "unknown-entity";
+
+/// Maximum knowledge states listed in a hover card.
+const HOVER_KNOWLEDGE_LIMIT: usize = 8;
+
+pub struct NarraLanguageServer {
+    client: Client,
+    ctx: Arc<AppContext>,
+    documents: RwLock<HashMap<Url, String>>,
+    entities: RwLock<Vec<KnownEntity>>,
+}
+
+impl NarraLanguageServer {
+    pub fn new(client: Client, ctx: Arc<AppContext>) -> Self {
+        Self {
+            client,
+            ctx,
+            documents: RwLock::new(HashMap::new()),
+            entities: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Reload the gazetteer of known entity names from the world.
+    async fn reload_entities(&self) -> anyhow::Result<()> {
+        let repo = &self.ctx.entity_repo;
+        let mut known = Vec::new();
+        for c in repo.list_characters().await? {
+            let mut terms = vec![c.name.clone()];
+            terms.extend(c.aliases);
+            known.push(KnownEntity {
+                id: c.id.to_string(),
+                entity_type: "character".into(),
+                name: c.name,
+                terms,
+            });
+        }
+        for l in repo.list_locations().await? {
+            known.push(KnownEntity {
+                id: l.id.to_string(),
+                entity_type: "location".into(),
+                terms: vec![l.name.clone()],
+                name: l.name,
+            });
+        }
+        for e in repo.list_events().await? {
+            known.push(KnownEntity {
+                id: e.id.to_string(),
+                entity_type: "event".into(),
+                terms: vec![e.title.clone()],
+                name: e.title,
+            });
+        }
+        for s in repo.list_scenes().await? {
+            known.push(KnownEntity {
+                id: s.id.to_string(),
+                entity_type: "scene".into(),
+                terms: vec![s.title.clone()],
+                name: s.title,
+            });
+        }
+        *self.entities.write().await = known;
+        Ok(())
+    }
+
+    /// Publish mention underlines, plus unknown-name hints when `run_ner` is set.
+    ///
+    /// NER is comparatively slow, so it runs on open/save rather than every edit.
+    async fn publish(&self, uri: Url, run_ner: bool) {
+        let Some(text) = self.documents.read().await.get(&uri).cloned() else {
+            return;
+        };
+        let entities = self.entities.read().await;
+        let mentions = find_mentions(&text, &entities);
+
+        let mut diagnostics: Vec<Diagnostic> = mentions
+            .iter()
+            .map(|m| {
+                let e = &entities[m.entity_index];
+                Diagnostic {
+                    range: span_to_range(&text, m.start, m.end),
+                    severity: Some(DiagnosticSeverity::INFORMATION),
+                    source: Some("narra".into()),
+                    message: format!("{}: {}", e.entity_type, e.name),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        if run_ner && self.ctx.ner_service.is_available() {
+            match self.ctx.ner_service.extract_entities(&text).await {
+                Ok(output) => {
+                    for ner in output.entities {
+                        let Some(entity_type) = ner_label_to_type(&ner.label) else {
+                            continue;
+                        };
+                        let start = char_to_byte(&text, ner.start);
+                        let end = char_to_byte(&text, ner.end);
+                        if overlaps_any(&mentions, start, end) {
+                            continue;
+                        }
+                        let name = text[start..end].trim().to_string();
+                        // Another spelling of a known name ("Osten" for "Östen")
+                        if let Some(known) = entities
+                            .iter()
+                            .find(|e| e.terms.iter().any(|t| same_name(t, &name)))
+                        {
+                            diagnostics.push(Diagnostic {
+                                range: span_to_range(&text, start, end),
+                                severity: Some(DiagnosticSeverity::INFORMATION),
+                                source: Some("narra".into()),
+                                message: format!("{}: {}", known.entity_type, known.name),
+                                ..Default::default()
+                            });
+                            continue;
+                        }
+                        diagnostics.push(Diagnostic {
+                            range: span_to_range(&text, start, end),
+                            severity: Some(DiagnosticSeverity::HINT),
+                            code: Some(NumberOrString::String(UNKNOWN_ENTITY_CODE.into())),
+                            source: Some("narra".into()),
+                            message: format!("Unknown {} '{}'", entity_type, name),
+                            data: Some(serde_json::json!({
+                                "entity_type": entity_type,
+                                "name": name,
+                            })),
+                            ..Default::default()
+                        });
+                    }
+                }
+                Err(e) => tracing::debug!("NER failed for {}: {}", uri, e),
+            }
+        }
+
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
+
+    /// Markdown hover card for a mentioned entity.
+    async fn hover_card(&self, entity: &KnownEntity, text: &str, offset: usize) -> String {
+        let mut card = format!("**{}** ({})\n\n", entity.name, entity.entity_type);
+
+        if let Ok(Some(summary)) = self
+            .ctx
+            .summary_service
+            .get_entity_content(&entity.id, DetailLevel::Summary)
+            .await
+        {
+            card.push_str(&summary.content);
+            card.push_str("\n\n");
+        }
+
+        if entity.entity_type != "character" {
+            return card;
+        }
+
+        let character_key = entity.id.trim_start_matches("character:");
+        let entities = self.entities.read().await;
+        let anchor_event = latest_event_before(text, offset, &entities);
+
+        let states = match &anchor_event {
+            Some((event_key, _)) => {
+                self.ctx
+                    .knowledge_repo
+                    .get_knowledge_at_event(character_key, event_key)
+                    .await
+            }
+            None => {
+                self.ctx
+                    .knowledge_repo
+                    .get_character_knowledge_states(character_key)
+                    .await
+            }
+        };
+        let Ok(states) = states else {
+            return card;
+        };
+
+        match &anchor_event {
+            Some((_, title)) => card.push_str(&format!("*Knows as of {}:*\n", title)),
+            None => card.push_str("*Knows:*\n"),
+        }
+        if states.is_empty() {
+            card.push_str("- nothing recorded\n");
+        }
+        for state in states.iter().take(HOVER_KNOWLEDGE_LIMIT) {
+            let target = state.target.to_string();
+            let label = match target.strip_prefix("knowledge:") {
+                Some(key) => match self.ctx.knowledge_repo.get_knowledge(key).await {
+                    Ok(Some(k)) => k.fact,
+                    _ => target.clone(),
+                },
+                None => entities
+                    .iter()
+                    .find(|e| e.id == target)
+                    .map(|e| e.name.clone())
+                    .unwrap_or_else(|| target.clone()),
+            };
+            card.push_str(&format!("- {} ({:?})\n", label, state.certainty));
+        }
+        card
+    }
+
+    async fn create_entity(&self, entity_type: &str, name: &str) -> anyhow::Result<String> {
+        let id = match entity_type {
+            "character" => self
+                .ctx
+                .entity_repo
+                .create_character(CharacterCreate {
+                    name: name.to_string(),
+                    ..Default::default()
+                })
+                .await?
+                .id
+                .to_string(),
+            "location" => self
+                .ctx
+                .entity_repo
+                .create_location(LocationCreate {
+                    name: name.to_string(),
+                    description: None,
+                    loc_type: "general".to_string(),
+                    parent: None,
+                })
+                .await?
+                .id
+                .to_string(),
+            other => anyhow::bail!("Cannot create entity of type '{}'", other),
+        };
+        self.ctx
+            .staleness_manager
+            .spawn_regeneration(id.clone(), entity_type.to_string(), None);
+        Ok(id)
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for NarraLanguageServer {
+    async fn initialize(&self, _: InitializeParams) -> RpcResult<InitializeResult> {
+        if let Err(e) = self.reload_entities().await {
+            tracing::warn!("Failed to load entities: {}", e);
+        }
+
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::FULL),
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                        ..Default::default()
+                    },
+                )),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![CREATE_ENTITY_COMMAND.to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "narra".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn shutdown(&self) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents
+            .write()
+            .await
+            .insert(uri.clone(), params.text_document.text);
+        self.publish(uri, true).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // Full sync: the last change carries the whole buffer
+        let Some(change) = params.content_changes.into_iter().next_back() else {
+            return;
+        };
+        let uri = params.text_document.uri;
+        self.documents
+            .write()
+            .await
+            .insert(uri.clone(), change.text);
+        self.publish(uri, false).await;
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        self.publish(params.text_document.uri, true).await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.write().await.remove(&uri);
+        self.client.publish_diagnostics(uri, vec![], None).await;
+    }
+
+    async fn hover(&self, params: HoverParams) -> RpcResult<Option<Hover>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        let Some(text) = self.documents.read().await.get(&uri).cloned() else {
+            return Ok(None);
+        };
+
+        let offset = position_to_offset(&text, position);
+        let hit = {
+            let entities = self.entities.read().await;
+            find_mentions(&text, &entities)
+                .into_iter()
+                .find(|m| m.start <= offset && offset <= m.end)
+                .map(|m| (entities[m.entity_index].clone(), m))
+        };
+        let Some((entity, mention)) = hit else {
+            return Ok(None);
+        };
+
+        let card = self.hover_card(&entity, &text, mention.start).await;
+        Ok(Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: card,
+            }),
+            range: Some(span_to_range(&text, mention.start, mention.end)),
+        }))
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> RpcResult<Option<CodeActionResponse>> {
+        let actions: Vec<CodeActionOrCommand> = params
+            .context
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == Some(NumberOrString::String(UNKNOWN_ENTITY_CODE.into())))
+            .filter_map(|d| {
+                let data = d.data.as_ref()?;
+                let entity_type = data.get("entity_type")?.as_str()?;
+                let name = data.get("name")?.as_str()?;
+                let title = format!("Create {} '{}'", entity_type, name);
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: title.clone(),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![d.clone()]),
+                    command: Some(Command {
+                        title,
+                        command: CREATE_ENTITY_COMMAND.to_string(),
+                        arguments: Some(vec![
+                            Value::String(entity_type.to_string()),
+                            Value::String(name.to_string()),
+                        ]),
+                    }),
+                    ..Default::default()
+                }))
+            })
+            .collect();
+
+        Ok((!actions.is_empty()).then_some(actions))
+    }
+
+    async fn execute_command(&self, params: ExecuteCommandParams) -> RpcResult<Option<Value>> {
+        if params.command != CREATE_ENTITY_COMMAND {
+            return Err(RpcError::method_not_found());
+        }
+        let (Some(entity_type), Some(name)) = (
+            params.arguments.first().and_then(Value::as_str),
+            params.arguments.get(1).and_then(Value::as_str),
+        ) else {
+            return Err(RpcError::invalid_params(
+                "expected [entity_type, name] arguments",
+            ));
+        };
+
+        let id = self
+            .create_entity(entity_type, name)
+            .await
+            .map_err(|e| RpcError::invalid_params(e.to_string()))?;
+        self.client
+            .show_message(MessageType::INFO, format!("Created {}", id))
+            .await;
+
+        // New name is now known: refresh underlines in every open buffer
+        if let Err(e) = self.reload_entities().await {
+            tracing::warn!("Failed to reload entities: {}", e);
+        }
+        let uris: Vec<Url> = self.documents.read().await.keys().cloned().collect();
+        for uri in uris {
+            self.publish(uri, false).await;
+        }
+
+        Ok(Some(Value::String(id)))
+    }
+}
+
+/// Map an NER label to the entity type a quick fix would create.
+fn ner_label_to_type(label: &str) -> Option<&'static str> {
+    match label {
+        "PER" => Some("character"),
+        "LOC" => Some("location"),
+        _ => None,
+    }
+}
+
+fn overlaps_any(mentions: &[Mention], start: usize, end: usize) -> bool {
+    mentions.iter().any(|m| m.start < end && start < m.end)
+}
+
+/// Most recent event mentioned before `offset`, as (event key, title).
+///
+/// Used as the "as of" point for a character's knowledge in hover cards.
+fn latest_event_before(
+    text: &str,
+    offset: usize,
+    entities: &[KnownEntity],
+) -> Option<(String, String)> {
+    find_mentions(&text[..offset.min(text.len())], entities)
+        .into_iter()
+        .rev()
+        .map(|m| &entities[m.entity_index])
+        .find(|e| e.entity_type == "event")
+        .map(|e| {
+            (
+                e.id.trim_start_matches("event:").to_string(),
+                e.name.clone(),
+            )
+        })
+}
@@ -1,24 +1,42 @@
 //! Narra - Narrative intelligence engine for fiction writing
 //!
 //! Usage:
+//!   narra init                   Set up a project interactively
 //!   narra mcp                    Start MCP server on stdio
+//!   narra lsp                    Start language server on stdio
+//!   narra models list            Cached ML models and disk usage
 //!   narra find "query"           Search across all entities
 //!   narra get <name_or_id>       Get any entity by name or ID
 //!   narra list characters        List all characters
 //!   narra world status           World overview dashboard
 //!   narra --help                 Show all commands
 
-use clap::{CommandFactory, Parser};
+use clap::{CommandFactory, FromArgMatches};
 use colored::Colorize;
 
+use narra::cli::handlers::init::{handle_init, InitOptions};
+use narra::cli::handlers::{doctor, migrate, models, template, vault};
 use narra::cli::output::{DetailLevel, OutputMode};
-use narra::cli::{Cli, Commands};
+use narra::cli::{Cli, Commands, MigrateCommands, ModelsCommands, TemplateCommands, VaultCommands};
+use narra::config::NarraConfig;
 use narra::init::AppContext;
+use narra::lsp::run_lsp_server;
 use narra::mcp::server::run_mcp_server;
+use narra::services::composite::invalidate_on_mutation;
+use narra::services::mentions::sync_on_mutation;
+use narra::services::oplog::record_on_mutation;
+use narra::services::{EventSink, WebhookConfig, WebhookDispatcher};
 
 #[tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let config = NarraConfig::load().unwrap_or_else(|e| {
+        eprintln!("{} {}", "Error:".red().bold(), e);
+        std::process::exit(1);
+    });
+
+    let matches = config.apply_cli_defaults(Cli::command()).get_matches();
+    narra::cli::output::schema::set_command(narra::cli::command_path(&matches));
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Tracing to stderr (safe for MCP stdio transport)
     tracing_subscriber::fmt()
@@ -29,33 +47,183 @@ async fn main() {
         )
         .init();
 
-    if let Err(e) = run(cli).await {
+    if let Err(e) = run(cli, config).await {
+        if let Some(status) = e.downcast_ref::<narra::cli::ExitStatus>() {
+            std::process::exit(status.0);
+        }
         eprintln!("{} {}", "Error:".red().bold(), e);
         std::process::exit(1);
     }
 }
 
-async fn run(cli: Cli) -> anyhow::Result<()> {
-    let mode = OutputMode::from_flags(cli.json, cli.md);
-    let detail = DetailLevel::from_flags(cli.brief, cli.full);
-    let no_semantic = cli.no_semantic;
+async fn run(cli: Cli, config: NarraConfig) -> anyhow::Result<()> {
+    // Flags beat config; config beats built-in defaults
+    let mode = if cli.json || cli.md {
+        OutputMode::from_flags(cli.json, cli.md)
+    } else {
+        config
+            .output
+            .format
+            .as_deref()
+            .and_then(OutputMode::from_name)
+            .unwrap_or(OutputMode::Human)
+    };
+    let detail = if cli.brief || cli.full {
+        DetailLevel::from_flags(cli.brief, cli.full)
+    } else {
+        config
+            .output
+            .detail
+            .as_deref()
+            .and_then(DetailLevel::from_name)
+            .unwrap_or(DetailLevel::Standard)
+    };
+    narra::embedding::hub::set_offline(cli.offline);
+    let no_semantic = cli.no_semantic || config.output.semantic == Some(false);
+    let data_path = cli.data_path.clone().or_else(|| config.data_path.clone());
 
     // Commands that don't need AppContext
     if let Commands::Completions { shell } = &cli.command {
-        clap_complete::generate(*shell, &mut Cli::command(), "narra", &mut std::io::stdout());
+        narra::cli::print_completions(*shell);
         return Ok(());
     }
 
-    match &cli.command {
-        Commands::Mcp => {
-            let ctx = AppContext::new(cli.data_path.clone()).await?;
-            run_mcp_server(ctx).await?;
-        }
-        cmd => {
-            let ctx = AppContext::new(cli.data_path.clone()).await?;
-            narra::cli::execute(cmd, &ctx, mode, detail, no_semantic).await?;
-        }
+    // Init creates the data directory and config files AppContext reads
+    if let Commands::Init {
+        model,
+        language,
+        sample,
+        no_sample,
+        yes,
+    } = &cli.command
+    {
+        let options = InitOptions {
+            data_path,
+            model: model.clone(),
+            language: language.clone(),
+            sample: (*sample || *no_sample).then_some(*sample),
+            yes: *yes,
+        };
+        return handle_init(options, &config, mode).await;
+    }
+
+    // Model management must not load every model the way AppContext does
+    if let Commands::Models(cmd) = &cli.command {
+        let data_path = narra::init::resolve_data_path(data_path);
+        return match cmd {
+            ModelsCommands::Pull { all } => {
+                models::handle_models_pull(&data_path, &config, *all, mode)
+            }
+            ModelsCommands::List => models::handle_models_list(&data_path, &config, mode),
+            ModelsCommands::Remove {
+                names,
+                unused,
+                force,
+            } => models::handle_models_remove(&data_path, &config, names, *unused, *force, mode),
+            ModelsCommands::Bench {
+                device,
+                texts,
+                batch,
+                threads,
+            } => models::handle_models_bench(
+                &data_path,
+                &config,
+                device,
+                models::BenchOptions {
+                    texts: *texts,
+                    batch: *batch,
+                    threads: *threads,
+                },
+                mode,
+            ),
+        };
+    }
+
+    // Doctor times opening the world, so it builds the AppContext itself
+    if let Commands::Doctor = &cli.command {
+        return doctor::handle_doctor(data_path, config, mode).await;
+    }
+
+    // Migrations are reported before opening the world, which would apply them
+    if let Commands::Migrate(cmd) = &cli.command {
+        return match cmd {
+            MigrateCommands::Status => migrate::handle_migrate_status(data_path, mode).await,
+            MigrateCommands::Up { dry_run } => {
+                migrate::handle_migrate_up(data_path, *dry_run, mode).await
+            }
+        };
+    }
+
+    // Vault commands convert the database itself, so they can't hold it open
+    if let Commands::Vault(cmd) = &cli.command {
+        let data_path = narra::init::resolve_data_path(data_path);
+        return match cmd {
+            VaultCommands::Status => vault::handle_vault_status(&data_path, mode),
+            VaultCommands::Encrypt { keyring } => {
+                vault::handle_vault_encrypt(&data_path, *keyring, mode).await
+            }
+            VaultCommands::Decrypt => vault::handle_vault_decrypt(&data_path, mode).await,
+            VaultCommands::Keyring { forget } => {
+                vault::handle_vault_keyring(&data_path, *forget, mode)
+            }
+        };
+    }
+
+    // Templates are plain files; no database needed
+    if let Commands::Template(cmd) = &cli.command {
+        let data_path = narra::init::resolve_data_path(data_path);
+        return match cmd {
+            TemplateCommands::List => template::handle_template_list(&data_path, mode),
+            TemplateCommands::Edit { name, user } => {
+                template::handle_template_edit(&data_path, name, *user, mode)
+            }
+        };
+    }
+
+    // Completion runs on every <TAB>: open the database only, skip model loading
+    if let Commands::Complete { kind, prefix } = &cli.command {
+        let db = narra::init::connect_db(data_path).await?;
+        return narra::cli::handlers::complete::handle_complete(&db, kind, prefix).await;
+    }
+
+    let ctx = AppContext::with_config(data_path, config).await?;
+    if let Some(target) = &cli.events {
+        ctx.event_bus.add_sink(EventSink::open(target)?);
+    }
+    let webhooks = WebhookDispatcher::start(&ctx.event_bus, WebhookConfig::load(&ctx.data_path));
+    let plugin_hooks = ctx.plugins.listen(&ctx.event_bus);
+    let autoseal = ctx
+        .vault
+        .as_ref()
+        .map(|vault| vault.listen(&ctx.event_bus, ctx.db.clone()));
+    let composite_cache = invalidate_on_mutation(&ctx.event_bus, ctx.db.clone());
+    let oplog = record_on_mutation(&ctx.event_bus, ctx.db.clone());
+    let mentions = sync_on_mutation(&ctx.event_bus, ctx.db.clone(), ctx.ner_service.clone());
+
+    let result = match &cli.command {
+        Commands::Mcp => run_mcp_server(ctx).await,
+        Commands::Lsp => run_lsp_server(ctx).await,
+        cmd => narra::cli::execute(cmd, &ctx, mode, detail, no_semantic).await,
+    };
+
+    // Deliver webhooks and run plugin hooks for mutations made before exit
+    if let Some(webhooks) = webhooks {
+        webhooks.finish().await;
+    }
+    if let Some(hooks) = plugin_hooks {
+        hooks.finish().await;
+    }
+    if let Some(autoseal) = autoseal {
+        autoseal.finish().await;
+    }
+    composite_cache.finish().await;
+    oplog.finish().await;
+    mentions.finish().await;
+
+    // Encrypted worlds live in memory; write them back before exiting
+    if let Some(vault) = &ctx.vault {
+        vault.seal(&ctx.db).await?;
     }
 
-    Ok(())
+    result
 }
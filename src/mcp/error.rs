@@ -41,6 +41,14 @@ impl From<String> for ToolError {
                 field: None,
                 example: None,
             }
+        } else if msg.starts_with("Permission denied") {
+            ToolError {
+                error_code: "PERMISSION_DENIED".into(),
+                message: msg,
+                suggestion: "Your access role doesn't allow this change. Ask a world owner for an editor grant.".into(),
+                field: None,
+                example: None,
+            }
         } else if msg.starts_with("CRITICAL") {
             ToolError {
                 error_code: "CONSTRAINT_VIOLATION".into(),
@@ -118,6 +126,13 @@ impl From<NarraError> for ErrorData {
                     "suggestion": "Check for duplicate names or concurrent modifications"
                 })),
             },
+            NarraError::PermissionDenied(msg) => ErrorData {
+                code: ErrorCode::INVALID_REQUEST,
+                message: Cow::Owned(format!("Permission denied: {}", msg)),
+                data: Some(serde_json::json!({
+                    "suggestion": "Ask a world owner to grant an editor role"
+                })),
+            },
             NarraError::Transaction(msg) => ErrorData {
                 code: ErrorCode::INTERNAL_ERROR,
                 message: Cow::Owned(format!("Transaction error: {}", msg)),
@@ -177,6 +192,12 @@ mod tests {
         assert_eq!(err.error_code, "VALIDATION_ERROR");
     }
 
+    #[test]
+    fn test_permission_denied_classification() {
+        let err = ToolError::from("Permission denied: bob is a reader".to_string());
+        assert_eq!(err.error_code, "PERMISSION_DENIED");
+    }
+
     #[test]
     fn test_batch_failed_classification() {
         let err = ToolError::from("All 3 knowledge entries failed: err1; err2".to_string());
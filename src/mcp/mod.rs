@@ -2,7 +2,9 @@ pub mod error;
 pub mod progress;
 pub mod prompts;
 pub mod resources;
+pub mod sampling;
 pub mod server;
+pub mod subscriptions;
 pub mod tools;
 pub mod types;
 
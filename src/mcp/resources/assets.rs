@@ -0,0 +1,76 @@
+//! Asset MCP resources.
+//!
+//! `narra://assets` lists images attached to entities; `narra://asset/{id}`
+//! returns one image base64-encoded for multimodal clients, or its metadata
+//! and a `file://` URI when it is too large to inline.
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::Serialize;
+
+use crate::models::Asset;
+use crate::services::AssetStore;
+
+/// Largest image returned inline as a blob.
+pub const MAX_INLINE_ASSET_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Serialize)]
+struct AssetEntry {
+    id: String,
+    uri: String,
+    entity: Option<String>,
+    original_name: String,
+    mime_type: String,
+    size_bytes: u64,
+    caption: Option<String>,
+    /// Direct file access; absent for encrypted worlds, whose files are ciphertext
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_uri: Option<String>,
+}
+
+/// What `narra://asset/{id}` returns.
+pub enum AssetContent {
+    /// Base64-encoded image bytes
+    Blob { mime_type: String, blob: String },
+    /// JSON metadata for images too large to inline
+    Metadata(String),
+}
+
+fn entry(store: &AssetStore, asset: &Asset) -> AssetEntry {
+    AssetEntry {
+        id: asset.id.to_string(),
+        uri: format!("narra://asset/{}", asset.id.key()),
+        entity: asset.entity.as_ref().map(|e| e.to_string()),
+        original_name: asset.original_name.clone(),
+        mime_type: asset.mime_type.clone(),
+        size_bytes: asset.size_bytes,
+        caption: asset.caption.clone(),
+        file_uri: (!asset.encrypted).then(|| format!("file://{}", store.path(asset).display())),
+    }
+}
+
+/// All assets as a JSON string for the `narra://assets` resource.
+pub async fn get_assets_resource(store: &AssetStore) -> Result<String, String> {
+    let assets = store
+        .list(None)
+        .await
+        .map_err(|e| format!("Failed to list assets: {}", e))?;
+    let entries: Vec<AssetEntry> = assets.iter().map(|a| entry(store, a)).collect();
+    serde_json::to_string_pretty(&entries).map_err(|e| format!("Failed to serialize assets: {}", e))
+}
+
+/// One asset for the `narra://asset/{id}` resource.
+pub async fn get_asset_resource(store: &AssetStore, id: &str) -> Result<AssetContent, String> {
+    let asset = store.get(id).await.map_err(|e| e.to_string())?;
+    if asset.size_bytes > MAX_INLINE_ASSET_BYTES {
+        let json = serde_json::to_string_pretty(&entry(store, &asset))
+            .map_err(|e| format!("Failed to serialize asset: {}", e))?;
+        return Ok(AssetContent::Metadata(json));
+    }
+    let bytes = store
+        .read(&asset)
+        .map_err(|e| format!("Failed to read asset {}: {}", asset.id, e))?;
+    Ok(AssetContent::Blob {
+        mime_type: asset.mime_type,
+        blob: general_purpose::STANDARD.encode(bytes),
+    })
+}
@@ -3,16 +3,20 @@
 //! Resources expose static/cached reference data that Claude can access
 //! without tool calls. Resources are for reading context, tools are for actions.
 
+mod assets;
 mod consistency;
 mod entity;
 mod operations_guide;
 pub mod schema;
 mod session;
+mod style;
 mod world_overview;
 
+pub use assets::{get_asset_resource, get_assets_resource, AssetContent};
 pub use consistency::get_consistency_issues_resource;
 pub use entity::get_entity_resource;
 pub use operations_guide::get_operations_guide;
 pub use schema::{get_import_schema, get_import_template};
-pub use session::get_session_context_resource;
+pub use session::{get_session_context_resource, get_session_stats_resource};
+pub use style::get_style_sheet_resource;
 pub use world_overview::get_world_overview_resource;
@@ -31,21 +31,26 @@ pub fn get_operations_guide() -> String {
 - Tension between characters → `query(tension_matrix)`
 
 ### "I want to create something..."
-- Character → `create_character`
+- Character → `create_character` (refused when an existing character's full name nearly matches; update that one, or pass `allow_similar: true`)
 - Relationship → `create_relationship`
 - Location → `mutate(create_location)`
 - Event → `mutate(create_event)`
 - Scene → `mutate(create_scene)`
 - Knowledge entry → `record_knowledge`
 - Fact/rule → `mutate(create_fact)`
-- Note → `mutate(create_note)`
+- Note → `mutate(create_note)` (kind: research, todo, critique)
+- Foreshadowing link → `mutate(create_foreshadow)` / `mutate(remove_foreshadow)`
 - Many at once → `mutate(batch_create_*)`
 - Import YAML → `mutate(import_yaml)`
 
 ### "I want to modify something..."
 - Update fields → `update_entity`
 - Delete entity → `mutate(delete)` (run `query(analyze_impact)` first)
+- Touch something a final scene references → pass `force: true` to `update_entity` or `mutate(update|delete|add_event_participant|remove_event_participant|tag_entity|untag_entity)`
 - Protect entity → `mutate(protect_entity)`
+- Resolve a todo or critique → `mutate(set_note_status)`
+- How far one character trusts another → `mutate(set_trust)`
+- Who took part in an event → `mutate(add_event_participant)` / `mutate(remove_event_participant)`
 
 ### "I want to check consistency..."
 - Single entity → `validate_entity`
@@ -95,15 +100,16 @@ pub fn get_operations_guide() -> String {
 - Generate graph → `generate_graph`
 - Session context → `session(get_context)`
 - Pin/unpin → `session(pin_entity)` / `session(unpin_entity)`
+- Journal what happened this session → `session(log)`
 
 ## Tool Count Summary
 - Essential dedicated tools: 5
 - Standard dedicated tools: 8
 - Parameterized query operations: 40
-- Parameterized mutate operations: 25
-- Session operations: 3
+- Parameterized mutate operations: 27
+- Session operations: 4
 - Utility tools: 2 (export_world, generate_graph)
-- Total: 18 tools covering 83 operations
+- Total: 18 tools covering 86 operations
 "#
     .to_string()
 }
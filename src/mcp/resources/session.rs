@@ -1,12 +1,14 @@
-//! Session context MCP resource.
+//! Session context and stats MCP resources.
 //!
 //! Exposes session state (hot entities, recent activity, pending decisions)
-//! as a static resource for Claude to reference without tool calls.
+//! and attention stats as static resources for Claude to reference without
+//! tool calls.
 
 use crate::db::connection::NarraDb;
 use std::sync::Arc;
 
-use crate::session::{generate_startup_context, SessionStateManager};
+use crate::services::TokenCounter;
+use crate::session::{generate_startup_context, session_stats, SessionStateManager};
 
 /// Get session context as JSON string for MCP resource.
 ///
@@ -14,11 +16,28 @@ use crate::session::{generate_startup_context, SessionStateManager};
 pub async fn get_session_context_resource(
     session_manager: &Arc<SessionStateManager>,
     db: &Arc<NarraDb>,
+    token_counter: &dyn TokenCounter,
 ) -> Result<String, String> {
-    let startup_info = generate_startup_context(session_manager, db)
+    let startup_info = generate_startup_context(session_manager, db, token_counter)
         .await
         .map_err(|e| format!("Failed to generate session context: {}", e))?;
 
     serde_json::to_string_pretty(&startup_info)
         .map_err(|e| format!("Failed to serialize session context: {}", e))
 }
+
+/// Entities per list in the stats resource.
+const STATS_LIMIT: usize = 10;
+
+/// Get session stats (focus, neglected, untouched) as JSON string for MCP resource.
+pub async fn get_session_stats_resource(
+    session_manager: &Arc<SessionStateManager>,
+    db: &Arc<NarraDb>,
+) -> Result<String, String> {
+    let stats = session_stats(session_manager, db, STATS_LIMIT)
+        .await
+        .map_err(|e| format!("Failed to compute session stats: {}", e))?;
+
+    serde_json::to_string_pretty(&stats)
+        .map_err(|e| format!("Failed to serialize session stats: {}", e))
+}
@@ -0,0 +1,35 @@
+//! Style sheet MCP resource.
+//!
+//! `narra://style/sheet` lists the project's spelling, capitalization and
+//! punctuation rules so a client drafting prose can follow them.
+
+use serde::Serialize;
+
+use crate::db::connection::NarraDb;
+use crate::models::style::{list_style_rules, StyleRule};
+
+#[derive(Serialize)]
+struct StyleSheet {
+    spelling: Vec<StyleRule>,
+    capitalization: Vec<StyleRule>,
+    punctuation: Vec<StyleRule>,
+}
+
+/// The style sheet as a JSON string, grouped by rule kind.
+pub async fn get_style_sheet_resource(db: &NarraDb) -> Result<String, String> {
+    use crate::models::style::StyleRuleKind;
+
+    let rules = list_style_rules(db)
+        .await
+        .map_err(|e| format!("Failed to load style sheet: {}", e))?;
+    let of = |kind: StyleRuleKind| -> Vec<StyleRule> {
+        rules.iter().filter(|r| r.kind == kind).cloned().collect()
+    };
+    let sheet = StyleSheet {
+        spelling: of(StyleRuleKind::Spelling),
+        capitalization: of(StyleRuleKind::Capitalization),
+        punctuation: of(StyleRuleKind::Punctuation),
+    };
+    serde_json::to_string_pretty(&sheet)
+        .map_err(|e| format!("Failed to serialize style sheet: {}", e))
+}
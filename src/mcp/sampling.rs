@@ -0,0 +1,190 @@
+//! Report compression through MCP sampling.
+//!
+//! When the client advertises the `sampling` capability, composite reports
+//! (dossier, situation report, scene planning) can ask the client's own model
+//! to compress sections that don't fit the token budget instead of showing
+//! fewer items. Sections are compressed one at a time so the report keeps its
+//! headings; a section the client fails to compress is left as it was and the
+//! usual truncation applies.
+
+use async_trait::async_trait;
+use rmcp::model::{Content, ContextInclusion, CreateMessageRequestParams, Role, SamplingMessage};
+use rmcp::{Peer, RoleServer};
+
+use crate::services::token_counter::TokenCounter;
+
+const SYSTEM_PROMPT: &str = "You compress sections of a story-world analysis report. \
+Keep every character and entity name, ID, number, and fact a writer would act on; \
+drop repetition and filler. Reply with the compressed section only, as markdown.";
+
+/// Compresses text to a token target.
+#[async_trait]
+pub trait SectionCompressor: Send + Sync {
+    async fn compress(&self, text: &str, max_tokens: usize) -> Result<String, String>;
+}
+
+/// Compresses text with the connected client's model.
+#[derive(Clone)]
+pub struct ClientSampler {
+    client: Peer<RoleServer>,
+}
+
+impl ClientSampler {
+    /// A sampler for `client`, or None when the client can't sample.
+    pub fn for_client(client: &Peer<RoleServer>) -> Option<Self> {
+        client.peer_info()?.capabilities.sampling.as_ref()?;
+        Some(Self {
+            client: client.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl SectionCompressor for ClientSampler {
+    async fn compress(&self, text: &str, max_tokens: usize) -> Result<String, String> {
+        let result = self
+            .client
+            .create_message(CreateMessageRequestParams {
+                meta: None,
+                task: None,
+                messages: vec![SamplingMessage {
+                    role: Role::User,
+                    content: Content::text(format!(
+                        "Compress this section to at most {} tokens:\n\n{}",
+                        max_tokens, text
+                    )),
+                }],
+                model_preferences: None,
+                system_prompt: Some(SYSTEM_PROMPT.to_string()),
+                include_context: Some(ContextInclusion::None),
+                temperature: Some(0.2),
+                max_tokens: max_tokens as u32,
+                stop_sequences: None,
+                metadata: None,
+            })
+            .await
+            .map_err(|e| format!("Sampling failed: {}", e))?;
+        result
+            .message
+            .content
+            .as_text()
+            .map(|t| t.text.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| "Sampling returned no text".to_string())
+    }
+}
+
+/// Split a markdown report into its preamble and `## ` sections.
+fn split_sections(content: &str) -> Vec<String> {
+    let mut sections: Vec<String> = vec![String::new()];
+    for line in content.lines() {
+        if line.trim_start().starts_with("## ") {
+            sections.push(String::new());
+        }
+        let current = sections.last_mut().expect("at least one section");
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    sections.retain(|s| !s.trim().is_empty());
+    sections
+}
+
+/// Compress `content` section by section to fit `budget` tokens.
+///
+/// Each section gets a share of the budget proportional to its size; the
+/// ones over their share are compressed (heading kept verbatim). Returns the
+/// new content and how many sections were compressed.
+pub async fn compress_report(
+    compressor: &dyn SectionCompressor,
+    counter: &dyn TokenCounter,
+    content: &str,
+    budget: usize,
+) -> (String, usize) {
+    let total = counter.count(content);
+    if total <= budget {
+        return (content.to_string(), 0);
+    }
+
+    let mut compressed = 0;
+    let mut parts = Vec::new();
+    for section in split_sections(content) {
+        let tokens = counter.count(&section);
+        let share = (budget * tokens / total.max(1)).max(1);
+        if tokens <= share {
+            parts.push(section);
+            continue;
+        }
+        let (heading, body) = match section.split_once('\n') {
+            Some((heading, body)) if heading.trim_start().starts_with("## ") => {
+                (Some(heading), body)
+            }
+            _ => (None, section.as_str()),
+        };
+        let target = share.saturating_sub(heading.map_or(0, |h| counter.count(h)));
+        match compressor.compress(body, target.max(1)).await {
+            Ok(body) => {
+                compressed += 1;
+                parts.push(match heading {
+                    Some(heading) => format!("{}\n{}", heading, body),
+                    None => body,
+                });
+            }
+            Err(e) => {
+                tracing::debug!("Keeping section uncompressed: {}", e);
+                parts.push(section);
+            }
+        }
+    }
+    (parts.join("\n\n"), compressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::token_counter::HeuristicTokenCounter;
+
+    /// Keeps the first `max_tokens * 4` characters, or fails when asked to.
+    struct Clip {
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl SectionCompressor for Clip {
+        async fn compress(&self, text: &str, max_tokens: usize) -> Result<String, String> {
+            if self.fail {
+                return Err("no".to_string());
+            }
+            Ok(text.chars().take(max_tokens * 4).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compress_report_keeps_headings() {
+        let report = format!(
+            "# Dossier: Alice\nRoles: spy\n\n## Relationships (40)\n{}\n## Suggestions\n- Write the duel",
+            "- Bob (ally)\n".repeat(40)
+        );
+        let counter = HeuristicTokenCounter;
+
+        let (content, compressed) =
+            compress_report(&Clip { fail: false }, &counter, &report, 60).await;
+        assert_eq!(compressed, 1);
+        assert!(content.starts_with("# Dossier: Alice"));
+        assert!(content.contains("## Relationships (40)"));
+        assert!(content.contains("- Write the duel"));
+        assert!(counter.count(&content) < counter.count(&report));
+
+        // Within budget: untouched
+        let (content, compressed) =
+            compress_report(&Clip { fail: false }, &counter, &report, 10_000).await;
+        assert_eq!((content.as_str(), compressed), (report.as_str(), 0));
+
+        // A failing client leaves sections as they were
+        let (content, compressed) =
+            compress_report(&Clip { fail: true }, &counter, &report, 60).await;
+        assert_eq!(compressed, 0);
+        assert!(content.contains(&"- Bob (ally)\n".repeat(40)[..100]));
+    }
+}
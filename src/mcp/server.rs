@@ -1,5 +1,7 @@
 use crate::db::connection::NarraDb;
 use crate::mcp::progress::make_mcp_progress;
+use crate::mcp::sampling::ClientSampler;
+use crate::mcp::subscriptions::{self, ResourceSubscriptions};
 use rmcp::{
     handler::server::tool::ToolRouter,
     handler::server::wrapper::{Json, Parameters},
@@ -11,6 +13,7 @@ use rmcp::{
 use std::sync::Arc;
 use tracing::instrument;
 
+use crate::embedding::lazy::Lazy;
 use crate::embedding::{EmbeddingService, StalenessManager};
 use crate::mcp::prompts::{
     get_character_voice_prompt, get_conflict_detection_prompt, get_consistency_check_prompt,
@@ -18,15 +21,21 @@ use crate::mcp::prompts::{
     get_scene_planning_prompt,
 };
 use crate::mcp::resources::{
-    get_consistency_issues_resource, get_entity_resource, get_import_schema, get_import_template,
-    get_operations_guide, get_session_context_resource, get_world_overview_resource,
+    get_asset_resource, get_assets_resource, get_consistency_issues_resource, get_entity_resource,
+    get_import_schema, get_import_template, get_operations_guide, get_session_context_resource,
+    get_session_stats_resource, get_style_sheet_resource, get_world_overview_resource,
+    AssetContent,
 };
 use crate::repository::{
     SurrealEntityRepository, SurrealKnowledgeRepository, SurrealRelationshipRepository,
 };
+use crate::services::access::AccessPolicy;
+use crate::services::ontology::RelationshipOntology;
+use crate::services::transmission::TransmissionRules;
 use crate::services::EmotionService;
 use crate::services::NerService;
 use crate::services::ThemeService;
+use crate::services::{AssetStore, BackupService, EventBus, HeuristicTokenCounter, TokenCounter};
 use crate::services::{
     CachedContextService, CachedSummaryService, ConsistencyChecker, ConsistencyService,
     ContextService, ImpactAnalyzer, ImpactService, SearchService, SummaryService,
@@ -66,6 +75,20 @@ pub struct NarraServer {
     pub(crate) emotion_service: Arc<dyn EmotionService + Send + Sync>,
     pub(crate) theme_service: Arc<dyn ThemeService + Send + Sync>,
     pub(crate) ner_service: Arc<dyn NerService + Send + Sync>,
+    pub(crate) token_counter: Arc<dyn TokenCounter>,
+    pub(crate) event_bus: Arc<EventBus>,
+    /// Automatic backups before destructive mutations (none without a data path)
+    pub(crate) backup_service: Option<Arc<BackupService>>,
+    /// Images served as `narra://asset/{id}` (none without a data path)
+    pub(crate) asset_store: Option<Arc<AssetStore>>,
+    /// Canonical relationship types enforced on create
+    pub(crate) ontology: Arc<RelationshipOntology>,
+    /// How certainty carries over when knowledge is passed on
+    pub(crate) transmission_rules: Arc<TransmissionRules>,
+    /// The connected user's role in a shared world
+    pub(crate) access: Arc<AccessPolicy>,
+    /// Resources the client asked to be notified about
+    pub(crate) subscriptions: Arc<ResourceSubscriptions>,
     tool_router: ToolRouter<Self>,
 }
 
@@ -104,34 +127,50 @@ impl NarraServer {
         let staleness_manager =
             Arc::new(StalenessManager::new(db.clone(), embedding_service.clone()));
 
-        // Emotion classifier — degrades gracefully if model unavailable
+        // Annotation models load on first use and degrade gracefully if unavailable
         let emotion_service: Arc<dyn EmotionService + Send + Sync> = {
-            let service = crate::services::LocalEmotionService::new(db.clone());
-            if service.is_available() {
-                Arc::new(service)
-            } else {
-                Arc::new(crate::services::NoopEmotionService::new())
-            }
+            let db = db.clone();
+            Arc::new(Lazy::<dyn EmotionService + Send + Sync>::new(
+                "emotion classifier",
+                move || {
+                    let service = crate::services::LocalEmotionService::new(db.clone());
+                    if service.is_available() {
+                        Arc::new(service)
+                    } else {
+                        Arc::new(crate::services::NoopEmotionService::new())
+                    }
+                },
+            ))
         };
 
-        // Theme classifier — degrades gracefully if model unavailable
         let theme_service: Arc<dyn ThemeService + Send + Sync> = {
-            let service = crate::services::LocalThemeService::new(db.clone());
-            if service.is_available() {
-                Arc::new(service)
-            } else {
-                Arc::new(crate::services::NoopThemeService::new())
-            }
+            let db = db.clone();
+            Arc::new(Lazy::<dyn ThemeService + Send + Sync>::new(
+                "theme classifier",
+                move || {
+                    let service = crate::services::LocalThemeService::new(db.clone());
+                    if service.is_available() {
+                        Arc::new(service)
+                    } else {
+                        Arc::new(crate::services::NoopThemeService::new())
+                    }
+                },
+            ))
         };
 
-        // NER classifier — degrades gracefully if model unavailable
         let ner_service: Arc<dyn NerService + Send + Sync> = {
-            let service = crate::services::LocalNerService::new(db.clone());
-            if service.is_available() {
-                Arc::new(service)
-            } else {
-                Arc::new(crate::services::NoopNerService::new())
-            }
+            let db = db.clone();
+            Arc::new(Lazy::<dyn NerService + Send + Sync>::new(
+                "NER classifier",
+                move || {
+                    let service = crate::services::LocalNerService::new(db.clone());
+                    if service.is_available() {
+                        Arc::new(service)
+                    } else {
+                        Arc::new(crate::services::NoopNerService::new())
+                    }
+                },
+            ))
         };
 
         Self {
@@ -150,10 +189,65 @@ impl NarraServer {
             emotion_service,
             theme_service,
             ner_service,
+            token_counter: Arc::new(HeuristicTokenCounter),
+            event_bus: Arc::new(EventBus::new()),
+            backup_service: None,
+            asset_store: None,
+            ontology: Arc::new(RelationshipOntology::builtin()),
+            transmission_rules: Arc::new(TransmissionRules::default()),
+            access: Arc::new(AccessPolicy::unrestricted()),
+            subscriptions: Arc::new(ResourceSubscriptions::default()),
             tool_router: Self::tool_router(),
         }
     }
 
+    /// Replace the token counter used for budget enforcement.
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.token_counter = counter;
+        self
+    }
+
+    /// Replace the event bus mutations are reported on.
+    pub fn with_event_bus(mut self, bus: Arc<EventBus>) -> Self {
+        self.event_bus = bus;
+        self
+    }
+
+    /// Take automatic backups before destructive mutations.
+    pub fn with_backups(mut self, backups: Arc<BackupService>) -> Self {
+        self.backup_service = Some(backups);
+        self
+    }
+
+    /// Serve images from an asset store as resources.
+    pub fn with_assets(mut self, assets: Arc<AssetStore>) -> Self {
+        self.asset_store = Some(assets);
+        self
+    }
+
+    /// Replace the relationship type ontology.
+    pub fn with_ontology(mut self, ontology: Arc<RelationshipOntology>) -> Self {
+        self.ontology = ontology;
+        self
+    }
+
+    /// Replace the rules for certainty derived when knowledge is passed on.
+    pub fn with_transmission_rules(mut self, rules: Arc<TransmissionRules>) -> Self {
+        self.transmission_rules = rules;
+        self
+    }
+
+    /// Automatic backup before a destructive mutation; a no-op without backups.
+    pub(crate) async fn backup_before(&self, operation: &str) -> Result<(), String> {
+        if let Some(backups) = &self.backup_service {
+            backups
+                .before(operation)
+                .await
+                .map_err(|e| format!("Backup before {} failed: {}", operation, e))?;
+        }
+        Ok(())
+    }
+
     /// Replace annotation services (emotion, theme, NER) for testing.
     /// Follows the established `::with_provider()` pattern used by other services.
     pub fn with_annotation_services(
@@ -189,7 +283,7 @@ impl NarraServer {
             .await;
 
         let result = self
-            .handle_query(request)
+            .handle_query_with_sampler(request, ClientSampler::for_client(&client))
             .await
             .map(Json)
             .map_err(ToolError::from);
@@ -201,7 +295,7 @@ impl NarraServer {
     }
 
     #[tool(
-        description = "Advanced write operations (25): batch creation, YAML import, embeddings, arc baselines, protect/unprotect, and more. For common writes, prefer: record_knowledge, create_character, create_relationship, update_entity."
+        description = "Advanced write operations (27): batch creation, YAML import, embeddings, arc baselines, protect/unprotect, and more. For common writes, prefer: record_knowledge, create_character, create_relationship, update_entity."
     )]
     #[instrument(name = "mcp.mutate", skip_all)]
     pub async fn mutate(
@@ -228,7 +322,7 @@ impl NarraServer {
     }
 
     #[tool(
-        description = "Session management: get context summary (hot entities, pinned items, recent work), pin/unpin entities to working context."
+        description = "Session management: get context summary (hot entities, pinned items, recent work, journal), pin/unpin entities to working context, log what happened this session."
     )]
     #[instrument(name = "mcp.session", skip_all)]
     pub async fn session(
@@ -354,7 +448,7 @@ impl NarraServer {
     }
 
     #[tool(
-        description = "Record what a character knows or believes about something. Supports certainty levels: knows, suspects, believes_wrongly, uncertain."
+        description = "Record what a character knows or believes about something. Supports certainty levels: knows, suspects, believes_wrongly, uncertain, or derive to carry over the source character's certainty weighed by trust."
     )]
     #[instrument(name = "mcp.record_knowledge", skip_all)]
     pub async fn record_knowledge(
@@ -362,6 +456,9 @@ impl NarraServer {
         request: Parameters<RecordKnowledgeInput>,
     ) -> Result<Json<MutationResponse>, ToolError> {
         let Parameters(input) = request;
+        self.access
+            .check("knows")
+            .map_err(|e| ToolError::from(e.to_string()))?;
         self.handle_record_knowledge(
             input.character_id,
             input.target_id,
@@ -429,6 +526,9 @@ impl NarraServer {
         request: Parameters<CreateCharacterInput>,
     ) -> Result<Json<MutationResponse>, ToolError> {
         let Parameters(input) = request;
+        self.access
+            .check("character")
+            .map_err(|e| ToolError::from(e.to_string()))?;
         self.handle_create_character(
             input.id,
             input.name,
@@ -436,6 +536,7 @@ impl NarraServer {
             input.aliases,
             input.description,
             input.profile,
+            input.allow_similar.unwrap_or(false),
         )
         .await
         .map(Json)
@@ -451,12 +552,16 @@ impl NarraServer {
         request: Parameters<CreateRelationshipInput>,
     ) -> Result<Json<MutationResponse>, ToolError> {
         let Parameters(input) = request;
+        self.access
+            .check("relates_to")
+            .map_err(|e| ToolError::from(e.to_string()))?;
         self.handle_create_relationship(
             input.from_character_id,
             input.to_character_id,
             input.rel_type,
             input.subtype,
             input.label,
+            input.with_perceptions,
         )
         .await
         .map(Json)
@@ -475,6 +580,7 @@ impl NarraServer {
         self.handle_dramatic_irony_report(
             input.character_id,
             input.min_scene_threshold.unwrap_or(3),
+            input.phase,
         )
         .await
         .map(Json)
@@ -490,10 +596,15 @@ impl NarraServer {
         request: Parameters<UpdateEntityInput>,
     ) -> Result<Json<MutationResponse>, ToolError> {
         let Parameters(input) = request;
-        self.handle_update(&input.entity_id, input.fields)
+        self.access
+            .check_entity(&input.entity_id)
+            .map_err(|e| ToolError::from(e.to_string()))?;
+        let response = self
+            .handle_update(&input.entity_id, input.fields, input.force.unwrap_or(false))
             .await
-            .map(Json)
-            .map_err(ToolError::from)
+            .map_err(ToolError::from)?;
+        self.emit_mutation_event("update", &response);
+        Ok(Json(response))
     }
 
     #[tool(
@@ -535,6 +646,7 @@ impl ServerHandler for NarraServer {
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
                 .enable_resources()
+                .enable_resources_subscribe()
                 .enable_prompts()
                 .build(),
             server_info: Implementation {
@@ -565,26 +677,33 @@ impl ServerHandler for NarraServer {
 - knowledge_asymmetries — What A knows that B doesn't, and vice versa
 - validate_entity — Check consistency (fact violations, timeline, relationships)
 
-## Advanced Tools (parameterized, 70 operations)
+## Advanced Tools (parameterized, 71 operations)
 - query(operation) — 40 read ops: graph traversal, arc history/comparison/drift, perception gap/matrix/shift, centrality, influence, clustering, ...
-- mutate(operation) — 25 write ops: batch create, import YAML, backfill embeddings, baseline arcs, protect entity, ...
-- session(operation) — get_context, pin_entity, unpin_entity
+- mutate(operation) — 27 write ops: batch create, import YAML, backfill embeddings, baseline arcs, protect entity, ...
+- session(operation) — get_context, pin_entity, unpin_entity, log
 - export_world — Export to YAML
 - generate_graph — Mermaid diagram
 
 ## Resources
-- narra://session/context — Hot entities, pinned items
+- narra://session/context — Hot entities, pinned items, recent journal
+- narra://session/stats — What the author has focused on vs neglected
 - narra://world/overview — Current world state summary
 - narra://entity/{type}:{id} — Full entity view
 - narra://character/{id}/dossier — Character analysis
 - narra://consistency/issues — Current violations
 - narra://operations/guide — Categorized operation list with decision trees
 - narra://schema/import-template — YAML import template
+- narra://style/sheet — Spelling, capitalization and punctuation rules for prose
+- narra://assets — Images attached to entities (portraits, maps)
+- narra://asset/{id} — One image, base64-encoded
+- Subscribe to session/context, consistency/issues, entity/{id} or character/{id}/dossier to be notified when they change
 
 ## Key Patterns
 - Start sessions: session(get_context), then overview
 - Before deleting: query(analyze_impact) first
 - Consistency: CRITICAL violations block mutations
+- Revision lock: updating or deleting anything a scene marked final references needs force=true
+- Duplicates: creating a character whose full name nearly matches an existing one is refused (lesser resemblances come back as hints); update the existing one, or pass allow_similar=true
 - After data entry: mutate(backfill_embeddings) once
 - IDs: lowercase table:slug format (character:alice)
 "#.to_string()),
@@ -614,6 +733,22 @@ impl ServerHandler for NarraServer {
                         },
                         None,
                     ),
+                    Annotated::new(
+                        RawResource {
+                            uri: "narra://session/stats".to_string(),
+                            name: "Session Stats".to_string(),
+                            title: None,
+                            description: Some(
+                                "Entities the author has focused on lately, ones that cooled off, and ones never touched"
+                                    .to_string()
+                            ),
+                            mime_type: Some("application/json".to_string()),
+                            size: None,
+                            icons: None,
+                            meta: None,
+                        },
+                        None,
+                    ),
                     Annotated::new(
                         RawResource {
                             uri: "narra://consistency/issues".to_string(),
@@ -710,6 +845,38 @@ impl ServerHandler for NarraServer {
                         },
                         None,
                     ),
+                    Annotated::new(
+                        RawResource {
+                            uri: "narra://style/sheet".to_string(),
+                            name: "Style Sheet".to_string(),
+                            title: None,
+                            description: Some(
+                                "Project style sheet: spelling choices, capitalization of in-world terms, punctuation conventions"
+                                    .to_string()
+                            ),
+                            mime_type: Some("application/json".to_string()),
+                            size: None,
+                            icons: None,
+                            meta: None,
+                        },
+                        None,
+                    ),
+                    Annotated::new(
+                        RawResource {
+                            uri: "narra://assets".to_string(),
+                            name: "Assets".to_string(),
+                            title: None,
+                            description: Some(
+                                "Images attached to entities (portraits, maps) with their narra://asset/{id} URIs"
+                                    .to_string()
+                            ),
+                            mime_type: Some("application/json".to_string()),
+                            size: None,
+                            icons: None,
+                            meta: None,
+                        },
+                        None,
+                    ),
                 ],
                 next_cursor: None,
                 meta: None,
@@ -753,6 +920,21 @@ impl ServerHandler for NarraServer {
                         },
                         None,
                     ),
+                    Annotated::new(
+                        RawResourceTemplate {
+                            uri_template: "narra://asset/{id}".to_string(),
+                            name: "Asset".to_string(),
+                            title: None,
+                            description: Some(
+                                "An image attached to an entity, base64-encoded. Images over 4 MiB \
+                                 return JSON metadata with a file:// URI instead. IDs come from narra://assets"
+                                    .to_string()
+                            ),
+                            mime_type: None,
+                            icons: None,
+                        },
+                        None,
+                    ),
                 ],
                 next_cursor: None,
                 meta: None,
@@ -777,6 +959,8 @@ impl ServerHandler for NarraServer {
         // Route based on URI path
         if uri == "narra://session/context" {
             self.read_session_context_resource(uri).await
+        } else if uri == "narra://session/stats" {
+            self.read_session_stats_resource(uri).await
         } else if uri == "narra://consistency/issues" {
             self.read_consistency_issues_resource(uri).await
         } else if uri == "narra://schema/import-template" {
@@ -789,6 +973,12 @@ impl ServerHandler for NarraServer {
             self.read_world_overview_resource(uri).await
         } else if uri == "narra://operations/guide" {
             self.read_operations_guide_resource(uri)
+        } else if uri == "narra://style/sheet" {
+            self.read_style_sheet_resource(uri).await
+        } else if uri == "narra://assets" {
+            self.read_assets_resource(uri).await
+        } else if let Some(asset_id) = uri.strip_prefix("narra://asset/") {
+            self.read_asset_resource(uri, asset_id).await
         } else if let Some(rest) = uri.strip_prefix("narra://character/") {
             if let Some(char_id) = rest.strip_suffix("/dossier") {
                 let full_id = format!("character:{}", char_id);
@@ -809,6 +999,37 @@ impl ServerHandler for NarraServer {
         }
     }
 
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        if !subscriptions::is_subscribable(&request.uri) {
+            return Err(McpError::invalid_params(
+                format!("Resource does not support subscriptions: {}", request.uri),
+                None,
+            ));
+        }
+        self.subscriptions
+            .subscribe(
+                &request.uri,
+                context.peer.clone(),
+                self.db.clone(),
+                self.session_manager.clone(),
+            )
+            .await;
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.subscriptions.unsubscribe(&request.uri).await;
+        Ok(())
+    }
+
     async fn list_prompts(
         &self,
         _request: Option<PaginatedRequestParams>,
@@ -1003,6 +1224,14 @@ impl NarraServer {
             emotion_service: ctx.emotion_service.clone(),
             theme_service: ctx.theme_service.clone(),
             ner_service: ctx.ner_service.clone(),
+            token_counter: ctx.token_counter.clone(),
+            event_bus: ctx.event_bus.clone(),
+            backup_service: Some(ctx.backup_service.clone()),
+            asset_store: Some(ctx.asset_store.clone()),
+            ontology: ctx.ontology.clone(),
+            transmission_rules: ctx.transmission_rules.clone(),
+            access: ctx.access.clone(),
+            subscriptions: Arc::new(ResourceSubscriptions::default()),
             tool_router: Self::tool_router(),
         }
     }
@@ -1011,7 +1240,26 @@ impl NarraServer {
         &self,
         uri: &str,
     ) -> Result<ReadResourceResult, McpError> {
-        let content = get_session_context_resource(&self.session_manager, &self.db)
+        let content = get_session_context_resource(
+            &self.session_manager,
+            &self.db,
+            self.token_counter.as_ref(),
+        )
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("application/json".to_string()),
+                text: content,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn read_session_stats_resource(&self, uri: &str) -> Result<ReadResourceResult, McpError> {
+        let content = get_session_stats_resource(&self.session_manager, &self.db)
             .await
             .map_err(|e| McpError::internal_error(e, None))?;
 
@@ -1130,6 +1378,76 @@ impl NarraServer {
         })
     }
 
+    fn asset_store(&self) -> Result<&AssetStore, McpError> {
+        self.asset_store.as_deref().ok_or_else(|| {
+            McpError::resource_not_found("Assets are not available on this server", None)
+        })
+    }
+
+    async fn read_style_sheet_resource(&self, uri: &str) -> Result<ReadResourceResult, McpError> {
+        let content = get_style_sheet_resource(&self.db)
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("application/json".to_string()),
+                text: content,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn read_assets_resource(&self, uri: &str) -> Result<ReadResourceResult, McpError> {
+        let content = get_assets_resource(self.asset_store()?)
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("application/json".to_string()),
+                text: content,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn read_asset_resource(
+        &self,
+        uri: &str,
+        asset_id: &str,
+    ) -> Result<ReadResourceResult, McpError> {
+        let content = get_asset_resource(self.asset_store()?, asset_id)
+            .await
+            .map_err(|e| {
+                if e.starts_with("Not found") {
+                    McpError::resource_not_found(e, None)
+                } else {
+                    McpError::internal_error(e, None)
+                }
+            })?;
+
+        let contents = match content {
+            AssetContent::Blob { mime_type, blob } => ResourceContents::BlobResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(mime_type),
+                blob,
+                meta: None,
+            },
+            AssetContent::Metadata(text) => ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("application/json".to_string()),
+                text,
+                meta: None,
+            },
+        };
+        Ok(ReadResourceResult {
+            contents: vec![contents],
+        })
+    }
+
     async fn read_tension_matrix_resource(
         &self,
         uri: &str,
@@ -1232,6 +1550,20 @@ pub async fn run_mcp_server(ctx: crate::init::AppContext) -> anyhow::Result<()>
     let service = server.serve(transport).await?;
     tracing::info!("MCP server listening on stdio (18 tools)");
 
+    // Background annotation at idle times, per `[annotate] schedule`
+    let scheduler = crate::services::scheduler::AnnotationScheduler::start(
+        ctx.config.annotate.policy(),
+        &ctx.event_bus,
+        ctx.db.clone(),
+        crate::services::AnnotationPipeline::new(
+            ctx.db.clone(),
+            ctx.emotion_service.clone(),
+            ctx.theme_service.clone(),
+            ctx.ner_service.clone(),
+        ),
+        ctx.session_manager.clone(),
+    );
+
     // Graceful shutdown
     let session_manager = ctx.session_manager.clone();
     tokio::spawn(async move {
@@ -1246,6 +1578,9 @@ pub async fn run_mcp_server(ctx: crate::init::AppContext) -> anyhow::Result<()>
     service.waiting().await?;
 
     tracing::info!("MCP server shutting down");
+    if let Some(scheduler) = scheduler {
+        scheduler.stop();
+    }
     ctx.session_manager.mark_session_end().await;
     ctx.session_manager.save().await?;
     tracing::info!("Session state saved");
@@ -0,0 +1,236 @@
+//! MCP resource subscriptions backed by SurrealDB live queries.
+//!
+//! Clients may subscribe to `narra://session/context`,
+//! `narra://consistency/issues`, `narra://entity/{type}:{id}` and
+//! `narra://character/{id}/dossier`. The first subscription starts a change
+//! feed (see `db::live`); each burst of changes is matched against the
+//! subscribed URIs and the client is sent `notifications/resources/updated`
+//! for every one it affects, so it can re-read instead of polling.
+
+use std::collections::{BTreeSet, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use rmcp::model::ResourceUpdatedNotificationParam;
+use rmcp::{Peer, RoleServer};
+use tokio::sync::Mutex;
+
+use crate::db::connection::NarraDb;
+use crate::db::live::{self, Change};
+use crate::session::SessionStateManager;
+
+pub const SESSION_CONTEXT_URI: &str = "narra://session/context";
+pub const CONSISTENCY_ISSUES_URI: &str = "narra://consistency/issues";
+
+/// Changes arriving this close together are reported once.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Hot entities whose edits refresh the session context.
+const HOT_LIMIT: usize = 10;
+
+/// Whether `uri` names a resource that supports subscriptions.
+pub fn is_subscribable(uri: &str) -> bool {
+    uri == SESSION_CONTEXT_URI
+        || uri == CONSISTENCY_ISSUES_URI
+        || uri
+            .strip_prefix("narra://entity/")
+            .is_some_and(|id| id.contains(':'))
+        || uri
+            .strip_prefix("narra://character/")
+            .and_then(|rest| rest.strip_suffix("/dossier"))
+            .is_some_and(|key| !key.is_empty())
+}
+
+/// Subscribed URIs a change affects. `session_ids` are the pinned and hot
+/// entities shown in the session context.
+pub fn affected_uris(
+    subscribed: &HashSet<String>,
+    change: &Change,
+    session_ids: &[String],
+) -> Vec<String> {
+    subscribed
+        .iter()
+        .filter(|uri| {
+            if *uri == CONSISTENCY_ISSUES_URI {
+                return true;
+            }
+            if *uri == SESSION_CONTEXT_URI {
+                return change.is_create_or_delete()
+                    || session_ids.iter().any(|id| change.touches(id));
+            }
+            if let Some(id) = uri.strip_prefix("narra://entity/") {
+                return change.touches(id);
+            }
+            uri.strip_prefix("narra://character/")
+                .and_then(|rest| rest.strip_suffix("/dossier"))
+                .is_some_and(|key| change.touches(&format!("character:{}", key)))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Subscriptions of one MCP connection.
+#[derive(Default)]
+pub struct ResourceSubscriptions {
+    uris: Mutex<HashSet<String>>,
+    peer: Mutex<Option<Peer<RoleServer>>>,
+    feed_started: AtomicBool,
+}
+
+impl ResourceSubscriptions {
+    /// Subscribe to `uri`, starting the change feed on first use.
+    pub async fn subscribe(
+        self: &Arc<Self>,
+        uri: &str,
+        peer: Peer<RoleServer>,
+        db: Arc<NarraDb>,
+        session_manager: Arc<SessionStateManager>,
+    ) {
+        self.uris.lock().await.insert(uri.to_string());
+        *self.peer.lock().await = Some(peer);
+        if !self.feed_started.swap(true, Ordering::SeqCst) {
+            let this = Arc::clone(self);
+            tokio::spawn(async move { this.run_feed(db, session_manager).await });
+        }
+    }
+
+    pub async fn unsubscribe(&self, uri: &str) {
+        self.uris.lock().await.remove(uri);
+    }
+
+    /// Tell the client `uri` changed, if it is subscribed. For changes the
+    /// database does not see, like pins and journal entries.
+    pub async fn touch(&self, uri: &str) {
+        if self.uris.lock().await.contains(uri) {
+            self.notify(uri).await;
+        }
+    }
+
+    /// Send an update notification; false once the client is gone.
+    async fn notify(&self, uri: &str) -> bool {
+        let Some(peer) = self.peer.lock().await.clone() else {
+            return true;
+        };
+        peer.notify_resource_updated(ResourceUpdatedNotificationParam {
+            uri: uri.to_string(),
+        })
+        .await
+        .is_ok()
+    }
+
+    async fn run_feed(&self, db: Arc<NarraDb>, session_manager: Arc<SessionStateManager>) {
+        let mut changes = match live::subscribe(&db).await {
+            Ok(changes) => changes,
+            Err(e) => {
+                tracing::warn!("Resource subscriptions unavailable: {}", e);
+                self.feed_started.store(false, Ordering::SeqCst);
+                return;
+            }
+        };
+
+        while let Some(first) = changes.next().await {
+            let mut batch = vec![first];
+            while let Ok(Some(next)) = tokio::time::timeout(DEBOUNCE, changes.next()).await {
+                batch.push(next);
+            }
+
+            let subscribed = self.uris.lock().await.clone();
+            if subscribed.is_empty() {
+                continue;
+            }
+            let mut session_ids = session_manager.get_pinned().await;
+            session_ids.extend(session_manager.get_hot(HOT_LIMIT).await);
+
+            let mut updated = BTreeSet::new();
+            for change in batch {
+                match change {
+                    Ok(change) => updated.extend(affected_uris(&subscribed, &change, &session_ids)),
+                    Err(e) => tracing::warn!("Live query error: {}", e),
+                }
+            }
+            for uri in updated {
+                if !self.notify(&uri).await {
+                    tracing::debug!("Client gone; stopping resource subscriptions");
+                    return;
+                }
+            }
+        }
+        tracing::warn!("Live query feed ended; resource subscriptions stopped");
+        self.feed_started.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::live::ChangedRecord;
+    use surrealdb::{Action, RecordId};
+
+    fn change(action: Action, id: (&str, &str), edge: Option<(&str, &str)>) -> Change {
+        Change {
+            action,
+            record: ChangedRecord {
+                id: RecordId::from(id),
+                from: edge.map(|_| RecordId::from(("character", "alice"))),
+                to: edge.map(RecordId::from),
+            },
+        }
+    }
+
+    #[test]
+    fn test_changes_map_to_affected_resources() {
+        let subscribed: HashSet<String> = [
+            SESSION_CONTEXT_URI,
+            CONSISTENCY_ISSUES_URI,
+            "narra://entity/character:bob",
+            "narra://character/alice/dossier",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let sorted = |mut uris: Vec<String>| {
+            uris.sort();
+            uris
+        };
+
+        // Edge alice -> bob: bob's entity view, alice's dossier and consistency
+        let edge = change(
+            Action::Update,
+            ("relates_to", "r1"),
+            Some(("character", "bob")),
+        );
+        assert_eq!(
+            sorted(affected_uris(&subscribed, &edge, &[])),
+            vec![
+                "narra://character/alice/dossier",
+                "narra://consistency/issues",
+                "narra://entity/character:bob",
+            ]
+        );
+
+        // Editing an unrelated location: only consistency, unless it is pinned
+        let edit = change(Action::Update, ("location", "docks"), None);
+        assert_eq!(
+            affected_uris(&subscribed, &edit, &[]),
+            vec![CONSISTENCY_ISSUES_URI]
+        );
+        assert_eq!(
+            sorted(affected_uris(
+                &subscribed,
+                &edit,
+                &["location:docks".into()]
+            )),
+            vec![CONSISTENCY_ISSUES_URI, SESSION_CONTEXT_URI]
+        );
+
+        // Creating anything changes the session context's counts
+        let create = change(Action::Create, ("scene", "s9"), None);
+        assert!(affected_uris(&subscribed, &create, &[]).contains(&SESSION_CONTEXT_URI.to_string()));
+
+        assert!(is_subscribable("narra://entity/event:wedding"));
+        assert!(!is_subscribable("narra://entity/wedding"));
+        assert!(!is_subscribable("narra://world/overview"));
+    }
+}
@@ -106,7 +106,14 @@ impl NarraServer {
             suggestions.push("Review warnings below before proceeding.".to_string());
         }
 
-        if !analysis.has_protected_impact && analysis.total_affected < 3 {
+        if analysis.is_locked() {
+            suggestions.push(format!(
+                "{} scene(s) marked final would be affected; update and delete need force=true.",
+                analysis.final_scenes.len()
+            ));
+        }
+
+        if !analysis.has_protected_impact && !analysis.is_locked() && analysis.total_affected < 3 {
             suggestions.push("Low impact change. Safe to proceed.".to_string());
         }
 
@@ -1,13 +1,22 @@
 mod mutate_batch;
 mod mutate_entity;
 mod mutate_facts;
+mod mutate_foreshadows;
 mod mutate_import;
 mod mutate_knowledge;
 mod mutate_notes;
 mod mutate_ops;
+mod mutate_participants;
 mod mutate_phases;
+mod mutate_tags;
 
-use crate::mcp::{ImpactSummary, MutationInput, MutationRequest, MutationResponse, NarraServer};
+use mutate_facts::FactScopeInput;
+
+use crate::mcp::{
+    EntityResult, ImpactSummary, MutationInput, MutationRequest, MutationResponse, NarraServer,
+};
+use crate::services::events;
+use crate::services::impact::final_scenes_referencing;
 use crate::services::{ConsistencySeverity, ImpactAnalysis, ValidationResult};
 use rmcp::handler::server::wrapper::Parameters;
 
@@ -17,6 +26,8 @@ impl NarraServer {
         &self,
         Parameters(input): Parameters<MutationInput>,
     ) -> Result<MutationResponse, String> {
+        let operation = input.operation.clone();
+
         // Reconstruct the full request object for deserialization
         let mut full_request = serde_json::Map::new();
         full_request.insert("operation".to_string(), serde_json::json!(input.operation));
@@ -26,8 +37,11 @@ impl NarraServer {
         let request: MutationRequest =
             serde_json::from_value(serde_json::Value::Object(full_request))
                 .map_err(|e| format!("Invalid mutation parameters: {}", e))?;
+        self.access
+            .check(request.target_type())
+            .map_err(|e| e.to_string())?;
 
-        match request {
+        let result = match request {
             MutationRequest::CreateCharacter {
                 id,
                 name,
@@ -35,9 +49,18 @@ impl NarraServer {
                 aliases,
                 description,
                 profile,
+                allow_similar,
             } => {
-                self.handle_create_character(id, name, role, aliases, description, profile)
-                    .await
+                self.handle_create_character(
+                    id,
+                    name,
+                    role,
+                    aliases,
+                    description,
+                    profile,
+                    allow_similar.unwrap_or(false),
+                )
+                .await
             }
             MutationRequest::CreateLocation {
                 id,
@@ -55,9 +78,18 @@ impl NarraServer {
                 sequence,
                 date,
                 date_precision,
+                narrative_status,
             } => {
-                self.handle_create_event(id, title, description, sequence, date, date_precision)
-                    .await
+                self.handle_create_event(
+                    id,
+                    title,
+                    description,
+                    sequence,
+                    date,
+                    date_precision,
+                    narrative_status,
+                )
+                .await
             }
             MutationRequest::CreateScene {
                 title,
@@ -68,8 +100,13 @@ impl NarraServer {
                 self.handle_create_scene(title, summary, event_id, location_id)
                     .await
             }
-            MutationRequest::Update { entity_id, fields } => {
-                self.handle_update(&entity_id, fields).await
+            MutationRequest::Update {
+                entity_id,
+                fields,
+                force,
+            } => {
+                self.handle_update(&entity_id, fields, force.unwrap_or(false))
+                    .await
             }
             MutationRequest::RecordKnowledge {
                 character_id,
@@ -91,28 +128,69 @@ impl NarraServer {
                 )
                 .await
             }
-            MutationRequest::Delete { entity_id, hard } => {
-                self.handle_delete(&entity_id, hard.unwrap_or(true)).await
+            MutationRequest::Delete {
+                entity_id,
+                hard,
+                force,
+            } => {
+                self.handle_delete(&entity_id, hard.unwrap_or(true), force.unwrap_or(false))
+                    .await
             }
             MutationRequest::CreateNote {
                 title,
                 body,
+                kind,
                 attach_to,
-            } => self.handle_create_note(title, body, attach_to).await,
+            } => {
+                self.handle_create_note(title, body, kind.unwrap_or_default(), attach_to)
+                    .await
+            }
+            MutationRequest::SetNoteStatus { note_id, status } => {
+                self.handle_set_note_status(note_id, status).await
+            }
             MutationRequest::AttachNote { note_id, entity_id } => {
                 self.handle_attach_note(note_id, entity_id).await
             }
             MutationRequest::DetachNote { note_id, entity_id } => {
                 self.handle_detach_note(note_id, entity_id).await
             }
+            MutationRequest::TagEntity {
+                entity_id,
+                tags,
+                force,
+            } => {
+                self.handle_tag_entity(&entity_id, &tags, force.unwrap_or(false))
+                    .await
+            }
+            MutationRequest::UntagEntity {
+                entity_id,
+                tags,
+                force,
+            } => {
+                self.handle_untag_entity(&entity_id, &tags, force.unwrap_or(false))
+                    .await
+            }
             MutationRequest::CreateFact {
                 title,
                 description,
                 categories,
                 enforcement_level,
+                location,
+                valid_from,
+                valid_until,
             } => {
-                self.handle_create_fact(title, description, categories, enforcement_level)
-                    .await
+                self.handle_create_fact(
+                    title,
+                    description,
+                    categories,
+                    enforcement_level,
+                    FactScopeInput {
+                        location,
+                        valid_from,
+                        valid_until,
+                    },
+                )
+                .await
             }
             MutationRequest::UpdateFact {
                 fact_id,
@@ -120,9 +198,23 @@ impl NarraServer {
                 description,
                 categories,
                 enforcement_level,
+                location,
+                valid_from,
+                valid_until,
             } => {
-                self.handle_update_fact(fact_id, title, description, categories, enforcement_level)
-                    .await
+                self.handle_update_fact(
+                    fact_id,
+                    title,
+                    description,
+                    categories,
+                    enforcement_level,
+                    FactScopeInput {
+                        location,
+                        valid_from,
+                        valid_until,
+                    },
+                )
+                .await
             }
             MutationRequest::DeleteFact { fact_id } => self.handle_delete_fact(fact_id).await,
             MutationRequest::LinkFact { fact_id, entity_id } => {
@@ -131,12 +223,60 @@ impl NarraServer {
             MutationRequest::UnlinkFact { fact_id, entity_id } => {
                 self.handle_unlink_fact(fact_id, entity_id).await
             }
+            MutationRequest::AddFactException {
+                fact_id,
+                entity_id,
+                reason,
+            } => {
+                self.handle_fact_exception(fact_id, entity_id, Some(reason))
+                    .await
+            }
+            MutationRequest::RemoveFactException { fact_id, entity_id } => {
+                self.handle_fact_exception(fact_id, entity_id, None).await
+            }
+            MutationRequest::CreateForeshadow {
+                setup_id,
+                payoff_id,
+                note,
+            } => {
+                self.handle_create_foreshadow(setup_id, payoff_id, note)
+                    .await
+            }
+            MutationRequest::RemoveForeshadow {
+                setup_id,
+                payoff_id,
+            } => self.handle_remove_foreshadow(setup_id, payoff_id).await,
+            MutationRequest::AddEventParticipant {
+                event_id,
+                character_id,
+                role,
+                impact,
+                force,
+            } => {
+                self.handle_add_event_participant(
+                    event_id,
+                    character_id,
+                    role,
+                    impact,
+                    force.unwrap_or(false),
+                )
+                .await
+            }
+            MutationRequest::RemoveEventParticipant {
+                event_id,
+                character_id,
+                force,
+            } => {
+                self.handle_remove_event_participant(event_id, character_id, force.unwrap_or(false))
+                    .await
+            }
             MutationRequest::CreateRelationship {
                 from_character_id,
                 to_character_id,
                 rel_type,
                 subtype,
                 label,
+                with_perceptions,
             } => {
                 self.handle_create_relationship(
                     from_character_id,
@@ -144,9 +284,18 @@ impl NarraServer {
                     rel_type,
                     subtype,
                     label,
+                    with_perceptions,
                 )
                 .await
             }
+            MutationRequest::SetTrust {
+                from_character_id,
+                to_character_id,
+                trust,
+            } => {
+                self.handle_set_trust(from_character_id, to_character_id, trust)
+                    .await
+            }
             MutationRequest::BatchCreateCharacters { characters } => {
                 self.handle_batch_create_characters(characters).await
             }
@@ -184,6 +333,7 @@ impl NarraServer {
                 content_weight,
                 neighborhood_weight,
                 temporal_weight,
+                seed,
             } => {
                 self.handle_save_phases(
                     entity_types,
@@ -191,16 +341,21 @@ impl NarraServer {
                     content_weight,
                     neighborhood_weight,
                     temporal_weight,
+                    seed,
                 )
                 .await
             }
             MutationRequest::ClearPhases => self.handle_clear_phases().await,
+            MutationRequest::RenamePhase { phase_id, label } => {
+                self.handle_rename_phase(&phase_id, &label).await
+            }
             MutationRequest::AnnotateEntities {
                 entity_types,
                 run_emotions,
                 run_themes,
                 run_ner,
                 concurrency,
+                incremental,
             } => {
                 self.handle_annotate_entities(
                     entity_types,
@@ -208,9 +363,50 @@ impl NarraServer {
                     run_themes,
                     run_ner,
                     concurrency,
+                    incremental,
                 )
                 .await
             }
+        };
+
+        if let Ok(response) = &result {
+            self.emit_mutation_event(&operation, response);
+        }
+        result
+    }
+
+    /// Report a successful mutation on the event bus.
+    pub(crate) fn emit_mutation_event(&self, operation: &str, response: &MutationResponse) {
+        let event = if operation == "backfill_embeddings" {
+            events::EMBEDDING_BACKFILLED
+        } else if operation.starts_with("create_") || operation.starts_with("batch_create_") {
+            events::ENTITY_CREATED
+        } else if operation == "delete" || operation.starts_with("delete_") {
+            events::ENTITY_DELETED
+        } else if operation == "update" || operation.starts_with("update_") {
+            events::ENTITY_UPDATED
+        } else {
+            return;
+        };
+
+        if event == events::EMBEDDING_BACKFILLED {
+            self.event_bus.emit(
+                event,
+                serde_json::json!({
+                    "summary": response.entity.content,
+                    "source": "mcp",
+                }),
+            );
+            return;
+        }
+
+        let entities: Vec<&EntityResult> = match &response.entities {
+            Some(batch) => batch.iter().collect(),
+            None => vec![&response.entity],
+        };
+        for entity in entities {
+            self.event_bus
+                .emit_entity(event, "mcp", &entity.id, &entity.entity_type, &entity.name);
         }
     }
 
@@ -221,6 +417,9 @@ impl NarraServer {
         result: &ValidationResult,
         operation_description: &str,
     ) -> Result<Vec<String>, String> {
+        self.event_bus
+            .emit_violations("mcp", operation_description, result);
+
         if result.has_blocking_violations {
             // Format CRITICAL violations into error message
             let critical_messages: Vec<String> = result
@@ -240,8 +439,52 @@ impl NarraServer {
         Ok(result.warnings())
     }
 
+    /// Refuse a mutation reaching scenes marked final unless it is forced.
+    pub(crate) fn check_revision_lock(
+        &self,
+        analysis: &ImpactAnalysis,
+        operation_description: &str,
+        force: bool,
+    ) -> Result<(), String> {
+        if force || !analysis.is_locked() {
+            return Ok(());
+        }
+        Err(format!(
+            "REVISION LOCK: {} would change {} scene(s) marked final: {}. Pass force=true to proceed.",
+            operation_description,
+            analysis.final_scenes.len(),
+            analysis.final_scenes.join(", ")
+        ))
+    }
+
+    /// Refuse to change `entity_id` while scenes marked final reference it,
+    /// unless forced. For mutations that skip impact analysis.
+    pub(crate) async fn check_final_scenes(
+        &self,
+        entity_id: &str,
+        operation_description: &str,
+        force: bool,
+    ) -> Result<(), String> {
+        if force {
+            return Ok(());
+        }
+        let locked = final_scenes_referencing(&self.db, entity_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        if locked.is_empty() {
+            return Ok(());
+        }
+        let scenes: Vec<String> = locked.iter().map(|s| s.id.clone()).collect();
+        Err(format!(
+            "REVISION LOCK: {} would change {} scene(s) marked final: {}. Pass force=true to proceed.",
+            operation_description,
+            locked.len(),
+            scenes.join(", ")
+        ))
+    }
+
     pub(crate) fn convert_impact(&self, analysis: &ImpactAnalysis) -> ImpactSummary {
-        let severity = if analysis.has_protected_impact {
+        let severity = if analysis.has_protected_impact || analysis.is_locked() {
             "Critical".to_string()
         } else if analysis.total_affected > 10 {
             "High".to_string()
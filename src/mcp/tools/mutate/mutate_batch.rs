@@ -201,6 +201,7 @@ impl NarraServer {
                 date: parsed_date,
                 date_precision: spec.date_precision,
                 duration_end: None,
+                narrative_status: spec.narrative_status,
             };
 
             let result = if let Some(ref id) = spec.id {
@@ -267,12 +268,23 @@ impl NarraServer {
         relationships: Vec<RelationshipSpec>,
     ) -> Result<MutationResponse, String> {
         use crate::models::relationship::{create_relationship, RelationshipCreate};
+        use crate::services::reciprocity::set_intentional;
 
         let count = relationships.len();
         let mut entities = Vec::with_capacity(count);
         let mut errors: Vec<String> = Vec::new();
 
-        for spec in relationships {
+        for mut spec in relationships {
+            match self.ontology.check(&spec.rel_type) {
+                Ok(checked) => spec.rel_type = checked.rel_type,
+                Err(e) => {
+                    errors.push(format!(
+                        "Failed to create {} -> {}: {}",
+                        spec.from_character_id, spec.to_character_id, e
+                    ));
+                    continue;
+                }
+            }
             let create = RelationshipCreate {
                 from_character_id: spec.from_character_id.clone(),
                 to_character_id: spec.to_character_id.clone(),
@@ -281,7 +293,13 @@ impl NarraServer {
                 label: spec.label,
             };
 
-            match create_relationship(&self.db, create).await {
+            let result = match create_relationship(&self.db, create).await {
+                Ok(rel) if spec.intentional == Some(true) => {
+                    set_intentional(&self.db, &rel.id.to_string(), true).await
+                }
+                other => other,
+            };
+            match result {
                 Ok(rel) => {
                     let entity_id = rel.id.to_string();
                     // Mark relates_to edge for embedding generation
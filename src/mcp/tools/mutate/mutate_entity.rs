@@ -2,6 +2,7 @@ use crate::mcp::{EntityResult, MutationResponse, NarraServer};
 use crate::models::{CharacterCreate, EventCreate, LocationCreate, SceneCreate};
 
 impl NarraServer {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn handle_create_character(
         &self,
         id: Option<String>,
@@ -10,8 +11,12 @@ impl NarraServer {
         aliases: Option<Vec<String>>,
         description: Option<String>,
         profile: Option<std::collections::HashMap<String, Vec<String>>>,
+        allow_similar: bool,
     ) -> Result<MutationResponse, String> {
         use crate::models::character::{create_character, create_character_with_id};
+        use crate::services::confusability::{
+            describe_similar, ConfusabilityOptions, ConfusabilityService,
+        };
 
         // Build creation data for consistency check
         let creation_data = serde_json::json!({
@@ -40,6 +45,21 @@ impl NarraServer {
             profile: profile.unwrap_or_default(),
         };
 
+        let similar = ConfusabilityService::new(self.db.clone())
+            .similar_to_new(
+                &create,
+                self.embedding_service.as_ref(),
+                &ConfusabilityOptions::default(),
+            )
+            .await
+            .map_err(|e| format!("Similarity check failed: {}", e))?;
+        if !allow_similar && !similar.duplicates.is_empty() {
+            return Err(format!(
+                "{}. Did you mean to update it instead? Pass allow_similar: true to create it anyway.",
+                describe_similar(&similar.duplicates).join("; ")
+            ));
+        }
+
         let character = if let Some(ref slug) = id {
             create_character_with_id(&self.db, slug, create).await
         } else {
@@ -69,6 +89,11 @@ impl NarraServer {
                 .to_string(),
         ];
         hints.extend(consistency_warnings);
+        hints.extend(
+            describe_similar(&similar.lookalikes)
+                .into_iter()
+                .map(|line| format!("Readers may mix them up: {}", line)),
+        );
 
         Ok(MutationResponse {
             entity: result,
@@ -157,6 +182,7 @@ impl NarraServer {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn handle_create_event(
         &self,
         id: Option<String>,
@@ -165,6 +191,7 @@ impl NarraServer {
         sequence: Option<i32>,
         date: Option<String>,
         date_precision: Option<String>,
+        narrative_status: Option<String>,
     ) -> Result<MutationResponse, String> {
         use crate::models::event::{create_event, create_event_with_id};
 
@@ -204,6 +231,7 @@ impl NarraServer {
             date: parsed_date,
             date_precision,
             duration_end: None,
+            narrative_status,
         };
 
         let event = if let Some(ref slug) = id {
@@ -325,6 +353,7 @@ impl NarraServer {
         &self,
         entity_id: &str,
         fields: serde_json::Value,
+        force: bool,
     ) -> Result<MutationResponse, String> {
         // Detect entity type from ID format (table:id)
         let entity_type = self.detect_entity_type(entity_id);
@@ -345,6 +374,7 @@ impl NarraServer {
             },
         )?;
 
+        self.check_revision_lock(&impact_analysis, &format!("Update {}", entity_id), force)?;
         let consistency_warnings =
             self.process_consistency_result(&consistency_result, &format!("Update {}", entity_id))?;
 
@@ -428,6 +458,10 @@ impl NarraServer {
                                 .map(|dt| dt.with_timezone(&chrono::Utc).into())
                         })
                     }),
+                    narrative_status: fields
+                        .get("narrative_status")
+                        .and_then(|v| v.as_str())
+                        .map(String::from),
                     updated_at: chrono::Utc::now().into(),
                 };
 
@@ -507,6 +541,13 @@ impl NarraServer {
             }
         }
 
+        // A rename can change what [[links]] in notes resolve to
+        if crate::services::note_links::renames(&fields) {
+            if let Err(e) = crate::services::note_links::resync_all_note_links(&self.db).await {
+                tracing::warn!("Failed to re-resolve note links after {}: {}", entity_id, e);
+            }
+        }
+
         // If updating a perceives edge, regenerate its perspective embedding
         if entity_type == "perceives" {
             if let Err(e) = self.staleness_manager.mark_stale(entity_id).await {
@@ -533,7 +574,10 @@ impl NarraServer {
         };
 
         // Include impact summary for high-severity changes
-        let impact = if impact_analysis.has_protected_impact || impact_analysis.total_affected > 5 {
+        let impact = if impact_analysis.has_protected_impact
+            || impact_analysis.is_locked()
+            || impact_analysis.total_affected > 5
+        {
             Some(self.convert_impact(&impact_analysis))
         } else {
             None
@@ -563,6 +607,7 @@ impl NarraServer {
         &self,
         entity_id: &str,
         hard: bool,
+        force: bool,
     ) -> Result<MutationResponse, String> {
         let entity_type = self.detect_entity_type(entity_id);
 
@@ -573,6 +618,8 @@ impl NarraServer {
             .await
             .map_err(|e| format!("Impact analysis failed: {}", e))?;
 
+        self.check_revision_lock(&impact_analysis, &format!("Delete {}", entity_id), force)?;
+
         // Warn if high-severity deletion (protected entities affected)
         if impact_analysis.has_protected_impact && !hard {
             let warnings: Vec<String> = impact_analysis.warnings.clone();
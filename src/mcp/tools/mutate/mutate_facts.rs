@@ -1,9 +1,23 @@
 use crate::mcp::{EntityResult, MutationResponse, NarraServer};
 use crate::models::fact::{
-    create_fact, delete_fact, link_fact_to_entity, unlink_fact_from_entity, update_fact,
+    add_fact_exception, apply_scope_bounds, create_fact, delete_fact, get_fact,
+    link_fact_to_entity, remove_fact_exception, unlink_fact_from_entity, update_fact,
     EnforcementLevel, FactCategory, FactCreate, FactUpdate,
 };
 
+/// Location and time bounds for a fact's scope, as given to create/update.
+pub(crate) struct FactScopeInput {
+    pub location: Option<String>,
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+}
+
+impl FactScopeInput {
+    fn is_empty(&self) -> bool {
+        self.location.is_none() && self.valid_from.is_none() && self.valid_until.is_none()
+    }
+}
+
 impl NarraServer {
     pub(crate) async fn handle_create_fact(
         &self,
@@ -11,6 +25,7 @@ impl NarraServer {
         description: String,
         categories: Option<Vec<String>>,
         enforcement_level: Option<String>,
+        scope: FactScopeInput,
     ) -> Result<MutationResponse, String> {
         // Parse categories strings to FactCategory enum
         let parsed_categories = categories
@@ -22,12 +37,28 @@ impl NarraServer {
             .map(|e| Self::parse_enforcement_level(&e))
             .unwrap_or(EnforcementLevel::Warning);
 
+        let scope = if scope.is_empty() {
+            None
+        } else {
+            Some(
+                apply_scope_bounds(
+                    &self.db,
+                    None,
+                    scope.location.as_deref(),
+                    scope.valid_from.as_deref(),
+                    scope.valid_until.as_deref(),
+                )
+                .await
+                .map_err(|e| format!("Invalid fact scope: {}", e))?,
+            )
+        };
+
         let create = FactCreate {
             title: title.clone(),
             description: description.clone(),
             categories: parsed_categories,
             enforcement_level: parsed_enforcement,
-            scope: None, // Scope can be set via update
+            scope,
         };
 
         let fact = create_fact(&self.db, create)
@@ -52,11 +83,16 @@ impl NarraServer {
             last_modified: Some(fact.updated_at.to_string()),
         };
 
-        let hints = vec![
+        let mut hints = vec![
             format!("Universe fact '{}' created successfully", title),
             "Link this fact to entities using graph operations".to_string(),
             format!("Enforcement level: {:?}", parsed_enforcement),
         ];
+        if let Some(scope) = fact.scope.as_ref().map(|s| s.describe()) {
+            if !scope.is_empty() {
+                hints.push(format!("Scope: {}", scope));
+            }
+        }
 
         Ok(MutationResponse {
             entity: result,
@@ -73,10 +109,32 @@ impl NarraServer {
         description: Option<String>,
         categories: Option<Vec<String>>,
         enforcement_level: Option<String>,
+        scope: FactScopeInput,
     ) -> Result<MutationResponse, String> {
         // Extract fact key from fact_id (handle "universe_fact:xxx" format)
         let fact_key = fact_id.split(':').next_back().unwrap_or(&fact_id);
 
+        // Bounds not given keep their current value
+        let scope = if scope.is_empty() {
+            None
+        } else {
+            let current = get_fact(&self.db, fact_key)
+                .await
+                .map_err(|e| format!("Failed to get fact: {}", e))?
+                .ok_or_else(|| format!("Fact not found: {}", fact_id))?;
+            Some(
+                apply_scope_bounds(
+                    &self.db,
+                    current.scope,
+                    scope.location.as_deref(),
+                    scope.valid_from.as_deref(),
+                    scope.valid_until.as_deref(),
+                )
+                .await
+                .map_err(|e| format!("Invalid fact scope: {}", e))?,
+            )
+        };
+
         // Parse optional categories
         let parsed_categories =
             categories.map(|cats| cats.into_iter().map(|c| Self::parse_category(&c)).collect());
@@ -89,7 +147,7 @@ impl NarraServer {
             description,
             categories: parsed_categories,
             enforcement_level: parsed_enforcement,
-            scope: None, // Don't update scope in this operation
+            scope,
             updated_at: chrono::Utc::now().into(),
         };
 
@@ -132,6 +190,11 @@ impl NarraServer {
         if let Some(ref level) = parsed_enforcement {
             hints.push(format!("Fact enforcement changed to {:?}", level));
         }
+        if let Some(scope) = fact.scope.as_ref().map(|s| s.describe()) {
+            if !scope.is_empty() {
+                hints.push(format!("Scope: {}", scope));
+            }
+        }
 
         Ok(MutationResponse {
             entity: result,
@@ -247,6 +310,48 @@ impl NarraServer {
         })
     }
 
+    /// Add (`reason` given) or remove an entity's exemption from a fact.
+    pub(crate) async fn handle_fact_exception(
+        &self,
+        fact_id: String,
+        entity_id: String,
+        reason: Option<String>,
+    ) -> Result<MutationResponse, String> {
+        let fact_key = fact_id.split(':').next_back().unwrap_or(&fact_id);
+
+        let fact = match &reason {
+            Some(reason) => add_fact_exception(&self.db, fact_key, &entity_id, reason).await,
+            None => remove_fact_exception(&self.db, fact_key, &entity_id).await,
+        }
+        .map_err(|e| format!("Failed to update fact exceptions: {}", e))?
+        .ok_or_else(|| format!("Fact not found: {}", fact_id))?;
+
+        let content = match &reason {
+            Some(reason) => format!("{} is exempt from '{}': {}", entity_id, fact.title, reason),
+            None => format!("{} is no longer exempt from '{}'", entity_id, fact.title),
+        };
+        let result = EntityResult {
+            id: fact.id.to_string(),
+            entity_type: "universe_fact".to_string(),
+            name: fact.title.clone(),
+            content,
+            confidence: Some(1.0),
+            last_modified: Some(fact.updated_at.to_string()),
+        };
+
+        let hints = vec![format!(
+            "Fact has {} exception(s); consistency checks skip excepted entities",
+            fact.exceptions.len()
+        )];
+
+        Ok(MutationResponse {
+            entity: result,
+            entities: None,
+            impact: None,
+            hints,
+        })
+    }
+
     /// Parse category string to FactCategory enum
     pub(crate) fn parse_category(s: &str) -> FactCategory {
         match s {
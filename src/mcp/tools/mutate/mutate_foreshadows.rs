@@ -0,0 +1,69 @@
+use crate::mcp::{EntityResult, MutationResponse, NarraServer};
+use crate::models::foreshadow::{create_foreshadow, remove_foreshadow};
+
+impl NarraServer {
+    pub(crate) async fn handle_create_foreshadow(
+        &self,
+        setup_id: String,
+        payoff_id: String,
+        note: Option<String>,
+    ) -> Result<MutationResponse, String> {
+        let edge = create_foreshadow(&self.db, &setup_id, &payoff_id, note)
+            .await
+            .map_err(|e| format!("Failed to create foreshadowing link: {}", e))?;
+
+        let result = EntityResult {
+            id: edge.id.to_string(),
+            entity_type: "foreshadows".to_string(),
+            name: format!("{} -> {}", setup_id, payoff_id),
+            content: format!("{} foreshadows {}", setup_id, payoff_id),
+            confidence: Some(1.0),
+            last_modified: Some(edge.created_at.to_string()),
+        };
+
+        let hints = vec![
+            format!("{} now foreshadows {}", setup_id, payoff_id),
+            "Run 'narra analyze foreshadowing' to check setups come before their payoffs"
+                .to_string(),
+        ];
+
+        Ok(MutationResponse {
+            entity: result,
+            entities: None,
+            impact: None,
+            hints,
+        })
+    }
+
+    pub(crate) async fn handle_remove_foreshadow(
+        &self,
+        setup_id: String,
+        payoff_id: String,
+    ) -> Result<MutationResponse, String> {
+        let removed = remove_foreshadow(&self.db, &setup_id, &payoff_id)
+            .await
+            .map_err(|e| format!("Failed to remove foreshadowing link: {}", e))?;
+        if !removed {
+            return Err(format!(
+                "No foreshadowing link from {} to {}",
+                setup_id, payoff_id
+            ));
+        }
+
+        let result = EntityResult {
+            id: format!("foreshadows:{}:{}", setup_id, payoff_id),
+            entity_type: "foreshadows".to_string(),
+            name: format!("{} -> {}", setup_id, payoff_id),
+            content: format!("Removed link from {} to {}", setup_id, payoff_id),
+            confidence: Some(1.0),
+            last_modified: Some(chrono::Utc::now().to_rfc3339()),
+        };
+
+        Ok(MutationResponse {
+            entity: result,
+            entities: None,
+            impact: None,
+            hints: vec![format!("{} no longer foreshadows {}", setup_id, payoff_id)],
+        })
+    }
+}
@@ -8,6 +8,7 @@ impl NarraServer {
         import: NarraImport,
         on_conflict: ConflictMode,
     ) -> Result<MutationResponse, String> {
+        self.backup_before("import").await?;
         let import_service = ImportService::new(self.db.clone(), self.staleness_manager.clone());
 
         let result = import_service
@@ -63,6 +64,21 @@ impl NarraServer {
             }
         }
 
+        // Field-level diffs for entities that already existed
+        for conflict in result.conflicts.iter().filter(|c| !c.fields.is_empty()) {
+            let fields: Vec<String> = conflict
+                .fields
+                .iter()
+                .map(|f| format!("{}: {} -> {}", f.field, f.old, f.new))
+                .collect();
+            hints.push(format!(
+                "Conflict {} ({:?}): {}",
+                conflict.entity_id,
+                conflict.resolution.unwrap_or(on_conflict),
+                fields.join("; ")
+            ));
+        }
+
         if result.total_created > 0 {
             hints.push(
                 "Run mutate(BackfillEmbeddings) to generate embeddings for imported entities"
@@ -14,7 +14,10 @@ impl NarraServer {
         source_character_id: Option<String>,
         event_id: Option<String>,
     ) -> Result<MutationResponse, String> {
-        use crate::models::knowledge::{create_knowledge, create_knowledge_state, KnowledgeCreate};
+        use crate::models::knowledge::{
+            create_knowledge, create_knowledge_state, record_derivation, KnowledgeCreate,
+        };
+        use crate::services::transmission::derive_certainty;
         use surrealdb::RecordId;
 
         // Build creation data for consistency check
@@ -47,6 +50,28 @@ impl NarraServer {
             }
         }
 
+        // Derive certainty from the source's own when asked to
+        let char_key = character_id.split(':').nth(1).unwrap_or(&character_id);
+        let derivation = if certainty.eq_ignore_ascii_case("derive") {
+            let source = source_character_id
+                .as_deref()
+                .ok_or("certainty 'derive' needs source_character_id")?;
+            let derivation = derive_certainty(
+                &self.db,
+                &self.ontology,
+                &self.transmission_rules,
+                char_key,
+                source.strip_prefix("character:").unwrap_or(source),
+                &fact,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            hints.push(format!("Certainty derived: {}", derivation.explanation));
+            Some(derivation)
+        } else {
+            None
+        };
+
         // Step 1: Create knowledge entity with the fact
         // The knowledge entity stores: character (the knower) + fact (the information)
         // The target_id from the API is incorporated into the fact text
@@ -65,15 +90,23 @@ impl NarraServer {
         .map_err(|e| format!("Failed to create knowledge entity: {}", e))?;
 
         // Step 2: Parse certainty level and learning method
-        let certainty_level = match certainty.to_lowercase().as_str() {
-            "knows" | "certain" => CertaintyLevel::Knows,
-            "suspects" => CertaintyLevel::Suspects,
-            "believes_wrongly" | "wrong" => CertaintyLevel::BelievesWrongly,
-            "uncertain" => CertaintyLevel::Uncertain,
-            "assumes" => CertaintyLevel::Assumes,
-            "denies" => CertaintyLevel::Denies,
-            "forgotten" => CertaintyLevel::Forgotten,
-            _ => CertaintyLevel::Uncertain,
+        let certainty_level = match derivation.as_ref() {
+            Some(d) => d.certainty,
+            None => match certainty.to_lowercase().as_str() {
+                "knows" | "certain" => CertaintyLevel::Knows,
+                "suspects" => CertaintyLevel::Suspects,
+                "believes_wrongly" | "wrong" => CertaintyLevel::BelievesWrongly,
+                "uncertain" => CertaintyLevel::Uncertain,
+                "assumes" => CertaintyLevel::Assumes,
+                "denies" => CertaintyLevel::Denies,
+                "forgotten" => CertaintyLevel::Forgotten,
+                _ => CertaintyLevel::Uncertain,
+            },
+        };
+        let default_method = if derivation.is_some() {
+            LearningMethod::Told
+        } else {
+            LearningMethod::Discovered
         };
 
         let learning_method = method.map(|m| match m.to_lowercase().as_str() {
@@ -94,24 +127,33 @@ impl NarraServer {
         // Step 3: Create knows edge from character to knowledge entity
         let create = KnowledgeStateCreate {
             certainty: certainty_level,
-            learning_method: learning_method.unwrap_or(LearningMethod::Discovered),
+            learning_method: learning_method.unwrap_or(default_method),
             source_character: source_character_id,
             event: event_id,
             premises: None,
-            truth_value: if certainty_level == CertaintyLevel::BelievesWrongly {
-                Some(fact.clone())
-            } else {
-                None
+            truth_value: match &derivation {
+                Some(d) => d.truth_value.clone(),
+                None if certainty_level == CertaintyLevel::BelievesWrongly => Some(fact.clone()),
+                None => None,
             },
         };
 
-        // Extract character key (function expects just key, not table:key)
-        let char_key = character_id.split(':').nth(1).unwrap_or(&character_id);
         let knowledge_target = format!("knowledge:{}", knowledge_entity.id.key());
 
-        let knowledge_state = create_knowledge_state(&self.db, char_key, &knowledge_target, create)
+        let mut knowledge_state =
+            create_knowledge_state(&self.db, char_key, &knowledge_target, create)
+                .await
+                .map_err(|e| format!("Failed to create knowledge state: {}", e))?;
+        if let Some(d) = &derivation {
+            knowledge_state = record_derivation(
+                &self.db,
+                &knowledge_state.id,
+                &d.source_state,
+                &d.explanation,
+            )
             .await
-            .map_err(|e| format!("Failed to create knowledge state: {}", e))?;
+            .map_err(|e| format!("Failed to record derivation: {}", e))?;
+        }
 
         // Trigger async embedding generation for the knowledge entity
         let knowledge_entity_id = knowledge_entity.id.to_string();
@@ -1,17 +1,24 @@
 use crate::mcp::{EntityResult, MutationResponse, NarraServer};
+use crate::models::note::{NoteKind, NoteStatus};
 
 impl NarraServer {
     pub(crate) async fn handle_create_note(
         &self,
         title: String,
         body: String,
+        kind: NoteKind,
         attach_to: Option<Vec<String>>,
     ) -> Result<MutationResponse, String> {
-        use crate::models::note::{attach_note, create_note, NoteCreate};
+        use crate::models::note::{attach_note, check_critique_targets, create_note, NoteCreate};
+
+        check_critique_targets(kind, attach_to.as_deref().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
 
         let create = NoteCreate {
             title: title.clone(),
             body: body.clone(),
+            kind,
+            ..Default::default()
         };
 
         let note = create_note(&self.db, create)
@@ -31,6 +38,10 @@ impl NarraServer {
             }
         }
 
+        let links = crate::services::note_links::sync_note_links(&self.db, &note_key)
+            .await
+            .map_err(|e| format!("Failed to resolve note links: {}", e))?;
+
         let entity_id = note.id.to_string();
 
         // Trigger embedding generation for the new note
@@ -50,11 +61,29 @@ impl NarraServer {
         };
 
         let mut hints = vec![format!("Note '{}' created successfully", title)];
+        if kind.has_status() {
+            hints.push(format!(
+                "Use set_note_status to resolve this {} when it's done",
+                kind
+            ));
+        }
         if attached_count > 0 {
             hints.push(format!("Attached to {} entities", attached_count));
-        } else {
+        } else if links.linked.is_empty() {
             hints.push("Use attach_note to link this note to entities".to_string());
         }
+        if !links.linked.is_empty() {
+            hints.push(format!("Linked to {}", links.linked.join(", ")));
+        }
+        if !links.unresolved.is_empty() {
+            hints.push(format!("No entity named: {}", links.unresolved.join(", ")));
+        }
+        if !links.ambiguous.is_empty() {
+            hints.push(format!(
+                "Several entities match: {} (link by ID, e.g. [[character:alice]])",
+                links.ambiguous.join(", ")
+            ));
+        }
 
         Ok(MutationResponse {
             entity: result,
@@ -64,6 +93,38 @@ impl NarraServer {
         })
     }
 
+    pub(crate) async fn handle_set_note_status(
+        &self,
+        note_id: String,
+        status: NoteStatus,
+    ) -> Result<MutationResponse, String> {
+        use crate::models::note::set_note_status;
+
+        // Extract note key (handle both "note:xxx" and "xxx" formats)
+        let note_key = note_id.split(':').next_back().unwrap_or(&note_id);
+
+        let note = set_note_status(&self.db, note_key, status)
+            .await
+            .map_err(|e| format!("Failed to set note status: {}", e))?
+            .ok_or_else(|| format!("Note not found: {}", note_id))?;
+
+        let result = EntityResult {
+            id: note.id.to_string(),
+            entity_type: "note".to_string(),
+            name: note.title.clone(),
+            content: format!("Marked {} '{}' {}", note.kind, note.title, note.status),
+            confidence: Some(1.0),
+            last_modified: Some(note.updated_at.to_string()),
+        };
+
+        Ok(MutationResponse {
+            entity: result,
+            entities: None,
+            impact: None,
+            hints: vec![format!("{} is now {}", note.id, note.status)],
+        })
+    }
+
     pub(crate) async fn handle_attach_note(
         &self,
         note_id: String,
@@ -8,8 +8,16 @@ impl NarraServer {
         rel_type: String,
         subtype: Option<String>,
         label: Option<String>,
+        with_perceptions: bool,
     ) -> Result<MutationResponse, String> {
         use crate::models::relationship::{create_relationship, RelationshipCreate};
+        use crate::services::ontology::create_baseline_perceptions;
+
+        let checked = self.ontology.check(&rel_type).map_err(|e| e.to_string())?;
+        let rel_type = checked.rel_type;
+        if with_perceptions {
+            self.access.check("perceives").map_err(|e| e.to_string())?;
+        }
 
         let create = RelationshipCreate {
             from_character_id: from_character_id.clone(),
@@ -25,6 +33,29 @@ impl NarraServer {
 
         let entity_id = relationship.id.to_string();
 
+        let seeded = if with_perceptions {
+            let seeded = create_baseline_perceptions(
+                &self.db,
+                &self.ontology,
+                &from_character_id,
+                &to_character_id,
+                &rel_type,
+                subtype.as_deref(),
+            )
+            .await
+            .map_err(|e| format!("Failed to seed perceptions: {}", e))?;
+            for perception in &seeded.created {
+                self.staleness_manager.spawn_regeneration(
+                    perception.id.to_string(),
+                    "perceives".to_string(),
+                    None,
+                );
+            }
+            Some(seeded)
+        } else {
+            None
+        };
+
         // Trigger embedding generation for the relates_to edge itself
         self.staleness_manager.spawn_regeneration(
             entity_id.clone(),
@@ -87,13 +118,100 @@ impl NarraServer {
             last_modified: Some(relationship.created_at.to_string()),
         };
 
-        let hints = vec![
+        let mut hints = vec![
             format!(
                 "Relationship '{}' created between character:{} and character:{}",
                 display_label, from_character_id, to_character_id
             ),
             "Use graph_traversal to see connected entities".to_string(),
         ];
+        hints.extend(checked.note);
+        if let Some(seeded) = &seeded {
+            hints.push(format!(
+                "Seeded {} baseline perception(s){}; refine them with update as the story develops",
+                seeded.created.len(),
+                if seeded.existing > 0 {
+                    format!(", kept {} existing", seeded.existing)
+                } else {
+                    String::new()
+                }
+            ));
+        }
+
+        Ok(MutationResponse {
+            entity: result,
+            entities: None,
+            impact: None,
+            hints,
+        })
+    }
+
+    pub(crate) async fn handle_set_trust(
+        &self,
+        from_character_id: String,
+        to_character_id: String,
+        trust: Option<f32>,
+    ) -> Result<MutationResponse, String> {
+        use crate::models::perception::set_trust;
+        use crate::services::trust::estimate_trust;
+
+        let from = from_character_id
+            .strip_prefix("character:")
+            .unwrap_or(&from_character_id);
+        let to = to_character_id
+            .strip_prefix("character:")
+            .unwrap_or(&to_character_id);
+
+        let perception = set_trust(&self.db, from, to, trust)
+            .await
+            .map_err(|e| format!("Failed to set trust: {}", e))?
+            .ok_or_else(|| {
+                format!(
+                    "character:{} has no perception of character:{}; record one first",
+                    from, to
+                )
+            })?;
+        let estimate = estimate_trust(&self.db, &self.ontology, from, to)
+            .await
+            .map_err(|e| format!("Failed to estimate trust: {}", e))?;
+
+        let content = match trust {
+            Some(value) => format!(
+                "character:{} trusts character:{} at {:.2} ({})",
+                from,
+                to,
+                value,
+                estimate.level()
+            ),
+            None => format!(
+                "Cleared trust; character:{} trust in character:{} is inferred at {:.2} ({})",
+                from,
+                to,
+                estimate.inferred,
+                estimate.level()
+            ),
+        };
+        let result = EntityResult {
+            id: perception.id.to_string(),
+            entity_type: "perceives".to_string(),
+            name: format!("character:{} -> character:{}", from, to),
+            content,
+            confidence: Some(1.0),
+            last_modified: Some(perception.updated_at.to_string()),
+        };
+
+        let mut hints = Vec::new();
+        if trust.is_some() && !estimate.reasons.is_empty() {
+            hints.push(format!(
+                "History alone suggests {:.2} ({})",
+                estimate.inferred,
+                estimate.reasons.join(", ")
+            ));
+        }
+        hints.push(
+            "Trust weighs influence_propagation paths and certainty 'derive' in record_knowledge"
+                .to_string(),
+        );
 
         Ok(MutationResponse {
             entity: result,
@@ -189,89 +307,15 @@ impl NarraServer {
             }
         }
 
-        let types_to_process: Vec<String> = match &entity_type {
-            Some(et) => vec![et.clone()],
-            None => vec!["character".to_string(), "knowledge".to_string()],
+        let types_to_process: Vec<&str> = match &entity_type {
+            Some(et) => vec![et.as_str()],
+            None => vec!["character", "knowledge"],
         };
 
-        let mut total_created = 0usize;
-        let mut total_skipped = 0usize;
-
-        for etype in &types_to_process {
-            if !matches!(etype.as_str(), "character" | "knowledge") {
-                continue;
-            }
-
-            // Fetch entities that have embeddings
-            let fetch_query = format!(
-                "SELECT id, embedding FROM {} WHERE embedding IS NOT NONE",
-                etype
-            );
-
-            let mut response = self
-                .db
-                .query(&fetch_query)
-                .await
-                .map_err(|e| format!("Failed to fetch {} entities: {}", etype, e))?;
-
-            #[derive(serde::Deserialize)]
-            struct EntityWithEmbedding {
-                id: surrealdb::RecordId,
-                embedding: Vec<f32>,
-            }
-
-            let entities: Vec<EntityWithEmbedding> = response
-                .take(0)
-                .map_err(|e| format!("Failed to parse {} entities: {}", etype, e))?;
-
-            for entity in &entities {
-                let entity_id = entity.id.to_string();
-
-                // Check if snapshot already exists
-                let mut count_response = self
-                    .db
-                    .query(
-                        "SELECT count() AS cnt FROM arc_snapshot WHERE entity_id = $eid GROUP ALL",
-                    )
-                    .bind(("eid", entity.id.clone()))
-                    .await
-                    .map_err(|e| format!("Failed to check snapshots for {}: {}", entity_id, e))?;
-
-                #[derive(serde::Deserialize)]
-                struct CountResult {
-                    cnt: i64,
-                }
-
-                let count: Option<CountResult> = count_response.take(0).unwrap_or(None);
-                let existing = count.map(|c| c.cnt).unwrap_or(0);
-
-                if existing > 0 {
-                    total_skipped += 1;
-                    continue;
-                }
-
-                // Create baseline snapshot (no delta_magnitude for first snapshot)
-                if let Err(e) = self
-                    .db
-                    .query(
-                        "CREATE arc_snapshot SET entity_id = $eid, entity_type = $etype, embedding = $embedding",
-                    )
-                    .bind(("eid", entity.id.clone()))
-                    .bind(("etype", etype.clone()))
-                    .bind(("embedding", entity.embedding.clone()))
-                    .await
-                {
-                    tracing::warn!(
-                        "Failed to create baseline snapshot for {}: {}",
-                        entity_id,
-                        e
-                    );
-                    continue;
-                }
-
-                total_created += 1;
-            }
-        }
+        let counts = crate::services::arc::baseline_arc_snapshots(&self.db, &types_to_process)
+            .await
+            .map_err(|e| format!("Failed to create baseline snapshots: {}", e))?;
+        let (total_created, total_skipped) = (counts.created, counts.skipped);
 
         let content = format!(
             "Baseline arc snapshots: {} created, {} skipped (already had snapshots)",
@@ -401,6 +445,7 @@ impl NarraServer {
         run_themes: bool,
         run_ner: bool,
         concurrency: Option<usize>,
+        incremental: bool,
     ) -> Result<MutationResponse, String> {
         use crate::services::{AnnotationPipeline, PipelineConfig};
 
@@ -418,6 +463,7 @@ impl NarraServer {
             run_themes,
             run_ner,
             concurrency: concurrency.unwrap_or(4),
+            incremental,
         };
 
         let pipeline = AnnotationPipeline::new(
@@ -446,6 +492,13 @@ impl NarraServer {
                 report.emotion_successes, report.theme_successes, report.ner_successes
             ));
 
+            if incremental {
+                content_parts.push(format!(
+                    "\n**Unchanged:** {} model runs skipped because their input had not changed",
+                    report.unchanged
+                ));
+            }
+
             if report.errors > 0 {
                 content_parts.push(format!(
                     "\n**Errors:** {} entities had classifier errors",
@@ -0,0 +1,118 @@
+use crate::mcp::{EntityResult, MutationResponse, NarraServer};
+use crate::models::scene::{remove_event_involvement, set_event_involvement, InvolvementCreate};
+
+impl NarraServer {
+    pub(crate) async fn handle_add_event_participant(
+        &self,
+        event_id: String,
+        character_id: String,
+        role: Option<String>,
+        impact: Option<String>,
+        force: bool,
+    ) -> Result<MutationResponse, String> {
+        let event = event_id.strip_prefix("event:").unwrap_or(&event_id);
+        let character = character_id
+            .strip_prefix("character:")
+            .unwrap_or(&character_id);
+        self.check_final_scenes(
+            &format!("event:{}", event),
+            &format!("Adding a participant to event:{}", event),
+            force,
+        )
+        .await?;
+
+        let involvement = set_event_involvement(
+            &self.db,
+            InvolvementCreate {
+                character_id: character.to_string(),
+                event_id: event.to_string(),
+                role,
+                impact,
+            },
+        )
+        .await
+        .map_err(|e| format!("Failed to add event participant: {}", e))?;
+
+        let role = involvement
+            .role
+            .as_deref()
+            .map(|r| format!(" as {}", r))
+            .unwrap_or_default();
+        let result = EntityResult {
+            id: involvement.id.to_string(),
+            entity_type: "involved_in".to_string(),
+            name: format!("character:{} -> event:{}", character, event),
+            content: format!(
+                "character:{} took part in event:{}{}",
+                character, event, role
+            ),
+            confidence: Some(1.0),
+            last_modified: Some(involvement.created_at.to_string()),
+        };
+
+        let mut hints = Vec::new();
+        if involvement.role.is_none() {
+            hints.push(
+                "Set a role (witness, instigator, ...) to say how they took part".to_string(),
+            );
+        }
+        hints.push(
+            "validate_entity flags knowledge witnessed or overheard here by non-participants"
+                .to_string(),
+        );
+
+        Ok(MutationResponse {
+            entity: result,
+            entities: None,
+            impact: None,
+            hints,
+        })
+    }
+
+    pub(crate) async fn handle_remove_event_participant(
+        &self,
+        event_id: String,
+        character_id: String,
+        force: bool,
+    ) -> Result<MutationResponse, String> {
+        let event = event_id.strip_prefix("event:").unwrap_or(&event_id);
+        let character = character_id
+            .strip_prefix("character:")
+            .unwrap_or(&character_id);
+        self.check_final_scenes(
+            &format!("event:{}", event),
+            &format!("Removing a participant from event:{}", event),
+            force,
+        )
+        .await?;
+
+        let removed = remove_event_involvement(&self.db, character, event)
+            .await
+            .map_err(|e| format!("Failed to remove event participant: {}", e))?;
+        if removed.is_empty() {
+            return Err(format!(
+                "character:{} is not a participant in event:{}",
+                character, event
+            ));
+        }
+
+        let result = EntityResult {
+            id: removed[0].id.to_string(),
+            entity_type: "involved_in".to_string(),
+            name: format!("character:{} -> event:{}", character, event),
+            content: format!("Removed character:{} from event:{}", character, event),
+            confidence: Some(1.0),
+            last_modified: Some(chrono::Utc::now().to_rfc3339()),
+        };
+
+        Ok(MutationResponse {
+            entity: result,
+            entities: None,
+            impact: None,
+            hints: vec![format!(
+                "character:{} no longer took part in event:{}",
+                character, event
+            )],
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::mcp::{EntityResult, MutationResponse, NarraServer};
-use crate::services::{EntityType, PhaseWeights, TemporalService};
+use crate::services::{kmeans, EntityType, PhaseWeights, TemporalService};
 
 fn parse_entity_types(types: Option<Vec<String>>) -> Vec<EntityType> {
     types
@@ -26,6 +26,7 @@ impl NarraServer {
         content_weight: Option<f32>,
         neighborhood_weight: Option<f32>,
         temporal_weight: Option<f32>,
+        seed: Option<u64>,
     ) -> Result<MutationResponse, String> {
         let type_filter = {
             let parsed = parse_entity_types(entity_types);
@@ -49,7 +50,7 @@ impl NarraServer {
             None
         };
 
-        let service = TemporalService::new(self.db.clone());
+        let service = TemporalService::new(self.db.clone()).with_options(kmeans::seeded(seed));
 
         let result = service
             .detect_phases(type_filter, num_phases, weights)
@@ -85,6 +86,7 @@ impl NarraServer {
     }
 
     pub(crate) async fn handle_clear_phases(&self) -> Result<MutationResponse, String> {
+        self.backup_before("clear-phases").await?;
         let service = TemporalService::new(self.db.clone());
 
         let count = service
@@ -106,4 +108,37 @@ impl NarraServer {
             impact: None,
         })
     }
+
+    pub(crate) async fn handle_rename_phase(
+        &self,
+        phase_id: &str,
+        label: &str,
+    ) -> Result<MutationResponse, String> {
+        let service = TemporalService::new(self.db.clone());
+        let phase = service
+            .rename_phase(phase_id, label)
+            .await
+            .map_err(|e| format!("Failed to rename phase: {}", e))?;
+
+        Ok(MutationResponse {
+            entity: EntityResult {
+                id: format!("phase:phase_{}", phase.phase_id),
+                entity_type: "phase".to_string(),
+                name: phase.label.clone(),
+                content: format!(
+                    "Phase {} renamed to \"{}\" (generated label: {})",
+                    phase.phase_id,
+                    phase.label,
+                    phase.auto_label.as_deref().unwrap_or_default()
+                ),
+                confidence: None,
+                last_modified: None,
+            },
+            entities: None,
+            hints: vec![
+                "The label follows this phase's members when phases are re-detected".to_string(),
+            ],
+            impact: None,
+        })
+    }
 }
@@ -0,0 +1,52 @@
+use crate::mcp::{EntityResult, MutationResponse, NarraServer};
+
+impl NarraServer {
+    pub(crate) async fn handle_tag_entity(
+        &self,
+        entity_id: &str,
+        tags: &[String],
+        force: bool,
+    ) -> Result<MutationResponse, String> {
+        self.check_final_scenes(entity_id, &format!("Tagging {}", entity_id), force)
+            .await?;
+        let all = crate::models::tag::add_tags(&self.db, entity_id, tags)
+            .await
+            .map_err(|e| format!("Failed to tag {}: {}", entity_id, e))?;
+        Ok(tag_response(entity_id, all, "Tagged"))
+    }
+
+    pub(crate) async fn handle_untag_entity(
+        &self,
+        entity_id: &str,
+        tags: &[String],
+        force: bool,
+    ) -> Result<MutationResponse, String> {
+        self.check_final_scenes(entity_id, &format!("Untagging {}", entity_id), force)
+            .await?;
+        let remaining = crate::models::tag::remove_tags(&self.db, entity_id, tags)
+            .await
+            .map_err(|e| format!("Failed to untag {}: {}", entity_id, e))?;
+        Ok(tag_response(entity_id, remaining, "Untagged"))
+    }
+}
+
+fn tag_response(entity_id: &str, tags: Vec<String>, verb: &str) -> MutationResponse {
+    let content = if tags.is_empty() {
+        format!("{} {}; no tags left", verb, entity_id)
+    } else {
+        format!("{} {}; tags: {}", verb, entity_id, tags.join(", "))
+    };
+    MutationResponse {
+        entity: EntityResult {
+            id: entity_id.to_string(),
+            entity_type: entity_id.split_once(':').map_or("", |(t, _)| t).to_string(),
+            name: entity_id.to_string(),
+            content,
+            confidence: Some(1.0),
+            last_modified: Some(chrono::Utc::now().to_rfc3339()),
+        },
+        entities: None,
+        impact: None,
+        hints: vec!["Use list_tags to see tag usage across the world".to_string()],
+    }
+}
@@ -9,6 +9,7 @@ mod query_search;
 mod query_validation;
 mod query_vector_ops;
 
+use crate::mcp::sampling::{compress_report, ClientSampler};
 use crate::mcp::NarraServer;
 use crate::mcp::{
     EntityResult, QueryInput, QueryRequest, QueryResponse, SearchMetadataFilter, TruncationInfo,
@@ -18,6 +19,7 @@ use crate::services::{EntityType, FilterOp, MetadataFilter};
 use base64::{engine::general_purpose, Engine as _};
 use rmcp::handler::server::wrapper::Parameters;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 // Cursor data structure (internal, opaque to clients)
 #[derive(Serialize, Deserialize)]
@@ -81,12 +83,48 @@ pub(crate) fn parse_metadata_filter(filter: &SearchMetadataFilter) -> Vec<Metada
             value: serde_json::json!(loc_type),
         });
     }
+    if let Some(kind) = filter.note_kind {
+        filters.push(MetadataFilter {
+            field: "kind".into(),
+            op: FilterOp::Eq,
+            bind_key: "filter_note_kind".into(),
+            value: serde_json::json!(kind),
+        });
+    }
 
     filters
 }
 
-/// Parse entity type strings into EntityType enums, filtering to embeddable types.
-/// Returns empty vec if input is None (callers decide default behavior).
+/// How an over-budget query response is cut down to size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum TruncationStrategy {
+    /// Keep leading results whole, drop the rest.
+    #[default]
+    Drop,
+    /// Shorten every result so more of them fit.
+    Summarize,
+    /// Have the client's model compress composite report sections (MCP
+    /// sampling); summarizes when the client can't sample.
+    Sample,
+}
+
+impl TruncationStrategy {
+    pub(crate) fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.map(|v| v.to_lowercase()).as_deref() {
+            None | Some("drop") => Ok(Self::Drop),
+            Some("summarize") | Some("summarise") => Ok(Self::Summarize),
+            Some("sample") | Some("sampling") => Ok(Self::Sample),
+            Some(other) => Err(format!(
+                "Unknown truncation_strategy '{}'. Use 'drop', 'summarize' or 'sample'.",
+                other
+            )),
+        }
+    }
+}
+
+/// Minimum tokens a summarized result keeps, so compression never degrades to bare names.
+const MIN_SUMMARY_TOKENS: usize = 25;
+
 /// Per-tool-type default token budget. Composite reports get more room,
 /// simple lookups get less. Falls back to DEFAULT_TOKEN_BUDGET for uncategorized ops.
 fn tool_type_budget(request: &QueryRequest) -> usize {
@@ -136,6 +174,7 @@ pub(crate) fn parse_entity_types(types: Option<Vec<String>>) -> Vec<EntityType>
                     "knowledge" => Some(EntityType::Knowledge),
                     "note" => Some(EntityType::Note),
                     "fact" => Some(EntityType::Fact),
+                    "term" => Some(EntityType::Term),
                     _ => None,
                 })
                 .collect()
@@ -146,11 +185,33 @@ pub(crate) fn parse_entity_types(types: Option<Vec<String>>) -> Vec<EntityType>
 impl NarraServer {
     /// Handler for query tool - implementation called from server.rs
     pub async fn handle_query(
+        &self,
+        input: Parameters<QueryInput>,
+    ) -> Result<QueryResponse, String> {
+        self.handle_query_with_sampler(input, None).await
+    }
+
+    /// Handler for query tool, with the client's sampler when it supports
+    /// sampling (used by `truncation_strategy: "sample"`).
+    pub async fn handle_query_with_sampler(
         &self,
         Parameters(input): Parameters<QueryInput>,
+        sampler: Option<ClientSampler>,
     ) -> Result<QueryResponse, String> {
         // Extract per-request budget before consuming input for deserialization
         let request_budget = input.token_budget;
+        let mut strategy = TruncationStrategy::parse(input.truncation_strategy.as_deref())?;
+        let mut sampling_unavailable = false;
+        if strategy == TruncationStrategy::Sample && sampler.is_none() {
+            strategy = TruncationStrategy::Summarize;
+            sampling_unavailable = true;
+        }
+        // Composite reports are built at full detail and compressed afterwards,
+        // unless the caller picked a detail level
+        let sample_detail = |detail_level: Option<String>| match strategy {
+            TruncationStrategy::Sample => detail_level.or(Some("full".to_string())),
+            _ => detail_level,
+        };
 
         // Reconstruct the full request object for deserialization
         let mut full_request = serde_json::Map::new();
@@ -225,9 +286,24 @@ impl NarraServer {
                 )
                 .await
             }
-            QueryRequest::ListNotes { entity_id, limit } => {
-                self.handle_list_notes(entity_id, limit.unwrap_or(50).min(MAX_LIMIT))
-                    .await
+            QueryRequest::ListNotes {
+                entity_id,
+                tag,
+                kind,
+                status,
+                limit,
+            } => {
+                self.handle_list_notes(
+                    entity_id,
+                    tag,
+                    kind,
+                    status,
+                    limit.unwrap_or(50).min(MAX_LIMIT),
+                )
+                .await
+            }
+            QueryRequest::ListTags { entity_id, tag } => {
+                self.handle_list_tags(entity_id, tag).await
             }
             QueryRequest::GetFact { fact_id } => self.handle_get_fact(&fact_id).await,
             QueryRequest::ListFacts {
@@ -296,9 +372,15 @@ impl NarraServer {
                 scope,
                 metrics,
                 limit,
+                phase,
             } => {
-                self.handle_centrality_metrics(scope, metrics, limit.unwrap_or(20).min(MAX_LIMIT))
-                    .await
+                self.handle_centrality_metrics(
+                    scope,
+                    metrics,
+                    limit.unwrap_or(20).min(MAX_LIMIT),
+                    phase,
+                )
+                .await
             }
             QueryRequest::InfluencePropagation {
                 from_character_id,
@@ -315,9 +397,14 @@ impl NarraServer {
             QueryRequest::DramaticIronyReport {
                 character_id,
                 min_scene_threshold,
+                phase,
             } => {
-                self.handle_dramatic_irony_report(character_id, min_scene_threshold.unwrap_or(3))
-                    .await
+                self.handle_dramatic_irony_report(
+                    character_id,
+                    min_scene_threshold.unwrap_or(3),
+                    phase,
+                )
+                .await
             }
             QueryRequest::SemanticJoin {
                 query,
@@ -330,8 +417,10 @@ impl NarraServer {
             QueryRequest::ThematicClustering {
                 entity_types,
                 num_themes,
+                seed,
+                phase,
             } => {
-                self.handle_thematic_clustering(entity_types, num_themes)
+                self.handle_thematic_clustering(entity_types, num_themes, seed, phase)
                     .await
             }
             QueryRequest::SemanticKnowledge {
@@ -492,7 +581,7 @@ impl NarraServer {
             }
             QueryRequest::SituationReport { detail_level } => {
                 self.handle_situation_report(
-                    detail_level,
+                    sample_detail(detail_level),
                     token_budget,
                     crate::services::noop_progress(),
                 )
@@ -504,7 +593,7 @@ impl NarraServer {
             } => {
                 self.handle_character_dossier(
                     &character_id,
-                    detail_level,
+                    sample_detail(detail_level),
                     token_budget,
                     crate::services::noop_progress(),
                 )
@@ -516,7 +605,7 @@ impl NarraServer {
             } => {
                 self.handle_scene_planning(
                     &character_ids,
-                    detail_level,
+                    sample_detail(detail_level),
                     token_budget,
                     crate::services::noop_progress(),
                 )
@@ -600,6 +689,7 @@ impl NarraServer {
                 neighborhood_weight,
                 temporal_weight,
                 save,
+                seed,
             } => {
                 self.handle_detect_phases(
                     entity_types,
@@ -608,6 +698,7 @@ impl NarraServer {
                     neighborhood_weight,
                     temporal_weight,
                     save.unwrap_or(false),
+                    seed,
                 )
                 .await
             }
@@ -634,10 +725,12 @@ impl NarraServer {
             QueryRequest::NarrativeTensions {
                 limit,
                 min_severity,
+                phase,
             } => {
                 self.handle_narrative_tensions(
                     limit.unwrap_or(20).min(MAX_LIMIT),
                     min_severity.unwrap_or(0.0),
+                    phase,
                 )
                 .await
             }
@@ -651,6 +744,7 @@ impl NarraServer {
                 content_weight,
                 neighborhood_weight,
                 temporal_weight,
+                seed,
             } => {
                 self.handle_detect_transitions(
                     entity_types,
@@ -658,27 +752,110 @@ impl NarraServer {
                     content_weight,
                     neighborhood_weight,
                     temporal_weight,
+                    seed,
                 )
                 .await
             }
         }?;
 
+        // Re-measure with the configured token counter; handlers only estimate
+        if !response.results.is_empty() {
+            response.token_estimate = self.estimate_tokens_from_results(&response.results);
+        }
+
+        // Have the client compress composite reports before anything is cut
+        let mut sampled = 0;
+        if let Some(sampler) = sampler.filter(|_| strategy == TruncationStrategy::Sample) {
+            if response.token_estimate > token_budget {
+                sampled = self
+                    .sample_reports(&sampler, &mut response.results, token_budget)
+                    .await;
+                response.token_estimate = self.estimate_tokens_from_results(&response.results);
+            }
+        }
+
         // Apply token budget enforcement if response exceeds limit
         if response.token_estimate > token_budget && !response.results.is_empty() {
-            let (truncated_results, truncation_info) =
-                self.apply_token_budget(response.results, token_budget, "query");
+            let (truncated_results, truncation_info) = match strategy {
+                TruncationStrategy::Drop => {
+                    self.apply_token_budget(response.results, token_budget, "query")
+                }
+                TruncationStrategy::Summarize | TruncationStrategy::Sample => {
+                    self.apply_summarized_budget(response.results, token_budget)
+                }
+            };
 
             response.results = truncated_results;
             response.truncated = truncation_info;
             response.token_estimate = self.estimate_tokens_from_results(&response.results);
+        } else if sampled > 0 {
+            response.truncated = Some(TruncationInfo {
+                reason: "sampled".to_string(),
+                original_count: response.results.len(),
+                returned_count: response.results.len(),
+                suggestion: format!(
+                    "{} report section(s) compressed by the client's model to fit token budget. \
+                     Raise token_budget or pick a detail_level for the uncompressed report.",
+                    sampled
+                ),
+            });
+        }
+        if sampling_unavailable {
+            response.hints.push(
+                "The client doesn't support sampling; results were summarized instead.".to_string(),
+            );
         }
 
         Ok(response)
     }
 
     // Helper methods
+    /// Members of a phase filter, for analyses that accept `phase`.
+    pub(crate) async fn phase_members(
+        &self,
+        phase: Option<usize>,
+    ) -> Result<Option<HashSet<String>>, String> {
+        let Some(phase_id) = phase else {
+            return Ok(None);
+        };
+        let phase = crate::services::TemporalService::new(self.db.clone())
+            .find_phase(phase_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Some(phase.member_ids()))
+    }
+
     fn estimate_tokens_from_results(&self, results: &[EntityResult]) -> usize {
-        results.iter().map(|r| r.content.len() / 4 + 20).sum()
+        results
+            .iter()
+            .map(|r| self.token_counter.count(&r.content) + 20)
+            .sum()
+    }
+
+    /// Compress composite report results with the client's model, each within
+    /// its share of the budget. Returns how many sections were compressed.
+    async fn sample_reports(
+        &self,
+        sampler: &ClientSampler,
+        results: &mut [EntityResult],
+        budget: usize,
+    ) -> usize {
+        let count = results.len().max(1);
+        // Envelope overhead (50), then 20 tokens of framing per result
+        let per_result = (budget.saturating_sub(50) / count).saturating_sub(20);
+        let mut compressed = 0;
+        for result in results.iter_mut().filter(|r| r.entity_type == "report") {
+            let (content, sections) = compress_report(
+                sampler,
+                self.token_counter.as_ref(),
+                &result.content,
+                per_result,
+            )
+            .await;
+            result.content = content;
+            compressed += sections;
+        }
+        compressed
     }
 
     /// Truncate results to fit within token budget while preserving utility.
@@ -700,7 +877,7 @@ impl NarraServer {
         let mut running_tokens = 50; // Response envelope overhead
 
         for result in results.iter() {
-            let result_tokens = result.content.len() / 4 + 20;
+            let result_tokens = self.token_counter.count(&result.content) + 20;
             if running_tokens + result_tokens > budget && !kept_results.is_empty() {
                 break;
             }
@@ -723,6 +900,65 @@ impl NarraServer {
         (kept_results, Some(truncation))
     }
 
+    /// Fit results within token budget by compressing each one via the summary service.
+    /// Falls back to dropping trailing results only if even minimal summaries overflow.
+    fn apply_summarized_budget(
+        &self,
+        results: Vec<EntityResult>,
+        budget: usize,
+    ) -> (Vec<EntityResult>, Option<TruncationInfo>) {
+        let full_tokens = self.estimate_tokens_from_results(&results);
+
+        if full_tokens <= budget {
+            return (results, None);
+        }
+
+        let original_count = results.len();
+        // Envelope overhead (50), shared evenly; per-result framing (20) plus the
+        // ellipsis appended by summarization (1) comes out of each share
+        let per_result = (budget.saturating_sub(50) / original_count)
+            .saturating_sub(21)
+            .max(MIN_SUMMARY_TOKENS);
+
+        let summarized: Vec<EntityResult> = results
+            .into_iter()
+            .map(|mut r| {
+                if self.token_counter.count(&r.content) > per_result {
+                    r.content = self.summary_service.summarize_text(&r.content, per_result);
+                }
+                r
+            })
+            .collect();
+
+        let (kept_results, dropped) = self.apply_token_budget(summarized, budget, "query");
+        let returned_count = kept_results.len();
+
+        let truncation = TruncationInfo {
+            reason: if dropped.is_some() {
+                "token_budget".to_string()
+            } else {
+                "summarized".to_string()
+            },
+            original_count,
+            returned_count,
+            suggestion: if returned_count < original_count {
+                format!(
+                    "Results summarized to ~{} tokens each and reduced from {} to {} to fit token budget. \
+                     Raise token_budget or narrow your query.",
+                    per_result, original_count, returned_count
+                )
+            } else {
+                format!(
+                    "Each result summarized to ~{} tokens to fit token budget. \
+                     Raise token_budget or use lookup for full detail.",
+                    per_result
+                )
+            },
+        };
+
+        (kept_results, Some(truncation))
+    }
+
     /// Prioritize and truncate hints to max 3 items.
     fn truncate_hints(&self, hints: Vec<String>) -> Vec<String> {
         hints.into_iter().take(3).collect()
@@ -781,6 +1017,7 @@ impl NarraServer {
 mod tests {
     use super::*;
     use crate::mcp::SearchMetadataFilter;
+    use crate::models::note::NoteKind;
 
     #[test]
     fn test_parse_empty_filter() {
@@ -822,9 +1059,11 @@ mod tests {
             sequence_min: Some(1),
             sequence_max: Some(100),
             loc_type: Some("castle".to_string()),
+            note_kind: Some(NoteKind::Research),
         };
         let result = parse_metadata_filter(&filter);
-        assert_eq!(result.len(), 5);
+        assert_eq!(result.len(), 6);
+        assert_eq!(result[5].value, serde_json::json!("research"));
     }
 
     #[test]
@@ -834,6 +1073,23 @@ mod tests {
         assert_eq!(parsed.offset, 42);
     }
 
+    #[test]
+    fn test_truncation_strategy_parse() {
+        assert_eq!(
+            TruncationStrategy::parse(None).unwrap(),
+            TruncationStrategy::Drop
+        );
+        assert_eq!(
+            TruncationStrategy::parse(Some("Summarize")).unwrap(),
+            TruncationStrategy::Summarize
+        );
+        assert_eq!(
+            TruncationStrategy::parse(Some("sample")).unwrap(),
+            TruncationStrategy::Sample
+        );
+        assert!(TruncationStrategy::parse(Some("squash")).is_err());
+    }
+
     #[test]
     fn test_invalid_cursor() {
         let result = parse_cursor("not-valid-base64!!!");
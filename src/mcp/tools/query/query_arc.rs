@@ -1,3 +1,4 @@
+use crate::db::query::record_id;
 use crate::mcp::NarraServer;
 use crate::mcp::{EntityResult, QueryResponse};
 use crate::services::arc::ArcService;
@@ -560,12 +561,15 @@ impl NarraServer {
         };
         let char_key = char_ref.strip_prefix("character:").unwrap_or(character_id);
         let fact_key = fact_ref.strip_prefix("knowledge:").unwrap_or(fact_id);
+        let char_record_id = record_id("character", &char_ref).map_err(|e| e.to_string())?;
+        let fact_record_id = record_id("knowledge", &fact_ref).map_err(|e| e.to_string())?;
         let certainty = certainty.unwrap_or("knows");
 
         // Step 2: Fetch character
         let mut char_resp = self
             .db
-            .query(format!("SELECT * FROM {}", char_ref))
+            .query("SELECT * FROM $character")
+            .bind(("character", char_record_id.clone()))
             .await
             .map_err(|e| format!("Failed to fetch character: {}", e))?;
 
@@ -582,14 +586,10 @@ impl NarraServer {
             character_name: Option<String>,
         }
 
-        let fact_query = format!(
-            "SELECT fact, character.name AS character_name FROM {}",
-            fact_ref
-        );
-
         let mut fact_resp = self
             .db
-            .query(&fact_query)
+            .query("SELECT fact, character.name AS character_name FROM $knowledge")
+            .bind(("knowledge", fact_record_id.clone()))
             .await
             .map_err(|e| format!("Failed to fetch fact: {}", e))?;
 
@@ -614,10 +614,10 @@ impl NarraServer {
         };
 
         // Step 4: Fetch current embedding
-        let emb_query = format!("SELECT VALUE embedding FROM {}", char_ref);
         let mut emb_resp = self
             .db
-            .query(&emb_query)
+            .query("SELECT VALUE embedding FROM $character")
+            .bind(("character", char_record_id.clone()))
             .await
             .map_err(|e| format!("Failed to fetch character embedding: {}", e))?;
 
@@ -631,11 +631,13 @@ impl NarraServer {
 
         // Step 5: Build hypothetical composite
         // Fetch relationships
-        let mut rel_result = self.db
-            .query(format!(
-                "SELECT ->relates_to->character.{{name, relationship_type}} AS relationships FROM {}",
-                char_ref
-            ))
+        let mut rel_result = self
+            .db
+            .query(
+                "SELECT ->relates_to->character.{name, relationship_type} AS relationships \
+                 FROM $character",
+            )
+            .bind(("character", char_record_id.clone()))
             .await
             .map_err(|e| format!("Failed to fetch relationships: {}", e))?;
 
@@ -665,10 +667,10 @@ impl NarraServer {
 
         let mut perc_result = self
             .db
-            .query(format!(
-                "SELECT out.name AS target_name, perception FROM perceives WHERE in = {}",
-                char_ref
-            ))
+            .query(
+                "SELECT out.name AS target_name, perception FROM perceives WHERE in = $character",
+            )
+            .bind(("character", char_record_id.clone()))
             .await
             .map_err(|e| format!("Failed to fetch perceptions: {}", e))?;
 
@@ -717,14 +719,13 @@ impl NarraServer {
             .await
             .map_err(|e| format!("Failed to embed fact text: {}", e))?;
 
-        let knowledge_query = format!(
-            "SELECT id, fact, embedding FROM knowledge WHERE character = {} AND embedding IS NOT NONE",
-            char_ref
-        );
-
         let mut knowledge_resp = self
             .db
-            .query(&knowledge_query)
+            .query(
+                "SELECT id, fact, embedding FROM knowledge \
+                 WHERE character = $character AND embedding IS NOT NONE",
+            )
+            .bind(("character", char_record_id.clone()))
             .await
             .map_err(|e| format!("Failed to fetch character knowledge: {}", e))?;
 
@@ -757,15 +758,13 @@ impl NarraServer {
         }
 
         // Step 9: Cascade preview
-        let cascade_query = format!(
-            "SELECT type::string(in) AS observer_id, in.name AS observer_name \
-             FROM perceives WHERE out = {} AND embedding IS NOT NONE",
-            char_ref
-        );
+        let cascade_query = "SELECT type::string(in) AS observer_id, in.name AS observer_name \
+             FROM perceives WHERE out = $character AND embedding IS NOT NONE";
 
         let mut cascade_resp = self
             .db
-            .query(&cascade_query)
+            .query(cascade_query)
+            .bind(("character", char_record_id.clone()))
             .await
             .map_err(|e| format!("Failed to fetch cascade targets: {}", e))?;
 
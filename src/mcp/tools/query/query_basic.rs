@@ -1,5 +1,6 @@
 use crate::mcp::NarraServer;
 use crate::mcp::{DetailLevel, EntityResult, QueryResponse};
+use crate::models::note::{NoteKind, NoteStatus};
 use crate::repository::{EntityRepository, KnowledgeRepository};
 use crate::services::{EntityType, SearchFilter};
 
@@ -432,11 +433,14 @@ impl NarraServer {
     pub(crate) async fn handle_list_notes(
         &self,
         entity_id: Option<String>,
+        tag: Option<String>,
+        kind: Option<NoteKind>,
+        status: Option<NoteStatus>,
         limit: usize,
     ) -> Result<QueryResponse, String> {
         use crate::models::note::{get_entity_notes, list_notes};
 
-        let notes = if let Some(ref eid) = entity_id {
+        let mut notes = if let Some(ref eid) = entity_id {
             // Get notes attached to a specific entity
             get_entity_notes(&self.db, eid)
                 .await
@@ -447,6 +451,16 @@ impl NarraServer {
                 .await
                 .map_err(|e| format!("Failed to list notes: {}", e))?
         };
+        if let Some(ref tag) = tag {
+            let tag = crate::models::tag::normalize_tag(tag).map_err(|e| e.to_string())?;
+            notes.retain(|n| n.tags.contains(&tag));
+        }
+        if let Some(kind) = kind {
+            notes.retain(|n| n.kind == kind);
+        }
+        if let Some(status) = status {
+            notes.retain(|n| n.kind.has_status() && n.status == status);
+        }
 
         let entity_results: Vec<EntityResult> = notes
             .into_iter()
@@ -455,7 +469,13 @@ impl NarraServer {
                 id: n.id.to_string(),
                 entity_type: "note".to_string(),
                 name: n.title.clone(),
-                content: n.body.clone(),
+                content: if n.kind.has_status() {
+                    format!("[{} {}] {}", n.status, n.kind, n.body)
+                } else if n.kind == NoteKind::General {
+                    n.body.clone()
+                } else {
+                    format!("[{}] {}", n.kind, n.body)
+                },
                 confidence: None,
                 last_modified: Some(n.updated_at.to_string()),
             })
@@ -489,6 +509,90 @@ impl NarraServer {
         })
     }
 
+    pub(crate) async fn handle_list_tags(
+        &self,
+        entity_id: Option<String>,
+        tag: Option<String>,
+    ) -> Result<QueryResponse, String> {
+        use crate::models::tag::{get_tags, tag_counts, tagged_entities};
+
+        let (results, hints) = if let Some(tag) = tag {
+            let entities = tagged_entities(&self.db, &tag)
+                .await
+                .map_err(|e| format!("Failed to list tagged entities: {}", e))?;
+            let results: Vec<EntityResult> = entities
+                .into_iter()
+                .map(|e| {
+                    let id = e.id.to_string();
+                    EntityResult {
+                        entity_type: id.split_once(':').map_or("", |(t, _)| t).to_string(),
+                        id,
+                        name: e.title.clone(),
+                        content: format!("{} (tagged {})", e.title, tag),
+                        confidence: None,
+                        last_modified: None,
+                    }
+                })
+                .collect();
+            let hints = vec![format!("{} entities tagged '{}'", results.len(), tag)];
+            (results, hints)
+        } else if let Some(entity_id) = entity_id {
+            let tags = get_tags(&self.db, &entity_id)
+                .await
+                .map_err(|e| format!("Failed to get tags: {}", e))?;
+            let content = if tags.is_empty() {
+                "No tags".to_string()
+            } else {
+                tags.join(", ")
+            };
+            let result = EntityResult {
+                id: entity_id.clone(),
+                entity_type: entity_id.split_once(':').map_or("", |(t, _)| t).to_string(),
+                name: entity_id.clone(),
+                content,
+                confidence: None,
+                last_modified: None,
+            };
+            let hints = vec!["Use tag_entity/untag_entity to change tags".to_string()];
+            (vec![result], hints)
+        } else {
+            let counts = tag_counts(&self.db)
+                .await
+                .map_err(|e| format!("Failed to count tags: {}", e))?;
+            let results: Vec<EntityResult> = counts
+                .iter()
+                .map(|c| EntityResult {
+                    id: format!("tag:{}", c.tag),
+                    entity_type: "tag".to_string(),
+                    name: c.tag.clone(),
+                    content: format!(
+                        "{} scenes, {} events, {} notes",
+                        c.scenes, c.events, c.notes
+                    ),
+                    confidence: None,
+                    last_modified: None,
+                })
+                .collect();
+            let hints = vec![
+                format!("{} tags in use, most used first", results.len()),
+                "Pass tag to list the scenes, events, and notes carrying it".to_string(),
+            ];
+            (results, hints)
+        };
+
+        let total = results.len();
+        let token_estimate = results.iter().map(|r| r.content.len() / 4 + 20).sum();
+
+        Ok(QueryResponse {
+            results,
+            total,
+            next_cursor: None,
+            hints,
+            token_estimate,
+            truncated: None,
+        })
+    }
+
     pub(crate) async fn handle_get_fact(&self, fact_id: &str) -> Result<QueryResponse, String> {
         use crate::models::fact::get_fact;
 
@@ -513,7 +617,7 @@ impl NarraServer {
             .collect::<Vec<_>>()
             .join(", ");
 
-        let content = format!(
+        let mut content = format!(
             "**{}**\n\n{}\n\n**Categories:** {}\n**Enforcement:** {:?}",
             fact.title,
             fact.description,
@@ -524,6 +628,15 @@ impl NarraServer {
             },
             fact.enforcement_level
         );
+        if !fact.exceptions.is_empty() {
+            content.push_str("\n**Exceptions:**");
+            for exception in &fact.exceptions {
+                content.push_str(&format!(
+                    "\n- {}: {}",
+                    exception.entity_id, exception.reason
+                ));
+            }
+        }
 
         let token_estimate = content.len() / 4 + 30;
 
@@ -432,6 +432,36 @@ impl NarraServer {
             }
         }
 
+        // Goals and the conflicts between them
+        if !dossier.goals.is_empty() {
+            content_parts.push(format!("\n## Goals ({})", dossier.goals.len()));
+            for g in &dossier.goals {
+                let mut line = format!("- [{}] **{}**", g.status, g.want);
+                if let Some(need) = &g.need {
+                    line.push_str(&format!(" | needs: {}", need));
+                }
+                if let Some(stakes) = &g.stakes {
+                    line.push_str(&format!(" | stakes: {}", stakes));
+                }
+                if let Some(obstacle) = &g.obstacle {
+                    line.push_str(&format!(" | obstacle: {}", obstacle));
+                }
+                content_parts.push(line);
+            }
+        }
+        if !dossier.goal_conflicts.is_empty() {
+            content_parts.push(format!(
+                "\n## Goal Conflicts ({})",
+                dossier.goal_conflicts.len()
+            ));
+            for c in &dossier.goal_conflicts {
+                content_parts.push(format!(
+                    "- {} ({} shared scenes)",
+                    c.description, c.shared_scenes
+                ));
+            }
+        }
+
         // Emotion profile (ML)
         if let Some(ref emotions) = dossier.emotion_profile {
             content_parts.push(format!(
@@ -642,6 +672,34 @@ impl NarraServer {
             }
         }
 
+        // What each character wants, and where those wants collide
+        if !plan.goals.is_empty() {
+            content_parts.push("\n## Goals".to_string());
+            for g in &plan.goals {
+                match &g.obstacle {
+                    Some(o) => content_parts.push(format!(
+                        "- **{}** wants {} (obstacle: {})",
+                        g.character_name, g.want, o
+                    )),
+                    None => {
+                        content_parts.push(format!("- **{}** wants {}", g.character_name, g.want))
+                    }
+                }
+            }
+        }
+        if !plan.goal_conflicts.is_empty() {
+            content_parts.push(format!(
+                "\n## Goal Conflicts ({})",
+                plan.goal_conflicts.len()
+            ));
+            for c in &plan.goal_conflicts {
+                content_parts.push(format!(
+                    "- **{} ↔ {}** [{}]: {}",
+                    c.character, c.other_character, c.kind, c.description
+                ));
+            }
+        }
+
         // Character roles in this scene
         if !plan.character_roles.is_empty() {
             content_parts.push("\n## Character Roles".to_string());
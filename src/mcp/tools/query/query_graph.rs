@@ -149,11 +149,13 @@ impl NarraServer {
         scope: Option<String>,
         metrics: Option<Vec<String>>,
         limit: usize,
+        phase: Option<usize>,
     ) -> Result<QueryResponse, String> {
         use crate::services::{CentralityMetric, GraphAnalyticsService};
 
         // Create GraphAnalyticsService
-        let graph_service = GraphAnalyticsService::new(self.db.clone());
+        let members = self.phase_members(phase).await?;
+        let graph_service = GraphAnalyticsService::new(self.db.clone()).with_members(members);
 
         // Parse metrics strings to CentralityMetric enum (default to All)
         let metric_list = if let Some(m) = metrics {
@@ -223,7 +225,8 @@ impl NarraServer {
         use crate::services::InfluenceService;
 
         // Create InfluenceService
-        let influence_service = InfluenceService::new(self.db.clone());
+        let influence_service =
+            InfluenceService::with_ontology(self.db.clone(), self.ontology.clone());
 
         // Call trace_propagation or trace_knowledge_propagation based on knowledge_fact
         let propagation_result = if let Some(ref fact) = knowledge_fact {
@@ -249,10 +252,13 @@ impl NarraServer {
                     if i == 0 {
                         path_str.push_str(&step.character_name);
                     } else {
-                        path_str.push_str(&format!(
-                            " -> ({}) -> {}",
-                            step.relationship_type, step.character_name
-                        ));
+                        let edge = match step.trust {
+                            Some(trust) => {
+                                format!("{}, trust {:.2}", step.relationship_type, trust)
+                            }
+                            None => step.relationship_type.clone(),
+                        };
+                        path_str.push_str(&format!(" -> ({}) -> {}", edge, step.character_name));
                     }
                 }
                 path_str.push_str(&format!(" | Strength: {}", path.path_strength));
@@ -5,6 +5,7 @@
 
 use serde::Deserialize;
 
+use crate::db::query::parse_record_id;
 use crate::mcp::types::MAX_LIMIT;
 use crate::mcp::{EntityResult, NarraServer, QueryResponse};
 use crate::utils::sanitize::validate_entity_id;
@@ -119,6 +120,7 @@ impl NarraServer {
         character_id: &str,
     ) -> Result<QueryResponse, String> {
         validate_entity_id(character_id).map_err(|e| e.to_string())?;
+        let character_ref = parse_record_id(character_id).map_err(|e| e.to_string())?;
 
         // Get character name
         #[derive(Deserialize)]
@@ -126,10 +128,10 @@ impl NarraServer {
             name: String,
         }
 
-        let name_query = format!("SELECT name FROM {} LIMIT 1", character_id);
         let mut resp = self
             .db
-            .query(&name_query)
+            .query("SELECT name FROM $character LIMIT 1")
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Failed to look up character: {}", e))?;
         let name_row: Option<NameRow> = resp
@@ -148,15 +150,13 @@ impl NarraServer {
             truth_value: Option<String>,
         }
 
-        let knowledge_query = format!(
-            "SELECT meta::id(out) AS target, certainty, out.fact AS fact, \
+        let knowledge_query = "SELECT meta::id(out) AS target, certainty, out.fact AS fact, \
              truth_value, learned_at \
-             FROM knows WHERE in = {} ORDER BY learned_at DESC",
-            character_id
-        );
+             FROM knows WHERE in = $character ORDER BY learned_at DESC";
         let mut resp = self
             .db
-            .query(&knowledge_query)
+            .query(knowledge_query)
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Knowledge query failed: {}", e))?;
         let known: Vec<KnowledgeRow> = resp
@@ -319,6 +319,7 @@ impl NarraServer {
     ) -> Result<QueryResponse, String> {
         let limit = limit.unwrap_or(20).min(MAX_LIMIT);
         validate_entity_id(character_id).map_err(|e| e.to_string())?;
+        let character_ref = parse_record_id(character_id).map_err(|e| e.to_string())?;
 
         // Get character name
         #[derive(Deserialize)]
@@ -326,10 +327,10 @@ impl NarraServer {
             name: String,
         }
 
-        let name_query = format!("SELECT name FROM {} LIMIT 1", character_id);
         let mut resp = self
             .db
-            .query(&name_query)
+            .query("SELECT name FROM $character LIMIT 1")
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Failed to look up character: {}", e))?;
         let name_row: Option<NameRow> = resp
@@ -352,12 +353,13 @@ impl NarraServer {
         let out_query = format!(
             "SELECT meta::id(out) AS other, out.name AS other_name, \
              type AS rel_type, subtype, label \
-             FROM relates_to WHERE in = {} LIMIT {}",
-            character_id, limit
+             FROM relates_to WHERE in = $character LIMIT {}",
+            limit
         );
         let mut resp = self
             .db
             .query(&out_query)
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Outgoing relationships query failed: {}", e))?;
         let outgoing: Vec<RelRow> = resp
@@ -368,12 +370,13 @@ impl NarraServer {
         let in_query = format!(
             "SELECT meta::id(in) AS other, in.name AS other_name, \
              type AS rel_type, subtype, label \
-             FROM relates_to WHERE out = {} LIMIT {}",
-            character_id, limit
+             FROM relates_to WHERE out = $character LIMIT {}",
+            limit
         );
         let mut resp = self
             .db
             .query(&in_query)
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Incoming relationships query failed: {}", e))?;
         let incoming: Vec<RelRow> = resp
@@ -388,14 +391,12 @@ impl NarraServer {
             feelings: Option<String>,
         }
 
-        let perc_query = format!(
-            "SELECT meta::id(out) AS other, tension_level, feelings \
-             FROM perceives WHERE in = {}",
-            character_id
-        );
+        let perc_query = "SELECT meta::id(out) AS other, tension_level, feelings \
+             FROM perceives WHERE in = $character";
         let mut resp = self
             .db
-            .query(&perc_query)
+            .query(perc_query)
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Perceptions query failed: {}", e))?;
         let perceptions: Vec<PercRow> = resp
@@ -665,8 +666,10 @@ impl NarraServer {
         validate_entity_id(entity_id).map_err(|e| e.to_string())?;
         if !self.emotion_service.is_available() {
             return Err(
-                "Emotion classifier is not available. The GoEmotions model may not be downloaded."
-                    .to_string(),
+                format!(
+                "Emotion classifier is not available. The GoEmotions model may not be downloaded. {}",
+                crate::embedding::hub::unavailable_hint()
+            ),
             );
         }
 
@@ -681,9 +684,10 @@ impl NarraServer {
 
         let mut resp = self
             .db
-            .query(format!(
-                "SELECT composite_text, name, title, fact FROM {} LIMIT 1",
-                entity_id
+            .query("SELECT composite_text, name, title, fact FROM $ref LIMIT 1")
+            .bind((
+                "ref",
+                parse_record_id(entity_id).map_err(|e| e.to_string())?,
             ))
             .await
             .map_err(|e| format!("Entity lookup failed: {}", e))?;
@@ -758,10 +762,10 @@ impl NarraServer {
     ) -> Result<QueryResponse, String> {
         validate_entity_id(entity_id).map_err(|e| e.to_string())?;
         if !self.ner_service.is_available() {
-            return Err(
-                "NER model is not available. The bert-base-NER model may not be downloaded."
-                    .to_string(),
-            );
+            return Err(format!(
+                "NER model is not available. The bert-base-NER model may not be downloaded. {}",
+                crate::embedding::hub::unavailable_hint()
+            ));
         }
 
         // Fetch composite text for the entity
@@ -776,9 +780,10 @@ impl NarraServer {
 
         let mut resp = self
             .db
-            .query(format!(
-                "SELECT composite_text, name, title, fact, description FROM {} LIMIT 1",
-                entity_id
+            .query("SELECT composite_text, name, title, fact, description FROM $ref LIMIT 1")
+            .bind((
+                "ref",
+                parse_record_id(entity_id).map_err(|e| e.to_string())?,
             ))
             .await
             .map_err(|e| format!("Entity lookup failed: {}", e))?;
@@ -875,10 +880,10 @@ impl NarraServer {
     ) -> Result<QueryResponse, String> {
         validate_entity_id(entity_id).map_err(|e| e.to_string())?;
         if !self.theme_service.is_available() {
-            return Err(
-                "Theme classifier is not available. The NLI model may not be downloaded."
-                    .to_string(),
-            );
+            return Err(format!(
+                "Theme classifier is not available. The NLI model may not be downloaded. {}",
+                crate::embedding::hub::unavailable_hint()
+            ));
         }
 
         // Fetch composite text for the entity
@@ -893,9 +898,10 @@ impl NarraServer {
 
         let mut resp = self
             .db
-            .query(format!(
-                "SELECT composite_text, name, title, fact, description FROM {} LIMIT 1",
-                entity_id
+            .query("SELECT composite_text, name, title, fact, description FROM $ref LIMIT 1")
+            .bind((
+                "ref",
+                parse_record_id(entity_id).map_err(|e| e.to_string())?,
             ))
             .await
             .map_err(|e| format!("Entity lookup failed: {}", e))?;
@@ -973,8 +979,10 @@ impl NarraServer {
         &self,
         limit: usize,
         min_severity: f32,
+        phase: Option<usize>,
     ) -> Result<QueryResponse, String> {
-        let service = crate::services::TensionService::new(self.db.clone());
+        let members = self.phase_members(phase).await?;
+        let service = crate::services::TensionService::new(self.db.clone()).with_members(members);
         let report = service
             .detect_tensions(limit, min_severity)
             .await
@@ -1145,6 +1153,7 @@ impl NarraServer {
         character_id: &str,
     ) -> Result<QueryResponse, String> {
         validate_entity_id(character_id).map_err(|e| e.to_string())?;
+        let character_ref = parse_record_id(character_id).map_err(|e| e.to_string())?;
         // Get character details
         #[derive(Deserialize)]
         struct CharacterRow {
@@ -1154,13 +1163,10 @@ impl NarraServer {
             profile: Option<std::collections::HashMap<String, Vec<String>>>,
         }
 
-        let char_query = format!(
-            "SELECT name, roles, description, profile FROM {} LIMIT 1",
-            character_id
-        );
         let mut resp = self
             .db
-            .query(&char_query)
+            .query("SELECT name, roles, description, profile FROM $character LIMIT 1")
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Character lookup failed: {}", e))?;
         let character: Option<CharacterRow> = resp
@@ -1177,14 +1183,12 @@ impl NarraServer {
             count: usize,
         }
 
-        let certainty_query = format!(
-            "SELECT certainty, count() AS count FROM knows \
-             WHERE in = {} GROUP BY certainty",
-            character_id
-        );
+        let certainty_query = "SELECT certainty, count() AS count FROM knows \
+             WHERE in = $character GROUP BY certainty";
         let mut resp = self
             .db
-            .query(&certainty_query)
+            .query(certainty_query)
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Knowledge certainty query failed: {}", e))?;
         let certainty_dist: Vec<CertaintyCount> = resp.take(0).unwrap_or_default();
@@ -1197,14 +1201,12 @@ impl NarraServer {
             tension_level: Option<i32>,
         }
 
-        let perc_query = format!(
-            "SELECT out.name AS target_name, feelings, tension_level \
-             FROM perceives WHERE in = {} LIMIT 10",
-            character_id
-        );
+        let perc_query = "SELECT out.name AS target_name, feelings, tension_level \
+             FROM perceives WHERE in = $character LIMIT 10";
         let mut resp = self
             .db
-            .query(&perc_query)
+            .query(perc_query)
+            .bind(("character", character_ref.clone()))
             .await
             .map_err(|e| format!("Perception query failed: {}", e))?;
         let perceptions: Vec<PercOutRow> = resp.take(0).unwrap_or_default();
@@ -1,6 +1,7 @@
 use crate::mcp::NarraServer;
 use crate::mcp::{EntityResult, QueryResponse};
 use crate::models::knowledge::find_knowledge_conflicts;
+use crate::services::access::ANY_TYPE;
 use crate::services::EntityType;
 use crate::utils::math::cosine_similarity;
 
@@ -11,11 +12,13 @@ impl NarraServer {
         &self,
         character_id: Option<String>,
         min_scene_threshold: usize,
+        phase: Option<usize>,
     ) -> Result<QueryResponse, String> {
         use crate::services::IronyService;
 
         // Create IronyService
-        let irony_service = IronyService::new(self.db.clone());
+        let members = self.phase_members(phase).await?;
+        let irony_service = IronyService::new(self.db.clone()).with_members(members);
 
         // Call generate_report
         let report = irony_service
@@ -174,11 +177,16 @@ impl NarraServer {
         &self,
         entity_types: Option<Vec<String>>,
         num_themes: Option<usize>,
+        seed: Option<u64>,
+        phase: Option<usize>,
     ) -> Result<QueryResponse, String> {
-        use crate::services::ClusteringService;
+        use crate::services::{kmeans, ClusteringService};
 
         // Create ClusteringService
-        let clustering_service = ClusteringService::new(self.db.clone());
+        let members = self.phase_members(phase).await?;
+        let clustering_service = ClusteringService::new(self.db.clone())
+            .with_options(kmeans::seeded(seed))
+            .with_members(members);
 
         // Convert entity_types strings to EntityType enum (default to embeddable)
         let type_filter = {
@@ -699,9 +707,19 @@ impl NarraServer {
                     .map(|tv| format!(" (actual truth: {})", tv))
                     .unwrap_or_default();
 
+                let derivation_info = if state.derivation.is_empty() {
+                    String::new()
+                } else {
+                    format!(". Derived: {}", state.derivation.join(" <- "))
+                };
+
                 let content = format!(
-                    "Character {} believes wrongly about {} with certainty {:?}{}",
-                    state.character_id, conflict.target, state.certainty, truth_info
+                    "Character {} believes wrongly about {} with certainty {:?}{}{}",
+                    state.character_id,
+                    conflict.target,
+                    state.certainty,
+                    truth_info,
+                    derivation_info
                 );
 
                 entity_results.push(EntityResult {
@@ -752,6 +770,7 @@ impl NarraServer {
     // Narrative Phase Detection
     // ========================================================================
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn handle_detect_phases(
         &self,
         entity_types: Option<Vec<String>>,
@@ -760,8 +779,9 @@ impl NarraServer {
         neighborhood_weight: Option<f32>,
         temporal_weight: Option<f32>,
         save: bool,
+        seed: Option<u64>,
     ) -> Result<QueryResponse, String> {
-        use crate::services::{PhaseWeights, TemporalService};
+        use crate::services::{kmeans, PhaseWeights, TemporalService};
 
         let type_filter = {
             let parsed = parse_entity_types(entity_types);
@@ -785,7 +805,12 @@ impl NarraServer {
             None
         };
 
-        let service = TemporalService::new(self.db.clone());
+        if save {
+            // Saving replaces every phase, a world-wide write like `save_phases`
+            self.access.check(ANY_TYPE).map_err(|e| e.to_string())?;
+        }
+
+        let service = TemporalService::new(self.db.clone()).with_options(kmeans::seeded(seed));
         let result = service
             .detect_phases(type_filter, num_phases, weights)
             .await
@@ -1003,8 +1028,9 @@ impl NarraServer {
         content_weight: Option<f32>,
         neighborhood_weight: Option<f32>,
         temporal_weight: Option<f32>,
+        seed: Option<u64>,
     ) -> Result<QueryResponse, String> {
-        use crate::services::{PhaseWeights, TemporalService};
+        use crate::services::{kmeans, PhaseWeights, TemporalService};
 
         let type_filter = {
             let parsed = parse_entity_types(entity_types);
@@ -1028,7 +1054,7 @@ impl NarraServer {
             None
         };
 
-        let service = TemporalService::new(self.db.clone());
+        let service = TemporalService::new(self.db.clone()).with_options(kmeans::seeded(seed));
         let result = service
             .detect_transitions(type_filter, num_phases, weights)
             .await
@@ -358,6 +358,7 @@ impl NarraServer {
                     EntityType::Knowledge => ("knowledge", "fact"),
                     EntityType::Note => ("note", "title"),
                     EntityType::Fact => ("fact", "title"),
+                    EntityType::Term => ("term", "name"),
                 };
                 let table = table.to_string();
                 let name_field = name_field.to_string();
@@ -200,7 +200,7 @@ impl NarraServer {
             .await
             .map_err(|e| format!("Impact analysis failed: {}", e))?;
 
-        let severity = if analysis.has_protected_impact {
+        let severity = if analysis.has_protected_impact || analysis.is_locked() {
             "Critical"
         } else if analysis.total_affected > 10 {
             "High"
@@ -241,12 +241,18 @@ impl NarraServer {
                     .to_string(),
             );
         }
+        if analysis.is_locked() {
+            hints.push(format!(
+                "CRITICAL: {} scene(s) marked final would be affected. Update and delete need force=true.",
+                analysis.final_scenes.len()
+            ));
+        }
         if analysis.total_affected > 10 {
             hints.push(
                 "Consider breaking this change into smaller, incremental mutations.".to_string(),
             );
         }
-        if !analysis.has_protected_impact && analysis.total_affected < 3 {
+        if !analysis.has_protected_impact && !analysis.is_locked() && analysis.total_affected < 3 {
             hints.push("Low impact change. Safe to proceed.".to_string());
         }
 
@@ -92,9 +92,10 @@ impl NarraServer {
         Parameters(_request): Parameters<SessionContextRequest>,
     ) -> Result<SessionContextResponse, String> {
         // Generate startup context
-        let startup_info = generate_startup_context(&self.session_manager, &self.db)
-            .await
-            .map_err(|e| format!("Failed to generate session context: {}", e))?;
+        let startup_info =
+            generate_startup_context(&self.session_manager, &self.db, self.token_counter.as_ref())
+                .await
+                .map_err(|e| format!("Failed to generate session context: {}", e))?;
 
         // Convert to response format
         Ok(SessionContextResponse {
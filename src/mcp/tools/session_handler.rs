@@ -1,10 +1,12 @@
 //! Consolidated session tool handler (pin/unpin + get_session_context).
 
+use crate::mcp::subscriptions::SESSION_CONTEXT_URI;
 use crate::mcp::{
-    HotEntityInfo, NarraServer, PendingDecisionInfo as PendingDecisionInfoType, PinResult,
+    HotEntityInfo, JournalEntryInfo, NarraServer, OpenTodoInfo,
+    PendingDecisionInfo as PendingDecisionInfoType, PinResult, PinnedEntityInfo,
     SessionContextData, SessionInput, SessionRequest, SessionResponse, WorldOverviewInfo,
 };
-use crate::session::generate_startup_context;
+use crate::session::{generate_startup_context, PinPriority};
 use rmcp::handler::server::wrapper::Parameters;
 
 impl NarraServer {
@@ -33,8 +35,15 @@ impl NarraServer {
                     hints: vec![],
                 })
             }
-            SessionRequest::PinEntity { entity_id } => {
-                let result = self.handle_pin_entity_session(&entity_id).await?;
+            SessionRequest::PinEntity {
+                entity_id,
+                priority,
+                until,
+            } => {
+                let result = self
+                    .handle_pin_entity_session(&entity_id, priority, until)
+                    .await?;
+                self.subscriptions.touch(SESSION_CONTEXT_URI).await;
                 Ok(SessionResponse {
                     operation: "pin_entity".to_string(),
                     context: None,
@@ -42,8 +51,32 @@ impl NarraServer {
                     hints: vec![format!("Entity '{}' pinned to working context", entity_id)],
                 })
             }
+            SessionRequest::Log {
+                message,
+                entity_ids,
+            } => {
+                if message.trim().is_empty() {
+                    return Err("Journal entry cannot be empty".to_string());
+                }
+                let entry = self
+                    .session_manager
+                    .log_entry(message.trim(), entity_ids)
+                    .await;
+                self.session_manager
+                    .save()
+                    .await
+                    .map_err(|e| format!("Failed to save session: {}", e))?;
+                self.subscriptions.touch(SESSION_CONTEXT_URI).await;
+                Ok(SessionResponse {
+                    operation: "log".to_string(),
+                    context: None,
+                    pin_result: None,
+                    hints: vec![format!("Logged: {}", entry.message)],
+                })
+            }
             SessionRequest::UnpinEntity { entity_id } => {
                 let result = self.handle_unpin_entity_session(&entity_id).await?;
+                self.subscriptions.touch(SESSION_CONTEXT_URI).await;
                 Ok(SessionResponse {
                     operation: "unpin_entity".to_string(),
                     context: None,
@@ -58,9 +91,10 @@ impl NarraServer {
     }
 
     async fn handle_get_context_session(&self) -> Result<SessionContextData, String> {
-        let startup_info = generate_startup_context(&self.session_manager, &self.db)
-            .await
-            .map_err(|e| format!("Failed to generate session context: {}", e))?;
+        let startup_info =
+            generate_startup_context(&self.session_manager, &self.db, self.token_counter.as_ref())
+                .await
+                .map_err(|e| format!("Failed to generate session context: {}", e))?;
 
         Ok(SessionContextData {
             verbosity: startup_info.verbosity.to_string(),
@@ -93,16 +127,91 @@ impl NarraServer {
                 scene_count: o.scene_count,
                 relationship_count: o.relationship_count,
             }),
+            recent_journal: startup_info
+                .recent_journal
+                .into_iter()
+                .map(|e| JournalEntryInfo {
+                    message: e.message,
+                    age: e.age,
+                    entities: e.entities,
+                })
+                .collect(),
+            open_todos: startup_info
+                .open_todos
+                .into_iter()
+                .map(|t| OpenTodoInfo {
+                    id: t.id,
+                    title: t.title,
+                    age: t.age,
+                })
+                .collect(),
+            pinned: startup_info
+                .pinned
+                .into_iter()
+                .map(|p| PinnedEntityInfo {
+                    id: p.id,
+                    name: p.name,
+                    priority: p.priority,
+                    until: p.until,
+                })
+                .collect(),
+            expired_pins: startup_info.expired_pins,
+            suggested_pins: startup_info
+                .suggested_pins
+                .into_iter()
+                .map(|e| HotEntityInfo {
+                    id: e.id,
+                    name: e.name,
+                    entity_type: e.entity_type,
+                    last_accessed: e.last_accessed,
+                })
+                .collect(),
+            always_included: startup_info
+                .always_included
+                .into_iter()
+                .map(|e| HotEntityInfo {
+                    id: e.id,
+                    name: e.name,
+                    entity_type: e.entity_type,
+                    last_accessed: e.last_accessed,
+                })
+                .collect(),
+            neglected: startup_info
+                .neglected
+                .into_iter()
+                .map(|e| HotEntityInfo {
+                    entity_type: e.id.split(':').next().unwrap_or_default().to_string(),
+                    name: e.name.unwrap_or_default(),
+                    id: e.id,
+                    last_accessed: e.last_accessed,
+                })
+                .collect(),
+            scheduled_run: startup_info.scheduled_run,
+            trimmed: startup_info.trimmed,
         })
     }
 
-    async fn handle_pin_entity_session(&self, entity_id: &str) -> Result<PinResult, String> {
-        self.session_manager.pin_entity(entity_id).await;
+    async fn handle_pin_entity_session(
+        &self,
+        entity_id: &str,
+        priority: PinPriority,
+        until: Option<String>,
+    ) -> Result<PinResult, String> {
+        if let Some(event) = &until {
+            if !event.starts_with("event:") {
+                return Err(format!("until must be an event ID, got '{}'", event));
+            }
+        }
+        self.session_manager
+            .pin_entity_with(entity_id, priority, until.clone())
+            .await;
         let pinned = self.session_manager.get_pinned().await;
         Ok(PinResult {
             success: true,
             entity_id: entity_id.to_string(),
             pinned_count: pinned.len(),
+            priority: Some(priority),
+            until,
         })
     }
 
@@ -113,6 +222,8 @@ impl NarraServer {
             success: true,
             entity_id: entity_id.to_string(),
             pinned_count: pinned.len(),
+            priority: None,
+            until: None,
         })
     }
 }
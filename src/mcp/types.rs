@@ -2,6 +2,9 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::models::note::{NoteKind, NoteStatus};
+use crate::session::{PinPriority, ScheduledRunInfo};
+
 /// Maximum allowed limit for result counts (prevents unbounded queries).
 pub const MAX_LIMIT: usize = 500;
 
@@ -84,9 +87,26 @@ pub enum QueryRequest {
         /// Filter to notes attached to this entity
         #[serde(default)]
         entity_id: Option<String>,
+        /// Filter to notes carrying this tag
+        #[serde(default)]
+        tag: Option<String>,
+        /// Filter by kind: general, research, todo, critique
+        #[serde(default)]
+        kind: Option<NoteKind>,
+        /// Filter todos and critiques by status: open, resolved, dismissed
+        #[serde(default)]
+        status: Option<NoteStatus>,
         #[serde(default)]
         limit: Option<usize>,
     },
+    /// Tag usage counts; the tags on `entity_id`; or, with `tag`, the scenes,
+    /// events, and notes carrying it.
+    ListTags {
+        #[serde(default)]
+        entity_id: Option<String>,
+        #[serde(default)]
+        tag: Option<String>,
+    },
     /// Get a specific universe fact by ID.
     GetFact { fact_id: String },
     /// List universe facts with optional filters.
@@ -170,6 +190,9 @@ pub enum QueryRequest {
         metrics: Option<Vec<String>>,
         #[serde(default)]
         limit: Option<usize>,
+        /// Only consider members of this narrative phase (see detect_phases)
+        #[serde(default)]
+        phase: Option<usize>,
     },
     /// Trace how information could propagate through the character network.
     /// Follows directed relationship paths to find who could learn something.
@@ -187,6 +210,9 @@ pub enum QueryRequest {
         character_id: Option<String>,
         #[serde(default)]
         min_scene_threshold: Option<usize>,
+        /// Only consider members of this narrative phase (see detect_phases)
+        #[serde(default)]
+        phase: Option<usize>,
     },
     /// Cross-field semantic search across entity types.
     /// Free-form queries like "characters whose desires conflict with Alice's wounds".
@@ -204,6 +230,12 @@ pub enum QueryRequest {
         entity_types: Option<Vec<String>>,
         #[serde(default)]
         num_themes: Option<usize>,
+        /// RNG seed for k-means; the same seed always gives the same result
+        #[serde(default)]
+        seed: Option<u64>,
+        /// Only consider members of this narrative phase (see detect_phases)
+        #[serde(default)]
+        phase: Option<usize>,
     },
     /// Search character knowledge by meaning.
     /// Finds knowledge entities semantically similar to the query.
@@ -571,6 +603,9 @@ pub enum QueryRequest {
         /// Persist detected phases to database for instant loading
         #[serde(default)]
         save: Option<bool>,
+        /// RNG seed for k-means; the same seed always gives the same result
+        #[serde(default)]
+        seed: Option<u64>,
     },
     /// Load previously saved phases from database (returns nothing if none saved).
     LoadPhases,
@@ -622,6 +657,9 @@ pub enum QueryRequest {
         /// Minimum severity threshold 0.0–1.0 (default: 0.0)
         #[serde(default)]
         min_severity: Option<f32>,
+        /// Only consider members of this narrative phase (see detect_phases)
+        #[serde(default)]
+        phase: Option<usize>,
     },
     /// Infer narrative roles for characters based on graph topology,
     /// knowledge patterns, relationship types, and profile traits.
@@ -651,6 +689,9 @@ pub enum QueryRequest {
         /// Temporal weight 0.0–1.0 (default: 0.15)
         #[serde(default)]
         temporal_weight: Option<f32>,
+        /// RNG seed for k-means; the same seed always gives the same result
+        #[serde(default)]
+        seed: Option<u64>,
     },
 }
 
@@ -691,6 +732,9 @@ pub struct SearchMetadataFilter {
     /// Filter locations by type (e.g. "castle", "forest")
     #[serde(default)]
     pub loc_type: Option<String>,
+    /// Filter notes by kind (e.g. "research")
+    #[serde(default)]
+    pub note_kind: Option<NoteKind>,
 }
 
 /// Spec for a character in batch creation.
@@ -742,6 +786,9 @@ pub struct EventSpec {
     pub date: Option<String>,
     #[serde(default)]
     pub date_precision: Option<String>,
+    /// "backstory", "on_page" (the default) or "planned"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub narrative_status: Option<String>,
 }
 
 /// Spec for a relationship in batch creation.
@@ -754,6 +801,9 @@ pub struct RelationshipSpec {
     pub subtype: Option<String>,
     #[serde(default)]
     pub label: Option<String>,
+    /// Deliberately one-sided or contradictory (skipped by reciprocity checks)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intentional: Option<bool>,
 }
 
 /// Conflict resolution mode for import operations.
@@ -855,6 +905,12 @@ pub struct NoteSpec {
     pub id: Option<String>,
     pub title: String,
     pub body: String,
+    /// general (default), research, todo, or critique
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<NoteKind>,
+    /// open (default), resolved, or dismissed; todos and critiques only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<NoteStatus>,
     #[serde(default)]
     pub attach_to: Vec<String>,
 }
@@ -904,6 +960,46 @@ pub struct ImportResult {
     pub total_updated: usize,
     pub total_errors: usize,
     pub by_type: Vec<ImportTypeResult>,
+    /// Restored from the derived-data sidecar (`--include-derived`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derived: Option<DerivedStats>,
+    /// Imported entities whose IDs already existed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<EntityConflict>,
+}
+
+/// One field an import would change on an existing entity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FieldDiff {
+    pub field: String,
+    pub old: serde_json::Value,
+    pub new: serde_json::Value,
+}
+
+/// An imported entity whose ID already exists in the world.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EntityConflict {
+    /// Full record ID (e.g., "character:alice")
+    pub entity_id: String,
+    /// Fields that differ; empty when the import matches the world
+    pub fields: Vec<FieldDiff>,
+    /// How the conflict was (or will be) resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<ConflictMode>,
+}
+
+/// Derived data (embeddings, arc snapshots, annotations) written or restored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct DerivedStats {
+    pub embeddings: usize,
+    pub arc_snapshots: usize,
+    pub annotations: usize,
+    /// Items whose entity no longer exists in the world
+    pub unmatched: usize,
+    pub skipped: usize,
+    /// Embedding model of the sidecar, when it differs from the world's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_mismatch: Option<String>,
 }
 
 /// Mutation operations (write).
@@ -926,6 +1022,9 @@ pub enum MutationRequest {
         /// values are lists of entries.
         #[serde(default)]
         profile: Option<HashMap<String, Vec<String>>>,
+        /// Create it even if an existing character looks like the same one.
+        #[serde(default)]
+        allow_similar: Option<bool>,
     },
     /// Create a new location.
     CreateLocation {
@@ -952,6 +1051,9 @@ pub enum MutationRequest {
         date: Option<String>,
         #[serde(default)]
         date_precision: Option<String>,
+        /// "backstory", "on_page" (the default) or "planned"
+        #[serde(default)]
+        narrative_status: Option<String>,
     },
     /// Create a new scene.
     CreateScene {
@@ -965,6 +1067,9 @@ pub enum MutationRequest {
     Update {
         entity_id: String,
         fields: serde_json::Value,
+        /// Required when the entity is referenced by a scene marked final
+        #[serde(default)]
+        force: Option<bool>,
     },
     /// Record character knowledge.
     RecordKnowledge {
@@ -984,19 +1089,49 @@ pub enum MutationRequest {
         entity_id: String,
         #[serde(default)]
         hard: Option<bool>,
+        /// Required when the entity is referenced by a scene marked final
+        #[serde(default)]
+        force: Option<bool>,
     },
     /// Create a new note.
     CreateNote {
         title: String,
+        /// Note text; `[[Entity Name]]` attaches the note to that entity
         body: String,
+        /// general (default), research, todo, or critique. Critiques must be
+        /// attached to at least one scene.
+        #[serde(default)]
+        kind: Option<NoteKind>,
         /// Optional entity IDs to attach the note to
         #[serde(default)]
         attach_to: Option<Vec<String>>,
     },
+    /// Resolve, dismiss, or reopen a todo or critique note.
+    SetNoteStatus {
+        note_id: String,
+        /// open, resolved, or dismissed
+        status: NoteStatus,
+    },
     /// Attach an existing note to an entity.
     AttachNote { note_id: String, entity_id: String },
     /// Detach a note from an entity.
     DetachNote { note_id: String, entity_id: String },
+    /// Add freeform tags ("action", "flashback", "b-plot") to a scene, event, or note.
+    TagEntity {
+        entity_id: String,
+        tags: Vec<String>,
+        /// Required when the entity is referenced by a scene marked final
+        #[serde(default)]
+        force: Option<bool>,
+    },
+    /// Remove tags from a scene, event, or note.
+    UntagEntity {
+        entity_id: String,
+        tags: Vec<String>,
+        /// Required when the entity is referenced by a scene marked final
+        #[serde(default)]
+        force: Option<bool>,
+    },
     /// Create a new universe fact.
     CreateFact {
         title: String,
@@ -1007,8 +1142,17 @@ pub enum MutationRequest {
         /// Enforcement level: informational, warning (default), strict
         #[serde(default)]
         enforcement_level: Option<String>,
+        /// Only enforce within this location and the locations inside it (e.g., "location:citadel")
+        #[serde(default)]
+        location: Option<String>,
+        /// Only enforce from this event on: event ID or sequence number
+        #[serde(default)]
+        valid_from: Option<String>,
+        /// Only enforce up to this event: event ID or sequence number
+        #[serde(default)]
+        valid_until: Option<String>,
     },
-    /// Update an existing universe fact.
+    /// Update an existing universe fact. Scope bounds not given are kept; "none" clears one.
     UpdateFact {
         fact_id: String,
         #[serde(default)]
@@ -1019,6 +1163,15 @@ pub enum MutationRequest {
         categories: Option<Vec<String>>,
         #[serde(default)]
         enforcement_level: Option<String>,
+        /// Location subtree to limit the fact to
+        #[serde(default)]
+        location: Option<String>,
+        /// Event ID or sequence number the fact holds from
+        #[serde(default)]
+        valid_from: Option<String>,
+        /// Event ID or sequence number the fact holds until
+        #[serde(default)]
+        valid_until: Option<String>,
     },
     /// Delete a universe fact.
     DeleteFact { fact_id: String },
@@ -1026,6 +1179,49 @@ pub enum MutationRequest {
     LinkFact { fact_id: String, entity_id: String },
     /// Unlink a universe fact from an entity.
     UnlinkFact { fact_id: String, entity_id: String },
+    /// Exempt an entity from a universe fact; consistency checks skip it.
+    AddFactException {
+        fact_id: String,
+        entity_id: String,
+        /// Why the fact doesn't hold for this entity
+        reason: String,
+    },
+    /// Remove an entity's exemption from a universe fact.
+    RemoveFactException { fact_id: String, entity_id: String },
+    /// Record that a scene, event or knowledge entry foreshadows a later payoff.
+    CreateForeshadow {
+        /// The earlier hint (scene:, event: or knowledge: ID)
+        setup_id: String,
+        /// What it pays off (scene:, event: or knowledge: ID)
+        payoff_id: String,
+        #[serde(default)]
+        note: Option<String>,
+    },
+    /// Remove a foreshadowing link.
+    RemoveForeshadow { setup_id: String, payoff_id: String },
+    /// Record that a character took part in an event. Updates the role and
+    /// impact when they already did.
+    AddEventParticipant {
+        event_id: String,
+        character_id: String,
+        /// Their part in the event (e.g., witness, instigator, victim)
+        #[serde(default)]
+        role: Option<String>,
+        /// How the event affected them
+        #[serde(default)]
+        impact: Option<String>,
+        /// Required when the event is referenced by a scene marked final
+        #[serde(default)]
+        force: Option<bool>,
+    },
+    /// Remove a character from an event's participants.
+    RemoveEventParticipant {
+        event_id: String,
+        character_id: String,
+        /// Required when the event is referenced by a scene marked final
+        #[serde(default)]
+        force: Option<bool>,
+    },
     /// Create a relationship between two characters.
     CreateRelationship {
         from_character_id: String,
@@ -1035,6 +1231,19 @@ pub enum MutationRequest {
         subtype: Option<String>,
         #[serde(default)]
         label: Option<String>,
+        /// Also seed both characters' perceptions of each other from the
+        /// relationship type's baseline perception (existing ones are kept)
+        #[serde(default)]
+        with_perceptions: bool,
+    },
+    /// Set how far one character trusts another (0.0-1.0), on their perception
+    /// edge. Omit `trust` to clear it so trust is inferred from relationships
+    /// and history again. Trust weighs influence paths and derived certainty.
+    SetTrust {
+        from_character_id: String,
+        to_character_id: String,
+        #[serde(default)]
+        trust: Option<f32>,
     },
     /// Batch-create multiple characters in one call.
     BatchCreateCharacters { characters: Vec<CharacterSpec> },
@@ -1106,9 +1315,20 @@ pub enum MutationRequest {
         /// Temporal weight 0.0-1.0 (default: 0.15)
         #[serde(default)]
         temporal_weight: Option<f32>,
+        /// RNG seed for k-means; the same seed always gives the same result
+        #[serde(default)]
+        seed: Option<u64>,
     },
     /// Clear all saved phases from database.
     ClearPhases,
+    /// Give a saved phase a custom label (e.g. "Act II: The Siege").
+    /// The label is kept across re-detection by the phase with the most
+    /// members in common.
+    RenamePhase {
+        /// Phase ID (e.g., "phase:phase_2") or phase number
+        phase_id: String,
+        label: String,
+    },
     /// Run ML annotation pipeline on all entities of the specified types.
     /// Annotates entities with emotion, theme, and NER classifiers in parallel.
     /// Results are cached as annotations in the database.
@@ -1129,9 +1349,56 @@ pub enum MutationRequest {
         /// Max concurrency for parallel processing (default: 4).
         #[serde(default)]
         concurrency: Option<usize>,
+        /// Re-run only models whose input text changed since their last run (default: false).
+        #[serde(default)]
+        incremental: bool,
     },
 }
 
+impl MutationRequest {
+    /// Entity type the operation writes, for access checks. World-wide jobs
+    /// (imports, backfills, phases) return `ANY_TYPE`.
+    pub fn target_type(&self) -> &str {
+        use crate::services::access::ANY_TYPE;
+        let table_of = |id: &str| id.split_once(':').map_or(ANY_TYPE, |(t, _)| t);
+        match self {
+            Self::CreateCharacter { .. } | Self::BatchCreateCharacters { .. } => "character",
+            Self::CreateLocation { .. } | Self::BatchCreateLocations { .. } => "location",
+            Self::CreateEvent { .. } | Self::BatchCreateEvents { .. } => "event",
+            Self::CreateScene { .. } => "scene",
+            Self::Update { entity_id, .. }
+            | Self::Delete { entity_id, .. }
+            | Self::ProtectEntity { entity_id }
+            | Self::UnprotectEntity { entity_id }
+            | Self::TagEntity { entity_id, .. }
+            | Self::UntagEntity { entity_id, .. } => table_of(entity_id),
+            Self::RecordKnowledge { .. } | Self::BatchRecordKnowledge { .. } => "knows",
+            Self::CreateNote { .. }
+            | Self::AttachNote { .. }
+            | Self::DetachNote { .. }
+            | Self::SetNoteStatus { .. } => "note",
+            Self::CreateFact { .. }
+            | Self::UpdateFact { .. }
+            | Self::DeleteFact { .. }
+            | Self::LinkFact { .. }
+            | Self::UnlinkFact { .. }
+            | Self::AddFactException { .. }
+            | Self::RemoveFactException { .. } => "universe_fact",
+            Self::CreateForeshadow { .. } | Self::RemoveForeshadow { .. } => "foreshadows",
+            Self::AddEventParticipant { .. } | Self::RemoveEventParticipant { .. } => "involved_in",
+            Self::CreateRelationship { .. } | Self::BatchCreateRelationships { .. } => "relates_to",
+            Self::SetTrust { .. } => "perceives",
+            Self::BackfillEmbeddings { .. }
+            | Self::BaselineArcSnapshots { .. }
+            | Self::ImportYaml { .. }
+            | Self::SavePhases { .. }
+            | Self::ClearPhases
+            | Self::AnnotateEntities { .. } => ANY_TYPE,
+            Self::RenamePhase { .. } => "phase",
+        }
+    }
+}
+
 fn default_true() -> bool {
     true
 }
@@ -1213,7 +1480,8 @@ pub struct RecordKnowledgeInput {
     pub target_id: String,
     /// The fact being learned (natural language)
     pub fact: String,
-    /// Certainty level: knows, suspects, believes_wrongly, uncertain
+    /// Certainty level: knows, suspects, believes_wrongly, uncertain, or derive
+    /// (from the source character's certainty and the character's trust in them)
     pub certainty: String,
     /// How they learned it (e.g., "witnessed", "told_by", "overheard")
     #[serde(default)]
@@ -1271,6 +1539,9 @@ pub struct CreateCharacterInput {
     /// Profile: keys are categories (wound, secret, desire, contradiction), values are entry lists
     #[serde(default)]
     pub profile: Option<HashMap<String, Vec<String>>>,
+    /// Create it even if an existing character looks like the same one (similar name or profile)
+    #[serde(default)]
+    pub allow_similar: Option<bool>,
 }
 
 /// Input for create_relationship tool.
@@ -1288,6 +1559,10 @@ pub struct CreateRelationshipInput {
     /// Human-readable label (e.g., "childhood friends")
     #[serde(default)]
     pub label: Option<String>,
+    /// Also seed both characters' perceptions of each other from the
+    /// relationship type's baseline perception (existing ones are kept)
+    #[serde(default)]
+    pub with_perceptions: bool,
 }
 
 /// Input for irony_report tool.
@@ -1299,6 +1574,9 @@ pub struct IronyReportInput {
     /// Minimum scene count since asymmetry to include (default: 3)
     #[serde(default)]
     pub min_scene_threshold: Option<usize>,
+    /// Only consider members of this narrative phase
+    #[serde(default)]
+    pub phase: Option<usize>,
 }
 
 /// Input for update_entity tool.
@@ -1308,6 +1586,9 @@ pub struct UpdateEntityInput {
     pub entity_id: String,
     /// Fields to update (JSON object with field names and new values)
     pub fields: serde_json::Value,
+    /// Required when the entity is referenced by a scene marked final
+    #[serde(default)]
+    pub force: Option<bool>,
 }
 
 /// Input for knowledge_asymmetries tool.
@@ -1341,6 +1622,12 @@ pub struct QueryInput {
     /// Capped at MAX_TOKEN_BUDGET (8000).
     #[serde(default)]
     pub token_budget: Option<usize>,
+    /// How to fit an over-budget response: "drop" (default) removes trailing results,
+    /// "summarize" shortens each result so more of them fit, "sample" has the client's
+    /// model compress composite report sections (falls back to "summarize" when the
+    /// client doesn't support sampling).
+    #[serde(default)]
+    pub truncation_strategy: Option<String>,
     /// Operation-specific parameters (validated at runtime)
     #[serde(flatten)]
     pub params: serde_json::Map<String, serde_json::Value>,
@@ -1359,7 +1646,7 @@ pub struct MutationInput {
 /// Free-form input for session tool (runtime deserialization to SessionRequest).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SessionInput {
-    /// Operation name (get_context, pin_entity, unpin_entity)
+    /// Operation name (get_context, pin_entity, unpin_entity, log)
     pub operation: String,
     /// Operation-specific parameters (validated at runtime)
     #[serde(flatten)]
@@ -1369,7 +1656,7 @@ pub struct SessionInput {
 /// Information about response truncation due to token budget constraints.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TruncationInfo {
-    /// Reason for truncation ("token_budget" or "result_limit")
+    /// Reason for truncation ("token_budget", "summarized", "sampled", or "result_limit")
     pub reason: String,
     /// Total results before truncation
     pub original_count: usize,
@@ -1451,15 +1738,31 @@ pub enum SessionRequest {
         force_full: bool,
     },
     /// Pin entity to working context. Pinned entities persist across sessions.
+    /// Pinning an already pinned entity updates its priority and expiry.
     PinEntity {
         /// Entity ID to pin (e.g., "character:alice")
         entity_id: String,
+        /// low, normal (default), or high; higher pins rank first in context
+        #[serde(default)]
+        priority: PinPriority,
+        /// Event the pin lasts until (e.g., "event:duel"); it expires once
+        /// work moves past that event
+        #[serde(default)]
+        until: Option<String>,
     },
     /// Unpin entity from working context.
     UnpinEntity {
         /// Entity ID to unpin
         entity_id: String,
     },
+    /// Record what happened in this writing session, for recall next time.
+    Log {
+        /// Journal entry (e.g., "drafted ch 7 confrontation")
+        message: String,
+        /// Entity IDs the work touched
+        #[serde(default)]
+        entity_ids: Vec<String>,
+    },
 }
 
 /// Response for session operations.
@@ -1489,6 +1792,43 @@ pub struct SessionContextData {
     pub pending_decisions: Vec<PendingDecisionInfo>,
     #[serde(default)]
     pub world_overview: Option<WorldOverviewInfo>,
+    #[serde(default)]
+    pub recent_journal: Vec<JournalEntryInfo>,
+    /// Todo notes not yet resolved, oldest first
+    #[serde(default)]
+    pub open_todos: Vec<OpenTodoInfo>,
+    /// Pinned entities, highest priority first
+    #[serde(default)]
+    pub pinned: Vec<PinnedEntityInfo>,
+    /// Pins removed because work moved past their event
+    #[serde(default)]
+    pub expired_pins: Vec<String>,
+    /// Frequently used entities worth pinning
+    #[serde(default)]
+    pub suggested_pins: Vec<HotEntityInfo>,
+    /// Entities the project always includes
+    #[serde(default)]
+    pub always_included: Vec<HotEntityInfo>,
+    /// Entities that had attention and cooled off
+    #[serde(default)]
+    pub neglected: Vec<HotEntityInfo>,
+    /// Latest background annotation run, when `[annotate] schedule` enables them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_run: Option<ScheduledRunInfo>,
+    /// List items dropped to stay under the configured token ceiling
+    #[serde(default)]
+    pub trimmed: usize,
+}
+
+/// Pinned entity in session context.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PinnedEntityInfo {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub priority: PinPriority,
+    #[serde(default)]
+    pub until: Option<String>,
 }
 
 /// Hot entity in session context.
@@ -1510,6 +1850,22 @@ pub struct PendingDecisionInfo {
     pub affected_count: usize,
 }
 
+/// Session journal entry in session context.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JournalEntryInfo {
+    pub message: String,
+    pub age: String,
+    pub entities: Vec<String>,
+}
+
+/// Open todo note in session context.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OpenTodoInfo {
+    pub id: String,
+    pub title: String,
+    pub age: String,
+}
+
 /// World overview counts.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorldOverviewInfo {
@@ -1526,4 +1882,8 @@ pub struct PinResult {
     pub success: bool,
     pub entity_id: String,
     pub pinned_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<PinPriority>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
 }
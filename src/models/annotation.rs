@@ -7,6 +7,7 @@
 use crate::db::connection::NarraDb;
 use crate::NarraError;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use surrealdb::{Datetime, RecordId};
 
 /// A cached ML model annotation for an entity.
@@ -25,6 +26,9 @@ pub struct Annotation {
     pub computed_at: Datetime,
     /// Whether the annotation needs recomputation
     pub stale: bool,
+    /// Hash of the text the model annotated (see [`input_hash`])
+    #[serde(default)]
+    pub input_hash: Option<String>,
 }
 
 /// Data for creating or upserting an annotation.
@@ -180,6 +184,48 @@ pub async fn mark_annotations_stale(db: &NarraDb, entity_id: &str) -> Result<usi
     Ok(updated.len())
 }
 
+/// Set or clear the stale flag of one model's annotation.
+pub async fn set_annotation_stale(
+    db: &NarraDb,
+    entity_id: &str,
+    model_type: &str,
+    stale: bool,
+) -> Result<(), NarraError> {
+    db.query(
+        "UPDATE annotation SET stale = $stale \
+         WHERE entity_id = $entity_id AND model_type = $model_type",
+    )
+    .bind(("entity_id", entity_id.to_string()))
+    .bind(("model_type", model_type.to_string()))
+    .bind(("stale", stale))
+    .await?;
+    Ok(())
+}
+
+/// Hash of a model's input text, stored with its annotation so unchanged
+/// input can be recognized without re-running the model.
+pub fn input_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// Record the hash of the text an annotation was computed from.
+pub async fn set_input_hash(
+    db: &NarraDb,
+    entity_id: &str,
+    model_type: &str,
+    hash: &str,
+) -> Result<(), NarraError> {
+    db.query(
+        "UPDATE annotation SET input_hash = $hash \
+         WHERE entity_id = $entity_id AND model_type = $model_type",
+    )
+    .bind(("entity_id", entity_id.to_string()))
+    .bind(("model_type", model_type.to_string()))
+    .bind(("hash", hash.to_string()))
+    .await?;
+    Ok(())
+}
+
 /// Get stale annotations, optionally filtered by model type.
 pub async fn get_stale_annotations(
     db: &NarraDb,
@@ -0,0 +1,102 @@
+//! Image assets (character portraits, maps) attached to entities.
+//!
+//! The bytes live under `<data_path>/assets/` (see `services::assets`); an
+//! `asset` record holds the metadata and the entity the image belongs to.
+
+use crate::db::connection::NarraDb;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// Tables an asset can be attached to.
+pub const ASSET_TABLES: &[&str] = &["character", "location", "event", "scene", "knowledge"];
+
+/// Metadata for a stored image.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Asset {
+    pub id: RecordId,
+    /// Stored file name inside the assets directory
+    pub file: String,
+    /// File name it was added from
+    pub original_name: String,
+    pub mime_type: String,
+    /// Size of the image itself (before encryption)
+    pub size_bytes: u64,
+    /// SHA-256 of the image itself, hex
+    pub sha256: String,
+    pub caption: Option<String>,
+    /// Whether the stored file is age-encrypted (encrypted worlds)
+    #[serde(default)]
+    pub encrypted: bool,
+    pub entity: Option<RecordId>,
+    pub created_at: Datetime,
+}
+
+/// Data for recording a stored image.
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetCreate {
+    pub file: String,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+    pub caption: Option<String>,
+    pub encrypted: bool,
+    pub entity: Option<RecordId>,
+}
+
+/// Parse the ID of an entity an asset can be attached to.
+pub fn asset_entity_ref(id: &str) -> Result<RecordId, NarraError> {
+    match id.split_once(':') {
+        Some((table, key)) if ASSET_TABLES.contains(&table) => Ok(RecordId::from((table, key))),
+        _ => Err(NarraError::Validation(format!(
+            "Assets attach to characters, locations, events, scenes and knowledge; got '{}'",
+            id
+        ))),
+    }
+}
+
+/// Record a stored image.
+pub async fn create_asset(db: &NarraDb, data: AssetCreate) -> Result<Asset, NarraError> {
+    let result: Option<Asset> = db.create("asset").content(data).await?;
+    result.ok_or_else(|| NarraError::Database("Failed to create asset".into()))
+}
+
+/// Get an asset by ID (key part or full `asset:...`).
+pub async fn get_asset(db: &NarraDb, id: &str) -> Result<Option<Asset>, NarraError> {
+    let key = id.strip_prefix("asset:").unwrap_or(id);
+    let result: Option<Asset> = db.select(("asset", key)).await?;
+    Ok(result)
+}
+
+/// All assets, or those attached to one entity, oldest first.
+pub async fn list_assets(db: &NarraDb, entity_id: Option<&str>) -> Result<Vec<Asset>, NarraError> {
+    let mut result = match entity_id {
+        Some(entity_id) => {
+            db.query("SELECT * FROM asset WHERE entity = $entity ORDER BY created_at")
+                .bind(("entity", asset_entity_ref(entity_id)?))
+                .await?
+        }
+        None => db.query("SELECT * FROM asset ORDER BY created_at").await?,
+    };
+    let assets: Vec<Asset> = result.take(0)?;
+    Ok(assets)
+}
+
+/// Delete an asset record. The stored file is left to the caller.
+pub async fn delete_asset(db: &NarraDb, id: &str) -> Result<Option<Asset>, NarraError> {
+    let key = id.strip_prefix("asset:").unwrap_or(id);
+    let result: Option<Asset> = db.delete(("asset", key)).await?;
+    Ok(result)
+}
+
+/// How many asset records point at a stored file.
+pub async fn file_references(db: &NarraDb, file: &str) -> Result<usize, NarraError> {
+    let mut result = db
+        .query("RETURN count(SELECT id FROM asset WHERE file = $file)")
+        .bind(("file", file.to_string()))
+        .await?;
+    let count: Option<usize> = result.take(0)?;
+    Ok(count.unwrap_or(0))
+}
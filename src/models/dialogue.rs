@@ -0,0 +1,84 @@
+//! Dialogue: spoken lines from imported scripts.
+//!
+//! Lines are ordered by `position` within their scene. Importing a scene
+//! again replaces its lines rather than appending to them.
+
+use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// One line of dialogue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dialogue {
+    pub id: RecordId,
+    pub scene: RecordId,
+    pub character: RecordId,
+    pub position: i64,
+    pub text: String,
+    /// Actor direction given with the line, e.g. "(whispering)"
+    pub parenthetical: Option<String>,
+    pub created_at: Datetime,
+}
+
+/// Data for creating a line of dialogue.
+#[derive(Debug, Clone, Serialize)]
+pub struct DialogueCreate {
+    pub scene: RecordId,
+    pub character: RecordId,
+    pub position: i64,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parenthetical: Option<String>,
+}
+
+// ============================================================================
+// Dialogue Operations
+// ============================================================================
+
+/// Replace a scene's dialogue with `lines`. Returns how many were stored.
+pub async fn replace_scene_dialogue(
+    db: &NarraDb,
+    scene_id: &str,
+    lines: Vec<DialogueCreate>,
+) -> Result<usize, NarraError> {
+    let scene = record_id("scene", scene_id)?;
+    let count = lines.len();
+    db.query(
+        "BEGIN TRANSACTION;
+         DELETE dialogue WHERE scene = $scene;
+         INSERT INTO dialogue $lines;
+         COMMIT TRANSACTION;",
+    )
+    .bind(("scene", scene))
+    .bind(("lines", lines))
+    .await?
+    .check()?;
+    Ok(count)
+}
+
+/// A scene's dialogue in script order.
+pub async fn get_scene_dialogue(db: &NarraDb, scene_id: &str) -> Result<Vec<Dialogue>, NarraError> {
+    let mut result = db
+        .query("SELECT * FROM dialogue WHERE scene = $scene ORDER BY position")
+        .bind(("scene", record_id("scene", scene_id)?))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+/// Everything a character says, grouped by scene in script order.
+pub async fn get_character_dialogue(
+    db: &NarraDb,
+    character_id: &str,
+) -> Result<Vec<Dialogue>, NarraError> {
+    let mut result = db
+        .query(
+            "SELECT *, scene.event.sequence AS sequence FROM dialogue \
+             WHERE character = $character ORDER BY sequence, scene, position",
+        )
+        .bind(("character", record_id("character", character_id)?))
+        .await?;
+    Ok(result.take(0)?)
+}
@@ -4,6 +4,9 @@ use surrealdb::{Datetime, RecordId};
 
 use crate::NarraError;
 
+/// Valid values for `Event::narrative_status`.
+pub const NARRATIVE_STATUSES: &[&str] = &["backstory", "on_page", "planned"];
+
 /// Event entity for timeline ordering.
 ///
 /// Events use a hybrid ordering system:
@@ -20,10 +23,20 @@ pub struct Event {
     pub date: Option<Datetime>,
     pub date_precision: Option<String>,
     pub duration_end: Option<Datetime>,
+    /// Freeform tags (see `models::tag`)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// "backstory" (before the story), "on_page" or "planned"
+    #[serde(default = "default_narrative_status")]
+    pub narrative_status: String,
     pub created_at: Datetime,
     pub updated_at: Datetime,
 }
 
+fn default_narrative_status() -> String {
+    "on_page".to_string()
+}
+
 /// Data for creating a new event.
 #[derive(Debug, Serialize)]
 pub struct EventCreate {
@@ -33,6 +46,9 @@ pub struct EventCreate {
     pub date: Option<Datetime>,
     pub date_precision: Option<String>,
     pub duration_end: Option<Datetime>,
+    /// Defaults to "on_page" in the schema
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub narrative_status: Option<String>,
 }
 
 /// Data for updating an event.
@@ -50,6 +66,8 @@ pub struct EventUpdate {
     pub date_precision: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration_end: Option<Option<Datetime>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub narrative_status: Option<String>,
     pub updated_at: Datetime,
 }
 
@@ -64,6 +82,9 @@ pub struct EventUpdate {
 ///
 /// The created event with generated ID and timestamps.
 pub async fn create_event(db: &NarraDb, data: EventCreate) -> Result<Event, NarraError> {
+    if let Some(status) = &data.narrative_status {
+        validate_narrative_status(status)?;
+    }
     let result: Option<Event> = db.create("event").content(data).await?;
     result.ok_or_else(|| NarraError::Database("Failed to create event".into()))
 }
@@ -74,10 +95,26 @@ pub async fn create_event_with_id(
     id: &str,
     data: EventCreate,
 ) -> Result<Event, NarraError> {
+    if let Some(status) = &data.narrative_status {
+        validate_narrative_status(status)?;
+    }
     let result: Option<Event> = db.create(("event", id)).content(data).await?;
     result.ok_or_else(|| NarraError::Database("Failed to create event".into()))
 }
 
+/// Check that a narrative status is one of [`NARRATIVE_STATUSES`].
+pub fn validate_narrative_status(status: &str) -> Result<(), NarraError> {
+    if NARRATIVE_STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(NarraError::Validation(format!(
+            "Invalid narrative status '{}'. Expected one of: {}",
+            status,
+            NARRATIVE_STATUSES.join(", ")
+        )))
+    }
+}
+
 /// Get an event by ID.
 ///
 /// # Arguments
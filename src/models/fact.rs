@@ -5,6 +5,7 @@
 //! or POV context.
 
 use crate::db::connection::NarraDb;
+use crate::db::query::{parse_record_id, record_id};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -94,17 +95,34 @@ impl EnforcementLevel {
 /// Temporal scope defining when a fact is valid.
 ///
 /// Event references are stored as strings (e.g., "event:abc123") for JSON compatibility.
+/// Bounds can be given as events or as raw sequence numbers; both are inclusive.
 #[skip_serializing_none]
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct TemporalScope {
     /// Event ID from which this fact becomes valid (e.g., "event:abc123")
     pub valid_from_event: Option<String>,
     /// Event ID at which this fact ends (e.g., "event:abc123")
     pub valid_until_event: Option<String>,
+    /// Event sequence from which this fact becomes valid
+    #[serde(default)]
+    pub from_sequence: Option<i64>,
+    /// Event sequence at which this fact ends
+    #[serde(default)]
+    pub until_sequence: Option<i64>,
     /// Freeform description for user-defined temporal bounds
     pub freeform_description: Option<String>,
 }
 
+impl TemporalScope {
+    /// Whether no bound is set.
+    pub fn is_unbounded(&self) -> bool {
+        self.valid_from_event.is_none()
+            && self.valid_until_event.is_none()
+            && self.from_sequence.is_none()
+            && self.until_sequence.is_none()
+    }
+}
+
 /// POV scope defining who the fact applies to.
 ///
 /// Character/entity references are stored as strings (e.g., "character:alice") for JSON compatibility.
@@ -119,14 +137,118 @@ pub enum PovScope {
     ExceptCharacters(Vec<String>),
 }
 
-/// Combined scope for temporal and POV filtering.
+/// Combined scope for temporal, POV and location filtering.
 #[skip_serializing_none]
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct FactScope {
-    /// Temporal bounds (AND logic with POV)
+    /// Temporal bounds (AND logic with POV and location)
     pub temporal: Option<TemporalScope>,
-    /// POV-specific scope (AND logic with temporal)
+    /// POV-specific scope (AND logic with temporal and location)
     pub pov: Option<PovScope>,
+    /// Location subtree the fact holds in (e.g., "location:citadel"): the
+    /// location itself and every location inside it. Entities are placed by
+    /// their own location, scene locations, or the scenes they appear in.
+    #[serde(default)]
+    pub location: Option<String>,
+}
+
+impl FactScope {
+    /// Short description, e.g. "in location:citadel, from event:siege".
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(location) = &self.location {
+            parts.push(format!("in {}", location));
+        }
+        if let Some(temporal) = &self.temporal {
+            let from = temporal
+                .valid_from_event
+                .clone()
+                .or(temporal.from_sequence.map(|s| format!("sequence {}", s)));
+            let until = temporal
+                .valid_until_event
+                .clone()
+                .or(temporal.until_sequence.map(|s| format!("sequence {}", s)));
+            if let Some(from) = from {
+                parts.push(format!("from {}", from));
+            }
+            if let Some(until) = until {
+                parts.push(format!("until {}", until));
+            }
+        }
+        match &self.pov {
+            Some(PovScope::Character(id)) => parts.push(format!("for {}", id)),
+            Some(PovScope::Group(group)) => parts.push(format!("for group {}", group)),
+            Some(PovScope::ExceptCharacters(ids)) => {
+                parts.push(format!("except {}", ids.join(", ")))
+            }
+            None => {}
+        }
+        parts.join(", ")
+    }
+}
+
+/// Apply location and time bounds, given as text, to a fact's scope.
+///
+/// `location` is a location ID; `from`/`until` are event IDs or sequence
+/// numbers. "none" clears a bound; bounds not given are kept from `base`.
+/// Locations and events must exist.
+pub async fn apply_scope_bounds(
+    db: &NarraDb,
+    base: Option<FactScope>,
+    location: Option<&str>,
+    from: Option<&str>,
+    until: Option<&str>,
+) -> Result<FactScope, NarraError> {
+    let mut scope = base.unwrap_or_default();
+    let mut temporal = scope.temporal.take().unwrap_or_default();
+
+    if let Some(location) = location {
+        scope.location = if location.eq_ignore_ascii_case("none") {
+            None
+        } else {
+            let key = location.trim().trim_start_matches("location:");
+            if crate::models::location::get_location(db, key)
+                .await?
+                .is_none()
+            {
+                return Err(NarraError::NotFound {
+                    entity_type: "location".to_string(),
+                    id: key.to_string(),
+                });
+            }
+            Some(format!("location:{}", key))
+        };
+    }
+
+    for (value, is_from) in [(from, true), (until, false)] {
+        let Some(value) = value else { continue };
+        let (sequence, event) = if value.eq_ignore_ascii_case("none") {
+            (None, None)
+        } else if let Ok(sequence) = value.trim().parse::<i64>() {
+            (Some(sequence), None)
+        } else {
+            let key = value.trim().trim_start_matches("event:");
+            if crate::models::event::get_event(db, key).await?.is_none() {
+                return Err(NarraError::NotFound {
+                    entity_type: "event".to_string(),
+                    id: key.to_string(),
+                });
+            }
+            (None, Some(format!("event:{}", key)))
+        };
+        if is_from {
+            temporal.from_sequence = sequence;
+            temporal.valid_from_event = event;
+        } else {
+            temporal.until_sequence = sequence;
+            temporal.valid_until_event = event;
+        }
+    }
+
+    if !temporal.is_unbounded() || temporal.freeform_description.is_some() {
+        scope.temporal = Some(temporal);
+    }
+    Ok(scope)
 }
 
 // ============================================================================
@@ -142,10 +264,28 @@ pub struct UniverseFact {
     pub categories: Vec<FactCategory>,
     pub enforcement_level: EnforcementLevel,
     pub scope: Option<FactScope>,
+    /// Entities the fact deliberately doesn't bind
+    #[serde(default)]
+    pub exceptions: Vec<FactException>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
 }
 
+impl UniverseFact {
+    /// The exception covering `entity_id`, if any.
+    pub fn exception_for(&self, entity_id: &str) -> Option<&FactException> {
+        self.exceptions.iter().find(|e| e.entity_id == entity_id)
+    }
+}
+
+/// An entity exempt from a fact, with the author's justification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FactException {
+    /// Full entity ID (e.g., "character:lyra")
+    pub entity_id: String,
+    pub reason: String,
+}
+
 /// Data for creating a new fact.
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -251,7 +391,7 @@ pub async fn list_facts(db: &NarraDb) -> Result<Vec<UniverseFact>, NarraError> {
 ///
 /// * `db` - Database connection
 /// * `id` - Fact ID (the key part, not the full RecordId)
-/// * `data` - Fields to update
+/// * `data` - Fields to update; a scope replaces the current one as a whole
 ///
 /// # Returns
 ///
@@ -259,10 +399,102 @@ pub async fn list_facts(db: &NarraDb) -> Result<Vec<UniverseFact>, NarraError> {
 pub async fn update_fact(
     db: &NarraDb,
     id: &str,
-    data: FactUpdate,
+    mut data: FactUpdate,
 ) -> Result<Option<UniverseFact>, NarraError> {
+    // MERGE would deep-merge the scope and keep cleared bounds
+    let scope = data.scope.take();
     let result: Option<UniverseFact> = db.update(("universe_fact", id)).merge(data).await?;
-    Ok(result)
+    match (result, scope) {
+        (Some(_), Some(scope)) => {
+            let mut response = db
+                .query("UPDATE $fact SET scope = $scope RETURN AFTER")
+                .bind(("fact", record_id("universe_fact", id)?))
+                .bind(("scope", scope))
+                .await?;
+            let updated: Vec<UniverseFact> = response.take(0)?;
+            Ok(updated.into_iter().next())
+        }
+        (result, _) => Ok(result),
+    }
+}
+
+/// Replace a fact's exception list.
+async fn set_fact_exceptions(
+    db: &NarraDb,
+    id: &str,
+    exceptions: Vec<FactException>,
+) -> Result<UniverseFact, NarraError> {
+    let mut response = db
+        .query("UPDATE $fact SET exceptions = $exceptions RETURN AFTER")
+        .bind(("fact", record_id("universe_fact", id)?))
+        .bind(("exceptions", exceptions))
+        .await?;
+    let updated: Vec<UniverseFact> = response.take(0)?;
+    updated
+        .into_iter()
+        .next()
+        .ok_or_else(|| NarraError::Database("Failed to update fact exceptions".into()))
+}
+
+/// Exempt an entity from a fact.
+///
+/// Replaces the justification if the entity is already excepted.
+///
+/// # Arguments
+///
+/// * `db` - Database connection
+/// * `id` - Fact ID (the key part, not the full RecordId)
+/// * `entity_id` - Full entity identifier (e.g., "character:lyra")
+/// * `reason` - Why the fact doesn't hold for this entity
+///
+/// # Returns
+///
+/// The updated fact if found, None otherwise.
+pub async fn add_fact_exception(
+    db: &NarraDb,
+    id: &str,
+    entity_id: &str,
+    reason: &str,
+) -> Result<Option<UniverseFact>, NarraError> {
+    let entity = parse_record_id(entity_id)?.to_string();
+    if reason.trim().is_empty() {
+        return Err(NarraError::Validation(format!(
+            "An exception for {} needs a justification",
+            entity
+        )));
+    }
+    let Some(fact) = get_fact(db, id).await? else {
+        return Ok(None);
+    };
+
+    let mut exceptions = fact.exceptions;
+    exceptions.retain(|e| e.entity_id != entity);
+    exceptions.push(FactException {
+        entity_id: entity,
+        reason: reason.trim().to_string(),
+    });
+    set_fact_exceptions(db, id, exceptions).await.map(Some)
+}
+
+/// Remove an entity's exemption from a fact.
+///
+/// # Returns
+///
+/// The updated fact if found, None otherwise. Removing an exception that
+/// doesn't exist is not an error.
+pub async fn remove_fact_exception(
+    db: &NarraDb,
+    id: &str,
+    entity_id: &str,
+) -> Result<Option<UniverseFact>, NarraError> {
+    let entity = parse_record_id(entity_id)?.to_string();
+    let Some(fact) = get_fact(db, id).await? else {
+        return Ok(None);
+    };
+
+    let mut exceptions = fact.exceptions;
+    exceptions.retain(|e| e.entity_id != entity);
+    set_fact_exceptions(db, id, exceptions).await.map(Some)
 }
 
 /// Delete a fact by ID.
@@ -308,14 +540,11 @@ pub async fn link_fact_to_entity(
     link_type: &str,
     confidence: Option<f32>,
 ) -> Result<FactApplication, NarraError> {
-    let query = format!(
-        "RELATE universe_fact:{}->applies_to->{} SET link_type = $link_type, confidence = $confidence",
-        fact_id, entity_id
-    );
-
     let link_type_owned = link_type.to_string();
     let mut result = db
-        .query(&query)
+        .query("RELATE $fact->applies_to->$entity SET link_type = $link_type, confidence = $confidence")
+        .bind(("fact", record_id("universe_fact", fact_id)?))
+        .bind(("entity", parse_record_id(entity_id)?))
         .bind(("link_type", link_type_owned))
         .bind(("confidence", confidence))
         .await?;
@@ -340,11 +569,10 @@ pub async fn unlink_fact_from_entity(
     fact_id: &str,
     entity_id: &str,
 ) -> Result<(), NarraError> {
-    let query = format!(
-        "DELETE applies_to WHERE in = universe_fact:{} AND out = {}",
-        fact_id, entity_id
-    );
-    db.query(&query).await?;
+    db.query("DELETE applies_to WHERE in = $fact AND out = $entity")
+        .bind(("fact", record_id("universe_fact", fact_id)?))
+        .bind(("entity", parse_record_id(entity_id)?))
+        .await?;
     Ok(())
 }
 
@@ -362,11 +590,10 @@ pub async fn get_fact_applications(
     db: &NarraDb,
     fact_id: &str,
 ) -> Result<Vec<FactApplication>, NarraError> {
-    let query = format!(
-        "SELECT * FROM applies_to WHERE in = universe_fact:{}",
-        fact_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM applies_to WHERE in = $fact")
+        .bind(("fact", record_id("universe_fact", fact_id)?))
+        .await?;
     let apps: Vec<FactApplication> = result.take(0)?;
     Ok(apps)
 }
@@ -385,12 +612,10 @@ pub async fn get_entity_facts(
     db: &NarraDb,
     entity_id: &str,
 ) -> Result<Vec<UniverseFact>, NarraError> {
-    let query = format!(
-        r#"SELECT in.* AS fact FROM applies_to WHERE out = {}"#,
-        entity_id
-    );
-
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT in.* AS fact FROM applies_to WHERE out = $entity")
+        .bind(("entity", parse_record_id(entity_id)?))
+        .await?;
 
     #[derive(Deserialize)]
     struct FactWrapper {
@@ -414,12 +639,10 @@ pub async fn get_entity_facts(
 ///
 /// The number of facts linked to this entity.
 pub async fn get_fact_count_for_entity(db: &NarraDb, entity_id: &str) -> Result<usize, NarraError> {
-    let query = format!(
-        "SELECT count() AS count FROM applies_to WHERE out = {} GROUP ALL",
-        entity_id
-    );
-
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT count() AS count FROM applies_to WHERE out = $entity GROUP ALL")
+        .bind(("entity", parse_record_id(entity_id)?))
+        .await?;
 
     #[derive(Deserialize)]
     struct CountResult {
@@ -441,12 +664,10 @@ pub async fn get_fact_count_for_entity(db: &NarraDb, entity_id: &str) -> Result<
 ///
 /// A vector of entity IDs (as strings) linked to this fact.
 pub async fn get_entities_for_fact(db: &NarraDb, fact_id: &str) -> Result<Vec<String>, NarraError> {
-    let query = format!(
-        "SELECT out FROM applies_to WHERE in = universe_fact:{}",
-        fact_id
-    );
-
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT out FROM applies_to WHERE in = $fact")
+        .bind(("fact", record_id("universe_fact", fact_id)?))
+        .await?;
 
     #[derive(Deserialize)]
     struct OutWrapper {
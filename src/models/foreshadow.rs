@@ -0,0 +1,88 @@
+//! Foreshadowing links between scenes, events and knowledge.
+//!
+//! A `foreshadows` edge runs from a setup (the earlier hint) to its payoff.
+//! `analyze foreshadowing` checks that every payoff has a setup placed
+//! earlier on the timeline.
+
+use crate::db::connection::NarraDb;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// Tables that can take part in foreshadowing.
+pub const FORESHADOW_TABLES: &[&str] = &["scene", "event", "knowledge"];
+
+/// A setup -> payoff edge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Foreshadow {
+    pub id: RecordId,
+    #[serde(rename = "in")]
+    pub setup: RecordId,
+    #[serde(rename = "out")]
+    pub payoff: RecordId,
+    pub note: Option<String>,
+    pub created_at: Datetime,
+}
+
+/// Parse a scene, event or knowledge ID.
+pub fn foreshadow_ref(id: &str) -> Result<RecordId, NarraError> {
+    match id.split_once(':') {
+        Some((table, key)) if FORESHADOW_TABLES.contains(&table) => {
+            Ok(RecordId::from((table, key)))
+        }
+        _ => Err(NarraError::Validation(format!(
+            "Foreshadowing links scenes, events and knowledge; got '{}'",
+            id
+        ))),
+    }
+}
+
+/// Link a setup to the payoff it foreshadows.
+pub async fn create_foreshadow(
+    db: &NarraDb,
+    setup_id: &str,
+    payoff_id: &str,
+    note: Option<String>,
+) -> Result<Foreshadow, NarraError> {
+    let setup = foreshadow_ref(setup_id)?;
+    let payoff = foreshadow_ref(payoff_id)?;
+    if setup == payoff {
+        return Err(NarraError::Validation(
+            "An entity cannot foreshadow itself".to_string(),
+        ));
+    }
+
+    let mut result = db
+        .query("RELATE $setup->foreshadows->$payoff SET note = $note")
+        .bind(("setup", setup))
+        .bind(("payoff", payoff))
+        .bind(("note", note))
+        .await?;
+    let edge: Option<Foreshadow> = result.take(0)?;
+    edge.ok_or_else(|| NarraError::Database("Failed to create foreshadowing link".into()))
+}
+
+/// Remove the link between a setup and a payoff. Returns whether one existed.
+pub async fn remove_foreshadow(
+    db: &NarraDb,
+    setup_id: &str,
+    payoff_id: &str,
+) -> Result<bool, NarraError> {
+    let mut result = db
+        .query("DELETE foreshadows WHERE in = $setup AND out = $payoff RETURN BEFORE")
+        .bind(("setup", foreshadow_ref(setup_id)?))
+        .bind(("payoff", foreshadow_ref(payoff_id)?))
+        .await?;
+    let removed: Vec<Foreshadow> = result.take(0)?;
+    Ok(!removed.is_empty())
+}
+
+/// All foreshadowing links.
+pub async fn list_foreshadows(db: &NarraDb) -> Result<Vec<Foreshadow>, NarraError> {
+    let mut result = db
+        .query("SELECT * FROM foreshadows ORDER BY created_at ASC")
+        .await?;
+    let edges: Vec<Foreshadow> = result.take(0)?;
+    Ok(edges)
+}
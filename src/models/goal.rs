@@ -0,0 +1,122 @@
+//! Character goals: motivation as structured data.
+//!
+//! A goal belongs to one character and says what they want, what they need
+//! (often not the same thing), what is at stake and what stands in the way.
+//! Its status moves from active to achieved, failed or abandoned. An
+//! optional target names what the goal is about; `analyze goal-conflicts`
+//! uses it to find characters who share scenes and want incompatible things.
+
+use crate::db::connection::NarraDb;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// Valid values for `Goal::status`.
+pub const GOAL_STATUSES: &[&str] = &["active", "achieved", "failed", "abandoned"];
+
+/// A character's goal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    pub id: RecordId,
+    pub character: RecordId,
+    /// What the character consciously wants
+    pub want: String,
+    /// What they actually need
+    pub need: Option<String>,
+    /// What they lose if they fail
+    pub stakes: Option<String>,
+    /// What stands in their way
+    pub obstacle: Option<String>,
+    pub status: String,
+    /// What the goal is about (character, location, knowledge or event)
+    pub target: Option<RecordId>,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+/// Data for creating a new goal.
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalCreate {
+    pub character: RecordId,
+    pub want: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub need: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stakes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obstacle: Option<String>,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<RecordId>,
+}
+
+// ============================================================================
+// Goal CRUD Operations
+// ============================================================================
+
+/// Create a new goal.
+pub async fn create_goal(db: &NarraDb, data: GoalCreate) -> Result<Goal, NarraError> {
+    if data.want.trim().is_empty() {
+        return Err(NarraError::Validation(
+            "A goal needs a want: what the character is after".to_string(),
+        ));
+    }
+    validate_status(&data.status)?;
+    let result: Option<Goal> = db.create("goal").content(data).await?;
+    result.ok_or_else(|| NarraError::Database("Failed to create goal".into()))
+}
+
+/// Get a goal by ID (key part only).
+pub async fn get_goal(db: &NarraDb, id: &str) -> Result<Option<Goal>, NarraError> {
+    let result: Option<Goal> = db.select(("goal", id)).await?;
+    Ok(result)
+}
+
+/// List goals, optionally for one character (key part only), oldest first.
+pub async fn list_goals(db: &NarraDb, character: Option<&str>) -> Result<Vec<Goal>, NarraError> {
+    let mut result = match character {
+        Some(key) => {
+            db.query("SELECT * FROM goal WHERE character = $character ORDER BY created_at ASC")
+                .bind(("character", RecordId::from(("character", key))))
+                .await?
+        }
+        None => {
+            db.query("SELECT * FROM goal ORDER BY character ASC, created_at ASC")
+                .await?
+        }
+    };
+    let goals: Vec<Goal> = result.take(0)?;
+    Ok(goals)
+}
+
+/// Delete a goal by ID (key part only).
+pub async fn delete_goal(db: &NarraDb, id: &str) -> Result<Option<Goal>, NarraError> {
+    let result: Option<Goal> = db.delete(("goal", id)).await?;
+    Ok(result)
+}
+
+/// Parse a goal target: a character, location, knowledge or event ID.
+pub fn target_ref(id: &str) -> Result<RecordId, NarraError> {
+    const TABLES: &[&str] = &["character", "location", "knowledge", "event"];
+    match id.split_once(':') {
+        Some((table, key)) if TABLES.contains(&table) => Ok(RecordId::from((table, key))),
+        _ => Err(NarraError::Validation(format!(
+            "Expected a {} ID, got '{}'",
+            TABLES.join(", "),
+            id
+        ))),
+    }
+}
+
+fn validate_status(status: &str) -> Result<(), NarraError> {
+    if GOAL_STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(NarraError::Validation(format!(
+            "Invalid goal status '{}'. Expected one of: {}",
+            status,
+            GOAL_STATUSES.join(", ")
+        )))
+    }
+}
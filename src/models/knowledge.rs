@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use crate::db::connection::NarraDb;
+use crate::db::query::{parse_record_id, record_id};
 use serde::{Deserialize, Serialize};
 use surrealdb::{Datetime, RecordId};
 
@@ -89,6 +90,12 @@ pub struct KnowledgeState {
     pub event: Option<RecordId>,            // Event where learned
     pub premises: Option<Vec<RecordId>>,    // For deductions: source knowledge IDs
     pub truth_value: Option<String>,        // For BelievesWrongly: the actual truth
+    /// The source's state this certainty was derived from (see `services::transmission`)
+    #[serde(default)]
+    pub derived_from: Option<RecordId>,
+    /// Why the certainty was derived as it was
+    #[serde(default)]
+    pub derivation: Option<String>,
     pub learned_at: Datetime,
     pub created_at: Datetime,
     pub updated_at: Datetime,
@@ -312,26 +319,19 @@ pub async fn create_knowledge_state(
 
     // When knowledge is tied to an event, use the event's timestamp for learned_at
     // This ensures temporal queries work correctly ("what did X know at event Y")
-    let learned_at_clause = if data.event.is_some() {
-        "$event.created_at"
-    } else {
-        "time::now()"
-    };
-
-    let query = format!(
-        r#"RELATE character:{}->knows->{} SET
+    let mut result = db
+        .query(
+            r#"RELATE $character->knows->$target SET
             certainty = $certainty,
             learning_method = $method,
             source_character = $source,
             event = $event,
             premises = $premises,
             truth_value = $truth_value,
-            learned_at = {}"#,
-        character_id, target, learned_at_clause
-    );
-
-    let mut result = db
-        .query(&query)
+            learned_at = IF $event THEN $event.created_at ELSE time::now() END"#,
+        )
+        .bind(("character", record_id("character", character_id)?))
+        .bind(("target", parse_record_id(target)?))
         .bind(("certainty", data.certainty))
         .bind(("method", data.learning_method))
         .bind(("source", source_ref))
@@ -361,11 +361,10 @@ pub async fn get_character_knowledge_states(
     db: &NarraDb,
     character_id: &str,
 ) -> Result<Vec<KnowledgeState>, NarraError> {
-    let query = format!(
-        "SELECT * FROM knows WHERE in = character:{} ORDER BY learned_at DESC",
-        character_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM knows WHERE in = $character ORDER BY learned_at DESC")
+        .bind(("character", record_id("character", character_id)?))
+        .await?;
     let states: Vec<KnowledgeState> = result.take(0)?;
     Ok(states)
 }
@@ -386,11 +385,10 @@ pub async fn get_fact_knowers(
     db: &NarraDb,
     target: &str,
 ) -> Result<Vec<KnowledgeState>, NarraError> {
-    let query = format!(
-        "SELECT * FROM knows WHERE out = {} ORDER BY learned_at ASC",
-        target
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM knows WHERE out = $target ORDER BY learned_at ASC")
+        .bind(("target", parse_record_id(target)?))
+        .await?;
     let states: Vec<KnowledgeState> = result.take(0)?;
     Ok(states)
 }
@@ -432,6 +430,63 @@ pub async fn update_knowledge_certainty(
     .await
 }
 
+/// Record that a knowledge state's certainty was derived from another state.
+pub async fn record_derivation(
+    db: &NarraDb,
+    state: &RecordId,
+    derived_from: &RecordId,
+    derivation: &str,
+) -> Result<KnowledgeState, NarraError> {
+    let mut result = db
+        .query("UPDATE $state SET derived_from = $from, derivation = $derivation RETURN AFTER")
+        .bind(("state", state.clone()))
+        .bind(("from", derived_from.clone()))
+        .bind(("derivation", derivation.to_string()))
+        .await?;
+    let updated: Option<KnowledgeState> = result.take(0)?;
+    updated.ok_or_else(|| NarraError::NotFound {
+        entity_type: "knowledge state".to_string(),
+        id: state.to_string(),
+    })
+}
+
+/// Derivations along a chain of passed-on knowledge, starting with `state`
+/// itself and following `derived_from` back towards the original source.
+pub async fn get_derivation_chain(
+    db: &NarraDb,
+    state: &RecordId,
+) -> Result<Vec<String>, NarraError> {
+    #[derive(Deserialize)]
+    struct Link {
+        derived_from: Option<RecordId>,
+        derivation: Option<String>,
+    }
+
+    let mut chain = Vec::new();
+    let mut seen = HashSet::new();
+    let mut next = Some(state.clone());
+    while let Some(id) = next.take() {
+        if !seen.insert(id.to_string()) {
+            break;
+        }
+        let mut result = db
+            .query("SELECT derived_from, derivation FROM $id")
+            .bind(("id", id))
+            .await?;
+        let link: Option<Link> = result.take(0)?;
+        let Some(Link {
+            derived_from,
+            derivation: Some(derivation),
+        }) = link
+        else {
+            break;
+        };
+        chain.push(derivation);
+        next = derived_from;
+    }
+    Ok(chain)
+}
+
 /// Delete a knowledge state edge by ID.
 ///
 /// Note: In append-only pattern, you typically don't delete history.
@@ -496,16 +551,14 @@ pub async fn get_knowledge_at_event(
     // Query knowledge learned at or before this event
     // Note: We use event's created_at as the reference timestamp
     // Knowledge with event = this event is also included (learned during event)
-    let query = format!(
-        r#"SELECT * FROM knows
-           WHERE in = character:{}
+    let mut result = db
+        .query(
+            r#"SELECT * FROM knows
+           WHERE in = $character
              AND learned_at <= $time
            ORDER BY out, learned_at DESC"#,
-        character_id
-    );
-
-    let mut result = db
-        .query(&query)
+        )
+        .bind(("character", record_id("character", character_id)?))
         .bind(("time", reference.created_at))
         .await?;
 
@@ -544,14 +597,15 @@ pub async fn get_knowledge_history(
     character_id: &str,
     target: &str,
 ) -> Result<Vec<KnowledgeState>, NarraError> {
-    let query = format!(
-        r#"SELECT * FROM knows
-           WHERE in = character:{} AND out = {}
+    let mut result = db
+        .query(
+            r#"SELECT * FROM knows
+           WHERE in = $character AND out = $target
            ORDER BY learned_at ASC"#,
-        character_id, target
-    );
-
-    let mut result = db.query(&query).await?;
+        )
+        .bind(("character", record_id("character", character_id)?))
+        .bind(("target", parse_record_id(target)?))
+        .await?;
     let history: Vec<KnowledgeState> = result.take(0)?;
     Ok(history)
 }
@@ -611,8 +665,9 @@ pub async fn get_transmission_chain(
     db: &NarraDb,
     target: &str,
 ) -> Result<Vec<KnowledgeTransmission>, NarraError> {
-    let query = format!(
-        r#"SELECT
+    let mut result = db
+        .query(
+            r#"SELECT
             string::concat(in.tb, ':', in.id) AS character_id,
             IF source_character != NONE THEN string::concat(source_character.tb, ':', source_character.id) ELSE NONE END AS source_id,
             learning_method,
@@ -620,12 +675,11 @@ pub async fn get_transmission_chain(
             learned_at,
             IF event != NONE THEN string::concat(event.tb, ':', event.id) ELSE NONE END AS event_id
            FROM knows
-           WHERE out = {}
+           WHERE out = $target
            ORDER BY learned_at ASC"#,
-        target
-    );
-
-    let mut result = db.query(&query).await?;
+        )
+        .bind(("target", parse_record_id(target)?))
+        .await?;
     let chain: Vec<KnowledgeTransmission> = result.take(0)?;
     Ok(chain)
 }
@@ -642,6 +696,9 @@ pub struct ConflictingState {
     pub character_id: String,
     pub certainty: CertaintyLevel,
     pub truth_value: Option<String>,
+    /// How the belief was passed on, nearest first (empty unless derived)
+    #[serde(default)]
+    pub derivation: Vec<String>,
 }
 
 /// Find knowledge conflicts where characters believe contradictory facts.
@@ -665,10 +722,12 @@ pub async fn find_knowledge_conflicts(db: &NarraDb) -> Result<Vec<KnowledgeConfl
     // Group by target to identify conflicting knowledge
     let query = r#"
         SELECT
+            id,
             string::concat(out.tb, ':', out.id) AS target,
             string::concat(in.tb, ':', in.id) AS character_id,
             certainty,
-            truth_value
+            truth_value,
+            derivation
         FROM knows
         WHERE certainty = 'believes_wrongly'
         ORDER BY target
@@ -678,10 +737,12 @@ pub async fn find_knowledge_conflicts(db: &NarraDb) -> Result<Vec<KnowledgeConfl
 
     #[derive(Debug, Deserialize)]
     struct WrongBeliefRow {
+        id: RecordId,
         target: String,
         character_id: String,
         certainty: CertaintyLevel,
         truth_value: Option<String>,
+        derivation: Option<String>,
     }
 
     let wrong_beliefs: Vec<WrongBeliefRow> = result.take(0)?;
@@ -691,10 +752,15 @@ pub async fn find_knowledge_conflicts(db: &NarraDb) -> Result<Vec<KnowledgeConfl
         std::collections::HashMap::new();
 
     for row in wrong_beliefs {
+        let derivation = match row.derivation {
+            Some(_) => get_derivation_chain(db, &row.id).await?,
+            None => Vec::new(),
+        };
         let state = ConflictingState {
             character_id: row.character_id,
             certainty: row.certainty,
             truth_value: row.truth_value,
+            derivation,
         };
         conflicts_map.entry(row.target).or_default().push(state);
     }
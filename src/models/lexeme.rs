@@ -0,0 +1,142 @@
+//! Lexicon entries: words of the world's constructed languages.
+//!
+//! Each word carries a gloss (what it means), optionally a part of speech,
+//! etymology, and the language it belongs to. `(language, word)` is unique.
+
+use crate::db::connection::NarraDb;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// A word in the lexicon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lexeme {
+    pub id: RecordId,
+    pub word: String,
+    pub gloss: String,
+    pub part_of_speech: Option<String>,
+    pub etymology: Option<String>,
+    /// Constructed language; none for the world's default tongue
+    pub language: Option<String>,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+/// Data for creating (or, on import, overwriting) a lexicon entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct LexemeCreate {
+    pub word: String,
+    pub gloss: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_of_speech: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etymology: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+// ============================================================================
+// Lexeme CRUD Operations
+// ============================================================================
+
+fn validate(data: &LexemeCreate) -> Result<(), NarraError> {
+    if data.word.trim().is_empty() {
+        return Err(NarraError::Validation(
+            "A lexicon entry needs a word".into(),
+        ));
+    }
+    if data.gloss.trim().is_empty() {
+        return Err(NarraError::Validation(format!(
+            "'{}' needs a gloss",
+            data.word
+        )));
+    }
+    Ok(())
+}
+
+/// Create a new lexicon entry. Fails if the word already exists in its language.
+pub async fn create_lexeme(db: &NarraDb, data: LexemeCreate) -> Result<Lexeme, NarraError> {
+    validate(&data)?;
+    if find_lexeme(db, &data.word, data.language.as_deref())
+        .await?
+        .is_some()
+    {
+        return Err(NarraError::Conflict(format!(
+            "'{}' is already in the lexicon",
+            data.word
+        )));
+    }
+    let result: Option<Lexeme> = db.create("lexeme").content(data).await?;
+    result.ok_or_else(|| NarraError::Database("Failed to create lexicon entry".into()))
+}
+
+/// Create an entry, or overwrite the gloss, part of speech and etymology of
+/// the existing one. Returns the entry and whether it was created.
+pub async fn upsert_lexeme(db: &NarraDb, data: LexemeCreate) -> Result<(Lexeme, bool), NarraError> {
+    validate(&data)?;
+    let Some(existing) = find_lexeme(db, &data.word, data.language.as_deref()).await? else {
+        return Ok((create_lexeme(db, data).await?, true));
+    };
+    let mut result = db
+        .query(
+            "UPDATE $id SET gloss = $gloss, part_of_speech = $pos, etymology = $etymology \
+             RETURN AFTER",
+        )
+        .bind(("id", existing.id))
+        .bind(("gloss", data.gloss))
+        .bind(("pos", data.part_of_speech))
+        .bind(("etymology", data.etymology))
+        .await?;
+    let updated: Option<Lexeme> = result.take(0)?;
+    let updated =
+        updated.ok_or_else(|| NarraError::Database("Failed to update lexicon entry".into()))?;
+    Ok((updated, false))
+}
+
+/// Get a lexicon entry by ID (key part only).
+pub async fn get_lexeme(db: &NarraDb, id: &str) -> Result<Option<Lexeme>, NarraError> {
+    let result: Option<Lexeme> = db.select(("lexeme", id)).await?;
+    Ok(result)
+}
+
+/// Look a word up in a language (case-insensitive).
+pub async fn find_lexeme(
+    db: &NarraDb,
+    word: &str,
+    language: Option<&str>,
+) -> Result<Option<Lexeme>, NarraError> {
+    let mut result = db
+        .query(
+            "SELECT * FROM lexeme WHERE string::lowercase(word) = string::lowercase($word) \
+             AND language = $language LIMIT 1",
+        )
+        .bind(("word", word.to_string()))
+        .bind(("language", language.map(String::from)))
+        .await?;
+    let found: Vec<Lexeme> = result.take(0)?;
+    Ok(found.into_iter().next())
+}
+
+/// List lexicon entries alphabetically, optionally for one language.
+pub async fn list_lexemes(db: &NarraDb, language: Option<&str>) -> Result<Vec<Lexeme>, NarraError> {
+    let mut result = match language {
+        Some(language) => {
+            db.query("SELECT * FROM lexeme WHERE language = $language ORDER BY word ASC")
+                .bind(("language", language.to_string()))
+                .await?
+        }
+        None => {
+            db.query("SELECT * FROM lexeme ORDER BY language ASC, word ASC")
+                .await?
+        }
+    };
+    let lexemes: Vec<Lexeme> = result.take(0)?;
+    Ok(lexemes)
+}
+
+/// Delete a lexicon entry by ID (key part only).
+pub async fn delete_lexeme(db: &NarraDb, id: &str) -> Result<Option<Lexeme>, NarraError> {
+    let result: Option<Lexeme> = db.delete(("lexeme", id)).await?;
+    Ok(result)
+}
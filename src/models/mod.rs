@@ -1,35 +1,55 @@
 pub mod annotation;
+pub mod asset;
 pub mod character;
+pub mod dialogue;
 pub mod event;
 pub mod fact;
+pub mod foreshadow;
+pub mod goal;
 pub mod knowledge;
+pub mod lexeme;
 pub mod location;
 pub mod note;
 pub mod perception;
 pub mod phase;
 pub mod relationship;
+pub mod requires;
 pub mod scene;
+pub mod style;
+pub mod tag;
+pub mod term;
+pub mod thread;
 
 pub use annotation::{
     Annotation, AnnotationCreate, EmotionOutput, EmotionScore, NerEntity, NerOutput, ThemeOutput,
     ThemeScore,
 };
+pub use asset::{Asset, AssetCreate};
 pub use character::{Character, CharacterCreate, CharacterUpdate};
+pub use dialogue::{Dialogue, DialogueCreate};
 pub use event::{Event, EventCreate, EventUpdate};
 pub use fact::{
-    EnforcementLevel, FactApplication, FactCategory, FactCreate, FactScope, FactUpdate, PovScope,
-    TemporalScope, UniverseFact,
+    EnforcementLevel, FactApplication, FactCategory, FactCreate, FactException, FactScope,
+    FactUpdate, PovScope, TemporalScope, UniverseFact,
 };
+pub use foreshadow::Foreshadow;
+pub use goal::{Goal, GoalCreate};
 pub use knowledge::{
     CertaintyLevel, Knowledge, KnowledgeConflict, KnowledgeCreate, KnowledgeState,
     KnowledgeStateCreate, KnowledgeTransmission, LearningMethod,
 };
+pub use lexeme::{Lexeme, LexemeCreate};
 pub use location::{Location, LocationCreate, LocationUpdate};
 pub use note::{Note, NoteAttachment, NoteCreate, NoteUpdate};
 pub use perception::{Perception, PerceptionCreate, PerceptionUpdate};
 pub use phase::Phase;
 pub use relationship::{Relationship, RelationshipCreate};
+pub use requires::Requirement;
 pub use scene::{
     Involvement, InvolvementCreate, Scene, SceneCreate, SceneParticipant, SceneParticipantCreate,
     SceneUpdate,
 };
+pub use style::{StyleRule, StyleRuleCreate, StyleRuleKind};
+pub use tag::TagCount;
+pub use term::{Term, TermCreate};
+pub use thread::{Thread, ThreadCreate};
@@ -4,36 +4,140 @@
 //! event, scene, knowledge). Attachments are stored as graph edges in the
 //! note_attachment table.
 
+use std::fmt;
+use std::str::FromStr;
+
 use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use surrealdb::{Datetime, RecordId};
 
 use crate::NarraError;
 
+/// What a note is for.
+///
+/// Todos show up in session context until resolved, critiques are attached
+/// to the scenes they critique, and research notes are surfaced by `ask`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteKind {
+    #[default]
+    General,
+    Research,
+    Todo,
+    Critique,
+}
+
+impl NoteKind {
+    /// Whether notes of this kind are worked through (open → resolved).
+    pub fn has_status(self) -> bool {
+        matches!(self, NoteKind::Todo | NoteKind::Critique)
+    }
+}
+
+impl fmt::Display for NoteKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NoteKind::General => "general",
+            NoteKind::Research => "research",
+            NoteKind::Todo => "todo",
+            NoteKind::Critique => "critique",
+        })
+    }
+}
+
+impl FromStr for NoteKind {
+    type Err = NarraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "general" => Ok(NoteKind::General),
+            "research" => Ok(NoteKind::Research),
+            "todo" => Ok(NoteKind::Todo),
+            "critique" => Ok(NoteKind::Critique),
+            _ => Err(NarraError::Validation(format!(
+                "Note kind must be general, research, todo, or critique (got '{}')",
+                s
+            ))),
+        }
+    }
+}
+
+/// Where a todo or critique stands. Other kinds stay `Open`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteStatus {
+    #[default]
+    Open,
+    Resolved,
+    /// Closed without acting on it (a critique you disagree with)
+    Dismissed,
+}
+
+impl fmt::Display for NoteStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NoteStatus::Open => "open",
+            NoteStatus::Resolved => "resolved",
+            NoteStatus::Dismissed => "dismissed",
+        })
+    }
+}
+
+impl FromStr for NoteStatus {
+    type Err = NarraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(NoteStatus::Open),
+            "resolved" => Ok(NoteStatus::Resolved),
+            "dismissed" => Ok(NoteStatus::Dismissed),
+            _ => Err(NarraError::Validation(format!(
+                "Note status must be open, resolved, or dismissed (got '{}')",
+                s
+            ))),
+        }
+    }
+}
+
 /// A freeform note with optional entity attachments.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Note {
     pub id: RecordId,
     pub title: String,
     pub body: String,
+    #[serde(default)]
+    pub kind: NoteKind,
+    #[serde(default)]
+    pub status: NoteStatus,
+    /// Freeform tags (see `models::tag`)
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
 }
 
 /// Data for creating a new note.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct NoteCreate {
     pub title: String,
     pub body: String,
+    pub kind: NoteKind,
+    pub status: NoteStatus,
 }
 
 /// Data for updating an existing note.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct NoteUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<NoteKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<NoteStatus>,
 }
 
 /// An attachment edge from note to any entity.
@@ -44,6 +148,10 @@ pub struct NoteAttachment {
     pub note: RecordId,
     #[serde(rename = "out")]
     pub entity: RecordId,
+    /// Link text, when a `[[...]]` link in the body made or confirmed this
+    /// attachment (see `services::note_links`)
+    #[serde(default)]
+    pub link: Option<String>,
     pub attached_at: Datetime,
 }
 
@@ -154,13 +262,56 @@ pub async fn list_notes(
     Ok(notes)
 }
 
+/// Set the status of a todo or critique.
+///
+/// # Returns
+///
+/// The updated note, None if it doesn't exist, or a validation error for
+/// kinds that have no status.
+pub async fn set_note_status(
+    db: &NarraDb,
+    id: &str,
+    status: NoteStatus,
+) -> Result<Option<Note>, NarraError> {
+    let Some(note) = get_note(db, id).await? else {
+        return Ok(None);
+    };
+    if !note.kind.has_status() {
+        return Err(NarraError::Validation(format!(
+            "Only todo and critique notes have a status; '{}' is a {} note",
+            note.title, note.kind
+        )));
+    }
+    update_note(
+        db,
+        id,
+        NoteUpdate {
+            status: Some(status),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Check that a critique names at least one scene to attach to.
+pub fn check_critique_targets(kind: NoteKind, attach_to: &[String]) -> Result<(), NarraError> {
+    if kind == NoteKind::Critique && !attach_to.iter().any(|id| id.starts_with("scene:")) {
+        return Err(NarraError::Validation(
+            "A critique must be attached to at least one scene (e.g. scene:opening)".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Note Attachment Operations
 // ============================================================================
 
 /// Attach a note to any entity.
 ///
-/// Uses SurrealDB RELATE to create a graph edge from note to entity.
+/// Uses SurrealDB RELATE to create a graph edge from note to entity. An
+/// existing link-only attachment to the same entity is kept and marked
+/// manual instead, so it outlives the link.
 ///
 /// # Arguments
 ///
@@ -177,10 +328,20 @@ pub async fn attach_note(
     entity_id: &str,
 ) -> Result<NoteAttachment, NarraError> {
     let note_ref = RecordId::from(("note", note_id));
-    let (table, key) = entity_id
-        .split_once(':')
-        .ok_or_else(|| NarraError::Validation(format!("Invalid entity ID: {}", entity_id)))?;
-    let entity_ref = RecordId::from((table, key));
+    let entity_ref = parse_record_id(entity_id)?;
+
+    let mut result = db
+        .query(
+            "UPDATE note_attachment SET manual = true \
+             WHERE in = $from AND out = $to AND manual = false RETURN AFTER",
+        )
+        .bind(("from", note_ref.clone()))
+        .bind(("to", entity_ref.clone()))
+        .await?;
+    let existing: Vec<NoteAttachment> = result.take(0)?;
+    if let Some(attachment) = existing.into_iter().next() {
+        return Ok(attachment);
+    }
 
     let mut result = db
         .query("RELATE $from->note_attachment->$to")
@@ -204,10 +365,7 @@ pub async fn attach_note(
 /// Ok(()) if detachment succeeded (or edge didn't exist).
 pub async fn detach_note(db: &NarraDb, note_id: &str, entity_id: &str) -> Result<(), NarraError> {
     let note_ref = RecordId::from(("note", note_id));
-    let (table, key) = entity_id
-        .split_once(':')
-        .ok_or_else(|| NarraError::Validation(format!("Invalid entity ID: {}", entity_id)))?;
-    let entity_ref = RecordId::from((table, key));
+    let entity_ref = parse_record_id(entity_id)?;
 
     db.query("DELETE note_attachment WHERE in = $note_ref AND out = $entity_ref")
         .bind(("note_ref", note_ref))
@@ -250,10 +408,7 @@ pub async fn get_note_attachments(
 ///
 /// A vector of notes attached to this entity.
 pub async fn get_entity_notes(db: &NarraDb, entity_id: &str) -> Result<Vec<Note>, NarraError> {
-    let (table, key) = entity_id
-        .split_once(':')
-        .ok_or_else(|| NarraError::Validation(format!("Invalid entity ID: {}", entity_id)))?;
-    let entity_ref = RecordId::from((table, key));
+    let entity_ref = parse_record_id(entity_id)?;
 
     let mut result = db
         .query("SELECT in.* AS note FROM note_attachment WHERE out = $entity_ref")
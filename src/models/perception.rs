@@ -6,6 +6,7 @@
 //! where what A knows about B differs from what B knows about A.
 
 use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
 use serde::{Deserialize, Serialize};
 use surrealdb::{Datetime, RecordId};
 
@@ -47,6 +48,10 @@ pub struct Perception {
     pub tension_level: Option<i32>,
     /// Notes about the history of this relationship from this perspective.
     pub history_notes: Option<String>,
+    /// How far this character trusts the other (0.0-1.0); unset means inferred
+    /// (see `services::trust`).
+    #[serde(default)]
+    pub trust: Option<f32>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
 }
@@ -105,19 +110,18 @@ pub async fn create_perception(
     to_character_id: &str,
     data: PerceptionCreate,
 ) -> Result<Perception, NarraError> {
-    let query = format!(
-        r#"RELATE character:{}->perceives->character:{} SET
+    let mut result = db
+        .query(
+            r#"RELATE $from->perceives->$to SET
             rel_types = $rel_types,
             subtype = $subtype,
             feelings = $feelings,
             perception = $perception,
             tension_level = $tension_level,
             history_notes = $history_notes"#,
-        from_character_id, to_character_id
-    );
-
-    let mut result = db
-        .query(&query)
+        )
+        .bind(("from", record_id("character", from_character_id)?))
+        .bind(("to", record_id("character", to_character_id)?))
         .bind(("rel_types", data.rel_types))
         .bind(("subtype", data.subtype))
         .bind(("feelings", data.feelings))
@@ -177,11 +181,11 @@ pub async fn get_perception(
     from_char: &str,
     to_char: &str,
 ) -> Result<Option<Perception>, NarraError> {
-    let query = format!(
-        "SELECT * FROM perceives WHERE in = character:{} AND out = character:{}",
-        from_char, to_char
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM perceives WHERE in = $from AND out = $to")
+        .bind(("from", record_id("character", from_char)?))
+        .bind(("to", record_id("character", to_char)?))
+        .await?;
     let perceptions: Vec<Perception> = result.take(0)?;
     Ok(perceptions.into_iter().next())
 }
@@ -202,11 +206,10 @@ pub async fn get_perceptions_from(
     db: &NarraDb,
     character_id: &str,
 ) -> Result<Vec<Perception>, NarraError> {
-    let query = format!(
-        "SELECT * FROM perceives WHERE in = character:{}",
-        character_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM perceives WHERE in = $character")
+        .bind(("character", record_id("character", character_id)?))
+        .await?;
     let perceptions: Vec<Perception> = result.take(0)?;
     Ok(perceptions)
 }
@@ -227,11 +230,10 @@ pub async fn get_perceptions_of(
     db: &NarraDb,
     character_id: &str,
 ) -> Result<Vec<Perception>, NarraError> {
-    let query = format!(
-        "SELECT * FROM perceives WHERE out = character:{}",
-        character_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM perceives WHERE out = $character")
+        .bind(("character", record_id("character", character_id)?))
+        .await?;
     let perceptions: Vec<Perception> = result.take(0)?;
     Ok(perceptions)
 }
@@ -281,6 +283,41 @@ pub async fn update_perception(
     Ok(result)
 }
 
+/// Set or clear how far `from_char` trusts `to_char`.
+///
+/// Trust lives on the perception edge, so one must exist. `None` clears the
+/// explicit value and trust is inferred again.
+///
+/// # Returns
+///
+/// The updated perception if found, None otherwise.
+pub async fn set_trust(
+    db: &NarraDb,
+    from_char: &str,
+    to_char: &str,
+    trust: Option<f32>,
+) -> Result<Option<Perception>, NarraError> {
+    if let Some(value) = trust {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(NarraError::Validation(format!(
+                "Trust must be between 0.0 and 1.0, got {}",
+                value
+            )));
+        }
+    }
+    let mut result = db
+        .query(
+            "UPDATE perceives SET trust = $trust \
+             WHERE in = $from AND out = $to RETURN AFTER",
+        )
+        .bind(("from", record_id("character", from_char)?))
+        .bind(("to", record_id("character", to_char)?))
+        .bind(("trust", trust))
+        .await?;
+    let perceptions: Vec<Perception> = result.take(0)?;
+    Ok(perceptions.into_iter().next())
+}
+
 /// Delete a perception by ID.
 ///
 /// # Arguments
@@ -3,8 +3,12 @@
 //! Phases are detected via `TemporalService::detect_phases()` and optionally
 //! saved to SurrealDB as `phase:phase_0`, `phase:phase_1`, etc. with
 //! `belongs_to_phase` membership edges for instant loading.
+//!
+//! Custom labels live in `phase_label` together with the members of the
+//! phase they were given to, so re-detection can carry them over.
 
 use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
 use crate::NarraError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -24,6 +28,9 @@ pub struct Phase {
     pub weights_neighborhood: f64,
     pub weights_temporal: f64,
     pub member_count: i64,
+    /// Generated label, kept when `label` is a custom name
+    #[serde(default)]
+    pub auto_label: Option<String>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
 }
@@ -43,6 +50,15 @@ pub struct PhaseCreate {
     pub weights_neighborhood: f64,
     pub weights_temporal: f64,
     pub member_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_label: Option<String>,
+}
+
+/// A custom phase label and the members of the phase it was given to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseLabel {
+    pub label: String,
+    pub members: Vec<String>,
 }
 
 /// A membership edge: entity belongs to a phase.
@@ -118,10 +134,7 @@ pub async fn create_membership(
     centrality: f64,
     sequence_position: Option<f64>,
 ) -> Result<PhaseMembership, NarraError> {
-    let (entity_table, entity_key) = entity_id
-        .split_once(':')
-        .ok_or_else(|| NarraError::Validation(format!("Invalid entity ID: {}", entity_id)))?;
-    let entity_ref = RecordId::from((entity_table, entity_key));
+    let entity_ref = parse_record_id(entity_id)?;
     let phase_ref = RecordId::from(("phase", phase_id));
 
     let mut result = db
@@ -157,3 +170,38 @@ pub async fn get_phase_members(
     let members: Vec<PhaseMembership> = result.take(0)?;
     Ok(members)
 }
+
+/// Set a saved phase's label, keeping the generated one in `auto_label`.
+pub async fn set_phase_label(
+    db: &NarraDb,
+    id: &str,
+    label: &str,
+    auto_label: &str,
+) -> Result<Option<Phase>, NarraError> {
+    let mut result = db
+        .query("UPDATE $phase SET label = $label, auto_label = $auto_label RETURN AFTER")
+        .bind(("phase", RecordId::from(("phase", id))))
+        .bind(("label", label.to_string()))
+        .bind(("auto_label", auto_label.to_string()))
+        .await?;
+    let phase: Option<Phase> = result.take(0)?;
+    Ok(phase)
+}
+
+/// All custom phase labels.
+pub async fn list_phase_labels(db: &NarraDb) -> Result<Vec<PhaseLabel>, NarraError> {
+    let mut result = db
+        .query("SELECT label, members FROM phase_label ORDER BY label ASC")
+        .await?;
+    let labels: Vec<PhaseLabel> = result.take(0)?;
+    Ok(labels)
+}
+
+/// Replace all custom phase labels.
+pub async fn replace_phase_labels(db: &NarraDb, labels: &[PhaseLabel]) -> Result<(), NarraError> {
+    db.query("DELETE FROM phase_label; FOR $l IN $labels { CREATE phase_label CONTENT $l; };")
+        .bind(("labels", labels.to_vec()))
+        .await?
+        .check()?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
 use serde::{Deserialize, Serialize};
 use surrealdb::{Datetime, RecordId};
 
@@ -29,6 +30,9 @@ pub struct Relationship {
     pub rel_type: String,
     pub subtype: Option<String>,
     pub label: Option<String>,
+    /// One-sided or contradictory on purpose; skipped by reciprocity checks
+    #[serde(default)]
+    pub intentional: bool,
     pub created_at: Datetime,
 }
 
@@ -58,16 +62,18 @@ pub async fn create_relationship(
     db: &NarraDb,
     data: RelationshipCreate,
 ) -> Result<Relationship, NarraError> {
-    let query = format!(
-        r#"RELATE character:{}->relates_to->character:{} SET
+    let from = record_id("character", &data.from_character_id)?;
+    let to = record_id("character", &data.to_character_id)?;
+
+    let mut result = db
+        .query(
+            r#"RELATE $from->relates_to->$to SET
             rel_type = $rel_type,
             subtype = $subtype,
             label = $label"#,
-        data.from_character_id, data.to_character_id
-    );
-
-    let mut result = db
-        .query(&query)
+        )
+        .bind(("from", from))
+        .bind(("to", to))
         .bind(("rel_type", data.rel_type))
         .bind(("subtype", data.subtype))
         .bind(("label", data.label))
@@ -91,11 +97,10 @@ pub async fn get_relationships_from(
     db: &NarraDb,
     character_id: &str,
 ) -> Result<Vec<Relationship>, NarraError> {
-    let query = format!(
-        "SELECT * FROM relates_to WHERE in = character:{}",
-        character_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM relates_to WHERE in = $character")
+        .bind(("character", record_id("character", character_id)?))
+        .await?;
     let rels: Vec<Relationship> = result.take(0)?;
     Ok(rels)
 }
@@ -114,11 +119,10 @@ pub async fn get_relationships_to(
     db: &NarraDb,
     character_id: &str,
 ) -> Result<Vec<Relationship>, NarraError> {
-    let query = format!(
-        "SELECT * FROM relates_to WHERE out = character:{}",
-        character_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM relates_to WHERE out = $character")
+        .bind(("character", record_id("character", character_id)?))
+        .await?;
     let rels: Vec<Relationship> = result.take(0)?;
     Ok(rels)
 }
@@ -140,11 +144,10 @@ pub async fn get_all_relationships(
     db: &NarraDb,
     character_id: &str,
 ) -> Result<Vec<Relationship>, NarraError> {
-    let query = format!(
-        "SELECT * FROM relates_to WHERE in = character:{} OR out = character:{}",
-        character_id, character_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM relates_to WHERE in = $character OR out = $character")
+        .bind(("character", record_id("character", character_id)?))
+        .await?;
     let rels: Vec<Relationship> = result.take(0)?;
     Ok(rels)
 }
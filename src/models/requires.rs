@@ -0,0 +1,68 @@
+//! Scene dependencies.
+//!
+//! A `requires` edge runs from a scene to an earlier scene it relies on.
+//! `analyze reorder` uses these (plus dependencies inferred from knowledge
+//! premises) to report what a proposed move would break.
+
+use crate::db::connection::NarraDb;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// A scene -> prerequisite scene edge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Requirement {
+    pub id: RecordId,
+    #[serde(rename = "in")]
+    pub scene: RecordId,
+    #[serde(rename = "out")]
+    pub prerequisite: RecordId,
+    pub note: Option<String>,
+    pub created_at: Datetime,
+}
+
+/// Parse a scene ID.
+pub fn scene_ref(id: &str) -> Result<RecordId, NarraError> {
+    match id.split_once(':') {
+        Some(("scene", key)) => Ok(RecordId::from(("scene", key))),
+        _ => Err(NarraError::Validation(format!(
+            "Scene dependencies link scenes; got '{}'",
+            id
+        ))),
+    }
+}
+
+/// Record that `scene_id` relies on `prerequisite_id` coming first.
+pub async fn create_requirement(
+    db: &NarraDb,
+    scene_id: &str,
+    prerequisite_id: &str,
+    note: Option<String>,
+) -> Result<Requirement, NarraError> {
+    let scene = scene_ref(scene_id)?;
+    let prerequisite = scene_ref(prerequisite_id)?;
+    if scene == prerequisite {
+        return Err(NarraError::Validation(
+            "A scene cannot require itself".to_string(),
+        ));
+    }
+
+    let mut result = db
+        .query("RELATE $scene->requires->$prerequisite SET note = $note")
+        .bind(("scene", scene))
+        .bind(("prerequisite", prerequisite))
+        .bind(("note", note))
+        .await?;
+    let edge: Option<Requirement> = result.take(0)?;
+    edge.ok_or_else(|| NarraError::Database("Failed to create scene dependency".into()))
+}
+
+/// All scene dependencies.
+pub async fn list_requirements(db: &NarraDb) -> Result<Vec<Requirement>, NarraError> {
+    let mut result = db
+        .query("SELECT * FROM requires ORDER BY created_at ASC")
+        .await?;
+    let edges: Vec<Requirement> = result.take(0)?;
+    Ok(edges)
+}
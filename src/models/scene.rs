@@ -4,12 +4,18 @@
 //! and location. They track character participation through the `participates_in`
 //! edge table and event involvement through the `involved_in` edge table.
 
+use std::collections::HashSet;
+
 use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
 use serde::{Deserialize, Serialize};
 use surrealdb::{Datetime, RecordId};
 
 use crate::NarraError;
 
+/// Valid values for `Scene::draft_status`, in revision order.
+pub const DRAFT_STATUSES: &[&str] = &["outline", "drafted", "revised", "final"];
+
 /// A scene in the narrative.
 ///
 /// Scenes are anchored to:
@@ -25,10 +31,23 @@ pub struct Scene {
     pub primary_location: RecordId,
     #[serde(default)]
     pub secondary_locations: Vec<RecordId>,
+    /// Manuscript word count, when tracked
+    #[serde(default)]
+    pub word_count: Option<i64>,
+    /// Freeform tags (see `models::tag`)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Where the prose stands (see [`DRAFT_STATUSES`])
+    #[serde(default = "default_draft_status")]
+    pub draft_status: String,
     pub created_at: Datetime,
     pub updated_at: Datetime,
 }
 
+fn default_draft_status() -> String {
+    "outline".to_string()
+}
+
 /// Data for creating a new scene.
 #[derive(Debug, Serialize)]
 pub struct SceneCreate {
@@ -96,6 +115,57 @@ pub async fn get_scene(db: &NarraDb, id: &str) -> Result<Option<Scene>, NarraErr
     Ok(result)
 }
 
+/// Check that a draft status is one of [`DRAFT_STATUSES`].
+pub fn validate_draft_status(status: &str) -> Result<(), NarraError> {
+    if DRAFT_STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(NarraError::Validation(format!(
+            "Invalid draft status '{}'. Expected one of: {}",
+            status,
+            DRAFT_STATUSES.join(", ")
+        )))
+    }
+}
+
+/// Set the draft status of several scenes (key parts or full IDs) at once.
+/// Returns the scenes that changed.
+pub async fn set_draft_status(
+    db: &NarraDb,
+    ids: &[String],
+    status: &str,
+) -> Result<Vec<Scene>, NarraError> {
+    validate_draft_status(status)?;
+    let ids = ids
+        .iter()
+        .map(|id| record_id("scene", id))
+        .collect::<Result<Vec<RecordId>, _>>()?;
+    let mut result = db
+        .query(
+            "UPDATE scene SET draft_status = $status \
+             WHERE id IN $ids AND draft_status != $status RETURN AFTER",
+        )
+        .bind(("ids", ids))
+        .bind(("status", status.to_string()))
+        .await?;
+    let scenes: Vec<Scene> = result.take(0)?;
+    Ok(scenes)
+}
+
+/// IDs of every scene at a draft status.
+pub async fn scene_ids_with_status(
+    db: &NarraDb,
+    status: &str,
+) -> Result<HashSet<String>, NarraError> {
+    validate_draft_status(status)?;
+    let mut result = db
+        .query("SELECT VALUE id FROM scene WHERE (draft_status ?? 'outline') = $status")
+        .bind(("status", status.to_string()))
+        .await?;
+    let ids: Vec<RecordId> = result.take(0)?;
+    Ok(ids.iter().map(|id| id.to_string()).collect())
+}
+
 /// List all scenes.
 ///
 /// # Arguments
@@ -245,15 +315,14 @@ pub async fn add_scene_participant(
     db: &NarraDb,
     data: SceneParticipantCreate,
 ) -> Result<SceneParticipant, NarraError> {
-    let query = format!(
-        r#"RELATE character:{}->participates_in->scene:{} SET
+    let mut result = db
+        .query(
+            r#"RELATE $character->participates_in->$scene SET
             role = $role,
             notes = $notes"#,
-        data.character_id, data.scene_id
-    );
-
-    let mut result = db
-        .query(&query)
+        )
+        .bind(("character", record_id("character", &data.character_id)?))
+        .bind(("scene", record_id("scene", &data.scene_id)?))
         .bind(("role", data.role))
         .bind(("notes", data.notes))
         .await?;
@@ -276,11 +345,10 @@ pub async fn get_scene_participants(
     db: &NarraDb,
     scene_id: &str,
 ) -> Result<Vec<SceneParticipant>, NarraError> {
-    let query = format!(
-        "SELECT * FROM participates_in WHERE out = scene:{}",
-        scene_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM participates_in WHERE out = $scene")
+        .bind(("scene", record_id("scene", scene_id)?))
+        .await?;
     let participants: Vec<SceneParticipant> = result.take(0)?;
     Ok(participants)
 }
@@ -299,11 +367,10 @@ pub async fn get_character_scenes(
     db: &NarraDb,
     character_id: &str,
 ) -> Result<Vec<SceneParticipant>, NarraError> {
-    let query = format!(
-        "SELECT * FROM participates_in WHERE in = character:{}",
-        character_id
-    );
-    let mut result = db.query(&query).await?;
+    let mut result = db
+        .query("SELECT * FROM participates_in WHERE in = $character")
+        .bind(("character", record_id("character", character_id)?))
+        .await?;
     let participations: Vec<SceneParticipant> = result.take(0)?;
     Ok(participations)
 }
@@ -376,15 +443,14 @@ pub async fn add_event_involvement(
     db: &NarraDb,
     data: InvolvementCreate,
 ) -> Result<Involvement, NarraError> {
-    let query = format!(
-        r#"RELATE character:{}->involved_in->event:{} SET
+    let mut result = db
+        .query(
+            r#"RELATE $character->involved_in->$event SET
             role = $role,
             impact = $impact"#,
-        data.character_id, data.event_id
-    );
-
-    let mut result = db
-        .query(&query)
+        )
+        .bind(("character", record_id("character", &data.character_id)?))
+        .bind(("event", record_id("event", &data.event_id)?))
         .bind(("role", data.role))
         .bind(("impact", data.impact))
         .await?;
@@ -456,3 +522,55 @@ pub async fn delete_event_involvement(
     let result: Option<Involvement> = db.delete(("involved_in", id)).await?;
     Ok(result)
 }
+
+/// Set a character's involvement in an event, updating the role and impact
+/// of an existing edge instead of adding a second one.
+///
+/// # Returns
+///
+/// The created or updated involvement edge.
+pub async fn set_event_involvement(
+    db: &NarraDb,
+    data: InvolvementCreate,
+) -> Result<Involvement, NarraError> {
+    let mut result = db
+        .query(
+            r#"UPDATE involved_in SET role = $role, impact = $impact
+            WHERE in = $character AND out = $event"#,
+        )
+        .bind(("character", record_id("character", &data.character_id)?))
+        .bind(("event", record_id("event", &data.event_id)?))
+        .bind(("role", data.role.clone()))
+        .bind(("impact", data.impact.clone()))
+        .await?;
+    let updated: Vec<Involvement> = result.take(0)?;
+    match updated.into_iter().next() {
+        Some(involvement) => Ok(involvement),
+        None => add_event_involvement(db, data).await,
+    }
+}
+
+/// Remove a character's involvement in an event.
+///
+/// # Arguments
+///
+/// * `db` - Database connection
+/// * `character_id` - Character ID (the key part, not the full RecordId)
+/// * `event_id` - Event ID (the key part, not the full RecordId)
+///
+/// # Returns
+///
+/// The deleted involvement edges (empty if the character wasn't involved).
+pub async fn remove_event_involvement(
+    db: &NarraDb,
+    character_id: &str,
+    event_id: &str,
+) -> Result<Vec<Involvement>, NarraError> {
+    let mut result = db
+        .query("DELETE involved_in WHERE in = $character AND out = $event RETURN BEFORE")
+        .bind(("character", record_id("character", character_id)?))
+        .bind(("event", record_id("event", event_id)?))
+        .await?;
+    let removed: Vec<Involvement> = result.take(0)?;
+    Ok(removed)
+}
@@ -0,0 +1,111 @@
+//! Style sheet rules: spelling choices, capitalization of in-world terms,
+//! and punctuation conventions. See `services::style` for how they are
+//! checked against manuscript text.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::db::connection::NarraDb;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// What a style rule governs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StyleRuleKind {
+    /// Whole words in `avoid` are spelled `preferred` instead
+    Spelling,
+    /// `preferred` is always written with exactly this case
+    Capitalization,
+    /// Literal sequences in `avoid` are written `preferred` instead
+    Punctuation,
+}
+
+impl fmt::Display for StyleRuleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StyleRuleKind::Spelling => "spelling",
+            StyleRuleKind::Capitalization => "capitalization",
+            StyleRuleKind::Punctuation => "punctuation",
+        })
+    }
+}
+
+impl FromStr for StyleRuleKind {
+    type Err = NarraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "spelling" => Ok(StyleRuleKind::Spelling),
+            "capitalization" | "capitalisation" | "caps" => Ok(StyleRuleKind::Capitalization),
+            "punctuation" => Ok(StyleRuleKind::Punctuation),
+            _ => Err(NarraError::Validation(format!(
+                "Style rule kind must be spelling, capitalization, or punctuation (got '{}')",
+                s
+            ))),
+        }
+    }
+}
+
+/// A rule in the project style sheet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StyleRule {
+    pub id: RecordId,
+    pub kind: StyleRuleKind,
+    pub preferred: String,
+    #[serde(default)]
+    pub avoid: Vec<String>,
+    pub note: Option<String>,
+    pub created_at: Datetime,
+}
+
+/// Data for creating a new style rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct StyleRuleCreate {
+    pub kind: StyleRuleKind,
+    pub preferred: String,
+    pub avoid: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+// ============================================================================
+// Style Rule CRUD Operations
+// ============================================================================
+
+/// Create a style rule. Spelling and punctuation rules need something to avoid.
+pub async fn create_style_rule(
+    db: &NarraDb,
+    data: StyleRuleCreate,
+) -> Result<StyleRule, NarraError> {
+    if data.preferred.trim().is_empty() {
+        return Err(NarraError::Validation(
+            "A style rule needs a preferred form".into(),
+        ));
+    }
+    if data.kind != StyleRuleKind::Capitalization && data.avoid.is_empty() {
+        return Err(NarraError::Validation(format!(
+            "A {} rule needs at least one form to avoid (--avoid)",
+            data.kind
+        )));
+    }
+    let result: Option<StyleRule> = db.create("style_rule").content(data).await?;
+    result.ok_or_else(|| NarraError::Database("Failed to create style rule".into()))
+}
+
+/// The whole style sheet, grouped by kind.
+pub async fn list_style_rules(db: &NarraDb) -> Result<Vec<StyleRule>, NarraError> {
+    let mut result = db
+        .query("SELECT * FROM style_rule ORDER BY kind ASC, preferred ASC")
+        .await?;
+    let rules: Vec<StyleRule> = result.take(0)?;
+    Ok(rules)
+}
+
+/// Delete a style rule by ID (key part only).
+pub async fn delete_style_rule(db: &NarraDb, id: &str) -> Result<Option<StyleRule>, NarraError> {
+    let result: Option<StyleRule> = db.delete(("style_rule", id)).await?;
+    Ok(result)
+}
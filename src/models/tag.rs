@@ -0,0 +1,202 @@
+//! Freeform tags on scenes, events, and notes.
+//!
+//! Tags are stored as a lowercase, deduplicated `tags` array on the record
+//! itself ("action", "flashback", "b-plot"). There is no tag table: the set
+//! of tags in use is whatever the records currently carry.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::NarraError;
+
+/// Tables that carry a `tags` field.
+pub const TAGGABLE_TABLES: &[&str] = &["scene", "event", "note"];
+
+/// A scene, event, or note carrying a tag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaggedEntity {
+    pub id: RecordId,
+    pub title: String,
+}
+
+/// How often a tag is used, per table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub scenes: usize,
+    pub events: usize,
+    pub notes: usize,
+}
+
+impl TagCount {
+    /// Uses across all taggable tables.
+    pub fn total(&self) -> usize {
+        self.scenes + self.events + self.notes
+    }
+}
+
+/// Canonical form of a tag: trimmed, lowercase, inner whitespace collapsed.
+pub fn normalize_tag(tag: &str) -> Result<String, NarraError> {
+    let normalized = tag
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if normalized.is_empty() {
+        return Err(NarraError::Validation("Tag cannot be empty".into()));
+    }
+    if normalized.contains(',') {
+        return Err(NarraError::Validation(format!(
+            "Tag cannot contain a comma: {}",
+            tag
+        )));
+    }
+    Ok(normalized)
+}
+
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>, NarraError> {
+    let mut out = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = normalize_tag(tag)?;
+        if !out.contains(&tag) {
+            out.push(tag);
+        }
+    }
+    Ok(out)
+}
+
+/// Record ID and table of a taggable entity, rejecting tables without a
+/// `tags` field.
+fn taggable_id(entity_id: &str) -> Result<(RecordId, String), NarraError> {
+    let id = parse_record_id(entity_id)?;
+    let table = entity_id.split_once(':').map_or("", |(t, _)| t);
+    if !TAGGABLE_TABLES.contains(&table) {
+        return Err(NarraError::Validation(format!(
+            "Only scenes, events, and notes can be tagged, not {}",
+            entity_id
+        )));
+    }
+    Ok((id, table.to_string()))
+}
+
+/// Run a tag update and return the entity's tags afterwards.
+async fn update_tags(
+    db: &NarraDb,
+    entity_id: &str,
+    tags: &[String],
+    sql: &str,
+) -> Result<Vec<String>, NarraError> {
+    let (id, table) = taggable_id(entity_id)?;
+    let tags = normalize_tags(tags)?;
+
+    let mut result = db.query(sql).bind(("id", id)).bind(("tags", tags)).await?;
+    let updated: Vec<Vec<String>> = result.take(0)?;
+    updated
+        .into_iter()
+        .next()
+        .ok_or_else(|| NarraError::NotFound {
+            entity_type: table,
+            id: entity_id.to_string(),
+        })
+}
+
+/// Add tags to a scene, event, or note. Returns the full tag list.
+pub async fn add_tags(
+    db: &NarraDb,
+    entity_id: &str,
+    tags: &[String],
+) -> Result<Vec<String>, NarraError> {
+    update_tags(
+        db,
+        entity_id,
+        tags,
+        "UPDATE $id SET tags = array::sort(array::union(tags ?? [], $tags)) RETURN VALUE tags",
+    )
+    .await
+}
+
+/// Remove tags from a scene, event, or note. Returns the remaining tags.
+pub async fn remove_tags(
+    db: &NarraDb,
+    entity_id: &str,
+    tags: &[String],
+) -> Result<Vec<String>, NarraError> {
+    update_tags(
+        db,
+        entity_id,
+        tags,
+        "UPDATE $id SET tags = array::complement(tags ?? [], $tags) RETURN VALUE tags",
+    )
+    .await
+}
+
+/// Tags on a scene, event, or note.
+pub async fn get_tags(db: &NarraDb, entity_id: &str) -> Result<Vec<String>, NarraError> {
+    let (id, table) = taggable_id(entity_id)?;
+
+    let mut result = db
+        .query("SELECT VALUE tags ?? [] FROM $id")
+        .bind(("id", id))
+        .await?;
+    let tags: Vec<Vec<String>> = result.take(0)?;
+    tags.into_iter().next().ok_or_else(|| NarraError::NotFound {
+        entity_type: table,
+        id: entity_id.to_string(),
+    })
+}
+
+/// Scenes, events, and notes carrying `tag`.
+pub async fn tagged_entities(db: &NarraDb, tag: &str) -> Result<Vec<TaggedEntity>, NarraError> {
+    let tag = normalize_tag(tag)?;
+    let mut entities = Vec::new();
+    for table in TAGGABLE_TABLES {
+        let mut result = db
+            .query("SELECT id, title FROM type::table($table) WHERE $tag IN tags ORDER BY title")
+            .bind(("table", *table))
+            .bind(("tag", tag.clone()))
+            .await?;
+        let rows: Vec<TaggedEntity> = result.take(0)?;
+        entities.extend(rows);
+    }
+    Ok(entities)
+}
+
+/// IDs ("scene:abc") of every entity carrying `tag`.
+pub async fn tagged_ids(db: &NarraDb, tag: &str) -> Result<HashSet<String>, NarraError> {
+    Ok(tagged_entities(db, tag)
+        .await?
+        .iter()
+        .map(|e| e.id.to_string())
+        .collect())
+}
+
+/// Every tag in use, most used first.
+pub async fn tag_counts(db: &NarraDb) -> Result<Vec<TagCount>, NarraError> {
+    let mut counts: BTreeMap<String, TagCount> = BTreeMap::new();
+    for table in TAGGABLE_TABLES {
+        let mut result = db
+            .query("SELECT VALUE tags FROM type::table($table) WHERE array::len(tags ?? []) > 0")
+            .bind(("table", *table))
+            .await?;
+        let rows: Vec<Vec<String>> = result.take(0)?;
+        for tag in rows.into_iter().flatten() {
+            let entry = counts.entry(tag.clone()).or_insert_with(|| TagCount {
+                tag,
+                ..Default::default()
+            });
+            match *table {
+                "scene" => entry.scenes += 1,
+                "event" => entry.events += 1,
+                _ => entry.notes += 1,
+            }
+        }
+    }
+
+    let mut counts: Vec<TagCount> = counts.into_values().collect();
+    counts.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.tag.cmp(&b.tag)));
+    Ok(counts)
+}
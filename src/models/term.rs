@@ -0,0 +1,91 @@
+//! Glossary terms: in-world jargon, spells, and slang.
+//!
+//! A term has a definition, the other names it goes by, and optionally the
+//! event where the reader first meets it. `analyze terminology` checks scene
+//! summaries against that first use.
+
+use crate::db::connection::NarraDb;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// A glossary term.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Term {
+    pub id: RecordId,
+    pub name: String,
+    pub definition: String,
+    /// What sort of term it is (jargon, spell, slang, ...)
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Event where the term is introduced
+    pub first_use: Option<RecordId>,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+impl Term {
+    /// The name and every alias.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+/// Data for creating a new term.
+#[derive(Debug, Clone, Serialize)]
+pub struct TermCreate {
+    pub name: String,
+    pub definition: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_use: Option<RecordId>,
+}
+
+// ============================================================================
+// Term CRUD Operations
+// ============================================================================
+
+/// Create a new term.
+pub async fn create_term(db: &NarraDb, data: TermCreate) -> Result<Term, NarraError> {
+    if data.name.trim().is_empty() {
+        return Err(NarraError::Validation("A term needs a name".to_string()));
+    }
+    let result: Option<Term> = db.create("term").content(data).await?;
+    result.ok_or_else(|| NarraError::Database("Failed to create term".into()))
+}
+
+/// Get a term by ID (key part only).
+pub async fn get_term(db: &NarraDb, id: &str) -> Result<Option<Term>, NarraError> {
+    let result: Option<Term> = db.select(("term", id)).await?;
+    Ok(result)
+}
+
+/// List all terms alphabetically.
+pub async fn list_terms(db: &NarraDb) -> Result<Vec<Term>, NarraError> {
+    let mut result = db
+        .query("SELECT * OMIT embedding FROM term ORDER BY name ASC")
+        .await?;
+    let terms: Vec<Term> = result.take(0)?;
+    Ok(terms)
+}
+
+/// Delete a term by ID (key part only).
+pub async fn delete_term(db: &NarraDb, id: &str) -> Result<Option<Term>, NarraError> {
+    let result: Option<Term> = db.delete(("term", id)).await?;
+    Ok(result)
+}
+
+/// Parse a first-use anchor: an `event:` ID.
+pub fn first_use_ref(id: &str) -> Result<RecordId, NarraError> {
+    match id.split_once(':') {
+        Some(("event", key)) if !key.is_empty() => Ok(RecordId::from(("event", key))),
+        _ => Err(NarraError::Validation(format!(
+            "Expected an event ID for first use, got '{}'",
+            id
+        ))),
+    }
+}
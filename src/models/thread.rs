@@ -0,0 +1,170 @@
+//! Plot threads: the thread registry.
+//!
+//! A thread names a storyline that is planted at a setup point (event or
+//! scene) and resolved at a payoff point, and lists the knowledge and facts
+//! it is about. `analyze setups` pairs these against the timeline.
+//!
+//! A thread can also carry a deadline: an event sequence (`resolve_by`) or a
+//! narrative phase (`resolve_by_phase`) it must pay off by. `analyze
+//! thread-deadlines` checks these against the story's current position.
+
+use crate::db::connection::NarraDb;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::NarraError;
+
+/// Valid values for `Thread::status`.
+pub const THREAD_STATUSES: &[&str] = &["open", "resolved", "abandoned"];
+
+/// A registered plot thread.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Thread {
+    pub id: RecordId,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub setup: Option<RecordId>,
+    pub payoff: Option<RecordId>,
+    #[serde(default)]
+    pub elements: Vec<RecordId>,
+    /// Event sequence the thread must resolve by
+    #[serde(default)]
+    pub resolve_by: Option<i64>,
+    /// Phase label or name the thread must resolve by
+    #[serde(default)]
+    pub resolve_by_phase: Option<String>,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+/// Data for creating a new thread.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadCreate {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup: Option<RecordId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payoff: Option<RecordId>,
+    pub elements: Vec<RecordId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_by: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_by_phase: Option<String>,
+}
+
+// ============================================================================
+// Thread CRUD Operations
+// ============================================================================
+
+/// Create a new thread.
+pub async fn create_thread(db: &NarraDb, data: ThreadCreate) -> Result<Thread, NarraError> {
+    validate_status(&data.status)?;
+    let result: Option<Thread> = db.create("thread").content(data).await?;
+    result.ok_or_else(|| NarraError::Database("Failed to create thread".into()))
+}
+
+/// Get a thread by ID (key part only).
+pub async fn get_thread(db: &NarraDb, id: &str) -> Result<Option<Thread>, NarraError> {
+    let result: Option<Thread> = db.select(("thread", id)).await?;
+    Ok(result)
+}
+
+/// List threads, optionally filtered by status, oldest first.
+pub async fn list_threads(db: &NarraDb, status: Option<&str>) -> Result<Vec<Thread>, NarraError> {
+    let mut result = match status {
+        Some(status) => {
+            db.query("SELECT * FROM thread WHERE status = $status ORDER BY created_at ASC")
+                .bind(("status", status.to_string()))
+                .await?
+        }
+        None => {
+            db.query("SELECT * FROM thread ORDER BY created_at ASC")
+                .await?
+        }
+    };
+    let threads: Vec<Thread> = result.take(0)?;
+    Ok(threads)
+}
+
+/// Delete a thread by ID (key part only).
+pub async fn delete_thread(db: &NarraDb, id: &str) -> Result<Option<Thread>, NarraError> {
+    let result: Option<Thread> = db.delete(("thread", id)).await?;
+    Ok(result)
+}
+
+/// Add a knowledge or fact record to a thread's elements.
+pub async fn add_thread_element(
+    db: &NarraDb,
+    thread_id: &str,
+    element_id: &str,
+) -> Result<Option<Thread>, NarraError> {
+    let element = element_ref(element_id)?;
+    let mut result = db
+        .query("UPDATE ONLY $thread SET elements += $element RETURN AFTER")
+        .bind(("thread", RecordId::from(("thread", thread_id))))
+        .bind(("element", element))
+        .await?;
+    let thread: Option<Thread> = result.take(0)?;
+    Ok(thread)
+}
+
+/// Remove a record from a thread's elements.
+pub async fn remove_thread_element(
+    db: &NarraDb,
+    thread_id: &str,
+    element_id: &str,
+) -> Result<Option<Thread>, NarraError> {
+    let element = element_ref(element_id)?;
+    let mut result = db
+        .query("UPDATE ONLY $thread SET elements -= $element RETURN AFTER")
+        .bind(("thread", RecordId::from(("thread", thread_id))))
+        .bind(("element", element))
+        .await?;
+    let thread: Option<Thread> = result.take(0)?;
+    Ok(thread)
+}
+
+/// Split a `--resolve-by` value into a sequence or a phase label.
+pub fn parse_deadline(value: &str) -> (Option<i64>, Option<String>) {
+    match value.trim().parse::<i64>() {
+        Ok(sequence) => (Some(sequence), None),
+        Err(_) => (None, Some(value.trim().to_string())),
+    }
+}
+
+fn validate_status(status: &str) -> Result<(), NarraError> {
+    if THREAD_STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(NarraError::Validation(format!(
+            "Invalid thread status '{}'. Expected one of: {}",
+            status,
+            THREAD_STATUSES.join(", ")
+        )))
+    }
+}
+
+/// Parse a setup/payoff anchor: an `event:` or `scene:` ID.
+pub fn anchor_ref(id: &str) -> Result<RecordId, NarraError> {
+    typed_ref(id, &["event", "scene"])
+}
+
+/// Parse a thread element: a `knowledge:` or `universe_fact:` ID.
+pub fn element_ref(id: &str) -> Result<RecordId, NarraError> {
+    typed_ref(id, &["knowledge", "universe_fact"])
+}
+
+fn typed_ref(id: &str, tables: &[&str]) -> Result<RecordId, NarraError> {
+    match id.split_once(':') {
+        Some((table, key)) if tables.contains(&table) => Ok(RecordId::from((table, key))),
+        _ => Err(NarraError::Validation(format!(
+            "Expected a {} ID, got '{}'",
+            tables.join(" or "),
+            id
+        ))),
+    }
+}
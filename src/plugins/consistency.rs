@@ -0,0 +1,126 @@
+//! Consistency service decorator that adds plugin `on_validate` hooks.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::PluginHost;
+use crate::db::connection::NarraDb;
+use crate::services::{ConsistencyService, ValidationResult, Violation};
+use crate::NarraError;
+
+/// Wraps another `ConsistencyService`, appending violations reported by
+/// plugin `on_validate` hooks to fact-based checks.
+pub struct PluginConsistencyService {
+    inner: Arc<dyn ConsistencyService>,
+    plugins: Arc<PluginHost>,
+    db: Arc<NarraDb>,
+}
+
+impl PluginConsistencyService {
+    pub fn new(
+        inner: Arc<dyn ConsistencyService>,
+        plugins: Arc<PluginHost>,
+        db: Arc<NarraDb>,
+    ) -> Self {
+        Self { inner, plugins, db }
+    }
+
+    /// Current record as plain JSON, with `fields` merged over it.
+    async fn entity_view(
+        &self,
+        entity_id: &str,
+        fields: &serde_json::Value,
+    ) -> Result<serde_json::Value, NarraError> {
+        let (table, key) = entity_id
+            .split_once(':')
+            .ok_or_else(|| NarraError::Validation(format!("Invalid entity ID '{}'", entity_id)))?;
+
+        let mut resp = self
+            .db
+            .query("SELECT * FROM ONLY $ref")
+            .bind(("ref", surrealdb::RecordId::from((table, key))))
+            .await?;
+        let current: surrealdb::Value = resp.take(0)?;
+
+        let mut view = match current.into_inner().into_json() {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+        if let serde_json::Value::Object(updates) = fields {
+            view.extend(updates.clone());
+        }
+        view.insert("id".to_string(), serde_json::json!(entity_id));
+        view.insert("entity_type".to_string(), serde_json::json!(table));
+        Ok(serde_json::Value::Object(view))
+    }
+}
+
+#[async_trait]
+impl ConsistencyService for PluginConsistencyService {
+    async fn check_entity_mutation(
+        &self,
+        entity_id: &str,
+        mutation_fields: &serde_json::Value,
+    ) -> Result<ValidationResult, NarraError> {
+        let mut result = self
+            .inner
+            .check_entity_mutation(entity_id, mutation_fields)
+            .await?;
+
+        let view = self.entity_view(entity_id, mutation_fields).await?;
+        for violation in self.plugins.on_validate(&view).await {
+            result.add_violation(violation);
+        }
+        Ok(result)
+    }
+
+    async fn check_entity_creation(
+        &self,
+        entity_type: &str,
+        creation_data: &serde_json::Value,
+    ) -> Result<ValidationResult, NarraError> {
+        let mut result = self
+            .inner
+            .check_entity_creation(entity_type, creation_data)
+            .await?;
+
+        let mut view = match creation_data {
+            serde_json::Value::Object(map) => map.clone(),
+            _ => serde_json::Map::new(),
+        };
+        view.insert("entity_type".to_string(), serde_json::json!(entity_type));
+        for violation in self
+            .plugins
+            .on_validate(&serde_json::Value::Object(view))
+            .await
+        {
+            result.add_violation(violation);
+        }
+        Ok(result)
+    }
+
+    async fn check_timeline_violations(
+        &self,
+        character_id: &str,
+    ) -> Result<Vec<Violation>, NarraError> {
+        self.inner.check_timeline_violations(character_id).await
+    }
+
+    async fn check_relationship_violations(
+        &self,
+        character_id: &str,
+    ) -> Result<Vec<Violation>, NarraError> {
+        self.inner.check_relationship_violations(character_id).await
+    }
+
+    async fn investigate_contradictions(
+        &self,
+        entity_id: &str,
+        max_depth: usize,
+    ) -> Result<(Vec<Violation>, usize), NarraError> {
+        self.inner
+            .investigate_contradictions(entity_id, max_depth)
+            .await
+    }
+}
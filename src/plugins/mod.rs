@@ -0,0 +1,203 @@
+//! User plugins: Lua scripts that hook into narra without forking the crate.
+//!
+//! Scripts live in `<data_path>/plugins/*.lua` and register hooks through the
+//! `narra` global:
+//!
+//! ```lua
+//! narra.on_create(function(entity) narra.log("created " .. entity.name) end)
+//!
+//! narra.on_validate(function(entity)
+//!   if entity.entity_type == "character" and (entity.age or 0) > 900 then
+//!     return { { severity = "warning", message = "Nobody lives that long here" } }
+//!   end
+//! end)
+//!
+//! narra.command("ftl", "List ships faster than light", function(args)
+//!   return narra.query("SELECT name FROM character WHERE 'ship' IN roles")
+//! end)
+//! ```
+//!
+//! `on_create` hooks receive `entity.created` events; `on_validate` hooks run
+//! inside consistency checks (see `PluginConsistencyService`); commands run via
+//! `narra analyze plugin <name>`.
+
+pub mod consistency;
+pub mod runtime;
+
+pub use consistency::PluginConsistencyService;
+pub use runtime::{Plugin, PluginCommand};
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::db::connection::NarraDb;
+use crate::services::events::{self, EventBus, EventListener};
+use crate::services::{ConsistencySeverity, Violation};
+
+/// All loaded plugins.
+#[derive(Default)]
+pub struct PluginHost {
+    plugins: Vec<Plugin>,
+}
+
+impl PluginHost {
+    /// Load every `*.lua` in `<data_path>/plugins`, in file-name order.
+    ///
+    /// A plugin that fails to load is logged and skipped.
+    pub async fn load(data_path: &Path, db: Arc<NarraDb>) -> Self {
+        let dir = data_path.join("plugins");
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return Self::default();
+        };
+
+        let mut paths: Vec<_> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "lua"))
+            .collect();
+        paths.sort();
+
+        let mut plugins = Vec::new();
+        for path in paths {
+            match Plugin::load(&path, db.clone()).await {
+                Ok(plugin) => {
+                    tracing::info!("Loaded plugin '{}'", plugin.name);
+                    plugins.push(plugin);
+                }
+                Err(e) => tracing::warn!("Skipping plugin {}: {:#}", path.display(), e),
+            }
+        }
+        Self { plugins }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    pub fn has_hook(&self, kind: &str) -> bool {
+        self.plugins.iter().any(|p| p.has_hook(kind))
+    }
+
+    pub fn commands(&self) -> Vec<PluginCommand> {
+        let mut commands: Vec<_> = self.plugins.iter().flat_map(|p| p.commands()).collect();
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        commands
+    }
+
+    /// Run the first plugin command named `name`.
+    pub async fn run_command(&self, name: &str, args: &[String]) -> Result<serde_json::Value> {
+        for plugin in &self.plugins {
+            if let Some(result) = plugin.run_command(name, args).await {
+                return result;
+            }
+        }
+        anyhow::bail!("No plugin provides command '{}'", name)
+    }
+
+    /// Run `on_create` hooks for an `entity.created` payload.
+    pub async fn on_create(&self, entity: &serde_json::Value) {
+        for plugin in &self.plugins {
+            plugin.call_hooks("on_create", entity).await;
+        }
+    }
+
+    /// Run `on_validate` hooks and collect the violations they report.
+    pub async fn on_validate(&self, entity: &serde_json::Value) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for plugin in &self.plugins {
+            for result in plugin.call_hooks("on_validate", entity).await {
+                violations.extend(parse_violations(&plugin.name, &result));
+            }
+        }
+        violations
+    }
+
+    /// Feed `entity.created` events to `on_create` hooks in the background.
+    ///
+    /// Returns `None` when no plugin registered an `on_create` hook.
+    pub fn listen(self: &Arc<Self>, bus: &EventBus) -> Option<EventListener> {
+        if !self.has_hook("on_create") {
+            return None;
+        }
+        let host = self.clone();
+        Some(EventListener::spawn(bus, move |event| {
+            let host = host.clone();
+            async move {
+                if event.event == events::ENTITY_CREATED {
+                    host.on_create(&event.data).await;
+                }
+            }
+        }))
+    }
+}
+
+/// Convert an `on_validate` return value into violations.
+///
+/// Accepts nil, a message string (a warning), one `{severity, message}` table,
+/// or a list of them.
+fn parse_violations(plugin: &str, value: &serde_json::Value) -> Vec<Violation> {
+    let violation = |severity: ConsistencySeverity, message: String| Violation {
+        fact_id: format!("plugin:{}", plugin),
+        fact_title: format!("Plugin: {}", plugin),
+        severity,
+        message,
+        confidence: 1.0,
+        auto_detected_as_intentional: false,
+    };
+
+    let from_object = |obj: &serde_json::Value| {
+        let message = obj.get("message")?.as_str()?.to_string();
+        let severity = match obj.get("severity").and_then(|s| s.as_str()) {
+            Some(s) if s.eq_ignore_ascii_case("critical") => ConsistencySeverity::Critical,
+            Some(s) if s.eq_ignore_ascii_case("info") => ConsistencySeverity::Info,
+            _ => ConsistencySeverity::Warning,
+        };
+        Some(violation(severity, message))
+    };
+
+    match value {
+        serde_json::Value::String(msg) => {
+            vec![violation(ConsistencySeverity::Warning, msg.clone())]
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                serde_json::Value::String(msg) => {
+                    Some(violation(ConsistencySeverity::Warning, msg.clone()))
+                }
+                other => from_object(other),
+            })
+            .collect(),
+        serde_json::Value::Object(_) => from_object(value).into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_violations_shapes() {
+        let single = parse_violations("sf", &serde_json::json!("too fast"));
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].severity, ConsistencySeverity::Warning);
+        assert_eq!(single[0].fact_id, "plugin:sf");
+
+        let list = parse_violations(
+            "sf",
+            &serde_json::json!([
+                {"severity": "critical", "message": "FTL travel"},
+                {"severity": "info", "message": "odd orbit"},
+                {"severity": "critical"},
+            ]),
+        );
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].severity, ConsistencySeverity::Critical);
+        assert_eq!(list[1].severity, ConsistencySeverity::Info);
+
+        assert!(parse_violations("sf", &serde_json::Value::Null).is_empty());
+    }
+}
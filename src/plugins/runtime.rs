@@ -0,0 +1,272 @@
+//! Sandboxed Lua runtime for a single plugin script.
+//!
+//! Each plugin gets its own Lua state with only the `table`, `string`, `math`,
+//! and `utf8` libraries — no `io`, `os`, `package`, `debug`, or code loading.
+//! Memory is capped and every call runs under a wall-clock budget enforced by
+//! an instruction-count hook.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use mlua::{
+    Function, HookTriggers, Lua, LuaOptions, LuaSerdeExt, StdLib, Table, Value as LuaValue, VmState,
+};
+
+use crate::db::connection::NarraDb;
+
+/// Memory cap per plugin state.
+const MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+/// Wall-clock budget per hook or command invocation.
+const CALL_BUDGET: Duration = Duration::from_secs(5);
+/// Instructions between budget checks.
+const HOOK_INTERVAL: u32 = 10_000;
+/// Registry key of the hook table built by `PRELUDE`.
+const HOOKS_KEY: &str = "narra_hooks";
+
+/// Registration API exposed to scripts as `narra.*`.
+const PRELUDE: &str = r#"
+local hooks = { on_create = {}, on_validate = {}, commands = {} }
+
+function narra.on_create(f)
+    assert(type(f) == "function", "narra.on_create expects a function")
+    table.insert(hooks.on_create, f)
+end
+
+function narra.on_validate(f)
+    assert(type(f) == "function", "narra.on_validate expects a function")
+    table.insert(hooks.on_validate, f)
+end
+
+function narra.command(name, description, f)
+    assert(type(name) == "string", "narra.command expects a name")
+    assert(type(f) == "function", "narra.command expects a function")
+    hooks.commands[name] = { description = description or "", run = f }
+end
+
+return hooks
+"#;
+
+/// A custom command registered by a plugin.
+#[derive(Debug, Clone)]
+pub struct PluginCommand {
+    pub plugin: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// One loaded plugin script.
+pub struct Plugin {
+    pub name: String,
+    lua: Lua,
+    deadline: Arc<Mutex<Instant>>,
+}
+
+impl Plugin {
+    /// Load and run a plugin script so it can register its hooks.
+    pub async fn load(path: &Path, db: Arc<NarraDb>) -> Result<Self> {
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "plugin".to_string());
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read plugin {}", path.display()))?;
+
+        let lua = Lua::new_with(
+            StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8,
+            LuaOptions::default(),
+        )?;
+        lua.set_memory_limit(MEMORY_LIMIT)?;
+
+        let deadline = Arc::new(Mutex::new(Instant::now() + CALL_BUDGET));
+        let hook_deadline = deadline.clone();
+        lua.set_hook(
+            HookTriggers::new().every_nth_instruction(HOOK_INTERVAL),
+            move |_, _| {
+                if Instant::now() > *hook_deadline.lock().unwrap() {
+                    Err(mlua::Error::runtime("plugin exceeded its time budget"))
+                } else {
+                    Ok(VmState::Continue)
+                }
+            },
+        );
+
+        let globals = lua.globals();
+        for unsafe_global in ["dofile", "loadfile", "load", "require", "collectgarbage"] {
+            globals.set(unsafe_global, LuaValue::Nil)?;
+        }
+
+        let narra = lua.create_table()?;
+        let plugin_name = name.clone();
+        narra.set(
+            "log",
+            lua.create_function(move |_, msg: String| {
+                tracing::info!("[plugin {}] {}", plugin_name, msg);
+                Ok(())
+            })?,
+        )?;
+        narra.set(
+            "query",
+            lua.create_async_function(move |lua, sql: String| {
+                let db = db.clone();
+                async move {
+                    let rows = read_only_query(&db, &sql)
+                        .await
+                        .map_err(mlua::Error::external)?;
+                    lua.to_value(&rows)
+                }
+            })?,
+        )?;
+        globals.set("narra", narra)?;
+
+        let hooks: Table = lua.load(PRELUDE).set_name("prelude").eval()?;
+        lua.set_named_registry_value(HOOKS_KEY, hooks)?;
+
+        let plugin = Self {
+            name,
+            lua,
+            deadline,
+        };
+        plugin.reset_budget();
+        plugin
+            .lua
+            .load(&source)
+            .set_name(plugin.name.as_str())
+            .exec_async()
+            .await
+            .with_context(|| format!("Plugin '{}' failed to load", plugin.name))?;
+
+        Ok(plugin)
+    }
+
+    fn reset_budget(&self) {
+        *self.deadline.lock().unwrap() = Instant::now() + CALL_BUDGET;
+    }
+
+    fn hooks(&self) -> mlua::Result<Table> {
+        self.lua.named_registry_value(HOOKS_KEY)
+    }
+
+    fn hook_functions(&self, kind: &str) -> Vec<Function> {
+        self.hooks()
+            .and_then(|h| h.get::<Table>(kind))
+            .map(|t| t.sequence_values::<Function>().flatten().collect())
+            .unwrap_or_default()
+    }
+
+    /// Commands this plugin registered.
+    pub fn commands(&self) -> Vec<PluginCommand> {
+        let Ok(commands) = self.hooks().and_then(|h| h.get::<Table>("commands")) else {
+            return Vec::new();
+        };
+        commands
+            .pairs::<String, Table>()
+            .flatten()
+            .map(|(name, cmd)| PluginCommand {
+                plugin: self.name.clone(),
+                name,
+                description: cmd.get("description").unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    pub fn has_hook(&self, kind: &str) -> bool {
+        !self.hook_functions(kind).is_empty()
+    }
+
+    /// Call every `kind` hook with `arg`, returning each hook's result as JSON.
+    ///
+    /// A failing hook is logged and skipped so one bad plugin can't break a
+    /// mutation.
+    pub async fn call_hooks(&self, kind: &str, arg: &serde_json::Value) -> Vec<serde_json::Value> {
+        let mut results = Vec::new();
+        for hook in self.hook_functions(kind) {
+            self.reset_budget();
+            let outcome = async {
+                let arg = self.lua.to_value(arg)?;
+                let ret: LuaValue = hook.call_async(arg).await?;
+                self.lua.from_value::<serde_json::Value>(ret)
+            }
+            .await;
+            match outcome {
+                Ok(value) => results.push(value),
+                Err(e) => tracing::warn!("Plugin '{}' {} hook failed: {}", self.name, kind, e),
+            }
+        }
+        results
+    }
+
+    /// Run a registered command. Returns `None` if this plugin lacks it.
+    pub async fn run_command(
+        &self,
+        name: &str,
+        args: &[String],
+    ) -> Option<Result<serde_json::Value>> {
+        let command: Table = self
+            .hooks()
+            .and_then(|h| h.get::<Table>("commands"))
+            .and_then(|c| c.get::<Option<Table>>(name))
+            .ok()
+            .flatten()?;
+
+        self.reset_budget();
+        let outcome = async {
+            let run: Function = command.get("run")?;
+            let ret: LuaValue = run.call_async(args.to_vec()).await?;
+            self.lua.from_value::<serde_json::Value>(ret)
+        }
+        .await;
+        Some(outcome.with_context(|| format!("Plugin '{}' command '{}' failed", self.name, name)))
+    }
+}
+
+/// AST nodes that write, as serialized variant names: the writing
+/// statements and subqueries, plus custom functions and scripts, which may.
+const WRITE_NODES: &[&str] = &[
+    "Create", "Update", "Upsert", "Delete", "Relate", "Insert", "Define", "Remove", "Rebuild",
+    "Alter", "Access", "Custom", "Script",
+];
+
+fn contains_write(node: &serde_json::Value) -> bool {
+    match node {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .any(|(key, value)| WRITE_NODES.contains(&key.as_str()) || contains_write(value)),
+        serde_json::Value::Array(items) => items.iter().any(contains_write),
+        _ => false,
+    }
+}
+
+/// Run a single SELECT statement and return rows as plain JSON.
+///
+/// Statements that contain a write anywhere, such as a `(DELETE ...)`
+/// subquery, are refused up front. The query then runs inside a transaction
+/// that is always cancelled, so nothing it might still reach is kept.
+async fn read_only_query(db: &NarraDb, sql: &str) -> Result<serde_json::Value> {
+    let query = surrealdb::sql::parse(sql).context("narra.query could not parse the query")?;
+    let statement = match query.0 .0.as_slice() {
+        [statement @ surrealdb::sql::Statement::Select(_)] => statement,
+        _ => anyhow::bail!("narra.query only accepts a single SELECT statement"),
+    };
+    if contains_write(&serde_json::to_value(statement)?) {
+        anyhow::bail!("narra.query is read-only; the query would write");
+    }
+
+    let mut resp = db
+        .query(format!(
+            "BEGIN TRANSACTION; LET $narra_rows = ({}); CANCEL TRANSACTION; RETURN $narra_rows;",
+            statement
+        ))
+        .await?;
+    let last = resp.num_statements() - 1;
+    if let Some(err) = resp
+        .take_errors()
+        .into_values()
+        .find(|err| !err.to_string().contains("cancelled transaction"))
+    {
+        return Err(err.into());
+    }
+    let value: surrealdb::Value = resp.take(last)?;
+    Ok(value.into_inner().into_json())
+}
@@ -3,6 +3,7 @@ use crate::models::{
     Character, CharacterCreate, CharacterUpdate, Event, EventCreate, EventUpdate, Location,
     LocationCreate, LocationUpdate, Scene, SceneCreate, SceneUpdate,
 };
+use crate::services::access::AccessPolicy;
 use crate::NarraError;
 use async_trait::async_trait;
 use futures::stream::{self, StreamExt};
@@ -62,12 +63,22 @@ pub trait EntityRepository: Send + Sync {
 /// Wraps the database connection and delegates to model functions.
 pub struct SurrealEntityRepository {
     db: Arc<NarraDb>,
+    access: Arc<AccessPolicy>,
 }
 
 impl SurrealEntityRepository {
     /// Create a new repository with the given database connection.
     pub fn new(db: Arc<NarraDb>) -> Self {
-        Self { db }
+        Self {
+            db,
+            access: Arc::new(AccessPolicy::unrestricted()),
+        }
+    }
+
+    /// Reject mutations the access policy doesn't allow.
+    pub fn with_access(mut self, access: Arc<AccessPolicy>) -> Self {
+        self.access = access;
+        self
     }
 }
 
@@ -83,6 +94,7 @@ impl EntityRepository for SurrealEntityRepository {
     }
 
     async fn create_character(&self, data: CharacterCreate) -> Result<Character, NarraError> {
+        self.access.check("character")?;
         crate::models::character::create_character(&self.db, data).await
     }
 
@@ -91,10 +103,12 @@ impl EntityRepository for SurrealEntityRepository {
         id: &str,
         data: CharacterUpdate,
     ) -> Result<Option<Character>, NarraError> {
+        self.access.check("character")?;
         crate::models::character::update_character(&self.db, id, data).await
     }
 
     async fn delete_character(&self, id: &str) -> Result<Option<Character>, NarraError> {
+        self.access.check("character")?;
         crate::models::character::delete_character(&self.db, id).await
     }
 
@@ -108,6 +122,7 @@ impl EntityRepository for SurrealEntityRepository {
     }
 
     async fn create_location(&self, data: LocationCreate) -> Result<Location, NarraError> {
+        self.access.check("location")?;
         crate::models::location::create_location(&self.db, data).await
     }
 
@@ -116,10 +131,12 @@ impl EntityRepository for SurrealEntityRepository {
         id: &str,
         data: LocationUpdate,
     ) -> Result<Option<Location>, NarraError> {
+        self.access.check("location")?;
         crate::models::location::update_location(&self.db, id, data).await
     }
 
     async fn delete_location(&self, id: &str) -> Result<Option<Location>, NarraError> {
+        self.access.check("location")?;
         crate::models::location::delete_location(&self.db, id).await
     }
 
@@ -133,14 +150,17 @@ impl EntityRepository for SurrealEntityRepository {
     }
 
     async fn create_event(&self, data: EventCreate) -> Result<Event, NarraError> {
+        self.access.check("event")?;
         crate::models::event::create_event(&self.db, data).await
     }
 
     async fn update_event(&self, id: &str, data: EventUpdate) -> Result<Option<Event>, NarraError> {
+        self.access.check("event")?;
         crate::models::event::update_event(&self.db, id, data).await
     }
 
     async fn delete_event(&self, id: &str) -> Result<Option<Event>, NarraError> {
+        self.access.check("event")?;
         crate::models::event::delete_event(&self.db, id).await
     }
 
@@ -154,14 +174,17 @@ impl EntityRepository for SurrealEntityRepository {
     }
 
     async fn create_scene(&self, data: SceneCreate) -> Result<Scene, NarraError> {
+        self.access.check("scene")?;
         crate::models::scene::create_scene(&self.db, data).await
     }
 
     async fn update_scene(&self, id: &str, data: SceneUpdate) -> Result<Option<Scene>, NarraError> {
+        self.access.check("scene")?;
         crate::models::scene::update_scene(&self.db, id, data).await
     }
 
     async fn delete_scene(&self, id: &str) -> Result<Option<Scene>, NarraError> {
+        self.access.check("scene")?;
         crate::models::scene::delete_scene(&self.db, id).await
     }
 
@@ -171,6 +194,7 @@ impl EntityRepository for SurrealEntityRepository {
         I: IntoIterator<Item = CharacterCreate> + Send,
         I::IntoIter: Send,
     {
+        self.access.check("character")?;
         let mut results = Vec::new();
         let chunks = stream::iter(items).chunks(50); // Process 50 at a time
         tokio::pin!(chunks);
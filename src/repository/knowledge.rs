@@ -3,6 +3,7 @@ use crate::models::{
     CertaintyLevel, Knowledge, KnowledgeConflict, KnowledgeCreate, KnowledgeState,
     KnowledgeStateCreate, KnowledgeTransmission,
 };
+use crate::services::access::AccessPolicy;
 use crate::NarraError;
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -73,17 +74,28 @@ pub trait KnowledgeRepository: Send + Sync {
 /// SurrealDB implementation of KnowledgeRepository.
 pub struct SurrealKnowledgeRepository {
     db: Arc<NarraDb>,
+    access: Arc<AccessPolicy>,
 }
 
 impl SurrealKnowledgeRepository {
     pub fn new(db: Arc<NarraDb>) -> Self {
-        Self { db }
+        Self {
+            db,
+            access: Arc::new(AccessPolicy::unrestricted()),
+        }
+    }
+
+    /// Reject mutations the access policy doesn't allow.
+    pub fn with_access(mut self, access: Arc<AccessPolicy>) -> Self {
+        self.access = access;
+        self
     }
 }
 
 #[async_trait]
 impl KnowledgeRepository for SurrealKnowledgeRepository {
     async fn create_knowledge(&self, data: KnowledgeCreate) -> Result<Knowledge, NarraError> {
+        self.access.check("knowledge")?;
         crate::models::knowledge::create_knowledge(&self.db, data).await
     }
 
@@ -108,6 +120,7 @@ impl KnowledgeRepository for SurrealKnowledgeRepository {
         target: &str,
         data: KnowledgeStateCreate,
     ) -> Result<KnowledgeState, NarraError> {
+        self.access.check("knows")?;
         crate::models::knowledge::create_knowledge_state(&self.db, character_id, target, data).await
     }
 
@@ -129,6 +142,7 @@ impl KnowledgeRepository for SurrealKnowledgeRepository {
         new_certainty: CertaintyLevel,
         event_id: &str,
     ) -> Result<KnowledgeState, NarraError> {
+        self.access.check("knows")?;
         crate::models::knowledge::update_knowledge_certainty(
             &self.db,
             character_id,
@@ -3,6 +3,7 @@ use crate::models::{
     Involvement, InvolvementCreate, Perception, PerceptionCreate, Relationship, RelationshipCreate,
     SceneParticipant, SceneParticipantCreate,
 };
+use crate::services::access::AccessPolicy;
 use crate::NarraError;
 use async_trait::async_trait;
 use std::collections::HashSet;
@@ -67,11 +68,21 @@ pub trait RelationshipRepository: Send + Sync {
 /// SurrealDB implementation of RelationshipRepository.
 pub struct SurrealRelationshipRepository {
     db: Arc<NarraDb>,
+    access: Arc<AccessPolicy>,
 }
 
 impl SurrealRelationshipRepository {
     pub fn new(db: Arc<NarraDb>) -> Self {
-        Self { db }
+        Self {
+            db,
+            access: Arc::new(AccessPolicy::unrestricted()),
+        }
+    }
+
+    /// Reject mutations the access policy doesn't allow.
+    pub fn with_access(mut self, access: Arc<AccessPolicy>) -> Self {
+        self.access = access;
+        self
     }
 }
 
@@ -83,6 +94,7 @@ impl RelationshipRepository for SurrealRelationshipRepository {
         to_id: &str,
         data: RelationshipCreate,
     ) -> Result<Relationship, NarraError> {
+        self.access.check("relates_to")?;
         // RelationshipCreate already contains from/to IDs, construct complete struct
         let relationship_data = RelationshipCreate {
             from_character_id: from_id.to_string(),
@@ -107,6 +119,7 @@ impl RelationshipRepository for SurrealRelationshipRepository {
         to_id: &str,
         data: PerceptionCreate,
     ) -> Result<Perception, NarraError> {
+        self.access.check("perceives")?;
         crate::models::perception::create_perception(&self.db, from_id, to_id, data).await
     }
 
@@ -124,6 +137,7 @@ impl RelationshipRepository for SurrealRelationshipRepository {
         scene_id: &str,
         data: SceneParticipantCreate,
     ) -> Result<SceneParticipant, NarraError> {
+        self.access.check("participates_in")?;
         // SceneParticipantCreate already contains character_id and scene_id
         let participant_data = SceneParticipantCreate {
             character_id: character_id.to_string(),
@@ -147,6 +161,7 @@ impl RelationshipRepository for SurrealRelationshipRepository {
         event_id: &str,
         data: InvolvementCreate,
     ) -> Result<Involvement, NarraError> {
+        self.access.check("involved_in")?;
         // InvolvementCreate already contains character_id and event_id
         let involvement_data = InvolvementCreate {
             character_id: character_id.to_string(),
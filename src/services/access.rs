@@ -0,0 +1,279 @@
+//! Role-based access for shared worlds.
+//!
+//! Grants live in the world itself, so every collaborator on a remote
+//! database sees the same roles. Owners manage grants, editors change canon
+//! (optionally only some entity types), readers query. A world without
+//! grants is unrestricted. The user is the database username for remote
+//! worlds and the configured author otherwise; users without a grant read.
+//!
+//! Checks run in narra (repositories and mutation entry points), not in the
+//! database: pair them with database users for a hard guarantee.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::Datetime;
+
+use crate::db::connection::NarraDb;
+use crate::NarraError;
+
+/// Entity type standing for world-wide writes (imports, batch jobs).
+pub const ANY_TYPE: &str = "*";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Reader,
+    Editor,
+    Owner,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Role::Owner => "owner",
+            Role::Editor => "editor",
+            Role::Reader => "reader",
+        })
+    }
+}
+
+impl FromStr for Role {
+    type Err = NarraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "owner" => Ok(Role::Owner),
+            "editor" => Ok(Role::Editor),
+            "reader" => Ok(Role::Reader),
+            _ => Err(NarraError::Validation(format!(
+                "Role must be owner, editor, or reader (got '{}')",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessGrant {
+    pub user: String,
+    pub role: Role,
+    /// Entity types an editor may change; empty means all
+    #[serde(default)]
+    pub entity_types: Vec<String>,
+    pub granted_by: Option<String>,
+    pub created_at: Datetime,
+}
+
+/// The current user's effective permissions.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessPolicy {
+    pub user: Option<String>,
+    pub role: Role,
+    pub entity_types: Vec<String>,
+    /// False when the world has no grants
+    pub enforced: bool,
+}
+
+impl AccessPolicy {
+    pub fn unrestricted() -> Self {
+        Self {
+            user: None,
+            role: Role::Owner,
+            entity_types: Vec::new(),
+            enforced: false,
+        }
+    }
+
+    /// Effective policy for `user` given the world's grants.
+    pub fn resolve(user: Option<&str>, grants: &[AccessGrant]) -> Self {
+        if grants.is_empty() {
+            return Self {
+                user: user.map(str::to_string),
+                ..Self::unrestricted()
+            };
+        }
+        let grant = user.and_then(|u| grants.iter().find(|g| g.user == u));
+        Self {
+            user: user.map(str::to_string),
+            role: grant.map(|g| g.role).unwrap_or(Role::Reader),
+            entity_types: grant.map(|g| g.entity_types.clone()).unwrap_or_default(),
+            enforced: true,
+        }
+    }
+
+    /// Whether the user may change entities of `entity_type` (or `ANY_TYPE`).
+    pub fn can_mutate(&self, entity_type: &str) -> bool {
+        match self.role {
+            Role::Owner => true,
+            Role::Reader => false,
+            Role::Editor => {
+                self.entity_types.is_empty()
+                    || (entity_type != ANY_TYPE
+                        && self.entity_types.iter().any(|t| t == entity_type))
+            }
+        }
+    }
+
+    pub fn check(&self, entity_type: &str) -> Result<(), NarraError> {
+        if self.can_mutate(entity_type) {
+            return Ok(());
+        }
+        let who = self.user.as_deref().unwrap_or("anonymous user");
+        let what = if entity_type == ANY_TYPE {
+            "make world-wide changes".to_string()
+        } else {
+            format!("change {} entities", entity_type)
+        };
+        Err(NarraError::PermissionDenied(format!(
+            "{} has role '{}' and cannot {}",
+            who, self.role, what
+        )))
+    }
+
+    /// Check a mutation by entity ID (`table:key`).
+    pub fn check_entity(&self, entity_id: &str) -> Result<(), NarraError> {
+        self.check(entity_id.split_once(':').map_or(ANY_TYPE, |(t, _)| t))
+    }
+
+    pub fn check_owner(&self) -> Result<(), NarraError> {
+        if self.role == Role::Owner {
+            Ok(())
+        } else {
+            Err(NarraError::PermissionDenied(format!(
+                "only owners manage access; {} has role '{}'",
+                self.user.as_deref().unwrap_or("anonymous user"),
+                self.role
+            )))
+        }
+    }
+}
+
+pub struct AccessService {
+    db: Arc<NarraDb>,
+}
+
+impl AccessService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    pub async fn list(&self) -> Result<Vec<AccessGrant>, NarraError> {
+        let mut response = self
+            .db
+            .query("SELECT * OMIT id FROM access_grant ORDER BY user ASC")
+            .await?;
+        let grants: Vec<AccessGrant> = response.take(0)?;
+        Ok(grants)
+    }
+
+    pub async fn policy_for(&self, user: Option<&str>) -> Result<AccessPolicy, NarraError> {
+        Ok(AccessPolicy::resolve(user, &self.list().await?))
+    }
+
+    /// Grant `role` to `user`, replacing any earlier grant. `by` is the
+    /// acting user's policy.
+    pub async fn grant(
+        &self,
+        by: &AccessPolicy,
+        user: &str,
+        role: Role,
+        entity_types: Vec<String>,
+    ) -> Result<AccessGrant, NarraError> {
+        by.check_owner()?;
+        if !by.enforced && (by.user.as_deref() != Some(user) || role != Role::Owner) {
+            return Err(NarraError::Validation(format!(
+                "The first grant must make you an owner: narra access grant {} --role owner",
+                by.user.as_deref().unwrap_or("<you>")
+            )));
+        }
+        if role != Role::Editor && !entity_types.is_empty() {
+            return Err(NarraError::Validation(
+                "Entity types only restrict editors".to_string(),
+            ));
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "DELETE access_grant WHERE user = $user; \
+                 CREATE access_grant SET user = $user, role = $role, \
+                 entity_types = $entity_types, granted_by = $by RETURN AFTER",
+            )
+            .bind(("user", user.to_string()))
+            .bind(("role", role.to_string()))
+            .bind(("entity_types", entity_types))
+            .bind(("by", by.user.clone()))
+            .await?;
+        let grant: Option<AccessGrant> = response.take(1)?;
+        grant.ok_or_else(|| NarraError::Database("Failed to create access grant".into()))
+    }
+
+    /// Remove `user`'s grant. The last owner can't be removed.
+    pub async fn revoke(&self, by: &AccessPolicy, user: &str) -> Result<bool, NarraError> {
+        by.check_owner()?;
+        let grants = self.list().await?;
+        let Some(grant) = grants.iter().find(|g| g.user == user) else {
+            return Ok(false);
+        };
+        let owners = grants.iter().filter(|g| g.role == Role::Owner).count();
+        if grant.role == Role::Owner && owners == 1 && grants.len() > 1 {
+            return Err(NarraError::Validation(
+                "Cannot revoke the last owner while other grants exist".to_string(),
+            ));
+        }
+        self.db
+            .query("DELETE access_grant WHERE user = $user")
+            .bind(("user", user.to_string()))
+            .await?
+            .check()?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(user: &str, role: Role, types: &[&str]) -> AccessGrant {
+        AccessGrant {
+            user: user.to_string(),
+            role,
+            entity_types: types.iter().map(|t| t.to_string()).collect(),
+            granted_by: None,
+            created_at: Datetime::from(chrono::Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_policy_resolution() {
+        let open = AccessPolicy::resolve(Some("ana"), &[]);
+        assert!(!open.enforced);
+        assert!(open.can_mutate(ANY_TYPE));
+
+        let grants = vec![
+            grant("ana", Role::Owner, &[]),
+            grant("ben", Role::Editor, &["character", "note"]),
+            grant("cy", Role::Reader, &[]),
+        ];
+        let ben = AccessPolicy::resolve(Some("ben"), &grants);
+        assert!(ben.can_mutate("character"));
+        assert!(!ben.can_mutate("universe_fact"));
+        assert!(!ben.can_mutate(ANY_TYPE));
+        assert!(ben.check_entity("note:draft").is_ok());
+        assert!(ben.check_owner().is_err());
+
+        let cy = AccessPolicy::resolve(Some("cy"), &grants);
+        let err = cy.check_entity("character:alice").unwrap_err().to_string();
+        assert!(err.contains("cy has role 'reader'"));
+
+        // No grant: read-only
+        let stranger = AccessPolicy::resolve(Some("dee"), &grants);
+        assert_eq!(stranger.role, Role::Reader);
+        assert!(AccessPolicy::resolve(Some("ana"), &grants)
+            .check_owner()
+            .is_ok());
+    }
+}
@@ -3,6 +3,11 @@
 //! Streams entities through emotion → theme → NER classifiers in parallel,
 //! using `tokio-stream` for backpressure and `async-stream` for ergonomic
 //! stream construction. Designed for throughput when annotating many entities.
+//!
+//! Each annotation records a hash of the text its model saw. In incremental
+//! mode a model is re-run only when that hash no longer matches, whatever the
+//! stale flag says: a stale annotation whose input is unchanged is kept, and
+//! a fresh one whose input changed is recomputed.
 
 use std::sync::Arc;
 
@@ -10,8 +15,14 @@ use futures::StreamExt;
 use serde::Serialize;
 
 use crate::db::connection::NarraDb;
-use crate::models::annotation::{EmotionOutput, NerOutput, ThemeOutput};
+use crate::models::annotation::{
+    get_entity_annotations, input_hash, set_annotation_stale, set_input_hash, EmotionOutput,
+    NerOutput, ThemeOutput,
+};
+use crate::services::emotion::EMOTION_MODEL_TYPE;
+use crate::services::ner::NER_MODEL_TYPE;
 use crate::services::progress::ProgressReporter;
+use crate::services::theme::THEME_MODEL_TYPE;
 use crate::services::{EmotionService, NerService, ThemeService};
 use crate::utils::sanitize::validate_table;
 use crate::NarraError;
@@ -25,6 +36,10 @@ pub struct AnnotationResult {
     pub themes: Option<ThemeOutput>,
     pub entities: Option<NerOutput>,
     pub errors: Vec<String>,
+    /// Models whose cached annotation was kept because their input is
+    /// unchanged (incremental mode)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unchanged: Vec<String>,
 }
 
 /// Summary of a batch annotation run.
@@ -35,6 +50,8 @@ pub struct BatchAnnotationReport {
     pub theme_successes: usize,
     pub ner_successes: usize,
     pub errors: usize,
+    /// Model runs skipped because their input was unchanged
+    pub unchanged: usize,
     pub results: Vec<AnnotationResult>,
 }
 
@@ -46,6 +63,8 @@ pub struct PipelineConfig {
     pub run_ner: bool,
     /// Max concurrency for parallel entity processing.
     pub concurrency: usize,
+    /// Re-run only models whose input text changed since they last ran.
+    pub incremental: bool,
 }
 
 impl Default for PipelineConfig {
@@ -55,10 +74,20 @@ impl Default for PipelineConfig {
             run_themes: true,
             run_ner: true,
             concurrency: 4,
+            incremental: false,
         }
     }
 }
 
+/// Classifier services, shared by every entity in a run.
+#[derive(Clone)]
+struct Classifiers {
+    db: Arc<NarraDb>,
+    emotion: Arc<dyn EmotionService + Send + Sync>,
+    theme: Arc<dyn ThemeService + Send + Sync>,
+    ner: Arc<dyn NerService + Send + Sync>,
+}
+
 /// Entity with its text for annotation.
 #[derive(Debug, Clone)]
 struct EntityText {
@@ -103,6 +132,7 @@ impl AnnotationPipeline {
         // Fetch all entities with their composite text
         let entities = self.fetch_entity_texts(entity_types).await?;
         let total = entities.len();
+        let concurrency = config.concurrency;
 
         if total == 0 {
             return Ok(BatchAnnotationReport {
@@ -111,97 +141,37 @@ impl AnnotationPipeline {
                 theme_successes: 0,
                 ner_successes: 0,
                 errors: 0,
+                unchanged: 0,
                 results: vec![],
             });
         }
 
         // Stream entities through the pipeline with bounded concurrency
-        let emotion_svc = self.emotion_service.clone();
-        let theme_svc = self.theme_service.clone();
-        let ner_svc = self.ner_service.clone();
-
-        let run_emotions = config.run_emotions && emotion_svc.is_available();
-        let run_themes = config.run_themes && theme_svc.is_available();
-        let run_ner = config.run_ner && ner_svc.is_available();
-
+        let classifiers = self.classifiers();
         let stream = tokio_stream::iter(entities).map(move |entity| {
-            let emotion_svc = emotion_svc.clone();
-            let theme_svc = theme_svc.clone();
-            let ner_svc = ner_svc.clone();
-
+            let classifiers = classifiers.clone();
+            let config = config.clone();
             async move {
-                let mut result = AnnotationResult {
-                    entity_id: entity.entity_id.clone(),
-                    entity_name: entity.entity_name.clone(),
-                    emotions: None,
-                    themes: None,
-                    entities: None,
-                    errors: vec![],
-                };
-
-                // Run classifiers in parallel for each entity
-                let (emotion_result, theme_result, ner_result) = tokio::join!(
-                    async {
-                        if run_emotions {
-                            Some(
-                                emotion_svc
-                                    .get_emotions(&entity.entity_id, &entity.text)
-                                    .await,
-                            )
-                        } else {
-                            None
-                        }
-                    },
-                    async {
-                        if run_themes {
-                            Some(
-                                theme_svc
-                                    .get_themes(&entity.entity_id, &entity.text, None)
-                                    .await,
-                            )
-                        } else {
-                            None
-                        }
-                    },
-                    async {
-                        if run_ner {
-                            Some(ner_svc.get_entities(&entity.entity_id, &entity.text).await)
-                        } else {
-                            None
-                        }
-                    },
-                );
-
-                if let Some(Ok(emotions)) = emotion_result {
-                    result.emotions = Some(emotions);
-                } else if let Some(Err(e)) = emotion_result {
-                    result.errors.push(format!("emotion: {}", e));
-                }
-
-                if let Some(Ok(themes)) = theme_result {
-                    result.themes = Some(themes);
-                } else if let Some(Err(e)) = theme_result {
-                    result.errors.push(format!("theme: {}", e));
-                }
-
-                if let Some(Ok(entities)) = ner_result {
-                    result.entities = Some(entities);
-                } else if let Some(Err(e)) = ner_result {
-                    result.errors.push(format!("ner: {}", e));
-                }
-
-                result
+                classifiers
+                    .annotate(
+                        &entity.entity_id,
+                        &entity.entity_name,
+                        &entity.text,
+                        &config,
+                    )
+                    .await
             }
         });
 
         // Buffer unordered for concurrency
-        let mut buffered = stream.buffer_unordered(config.concurrency);
+        let mut buffered = stream.buffer_unordered(concurrency);
 
         let mut results = Vec::with_capacity(total);
         let mut emotion_ok = 0usize;
         let mut theme_ok = 0usize;
         let mut ner_ok = 0usize;
         let mut error_count = 0usize;
+        let mut unchanged = 0usize;
         let mut processed = 0usize;
 
         progress
@@ -221,6 +191,7 @@ impl AnnotationPipeline {
             if !result.errors.is_empty() {
                 error_count += 1;
             }
+            unchanged += result.unchanged.len();
             processed += 1;
             progress
                 .report(
@@ -241,6 +212,7 @@ impl AnnotationPipeline {
             theme_successes: theme_ok,
             ner_successes: ner_ok,
             errors: error_count,
+            unchanged,
             results,
         })
     }
@@ -253,62 +225,18 @@ impl AnnotationPipeline {
         text: &str,
         config: &PipelineConfig,
     ) -> AnnotationResult {
-        let mut result = AnnotationResult {
-            entity_id: entity_id.to_string(),
-            entity_name: entity_name.to_string(),
-            emotions: None,
-            themes: None,
-            entities: None,
-            errors: vec![],
-        };
-
-        let run_emotions = config.run_emotions && self.emotion_service.is_available();
-        let run_themes = config.run_themes && self.theme_service.is_available();
-        let run_ner = config.run_ner && self.ner_service.is_available();
-
-        let (emotion_result, theme_result, ner_result) = tokio::join!(
-            async {
-                if run_emotions {
-                    Some(self.emotion_service.get_emotions(entity_id, text).await)
-                } else {
-                    None
-                }
-            },
-            async {
-                if run_themes {
-                    Some(self.theme_service.get_themes(entity_id, text, None).await)
-                } else {
-                    None
-                }
-            },
-            async {
-                if run_ner {
-                    Some(self.ner_service.get_entities(entity_id, text).await)
-                } else {
-                    None
-                }
-            },
-        );
-
-        if let Some(Ok(emotions)) = emotion_result {
-            result.emotions = Some(emotions);
-        } else if let Some(Err(e)) = emotion_result {
-            result.errors.push(format!("emotion: {}", e));
-        }
-
-        if let Some(Ok(themes)) = theme_result {
-            result.themes = Some(themes);
-        } else if let Some(Err(e)) = theme_result {
-            result.errors.push(format!("theme: {}", e));
-        }
+        self.classifiers()
+            .annotate(entity_id, entity_name, text, config)
+            .await
+    }
 
-        if let Some(Ok(entities)) = ner_result {
-            result.entities = Some(entities);
-        } else if let Some(Err(e)) = ner_result {
-            result.errors.push(format!("ner: {}", e));
+    fn classifiers(&self) -> Classifiers {
+        Classifiers {
+            db: self.db.clone(),
+            emotion: self.emotion_service.clone(),
+            theme: self.theme_service.clone(),
+            ner: self.ner_service.clone(),
         }
-
-        result
     }
 
     /// Fetch composite text for entities of the given types.
@@ -364,6 +292,144 @@ impl AnnotationPipeline {
     }
 }
 
+impl Classifiers {
+    /// Models to run for an entity whose text hashes to `hash`: the ones to
+    /// compute and, in incremental mode, the ones kept as they are.
+    ///
+    /// Normally the stale flag decides. In incremental mode the stored input
+    /// hash does, and an annotation without one is recomputed.
+    async fn plan(
+        &self,
+        entity_id: &str,
+        hash: &str,
+        models: &[&'static str],
+        incremental: bool,
+    ) -> (Vec<&'static str>, Vec<String>) {
+        let existing = get_entity_annotations(&self.db, entity_id)
+            .await
+            .unwrap_or_default();
+        let mut compute = Vec::new();
+        let mut unchanged = Vec::new();
+        for &model_type in models {
+            let Some(annotation) = existing.iter().find(|a| a.model_type == model_type) else {
+                compute.push(model_type);
+                continue;
+            };
+            let same_input = annotation.input_hash.as_deref() == Some(hash);
+            if !incremental {
+                if annotation.stale {
+                    compute.push(model_type);
+                }
+            } else if same_input {
+                // The services serve fresh annotations from cache
+                if annotation.stale {
+                    let _ = set_annotation_stale(&self.db, entity_id, model_type, false).await;
+                }
+                unchanged.push(model_type.to_string());
+            } else {
+                if !annotation.stale {
+                    let _ = set_annotation_stale(&self.db, entity_id, model_type, true).await;
+                }
+                compute.push(model_type);
+            }
+        }
+        (compute, unchanged)
+    }
+
+    async fn annotate(
+        &self,
+        entity_id: &str,
+        entity_name: &str,
+        text: &str,
+        config: &PipelineConfig,
+    ) -> AnnotationResult {
+        let mut result = AnnotationResult {
+            entity_id: entity_id.to_string(),
+            entity_name: entity_name.to_string(),
+            emotions: None,
+            themes: None,
+            entities: None,
+            errors: vec![],
+            unchanged: vec![],
+        };
+
+        let run_emotions = config.run_emotions && self.emotion.is_available();
+        let run_themes = config.run_themes && self.theme.is_available();
+        let run_ner = config.run_ner && self.ner.is_available();
+
+        let models: Vec<&'static str> = [
+            (EMOTION_MODEL_TYPE, run_emotions),
+            (THEME_MODEL_TYPE, run_themes),
+            (NER_MODEL_TYPE, run_ner),
+        ]
+        .into_iter()
+        .filter_map(|(model, run)| run.then_some(model))
+        .collect();
+        let hash = input_hash(text);
+        let (compute, unchanged) = self
+            .plan(entity_id, &hash, &models, config.incremental)
+            .await;
+        result.unchanged = unchanged;
+
+        // Run classifiers in parallel for each entity
+        let (emotion_result, theme_result, ner_result) = tokio::join!(
+            async {
+                if run_emotions {
+                    Some(self.emotion.get_emotions(entity_id, text).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                if run_themes {
+                    Some(self.theme.get_themes(entity_id, text, None).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                if run_ner {
+                    Some(self.ner.get_entities(entity_id, text).await)
+                } else {
+                    None
+                }
+            },
+        );
+
+        if let Some(Ok(emotions)) = emotion_result {
+            result.emotions = Some(emotions);
+        } else if let Some(Err(e)) = emotion_result {
+            result.errors.push(format!("emotion: {}", e));
+        }
+
+        if let Some(Ok(themes)) = theme_result {
+            result.themes = Some(themes);
+        } else if let Some(Err(e)) = theme_result {
+            result.errors.push(format!("theme: {}", e));
+        }
+
+        if let Some(Ok(entities)) = ner_result {
+            result.entities = Some(entities);
+        } else if let Some(Err(e)) = ner_result {
+            result.errors.push(format!("ner: {}", e));
+        }
+
+        // Remember what the recomputed annotations were computed from
+        for model_type in compute {
+            let computed = match model_type {
+                EMOTION_MODEL_TYPE => result.emotions.is_some(),
+                THEME_MODEL_TYPE => result.themes.is_some(),
+                _ => result.entities.is_some(),
+            };
+            if computed {
+                let _ = set_input_hash(&self.db, entity_id, model_type, &hash).await;
+            }
+        }
+
+        result
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -389,6 +455,7 @@ mod tests {
             theme_successes: 0,
             ner_successes: 0,
             errors: 0,
+            unchanged: 0,
             results: vec![],
         };
         assert_eq!(report.total_processed, 0);
@@ -404,6 +471,7 @@ mod tests {
             themes: None,
             entities: None,
             errors: vec![],
+            unchanged: vec![],
         };
         assert!(result.errors.is_empty());
     }
@@ -442,6 +510,7 @@ mod tests {
                         themes,
                         entities,
                         errors,
+                        unchanged: vec![],
                     }
                 })
         }
@@ -459,6 +528,7 @@ mod tests {
                     theme_successes: theme_ok,
                     ner_successes: ner_ok,
                     errors,
+                    unchanged: 0,
                     results,
                 }
             })
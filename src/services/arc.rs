@@ -476,6 +476,78 @@ impl ArcService {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Baseline snapshots
+// ---------------------------------------------------------------------------
+
+/// Outcome of [`baseline_arc_snapshots`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct BaselineCounts {
+    pub created: usize,
+    /// Entities that already had a snapshot
+    pub skipped: usize,
+}
+
+/// Record a baseline snapshot of the current embedding for every entity of
+/// `entity_types` that has an embedding but no arc snapshot yet. A failed
+/// insert is logged and the entity is left for the next run.
+pub async fn baseline_arc_snapshots(
+    db: &NarraDb,
+    entity_types: &[&str],
+) -> Result<BaselineCounts, NarraError> {
+    #[derive(serde::Deserialize)]
+    struct EntityWithEmbedding {
+        id: surrealdb::RecordId,
+        embedding: Vec<f32>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct SnapshotCount {
+        cnt: i64,
+    }
+
+    let mut counts = BaselineCounts::default();
+    for entity_type in entity_types {
+        let mut resp = db
+            .query(format!(
+                "SELECT id, embedding FROM {} WHERE embedding IS NOT NONE",
+                entity_type
+            ))
+            .await?;
+        let entities: Vec<EntityWithEmbedding> = resp.take(0)?;
+
+        for entity in entities {
+            let mut resp = db
+                .query("SELECT count() AS cnt FROM arc_snapshot WHERE entity_id = $eid GROUP ALL")
+                .bind(("eid", entity.id.clone()))
+                .await?;
+            let existing: Option<SnapshotCount> = resp.take(0).unwrap_or(None);
+            if existing.is_some_and(|c| c.cnt > 0) {
+                counts.skipped += 1;
+                continue;
+            }
+            if let Err(e) = db
+                .query(
+                    "CREATE arc_snapshot SET entity_id = $eid, entity_type = $etype, embedding = $embedding",
+                )
+                .bind(("eid", entity.id.clone()))
+                .bind(("etype", entity_type.to_string()))
+                .bind(("embedding", entity.embedding))
+                .await
+            {
+                tracing::warn!(
+                    "Failed to create baseline snapshot for {}: {}",
+                    entity.id,
+                    e
+                );
+                continue;
+            }
+            counts.created += 1;
+        }
+    }
+    Ok(counts)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
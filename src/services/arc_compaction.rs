@@ -0,0 +1,248 @@
+//! Arc snapshot retention.
+//!
+//! Automatic snapshotting grows `arc_snapshot` without bound. Compaction
+//! thins each entity's history (per facet) with a retention policy while
+//! always keeping its first and latest snapshot, so arc history, drift and
+//! comparisons keep their endpoints.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::connection::NarraDb;
+use crate::utils::math::cosine_similarity;
+use crate::NarraError;
+
+/// Rows deleted per query.
+const DELETE_BATCH: usize = 500;
+
+/// Which snapshots survive compaction. The first and latest snapshot of every
+/// entity are always kept.
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    /// Keep every Nth snapshot (counting from the first) and drop the rest.
+    pub keep_every: Option<usize>,
+    /// Always keep snapshots anchored to an event.
+    pub keep_anchors: bool,
+    /// Drop snapshots that drifted less than this from the last kept one.
+    pub min_drift: Option<f32>,
+    /// Restrict to one snapshot entity type (character, knowledge, perspective, relationship).
+    pub entity_type: Option<String>,
+}
+
+/// Outcome of a compaction run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionReport {
+    pub examined: usize,
+    pub removed: usize,
+    pub kept: usize,
+    pub entities: usize,
+    /// Estimated storage freed by the removed snapshots.
+    pub reclaimed_bytes: u64,
+    pub dry_run: bool,
+}
+
+/// The fields of a snapshot compaction looks at.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SnapshotRow {
+    pub id: surrealdb::RecordId,
+    pub entity_id: surrealdb::RecordId,
+    pub facet: Option<String>,
+    pub embedding: Vec<f32>,
+    pub event_id: Option<surrealdb::RecordId>,
+}
+
+impl SnapshotRow {
+    /// Rough on-disk size: embeddings are stored as 64-bit floats, plus
+    /// record overhead.
+    fn approx_bytes(&self) -> u64 {
+        self.embedding.len() as u64 * 8 + 128
+    }
+}
+
+/// A kept snapshot whose predecessor was removed, with its new delta.
+struct Rebased {
+    id: surrealdb::RecordId,
+    delta: f32,
+}
+
+/// Decide which snapshots of one chronologically ordered history to keep.
+pub fn retained(history: &[SnapshotRow], policy: &RetentionPolicy) -> Vec<bool> {
+    let last = history.len().saturating_sub(1);
+    let mut keep = Vec::with_capacity(history.len());
+    let mut last_kept: Option<&SnapshotRow> = None;
+
+    for (i, snapshot) in history.iter().enumerate() {
+        let protected = i == 0 || i == last || (policy.keep_anchors && snapshot.event_id.is_some());
+        let on_stride = !matches!(policy.keep_every, Some(n) if n > 1 && i % n != 0);
+        let drifted = match (policy.min_drift, last_kept) {
+            (Some(min), Some(prev)) => {
+                1.0 - cosine_similarity(&prev.embedding, &snapshot.embedding) >= min
+            }
+            _ => true,
+        };
+        let kept = protected || (on_stride && drifted);
+        if kept {
+            last_kept = Some(snapshot);
+        }
+        keep.push(kept);
+    }
+    keep
+}
+
+pub struct ArcCompactionService {
+    db: Arc<NarraDb>,
+}
+
+impl ArcCompactionService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Apply `policy` to every entity's snapshots. With `dry_run`, only report.
+    pub async fn compact(
+        &self,
+        policy: &RetentionPolicy,
+        dry_run: bool,
+    ) -> Result<CompactionReport, NarraError> {
+        if policy.keep_every.is_none() && policy.min_drift.is_none() {
+            return Err(NarraError::Validation(
+                "Nothing to compact: set keep_every and/or min_drift".to_string(),
+            ));
+        }
+
+        let filter = if policy.entity_type.is_some() {
+            "WHERE entity_type = $entity_type "
+        } else {
+            ""
+        };
+        let mut response = self
+            .db
+            .query(format!(
+                "SELECT id, entity_id, facet, embedding, event_id FROM arc_snapshot {}\
+                 ORDER BY created_at ASC",
+                filter
+            ))
+            .bind(("entity_type", policy.entity_type.clone()))
+            .await?;
+        let rows: Vec<SnapshotRow> = response.take(0)?;
+        let examined = rows.len();
+
+        let mut histories: BTreeMap<(String, Option<String>), Vec<SnapshotRow>> = BTreeMap::new();
+        for row in rows {
+            histories
+                .entry((row.entity_id.to_string(), row.facet.clone()))
+                .or_default()
+                .push(row);
+        }
+        let entities = histories.len();
+
+        let mut removed = Vec::new();
+        let mut rebased = Vec::new();
+        let mut reclaimed_bytes = 0u64;
+        for history in histories.values() {
+            let keep = retained(history, policy);
+            let mut prev_kept: Option<&SnapshotRow> = None;
+            let mut gap = false;
+            for (snapshot, kept) in history.iter().zip(keep) {
+                if !kept {
+                    reclaimed_bytes += snapshot.approx_bytes();
+                    removed.push(snapshot.id.clone());
+                    gap = true;
+                    continue;
+                }
+                // Deltas are relative to the previous snapshot, which may be gone now
+                if let (true, Some(prev)) = (gap, prev_kept) {
+                    rebased.push(Rebased {
+                        id: snapshot.id.clone(),
+                        delta: 1.0 - cosine_similarity(&prev.embedding, &snapshot.embedding),
+                    });
+                }
+                prev_kept = Some(snapshot);
+                gap = false;
+            }
+        }
+
+        if !dry_run {
+            for batch in removed.chunks(DELETE_BATCH) {
+                self.db
+                    .query("DELETE arc_snapshot WHERE id IN $ids")
+                    .bind(("ids", batch.to_vec()))
+                    .await?
+                    .check()?;
+            }
+            for r in rebased {
+                self.db
+                    .query("UPDATE $id SET delta_magnitude = $delta")
+                    .bind(("id", r.id))
+                    .bind(("delta", r.delta))
+                    .await?
+                    .check()?;
+            }
+        }
+
+        Ok(CompactionReport {
+            examined,
+            removed: removed.len(),
+            kept: examined - removed.len(),
+            entities,
+            reclaimed_bytes,
+            dry_run,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(n: usize, embedding: Vec<f32>, event: bool) -> SnapshotRow {
+        SnapshotRow {
+            id: surrealdb::RecordId::from(("arc_snapshot", n.to_string().as_str())),
+            entity_id: surrealdb::RecordId::from(("character", "alice")),
+            facet: None,
+            embedding,
+            event_id: event.then(|| surrealdb::RecordId::from(("event", "betrayal"))),
+        }
+    }
+
+    #[test]
+    fn test_retained_keeps_endpoints_anchors_and_drift() {
+        let history = vec![
+            snapshot(0, vec![1.0, 0.0], false),
+            snapshot(1, vec![1.0, 0.01], false),
+            snapshot(2, vec![1.0, 0.02], true),
+            snapshot(3, vec![0.0, 1.0], false),
+            snapshot(4, vec![0.0, 1.0], false),
+        ];
+
+        let every_other = RetentionPolicy {
+            keep_every: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            retained(&history, &every_other),
+            vec![true, false, true, false, true]
+        );
+
+        let drift = RetentionPolicy {
+            min_drift: Some(0.1),
+            keep_anchors: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            retained(&history, &drift),
+            vec![true, false, true, true, true]
+        );
+
+        let drift_only = RetentionPolicy {
+            min_drift: Some(0.1),
+            ..Default::default()
+        };
+        assert_eq!(
+            retained(&history, &drift_only),
+            vec![true, false, false, true, true]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Image assets: portraits, maps and other pictures attached to entities.
+//!
+//! Files live in `<data_path>/assets/` named by their SHA-256 (plus `.age`
+//! for encrypted worlds), so the same image added twice is stored once.
+//! Metadata lives in the `asset` table (see `models::asset`).
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+
+use crate::db::connection::NarraDb;
+use crate::db::vault::Vault;
+use crate::models::asset::{self, asset_entity_ref, Asset, AssetCreate};
+use crate::NarraError;
+
+/// Image types assets accept, by file extension.
+const IMAGE_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+];
+
+/// Largest image accepted.
+pub const MAX_ASSET_BYTES: u64 = 20 * 1024 * 1024;
+
+pub struct AssetStore {
+    db: Arc<NarraDb>,
+    dir: PathBuf,
+    vault: Option<Arc<Vault>>,
+}
+
+/// MIME type for an image path, from its extension.
+pub fn image_mime_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    IMAGE_TYPES
+        .iter()
+        .find(|(e, _)| *e == ext)
+        .map(|(_, mime)| *mime)
+}
+
+impl AssetStore {
+    pub fn new(db: Arc<NarraDb>, data_path: &Path, vault: Option<Arc<Vault>>) -> Self {
+        Self {
+            db,
+            dir: data_path.join("assets"),
+            vault,
+        }
+    }
+
+    /// Copy an image into the store and record it, optionally attached to an entity.
+    pub async fn add(
+        &self,
+        source: &Path,
+        entity_id: Option<&str>,
+        caption: Option<String>,
+    ) -> Result<Asset, NarraError> {
+        let mime_type = image_mime_type(source).ok_or_else(|| {
+            NarraError::Validation(format!(
+                "{} is not a supported image (png, jpg, gif, webp, svg)",
+                source.display()
+            ))
+        })?;
+        let entity = entity_id.map(asset_entity_ref).transpose()?;
+
+        let bytes = std::fs::read(source)?;
+        if bytes.len() as u64 > MAX_ASSET_BYTES {
+            return Err(NarraError::Validation(format!(
+                "{} is larger than the {} MiB asset limit",
+                source.display(),
+                MAX_ASSET_BYTES / (1024 * 1024)
+            )));
+        }
+        let sha256 = hex::encode(Sha256::digest(&bytes));
+        let ext = source
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        let file = match &self.vault {
+            Some(_) => format!("{}.{}.age", sha256, ext),
+            None => format!("{}.{}", sha256, ext),
+        };
+
+        let stored = self.dir.join(&file);
+        if !stored.exists() {
+            std::fs::create_dir_all(&self.dir)?;
+            let contents = match &self.vault {
+                Some(vault) => vault.encrypt(&bytes)?,
+                None => bytes.clone(),
+            };
+            std::fs::write(&stored, contents)?;
+        }
+
+        let original_name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| file.clone());
+        asset::create_asset(
+            &self.db,
+            AssetCreate {
+                file,
+                original_name,
+                mime_type: mime_type.to_string(),
+                size_bytes: bytes.len() as u64,
+                sha256,
+                caption,
+                encrypted: self.vault.is_some(),
+                entity,
+            },
+        )
+        .await
+    }
+
+    /// All assets, or those attached to one entity.
+    pub async fn list(&self, entity_id: Option<&str>) -> Result<Vec<Asset>, NarraError> {
+        asset::list_assets(&self.db, entity_id).await
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Asset, NarraError> {
+        asset::get_asset(&self.db, id)
+            .await?
+            .ok_or_else(|| NarraError::NotFound {
+                entity_type: "asset".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    /// Where an asset's file is stored.
+    pub fn path(&self, asset: &Asset) -> PathBuf {
+        self.dir.join(&asset.file)
+    }
+
+    /// The image bytes, decrypted and checked against the recorded checksum.
+    pub fn read(&self, asset: &Asset) -> Result<Vec<u8>, NarraError> {
+        let stored = std::fs::read(self.path(asset))?;
+        let bytes = match (&self.vault, asset.encrypted) {
+            (Some(vault), true) => vault.decrypt(&stored)?,
+            (None, true) => {
+                return Err(NarraError::Validation(format!(
+                    "Asset {} is encrypted but this world is not",
+                    asset.id
+                )))
+            }
+            (_, false) => stored,
+        };
+        if hex::encode(Sha256::digest(&bytes)) != asset.sha256 {
+            return Err(NarraError::Database(format!(
+                "Asset {} no longer matches its checksum",
+                asset.id
+            )));
+        }
+        Ok(bytes)
+    }
+
+    /// Delete an asset, and its file once no other asset shares it.
+    pub async fn remove(&self, id: &str) -> Result<Asset, NarraError> {
+        let removed =
+            asset::delete_asset(&self.db, id)
+                .await?
+                .ok_or_else(|| NarraError::NotFound {
+                    entity_type: "asset".to_string(),
+                    id: id.to_string(),
+                })?;
+        if asset::file_references(&self.db, &removed.file).await? == 0 {
+            std::fs::remove_file(self.path(&removed)).ok();
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_asset_add_read_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = surrealdb::engine::any::connect("mem://").await.unwrap();
+        db.use_ns("narra").use_db("world").await.unwrap();
+        let db = Arc::new(db);
+        let store = AssetStore::new(db.clone(), dir.path(), None);
+
+        let source = dir.path().join("Alice Portrait.PNG");
+        std::fs::write(&source, b"\x89PNG not really").unwrap();
+
+        let first = store
+            .add(
+                &source,
+                Some("character:alice"),
+                Some("Alice at court".into()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.mime_type, "image/png");
+        assert_eq!(first.original_name, "Alice Portrait.PNG");
+        assert_eq!(store.read(&first).unwrap(), b"\x89PNG not really");
+
+        // Same image again shares the stored file
+        let second = store.add(&source, None, None).await.unwrap();
+        assert_eq!(first.file, second.file);
+        assert_eq!(store.list(Some("character:alice")).await.unwrap().len(), 1);
+        assert_eq!(store.list(None).await.unwrap().len(), 2);
+
+        store.remove(&first.id.key().to_string()).await.unwrap();
+        assert!(store.path(&second).exists());
+        store.remove(&second.id.key().to_string()).await.unwrap();
+        assert!(!store.path(&second).exists());
+
+        assert!(store
+            .add(&dir.path().join("notes.txt"), None, None)
+            .await
+            .is_err());
+        assert!(store.add(&source, Some("note:idea"), None).await.is_err());
+    }
+}
@@ -0,0 +1,323 @@
+//! World-wide consistency audit.
+//!
+//! Runs every consistency check the configured `ConsistencyService` offers
+//! (fact checks, including plugin `on_validate` hooks, timeline ordering, and
+//! relationship state) across the whole world rather than a single entity,
+//! and groups what it finds by fact and severity. Fact checks see each
+//! entity's stored record, so they catch contradictions already in the world,
+//! not only ones introduced by a mutation.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::connection::NarraDb;
+use crate::services::{ConsistencyService, ConsistencySeverity, Violation};
+use crate::NarraError;
+
+/// Tables whose records are checked against universe facts.
+const FACT_CHECKED_TABLES: &[&str] = &["character", "location", "event", "scene", "knowledge"];
+
+/// One of the checks an audit runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditCheck {
+    /// Entity records against universe facts and plugin validators
+    Facts,
+    /// Characters knowing things before they learn them
+    Timeline,
+    /// Impossible, asymmetric, or unreciprocated relationships
+    Relationships,
+}
+
+impl AuditCheck {
+    pub const ALL: [AuditCheck; 3] = [Self::Facts, Self::Timeline, Self::Relationships];
+}
+
+impl std::str::FromStr for AuditCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "facts" | "fact" => Ok(Self::Facts),
+            "timeline" => Ok(Self::Timeline),
+            "relationships" | "relationship" => Ok(Self::Relationships),
+            other => Err(format!(
+                "Unknown check '{}'. Expected: facts, timeline, relationships",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for AuditCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Facts => "facts",
+            Self::Timeline => "timeline",
+            Self::Relationships => "relationships",
+        })
+    }
+}
+
+/// A violation found on one entity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditFinding {
+    pub entity_id: String,
+    pub check: AuditCheck,
+    pub message: String,
+    pub confidence: f32,
+    /// Whether it was detected as intentional (e.g., dramatic irony)
+    pub intentional: bool,
+}
+
+/// Findings sharing a violated fact (or rule) and severity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditGroup {
+    pub fact_id: String,
+    pub fact_title: String,
+    pub severity: ConsistencySeverity,
+    pub findings: Vec<AuditFinding>,
+}
+
+/// Result of auditing the whole world.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub checks: Vec<AuditCheck>,
+    /// Entities checked
+    pub checked: usize,
+    pub total_violations: usize,
+    pub critical: usize,
+    pub warning: usize,
+    pub info: usize,
+    /// Most severe first, then largest
+    pub groups: Vec<AuditGroup>,
+}
+
+impl AuditReport {
+    /// Most severe violation found, if any.
+    pub fn worst(&self) -> Option<ConsistencySeverity> {
+        self.groups.iter().map(|g| g.severity).max()
+    }
+
+    fn from_findings(
+        checks: Vec<AuditCheck>,
+        checked: usize,
+        found: Vec<(Violation, AuditFinding)>,
+    ) -> Self {
+        let mut grouped: BTreeMap<(ConsistencySeverity, String), AuditGroup> = BTreeMap::new();
+        for (violation, finding) in found {
+            let group = grouped
+                .entry((violation.severity, violation.fact_id.clone()))
+                .or_insert_with(|| AuditGroup {
+                    fact_id: violation.fact_id.clone(),
+                    fact_title: violation.fact_title.clone(),
+                    severity: violation.severity,
+                    findings: Vec::new(),
+                });
+            // Relationship checks see each pair from both ends
+            if !group
+                .findings
+                .iter()
+                .any(|f| f.message == finding.message && f.check == finding.check)
+            {
+                group.findings.push(finding);
+            }
+        }
+
+        let mut groups: Vec<AuditGroup> = grouped.into_values().collect();
+        groups.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then(b.findings.len().cmp(&a.findings.len()))
+                .then_with(|| a.fact_title.cmp(&b.fact_title))
+        });
+        let count = |severity: ConsistencySeverity| {
+            groups
+                .iter()
+                .filter(|g| g.severity == severity)
+                .map(|g| g.findings.len())
+                .sum()
+        };
+
+        Self {
+            checks,
+            checked,
+            total_violations: groups.iter().map(|g| g.findings.len()).sum(),
+            critical: count(ConsistencySeverity::Critical),
+            warning: count(ConsistencySeverity::Warning),
+            info: count(ConsistencySeverity::Info),
+            groups,
+        }
+    }
+}
+
+/// Stored records of a table as plain JSON, keyed by entity ID. Embedding
+/// vectors are dropped; they're noise to text-based fact checks.
+async fn records(
+    db: &NarraDb,
+    table: &str,
+) -> Result<Vec<(String, serde_json::Value)>, NarraError> {
+    let mut resp = db
+        .query("SELECT * FROM type::table($table)")
+        .bind(("table", table.to_string()))
+        .await?;
+    let rows: surrealdb::Value = resp.take(0)?;
+    let serde_json::Value::Array(rows) = rows.into_inner().into_json() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match row {
+            serde_json::Value::Object(mut map) => {
+                let id = map.get("id")?.as_str()?.to_string();
+                map.retain(|key, _| !key.ends_with("embedding"));
+                Some((id, serde_json::Value::Object(map)))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+/// Run `checks` across the whole world.
+pub async fn audit_world(
+    db: &NarraDb,
+    consistency: &dyn ConsistencyService,
+    checks: &[AuditCheck],
+) -> Result<AuditReport, NarraError> {
+    let mut found: Vec<(Violation, AuditFinding)> = Vec::new();
+    let mut checked = std::collections::HashSet::new();
+    let mut record = |entity_id: &str, check: AuditCheck, violations: Vec<Violation>| {
+        for violation in violations {
+            let finding = AuditFinding {
+                entity_id: entity_id.to_string(),
+                check,
+                message: violation.message.clone(),
+                confidence: violation.confidence,
+                intentional: violation.auto_detected_as_intentional,
+            };
+            found.push((violation, finding));
+        }
+    };
+
+    if checks.contains(&AuditCheck::Facts) {
+        for table in FACT_CHECKED_TABLES {
+            for (entity_id, data) in records(db, table).await? {
+                let result = consistency.check_entity_mutation(&entity_id, &data).await?;
+                record(
+                    &entity_id,
+                    AuditCheck::Facts,
+                    result
+                        .violations_by_severity
+                        .into_values()
+                        .flatten()
+                        .collect(),
+                );
+                checked.insert(entity_id);
+            }
+        }
+    }
+
+    if checks.contains(&AuditCheck::Timeline) || checks.contains(&AuditCheck::Relationships) {
+        let mut resp = db.query("SELECT VALUE id FROM character").await?;
+        let characters: Vec<surrealdb::RecordId> = resp.take(0)?;
+        for character in characters {
+            let entity_id = character.to_string();
+            let key = character.key().to_string();
+            if checks.contains(&AuditCheck::Timeline) {
+                let violations = consistency.check_timeline_violations(&key).await?;
+                record(&entity_id, AuditCheck::Timeline, violations);
+            }
+            if checks.contains(&AuditCheck::Relationships) {
+                let violations = consistency.check_relationship_violations(&key).await?;
+                record(&entity_id, AuditCheck::Relationships, violations);
+            }
+            checked.insert(entity_id);
+        }
+    }
+
+    let mut checks = checks.to_vec();
+    checks.sort();
+    checks.dedup();
+    Ok(AuditReport::from_findings(checks, checked.len(), found))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(fact_id: &str, severity: ConsistencySeverity, message: &str) -> Violation {
+        Violation {
+            fact_id: fact_id.to_string(),
+            fact_title: format!("Fact {}", fact_id),
+            severity,
+            message: message.to_string(),
+            confidence: 0.9,
+            auto_detected_as_intentional: false,
+        }
+    }
+
+    fn finding(entity_id: &str, violation: &Violation) -> AuditFinding {
+        AuditFinding {
+            entity_id: entity_id.to_string(),
+            check: AuditCheck::Relationships,
+            message: violation.message.clone(),
+            confidence: violation.confidence,
+            intentional: false,
+        }
+    }
+
+    #[test]
+    fn test_report_groups_by_fact_and_severity() {
+        let found = [
+            (
+                "character:a",
+                violation("f1", ConsistencySeverity::Warning, "a"),
+            ),
+            (
+                "character:b",
+                violation("f1", ConsistencySeverity::Warning, "b"),
+            ),
+            (
+                "character:b",
+                violation("f1", ConsistencySeverity::Info, "c"),
+            ),
+            (
+                "character:c",
+                violation("f2", ConsistencySeverity::Critical, "d"),
+            ),
+            // Same pair seen from the other end
+            (
+                "character:d",
+                violation("f2", ConsistencySeverity::Critical, "d"),
+            ),
+        ]
+        .into_iter()
+        .map(|(entity, v)| {
+            let f = finding(entity, &v);
+            (v, f)
+        })
+        .collect();
+
+        let report = AuditReport::from_findings(AuditCheck::ALL.to_vec(), 4, found);
+        assert_eq!(report.total_violations, 4);
+        assert_eq!((report.critical, report.warning, report.info), (1, 2, 1));
+        assert_eq!(report.worst(), Some(ConsistencySeverity::Critical));
+        let groups: Vec<(&str, ConsistencySeverity, usize)> = report
+            .groups
+            .iter()
+            .map(|g| (g.fact_id.as_str(), g.severity, g.findings.len()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("f2", ConsistencySeverity::Critical, 1),
+                ("f1", ConsistencySeverity::Warning, 2),
+                ("f1", ConsistencySeverity::Info, 1),
+            ]
+        );
+        assert_eq!("Timeline".parse::<AuditCheck>(), Ok(AuditCheck::Timeline));
+        assert!("vibes".parse::<AuditCheck>().is_err());
+    }
+}
@@ -0,0 +1,249 @@
+//! World backups: compressed SurrealQL dumps with SHA-256 checksums.
+//!
+//! Backups live in `<data_path>/backups/` as `<name>.surql.gz` (or
+//! `<name>.surql.gz.age` for encrypted worlds) next to a `<name>.json`
+//! manifest. Automatic backups are taken before destructive operations and
+//! rotated; manual ones are kept until deleted by hand.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::db::connection::{export_surql, NarraDb};
+use crate::db::vault::Vault;
+use crate::NarraError;
+
+/// Automatic backups kept when `backup.keep` is not configured.
+pub const DEFAULT_KEEP: usize = 10;
+
+/// Manifest stored beside each archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub name: String,
+    /// Why it was taken (e.g., "manual", "import", "re-embed")
+    pub reason: String,
+    pub automatic: bool,
+    pub created_at: DateTime<Utc>,
+    pub archive: String,
+    pub size_bytes: u64,
+    /// SHA-256 of the archive file, hex
+    pub sha256: String,
+    pub encrypted: bool,
+}
+
+pub struct BackupService {
+    db: Arc<NarraDb>,
+    dir: PathBuf,
+    vault: Option<Arc<Vault>>,
+    /// Automatic backups to keep; 0 disables them.
+    keep: usize,
+}
+
+fn backup_error(msg: impl std::fmt::Display) -> NarraError {
+    NarraError::Database(format!("Backup: {}", msg))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+impl BackupService {
+    pub fn new(db: Arc<NarraDb>, data_path: &Path, vault: Option<Arc<Vault>>, keep: usize) -> Self {
+        Self {
+            db,
+            dir: data_path.join("backups"),
+            vault,
+            keep,
+        }
+    }
+
+    /// Take a backup now.
+    pub async fn create(&self, reason: &str, automatic: bool) -> Result<BackupInfo, NarraError> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let dump = export_surql(&self.db).await?;
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&dump)?;
+        let mut archive = encoder.finish()?;
+        if let Some(vault) = &self.vault {
+            archive = vault.encrypt(&archive)?;
+        }
+
+        let created_at = Utc::now();
+        let slug: String = reason
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let name = format!("{}-{}", created_at.format("%Y%m%dT%H%M%S%3fZ"), slug);
+        let extension = if self.vault.is_some() {
+            "surql.gz.age"
+        } else {
+            "surql.gz"
+        };
+        let archive_name = format!("{}.{}", name, extension);
+
+        let info = BackupInfo {
+            name: name.clone(),
+            reason: reason.to_string(),
+            automatic,
+            created_at,
+            archive: archive_name.clone(),
+            size_bytes: archive.len() as u64,
+            sha256: sha256_hex(&archive),
+            encrypted: self.vault.is_some(),
+        };
+        std::fs::write(self.dir.join(&archive_name), &archive)?;
+        std::fs::write(
+            self.dir.join(format!("{}.json", name)),
+            serde_json::to_string_pretty(&info)?,
+        )?;
+        tracing::info!("Backup {} written ({})", name, reason);
+        Ok(info)
+    }
+
+    /// Automatic backup before a destructive operation, then rotation.
+    ///
+    /// Returns `None` when automatic backups are disabled (`keep = 0`).
+    pub async fn before(&self, operation: &str) -> Result<Option<BackupInfo>, NarraError> {
+        if self.keep == 0 {
+            return Ok(None);
+        }
+        let info = self.create(operation, true).await?;
+        self.rotate()?;
+        Ok(Some(info))
+    }
+
+    /// All backups, newest first.
+    pub fn list(&self) -> Vec<BackupInfo> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut backups: Vec<BackupInfo> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|p| {
+                let contents = std::fs::read_to_string(&p).ok()?;
+                serde_json::from_str(&contents).ok()
+            })
+            .collect();
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        backups
+    }
+
+    /// Find a backup by name, name prefix, or "latest".
+    pub fn find(&self, name: &str) -> Result<BackupInfo, NarraError> {
+        let backups = self.list();
+        let found = if name == "latest" {
+            backups.into_iter().next()
+        } else {
+            backups.into_iter().find(|b| b.name.starts_with(name))
+        };
+        found.ok_or_else(|| backup_error(format!("no backup matching '{}'", name)))
+    }
+
+    /// Whether the archive still matches its recorded checksum.
+    pub fn verify(&self, info: &BackupInfo) -> bool {
+        std::fs::read(self.dir.join(&info.archive))
+            .map(|bytes| sha256_hex(&bytes) == info.sha256)
+            .unwrap_or(false)
+    }
+
+    /// Replace the world with a backup, taking a "pre-restore" backup first.
+    pub async fn restore(&self, name: &str) -> Result<BackupInfo, NarraError> {
+        let info = self.find(name)?;
+        let archive = std::fs::read(self.dir.join(&info.archive))?;
+        if sha256_hex(&archive) != info.sha256 {
+            return Err(backup_error(format!(
+                "{} failed its checksum; refusing to restore",
+                info.name
+            )));
+        }
+
+        let compressed = match (&self.vault, info.encrypted) {
+            (Some(vault), true) => vault.decrypt(&archive)?,
+            (None, true) => return Err(backup_error(
+                "backup is encrypted but this world is not; restore it from the encrypted world",
+            )),
+            (_, false) => archive,
+        };
+        let mut dump = String::new();
+        GzDecoder::new(&compressed[..]).read_to_string(&mut dump)?;
+
+        self.create("pre-restore", true).await?;
+
+        let mut resp = self
+            .db
+            .query("RETURN [session::ns(), session::db()]")
+            .await?;
+        let current: Option<(String, String)> = resp.take(0)?;
+        let (ns, database) =
+            current.ok_or_else(|| backup_error("could not determine the current database"))?;
+        self.db
+            .query(format!("REMOVE DATABASE IF EXISTS `{}`", database))
+            .await?
+            .check()?;
+        self.db.use_ns(&ns).use_db(&database).await?;
+        self.db.query(dump).await?.check()?;
+
+        self.rotate()?;
+        Ok(info)
+    }
+
+    /// Delete the oldest automatic backups beyond `keep`.
+    fn rotate(&self) -> Result<(), NarraError> {
+        let automatic: Vec<BackupInfo> = self.list().into_iter().filter(|b| b.automatic).collect();
+        for old in automatic.iter().skip(self.keep.max(1)) {
+            std::fs::remove_file(self.dir.join(&old.archive)).ok();
+            std::fs::remove_file(self.dir.join(format!("{}.json", old.name)))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backup_rotate_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = surrealdb::engine::any::connect("mem://").await.unwrap();
+        db.use_ns("narra").use_db("world").await.unwrap();
+        let db = Arc::new(db);
+        let service = BackupService::new(db.clone(), dir.path(), None, 2);
+
+        db.query("CREATE character:alice SET name = 'Alice'")
+            .await
+            .unwrap();
+        let manual = service.create("manual", false).await.unwrap();
+        assert!(service.verify(&manual));
+
+        for _ in 0..3 {
+            service.before("import").await.unwrap();
+        }
+        let backups = service.list();
+        assert_eq!(backups.iter().filter(|b| b.automatic).count(), 2);
+        assert!(backups.iter().any(|b| b.name == manual.name));
+
+        db.query("DELETE character").await.unwrap();
+        service.restore(&manual.name).await.unwrap();
+        let mut resp = db.query("SELECT VALUE name FROM character").await.unwrap();
+        let names: Vec<String> = resp.take(0).unwrap();
+        assert_eq!(names, vec!["Alice".to_string()]);
+
+        std::fs::write(
+            dir.path().join("backups").join(&manual.archive),
+            b"tampered",
+        )
+        .unwrap();
+        assert!(!service.verify(&manual));
+        assert!(service.restore(&manual.name).await.is_err());
+    }
+}
@@ -0,0 +1,374 @@
+//! Screen-time balance: structural importance against page time.
+//!
+//! Each character gets two percentile ranks among the characters analysed:
+//! one for narrative centrality (the mean of degree, betweenness and
+//! closeness over the perception and relationship graph) and one for the
+//! share of scenes they appear in. A character whose centrality rank beats
+//! their screen rank by the threshold is under-shown; the reverse is
+//! over-shown.
+//!
+//! Suggestions come from the graph. An under-shown character could join the
+//! scenes where most of their connections already are; an over-shown one
+//! could be cut from the scenes where none of their connections are.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::services::{CentralityMetric, GraphAnalyticsService};
+use crate::NarraError;
+
+/// Options for the balance report.
+#[derive(Debug, Clone, Copy)]
+pub struct BalanceOptions {
+    /// Rank difference (0-1) at which a character is flagged
+    pub threshold: f64,
+    /// Scene suggestions per flagged character
+    pub suggestions: usize,
+}
+
+impl Default for BalanceOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.3,
+            suggestions: 3,
+        }
+    }
+}
+
+/// A character's centrality and the 0-based scene positions they appear in.
+#[derive(Debug, Clone)]
+pub struct BalanceInput {
+    pub character_id: String,
+    pub name: String,
+    pub centrality: f64,
+    pub scenes: Vec<usize>,
+}
+
+/// A scene in reading order with its participants (full IDs).
+#[derive(Debug, Clone)]
+pub struct BalanceScene {
+    pub id: String,
+    pub title: String,
+    pub participants: HashSet<String>,
+}
+
+/// A scene to add the character to, or cut them from.
+#[derive(Debug, Clone, Serialize)]
+pub struct SceneSuggestion {
+    pub scene_id: String,
+    pub title: String,
+    /// 1-based reading position
+    pub position: usize,
+    /// Names of the character's connections in the scene
+    pub connections: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceEntry {
+    pub character_id: String,
+    pub name: String,
+    pub centrality: f64,
+    pub appearances: usize,
+    pub share: f64,
+    pub centrality_rank: f64,
+    pub screen_rank: f64,
+    /// Centrality rank minus screen rank
+    pub gap: f64,
+    /// "under-shown" or "over-shown"
+    pub flag: Option<String>,
+    /// Scenes to join when under-shown, to leave when over-shown
+    pub suggestions: Vec<SceneSuggestion>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceReport {
+    pub total_scenes: usize,
+    pub characters: Vec<BalanceEntry>,
+}
+
+/// Percentile rank of each value among all of them (ties share the mean
+/// rank); 0.5 for a single value.
+pub fn percentile_ranks(values: &[f64]) -> Vec<f64> {
+    if values.len() < 2 {
+        return vec![0.5; values.len()];
+    }
+    let last = (values.len() - 1) as f64;
+    values
+        .iter()
+        .map(|v| {
+            let below = values.iter().filter(|o| *o < v).count() as f64;
+            let equal = values.iter().filter(|o| *o == v).count() as f64;
+            (below + (equal - 1.0) / 2.0) / last
+        })
+        .collect()
+}
+
+/// Compare centrality with page time and suggest scenes for the
+/// characters out of balance. `neighbours` maps a character to the
+/// characters they share a perception or relationship with.
+pub fn balance(
+    inputs: Vec<BalanceInput>,
+    scenes: &[BalanceScene],
+    neighbours: &HashMap<String, HashSet<String>>,
+    names: &HashMap<String, String>,
+    options: &BalanceOptions,
+) -> BalanceReport {
+    let total = scenes.len();
+    let shares: Vec<f64> = inputs
+        .iter()
+        .map(|i| {
+            if total == 0 {
+                0.0
+            } else {
+                i.scenes.len() as f64 / total as f64
+            }
+        })
+        .collect();
+    let centrality_ranks =
+        percentile_ranks(&inputs.iter().map(|i| i.centrality).collect::<Vec<_>>());
+    let screen_ranks = percentile_ranks(&shares);
+    let none = HashSet::new();
+
+    let mut characters: Vec<BalanceEntry> = inputs
+        .into_iter()
+        .enumerate()
+        .map(|(i, input)| {
+            let gap = centrality_ranks[i] - screen_ranks[i];
+            let flag = if gap >= options.threshold {
+                Some("under-shown")
+            } else if gap <= -options.threshold {
+                Some("over-shown")
+            } else {
+                None
+            };
+            let connected = neighbours.get(&input.character_id).unwrap_or(&none);
+            let in_scene: HashSet<usize> = input.scenes.iter().copied().collect();
+            let connections = |scene: &BalanceScene| -> Vec<String> {
+                let mut found: Vec<String> = scene
+                    .participants
+                    .intersection(connected)
+                    .map(|id| names.get(id).cloned().unwrap_or_else(|| id.clone()))
+                    .collect();
+                found.sort();
+                found
+            };
+
+            let mut candidates: Vec<(usize, Vec<String>)> = match flag {
+                Some("under-shown") => scenes
+                    .iter()
+                    .enumerate()
+                    .filter(|(p, _)| !in_scene.contains(p))
+                    .map(|(p, s)| (p, connections(s)))
+                    .filter(|(_, c)| !c.is_empty())
+                    .collect(),
+                Some(_) => scenes
+                    .iter()
+                    .enumerate()
+                    .filter(|(p, _)| in_scene.contains(p))
+                    .map(|(p, s)| (p, connections(s)))
+                    .filter(|(_, c)| c.is_empty())
+                    .collect(),
+                None => Vec::new(),
+            };
+            // Most connections first, later scenes breaking ties
+            candidates.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(b.0.cmp(&a.0)));
+            candidates.truncate(options.suggestions);
+
+            BalanceEntry {
+                character_id: input.character_id,
+                name: input.name,
+                centrality: input.centrality,
+                appearances: input.scenes.len(),
+                share: shares[i],
+                centrality_rank: centrality_ranks[i],
+                screen_rank: screen_ranks[i],
+                gap,
+                flag: flag.map(str::to_string),
+                suggestions: candidates
+                    .into_iter()
+                    .map(|(p, connections)| SceneSuggestion {
+                        scene_id: scenes[p].id.clone(),
+                        title: scenes[p].title.clone(),
+                        position: p + 1,
+                        connections,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    characters.sort_by(|a, b| b.gap.abs().total_cmp(&a.gap.abs()));
+    BalanceReport {
+        total_scenes: total,
+        characters,
+    }
+}
+
+pub struct BalanceService {
+    db: Arc<NarraDb>,
+    members: Option<HashSet<String>>,
+}
+
+impl BalanceService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db, members: None }
+    }
+
+    /// Restrict the analysis to these characters (a phase's members).
+    pub fn with_members(mut self, members: Option<HashSet<String>>) -> Self {
+        self.members = members;
+        self
+    }
+
+    pub async fn report(&self, options: &BalanceOptions) -> Result<BalanceReport, NarraError> {
+        #[derive(Deserialize)]
+        struct SceneRow {
+            id: RecordId,
+            title: String,
+        }
+        #[derive(Deserialize)]
+        struct EdgeRow {
+            #[serde(rename = "in")]
+            from: RecordId,
+            out: RecordId,
+        }
+
+        let centrality = GraphAnalyticsService::new(self.db.clone())
+            .with_members(self.members.clone())
+            .compute_centrality(None, vec![CentralityMetric::All], usize::MAX)
+            .await?;
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, title, event.sequence AS sequence, created_at FROM scene \
+                 ORDER BY sequence ASC, created_at ASC; \
+                 SELECT in, out FROM participates_in; \
+                 SELECT in, out FROM perceives; \
+                 SELECT in, out FROM relates_to",
+            )
+            .await?;
+        let scene_rows: Vec<SceneRow> = response.take(0)?;
+        let participation: Vec<EdgeRow> = response.take(1)?;
+        let perceives: Vec<EdgeRow> = response.take(2)?;
+        let relates: Vec<EdgeRow> = response.take(3)?;
+
+        let position: HashMap<String, usize> = scene_rows
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.id.to_string(), i))
+            .collect();
+        let mut scenes: Vec<BalanceScene> = scene_rows
+            .into_iter()
+            .map(|s| BalanceScene {
+                id: s.id.to_string(),
+                title: s.title,
+                participants: HashSet::new(),
+            })
+            .collect();
+        let mut appearances: HashMap<String, Vec<usize>> = HashMap::new();
+        for edge in participation {
+            if let Some(&i) = position.get(&edge.out.to_string()) {
+                let character = edge.from.to_string();
+                scenes[i].participants.insert(character.clone());
+                appearances.entry(character).or_default().push(i);
+            }
+        }
+
+        let mut neighbours: HashMap<String, HashSet<String>> = HashMap::new();
+        for edge in perceives.iter().chain(&relates) {
+            let (a, b) = (edge.from.to_string(), edge.out.to_string());
+            if a == b {
+                continue;
+            }
+            neighbours.entry(a.clone()).or_default().insert(b.clone());
+            neighbours.entry(b).or_default().insert(a);
+        }
+
+        let names: HashMap<String, String> = centrality
+            .iter()
+            .map(|c| (c.character_id.clone(), c.character_name.clone()))
+            .collect();
+        let inputs = centrality
+            .into_iter()
+            .map(|c| {
+                let mut scenes = appearances.remove(&c.character_id).unwrap_or_default();
+                scenes.sort_unstable();
+                scenes.dedup();
+                BalanceInput {
+                    centrality: (c.degree + c.betweenness + c.closeness) / 3.0,
+                    character_id: c.character_id,
+                    name: c.character_name,
+                    scenes,
+                }
+            })
+            .collect();
+        Ok(balance(inputs, &scenes, &neighbours, &names, options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_flags_and_suggests_scenes() {
+        let id = |n: &str| format!("character:{}", n);
+        let scene = |n: usize, cast: &[&str]| BalanceScene {
+            id: format!("scene:s{}", n),
+            title: format!("Scene {}", n),
+            participants: cast.iter().map(|c| id(c)).collect(),
+        };
+        let scenes = vec![
+            scene(1, &["alice", "bob", "gray"]),
+            scene(2, &["bob", "gray"]),
+            scene(3, &["gray"]),
+            scene(4, &["bob", "gray", "carol"]),
+        ];
+        let input = |n: &str, centrality: f64, scenes: &[usize]| BalanceInput {
+            character_id: id(n),
+            name: n.to_string(),
+            centrality,
+            scenes: scenes.to_vec(),
+        };
+        let inputs = vec![
+            input("alice", 0.9, &[0]),
+            input("bob", 0.6, &[0, 1, 3]),
+            input("carol", 0.4, &[3]),
+            input("gray", 0.1, &[0, 1, 2, 3]),
+        ];
+        let mut neighbours: HashMap<String, HashSet<String>> = HashMap::new();
+        neighbours.insert(id("alice"), [id("bob"), id("carol")].into());
+        neighbours.insert(id("gray"), [id("carol")].into());
+        let names: HashMap<String, String> = ["alice", "bob", "carol", "gray"]
+            .iter()
+            .map(|n| (id(n), n.to_string()))
+            .collect();
+
+        let report = balance(
+            inputs,
+            &scenes,
+            &neighbours,
+            &names,
+            &BalanceOptions::default(),
+        );
+        let get = |n: &str| report.characters.iter().find(|c| c.name == n).unwrap();
+
+        let alice = get("alice");
+        assert_eq!(alice.flag.as_deref(), Some("under-shown"));
+        let positions: Vec<usize> = alice.suggestions.iter().map(|s| s.position).collect();
+        assert_eq!(positions, vec![4, 2]);
+        assert_eq!(alice.suggestions[0].connections, vec!["bob", "carol"]);
+
+        let gray = get("gray");
+        assert_eq!(gray.flag.as_deref(), Some("over-shown"));
+        let positions: Vec<usize> = gray.suggestions.iter().map(|s| s.position).collect();
+        assert_eq!(positions, vec![3, 2, 1]);
+
+        assert_eq!(percentile_ranks(&[1.0, 1.0, 3.0]), vec![0.25, 0.25, 1.0]);
+    }
+}
@@ -0,0 +1,395 @@
+//! Row parsing for `narra batch`: YAML, CSV and JSONL input.
+//!
+//! Every format is split into rows that deserialize independently, so one
+//! malformed row is reported with its position instead of failing the whole
+//! file, and nothing needs to be written before the input is known to be good.
+//!
+//! CSV headers name spec fields directly (case-insensitive; spaces and dashes
+//! read as underscores) unless a `field=Column` mapping says otherwise. In CSV
+//! cells, list fields (`aliases`, `profile.<key>`) are `;`-separated.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::services::tabular::{split_list, PROFILE_PREFIX};
+use crate::NarraError;
+
+/// CSV columns holding `;`-separated lists.
+const LIST_FIELDS: &[&str] = &["aliases"];
+/// CSV columns holding whole numbers.
+const INTEGER_FIELDS: &[&str] = &["sequence"];
+/// CSV columns holding yes/no flags.
+const BOOL_FIELDS: &[&str] = &["intentional"];
+
+/// Spec fields per batch type, for matching CSV headers.
+pub const CHARACTER_FIELDS: &[&str] = &["id", "name", "role", "aliases", "description", "profile"];
+pub const LOCATION_FIELDS: &[&str] = &["id", "name", "description", "parent_id", "loc_type"];
+pub const EVENT_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "sequence",
+    "date",
+    "date_precision",
+];
+pub const RELATIONSHIP_FIELDS: &[&str] = &[
+    "from_character_id",
+    "to_character_id",
+    "rel_type",
+    "subtype",
+    "label",
+    "intentional",
+];
+
+/// Input format for `narra batch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchFormat {
+    Yaml,
+    Csv,
+    Jsonl,
+}
+
+impl BatchFormat {
+    pub fn parse(s: &str) -> Result<Self, NarraError> {
+        match s.to_lowercase().as_str() {
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "csv" => Ok(Self::Csv),
+            "jsonl" | "ndjson" => Ok(Self::Jsonl),
+            _ => Err(NarraError::Validation(format!(
+                "Batch format must be yaml, csv, or jsonl (got '{}')",
+                s
+            ))),
+        }
+    }
+
+    /// Guess the format from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "yaml" | "yml" => Some(Self::Yaml),
+            "csv" => Some(Self::Csv),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+
+    /// What a row position counts: YAML items, or lines of the file.
+    pub fn row_label(self) -> &'static str {
+        match self {
+            Self::Yaml => "item",
+            Self::Csv | Self::Jsonl => "line",
+        }
+    }
+}
+
+/// A row that cannot be created as written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowError {
+    /// 1-based: item number for YAML, line number for CSV and JSONL
+    pub row: usize,
+    pub message: String,
+}
+
+impl RowError {
+    pub fn new(row: usize, message: impl Into<String>) -> Self {
+        Self {
+            row,
+            message: message.into(),
+        }
+    }
+}
+
+/// Rows that parsed, with their positions, and the ones that did not.
+#[derive(Debug)]
+pub struct BatchRows<T> {
+    pub rows: Vec<(usize, T)>,
+    pub errors: Vec<RowError>,
+    /// CSV headers that match no field and were not read
+    pub ignored_columns: Vec<String>,
+}
+
+impl<T> Default for BatchRows<T> {
+    fn default() -> Self {
+        Self {
+            rows: Vec::new(),
+            errors: Vec::new(),
+            ignored_columns: Vec::new(),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> BatchRows<T> {
+    fn push(&mut self, row: usize, value: Result<T, String>) {
+        match value {
+            Ok(spec) => self.rows.push((row, spec)),
+            Err(e) => self.errors.push(RowError::new(row, e)),
+        }
+    }
+}
+
+/// Parse `field=Column,field=Column`. `fields` are the spec's field names;
+/// `profile.<key>` is accepted when `profile` is one of them.
+pub fn parse_column_map(dsl: &str, fields: &[&str]) -> Result<HashMap<String, String>, NarraError> {
+    let mut columns = HashMap::new();
+    for pair in dsl.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (field, column) = pair.split_once('=').ok_or_else(|| {
+            NarraError::Validation(format!("Expected field=Column in --map, got '{}'", pair))
+        })?;
+        let field = field.trim();
+        let known = fields.contains(&field)
+            || (fields.contains(&"profile") && field.starts_with(PROFILE_PREFIX));
+        if !known {
+            return Err(NarraError::Validation(format!(
+                "Unknown field '{}' in --map (expected one of: {})",
+                field,
+                fields.join(", ")
+            )));
+        }
+        columns.insert(field.to_string(), column.trim().to_string());
+    }
+    Ok(columns)
+}
+
+/// `Parent ID` → `parent_id`.
+fn normalize_header(header: &str) -> String {
+    header.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+/// A CSV cell as JSON for `field`.
+fn cell_value(field: &str, cell: &str) -> Result<Value, String> {
+    if LIST_FIELDS.contains(&field) {
+        return Ok(Value::from(split_list(cell)));
+    }
+    if INTEGER_FIELDS.contains(&field) {
+        return cell
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("{} '{}' is not a whole number", field, cell));
+    }
+    if BOOL_FIELDS.contains(&field) {
+        return match cell.to_lowercase().as_str() {
+            "true" | "yes" | "y" | "1" => Ok(Value::Bool(true)),
+            "false" | "no" | "n" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("{} '{}' is not yes or no", field, cell)),
+        };
+    }
+    Ok(Value::from(cell))
+}
+
+fn read_yaml<T: DeserializeOwned>(input: &str) -> Result<BatchRows<T>, NarraError> {
+    let items: Vec<serde_yaml_ng::Value> = serde_yaml_ng::from_str(input)
+        .map_err(|e| NarraError::Validation(format!("Invalid YAML: {}", e)))?;
+    let mut parsed = BatchRows::default();
+    for (i, item) in items.into_iter().enumerate() {
+        parsed.push(
+            i + 1,
+            serde_yaml_ng::from_value(item).map_err(|e| e.to_string()),
+        );
+    }
+    Ok(parsed)
+}
+
+fn read_jsonl<T: DeserializeOwned>(input: &str) -> BatchRows<T> {
+    let mut parsed = BatchRows::default();
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        parsed.push(i + 1, serde_json::from_str(line).map_err(|e| e.to_string()));
+    }
+    parsed
+}
+
+fn read_csv<T: DeserializeOwned>(
+    input: &str,
+    fields: &[&str],
+    map: &HashMap<String, String>,
+) -> Result<BatchRows<T>, NarraError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| NarraError::Validation(format!("CSV: {}", e)))?
+        .iter()
+        .map(String::from)
+        .collect();
+
+    for (field, column) in map {
+        if !headers.iter().any(|h| h.eq_ignore_ascii_case(column)) {
+            return Err(NarraError::Validation(format!(
+                "Column '{}' (mapped to {}) not found; headers are: {}",
+                column,
+                field,
+                headers.join(", ")
+            )));
+        }
+    }
+
+    // Column index → field; explicit mappings win over same-named headers
+    let mut parsed = BatchRows::default();
+    let mut columns: Vec<Option<String>> = Vec::with_capacity(headers.len());
+    for header in &headers {
+        let mapped = map
+            .iter()
+            .find(|(_, column)| header.eq_ignore_ascii_case(column))
+            .map(|(field, _)| field.clone());
+        let field = mapped.or_else(|| {
+            let name = normalize_header(header);
+            let known = fields.contains(&name.as_str())
+                || (fields.contains(&"profile") && name.starts_with(PROFILE_PREFIX));
+            (known && !map.contains_key(&name)).then_some(name)
+        });
+        if field.is_none() {
+            parsed.ignored_columns.push(header.clone());
+        }
+        columns.push(field);
+    }
+
+    for (i, record) in reader.records().enumerate() {
+        // Header is line 1
+        let fallback_line = i + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e
+                    .position()
+                    .map(|p| p.line() as usize)
+                    .unwrap_or(fallback_line);
+                parsed.errors.push(RowError::new(line, e.to_string()));
+                continue;
+            }
+        };
+        let line = record
+            .position()
+            .map(|p| p.line() as usize)
+            .unwrap_or(fallback_line);
+
+        let row: Result<T, String> = (|| {
+            let mut object = Map::new();
+            let mut profile = Map::new();
+            for (field, cell) in columns.iter().zip(record.iter()) {
+                let Some(field) = field else { continue };
+                if cell.is_empty() {
+                    continue;
+                }
+                if let Some(key) = field.strip_prefix(PROFILE_PREFIX) {
+                    profile.insert(key.to_string(), Value::from(split_list(cell)));
+                } else {
+                    object.insert(field.clone(), cell_value(field, cell)?);
+                }
+            }
+            if !profile.is_empty() {
+                object.insert("profile".to_string(), Value::Object(profile));
+            }
+            serde_json::from_value(Value::Object(object)).map_err(|e| e.to_string())
+        })();
+        parsed.push(line, row);
+    }
+    Ok(parsed)
+}
+
+/// Split `input` into rows of `T`. `fields` are the spec's field names, used
+/// to match CSV headers and check `map`.
+pub fn parse_rows<T: DeserializeOwned>(
+    input: &str,
+    format: BatchFormat,
+    fields: &[&str],
+    map: Option<&str>,
+) -> Result<BatchRows<T>, NarraError> {
+    let map = match map {
+        Some(dsl) => parse_column_map(dsl, fields)?,
+        None => HashMap::new(),
+    };
+    if !map.is_empty() && format != BatchFormat::Csv {
+        return Err(NarraError::Validation(
+            "--map only applies to CSV input".into(),
+        ));
+    }
+    match format {
+        BatchFormat::Yaml => read_yaml(input),
+        BatchFormat::Jsonl => Ok(read_jsonl(input)),
+        BatchFormat::Csv => read_csv(input, fields, &map),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::types::{CharacterSpec, RelationshipSpec};
+
+    #[test]
+    fn test_csv_maps_headers_and_reports_bad_rows() {
+        let csv = "Full Name,Role,Aliases,profile.wound,Notes\n\
+                   Alice,detective,Al; Ally,lost her sister,tall\n\
+                   ,villain,,,\n\
+                   Bob,,,,\n";
+        let parsed: BatchRows<CharacterSpec> = parse_rows(
+            csv,
+            BatchFormat::Csv,
+            CHARACTER_FIELDS,
+            Some("name=Full Name"),
+        )
+        .unwrap();
+
+        assert_eq!(parsed.ignored_columns, vec!["Notes".to_string()]);
+        assert_eq!(parsed.rows.len(), 2);
+        let (line, alice) = &parsed.rows[0];
+        assert_eq!(*line, 2);
+        assert_eq!(alice.name, "Alice");
+        assert_eq!(alice.role.as_deref(), Some("detective"));
+        assert_eq!(
+            alice.aliases,
+            Some(vec!["Al".to_string(), "Ally".to_string()])
+        );
+        assert_eq!(
+            alice.profile.as_ref().unwrap()["wound"],
+            vec!["lost her sister".to_string()]
+        );
+        assert_eq!(parsed.rows[1].0, 4);
+
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].row, 3);
+        assert!(parsed.errors[0].message.contains("name"));
+
+        assert!(parse_rows::<CharacterSpec>(
+            csv,
+            BatchFormat::Csv,
+            CHARACTER_FIELDS,
+            Some("nickname=Aliases")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_jsonl_and_yaml_report_rows_independently() {
+        let jsonl = "{\"from_character_id\":\"character:a\",\"to_character_id\":\"character:b\",\"rel_type\":\"rivalry\"}\n\
+                     \n\
+                     {\"from_character_id\":\"character:a\"}\n\
+                     not json\n";
+        let parsed: BatchRows<RelationshipSpec> =
+            parse_rows(jsonl, BatchFormat::Jsonl, RELATIONSHIP_FIELDS, None).unwrap();
+        assert_eq!(parsed.rows.len(), 1);
+        let rows: Vec<usize> = parsed.errors.iter().map(|e| e.row).collect();
+        assert_eq!(rows, vec![3, 4]);
+
+        let yaml = "- name: Alice\n- role: villain\n";
+        let parsed: BatchRows<CharacterSpec> =
+            parse_rows(yaml, BatchFormat::Yaml, CHARACTER_FIELDS, None).unwrap();
+        assert_eq!(parsed.rows.len(), 1);
+        assert_eq!(parsed.errors[0].row, 2);
+
+        assert!(parse_rows::<CharacterSpec>(
+            yaml,
+            BatchFormat::Yaml,
+            CHARACTER_FIELDS,
+            Some("name=N")
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,780 @@
+//! Compiled world bible: characters, locations, timeline, and a fact appendix
+//! rendered as one long-form document.
+//!
+//! The bible is assembled as Markdown chapters, then rendered to HTML (single
+//! file), EPUB 3 (one XHTML file per chapter), or PDF (the standard Helvetica
+//! fonts, so nothing needs embedding). The Markdown comes from the `bible`
+//! template, so writers can restructure it (see `services::templates`).
+//! Portraits are placeholders: Markdown images with a `portrait:<id>` target
+//! that each renderer draws as a box.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use serde_json::{json, Value};
+
+use crate::mcp::types::NarraImport;
+use crate::services::export::normalize_id;
+use crate::services::templates::Templates;
+use crate::NarraError;
+
+const HTML_TEMPLATE: &str = include_str!("templates/bible.html");
+const CSS: &str = include_str!("templates/bible.css");
+const CHAPTER_TEMPLATE: &str = include_str!("templates/bible-chapter.xhtml");
+const OPF_TEMPLATE: &str = include_str!("templates/bible.opf");
+
+const PORTRAIT_SCHEME: &str = "portrait:";
+
+/// Output formats for `narra world compile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BibleFormat {
+    Markdown,
+    Html,
+    Epub,
+    Pdf,
+}
+
+impl BibleFormat {
+    pub fn parse(s: &str) -> Result<Self, NarraError> {
+        match s.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "epub" => Ok(Self::Epub),
+            "pdf" => Ok(Self::Pdf),
+            _ => Err(NarraError::Validation(format!(
+                "Unknown format '{}' (expected epub, pdf, html, or md)",
+                s
+            ))),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Epub => "epub",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+pub struct Chapter {
+    /// Anchor and EPUB file stem
+    pub slug: String,
+    pub title: String,
+    pub markdown: String,
+}
+
+pub struct WorldBible {
+    pub title: String,
+    pub chapters: Vec<Chapter>,
+}
+
+fn key(id: &str) -> String {
+    normalize_id("", id)
+        .split_once(':')
+        .map(|(_, k)| k.to_string())
+        .unwrap_or_default()
+}
+
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |acc, (name, value)| {
+            acc.replace(&format!("{{{{{}}}}}", name), value)
+        })
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn slugify(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Data handed to the `bible` template: sorted, with references resolved to names.
+fn bible_view(title: &str, world: &NarraImport) -> Value {
+    let mut names: HashMap<String, String> = HashMap::new();
+    for c in &world.characters {
+        if let Some(id) = &c.id {
+            names.insert(key(id), c.name.clone());
+        }
+    }
+    for l in &world.locations {
+        if let Some(id) = &l.id {
+            names.insert(key(id), l.name.clone());
+        }
+    }
+    let name_of = |id: &str| names.get(&key(id)).cloned().unwrap_or_else(|| key(id));
+
+    let mut characters: Vec<_> = world.characters.iter().collect();
+    characters.sort_by_key(|c| c.name.to_lowercase());
+    let characters: Vec<Value> = characters
+        .into_iter()
+        .map(|c| {
+            let id = c.id.as_deref().map(key).unwrap_or_default();
+            let mut profile: Vec<Value> = c
+                .profile
+                .iter()
+                .flatten()
+                .map(|(k, values)| json!({ "key": k, "values": values }))
+                .collect();
+            profile.sort_by(|a, b| a["key"].as_str().cmp(&b["key"].as_str()));
+            let relationships: Vec<Value> = world
+                .relationships
+                .iter()
+                .filter(|r| key(&r.from_character_id) == id)
+                .map(|r| {
+                    let kind = r.label.clone().unwrap_or_else(|| match &r.subtype {
+                        Some(sub) => format!("{} ({})", r.rel_type, sub),
+                        None => r.rel_type.clone(),
+                    });
+                    json!({ "kind": kind, "other": name_of(&r.to_character_id) })
+                })
+                .collect();
+            json!({
+                "id": id,
+                "name": c.name,
+                "role": c.role,
+                "aliases": c.aliases.clone().unwrap_or_default(),
+                "description": c.description,
+                "profile": profile,
+                "relationships": relationships,
+            })
+        })
+        .collect();
+
+    let mut locations: Vec<_> = world.locations.iter().collect();
+    locations.sort_by_key(|l| l.name.to_lowercase());
+    let locations: Vec<Value> = locations
+        .into_iter()
+        .map(|l| {
+            let mut meta: Vec<String> = l.loc_type.iter().cloned().collect();
+            if let Some(parent) = &l.parent_id {
+                meta.push(format!("in {}", name_of(parent)));
+            }
+            json!({ "name": l.name, "meta": meta, "description": l.description })
+        })
+        .collect();
+
+    let mut events: Vec<_> = world.events.iter().collect();
+    events.sort_by_key(|e| (e.sequence.is_none(), e.sequence));
+    let events: Vec<Value> = events
+        .into_iter()
+        .map(|e| {
+            let event_id = e.id.as_deref().map(key).unwrap_or_default();
+            let scenes: Vec<Value> = world
+                .scenes
+                .iter()
+                .filter(|s| key(&s.event_id) == event_id)
+                .map(|s| {
+                    json!({
+                        "title": s.title,
+                        "location": name_of(&s.location_id),
+                        "summary": s.summary,
+                    })
+                })
+                .collect();
+            json!({
+                "sequence": e.sequence,
+                "title": e.title,
+                "status": e.narrative_status.as_deref().map(|s| s.replace('_', " ")),
+                "date": e.date,
+                "description": e.description,
+                "scenes": scenes,
+            })
+        })
+        .collect();
+
+    let facts: Vec<Value> = world
+        .facts
+        .iter()
+        .map(|f| {
+            let mut meta = f.categories.clone();
+            if let Some(level) = &f.enforcement_level {
+                meta.push(format!("{} enforcement", level));
+            }
+            json!({ "title": f.title, "meta": meta, "description": f.description })
+        })
+        .collect();
+
+    json!({
+        "title": title,
+        "characters": characters,
+        "locations": locations,
+        "events": events,
+        "facts": facts,
+    })
+}
+
+impl WorldBible {
+    /// Render the `bible` template and split it into chapters at each
+    /// top-level heading.
+    pub fn build(
+        title: &str,
+        world: &NarraImport,
+        templates: &Templates,
+    ) -> Result<Self, NarraError> {
+        let markdown = templates.render("bible", &bible_view(title, world))?;
+
+        let mut chapters: Vec<Chapter> = Vec::new();
+        for line in markdown.lines() {
+            if let Some(heading) = line.strip_prefix("# ") {
+                chapters.push(Chapter {
+                    slug: slugify(heading),
+                    title: heading.trim().to_string(),
+                    markdown: String::new(),
+                });
+            } else if chapters.is_empty() && !line.trim().is_empty() {
+                // Text before the first heading becomes a preface
+                chapters.push(Chapter {
+                    slug: "preface".into(),
+                    title: title.to_string(),
+                    markdown: String::new(),
+                });
+            }
+            if let Some(chapter) = chapters.last_mut() {
+                chapter.markdown.push_str(line);
+                chapter.markdown.push('\n');
+            }
+        }
+
+        Ok(Self {
+            title: title.to_string(),
+            chapters,
+        })
+    }
+
+    pub fn render(&self, format: BibleFormat) -> Result<Vec<u8>, NarraError> {
+        match format {
+            BibleFormat::Markdown => Ok(self.to_markdown().into_bytes()),
+            BibleFormat::Html => Ok(self.to_html().into_bytes()),
+            BibleFormat::Epub => self.to_epub(),
+            BibleFormat::Pdf => Ok(self.to_pdf()),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("% {}\n\n", self.title);
+        for chapter in &self.chapters {
+            md.push_str(&chapter.markdown);
+        }
+        md
+    }
+
+    pub fn to_html(&self) -> String {
+        let toc: String = self
+            .chapters
+            .iter()
+            .map(|c| {
+                format!(
+                    "<li><a href=\"#{}\">{}</a></li>\n",
+                    c.slug,
+                    escape_xml(&c.title)
+                )
+            })
+            .collect();
+        let body: String = self
+            .chapters
+            .iter()
+            .map(|c| {
+                format!(
+                    "<section class=\"chapter\" id=\"{}\">\n{}</section>\n",
+                    c.slug,
+                    markdown_to_html(&c.markdown)
+                )
+            })
+            .collect();
+        fill(
+            HTML_TEMPLATE,
+            &[
+                ("title", escape_xml(&self.title).as_str()),
+                ("css", CSS),
+                (
+                    "date",
+                    chrono::Utc::now().format("%Y-%m-%d").to_string().as_str(),
+                ),
+                ("toc", toc.as_str()),
+                ("body", body.as_str()),
+            ],
+        )
+    }
+
+    pub fn to_epub(&self) -> Result<Vec<u8>, NarraError> {
+        use zip::write::SimpleFileOptions;
+        use zip::CompressionMethod;
+
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let title = escape_xml(&self.title);
+
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let mut add = |name: &str, contents: &str, options| -> Result<(), NarraError> {
+            zip.start_file(name, options)
+                .map_err(std::io::Error::other)?;
+            zip.write_all(contents.as_bytes())?;
+            Ok(())
+        };
+
+        // The mimetype entry must come first and uncompressed
+        add("mimetype", "application/epub+zip", stored)?;
+        add(
+            "META-INF/container.xml",
+            concat!(
+                "<?xml version=\"1.0\"?>\n",
+                "<container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">\n",
+                "<rootfiles><rootfile full-path=\"OEBPS/content.opf\" media-type=\"application/oebps-package+xml\"/></rootfiles>\n",
+                "</container>\n"
+            ),
+            deflated,
+        )?;
+        add("OEBPS/style.css", CSS, deflated)?;
+
+        let mut manifest = String::new();
+        let mut spine = String::new();
+        let mut nav = format!("<nav epub:type=\"toc\">\n<h1>{}</h1>\n<ol>\n", title);
+        for chapter in &self.chapters {
+            let file = format!("{}.xhtml", chapter.slug);
+            add(
+                &format!("OEBPS/{}", file),
+                &fill(
+                    CHAPTER_TEMPLATE,
+                    &[
+                        ("title", escape_xml(&chapter.title).as_str()),
+                        ("body", markdown_to_html(&chapter.markdown).as_str()),
+                    ],
+                ),
+                deflated,
+            )?;
+            manifest.push_str(&format!(
+                "<item id=\"{0}\" href=\"{1}\" media-type=\"application/xhtml+xml\"/>\n",
+                chapter.slug, file
+            ));
+            spine.push_str(&format!("<itemref idref=\"{}\"/>\n", chapter.slug));
+            nav.push_str(&format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                file,
+                escape_xml(&chapter.title)
+            ));
+        }
+        nav.push_str("</ol>\n</nav>\n");
+
+        add(
+            "OEBPS/nav.xhtml",
+            &fill(
+                CHAPTER_TEMPLATE,
+                &[("title", title.as_str()), ("body", nav.as_str())],
+            ),
+            deflated,
+        )?;
+        add(
+            "OEBPS/content.opf",
+            &fill(
+                OPF_TEMPLATE,
+                &[
+                    ("uuid", uuid::Uuid::new_v4().to_string().as_str()),
+                    ("title", title.as_str()),
+                    (
+                        "modified",
+                        chrono::Utc::now()
+                            .format("%Y-%m-%dT%H:%M:%SZ")
+                            .to_string()
+                            .as_str(),
+                    ),
+                    ("manifest", manifest.as_str()),
+                    ("spine", spine.as_str()),
+                ],
+            ),
+            deflated,
+        )?;
+
+        let cursor = zip.finish().map_err(std::io::Error::other)?;
+        Ok(cursor.into_inner())
+    }
+
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut pdf = PdfWriter::default();
+        pdf.heading(&self.title, 26.0);
+        for chapter in &self.chapters {
+            pdf.page_break();
+            pdf.markdown(&chapter.markdown);
+        }
+        pdf.finish()
+    }
+}
+
+fn markdown_parser(md: &str) -> Parser<'_> {
+    Parser::new_ext(md, Options::ENABLE_HEADING_ATTRIBUTES)
+}
+
+/// Markdown to (X)HTML, with portrait images drawn as placeholder boxes.
+fn markdown_to_html(md: &str) -> String {
+    let mut events = Vec::new();
+    let mut in_portrait = false;
+    for event in markdown_parser(md) {
+        match event {
+            Event::Start(Tag::Image { dest_url, .. }) if dest_url.starts_with(PORTRAIT_SCHEME) => {
+                in_portrait = true;
+            }
+            Event::Text(alt) if in_portrait => events.push(Event::Html(
+                format!("<span class=\"portrait\">{}</span>", escape_xml(&alt)).into(),
+            )),
+            Event::End(TagEnd::Image) if in_portrait => in_portrait = false,
+            other => events.push(other),
+        }
+    }
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    html
+}
+
+// ---------------------------------------------------------------------------
+// Minimal PDF writer
+// ---------------------------------------------------------------------------
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const BODY_SIZE: f32 = 10.5;
+const PORTRAIT_WIDTH: f32 = 84.0;
+const PORTRAIT_HEIGHT: f32 = 102.0;
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+}
+
+impl Font {
+    fn name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Italic => "F3",
+        }
+    }
+}
+
+/// Lays out text top to bottom with greedy word wrap. Glyph widths are
+/// approximated, which is close enough for Helvetica body text.
+#[derive(Default)]
+struct PdfWriter {
+    pages: Vec<String>,
+    y: f32,
+    /// Text is kept clear of a portrait box until this height
+    indent_until: f32,
+}
+
+fn glyph_width(c: char, size: f32) -> f32 {
+    let em = match c {
+        'i' | 'j' | 'l' | '.' | ',' | ';' | ':' | '\'' | '|' | '!' | ' ' => 0.28,
+        'f' | 't' | 'r' | '(' | ')' | '-' => 0.33,
+        'm' | 'w' | 'M' | 'W' => 0.83,
+        c if c.is_uppercase() => 0.67,
+        _ => 0.55,
+    };
+    em * size
+}
+
+/// Encode as a PDF literal string in WinAnsiEncoding.
+fn pdf_string(text: &str) -> String {
+    let mut out = String::from("(");
+    for c in text.chars() {
+        let byte: u32 = match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                c as u32
+            }
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201C}' => 0x93,
+            '\u{201D}' => 0x94,
+            '\u{2026}' => 0x85,
+            c if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) => c as u32,
+            _ => '?' as u32,
+        };
+        if byte < 0x80 {
+            out.push(byte as u8 as char);
+        } else {
+            out.push_str(&format!("\\{:03o}", byte));
+        }
+    }
+    out.push(')');
+    out
+}
+
+impl PdfWriter {
+    fn page_break(&mut self) {
+        self.pages.push(String::new());
+        self.y = PAGE_HEIGHT - MARGIN;
+        self.indent_until = PAGE_HEIGHT;
+    }
+
+    fn ensure_room(&mut self, height: f32) {
+        if self.pages.is_empty() || self.y - height < MARGIN {
+            self.page_break();
+        }
+    }
+
+    fn width(&self) -> f32 {
+        let full = PAGE_WIDTH - 2.0 * MARGIN;
+        if self.y > self.indent_until {
+            full - PORTRAIT_WIDTH - 12.0
+        } else {
+            full
+        }
+    }
+
+    fn paragraph(&mut self, text: &str, font: Font, size: f32, indent: f32) {
+        let leading = size * 1.35;
+        let mut line = String::new();
+        let mut line_width = 0.0;
+        let space = glyph_width(' ', size);
+        for word in text.split_whitespace() {
+            let word_width: f32 = word.chars().map(|c| glyph_width(c, size)).sum();
+            let limit = self.width() - indent;
+            if !line.is_empty() && line_width + space + word_width > limit {
+                self.line(&line, font, size, indent, leading);
+                line.clear();
+                line_width = 0.0;
+            }
+            if !line.is_empty() {
+                line.push(' ');
+                line_width += space;
+            }
+            line.push_str(word);
+            line_width += word_width;
+        }
+        if !line.is_empty() {
+            self.line(&line, font, size, indent, leading);
+        }
+        self.y -= size * 0.6;
+    }
+
+    fn line(&mut self, text: &str, font: Font, size: f32, indent: f32, leading: f32) {
+        self.ensure_room(leading);
+        self.y -= leading;
+        let page = self.pages.last_mut().expect("page_break pushes a page");
+        page.push_str(&format!(
+            "BT /{} {:.1} Tf {:.1} {:.1} Td {} Tj ET\n",
+            font.name(),
+            size,
+            MARGIN + indent,
+            self.y,
+            pdf_string(text)
+        ));
+    }
+
+    fn heading(&mut self, text: &str, size: f32) {
+        // Keep headings with at least a couple of body lines
+        self.ensure_room(size * 1.6 + BODY_SIZE * 4.0);
+        self.y -= size * 0.4;
+        self.paragraph(text, Font::Bold, size, 0.0);
+    }
+
+    fn portrait(&mut self, caption: &str) {
+        self.ensure_room(PORTRAIT_HEIGHT);
+        let x = PAGE_WIDTH - MARGIN - PORTRAIT_WIDTH;
+        let top = self.y;
+        let page = self.pages.last_mut().expect("page_break pushes a page");
+        page.push_str(&format!(
+            "0.6 G [3 3] 0 d {:.1} {:.1} {:.1} {:.1} re S [] 0 d 0 G\n",
+            x,
+            top - PORTRAIT_HEIGHT,
+            PORTRAIT_WIDTH,
+            PORTRAIT_HEIGHT
+        ));
+        page.push_str(&format!(
+            "0.6 g BT /F3 7.0 Tf {:.1} {:.1} Td {} Tj ET 0 g\n",
+            x + 6.0,
+            top - PORTRAIT_HEIGHT / 2.0,
+            pdf_string(caption)
+        ));
+        self.indent_until = top - PORTRAIT_HEIGHT - 6.0;
+    }
+
+    /// Render headings, paragraphs, list items, and portraits; inline
+    /// emphasis is flattened.
+    fn markdown(&mut self, md: &str) {
+        let mut text = String::new();
+        let mut font = Font::Regular;
+        let mut in_portrait = false;
+        let mut list_depth = 0usize;
+        for event in markdown_parser(md) {
+            match event {
+                Event::Start(Tag::Image { dest_url, .. })
+                    if dest_url.starts_with(PORTRAIT_SCHEME) =>
+                {
+                    in_portrait = true;
+                }
+                Event::End(TagEnd::Image) if in_portrait => {
+                    in_portrait = false;
+                    let caption = std::mem::take(&mut text);
+                    self.portrait(&caption);
+                }
+                Event::Start(Tag::Heading { .. }) => text.clear(),
+                Event::End(TagEnd::Heading(level)) => {
+                    let size = match level {
+                        HeadingLevel::H1 => 20.0,
+                        HeadingLevel::H2 => 14.0,
+                        _ => 12.0,
+                    };
+                    let heading = std::mem::take(&mut text);
+                    self.heading(&heading, size);
+                }
+                Event::Start(Tag::List(_)) => list_depth += 1,
+                Event::End(TagEnd::List(_)) => {
+                    list_depth -= 1;
+                    if list_depth == 0 {
+                        self.y -= BODY_SIZE * 0.4;
+                    }
+                }
+                Event::Start(Tag::Item) => text = String::from("\u{2022} "),
+                Event::End(TagEnd::Item) => {
+                    let item = std::mem::take(&mut text);
+                    self.paragraph(&item, Font::Regular, BODY_SIZE, 12.0 * list_depth as f32);
+                    self.y += BODY_SIZE * 0.6;
+                }
+                Event::Start(Tag::Emphasis) if text.is_empty() => font = Font::Italic,
+                Event::End(TagEnd::Paragraph) if list_depth == 0 => {
+                    let paragraph = std::mem::take(&mut text);
+                    self.paragraph(&paragraph, font, BODY_SIZE, 0.0);
+                    font = Font::Regular;
+                }
+                Event::Text(t) | Event::Code(t) => text.push_str(&t),
+                Event::SoftBreak | Event::HardBreak => text.push(' '),
+                _ => {}
+            }
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        let mut objects: Vec<String> = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".into(),
+            String::new(), // page tree, filled in below
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .into(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .into(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Oblique /Encoding /WinAnsiEncoding >>"
+                .into(),
+        ];
+        let mut kids = Vec::new();
+        for content in &self.pages {
+            let content_id = objects.len() + 2;
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH, PAGE_HEIGHT, content_id
+            ));
+            kids.push(format!("{} 0 R", objects.len()));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            ));
+        }
+        objects[1] = format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            kids.len()
+        );
+
+        let mut out = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+        }
+        let xref = out.len();
+        out.push_str(&format!(
+            "xref\n0 {}\n0000000000 65535 f \n",
+            objects.len() + 1
+        ));
+        for offset in offsets {
+            out.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        out.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        ));
+        out.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::types::{CharacterSpec, EventSpec, FactSpec};
+
+    fn world() -> NarraImport {
+        NarraImport {
+            characters: vec![CharacterSpec {
+                id: Some("alice".into()),
+                name: "Alice".into(),
+                role: Some("Detective (retired)".into()),
+                aliases: None,
+                description: Some("Knows too much.".into()),
+                profile: None,
+            }],
+            events: vec![EventSpec {
+                id: Some("fire".into()),
+                title: "The Fire".into(),
+                description: None,
+                sequence: Some(10),
+                date: None,
+                date_precision: None,
+                narrative_status: None,
+            }],
+            facts: vec![FactSpec {
+                id: None,
+                title: "No magic".into(),
+                description: "Nothing supernatural happens.".into(),
+                categories: vec![],
+                enforcement_level: None,
+                applies_to: vec![],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bible_renders_all_formats() {
+        let bible = WorldBible::build("Noir", &world(), &Templates::builtin()).unwrap();
+        let titles: Vec<_> = bible.chapters.iter().map(|c| c.slug.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["characters", "timeline", "appendix-universe-facts"]
+        );
+
+        let html = bible.to_html();
+        assert!(html.contains("<span class=\"portrait\">Portrait of Alice</span>"));
+        assert!(html.contains("id=\"timeline\""));
+
+        let epub = bible.to_epub().unwrap();
+        assert_eq!(&epub[..2], b"PK");
+        let mut archive = zip::ZipArchive::new(Cursor::new(epub)).unwrap();
+        assert_eq!(archive.by_index(0).unwrap().name(), "mimetype");
+
+        let pdf = String::from_utf8(bible.to_pdf()).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("(Detective \\(retired\\)) Tj"));
+        assert!(pdf.trim_end().ends_with("%%EOF"));
+    }
+}
@@ -4,13 +4,11 @@
 
 use crate::db::connection::NarraDb;
 use async_trait::async_trait;
-use linfa::prelude::*;
-use linfa_clustering::KMeans;
-use ndarray::{Array1, Array2};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use crate::services::kmeans::{self, KMeansOptions};
 use crate::services::EntityType;
 use crate::NarraError;
 
@@ -149,17 +147,34 @@ impl ClusteringDataProvider for SurrealClusteringDataProvider {
 /// Service for discovering thematic clusters in story entities.
 pub struct ClusteringService {
     data: Arc<dyn ClusteringDataProvider>,
+    options: KMeansOptions,
+    members: Option<HashSet<String>>,
 }
 
 impl ClusteringService {
     pub fn new(db: Arc<NarraDb>) -> Self {
+        Self::with_provider(Arc::new(SurrealClusteringDataProvider::new(db)))
+    }
+
+    pub fn with_provider(data: Arc<dyn ClusteringDataProvider>) -> Self {
         Self {
-            data: Arc::new(SurrealClusteringDataProvider::new(db)),
+            data,
+            options: kmeans::configured(),
+            members: None,
         }
     }
 
-    pub fn with_provider(data: Arc<dyn ClusteringDataProvider>) -> Self {
-        Self { data }
+    /// Restrict the analysis to these entity IDs (a phase's members);
+    /// `None` covers the whole world.
+    pub fn with_members(mut self, members: Option<HashSet<String>>) -> Self {
+        self.members = members;
+        self
+    }
+
+    /// Override the configured memory ceiling and seed.
+    pub fn with_options(mut self, options: KMeansOptions) -> Self {
+        self.options = options;
+        self
     }
 
     /// Discover thematic clusters by analyzing entity embeddings.
@@ -168,10 +183,15 @@ impl ClusteringService {
         entity_types: Vec<EntityType>,
         num_themes: Option<usize>,
     ) -> Result<ClusteringResult, NarraError> {
-        let (entity_data, total_entities) = self
+        let (mut entity_data, mut total_entities) = self
             .data
             .get_entities_with_embeddings(&entity_types)
             .await?;
+        if let Some(members) = &self.members {
+            // Phase members are detected from embeddings, so all have one
+            entity_data.retain(|e| members.contains(&e.id));
+            total_entities = entity_data.len();
+        }
 
         let entities_with_embeddings = entity_data.len();
         let entities_without_embeddings = total_entities.saturating_sub(entities_with_embeddings);
@@ -190,56 +210,16 @@ impl ClusteringService {
             auto.max(2).min(entities_with_embeddings - 1)
         };
 
-        // Determine embedding dimensions from first entity
-        let num_dims = entity_data[0].embedding.len();
-        let mut matrix_data = Vec::with_capacity(entities_with_embeddings * num_dims);
-
-        for entity in &entity_data {
-            if entity.embedding.len() != num_dims {
-                return Err(NarraError::Database(format!(
-                    "Invalid embedding dimension: expected {}, got {}",
-                    num_dims,
-                    entity.embedding.len()
-                )));
-            }
-            for &value in &entity.embedding {
-                matrix_data.push(value as f64);
-            }
-        }
-
-        let embedding_matrix =
-            Array2::from_shape_vec((entities_with_embeddings, num_dims), matrix_data).map_err(
-                |e| NarraError::Database(format!("Failed to create embedding matrix: {}", e)),
-            )?;
-
-        let dataset = DatasetBase::new(
-            embedding_matrix.clone(),
-            Array1::from_elem(entities_with_embeddings, ()),
-        );
-
-        let model = KMeans::params(num_clusters)
-            .max_n_iterations(300)
-            .tolerance(1e-4)
-            .fit(&dataset)
-            .map_err(|e| NarraError::Database(format!("K-means clustering failed: {}", e)))?;
-
-        let predictions = model.predict(&dataset);
-        let cluster_assignments: Vec<usize> = predictions.iter().cloned().collect();
-
-        let centroids = model.centroids();
+        let embeddings: Vec<&[f32]> = entity_data.iter().map(|e| e.embedding.as_slice()).collect();
+        let fit = kmeans::fit(&embeddings, num_clusters, &self.options)?;
+        let cluster_assignments = fit.assignments;
+        let centroids = fit.centroids;
 
         let mut clusters: HashMap<usize, Vec<(String, String, String, f32)>> = HashMap::new();
 
         for (idx, cluster_id) in cluster_assignments.iter().enumerate() {
             let entity = &entity_data[idx];
-            let centroid = centroids.row(*cluster_id);
-
-            let mut distance_sq = 0.0_f64;
-            for (i, &emb_val) in entity.embedding.iter().enumerate() {
-                let diff = (emb_val as f64) - centroid[i];
-                distance_sq += diff * diff;
-            }
-            let distance = distance_sq.sqrt();
+            let distance = kmeans::distance_sq(&entity.embedding, &centroids[*cluster_id]).sqrt();
 
             let centrality = 1.0 / (1.0 + distance);
 
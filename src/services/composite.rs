@@ -2,11 +2,16 @@
 //! into higher-level narrative insights.
 
 use crate::db::connection::NarraDb;
+use crate::db::query::{parse_record_id, record_id};
 use crate::models::annotation::{EmotionOutput, ThemeOutput};
-use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::sync::Arc;
 
 use crate::models::knowledge::find_knowledge_conflicts;
+use crate::services::events::{EventBus, EventListener};
+use crate::services::goal_conflicts::{GoalConflict, GoalConflictService};
 use crate::services::role_inference::InferredRole;
 use crate::services::tension::NarrativeTension;
 use crate::services::{
@@ -19,16 +24,38 @@ use crate::NarraError;
 ///
 /// Orchestrates multiple analytics services to produce combined insights
 /// like situation reports, character dossiers, and scene planning.
+///
+/// Situation reports and dossiers are cached in `composite_cache`, keyed by a
+/// hash of the world state they were computed from (see `world_revision`).
 pub struct CompositeIntelligenceService {
     db: Arc<NarraDb>,
+    fresh: bool,
 }
 
+/// Tables whose rows carry `updated_at`: row count plus latest change.
+const STAMPED_TABLES: &[&str] = &[
+    "character",
+    "location",
+    "event",
+    "scene",
+    "perceives",
+    "knows",
+    "universe_fact",
+    "goal",
+];
+
+/// Append-only tables: the row count is enough.
+const COUNTED_TABLES: &[&str] = &["arc_snapshot"];
+
+/// Small tables without timestamps, hashed by content.
+const HASHED_TABLES: &[&str] = &["relates_to", "knowledge", "involved_in", "participates_in"];
+
 // ---------------------------------------------------------------------------
 // Output types
 // ---------------------------------------------------------------------------
 
 /// A summary of a knowledge conflict (BelievesWrongly).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConflictSummary {
     pub target: String,
     pub character_id: String,
@@ -37,7 +64,7 @@ pub struct ConflictSummary {
 }
 
 /// A high-tension perception pair.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TensionPair {
     pub observer: String,
     pub target: String,
@@ -46,7 +73,7 @@ pub struct TensionPair {
 }
 
 /// Full narrative situation report.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SituationReport {
     pub irony_highlights: Vec<KnowledgeAsymmetry>,
     pub knowledge_conflicts: Vec<ConflictSummary>,
@@ -60,7 +87,7 @@ pub struct SituationReport {
 }
 
 /// Narrative momentum assessment.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum NarrativeMomentum {
     Accelerating { reason: String },
@@ -69,7 +96,7 @@ pub enum NarrativeMomentum {
 }
 
 /// An unresolved plot thread.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnresolvedThread {
     pub thread_type: String, // "secret", "tension", "false_belief", "stale_arc"
     pub description: String,
@@ -78,7 +105,7 @@ pub struct UnresolvedThread {
 }
 
 /// Brief character arc summary.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterArcBrief {
     pub character_id: String,
     pub character_name: String,
@@ -88,7 +115,7 @@ pub struct CharacterArcBrief {
 }
 
 /// How others perceive a character.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerceptionSummary {
     pub observer: String,
     pub tension_level: Option<i32>,
@@ -96,7 +123,7 @@ pub struct PerceptionSummary {
 }
 
 /// Comprehensive dossier for a single character.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterDossier {
     pub name: String,
     pub roles: Vec<String>,
@@ -114,6 +141,12 @@ pub struct CharacterDossier {
     pub knowledge_inventory: KnowledgeInventory,
     /// Structural narrative tensions involving this character.
     pub narrative_tensions: Vec<NarrativeTension>,
+    /// The character's goals, active first.
+    #[serde(default)]
+    pub goals: Vec<GoalBrief>,
+    /// Conflicts between their active goals and those of characters they share scenes with.
+    #[serde(default)]
+    pub goal_conflicts: Vec<GoalConflict>,
     /// Emotion profile from ML classification (populated at handler level).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub emotion_profile: Option<EmotionOutput>,
@@ -123,7 +156,7 @@ pub struct CharacterDossier {
 }
 
 /// Brief arc trajectory summary.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArcTrajectoryBrief {
     pub direction: String, // "growing", "declining", "stable"
     pub total_drift: f32,
@@ -132,7 +165,7 @@ pub struct ArcTrajectoryBrief {
 }
 
 /// Brief relationship summary.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RelationshipBrief {
     pub other_character: String,
     pub other_name: String,
@@ -140,8 +173,21 @@ pub struct RelationshipBrief {
     pub tension: Option<i32>,
 }
 
+/// Brief goal summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalBrief {
+    pub goal_id: String,
+    pub character_id: String,
+    pub character_name: String,
+    pub want: String,
+    pub need: Option<String>,
+    pub stakes: Option<String>,
+    pub obstacle: Option<String>,
+    pub status: String,
+}
+
 /// Knowledge totals by certainty level.
-#[derive(Debug, Clone, Serialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct KnowledgeInventory {
     pub knows: usize,
     pub suspects: usize,
@@ -177,6 +223,10 @@ pub struct ScenePlan {
     pub narrative_tensions: Vec<NarrativeTension>,
     /// Inferred narrative roles for each scene character.
     pub character_roles: Vec<InferredRole>,
+    /// Active goals the scene characters bring in.
+    pub goals: Vec<GoalBrief>,
+    /// Conflicts between those goals.
+    pub goal_conflicts: Vec<GoalConflict>,
     /// Combined theme analysis for the scene (populated at handler level).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scene_themes: Option<ThemeOutput>,
@@ -315,13 +365,130 @@ struct FactConstraintRow {
 
 impl CompositeIntelligenceService {
     pub fn new(db: Arc<NarraDb>) -> Self {
-        Self { db }
+        Self { db, fresh: false }
+    }
+
+    /// Skip cached reports. Fresh results still replace the cached ones.
+    pub fn fresh(mut self, fresh: bool) -> Self {
+        self.fresh = fresh;
+        self
+    }
+
+    /// Hash of every table composite reports read from. Any create, update or
+    /// delete changes it, so a cached report is valid only while it matches.
+    pub async fn world_revision(&self) -> Result<String, NarraError> {
+        let parts: Vec<String> = STAMPED_TABLES
+            .iter()
+            .map(|t| {
+                format!(
+                    "[count((SELECT VALUE id FROM {t})), time::max((SELECT VALUE updated_at FROM {t}))]"
+                )
+            })
+            .chain(
+                COUNTED_TABLES
+                    .iter()
+                    .map(|t| format!("count((SELECT VALUE id FROM {t}))")),
+            )
+            .chain(
+                HASHED_TABLES
+                    .iter()
+                    .map(|t| format!("(SELECT * OMIT embedding, composite_text FROM {t})")),
+            )
+            .collect();
+        let query = format!("RETURN crypto::md5(<string> [{}])", parts.join(", "));
+
+        let mut response = self.db.query(query).await?;
+        let revision: Option<String> = response.take(0)?;
+        revision.ok_or_else(|| NarraError::Database("World revision query returned nothing".into()))
+    }
+
+    async fn cached<T: DeserializeOwned>(
+        &self,
+        report: &str,
+        subject: &str,
+        revision: &str,
+    ) -> Option<T> {
+        let mut response = self
+            .db
+            .query(
+                "SELECT VALUE payload FROM type::thing('composite_cache', [$report, $subject]) \
+                 WHERE revision = $revision",
+            )
+            .bind(("report", report.to_string()))
+            .bind(("subject", subject.to_string()))
+            .bind(("revision", revision.to_string()))
+            .await
+            .ok()?;
+        let payload: Option<String> = response.take(0).ok()?;
+        serde_json::from_str(&payload?).ok()
+    }
+
+    /// Best effort: a report that can't be cached is still returned.
+    async fn store<T: Serialize>(&self, report: &str, subject: &str, revision: &str, value: &T) {
+        let payload = match serde_json::to_string(value) {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::warn!("Failed to serialize {} report for cache: {}", report, e);
+                return;
+            }
+        };
+        let result = self
+            .db
+            .query(
+                "UPSERT type::thing('composite_cache', [$report, $subject]) \
+                 SET revision = $revision, payload = $payload, computed_at = time::now()",
+            )
+            .bind(("report", report.to_string()))
+            .bind(("subject", subject.to_string()))
+            .bind(("revision", revision.to_string()))
+            .bind(("payload", payload))
+            .await
+            .and_then(|r| r.check());
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache {} report: {}", report, e);
+        }
+    }
+
+    async fn through_cache<T, F>(
+        &self,
+        report: &str,
+        subject: &str,
+        compute: F,
+    ) -> Result<T, NarraError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<T, NarraError>>,
+    {
+        // Without a revision there is nothing to validate against: just compute
+        let revision = match self.world_revision().await {
+            Ok(r) => r,
+            Err(e) => {
+                tracing::warn!("Composite cache disabled: {}", e);
+                return compute.await;
+            }
+        };
+
+        if !self.fresh {
+            if let Some(hit) = self.cached(report, subject, &revision).await {
+                tracing::debug!("Composite cache hit: {} {}", report, subject);
+                return Ok(hit);
+            }
+        }
+
+        let value = compute.await?;
+        self.store(report, subject, &revision, &value).await;
+        Ok(value)
     }
 
     /// Generate a high-level narrative situation report.
     ///
     /// Combines irony, conflicts, tensions, and thematic clustering into one view.
     pub async fn situation_report(&self) -> Result<SituationReport, NarraError> {
+        self.through_cache("situation", "world", self.compute_situation_report())
+            .await
+    }
+
+    async fn compute_situation_report(&self) -> Result<SituationReport, NarraError> {
         let irony_service = IronyService::new(self.db.clone());
         let clustering_service = ClusteringService::new(self.db.clone());
         let tension_service = TensionService::new(self.db.clone());
@@ -411,14 +578,26 @@ impl CompositeIntelligenceService {
         character_id: &str,
     ) -> Result<CharacterDossier, NarraError> {
         let full_id = normalize_character_id(character_id);
+        self.through_cache(
+            "dossier",
+            &full_id,
+            self.compute_character_dossier(&full_id),
+        )
+        .await
+    }
 
+    async fn compute_character_dossier(
+        &self,
+        full_id: &str,
+    ) -> Result<CharacterDossier, NarraError> {
         let analytics = GraphAnalyticsService::new(self.db.clone());
         let influence_service = InfluenceService::new(self.db.clone());
         let irony_service = IronyService::new(self.db.clone());
         let role_service = RoleInferenceService::new(self.db.clone());
         let tension_service = TensionService::new(self.db.clone());
+        let goal_conflict_service = GoalConflictService::new(self.db.clone());
 
-        // Run all 11 async queries in parallel
+        // Run all 13 async queries in parallel
         let (
             char_info_result,
             centrality_result,
@@ -431,18 +610,22 @@ impl CompositeIntelligenceService {
             knowledge_inventory,
             role_result,
             tension_result,
+            goals_result,
+            goal_conflicts_result,
         ) = tokio::join!(
-            self.fetch_character_info(&full_id),
+            self.fetch_character_info(full_id),
             analytics.compute_centrality(None, vec![CentralityMetric::Degree], 100),
-            influence_service.trace_propagation(&full_id, 3),
-            irony_service.generate_report(Some(&full_id), 0),
-            self.count_false_beliefs(&full_id),
-            self.fetch_perceptions_about(&full_id, 5),
-            self.compute_arc_trajectory(&full_id),
-            self.build_relationship_map(&full_id),
-            self.build_knowledge_inventory(&full_id),
+            influence_service.trace_propagation(full_id, 3),
+            irony_service.generate_report(Some(full_id), 0),
+            self.count_false_beliefs(full_id),
+            self.fetch_perceptions_about(full_id, 5),
+            self.compute_arc_trajectory(full_id),
+            self.build_relationship_map(full_id),
+            self.build_knowledge_inventory(full_id),
             role_service.infer_roles(100),
             tension_service.detect_tensions(20, 0.0),
+            self.fetch_goals(&[full_id.to_string()], false),
+            goal_conflict_service.detect(&[]),
         );
 
         // Extract results — char_info is the only hard error
@@ -460,7 +643,7 @@ impl CompositeIntelligenceService {
             .unwrap_or(0);
 
         let irony_report = irony_result.unwrap_or_else(|_| crate::services::IronyReport {
-            focus: full_id.clone(),
+            focus: full_id.to_string(),
             asymmetries: vec![],
             total_asymmetries: 0,
             high_signal_count: 0,
@@ -495,6 +678,13 @@ impl CompositeIntelligenceService {
             .filter(|t| t.character_a_id == full_id || t.character_b_id == full_id)
             .collect();
 
+        let goals = goals_result.unwrap_or_default();
+        let goal_conflicts: Vec<GoalConflict> = goal_conflicts_result
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|c| c.character_id == full_id || c.other_character_id == full_id)
+            .collect();
+
         // Suggestions depend on the above results
         let suggestions = generate_dossier_suggestions(
             &name,
@@ -522,6 +712,8 @@ impl CompositeIntelligenceService {
             relationship_map,
             knowledge_inventory,
             narrative_tensions,
+            goals,
+            goal_conflicts,
             emotion_profile: None,
             theme_tags: None,
         })
@@ -558,14 +750,25 @@ impl CompositeIntelligenceService {
         // Run all pair queries + fact + tension + role queries in parallel
         let tension_service = TensionService::new(self.db.clone());
         let role_service = RoleInferenceService::new(self.db.clone());
+        let goal_conflict_service = GoalConflictService::new(self.db.clone());
         let char_count = normalized.len();
 
-        let (pair_results, applicable_facts, fact_constraints, tension_result, role_result) = tokio::join!(
+        let (
+            pair_results,
+            applicable_facts,
+            fact_constraints,
+            tension_result,
+            role_result,
+            goals,
+            goal_conflicts,
+        ) = tokio::join!(
             futures::future::join_all(pair_futures),
             self.fetch_applicable_facts(&normalized),
             self.fetch_fact_constraints(&normalized),
             tension_service.detect_tensions(20, 0.0),
             role_service.infer_roles(char_count + 10),
+            self.fetch_goals(&normalized, true),
+            goal_conflict_service.detect(&normalized),
         );
         let applicable_facts = applicable_facts?;
 
@@ -646,6 +849,8 @@ impl CompositeIntelligenceService {
             fact_constraints,
             narrative_tensions,
             character_roles,
+            goals: goals.unwrap_or_default(),
+            goal_conflicts: goal_conflicts.unwrap_or_default(),
             scene_themes: None,
         })
     }
@@ -687,13 +892,70 @@ impl CompositeIntelligenceService {
             .collect())
     }
 
+    /// Goals of the given characters (full IDs), active first.
+    async fn fetch_goals(
+        &self,
+        character_ids: &[String],
+        active_only: bool,
+    ) -> Result<Vec<GoalBrief>, NarraError> {
+        #[derive(serde::Deserialize)]
+        struct GoalRow {
+            id: surrealdb::RecordId,
+            character: surrealdb::RecordId,
+            character_name: Option<String>,
+            want: String,
+            need: Option<String>,
+            stakes: Option<String>,
+            obstacle: Option<String>,
+            status: String,
+        }
+
+        let characters: Vec<surrealdb::RecordId> = character_ids
+            .iter()
+            .filter_map(|id| id.split_once(':'))
+            .map(surrealdb::RecordId::from)
+            .collect();
+        let filter = if active_only {
+            " AND status = 'active'"
+        } else {
+            ""
+        };
+        let mut result = self
+            .db
+            .query(format!(
+                "SELECT id, character, character.name AS character_name, want, need, stakes, \
+                 obstacle, status, status = 'active' AS active, created_at FROM goal \
+                 WHERE character IN $characters{} ORDER BY active DESC, created_at ASC",
+                filter
+            ))
+            .bind(("characters", characters))
+            .await
+            .map_err(|e| NarraError::Database(e.to_string()))?;
+
+        let rows: Vec<GoalRow> = result.take(0).unwrap_or_default();
+        Ok(rows
+            .into_iter()
+            .map(|r| GoalBrief {
+                goal_id: r.id.to_string(),
+                character_id: r.character.to_string(),
+                character_name: r.character_name.unwrap_or_else(|| r.character.to_string()),
+                want: r.want,
+                need: r.need,
+                stakes: r.stakes,
+                obstacle: r.obstacle,
+                status: r.status,
+            })
+            .collect())
+    }
+
     async fn fetch_character_info(
         &self,
         full_id: &str,
     ) -> Result<(String, Vec<String>), NarraError> {
         let mut result = self
             .db
-            .query(format!("SELECT name, roles FROM {}", full_id))
+            .query("SELECT name, roles FROM $char_id")
+            .bind(("char_id", record_id("character", full_id)?))
             .await
             .map_err(|e| NarraError::Database(e.to_string()))?;
 
@@ -739,11 +1001,12 @@ impl CompositeIntelligenceService {
                 "SELECT type::string(in) AS observer, in.name AS observer_name, \
                  tension_level, feelings \
                  FROM perceives \
-                 WHERE out = {} \
+                 WHERE out = $char_id \
                  ORDER BY tension_level DESC \
                  LIMIT {}",
-                full_id, limit
+                limit
             ))
+            .bind(("char_id", record_id("character", full_id)?))
             .await
             .map_err(|e| NarraError::Database(e.to_string()))?;
 
@@ -1030,16 +1293,18 @@ impl CompositeIntelligenceService {
 
     /// Compute arc trajectory for a character.
     async fn compute_arc_trajectory(&self, full_id: &str) -> Option<ArcTrajectoryBrief> {
-        let query = format!(
-            "SELECT delta_magnitude, type::string(event_id) AS event_id, created_at \
-            FROM arc_snapshot \
-            WHERE entity_id = {} AND entity_type = 'character' \
-            ORDER BY created_at DESC \
-            LIMIT 10",
-            full_id
-        );
-
-        let snapshots = match self.db.query(&query).await {
+        let snapshots = match self
+            .db
+            .query(
+                "SELECT delta_magnitude, type::string(event_id) AS event_id, created_at \
+                FROM arc_snapshot \
+                WHERE entity_id = $char_id AND entity_type = 'character' \
+                ORDER BY created_at DESC \
+                LIMIT 10",
+            )
+            .bind(("char_id", record_id("character", full_id).ok()?))
+            .await
+        {
             Ok(mut r) => {
                 let rows: Vec<ArcSnapshotRow> = r.take(0).unwrap_or_default();
                 rows
@@ -1086,15 +1351,20 @@ impl CompositeIntelligenceService {
     /// Build relationship map for a character with tension overlays.
     async fn build_relationship_map(&self, full_id: &str) -> Vec<RelationshipBrief> {
         // Query relationships
-        let query = format!(
-            "SELECT type::string(out) AS other_character, out.name AS other_name, rel_type \
-            FROM relates_to \
-            WHERE in = {} \
-            LIMIT 20",
-            full_id
-        );
-
-        let relationships = match self.db.query(&query).await {
+        let Ok(char_id) = record_id("character", full_id) else {
+            return vec![];
+        };
+        let relationships = match self
+            .db
+            .query(
+                "SELECT type::string(out) AS other_character, out.name AS other_name, rel_type \
+                FROM relates_to \
+                WHERE in = $char_id \
+                LIMIT 20",
+            )
+            .bind(("char_id", char_id.clone()))
+            .await
+        {
             Ok(mut r) => {
                 let rows: Vec<RelationshipRow> = r.take(0).unwrap_or_default();
                 rows
@@ -1103,26 +1373,27 @@ impl CompositeIntelligenceService {
         };
 
         // Query tension for all relationships in parallel
-        let full_id_owned = full_id.to_string();
         let tension_futures: Vec<_> = relationships
             .iter()
             .map(|rel| {
                 let db = self.db.clone();
-                let fid = full_id_owned.clone();
-                let other = rel.other_character.clone();
+                let char_id = char_id.clone();
+                let other = parse_record_id(&rel.other_character);
                 async move {
                     #[derive(serde::Deserialize)]
                     struct TensionOnlyRow {
                         tension_level: Option<i32>,
                     }
 
-                    let tension_query = format!(
-                        "SELECT tension_level FROM perceives \
-                        WHERE (in = {} AND out = {}) OR (in = {} AND out = {}) \
-                        ORDER BY tension_level DESC LIMIT 1",
-                        fid, other, other, fid
-                    );
-                    match db.query(&tension_query).await {
+                    let query = db
+                        .query(
+                            "SELECT tension_level FROM perceives \
+                            WHERE (in = $a AND out = $b) OR (in = $b AND out = $a) \
+                            ORDER BY tension_level DESC LIMIT 1",
+                        )
+                        .bind(("a", char_id))
+                        .bind(("b", other.ok()?));
+                    match query.await {
                         Ok(mut r) => {
                             let row: Option<TensionOnlyRow> = r.take(0).unwrap_or(None);
                             row.and_then(|t| t.tension_level)
@@ -1149,15 +1420,20 @@ impl CompositeIntelligenceService {
 
     /// Build knowledge inventory grouped by certainty level.
     async fn build_knowledge_inventory(&self, full_id: &str) -> KnowledgeInventory {
-        let query = format!(
-            "SELECT certainty, count() AS count \
-            FROM knows \
-            WHERE in = {} \
-            GROUP BY certainty",
-            full_id
-        );
-
-        let rows = match self.db.query(&query).await {
+        let Ok(char_id) = record_id("character", full_id) else {
+            return KnowledgeInventory::default();
+        };
+        let rows = match self
+            .db
+            .query(
+                "SELECT certainty, count() AS count \
+                FROM knows \
+                WHERE in = $char_id \
+                GROUP BY certainty",
+            )
+            .bind(("char_id", char_id))
+            .await
+        {
             Ok(mut r) => {
                 let rows: Vec<CertaintyCountRow> = r.take(0).unwrap_or_default();
                 rows
@@ -1254,12 +1530,17 @@ async fn fetch_pair_tension_standalone(
     a: &str,
     b: &str,
 ) -> (Option<i32>, Option<String>) {
-    let query = format!(
-        "SELECT tension_level, feelings FROM perceives \
-         WHERE (in = {} AND out = {}) OR (in = {} AND out = {}) \
-         ORDER BY tension_level DESC LIMIT 1",
-        a, b, b, a
-    );
+    let (Ok(a), Ok(b)) = (parse_record_id(a), parse_record_id(b)) else {
+        return (None, None);
+    };
+    let query = db
+        .query(
+            "SELECT tension_level, feelings FROM perceives \
+             WHERE (in = $a AND out = $b) OR (in = $b AND out = $a) \
+             ORDER BY tension_level DESC LIMIT 1",
+        )
+        .bind(("a", a))
+        .bind(("b", b));
 
     #[derive(serde::Deserialize)]
     struct PairRow {
@@ -1267,7 +1548,7 @@ async fn fetch_pair_tension_standalone(
         feelings: Option<String>,
     }
 
-    match db.query(&query).await {
+    match query.await {
         Ok(mut r) => {
             let row: Option<PairRow> = r.take(0).unwrap_or(None);
             match row {
@@ -1280,19 +1561,24 @@ async fn fetch_pair_tension_standalone(
 }
 
 async fn count_shared_scenes_standalone(db: &NarraDb, a: &str, b: &str) -> usize {
-    let query = format!(
-        "SELECT count() AS count FROM (SELECT out FROM participates_in WHERE in = {}) \
-         WHERE out IN (SELECT VALUE out FROM participates_in WHERE in = {}) \
-         GROUP ALL",
-        a, b
-    );
+    let (Ok(a), Ok(b)) = (parse_record_id(a), parse_record_id(b)) else {
+        return 0;
+    };
+    let query = db
+        .query(
+            "SELECT count() AS count FROM (SELECT out FROM participates_in WHERE in = $a) \
+             WHERE out IN (SELECT VALUE out FROM participates_in WHERE in = $b) \
+             GROUP ALL",
+        )
+        .bind(("a", a))
+        .bind(("b", b));
 
     #[derive(serde::Deserialize)]
     struct CountRow {
         count: usize,
     }
 
-    match db.query(&query).await {
+    match query.await {
         Ok(mut r) => {
             let row: Option<CountRow> = r.take(0).unwrap_or(None);
             row.map(|r| r.count).unwrap_or(0)
@@ -1461,6 +1747,28 @@ fn generate_scene_opportunities(
     opportunities
 }
 
+/// Drop every cached composite report.
+pub async fn clear_composite_cache(db: &NarraDb) -> Result<(), NarraError> {
+    db.query("DELETE composite_cache").await?.check()?;
+    Ok(())
+}
+
+/// Clear cached reports after every entity mutation. The revision check
+/// already rejects stale entries; this keeps the table from accumulating them.
+pub fn invalidate_on_mutation(bus: &EventBus, db: Arc<NarraDb>) -> EventListener {
+    EventListener::spawn(bus, move |event| {
+        let db = db.clone();
+        async move {
+            if !event.event.starts_with("entity.") {
+                return;
+            }
+            if let Err(e) = clear_composite_cache(&db).await {
+                tracing::warn!("Failed to invalidate composite cache: {}", e);
+            }
+        }
+    })
+}
+
 /// Combine pairs from N characters.
 pub fn pair_count(n: usize) -> usize {
     if n < 2 {
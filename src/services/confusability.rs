@@ -0,0 +1,475 @@
+//! Cast confusability: character pairs readers are likely to mix up.
+//!
+//! Two signals, each enough to flag a pair:
+//! - Names: given names and aliases that are close by edit distance or share
+//!   a Soundex code. Shared surnames are left alone (families share them), and
+//!   so are titles and particles ("The", "Captain", "de").
+//! - Profiles: character embeddings that are near-duplicates.
+//!
+//! The same comparison runs before a character is created. Only a full name
+//! that nearly matches an existing one refuses it, so a second "Bran" isn't
+//! added where an update was meant; lesser resemblances are warnings.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::db::connection::NarraDb;
+use crate::embedding::composite::character_composite;
+use crate::embedding::EmbeddingService;
+use crate::models::{Character, CharacterCreate};
+use crate::services::names::{edit_similarity, fold_name, name_parts, NameMatch};
+use crate::utils::math::cosine_similarity;
+use crate::NarraError;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ConfusabilityOptions {
+    /// Names at or above this edit similarity are confusable.
+    pub name_similarity: f64,
+    /// Profiles at or above this cosine similarity are near-duplicates.
+    pub profile_similarity: f32,
+}
+
+impl Default for ConfusabilityOptions {
+    fn default() -> Self {
+        Self {
+            name_similarity: 0.75,
+            profile_similarity: 0.9,
+        }
+    }
+}
+
+/// Titles and particles that don't tell characters apart, folded.
+const IGNORED_WORDS: &[&str] = &[
+    "the", "a", "an", "of", "de", "da", "di", "du", "del", "la", "le", "van", "von", "der", "mr",
+    "mrs", "ms", "miss", "dr", "sir", "dame", "lord", "lady", "king", "queen", "prince",
+    "princess", "captain", "general", "father", "mother", "brother", "sister", "saint", "st",
+];
+
+fn is_ignored(word: &str) -> bool {
+    IGNORED_WORDS.contains(&fold_name(word).as_str())
+}
+
+/// The folded words of a name that tell it apart ("Captain Ada Reyes" ->
+/// ada, reyes); numbers and single letters count.
+fn significant_words(name: &str) -> Vec<String> {
+    fold_name(name)
+        .split_whitespace()
+        .filter(|w| !IGNORED_WORDS.contains(w))
+        .map(str::to_string)
+        .collect()
+}
+
+/// How alike two full names are: their weakest word match, in order, so
+/// "Node A" and "Node B" or "Alice Smith" and "Alice Jones" stay apart. A
+/// name that starts the other ("Bran", "Bran Stark") is compared on its
+/// own words.
+fn full_name_similarity(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| edit_similarity(x, y))
+        .fold(1.0, f64::min)
+}
+
+/// A character as input to the check.
+#[derive(Debug, Clone)]
+pub struct CastMember {
+    pub id: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl CastMember {
+    /// The names a reader addresses the character by: the given name plus
+    /// every alias word, leaving out titles and particles.
+    fn called(&self) -> Vec<&str> {
+        name_parts(&self.name)
+            .into_iter()
+            .filter(|p| !is_ignored(p))
+            .take(1)
+            .chain(
+                self.aliases
+                    .iter()
+                    .flat_map(|a| name_parts(a))
+                    .filter(|p| !is_ignored(p)),
+            )
+            .collect()
+    }
+
+    /// The full name and each alias, as significant words.
+    fn full_names(&self) -> Vec<Vec<String>> {
+        std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .map(|n| significant_words(n))
+            .filter(|words| !words.is_empty())
+            .collect()
+    }
+
+    /// Closest full-name similarity between any name or alias of the two.
+    fn full_name_similarity(&self, other: &CastMember) -> f64 {
+        let theirs = other.full_names();
+        self.full_names()
+            .iter()
+            .flat_map(|a| theirs.iter().map(move |b| full_name_similarity(a, b)))
+            .fold(0.0, f64::max)
+    }
+}
+
+/// The two names that collide.
+#[derive(Debug, Clone, Serialize)]
+pub struct NameCollision {
+    pub a: String,
+    pub b: String,
+    pub edit_similarity: f64,
+    pub same_soundex: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfusablePair {
+    pub a_id: String,
+    pub a_name: String,
+    pub b_id: String,
+    pub b_name: String,
+    pub name_collision: Option<NameCollision>,
+    pub profile_similarity: Option<f32>,
+    pub suggestion: String,
+}
+
+impl ConfusablePair {
+    /// Higher is worse; orders the report.
+    fn severity(&self, options: &ConfusabilityOptions) -> f64 {
+        let name = self.name_collision.as_ref().map_or(0.0, |n| {
+            n.edit_similarity
+                .max(if n.same_soundex { 0.8 } else { 0.0 })
+        });
+        let profile = match self.profile_similarity {
+            Some(s) if s >= options.profile_similarity => s as f64,
+            _ => 0.0,
+        };
+        name + profile
+    }
+
+    /// The closer of the name and profile similarities, from 0 to 1.
+    pub fn similarity(&self) -> f64 {
+        let name = self
+            .name_collision
+            .as_ref()
+            .map_or(0.0, |n| n.edit_similarity);
+        name.max(self.profile_similarity.unwrap_or(0.0) as f64)
+    }
+}
+
+/// The pair, when either signal flags it.
+fn compare(
+    a: &CastMember,
+    b: &CastMember,
+    options: &ConfusabilityOptions,
+) -> Option<ConfusablePair> {
+    let name_collision = a
+        .called()
+        .iter()
+        .flat_map(|x| b.called().into_iter().map(move |y| (*x, y)))
+        .map(|(x, y)| (x, y, NameMatch::between(x, y)))
+        .filter(|(_, _, m)| m.is_confusable(options.name_similarity))
+        .max_by(|p, q| p.2.edit_similarity.total_cmp(&q.2.edit_similarity))
+        .map(|(x, y, m)| NameCollision {
+            a: x.to_string(),
+            b: y.to_string(),
+            edit_similarity: m.edit_similarity,
+            same_soundex: m.same_soundex,
+        });
+    let profile_similarity = match (&a.embedding, &b.embedding) {
+        (Some(x), Some(y)) if x.len() == y.len() => Some(cosine_similarity(x, y)),
+        _ => None,
+    };
+    let profile_flagged = profile_similarity.is_some_and(|s| s >= options.profile_similarity);
+    if name_collision.is_none() && !profile_flagged {
+        return None;
+    }
+
+    let suggestion = match (&name_collision, profile_flagged) {
+        (Some(n), true) => format!(
+            "Rename one of '{}'/'{}' and sharpen what sets them apart (goals, voice, role)",
+            n.a, n.b
+        ),
+        (Some(n), false) if n.same_soundex && n.edit_similarity < options.name_similarity => {
+            format!(
+                "'{}' and '{}' sound alike; change the first letter or sound of one",
+                n.a, n.b
+            )
+        }
+        (Some(n), false) => format!(
+            "'{}' and '{}' look alike; vary length and initial letter",
+            n.a, n.b
+        ),
+        (None, _) => {
+            "Profiles are near-duplicates; give each a distinct want, wound or manner of speech"
+                .to_string()
+        }
+    };
+
+    Some(ConfusablePair {
+        a_id: a.id.clone(),
+        a_name: a.name.clone(),
+        b_id: b.id.clone(),
+        b_name: b.name.clone(),
+        name_collision,
+        profile_similarity,
+        suggestion,
+    })
+}
+
+pub fn find_confusable(cast: &[CastMember], options: &ConfusabilityOptions) -> Vec<ConfusablePair> {
+    let mut pairs = Vec::new();
+    for (i, a) in cast.iter().enumerate() {
+        for b in &cast[i + 1..] {
+            pairs.extend(compare(a, b, options));
+        }
+    }
+    pairs.sort_by(|p, q| q.severity(options).total_cmp(&p.severity(options)));
+    pairs
+}
+
+/// Existing characters a new one resembles, most similar first.
+#[derive(Debug, Clone, Default)]
+pub struct Resemblance {
+    /// Full names (or aliases) nearly match: likely the same character
+    pub duplicates: Vec<ConfusablePair>,
+    /// Given names or profiles alike: worth a warning, not a refusal
+    pub lookalikes: Vec<ConfusablePair>,
+}
+
+/// Existing characters a new one may duplicate or be mixed up with.
+pub fn similar_to(
+    candidate: &CastMember,
+    cast: &[CastMember],
+    options: &ConfusabilityOptions,
+) -> Resemblance {
+    let mut resemblance = Resemblance::default();
+    for b in cast {
+        let Some(pair) = compare(candidate, b, options) else {
+            continue;
+        };
+        if candidate.full_name_similarity(b) >= options.name_similarity {
+            resemblance.duplicates.push(pair);
+        } else {
+            resemblance.lookalikes.push(pair);
+        }
+    }
+    for pairs in [&mut resemblance.duplicates, &mut resemblance.lookalikes] {
+        pairs.sort_by(|p, q| q.similarity().total_cmp(&p.similarity()));
+    }
+    resemblance
+}
+
+pub struct ConfusabilityService {
+    db: Arc<NarraDb>,
+}
+
+impl ConfusabilityService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    async fn cast(&self) -> Result<Vec<CastMember>, NarraError> {
+        #[derive(Deserialize)]
+        struct Row {
+            id: RecordId,
+            name: String,
+            #[serde(default)]
+            aliases: Vec<String>,
+            embedding: Option<Vec<f32>>,
+        }
+
+        let mut response = self
+            .db
+            .query("SELECT id, name, aliases, embedding FROM character")
+            .await?;
+        let rows: Vec<Row> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|r| CastMember {
+                id: r.id.to_string(),
+                name: r.name,
+                aliases: r.aliases,
+                embedding: r.embedding,
+            })
+            .collect())
+    }
+
+    pub async fn report(
+        &self,
+        options: &ConfusabilityOptions,
+    ) -> Result<Vec<ConfusablePair>, NarraError> {
+        Ok(find_confusable(&self.cast().await?, options))
+    }
+
+    /// Existing characters that a character about to be created from `data`
+    /// may duplicate or be mixed up with. The profile is compared when the embedding model is
+    /// available, using the same composite text stored embeddings come from.
+    pub async fn similar_to_new(
+        &self,
+        data: &CharacterCreate,
+        embedding: &(dyn EmbeddingService + Send + Sync),
+        options: &ConfusabilityOptions,
+    ) -> Result<Resemblance, NarraError> {
+        let embedding = if embedding.is_available() {
+            let draft = Character {
+                id: RecordId::from(("character", "new")),
+                name: data.name.clone(),
+                aliases: data.aliases.clone(),
+                roles: data.roles.clone(),
+                profile: data.profile.clone(),
+                created_at: Datetime::default(),
+                updated_at: Datetime::default(),
+            };
+            match embedding
+                .embed_text(&character_composite(&draft, &[], &[]))
+                .await
+            {
+                Ok(vector) => Some(vector),
+                Err(e) => {
+                    tracing::debug!("Comparing names only: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let candidate = CastMember {
+            id: String::new(),
+            name: data.name.clone(),
+            aliases: data.aliases.clone(),
+            embedding,
+        };
+        Ok(similar_to(&candidate, &self.cast().await?, options))
+    }
+}
+
+/// One line per resembling character, e.g. "Bram is 92% similar to Bran
+/// (character:bran)".
+pub fn describe_similar(pairs: &[ConfusablePair]) -> Vec<String> {
+    pairs
+        .iter()
+        .map(|p| {
+            format!(
+                "{} is {:.0}% similar to {} ({})",
+                p.a_name,
+                p.similarity() * 100.0,
+                p.b_name,
+                p.b_id
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, aliases: &[&str], embedding: Option<Vec<f32>>) -> CastMember {
+        CastMember {
+            id: format!("character:{}", name.to_lowercase().replace(' ', "_")),
+            name: name.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            embedding,
+        }
+    }
+
+    #[test]
+    fn test_names_and_profiles_flag_pairs() {
+        let cast = vec![
+            member("Marian Holt", &[], Some(vec![1.0, 0.0])),
+            member("Marion Holt", &[], Some(vec![0.0, 1.0])),
+            member("Alice Morgan", &[], Some(vec![0.6, 0.8])),
+            member("Bob Morgan", &["Bobby"], Some(vec![0.61, 0.79])),
+            member("Gray", &["The Mayor"], None),
+        ];
+        let pairs = find_confusable(&cast, &ConfusabilityOptions::default());
+
+        let found: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|p| (p.a_name.as_str(), p.b_name.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Marian Holt", "Marion Holt"),
+                ("Alice Morgan", "Bob Morgan")
+            ]
+        );
+
+        let marian = &pairs[1];
+        let names = marian.name_collision.as_ref().unwrap();
+        assert_eq!((names.a.as_str(), names.b.as_str()), ("Marian", "Marion"));
+        assert!(names.same_soundex);
+        assert!(marian.profile_similarity.unwrap() < 0.1);
+
+        // Shared surname alone doesn't count; the profiles do
+        let morgans = &pairs[0];
+        assert!(morgans.name_collision.is_none());
+        assert!(morgans.profile_similarity.unwrap() > 0.99);
+    }
+
+    #[test]
+    fn test_similar_to_new_character() {
+        let cast = vec![
+            member("Bran", &[], Some(vec![1.0, 0.0])),
+            member("Robert", &[], Some(vec![0.0, 1.0])),
+            member("Mira Vale", &["The Gull"], Some(vec![0.71, 0.71])),
+        ];
+        let options = ConfusabilityOptions::default();
+
+        let bram = member("Bram", &[], None);
+        let found = similar_to(&bram, &cast, &options);
+        assert_eq!(found.duplicates.len(), 1);
+        assert_eq!(found.duplicates[0].b_name, "Bran");
+        assert_eq!(
+            describe_similar(&found.duplicates),
+            vec!["Bram is 75% similar to Bran (character:bran)"]
+        );
+
+        // Sounding alike or a near-identical profile is only a lookalike
+        let rupert = member("Rupert", &[], Some(vec![0.7, 0.72]));
+        let found = similar_to(&rupert, &cast, &options);
+        assert!(found.duplicates.is_empty());
+        let names: Vec<&str> = found.lookalikes.iter().map(|p| p.b_name.as_str()).collect();
+        assert_eq!(names, vec!["Mira Vale", "Robert"]);
+
+        // An alias matching the full name is a duplicate
+        let gull = member("Gull", &[], None);
+        let found = similar_to(&gull, &cast, &options);
+        assert_eq!(found.duplicates[0].b_name, "Mira Vale");
+    }
+
+    #[test]
+    fn test_distinct_full_names_are_not_duplicates() {
+        let options = ConfusabilityOptions::default();
+        for (new, existing) in [
+            ("Alice Jones", "Alice Smith"),
+            ("Character 2", "Character 1"),
+            ("Node B", "Node A"),
+            ("The Mayor", "The Gull"),
+        ] {
+            let found = similar_to(
+                &member(new, &[], None),
+                &[member(existing, &[], None)],
+                &options,
+            );
+            assert!(found.duplicates.is_empty(), "{} vs {}", new, existing);
+        }
+
+        // Titles don't make a name different either
+        let found = similar_to(
+            &member("Captain Marion Holt", &[], None),
+            &[member("Marian Holt", &[], None)],
+            &options,
+        );
+        assert_eq!(found.duplicates.len(), 1);
+    }
+}
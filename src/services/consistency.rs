@@ -7,17 +7,22 @@
 use crate::db::connection::NarraDb;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use surrealdb::RecordId;
 use tokio::time::{timeout, Duration};
 
 use crate::models::event::get_event;
 use crate::models::fact::{
     get_entity_facts, list_facts, EnforcementLevel, PovScope, TemporalScope, UniverseFact,
 };
-use crate::models::knowledge::get_character_knowledge_states;
+use crate::models::knowledge::{get_character_knowledge_states, LearningMethod};
+use crate::models::location::get_location;
 use crate::models::perception::{get_perception, get_perceptions_from};
-use crate::models::scene::{get_character_scenes, get_scene};
+use crate::models::scene::{
+    get_character_events, get_character_scenes, get_event_characters, get_scene,
+};
+use crate::services::reciprocity::{check_reciprocity, ReciprocityIssueKind};
 use crate::NarraError;
 
 // ============================================================================
@@ -39,6 +44,48 @@ pub enum ConsistencySeverity {
     Critical,
 }
 
+/// How strictly fact violations are enforced.
+///
+/// Set via `[consistency] strictness` in config or `NARRA_CONSISTENCY`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsistencyStrictness {
+    /// Warning-level fact violations also block mutations.
+    Strict,
+    /// Severity follows each fact's enforcement level.
+    #[default]
+    Standard,
+    /// Nothing blocks; critical violations are reported as warnings.
+    Lenient,
+}
+
+impl ConsistencyStrictness {
+    /// Adjust a fact-violation severity for this strictness level.
+    pub fn apply(self, severity: ConsistencySeverity) -> ConsistencySeverity {
+        match (self, severity) {
+            (Self::Strict, ConsistencySeverity::Warning) => ConsistencySeverity::Critical,
+            (Self::Lenient, ConsistencySeverity::Critical) => ConsistencySeverity::Warning,
+            (_, severity) => severity,
+        }
+    }
+}
+
+impl std::str::FromStr for ConsistencyStrictness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "standard" => Ok(Self::Standard),
+            "lenient" => Ok(Self::Lenient),
+            other => Err(format!(
+                "Unknown strictness '{}'. Expected: strict, standard, lenient",
+                other
+            )),
+        }
+    }
+}
+
 // ============================================================================
 // Violation Types
 // ============================================================================
@@ -265,14 +312,33 @@ pub trait ConsistencyService: Send + Sync {
 // ConsistencyChecker Implementation
 // ============================================================================
 
+/// Where and when the entity being checked sits, for fact scopes.
+#[derive(Debug, Default)]
+struct ScopeContext {
+    /// Event sequence the entity is at
+    sequence: Option<i64>,
+    /// Locations the entity is placed in, with every enclosing location
+    locations: HashSet<String>,
+}
+
 /// Consistency checker that validates entities against universe facts.
 pub struct ConsistencyChecker {
     db: Arc<NarraDb>,
+    strictness: ConsistencyStrictness,
 }
 
 impl ConsistencyChecker {
     pub fn new(db: Arc<NarraDb>) -> Self {
-        Self { db }
+        Self {
+            db,
+            strictness: ConsistencyStrictness::default(),
+        }
+    }
+
+    /// Override how strictly fact violations are enforced.
+    pub fn with_strictness(mut self, strictness: ConsistencyStrictness) -> Self {
+        self.strictness = strictness;
+        self
     }
 
     /// Get directly connected entity IDs via graph edges.
@@ -280,7 +346,8 @@ impl ConsistencyChecker {
     /// Queries SurrealDB for entities connected via:
     /// - perceives (character -> character)
     /// - knows (character -> knowledge)
-    /// - involved_in (character -> scene)
+    /// - involved_in (character -> event)
+    /// - participates_in (character -> scene)
     /// - has (location -> location hierarchy)
     /// - applies_to (fact -> entity)
     async fn get_connected_entities(&self, entity_id: &str) -> Result<Vec<String>, NarraError> {
@@ -310,9 +377,30 @@ impl ConsistencyChecker {
                     connected.push(t.to_string());
                 }
 
-                // Scenes character is involved in
+                // Events and scenes the character takes part in
+                let query = format!(
+                    "SELECT VALUE out FROM involved_in WHERE in = character:{0}; \
+                     SELECT VALUE out FROM participates_in WHERE in = character:{0}",
+                    entity_key
+                );
+                let mut result = self
+                    .db
+                    .query(&query)
+                    .await
+                    .map_err(|e| NarraError::Database(e.to_string()))?;
+                for index in 0..2 {
+                    let targets: Vec<surrealdb::sql::Thing> = result
+                        .take(index)
+                        .map_err(|e| NarraError::Database(e.to_string()))?;
+                    for t in targets {
+                        connected.push(t.to_string());
+                    }
+                }
+            }
+            "event" => {
+                // Characters who took part in the event (reverse involved_in)
                 let query = format!(
-                    "SELECT VALUE out FROM involved_in WHERE in = character:{}",
+                    "SELECT VALUE in FROM involved_in WHERE out = event:{}",
                     entity_key
                 );
                 let mut result = self
@@ -328,9 +416,9 @@ impl ConsistencyChecker {
                 }
             }
             "scene" => {
-                // Characters in scene (reverse involved_in)
+                // Characters in scene (reverse participates_in)
                 let query = format!(
-                    "SELECT VALUE in FROM involved_in WHERE out = scene:{}",
+                    "SELECT VALUE in FROM participates_in WHERE out = scene:{}",
                     entity_key
                 );
                 let mut result = self
@@ -369,6 +457,94 @@ impl ConsistencyChecker {
         Ok(connected)
     }
 
+    /// Where and when an entity sits, for scope checks.
+    ///
+    /// Events and scenes are at their own sequence; other entities at the
+    /// world's latest event. Locations are placed at themselves, scenes at
+    /// their locations, events and characters at the locations of their
+    /// scenes, each with every enclosing location.
+    async fn scope_context(
+        &self,
+        entity_id: &str,
+        entity_data: &serde_json::Value,
+    ) -> Result<ScopeContext, NarraError> {
+        let (table, key) = entity_id.split_once(':').unwrap_or(("", entity_id));
+        let record = RecordId::from((table, key));
+        let field = |name: &str| entity_data.get(name).and_then(|v| v.as_str());
+        let mut sequence = entity_data.get("sequence").and_then(|v| v.as_i64());
+        let mut places: Vec<String> = ["primary_location", "parent"]
+            .iter()
+            .filter_map(|name| field(name))
+            .chain(
+                entity_data
+                    .get("secondary_locations")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_str()),
+            )
+            .filter(|id| id.starts_with("location:"))
+            .map(str::to_string)
+            .collect();
+
+        match table {
+            "location" => places.push(entity_id.to_string()),
+            "event" if sequence.is_none() => {
+                sequence = get_event(&self.db, key).await?.map(|e| e.sequence);
+            }
+            "scene" => {
+                if let Some(event) = field("event") {
+                    sequence = self.resolve_event_sequence(event).await;
+                }
+                if let Some(scene) = get_scene(&self.db, key).await? {
+                    if sequence.is_none() {
+                        sequence = self.resolve_event_sequence(&scene.event.to_string()).await;
+                    }
+                    places.push(scene.primary_location.to_string());
+                    places.extend(scene.secondary_locations.iter().map(|l| l.to_string()));
+                }
+            }
+            _ => {}
+        }
+        let scene_places = match table {
+            "character" => {
+                "SELECT VALUE out.primary_location FROM participates_in WHERE in = $record"
+            }
+            "event" => "SELECT VALUE primary_location FROM scene WHERE event = $record",
+            _ => "",
+        };
+        if !scene_places.is_empty() {
+            let mut result = self.db.query(scene_places).bind(("record", record)).await?;
+            let found: Vec<Option<RecordId>> = result.take(0)?;
+            places.extend(found.into_iter().flatten().map(|l| l.to_string()));
+        }
+        if sequence.is_none() {
+            sequence = self.get_latest_event_sequence().await;
+        }
+
+        // Walk up the location hierarchy
+        let mut locations: HashSet<String> = HashSet::new();
+        while let Some(place) = places.pop() {
+            if !locations.insert(place.clone()) {
+                continue;
+            }
+            let Some((_, place_key)) = place.split_once(':') else {
+                continue;
+            };
+            if let Some(parent) = get_location(&self.db, place_key)
+                .await?
+                .and_then(|l| l.parent)
+            {
+                places.push(parent.to_string());
+            }
+        }
+
+        Ok(ScopeContext {
+            sequence,
+            locations,
+        })
+    }
+
     /// Get facts that apply to this entity (linked via applies_to).
     /// Falls back to all Strict facts if no specific links exist.
     async fn get_applicable_facts(&self, entity_id: &str) -> Result<Vec<UniverseFact>, NarraError> {
@@ -390,19 +566,25 @@ impl ConsistencyChecker {
     /// Evaluate a single fact against entity data.
     /// Returns Some(Violation) if fact is violated, None otherwise.
     ///
-    /// `entity_id` and `current_event_sequence` are used for scope filtering:
-    /// if the fact's scope excludes this entity or time context, the fact is skipped.
+    /// `entity_id` and `context` are used for scope filtering: if the fact's
+    /// scope excludes this entity, its place, or its time, or the entity is
+    /// one of the fact's exceptions, the fact is skipped.
     async fn evaluate_fact(
         &self,
         fact: &UniverseFact,
         entity_id: &str,
         entity_data: &serde_json::Value,
         is_intentional: bool,
-        current_event_sequence: Option<i64>,
+        context: &ScopeContext,
     ) -> Option<Violation> {
-        // Check scope — if fact doesn't apply to this entity/time, skip it
+        // Excepted entities are deliberately outside the fact
+        if fact.exception_for(entity_id).is_some() {
+            return None;
+        }
+
+        // Check scope — if fact doesn't apply to this entity/place/time, skip it
         if !self
-            .is_fact_in_scope(fact, entity_id, entity_data, current_event_sequence)
+            .is_fact_in_scope(fact, entity_id, entity_data, context)
             .await
         {
             return None;
@@ -420,8 +602,11 @@ impl ConsistencyChecker {
 
         if has_potential_violation {
             let confidence = self.calculate_confidence(&data_str, &fact_title_lower);
-            let severity =
-                map_enforcement_to_severity(fact.enforcement_level, confidence, is_intentional);
+            let severity = self.strictness.apply(map_enforcement_to_severity(
+                fact.enforcement_level,
+                confidence,
+                is_intentional,
+            ));
 
             Some(Violation {
                 fact_id: fact.id.to_string(),
@@ -439,14 +624,14 @@ impl ConsistencyChecker {
         }
     }
 
-    /// Check whether a fact's scope includes the given entity and time context.
+    /// Check whether a fact's scope includes the given entity, place and time.
     /// Returns `true` if the fact should be evaluated against this entity.
     async fn is_fact_in_scope(
         &self,
         fact: &UniverseFact,
         entity_id: &str,
         entity_data: &serde_json::Value,
-        current_event_sequence: Option<i64>,
+        context: &ScopeContext,
     ) -> bool {
         let scope = match &fact.scope {
             Some(s) => s,
@@ -462,10 +647,14 @@ impl ConsistencyChecker {
 
         // Check temporal scope
         if let Some(ref temporal) = scope.temporal {
-            if !self
-                .check_temporal_scope(temporal, current_event_sequence)
-                .await
-            {
+            if !self.check_temporal_scope(temporal, context.sequence).await {
+                return false;
+            }
+        }
+
+        // Check location scope: unplaced entities are outside every subtree
+        if let Some(ref location) = scope.location {
+            if !context.locations.contains(location) {
                 return false;
             }
         }
@@ -515,7 +704,7 @@ impl ConsistencyChecker {
             None => return true, // No event context = can't exclude by time
         };
 
-        // Check valid_from_event
+        // Check valid_from_event / from_sequence
         if let Some(ref from_event_id) = temporal.valid_from_event {
             if let Some(from_seq) = self.resolve_event_sequence(from_event_id).await {
                 if current_seq < from_seq {
@@ -523,8 +712,14 @@ impl ConsistencyChecker {
                 }
             }
         }
+        if temporal
+            .from_sequence
+            .is_some_and(|from| current_seq < from)
+        {
+            return false;
+        }
 
-        // Check valid_until_event
+        // Check valid_until_event / until_sequence
         if let Some(ref until_event_id) = temporal.valid_until_event {
             if let Some(until_seq) = self.resolve_event_sequence(until_event_id).await {
                 if current_seq > until_seq {
@@ -532,6 +727,12 @@ impl ConsistencyChecker {
                 }
             }
         }
+        if temporal
+            .until_sequence
+            .is_some_and(|until| current_seq > until)
+        {
+            return false;
+        }
 
         true
     }
@@ -649,7 +850,9 @@ impl ConsistencyChecker {
     /// Check for timeline violations in character knowledge.
     ///
     /// Detects when a character knows something at a scene that occurs
-    /// before the event where they learned it.
+    /// before the event where they learned it, and when they witnessed or
+    /// overheard something at an event with recorded participants without
+    /// being one of them or in any of its scenes.
     ///
     /// Per CONTEXT.md:
     /// - Strict ordering enforced (can't know X before learning X)
@@ -667,6 +870,59 @@ impl ConsistencyChecker {
         // Get all scene participations for this character
         let participations = get_character_scenes(&self.db, character_id).await?;
 
+        // Events the character was at: taken part in directly or through a scene
+        let mut present_at: HashSet<String> = get_character_events(&self.db, character_id)
+            .await?
+            .into_iter()
+            .map(|i| i.event.to_string())
+            .collect();
+        for participation in &participations {
+            if let Some(scene) = get_scene(&self.db, &participation.scene.key().to_string()).await?
+            {
+                present_at.insert(scene.event.to_string());
+            }
+        }
+
+        for knowledge_state in &knowledge_states {
+            let Some(event_id) = &knowledge_state.event else {
+                continue;
+            };
+            if !matches!(
+                knowledge_state.learning_method,
+                LearningMethod::Witnessed | LearningMethod::Overheard
+            ) || present_at.contains(&event_id.to_string())
+            {
+                continue;
+            }
+            let event_key = event_id.key().to_string();
+            // Only events whose participants are recorded can rule presence out
+            if get_event_characters(&self.db, &event_key).await?.is_empty() {
+                continue;
+            }
+            let event_title = get_event(&self.db, &event_key)
+                .await?
+                .map(|e| e.title)
+                .unwrap_or(event_key);
+            let target_key = knowledge_state.target.key().to_string();
+            violations.push(Violation {
+                fact_id: target_key.clone(),
+                fact_title: "Timeline: Witnessed without being present".to_string(),
+                severity: ConsistencySeverity::Warning,
+                message: format!(
+                    "Character {} '{}' at event '{}' but is not among its participants or in any of its scenes",
+                    if knowledge_state.learning_method == LearningMethod::Witnessed {
+                        "witnessed"
+                    } else {
+                        "overheard"
+                    },
+                    target_key,
+                    event_title
+                ),
+                confidence: 0.7,
+                auto_detected_as_intentional: false,
+            });
+        }
+
         // For each knowledge state with a learning event
         for knowledge_state in &knowledge_states {
             if let Some(learning_event_id) = &knowledge_state.event {
@@ -787,6 +1043,8 @@ impl ConsistencyChecker {
     /// - Impossible states: A is B's parent AND B is A's parent (CRITICAL)
     /// - Family/professional asymmetry: likely unintentional (WARNING)
     /// - Romantic/rival asymmetry: often intentional drama (INFO)
+    /// - Relationships whose reciprocal edge contradicts them (WARNING) or is
+    ///   missing (INFO), unless marked intentional (see `services::reciprocity`)
     ///
     /// Per CONTEXT.md:
     /// - One-way relationships are NOT violations (unrequited love valid)
@@ -902,6 +1160,30 @@ impl ConsistencyChecker {
             // If no reverse perception exists, that's valid (one-way relationships allowed)
         }
 
+        let character = RecordId::from(("character", character_id));
+        for issue in check_reciprocity(&self.db, Some(&character)).await? {
+            let (fact_title, severity, confidence) = match issue.kind {
+                ReciprocityIssueKind::Contradictory => (
+                    "Contradictory relationship",
+                    ConsistencySeverity::Warning,
+                    0.8,
+                ),
+                ReciprocityIssueKind::Missing => (
+                    "Missing reciprocal relationship",
+                    ConsistencySeverity::Info,
+                    0.9,
+                ),
+            };
+            violations.push(Violation {
+                fact_id: issue.relationship_id.clone(),
+                fact_title: fact_title.to_string(),
+                severity,
+                message: issue.message(),
+                confidence,
+                auto_detected_as_intentional: false,
+            });
+        }
+
         Ok(violations)
     }
 
@@ -914,26 +1196,30 @@ impl ConsistencyChecker {
         let mut result = ValidationResult::new();
 
         // Get applicable facts (linked facts or global Strict facts)
-        let facts = self.get_applicable_facts(entity_id).await?;
+        let context = self.scope_context(entity_id, entity_data).await?;
+        let mut facts = self.get_applicable_facts(entity_id).await?;
+
+        // Location-scoped facts hold throughout their subtree without links
+        for fact in list_facts(&self.db).await? {
+            let in_subtree = fact
+                .scope
+                .as_ref()
+                .and_then(|s| s.location.as_ref())
+                .is_some_and(|l| context.locations.contains(l));
+            if in_subtree && !facts.iter().any(|f| f.id == fact.id) {
+                facts.push(fact);
+            }
+        }
 
         // Check for intentional contradiction pattern
         let is_intentional = self
             .is_intentional_contradiction(entity_id, entity_data)
             .await;
 
-        // Pre-resolve current event sequence for temporal scope checks
-        let current_event_sequence = self.get_latest_event_sequence().await;
-
         // Evaluate each fact against entity data
         for fact in facts {
             if let Some(violation) = self
-                .evaluate_fact(
-                    &fact,
-                    entity_id,
-                    entity_data,
-                    is_intentional,
-                    current_event_sequence,
-                )
+                .evaluate_fact(&fact, entity_id, entity_data, is_intentional, &context)
                 .await
             {
                 result.add_violation(violation);
@@ -1117,6 +1403,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_strictness_adjusts_severity() {
+        use ConsistencySeverity::*;
+        assert_eq!(ConsistencyStrictness::Strict.apply(Warning), Critical);
+        assert_eq!(ConsistencyStrictness::Strict.apply(Info), Info);
+        assert_eq!(ConsistencyStrictness::Standard.apply(Warning), Warning);
+        assert_eq!(ConsistencyStrictness::Lenient.apply(Critical), Warning);
+        assert_eq!(
+            "LENIENT".parse::<ConsistencyStrictness>(),
+            Ok(ConsistencyStrictness::Lenient)
+        );
+    }
+
     // -- ValidationResult tests --
 
     #[test]
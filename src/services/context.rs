@@ -6,7 +6,8 @@ use std::sync::Arc;
 
 use crate::repository::{RelationshipRepository, SurrealRelationshipRepository};
 use crate::services::summary::{CachedSummaryService, DetailLevel, SummaryService};
-use crate::session::SessionStateManager;
+use crate::services::token_counter::{HeuristicTokenCounter, TokenCounter};
+use crate::session::{PinPriority, SessionStateManager};
 use crate::NarraError;
 
 pub use crate::services::summary::EntityFullContent;
@@ -124,6 +125,8 @@ pub struct CachedContextService {
     summary_service: Arc<CachedSummaryService>,
     /// Session state manager (single source of truth for pinned/recent)
     session_manager: Arc<SessionStateManager>,
+    /// Token counter used when packing entities into the budget
+    token_counter: Arc<dyn TokenCounter>,
 }
 
 impl CachedContextService {
@@ -134,9 +137,16 @@ impl CachedContextService {
             relationship_repo: Arc::new(SurrealRelationshipRepository::new(db.clone())),
             summary_service: Arc::new(CachedSummaryService::with_defaults(db)),
             session_manager,
+            token_counter: Arc::new(HeuristicTokenCounter),
         }
     }
 
+    /// Use a specific token counter for budget packing (default: ~4 chars/token).
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.token_counter = counter;
+        self
+    }
+
     /// Calculate relevance score for an entity.
     fn calculate_score(
         &self,
@@ -144,7 +154,7 @@ impl CachedContextService {
         mentioned: &[String],
         recent: &[String],
         graph_distances: &HashMap<String, usize>,
-        pinned: &std::collections::HashSet<String>,
+        pinned: &HashMap<String, PinPriority>,
     ) -> (f32, ScoreBreakdown) {
         calculate_score(entity_id, mentioned, recent, graph_distances, pinned)
     }
@@ -159,12 +169,13 @@ impl ContextService for CachedContextService {
     ) -> Result<ContextResponse, NarraError> {
         use crate::models::note::get_entity_notes;
 
-        let recent = self.session_manager.get_recent(100).await;
-        let pinned: std::collections::HashSet<String> = self
+        let recent = self.session_manager.get_hot(100).await;
+        let pinned: HashMap<String, PinPriority> = self
             .session_manager
-            .get_pinned()
+            .get_pins()
             .await
             .into_iter()
+            .map(|p| (p.entity_id, p.priority))
             .collect();
 
         // Build graph distances for mentioned entities
@@ -191,7 +202,7 @@ impl ContextService for CachedContextService {
         candidates.extend(mentioned_entities.iter().cloned());
         candidates.extend(graph_distances.keys().cloned());
         candidates.extend(recent.iter().cloned());
-        candidates.extend(pinned.iter().cloned());
+        candidates.extend(pinned.keys().cloned());
         candidates.extend(config.pinned_entities.iter().cloned());
 
         // Score and sort candidates
@@ -249,8 +260,7 @@ impl ContextService for CachedContextService {
                     let note_score = parent_score * note_score_multiplier;
                     let content = format!("{}\n\n(Attached to {})", note.body, entity_id);
 
-                    // Token estimation: ~4 chars per token, notes typically moderate size
-                    let estimated_tokens = content.len().div_ceil(4);
+                    let estimated_tokens = self.token_counter.count(&content);
 
                     scored.push(ScoredEntity {
                         id: note_id,
@@ -283,11 +293,10 @@ impl ContextService for CachedContextService {
         let mut final_entities: Vec<ScoredEntity> = Vec::new();
 
         for entity in scored {
-            // Use actual token estimate from summary
             let entity_tokens = entity
                 .content
                 .as_ref()
-                .map(|c| c.len().div_ceil(4))
+                .map(|c| self.token_counter.count(c))
                 .unwrap_or(100);
 
             if total_tokens + entity_tokens > config.token_budget && !final_entities.is_empty() {
@@ -322,7 +331,7 @@ impl ContextService for CachedContextService {
     }
 
     async fn get_hot_entities(&self, limit: usize) -> Vec<String> {
-        self.session_manager.get_recent(limit).await
+        self.session_manager.get_hot(limit).await
     }
 
     async fn get_entity_full_detail(
@@ -339,7 +348,7 @@ fn calculate_score(
     mentioned: &[String],
     recent: &[String],
     graph_distances: &HashMap<String, usize>,
-    pinned: &std::collections::HashSet<String>,
+    pinned: &HashMap<String, PinPriority>,
 ) -> (f32, ScoreBreakdown) {
     let mut breakdown = ScoreBreakdown::default();
 
@@ -360,9 +369,13 @@ fn calculate_score(
         }
     }
 
-    // Pin score (2.0 if pinned)
-    if pinned.contains(entity_id) {
-        breakdown.pin_score = 2.0;
+    // Pin score by priority (low 1.0, normal 2.0, high 4.0)
+    if let Some(priority) = pinned.get(entity_id) {
+        breakdown.pin_score = match priority {
+            PinPriority::Low => 1.0,
+            PinPriority::Normal => 2.0,
+            PinPriority::High => 4.0,
+        };
     }
 
     let total = breakdown.mention_score
@@ -376,14 +389,13 @@ fn calculate_score(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashSet;
 
     fn empty_distances() -> HashMap<String, usize> {
         HashMap::new()
     }
 
-    fn empty_pinned() -> HashSet<String> {
-        HashSet::new()
+    fn empty_pinned() -> HashMap<String, PinPriority> {
+        HashMap::new()
     }
 
     #[test]
@@ -446,21 +458,32 @@ mod tests {
 
     #[test]
     fn test_pinned_entity_gets_2_points() {
-        let mut pinned = HashSet::new();
-        pinned.insert("character:alice".to_string());
+        let mut pinned = HashMap::new();
+        pinned.insert("character:alice".to_string(), PinPriority::Normal);
         let (_, breakdown) =
             calculate_score("character:alice", &[], &[], &empty_distances(), &pinned);
         assert!((breakdown.pin_score - 2.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_pin_score_follows_priority() {
+        let mut pinned = HashMap::new();
+        pinned.insert("character:alice".to_string(), PinPriority::High);
+        pinned.insert("character:bob".to_string(), PinPriority::Low);
+        let (high, _) = calculate_score("character:alice", &[], &[], &empty_distances(), &pinned);
+        let (low, _) = calculate_score("character:bob", &[], &[], &empty_distances(), &pinned);
+        assert!((high - 4.0).abs() < 0.01);
+        assert!((low - 1.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_combined_scores_add_correctly() {
         let mentioned = vec!["character:alice".to_string()];
         let recent = vec!["character:alice".to_string()];
         let mut distances = HashMap::new();
         distances.insert("character:alice".to_string(), 1);
-        let mut pinned = HashSet::new();
-        pinned.insert("character:alice".to_string());
+        let mut pinned = HashMap::new();
+        pinned.insert("character:alice".to_string(), PinPriority::Normal);
 
         let (total, breakdown) =
             calculate_score("character:alice", &mentioned, &recent, &distances, &pinned);
@@ -0,0 +1,507 @@
+//! Derived-data sidecar for YAML exports.
+//!
+//! The YAML export only carries authored content. `--include-derived` writes
+//! a gzipped sidecar next to it holding what is expensive to recompute:
+//! embeddings (including character facets and edge embeddings), arc snapshots,
+//! and fresh ML annotations. Vectors are stored as base64 little-endian `f32`.
+//!
+//! Knowledge records and relationship/perception edges get new IDs on import,
+//! so the sidecar refers to them by natural key ([`DerivedRef`]) and resolves
+//! those keys against the importing world.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use base64::{engine::general_purpose, Engine as _};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::mcp::types::DerivedStats;
+use crate::models::annotation::{upsert_annotation, AnnotationCreate};
+use crate::NarraError;
+
+/// Bumped when the sidecar layout changes incompatibly.
+const SIDECAR_VERSION: u32 = 1;
+
+/// (table, embedding field, composite text field, stale flag)
+const EMBEDDING_FIELDS: &[(&str, &str, &str, &str)] = &[
+    (
+        "character",
+        "embedding",
+        "composite_text",
+        "embedding_stale",
+    ),
+    (
+        "character",
+        "identity_embedding",
+        "identity_composite",
+        "identity_stale",
+    ),
+    (
+        "character",
+        "psychology_embedding",
+        "psychology_composite",
+        "psychology_stale",
+    ),
+    (
+        "character",
+        "social_embedding",
+        "social_composite",
+        "social_stale",
+    ),
+    (
+        "character",
+        "narrative_embedding",
+        "narrative_composite",
+        "narrative_stale",
+    ),
+    ("location", "embedding", "composite_text", "embedding_stale"),
+    ("event", "embedding", "composite_text", "embedding_stale"),
+    ("scene", "embedding", "composite_text", "embedding_stale"),
+    (
+        "knowledge",
+        "embedding",
+        "composite_text",
+        "embedding_stale",
+    ),
+    (
+        "relates_to",
+        "embedding",
+        "composite_text",
+        "embedding_stale",
+    ),
+    (
+        "perceives",
+        "embedding",
+        "composite_text",
+        "embedding_stale",
+    ),
+];
+
+/// Sidecar path for a YAML export (`world.yaml` -> `world.derived.gz`).
+pub fn sidecar_path(export_path: &Path) -> PathBuf {
+    export_path.with_extension("derived.gz")
+}
+
+/// A record reference that survives a YAML round trip.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DerivedRef {
+    /// Characters, locations, events, scenes: IDs are preserved by export.
+    Record {
+        id: String,
+    },
+    Knowledge {
+        character: String,
+        fact: String,
+    },
+    Relationship {
+        from: String,
+        to: String,
+        rel_type: String,
+    },
+    Perception {
+        from: String,
+        to: String,
+    },
+}
+
+impl DerivedRef {
+    /// Entity IDs this reference depends on.
+    pub fn entity_ids(&self) -> Vec<&str> {
+        match self {
+            DerivedRef::Record { id } => vec![id],
+            DerivedRef::Knowledge { character, .. } => vec![character],
+            DerivedRef::Relationship { from, to, .. } | DerivedRef::Perception { from, to } => {
+                vec![from, to]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedEmbedding {
+    pub target: DerivedRef,
+    pub field: String,
+    #[serde(with = "vector_b64")]
+    pub vector: Vec<f32>,
+    pub composite: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedArcSnapshot {
+    pub target: DerivedRef,
+    pub entity_type: String,
+    pub facet: Option<String>,
+    #[serde(with = "vector_b64")]
+    pub embedding: Vec<f32>,
+    pub delta_magnitude: Option<f32>,
+    pub event_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedAnnotation {
+    pub target: DerivedRef,
+    pub model_type: String,
+    pub model_version: String,
+    pub output: serde_json::Value,
+}
+
+/// Everything written to the sidecar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedData {
+    pub version: u32,
+    /// Embedding model that produced the vectors
+    pub embedding_model: String,
+    pub embeddings: Vec<DerivedEmbedding>,
+    /// Oldest first, so re-inserting preserves each arc's order
+    pub arc_snapshots: Vec<DerivedArcSnapshot>,
+    pub annotations: Vec<DerivedAnnotation>,
+}
+
+impl DerivedData {
+    /// Keep only items whose entities all pass `keep` (for scoped exports).
+    pub fn retain_entities(&mut self, keep: impl Fn(&str) -> bool) {
+        let in_scope = |target: &DerivedRef| target.entity_ids().into_iter().all(&keep);
+        self.embeddings.retain(|e| in_scope(&e.target));
+        self.arc_snapshots.retain(|a| in_scope(&a.target));
+        self.annotations.retain(|a| in_scope(&a.target));
+    }
+}
+
+mod vector_b64 {
+    use super::*;
+
+    pub fn serialize<S: serde::Serializer>(v: &[f32], s: S) -> Result<S::Ok, S::Error> {
+        let bytes: Vec<u8> = v.iter().flat_map(|f| f.to_le_bytes()).collect();
+        s.serialize_str(&general_purpose::STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Vec<f32>, D::Error> {
+        let encoded = String::deserialize(d)?;
+        let bytes = general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(serde::de::Error::custom)?;
+        if bytes.len() % 4 != 0 {
+            return Err(serde::de::Error::custom(
+                "vector length is not a multiple of 4",
+            ));
+        }
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyRow {
+    id: String,
+    a: String,
+    b: Option<String>,
+    c: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingRow {
+    id: String,
+    vector: Vec<f32>,
+    composite: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArcRow {
+    entity_id: String,
+    entity_type: String,
+    facet: Option<String>,
+    embedding: Vec<f32>,
+    delta_magnitude: Option<f32>,
+    event_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnnotationRow {
+    entity_id: String,
+    model_type: String,
+    model_version: String,
+    output: serde_json::Value,
+}
+
+pub struct DerivedDataService {
+    db: Arc<NarraDb>,
+}
+
+impl DerivedDataService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Natural keys for every record whose ID does not survive export.
+    async fn natural_keys(&self) -> Result<Vec<(String, DerivedRef)>, NarraError> {
+        let mut resp = self
+            .db
+            .query(
+                "SELECT type::string(id) AS id, type::string(character) AS a, fact AS b FROM knowledge; \
+                 SELECT type::string(id) AS id, type::string(in) AS a, type::string(out) AS b, rel_type AS c FROM relates_to; \
+                 SELECT type::string(id) AS id, type::string(in) AS a, type::string(out) AS b FROM perceives",
+            )
+            .await?;
+        let knowledge: Vec<KeyRow> = resp.take(0)?;
+        let relationships: Vec<KeyRow> = resp.take(1)?;
+        let perceptions: Vec<KeyRow> = resp.take(2)?;
+
+        let mut keys = Vec::new();
+        keys.extend(knowledge.into_iter().map(|r| {
+            let key = DerivedRef::Knowledge {
+                character: r.a,
+                fact: r.b.unwrap_or_default(),
+            };
+            (r.id, key)
+        }));
+        keys.extend(relationships.into_iter().map(|r| {
+            let key = DerivedRef::Relationship {
+                from: r.a,
+                to: r.b.unwrap_or_default(),
+                rel_type: r.c.unwrap_or_default(),
+            };
+            (r.id, key)
+        }));
+        keys.extend(perceptions.into_iter().map(|r| {
+            let key = DerivedRef::Perception {
+                from: r.a,
+                to: r.b.unwrap_or_default(),
+            };
+            (r.id, key)
+        }));
+        Ok(keys)
+    }
+
+    /// Collect derived data for the whole world.
+    pub async fn export(&self, embedding_model: &str) -> Result<DerivedData, NarraError> {
+        let keys: HashMap<String, DerivedRef> = self.natural_keys().await?.into_iter().collect();
+        let to_ref = |id: String| keys.get(&id).cloned().unwrap_or(DerivedRef::Record { id });
+
+        let mut embeddings = Vec::new();
+        for (table, field, composite, _) in EMBEDDING_FIELDS {
+            let rows: Vec<EmbeddingRow> = self
+                .db
+                .query(format!(
+                    "SELECT type::string(id) AS id, {field} AS vector, {composite} AS composite \
+                     FROM {table} WHERE {field} IS NOT NONE"
+                ))
+                .await?
+                .take(0)?;
+            embeddings.extend(rows.into_iter().map(|r| DerivedEmbedding {
+                target: to_ref(r.id),
+                field: field.to_string(),
+                vector: r.vector,
+                composite: r.composite,
+            }));
+        }
+
+        let arcs: Vec<ArcRow> = self
+            .db
+            .query(
+                "SELECT type::string(entity_id) AS entity_id, entity_type, facet, embedding, \
+                 delta_magnitude, IF event_id THEN type::string(event_id) END AS event_id, created_at \
+                 FROM arc_snapshot ORDER BY created_at ASC",
+            )
+            .await?
+            .take(0)?;
+        let arc_snapshots = arcs
+            .into_iter()
+            .map(|r| DerivedArcSnapshot {
+                target: to_ref(r.entity_id),
+                entity_type: r.entity_type,
+                facet: r.facet,
+                embedding: r.embedding,
+                delta_magnitude: r.delta_magnitude,
+                event_id: r.event_id,
+            })
+            .collect();
+
+        let annotation_rows: Vec<AnnotationRow> = self
+            .db
+            .query(
+                "SELECT entity_id, model_type, model_version, output FROM annotation WHERE stale = false",
+            )
+            .await?
+            .take(0)?;
+        let annotations = annotation_rows
+            .into_iter()
+            .map(|r| DerivedAnnotation {
+                target: to_ref(r.entity_id),
+                model_type: r.model_type,
+                model_version: r.model_version,
+                output: r.output,
+            })
+            .collect();
+
+        Ok(DerivedData {
+            version: SIDECAR_VERSION,
+            embedding_model: embedding_model.to_string(),
+            embeddings,
+            arc_snapshots,
+            annotations,
+        })
+    }
+
+    /// Restore derived data after a YAML import.
+    ///
+    /// Vectors from a different embedding model are skipped (the world needs a
+    /// backfill anyway); annotations are model-independent and always restored.
+    pub async fn import(
+        &self,
+        data: DerivedData,
+        embedding_model: &str,
+    ) -> Result<DerivedStats, NarraError> {
+        if data.version != SIDECAR_VERSION {
+            return Err(NarraError::Validation(format!(
+                "Unsupported derived-data sidecar version {} (expected {})",
+                data.version, SIDECAR_VERSION
+            )));
+        }
+
+        let keys: HashMap<DerivedRef, String> = self
+            .natural_keys()
+            .await?
+            .into_iter()
+            .map(|(id, key)| (key, id))
+            .collect();
+        let resolve = |target: &DerivedRef| -> Option<RecordId> {
+            let id = match target {
+                DerivedRef::Record { id } => id.clone(),
+                other => keys.get(other)?.clone(),
+            };
+            id.parse().ok()
+        };
+
+        let mut stats = DerivedStats::default();
+        let vectors_usable = data.embedding_model == embedding_model;
+        if !vectors_usable {
+            stats.model_mismatch = Some(data.embedding_model.clone());
+            stats.skipped += data.embeddings.len() + data.arc_snapshots.len();
+        }
+
+        if vectors_usable {
+            for embedding in data.embeddings {
+                let Some((_, field, composite, stale)) = EMBEDDING_FIELDS
+                    .iter()
+                    .find(|(_, f, _, _)| *f == embedding.field)
+                else {
+                    stats.skipped += 1;
+                    continue;
+                };
+                let Some(record) = resolve(&embedding.target) else {
+                    stats.unmatched += 1;
+                    continue;
+                };
+                self.db
+                    .query(format!(
+                        "UPDATE ONLY $ref SET {field} = $vector, {composite} = $composite, {stale} = false"
+                    ))
+                    .bind(("ref", record))
+                    .bind(("vector", embedding.vector))
+                    .bind(("composite", embedding.composite))
+                    .await?
+                    .check()?;
+                stats.embeddings += 1;
+            }
+
+            for snapshot in data.arc_snapshots {
+                let Some(record) = resolve(&snapshot.target) else {
+                    stats.unmatched += 1;
+                    continue;
+                };
+                let event: Option<RecordId> = snapshot.event_id.and_then(|e| e.parse().ok());
+                self.db
+                    .query(
+                        "CREATE arc_snapshot SET entity_id = $eid, entity_type = $entity_type, \
+                         facet = $facet, embedding = $embedding, delta_magnitude = $delta, event_id = $event",
+                    )
+                    .bind(("eid", record))
+                    .bind(("entity_type", snapshot.entity_type))
+                    .bind(("facet", snapshot.facet))
+                    .bind(("embedding", snapshot.embedding))
+                    .bind(("delta", snapshot.delta_magnitude))
+                    .bind(("event", event))
+                    .await?
+                    .check()?;
+                stats.arc_snapshots += 1;
+            }
+        }
+
+        for annotation in data.annotations {
+            let Some(record) = resolve(&annotation.target) else {
+                stats.unmatched += 1;
+                continue;
+            };
+            upsert_annotation(
+                &self.db,
+                AnnotationCreate {
+                    entity_id: record.to_string(),
+                    model_type: annotation.model_type,
+                    model_version: annotation.model_version,
+                    output: annotation.output,
+                },
+            )
+            .await?;
+            stats.annotations += 1;
+        }
+
+        Ok(stats)
+    }
+}
+
+/// Write `data` as a gzipped sidecar.
+pub fn write_sidecar(path: &Path, data: &DerivedData) -> Result<(), NarraError> {
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&serde_json::to_vec(data)?)?;
+    std::fs::write(path, encoder.finish()?)?;
+    Ok(())
+}
+
+pub fn read_sidecar(path: &Path) -> Result<DerivedData, NarraError> {
+    let compressed = std::fs::read(path)?;
+    let mut json = Vec::new();
+    GzDecoder::new(&compressed[..]).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_roundtrip_preserves_vectors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = sidecar_path(&dir.path().join("world.yaml"));
+        assert!(path.ends_with("world.derived.gz"));
+
+        let data = DerivedData {
+            version: SIDECAR_VERSION,
+            embedding_model: "bge-small-en-v1.5".to_string(),
+            embeddings: vec![DerivedEmbedding {
+                target: DerivedRef::Knowledge {
+                    character: "character:alice".to_string(),
+                    fact: "The vault code is 1234".to_string(),
+                },
+                field: "embedding".to_string(),
+                vector: vec![0.25, -1.5, f32::MIN_POSITIVE],
+                composite: Some("Alice knows the vault code".to_string()),
+            }],
+            arc_snapshots: vec![],
+            annotations: vec![],
+        };
+        write_sidecar(&path, &data).unwrap();
+
+        let read = read_sidecar(&path).unwrap();
+        assert_eq!(read.embeddings[0].vector, data.embeddings[0].vector);
+        assert_eq!(read.embeddings[0].target, data.embeddings[0].target);
+    }
+}
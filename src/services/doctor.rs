@@ -0,0 +1,258 @@
+//! World diagnostics for `narra doctor`.
+//!
+//! Each check reports ok, warn or fail along with the command that fixes it.
+//! The checks here need only the database; the CLI handler adds the
+//! environment (disk, model cache) and measures cold-start latency.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::connection::NarraDb;
+use crate::services::vector_index::{self, VectorIndexKind, VectorIndexSettings};
+use crate::services::EntityType;
+use crate::NarraError;
+
+/// Outcome of one check, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl std::fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "ok",
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+        })
+    }
+}
+
+/// One diagnostic result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to run or change when the check isn't ok
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl DoctorCheck {
+    pub fn ok(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    pub fn warn(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn fail(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Relation tables whose endpoints must exist.
+pub(crate) const EDGE_TABLES: &[&str] = &[
+    "relates_to",
+    "perceives",
+    "knows",
+    "participates_in",
+    "involved_in",
+    "applies_to",
+    "note_attachment",
+    "belongs_to_phase",
+    "requires",
+    "foreshadows",
+];
+
+/// Indexes the schema defines that queries depend on. Missing ones mean the
+/// schema was only partly applied.
+const REQUIRED_INDEXES: &[(&str, &str)] = &[
+    ("character", "idx_character_name"),
+    ("location", "idx_location_name"),
+    ("event", "idx_event_sequence"),
+    ("scene", "idx_scene_event"),
+    ("knows", "idx_knows_out"),
+    ("note", "idx_note_search_body"),
+    ("universe_fact", "idx_fact_search_description"),
+];
+
+#[derive(Deserialize)]
+struct CountResult {
+    count: usize,
+}
+
+fn first_count(rows: Vec<CountResult>) -> usize {
+    rows.first().map_or(0, |r| r.count)
+}
+
+/// Edges whose `in` or `out` record no longer exists.
+pub async fn check_integrity(db: &NarraDb) -> Result<DoctorCheck, NarraError> {
+    let sql: String = EDGE_TABLES
+        .iter()
+        .map(|t| {
+            format!(
+                "SELECT count() AS count FROM {} WHERE in.id IS NONE OR out.id IS NONE GROUP ALL;\n",
+                t
+            )
+        })
+        .collect();
+    let mut response = db.query(sql).await?;
+
+    let mut dangling = Vec::new();
+    for (i, table) in EDGE_TABLES.iter().enumerate() {
+        let count = first_count(response.take(i)?);
+        if count > 0 {
+            dangling.push(format!("{} {}", count, table));
+        }
+    }
+
+    Ok(if dangling.is_empty() {
+        DoctorCheck::ok(
+            "integrity",
+            format!("{} edge tables, no dangling edges", EDGE_TABLES.len()),
+        )
+    } else {
+        DoctorCheck::fail(
+            "integrity",
+            format!("Edges pointing at deleted records: {}", dangling.join(", ")),
+            "Restore a backup from before the damage (narra backup list), or delete the edges \
+             WHERE in.id IS NONE OR out.id IS NONE",
+        )
+    })
+}
+
+/// Share of embeddable records with a current embedding.
+pub async fn check_embedding_coverage(db: &NarraDb) -> Result<DoctorCheck, NarraError> {
+    let tables: Vec<&str> = EntityType::embeddable()
+        .iter()
+        .map(|t| t.table_name())
+        .chain(["relates_to", "perceives"])
+        .collect();
+    let sql: String = tables
+        .iter()
+        .map(|t| {
+            format!(
+                "SELECT count() AS count FROM {t} GROUP ALL;\n\
+                 SELECT count() AS count FROM {t} WHERE embedding IS NOT NONE GROUP ALL;\n\
+                 SELECT count() AS count FROM {t} WHERE embedding_stale = true GROUP ALL;\n",
+                t = t
+            )
+        })
+        .collect();
+    let mut response = db.query(sql).await?;
+
+    let (mut total, mut embedded, mut stale) = (0, 0, 0);
+    for i in 0..tables.len() {
+        total += first_count(response.take(i * 3)?);
+        embedded += first_count(response.take(i * 3 + 1)?);
+        stale += first_count(response.take(i * 3 + 2)?);
+    }
+
+    if total == 0 {
+        return Ok(DoctorCheck::ok("embeddings", "No entities yet"));
+    }
+    let detail = format!(
+        "{}/{} embedded ({:.0}%), {} stale",
+        embedded,
+        total,
+        embedded as f64 / total as f64 * 100.0,
+        stale
+    );
+    Ok(if embedded < total || stale > 0 {
+        DoctorCheck::warn("embeddings", detail, "narra world backfill")
+    } else {
+        DoctorCheck::ok("embeddings", detail)
+    })
+}
+
+/// Schema indexes, and whether the configured vector index is built for
+/// the current embedding dimensions.
+pub async fn check_indexes(
+    db: &NarraDb,
+    settings: &VectorIndexSettings,
+    dimensions: usize,
+) -> Result<Vec<DoctorCheck>, NarraError> {
+    let sql: String = REQUIRED_INDEXES
+        .iter()
+        .map(|(table, _)| format!("INFO FOR TABLE {};\n", table))
+        .collect();
+    let mut response = db.query(sql).await?;
+
+    let mut missing = Vec::new();
+    for (i, (table, index)) in REQUIRED_INDEXES.iter().enumerate() {
+        let info: surrealdb::Value = response.take(i)?;
+        let present = info
+            .into_inner()
+            .into_json()
+            .get("indexes")
+            .and_then(|v| v.get(*index))
+            .is_some();
+        if !present {
+            missing.push(format!("{}.{}", table, index));
+        }
+    }
+    let schema = if missing.is_empty() {
+        DoctorCheck::ok(
+            "schema indexes",
+            format!("{} present", REQUIRED_INDEXES.len()),
+        )
+    } else {
+        DoctorCheck::fail(
+            "schema indexes",
+            format!("Missing: {}", missing.join(", ")),
+            "Indexes are re-created on startup; if they stay missing, restore a backup \
+             (narra backup list)",
+        )
+    };
+
+    let built = vector_index::built_index(db).await?;
+    let vector = match (settings.kind, built) {
+        (VectorIndexKind::None, None) => {
+            DoctorCheck::ok("vector index", "None configured (exact scan)")
+        }
+        (_, Some(built)) if built.dimensions != dimensions => DoctorCheck::warn(
+            "vector index",
+            format!(
+                "{} index built for {} dimensions, model has {}",
+                built.kind, built.dimensions, dimensions
+            ),
+            "narra world reindex-vectors",
+        ),
+        (kind, Some(built)) if built.kind != kind => DoctorCheck::warn(
+            "vector index",
+            format!("{} index built, config asks for {}", built.kind, kind),
+            "narra world reindex-vectors",
+        ),
+        (kind, Some(built)) => DoctorCheck::ok(
+            "vector index",
+            format!("{} ({} dimensions)", kind, built.dimensions),
+        ),
+        (kind, None) => DoctorCheck::warn(
+            "vector index",
+            format!("{} configured but not built", kind),
+            "narra world reindex-vectors",
+        ),
+    };
+
+    Ok(vec![schema, vector])
+}
@@ -0,0 +1,382 @@
+//! Draft progress: manuscript word counts against a target.
+//!
+//! Scenes carry an optional `word_count`, set by hand or ingested from a
+//! manuscript directory where each file is named after a scene key or title.
+//! Acts are the persisted narrative phases; scenes outside any phase are
+//! grouped as "Unphased". Scenes are also broken down by draft status
+//! (outline, drafted, revised, final). Each report run records a snapshot
+//! when the total has changed, giving a history of the draft over time.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+use crate::db::connection::NarraDb;
+use crate::models::scene::DRAFT_STATUSES;
+use crate::NarraError;
+
+/// Manuscript file extensions picked up by ingestion.
+pub const MANUSCRIPT_EXTENSIONS: &[&str] = &["md", "markdown", "txt", "fountain"];
+
+/// Words in a manuscript text: whitespace-separated tokens with at least one
+/// letter or digit, so markdown markers and dashes don't count.
+pub fn count_words(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|t| t.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+fn slugify(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// A scene's words, the act it falls in, and its draft status.
+#[derive(Debug, Clone)]
+pub struct SceneWords {
+    pub act: Option<String>,
+    pub words: Option<i64>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActProgress {
+    pub act: String,
+    pub scenes: usize,
+    pub counted_scenes: usize,
+    pub words: i64,
+    /// Share of the counted total
+    pub share: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusProgress {
+    pub status: String,
+    pub scenes: usize,
+    pub words: i64,
+    /// Share of all scenes
+    pub share: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressSnapshot {
+    pub total_words: i64,
+    pub counted_scenes: i64,
+    pub total_scenes: i64,
+    pub target: Option<i64>,
+    pub recorded_at: Datetime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DraftProgress {
+    pub total_words: i64,
+    pub counted_scenes: usize,
+    pub total_scenes: usize,
+    pub target: Option<i64>,
+    /// Percent of the target reached
+    pub percent: Option<f32>,
+    pub remaining: Option<i64>,
+    pub acts: Vec<ActProgress>,
+    /// Every draft status in revision order, including empty ones
+    pub by_status: Vec<StatusProgress>,
+    pub history: Vec<ProgressSnapshot>,
+}
+
+/// Totals, per-act distribution and per-status breakdown. Acts come out in
+/// `act_order`, then any unknown acts, then "Unphased".
+pub fn summarize(
+    scenes: &[SceneWords],
+    act_order: &[String],
+    target: Option<i64>,
+) -> DraftProgress {
+    let total_words: i64 = scenes.iter().filter_map(|s| s.words).sum();
+    let counted_scenes = scenes.iter().filter(|s| s.words.is_some()).count();
+
+    let mut by_act: HashMap<String, ActProgress> = HashMap::new();
+    for scene in scenes {
+        let act = scene.act.clone().unwrap_or_else(|| "Unphased".to_string());
+        let entry = by_act.entry(act.clone()).or_insert(ActProgress {
+            act,
+            scenes: 0,
+            counted_scenes: 0,
+            words: 0,
+            share: 0.0,
+        });
+        entry.scenes += 1;
+        if let Some(words) = scene.words {
+            entry.counted_scenes += 1;
+            entry.words += words;
+        }
+    }
+
+    let rank = |act: &str| {
+        act_order
+            .iter()
+            .position(|a| a == act)
+            .unwrap_or(if act == "Unphased" {
+                usize::MAX
+            } else {
+                act_order.len()
+            })
+    };
+    let mut acts: Vec<ActProgress> = by_act.into_values().collect();
+    acts.sort_by(|a, b| rank(&a.act).cmp(&rank(&b.act)).then(a.act.cmp(&b.act)));
+    for act in &mut acts {
+        if total_words > 0 {
+            act.share = act.words as f32 / total_words as f32;
+        }
+    }
+
+    let by_status = DRAFT_STATUSES
+        .iter()
+        .map(|status| {
+            let at: Vec<&SceneWords> = scenes.iter().filter(|s| s.status == *status).collect();
+            StatusProgress {
+                status: status.to_string(),
+                scenes: at.len(),
+                words: at.iter().filter_map(|s| s.words).sum(),
+                share: if scenes.is_empty() {
+                    0.0
+                } else {
+                    at.len() as f32 / scenes.len() as f32
+                },
+            }
+        })
+        .collect();
+
+    let target = target.filter(|t| *t > 0);
+    DraftProgress {
+        total_words,
+        counted_scenes,
+        total_scenes: scenes.len(),
+        target,
+        percent: target.map(|t| total_words as f32 / t as f32 * 100.0),
+        remaining: target.map(|t| (t - total_words).max(0)),
+        acts,
+        by_status,
+        history: Vec::new(),
+    }
+}
+
+/// Outcome of ingesting a manuscript directory.
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestResult {
+    /// (scene ID, file, words)
+    pub counted: Vec<(String, PathBuf, usize)>,
+    /// Files that matched no scene
+    pub unmatched: Vec<PathBuf>,
+}
+
+pub struct DraftProgressService {
+    db: Arc<NarraDb>,
+}
+
+impl DraftProgressService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Set (or clear, with `None`) the world's word target.
+    pub async fn set_target(&self, target: Option<i64>) -> Result<(), NarraError> {
+        self.db
+            .query("UPSERT world_meta:default SET word_target = $target, updated_at = time::now()")
+            .bind(("target", target))
+            .await?;
+        Ok(())
+    }
+
+    /// Count words in each manuscript file under `dir` and store them on the
+    /// scene whose key or title slug matches the file stem.
+    pub async fn ingest(&self, dir: &Path) -> Result<IngestResult, NarraError> {
+        #[derive(Deserialize)]
+        struct Row {
+            id: RecordId,
+            title: String,
+        }
+
+        let mut response = self.db.query("SELECT id, title FROM scene").await?;
+        let scenes: Vec<Row> = response.take(0)?;
+        let mut lookup: HashMap<String, RecordId> = HashMap::new();
+        for scene in scenes {
+            lookup.insert(slugify(&scene.title), scene.id.clone());
+            lookup.insert(scene.id.key().to_string().to_lowercase(), scene.id);
+        }
+
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| NarraError::Validation(format!("Cannot read {}: {}", dir.display(), e)))?;
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| MANUSCRIPT_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+            })
+            .collect();
+        files.sort();
+
+        let mut result = IngestResult {
+            counted: Vec::new(),
+            unmatched: Vec::new(),
+        };
+        for path in files {
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let scene = lookup
+                .get(&stem.to_lowercase())
+                .or_else(|| lookup.get(&slugify(stem)));
+            let Some(scene) = scene else {
+                result.unmatched.push(path);
+                continue;
+            };
+            let text = std::fs::read_to_string(&path).map_err(|e| {
+                NarraError::Validation(format!("Cannot read {}: {}", path.display(), e))
+            })?;
+            let words = count_words(&text);
+            self.db
+                .query("UPDATE $scene SET word_count = $words")
+                .bind(("scene", scene.clone()))
+                .bind(("words", words as i64))
+                .await?;
+            result.counted.push((scene.to_string(), path, words));
+        }
+        Ok(result)
+    }
+
+    /// Current progress with history. With `record`, a snapshot is stored
+    /// when the total differs from the latest one.
+    pub async fn report(&self, record: bool) -> Result<DraftProgress, NarraError> {
+        #[derive(Deserialize)]
+        struct SceneRow {
+            act: Option<String>,
+            word_count: Option<i64>,
+            draft_status: Option<String>,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT word_count, draft_status, \
+                 (SELECT VALUE out.label FROM belongs_to_phase WHERE in = $parent.id)[0] AS act \
+                 FROM scene; \
+                 SELECT VALUE label FROM phase ORDER BY phase_order ASC; \
+                 SELECT VALUE word_target FROM ONLY world_meta:default; \
+                 SELECT total_words, counted_scenes, total_scenes, target, recorded_at \
+                 FROM progress_snapshot ORDER BY recorded_at ASC",
+            )
+            .await?;
+        let scenes: Vec<SceneRow> = response.take(0)?;
+        let act_order: Vec<String> = response.take(1)?;
+        let target: Option<i64> = response.take(2).ok().flatten();
+        let mut history: Vec<ProgressSnapshot> = response.take(3)?;
+
+        let scenes: Vec<SceneWords> = scenes
+            .into_iter()
+            .map(|s| SceneWords {
+                act: s.act,
+                words: s.word_count,
+                status: s.draft_status.unwrap_or_else(|| "outline".to_string()),
+            })
+            .collect();
+        let mut progress = summarize(&scenes, &act_order, target);
+
+        let changed = match history.last() {
+            Some(last) => {
+                last.total_words != progress.total_words || last.target != progress.target
+            }
+            None => true,
+        };
+        if record && progress.counted_scenes > 0 && changed {
+            let mut response = self
+                .db
+                .query(
+                    "CREATE progress_snapshot SET total_words = $total_words, \
+                     counted_scenes = $counted_scenes, total_scenes = $total_scenes, \
+                     target = $target",
+                )
+                .bind(("total_words", progress.total_words))
+                .bind(("counted_scenes", progress.counted_scenes as i64))
+                .bind(("total_scenes", progress.total_scenes as i64))
+                .bind(("target", progress.target))
+                .await?;
+            let created: Option<ProgressSnapshot> = response.take(0)?;
+            history.extend(created);
+        }
+        progress.history = history;
+        Ok(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(act: Option<&str>, words: Option<i64>, status: &str) -> SceneWords {
+        SceneWords {
+            act: act.map(str::to_string),
+            words,
+            status: status.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_totals_and_acts() {
+        assert_eq!(
+            count_words("# Chapter One\n\nShe ran -- fast. *Then* stopped."),
+            7
+        );
+
+        let scenes = vec![
+            scene(Some("Act II"), Some(3000), "revised"),
+            scene(Some("Act I"), Some(1000), "drafted"),
+            scene(Some("Act I"), None, "outline"),
+            scene(None, Some(1000), "drafted"),
+        ];
+        let order = vec!["Act I".to_string(), "Act II".to_string()];
+        let progress = summarize(&scenes, &order, Some(10_000));
+
+        assert_eq!(progress.total_words, 5000);
+        assert_eq!((progress.counted_scenes, progress.total_scenes), (3, 4));
+        assert_eq!(progress.percent, Some(50.0));
+        assert_eq!(progress.remaining, Some(5000));
+
+        let acts: Vec<(&str, usize, i64)> = progress
+            .acts
+            .iter()
+            .map(|a| (a.act.as_str(), a.scenes, a.words))
+            .collect();
+        assert_eq!(
+            acts,
+            vec![
+                ("Act I", 2, 1000),
+                ("Act II", 1, 3000),
+                ("Unphased", 1, 1000)
+            ]
+        );
+        assert!((progress.acts[1].share - 0.6).abs() < 1e-6);
+
+        let statuses: Vec<(&str, usize, i64)> = progress
+            .by_status
+            .iter()
+            .map(|s| (s.status.as_str(), s.scenes, s.words))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("outline", 1, 0),
+                ("drafted", 2, 2000),
+                ("revised", 1, 3000),
+                ("final", 0, 0)
+            ]
+        );
+        assert!((progress.by_status[1].share - 0.5).abs() < 1e-6);
+    }
+}
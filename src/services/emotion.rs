@@ -19,9 +19,9 @@ use crate::NarraError;
 /// Default sigmoid activation threshold for GoEmotions multi-label output.
 const EMOTION_THRESHOLD: f32 = 0.3;
 
-const EMOTION_MODEL_REPO: &str = "SamLowe/roberta-base-go_emotions";
+pub(crate) const EMOTION_MODEL_REPO: &str = "SamLowe/roberta-base-go_emotions";
 const EMOTION_MODEL_VERSION: &str = "roberta-base-go_emotions-v1";
-const EMOTION_MODEL_TYPE: &str = "emotion";
+pub(crate) const EMOTION_MODEL_TYPE: &str = "emotion";
 
 /// Service trait for emotion classification.
 #[async_trait]
@@ -0,0 +1,516 @@
+//! Ensemble scenes: which characters would be interesting to put together.
+//!
+//! `scene_prep` plans a scene for characters the author has already picked.
+//! This proposes the picks. Every pair of candidates gets a score from three
+//! signals:
+//!
+//! - unresolved tension: the higher `tension_level` of their perceptions
+//! - knowledge asymmetry: things one knows (in any certainty) that the
+//!   other has no knowledge edge for
+//! - time apart: scenes since they last shared one, with never counting most
+//!
+//! A group scores the mean of its pairs, plus a bonus per member who has
+//! been in a scene at the chosen location. Candidates can be narrowed to the
+//! members of a narrative phase. Groups built around the same strongest pair
+//! are proposed only once, so the list isn't one pair with rotating extras.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::NarraError;
+
+/// Default characters per proposed scene.
+pub const DEFAULT_SIZE: usize = 3;
+
+/// Candidates kept for combination, by their best pair score.
+const POOL: usize = 12;
+
+/// Asymmetries beyond this many add nothing.
+const ASYMMETRY_CAP: usize = 5;
+
+/// Scenes apart after which a pair counts as fully estranged.
+const APART_WINDOW: usize = 10;
+
+/// Added to a group's score per member who has been at the location.
+const LOCATION_BONUS: f32 = 0.1;
+
+const TENSION_WEIGHT: f32 = 1.0;
+const ASYMMETRY_WEIGHT: f32 = 1.0;
+const APART_WEIGHT: f32 = 0.5;
+
+/// Options for proposing ensembles.
+#[derive(Debug, Clone, Default)]
+pub struct EnsembleOptions {
+    /// Location ID (`location:...`) the scene is set at
+    pub location: Option<String>,
+    /// Only members of this phase are candidates (full IDs)
+    pub members: Option<HashSet<String>>,
+    /// Characters per scene (at least 2)
+    pub size: usize,
+    pub limit: usize,
+}
+
+/// What makes a pair of characters worth putting in a room together.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PairSignals {
+    /// Higher tension of the two perceptions, if either has one
+    pub tension: Option<i32>,
+    /// Things one knows about that the other doesn't, both ways
+    pub asymmetries: usize,
+    /// Scenes since they last shared one; None if they never have
+    pub scenes_apart: Option<usize>,
+}
+
+impl PairSignals {
+    /// Weighted score, 0.0 to 2.5.
+    pub fn score(&self) -> f32 {
+        let tension = self.tension.unwrap_or(0).clamp(0, 10) as f32 / 10.0;
+        let asymmetry = self.asymmetries.min(ASYMMETRY_CAP) as f32 / ASYMMETRY_CAP as f32;
+        let apart = match self.scenes_apart {
+            Some(n) => n.min(APART_WINDOW) as f32 / APART_WINDOW as f32,
+            None => 1.0,
+        };
+        tension * TENSION_WEIGHT + asymmetry * ASYMMETRY_WEIGHT + apart * APART_WEIGHT
+    }
+}
+
+/// A character that can be cast.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub id: String,
+    pub name: String,
+    /// Has appeared in a scene at the chosen location
+    pub at_location: bool,
+}
+
+/// A proposed group of characters for one scene.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnsembleProposal {
+    pub character_ids: Vec<String>,
+    pub names: Vec<String>,
+    pub score: f32,
+    /// Why the group is interesting, strongest first
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnsembleReport {
+    pub location: Option<String>,
+    pub location_name: Option<String>,
+    /// Characters considered
+    pub candidates: usize,
+    pub proposals: Vec<EnsembleProposal>,
+}
+
+fn pair_key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+/// Reasons a pair is interesting, or none if it isn't.
+fn pair_reasons(a: &Candidate, b: &Candidate, signals: &PairSignals) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if let Some(t) = signals.tension.filter(|t| *t >= 5) {
+        reasons.push(format!("{} and {}: tension {}", a.name, b.name, t));
+    }
+    if signals.asymmetries > 0 {
+        reasons.push(format!(
+            "{} and {}: {} knowledge gap(s) to exploit",
+            a.name, b.name, signals.asymmetries
+        ));
+    }
+    match signals.scenes_apart {
+        None => reasons.push(format!(
+            "{} and {} have never shared a scene",
+            a.name, b.name
+        )),
+        Some(n) if n >= APART_WINDOW => reasons.push(format!(
+            "{} and {} haven't shared a scene in {} scenes",
+            a.name, b.name, n
+        )),
+        _ => {}
+    }
+    reasons
+}
+
+/// Every `size`-combination of `0..n`, in lexicographic order.
+fn combinations(n: usize, size: usize) -> Vec<Vec<usize>> {
+    let mut out = Vec::new();
+    if size == 0 || size > n {
+        return out;
+    }
+    let mut current: Vec<usize> = (0..size).collect();
+    loop {
+        out.push(current.clone());
+        let Some(i) = (0..size).rev().find(|&i| current[i] < n - size + i) else {
+            return out;
+        };
+        current[i] += 1;
+        for j in i + 1..size {
+            current[j] = current[j - 1] + 1;
+        }
+    }
+}
+
+/// Rank groups of `size` candidates by how much their pairs have to play out.
+pub fn propose(
+    candidates: &[Candidate],
+    pairs: &HashMap<(String, String), PairSignals>,
+    size: usize,
+    limit: usize,
+) -> Vec<EnsembleProposal> {
+    let signals = |a: &Candidate, b: &Candidate| {
+        pairs
+            .get(&pair_key(&a.id, &b.id))
+            .copied()
+            .unwrap_or_default()
+    };
+
+    // Keep the candidates with the most promising partner
+    let mut pool: Vec<(f32, &Candidate)> = candidates
+        .iter()
+        .map(|c| {
+            let best = candidates
+                .iter()
+                .filter(|o| o.id != c.id)
+                .map(|o| signals(c, o).score())
+                .fold(0.0, f32::max);
+            (best, c)
+        })
+        .collect();
+    pool.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    pool.truncate(POOL);
+    let pool: Vec<&Candidate> = pool.into_iter().map(|(_, c)| c).collect();
+
+    let mut scored: Vec<(f32, (usize, usize), Vec<usize>)> = combinations(pool.len(), size)
+        .into_iter()
+        .map(|group| {
+            let mut total = 0.0;
+            let mut count = 0;
+            let mut strongest = (0.0, (group[0], group[1]));
+            for (x, &i) in group.iter().enumerate() {
+                for &j in &group[x + 1..] {
+                    let score = signals(pool[i], pool[j]).score();
+                    total += score;
+                    count += 1;
+                    if score > strongest.0 {
+                        strongest = (score, (i, j));
+                    }
+                }
+            }
+            let bonus = group.iter().filter(|&&i| pool[i].at_location).count() as f32;
+            let score = total / count as f32 + bonus * LOCATION_BONUS;
+            (score, strongest.1, group)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut seen_pairs = HashSet::new();
+    let mut proposals = Vec::new();
+    for (score, strongest, group) in scored {
+        if proposals.len() >= limit {
+            break;
+        }
+        if !seen_pairs.insert(strongest) {
+            continue;
+        }
+        let members: Vec<&Candidate> = group.iter().map(|&i| pool[i]).collect();
+        let mut reasons: Vec<(f32, String)> = Vec::new();
+        for (x, a) in members.iter().enumerate() {
+            for b in &members[x + 1..] {
+                let s = signals(a, b);
+                reasons.extend(pair_reasons(a, b, &s).into_iter().map(|r| (s.score(), r)));
+            }
+        }
+        reasons.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let regulars: Vec<&str> = members
+            .iter()
+            .filter(|c| c.at_location)
+            .map(|c| c.name.as_str())
+            .collect();
+        let mut reasons: Vec<String> = reasons.into_iter().map(|(_, r)| r).collect();
+        if !regulars.is_empty() {
+            reasons.push(format!("Been here before: {}", regulars.join(", ")));
+        }
+        proposals.push(EnsembleProposal {
+            character_ids: members.iter().map(|c| c.id.clone()).collect(),
+            names: members.iter().map(|c| c.name.clone()).collect(),
+            score,
+            reasons,
+        });
+    }
+    proposals
+}
+
+pub struct EnsembleService {
+    db: Arc<NarraDb>,
+}
+
+impl EnsembleService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    pub async fn propose(&self, options: &EnsembleOptions) -> Result<EnsembleReport, NarraError> {
+        #[derive(Deserialize)]
+        struct CharacterRow {
+            id: RecordId,
+            name: String,
+        }
+        #[derive(Deserialize)]
+        struct SceneRow {
+            id: RecordId,
+            primary_location: Option<RecordId>,
+            #[serde(default)]
+            secondary_locations: Vec<RecordId>,
+        }
+        #[derive(Deserialize)]
+        struct ParticipationRow {
+            character: RecordId,
+            scene: RecordId,
+        }
+        #[derive(Deserialize)]
+        struct PerceptionRow {
+            observer: RecordId,
+            target: RecordId,
+            tension_level: Option<i32>,
+        }
+        #[derive(Deserialize)]
+        struct KnowsRow {
+            character: RecordId,
+            target: RecordId,
+        }
+
+        let size = options.size.max(2);
+        let location_name = match &options.location {
+            Some(id) => {
+                let mut response = self
+                    .db
+                    .query("SELECT VALUE name FROM $id")
+                    .bind(("id", crate::db::query::parse_record_id(id)?))
+                    .await?;
+                let name: Option<String> = response.take(0)?;
+                Some(name.ok_or_else(|| NarraError::NotFound {
+                    entity_type: "location".to_string(),
+                    id: id.clone(),
+                })?)
+            }
+            None => None,
+        };
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, name FROM character; \
+                 SELECT id, primary_location, secondary_locations, event.sequence AS sequence, \
+                 created_at FROM scene ORDER BY sequence ASC, created_at ASC; \
+                 SELECT in AS character, out AS scene FROM participates_in; \
+                 SELECT in AS observer, out AS target, tension_level FROM perceives; \
+                 SELECT in AS character, out AS target FROM knows",
+            )
+            .await?;
+        let characters: Vec<CharacterRow> = response.take(0)?;
+        let scenes: Vec<SceneRow> = response.take(1)?;
+        let participation: Vec<ParticipationRow> = response.take(2)?;
+        let perceptions: Vec<PerceptionRow> = response.take(3)?;
+        let knows: Vec<KnowsRow> = response.take(4)?;
+
+        let position: HashMap<String, usize> = scenes
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.id.to_string(), i))
+            .collect();
+        let location_scenes: HashSet<usize> = match &options.location {
+            Some(location) => scenes
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| {
+                    s.primary_location
+                        .iter()
+                        .chain(&s.secondary_locations)
+                        .any(|l| l.to_string() == *location)
+                })
+                .map(|(i, _)| i)
+                .collect(),
+            None => HashSet::new(),
+        };
+        let mut appearances: HashMap<String, HashSet<usize>> = HashMap::new();
+        for row in participation {
+            if let Some(&i) = position.get(&row.scene.to_string()) {
+                appearances
+                    .entry(row.character.to_string())
+                    .or_default()
+                    .insert(i);
+            }
+        }
+
+        let candidates: Vec<Candidate> = characters
+            .into_iter()
+            .map(|c| (c.id.to_string(), c.name))
+            .filter(|(id, _)| options.members.as_ref().is_none_or(|m| m.contains(id)))
+            .map(|(id, name)| Candidate {
+                at_location: appearances
+                    .get(&id)
+                    .is_some_and(|s| !s.is_disjoint(&location_scenes)),
+                id,
+                name,
+            })
+            .collect();
+
+        let mut known: HashMap<String, HashSet<String>> = HashMap::new();
+        for row in knows {
+            known
+                .entry(row.character.to_string())
+                .or_default()
+                .insert(row.target.to_string());
+        }
+        let mut tensions: HashMap<(String, String), i32> = HashMap::new();
+        for row in perceptions {
+            if let Some(t) = row.tension_level {
+                let key = pair_key(&row.observer.to_string(), &row.target.to_string());
+                let entry = tensions.entry(key).or_insert(t);
+                *entry = (*entry).max(t);
+            }
+        }
+
+        let empty: HashSet<String> = HashSet::new();
+        let total_scenes = scenes.len();
+        let mut pairs = HashMap::new();
+        for (x, a) in candidates.iter().enumerate() {
+            for b in &candidates[x + 1..] {
+                let key = pair_key(&a.id, &b.id);
+                let known_a = known.get(&a.id).unwrap_or(&empty);
+                let known_b = known.get(&b.id).unwrap_or(&empty);
+                let asymmetries = known_a
+                    .symmetric_difference(known_b)
+                    .filter(|t| **t != a.id && **t != b.id)
+                    .count();
+                let last_shared = appearances
+                    .get(&a.id)
+                    .zip(appearances.get(&b.id))
+                    .and_then(|(sa, sb)| sa.intersection(sb).max().copied());
+                pairs.insert(
+                    key.clone(),
+                    PairSignals {
+                        tension: tensions.get(&key).copied(),
+                        asymmetries,
+                        scenes_apart: last_shared.map(|s| total_scenes - s - 1),
+                    },
+                );
+            }
+        }
+
+        Ok(EnsembleReport {
+            location: options.location.clone(),
+            location_name,
+            candidates: candidates.len(),
+            proposals: propose(&candidates, &pairs, size, options.limit),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(name: &str, at_location: bool) -> Candidate {
+        Candidate {
+            id: format!("character:{}", name.to_lowercase()),
+            name: name.to_string(),
+            at_location,
+        }
+    }
+
+    fn pairs() -> HashMap<(String, String), PairSignals> {
+        let mut pairs = HashMap::new();
+        pairs.insert(
+            pair_key("character:alice", "character:bob"),
+            PairSignals {
+                tension: Some(9),
+                asymmetries: 0,
+                scenes_apart: None,
+            },
+        );
+        pairs.insert(
+            pair_key("character:carol", "character:alice"),
+            PairSignals {
+                tension: None,
+                asymmetries: 3,
+                scenes_apart: None,
+            },
+        );
+        pairs.insert(
+            pair_key("character:carol", "character:dave"),
+            PairSignals {
+                tension: Some(2),
+                asymmetries: 0,
+                scenes_apart: Some(0),
+            },
+        );
+        pairs
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(combinations(4, 2).len(), 6);
+        assert_eq!(combinations(5, 3).len(), 10);
+        assert_eq!(combinations(3, 3), vec![vec![0, 1, 2]]);
+        assert!(combinations(2, 3).is_empty());
+    }
+
+    #[test]
+    fn test_pair_score() {
+        let estranged = PairSignals {
+            tension: Some(10),
+            asymmetries: 12,
+            scenes_apart: None,
+        };
+        assert!((estranged.score() - 2.5).abs() < 1e-6);
+        let together = PairSignals {
+            tension: None,
+            asymmetries: 0,
+            scenes_apart: Some(0),
+        };
+        assert_eq!(together.score(), 0.0);
+    }
+
+    #[test]
+    fn test_propose_ranks_and_varies_groups() {
+        let candidates = vec![
+            candidate("Alice", false),
+            candidate("Bob", false),
+            candidate("Carol", false),
+            candidate("Dave", true),
+        ];
+
+        let duos = propose(&candidates, &pairs(), 2, 3);
+        let names: Vec<Vec<String>> = duos.iter().map(|p| p.names.clone()).collect();
+        assert_eq!(
+            names,
+            vec![
+                vec!["Alice".to_string(), "Bob".to_string()],
+                vec!["Alice".to_string(), "Carol".to_string()],
+                vec!["Alice".to_string(), "Dave".to_string()],
+            ]
+        );
+        assert_eq!(duos[0].reasons[0], "Alice and Bob: tension 9");
+
+        // Alice, Bob and Dave is built around the same pair as Alice, Bob and Carol
+        let trios = propose(&candidates, &pairs(), 3, 5);
+        assert_eq!(trios.len(), 3);
+        assert_eq!(trios[0].names, vec!["Alice", "Bob", "Carol"]);
+        assert!(trios
+            .iter()
+            .all(|p| p.names != vec!["Alice", "Bob", "Dave"]));
+        assert!(trios[1]
+            .reasons
+            .contains(&"Been here before: Dave".to_string()));
+    }
+}
@@ -0,0 +1,389 @@
+//! Event inference: group scenes that were imported one event apiece.
+//!
+//! Importers that only know about scenes (screenplays, for one) give every
+//! scene its own stand-in event: an event with the scene's title, nothing
+//! else anchored to it, and no description, date, participants, notes,
+//! facts or foreshadowing of its own. Runs of adjacent stand-in scenes that
+//! look like one happening are proposed as a single event. Two scenes are
+//! alike by three signals:
+//!
+//! - same primary location
+//! - overlap of their participants (Jaccard)
+//! - cosine similarity of their embeddings
+//!
+//! A signal missing on either side is left out of the weighting. A scene
+//! joins the running group when it is alike enough to any scene in it; any
+//! other scene in between ends the run, so accepted groups never change the
+//! reading order. Each proposal reuses the stand-in event of its first scene
+//! at that event's sequence; accepting it moves the other scenes there and
+//! deletes their stand-ins.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
+use crate::utils::math::cosine_similarity;
+use crate::NarraError;
+
+/// Default similarity a scene needs to join a group.
+pub const DEFAULT_THRESHOLD: f32 = 0.5;
+
+const LOCATION_WEIGHT: f32 = 0.4;
+const PARTICIPANT_WEIGHT: f32 = 0.3;
+const SEMANTIC_WEIGHT: f32 = 0.3;
+
+/// What a scene is compared by.
+#[derive(Debug, Clone, Default)]
+pub struct SceneSignals {
+    pub location: String,
+    pub participants: HashSet<String>,
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// How alike two scenes are, from 0 to 1.
+pub fn similarity(a: &SceneSignals, b: &SceneSignals) -> f32 {
+    let mut score = 0.0;
+    let mut weight = 0.0;
+    if !a.location.is_empty() && !b.location.is_empty() {
+        score += LOCATION_WEIGHT * if a.location == b.location { 1.0 } else { 0.0 };
+        weight += LOCATION_WEIGHT;
+    }
+    if !a.participants.is_empty() && !b.participants.is_empty() {
+        let shared = a.participants.intersection(&b.participants).count() as f32;
+        let all = a.participants.union(&b.participants).count() as f32;
+        score += PARTICIPANT_WEIGHT * shared / all;
+        weight += PARTICIPANT_WEIGHT;
+    }
+    if let (Some(x), Some(y)) = (&a.embedding, &b.embedding) {
+        score += SEMANTIC_WEIGHT * cosine_similarity(x, y).max(0.0);
+        weight += SEMANTIC_WEIGHT;
+    }
+    if weight == 0.0 {
+        0.0
+    } else {
+        score / weight
+    }
+}
+
+/// Group scenes in reading order. `None` marks a scene that is anchored to
+/// a real event and ends the run. Returns groups of indices, in order,
+/// including single scenes.
+pub fn cluster(scenes: &[Option<SceneSignals>], threshold: f32) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut open = false;
+    for (i, scene) in scenes.iter().enumerate() {
+        let Some(scene) = scene else {
+            open = false;
+            continue;
+        };
+        let joins = open
+            && groups.last().is_some_and(|group| {
+                group.iter().any(|&j| {
+                    scenes[j]
+                        .as_ref()
+                        .is_some_and(|other| similarity(scene, other) >= threshold)
+                })
+            });
+        if joins {
+            groups.last_mut().expect("open group").push(i);
+        } else {
+            groups.push(vec![i]);
+            open = true;
+        }
+    }
+    groups
+}
+
+/// A scene in a proposal.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposedScene {
+    pub id: String,
+    pub title: String,
+    /// Its stand-in event
+    pub event_id: String,
+}
+
+/// Scenes that look like one event.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventProposal {
+    /// Stand-in event that becomes the merged event
+    pub event_id: String,
+    pub title: String,
+    pub sequence: i64,
+    pub location: Option<String>,
+    pub participants: Vec<String>,
+    /// Mean similarity between the scenes
+    pub cohesion: f32,
+    pub scenes: Vec<ProposedScene>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InferenceReport {
+    /// Scenes on stand-in events
+    pub unanchored: usize,
+    pub proposals: Vec<EventProposal>,
+    /// Stand-in scenes no neighbour resembled
+    pub singles: usize,
+}
+
+pub struct EventInferenceService {
+    db: Arc<NarraDb>,
+}
+
+impl EventInferenceService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Propose events for runs of alike stand-in scenes.
+    pub async fn propose(&self, threshold: f32) -> Result<InferenceReport, NarraError> {
+        #[derive(Deserialize)]
+        struct SceneRow {
+            id: RecordId,
+            title: String,
+            event: RecordId,
+            primary_location: RecordId,
+            embedding: Option<Vec<f32>>,
+        }
+        #[derive(Deserialize)]
+        struct EventRow {
+            id: RecordId,
+            title: String,
+            description: Option<String>,
+            date: Option<surrealdb::Datetime>,
+            sequence: i64,
+        }
+        #[derive(Deserialize)]
+        struct EdgeRow {
+            #[serde(rename = "in")]
+            from: RecordId,
+            out: RecordId,
+        }
+        #[derive(Deserialize)]
+        struct NameRow {
+            id: RecordId,
+            name: String,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, title, event, primary_location, embedding, created_at, \
+                 event.sequence AS sequence FROM scene ORDER BY sequence ASC, created_at ASC; \
+                 SELECT id, title, description, date, sequence FROM event; \
+                 SELECT in, out FROM participates_in; \
+                 SELECT VALUE out FROM involved_in; \
+                 SELECT VALUE out FROM note_attachment; \
+                 SELECT VALUE out FROM applies_to; \
+                 SELECT in, out FROM foreshadows; \
+                 SELECT id, name FROM character; \
+                 SELECT id, name FROM location",
+            )
+            .await?;
+        let scenes: Vec<SceneRow> = response.take(0)?;
+        let events: Vec<EventRow> = response.take(1)?;
+        let participations: Vec<EdgeRow> = response.take(2)?;
+        let involved: Vec<RecordId> = response.take(3)?;
+        let notes: Vec<RecordId> = response.take(4)?;
+        let facts: Vec<RecordId> = response.take(5)?;
+        let foreshadows: Vec<EdgeRow> = response.take(6)?;
+        let characters: Vec<NameRow> = response.take(7)?;
+        let locations: Vec<NameRow> = response.take(8)?;
+
+        let linked: HashSet<String> = involved
+            .iter()
+            .chain(&notes)
+            .chain(&facts)
+            .chain(foreshadows.iter().flat_map(|f| [&f.from, &f.out]))
+            .map(|id| id.to_string())
+            .collect();
+        let mut per_event: HashMap<String, usize> = HashMap::new();
+        for scene in &scenes {
+            *per_event.entry(scene.event.to_string()).or_default() += 1;
+        }
+        let events: HashMap<String, EventRow> =
+            events.into_iter().map(|e| (e.id.to_string(), e)).collect();
+        let stand_in = |scene: &SceneRow| {
+            let key = scene.event.to_string();
+            events.get(&key).is_some_and(|e| {
+                e.title == scene.title
+                    && e.description.as_deref().is_none_or(str::is_empty)
+                    && e.date.is_none()
+                    && per_event.get(&key) == Some(&1)
+                    && !linked.contains(&key)
+            })
+        };
+
+        let mut participants: HashMap<String, HashSet<String>> = HashMap::new();
+        for edge in participations {
+            participants
+                .entry(edge.out.to_string())
+                .or_default()
+                .insert(edge.from.to_string());
+        }
+        let names: HashMap<String, String> = characters
+            .into_iter()
+            .chain(locations)
+            .map(|n| (n.id.to_string(), n.name))
+            .collect();
+
+        let signals: Vec<Option<SceneSignals>> = scenes
+            .iter()
+            .map(|scene| {
+                stand_in(scene).then(|| SceneSignals {
+                    location: scene.primary_location.to_string(),
+                    participants: participants
+                        .get(&scene.id.to_string())
+                        .cloned()
+                        .unwrap_or_default(),
+                    embedding: scene.embedding.clone(),
+                })
+            })
+            .collect();
+        let unanchored = signals.iter().filter(|s| s.is_some()).count();
+
+        let mut proposals = Vec::new();
+        let mut singles = 0;
+        for group in cluster(&signals, threshold) {
+            if group.len() < 2 {
+                singles += 1;
+                continue;
+            }
+            let first = &scenes[group[0]];
+            let event = &events[&first.event.to_string()];
+            let members: Vec<&SceneSignals> =
+                group.iter().filter_map(|&i| signals[i].as_ref()).collect();
+
+            let mut pairs = 0;
+            let mut total = 0.0;
+            for (i, a) in members.iter().enumerate() {
+                for b in &members[i + 1..] {
+                    total += similarity(a, b);
+                    pairs += 1;
+                }
+            }
+            let locations: HashSet<&str> = members.iter().map(|m| m.location.as_str()).collect();
+            let location = (locations.len() == 1)
+                .then(|| names.get(&first.primary_location.to_string()).cloned())
+                .flatten();
+            let mut cast: Vec<String> = members
+                .iter()
+                .flat_map(|m| &m.participants)
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .map(|id| names.get(id).cloned().unwrap_or_else(|| id.clone()))
+                .collect();
+            cast.sort();
+
+            proposals.push(EventProposal {
+                event_id: event.id.to_string(),
+                title: event.title.clone(),
+                sequence: event.sequence,
+                location,
+                participants: cast,
+                cohesion: total / pairs as f32,
+                scenes: group
+                    .iter()
+                    .map(|&i| ProposedScene {
+                        id: scenes[i].id.to_string(),
+                        title: scenes[i].title.clone(),
+                        event_id: scenes[i].event.to_string(),
+                    })
+                    .collect(),
+            });
+        }
+
+        Ok(InferenceReport {
+            unanchored,
+            proposals,
+            singles,
+        })
+    }
+
+    /// Merge a proposal's scenes into its event, renamed to `title`.
+    /// Knowledge, arc snapshots and terms anchored to the absorbed stand-ins
+    /// move along; the stand-ins are then deleted. Returns the merged event.
+    pub async fn accept(
+        &self,
+        proposal: &EventProposal,
+        title: &str,
+    ) -> Result<String, NarraError> {
+        let event = record_id("event", &proposal.event_id)?;
+        let mut statements =
+            String::from("BEGIN TRANSACTION;\nUPDATE $event SET title = $title RETURN NONE;\n");
+        let mut absorbed = Vec::new();
+        for (i, scene) in proposal.scenes.iter().enumerate() {
+            if scene.event_id == proposal.event_id {
+                continue;
+            }
+            statements.push_str(&format!(
+                "UPDATE $scene_{i} SET event = $event RETURN NONE;\n\
+                 UPDATE knows SET event = $event WHERE event = $old_{i} RETURN NONE;\n\
+                 UPDATE arc_snapshot SET event_id = $event WHERE event_id = $old_{i} RETURN NONE;\n\
+                 UPDATE term SET first_use = $event WHERE first_use = $old_{i} RETURN NONE;\n\
+                 DELETE $old_{i};\n"
+            ));
+            absorbed.push((
+                i,
+                record_id("scene", &scene.id)?,
+                record_id("event", &scene.event_id)?,
+            ));
+        }
+        statements.push_str("COMMIT TRANSACTION;");
+
+        let mut query = self
+            .db
+            .query(statements)
+            .bind(("event", event.clone()))
+            .bind(("title", title.to_string()));
+        for (i, scene, old) in absorbed {
+            query = query
+                .bind((format!("scene_{i}"), scene))
+                .bind((format!("old_{i}"), old));
+        }
+        query.await?.check()?;
+        Ok(event.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(location: &str, participants: &[&str]) -> Option<SceneSignals> {
+        Some(SceneSignals {
+            location: location.to_string(),
+            participants: participants.iter().map(|p| p.to_string()).collect(),
+            embedding: None,
+        })
+    }
+
+    #[test]
+    fn test_cluster_groups_adjacent_alike_scenes() {
+        let scenes = vec![
+            scene("location:bar", &["character:alice", "character:bob"]),
+            scene("location:bar", &["character:alice"]),
+            scene("location:docks", &["character:carol"]),
+            // Anchored to a real event: ends the run
+            None,
+            scene("location:docks", &["character:carol"]),
+            scene("location:docks", &["character:carol", "character:dave"]),
+        ];
+
+        assert_eq!(
+            cluster(&scenes, DEFAULT_THRESHOLD),
+            vec![vec![0, 1], vec![2], vec![4, 5]]
+        );
+        // Same place, half the cast: 0.4 + 0.3 * 0.5 over 0.7
+        let same_place = similarity(scenes[0].as_ref().unwrap(), scenes[1].as_ref().unwrap());
+        assert!((same_place - 0.55 / 0.7).abs() < 1e-6);
+        assert_eq!(
+            similarity(&SceneSignals::default(), &SceneSignals::default()),
+            0.0
+        );
+    }
+}
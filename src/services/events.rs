@@ -0,0 +1,290 @@
+//! Machine-readable event stream for external tooling.
+//!
+//! Mutations, backfills, and validation runs emit `NarraEvent`s. Each event is
+//! written as one JSON line to every attached sink (an append-only file or a
+//! Unix socket, selected with `--events`) and broadcast to in-process
+//! subscribers such as webhooks and plugin hooks (`EventListener`).
+
+use std::future::Future;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Notify};
+use tokio::task::JoinHandle;
+
+use crate::services::ValidationResult;
+
+/// An entity (or batch of entities) was created.
+pub const ENTITY_CREATED: &str = "entity.created";
+/// An entity's fields were updated.
+pub const ENTITY_UPDATED: &str = "entity.updated";
+/// An entity was deleted.
+pub const ENTITY_DELETED: &str = "entity.deleted";
+/// An embedding backfill run finished.
+pub const EMBEDDING_BACKFILLED: &str = "embedding.backfilled";
+/// A consistency check found a universe-fact violation.
+pub const VALIDATION_VIOLATION: &str = "validation.violation";
+
+/// Capacity of the in-process broadcast channel (slow subscribers lag, never block).
+const BROADCAST_CAPACITY: usize = 256;
+/// How long a short-lived CLI process waits for listeners to drain on exit.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A single emitted event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NarraEvent {
+    /// Event name, e.g. "entity.created".
+    pub event: String,
+    /// RFC 3339 timestamp.
+    pub timestamp: String,
+    /// Event-specific payload.
+    pub data: serde_json::Value,
+}
+
+/// Destination for serialized events.
+pub enum EventSink {
+    File(std::fs::File),
+    #[cfg(unix)]
+    Socket(std::os::unix::net::UnixStream),
+}
+
+impl EventSink {
+    /// Open a sink: connect if `target` is an existing Unix socket, otherwise
+    /// append to it as a JSON-lines file (created if missing).
+    pub fn open(target: &str) -> Result<Self> {
+        let path = Path::new(target);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if let Ok(meta) = std::fs::metadata(path) {
+                if meta.file_type().is_socket() {
+                    let stream = std::os::unix::net::UnixStream::connect(path)
+                        .with_context(|| format!("Failed to connect to event socket {}", target))?;
+                    return Ok(EventSink::Socket(stream));
+                }
+            }
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open event file {}", target))?;
+        Ok(EventSink::File(file))
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        match self {
+            EventSink::File(f) => f.write_all(line),
+            #[cfg(unix)]
+            EventSink::Socket(s) => s.write_all(line),
+        }
+    }
+}
+
+/// Fan-out point for events.
+pub struct EventBus {
+    sinks: Mutex<Vec<EventSink>>,
+    tx: broadcast::Sender<NarraEvent>,
+    author: Option<String>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self {
+            sinks: Mutex::new(Vec::new()),
+            tx,
+            author: None,
+        }
+    }
+
+    /// Record `author` on every entity event.
+    pub fn with_author(mut self, author: Option<String>) -> Self {
+        self.author = author;
+        self
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Attach an output sink.
+    pub fn add_sink(&self, sink: EventSink) {
+        self.sinks.lock().unwrap().push(sink);
+    }
+
+    /// Receive every event emitted after this call.
+    pub fn subscribe(&self) -> broadcast::Receiver<NarraEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Emit an event. Never fails: a sink that errors is logged and detached.
+    pub fn emit(&self, event: &str, data: serde_json::Value) {
+        let event = NarraEvent {
+            event: event.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            data,
+        };
+
+        {
+            let mut sinks = self.sinks.lock().unwrap();
+            if !sinks.is_empty() {
+                match serde_json::to_vec(&event) {
+                    Ok(mut line) => {
+                        line.push(b'\n');
+                        sinks.retain_mut(|sink| match sink.write_line(&line) {
+                            Ok(()) => true,
+                            Err(e) => {
+                                tracing::warn!("Event sink failed, detaching: {}", e);
+                                false
+                            }
+                        });
+                    }
+                    Err(e) => tracing::warn!("Failed to serialize event: {}", e),
+                }
+            }
+        }
+
+        // No receivers is the common case, not an error
+        let _ = self.tx.send(event);
+    }
+
+    /// Emit an entity lifecycle event with the standard payload.
+    ///
+    /// `source` is the interface that made the change ("cli" or "mcp");
+    /// `author` is the configured writer, when known.
+    pub fn emit_entity(&self, event: &str, source: &str, id: &str, entity_type: &str, name: &str) {
+        self.emit(
+            event,
+            serde_json::json!({
+                "id": id,
+                "entity_type": entity_type,
+                "name": name,
+                "source": source,
+                "author": self.author,
+            }),
+        );
+    }
+
+    /// Emit one `validation.violation` event per violation in a check result.
+    ///
+    /// `subject` names what was checked (an entity ID or mutation description).
+    pub fn emit_violations(&self, source: &str, subject: &str, result: &ValidationResult) {
+        for violation in result.violations_by_severity.values().flatten() {
+            self.emit(
+                VALIDATION_VIOLATION,
+                serde_json::json!({
+                    "subject": subject,
+                    "severity": violation.severity,
+                    "fact_id": violation.fact_id,
+                    "fact_title": violation.fact_title,
+                    "message": violation.message,
+                    "blocking": result.has_blocking_violations,
+                    "source": source,
+                }),
+            );
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Background consumer that handles bus events one at a time.
+///
+/// CLI commands exit right after mutating, so `finish` drains events that were
+/// already emitted before stopping.
+pub struct EventListener {
+    handle: JoinHandle<()>,
+    shutdown: Arc<Notify>,
+}
+
+impl EventListener {
+    pub fn spawn<F, Fut>(bus: &EventBus, mut handler: F) -> Self
+    where
+        F: FnMut(NarraEvent) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut rx = bus.subscribe();
+        let shutdown = Arc::new(Notify::new());
+        let stop = shutdown.clone();
+
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    received = rx.recv() => match received {
+                        Ok(event) => handler(event).await,
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            tracing::warn!("Event listener lagged, dropped {} events", n);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    },
+                    _ = stop.notified() => {
+                        while let Ok(event) = rx.try_recv() {
+                            handler(event).await;
+                        }
+                        return;
+                    }
+                }
+            }
+        });
+
+        Self { handle, shutdown }
+    }
+
+    /// Handle events already emitted, then stop.
+    pub async fn finish(self) {
+        self.shutdown.notify_one();
+        if tokio::time::timeout(FLUSH_TIMEOUT, self.handle)
+            .await
+            .is_err()
+        {
+            tracing::warn!("Timed out draining event listener");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_appends_json_lines_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        let bus = EventBus::new();
+        bus.add_sink(EventSink::open(path.to_str().unwrap()).unwrap());
+        bus.emit(ENTITY_CREATED, serde_json::json!({"id": "character:alice"}));
+        bus.emit(ENTITY_DELETED, serde_json::json!({"id": "character:alice"}));
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let events: Vec<NarraEvent> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, ENTITY_CREATED);
+        assert_eq!(events[1].data["id"], "character:alice");
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        bus.emit(EMBEDDING_BACKFILLED, serde_json::json!({"embedded": 3}));
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.event, EMBEDDING_BACKFILLED);
+        assert_eq!(event.data["embedded"], 3);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use serde::Deserialize;
@@ -13,7 +14,7 @@ use crate::models::character::Character;
 use crate::models::event::Event;
 use crate::models::fact::{FactApplication, UniverseFact};
 use crate::models::location::Location;
-use crate::models::note::{Note, NoteAttachment};
+use crate::models::note::{Note, NoteAttachment, NoteKind};
 use crate::models::relationship::Relationship;
 use crate::models::scene::{Scene, SceneParticipant};
 use crate::NarraError;
@@ -41,6 +42,24 @@ impl ExportService {
         Self { db }
     }
 
+    /// Export only the neighborhood of `root`; see [`scope_import`].
+    ///
+    /// Perceptions are not part of the YAML format but still count as
+    /// connections when walking the neighborhood.
+    pub async fn export_scoped(&self, root: &str, depth: usize) -> Result<NarraImport, NarraError> {
+        let world = self.export_world().await?;
+        let perceptions: Vec<(RecordId, RecordId)> = self
+            .db
+            .query("SELECT VALUE [in, out] FROM perceives")
+            .await?
+            .take(0)?;
+        let extra_edges: Vec<(String, String)> = perceptions
+            .into_iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect();
+        scope_import(world, root, depth, &extra_edges)
+    }
+
     pub async fn export_world(&self) -> Result<NarraImport, NarraError> {
         let characters = self.export_characters().await?;
         let locations = self.export_locations().await?;
@@ -121,6 +140,7 @@ impl ExportService {
                 sequence: Some(e.sequence as i32),
                 date: e.date.map(|d| d.to_string()),
                 date_precision: e.date_precision,
+                narrative_status: Some(e.narrative_status).filter(|s| s != "on_page"),
             })
             .collect())
     }
@@ -180,6 +200,7 @@ impl ExportService {
                 rel_type: r.rel_type,
                 subtype: r.subtype,
                 label: r.label,
+                intentional: r.intentional.then_some(true),
             })
             .collect())
     }
@@ -232,6 +253,8 @@ impl ExportService {
                 id: Some(note_key),
                 title: note.title,
                 body: note.body,
+                kind: (note.kind != NoteKind::General).then_some(note.kind),
+                status: note.kind.has_status().then_some(note.status),
                 attach_to,
             });
         }
@@ -289,3 +312,447 @@ impl ExportService {
         Ok(specs)
     }
 }
+
+/// `table:key` with SurrealDB's key quoting removed, so IDs read from the
+/// database and IDs written by hand in YAML compare equal.
+pub(crate) fn normalize_id(default_table: &str, id: &str) -> String {
+    let (table, key) = id.split_once(':').unwrap_or((default_table, id));
+    format!(
+        "{}:{}",
+        table,
+        key.trim_matches(|c| matches!(c, '⟨' | '⟩' | '`'))
+    )
+}
+
+/// Membership test for the characters, locations, events, and scenes in a
+/// scoped export.
+pub fn scope_filter(scoped: &NarraImport) -> impl Fn(&str) -> bool {
+    let ids: HashSet<String> = scoped
+        .characters
+        .iter()
+        .filter_map(|c| Some(normalize_id("character", c.id.as_deref()?)))
+        .chain(
+            scoped
+                .locations
+                .iter()
+                .filter_map(|l| Some(normalize_id("location", l.id.as_deref()?))),
+        )
+        .chain(
+            scoped
+                .events
+                .iter()
+                .filter_map(|e| Some(normalize_id("event", e.id.as_deref()?))),
+        )
+        .chain(
+            scoped
+                .scenes
+                .iter()
+                .filter_map(|s| Some(normalize_id("scene", s.id.as_deref()?))),
+        )
+        .collect();
+    move |id| ids.contains(&normalize_id("character", id))
+}
+
+/// Restrict `world` to the neighborhood of the character `root`.
+///
+/// Characters within `depth` hops of `root` (over relationships and any
+/// `extra_edges`, e.g. perceptions) are kept, along with the relationships and
+/// knowledge among them, the scenes they appear in, and the events and
+/// locations (with their parents) those scenes and knowledge need. Notes and
+/// facts are kept when attached to a kept entity; world-wide facts (attached
+/// to nothing) always are. References to anything out of scope are dropped so
+/// the result imports cleanly on its own.
+pub fn scope_import(
+    world: NarraImport,
+    root: &str,
+    depth: usize,
+    extra_edges: &[(String, String)],
+) -> Result<NarraImport, NarraError> {
+    let root = normalize_id("character", root);
+    if !root.starts_with("character:") {
+        return Err(NarraError::Validation(format!(
+            "Scope must be a character (e.g., character:alice), got '{}'",
+            root
+        )));
+    }
+    let spec_id =
+        |table: &str, id: &Option<String>| id.as_deref().map(|id| normalize_id(table, id));
+    if !world
+        .characters
+        .iter()
+        .any(|c| spec_id("character", &c.id).as_deref() == Some(root.as_str()))
+    {
+        return Err(NarraError::NotFound {
+            entity_type: "character".to_string(),
+            id: root,
+        });
+    }
+
+    // Breadth-first walk over character-to-character edges
+    let mut adjacency: HashMap<String, Vec<String>> = HashMap::new();
+    let edges = world
+        .relationships
+        .iter()
+        .map(|r| (r.from_character_id.as_str(), r.to_character_id.as_str()))
+        .chain(extra_edges.iter().map(|(a, b)| (a.as_str(), b.as_str())));
+    for (from, to) in edges {
+        let (from, to) = (
+            normalize_id("character", from),
+            normalize_id("character", to),
+        );
+        adjacency.entry(from.clone()).or_default().push(to.clone());
+        adjacency.entry(to).or_default().push(from);
+    }
+    let mut characters: HashSet<String> = HashSet::from([root.clone()]);
+    let mut frontier = vec![root];
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for id in &frontier {
+            for neighbor in adjacency.get(id).into_iter().flatten() {
+                if characters.insert(neighbor.clone()) {
+                    next.push(neighbor.clone());
+                }
+            }
+        }
+        frontier = next;
+    }
+    let has_character = |id: &str| characters.contains(&normalize_id("character", id));
+
+    let relationships: Vec<RelationshipSpec> = world
+        .relationships
+        .into_iter()
+        .filter(|r| has_character(&r.from_character_id) && has_character(&r.to_character_id))
+        .collect();
+
+    let scenes: Vec<SceneSpec> = world
+        .scenes
+        .into_iter()
+        .filter_map(|mut scene| {
+            scene
+                .participants
+                .retain(|p| has_character(&p.character_id));
+            (!scene.participants.is_empty()).then_some(scene)
+        })
+        .collect();
+
+    let mut events: HashSet<String> = HashSet::new();
+    let mut locations: HashSet<String> = HashSet::new();
+    for scene in &scenes {
+        events.insert(normalize_id("event", &scene.event_id));
+        locations.insert(normalize_id("location", &scene.location_id));
+        locations.extend(
+            scene
+                .secondary_locations
+                .iter()
+                .map(|l| normalize_id("location", l)),
+        );
+    }
+
+    let knowledge: Vec<KnowledgeSpec> = world
+        .knowledge
+        .into_iter()
+        .filter(|k| {
+            let target = normalize_id("character", &k.target_id);
+            has_character(&k.character_id)
+                && (!target.starts_with("character:") || characters.contains(&target))
+        })
+        .map(|mut k| {
+            k.source_character_id = k.source_character_id.filter(|c| has_character(c));
+            if let Some(event) = &k.event_id {
+                events.insert(normalize_id("event", event));
+            }
+            k
+        })
+        .collect();
+
+    // Parents of kept locations, so `parent_id` still resolves
+    let parents: HashMap<String, String> = world
+        .locations
+        .iter()
+        .filter_map(|l| {
+            Some((
+                spec_id("location", &l.id)?,
+                normalize_id("location", l.parent_id.as_deref()?),
+            ))
+        })
+        .collect();
+    let mut pending: Vec<String> = locations.iter().cloned().collect();
+    while let Some(id) = pending.pop() {
+        if let Some(parent) = parents.get(&id) {
+            if locations.insert(parent.clone()) {
+                pending.push(parent.clone());
+            }
+        }
+    }
+
+    let characters_out: Vec<CharacterSpec> = world
+        .characters
+        .into_iter()
+        .filter(|c| spec_id("character", &c.id).is_some_and(|id| characters.contains(&id)))
+        .collect();
+    let locations_out: Vec<LocationSpec> = world
+        .locations
+        .into_iter()
+        .filter(|l| spec_id("location", &l.id).is_some_and(|id| locations.contains(&id)))
+        .collect();
+    let events_out: Vec<EventSpec> = world
+        .events
+        .into_iter()
+        .filter(|e| spec_id("event", &e.id).is_some_and(|id| events.contains(&id)))
+        .collect();
+
+    let mut kept: HashSet<String> = characters;
+    kept.extend(locations);
+    kept.extend(events);
+    kept.extend(scenes.iter().filter_map(|s| spec_id("scene", &s.id)));
+    let is_kept = |id: &str| kept.contains(&normalize_id("character", id));
+
+    let notes: Vec<NoteSpec> = world
+        .notes
+        .into_iter()
+        .filter_map(|mut note| {
+            note.attach_to.retain(|id| is_kept(id));
+            (!note.attach_to.is_empty()).then_some(note)
+        })
+        .collect();
+    let facts: Vec<FactSpec> = world
+        .facts
+        .into_iter()
+        .filter_map(|mut fact| {
+            if fact.applies_to.is_empty() {
+                return Some(fact);
+            }
+            fact.applies_to.retain(|link| is_kept(&link.entity_id));
+            (!fact.applies_to.is_empty()).then_some(fact)
+        })
+        .collect();
+
+    Ok(NarraImport {
+        characters: characters_out,
+        locations: locations_out,
+        events: events_out,
+        scenes,
+        relationships,
+        knowledge,
+        notes,
+        facts,
+    })
+}
+
+/// Profile keys stripped by `--redact secrets` unless `[export] secret_keys`
+/// is configured.
+pub const DEFAULT_SECRET_KEYS: &[&str] = &[
+    "secret", "secrets", "twist", "twists", "spoiler", "spoilers",
+];
+
+/// Content category removed from an export before it's shared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Redaction {
+    /// All notes (private authorial notes)
+    Notes,
+    /// Character profile entries under secret keys
+    Secrets,
+    /// Who knows what (reveals which secrets exist)
+    Knowledge,
+}
+
+impl std::str::FromStr for Redaction {
+    type Err = NarraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "notes" => Ok(Redaction::Notes),
+            "secrets" => Ok(Redaction::Secrets),
+            "knowledge" => Ok(Redaction::Knowledge),
+            _ => Err(NarraError::Validation(format!(
+                "Unknown redaction '{}' (expected notes, secrets, or knowledge)",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for Redaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Redaction::Notes => "notes",
+            Redaction::Secrets => "secrets",
+            Redaction::Knowledge => "knowledge",
+        })
+    }
+}
+
+/// Strip `redactions` from `world` in place. Profile keys match
+/// `secret_keys` case-insensitively. Returns how many items were removed.
+pub fn redact(world: &mut NarraImport, redactions: &[Redaction], secret_keys: &[String]) -> usize {
+    let mut removed = 0;
+    for redaction in redactions {
+        match redaction {
+            Redaction::Notes => removed += std::mem::take(&mut world.notes).len(),
+            Redaction::Knowledge => removed += std::mem::take(&mut world.knowledge).len(),
+            Redaction::Secrets => {
+                for profile in world
+                    .characters
+                    .iter_mut()
+                    .filter_map(|c| c.profile.as_mut())
+                {
+                    let before = profile.len();
+                    profile
+                        .retain(|key, _| !secret_keys.iter().any(|s| s.eq_ignore_ascii_case(key)));
+                    removed += before - profile.len();
+                }
+            }
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(id: &str) -> CharacterSpec {
+        CharacterSpec {
+            id: Some(id.to_string()),
+            name: id.to_string(),
+            role: None,
+            aliases: None,
+            description: None,
+            profile: None,
+        }
+    }
+
+    fn relationship(from: &str, to: &str) -> RelationshipSpec {
+        RelationshipSpec {
+            from_character_id: format!("character:{}", from),
+            to_character_id: format!("character:{}", to),
+            rel_type: "ally".to_string(),
+            subtype: None,
+            label: None,
+            intentional: None,
+        }
+    }
+
+    #[test]
+    fn test_scope_import_keeps_neighborhood_only() {
+        let world = NarraImport {
+            characters: ["alice", "bob", "carol", "dave"]
+                .iter()
+                .map(|id| character(id))
+                .collect(),
+            locations: vec![
+                LocationSpec {
+                    id: Some("city".to_string()),
+                    name: "City".to_string(),
+                    description: None,
+                    parent_id: None,
+                    loc_type: None,
+                },
+                LocationSpec {
+                    id: Some("bar".to_string()),
+                    name: "Bar".to_string(),
+                    description: None,
+                    parent_id: Some("location:city".to_string()),
+                    loc_type: None,
+                },
+            ],
+            events: vec![EventSpec {
+                id: Some("heist".to_string()),
+                title: "Heist".to_string(),
+                description: None,
+                sequence: Some(1),
+                date: None,
+                date_precision: None,
+                narrative_status: None,
+            }],
+            scenes: vec![SceneSpec {
+                id: Some("meeting".to_string()),
+                title: "Meeting".to_string(),
+                event_id: "event:heist".to_string(),
+                location_id: "location:bar".to_string(),
+                summary: None,
+                secondary_locations: vec![],
+                participants: vec![
+                    ParticipantSpec {
+                        character_id: "character:alice".to_string(),
+                        role: "pov".to_string(),
+                        notes: None,
+                    },
+                    ParticipantSpec {
+                        character_id: "character:dave".to_string(),
+                        role: "support".to_string(),
+                        notes: None,
+                    },
+                ],
+            }],
+            relationships: vec![
+                relationship("alice", "bob"),
+                relationship("bob", "carol"),
+                relationship("carol", "dave"),
+            ],
+            knowledge: vec![],
+            notes: vec![NoteSpec {
+                id: None,
+                title: "Dave's secret".to_string(),
+                body: String::new(),
+                kind: None,
+                status: None,
+                attach_to: vec!["character:dave".to_string()],
+            }],
+            facts: vec![],
+        };
+
+        let scoped = scope_import(world, "character:alice", 1, &[]).unwrap();
+        let names: Vec<&str> = scoped.characters.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["alice", "bob"]);
+        assert_eq!(scoped.relationships.len(), 1);
+        assert_eq!(scoped.scenes[0].participants.len(), 1);
+        assert_eq!(scoped.events.len(), 1);
+        // The bar's parent city comes along so parent_id still resolves
+        assert_eq!(scoped.locations.len(), 2);
+        assert!(scoped.notes.is_empty());
+    }
+
+    #[test]
+    fn test_scope_import_rejects_unknown_root() {
+        let world = NarraImport {
+            characters: vec![character("alice")],
+            ..Default::default()
+        };
+        assert!(scope_import(world.clone(), "character:zed", 2, &[]).is_err());
+        assert!(scope_import(world, "location:bar", 2, &[]).is_err());
+    }
+
+    #[test]
+    fn test_redact_strips_notes_and_secret_profile_keys() {
+        let mut alice = character("alice");
+        alice.profile = Some(HashMap::from([
+            ("wound".to_string(), vec!["Lost her brother".to_string()]),
+            ("Secret".to_string(), vec!["She is the traitor".to_string()]),
+        ]));
+        let mut world = NarraImport {
+            characters: vec![alice],
+            notes: vec![NoteSpec {
+                id: None,
+                title: "Ending".to_string(),
+                body: "Alice dies".to_string(),
+                kind: None,
+                status: None,
+                attach_to: vec![],
+            }],
+            ..Default::default()
+        };
+        let keys: Vec<String> = DEFAULT_SECRET_KEYS.iter().map(|k| k.to_string()).collect();
+        let redactions: Vec<Redaction> = "notes,secrets"
+            .split(',')
+            .map(|r| r.parse().unwrap())
+            .collect();
+
+        assert_eq!(redact(&mut world, &redactions, &keys), 2);
+        assert!(world.notes.is_empty());
+        let profile = world.characters[0].profile.as_ref().unwrap();
+        assert!(profile.contains_key("wound"));
+        assert!(!profile.contains_key("Secret"));
+        assert!("ending".parse::<Redaction>().is_err());
+    }
+}
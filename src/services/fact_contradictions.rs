@@ -0,0 +1,288 @@
+//! Contradictions between universe facts themselves.
+//!
+//! Entity checks assume the canon is coherent; this pass questions the canon.
+//! Two facts are flagged when they talk about the same thing (embedding
+//! similarity of their texts, or shared terms when no model is loaded) and
+//! take opposite stances on it: one negates what the other asserts ("Magic
+//! is forbidden" vs "Magic flows freely in the capital"), or they use
+//! opposed words ("always" vs "never"). Findings are candidates for the
+//! author to review, not violations.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::db::connection::NarraDb;
+use crate::embedding::EmbeddingService;
+use crate::models::fact::{list_facts, UniverseFact};
+use crate::utils::math::cosine_similarity;
+use crate::NarraError;
+
+/// Words that flip a statement's polarity.
+const NEGATIONS: &[&str] = &[
+    "no",
+    "not",
+    "never",
+    "none",
+    "nobody",
+    "nothing",
+    "nowhere",
+    "cannot",
+    "without",
+    "lacks",
+    "forbidden",
+    "prohibited",
+    "banned",
+    "impossible",
+    "outlawed",
+];
+
+/// Word pairs that take opposite stances without a negation.
+const ANTONYMS: &[(&str, &str)] = &[
+    ("always", "never"),
+    ("all", "none"),
+    ("everyone", "nobody"),
+    ("possible", "impossible"),
+    ("allowed", "forbidden"),
+    ("permitted", "prohibited"),
+    ("legal", "illegal"),
+    ("alive", "dead"),
+    ("mortal", "immortal"),
+    ("common", "rare"),
+    ("visible", "invisible"),
+];
+
+/// Too common to say two facts share a topic.
+const STOPWORDS: &[&str] = &[
+    "that", "this", "with", "from", "they", "them", "their", "there", "have", "been", "were",
+    "will", "would", "only", "also", "into", "than", "when", "where", "which", "while", "every",
+    "each", "some", "such", "more", "most", "must", "should", "does", "done",
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct FactContradictionOptions {
+    /// Embedding similarity at or above which two facts share a topic.
+    pub similarity: f32,
+    /// Share of terms in common, used instead when no model is loaded.
+    pub term_overlap: f32,
+}
+
+impl Default for FactContradictionOptions {
+    fn default() -> Self {
+        Self {
+            similarity: 0.7,
+            term_overlap: 0.25,
+        }
+    }
+}
+
+/// Two facts that likely contradict each other.
+#[derive(Debug, Clone, Serialize)]
+pub struct FactContradiction {
+    pub a_id: String,
+    pub a_title: String,
+    pub b_id: String,
+    pub b_title: String,
+    /// Embedding similarity, or term overlap when no model is loaded
+    pub similarity: f32,
+    /// What they both talk about
+    pub shared_terms: Vec<String>,
+    /// Why they look opposed
+    pub reason: String,
+}
+
+/// A fact's text split for comparison.
+struct Statement {
+    negated: bool,
+    words: BTreeSet<String>,
+    terms: BTreeSet<String>,
+}
+
+impl Statement {
+    fn parse(text: &str) -> Self {
+        let words: BTreeSet<String> = text
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric() && c != '\'')
+            .filter(|w| !w.is_empty())
+            .map(|w| w.trim_matches('\'').to_string())
+            .collect();
+        let negated = words
+            .iter()
+            .any(|w| NEGATIONS.contains(&w.as_str()) || w.ends_with("n't"));
+        let terms = words
+            .iter()
+            .filter(|w| w.len() > 3 && !w.contains('\''))
+            .filter(|w| !NEGATIONS.contains(&w.as_str()) && !STOPWORDS.contains(&w.as_str()))
+            .filter(|w| !ANTONYMS.iter().any(|(a, b)| w == a || w == b))
+            .map(|w| w.strip_suffix('s').unwrap_or(w).to_string())
+            .collect();
+        Self {
+            negated,
+            words,
+            terms,
+        }
+    }
+
+    fn term_overlap(&self, other: &Statement) -> f32 {
+        let union = self.terms.union(&other.terms).count();
+        if union == 0 {
+            return 0.0;
+        }
+        self.terms.intersection(&other.terms).count() as f32 / union as f32
+    }
+}
+
+fn fact_text(fact: &UniverseFact) -> String {
+    format!("{}. {}", fact.title, fact.description)
+}
+
+/// Why two statements about the same topic look opposed, if they do.
+fn opposition(a: &Statement, b: &Statement) -> Option<String> {
+    if a.negated != b.negated {
+        return Some("one negates what the other asserts".to_string());
+    }
+    ANTONYMS.iter().find_map(|(x, y)| {
+        let crossed = (a.words.contains(*x) && b.words.contains(*y))
+            || (a.words.contains(*y) && b.words.contains(*x));
+        crossed.then(|| format!("opposed terms '{}' / '{}'", x, y))
+    })
+}
+
+/// Candidate contradictions among `facts`, given each pair's similarity.
+fn find_contradictions(
+    facts: &[UniverseFact],
+    similarity: impl Fn(usize, usize, &Statement, &Statement) -> f32,
+    threshold: f32,
+) -> Vec<FactContradiction> {
+    let statements: Vec<Statement> = facts
+        .iter()
+        .map(|f| Statement::parse(&fact_text(f)))
+        .collect();
+
+    let mut found = Vec::new();
+    for i in 0..facts.len() {
+        for j in (i + 1)..facts.len() {
+            let (a, b) = (&statements[i], &statements[j]);
+            let shared: Vec<String> = a.terms.intersection(&b.terms).cloned().collect();
+            if shared.is_empty() {
+                continue;
+            }
+            let Some(reason) = opposition(a, b) else {
+                continue;
+            };
+            let score = similarity(i, j, a, b);
+            if score < threshold {
+                continue;
+            }
+            found.push(FactContradiction {
+                a_id: facts[i].id.to_string(),
+                a_title: facts[i].title.clone(),
+                b_id: facts[j].id.to_string(),
+                b_title: facts[j].title.clone(),
+                similarity: score,
+                shared_terms: shared,
+                reason,
+            });
+        }
+    }
+    found.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    found
+}
+
+pub struct FactContradictionService {
+    db: Arc<NarraDb>,
+    embedding: Arc<dyn EmbeddingService + Send + Sync>,
+}
+
+impl FactContradictionService {
+    pub fn new(db: Arc<NarraDb>, embedding: Arc<dyn EmbeddingService + Send + Sync>) -> Self {
+        Self { db, embedding }
+    }
+
+    /// Whether topics are matched by embedding rather than shared terms.
+    pub fn is_semantic(&self) -> bool {
+        self.embedding.is_available()
+    }
+
+    /// Fact pairs likely to contradict each other, most similar first.
+    pub async fn report(
+        &self,
+        options: &FactContradictionOptions,
+    ) -> Result<Vec<FactContradiction>, NarraError> {
+        let facts = list_facts(&self.db).await?;
+        if facts.len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        if !self.is_semantic() {
+            return Ok(find_contradictions(
+                &facts,
+                |_, _, a, b| a.term_overlap(b),
+                options.term_overlap,
+            ));
+        }
+        let texts: Vec<String> = facts.iter().map(fact_text).collect();
+        let embeddings = self.embedding.embed_batch(&texts).await?;
+        Ok(find_contradictions(
+            &facts,
+            |i, j, _, _| cosine_similarity(&embeddings[i], &embeddings[j]),
+            options.similarity,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fact::EnforcementLevel;
+
+    fn fact(key: &str, title: &str, description: &str) -> UniverseFact {
+        UniverseFact {
+            id: surrealdb::RecordId::from(("universe_fact", key)),
+            title: title.to_string(),
+            description: description.to_string(),
+            categories: vec![],
+            enforcement_level: EnforcementLevel::Warning,
+            scope: None,
+            exceptions: vec![],
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_finds_opposed_facts_on_shared_topic() {
+        let facts = vec![
+            fact("ban", "No magic", "Magic is forbidden in the empire."),
+            fact(
+                "court",
+                "Court magic",
+                "Magic flows freely at the empire court.",
+            ),
+            fact("tides", "Tides", "The tides never turn at night."),
+            fact("moons", "Moons", "Two moons always rise together."),
+            fact("sea", "Night tides", "The tides always turn at night."),
+        ];
+        let found = find_contradictions(&facts, |_, _, a, b| a.term_overlap(b), 0.2);
+        let pairs: Vec<(&str, &str)> = found
+            .iter()
+            .map(|c| (c.a_id.as_str(), c.b_id.as_str()))
+            .collect();
+
+        assert!(pairs.contains(&("universe_fact:ban", "universe_fact:court")));
+        let tides = found
+            .iter()
+            .find(|c| c.a_id == "universe_fact:tides")
+            .expect("tides pair");
+        assert_eq!(tides.b_id, "universe_fact:sea");
+        assert!(tides.shared_terms.contains(&"tide".to_string()));
+        // Same stance, or nothing in common
+        assert!(!pairs
+            .iter()
+            .any(|(a, b)| a.ends_with("moons") || b.ends_with("moons")));
+
+        // Below the similarity threshold nothing is reported
+        assert!(find_contradictions(&facts, |_, _, _, _| 0.1, 0.5).is_empty());
+    }
+}
@@ -0,0 +1,451 @@
+//! Candidate universe facts from free-form notes.
+//!
+//! Worldbuilding often starts as a long note. This pass splits a note into
+//! sentences, keeps the ones that read like rules ("Magic always costs
+//! blood", "No commoner may own a horse"), and proposes each as a fact: a
+//! category from the theme classifier (or keywords when it isn't loaded), an
+//! enforcement level from how absolute the wording is, and the characters and
+//! locations it mentions (named entity recognition when loaded, known names
+//! otherwise) as entities to link.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::db::connection::NarraDb;
+use crate::models::fact::{list_facts, EnforcementLevel, FactCategory};
+use crate::models::note::get_note;
+use crate::services::ner::NerService;
+use crate::services::theme::ThemeService;
+use crate::NarraError;
+
+/// Words that make a sentence read like a rule of the world.
+const RULE_MARKERS: &[&str] = &[
+    "must",
+    "cannot",
+    "can't",
+    "never",
+    "always",
+    "only",
+    "forbidden",
+    "prohibited",
+    "banned",
+    "illegal",
+    "requires",
+    "require",
+    "impossible",
+    "every",
+    "all",
+    "nobody",
+    "law",
+    "laws",
+    "rule",
+    "rules",
+    "may not",
+    "no one",
+    "is not allowed",
+];
+
+/// Absolute wording: suggests strict enforcement.
+const ABSOLUTE: &[&str] = &[
+    "never",
+    "always",
+    "cannot",
+    "can't",
+    "impossible",
+    "no one",
+    "nobody",
+    "every",
+    "must",
+];
+
+/// Hedged wording: suggests the fact is informational.
+const HEDGED: &[&str] = &[
+    "usually",
+    "often",
+    "rarely",
+    "mostly",
+    "tend",
+    "tends",
+    "typically",
+    "seldom",
+    "most",
+];
+
+/// Category hypotheses for the theme classifier, with keyword fallbacks.
+const CATEGORIES: &[(&str, &str, &[&str])] = &[
+    (
+        "physics_magic",
+        "magic or the laws of nature",
+        &[
+            "magic", "spell", "mage", "wizard", "gravity", "ritual", "curse", "mana", "sorcery",
+            "enchant", "portal",
+        ],
+    ),
+    (
+        "social_cultural",
+        "social customs, laws, or culture",
+        &[
+            "law",
+            "custom",
+            "noble",
+            "commoner",
+            "marriage",
+            "religion",
+            "temple",
+            "guild",
+            "king",
+            "queen",
+            "caste",
+            "tradition",
+            "court",
+            "illegal",
+        ],
+    ),
+    (
+        "technology",
+        "technology, machines, or tools",
+        &[
+            "machine", "engine", "gun", "ship", "device", "steam", "electric", "weapon", "forge",
+            "computer", "vehicle",
+        ],
+    ),
+];
+
+/// Lowest classifier score that assigns a category.
+const CATEGORY_THRESHOLD: f32 = 0.5;
+
+/// Sentences shorter than this are too thin to be a rule.
+const MIN_WORDS: usize = 4;
+
+/// A name mentioned in a candidate, resolved to an entity when it matches one.
+#[derive(Debug, Clone, Serialize)]
+pub struct FactMention {
+    pub text: String,
+    /// NER label (PER, LOC, ORG, MISC), or the table of a matched known name
+    pub label: String,
+    pub entity_id: Option<String>,
+}
+
+/// A proposed universe fact.
+#[derive(Debug, Clone, Serialize)]
+pub struct FactCandidate {
+    pub title: String,
+    pub description: String,
+    pub categories: Vec<FactCategory>,
+    pub enforcement_level: EnforcementLevel,
+    pub mentions: Vec<FactMention>,
+}
+
+impl FactCandidate {
+    /// Entities to link the fact to on acceptance.
+    pub fn entity_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .mentions
+            .iter()
+            .filter_map(|m| m.entity_id.clone())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+}
+
+fn contains_phrase(sentence: &str, phrase: &str) -> bool {
+    let padded = format!(" {} ", sentence);
+    padded.contains(&format!(" {} ", phrase))
+}
+
+/// Lowercased, punctuation stripped to spaces (apostrophes kept).
+fn normalize(sentence: &str) -> String {
+    sentence
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split text into sentences, keeping list items and headings apart.
+fn sentences(text: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    for line in text.lines() {
+        let line = line.trim().trim_start_matches(['-', '*', '#', '>']).trim();
+        let mut current = String::new();
+        for c in line.chars() {
+            current.push(c);
+            if matches!(c, '.' | '!' | '?' | ';') {
+                found.push(std::mem::take(&mut current));
+            }
+        }
+        found.push(current);
+    }
+    found
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| s.split_whitespace().count() >= MIN_WORDS)
+        .collect()
+}
+
+/// Hedged sentences count too: a norm that usually holds is still canon.
+fn is_rule(sentence: &str) -> bool {
+    let normalized = normalize(sentence);
+    RULE_MARKERS
+        .iter()
+        .chain(HEDGED)
+        .any(|m| contains_phrase(&normalized, m))
+}
+
+fn suggest_enforcement(sentence: &str) -> EnforcementLevel {
+    let normalized = normalize(sentence);
+    if HEDGED.iter().any(|w| contains_phrase(&normalized, w)) {
+        EnforcementLevel::Informational
+    } else if ABSOLUTE.iter().any(|w| contains_phrase(&normalized, w)) {
+        EnforcementLevel::Strict
+    } else {
+        EnforcementLevel::Warning
+    }
+}
+
+fn category(name: &str) -> FactCategory {
+    match name {
+        "physics_magic" => FactCategory::PhysicsMagic,
+        "social_cultural" => FactCategory::SocialCultural,
+        "technology" => FactCategory::Technology,
+        other => FactCategory::Custom(other.to_string()),
+    }
+}
+
+fn keyword_categories(sentence: &str) -> Vec<FactCategory> {
+    let normalized = normalize(sentence);
+    CATEGORIES
+        .iter()
+        .filter(|(_, _, keywords)| {
+            normalized
+                .split(' ')
+                .any(|w| keywords.iter().any(|k| w.starts_with(k)))
+        })
+        .map(|(name, _, _)| category(name))
+        .collect()
+}
+
+/// A short title: the sentence's first words, without the trailing period.
+fn title_for(sentence: &str) -> String {
+    const MAX_WORDS: usize = 8;
+    let words: Vec<&str> = sentence
+        .trim_end_matches(['.', '!', '?', ';'])
+        .split_whitespace()
+        .collect();
+    if words.len() <= MAX_WORDS {
+        words.join(" ")
+    } else {
+        format!("{}…", words[..MAX_WORDS].join(" "))
+    }
+}
+
+/// Rule-like sentences of `text` as candidates, before classification.
+fn rule_candidates(text: &str) -> Vec<FactCandidate> {
+    sentences(text)
+        .into_iter()
+        .filter(|s| is_rule(s))
+        .map(|s| FactCandidate {
+            title: title_for(&s),
+            categories: keyword_categories(&s),
+            enforcement_level: suggest_enforcement(&s),
+            mentions: Vec::new(),
+            description: s,
+        })
+        .collect()
+}
+
+pub struct FactExtractionService {
+    db: Arc<NarraDb>,
+    theme: Arc<dyn ThemeService + Send + Sync>,
+    ner: Arc<dyn NerService + Send + Sync>,
+}
+
+impl FactExtractionService {
+    pub fn new(
+        db: Arc<NarraDb>,
+        theme: Arc<dyn ThemeService + Send + Sync>,
+        ner: Arc<dyn NerService + Send + Sync>,
+    ) -> Self {
+        Self { db, theme, ner }
+    }
+
+    /// Candidate facts from a note, skipping ones already in the world.
+    pub async fn extract_from_note(&self, note_id: &str) -> Result<Vec<FactCandidate>, NarraError> {
+        let key = note_id.trim_start_matches("note:");
+        let note = get_note(&self.db, key)
+            .await?
+            .ok_or_else(|| NarraError::NotFound {
+                entity_type: "note".to_string(),
+                id: key.to_string(),
+            })?;
+
+        let existing: Vec<String> = list_facts(&self.db)
+            .await?
+            .into_iter()
+            .map(|f| normalize(&f.description))
+            .collect();
+        let mut candidates = rule_candidates(&note.body);
+        candidates.retain(|c| !existing.contains(&normalize(&c.description)));
+
+        let known = self.known_names().await?;
+        for candidate in &mut candidates {
+            self.classify(candidate).await;
+            candidate.mentions = self.mentions(&candidate.description, &known).await;
+        }
+        Ok(candidates)
+    }
+
+    /// Replace keyword categories with the classifier's, when it's loaded.
+    async fn classify(&self, candidate: &mut FactCandidate) {
+        if !self.theme.is_available() {
+            return;
+        }
+        let hypotheses: Vec<String> = CATEGORIES.iter().map(|(_, h, _)| h.to_string()).collect();
+        match self
+            .theme
+            .classify_themes(&candidate.description, Some(&hypotheses))
+            .await
+        {
+            Ok(output) => {
+                let classified: Vec<FactCategory> = output
+                    .themes
+                    .iter()
+                    .filter(|t| t.score >= CATEGORY_THRESHOLD)
+                    .filter_map(|t| CATEGORIES.iter().find(|(_, h, _)| *h == t.label))
+                    .map(|(name, _, _)| category(name))
+                    .collect();
+                if !classified.is_empty() {
+                    candidate.categories = classified;
+                }
+            }
+            Err(e) => tracing::debug!("Keeping keyword categories: {}", e),
+        }
+    }
+
+    /// Character and location names with their IDs and tables.
+    async fn known_names(&self) -> Result<Vec<(String, String, String)>, NarraError> {
+        #[derive(serde::Deserialize)]
+        struct Named {
+            id: String,
+            name: String,
+        }
+        let mut result = self
+            .db
+            .query("SELECT type::string(id) AS id, name FROM character")
+            .query("SELECT type::string(id) AS id, name FROM location")
+            .await?;
+        let characters: Vec<Named> = result.take(0)?;
+        let locations: Vec<Named> = result.take(1)?;
+        Ok(characters
+            .into_iter()
+            .map(|n| (n, "character"))
+            .chain(locations.into_iter().map(|n| (n, "location")))
+            .filter(|(n, _)| !n.name.trim().is_empty())
+            .map(|(n, table)| (n.name, n.id, table.to_string()))
+            .collect())
+    }
+
+    /// Names in `sentence`: known names found verbatim, plus NER spans when
+    /// the model is loaded.
+    async fn mentions(
+        &self,
+        sentence: &str,
+        known: &[(String, String, String)],
+    ) -> Vec<FactMention> {
+        let normalized = normalize(sentence);
+        let find = |text: &str| {
+            let text = normalize(text);
+            known
+                .iter()
+                .find(|(name, _, _)| normalize(name) == text)
+                .map(|(_, id, _)| id.clone())
+        };
+        let mut mentions: Vec<FactMention> = known
+            .iter()
+            .filter(|(name, _, _)| contains_phrase(&normalized, &normalize(name)))
+            .map(|(name, id, table)| FactMention {
+                text: name.clone(),
+                label: table.clone(),
+                entity_id: Some(id.clone()),
+            })
+            .collect();
+
+        if self.ner.is_available() {
+            match self.ner.extract_entities(sentence).await {
+                Ok(output) => {
+                    for entity in output.entities {
+                        if mentions
+                            .iter()
+                            .any(|m| normalize(&m.text) == normalize(&entity.text))
+                        {
+                            continue;
+                        }
+                        mentions.push(FactMention {
+                            entity_id: find(&entity.text),
+                            text: entity.text,
+                            label: entity.label,
+                        });
+                    }
+                }
+                Err(e) => tracing::debug!("Skipping NER mentions: {}", e),
+            }
+        }
+        mentions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_candidates_from_dump() {
+        let dump = "# Worldbuilding\n\
+            The capital sits on three rivers. Magic always costs the caster blood.\n\
+            - No one may carry a steam pistol inside the walls\n\
+            - Nobles usually marry within their guild.\n\
+            Rain.";
+        let candidates = rule_candidates(dump);
+        let found: Vec<(&str, EnforcementLevel)> = candidates
+            .iter()
+            .map(|c| (c.description.as_str(), c.enforcement_level))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "Magic always costs the caster blood.",
+                    EnforcementLevel::Strict
+                ),
+                (
+                    "No one may carry a steam pistol inside the walls",
+                    EnforcementLevel::Strict
+                ),
+                (
+                    "Nobles usually marry within their guild.",
+                    EnforcementLevel::Informational
+                ),
+            ]
+        );
+        assert_eq!(candidates[0].categories, vec![FactCategory::PhysicsMagic]);
+        assert_eq!(candidates[1].categories, vec![FactCategory::Technology]);
+        assert_eq!(candidates[2].categories, vec![FactCategory::SocialCultural]);
+        assert_eq!(candidates[0].title, "Magic always costs the caster blood");
+        assert_eq!(
+            title_for("One two three four five six seven eight nine."),
+            "One two three four five six seven eight…"
+        );
+    }
+}
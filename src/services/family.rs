@@ -0,0 +1,175 @@
+//! Family templates: the full set of kinship edges for a household.
+//!
+//! Family edges are `relates_to` with `rel_type = "family"`; the subtype on
+//! an edge A -> B says what A is to B ("parent" means A is B's parent).
+//! Each kinship is stored in both directions with reciprocal subtypes (see
+//! `services::reciprocity`). In-laws are derived from spouses already in the world.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::services::reciprocity::reciprocal;
+use crate::NarraError;
+
+/// Relationship type family edges use.
+pub const FAMILY: &str = "family";
+
+/// One planned kinship edge: `from` is `subtype` to `to`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FamilyEdge {
+    /// Character key (no `character:` prefix)
+    pub from: String,
+    pub to: String,
+    pub subtype: String,
+    /// Derived from kin already in the world rather than the family itself
+    pub derived: bool,
+}
+
+#[derive(Deserialize)]
+struct FamilyRow {
+    #[serde(rename = "in")]
+    from: RecordId,
+    #[serde(rename = "out")]
+    to: RecordId,
+    subtype: Option<String>,
+}
+
+/// Existing family edges touching any of `keys`, as (from, to, subtype).
+async fn existing_edges(
+    db: &NarraDb,
+    keys: &[String],
+) -> Result<Vec<(String, String, String)>, NarraError> {
+    let ids: Vec<RecordId> = keys
+        .iter()
+        .map(|k| RecordId::from(("character", k.as_str())))
+        .collect();
+    let mut result = db
+        .query(
+            "SELECT in, out, subtype FROM relates_to \
+             WHERE rel_type = $family AND (in IN $ids OR out IN $ids)",
+        )
+        .bind(("family", FAMILY))
+        .bind(("ids", ids))
+        .await?;
+    let rows: Vec<FamilyRow> = result.take(0)?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| Some((r.from.key().to_string(), r.to.key().to_string(), r.subtype?)))
+        .collect())
+}
+
+/// Plan the kinship edges for parents and their children.
+///
+/// Parents become each other's spouses (unless `spouses` is false), parents
+/// and children get parent/child edges, and children are siblings. In-laws
+/// come from the world: a child's existing spouse becomes a child-in-law of
+/// the parents and a sibling-in-law of the other children; a parent's
+/// existing siblings and parents become in-laws of the other parents, and
+/// a parent's existing parents become the children's grandparents.
+/// Edges that already exist are left out.
+pub async fn plan_family(
+    db: &NarraDb,
+    parents: &[String],
+    children: &[String],
+    spouses: bool,
+) -> Result<Vec<FamilyEdge>, NarraError> {
+    let mut members: Vec<String> = parents.to_vec();
+    members.extend(children.iter().cloned());
+    let unique: HashSet<&String> = members.iter().collect();
+    if unique.len() != members.len() {
+        return Err(NarraError::Validation(
+            "A character can appear only once in a family".to_string(),
+        ));
+    }
+    if parents.is_empty() || children.is_empty() {
+        return Err(NarraError::Validation(
+            "A family needs at least one parent and one child".to_string(),
+        ));
+    }
+
+    let existing = existing_edges(db, &members).await?;
+    let related = |who: &str, subtype: &str| -> Vec<String> {
+        existing
+            .iter()
+            .filter(|(from, to, s)| from == who && s == subtype && !members.contains(to))
+            .map(|(_, to, _)| to.clone())
+            .collect()
+    };
+
+    let mut edges: Vec<FamilyEdge> = Vec::new();
+    let mut add = |a: &str, b: &str, subtype: &str, derived: bool| {
+        for (from, to, subtype) in [
+            (a, b, subtype),
+            (b, a, reciprocal(subtype).unwrap_or(subtype)),
+        ] {
+            let edge = FamilyEdge {
+                from: from.to_string(),
+                to: to.to_string(),
+                subtype: subtype.to_string(),
+                derived,
+            };
+            let exists = existing
+                .iter()
+                .any(|(f, t, s)| f == from && t == to && s == subtype)
+                || edges
+                    .iter()
+                    .any(|e| e.from == from && e.to == to && e.subtype == subtype);
+            if !exists {
+                edges.push(edge);
+            }
+        }
+    };
+
+    for (i, a) in parents.iter().enumerate() {
+        if spouses {
+            for b in &parents[i + 1..] {
+                add(a, b, "spouse", false);
+            }
+        }
+        for child in children {
+            add(a, child, "parent", false);
+        }
+    }
+    for (i, a) in children.iter().enumerate() {
+        for b in &children[i + 1..] {
+            add(a, b, "sibling", false);
+        }
+    }
+
+    // In-laws through the children's spouses
+    for child in children {
+        for spouse in related(child, "spouse") {
+            for parent in parents {
+                add(parent, &spouse, "parent-in-law", true);
+            }
+            for sibling in children.iter().filter(|c| *c != child) {
+                add(sibling, &spouse, "sibling-in-law", true);
+            }
+        }
+    }
+
+    for parent in parents {
+        // The parents' own parents are the children's grandparents
+        for grandparent in related(parent, "child") {
+            for child in children {
+                add(&grandparent, child, "grandparent", true);
+            }
+        }
+        // In-laws through the other parents' families
+        if spouses {
+            for other in parents.iter().filter(|p| *p != parent) {
+                for sibling in related(other, "sibling") {
+                    add(parent, &sibling, "sibling-in-law", true);
+                }
+                for in_law in related(other, "child") {
+                    add(&in_law, parent, "parent-in-law", true);
+                }
+            }
+        }
+    }
+
+    Ok(edges)
+}
@@ -0,0 +1,243 @@
+//! Foreshadowing validation: every payoff needs a setup earlier in sequence.
+//!
+//! Events are placed by their sequence, scenes by their event's sequence and
+//! knowledge by the earliest event anyone learns it at. Payoffs whose setups
+//! all come at or after them, or that cannot be placed, are reported.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::models::foreshadow;
+use crate::NarraError;
+
+/// One side of a foreshadowing link, placed on the timeline.
+#[derive(Debug, Clone, Serialize)]
+pub struct Placed {
+    pub id: String,
+    pub label: String,
+    pub sequence: Option<i64>,
+}
+
+/// A setup -> payoff link as input to validation.
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub setup: Placed,
+    pub payoff: Placed,
+}
+
+/// A setup of a payoff and how far ahead of it it comes.
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupLink {
+    pub setup: Placed,
+    /// Payoff sequence minus setup sequence; positive when the setup is earlier.
+    pub distance: Option<i64>,
+}
+
+/// Validation result for one payoff.
+#[derive(Debug, Clone, Serialize)]
+pub struct PayoffCheck {
+    pub payoff: Placed,
+    pub setups: Vec<SetupLink>,
+    /// "ok", "no_earlier_setup" or "unplaced"
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForeshadowingReport {
+    pub links: usize,
+    pub payoffs: Vec<PayoffCheck>,
+}
+
+impl ForeshadowingReport {
+    pub fn problems(&self) -> impl Iterator<Item = &PayoffCheck> {
+        self.payoffs.iter().filter(|p| p.status != "ok")
+    }
+}
+
+/// Group links by payoff and check each has an earlier setup.
+pub fn validate(links: Vec<Link>) -> ForeshadowingReport {
+    let count = links.len();
+    let mut by_payoff: BTreeMap<String, (Placed, Vec<SetupLink>)> = BTreeMap::new();
+    for link in links {
+        let distance = match (link.setup.sequence, link.payoff.sequence) {
+            (Some(s), Some(p)) => Some(p - s),
+            _ => None,
+        };
+        by_payoff
+            .entry(link.payoff.id.clone())
+            .or_insert_with(|| (link.payoff, Vec::new()))
+            .1
+            .push(SetupLink {
+                setup: link.setup,
+                distance,
+            });
+    }
+
+    let mut payoffs: Vec<PayoffCheck> = by_payoff
+        .into_values()
+        .map(|(payoff, mut setups)| {
+            setups.sort_by_key(|s| s.setup.sequence);
+            let status = if payoff.sequence.is_none() {
+                "unplaced"
+            } else if setups.iter().any(|s| s.distance.is_some_and(|d| d > 0)) {
+                "ok"
+            } else if setups.iter().all(|s| s.setup.sequence.is_none()) {
+                "unplaced"
+            } else {
+                "no_earlier_setup"
+            };
+            PayoffCheck {
+                payoff,
+                setups,
+                status: status.to_string(),
+            }
+        })
+        .collect();
+
+    // Problems first, then timeline order
+    payoffs.sort_by(|a, b| {
+        (a.status == "ok")
+            .cmp(&(b.status == "ok"))
+            .then(a.payoff.sequence.cmp(&b.payoff.sequence))
+    });
+    ForeshadowingReport {
+        links: count,
+        payoffs,
+    }
+}
+
+pub struct ForeshadowingService {
+    db: Arc<NarraDb>,
+}
+
+impl ForeshadowingService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    pub async fn report(&self) -> Result<ForeshadowingReport, NarraError> {
+        #[derive(Deserialize)]
+        struct Row {
+            id: RecordId,
+            label: String,
+            sequence: Option<i64>,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, title AS label, sequence FROM event; \
+                 SELECT id, title AS label, event.sequence AS sequence FROM scene; \
+                 SELECT id, fact AS label, \
+                 math::min((SELECT VALUE event.sequence FROM knows \
+                 WHERE out = $parent.id AND event != NONE)) AS sequence FROM knowledge",
+            )
+            .await?;
+        let mut placed: HashMap<String, Placed> = HashMap::new();
+        for index in 0..3 {
+            let rows: Vec<Row> = response.take(index)?;
+            for row in rows {
+                let id = row.id.to_string();
+                placed.insert(
+                    id.clone(),
+                    Placed {
+                        id,
+                        label: row.label,
+                        sequence: row.sequence,
+                    },
+                );
+            }
+        }
+
+        let lookup = |id: &RecordId| {
+            let id = id.to_string();
+            placed.get(&id).cloned().unwrap_or(Placed {
+                label: id.clone(),
+                id,
+                sequence: None,
+            })
+        };
+        let links = foreshadow::list_foreshadows(&self.db)
+            .await?
+            .iter()
+            .map(|edge| Link {
+                setup: lookup(&edge.setup),
+                payoff: lookup(&edge.payoff),
+            })
+            .collect();
+
+        Ok(validate(links))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placed(id: &str, sequence: Option<i64>) -> Placed {
+        Placed {
+            id: id.to_string(),
+            label: id.to_string(),
+            sequence,
+        }
+    }
+
+    fn link(setup: Placed, payoff: Placed) -> Link {
+        Link { setup, payoff }
+    }
+
+    #[test]
+    fn test_payoffs_need_an_earlier_setup() {
+        let report = validate(vec![
+            link(
+                placed("scene:dream", Some(5)),
+                placed("event:fall", Some(40)),
+            ),
+            link(
+                placed("scene:omen", Some(50)),
+                placed("event:fall", Some(40)),
+            ),
+            link(
+                placed("event:late", Some(30)),
+                placed("event:reveal", Some(20)),
+            ),
+            link(
+                placed("knowledge:rumor", None),
+                placed("scene:twist", Some(60)),
+            ),
+            link(
+                placed("event:start", Some(1)),
+                placed("knowledge:secret", None),
+            ),
+        ]);
+
+        assert_eq!(report.links, 5);
+        let status: Vec<(&str, &str)> = report
+            .payoffs
+            .iter()
+            .map(|p| (p.payoff.id.as_str(), p.status.as_str()))
+            .collect();
+        assert_eq!(
+            status,
+            vec![
+                ("knowledge:secret", "unplaced"),
+                ("event:reveal", "no_earlier_setup"),
+                ("scene:twist", "unplaced"),
+                ("event:fall", "ok"),
+            ]
+        );
+
+        let fall = report
+            .payoffs
+            .iter()
+            .find(|p| p.payoff.id == "event:fall")
+            .unwrap();
+        assert_eq!(fall.setups[0].distance, Some(35));
+        assert_eq!(fall.setups[1].distance, Some(-10));
+        assert_eq!(report.problems().count(), 3);
+    }
+}
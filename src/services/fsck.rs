@@ -0,0 +1,433 @@
+//! Database integrity check for `narra world fsck`.
+//!
+//! Goes further than `narra doctor`'s integrity check: it lists the records
+//! at fault, and with `fix` repairs what can be repaired without losing
+//! anything authored. Derived data (dangling edges, cached annotations and
+//! arc snapshots of deleted entities, embeddings from another model) is
+//! removed or queued for re-embedding; authored data is only reported.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::services::doctor::EDGE_TABLES;
+use crate::NarraError;
+
+/// Tables an annotation or fact exception may point at.
+const ENTITY_TABLES: &[&str] = &[
+    "character",
+    "location",
+    "event",
+    "scene",
+    "knowledge",
+    "note",
+    "universe_fact",
+    "term",
+    "thread",
+    "relates_to",
+    "perceives",
+];
+
+/// Embedding fields, by table. All of these tables have `embedding_stale`,
+/// which backfill picks up after a bad vector is cleared.
+const EMBEDDING_FIELDS: &[(&str, &str)] = &[
+    ("character", "embedding"),
+    ("character", "identity_embedding"),
+    ("character", "psychology_embedding"),
+    ("character", "social_embedding"),
+    ("character", "narrative_embedding"),
+    ("location", "embedding"),
+    ("event", "embedding"),
+    ("scene", "embedding"),
+    ("knowledge", "embedding"),
+    ("term", "embedding"),
+    ("relates_to", "embedding"),
+    ("perceives", "embedding"),
+];
+
+/// Rows changed per query.
+const FIX_BATCH: usize = 500;
+
+/// One problem found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FsckFinding {
+    pub check: String,
+    pub record: String,
+    pub detail: String,
+    /// Whether `--fix` can repair it
+    pub fixable: bool,
+    pub repaired: bool,
+}
+
+/// Totals for one check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FsckCheck {
+    pub name: String,
+    pub scanned: usize,
+    pub problems: usize,
+    pub repaired: usize,
+}
+
+/// Outcome of a check run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FsckReport {
+    pub checks: Vec<FsckCheck>,
+    pub findings: Vec<FsckFinding>,
+    /// Estimated storage freed by deleted records and cleared embeddings
+    pub reclaimed_bytes: u64,
+    pub fixed: bool,
+}
+
+impl FsckReport {
+    /// Problems left after the run.
+    pub fn unresolved(&self) -> usize {
+        self.findings.iter().filter(|f| !f.repaired).count()
+    }
+
+    pub fn fixable(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.fixable && !f.repaired)
+            .count()
+    }
+}
+
+/// A record a check flagged, with its approximate size.
+#[derive(Debug, Deserialize)]
+struct Flagged {
+    id: RecordId,
+    #[serde(default)]
+    bytes: u64,
+    #[serde(default)]
+    dimensions: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnnotationRef {
+    id: RecordId,
+    entity_id: String,
+    #[serde(default)]
+    bytes: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct FactExceptions {
+    id: RecordId,
+    #[serde(default)]
+    entity_ids: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CountResult {
+    count: usize,
+}
+
+/// `table:key` with a known table, split into its parts.
+fn parse_entity_ref(entity_id: &str) -> Option<(&str, &str)> {
+    let (table, key) = entity_id.split_once(':')?;
+    let key = key
+        .strip_prefix('⟨')
+        .and_then(|k| k.strip_suffix('⟩'))
+        .unwrap_or(key);
+    (ENTITY_TABLES.contains(&table) && !key.is_empty() && !key.contains(char::is_whitespace))
+        .then_some((table, key))
+}
+
+/// Checks the world and, with `fix`, repairs what it safely can.
+/// `dimensions` is the current embedding model's vector size.
+pub async fn fsck(db: &NarraDb, dimensions: usize, fix: bool) -> Result<FsckReport, NarraError> {
+    let mut run = Run {
+        db,
+        fix,
+        report: FsckReport {
+            checks: Vec::new(),
+            findings: Vec::new(),
+            reclaimed_bytes: 0,
+            fixed: fix,
+        },
+        keys: HashMap::new(),
+    };
+    run.check_edges().await?;
+    run.check_references().await?;
+    run.check_embeddings(dimensions).await?;
+    run.check_arc_snapshots(dimensions).await?;
+    Ok(run.report)
+}
+
+struct Run<'a> {
+    db: &'a NarraDb,
+    fix: bool,
+    report: FsckReport,
+    /// Record keys per table, loaded on first use
+    keys: HashMap<String, HashSet<String>>,
+}
+
+impl Run<'_> {
+    async fn count(&self, table: &str, filter: &str) -> Result<usize, NarraError> {
+        let mut response = self
+            .db
+            .query(format!(
+                "SELECT count() AS count FROM {} {} GROUP ALL",
+                table, filter
+            ))
+            .await?;
+        let rows: Vec<CountResult> = response.take(0)?;
+        Ok(rows.first().map_or(0, |r| r.count))
+    }
+
+    async fn exists(&mut self, table: &str, key: &str) -> Result<bool, NarraError> {
+        if !self.keys.contains_key(table) {
+            let mut response = self
+                .db
+                .query("SELECT VALUE <string> record::id(id) FROM type::table($table)")
+                .bind(("table", table.to_string()))
+                .await?;
+            let keys: Vec<String> = response.take(0)?;
+            self.keys
+                .insert(table.to_string(), keys.into_iter().collect());
+        }
+        Ok(self.keys[table].contains(key))
+    }
+
+    fn push_check(&mut self, name: &str, scanned: usize, first_finding: usize) {
+        let findings = &self.report.findings[first_finding..];
+        self.report.checks.push(FsckCheck {
+            name: name.to_string(),
+            scanned,
+            problems: findings.len(),
+            repaired: findings.iter().filter(|f| f.repaired).count(),
+        });
+    }
+
+    fn flag(&mut self, check: &str, record: String, detail: String, fixable: bool) {
+        self.report.findings.push(FsckFinding {
+            check: check.to_string(),
+            record,
+            detail,
+            fixable,
+            repaired: fixable && self.fix,
+        });
+    }
+
+    /// Delete `ids` when fixing, counting `bytes` as reclaimed.
+    async fn delete(&mut self, ids: Vec<RecordId>, bytes: u64) -> Result<(), NarraError> {
+        if !self.fix || ids.is_empty() {
+            return Ok(());
+        }
+        for batch in ids.chunks(FIX_BATCH) {
+            self.db
+                .query("DELETE $ids")
+                .bind(("ids", batch.to_vec()))
+                .await?
+                .check()?;
+        }
+        self.report.reclaimed_bytes += bytes;
+        Ok(())
+    }
+
+    /// Edges whose `in` or `out` record no longer exists.
+    async fn check_edges(&mut self) -> Result<(), NarraError> {
+        let first = self.report.findings.len();
+        let mut scanned = 0;
+        for table in EDGE_TABLES {
+            scanned += self.count(table, "").await?;
+            let mut response = self
+                .db
+                .query(format!(
+                    "SELECT id, string::len(<string> $this) AS bytes FROM {} \
+                     WHERE in.id IS NONE OR out.id IS NONE",
+                    table
+                ))
+                .await?;
+            let dangling: Vec<Flagged> = response.take(0)?;
+            let bytes = dangling.iter().map(|f| f.bytes).sum();
+            for f in &dangling {
+                self.flag(
+                    "edges",
+                    f.id.to_string(),
+                    "Endpoint was deleted".to_string(),
+                    true,
+                );
+            }
+            self.delete(dangling.into_iter().map(|f| f.id).collect(), bytes)
+                .await?;
+        }
+        self.push_check("edges", scanned, first);
+        Ok(())
+    }
+
+    /// Entity IDs stored as strings: annotation targets and fact exceptions.
+    async fn check_references(&mut self) -> Result<(), NarraError> {
+        let first = self.report.findings.len();
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, entity_id, string::len(<string> $this) AS bytes FROM annotation;\
+                 SELECT id, exceptions.*.entity_id AS entity_ids FROM universe_fact \
+                 WHERE array::len(exceptions) > 0",
+            )
+            .await?;
+        let annotations: Vec<AnnotationRef> = response.take(0)?;
+        let facts: Vec<FactExceptions> = response.take(1)?;
+        let scanned = annotations.len() + facts.iter().map(|f| f.entity_ids.len()).sum::<usize>();
+
+        let mut doomed = Vec::new();
+        let mut bytes = 0;
+        for a in annotations {
+            let problem = match parse_entity_ref(&a.entity_id) {
+                None => format!("Malformed entity ID '{}'", a.entity_id),
+                Some((table, key)) => {
+                    if self.exists(table, key).await? {
+                        continue;
+                    }
+                    format!("Annotation of deleted {}", a.entity_id)
+                }
+            };
+            self.flag("references", a.id.to_string(), problem, true);
+            bytes += a.bytes;
+            doomed.push(a.id);
+        }
+        self.delete(doomed, bytes).await?;
+
+        // Exceptions are authored: report them for the writer to edit
+        for fact in facts {
+            for entity_id in &fact.entity_ids {
+                let problem = match parse_entity_ref(entity_id) {
+                    None => format!("Exception names malformed entity ID '{}'", entity_id),
+                    Some((table, key)) => {
+                        if self.exists(table, key).await? {
+                            continue;
+                        }
+                        format!("Exception names deleted {}", entity_id)
+                    }
+                };
+                self.flag("references", fact.id.to_string(), problem, false);
+            }
+        }
+        self.push_check("references", scanned, first);
+        Ok(())
+    }
+
+    /// Embeddings whose size doesn't match the current model. Search and
+    /// similarity skip or reject them; clearing one queues it for backfill.
+    async fn check_embeddings(&mut self, dimensions: usize) -> Result<(), NarraError> {
+        let first = self.report.findings.len();
+        let mut scanned = 0;
+        for (table, field) in EMBEDDING_FIELDS {
+            scanned += self
+                .count(table, &format!("WHERE {} IS NOT NONE", field))
+                .await?;
+            let mut response = self
+                .db
+                .query(format!(
+                    "SELECT id, array::len({f}) AS dimensions, string::len(<string> {f}) AS bytes \
+                     FROM {t} WHERE {f} IS NOT NONE AND array::len({f}) != $dims",
+                    f = field,
+                    t = table
+                ))
+                .bind(("dims", dimensions as i64))
+                .await?;
+            let mismatched: Vec<Flagged> = response.take(0)?;
+            if mismatched.is_empty() {
+                continue;
+            }
+            for f in &mismatched {
+                self.flag(
+                    "embeddings",
+                    f.id.to_string(),
+                    format!(
+                        "{} has {} dimensions, model has {}",
+                        field,
+                        f.dimensions.unwrap_or(0),
+                        dimensions
+                    ),
+                    true,
+                );
+            }
+            if self.fix {
+                let ids: Vec<RecordId> = mismatched.iter().map(|f| f.id.clone()).collect();
+                for batch in ids.chunks(FIX_BATCH) {
+                    self.db
+                        .query(format!(
+                            "UPDATE $ids SET {} = NONE, embedding_stale = true",
+                            field
+                        ))
+                        .bind(("ids", batch.to_vec()))
+                        .await?
+                        .check()?;
+                }
+                self.report.reclaimed_bytes += mismatched.iter().map(|f| f.bytes).sum::<u64>();
+            }
+        }
+        self.push_check("embeddings", scanned, first);
+        Ok(())
+    }
+
+    /// Snapshots of deleted entities (removed), and snapshots from another
+    /// embedding model (reported: arc history can't be recomputed).
+    async fn check_arc_snapshots(&mut self, dimensions: usize) -> Result<(), NarraError> {
+        let first = self.report.findings.len();
+        let scanned = self.count("arc_snapshot", "").await?;
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, string::len(<string> $this) AS bytes FROM arc_snapshot \
+                 WHERE entity_id.id IS NONE;\
+                 SELECT id, array::len(embedding) AS dimensions FROM arc_snapshot \
+                 WHERE entity_id.id IS NOT NONE AND array::len(embedding) != $dims",
+            )
+            .bind(("dims", dimensions as i64))
+            .await?;
+        let orphaned: Vec<Flagged> = response.take(0)?;
+        let mismatched: Vec<Flagged> = response.take(1)?;
+
+        let bytes = orphaned.iter().map(|f| f.bytes).sum();
+        for f in &orphaned {
+            self.flag(
+                "arc snapshots",
+                f.id.to_string(),
+                "Snapshot of a deleted entity".to_string(),
+                true,
+            );
+        }
+        self.delete(orphaned.into_iter().map(|f| f.id).collect(), bytes)
+            .await?;
+        for f in mismatched {
+            self.flag(
+                "arc snapshots",
+                f.id.to_string(),
+                format!(
+                    "{} dimensions, model has {}; drift against newer snapshots is meaningless",
+                    f.dimensions.unwrap_or(0),
+                    dimensions
+                ),
+                false,
+            );
+        }
+        self.push_check("arc snapshots", scanned, first);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entity_ref() {
+        assert_eq!(
+            parse_entity_ref("character:alice"),
+            Some(("character", "alice"))
+        );
+        assert_eq!(
+            parse_entity_ref("event:⟨the-wedding⟩"),
+            Some(("event", "the-wedding"))
+        );
+        assert_eq!(parse_entity_ref("alice"), None);
+        assert_eq!(parse_entity_ref("widget:alice"), None);
+        assert_eq!(parse_entity_ref("character:"), None);
+        assert_eq!(parse_entity_ref("character:al ice"), None);
+    }
+}
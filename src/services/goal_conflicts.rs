@@ -0,0 +1,310 @@
+//! Goal conflicts between characters who share scenes.
+//!
+//! Only active goals count, and only between characters who appear in at
+//! least one scene together. Two kinds of conflict are detected:
+//!
+//! - contested: both characters have a goal about the same target
+//! - obstructs: one character's goal names the other (by name or alias)
+//!   as its obstacle
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::services::mentions::{find_mentions, KnownEntity};
+use crate::NarraError;
+
+/// An active goal with what conflict detection needs.
+#[derive(Debug, Clone)]
+pub struct GoalInfo {
+    pub goal_id: String,
+    pub character_id: String,
+    pub want: String,
+    pub obstacle: Option<String>,
+    pub target: Option<String>,
+}
+
+/// Two characters whose goals are at odds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalConflict {
+    /// "contested" or "obstructs"
+    pub kind: String,
+    pub character_id: String,
+    pub character: String,
+    pub goal_id: String,
+    pub want: String,
+    pub other_character_id: String,
+    pub other_character: String,
+    /// The other character's goal, for contested targets
+    pub other_goal_id: Option<String>,
+    pub other_want: Option<String>,
+    pub shared_scenes: usize,
+    pub description: String,
+}
+
+/// Scenes shared by each pair of characters, keyed with the smaller ID first.
+pub fn pair_key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+/// Find conflicts among `goals`. `characters` supplies names and aliases
+/// (as [`KnownEntity`] terms); `shared` counts scenes per [`pair_key`].
+pub fn detect_conflicts(
+    goals: &[GoalInfo],
+    characters: &[KnownEntity],
+    shared: &HashMap<(String, String), usize>,
+) -> Vec<GoalConflict> {
+    let name = |id: &str| {
+        characters
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let together = |a: &str, b: &str| shared.get(&pair_key(a, b)).copied().unwrap_or(0);
+
+    let mut conflicts = Vec::new();
+    for (i, a) in goals.iter().enumerate() {
+        for b in &goals[i + 1..] {
+            if a.character_id == b.character_id || a.target.is_none() || a.target != b.target {
+                continue;
+            }
+            let scenes = together(&a.character_id, &b.character_id);
+            if scenes == 0 {
+                continue;
+            }
+            let target = a.target.as_deref().unwrap_or_default();
+            conflicts.push(GoalConflict {
+                kind: "contested".to_string(),
+                character_id: a.character_id.clone(),
+                character: name(&a.character_id),
+                goal_id: a.goal_id.clone(),
+                want: a.want.clone(),
+                other_character_id: b.character_id.clone(),
+                other_character: name(&b.character_id),
+                other_goal_id: Some(b.goal_id.clone()),
+                other_want: Some(b.want.clone()),
+                shared_scenes: scenes,
+                description: format!(
+                    "Both are after {}: \"{}\" vs \"{}\"",
+                    characters
+                        .iter()
+                        .find(|c| c.id == target)
+                        .map(|c| c.name.as_str())
+                        .unwrap_or(target),
+                    a.want,
+                    b.want
+                ),
+            });
+        }
+    }
+
+    for goal in goals {
+        let Some(obstacle) = &goal.obstacle else {
+            continue;
+        };
+        let mut named: Vec<&KnownEntity> = find_mentions(obstacle, characters)
+            .into_iter()
+            .map(|m| &characters[m.entity_index])
+            .filter(|c| c.id != goal.character_id)
+            .collect();
+        named.sort_by(|a, b| a.id.cmp(&b.id));
+        named.dedup_by(|a, b| a.id == b.id);
+        for other in named {
+            let scenes = together(&goal.character_id, &other.id);
+            if scenes == 0 {
+                continue;
+            }
+            conflicts.push(GoalConflict {
+                kind: "obstructs".to_string(),
+                character_id: goal.character_id.clone(),
+                character: name(&goal.character_id),
+                goal_id: goal.goal_id.clone(),
+                want: goal.want.clone(),
+                other_character_id: other.id.clone(),
+                other_character: other.name.clone(),
+                other_goal_id: None,
+                other_want: None,
+                shared_scenes: scenes,
+                description: format!("{} stands in the way of \"{}\"", other.name, goal.want),
+            });
+        }
+    }
+
+    conflicts.sort_by(|a, b| b.shared_scenes.cmp(&a.shared_scenes));
+    conflicts
+}
+
+pub struct GoalConflictService {
+    db: Arc<NarraDb>,
+}
+
+impl GoalConflictService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Conflicts between active goals. With `characters` (full IDs), only
+    /// conflicts among those characters are returned.
+    pub async fn detect(&self, characters: &[String]) -> Result<Vec<GoalConflict>, NarraError> {
+        #[derive(Deserialize)]
+        struct GoalRow {
+            id: RecordId,
+            character: RecordId,
+            want: String,
+            obstacle: Option<String>,
+            target: Option<RecordId>,
+        }
+        #[derive(Deserialize)]
+        struct CharacterRow {
+            id: RecordId,
+            name: String,
+            #[serde(default)]
+            aliases: Vec<String>,
+        }
+        #[derive(Deserialize)]
+        struct ParticipationRow {
+            character: RecordId,
+            scene: RecordId,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, character, want, obstacle, target FROM goal WHERE status = 'active'; \
+                 SELECT id, name, aliases FROM character; \
+                 SELECT in AS character, out AS scene FROM participates_in",
+            )
+            .await?;
+        let goals: Vec<GoalRow> = response.take(0)?;
+        let rows: Vec<CharacterRow> = response.take(1)?;
+        let participation: Vec<ParticipationRow> = response.take(2)?;
+
+        let known: Vec<KnownEntity> = rows
+            .into_iter()
+            .map(|c| KnownEntity {
+                id: c.id.to_string(),
+                entity_type: "character".to_string(),
+                terms: std::iter::once(c.name.clone()).chain(c.aliases).collect(),
+                name: c.name,
+            })
+            .collect();
+
+        let mut casts: HashMap<String, Vec<String>> = HashMap::new();
+        for row in participation {
+            casts
+                .entry(row.scene.to_string())
+                .or_default()
+                .push(row.character.to_string());
+        }
+        let mut shared: HashMap<(String, String), usize> = HashMap::new();
+        for cast in casts.values_mut() {
+            cast.sort();
+            cast.dedup();
+            for (i, a) in cast.iter().enumerate() {
+                for b in &cast[i + 1..] {
+                    *shared.entry(pair_key(a, b)).or_default() += 1;
+                }
+            }
+        }
+
+        let goals: Vec<GoalInfo> = goals
+            .into_iter()
+            .map(|g| GoalInfo {
+                goal_id: g.id.to_string(),
+                character_id: g.character.to_string(),
+                want: g.want,
+                obstacle: g.obstacle,
+                target: g.target.map(|t| t.to_string()),
+            })
+            .collect();
+        let mut conflicts = detect_conflicts(&goals, &known, &shared);
+        if !characters.is_empty() {
+            conflicts.retain(|c| {
+                characters.contains(&c.character_id) && characters.contains(&c.other_character_id)
+            });
+        }
+        Ok(conflicts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(key: &str, name: &str, aliases: &[&str]) -> KnownEntity {
+        KnownEntity {
+            id: format!("character:{}", key),
+            entity_type: "character".to_string(),
+            name: name.to_string(),
+            terms: std::iter::once(name)
+                .chain(aliases.iter().copied())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    fn goal(key: &str, character: &str, obstacle: Option<&str>, target: Option<&str>) -> GoalInfo {
+        GoalInfo {
+            goal_id: format!("goal:{}", key),
+            character_id: format!("character:{}", character),
+            want: format!("{} wants it", character),
+            obstacle: obstacle.map(str::to_string),
+            target: target.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_detect_conflicts_needs_shared_scenes() {
+        let characters = vec![
+            character("alice", "Alice", &[]),
+            character("vane", "Inspector Vane", &["Vane"]),
+            character("gray", "Gray", &[]),
+        ];
+        let goals = vec![
+            goal(
+                "a1",
+                "alice",
+                Some("Vane keeps the ledger locked up"),
+                Some("knowledge:ledger"),
+            ),
+            goal("v1", "vane", None, Some("knowledge:ledger")),
+            goal(
+                "g1",
+                "gray",
+                Some("Alice is watching"),
+                Some("knowledge:ledger"),
+            ),
+        ];
+        let mut shared = HashMap::new();
+        shared.insert(pair_key("character:vane", "character:alice"), 2);
+
+        let conflicts = detect_conflicts(&goals, &characters, &shared);
+        let kinds: Vec<(&str, &str, &str)> = conflicts
+            .iter()
+            .map(|c| {
+                (
+                    c.kind.as_str(),
+                    c.character.as_str(),
+                    c.other_character.as_str(),
+                )
+            })
+            .collect();
+        // Gray never shares a scene with either, so neither conflict counts
+        assert_eq!(
+            kinds,
+            vec![
+                ("contested", "Alice", "Inspector Vane"),
+                ("obstructs", "Alice", "Inspector Vane"),
+            ]
+        );
+        assert_eq!(conflicts[0].shared_scenes, 2);
+    }
+}
@@ -53,10 +53,10 @@ fn relationship_color(rel_type: &str) -> &'static str {
         "family" => "stroke:#22c55e,stroke-width:2px", // green
         "romantic" => "stroke:#ef4444,stroke-width:2px", // red
         "professional" => "stroke:#3b82f6,stroke-width:2px", // blue
-        "friendship" => "stroke:#f59e0b,stroke-width:2px", // amber
-        "rivalry" => "stroke:#8b5cf6,stroke-width:2px", // purple
-        "mentorship" => "stroke:#14b8a6,stroke-width:2px", // teal
-        "alliance" => "stroke:#6366f1,stroke-width:2px", // indigo
+        "friend" | "friendship" => "stroke:#f59e0b,stroke-width:2px", // amber
+        "rival" | "rivalry" => "stroke:#8b5cf6,stroke-width:2px", // purple
+        "mentor" | "mentorship" => "stroke:#14b8a6,stroke-width:2px", // teal
+        "ally" | "alliance" => "stroke:#6366f1,stroke-width:2px", // indigo
         _ => "stroke:#6b7280,stroke-width:1px",        // gray (unknown)
     }
 }
@@ -453,6 +453,37 @@ impl MermaidGraphService {
             }
         }
 
+        // Profiles, descriptions and summaries that name the entity
+        if results.len() < limit {
+            #[derive(Deserialize)]
+            struct MentionRow {
+                #[serde(rename = "in")]
+                source: surrealdb::RecordId,
+                field: String,
+            }
+
+            let mut result = self
+                .db
+                .query("SELECT in, field FROM mentions WHERE out = $entity ORDER BY in")
+                .bind(("entity", crate::db::query::parse_record_id(entity_id)?))
+                .await?;
+            let mentions: Vec<MentionRow> = result.take(0).unwrap_or_default();
+            for mention in mentions {
+                let source_type = mention.source.table().to_string();
+                if results.len() >= limit {
+                    break;
+                }
+                if !should_include(&source_type) {
+                    continue;
+                }
+                results.push(ReverseQueryResult {
+                    entity_type: source_type,
+                    entity_id: mention.source.to_string(),
+                    reference_field: format!("{} (mention)", mention.field),
+                });
+            }
+        }
+
         Ok(results)
     }
 
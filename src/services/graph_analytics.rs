@@ -7,7 +7,7 @@
 use crate::db::connection::NarraDb;
 use async_trait::async_trait;
 use graphrs::{algorithms::centrality, Edge, Graph, GraphSpecs, Node};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::NarraError;
@@ -160,17 +160,26 @@ impl GraphDataProvider for SurrealGraphDataProvider {
 /// Service for computing graph analytics on character networks.
 pub struct GraphAnalyticsService {
     data: Arc<dyn GraphDataProvider>,
+    members: Option<HashSet<String>>,
 }
 
 impl GraphAnalyticsService {
     pub fn new(db: Arc<NarraDb>) -> Self {
+        Self::with_provider(Arc::new(SurrealGraphDataProvider::new(db)))
+    }
+
+    pub fn with_provider(data: Arc<dyn GraphDataProvider>) -> Self {
         Self {
-            data: Arc::new(SurrealGraphDataProvider::new(db)),
+            data,
+            members: None,
         }
     }
 
-    pub fn with_provider(data: Arc<dyn GraphDataProvider>) -> Self {
-        Self { data }
+    /// Restrict the analysis to these entity IDs (a phase's members);
+    /// `None` covers the whole world.
+    pub fn with_members(mut self, members: Option<HashSet<String>>) -> Self {
+        self.members = members;
+        self
     }
 
     /// Compute centrality metrics for characters in the network.
@@ -180,7 +189,10 @@ impl GraphAnalyticsService {
         metrics: Vec<CentralityMetric>,
         limit: usize,
     ) -> Result<Vec<CentralityResult>, NarraError> {
-        let characters = self.data.get_all_characters().await?;
+        let mut characters = self.data.get_all_characters().await?;
+        if let Some(members) = &self.members {
+            characters.retain(|c| members.contains(&c.id));
+        }
         let perception_edges = self.data.get_all_perception_edges().await?;
         let relationship_edges = self.data.get_all_relationship_edges().await?;
 
@@ -615,6 +627,39 @@ mod tests {
         assert!(results[0].degree > results[1].degree);
     }
 
+    #[tokio::test]
+    async fn test_graph_members_restrict_subgraph() {
+        // Alice is the hub, but outside the phase Bob and Charlie only know each other
+        let provider = MockGraphDataProvider {
+            characters: vec![
+                char_node("alice", "Alice"),
+                char_node("bob", "Bob"),
+                char_node("charlie", "Charlie"),
+            ],
+            edges: vec![
+                edge("alice", "bob"),
+                edge("alice", "charlie"),
+                edge("bob", "charlie"),
+            ],
+            relationship_edges: vec![],
+        };
+        let members: HashSet<String> = ["character:bob", "character:charlie"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let service =
+            GraphAnalyticsService::with_provider(Arc::new(provider)).with_members(Some(members));
+        let results = service
+            .compute_centrality(None, vec![CentralityMetric::Degree], 10)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.character_name != "Alice"));
+        for r in &results {
+            assert!((r.degree - 1.0).abs() < 1e-6);
+        }
+    }
+
     #[tokio::test]
     async fn test_graph_isolated_nodes() {
         let provider = MockGraphDataProvider {
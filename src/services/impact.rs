@@ -5,6 +5,7 @@ use std::sync::Arc;
 use surrealdb::Datetime;
 
 use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
 
 use crate::repository::{RelationshipRepository, SurrealRelationshipRepository};
 use crate::NarraError;
@@ -57,6 +58,60 @@ pub struct ImpactAnalysis {
     pub has_protected_impact: bool,
     /// Warnings for protected entities
     pub warnings: Vec<String>,
+    /// Scenes marked final that reference the changed entity; changing it
+    /// reopens locked prose, so callers require an explicit force
+    #[serde(default)]
+    pub final_scenes: Vec<String>,
+}
+
+/// A scene marked final that references an entity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedScene {
+    pub id: String,
+    pub title: String,
+}
+
+/// Scenes marked final that reference `entity_id`: the scene itself, scenes at
+/// an event, scenes set at a location, and scenes a character takes part in.
+/// An ID that isn't `table:key` references nothing.
+pub async fn final_scenes_referencing(
+    db: &NarraDb,
+    entity_id: &str,
+) -> Result<Vec<LockedScene>, NarraError> {
+    #[derive(Deserialize)]
+    struct Row {
+        id: surrealdb::RecordId,
+        title: String,
+    }
+
+    let Ok(entity) = parse_record_id(entity_id) else {
+        return Ok(Vec::new());
+    };
+    let mut response = db
+        .query(
+            "SELECT id, title FROM scene WHERE draft_status = 'final' AND ( \
+             id = $entity OR event = $entity OR primary_location = $entity \
+             OR $entity IN secondary_locations \
+             OR id IN (SELECT VALUE out FROM participates_in WHERE in = $entity)) \
+             ORDER BY title",
+        )
+        .bind(("entity", entity))
+        .await?;
+    let rows: Vec<Row> = response.take(0)?;
+    Ok(rows
+        .into_iter()
+        .map(|r| LockedScene {
+            id: r.id.to_string(),
+            title: r.title,
+        })
+        .collect())
+}
+
+impl ImpactAnalysis {
+    /// Whether the change reaches a scene marked final.
+    pub fn is_locked(&self) -> bool {
+        !self.final_scenes.is_empty()
+    }
 }
 
 /// A decision record with impact tracking.
@@ -180,7 +235,7 @@ impl ImpactAnalyzer {
             return None;
         }
 
-        let (entity_type, id) = (parts[0], parts[1]);
+        let entity_type = parts[0];
 
         // Query for name based on type
         let name_field = match entity_type {
@@ -190,9 +245,12 @@ impl ImpactAnalyzer {
             _ => return None,
         };
 
-        let query = format!("SELECT {name_field} AS name FROM {entity_type}:{id}");
-
-        let mut response = self.db.query(&query).await.ok()?;
+        let mut response = self
+            .db
+            .query(format!("SELECT {name_field} AS name FROM $ref"))
+            .bind(("ref", parse_record_id(entity_id).ok()?))
+            .await
+            .ok()?;
 
         #[derive(Deserialize)]
         struct NameOnly {
@@ -235,10 +293,21 @@ impl ImpactService for ImpactAnalyzer {
             ));
         }
 
+        // Final scenes are locked prose: any change reaching them is critical
+        let locked = final_scenes_referencing(&self.db, changed_entity).await?;
+        let locked_ids: HashSet<&str> = locked.iter().map(|l| l.id.as_str()).collect();
+        for scene in &locked {
+            warnings.push(format!(
+                "REVISION LOCK: scene '{}' ({}) is marked final",
+                scene.title, scene.id
+            ));
+        }
+
         // Track distances (BFS order approximates distance)
         for (idx, entity_id) in connected.iter().enumerate() {
             let distance = idx + 1;
             let is_protected = protected.contains(entity_id);
+            let is_final = locked_ids.contains(entity_id.as_str());
 
             if is_protected {
                 has_protected_impact = true;
@@ -248,7 +317,7 @@ impl ImpactService for ImpactAnalyzer {
                 ));
             }
 
-            let severity = self.calculate_severity(distance, is_protected);
+            let severity = self.calculate_severity(distance, is_protected || is_final);
             let severity_key = format!("{:?}", severity).to_lowercase();
 
             // Get entity info for display
@@ -259,6 +328,8 @@ impl ImpactService for ImpactAnalyzer {
 
             let reason = if is_protected {
                 "Protected entity - requires explicit review".to_string()
+            } else if is_final {
+                "Scene marked final - requires force to change".to_string()
             } else {
                 format!("Connected via {} relationship(s)", distance)
             };
@@ -279,7 +350,27 @@ impl ImpactService for ImpactAnalyzer {
                 .push(affected);
         }
 
-        let total_affected = connected.len();
+        // Final scenes outside the traversal (the scene itself, or scenes
+        // reached only through fields) still count as directly affected
+        let mut total_affected = connected.len();
+        for scene in &locked {
+            if scene.id == changed_entity || connected.contains(&scene.id) {
+                continue;
+            }
+            total_affected += 1;
+            affected_by_severity
+                .entry("critical".to_string())
+                .or_default()
+                .push(AffectedEntity {
+                    id: scene.id.clone(),
+                    entity_type: "scene".to_string(),
+                    name: scene.title.clone(),
+                    severity: Severity::Critical,
+                    reason: "Scene marked final - requires force to change".to_string(),
+                    distance: 1,
+                    is_protected: false,
+                });
+        }
 
         Ok(ImpactAnalysis {
             changed_entity: changed_entity.to_string(),
@@ -288,6 +379,7 @@ impl ImpactService for ImpactAnalyzer {
             total_affected,
             has_protected_impact,
             warnings,
+            final_scenes: locked.into_iter().map(|l| l.id).collect(),
         })
     }
 
@@ -1,13 +1,18 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use surrealdb::RecordId;
 
 use crate::db::connection::NarraDb;
 
 use crate::embedding::StalenessManager;
 use crate::mcp::types::{
-    CharacterSpec, ConflictMode, EventSpec, FactSpec, ImportResult, ImportTypeResult,
-    KnowledgeSpec, LocationSpec, NarraImport, NoteSpec, RelationshipSpec, SceneSpec,
+    CharacterSpec, ConflictMode, EntityConflict, EventSpec, FactSpec, FieldDiff, ImportResult,
+    ImportTypeResult, KnowledgeSpec, LocationSpec, NarraImport, NoteSpec, RelationshipSpec,
+    SceneSpec,
 };
 use crate::models::character::{
     create_character, create_character_with_id, get_character, update_character, CharacterCreate,
@@ -36,8 +41,194 @@ use crate::models::scene::{
     add_scene_participant, create_scene, create_scene_with_id, get_scene, update_scene,
     SceneCreate, SceneParticipantCreate, SceneUpdate,
 };
+use crate::services::export::{normalize_id, ExportService};
+use crate::services::names::fold_name;
+use crate::services::reciprocity::set_intentional;
 use crate::NarraError;
 
+/// Per-entity conflict resolution with a fallback mode.
+#[derive(Debug, Clone, Default)]
+pub struct ConflictPolicy {
+    pub default: ConflictMode,
+    /// Keyed by full record ID (e.g., "character:alice")
+    pub overrides: HashMap<String, ConflictMode>,
+}
+
+impl ConflictPolicy {
+    pub fn uniform(mode: ConflictMode) -> Self {
+        Self {
+            default: mode,
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn resolve(&self, entity_id: &str) -> ConflictMode {
+        self.overrides
+            .get(entity_id)
+            .copied()
+            .unwrap_or(self.default)
+    }
+
+    fn mode_for(&self, table: &str, id: &str) -> ConflictMode {
+        self.resolve(&normalize_id(table, id))
+    }
+}
+
+/// Replayable record of how an import's conflicts were resolved
+/// (`narra world import --resolution-log`, replayed with `--replay`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionLog {
+    /// File the import was read from
+    pub source: String,
+    pub created_at: DateTime<Utc>,
+    pub resolutions: Vec<EntityConflict>,
+}
+
+impl ResolutionLog {
+    /// Recorded resolutions as policy overrides.
+    pub fn overrides(&self) -> impl Iterator<Item = (String, ConflictMode)> + '_ {
+        self.resolutions
+            .iter()
+            .filter_map(|c| Some((c.entity_id.clone(), c.resolution?)))
+    }
+}
+
+/// Fields set in `new` that differ from `old`. Omitted (null) fields are
+/// skipped: an update leaves them as they are.
+fn field_diffs(old: &Value, new: &Value) -> Vec<FieldDiff> {
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter(|(field, value)| {
+            field.as_str() != "id" && !value.is_null() && old.get(*field) != Some(*value)
+        })
+        .map(|(field, value)| FieldDiff {
+            field: field.clone(),
+            old: old.get(field).cloned().unwrap_or(Value::Null),
+            new: value.clone(),
+        })
+        .collect()
+}
+
+fn diff_specs<T: Serialize>(
+    table: &str,
+    existing: &[T],
+    incoming: &[T],
+    id_of: impl Fn(&T) -> Option<&str>,
+    conflicts: &mut Vec<EntityConflict>,
+) -> Result<(), NarraError> {
+    let current: HashMap<String, &T> = existing
+        .iter()
+        .filter_map(|spec| Some((normalize_id(table, id_of(spec)?), spec)))
+        .collect();
+    for spec in incoming {
+        let Some(id) = id_of(spec) else {
+            continue;
+        };
+        let entity_id = normalize_id(table, id);
+        if let Some(old) = current.get(&entity_id) {
+            conflicts.push(EntityConflict {
+                entity_id,
+                fields: field_diffs(&serde_json::to_value(old)?, &serde_json::to_value(spec)?),
+                resolution: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Give characters and locations in `import` that lack an ID the ID of an
+/// existing entity with the same name once folded (see
+/// [`fold_name`](crate::services::names::fold_name)); character aliases count
+/// too. "Osten" from a manuscript then lands on the world's "Östen" instead of
+/// becoming a duplicate. Returns how many specs were matched.
+pub fn match_existing_names(import: &mut NarraImport, world: &NarraImport) -> usize {
+    let key = |id: &Option<String>| {
+        id.as_deref()
+            .map(|id| id.split_once(':').map_or(id, |(_, key)| key).to_string())
+    };
+    let mut characters: HashMap<String, String> = HashMap::new();
+    for c in &world.characters {
+        let Some(id) = key(&c.id) else { continue };
+        for name in std::iter::once(&c.name).chain(c.aliases.iter().flatten()) {
+            characters
+                .entry(fold_name(name))
+                .or_insert_with(|| id.clone());
+        }
+    }
+    let mut locations: HashMap<String, String> = world
+        .locations
+        .iter()
+        .filter_map(|l| Some((fold_name(&l.name), key(&l.id)?)))
+        .collect();
+    // Names with nothing left after folding match nothing
+    characters.remove("");
+    locations.remove("");
+
+    let mut matched = 0;
+    for spec in import.characters.iter_mut().filter(|c| c.id.is_none()) {
+        spec.id = characters.get(&fold_name(&spec.name)).cloned();
+        matched += usize::from(spec.id.is_some());
+    }
+    for spec in import.locations.iter_mut().filter(|l| l.id.is_none()) {
+        spec.id = locations.get(&fold_name(&spec.name)).cloned();
+        matched += usize::from(spec.id.is_some());
+    }
+    matched
+}
+
+/// Conflicts between `import` and the exported `world`.
+fn conflicts_with(
+    world: &NarraImport,
+    import: &NarraImport,
+) -> Result<Vec<EntityConflict>, NarraError> {
+    let mut conflicts = Vec::new();
+    diff_specs(
+        "character",
+        &world.characters,
+        &import.characters,
+        |s| s.id.as_deref(),
+        &mut conflicts,
+    )?;
+    diff_specs(
+        "location",
+        &world.locations,
+        &import.locations,
+        |s| s.id.as_deref(),
+        &mut conflicts,
+    )?;
+    diff_specs(
+        "event",
+        &world.events,
+        &import.events,
+        |s| s.id.as_deref(),
+        &mut conflicts,
+    )?;
+    diff_specs(
+        "scene",
+        &world.scenes,
+        &import.scenes,
+        |s| s.id.as_deref(),
+        &mut conflicts,
+    )?;
+    diff_specs(
+        "note",
+        &world.notes,
+        &import.notes,
+        |s| s.id.as_deref(),
+        &mut conflicts,
+    )?;
+    diff_specs(
+        "universe_fact",
+        &world.facts,
+        &import.facts,
+        |s| s.id.as_deref(),
+        &mut conflicts,
+    )?;
+    Ok(conflicts)
+}
+
 pub struct ImportService {
     db: Arc<NarraDb>,
     staleness_manager: Arc<StalenessManager>,
@@ -56,17 +247,48 @@ impl ImportService {
         import: NarraImport,
         mode: ConflictMode,
     ) -> Result<ImportResult, NarraError> {
-        let mut result = ImportResult::default();
+        self.execute_import_with(import, &ConflictPolicy::uniform(mode))
+            .await
+    }
+
+    /// Entities in `import` whose IDs already exist, with the fields that differ.
+    ///
+    /// Both sides are compared in export form, so the diff shows what an
+    /// update would change.
+    pub async fn conflicts(&self, import: &NarraImport) -> Result<Vec<EntityConflict>, NarraError> {
+        let world = ExportService::new(self.db.clone()).export_world().await?;
+        let mut import = import.clone();
+        match_existing_names(&mut import, &world);
+        conflicts_with(&world, &import)
+    }
+
+    /// Import with per-entity conflict resolution. The conflict report is
+    /// computed before any writes and returned with each entry's resolution.
+    pub async fn execute_import_with(
+        &self,
+        import: NarraImport,
+        policy: &ConflictPolicy,
+    ) -> Result<ImportResult, NarraError> {
+        let world = ExportService::new(self.db.clone()).export_world().await?;
+        let mut import = import;
+        match_existing_names(&mut import, &world);
+        let mut result = ImportResult {
+            conflicts: conflicts_with(&world, &import)?,
+            ..Default::default()
+        };
+        for conflict in &mut result.conflicts {
+            conflict.resolution = Some(policy.resolve(&conflict.entity_id));
+        }
 
         // Process in dependency order
-        let char_result = self.import_characters(&import.characters, mode).await;
-        let loc_result = self.import_locations(&import.locations, mode).await;
-        let event_result = self.import_events(&import.events, mode).await;
-        let scene_result = self.import_scenes(&import.scenes, mode).await;
+        let char_result = self.import_characters(&import.characters, policy).await;
+        let loc_result = self.import_locations(&import.locations, policy).await;
+        let event_result = self.import_events(&import.events, policy).await;
+        let scene_result = self.import_scenes(&import.scenes, policy).await;
         let rel_result = self.import_relationships(&import.relationships).await;
         let know_result = self.import_knowledge(&import.knowledge).await;
-        let note_result = self.import_notes(&import.notes, mode).await;
-        let fact_result = self.import_facts(&import.facts, mode).await;
+        let note_result = self.import_notes(&import.notes, policy).await;
+        let fact_result = self.import_facts(&import.facts, policy).await;
 
         let type_results = vec![
             char_result,
@@ -93,7 +315,7 @@ impl ImportService {
     async fn import_characters(
         &self,
         specs: &[CharacterSpec],
-        mode: ConflictMode,
+        policy: &ConflictPolicy,
     ) -> ImportTypeResult {
         let mut result = ImportTypeResult {
             entity_type: "character".to_string(),
@@ -115,7 +337,7 @@ impl ImportService {
             if let Some(ref id) = spec.id {
                 // Check existence for conflict handling
                 match get_character(&self.db, id).await {
-                    Ok(Some(existing)) => match mode {
+                    Ok(Some(existing)) => match policy.mode_for("character", id) {
                         ConflictMode::Error => {
                             result
                                 .errors
@@ -195,7 +417,7 @@ impl ImportService {
     async fn import_locations(
         &self,
         specs: &[LocationSpec],
-        mode: ConflictMode,
+        policy: &ConflictPolicy,
     ) -> ImportTypeResult {
         let mut result = ImportTypeResult {
             entity_type: "location".to_string(),
@@ -226,7 +448,7 @@ impl ImportService {
 
             if let Some(ref id) = spec.id {
                 match get_location(&self.db, id).await {
-                    Ok(Some(existing)) => match mode {
+                    Ok(Some(existing)) => match policy.mode_for("location", id) {
                         ConflictMode::Error => {
                             result
                                 .errors
@@ -302,7 +524,11 @@ impl ImportService {
         result
     }
 
-    async fn import_events(&self, specs: &[EventSpec], mode: ConflictMode) -> ImportTypeResult {
+    async fn import_events(
+        &self,
+        specs: &[EventSpec],
+        policy: &ConflictPolicy,
+    ) -> ImportTypeResult {
         let mut result = ImportTypeResult {
             entity_type: "event".to_string(),
             ..Default::default()
@@ -329,11 +555,12 @@ impl ImportService {
                 date: parsed_date,
                 date_precision: spec.date_precision.clone(),
                 duration_end: None,
+                narrative_status: spec.narrative_status.clone(),
             };
 
             if let Some(ref id) = spec.id {
                 match get_event(&self.db, id).await {
-                    Ok(Some(existing)) => match mode {
+                    Ok(Some(existing)) => match policy.mode_for("event", id) {
                         ConflictMode::Error => {
                             result.errors.push(format!("Event '{}' already exists", id));
                             continue;
@@ -350,6 +577,7 @@ impl ImportService {
                                 date: None,
                                 date_precision: None,
                                 duration_end: None,
+                                narrative_status: spec.narrative_status.clone(),
                                 updated_at: existing.updated_at,
                             };
                             match update_event(&self.db, id, update).await {
@@ -409,7 +637,11 @@ impl ImportService {
         result
     }
 
-    async fn import_scenes(&self, specs: &[SceneSpec], mode: ConflictMode) -> ImportTypeResult {
+    async fn import_scenes(
+        &self,
+        specs: &[SceneSpec],
+        policy: &ConflictPolicy,
+    ) -> ImportTypeResult {
         let mut result = ImportTypeResult {
             entity_type: "scene".to_string(),
             ..Default::default()
@@ -470,7 +702,7 @@ impl ImportService {
 
             let scene_id = if let Some(ref id) = spec.id {
                 match get_scene(&self.db, id).await {
-                    Ok(Some(existing)) => match mode {
+                    Ok(Some(existing)) => match policy.mode_for("scene", id) {
                         ConflictMode::Error => {
                             result.errors.push(format!("Scene '{}' already exists", id));
                             continue;
@@ -601,7 +833,13 @@ impl ImportService {
                 label: spec.label.clone(),
             };
 
-            match create_relationship(&self.db, create_data).await {
+            let created = match create_relationship(&self.db, create_data).await {
+                Ok(rel) if spec.intentional == Some(true) => {
+                    set_intentional(&self.db, &rel.id.to_string(), true).await
+                }
+                other => other,
+            };
+            match created {
                 Ok(rel) => {
                     result.created += 1;
                     self.spawn_regen(&rel.id.to_string(), "relates_to");
@@ -707,7 +945,7 @@ impl ImportService {
         result
     }
 
-    async fn import_notes(&self, specs: &[NoteSpec], mode: ConflictMode) -> ImportTypeResult {
+    async fn import_notes(&self, specs: &[NoteSpec], policy: &ConflictPolicy) -> ImportTypeResult {
         let mut result = ImportTypeResult {
             entity_type: "note".to_string(),
             ..Default::default()
@@ -717,11 +955,13 @@ impl ImportService {
             let create_data = NoteCreate {
                 title: spec.title.clone(),
                 body: spec.body.clone(),
+                kind: spec.kind.unwrap_or_default(),
+                status: spec.status.unwrap_or_default(),
             };
 
             let note_id = if let Some(ref id) = spec.id {
                 match get_note(&self.db, id).await {
-                    Ok(Some(_)) => match mode {
+                    Ok(Some(_)) => match policy.mode_for("note", id) {
                         ConflictMode::Error => {
                             result.errors.push(format!("Note '{}' already exists", id));
                             continue;
@@ -734,6 +974,8 @@ impl ImportService {
                             let update = NoteUpdate {
                                 title: Some(spec.title.clone()),
                                 body: Some(spec.body.clone()),
+                                kind: spec.kind,
+                                status: spec.status,
                             };
                             match update_note(&self.db, id, update).await {
                                 Ok(Some(_)) => {
@@ -803,7 +1045,7 @@ impl ImportService {
         result
     }
 
-    async fn import_facts(&self, specs: &[FactSpec], mode: ConflictMode) -> ImportTypeResult {
+    async fn import_facts(&self, specs: &[FactSpec], policy: &ConflictPolicy) -> ImportTypeResult {
         let mut result = ImportTypeResult {
             entity_type: "fact".to_string(),
             ..Default::default()
@@ -841,7 +1083,7 @@ impl ImportService {
 
             let fact_id = if let Some(ref id) = spec.id {
                 match get_fact(&self.db, id).await {
-                    Ok(Some(existing)) => match mode {
+                    Ok(Some(existing)) => match policy.mode_for("universe_fact", id) {
                         ConflictMode::Error => {
                             result.errors.push(format!("Fact '{}' already exists", id));
                             continue;
@@ -3,8 +3,12 @@
 //! Models how knowledge could spread through directed relationship paths.
 //! Respects asymmetric relationships - if Alice trusts Bob, that doesn't mean
 //! Bob trusts Alice. Only follows OUTGOING perceives edges for propagation.
+//! Where a character's trust in the next one is known (set on the edge or
+//! inferred, see `services::trust`), it decides how well information flows.
 
 use crate::db::connection::NarraDb;
+use crate::services::ontology::RelationshipOntology;
+use crate::services::trust::{estimate_trust, NEUTRAL_TRUST};
 use async_trait::async_trait;
 use serde::Serialize;
 use std::collections::{HashSet, VecDeque};
@@ -21,6 +25,9 @@ pub struct InfluenceStep {
     pub depth: usize,
     /// Tension level from the perceives edge leading to this step
     pub tension_level: Option<i32>,
+    /// Trust along the edge leading to this step, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust: Option<f32>,
 }
 
 /// A complete propagation path from source to a reachable character.
@@ -29,7 +36,7 @@ pub struct InfluencePath {
     pub steps: Vec<InfluenceStep>,
     pub total_hops: usize,
     pub path_strength: String,
-    /// Numeric strength (0.0-1.0) factoring in hops, tension, and rel_type
+    /// Numeric strength (0.0-1.0) factoring in hops, trust, tension, and rel_type
     pub numeric_strength: f32,
 }
 
@@ -55,8 +62,12 @@ pub(crate) fn classify_path_strength(hops: usize) -> &'static str {
     }
 }
 
-/// Compute path strength factoring in hops, tension, and relationship types.
+/// Compute path strength factoring in hops, trust, tension, and relationship types.
 /// Returns `(label, numeric_strength)` where numeric_strength is 0.0-1.0.
+///
+/// An edge with known trust carries information fully from neutral trust up
+/// and proportionally less below it; trust already weighs the relationship
+/// and tension, so those heuristics only apply to edges without it.
 pub(crate) fn compute_path_strength(steps: &[InfluenceStep]) -> (String, f32) {
     let hops = steps.len().saturating_sub(1);
     let label = classify_path_strength(hops).to_string();
@@ -70,6 +81,11 @@ pub(crate) fn compute_path_strength(steps: &[InfluenceStep]) -> (String, f32) {
 
     // Apply per-edge modifiers (skip the source step at index 0)
     for step in steps.iter().skip(1) {
+        if let Some(trust) = step.trust {
+            strength *= (trust / NEUTRAL_TRUST).min(1.0);
+            continue;
+        }
+
         // High tension reduces strength (enemies less likely to share)
         if let Some(t) = step.tension_level {
             if t >= 7 {
@@ -110,6 +126,8 @@ pub struct PerceptionEdgeInfo {
     pub tension_level: Option<i32>,
     /// Relationship subtype for finer classification
     pub subtype: Option<String>,
+    /// Source's trust in the target, when set or inferable
+    pub trust: Option<f32>,
 }
 
 /// Data access abstraction for the influence service.
@@ -126,11 +144,17 @@ pub trait InfluenceDataProvider: Send + Sync {
 /// SurrealDB implementation of InfluenceDataProvider.
 pub struct SurrealInfluenceDataProvider {
     db: Arc<NarraDb>,
+    ontology: Arc<RelationshipOntology>,
 }
 
 impl SurrealInfluenceDataProvider {
     pub fn new(db: Arc<NarraDb>) -> Self {
-        Self { db }
+        Self::with_ontology(db, Arc::new(RelationshipOntology::builtin()))
+    }
+
+    /// Infer trust with a world's own relationship types.
+    pub fn with_ontology(db: Arc<NarraDb>, ontology: Arc<RelationshipOntology>) -> Self {
+        Self { db, ontology }
     }
 }
 
@@ -162,15 +186,20 @@ impl InfluenceDataProvider for SurrealInfluenceDataProvider {
         let mut result = self.db.query(&query).await?;
         let edges: Vec<PerceptionEdge> = result.take(0).unwrap_or_default();
 
-        Ok(edges
-            .into_iter()
-            .map(|e| PerceptionEdgeInfo {
+        let mut infos = Vec::with_capacity(edges.len());
+        for e in edges {
+            let target_key = e.target.id.to_raw();
+            let estimate =
+                estimate_trust(&self.db, &self.ontology, character_key, &target_key).await?;
+            infos.push(PerceptionEdgeInfo {
                 target_id: e.target.to_string(),
                 rel_types: e.rel_types,
                 tension_level: e.tension_level,
                 subtype: e.subtype,
-            })
-            .collect())
+                trust: estimate.is_informed().then_some(estimate.value),
+            });
+        }
+        Ok(infos)
     }
 
     async fn get_character_name(&self, key: &str) -> Result<String, NarraError> {
@@ -212,6 +241,13 @@ impl InfluenceService {
         }
     }
 
+    /// Trace with a world's own relationship types for inferring trust.
+    pub fn with_ontology(db: Arc<NarraDb>, ontology: Arc<RelationshipOntology>) -> Self {
+        Self {
+            data: Arc::new(SurrealInfluenceDataProvider::with_ontology(db, ontology)),
+        }
+    }
+
     pub fn with_provider(data: Arc<dyn InfluenceDataProvider>) -> Self {
         Self { data }
     }
@@ -292,6 +328,7 @@ impl InfluenceService {
                 relationship_type: "source".to_string(),
                 depth: 0,
                 tension_level: None,
+                trust: None,
             }],
             initial_visited,
             0,
@@ -346,6 +383,7 @@ impl InfluenceService {
                     relationship_type: rel_type.to_string(),
                     depth: depth + 1,
                     tension_level: edge.tension_level,
+                    trust: edge.trust,
                 });
 
                 let mut new_visited = visited.clone();
@@ -440,6 +478,7 @@ mod tests {
             rel_types: vec![rel_type.to_string()],
             tension_level: None,
             subtype: None,
+            trust: None,
         }
     }
 
@@ -449,6 +488,7 @@ mod tests {
             rel_types: vec![rel_type.to_string()],
             tension_level: Some(tension),
             subtype: None,
+            trust: None,
         }
     }
 
@@ -648,6 +688,7 @@ mod tests {
                 relationship_type: "source".to_string(),
                 depth: 0,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:bob".to_string(),
@@ -655,6 +696,7 @@ mod tests {
                 relationship_type: "trusts".to_string(),
                 depth: 1,
                 tension_level: None,
+                trust: None,
             },
         ];
         let (label, strength) = compute_path_strength(&steps);
@@ -671,6 +713,7 @@ mod tests {
                 relationship_type: "source".to_string(),
                 depth: 0,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:bob".to_string(),
@@ -678,6 +721,7 @@ mod tests {
                 relationship_type: "trusts".to_string(),
                 depth: 1,
                 tension_level: Some(9),
+                trust: None,
             },
         ];
         let (_, strength) = compute_path_strength(&steps);
@@ -698,6 +742,7 @@ mod tests {
                 relationship_type: "source".to_string(),
                 depth: 0,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:bob".to_string(),
@@ -705,6 +750,7 @@ mod tests {
                 relationship_type: "rivalry".to_string(),
                 depth: 1,
                 tension_level: None,
+                trust: None,
             },
         ];
         let (_, strength) = compute_path_strength(&steps);
@@ -726,6 +772,7 @@ mod tests {
                 relationship_type: "source".to_string(),
                 depth: 0,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:bob".to_string(),
@@ -733,6 +780,7 @@ mod tests {
                 relationship_type: "mentor".to_string(),
                 depth: 1,
                 tension_level: Some(8), // high tension
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:charlie".to_string(),
@@ -740,6 +788,7 @@ mod tests {
                 relationship_type: "mentor".to_string(),
                 depth: 2,
                 tension_level: Some(9), // high tension
+                trust: None,
             },
         ];
         let (label, strength) = compute_path_strength(&steps);
@@ -762,6 +811,7 @@ mod tests {
                 relationship_type: "source".to_string(),
                 depth: 0,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:bob".to_string(),
@@ -769,6 +819,7 @@ mod tests {
                 relationship_type: "mentor".to_string(), // 1.0x
                 depth: 1,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:charlie".to_string(),
@@ -776,6 +827,7 @@ mod tests {
                 relationship_type: "professional".to_string(), // 0.7x
                 depth: 2,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:dave".to_string(),
@@ -783,6 +835,7 @@ mod tests {
                 relationship_type: "rivalry".to_string(), // 0.4x
                 depth: 3,
                 tension_level: None,
+                trust: None,
             },
         ];
         let (label, strength) = compute_path_strength(&steps);
@@ -806,6 +859,7 @@ mod tests {
                 relationship_type: "source".to_string(),
                 depth: 0,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:bob".to_string(),
@@ -813,6 +867,7 @@ mod tests {
                 relationship_type: "friend".to_string(),
                 depth: 1,
                 tension_level: None,
+                trust: None,
             },
         ];
         let steps_low = vec![
@@ -822,6 +877,7 @@ mod tests {
                 relationship_type: "source".to_string(),
                 depth: 0,
                 tension_level: None,
+                trust: None,
             },
             InfluenceStep {
                 character_id: "character:bob".to_string(),
@@ -829,6 +885,7 @@ mod tests {
                 relationship_type: "friend".to_string(),
                 depth: 1,
                 tension_level: Some(3), // below threshold
+                trust: None,
             },
         ];
         let (_, strength_none) = compute_path_strength(&steps_none);
@@ -841,6 +898,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute_path_strength_trust_overrides_heuristics() {
+        let step = |rel: &str, tension: Option<i32>, trust: Option<f32>| InfluenceStep {
+            character_id: "character:bob".to_string(),
+            character_name: "Bob".to_string(),
+            relationship_type: rel.to_string(),
+            depth: 1,
+            tension_level: tension,
+            trust,
+        };
+        let source = step("source", None, None);
+
+        // A trusted rival with high tension still passes information on fully
+        let (_, trusted) =
+            compute_path_strength(&[source.clone(), step("rivalry", Some(9), Some(0.9))]);
+        assert!((trusted - 1.0).abs() < 0.001, "trusted: {}", trusted);
+
+        // A distrusted mentor passes on little
+        let (_, distrusted) = compute_path_strength(&[source, step("mentor", None, Some(0.1))]);
+        assert!(
+            (distrusted - 0.2).abs() < 0.001,
+            "distrusted: {}",
+            distrusted
+        );
+    }
+
     #[tokio::test]
     async fn test_influence_tension_weighted_paths() {
         // Alice -> Bob (high tension), Alice -> Charlie (calm)
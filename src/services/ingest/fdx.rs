@@ -0,0 +1,269 @@
+//! Final Draft (`.fdx`) screenplay parser.
+//!
+//! An FDX file is XML: the script is a run of `<Paragraph Type="...">`
+//! elements whose `<Text>` children hold the words. Import reads scene
+//! headings (with the scene summary from `SceneProperties`), character cues,
+//! parentheticals and dialogue, including dual dialogue; the first action
+//! paragraph stands in for a missing summary. The title is the first line of
+//! the title page. Formatting runs, transitions and shots are ignored.
+
+use super::{character_name, Screenplay, ScriptAdapter, ScriptLine, ScriptScene};
+use crate::NarraError;
+
+/// Final Draft's XML format.
+pub struct FinalDraft;
+
+impl ScriptAdapter for FinalDraft {
+    fn name(&self) -> &'static str {
+        "fdx"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["fdx"]
+    }
+
+    fn parse(&self, source: &str) -> Result<Screenplay, NarraError> {
+        parse(source)
+    }
+}
+
+/// A paragraph being read: its type and text so far.
+struct Paragraph {
+    kind: Option<String>,
+    text: String,
+}
+
+/// Parse a Final Draft document.
+pub fn parse(source: &str) -> Result<Screenplay, NarraError> {
+    if !source.contains("<FinalDraft") {
+        return Err(NarraError::Validation(
+            "Not a Final Draft document (no <FinalDraft> element)".to_string(),
+        ));
+    }
+
+    let mut script = Screenplay::default();
+    // Paragraphs nest: dual dialogue and scene summaries sit inside another
+    let mut open: Vec<Paragraph> = Vec::new();
+    let mut text_depth = 0usize;
+    let mut in_summary = false;
+    let mut in_title_page = false;
+    let mut summary: Option<String> = None;
+    let mut speaker: Option<String> = None;
+    let mut parenthetical: Option<String> = None;
+
+    let mut rest = source;
+    while let Some(start) = rest.find('<') {
+        if text_depth > 0 {
+            if let Some(paragraph) = open.last_mut() {
+                paragraph.text.push_str(&unescape(&rest[..start]));
+            }
+        }
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with(['?', '!']) {
+            continue;
+        }
+
+        let closing = tag.starts_with('/');
+        let self_closing = tag.ends_with('/');
+        let opening = !closing && !self_closing;
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        match name {
+            "Paragraph" if opening => open.push(Paragraph {
+                kind: attr(tag, "Type"),
+                text: String::new(),
+            }),
+            "Paragraph" if closing => {
+                let Some(paragraph) = open.pop() else {
+                    continue;
+                };
+                let text = paragraph
+                    .text
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if text.is_empty() {
+                    continue;
+                }
+                if in_title_page {
+                    script.title.get_or_insert(text);
+                    continue;
+                }
+                if in_summary {
+                    summary.get_or_insert(text);
+                    continue;
+                }
+                match paragraph.kind.as_deref() {
+                    Some("Scene Heading") => {
+                        script.scenes.push(ScriptScene {
+                            heading: text,
+                            summary: summary.take(),
+                            lines: Vec::new(),
+                        });
+                        speaker = None;
+                    }
+                    Some("Character") => {
+                        speaker = character_name(&text);
+                        parenthetical = None;
+                    }
+                    Some("Parenthetical") => parenthetical = Some(text),
+                    Some("Dialogue") => {
+                        if let (Some(name), Some(scene)) = (&speaker, script.scenes.last_mut()) {
+                            scene.lines.push(ScriptLine {
+                                character: name.clone(),
+                                parenthetical: parenthetical.take(),
+                                text,
+                            });
+                        }
+                    }
+                    Some("Action") => {
+                        speaker = None;
+                        if let Some(scene) = script.scenes.last_mut() {
+                            scene.summary.get_or_insert(text);
+                        }
+                    }
+                    _ => speaker = None,
+                }
+            }
+            "Text" if opening => text_depth += 1,
+            "Text" if closing => text_depth = text_depth.saturating_sub(1),
+            "Summary" => in_summary = opening,
+            "TitlePage" => in_title_page = opening,
+            _ => {}
+        }
+    }
+    Ok(script)
+}
+
+/// Value of attribute `name` in a tag's source.
+fn attr(tag: &str, name: &str) -> Option<String> {
+    let pattern = format!("{}=\"", name);
+    let (at, _) = tag.match_indices(&pattern).find(|(i, _)| {
+        tag[..*i]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_whitespace)
+    })?;
+    let value = &tag[at + pattern.len()..];
+    let end = value.find('"')?;
+    Some(unescape(&value[..end]))
+}
+
+/// Decode XML character references.
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let c = match &rest[1..semi] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                entity => {
+                    let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<FinalDraft DocumentType="Script" Template="No" Version="5">
+  <Content>
+    <Paragraph Type="Scene Heading">
+      <SceneProperties Length="1/8" Page="1" Title="">
+        <Summary>
+          <Paragraph Alignment="Left"><Text>Alice confronts Bob.</Text></Paragraph>
+        </Summary>
+      </SceneProperties>
+      <Text>INT. KITCHEN - NIGHT</Text>
+    </Paragraph>
+    <Paragraph Type="Action"><Text>Rain.</Text></Paragraph>
+    <Paragraph Type="Character"><Text>ALICE (CONT'D)</Text></Paragraph>
+    <Paragraph Type="Parenthetical"><Text>(quietly)</Text></Paragraph>
+    <Paragraph Type="Dialogue"><Text>You&apos;re </Text><Text Style="Italic">late</Text><Text>.</Text></Paragraph>
+    <Paragraph>
+      <DualDialogue>
+        <Paragraph Type="Character"><Text>BOB</Text></Paragraph>
+        <Paragraph Type="Dialogue"><Text>Traffic &amp; rain.</Text></Paragraph>
+        <Paragraph Type="Character"><Text>ALICE</Text></Paragraph>
+        <Paragraph Type="Dialogue"><Text>Sure.</Text></Paragraph>
+      </DualDialogue>
+    </Paragraph>
+    <Paragraph Type="Transition"><Text>CUT TO:</Text></Paragraph>
+    <Paragraph Type="Scene Heading"><Text>EXT. STREET - DAY</Text></Paragraph>
+    <Paragraph Type="Action"><Text>Bob runs.</Text></Paragraph>
+  </Content>
+  <TitlePage>
+    <Content>
+      <Paragraph Alignment="Center"><Text>THE LONG NIGHT</Text></Paragraph>
+    </Content>
+  </TitlePage>
+</FinalDraft>
+"#;
+
+    #[test]
+    fn test_parse_fdx() {
+        let script = parse(SCRIPT).unwrap();
+        assert_eq!(script.title.as_deref(), Some("THE LONG NIGHT"));
+        assert_eq!(script.scenes.len(), 2);
+
+        let kitchen = &script.scenes[0];
+        assert_eq!(kitchen.heading, "INT. KITCHEN - NIGHT");
+        assert_eq!(kitchen.summary.as_deref(), Some("Alice confronts Bob."));
+        let lines: Vec<(&str, &str)> = kitchen
+            .lines
+            .iter()
+            .map(|l| (l.character.as_str(), l.text.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("Alice", "You're late."),
+                ("Bob", "Traffic & rain."),
+                ("Alice", "Sure."),
+            ]
+        );
+        assert_eq!(kitchen.lines[0].parenthetical.as_deref(), Some("(quietly)"));
+
+        assert_eq!(script.scenes[1].summary.as_deref(), Some("Bob runs."));
+        assert!(parse("<screenplay/>").is_err());
+    }
+}
@@ -0,0 +1,318 @@
+//! Fountain (`.fountain`) screenplay parser.
+//!
+//! Covers what import needs from the [Fountain](https://fountain.io/syntax)
+//! syntax: the title page's `Title:`, scene headings (including forced `.`
+//! headings and `#1#` scene numbers), character cues (including forced `@`
+//! cues and extensions like `(V.O.)`), dialogue with parentheticals, and
+//! synopses. Transitions, sections, page breaks, lyrics, centered text,
+//! notes and boneyard are skipped. Anything before the first heading is
+//! ignored.
+
+use super::{character_name, Screenplay, ScriptAdapter, ScriptLine, ScriptScene};
+use crate::NarraError;
+
+const HEADING_PREFIXES: &[&str] = &["INT./EXT", "INT/EXT", "I/E", "INT", "EXT", "EST"];
+
+/// Fountain, the plain-text screenplay format.
+pub struct Fountain;
+
+impl ScriptAdapter for Fountain {
+    fn name(&self) -> &'static str {
+        "fountain"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["fountain", "spmd"]
+    }
+
+    fn parse(&self, source: &str) -> Result<Screenplay, NarraError> {
+        Ok(parse(source))
+    }
+}
+
+/// Parse a Fountain screenplay.
+pub fn parse(source: &str) -> Screenplay {
+    let source = strip_delimited(&strip_delimited(source, "/*", "*/"), "[[", "]]");
+    let lines: Vec<&str> = source.lines().collect();
+
+    let (title, start) = title_page(&lines);
+    let mut script = Screenplay {
+        title,
+        scenes: Vec::new(),
+    };
+    let mut speaker: Option<String> = None;
+    let mut parenthetical: Option<String> = None;
+    let mut text = String::new();
+    let mut action = String::new();
+    let mut prev_blank = true;
+
+    let mut i = start;
+    while i < lines.len() {
+        let line = lines[i].trim();
+        i += 1;
+
+        if line.is_empty() {
+            if let Some(name) = speaker.take() {
+                flush_line(&mut script, &name, &mut parenthetical, &mut text);
+            }
+            finish_action(&mut script, &mut action);
+            prev_blank = true;
+            continue;
+        }
+        let after_blank = std::mem::replace(&mut prev_blank, false);
+
+        if let Some(name) = &speaker {
+            if line.starts_with('(') && line.ends_with(')') {
+                flush_line(&mut script, name, &mut parenthetical, &mut text);
+                parenthetical = Some(line.to_string());
+            } else {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(&plain(line));
+            }
+            continue;
+        }
+
+        if after_blank && is_heading(line) {
+            finish_action(&mut script, &mut action);
+            script.scenes.push(ScriptScene {
+                heading: heading_text(line),
+                ..Default::default()
+            });
+            continue;
+        }
+        if let Some(synopsis) = line.strip_prefix('=').filter(|s| !s.starts_with("==")) {
+            if let Some(scene) = script.scenes.last_mut() {
+                scene.summary.get_or_insert_with(|| plain(synopsis.trim()));
+            }
+            continue;
+        }
+        if is_skipped(line, after_blank) {
+            continue;
+        }
+        let next_filled = lines.get(i).is_some_and(|next| !next.trim().is_empty());
+        if after_blank && next_filled && is_cue(line) {
+            speaker = character_name(line);
+            continue;
+        }
+
+        // Action: the first paragraph of a scene stands in for its summary
+        if !action.is_empty() {
+            action.push(' ');
+        }
+        action.push_str(&plain(line.strip_prefix('!').unwrap_or(line)));
+    }
+    if let Some(name) = speaker.take() {
+        flush_line(&mut script, &name, &mut parenthetical, &mut text);
+    }
+    finish_action(&mut script, &mut action);
+    script
+}
+
+/// The title page's `Title:` value and the index of the first script line.
+fn title_page(lines: &[&str]) -> (Option<String>, usize) {
+    let first = lines.iter().position(|l| !l.trim().is_empty());
+    let Some(first) = first.filter(|&i| is_title_key(lines[i])) else {
+        return (None, 0);
+    };
+    let end = lines[first..]
+        .iter()
+        .position(|l| l.trim().is_empty())
+        .map_or(lines.len(), |n| first + n);
+
+    let mut title: Option<Vec<String>> = None;
+    let mut in_title = false;
+    for line in &lines[first..end] {
+        if is_title_key(line) {
+            let (key, value) = line.split_once(':').unwrap_or_default();
+            in_title = key.trim().eq_ignore_ascii_case("title");
+            if in_title && !value.trim().is_empty() {
+                title.get_or_insert_with(Vec::new).push(plain(value.trim()));
+            }
+        } else if in_title {
+            // Indented continuation lines
+            title.get_or_insert_with(Vec::new).push(plain(line.trim()));
+        }
+    }
+    (title.map(|parts| parts.join(" ")), end)
+}
+
+fn is_title_key(line: &str) -> bool {
+    !line.starts_with([' ', '\t'])
+        && line.split_once(':').is_some_and(|(key, _)| {
+            !key.is_empty() && key.chars().all(|c| c.is_alphabetic() || c == ' ')
+        })
+}
+
+fn is_heading(line: &str) -> bool {
+    if line.starts_with('.') {
+        return !line.starts_with("..");
+    }
+    HEADING_PREFIXES.iter().any(|prefix| {
+        line.get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+            && matches!(line[prefix.len()..].chars().next(), Some('.' | ' '))
+    })
+}
+
+/// Heading without the forcing dot or a trailing `#1A#` scene number.
+fn heading_text(line: &str) -> String {
+    let line = line.strip_prefix('.').unwrap_or(line).trim();
+    let line = match line.strip_suffix('#').and_then(|l| l.rsplit_once('#')) {
+        Some((heading, _number)) => heading.trim(),
+        None => line,
+    };
+    plain(line)
+}
+
+/// Transitions, sections, page breaks, lyrics and centered text.
+fn is_skipped(line: &str, after_blank: bool) -> bool {
+    if line.starts_with('#') || line.starts_with("===") || line.starts_with('~') {
+        return true;
+    }
+    if line.starts_with('>') {
+        // Forced transition or centered text
+        return true;
+    }
+    let upper = line.chars().any(char::is_alphabetic) && !line.chars().any(char::is_lowercase);
+    after_blank && upper && (line.ends_with("TO:") || line.starts_with("FADE "))
+}
+
+/// All caps before any extension, or forced with `@`.
+fn is_cue(line: &str) -> bool {
+    if line.starts_with('@') {
+        return true;
+    }
+    if line.starts_with('!') {
+        return false;
+    }
+    let name = line
+        .split('(')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('^');
+    name.chars().any(char::is_alphabetic) && !name.chars().any(char::is_lowercase)
+}
+
+fn flush_line(
+    script: &mut Screenplay,
+    speaker: &str,
+    parenthetical: &mut Option<String>,
+    text: &mut String,
+) {
+    let text = std::mem::take(text);
+    let parenthetical = parenthetical.take();
+    if text.is_empty() {
+        return;
+    }
+    if let Some(scene) = script.scenes.last_mut() {
+        scene.lines.push(ScriptLine {
+            character: speaker.to_string(),
+            parenthetical,
+            text,
+        });
+    }
+}
+
+fn finish_action(script: &mut Screenplay, action: &mut String) {
+    let action = std::mem::take(action);
+    if let Some(scene) = script.scenes.last_mut() {
+        if !action.is_empty() && scene.summary.is_none() {
+            scene.summary = Some(action);
+        }
+    }
+}
+
+/// Text without emphasis markers or escapes.
+fn plain(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut escaped = false;
+    for c in text.chars() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '*' | '_' if !escaped => {}
+            _ => {
+                out.push(c);
+                escaped = false;
+            }
+        }
+    }
+    out
+}
+
+/// `source` with everything from `open` to the matching `close` removed.
+fn strip_delimited(source: &str, open: &str, close: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find(open) {
+        out.push_str(&rest[..start]);
+        match rest[start + open.len()..].find(close) {
+            Some(end) => rest = &rest[start + open.len() + end + close.len()..],
+            None => {
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = "\
+Title: _The Long Night_
+Author: Someone
+
+INT. KITCHEN - NIGHT #1#
+
+= Alice confronts Bob about the money.
+
+Rain against the window. ALICE waits.
+
+ALICE
+(quietly)
+You're late.
+Again.
+
+BOB (O.S.)
+Traffic. /* cut this? */
+
+[[ Maybe a beat here ]]
+CUT TO:
+
+.SOMEWHERE ELSE
+
+@McCLANE
+(beat)
+Yippee.
+";
+
+    #[test]
+    fn test_parse_script() {
+        let script = parse(SCRIPT);
+        assert_eq!(script.title.as_deref(), Some("The Long Night"));
+        assert_eq!(script.scenes.len(), 2);
+
+        let kitchen = &script.scenes[0];
+        assert_eq!(kitchen.heading, "INT. KITCHEN - NIGHT");
+        assert_eq!(
+            kitchen.summary.as_deref(),
+            Some("Alice confronts Bob about the money.")
+        );
+        assert_eq!(kitchen.lines.len(), 2);
+        assert_eq!(kitchen.lines[0].character, "Alice");
+        assert_eq!(kitchen.lines[0].parenthetical.as_deref(), Some("(quietly)"));
+        assert_eq!(kitchen.lines[0].text, "You're late. Again.");
+        assert_eq!(kitchen.lines[1].character, "Bob");
+        assert_eq!(kitchen.lines[1].text, "Traffic.");
+
+        let elsewhere = &script.scenes[1];
+        assert_eq!(elsewhere.heading, "SOMEWHERE ELSE");
+        assert_eq!(elsewhere.summary, None);
+        assert_eq!(elsewhere.lines[0].character, "McCLANE");
+        assert_eq!(elsewhere.lines[0].text, "Yippee.");
+    }
+}
@@ -0,0 +1,535 @@
+//! Script import: scenes, characters and dialogue from screenplay formats.
+//!
+//! Each format's [`ScriptAdapter`] produces a [`Screenplay`]. [`plan_import`] turns it into
+//! a [`NarraImport`] so it goes through the regular world import (conflict
+//! handling, dry runs, backups): one location per place in the scene
+//! headings, one event and scene per heading, and a character per speaking
+//! part, reusing the world's characters and locations of the same name.
+//! [`store_dialogue`] then saves the spoken lines.
+
+pub mod fdx;
+pub mod fountain;
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::db::connection::NarraDb;
+use crate::mcp::types::{
+    CharacterSpec, EventSpec, LocationSpec, NarraImport, ParticipantSpec, SceneSpec,
+};
+use crate::models::character::get_character;
+use crate::models::dialogue::{replace_scene_dialogue, DialogueCreate};
+use crate::models::scene::get_scene;
+use crate::services::import::match_existing_names;
+use crate::NarraError;
+
+/// Participant role given to characters with dialogue in a scene.
+pub const SPEAKING_ROLE: &str = "speaking";
+
+/// Last heading segments that give the time of day rather than a place.
+const TIMES_OF_DAY: &[&str] = &[
+    "DAY",
+    "NIGHT",
+    "MORNING",
+    "AFTERNOON",
+    "EVENING",
+    "DAWN",
+    "DUSK",
+    "SUNRISE",
+    "SUNSET",
+    "NOON",
+    "MIDNIGHT",
+    "CONTINUOUS",
+    "LATER",
+    "MOMENTS LATER",
+    "SAME",
+    "SAME TIME",
+];
+
+/// A screenplay file format. Adding a format means implementing this and
+/// listing it in [`ADAPTERS`].
+pub trait ScriptAdapter: Send + Sync {
+    /// Name accepted by `--format`.
+    fn name(&self) -> &'static str;
+
+    /// File extensions the format is recognized by, lowercase.
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Parse a script in this format.
+    fn parse(&self, source: &str) -> Result<Screenplay, NarraError>;
+}
+
+/// Supported script formats.
+pub const ADAPTERS: &[&dyn ScriptAdapter] = &[&fountain::Fountain, &fdx::FinalDraft];
+
+/// The adapter called `name`.
+pub fn adapter(name: &str) -> Result<&'static dyn ScriptAdapter, NarraError> {
+    ADAPTERS
+        .iter()
+        .copied()
+        .find(|a| a.name().eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<_> = ADAPTERS.iter().map(|a| a.name()).collect();
+            NarraError::Validation(format!(
+                "Unknown script format '{}'; supported: {}",
+                name,
+                names.join(", ")
+            ))
+        })
+}
+
+/// The adapter for a file, guessed from its extension.
+pub fn adapter_for_path(path: &Path) -> Option<&'static dyn ScriptAdapter> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    ADAPTERS
+        .iter()
+        .copied()
+        .find(|a| a.extensions().contains(&ext.as_str()))
+}
+
+/// A parsed script.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Screenplay {
+    pub title: Option<String>,
+    pub scenes: Vec<ScriptScene>,
+}
+
+/// One scene: its heading, what happens, and who says what.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScriptScene {
+    /// Heading as written, e.g. `INT. KITCHEN - NIGHT`
+    pub heading: String,
+    /// Synopsis, or else the first action paragraph
+    pub summary: Option<String>,
+    pub lines: Vec<ScriptLine>,
+}
+
+/// A line of dialogue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptLine {
+    /// Speaker's name, already normalized (see [`character_name`])
+    pub character: String,
+    pub parenthetical: Option<String>,
+    pub text: String,
+}
+
+/// The parts of a scene heading.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heading {
+    /// "interior", "exterior", or none for INT./EXT. and establishing shots
+    pub setting: Option<&'static str>,
+    pub location: String,
+    pub time_of_day: Option<String>,
+}
+
+impl ScriptScene {
+    /// Speaking characters in order of first line.
+    pub fn speakers(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.lines
+            .iter()
+            .map(|l| l.character.as_str())
+            .filter(|name| seen.insert(*name))
+            .collect()
+    }
+
+    pub fn parsed_heading(&self) -> Heading {
+        parse_heading(&self.heading)
+    }
+
+    /// Scene title: place and time of day, e.g. "Kitchen - Night".
+    pub fn title(&self) -> String {
+        let heading = self.parsed_heading();
+        match heading.time_of_day {
+            Some(time) => format!("{} - {}", heading.location, title_case(&time)),
+            None => heading.location,
+        }
+    }
+}
+
+/// Split a scene heading (`INT. HOUSE - KITCHEN - NIGHT`) into setting,
+/// place and time of day. The place keeps any sub-location.
+pub fn parse_heading(heading: &str) -> Heading {
+    let trimmed = heading.trim().trim_start_matches('.');
+    let (setting, rest) = [
+        ("INT./EXT", None),
+        ("INT/EXT", None),
+        ("I/E", None),
+        ("INT", Some("interior")),
+        ("EXT", Some("exterior")),
+        ("EST", None),
+    ]
+    .iter()
+    .find(|(prefix, _)| {
+        trimmed
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+            && matches!(trimmed[prefix.len()..].chars().next(), Some('.' | ' '))
+    })
+    .map_or((None, trimmed), |(prefix, setting)| {
+        (*setting, &trimmed[prefix.len()..])
+    });
+    let rest = rest.trim_start_matches(['.', ' ']).trim();
+
+    let mut parts: Vec<&str> = rest.split(" - ").map(str::trim).collect();
+    let time_of_day = match parts.last() {
+        Some(last) if parts.len() > 1 && TIMES_OF_DAY.contains(&last.to_uppercase().as_str()) => {
+            parts.pop().map(str::to_uppercase)
+        }
+        _ => None,
+    };
+    Heading {
+        setting,
+        location: title_case(&parts.join(" - ")),
+        time_of_day,
+    }
+}
+
+/// Speaker name from a character cue: extensions like `(V.O.)` and the
+/// dual-dialogue caret dropped, capitalized as a name. None for an empty cue.
+pub fn character_name(cue: &str) -> Option<String> {
+    let name = cue.trim().trim_start_matches('@').trim_end_matches('^');
+    let name = name.split('(').next().unwrap_or_default().trim();
+    (!name.is_empty()).then(|| title_case(name))
+}
+
+/// `MARY O'NEIL` → `Mary O'Neil`. Text already in mixed case is kept.
+pub fn title_case(text: &str) -> String {
+    if text.chars().any(char::is_lowercase) {
+        return text.to_string();
+    }
+    text.split(' ')
+        .map(|word| {
+            let mut out = String::with_capacity(word.len());
+            let mut upper_next = true;
+            for (i, c) in word.chars().enumerate() {
+                if upper_next {
+                    out.extend(c.to_uppercase());
+                } else {
+                    out.extend(c.to_lowercase());
+                }
+                // Capitalize after "O'" or "D'" and after hyphens
+                upper_next = c == '-' || (c == '\'' && i == 1);
+            }
+            out
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Event and scene key prefix for a script file: a slug of its name.
+pub fn script_prefix(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match slug(&stem) {
+        s if s.is_empty() => "script".to_string(),
+        s => s,
+    }
+}
+
+/// A slug not yet in `taken`, which it is added to.
+fn unique_key(name: &str, fallback: &str, taken: &mut HashSet<String>) -> String {
+    let base = match slug(name) {
+        s if s.is_empty() => fallback.to_string(),
+        s => s,
+    };
+    let mut key = base.clone();
+    let mut n = 2;
+    while !taken.insert(key.clone()) {
+        key = format!("{}-{}", base, n);
+        n += 1;
+    }
+    key
+}
+
+fn bare_key(id: &Option<String>) -> Option<String> {
+    id.as_deref()
+        .map(|id| id.split_once(':').map_or(id, |(_, key)| key).to_string())
+}
+
+/// What importing a script will create, and the keys its lines need.
+#[derive(Debug, Clone)]
+pub struct ScriptPlan {
+    pub import: NarraImport,
+    /// Scene key for each script scene, in order
+    pub scene_keys: Vec<String>,
+    /// Character key for each speaker name
+    pub character_keys: HashMap<String, String>,
+    /// Characters and locations already in the world, reused by name
+    pub reused: usize,
+    pub dialogue_lines: usize,
+}
+
+/// Map a script onto the world. Events and scenes get the keys
+/// `<prefix>-001`, `<prefix>-002`, ... so importing the same script again
+/// meets them as conflicts instead of duplicating them.
+pub fn plan_import(script: &Screenplay, prefix: &str, world: &NarraImport) -> ScriptPlan {
+    let mut speakers = Vec::new();
+    let mut places = Vec::new();
+    let mut seen_speakers = HashSet::new();
+    let mut seen_places = HashSet::new();
+    for scene in &script.scenes {
+        for name in scene.speakers() {
+            if seen_speakers.insert(name.to_string()) {
+                speakers.push(name.to_string());
+            }
+        }
+        let heading = scene.parsed_heading();
+        if seen_places.insert(heading.location.clone()) {
+            places.push(heading);
+        }
+    }
+
+    let mut import = NarraImport {
+        characters: speakers
+            .iter()
+            .map(|name| CharacterSpec {
+                id: None,
+                name: name.clone(),
+                role: None,
+                aliases: None,
+                description: None,
+                profile: None,
+            })
+            .collect(),
+        locations: places
+            .iter()
+            .map(|heading| LocationSpec {
+                id: None,
+                name: heading.location.clone(),
+                description: None,
+                parent_id: None,
+                loc_type: heading.setting.map(String::from),
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let reused = match_existing_names(&mut import, world);
+
+    // Existing entities only need their keys; new ones get slugs
+    let mut character_keys = HashMap::new();
+    let mut taken: HashSet<String> = world
+        .characters
+        .iter()
+        .filter_map(|c| bare_key(&c.id))
+        .collect();
+    import.characters.retain_mut(|spec| {
+        let existing = spec.id.is_some();
+        let key = match &spec.id {
+            Some(id) => id.clone(),
+            None => unique_key(&spec.name, "character", &mut taken),
+        };
+        spec.id = Some(key.clone());
+        character_keys.insert(spec.name.clone(), key);
+        !existing
+    });
+    let mut location_keys = HashMap::new();
+    let mut taken: HashSet<String> = world
+        .locations
+        .iter()
+        .filter_map(|l| bare_key(&l.id))
+        .collect();
+    import.locations.retain_mut(|spec| {
+        let existing = spec.id.is_some();
+        let key = match &spec.id {
+            Some(id) => id.clone(),
+            None => unique_key(&spec.name, "location", &mut taken),
+        };
+        spec.id = Some(key.clone());
+        location_keys.insert(spec.name.clone(), key);
+        !existing
+    });
+
+    // Script events follow the world's timeline, ignoring an earlier import of this script
+    let own = format!("{}-", prefix);
+    let base = world
+        .events
+        .iter()
+        .filter(|e| !bare_key(&e.id).is_some_and(|key| key.starts_with(&own)))
+        .filter_map(|e| e.sequence)
+        .max()
+        .unwrap_or(0);
+
+    let mut scene_keys = Vec::new();
+    for (i, scene) in script.scenes.iter().enumerate() {
+        let key = format!("{}-{:03}", prefix, i + 1);
+        let title = scene.title();
+        import.events.push(EventSpec {
+            id: Some(key.clone()),
+            title: title.clone(),
+            description: None,
+            sequence: Some(base + i as i32 + 1),
+            date: None,
+            date_precision: None,
+            narrative_status: None,
+        });
+        import.scenes.push(SceneSpec {
+            id: Some(key.clone()),
+            title,
+            event_id: format!("event:{}", key),
+            location_id: format!(
+                "location:{}",
+                location_keys[&scene.parsed_heading().location]
+            ),
+            summary: scene.summary.clone(),
+            secondary_locations: Vec::new(),
+            participants: scene
+                .speakers()
+                .into_iter()
+                .map(|name| ParticipantSpec {
+                    character_id: format!("character:{}", character_keys[name]),
+                    role: SPEAKING_ROLE.to_string(),
+                    notes: None,
+                })
+                .collect(),
+        });
+        scene_keys.push(key);
+    }
+
+    ScriptPlan {
+        import,
+        scene_keys,
+        character_keys,
+        reused,
+        dialogue_lines: script.scenes.iter().map(|s| s.lines.len()).sum(),
+    }
+}
+
+/// Save each scene's dialogue, replacing what it had. Scenes in `skip`
+/// (ones that existed before an import that left them alone) and scenes or
+/// speakers the import failed to create are passed over. Returns how many
+/// lines were stored.
+pub async fn store_dialogue(
+    db: &NarraDb,
+    script: &Screenplay,
+    plan: &ScriptPlan,
+    skip: &HashSet<String>,
+) -> Result<usize, NarraError> {
+    let mut speakers = HashMap::new();
+    for (name, key) in &plan.character_keys {
+        if let Some(character) = get_character(db, key).await? {
+            speakers.insert(name.as_str(), character.id);
+        }
+    }
+
+    let mut stored = 0;
+    for (scene, key) in script.scenes.iter().zip(&plan.scene_keys) {
+        if scene.lines.is_empty() || skip.contains(key) {
+            continue;
+        }
+        let Some(existing) = get_scene(db, key).await? else {
+            continue;
+        };
+        let lines = scene
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                Some(DialogueCreate {
+                    scene: existing.id.clone(),
+                    character: speakers.get(line.character.as_str())?.clone(),
+                    position: i as i64,
+                    text: line.text.clone(),
+                    parenthetical: line.parenthetical.clone(),
+                })
+            })
+            .collect();
+        stored += replace_scene_dialogue(db, key, lines).await?;
+    }
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_heading() {
+        assert_eq!(
+            parse_heading("INT. HOUSE - KITCHEN - NIGHT"),
+            Heading {
+                setting: Some("interior"),
+                location: "House - Kitchen".into(),
+                time_of_day: Some("NIGHT".into()),
+            }
+        );
+        assert_eq!(
+            parse_heading("ext. o'neil farm"),
+            Heading {
+                setting: Some("exterior"),
+                location: "o'neil farm".into(),
+                time_of_day: None,
+            }
+        );
+        let heading = parse_heading("INT./EXT. MARY'S CAR - MOMENTS LATER");
+        assert_eq!(heading.setting, None);
+        assert_eq!(heading.location, "Mary's Car");
+        assert_eq!(
+            character_name("MARY O'NEIL (V.O.)^").as_deref(),
+            Some("Mary O'Neil")
+        );
+    }
+
+    #[test]
+    fn test_plan_reuses_world_entities_and_keys_scenes() {
+        let script = Screenplay {
+            title: Some("Pilot".into()),
+            scenes: vec![ScriptScene {
+                heading: "INT. KITCHEN - NIGHT".into(),
+                summary: Some("Alice waits.".into()),
+                lines: vec![
+                    ScriptLine {
+                        character: "Alice".into(),
+                        parenthetical: None,
+                        text: "You're late.".into(),
+                    },
+                    ScriptLine {
+                        character: "Bob".into(),
+                        parenthetical: Some("(sheepish)".into()),
+                        text: "Traffic.".into(),
+                    },
+                ],
+            }],
+        };
+        let world = NarraImport {
+            characters: vec![CharacterSpec {
+                id: Some("character:alice_w".into()),
+                name: "Alice".into(),
+                role: None,
+                aliases: None,
+                description: None,
+                profile: None,
+            }],
+            ..Default::default()
+        };
+
+        let plan = plan_import(&script, "pilot", &world);
+        assert_eq!(plan.reused, 1);
+        // Alice exists; only Bob and the kitchen are new
+        assert_eq!(plan.import.characters.len(), 1);
+        assert_eq!(plan.import.characters[0].id.as_deref(), Some("bob"));
+        assert_eq!(plan.character_keys["Alice"], "alice_w");
+        assert_eq!(plan.import.locations[0].id.as_deref(), Some("kitchen"));
+        assert_eq!(
+            plan.import.locations[0].loc_type.as_deref(),
+            Some("interior")
+        );
+
+        let scene = &plan.import.scenes[0];
+        assert_eq!(scene.id.as_deref(), Some("pilot-001"));
+        assert_eq!(scene.title, "Kitchen - Night");
+        assert_eq!(scene.event_id, "event:pilot-001");
+        assert_eq!(scene.participants[0].character_id, "character:alice_w");
+        assert_eq!(plan.dialogue_lines, 2);
+    }
+}
@@ -10,6 +10,7 @@ use std::sync::Arc;
 use surrealdb::RecordId;
 
 use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
 
 use crate::models::fact::EnforcementLevel;
 use crate::models::{Character, KnowledgeState};
@@ -24,7 +25,7 @@ pub struct PairContext {
 }
 
 /// A knowledge asymmetry between two characters.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KnowledgeAsymmetry {
     /// Character who knows (ID key only)
     pub knowing_character_id: String,
@@ -241,11 +242,11 @@ impl IronyDataProvider for SurrealIronyDataProvider {
         &self,
         character_id: &str,
     ) -> Result<Vec<KnowledgeState>, NarraError> {
-        let query = format!(
-            "SELECT * FROM knows WHERE in = character:{} ORDER BY learned_at DESC LIMIT 100",
-            character_id
-        );
-        let mut result = self.db.query(&query).await?;
+        let mut result = self
+            .db
+            .query("SELECT * FROM knows WHERE in = $character ORDER BY learned_at DESC LIMIT 100")
+            .bind(("character", record_id("character", character_id)?))
+            .await?;
         let states: Vec<KnowledgeState> = result.take(0)?;
         Ok(states)
     }
@@ -257,8 +258,11 @@ impl IronyDataProvider for SurrealIronyDataProvider {
 
         match table {
             "knowledge" => {
-                let query = format!("SELECT fact FROM knowledge:{}", key);
-                let mut result = self.db.query(&query).await?;
+                let mut result = self
+                    .db
+                    .query("SELECT fact FROM $ref")
+                    .bind(("ref", target.clone()))
+                    .await?;
 
                 #[derive(Deserialize)]
                 struct FactResult {
@@ -272,8 +276,11 @@ impl IronyDataProvider for SurrealIronyDataProvider {
                     .unwrap_or_else(|| format!("knowledge:{}", key)))
             }
             "character" => {
-                let query = format!("SELECT name FROM character:{}", key);
-                let mut result = self.db.query(&query).await?;
+                let mut result = self
+                    .db
+                    .query("SELECT name FROM $ref")
+                    .bind(("ref", target.clone()))
+                    .await?;
 
                 #[derive(Deserialize)]
                 struct NameResult {
@@ -431,17 +438,26 @@ impl IronyDataProvider for SurrealIronyDataProvider {
 /// Dramatic irony analysis service.
 pub struct IronyService {
     data: Arc<dyn IronyDataProvider>,
+    members: Option<HashSet<String>>,
 }
 
 impl IronyService {
     pub fn new(db: Arc<NarraDb>) -> Self {
+        Self::with_provider(Arc::new(SurrealIronyDataProvider::new(db)))
+    }
+
+    pub fn with_provider(data: Arc<dyn IronyDataProvider>) -> Self {
         Self {
-            data: Arc::new(SurrealIronyDataProvider::new(db)),
+            data,
+            members: None,
         }
     }
 
-    pub fn with_provider(data: Arc<dyn IronyDataProvider>) -> Self {
-        Self { data }
+    /// Restrict the analysis to these entity IDs (a phase's members);
+    /// `None` covers the whole world.
+    pub fn with_members(mut self, members: Option<HashSet<String>>) -> Self {
+        self.members = members;
+        self
     }
 
     /// Generate a dramatic irony report.
@@ -450,7 +466,10 @@ impl IronyService {
         character_id: Option<&str>,
         min_scene_threshold: usize,
     ) -> Result<IronyReport, NarraError> {
-        let characters = self.data.get_all_characters().await?;
+        let mut characters = self.data.get_all_characters().await?;
+        if let Some(members) = &self.members {
+            characters.retain(|c| members.contains(&c.id.to_string()));
+        }
 
         let char_names: HashMap<String, String> = characters
             .iter()
@@ -920,6 +939,8 @@ mod tests {
             event: None,
             premises: None,
             truth_value: None,
+            derived_from: None,
+            derivation: None,
             learned_at: surrealdb::Datetime::from(past),
             created_at: Default::default(),
             updated_at: Default::default(),
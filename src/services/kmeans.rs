@@ -0,0 +1,359 @@
+//! Memory-bounded, seeded k-means shared by theme clustering and phase detection.
+//!
+//! Small inputs run full k-means (linfa) on a dense matrix. When that matrix
+//! would exceed the configured memory ceiling, mini-batch k-means (Sculley,
+//! 2010) runs over the rows in place instead: k-means++ seeding on a sample,
+//! then centroid updates from random batches, so memory stays at
+//! `k × dims` plus one batch of row indices however large the world is.
+//! Both paths draw from one seeded RNG, so the same world and seed always
+//! give the same clusters.
+
+use std::sync::RwLock;
+
+use linfa::prelude::*;
+use linfa_clustering::KMeans;
+use ndarray::{Array1, Array2};
+use rand_xoshiro::rand_core::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256Plus;
+
+use crate::NarraError;
+
+/// Default ceiling for the dense k-means matrix.
+pub const DEFAULT_MEMORY_LIMIT_MB: usize = 256;
+/// Default RNG seed.
+pub const DEFAULT_SEED: u64 = 42;
+
+const MAX_ITERATIONS: u64 = 300;
+const TOLERANCE: f64 = 1e-4;
+const MIN_BATCH: usize = 256;
+const MAX_BATCH: usize = 4096;
+/// Mini-batch passes without a centroid moving more than the tolerance
+/// before stopping early.
+const PATIENCE: usize = 10;
+
+/// Memory ceiling and seed for clustering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KMeansOptions {
+    pub memory_limit_bytes: usize,
+    pub seed: u64,
+}
+
+impl Default for KMeansOptions {
+    fn default() -> Self {
+        Self {
+            memory_limit_bytes: DEFAULT_MEMORY_LIMIT_MB * 1024 * 1024,
+            seed: DEFAULT_SEED,
+        }
+    }
+}
+
+static CONFIGURED: RwLock<Option<KMeansOptions>> = RwLock::new(None);
+
+/// Set the process-wide options (from `[clustering]` config at startup).
+pub fn configure(options: KMeansOptions) {
+    *CONFIGURED.write().unwrap_or_else(|e| e.into_inner()) = Some(options);
+}
+
+/// Options set by [`configure`], or the defaults.
+pub fn configured() -> KMeansOptions {
+    let configured = *CONFIGURED.read().unwrap_or_else(|e| e.into_inner());
+    configured.unwrap_or_default()
+}
+
+/// Configured options, with the seed overridden when one is given
+/// (`--seed` on the CLI, `seed` over MCP).
+pub fn seeded(seed: Option<u64>) -> KMeansOptions {
+    let configured = configured();
+    KMeansOptions {
+        seed: seed.unwrap_or(configured.seed),
+        ..configured
+    }
+}
+
+/// How a fit was computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KMeansStrategy {
+    Full,
+    MiniBatch { batch_size: usize },
+}
+
+/// Cluster assignment per row, and the centroids.
+#[derive(Debug, Clone)]
+pub struct KMeansFit {
+    pub assignments: Vec<usize>,
+    pub centroids: Vec<Vec<f64>>,
+    pub strategy: KMeansStrategy,
+}
+
+/// Bytes full k-means needs for `rows × dims` with `k` clusters: the f64
+/// matrix, linfa's working copy, and the row-to-centroid distances.
+pub fn full_memory_bytes(rows: usize, dims: usize, k: usize) -> usize {
+    rows.saturating_mul(dims.saturating_mul(2).saturating_add(k))
+        .saturating_mul(std::mem::size_of::<f64>())
+}
+
+/// Cluster `rows` (all of equal length) into `k` groups.
+pub fn fit<R, T>(rows: &[R], k: usize, options: &KMeansOptions) -> Result<KMeansFit, NarraError>
+where
+    R: AsRef<[T]>,
+    T: Copy + Into<f64>,
+{
+    let n = rows.len();
+    if k == 0 || n < k {
+        return Err(NarraError::Validation(format!(
+            "Cannot form {} clusters from {} rows",
+            k, n
+        )));
+    }
+    let dims = rows[0].as_ref().len();
+    if let Some(bad) = rows.iter().find(|r| r.as_ref().len() != dims) {
+        return Err(NarraError::Database(format!(
+            "Invalid embedding dimension: expected {}, got {}",
+            dims,
+            bad.as_ref().len()
+        )));
+    }
+
+    if full_memory_bytes(n, dims, k) <= options.memory_limit_bytes {
+        return fit_full(rows, dims, k, options.seed);
+    }
+
+    let batch_size = (options.memory_limit_bytes / (dims.max(1) * std::mem::size_of::<f64>()))
+        .clamp(MIN_BATCH, MAX_BATCH)
+        .min(n);
+    tracing::info!(
+        "Clustering {} rows × {} dims with mini-batch k-means (batch {}): a dense matrix would exceed the {} MB ceiling",
+        n,
+        dims,
+        batch_size,
+        options.memory_limit_bytes / (1024 * 1024)
+    );
+    Ok(fit_mini_batch(rows, k, batch_size, options.seed))
+}
+
+fn fit_full<R, T>(rows: &[R], dims: usize, k: usize, seed: u64) -> Result<KMeansFit, NarraError>
+where
+    R: AsRef<[T]>,
+    T: Copy + Into<f64>,
+{
+    let n = rows.len();
+    let data: Vec<f64> = rows
+        .iter()
+        .flat_map(|r| r.as_ref().iter().map(|&v| v.into()))
+        .collect();
+    let matrix = Array2::from_shape_vec((n, dims), data)
+        .map_err(|e| NarraError::Database(format!("Failed to create embedding matrix: {}", e)))?;
+    let dataset = DatasetBase::new(matrix, Array1::from_elem(n, ()));
+
+    let model = KMeans::params_with_rng(k, Xoshiro256Plus::seed_from_u64(seed))
+        .max_n_iterations(MAX_ITERATIONS)
+        .tolerance(TOLERANCE)
+        .fit(&dataset)
+        .map_err(|e| NarraError::Database(format!("K-means clustering failed: {}", e)))?;
+
+    let assignments: Vec<usize> = model.predict(&dataset).iter().cloned().collect();
+    let centroids = model
+        .centroids()
+        .rows()
+        .into_iter()
+        .map(|row| row.to_vec())
+        .collect();
+    Ok(KMeansFit {
+        assignments,
+        centroids,
+        strategy: KMeansStrategy::Full,
+    })
+}
+
+fn fit_mini_batch<R, T>(rows: &[R], k: usize, batch_size: usize, seed: u64) -> KMeansFit
+where
+    R: AsRef<[T]>,
+    T: Copy + Into<f64>,
+{
+    let n = rows.len();
+    let mut rng = Xoshiro256Plus::seed_from_u64(seed);
+
+    // k-means++ seeding on a sample of a few batches
+    let sample: Vec<usize> = (0..(batch_size * 4).min(n))
+        .map(|_| uniform_index(&mut rng, n))
+        .collect();
+    let mut centroids: Vec<Vec<f64>> = Vec::with_capacity(k);
+    centroids.push(to_f64(
+        rows[sample[uniform_index(&mut rng, sample.len())]].as_ref(),
+    ));
+    let mut nearest: Vec<f64> = sample
+        .iter()
+        .map(|&i| distance_sq(rows[i].as_ref(), &centroids[0]))
+        .collect();
+    while centroids.len() < k {
+        let total: f64 = nearest.iter().sum();
+        let pick = if total > 0.0 {
+            let mut target = uniform_unit(&mut rng) * total;
+            nearest
+                .iter()
+                .position(|&d| {
+                    target -= d;
+                    target <= 0.0
+                })
+                .unwrap_or(sample.len() - 1)
+        } else {
+            uniform_index(&mut rng, sample.len())
+        };
+        let centroid = to_f64(rows[sample[pick]].as_ref());
+        for (d, &i) in nearest.iter_mut().zip(&sample) {
+            *d = d.min(distance_sq(rows[i].as_ref(), &centroid));
+        }
+        centroids.push(centroid);
+    }
+
+    // Mini-batch updates with a per-centroid learning rate of 1/count
+    let mut counts = vec![0u64; k];
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut calm = 0;
+    for _ in 0..MAX_ITERATIONS {
+        batch.clear();
+        batch.extend((0..batch_size).map(|_| uniform_index(&mut rng, n)));
+        let labels: Vec<usize> = batch
+            .iter()
+            .map(|&i| nearest_centroid(rows[i].as_ref(), &centroids))
+            .collect();
+
+        let mut shift = 0.0_f64;
+        for (&i, &c) in batch.iter().zip(&labels) {
+            counts[c] += 1;
+            let rate = 1.0 / counts[c] as f64;
+            for (centroid, &v) in centroids[c].iter_mut().zip(rows[i].as_ref()) {
+                let step = rate * (v.into() - *centroid);
+                *centroid += step;
+                shift = shift.max(step.abs());
+            }
+        }
+        if shift < TOLERANCE {
+            calm += 1;
+            if calm >= PATIENCE {
+                break;
+            }
+        } else {
+            calm = 0;
+        }
+    }
+
+    let assignments = rows
+        .iter()
+        .map(|r| nearest_centroid(r.as_ref(), &centroids))
+        .collect();
+    KMeansFit {
+        assignments,
+        centroids,
+        strategy: KMeansStrategy::MiniBatch { batch_size },
+    }
+}
+
+fn to_f64<T: Copy + Into<f64>>(row: &[T]) -> Vec<f64> {
+    row.iter().map(|&v| v.into()).collect()
+}
+
+/// Squared Euclidean distance between a row and a centroid.
+pub fn distance_sq<T: Copy + Into<f64>>(row: &[T], centroid: &[f64]) -> f64 {
+    row.iter()
+        .zip(centroid)
+        .map(|(&v, &c)| {
+            let diff = v.into() - c;
+            diff * diff
+        })
+        .sum()
+}
+
+fn nearest_centroid<T: Copy + Into<f64>>(row: &[T], centroids: &[Vec<f64>]) -> usize {
+    centroids
+        .iter()
+        .map(|c| distance_sq(row, c))
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(i, _)| i)
+}
+
+fn uniform_index(rng: &mut Xoshiro256Plus, n: usize) -> usize {
+    (rng.next_u64() % n as u64) as usize
+}
+
+fn uniform_unit(rng: &mut Xoshiro256Plus) -> f64 {
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two well-separated blobs of `per_blob` rows each.
+    fn blobs(per_blob: usize) -> Vec<Vec<f32>> {
+        (0..per_blob * 2)
+            .map(|i| {
+                let jitter = (i % 7) as f32 * 0.01;
+                if i < per_blob {
+                    vec![1.0 + jitter, 0.0, 0.0, jitter]
+                } else {
+                    vec![0.0, jitter, 0.0, 1.0 + jitter]
+                }
+            })
+            .collect()
+    }
+
+    fn same_cluster(fit: &KMeansFit, rows: std::ops::Range<usize>) -> bool {
+        let first = fit.assignments[rows.start];
+        fit.assignments[rows].iter().all(|&c| c == first)
+    }
+
+    #[test]
+    fn test_mini_batch_under_memory_ceiling() {
+        let rows = blobs(500);
+        let options = KMeansOptions {
+            memory_limit_bytes: 4096,
+            seed: 7,
+        };
+        let fit = fit(&rows, 2, &options).unwrap();
+
+        assert!(matches!(fit.strategy, KMeansStrategy::MiniBatch { .. }));
+        assert!(same_cluster(&fit, 0..500));
+        assert!(same_cluster(&fit, 500..1000));
+        assert_ne!(fit.assignments[0], fit.assignments[999]);
+    }
+
+    #[test]
+    fn test_same_seed_same_clusters() {
+        let rows = blobs(300);
+        let small = KMeansOptions {
+            memory_limit_bytes: 4096,
+            seed: 11,
+        };
+        let first = fit(&rows, 3, &small).unwrap();
+        let second = fit(&rows, 3, &small).unwrap();
+        assert_eq!(first.assignments, second.assignments);
+
+        let full = KMeansOptions {
+            seed: 11,
+            ..Default::default()
+        };
+        let first = fit(&rows, 2, &full).unwrap();
+        let second = fit(&rows, 2, &full).unwrap();
+        assert_eq!(first.strategy, KMeansStrategy::Full);
+        assert_eq!(first.assignments, second.assignments);
+    }
+
+    #[test]
+    fn test_seeded_overrides_only_the_seed() {
+        let configured = configured();
+        assert_eq!(seeded(None), configured);
+        let options = seeded(Some(99));
+        assert_eq!(options.seed, 99);
+        assert_eq!(options.memory_limit_bytes, configured.memory_limit_bytes);
+    }
+
+    #[test]
+    fn test_full_memory_estimate() {
+        // 50k rows of 384-dim embeddings need far more than the default ceiling
+        assert!(full_memory_bytes(50_000, 384, 150) > KMeansOptions::default().memory_limit_bytes);
+        assert!(full_memory_bytes(500, 384, 16) < KMeansOptions::default().memory_limit_bytes);
+        assert!(fit(&[vec![0.0f32; 4]], 2, &KMeansOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! Who knows what: characters × facts in one grid.
+//!
+//! Each cell is the certainty of the character's latest knowledge edge to
+//! the fact (by `learned_at`), or "unaware" when there is none. Facts are
+//! the knowledge targets at least one character has an edge to, ordered so
+//! the ones most characters are aware of come first.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::NarraError;
+
+/// Cell value for a character with no knowledge of a fact.
+pub const UNAWARE: &str = "unaware";
+
+/// A fact column.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixFact {
+    pub id: String,
+    pub label: String,
+    /// Characters with any knowledge edge to it
+    pub aware: usize,
+}
+
+/// A character row; `cells` line up with the matrix facts.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixRow {
+    pub character_id: String,
+    pub name: String,
+    pub cells: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KnowledgeMatrix {
+    pub facts: Vec<MatrixFact>,
+    pub rows: Vec<MatrixRow>,
+    /// Facts left out by the limit
+    pub omitted_facts: usize,
+}
+
+/// One knowledge edge.
+#[derive(Debug, Clone)]
+pub struct KnowledgeCell {
+    pub character_id: String,
+    pub target_id: String,
+    pub certainty: String,
+}
+
+/// One-character symbol for a certainty, for compact display.
+pub fn symbol(certainty: &str) -> &'static str {
+    match certainty {
+        "knows" => "K",
+        "suspects" => "S",
+        "believes_wrongly" => "W",
+        "uncertain" => "?",
+        "assumes" => "A",
+        "denies" => "D",
+        "forgotten" => "F",
+        UNAWARE => "·",
+        _ => "*",
+    }
+}
+
+/// Build the grid. `characters` are (id, name) rows in display order,
+/// `labels` name the facts, and `edges` are latest-first per pair (later
+/// duplicates are ignored). At most `limit` facts are kept.
+pub fn build_matrix(
+    characters: &[(String, String)],
+    labels: &HashMap<String, String>,
+    edges: &[KnowledgeCell],
+    limit: usize,
+) -> KnowledgeMatrix {
+    let mut cells: HashMap<(&str, &str), &str> = HashMap::new();
+    for edge in edges {
+        cells
+            .entry((edge.character_id.as_str(), edge.target_id.as_str()))
+            .or_insert(edge.certainty.as_str());
+    }
+
+    let in_rows: HashSet<&str> = characters.iter().map(|(id, _)| id.as_str()).collect();
+    let mut aware: HashMap<&str, usize> = HashMap::new();
+    for &(character, target) in cells.keys() {
+        if in_rows.contains(character) {
+            *aware.entry(target).or_default() += 1;
+        }
+    }
+    let label = |id: &str| labels.get(id).cloned().unwrap_or_else(|| id.to_string());
+    let mut facts: Vec<MatrixFact> = aware
+        .into_iter()
+        .map(|(id, aware)| MatrixFact {
+            id: id.to_string(),
+            label: label(id),
+            aware,
+        })
+        .collect();
+    facts.sort_by(|a, b| b.aware.cmp(&a.aware).then_with(|| a.label.cmp(&b.label)));
+    let omitted_facts = facts.len().saturating_sub(limit);
+    facts.truncate(limit);
+
+    let rows = characters
+        .iter()
+        .map(|(id, name)| MatrixRow {
+            character_id: id.clone(),
+            name: name.clone(),
+            cells: facts
+                .iter()
+                .map(|f| {
+                    cells
+                        .get(&(id.as_str(), f.id.as_str()))
+                        .unwrap_or(&UNAWARE)
+                        .to_string()
+                })
+                .collect(),
+        })
+        .collect();
+
+    KnowledgeMatrix {
+        facts,
+        rows,
+        omitted_facts,
+    }
+}
+
+impl KnowledgeMatrix {
+    /// Write as CSV: a `character` column, then one column per fact.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), NarraError> {
+        let csv_error = |e: csv::Error| NarraError::Validation(format!("CSV: {}", e));
+        let mut writer = csv::Writer::from_writer(writer);
+        let header =
+            std::iter::once("character").chain(self.facts.iter().map(|f| f.label.as_str()));
+        writer.write_record(header).map_err(csv_error)?;
+        for row in &self.rows {
+            let record =
+                std::iter::once(row.name.as_str()).chain(row.cells.iter().map(String::as_str));
+            writer.write_record(record).map_err(csv_error)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+pub struct KnowledgeMatrixService {
+    db: Arc<NarraDb>,
+}
+
+impl KnowledgeMatrixService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// The matrix for `characters` (full IDs; every character when empty).
+    pub async fn matrix(
+        &self,
+        characters: &[String],
+        limit: usize,
+    ) -> Result<KnowledgeMatrix, NarraError> {
+        #[derive(Deserialize)]
+        struct CharacterRow {
+            id: RecordId,
+            name: String,
+        }
+        #[derive(Deserialize)]
+        struct FactRow {
+            id: RecordId,
+            label: Option<String>,
+        }
+        #[derive(Deserialize)]
+        struct KnowsRow {
+            character: RecordId,
+            target: RecordId,
+            certainty: String,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, name FROM character ORDER BY name ASC; \
+                 SELECT id, fact AS label FROM knowledge; \
+                 SELECT in AS character, out AS target, certainty, learned_at FROM knows \
+                 ORDER BY learned_at DESC",
+            )
+            .await?;
+        let all: Vec<CharacterRow> = response.take(0)?;
+        let facts: Vec<FactRow> = response.take(1)?;
+        let knows: Vec<KnowsRow> = response.take(2)?;
+
+        let names: HashMap<String, String> = all
+            .into_iter()
+            .map(|c| (c.id.to_string(), c.name))
+            .collect();
+        let rows: Vec<(String, String)> = if characters.is_empty() {
+            let mut rows: Vec<(String, String)> = names
+                .iter()
+                .map(|(id, n)| (id.clone(), n.clone()))
+                .collect();
+            rows.sort_by(|a, b| a.1.cmp(&b.1));
+            rows
+        } else {
+            characters
+                .iter()
+                .map(|id| {
+                    names
+                        .get(id)
+                        .map(|n| (id.clone(), n.clone()))
+                        .ok_or_else(|| NarraError::NotFound {
+                            entity_type: "character".to_string(),
+                            id: id.clone(),
+                        })
+                })
+                .collect::<Result<_, _>>()?
+        };
+
+        // Knowledge about a character is labelled with their name
+        let mut labels = names;
+        labels.extend(
+            facts
+                .into_iter()
+                .filter_map(|f| Some((f.id.to_string(), f.label?))),
+        );
+        let edges: Vec<KnowledgeCell> = knows
+            .into_iter()
+            .map(|k| KnowledgeCell {
+                character_id: k.character.to_string(),
+                target_id: k.target.to_string(),
+                certainty: k.certainty,
+            })
+            .collect();
+        Ok(build_matrix(&rows, &labels, &edges, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(character: &str, target: &str, certainty: &str) -> KnowledgeCell {
+        KnowledgeCell {
+            character_id: character.to_string(),
+            target_id: target.to_string(),
+            certainty: certainty.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_matrix_uses_latest_edge_and_orders_facts() {
+        let characters = vec![
+            ("character:alice".to_string(), "Alice".to_string()),
+            ("character:bob".to_string(), "Bob".to_string()),
+        ];
+        let labels: HashMap<String, String> = [
+            ("knowledge:affair", "Gray is having an affair"),
+            ("knowledge:poison", "The wine was poisoned"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let edges = vec![
+            edge("character:alice", "knowledge:poison", "knows"),
+            edge("character:alice", "knowledge:poison", "suspects"),
+            edge("character:bob", "knowledge:poison", "believes_wrongly"),
+            edge("character:alice", "knowledge:affair", "suspects"),
+            edge("character:carol", "knowledge:affair", "knows"),
+        ];
+
+        let matrix = build_matrix(&characters, &labels, &edges, 10);
+        let facts: Vec<&str> = matrix.facts.iter().map(|f| f.label.as_str()).collect();
+        assert_eq!(
+            facts,
+            vec!["The wine was poisoned", "Gray is having an affair"]
+        );
+        assert_eq!(matrix.rows[0].cells, vec!["knows", "suspects"]);
+        assert_eq!(matrix.rows[1].cells, vec!["believes_wrongly", UNAWARE]);
+
+        let limited = build_matrix(&characters, &labels, &edges, 1);
+        assert_eq!(limited.facts.len(), 1);
+        assert_eq!(limited.omitted_facts, 1);
+
+        let mut csv = Vec::new();
+        matrix.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            vec![
+                "character,The wine was poisoned,Gray is having an affair",
+                "Alice,knows,suspects",
+                "Bob,believes_wrongly,unaware",
+            ]
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Constructed-language lexicon: CSV import and gloss drift.
+//!
+//! Notes often gloss a conlang word inline, as `vash (water)` or `vash,
+//! meaning water`. Each such gloss is compared with the lexicon's by the
+//! content words they share; a note whose gloss shares none has drifted
+//! from the lexicon, and so from any note that agrees with it.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::Read;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::models::lexeme::{list_lexemes, Lexeme, LexemeCreate};
+use crate::NarraError;
+
+/// Accepted headers for each lexicon column (case-insensitive).
+const COLUMNS: &[(&str, &[&str])] = &[
+    ("word", &["word", "lemma", "headword"]),
+    ("gloss", &["gloss", "meaning", "translation", "definition"]),
+    (
+        "part_of_speech",
+        &["part_of_speech", "pos", "part of speech"],
+    ),
+    ("etymology", &["etymology", "origin"]),
+    ("language", &["language", "lang"]),
+];
+
+/// Too common to tell two glosses apart.
+const STOPWORDS: &[&str] = &[
+    "the",
+    "and",
+    "for",
+    "with",
+    "one",
+    "that",
+    "who",
+    "which",
+    "from",
+    "into",
+    "its",
+    "his",
+    "her",
+    "their",
+    "something",
+    "someone",
+    "thing",
+    "kind",
+];
+
+/// Glosses longer than this are prose, not glosses.
+const MAX_GLOSS_WORDS: usize = 8;
+
+/// Read lexicon entries from CSV. `word` and `gloss` columns are required;
+/// rows without a `language` get `default_language`.
+pub fn read_lexicon_csv<R: Read>(
+    reader: R,
+    default_language: Option<&str>,
+) -> Result<Vec<LexemeCreate>, NarraError> {
+    let csv_error = |e: csv::Error| NarraError::Validation(format!("CSV: {}", e));
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(String::from)
+        .collect();
+
+    let columns: HashMap<&str, usize> = COLUMNS
+        .iter()
+        .filter_map(|(field, names)| {
+            headers
+                .iter()
+                .position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)))
+                .map(|i| (*field, i))
+        })
+        .collect();
+    for required in ["word", "gloss"] {
+        if !columns.contains_key(required) {
+            return Err(NarraError::Validation(format!(
+                "No '{}' column; headers are: {}",
+                required,
+                headers.join(", ")
+            )));
+        }
+    }
+
+    let mut entries = Vec::new();
+    for (row, record) in reader.records().enumerate() {
+        // Header is line 1
+        let line = row + 2;
+        let record = record.map_err(csv_error)?;
+        let get = |field: &str| {
+            columns
+                .get(field)
+                .and_then(|i| record.get(*i))
+                .filter(|v| !v.is_empty())
+                .map(String::from)
+        };
+        let Some(word) = get("word") else {
+            continue;
+        };
+        let gloss = get("gloss").ok_or_else(|| {
+            NarraError::Validation(format!("Line {}: '{}' has no gloss", line, word))
+        })?;
+        entries.push(LexemeCreate {
+            word,
+            gloss,
+            part_of_speech: get("part_of_speech"),
+            etymology: get("etymology"),
+            language: get("language").or_else(|| default_language.map(String::from)),
+        });
+    }
+    Ok(entries)
+}
+
+/// A gloss given for a word in a note.
+#[derive(Debug, Clone, Serialize)]
+pub struct GlossUsage {
+    pub note_id: String,
+    pub note_title: String,
+    pub gloss: String,
+    /// Whether it agrees with the lexicon
+    pub agrees: bool,
+}
+
+/// A word glossed differently from the lexicon in at least one note.
+#[derive(Debug, Clone, Serialize)]
+pub struct GlossDrift {
+    pub lexeme_id: String,
+    pub word: String,
+    pub language: Option<String>,
+    pub gloss: String,
+    /// Every inline gloss found, agreeing or not
+    pub usages: Vec<GlossUsage>,
+}
+
+/// A note's text to scan for inline glosses.
+#[derive(Debug, Clone)]
+pub struct NoteText {
+    pub id: String,
+    pub title: String,
+    pub body: String,
+}
+
+fn content_words(text: &str) -> BTreeSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() > 2 && !STOPWORDS.contains(w))
+        .map(|w| w.strip_suffix('s').unwrap_or(w).to_string())
+        .collect()
+}
+
+/// Whether a note's gloss means the same as the lexicon's.
+fn glosses_agree(lexicon: &str, found: &str) -> bool {
+    let (a, b) = (content_words(lexicon), content_words(found));
+    if a.is_empty() || b.is_empty() {
+        return lexicon.trim().eq_ignore_ascii_case(found.trim());
+    }
+    !a.is_disjoint(&b)
+}
+
+/// Glosses given inline for `word` in `text` (already lowercased).
+fn inline_glosses(text: &str, word: &str) -> Vec<String> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '\'' || c == '-';
+    let decoration = |c: char| matches!(c, '*' | '_' | '"' | '\'' | '\u{201d}' | '\u{2019}');
+
+    let mut glosses = Vec::new();
+    for (start, _) in text.match_indices(word) {
+        let end = start + word.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+        if before.is_some_and(is_word_char) || after.is_some_and(is_word_char) {
+            continue;
+        }
+
+        let rest = text[end..].trim_start_matches(decoration).trim_start();
+        let gloss = if let Some(inner) = rest.strip_prefix('(') {
+            inner.split(')').next()
+        } else {
+            let rest = rest.trim_start_matches(|c: char| {
+                matches!(c, ',' | ':' | '\u{2014}' | '\u{2013}') || c.is_whitespace()
+            });
+            ["means ", "meaning "]
+                .iter()
+                .find_map(|m| rest.strip_prefix(m))
+                .and_then(|g| g.split(['.', ',', ';', '!', '?', '\n', ')']).next())
+        };
+        let Some(gloss) = gloss else {
+            continue;
+        };
+        let gloss = gloss
+            .trim()
+            .trim_matches(|c: char| decoration(c) || c == '\u{201c}')
+            .trim();
+        let words = gloss.split_whitespace().count();
+        if words > 0 && words <= MAX_GLOSS_WORDS {
+            glosses.push(gloss.to_string());
+        }
+    }
+    glosses
+}
+
+/// Words whose inline glosses in notes disagree with the lexicon.
+pub fn find_gloss_drift(lexemes: &[Lexeme], notes: &[NoteText]) -> Vec<GlossDrift> {
+    // The same spelling may exist in several languages; a gloss agreeing
+    // with any of them is not drift.
+    let mut by_word: HashMap<String, Vec<&Lexeme>> = HashMap::new();
+    for lexeme in lexemes {
+        by_word
+            .entry(lexeme.word.to_lowercase())
+            .or_default()
+            .push(lexeme);
+    }
+    let bodies: Vec<String> = notes.iter().map(|n| n.body.to_lowercase()).collect();
+
+    let mut drift = Vec::new();
+    let mut words: Vec<(&String, &Vec<&Lexeme>)> = by_word.iter().collect();
+    words.sort_by(|a, b| a.0.cmp(b.0));
+    for (word, entries) in words {
+        let usages: Vec<GlossUsage> = notes
+            .iter()
+            .zip(&bodies)
+            .flat_map(|(note, body)| {
+                inline_glosses(body, word)
+                    .into_iter()
+                    .map(move |gloss| GlossUsage {
+                        note_id: note.id.clone(),
+                        note_title: note.title.clone(),
+                        agrees: entries.iter().any(|l| glosses_agree(&l.gloss, &gloss)),
+                        gloss,
+                    })
+            })
+            .collect();
+        if usages.iter().all(|u| u.agrees) {
+            continue;
+        }
+        let lexeme = entries[0];
+        drift.push(GlossDrift {
+            lexeme_id: lexeme.id.to_string(),
+            word: lexeme.word.clone(),
+            language: lexeme.language.clone(),
+            gloss: lexeme.gloss.clone(),
+            usages,
+        });
+    }
+    drift
+}
+
+pub struct LexiconService {
+    db: Arc<NarraDb>,
+}
+
+impl LexiconService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Words glossed in notes differently from the lexicon.
+    pub async fn gloss_drift(&self) -> Result<Vec<GlossDrift>, NarraError> {
+        #[derive(Deserialize)]
+        struct NoteRow {
+            id: RecordId,
+            title: String,
+            body: String,
+        }
+
+        let lexemes = list_lexemes(&self.db, None).await?;
+        if lexemes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut response = self.db.query("SELECT id, title, body FROM note").await?;
+        let notes: Vec<NoteRow> = response.take(0)?;
+        let notes: Vec<NoteText> = notes
+            .into_iter()
+            .map(|n| NoteText {
+                id: n.id.to_string(),
+                title: n.title,
+                body: n.body,
+            })
+            .collect();
+
+        Ok(find_gloss_drift(&lexemes, &notes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexeme(word: &str, gloss: &str) -> Lexeme {
+        Lexeme {
+            id: RecordId::from(("lexeme", word)),
+            word: word.to_string(),
+            gloss: gloss.to_string(),
+            part_of_speech: None,
+            etymology: None,
+            language: Some("Old Vesh".to_string()),
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        }
+    }
+
+    fn note(id: &str, body: &str) -> NoteText {
+        NoteText {
+            id: id.to_string(),
+            title: id.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_flags_glosses_that_drift_from_lexicon() {
+        let lexemes = vec![lexeme("vash", "water"), lexeme("kel", "stone, rock")];
+        let notes = vec![
+            note("note:a", "At the well they cry *vash* (flowing water)."),
+            note("note:b", "The word vash, meaning fire, is sacred."),
+            note("note:c", "Kel (rocks) line the road. Vashti is a name."),
+            note("note:d", "Kel means \"stone\"."),
+        ];
+
+        let drift = find_gloss_drift(&lexemes, &notes);
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].word, "vash");
+        let usages: Vec<(&str, &str, bool)> = drift[0]
+            .usages
+            .iter()
+            .map(|u| (u.note_id.as_str(), u.gloss.as_str(), u.agrees))
+            .collect();
+        assert_eq!(
+            usages,
+            vec![("note:a", "flowing water", true), ("note:b", "fire", false),]
+        );
+    }
+
+    #[test]
+    fn test_reads_lexicon_csv_with_alternate_headers() {
+        let csv = "Lemma,Meaning,POS,Origin\nvash,water,noun,Proto-Vesh *wa\nkel,stone,,\n";
+        let entries = read_lexicon_csv(csv.as_bytes(), Some("Old Vesh")).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].word, "vash");
+        assert_eq!(entries[0].part_of_speech.as_deref(), Some("noun"));
+        assert_eq!(entries[0].etymology.as_deref(), Some("Proto-Vesh *wa"));
+        assert_eq!(entries[1].part_of_speech, None);
+        assert_eq!(entries[1].language.as_deref(), Some("Old Vesh"));
+
+        assert!(read_lexicon_csv("word,pos\nvash,noun\n".as_bytes(), None).is_err());
+        assert!(read_lexicon_csv("word,gloss\nvash,\n".as_bytes(), None).is_err());
+    }
+}
@@ -0,0 +1,434 @@
+//! Prose mentions: entities named in other entities' text.
+//!
+//! A character profile, a location or event description, or a scene summary
+//! that names another character, location, event, or scene gets a `mentions`
+//! edge to it. Names and aliases are matched as whole words; when the NER
+//! model is loaded, the names it finds are resolved against folded names and
+//! aliases as well ("Osten" finds "Östen"), and count when exactly one entity
+//! matches. The edges are derived data, rebuilt when the text or a name
+//! changes, and reverse queries list them next to explicit references.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
+use crate::services::events::{self, EventBus, EventListener};
+use crate::services::names::same_name;
+use crate::services::NerService;
+use crate::NarraError;
+
+/// Tables whose text is scanned and whose names are looked for.
+pub const MENTION_TABLES: &[&str] = &["character", "location", "event", "scene"];
+
+/// The text field scanned on each table.
+pub fn prose_field(table: &str) -> &'static str {
+    match table {
+        "character" => "profile",
+        "scene" => "summary",
+        _ => "description",
+    }
+}
+
+/// A known world entity and the surface forms that refer to it.
+#[derive(Debug, Clone)]
+pub struct KnownEntity {
+    pub id: String,
+    pub entity_type: String,
+    pub name: String,
+    /// Name plus aliases.
+    pub terms: Vec<String>,
+}
+
+/// One occurrence of a known entity in a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    pub entity_index: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Find whole-word, case-sensitive mentions of known entities.
+///
+/// Longer terms win over shorter overlapping ones ("Old Harbor" over "Harbor").
+/// Returned mentions are sorted by position and never overlap.
+pub fn find_mentions(text: &str, entities: &[KnownEntity]) -> Vec<Mention> {
+    let mut terms: Vec<(&str, usize)> = entities
+        .iter()
+        .enumerate()
+        .flat_map(|(i, e)| e.terms.iter().map(move |t| (t.as_str(), i)))
+        .filter(|(t, _)| !t.trim().is_empty())
+        .collect();
+    terms.sort_by_key(|(t, _)| std::cmp::Reverse(t.len()));
+
+    let mut taken = vec![false; text.len()];
+    let mut mentions = Vec::new();
+
+    for (term, entity_index) in terms {
+        for (start, _) in text.match_indices(term) {
+            let end = start + term.len();
+            if !is_word_boundary(text, start, end) || taken[start..end].iter().any(|t| *t) {
+                continue;
+            }
+            taken[start..end].iter_mut().for_each(|t| *t = true);
+            mentions.push(Mention {
+                entity_index,
+                start,
+                end,
+            });
+        }
+    }
+
+    mentions.sort_by_key(|m| m.start);
+    mentions
+}
+
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+/// An entity named in a text, with the words that named it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MentionHit {
+    pub target: String,
+    pub term: String,
+}
+
+/// Entities named in `text`, once each and never `source` itself.
+///
+/// `ner_spans` are names found by NER; each counts when it folds to a name or
+/// alias of exactly one known entity.
+pub fn detect_mentions(
+    source: &str,
+    text: &str,
+    known: &[KnownEntity],
+    ner_spans: &[String],
+) -> Vec<MentionHit> {
+    let mut hits: Vec<MentionHit> = Vec::new();
+    let mut push = |target: &str, term: &str| {
+        if target != source && !hits.iter().any(|h| h.target == target) {
+            hits.push(MentionHit {
+                target: target.to_string(),
+                term: term.to_string(),
+            });
+        }
+    };
+
+    for m in find_mentions(text, known) {
+        push(&known[m.entity_index].id, &text[m.start..m.end]);
+    }
+    for span in ner_spans {
+        let span = span.trim();
+        let matches: Vec<&KnownEntity> = known
+            .iter()
+            .filter(|e| e.terms.iter().any(|t| same_name(t, span)))
+            .collect();
+        if let [entity] = matches.as_slice() {
+            push(&entity.id, span);
+        }
+    }
+    hits
+}
+
+/// A mentionable entity with its names and text.
+#[derive(Debug, Clone, Deserialize)]
+struct ProseRow {
+    id: RecordId,
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+impl ProseRow {
+    fn known(&self) -> KnownEntity {
+        let mut terms = vec![self.name.clone()];
+        terms.extend(self.aliases.iter().cloned());
+        KnownEntity {
+            id: self.id.to_string(),
+            entity_type: self.id.table().to_string(),
+            name: self.name.clone(),
+            terms,
+        }
+    }
+
+    fn text(&self) -> Option<&str> {
+        self.text.as_deref().filter(|t| !t.trim().is_empty())
+    }
+}
+
+#[derive(Deserialize)]
+struct EdgeRow {
+    id: RecordId,
+    #[serde(rename = "in")]
+    source: RecordId,
+    term: String,
+}
+
+/// Outcome of rebuilding every mention edge.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MentionRebuild {
+    /// Entities with text that was scanned
+    pub scanned: usize,
+    /// Mention edges written
+    pub mentions: usize,
+}
+
+pub struct MentionService {
+    db: Arc<NarraDb>,
+    ner: Arc<dyn NerService + Send + Sync>,
+}
+
+impl MentionService {
+    pub fn new(db: Arc<NarraDb>, ner: Arc<dyn NerService + Send + Sync>) -> Self {
+        Self { db, ner }
+    }
+
+    async fn load(&self) -> Result<Vec<ProseRow>, NarraError> {
+        let mut resp = self
+            .db
+            .query(
+                "SELECT id, name, aliases, \
+                 array::join(array::flatten(object::values(profile ?? {})), '\\n') AS text \
+                 FROM character",
+            )
+            .query("SELECT id, name, description AS text FROM location")
+            .query("SELECT id, title AS name, description AS text FROM event")
+            .query("SELECT id, title AS name, summary AS text FROM scene")
+            .await?;
+        let mut rows = Vec::new();
+        for index in 0..MENTION_TABLES.len() {
+            let table: Vec<ProseRow> = resp.take(index)?;
+            rows.extend(table);
+        }
+        Ok(rows)
+    }
+
+    /// Names NER finds in `text`, or none when the model isn't loaded.
+    async fn ner_spans(&self, text: &str) -> Vec<String> {
+        if !self.ner.is_available() {
+            return Vec::new();
+        }
+        match self.ner.extract_entities(text).await {
+            Ok(output) => output.entities.into_iter().map(|e| e.text).collect(),
+            Err(e) => {
+                tracing::debug!("Skipping NER mentions: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Replace the mention edges going out of `row`.
+    async fn write(&self, row: &ProseRow, hits: &[MentionHit]) -> Result<(), NarraError> {
+        self.db
+            .query("DELETE mentions WHERE in = $source")
+            .bind(("source", row.id.clone()))
+            .await?;
+        for hit in hits {
+            self.relate(&row.id, hit).await?;
+        }
+        Ok(())
+    }
+
+    async fn relate(&self, source: &RecordId, hit: &MentionHit) -> Result<(), NarraError> {
+        self.db
+            .query("RELATE $source->mentions->$target SET field = $field, term = $term")
+            .bind(("source", source.clone()))
+            .bind(("target", parse_record_id(&hit.target)?))
+            .bind(("field", prose_field(source.table())))
+            .bind(("term", hit.term.clone()))
+            .await?;
+        Ok(())
+    }
+
+    /// Re-derive the mentions of one entity after it was created, updated, or
+    /// deleted: the names in its own text, and other texts naming it under
+    /// its current name and aliases.
+    pub async fn sync_entity(&self, entity_id: &str) -> Result<Vec<MentionHit>, NarraError> {
+        let entity = parse_record_id(entity_id)?;
+        let rows = self.load().await?;
+        let Some(row) = rows.iter().find(|r| r.id == entity) else {
+            self.db
+                .query("DELETE mentions WHERE in = $entity OR out = $entity")
+                .bind(("entity", entity))
+                .await?;
+            return Ok(Vec::new());
+        };
+        let known: Vec<KnownEntity> = rows.iter().map(ProseRow::known).collect();
+        let id = row.id.to_string();
+
+        let hits = match row.text() {
+            Some(text) => {
+                let spans = self.ner_spans(text).await;
+                detect_mentions(&id, text, &known, &spans)
+            }
+            None => Vec::new(),
+        };
+        self.write(row, &hits).await?;
+
+        // Incoming: drop mentions by a name the entity no longer has, then
+        // add texts naming it that have no edge yet
+        let terms = row.known().terms;
+        let mut resp = self
+            .db
+            .query("SELECT id, in, term FROM mentions WHERE out = $entity")
+            .bind(("entity", entity.clone()))
+            .await?;
+        let incoming: Vec<EdgeRow> = resp.take(0)?;
+        let mut linked = Vec::new();
+        for edge in incoming {
+            if terms.iter().any(|t| same_name(t, &edge.term)) {
+                linked.push(edge.source);
+            } else {
+                self.db
+                    .query("DELETE $edge")
+                    .bind(("edge", edge.id))
+                    .await?;
+            }
+        }
+        for other in &rows {
+            if other.id == entity || linked.contains(&other.id) {
+                continue;
+            }
+            let Some(text) = other.text() else {
+                continue;
+            };
+            let source = other.id.to_string();
+            if let Some(hit) = detect_mentions(&source, text, &known, &[])
+                .into_iter()
+                .find(|h| h.target == id)
+            {
+                self.relate(&other.id, &hit).await?;
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Rebuild every mention edge from scratch.
+    pub async fn rebuild(&self) -> Result<MentionRebuild, NarraError> {
+        let rows = self.load().await?;
+        let known: Vec<KnownEntity> = rows.iter().map(ProseRow::known).collect();
+        self.db.query("DELETE mentions").await?.check()?;
+
+        let mut outcome = MentionRebuild::default();
+        for row in &rows {
+            let Some(text) = row.text() else {
+                continue;
+            };
+            let spans = self.ner_spans(text).await;
+            let hits = detect_mentions(&row.id.to_string(), text, &known, &spans);
+            for hit in &hits {
+                self.relate(&row.id, hit).await?;
+            }
+            outcome.scanned += 1;
+            outcome.mentions += hits.len();
+        }
+        Ok(outcome)
+    }
+}
+
+/// Keep mention edges current as entities are created, updated, and deleted.
+pub fn sync_on_mutation(
+    bus: &EventBus,
+    db: Arc<NarraDb>,
+    ner: Arc<dyn NerService + Send + Sync>,
+) -> EventListener {
+    let service = Arc::new(MentionService::new(db, ner));
+    EventListener::spawn(bus, move |event| {
+        let service = service.clone();
+        async move {
+            if !matches!(
+                event.event.as_str(),
+                events::ENTITY_CREATED | events::ENTITY_UPDATED | events::ENTITY_DELETED
+            ) {
+                return;
+            }
+            let Some(id) = event.data.get("id").and_then(|v| v.as_str()) else {
+                return;
+            };
+            let table = id.split(':').next().unwrap_or_default();
+            if !MENTION_TABLES.contains(&table) {
+                return;
+            }
+            if let Err(e) = service.sync_entity(id).await {
+                tracing::warn!("Failed to sync mentions of {}: {}", id, e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(id: &str, terms: &[&str]) -> KnownEntity {
+        KnownEntity {
+            id: id.to_string(),
+            entity_type: id.split(':').next().unwrap().to_string(),
+            name: terms[0].to_string(),
+            terms: terms.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_detect_mentions_by_name_alias_and_ner() {
+        let known = vec![
+            entity("character:osten", &["Östen", "the Ferryman"]),
+            entity("character:mira", &["Mira"]),
+            entity("location:harbor", &["Old Harbor"]),
+            entity("location:mill", &["Mill"]),
+            entity("location:mill_road", &["Mill"]),
+        ];
+        let text = "Mira waits at the Old Harbor for the Ferryman. Osten is late; Mira frets.";
+        let spans = vec![
+            "Osten".to_string(),
+            "Mill".to_string(),
+            "Nobody".to_string(),
+        ];
+
+        let hits = detect_mentions("character:mira", text, &known, &spans);
+        let found: Vec<(&str, &str)> = hits
+            .iter()
+            .map(|h| (h.target.as_str(), h.term.as_str()))
+            .collect();
+        // Mira mentioning herself doesn't count; "Mill" is ambiguous
+        assert_eq!(
+            found,
+            vec![
+                ("location:harbor", "Old Harbor"),
+                ("character:osten", "the Ferryman"),
+            ]
+        );
+        assert!(detect_mentions("scene:x", "", &known, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_find_mentions_whole_words_and_aliases() {
+        let entities = vec![
+            entity("character:alice", &["Alice", "Ally"]),
+            entity("location:harbor", &["Old Harbor"]),
+        ];
+        let text = "Alice met Ally at the Old Harbor. Alicent stayed home.";
+        let mentions = find_mentions(text, &entities);
+
+        let found: Vec<&str> = mentions.iter().map(|m| &text[m.start..m.end]).collect();
+        assert_eq!(found, vec!["Alice", "Ally", "Old Harbor"]);
+        assert_eq!(mentions[2].entity_index, 1);
+    }
+
+    #[test]
+    fn test_longer_term_wins_overlap() {
+        let entities = vec![
+            entity("location:harbor", &["Harbor"]),
+            entity("location:old_harbor", &["Old Harbor"]),
+        ];
+        let mentions = find_mentions("the Old Harbor", &entities);
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].entity_index, 1);
+    }
+}
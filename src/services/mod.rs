@@ -1,28 +1,80 @@
+pub mod access;
 pub mod annotation_pipeline;
 pub mod arc;
+pub mod arc_compaction;
+pub mod assets;
+pub mod audit;
+pub mod backup;
+pub mod balance;
+pub mod batch_input;
+pub mod bible;
 pub mod clustering;
 pub mod composite;
+pub mod confusability;
 pub mod progress;
 
 pub mod consistency;
 pub mod context;
+pub mod derived;
+pub mod doctor;
+pub mod draft_progress;
 pub mod emotion;
+pub mod ensemble;
+pub mod event_inference;
+pub mod events;
 pub mod export;
+pub mod fact_contradictions;
+pub mod fact_extraction;
+pub mod family;
+pub mod foreshadowing;
+pub mod fsck;
+pub mod goal_conflicts;
 pub mod graph;
 pub mod graph_analytics;
 pub mod impact;
 pub mod import;
 pub mod influence;
+pub mod ingest;
 pub mod irony;
+pub mod kmeans;
+pub mod knowledge_matrix;
+pub mod lexicon;
+pub mod mentions;
+pub mod names;
 pub mod ner;
+pub mod note_links;
+pub mod ontology;
+pub mod oplog;
 pub mod perception;
+pub mod pressure;
+pub mod reader_knowledge;
+pub mod reciprocity;
+pub mod rename;
+pub mod renumber;
+pub mod reorder;
+pub mod report;
 pub mod role_inference;
+pub mod scenario;
+pub mod scheduler;
 pub mod search;
+pub mod sentiment;
+pub mod setups;
+pub mod spotlight;
+pub mod style;
 pub mod summary;
+pub mod tabular;
+pub mod templates;
 pub mod temporal;
 pub mod tension;
+pub mod terminology;
 pub mod theme;
+pub mod thread_deadlines;
+pub mod token_counter;
+pub mod transmission;
+pub mod trust;
+pub mod vector_index;
 pub mod vector_ops;
+pub mod webhooks;
 
 pub use clustering::{ClusteringResult, ClusteringService, ThemeCluster};
 pub use composite::{
@@ -30,7 +82,7 @@ pub use composite::{
 };
 pub use consistency::{
     generate_suggested_fix, ConsistencyChecker, ConsistencyService, ConsistencySeverity,
-    ValidationResult, Violation,
+    ConsistencyStrictness, ValidationResult, Violation,
 };
 pub use context::{
     CachedContextService, ContextConfig, ContextResponse, ContextService, ScoredEntity,
@@ -38,8 +90,8 @@ pub use context::{
 pub use graph::{GraphOptions, GraphScope, GraphService, MermaidGraphService};
 pub use graph_analytics::{CentralityMetric, CentralityResult, GraphAnalyticsService};
 pub use impact::{
-    AffectedEntity, Decision, DeferredImplication, ImpactAnalysis, ImpactAnalyzer, ImpactService,
-    Severity,
+    final_scenes_referencing, AffectedEntity, Decision, DeferredImplication, ImpactAnalysis,
+    ImpactAnalyzer, ImpactService, LockedScene, Severity,
 };
 pub use influence::{InfluencePath, InfluenceService, InfluenceStep, PropagationResult};
 pub use irony::{IronyReport, IronyService, KnowledgeAsymmetry};
@@ -53,7 +105,10 @@ pub use summary::{
 };
 
 pub use arc::{ArcComparisonResult, ArcHistoryResult, ArcMomentResult, ArcService};
+pub use assets::AssetStore;
+pub use backup::{BackupInfo, BackupService};
 pub use emotion::{EmotionService, LocalEmotionService, NoopEmotionService};
+pub use events::{EventBus, EventListener, EventSink, NarraEvent};
 pub use ner::{LocalNerService, NerService, NoopNerService};
 pub use perception::{
     PerceptionGapResult, PerceptionMatrixResult, PerceptionService, PerceptionShiftResult,
@@ -65,9 +120,13 @@ pub use temporal::{
 };
 pub use tension::{TensionReport, TensionService};
 pub use theme::{LocalThemeService, NoopThemeService, ThemeService, DEFAULT_NARRATIVE_THEMES};
+pub use token_counter::{
+    create_token_counter, HeuristicTokenCounter, HfTokenCounter, TokenCounter,
+};
 pub use vector_ops::{
     ConvergenceResult, GrowthVectorResult, MidpointResult, MisperceptionResult, VectorOpsService,
 };
+pub use webhooks::{Webhook, WebhookConfig, WebhookDispatcher};
 
 pub use annotation_pipeline::{AnnotationPipeline, BatchAnnotationReport, PipelineConfig};
 pub use progress::{noop_progress, NoopProgressReporter, ProgressReporter};
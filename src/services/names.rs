@@ -0,0 +1,548 @@
+//! Character name generation and name similarity.
+//!
+//! Names are generated by an order-2 character Markov model trained on a
+//! built-in cultural corpus plus the world's existing cast, so new names
+//! sound like they belong. Candidates too close to an existing name (by edit
+//! distance, by Soundex code, or by embedding when a model is loaded) are
+//! dropped so readers don't mix characters up.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use rapidfuzz::distance::levenshtein;
+use serde::Serialize;
+
+use crate::db::connection::NarraDb;
+use crate::embedding::EmbeddingService;
+use crate::utils::math::cosine_similarity;
+use crate::NarraError;
+
+/// Built-in training corpora by culture.
+pub const CULTURES: &[(&str, &[&str])] = &[
+    (
+        "nordic",
+        &[
+            "Astrid", "Bjorn", "Dagny", "Einar", "Eirik", "Freydis", "Gunnar", "Halvard", "Hilda",
+            "Ingrid", "Ivar", "Leif", "Magnhild", "Ragnar", "Runa", "Sigrid", "Sigurd", "Solveig",
+            "Steinar", "Svala", "Thora", "Torvald", "Ulf", "Vigdis", "Yngvar",
+        ],
+    ),
+    (
+        "latin",
+        &[
+            "Aurelia", "Cassius", "Claudia", "Decimus", "Drusilla", "Flavia", "Gaius", "Julia",
+            "Livia", "Lucius", "Marcus", "Octavia", "Petronia", "Quintus", "Septimus", "Servius",
+            "Silvia", "Tiberius", "Valeria", "Vibia",
+        ],
+    ),
+    (
+        "slavic",
+        &[
+            "Bogdan",
+            "Borislav",
+            "Dobromir",
+            "Dragana",
+            "Jaroslav",
+            "Ljuba",
+            "Ludmila",
+            "Milena",
+            "Miroslav",
+            "Nadezhda",
+            "Radoslav",
+            "Slavomir",
+            "Snezhana",
+            "Stanislav",
+            "Svetlana",
+            "Vesna",
+            "Vladimir",
+            "Yaroslava",
+            "Zdenka",
+            "Zoran",
+        ],
+    ),
+    (
+        "celtic",
+        &[
+            "Aoife", "Bran", "Brigid", "Caoimhe", "Cathal", "Ciaran", "Deirdre", "Eamon", "Fionn",
+            "Grainne", "Maeve", "Niamh", "Oisin", "Ronan", "Saoirse", "Siobhan", "Tadhg", "Torin",
+        ],
+    ),
+    (
+        "japanese",
+        &[
+            "Akira", "Ayame", "Haruki", "Hikari", "Hiroshi", "Kaede", "Kenji", "Makoto", "Michiko",
+            "Natsumi", "Renjiro", "Sakura", "Satoshi", "Takumi", "Tomoko", "Yukiko", "Yoshiro",
+            "Yuna",
+        ],
+    ),
+    (
+        "greek",
+        &[
+            "Alexios",
+            "Andromeda",
+            "Chrysanthe",
+            "Damaris",
+            "Demetrios",
+            "Eleni",
+            "Evander",
+            "Kallisto",
+            "Leander",
+            "Lysandra",
+            "Nikandros",
+            "Philippa",
+            "Theodora",
+            "Thanos",
+            "Xanthe",
+            "Zenobia",
+        ],
+    ),
+];
+
+/// Names cosine-closer than this (by embedding) count as confusable.
+pub const EMBEDDING_SIMILARITY_LIMIT: f32 = 0.95;
+
+const MIN_LEN: usize = 3;
+const MAX_LEN: usize = 12;
+
+pub fn culture(name: &str) -> Option<&'static [&'static str]> {
+    CULTURES
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(name))
+        .map(|(_, names)| *names)
+}
+
+pub fn culture_names() -> Vec<&'static str> {
+    CULTURES.iter().map(|(c, _)| *c).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Similarity
+// ---------------------------------------------------------------------------
+
+/// Comparison key for a name across scripts and diacritics: transliterated
+/// to ASCII, lowercased, apostrophes dropped, other punctuation treated as
+/// spaces. "Östen" folds like "Osten" and "Москва" like "Moskva".
+pub fn fold_name(name: &str) -> String {
+    let folded: String = deunicode::deunicode(name)
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_ascii_alphanumeric() => Some(c),
+            '\'' | '`' => None,
+            _ => Some(' '),
+        })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether two names are the same once folded (see [`fold_name`]).
+pub fn same_name(a: &str, b: &str) -> bool {
+    let a = fold_name(a);
+    !a.is_empty() && a == fold_name(b)
+}
+
+/// American Soundex code (letter + three digits) of the transliterated name,
+/// or empty for names without letters.
+pub fn soundex(name: &str) -> String {
+    fn code(c: char) -> Option<char> {
+        match c {
+            'b' | 'f' | 'p' | 'v' => Some('1'),
+            'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+            'd' | 't' => Some('3'),
+            'l' => Some('4'),
+            'm' | 'n' => Some('5'),
+            'r' => Some('6'),
+            _ => None,
+        }
+    }
+
+    let letters: Vec<char> = fold_name(name)
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect();
+    let Some(&first) = letters.first() else {
+        return String::new();
+    };
+
+    let mut out = String::from(first.to_ascii_uppercase());
+    let mut previous = code(first);
+    for &c in &letters[1..] {
+        let current = code(c);
+        if current.is_some() && current != previous {
+            out.extend(current);
+            if out.len() == 4 {
+                break;
+            }
+        }
+        // h and w don't separate letters with the same code; vowels do
+        if c != 'h' && c != 'w' {
+            previous = current;
+        }
+    }
+    while out.len() < 4 {
+        out.push('0');
+    }
+    out
+}
+
+/// Normalized edit similarity (0.0-1.0) of the folded names, so case,
+/// diacritics, and script don't count as differences.
+pub fn edit_similarity(a: &str, b: &str) -> f64 {
+    levenshtein::normalized_similarity(fold_name(a).chars(), fold_name(b).chars())
+}
+
+/// How alike two single names look and sound.
+#[derive(Debug, Clone, Serialize)]
+pub struct NameMatch {
+    pub edit_similarity: f64,
+    pub same_soundex: bool,
+}
+
+impl NameMatch {
+    pub fn between(a: &str, b: &str) -> Self {
+        Self {
+            edit_similarity: edit_similarity(a, b),
+            same_soundex: !a.is_empty() && soundex(a) == soundex(b),
+        }
+    }
+
+    pub fn is_confusable(&self, max_edit_similarity: f64) -> bool {
+        self.same_soundex || self.edit_similarity >= max_edit_similarity
+    }
+}
+
+/// The individual words of a full name ("Alice Morgan" -> Alice, Morgan).
+pub fn name_parts(name: &str) -> Vec<&str> {
+    name.split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|p| p.chars().filter(|c| c.is_alphabetic()).count() >= 2)
+        .collect()
+}
+
+/// The existing name part closest to `candidate`, with how close it is.
+pub fn closest<'a>(candidate: &str, existing: &'a [String]) -> Option<(&'a str, NameMatch)> {
+    existing
+        .iter()
+        .flat_map(|name| name_parts(name))
+        .map(|part| (part, NameMatch::between(candidate, part)))
+        .max_by(|a, b| {
+            (a.1.same_soundex, a.1.edit_similarity)
+                .partial_cmp(&(b.1.same_soundex, b.1.edit_similarity))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+}
+
+// ---------------------------------------------------------------------------
+// Generation
+// ---------------------------------------------------------------------------
+
+/// Small deterministic PRNG (SplitMix64); names don't need more.
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n.max(1)
+    }
+}
+
+const START: char = '^';
+const END: char = '$';
+
+/// Order-2 character Markov model over lowercase names.
+pub struct MarkovNames {
+    transitions: HashMap<(char, char), Vec<(char, u32)>>,
+    training: HashSet<String>,
+}
+
+impl MarkovNames {
+    pub fn train<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut counts: HashMap<(char, char), HashMap<char, u32>> = HashMap::new();
+        let mut training = HashSet::new();
+        for name in names {
+            let lower: String = name
+                .chars()
+                .filter(|c| c.is_alphabetic())
+                .flat_map(char::to_lowercase)
+                .collect();
+            if lower.chars().count() < 2 {
+                continue;
+            }
+            let padded: Vec<char> = [START, START]
+                .into_iter()
+                .chain(lower.chars())
+                .chain([END])
+                .collect();
+            for w in padded.windows(3) {
+                *counts
+                    .entry((w[0], w[1]))
+                    .or_default()
+                    .entry(w[2])
+                    .or_default() += 1;
+            }
+            training.insert(lower);
+        }
+
+        let transitions = counts
+            .into_iter()
+            .map(|(state, next)| {
+                let mut next: Vec<(char, u32)> = next.into_iter().collect();
+                // HashMap order is random; sort so a seed always gives the same names
+                next.sort_unstable();
+                (state, next)
+            })
+            .collect();
+        Self {
+            transitions,
+            training,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.training.is_empty()
+    }
+
+    /// One new name, or None when the walk produced something unusable.
+    pub fn sample(&self, rng: &mut SplitMix64) -> Option<String> {
+        let mut state = (START, START);
+        let mut out = String::new();
+        loop {
+            let choices = self.transitions.get(&state)?;
+            let total: u32 = choices.iter().map(|(_, n)| n).sum();
+            let mut pick = rng.below(total as u64) as u32;
+            let next = choices
+                .iter()
+                .find(|(_, n)| {
+                    if pick < *n {
+                        true
+                    } else {
+                        pick -= n;
+                        false
+                    }
+                })
+                .map(|(c, _)| *c)?;
+            if next == END {
+                break;
+            }
+            out.push(next);
+            if out.chars().count() > MAX_LEN {
+                return None;
+            }
+            state = (state.1, next);
+        }
+
+        if out.chars().count() < MIN_LEN || self.training.contains(&out) {
+            return None;
+        }
+        let mut chars = out.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+    }
+}
+
+/// A generated name and the existing name it comes closest to.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedName {
+    pub name: String,
+    pub closest: Option<String>,
+    pub edit_similarity: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NameOptions {
+    pub culture: Option<String>,
+    pub count: usize,
+    /// Reject candidates at or above this edit similarity to a cast name.
+    pub max_similarity: f64,
+    pub seed: u64,
+}
+
+/// Generate up to `options.count` names unlike each other and unlike `existing`.
+pub fn generate(corpus: &[&str], existing: &[String], options: &NameOptions) -> Vec<GeneratedName> {
+    let model = MarkovNames::train(
+        corpus
+            .iter()
+            .copied()
+            .chain(existing.iter().flat_map(|n| name_parts(n))),
+    );
+    if model.is_empty() {
+        return Vec::new();
+    }
+
+    let mut rng = SplitMix64::new(options.seed);
+    let mut out: Vec<GeneratedName> = Vec::new();
+    let mut accepted: Vec<String> = Vec::new();
+    let attempts = options.count.max(1) * 200;
+    for _ in 0..attempts {
+        if out.len() >= options.count {
+            break;
+        }
+        let Some(name) = model.sample(&mut rng) else {
+            continue;
+        };
+        let nearest = closest(&name, existing);
+        if nearest
+            .as_ref()
+            .is_some_and(|(_, m)| m.is_confusable(options.max_similarity))
+        {
+            continue;
+        }
+        // Don't hand back two names that trip each other up either
+        if closest(&name, &accepted).is_some_and(|(_, m)| m.is_confusable(options.max_similarity)) {
+            continue;
+        }
+        accepted.push(name.clone());
+        out.push(GeneratedName {
+            name,
+            edit_similarity: nearest.as_ref().map_or(0.0, |(_, m)| m.edit_similarity),
+            closest: nearest.map(|(part, _)| part.to_string()),
+        });
+    }
+    out
+}
+
+pub struct NameService {
+    db: Arc<NarraDb>,
+    embedding: Arc<dyn EmbeddingService + Send + Sync>,
+}
+
+impl NameService {
+    pub fn new(db: Arc<NarraDb>, embedding: Arc<dyn EmbeddingService + Send + Sync>) -> Self {
+        Self { db, embedding }
+    }
+
+    /// Every character name and alias in the world.
+    pub async fn cast_names(&self) -> Result<Vec<String>, NarraError> {
+        let mut response = self
+            .db
+            .query("SELECT VALUE array::concat([name], aliases ?? []) FROM character")
+            .await?;
+        let names: Vec<Vec<String>> = response.take(0)?;
+        Ok(names.into_iter().flatten().collect())
+    }
+
+    pub async fn generate(&self, options: &NameOptions) -> Result<Vec<GeneratedName>, NarraError> {
+        let existing = self.cast_names().await?;
+        let corpus: &[&str] = match options.culture.as_deref() {
+            Some(c) => culture(c).ok_or_else(|| {
+                NarraError::Validation(format!(
+                    "Unknown culture '{}'. Available: {}",
+                    c,
+                    culture_names().join(", ")
+                ))
+            })?,
+            None => &[],
+        };
+        if corpus.is_empty() && existing.len() < 3 {
+            return Err(NarraError::Validation(format!(
+                "Too few character names to learn from; pick a --culture ({})",
+                culture_names().join(", ")
+            )));
+        }
+
+        // Over-generate so the embedding filter has something to drop
+        let candidates = generate(
+            corpus,
+            &existing,
+            &NameOptions {
+                count: options.count * 2,
+                ..options.clone()
+            },
+        );
+        let mut names = if self.embedding.is_available() && !existing.is_empty() {
+            self.drop_semantic_lookalikes(candidates, &existing).await?
+        } else {
+            candidates
+        };
+        names.truncate(options.count);
+        Ok(names)
+    }
+
+    async fn drop_semantic_lookalikes(
+        &self,
+        candidates: Vec<GeneratedName>,
+        existing: &[String],
+    ) -> Result<Vec<GeneratedName>, NarraError> {
+        let cast = self.embedding.embed_batch(existing).await?;
+        let texts: Vec<String> = candidates.iter().map(|c| c.name.clone()).collect();
+        let generated = self.embedding.embed_batch(&texts).await?;
+        Ok(candidates
+            .into_iter()
+            .zip(generated)
+            .filter(|(_, vector)| {
+                cast.iter()
+                    .all(|c| cosine_similarity(c, vector) < EMBEDDING_SIMILARITY_LIMIT)
+            })
+            .map(|(name, _)| name)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soundex_and_confusability() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert!(NameMatch::between("Marian", "Marion").is_confusable(0.75));
+        assert!(!NameMatch::between("Astrid", "Gunnar").is_confusable(0.75));
+        assert_eq!(
+            name_parts("Anne-Marie O'Neil"),
+            vec!["Anne", "Marie", "O'Neil"]
+        );
+    }
+
+    #[test]
+    fn test_fold_name_across_scripts() {
+        assert_eq!(fold_name("Östen"), "osten");
+        assert_eq!(fold_name("  Anne-Marie  O'Neil "), "anne marie oneil");
+        assert!(same_name("Москва", "Moskva"));
+        assert!(same_name("Ærøskøbing", "AEroskobing"));
+        assert!(!same_name("Östen", "Ostrid"));
+        assert!(!same_name("", ""));
+        assert_eq!(soundex("Östen"), soundex("Osten"));
+        assert_eq!(edit_similarity("Östen", "OSTEN"), 1.0);
+    }
+
+    #[test]
+    fn test_generated_names_avoid_the_cast() {
+        let cast = vec!["Sigrid Halvorsen".to_string(), "Torvald".to_string()];
+        let options = NameOptions {
+            culture: Some("nordic".to_string()),
+            count: 8,
+            max_similarity: 0.7,
+            seed: 42,
+        };
+        let names = generate(culture("nordic").unwrap(), &cast, &options);
+        assert!(!names.is_empty());
+        for generated in &names {
+            for part in ["Sigrid", "Halvorsen", "Torvald"] {
+                assert!(!NameMatch::between(&generated.name, part).is_confusable(0.7));
+            }
+            assert!(culture("nordic")
+                .unwrap()
+                .iter()
+                .all(|n| !n.eq_ignore_ascii_case(&generated.name)));
+        }
+
+        let again = generate(culture("nordic").unwrap(), &cast, &options);
+        let first: Vec<&str> = names.iter().map(|n| n.name.as_str()).collect();
+        let second: Vec<&str> = again.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(first, second, "same seed, same names");
+    }
+}
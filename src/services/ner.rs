@@ -1,7 +1,9 @@
 //! Named Entity Recognition service using BERT-based token classification.
 //!
 //! Extracts person, location, organization, and miscellaneous entities from text.
-//! Uses dslim/bert-base-NER (BIO tagging) with lazy annotation caching.
+//! Uses dslim/bert-base-NER (BIO tagging) with lazy annotation caching. Worlds
+//! whose `language` isn't English use a multilingual BERT NER model instead
+//! (no MISC label).
 
 use std::sync::Arc;
 
@@ -10,14 +12,17 @@ use tracing::{info, warn};
 
 use crate::db::connection::NarraDb;
 use crate::embedding::candle_backend::{download_model, select_device, TokenClassifier};
+use crate::embedding::is_english;
 use crate::models::annotation::{
     get_annotation, upsert_annotation, AnnotationCreate, NerEntity, NerOutput,
 };
 use crate::NarraError;
 
-const NER_MODEL_REPO: &str = "dslim/bert-base-NER";
+pub(crate) const NER_MODEL_REPO: &str = "dslim/bert-base-NER";
 const NER_MODEL_VERSION: &str = "bert-base-ner-v1";
-const NER_MODEL_TYPE: &str = "ner";
+pub(crate) const MULTILINGUAL_NER_MODEL_REPO: &str = "Davlan/bert-base-multilingual-cased-ner-hrl";
+const MULTILINGUAL_NER_MODEL_VERSION: &str = "bert-base-multilingual-ner-hrl-v1";
+pub(crate) const NER_MODEL_TYPE: &str = "ner";
 
 /// Service trait for named entity recognition.
 #[async_trait]
@@ -37,15 +42,31 @@ pub struct LocalNerService {
     classifier: Option<Arc<TokenClassifier>>,
     db: Arc<NarraDb>,
     available: bool,
+    model_version: &'static str,
+}
+
+/// NER model (repo, annotation version) for a manuscript language.
+pub(crate) fn ner_model_for_language(language: Option<&str>) -> (&'static str, &'static str) {
+    if is_english(language) {
+        (NER_MODEL_REPO, NER_MODEL_VERSION)
+    } else {
+        (MULTILINGUAL_NER_MODEL_REPO, MULTILINGUAL_NER_MODEL_VERSION)
+    }
 }
 
 impl LocalNerService {
-    /// Create a new local NER service.
+    /// Create a new local NER service for English text.
     ///
     /// Downloads and loads the BERT NER model eagerly. If model loading fails,
     /// the service will be unavailable but won't error (graceful degradation).
     pub fn new(db: Arc<NarraDb>) -> Self {
-        let files = match download_model(NER_MODEL_REPO, None) {
+        Self::for_language(db, None)
+    }
+
+    /// Create a NER service with the model suited to `language`.
+    pub fn for_language(db: Arc<NarraDb>, language: Option<&str>) -> Self {
+        let (repo, model_version) = ner_model_for_language(language);
+        let files = match download_model(repo, None) {
             Ok(files) => files,
             Err(e) => {
                 warn!(
@@ -56,6 +77,7 @@ impl LocalNerService {
                     classifier: None,
                     db,
                     available: false,
+                    model_version,
                 };
             }
         };
@@ -66,13 +88,14 @@ impl LocalNerService {
             Ok(classifier) => {
                 info!(
                     "NER classifier loaded ({}, {} labels via candle)",
-                    NER_MODEL_REPO,
+                    repo,
                     classifier.num_labels()
                 );
                 Self {
                     classifier: Some(Arc::new(classifier)),
                     db,
                     available: true,
+                    model_version,
                 }
             }
             Err(e) => {
@@ -84,6 +107,7 @@ impl LocalNerService {
                     classifier: None,
                     db,
                     available: false,
+                    model_version,
                 }
             }
         }
@@ -93,9 +117,9 @@ impl LocalNerService {
 #[async_trait]
 impl NerService for LocalNerService {
     async fn get_entities(&self, entity_id: &str, text: &str) -> Result<NerOutput, NarraError> {
-        // Check for fresh cached annotation
+        // Check for fresh cached annotation from the same model
         if let Ok(Some(annotation)) = get_annotation(&self.db, entity_id, NER_MODEL_TYPE).await {
-            if !annotation.stale {
+            if !annotation.stale && annotation.model_version == self.model_version {
                 if let Ok(output) = serde_json::from_value::<NerOutput>(annotation.output) {
                     return Ok(output);
                 }
@@ -115,7 +139,7 @@ impl NerService for LocalNerService {
             AnnotationCreate {
                 entity_id: entity_id.to_string(),
                 model_type: NER_MODEL_TYPE.to_string(),
-                model_version: NER_MODEL_VERSION.to_string(),
+                model_version: self.model_version.to_string(),
                 output: output_json,
             },
         )
@@ -348,6 +372,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ner_model_follows_language() {
+        assert_eq!(ner_model_for_language(None).0, NER_MODEL_REPO);
+        assert_eq!(ner_model_for_language(Some("en-US")).0, NER_MODEL_REPO);
+        assert_eq!(
+            ner_model_for_language(Some("de")).0,
+            MULTILINGUAL_NER_MODEL_REPO
+        );
+    }
+
     #[test]
     fn test_ner_entity_fields() {
         let entity = NerEntity {
@@ -0,0 +1,295 @@
+//! Wiki-style links in notes.
+//!
+//! `[[Entity Name]]` in a note body attaches the note to the character,
+//! location, event, or scene of that name, title, or alias, compared the way
+//! names are folded elsewhere ("[[Osten]]" finds "Östen"). `[[Name|text]]`
+//! links to Name, and `[[character:alice]]` links by ID.
+//!
+//! Link attachments are ordinary `note_attachment` edges carrying the link
+//! text. Syncing a note adds edges for new links and drops edges for links
+//! that left the body or now name someone else. A link that no longer
+//! resolves at all keeps its edge, so renaming an entity doesn't cut the
+//! notes that pointed at it. Manual attachments are never removed.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::models::note::Note;
+use crate::services::names::fold_name;
+use crate::NarraError;
+
+/// Tables a link can point at.
+const LINKABLE_TABLES: &[&str] = &["character", "location", "event", "scene"];
+
+/// Outcome of syncing one note's links.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkSync {
+    /// Entity IDs the note is now linked to
+    pub linked: Vec<String>,
+    /// Link texts that matched nothing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved: Vec<String>,
+    /// Link texts that matched more than one entity
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ambiguous: Vec<String>,
+}
+
+/// Link targets in `body`, in order of first appearance, without duplicates.
+pub fn parse_links(body: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let inner = &after[..end];
+        // A nested "[[" means the first opener was stray
+        if let Some(nested) = inner.rfind("[[") {
+            rest = &after[nested..];
+            continue;
+        }
+        let target = inner.split('|').next().unwrap_or_default().trim();
+        if !target.is_empty() && !links.iter().any(|l| l == target) {
+            links.push(target.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    links
+}
+
+#[derive(Deserialize)]
+struct NamedRow {
+    id: RecordId,
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+/// Folded names and aliases of every linkable entity.
+struct NameIndex {
+    by_name: HashMap<String, Vec<String>>,
+    ids: HashSet<String>,
+}
+
+impl NameIndex {
+    async fn load(db: &NarraDb) -> Result<Self, NarraError> {
+        let mut resp = db
+            .query("SELECT id, name, aliases FROM character")
+            .query("SELECT id, name FROM location")
+            .query("SELECT id, title AS name FROM event")
+            .query("SELECT id, title AS name FROM scene")
+            .await?;
+
+        let mut by_name: HashMap<String, Vec<String>> = HashMap::new();
+        let mut ids = HashSet::new();
+        for index in 0..LINKABLE_TABLES.len() {
+            let rows: Vec<NamedRow> = resp.take(index)?;
+            for row in rows {
+                let id = row.id.to_string();
+                for name in std::iter::once(&row.name).chain(&row.aliases) {
+                    let folded = fold_name(name);
+                    if folded.is_empty() {
+                        continue;
+                    }
+                    let entry = by_name.entry(folded).or_default();
+                    if !entry.contains(&id) {
+                        entry.push(id.clone());
+                    }
+                }
+                ids.insert(id);
+            }
+        }
+        Ok(Self { by_name, ids })
+    }
+
+    /// Entities a link text names: an ID if it is one, else by folded name.
+    fn resolve(&self, link: &str) -> Vec<String> {
+        if self.ids.contains(link) {
+            return vec![link.to_string()];
+        }
+        self.by_name
+            .get(&fold_name(link))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize)]
+struct AttachmentRow {
+    id: RecordId,
+    out: RecordId,
+    #[serde(default)]
+    link: Option<String>,
+    #[serde(default)]
+    manual: Option<bool>,
+}
+
+impl AttachmentRow {
+    /// Created by a link rather than by hand (older edges count as manual).
+    fn link_only(&self) -> bool {
+        self.manual == Some(false)
+    }
+}
+
+async fn sync_with_index(
+    db: &NarraDb,
+    note: &Note,
+    index: &NameIndex,
+) -> Result<LinkSync, NarraError> {
+    let mut sync = LinkSync::default();
+
+    // Link text -> resolution, and target -> the link text that names it
+    let mut resolved: HashMap<String, Vec<String>> = HashMap::new();
+    let mut wanted: BTreeMap<String, String> = BTreeMap::new();
+    for link in parse_links(&note.body) {
+        let targets = index.resolve(&link);
+        match targets.len() {
+            0 => sync.unresolved.push(link.clone()),
+            1 => {
+                wanted.entry(targets[0].clone()).or_insert(link.clone());
+            }
+            _ => sync.ambiguous.push(link.clone()),
+        }
+        resolved.insert(link, targets);
+    }
+
+    let mut resp = db
+        .query("SELECT id, out, link, manual FROM note_attachment WHERE in = $note")
+        .bind(("note", note.id.clone()))
+        .await?;
+    let existing: Vec<AttachmentRow> = resp.take(0)?;
+
+    let mut covered: HashSet<String> = HashSet::new();
+    for edge in &existing {
+        let target = edge.out.to_string();
+        if let Some(text) = wanted.get(&target) {
+            covered.insert(target.clone());
+            if edge.link.as_deref() != Some(text.as_str()) {
+                db.query("UPDATE $edge SET link = $link")
+                    .bind(("edge", edge.id.clone()))
+                    .bind(("link", text.clone()))
+                    .await?;
+            }
+            continue;
+        }
+        let Some(text) = &edge.link else {
+            continue;
+        };
+        // Still in the body and now matching nothing: the target was
+        // renamed, so keep the edge
+        let keep = resolved.get(text).is_some_and(|t| t.is_empty());
+        if keep {
+            covered.insert(target);
+        } else if edge.link_only() {
+            db.query("DELETE $edge")
+                .bind(("edge", edge.id.clone()))
+                .await?;
+        } else {
+            db.query("UPDATE $edge SET link = NONE")
+                .bind(("edge", edge.id.clone()))
+                .await?;
+        }
+    }
+
+    for (target, text) in &wanted {
+        if !covered.contains(target) {
+            let out = crate::db::query::parse_record_id(target)?;
+            db.query("RELATE $note->note_attachment->$out SET link = $link, manual = false")
+                .bind(("note", note.id.clone()))
+                .bind(("out", out))
+                .bind(("link", text.clone()))
+                .await?;
+        }
+    }
+
+    covered.extend(wanted.into_keys());
+    sync.linked = covered.into_iter().collect();
+    sync.linked.sort();
+    Ok(sync)
+}
+
+/// Whether an update to a linkable entity can change what links resolve to.
+pub fn renames(fields: &serde_json::Value) -> bool {
+    ["name", "title", "aliases"]
+        .iter()
+        .any(|key| fields.get(key).is_some())
+}
+
+/// Resolve the links in a note's body and update its link attachments.
+pub async fn sync_note_links(db: &NarraDb, note_id: &str) -> Result<LinkSync, NarraError> {
+    let note = crate::models::note::get_note(db, note_id)
+        .await?
+        .ok_or_else(|| NarraError::NotFound {
+            entity_type: "note".to_string(),
+            id: note_id.to_string(),
+        })?;
+    let index = NameIndex::load(db).await?;
+    sync_with_index(db, &note, &index).await
+}
+
+/// Re-resolve links in every note that has any, e.g. after an entity was
+/// renamed. Returns how many notes were synced.
+pub async fn resync_all_note_links(db: &NarraDb) -> Result<usize, NarraError> {
+    let mut resp = db
+        .query("SELECT * FROM note WHERE string::contains(body, '[[')")
+        .await?;
+    let notes: Vec<Note> = resp.take(0)?;
+    if notes.is_empty() {
+        return Ok(0);
+    }
+    let index = NameIndex::load(db).await?;
+    for note in &notes {
+        sync_with_index(db, note, &index).await?;
+    }
+    Ok(notes.len())
+}
+
+/// Notes that link to an entity with `[[...]]`, with the link text used.
+pub async fn backlinks(db: &NarraDb, entity_id: &str) -> Result<Vec<Backlink>, NarraError> {
+    let entity = crate::db::query::parse_record_id(entity_id)?;
+    let mut resp = db
+        .query(
+            "SELECT in.* AS note, link FROM note_attachment \
+             WHERE out = $entity AND link IS NOT NONE",
+        )
+        .bind(("entity", entity))
+        .await?;
+    let mut rows: Vec<Backlink> = resp.take(0)?;
+    rows.sort_by(|a, b| a.note.title.cmp(&b.note.title));
+    Ok(rows)
+}
+
+/// A note linking to an entity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backlink {
+    pub note: Note,
+    pub link: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_links() {
+        assert_eq!(
+            parse_links("Ask [[Alice]] about [[The Siege|the siege]], then [[alice]] again."),
+            vec!["Alice", "The Siege", "alice"]
+        );
+        assert_eq!(
+            parse_links("[[character:bob]] and [[ Carol ]]"),
+            vec!["character:bob", "Carol"]
+        );
+    }
+
+    #[test]
+    fn test_parse_links_ignores_broken_syntax() {
+        assert!(parse_links("no links [here] or [[]] or [[ | x]]").is_empty());
+        assert_eq!(parse_links("[[stray [[Dora]]"), vec!["Dora"]);
+        assert!(parse_links("[[unterminated").is_empty());
+    }
+}
@@ -0,0 +1,646 @@
+//! Relationship type ontology.
+//!
+//! `rel_type` is free text, so one world drifts into "friend", "friends" and
+//! "buddy" for the same thing. The ontology names canonical types, their
+//! synonyms, polarity and reciprocal. Creating a relationship maps synonyms
+//! to their canonical type and suggests near matches for unknown types (or
+//! rejects them when `[relationships] strict = true`); `narra relationship
+//! normalize` rewrites edges created before the ontology existed.
+//!
+//! User types come from `[relationships.types.<name>]` in the config and
+//! take precedence over the built-ins.
+//!
+//! Each type also has a baseline perception: text for how a character who
+//! holds the type towards another sees them. `--with-perceptions` on
+//! relationship creation uses it to seed both characters' perceptions, the
+//! reverse one from the reciprocal role, so perception analytics have
+//! something to work with in a freshly built world.
+
+use std::collections::BTreeMap;
+
+use rapidfuzz::distance::levenshtein;
+use serde::{Deserialize, Serialize};
+
+use crate::db::connection::NarraDb;
+use crate::models::perception::{create_perception, get_perception};
+use crate::models::{Perception, PerceptionCreate};
+use crate::services::reciprocity::reciprocal;
+use crate::NarraError;
+
+/// Whether a relationship type is friendly, hostile, or neither.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Polarity {
+    Positive,
+    Negative,
+    #[default]
+    Neutral,
+}
+
+impl std::fmt::Display for Polarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Polarity::Positive => "positive",
+            Polarity::Negative => "negative",
+            Polarity::Neutral => "neutral",
+        })
+    }
+}
+
+/// A user-defined relationship type, as written in the config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RelationshipTypeDef {
+    /// Other spellings that mean this type
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub synonyms: Vec<String>,
+    pub polarity: Option<Polarity>,
+    /// Type implied on the reverse edge ("mentor" -> "student")
+    pub reciprocal: Option<String>,
+    /// Baseline perception text; `{observer}`, `{target}` and `{role}` are
+    /// filled in
+    pub perception: Option<String>,
+}
+
+/// A canonical relationship type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipType {
+    pub name: String,
+    pub synonyms: Vec<String>,
+    pub polarity: Polarity,
+    pub reciprocal: Option<String>,
+    /// Baseline perception template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perception: Option<String>,
+    /// Defined in the config rather than built in
+    pub user_defined: bool,
+}
+
+/// Built-in types: (name, polarity, reciprocal, synonyms).
+const BUILTIN_TYPES: &[(&str, Polarity, Option<&str>, &[&str])] = &[
+    (
+        "family",
+        Polarity::Neutral,
+        None,
+        &["kin", "kinship", "relative", "relatives"],
+    ),
+    (
+        "romantic",
+        Polarity::Positive,
+        None,
+        &["romance", "love", "lover", "lovers"],
+    ),
+    (
+        "friend",
+        Polarity::Positive,
+        Some("friend"),
+        &["friends", "friendship", "buddy", "buddies", "pal"],
+    ),
+    (
+        "ally",
+        Polarity::Positive,
+        Some("ally"),
+        &["allies", "alliance"],
+    ),
+    (
+        "enemy",
+        Polarity::Negative,
+        Some("enemy"),
+        &["enemies", "foe", "foes", "nemesis"],
+    ),
+    (
+        "rival",
+        Polarity::Negative,
+        Some("rival"),
+        &["rivals", "rivalry"],
+    ),
+    (
+        "mentor",
+        Polarity::Positive,
+        Some("student"),
+        &["mentorship", "teacher"],
+    ),
+    (
+        "student",
+        Polarity::Positive,
+        Some("mentor"),
+        &["apprentice", "pupil", "protege"],
+    ),
+    (
+        "professional",
+        Polarity::Neutral,
+        None,
+        &["colleague", "colleagues", "coworker", "business"],
+    ),
+    (
+        "social",
+        Polarity::Neutral,
+        None,
+        &["acquaintance", "acquaintances"],
+    ),
+    (
+        "antagonistic",
+        Polarity::Negative,
+        None,
+        &["hostile", "antagonist"],
+    ),
+    ("custom", Polarity::Neutral, None, &[]),
+];
+
+/// Baseline perceptions of built-in types, from the point of view of the
+/// character holding the type towards the other.
+const BUILTIN_PERCEPTIONS: &[(&str, &str)] = &[
+    (
+        "family",
+        "{observer} sees {target} as family; {observer} is their {role}.",
+    ),
+    (
+        "romantic",
+        "{observer} is drawn to {target} and thinks of them as a partner.",
+    ),
+    (
+        "friend",
+        "{observer} counts {target} as a friend and trusts them.",
+    ),
+    (
+        "ally",
+        "{observer} sees {target} as an ally with shared aims.",
+    ),
+    (
+        "enemy",
+        "{observer} regards {target} as an enemy and a threat.",
+    ),
+    ("rival", "{observer} sees {target} as a rival to outdo."),
+    (
+        "mentor",
+        "{observer} sees {target} as a student in their care.",
+    ),
+    ("student", "{observer} looks up to {target} as a mentor."),
+    ("professional", "{observer} knows {target} through work."),
+    (
+        "social",
+        "{observer} knows {target} socially, without being close.",
+    ),
+    ("antagonistic", "{observer} is hostile towards {target}."),
+];
+
+/// Baseline perception for types without one.
+const FALLBACK_PERCEPTION: &str = "{observer} is {target}'s {role}.";
+
+/// Suggestions closer than this are offered for unknown types.
+const SUGGESTION_SIMILARITY: f64 = 0.6;
+
+/// How a `rel_type` resolved against the ontology.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeResolution {
+    /// Already a canonical type (possibly in another case)
+    Canonical(String),
+    /// A synonym of the canonical type
+    Synonym(String),
+    /// Not in the ontology; near matches, best first
+    Unknown(Vec<String>),
+}
+
+/// The type to store for a new relationship, and a note for the author.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckedType {
+    pub rel_type: String,
+    pub note: Option<String>,
+}
+
+/// Canonical relationship types and their synonyms.
+#[derive(Debug, Clone)]
+pub struct RelationshipOntology {
+    types: Vec<RelationshipType>,
+    strict: bool,
+}
+
+impl Default for RelationshipOntology {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+fn fold(name: &str) -> String {
+    name.trim().to_lowercase().replace(['_', ' '], "-")
+}
+
+impl RelationshipOntology {
+    /// The built-in types only, accepting unknown types.
+    pub fn builtin() -> Self {
+        let types = BUILTIN_TYPES
+            .iter()
+            .map(|(name, polarity, reciprocal, synonyms)| RelationshipType {
+                name: name.to_string(),
+                synonyms: synonyms.iter().map(|s| s.to_string()).collect(),
+                polarity: *polarity,
+                reciprocal: reciprocal.map(str::to_string),
+                perception: BUILTIN_PERCEPTIONS
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, text)| text.to_string()),
+                user_defined: false,
+            })
+            .collect();
+        Self {
+            types,
+            strict: false,
+        }
+    }
+
+    /// Built-ins overlaid with user types. A user type replaces a built-in
+    /// of the same name, and its synonyms are taken from any built-in.
+    pub fn new(
+        user_types: &BTreeMap<String, RelationshipTypeDef>,
+        strict: bool,
+    ) -> Result<Self, NarraError> {
+        let mut seen: BTreeMap<String, String> = BTreeMap::new();
+        let mut types = Vec::new();
+        for (name, def) in user_types {
+            let name = fold(name);
+            if name.is_empty() {
+                return Err(NarraError::Validation(
+                    "relationships.types: type names cannot be empty".to_string(),
+                ));
+            }
+            let synonyms: Vec<String> = def.synonyms.iter().map(|s| fold(s)).collect();
+            for spelling in std::iter::once(&name).chain(&synonyms) {
+                if let Some(other) = seen.insert(spelling.clone(), name.clone()) {
+                    return Err(NarraError::Validation(format!(
+                        "relationships.types: '{}' is claimed by both '{}' and '{}'",
+                        spelling, other, name
+                    )));
+                }
+            }
+            types.push(RelationshipType {
+                name,
+                synonyms,
+                polarity: def.polarity.unwrap_or_default(),
+                reciprocal: def.reciprocal.as_deref().map(fold),
+                perception: def.perception.clone(),
+                user_defined: true,
+            });
+        }
+
+        for mut builtin in Self::builtin().types {
+            if seen.contains_key(&builtin.name) {
+                continue;
+            }
+            builtin.synonyms.retain(|s| !seen.contains_key(s));
+            types.push(builtin);
+        }
+        Ok(Self { types, strict })
+    }
+
+    pub fn types(&self) -> &[RelationshipType] {
+        &self.types
+    }
+
+    /// Whether unknown types are rejected.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// The canonical type a name or synonym belongs to.
+    pub fn get(&self, rel_type: &str) -> Option<&RelationshipType> {
+        let folded = fold(rel_type);
+        self.types
+            .iter()
+            .find(|t| t.name == folded)
+            .or_else(|| self.types.iter().find(|t| t.synonyms.contains(&folded)))
+    }
+
+    pub fn resolve(&self, rel_type: &str) -> TypeResolution {
+        match self.get(rel_type) {
+            Some(t) if t.name == fold(rel_type) => TypeResolution::Canonical(t.name.clone()),
+            Some(t) => TypeResolution::Synonym(t.name.clone()),
+            None => TypeResolution::Unknown(self.suggest(rel_type)),
+        }
+    }
+
+    /// Canonical types whose name or a synonym is spelled like `rel_type`.
+    pub fn suggest(&self, rel_type: &str) -> Vec<String> {
+        let folded = fold(rel_type);
+        let mut scored: Vec<(f64, &str)> = self
+            .types
+            .iter()
+            .filter_map(|t| {
+                std::iter::once(&t.name)
+                    .chain(&t.synonyms)
+                    .map(|s| levenshtein::normalized_similarity(folded.chars(), s.chars()))
+                    .max_by(f64::total_cmp)
+                    .filter(|s| *s >= SUGGESTION_SIMILARITY)
+                    .map(|s| (s, t.name.as_str()))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored
+            .into_iter()
+            .take(3)
+            .map(|(_, n)| n.to_string())
+            .collect()
+    }
+
+    /// The type to store for a new relationship: synonyms become their
+    /// canonical type; unknown types pass with a suggestion, or fail when strict.
+    pub fn check(&self, rel_type: &str) -> Result<CheckedType, NarraError> {
+        match self.resolve(rel_type) {
+            TypeResolution::Canonical(name) => Ok(CheckedType {
+                rel_type: name,
+                note: None,
+            }),
+            TypeResolution::Synonym(name) => Ok(CheckedType {
+                note: Some(format!("Stored '{}' as '{}'", rel_type, name)),
+                rel_type: name,
+            }),
+            TypeResolution::Unknown(suggestions) => {
+                let hint = match suggestions.as_slice() {
+                    [] => String::new(),
+                    s => format!(" Did you mean {}?", s.join(", ")),
+                };
+                if self.strict {
+                    return Err(NarraError::Validation(format!(
+                        "Unknown relationship type '{}'.{} Add it under [relationships.types] in narra.toml to allow it",
+                        rel_type, hint
+                    )));
+                }
+                Ok(CheckedType {
+                    rel_type: rel_type.to_string(),
+                    note: Some(format!(
+                        "'{}' is not a known relationship type.{}",
+                        rel_type, hint
+                    )),
+                })
+            }
+        }
+    }
+
+    /// Baseline perception of `target` by `observer`, who holds `role`
+    /// (a subtype or type) towards them within a `rel_type` relationship.
+    pub fn baseline_perception(
+        &self,
+        rel_type: &str,
+        role: &str,
+        observer: &str,
+        target: &str,
+    ) -> String {
+        let template = self
+            .get(role)
+            .or_else(|| self.get(rel_type))
+            .and_then(|t| t.perception.as_deref())
+            .unwrap_or(FALLBACK_PERCEPTION);
+        template
+            .replace("{observer}", observer)
+            .replace("{target}", target)
+            .replace("{role}", role)
+    }
+
+    /// Baseline perceptions for a new `rel_type` relationship from `from` to
+    /// `to`, as (from's view of to, to's view of from). The reverse view uses
+    /// the reciprocal role, or the same role when there is none. Tension
+    /// follows the type's polarity.
+    pub fn baseline_perception_pair(
+        &self,
+        rel_type: &str,
+        subtype: Option<&str>,
+        from_name: &str,
+        to_name: &str,
+    ) -> (PerceptionCreate, PerceptionCreate) {
+        let role = subtype.unwrap_or(rel_type);
+        let reverse_role = reciprocal(role)
+            .map(str::to_string)
+            .or_else(|| self.get(role).and_then(|t| t.reciprocal.clone()))
+            .unwrap_or_else(|| role.to_string());
+        let tension = match self.get(rel_type).map(|t| t.polarity) {
+            Some(Polarity::Positive) => 1,
+            Some(Polarity::Negative) => 6,
+            _ => 3,
+        };
+        let view = |role: &str, observer: &str, target: &str| PerceptionCreate {
+            rel_types: vec![rel_type.to_string()],
+            subtype: subtype.map(|_| role.to_string()),
+            feelings: None,
+            perception: Some(self.baseline_perception(rel_type, role, observer, target)),
+            tension_level: Some(tension),
+            history_notes: Some(format!("Baseline from the {} relationship", rel_type)),
+        };
+        (
+            view(role, from_name, to_name),
+            view(&reverse_role, to_name, from_name),
+        )
+    }
+}
+
+/// Perceptions seeded for a new relationship.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselinePerceptions {
+    pub created: Vec<Perception>,
+    /// Directions left alone because a perception already existed
+    pub existing: usize,
+}
+
+/// Create baseline perceptions in both directions for a new `rel_type`
+/// relationship between two characters (keys without the `character:`
+/// prefix). Existing perceptions are never overwritten.
+pub async fn create_baseline_perceptions(
+    db: &NarraDb,
+    ontology: &RelationshipOntology,
+    from_key: &str,
+    to_key: &str,
+    rel_type: &str,
+    subtype: Option<&str>,
+) -> Result<BaselinePerceptions, NarraError> {
+    #[derive(Deserialize)]
+    struct Named {
+        name: String,
+    }
+
+    let mut names = Vec::new();
+    for key in [from_key, to_key] {
+        let mut result = db
+            .query("SELECT name FROM $id")
+            .bind(("id", surrealdb::RecordId::from(("character", key))))
+            .await?;
+        let named: Option<Named> = result.take(0)?;
+        names.push(named.map_or_else(|| key.to_string(), |n| n.name));
+    }
+
+    let (forward, reverse) =
+        ontology.baseline_perception_pair(rel_type, subtype, &names[0], &names[1]);
+    let mut seeded = BaselinePerceptions {
+        created: Vec::new(),
+        existing: 0,
+    };
+    for (observer, target, data) in [(from_key, to_key, forward), (to_key, from_key, reverse)] {
+        if get_perception(db, observer, target).await?.is_some() {
+            seeded.existing += 1;
+            continue;
+        }
+        seeded
+            .created
+            .push(create_perception(db, observer, target, data).await?);
+    }
+    Ok(seeded)
+}
+
+/// Edges whose type is rewritten to its canonical form.
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeRewrite {
+    pub from: String,
+    pub to: String,
+    pub count: usize,
+}
+
+/// Edges with a type the ontology doesn't know.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnknownType {
+    pub rel_type: String,
+    pub count: usize,
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NormalizeReport {
+    pub rewrites: Vec<TypeRewrite>,
+    pub unknown: Vec<UnknownType>,
+}
+
+#[derive(Deserialize)]
+struct TypeCount {
+    rel_type: String,
+    count: usize,
+}
+
+/// Rewrite existing relationships to canonical types. Unknown types are
+/// reported, not changed.
+pub async fn normalize_relationship_types(
+    db: &NarraDb,
+    ontology: &RelationshipOntology,
+    dry_run: bool,
+) -> Result<NormalizeReport, NarraError> {
+    let mut result = db
+        .query("SELECT rel_type, count() AS count FROM relates_to GROUP BY rel_type")
+        .await?;
+    let counts: Vec<TypeCount> = result.take(0)?;
+
+    let mut report = NormalizeReport::default();
+    for TypeCount { rel_type, count } in counts {
+        match ontology.resolve(&rel_type) {
+            TypeResolution::Canonical(name) | TypeResolution::Synonym(name) => {
+                if name == rel_type {
+                    continue;
+                }
+                if !dry_run {
+                    db.query("UPDATE relates_to SET rel_type = $to WHERE rel_type = $from")
+                        .bind(("to", name.clone()))
+                        .bind(("from", rel_type.clone()))
+                        .await?
+                        .check()?;
+                }
+                report.rewrites.push(TypeRewrite {
+                    from: rel_type,
+                    to: name,
+                    count,
+                });
+            }
+            TypeResolution::Unknown(suggestions) => report.unknown.push(UnknownType {
+                rel_type,
+                count,
+                suggestions,
+            }),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::reciprocity::reciprocal;
+
+    #[test]
+    fn test_synonyms_resolve_to_canonical() {
+        let ontology = RelationshipOntology::builtin();
+        assert_eq!(
+            ontology.resolve("Friends"),
+            TypeResolution::Synonym("friend".into())
+        );
+        assert_eq!(
+            ontology.resolve("buddy"),
+            TypeResolution::Synonym("friend".into())
+        );
+        assert_eq!(
+            ontology.resolve("ALLY"),
+            TypeResolution::Canonical("ally".into())
+        );
+        assert_eq!(ontology.check("rivalry").unwrap().rel_type, "rival");
+        match ontology.resolve("freind") {
+            TypeResolution::Unknown(suggestions) => assert_eq!(suggestions[0], "friend"),
+            other => panic!("expected unknown, got {:?}", other),
+        }
+        assert!(ontology.check("freind").unwrap().note.is_some());
+
+        // Built-in reciprocals agree with the reciprocity checks
+        for t in ontology.types() {
+            if let Some(reverse) = &t.reciprocal {
+                assert_eq!(reciprocal(&t.name), Some(reverse.as_str()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_baseline_perception_pair_uses_reciprocal_role() {
+        let ontology = RelationshipOntology::builtin();
+        let (forward, reverse) =
+            ontology.baseline_perception_pair("mentor", None, "Obi-Wan", "Luke");
+        assert_eq!(
+            forward.perception.as_deref(),
+            Some("Obi-Wan sees Luke as a student in their care.")
+        );
+        assert_eq!(
+            reverse.perception.as_deref(),
+            Some("Luke looks up to Obi-Wan as a mentor.")
+        );
+        assert_eq!(reverse.rel_types, vec!["mentor".to_string()]);
+        assert_eq!(forward.tension_level, Some(1));
+
+        let (forward, reverse) =
+            ontology.baseline_perception_pair("family", Some("parent"), "Ned", "Arya");
+        assert_eq!(
+            forward.perception.as_deref(),
+            Some("Ned sees Arya as family; Ned is their parent.")
+        );
+        assert_eq!(reverse.subtype.as_deref(), Some("child"));
+
+        let (forward, _) = ontology.baseline_perception_pair("enemy", None, "A", "B");
+        assert_eq!(forward.tension_level, Some(6));
+    }
+
+    #[test]
+    fn test_user_types_override_builtins() {
+        let types: BTreeMap<String, RelationshipTypeDef> = toml::from_str(
+            r#"
+            [liege]
+            synonyms = ["lord", "buddy"]
+            polarity = "positive"
+            reciprocal = "vassal"
+            perception = "{observer} owes {target} protection as their {role}."
+            "#,
+        )
+        .unwrap();
+        let ontology = RelationshipOntology::new(&types, true).unwrap();
+        assert_eq!(ontology.check("lord").unwrap().rel_type, "liege");
+        assert_eq!(ontology.check("buddy").unwrap().rel_type, "liege");
+        assert_eq!(ontology.check("friends").unwrap().rel_type, "friend");
+        assert!(ontology.check("sworn-brother").is_err());
+
+        assert_eq!(
+            ontology.baseline_perception("liege", "liege", "Ned", "Jon"),
+            "Ned owes Jon protection as their liege."
+        );
+
+        let clash: BTreeMap<String, RelationshipTypeDef> =
+            toml::from_str("[liege]\nsynonyms = [\"lord\"]\n[lord]\n").unwrap();
+        assert!(RelationshipOntology::new(&clash, false).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+//! Operation log for offline co-authoring (experimental).
+//!
+//! Every entity event is appended to the `oplog` table with its author and
+//! the entity's state after the change. Two writers working on separate
+//! copies of a world exchange logs as JSON lines; importing replays the other
+//! side's operations with last-writer-wins per entity, so whichever change
+//! was made last survives. Relationship edges and nested fields are not
+//! reconciled.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::services::events::{EventBus, EventListener, ENTITY_DELETED};
+use crate::NarraError;
+
+/// Tables whose `table:key` strings are turned back into record links on import.
+const LINK_TABLES: &[&str] = &[
+    "character",
+    "location",
+    "event",
+    "scene",
+    "knowledge",
+    "note",
+    "universe_fact",
+    "thread",
+    "phase",
+    "term",
+];
+
+/// One logged mutation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpRecord {
+    pub op_id: String,
+    pub author: Option<String>,
+    pub event: String,
+    pub entity_id: String,
+    pub entity_type: String,
+    pub name: Option<String>,
+    /// Entity fields after the change; none for deletions
+    pub state: Option<serde_json::Value>,
+    /// RFC 3339
+    pub timestamp: String,
+}
+
+impl OpRecord {
+    fn time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+}
+
+/// What an import does with incoming operations.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergePlan {
+    /// Latest incoming op per entity that beats the local state
+    pub apply: Vec<OpRecord>,
+    /// New ops to append to the local log (includes `apply`)
+    pub record: Vec<OpRecord>,
+    /// Already present locally
+    pub duplicates: usize,
+    /// Older than a local change to the same entity
+    pub superseded: Vec<OpRecord>,
+}
+
+/// Last-writer-wins merge. `latest` holds the newest local op time per entity.
+pub fn plan_merge(
+    mut incoming: Vec<OpRecord>,
+    known: &HashSet<String>,
+    latest: &HashMap<String, DateTime<Utc>>,
+) -> MergePlan {
+    incoming.sort_by_key(|op| op.time());
+    let mut plan = MergePlan::default();
+    let mut winners: HashMap<String, OpRecord> = HashMap::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for op in incoming {
+        if known.contains(&op.op_id) || !seen.insert(op.op_id.clone()) {
+            plan.duplicates += 1;
+            continue;
+        }
+        let newer = match (op.time(), latest.get(&op.entity_id)) {
+            (Some(t), Some(local)) => t > *local,
+            (None, Some(_)) => false,
+            (_, None) => true,
+        };
+        if newer {
+            winners.insert(op.entity_id.clone(), op.clone());
+        } else {
+            plan.superseded.push(op.clone());
+        }
+        plan.record.push(op);
+    }
+
+    plan.apply = winners.into_values().collect();
+    plan.apply.sort_by_key(|op| op.time());
+    plan
+}
+
+/// Fields holding `table:key` strings (or arrays of them) and RFC 3339 strings.
+fn typed_fields(state: &serde_json::Map<String, serde_json::Value>) -> (Vec<String>, Vec<String>) {
+    let is_link = |v: &serde_json::Value| {
+        v.as_str()
+            .and_then(|s| s.split_once(':'))
+            .is_some_and(|(table, key)| LINK_TABLES.contains(&table) && !key.is_empty())
+    };
+    let mut links = Vec::new();
+    let mut dates = Vec::new();
+    for (field, value) in state {
+        match value {
+            serde_json::Value::String(s) if DateTime::parse_from_rfc3339(s).is_ok() => {
+                dates.push(field.clone())
+            }
+            v if is_link(v) => links.push(field.clone()),
+            serde_json::Value::Array(items) if !items.is_empty() && items.iter().all(is_link) => {
+                links.push(field.clone())
+            }
+            _ => {}
+        }
+    }
+    (links, dates)
+}
+
+/// Outcome of an import.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportSummary {
+    pub applied: usize,
+    pub recorded: usize,
+    pub duplicates: usize,
+    pub superseded: Vec<OpRecord>,
+    pub dry_run: bool,
+}
+
+/// Append an oplog row for every entity event on the bus.
+pub fn record_on_mutation(bus: &EventBus, db: Arc<NarraDb>) -> EventListener {
+    EventListener::spawn(bus, move |event| {
+        let db = db.clone();
+        async move {
+            if !event.event.starts_with("entity.") {
+                return;
+            }
+            let service = OplogService::new(db);
+            if let Err(e) = service.append(&event.event, &event.data).await {
+                tracing::warn!("Failed to record oplog entry: {}", e);
+            }
+        }
+    })
+}
+
+pub struct OplogService {
+    db: Arc<NarraDb>,
+}
+
+impl OplogService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Log an entity event, snapshotting the entity unless it was deleted.
+    pub async fn append(&self, event: &str, data: &serde_json::Value) -> Result<(), NarraError> {
+        let field = |key: &str| data.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        let Some((table, key)) = field("id").and_then(|id| {
+            id.split_once(':')
+                .map(|(t, k)| (t.to_string(), k.to_string()))
+        }) else {
+            return Ok(());
+        };
+
+        self.db
+            .query(
+                "CREATE oplog SET op_id = rand::uuid(), author = $author, event = $event, \
+                 entity_id = <string> $entity, entity_type = $entity_type, name = $name, \
+                 state = IF $deleted THEN NONE ELSE (SELECT * OMIT id, embedding FROM ONLY $entity) END, \
+                 timestamp = time::now()",
+            )
+            .bind(("author", field("author")))
+            .bind(("event", event.to_string()))
+            .bind(("entity", RecordId::from((table.as_str(), key.as_str()))))
+            .bind(("entity_type", field("entity_type").unwrap_or(table)))
+            .bind(("name", field("name")))
+            .bind(("deleted", event == ENTITY_DELETED))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    /// Logged operations, oldest first, optionally only those after `since`.
+    pub async fn export(&self, since: Option<DateTime<Utc>>) -> Result<Vec<OpRecord>, NarraError> {
+        let mut response = self
+            .db
+            .query(
+                "SELECT op_id, author, event, entity_id, entity_type, name, state, timestamp \
+                 FROM oplog WHERE $since = NONE OR timestamp > <datetime> $since \
+                 ORDER BY timestamp ASC",
+            )
+            .bind(("since", since.map(|t| t.to_rfc3339())))
+            .await?;
+        let rows: surrealdb::Value = response.take(0)?;
+        serde_json::from_value(rows.into_inner().into_json())
+            .map_err(|e| NarraError::Database(format!("Malformed oplog row: {}", e)))
+    }
+
+    /// Merge another writer's operations into this world.
+    pub async fn import(
+        &self,
+        incoming: Vec<OpRecord>,
+        dry_run: bool,
+    ) -> Result<ImportSummary, NarraError> {
+        #[derive(Deserialize)]
+        struct Latest {
+            entity_id: String,
+            timestamp: String,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT VALUE op_id FROM oplog; \
+                 SELECT entity_id, <string> math::max(timestamp) AS timestamp \
+                 FROM oplog GROUP BY entity_id",
+            )
+            .await?;
+        let known: Vec<String> = response.take(0)?;
+        let latest: Vec<Latest> = response.take(1)?;
+        let known: HashSet<String> = known.into_iter().collect();
+        let latest: HashMap<String, DateTime<Utc>> = latest
+            .into_iter()
+            .filter_map(|l| {
+                DateTime::parse_from_rfc3339(&l.timestamp)
+                    .ok()
+                    .map(|t| (l.entity_id, t.with_timezone(&Utc)))
+            })
+            .collect();
+
+        let plan = plan_merge(incoming, &known, &latest);
+        if !dry_run {
+            for op in &plan.apply {
+                self.apply(op).await?;
+            }
+            for op in &plan.record {
+                self.db
+                    .query(
+                        "CREATE oplog SET op_id = $op.op_id, author = $op.author, \
+                         event = $op.event, entity_id = $op.entity_id, \
+                         entity_type = $op.entity_type, name = $op.name, state = $op.state, \
+                         timestamp = <datetime> $op.timestamp, imported = true",
+                    )
+                    .bind(("op", op.clone()))
+                    .await?
+                    .check()?;
+            }
+        }
+
+        Ok(ImportSummary {
+            applied: plan.apply.len(),
+            recorded: plan.record.len(),
+            duplicates: plan.duplicates,
+            superseded: plan.superseded,
+            dry_run,
+        })
+    }
+
+    async fn apply(&self, op: &OpRecord) -> Result<(), NarraError> {
+        let (table, key) = op.entity_id.split_once(':').ok_or_else(|| {
+            NarraError::Validation(format!("Invalid entity ID '{}' in oplog", op.entity_id))
+        })?;
+        let entity = RecordId::from((table, key));
+
+        if op.event == ENTITY_DELETED {
+            self.db
+                .query("DELETE $entity")
+                .bind(("entity", entity))
+                .await?
+                .check()?;
+            return Ok(());
+        }
+        let Some(serde_json::Value::Object(state)) = &op.state else {
+            return Ok(());
+        };
+        let (links, dates) = typed_fields(state);
+        self.db
+            .query(
+                "UPSERT $entity CONTENT object::from_entries(object::entries($state).map(|$e| \
+                 IF $e[0] IN $dates THEN [$e[0], <datetime> $e[1]] \
+                 ELSE IF $e[0] IN $links THEN [$e[0], IF type::is::array($e[1]) \
+                 THEN $e[1].map(|$v| type::thing($v)) ELSE type::thing($e[1]) END] \
+                 ELSE $e END))",
+            )
+            .bind(("entity", entity))
+            .bind(("state", serde_json::Value::Object(state.clone())))
+            .bind(("links", links))
+            .bind(("dates", dates))
+            .await?
+            .check()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(op_id: &str, entity: &str, author: &str, timestamp: &str) -> OpRecord {
+        OpRecord {
+            op_id: op_id.to_string(),
+            author: Some(author.to_string()),
+            event: "entity.updated".to_string(),
+            entity_id: entity.to_string(),
+            entity_type: "character".to_string(),
+            name: None,
+            state: Some(serde_json::json!({"name": author})),
+            timestamp: timestamp.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_plan_merge_last_writer_wins() {
+        let known: HashSet<String> = ["op-1".to_string()].into_iter().collect();
+        let latest: HashMap<String, DateTime<Utc>> = [(
+            "character:alice".to_string(),
+            "2026-01-02T00:00:00Z".parse().unwrap(),
+        )]
+        .into_iter()
+        .collect();
+
+        let plan = plan_merge(
+            vec![
+                op("op-1", "character:alice", "ana", "2026-01-01T00:00:00Z"),
+                // Older than the local edit to Alice
+                op("op-2", "character:alice", "ben", "2026-01-01T12:00:00Z"),
+                // Two newer edits to Bob: the later one wins
+                op("op-4", "character:bob", "ben", "2026-01-03T00:00:00+01:00"),
+                op("op-3", "character:bob", "ana", "2026-01-02T12:00:00Z"),
+                op("op-4", "character:bob", "ben", "2026-01-03T00:00:00+01:00"),
+            ],
+            &known,
+            &latest,
+        );
+
+        assert_eq!(plan.duplicates, 2);
+        assert_eq!(plan.superseded.len(), 1);
+        assert_eq!(plan.superseded[0].op_id, "op-2");
+        assert_eq!(plan.record.len(), 3);
+        assert_eq!(plan.apply.len(), 1);
+        assert_eq!(plan.apply[0].op_id, "op-4");
+
+        let state = serde_json::json!({
+            "name": "Alice",
+            "location": "location:keep",
+            "aliases": ["Al"],
+            "created_at": "2026-01-01T00:00:00Z",
+        });
+        let (links, dates) = typed_fields(state.as_object().unwrap());
+        assert_eq!(links, vec!["location"]);
+        assert_eq!(dates, vec!["created_at"]);
+    }
+}
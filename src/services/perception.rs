@@ -5,6 +5,7 @@
 //! and divergence over time.
 
 use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
 use async_trait::async_trait;
 use serde::Serialize;
 use std::sync::Arc;
@@ -220,8 +221,11 @@ impl PerceptionDataProvider for SurrealPerceptionDataProvider {
     }
 
     async fn fetch_real_embedding(&self, entity_id: &str) -> Result<Option<Vec<f32>>, NarraError> {
-        let query = format!("SELECT VALUE embedding FROM {}", entity_id);
-        let mut resp = self.db.query(&query).await?;
+        let mut resp = self
+            .db
+            .query("SELECT VALUE embedding FROM $ref")
+            .bind(("ref", parse_record_id(entity_id)?))
+            .await?;
         let embeddings: Vec<Option<Vec<f32>>> = resp.take(0).unwrap_or_default();
         Ok(embeddings.into_iter().next().flatten())
     }
@@ -260,11 +264,12 @@ impl PerceptionDataProvider for SurrealPerceptionDataProvider {
         observer_id: &str,
         target_id: &str,
     ) -> Result<Option<String>, NarraError> {
-        let query = format!(
-            "SELECT id FROM perceives WHERE in = {} AND out = {}",
-            observer_id, target_id
-        );
-        let mut resp = self.db.query(&query).await?;
+        let mut resp = self
+            .db
+            .query("SELECT id FROM perceives WHERE in = $observer AND out = $target")
+            .bind(("observer", parse_record_id(observer_id)?))
+            .bind(("target", parse_record_id(target_id)?))
+            .await?;
 
         #[derive(serde::Deserialize)]
         struct Row {
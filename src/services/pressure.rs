@@ -0,0 +1,399 @@
+//! Antagonist pressure: how hard the story pushes back on a protagonist.
+//!
+//! Opposition acts on the protagonist in the scenes they appear in:
+//!
+//! - rival: another participant holds a negative-polarity relationship
+//!   (enemy, rival, antagonistic, ...) with them, in either direction
+//! - antagonist: another participant has a declared antagonist role
+//! - strict fact: a strict universe fact applies to the scene or its event
+//!
+//! Scenes are grouped into the saved narrative phases by event sequence, or
+//! into three equal acts when no phase has a sequence range. A phase is
+//! flagged when the protagonist appears in it but too small a share of those
+//! scenes carry any opposition.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
+use crate::models::phase::list_phases;
+use crate::services::ontology::{Polarity, RelationshipOntology};
+use crate::NarraError;
+
+/// Default share of the protagonist's scenes that should carry opposition.
+pub const DEFAULT_MIN_SHARE: f64 = 0.25;
+
+/// Declared roles that make a character an antagonistic force.
+const ANTAGONIST_ROLES: &[&str] = &["antagonist", "villain", "nemesis"];
+
+/// One force acting against the protagonist in a scene.
+#[derive(Debug, Clone, Serialize)]
+pub struct PressureAct {
+    /// "rival", "antagonist" or "strict_fact"
+    pub kind: String,
+    pub source_id: String,
+    pub source_name: String,
+}
+
+/// A scene the protagonist appears in, in reading order.
+#[derive(Debug, Clone, Serialize)]
+pub struct PressureScene {
+    pub scene_id: String,
+    pub title: String,
+    pub sequence: i64,
+    pub acts: Vec<PressureAct>,
+}
+
+/// A stretch of the timeline, inclusive.
+#[derive(Debug, Clone)]
+pub struct PhaseRange {
+    pub label: String,
+    pub min: i64,
+    pub max: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PhasePressure {
+    pub label: String,
+    pub sequence_min: i64,
+    pub sequence_max: i64,
+    /// Scenes the protagonist appears in
+    pub scenes: usize,
+    /// Of those, scenes with any opposition
+    pub opposed: usize,
+    pub acts: usize,
+    pub share: f64,
+    /// Sequence of the latest opposed scene
+    pub last_opposed: Option<i64>,
+    /// "no opposition" or "weak opposition"
+    pub flag: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PressureReport {
+    pub protagonist_id: String,
+    pub protagonist: String,
+    pub phases: Vec<PhasePressure>,
+    /// The protagonist's scenes since the latest opposed one (all of them
+    /// when none is)
+    pub scenes_since_opposed: usize,
+    pub scenes: Vec<PressureScene>,
+}
+
+/// Three equal acts over `min..=max` (at least one sequence each).
+pub fn three_acts(min: i64, max: i64) -> Vec<PhaseRange> {
+    let span = (max - min + 1).max(3);
+    (0..3)
+        .map(|i| PhaseRange {
+            label: format!("Act {}", i + 1),
+            min: min + span * i / 3,
+            max: min + span * (i + 1) / 3 - 1,
+        })
+        .collect()
+}
+
+/// Tally opposition per phase. Scenes outside every phase are left out of
+/// the phase rows but still count for recency.
+pub fn tally(
+    scenes: &[PressureScene],
+    phases: &[PhaseRange],
+    min_share: f64,
+) -> Vec<PhasePressure> {
+    phases
+        .iter()
+        .map(|phase| {
+            let inside: Vec<&PressureScene> = scenes
+                .iter()
+                .filter(|s| (phase.min..=phase.max).contains(&s.sequence))
+                .collect();
+            let opposed: Vec<&&PressureScene> =
+                inside.iter().filter(|s| !s.acts.is_empty()).collect();
+            let share = if inside.is_empty() {
+                0.0
+            } else {
+                opposed.len() as f64 / inside.len() as f64
+            };
+            let flag = if inside.is_empty() {
+                None
+            } else if opposed.is_empty() {
+                Some("no opposition")
+            } else if share < min_share {
+                Some("weak opposition")
+            } else {
+                None
+            };
+            PhasePressure {
+                label: phase.label.clone(),
+                sequence_min: phase.min,
+                sequence_max: phase.max,
+                scenes: inside.len(),
+                opposed: opposed.len(),
+                acts: inside.iter().map(|s| s.acts.len()).sum(),
+                share,
+                last_opposed: opposed.iter().map(|s| s.sequence).max(),
+                flag: flag.map(str::to_string),
+            }
+        })
+        .collect()
+}
+
+pub struct PressureService {
+    db: Arc<NarraDb>,
+    ontology: Arc<RelationshipOntology>,
+}
+
+impl PressureService {
+    pub fn new(db: Arc<NarraDb>, ontology: Arc<RelationshipOntology>) -> Self {
+        Self { db, ontology }
+    }
+
+    /// Pressure on `protagonist` (a character ID).
+    pub async fn report(
+        &self,
+        protagonist: &str,
+        min_share: f64,
+    ) -> Result<PressureReport, NarraError> {
+        #[derive(Deserialize)]
+        struct CharacterRow {
+            id: RecordId,
+            name: String,
+            #[serde(default)]
+            roles: Vec<String>,
+        }
+        #[derive(Deserialize)]
+        struct SceneRow {
+            id: RecordId,
+            title: String,
+            event: RecordId,
+            sequence: i64,
+        }
+        #[derive(Deserialize)]
+        struct EdgeRow {
+            #[serde(rename = "in")]
+            from: RecordId,
+            out: RecordId,
+        }
+        #[derive(Deserialize)]
+        struct RelationshipRow {
+            #[serde(rename = "in")]
+            from: RecordId,
+            out: RecordId,
+            rel_type: String,
+        }
+        #[derive(Deserialize)]
+        struct FactRow {
+            id: RecordId,
+            title: String,
+        }
+
+        let protagonist = record_id("character", protagonist)?;
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, name, roles FROM character; \
+                 SELECT id, title, event, event.sequence AS sequence, created_at FROM scene \
+                 ORDER BY sequence ASC, created_at ASC; \
+                 SELECT in, out FROM participates_in; \
+                 SELECT in, out, rel_type FROM relates_to WHERE in = $character OR out = $character; \
+                 SELECT id, title FROM universe_fact WHERE enforcement_level = 'strict'; \
+                 SELECT in, out FROM applies_to",
+            )
+            .bind(("character", protagonist.clone()))
+            .await?;
+        let characters: Vec<CharacterRow> = response.take(0)?;
+        let scene_rows: Vec<SceneRow> = response.take(1)?;
+        let participation: Vec<EdgeRow> = response.take(2)?;
+        let relationships: Vec<RelationshipRow> = response.take(3)?;
+        let strict: Vec<FactRow> = response.take(4)?;
+        let applications: Vec<EdgeRow> = response.take(5)?;
+
+        let key = protagonist.to_string();
+        let name = characters
+            .iter()
+            .find(|c| c.id.to_string() == key)
+            .map(|c| c.name.clone())
+            .ok_or_else(|| NarraError::NotFound {
+                entity_type: "character".to_string(),
+                id: key.clone(),
+            })?;
+
+        // Who opposes the protagonist, and how
+        let mut forces: HashMap<String, (&str, String)> = HashMap::new();
+        for c in &characters {
+            let id = c.id.to_string();
+            if id != key
+                && c.roles
+                    .iter()
+                    .any(|r| ANTAGONIST_ROLES.contains(&r.to_lowercase().as_str()))
+            {
+                forces.insert(id, ("antagonist", c.name.clone()));
+            }
+        }
+        let names: HashMap<String, &str> = characters
+            .iter()
+            .map(|c| (c.id.to_string(), c.name.as_str()))
+            .collect();
+        for rel in &relationships {
+            let negative = self
+                .ontology
+                .get(&rel.rel_type)
+                .is_some_and(|t| t.polarity == Polarity::Negative);
+            let other = if rel.from.to_string() == key {
+                rel.out.to_string()
+            } else {
+                rel.from.to_string()
+            };
+            if negative && other != key {
+                let name = names.get(&other).copied().unwrap_or(&other).to_string();
+                forces.insert(other, ("rival", name));
+            }
+        }
+
+        let strict: HashMap<String, String> = strict
+            .into_iter()
+            .map(|f| (f.id.to_string(), f.title))
+            .collect();
+        let mut facts_on: HashMap<String, Vec<String>> = HashMap::new();
+        for edge in applications {
+            let fact = edge.from.to_string();
+            if strict.contains_key(&fact) {
+                facts_on.entry(edge.out.to_string()).or_default().push(fact);
+            }
+        }
+        let mut cast: HashMap<String, HashSet<String>> = HashMap::new();
+        for edge in participation {
+            cast.entry(edge.out.to_string())
+                .or_default()
+                .insert(edge.from.to_string());
+        }
+
+        let mut scenes = Vec::new();
+        for row in &scene_rows {
+            let scene_id = row.id.to_string();
+            let Some(present) = cast.get(&scene_id) else {
+                continue;
+            };
+            if !present.contains(&key) {
+                continue;
+            }
+            let mut acts: Vec<PressureAct> = present
+                .iter()
+                .filter_map(|c| {
+                    forces.get(c).map(|(kind, name)| PressureAct {
+                        kind: kind.to_string(),
+                        source_id: c.clone(),
+                        source_name: name.clone(),
+                    })
+                })
+                .collect();
+            let mut facts: Vec<&String> = facts_on
+                .get(&scene_id)
+                .into_iter()
+                .chain(facts_on.get(&row.event.to_string()))
+                .flatten()
+                .collect();
+            facts.sort();
+            facts.dedup();
+            acts.extend(facts.into_iter().map(|f| PressureAct {
+                kind: "strict_fact".to_string(),
+                source_id: f.clone(),
+                source_name: strict[f].clone(),
+            }));
+            acts.sort_by(|a, b| (&a.kind, &a.source_name).cmp(&(&b.kind, &b.source_name)));
+            scenes.push(PressureScene {
+                scene_id,
+                title: row.title.clone(),
+                sequence: row.sequence,
+                acts,
+            });
+        }
+
+        let mut saved = list_phases(&self.db).await?;
+        saved.sort_by_key(|p| p.phase_order);
+        let mut phases: Vec<PhaseRange> = saved
+            .into_iter()
+            .filter_map(|p| {
+                Some(PhaseRange {
+                    label: p.label,
+                    min: p.sequence_range_min?,
+                    max: p.sequence_range_max?,
+                })
+            })
+            .collect();
+        if phases.is_empty() {
+            let sequences = scene_rows.iter().map(|s| s.sequence);
+            if let (Some(min), Some(max)) = (sequences.clone().min(), sequences.max()) {
+                phases = three_acts(min, max);
+            }
+        }
+
+        let scenes_since_opposed = scenes
+            .iter()
+            .rev()
+            .take_while(|s| s.acts.is_empty())
+            .count();
+        Ok(PressureReport {
+            protagonist_id: key,
+            protagonist: name,
+            phases: tally(&scenes, &phases, min_share),
+            scenes_since_opposed,
+            scenes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(sequence: i64, kinds: &[&str]) -> PressureScene {
+        PressureScene {
+            scene_id: format!("scene:s{}", sequence),
+            title: format!("Scene {}", sequence),
+            sequence,
+            acts: kinds
+                .iter()
+                .map(|k| PressureAct {
+                    kind: k.to_string(),
+                    source_id: "character:vane".to_string(),
+                    source_name: "Vane".to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_tally_flags_acts_without_opposition() {
+        let acts = three_acts(1, 9);
+        let bounds: Vec<(i64, i64)> = acts.iter().map(|a| (a.min, a.max)).collect();
+        assert_eq!(bounds, vec![(1, 3), (4, 6), (7, 9)]);
+
+        let scenes = vec![
+            scene(1, &["rival"]),
+            scene(2, &[]),
+            scene(4, &[]),
+            scene(4, &[]),
+            scene(5, &[]),
+            scene(6, &[]),
+            scene(7, &[]),
+            scene(6, &["strict_fact"]),
+            scene(9, &["rival", "antagonist"]),
+        ];
+        let phases = tally(&scenes, &acts, DEFAULT_MIN_SHARE);
+
+        assert_eq!(phases[0].share, 0.5);
+        assert_eq!(phases[0].flag, None);
+        assert_eq!(phases[1].opposed, 1);
+        assert_eq!(phases[1].flag.as_deref(), Some("weak opposition"));
+        assert_eq!(phases[2].acts, 2);
+        assert_eq!(phases[2].last_opposed, Some(9));
+
+        let quiet = tally(&scenes[1..3], &acts, DEFAULT_MIN_SHARE);
+        assert_eq!(quiet[0].flag.as_deref(), Some("no opposition"));
+        assert_eq!(quiet[2].flag, None);
+    }
+}
@@ -0,0 +1,358 @@
+//! Reader knowledge audit: what the reader has been shown, scene by scene.
+//!
+//! Scenes are read in timeline order. A scene reveals the facts its POV
+//! character learns at the scene's event, or those any participant learns
+//! when the scene has no POV character. A gap is a fact the POV character
+//! already knows that the reader has not been shown yet: the page is told
+//! from inside a head holding information the reader never received.
+//!
+//! Scenes at planned events are not on the page yet and are left out.
+//! Knowledge learned at a backstory event counts as backstory, like
+//! knowledge with no event at all.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::NarraError;
+
+/// A scene as input to the simulation.
+#[derive(Debug, Clone)]
+pub struct SceneInput {
+    pub id: String,
+    pub title: String,
+    pub event_id: String,
+    pub sequence: Option<i64>,
+    pub pov: Option<String>,
+    pub participants: Vec<String>,
+    /// The scene's event is only planned
+    pub planned: bool,
+}
+
+/// A character learning a fact, from a `knows` edge.
+#[derive(Debug, Clone)]
+pub struct Learning {
+    pub character_id: String,
+    pub character_name: String,
+    pub knowledge_id: String,
+    pub fact: String,
+    pub event_id: Option<String>,
+    pub sequence: Option<i64>,
+    /// Learned at a backstory event
+    pub backstory: bool,
+}
+
+/// A fact the POV character holds but the reader was never shown.
+#[derive(Debug, Clone, Serialize)]
+pub struct KnowledgeGap {
+    pub knowledge_id: String,
+    pub fact: String,
+    pub character: String,
+    /// "off_page" (learned at an unshown event) or "backstory" (no event,
+    /// or a backstory event)
+    pub kind: String,
+    pub learned_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReaderScene {
+    pub scene_id: String,
+    pub title: String,
+    pub sequence: Option<i64>,
+    pub pov: Option<String>,
+    pub revealed: Vec<String>,
+    /// Facts the reader holds after this scene
+    pub reader_knows: usize,
+    pub gaps: Vec<KnowledgeGap>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReaderKnowledgeReport {
+    pub scenes: Vec<ReaderScene>,
+    pub total_gaps: usize,
+    /// Facts some character knows that the reader never sees
+    pub never_revealed: usize,
+    /// Scenes left out because their event is only planned
+    pub planned_scenes: usize,
+}
+
+/// Walk scenes in reading order, accumulating what the reader knows.
+///
+/// Each (character, fact) gap is reported once, at the first scene it shows.
+pub fn simulate(
+    mut scenes: Vec<SceneInput>,
+    learnings: &[Learning],
+    include_backstory: bool,
+) -> ReaderKnowledgeReport {
+    let before = scenes.len();
+    scenes.retain(|s| !s.planned);
+    let planned_scenes = before - scenes.len();
+    scenes.sort_by_key(|s| (s.sequence.is_none(), s.sequence));
+    let names: HashMap<&str, &str> = learnings
+        .iter()
+        .map(|l| (l.character_id.as_str(), l.character_name.as_str()))
+        .collect();
+
+    let mut reader: HashSet<&str> = HashSet::new();
+    let mut reported: HashSet<(&str, &str)> = HashSet::new();
+    let mut out = Vec::with_capacity(scenes.len());
+    let mut total_gaps = 0;
+
+    for scene in &scenes {
+        let revealers: Vec<&str> = match &scene.pov {
+            Some(pov) => vec![pov.as_str()],
+            None => scene.participants.iter().map(String::as_str).collect(),
+        };
+        let mut revealed = Vec::new();
+        for l in learnings {
+            if l.event_id.as_deref() == Some(scene.event_id.as_str())
+                && revealers.contains(&l.character_id.as_str())
+                && reader.insert(l.knowledge_id.as_str())
+            {
+                revealed.push(l.fact.clone());
+            }
+        }
+
+        let mut gaps = Vec::new();
+        if let Some(pov) = &scene.pov {
+            for l in learnings.iter().filter(|l| &l.character_id == pov) {
+                let backstory = l.backstory || l.event_id.is_none();
+                let kind = match (backstory, l.sequence, scene.sequence) {
+                    (true, _, _) if include_backstory => "backstory",
+                    (false, Some(learned), Some(now)) if learned <= now => "off_page",
+                    _ => continue,
+                };
+                if reader.contains(l.knowledge_id.as_str())
+                    || !reported.insert((pov.as_str(), l.knowledge_id.as_str()))
+                {
+                    continue;
+                }
+                gaps.push(KnowledgeGap {
+                    knowledge_id: l.knowledge_id.clone(),
+                    fact: l.fact.clone(),
+                    character: l.character_name.clone(),
+                    kind: kind.to_string(),
+                    learned_at: l.sequence,
+                });
+            }
+        }
+        total_gaps += gaps.len();
+
+        out.push(ReaderScene {
+            scene_id: scene.id.clone(),
+            title: scene.title.clone(),
+            sequence: scene.sequence,
+            pov: scene
+                .pov
+                .as_deref()
+                .map(|p| names.get(p).copied().unwrap_or(p).to_string()),
+            revealed,
+            reader_knows: reader.len(),
+            gaps,
+        });
+    }
+
+    let never_revealed = learnings
+        .iter()
+        .map(|l| l.knowledge_id.as_str())
+        .filter(|k| !reader.contains(k))
+        .collect::<HashSet<_>>()
+        .len();
+    ReaderKnowledgeReport {
+        scenes: out,
+        total_gaps,
+        never_revealed,
+        planned_scenes,
+    }
+}
+
+pub struct ReaderKnowledgeService {
+    db: Arc<NarraDb>,
+}
+
+impl ReaderKnowledgeService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    pub async fn report(
+        &self,
+        include_backstory: bool,
+    ) -> Result<ReaderKnowledgeReport, NarraError> {
+        #[derive(Deserialize)]
+        struct SceneRow {
+            id: RecordId,
+            title: String,
+            event: RecordId,
+            sequence: Option<i64>,
+            pov: Vec<RecordId>,
+            participants: Vec<RecordId>,
+            narrative_status: Option<String>,
+        }
+        #[derive(Deserialize)]
+        struct KnowsRow {
+            character: RecordId,
+            character_name: Option<String>,
+            knowledge: RecordId,
+            fact: Option<String>,
+            event: Option<RecordId>,
+            sequence: Option<i64>,
+            narrative_status: Option<String>,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, title, event, event.sequence AS sequence, \
+                 event.narrative_status AS narrative_status, \
+                 (SELECT VALUE in FROM participates_in WHERE out = $parent.id AND role = 'pov') AS pov, \
+                 (SELECT VALUE in FROM participates_in WHERE out = $parent.id) AS participants \
+                 FROM scene; \
+                 SELECT in AS character, in.name AS character_name, out AS knowledge, \
+                 out.fact AS fact, event, event.sequence AS sequence, \
+                 event.narrative_status AS narrative_status FROM knows \
+                 WHERE record::tb(out) = 'knowledge'",
+            )
+            .await?;
+        let scenes: Vec<SceneRow> = response.take(0)?;
+        let knows: Vec<KnowsRow> = response.take(1)?;
+
+        let scenes = scenes
+            .into_iter()
+            .map(|s| SceneInput {
+                id: s.id.to_string(),
+                title: s.title,
+                event_id: s.event.to_string(),
+                sequence: s.sequence,
+                pov: s.pov.first().map(|p| p.to_string()),
+                participants: s.participants.iter().map(|p| p.to_string()).collect(),
+                planned: s.narrative_status.as_deref() == Some("planned"),
+            })
+            .collect();
+        let learnings: Vec<Learning> = knows
+            .into_iter()
+            .map(|k| {
+                let character_id = k.character.to_string();
+                let knowledge_id = k.knowledge.to_string();
+                Learning {
+                    character_name: k.character_name.unwrap_or_else(|| character_id.clone()),
+                    fact: k.fact.unwrap_or_else(|| knowledge_id.clone()),
+                    character_id,
+                    knowledge_id,
+                    event_id: k.event.map(|e| e.to_string()),
+                    sequence: k.sequence,
+                    backstory: k.narrative_status.as_deref() == Some("backstory"),
+                }
+            })
+            .collect();
+
+        Ok(simulate(scenes, &learnings, include_backstory))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(
+        id: &str,
+        event: &str,
+        sequence: i64,
+        pov: Option<&str>,
+        participants: &[&str],
+    ) -> SceneInput {
+        SceneInput {
+            id: id.to_string(),
+            title: id.to_string(),
+            event_id: event.to_string(),
+            sequence: Some(sequence),
+            pov: pov.map(str::to_string),
+            participants: participants.iter().map(|p| p.to_string()).collect(),
+            planned: false,
+        }
+    }
+
+    fn learns(character: &str, knowledge: &str, event: Option<(&str, i64)>) -> Learning {
+        Learning {
+            character_id: character.to_string(),
+            character_name: character.to_string(),
+            knowledge_id: knowledge.to_string(),
+            fact: knowledge.to_string(),
+            event_id: event.map(|(e, _)| e.to_string()),
+            sequence: event.map(|(_, s)| s),
+            backstory: false,
+        }
+    }
+
+    #[test]
+    fn test_reader_gaps_follow_pov() {
+        let scenes = vec![
+            scene("scene:3", "event:c", 30, Some("alice"), &["alice"]),
+            scene("scene:1", "event:a", 10, Some("alice"), &["alice", "bob"]),
+            scene("scene:2", "event:b", 20, None, &["bob"]),
+        ];
+        let learnings = vec![
+            // Shown: Alice learns it in her own POV scene
+            learns("alice", "knowledge:map", Some(("event:a", 10))),
+            // Shown: omniscient scene, Bob learns it
+            learns("bob", "knowledge:poison", Some(("event:b", 20))),
+            // Not shown: Alice learns it at an event without a scene
+            learns("alice", "knowledge:traitor", Some(("event:x", 15))),
+            // Backstory, only reported on request
+            learns("alice", "knowledge:childhood", None),
+            // Bob learns it at Alice's POV scene: the reader doesn't see it
+            learns("bob", "knowledge:code", Some(("event:a", 10))),
+        ];
+
+        let report = simulate(scenes.clone(), &learnings, false);
+        let order: Vec<&str> = report.scenes.iter().map(|s| s.scene_id.as_str()).collect();
+        assert_eq!(order, vec!["scene:1", "scene:2", "scene:3"]);
+        assert_eq!(report.scenes[0].revealed, vec!["knowledge:map"]);
+        assert_eq!(report.scenes[1].revealed, vec!["knowledge:poison"]);
+        assert_eq!(report.scenes[2].reader_knows, 2);
+
+        assert_eq!(report.total_gaps, 1);
+        let gap = &report.scenes[2].gaps[0];
+        assert_eq!(
+            (gap.fact.as_str(), gap.kind.as_str()),
+            ("knowledge:traitor", "off_page")
+        );
+        assert_eq!(report.never_revealed, 3);
+
+        let with_backstory = simulate(scenes, &learnings, true);
+        assert_eq!(with_backstory.total_gaps, 2);
+        assert_eq!(with_backstory.scenes[0].gaps[0].kind, "backstory");
+    }
+
+    #[test]
+    fn test_reader_skips_planned_scenes_and_backstory_events() {
+        let mut planned = scene("scene:2", "event:b", 20, Some("alice"), &["alice"]);
+        planned.planned = true;
+        let scenes = vec![
+            scene("scene:1", "event:a", 10, Some("alice"), &["alice"]),
+            planned,
+            scene("scene:3", "event:c", 30, Some("alice"), &["alice"]),
+        ];
+        let mut war = learns("alice", "knowledge:war", Some(("event:war", 1)));
+        war.backstory = true;
+        let learnings = vec![
+            war,
+            // Learned in the planned scene: the reader hasn't seen it yet
+            learns("alice", "knowledge:letter", Some(("event:b", 20))),
+        ];
+
+        let report = simulate(scenes.clone(), &learnings, false);
+        assert_eq!(report.planned_scenes, 1);
+        assert_eq!(report.scenes.len(), 2);
+        assert_eq!(report.total_gaps, 1);
+        assert_eq!(report.scenes[1].gaps[0].kind, "off_page");
+
+        let with_backstory = simulate(scenes, &learnings, true);
+        assert_eq!(with_backstory.total_gaps, 2);
+        assert_eq!(with_backstory.scenes[0].gaps[0].fact, "knowledge:war");
+        assert_eq!(with_backstory.scenes[0].gaps[0].kind, "backstory");
+    }
+}
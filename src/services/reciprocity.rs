@@ -0,0 +1,261 @@
+//! Reciprocal relationship checks.
+//!
+//! A relationship's role is its subtype, or its type when it has none.
+//! Many roles imply one on the reverse edge: if A is B's "mentor", B should
+//! be A's "student". Some reverse roles contradict: A "ally" of B while B is
+//! A's "enemy". Edges marked `intentional` (unrequited love, a spy posing as
+//! a friend) are never flagged.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::models::Relationship;
+use crate::NarraError;
+
+/// Roles and the role they imply on the reverse edge.
+pub const RECIPROCALS: &[(&str, &str)] = &[
+    ("parent", "child"),
+    ("child", "parent"),
+    ("sibling", "sibling"),
+    ("spouse", "spouse"),
+    ("grandparent", "grandchild"),
+    ("grandchild", "grandparent"),
+    ("parent-in-law", "child-in-law"),
+    ("child-in-law", "parent-in-law"),
+    ("sibling-in-law", "sibling-in-law"),
+    ("mentor", "student"),
+    ("student", "mentor"),
+    ("employer", "employee"),
+    ("employee", "employer"),
+    ("master", "servant"),
+    ("servant", "master"),
+    ("guardian", "ward"),
+    ("ward", "guardian"),
+    ("ally", "ally"),
+    ("friend", "friend"),
+    ("enemy", "enemy"),
+    ("rival", "rival"),
+];
+
+/// Role pairs that cannot both hold between the same two characters.
+pub const CONTRADICTIONS: &[(&str, &str)] = &[
+    ("ally", "enemy"),
+    ("friend", "enemy"),
+    ("ally", "rival"),
+    ("spouse", "enemy"),
+    ("parent", "parent"),
+    ("child", "child"),
+    ("mentor", "mentor"),
+    ("employer", "employer"),
+];
+
+/// The role implied on the reverse edge, if the role has one.
+pub fn reciprocal(role: &str) -> Option<&'static str> {
+    let role = role.to_lowercase();
+    RECIPROCALS
+        .iter()
+        .find(|(r, _)| *r == role)
+        .map(|(_, reverse)| *reverse)
+}
+
+/// Whether A having `role` towards B contradicts B having `reverse` towards A.
+pub fn contradicts(role: &str, reverse: &str) -> bool {
+    let (role, reverse) = (role.to_lowercase(), reverse.to_lowercase());
+    CONTRADICTIONS
+        .iter()
+        .any(|(a, b)| (*a == role && *b == reverse) || (*b == role && *a == reverse))
+}
+
+/// The role an edge expresses: its subtype, else its type.
+pub fn role_of(rel: &Relationship) -> String {
+    rel.subtype
+        .as_deref()
+        .unwrap_or(&rel.rel_type)
+        .to_lowercase()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReciprocityIssueKind {
+    /// The reverse edge is missing
+    Missing,
+    /// The reverse edge has a role that contradicts this one
+    Contradictory,
+}
+
+/// A relationship whose reverse edge is missing or contradicts it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReciprocityIssue {
+    pub kind: ReciprocityIssueKind,
+    /// The edge that implies a reciprocal
+    pub relationship_id: String,
+    pub from: String,
+    pub to: String,
+    pub role: String,
+    /// Role the reverse edge should have
+    pub expected: String,
+    /// Role the reverse edge has (contradictions)
+    pub found: Option<String>,
+}
+
+impl ReciprocityIssue {
+    pub fn message(&self) -> String {
+        match self.kind {
+            ReciprocityIssueKind::Missing => format!(
+                "{} is {} of {}, but {} is not {} of {}",
+                self.from, self.role, self.to, self.to, self.expected, self.from
+            ),
+            ReciprocityIssueKind::Contradictory => format!(
+                "{} is {} of {}, but {} is {} of {}",
+                self.from,
+                self.role,
+                self.to,
+                self.to,
+                self.found.as_deref().unwrap_or_default(),
+                self.from
+            ),
+        }
+    }
+}
+
+/// Missing and contradictory reverse edges, for one character's outgoing
+/// edges or the whole world.
+pub async fn check_reciprocity(
+    db: &NarraDb,
+    character: Option<&RecordId>,
+) -> Result<Vec<ReciprocityIssue>, NarraError> {
+    let mut result = db.query("SELECT * FROM relates_to").await?;
+    let rels: Vec<Relationship> = result.take(0)?;
+
+    // (from, to) -> roles, with whether each edge is marked intentional
+    let mut roles_between: HashMap<(String, String), Vec<(String, bool)>> = HashMap::new();
+    for rel in &rels {
+        roles_between
+            .entry((rel.from_character.to_string(), rel.to_character.to_string()))
+            .or_default()
+            .push((role_of(rel), rel.intentional));
+    }
+
+    let mut issues = Vec::new();
+    for rel in &rels {
+        if rel.intentional || character.is_some_and(|c| *c != rel.from_character) {
+            continue;
+        }
+        let role = role_of(rel);
+        let Some(expected) = reciprocal(&role) else {
+            continue;
+        };
+        let reverse = roles_between
+            .get(&(rel.to_character.to_string(), rel.from_character.to_string()))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if reverse.iter().any(|(r, _)| r == expected) {
+            continue;
+        }
+        let issue = |kind, found: Option<&String>| ReciprocityIssue {
+            kind,
+            relationship_id: rel.id.to_string(),
+            from: rel.from_character.to_string(),
+            to: rel.to_character.to_string(),
+            role: role.clone(),
+            expected: expected.to_string(),
+            found: found.cloned(),
+        };
+        match reverse.iter().find(|(r, _)| contradicts(&role, r)) {
+            // Either side may be marked intentional
+            Some((_, true)) => {}
+            // A world-wide check reports each contradictory pair once
+            Some((found, false)) => {
+                let both_flagged = character.is_none()
+                    && reciprocal(found).is_some()
+                    && rel.to_character.to_string() < rel.from_character.to_string();
+                if !both_flagged {
+                    issues.push(issue(ReciprocityIssueKind::Contradictory, Some(found)));
+                }
+            }
+            None => issues.push(issue(ReciprocityIssueKind::Missing, None)),
+        }
+    }
+    Ok(issues)
+}
+
+/// Create the reverse edges `Missing` issues ask for. Contradictions are
+/// left alone: which side is right is the author's call.
+pub async fn create_missing_reciprocals(
+    db: &NarraDb,
+    issues: &[ReciprocityIssue],
+) -> Result<usize, NarraError> {
+    let mut created = 0;
+    for issue in issues
+        .iter()
+        .filter(|i| i.kind == ReciprocityIssueKind::Missing)
+    {
+        let Some((_, key)) = issue.relationship_id.split_once(':') else {
+            continue;
+        };
+        let rel: Option<Relationship> = db.select(("relates_to", key)).await?;
+        let Some(rel) = rel else {
+            continue;
+        };
+        // Mirror the original shape: a subtype role gets a reciprocal subtype
+        let (rel_type, subtype) = match rel.subtype {
+            Some(_) => (rel.rel_type.clone(), Some(issue.expected.clone())),
+            None => (issue.expected.clone(), None),
+        };
+        db.query(
+            "RELATE $from->relates_to->$to SET rel_type = $rel_type, subtype = $subtype, label = NONE",
+        )
+        .bind(("from", rel.to_character.clone()))
+        .bind(("to", rel.from_character.clone()))
+        .bind(("rel_type", rel_type))
+        .bind(("subtype", subtype))
+        .await?
+        .check()?;
+        created += 1;
+    }
+    Ok(created)
+}
+
+/// Mark a relationship as intentionally one-sided or contradictory, so
+/// reciprocity checks skip it.
+pub async fn set_intentional(
+    db: &NarraDb,
+    relationship_id: &str,
+    intentional: bool,
+) -> Result<Relationship, NarraError> {
+    let key = relationship_id
+        .strip_prefix("relates_to:")
+        .unwrap_or(relationship_id);
+    let rel: Option<Relationship> = db
+        .update(("relates_to", key))
+        .merge(serde_json::json!({ "intentional": intentional }))
+        .await?;
+    rel.ok_or_else(|| NarraError::NotFound {
+        entity_type: "relationship".to_string(),
+        id: relationship_id.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reciprocals_are_symmetric() {
+        for (role, reverse) in RECIPROCALS {
+            assert_eq!(reciprocal(reverse), Some(*role));
+        }
+        assert_eq!(reciprocal("Mentor"), Some("student"));
+        assert_eq!(reciprocal("acquaintance"), None);
+    }
+
+    #[test]
+    fn test_contradictions_either_way() {
+        assert!(contradicts("ally", "enemy"));
+        assert!(contradicts("Enemy", "ally"));
+        assert!(!contradicts("ally", "ally"));
+    }
+}
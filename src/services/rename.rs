@@ -0,0 +1,370 @@
+//! Renaming an entity across the world's prose.
+//!
+//! A rename changes the name (or title), keeps the old name as an alias on
+//! characters so links and searches by it still resolve, and finds the old
+//! name in character profiles, descriptions, scene summaries and note
+//! bodies. Occurrences match whole words and case, like prose mentions, and
+//! are only rewritten when asked; the plan shows each one in context first.
+//! Summaries of scenes marked final are locked and only rewritten when forced.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
+use crate::services::mentions::{find_mentions, prose_field, KnownEntity, MENTION_TABLES};
+use crate::NarraError;
+
+/// Characters past the match shown on each side of a preview.
+const CONTEXT_CHARS: usize = 30;
+
+/// The field holding the name shown for an entity of `table`.
+fn name_field(table: &str) -> &'static str {
+    match table {
+        "event" | "scene" => "title",
+        _ => "name",
+    }
+}
+
+fn old_name_terms(old: &str) -> [KnownEntity; 1] {
+    [KnownEntity {
+        id: String::new(),
+        entity_type: String::new(),
+        name: old.to_string(),
+        terms: vec![old.to_string()],
+    }]
+}
+
+/// `text` with whole-word, case-sensitive occurrences of `old` replaced by
+/// `new`, and how many there were.
+pub fn replace_name(text: &str, old: &str, new: &str) -> (String, usize) {
+    let mentions = find_mentions(text, &old_name_terms(old));
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for m in &mentions {
+        out.push_str(&text[last..m.start]);
+        out.push_str(new);
+        last = m.end;
+    }
+    out.push_str(&text[last..]);
+    (out, mentions.len())
+}
+
+/// The first occurrence of `old` in `text` with some context, before and
+/// after replacing it, on one line.
+pub fn preview(text: &str, old: &str, new: &str) -> Option<(String, String)> {
+    let m = find_mentions(text, &old_name_terms(old))
+        .into_iter()
+        .next()?;
+    let start = text[..m.start]
+        .char_indices()
+        .rev()
+        .nth(CONTEXT_CHARS - 1)
+        .map_or(0, |(i, _)| i);
+    let end = text[m.end..]
+        .char_indices()
+        .nth(CONTEXT_CHARS)
+        .map_or(text.len(), |(i, _)| m.end + i);
+    let lead = if start > 0 { "..." } else { "" };
+    let tail = if end < text.len() { "..." } else { "" };
+    let line = |name: &str| {
+        format!(
+            "{}{}{}{}{}",
+            lead,
+            &text[start..m.start],
+            name,
+            &text[m.end..end],
+            tail
+        )
+        .replace('\n', " ")
+    };
+    Some((line(old), line(new)))
+}
+
+/// A text that names the entity by its old name.
+#[derive(Debug, Clone, Serialize)]
+pub struct TextOccurrence {
+    pub entity_id: String,
+    /// Name or title of the entity holding the text
+    pub label: String,
+    /// `description`, `summary`, `body`, or `profile.<key>`
+    pub field: String,
+    pub count: usize,
+    pub before: String,
+    pub after: String,
+    /// The summary of a scene marked final
+    pub locked: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenamePlan {
+    pub entity_id: String,
+    pub old_name: String,
+    pub new_name: String,
+    /// Recorded as an alias (characters only)
+    pub alias: Option<String>,
+    pub occurrences: Vec<TextOccurrence>,
+}
+
+impl RenamePlan {
+    /// Occurrences across all texts.
+    pub fn total(&self) -> usize {
+        self.occurrences.iter().map(|o| o.count).sum()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenameOutcome {
+    #[serde(flatten)]
+    pub plan: RenamePlan,
+    /// Entities whose text was rewritten
+    pub rewritten: Vec<String>,
+    /// Final scenes whose summaries were left as they are
+    pub skipped_final: Vec<String>,
+}
+
+/// An entity's scannable text.
+#[derive(Debug, Clone, Deserialize)]
+struct TextRow {
+    id: RecordId,
+    label: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    profile: Option<HashMap<String, Vec<String>>>,
+    #[serde(default)]
+    draft_status: Option<String>,
+}
+
+impl TextRow {
+    fn is_final(&self) -> bool {
+        self.draft_status.as_deref() == Some("final")
+    }
+}
+
+/// The plain text field on `table`; characters keep their text in `profile`.
+fn text_field(table: &str) -> &'static str {
+    match table {
+        "note" => "body",
+        t => prose_field(t),
+    }
+}
+
+pub struct RenameService {
+    db: Arc<NarraDb>,
+}
+
+impl RenameService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    async fn load(&self) -> Result<Vec<TextRow>, NarraError> {
+        let mut resp = self
+            .db
+            .query("SELECT id, name AS label, profile FROM character")
+            .query("SELECT id, name AS label, description AS text FROM location")
+            .query("SELECT id, title AS label, description AS text FROM event")
+            .query("SELECT id, title AS label, summary AS text, draft_status FROM scene")
+            .query("SELECT id, title AS label, body AS text FROM note")
+            .await?;
+        let mut rows = Vec::new();
+        for index in 0..5 {
+            let table: Vec<TextRow> = resp.take(index)?;
+            rows.extend(table);
+        }
+        Ok(rows)
+    }
+
+    /// What renaming `entity_id` to `new_name` would change.
+    pub async fn plan(&self, entity_id: &str, new_name: &str) -> Result<RenamePlan, NarraError> {
+        let entity = parse_record_id(entity_id)?;
+        let table = entity.table().to_string();
+        if !MENTION_TABLES.contains(&table.as_str()) {
+            return Err(NarraError::Validation(format!(
+                "Only characters, locations, events and scenes can be renamed, got '{}'",
+                table
+            )));
+        }
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(NarraError::Validation("New name is empty".to_string()));
+        }
+
+        let mut resp = self
+            .db
+            .query(format!(
+                "SELECT VALUE {} FROM ONLY $ref",
+                name_field(&table)
+            ))
+            .bind(("ref", entity.clone()))
+            .await?;
+        let old_name: Option<String> = resp.take(0)?;
+        let old_name = old_name.ok_or_else(|| NarraError::NotFound {
+            entity_type: table.clone(),
+            id: entity_id.to_string(),
+        })?;
+        if old_name == new_name {
+            return Err(NarraError::Validation(format!(
+                "{} is already named '{}'",
+                entity_id, new_name
+            )));
+        }
+
+        let mut occurrences = Vec::new();
+        for row in self.load().await? {
+            let mut texts: Vec<(String, &str)> = Vec::new();
+            if let Some(text) = &row.text {
+                texts.push((text_field(row.id.table()).to_string(), text.as_str()));
+            }
+            if let Some(profile) = &row.profile {
+                let mut keys: Vec<&String> = profile.keys().collect();
+                keys.sort();
+                for key in keys {
+                    for value in &profile[key] {
+                        texts.push((format!("profile.{}", key), value.as_str()));
+                    }
+                }
+            }
+            for (field, text) in texts {
+                let (_, count) = replace_name(text, &old_name, new_name);
+                let Some((before, after)) = preview(text, &old_name, new_name) else {
+                    continue;
+                };
+                occurrences.push(TextOccurrence {
+                    entity_id: row.id.to_string(),
+                    label: row.label.clone(),
+                    field,
+                    count,
+                    before,
+                    after,
+                    locked: row.is_final(),
+                });
+            }
+        }
+
+        Ok(RenamePlan {
+            entity_id: entity.to_string(),
+            alias: (table == "character").then(|| old_name.clone()),
+            old_name,
+            new_name: new_name.to_string(),
+            occurrences,
+        })
+    }
+
+    /// Rename the entity, and rewrite the texts in the plan when
+    /// `replace_text` is set. Final scenes are skipped unless `force` is set.
+    pub async fn apply(
+        &self,
+        plan: RenamePlan,
+        replace_text: bool,
+        force: bool,
+    ) -> Result<RenameOutcome, NarraError> {
+        let entity = parse_record_id(&plan.entity_id)?;
+        let query = if plan.alias.is_some() {
+            "UPDATE $ref SET name = $new, \
+             aliases = array::complement(array::union(aliases, [$old]), [$new])"
+                .to_string()
+        } else {
+            format!("UPDATE $ref SET {} = $new", name_field(entity.table()))
+        };
+        self.db
+            .query(query)
+            .bind(("ref", entity))
+            .bind(("new", plan.new_name.clone()))
+            .bind(("old", plan.old_name.clone()))
+            .await?
+            .check()?;
+
+        let mut rewritten = Vec::new();
+        let mut skipped_final = Vec::new();
+        if replace_text {
+            let targets: HashSet<&str> = plan
+                .occurrences
+                .iter()
+                .map(|o| o.entity_id.as_str())
+                .collect();
+            for row in self.load().await? {
+                if !targets.contains(row.id.to_string().as_str()) {
+                    continue;
+                }
+                if row.is_final() && !force {
+                    skipped_final.push(row.id.to_string());
+                    continue;
+                }
+                self.rewrite(&row, &plan.old_name, &plan.new_name).await?;
+                rewritten.push(row.id.to_string());
+            }
+        }
+        Ok(RenameOutcome {
+            plan,
+            rewritten,
+            skipped_final,
+        })
+    }
+
+    async fn rewrite(&self, row: &TextRow, old: &str, new: &str) -> Result<(), NarraError> {
+        if let Some(text) = &row.text {
+            let (text, count) = replace_name(text, old, new);
+            if count > 0 {
+                self.db
+                    .query(format!(
+                        "UPDATE $ref SET {} = $text",
+                        text_field(row.id.table())
+                    ))
+                    .bind(("ref", row.id.clone()))
+                    .bind(("text", text))
+                    .await?
+                    .check()?;
+            }
+        }
+        if let Some(profile) = &row.profile {
+            let profile: HashMap<String, Vec<String>> = profile
+                .iter()
+                .map(|(key, values)| {
+                    let values = values.iter().map(|v| replace_name(v, old, new).0).collect();
+                    (key.clone(), values)
+                })
+                .collect();
+            self.db
+                .query("UPDATE $ref SET profile = $profile")
+                .bind(("ref", row.id.clone()))
+                .bind(("profile", profile))
+                .await?
+                .check()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_name_whole_words_only() {
+        let text = "Ann met Anna. Ann's brother called for ann, then for Ann.";
+        let (replaced, count) = replace_name(text, "Ann", "Annika");
+        assert_eq!(count, 3);
+        assert_eq!(
+            replaced,
+            "Annika met Anna. Annika's brother called for ann, then for Annika."
+        );
+        assert_eq!(replace_name("Nobody here", "Ann", "Annika").1, 0);
+    }
+
+    #[test]
+    fn test_preview_shows_first_occurrence_in_context() {
+        let (before, after) = preview("At dawn Ann left.", "Ann", "Annika").unwrap();
+        assert_eq!(before, "At dawn Ann left.");
+        assert_eq!(after, "At dawn Annika left.");
+
+        let long = format!("{}\nAnn waited.", "x".repeat(50));
+        let (before, _) = preview(&long, "Ann", "Annika").unwrap();
+        assert_eq!(before, format!("...{} Ann waited.", "x".repeat(29)));
+        assert!(preview("Anna", "Ann", "Annika").is_none());
+    }
+}
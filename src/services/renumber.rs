@@ -0,0 +1,334 @@
+//! Sequence renumbering.
+//!
+//! Events get evenly spaced sequences (`spacing`, `2 * spacing`, ...) in
+//! their current timeline order; events sharing a number are split in
+//! creation order, which is the order they are read in. Everything else that
+//! stores a sequence follows in the same transaction: thread deadlines,
+//! fact validity bounds, and saved phase ranges, labels and member
+//! positions. Numbers that fall between events are interpolated.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::models::phase::list_phases;
+use crate::services::reorder::{relabel, SequenceChange};
+use crate::NarraError;
+
+/// Old event sequences mapped to new ones.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceMap {
+    /// (old, new), by old; a shared old number maps to the first new one
+    points: Vec<(i64, i64)>,
+}
+
+impl SequenceMap {
+    pub fn new(changes: &[(i64, i64)]) -> Self {
+        let mut points: Vec<(i64, i64)> = changes.to_vec();
+        points.sort_by_key(|&(old, new)| (old, new));
+        points.dedup_by_key(|&mut (old, _)| old);
+        Self { points }
+    }
+
+    /// Where `value` lands: event sequences map exactly, numbers between two
+    /// events keep their relative place, and numbers outside the timeline
+    /// keep their distance from its ends.
+    pub fn map_f64(&self, value: f64) -> f64 {
+        let (Some(&first), Some(&last)) = (self.points.first(), self.points.last()) else {
+            return value;
+        };
+        if value <= first.0 as f64 {
+            return first.1 as f64 - (first.0 as f64 - value);
+        }
+        if value >= last.0 as f64 {
+            return last.1 as f64 + (value - last.0 as f64);
+        }
+        let after = self
+            .points
+            .partition_point(|&(old, _)| (old as f64) <= value);
+        let (a, b) = (self.points[after - 1], self.points[after]);
+        let share = (value - a.0 as f64) / (b.0 - a.0) as f64;
+        a.1 as f64 + share * (b.1 - a.1) as f64
+    }
+
+    pub fn map(&self, value: i64) -> i64 {
+        self.map_f64(value as f64).round() as i64
+    }
+}
+
+/// New sequences for `count` events in timeline order.
+pub fn renumber(count: usize, spacing: i64) -> Vec<i64> {
+    (1..=count as i64).map(|i| i * spacing).collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenumberReport {
+    pub spacing: i64,
+    /// Events whose sequence changes
+    pub changes: Vec<SequenceChange>,
+    pub threads: usize,
+    pub facts: usize,
+    pub phases: usize,
+    pub phase_members: usize,
+    pub applied: bool,
+}
+
+impl RenumberReport {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+            && self.threads == 0
+            && self.facts == 0
+            && self.phases == 0
+            && self.phase_members == 0
+    }
+}
+
+pub struct RenumberService {
+    db: Arc<NarraDb>,
+}
+
+impl RenumberService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Renumber every event `spacing` apart and update the records that
+    /// refer to sequences. With `dry_run` nothing is written.
+    pub async fn renumber(
+        &self,
+        spacing: i64,
+        dry_run: bool,
+    ) -> Result<RenumberReport, NarraError> {
+        if spacing < 1 {
+            return Err(NarraError::Validation(
+                "Spacing must be at least 1".to_string(),
+            ));
+        }
+
+        #[derive(Deserialize)]
+        struct EventRow {
+            id: RecordId,
+            title: String,
+            sequence: i64,
+        }
+        #[derive(Deserialize)]
+        struct ThreadRow {
+            id: RecordId,
+            resolve_by: i64,
+        }
+        #[derive(Deserialize)]
+        struct FactRow {
+            id: RecordId,
+            from_sequence: Option<i64>,
+            until_sequence: Option<i64>,
+        }
+        #[derive(Deserialize)]
+        struct MemberRow {
+            id: RecordId,
+            sequence_position: f64,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, title, sequence, created_at FROM event \
+                 ORDER BY sequence ASC, created_at ASC; \
+                 SELECT id, resolve_by FROM thread WHERE resolve_by != NONE; \
+                 SELECT id, scope.temporal.from_sequence AS from_sequence, \
+                 scope.temporal.until_sequence AS until_sequence FROM universe_fact \
+                 WHERE scope.temporal.from_sequence != NONE \
+                 OR scope.temporal.until_sequence != NONE; \
+                 SELECT id, sequence_position FROM belongs_to_phase \
+                 WHERE sequence_position != NONE",
+            )
+            .await?;
+        let events: Vec<EventRow> = response.take(0)?;
+        let threads: Vec<ThreadRow> = response.take(1)?;
+        let facts: Vec<FactRow> = response.take(2)?;
+        let members: Vec<MemberRow> = response.take(3)?;
+        let phases = list_phases(&self.db).await?;
+
+        let old: Vec<i64> = events.iter().map(|e| e.sequence).collect();
+        let new = renumber(old.len(), spacing);
+        let map = SequenceMap::new(
+            &old.iter()
+                .copied()
+                .zip(new.iter().copied())
+                .collect::<Vec<_>>(),
+        );
+        // Phase positions are normalised by the largest sequence
+        let old_max = old.iter().copied().max().unwrap_or(1).max(1) as f64;
+        let new_max = new.iter().copied().max().unwrap_or(1).max(1) as f64;
+
+        let mut statements = String::from("BEGIN TRANSACTION;\n");
+        let mut binds: Vec<(String, serde_json::Value)> = Vec::new();
+        let mut records: Vec<(String, RecordId)> = Vec::new();
+        let mut push =
+            |statement: String, record: RecordId, values: Vec<(String, serde_json::Value)>| {
+                let name = format!("r{}", records.len());
+                statements.push_str(&statement.replace("$record", &format!("${}", name)));
+                statements.push('\n');
+                records.push((name, record));
+                binds.extend(values);
+            };
+
+        let mut changes = Vec::new();
+        for (i, (event, &to)) in events.iter().zip(&new).enumerate() {
+            if event.sequence == to {
+                continue;
+            }
+            changes.push(SequenceChange {
+                event_id: event.id.to_string(),
+                title: event.title.clone(),
+                from: event.sequence,
+                to,
+            });
+            push(
+                format!("UPDATE $record SET sequence = $event_{i} RETURN NONE;"),
+                event.id.clone(),
+                vec![(format!("event_{i}"), to.into())],
+            );
+        }
+
+        let mut thread_count = 0;
+        for (i, thread) in threads.iter().enumerate() {
+            let to = map.map(thread.resolve_by);
+            if to == thread.resolve_by {
+                continue;
+            }
+            thread_count += 1;
+            push(
+                format!("UPDATE $record SET resolve_by = $thread_{i} RETURN NONE;"),
+                thread.id.clone(),
+                vec![(format!("thread_{i}"), to.into())],
+            );
+        }
+
+        let mut fact_count = 0;
+        for (i, fact) in facts.iter().enumerate() {
+            let from = fact.from_sequence.map(|s| map.map(s));
+            let until = fact.until_sequence.map(|s| map.map(s));
+            if (from, until) == (fact.from_sequence, fact.until_sequence) {
+                continue;
+            }
+            fact_count += 1;
+            let mut sets = Vec::new();
+            let mut values: Vec<(String, serde_json::Value)> = Vec::new();
+            if let Some(from) = from {
+                sets.push(format!("scope.temporal.from_sequence = $fact_from_{i}"));
+                values.push((format!("fact_from_{i}"), from.into()));
+            }
+            if let Some(until) = until {
+                sets.push(format!("scope.temporal.until_sequence = $fact_until_{i}"));
+                values.push((format!("fact_until_{i}"), until.into()));
+            }
+            push(
+                format!("UPDATE $record SET {} RETURN NONE;", sets.join(", ")),
+                fact.id.clone(),
+                values,
+            );
+        }
+
+        let mut phase_count = 0;
+        for (i, phase) in phases.iter().enumerate() {
+            let Some(range) = phase.sequence_range_min.zip(phase.sequence_range_max) else {
+                continue;
+            };
+            let to = (map.map(range.0), map.map(range.1));
+            if to == range {
+                continue;
+            }
+            phase_count += 1;
+            let mut sets = vec![
+                format!("sequence_range_min = $phase_min_{i}"),
+                format!("sequence_range_max = $phase_max_{i}"),
+                format!("label = $phase_label_{i}"),
+            ];
+            let mut values: Vec<(String, serde_json::Value)> = vec![
+                (format!("phase_min_{i}"), to.0.into()),
+                (format!("phase_max_{i}"), to.1.into()),
+                (
+                    format!("phase_label_{i}"),
+                    relabel(&phase.label, range, to).into(),
+                ),
+            ];
+            if let Some(auto_label) = &phase.auto_label {
+                sets.push(format!("auto_label = $phase_auto_{i}"));
+                values.push((
+                    format!("phase_auto_{i}"),
+                    relabel(auto_label, range, to).into(),
+                ));
+            }
+            push(
+                format!("UPDATE $record SET {} RETURN NONE;", sets.join(", ")),
+                phase.id.clone(),
+                values,
+            );
+        }
+
+        let mut member_count = 0;
+        for (i, member) in members.iter().enumerate() {
+            let to = map.map_f64(member.sequence_position * old_max) / new_max;
+            if (to - member.sequence_position).abs() < 1e-9 {
+                continue;
+            }
+            member_count += 1;
+            push(
+                format!("UPDATE $record SET sequence_position = $member_{i} RETURN NONE;"),
+                member.id.clone(),
+                vec![(format!("member_{i}"), to.into())],
+            );
+        }
+        statements.push_str("COMMIT TRANSACTION;");
+
+        let mut report = RenumberReport {
+            spacing,
+            changes,
+            threads: thread_count,
+            facts: fact_count,
+            phases: phase_count,
+            phase_members: member_count,
+            applied: false,
+        };
+        if dry_run || report.is_empty() {
+            return Ok(report);
+        }
+
+        let mut query = self.db.query(statements);
+        for (name, record) in records {
+            query = query.bind((name, record));
+        }
+        for (name, value) in binds {
+            query = query.bind((name, value));
+        }
+        query.await?.check()?;
+        report.applied = true;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_map_interpolates_between_events() {
+        let old = [5, 7, 300];
+        let new = renumber(old.len(), 10);
+        assert_eq!(new, vec![10, 20, 30]);
+        let map = SequenceMap::new(&old.into_iter().zip(new).collect::<Vec<_>>());
+
+        assert_eq!(map.map(7), 20);
+        // Halfway between 7 and 300 lands halfway between 20 and 30
+        assert_eq!(map.map(153), 25);
+        assert_eq!(map.map(3), 8);
+        assert_eq!(map.map(310), 40);
+
+        // A shared sequence maps to its first event
+        let tied = SequenceMap::new(&[(4, 10), (4, 20), (9, 30)]);
+        assert_eq!(tied.map(4), 10);
+        assert_eq!(SequenceMap::default().map(12), 12);
+    }
+}
@@ -0,0 +1,838 @@
+//! Scene reordering checks and bulk reordering.
+//!
+//! Scenes are read in timeline order. Dependencies come from explicit
+//! `requires` edges and are inferred from knowledge premises: when a
+//! deduction made in one scene rests on something learned in another, the
+//! deduction's scene requires the other. Moving a scene reports every
+//! dependency that would end up with the dependent at or before its
+//! prerequisite.
+//!
+//! Reordering in bulk takes the new reading order as a list of scene and
+//! event IDs and rewrites event sequences to match. A scene's place comes
+//! from its event, so the scenes of one event move together. The events
+//! being moved trade sequence numbers among themselves, which keeps the
+//! gaps between positions as they were; unlisted events stay put.
+//! Event-anchored arc snapshots and saved phases are brought in line with
+//! the new sequences.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
+use crate::models::phase::list_phases;
+use crate::models::requires;
+use crate::utils::math::cosine_similarity;
+use crate::NarraError;
+
+/// A scene in reading order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderedScene {
+    pub id: String,
+    pub title: String,
+}
+
+/// `scene` must come after `prerequisite`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Dependency {
+    pub scene: String,
+    pub prerequisite: String,
+    /// "explicit" or "inferred"
+    pub source: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenDependency {
+    pub scene: OrderedScene,
+    pub prerequisite: OrderedScene,
+    pub source: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReorderReport {
+    pub moved: OrderedScene,
+    /// 1-based positions in reading order
+    pub from: usize,
+    pub to: usize,
+    /// Dependencies touching the moved scene
+    pub dependencies: usize,
+    pub broken: Vec<BrokenDependency>,
+}
+
+/// Move `scene_id` to 1-based position `to` and list the dependencies that
+/// no longer hold.
+pub fn check_move(
+    order: &[OrderedScene],
+    dependencies: &[Dependency],
+    scene_id: &str,
+    to: usize,
+) -> Result<ReorderReport, NarraError> {
+    let from = order
+        .iter()
+        .position(|s| s.id == scene_id)
+        .ok_or_else(|| NarraError::NotFound {
+            entity_type: "scene".to_string(),
+            id: scene_id.to_string(),
+        })?;
+    if to == 0 || to > order.len() {
+        return Err(NarraError::Validation(format!(
+            "Position must be between 1 and {}",
+            order.len()
+        )));
+    }
+
+    let mut moved = order.to_vec();
+    let scene = moved.remove(from);
+    moved.insert(to - 1, scene.clone());
+
+    let touching: Vec<&Dependency> = dependencies
+        .iter()
+        .filter(|d| d.scene == scene_id || d.prerequisite == scene_id)
+        .collect();
+    let broken = broken_dependencies(&moved, touching.iter().copied());
+
+    Ok(ReorderReport {
+        moved: scene,
+        from: from + 1,
+        to,
+        dependencies: touching.len(),
+        broken,
+    })
+}
+
+/// Dependencies that `order` puts with the dependent at or before its
+/// prerequisite. Dependencies on scenes not in `order` are skipped.
+pub fn broken_dependencies<'a>(
+    order: &[OrderedScene],
+    dependencies: impl IntoIterator<Item = &'a Dependency>,
+) -> Vec<BrokenDependency> {
+    let position: HashMap<&str, usize> = order
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.id.as_str(), i))
+        .collect();
+    let lookup = |id: &str| order[position[id]].clone();
+    dependencies
+        .into_iter()
+        .filter(|d| {
+            match (
+                position.get(d.scene.as_str()),
+                position.get(d.prerequisite.as_str()),
+            ) {
+                (Some(s), Some(p)) => s <= p,
+                _ => false,
+            }
+        })
+        .map(|d| BrokenDependency {
+            scene: lookup(&d.scene),
+            prerequisite: lookup(&d.prerequisite),
+            source: d.source.clone(),
+            reason: d.reason.clone(),
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Bulk reordering
+// ---------------------------------------------------------------------------
+
+/// An event on the timeline with its scenes in reading order.
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    pub id: String,
+    pub title: String,
+    pub sequence: i64,
+    /// "backstory", "on_page" or "planned"
+    pub narrative_status: String,
+    pub scenes: Vec<OrderedScene>,
+}
+
+impl TimelineEvent {
+    /// The title, marked when the event is backstory or planned.
+    fn label(&self) -> String {
+        match self.narrative_status.as_str() {
+            "on_page" => self.title.clone(),
+            status => format!("{} ({})", self.title, status),
+        }
+    }
+}
+
+/// An event given a new sequence number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceChange {
+    pub event_id: String,
+    pub title: String,
+    pub from: i64,
+    pub to: i64,
+}
+
+/// New event sequences for a reading order.
+#[derive(Debug, Clone, Serialize)]
+pub struct SequencePlan {
+    pub changes: Vec<SequenceChange>,
+    /// Scenes in the new reading order
+    pub reading_order: Vec<OrderedScene>,
+    /// Moved events left sharing a sequence number with a neighbour, whose
+    /// relative order is then down to creation time
+    pub ties: usize,
+}
+
+/// A planned reorder with the dependencies it breaks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReorderPlan {
+    pub changes: Vec<SequenceChange>,
+    pub broken: Vec<BrokenDependency>,
+    pub ties: usize,
+}
+
+/// What applying a reorder touched besides event sequences.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReorderOutcome {
+    pub events: usize,
+    /// Event-anchored arc snapshots whose drift was recomputed
+    pub arc_snapshots: usize,
+    /// Saved phases whose sequence range was updated
+    pub phases: usize,
+}
+
+/// The order file: one line per scene, events without scenes on a line of
+/// their own, each line starting with the ID.
+pub fn format_order(timeline: &[TimelineEvent]) -> String {
+    let mut out = String::from(
+        "# Reading order. Move lines to reorder, then save and quit.\n\
+         # Scenes of the same event move together; keep their lines adjacent.\n\
+         # Lines starting with # are ignored. Delete every line to cancel.\n",
+    );
+    for event in timeline {
+        if event.scenes.is_empty() {
+            out.push_str(&format!(
+                "{:<28} [{}] {} (no scenes)\n",
+                event.id,
+                event.sequence,
+                event.label()
+            ));
+        }
+        for scene in &event.scenes {
+            out.push_str(&format!(
+                "{:<28} [{}] {} / {}\n",
+                scene.id,
+                event.sequence,
+                event.label(),
+                scene.title
+            ));
+        }
+    }
+    out
+}
+
+/// IDs from an order file: the first word of every line that isn't blank or
+/// a `#` comment.
+pub fn parse_order(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| l.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Work out event sequences for the reading order given by `entries`
+/// (scene and event IDs). The listed events take over the sequence numbers
+/// they hold now, in the new order.
+pub fn plan_sequences(
+    timeline: &[TimelineEvent],
+    entries: &[String],
+) -> Result<SequencePlan, NarraError> {
+    let mut owner: HashMap<&str, usize> = HashMap::new();
+    for (index, event) in timeline.iter().enumerate() {
+        owner.insert(event.id.as_str(), index);
+        for scene in &event.scenes {
+            owner.insert(scene.id.as_str(), index);
+        }
+    }
+
+    let mut order: Vec<usize> = Vec::new();
+    let mut seen_entries: HashSet<&str> = HashSet::new();
+    let mut listed_scenes: HashMap<usize, Vec<&str>> = HashMap::new();
+    for entry in entries {
+        let index = *owner
+            .get(entry.as_str())
+            .ok_or_else(|| NarraError::NotFound {
+                entity_type: if entry.starts_with("event:") {
+                    "event".to_string()
+                } else {
+                    "scene".to_string()
+                },
+                id: entry.clone(),
+            })?;
+        if !seen_entries.insert(entry.as_str()) {
+            return Err(NarraError::Validation(format!(
+                "'{}' is listed more than once",
+                entry
+            )));
+        }
+        if entry.starts_with("scene:") {
+            listed_scenes.entry(index).or_default().push(entry.as_str());
+        }
+        if order.last() == Some(&index) {
+            continue;
+        }
+        if order.contains(&index) {
+            return Err(NarraError::Validation(format!(
+                "'{}' is separated from the other scenes of '{}'; scenes of one event move together",
+                entry, timeline[index].title
+            )));
+        }
+        order.push(index);
+    }
+
+    // Within an event scenes follow creation time, which can't be changed
+    for (index, listed) in &listed_scenes {
+        let current: Vec<&str> = timeline[*index]
+            .scenes
+            .iter()
+            .map(|s| s.id.as_str())
+            .filter(|id| listed.contains(id))
+            .collect();
+        if &current != listed {
+            return Err(NarraError::Validation(format!(
+                "Scenes of '{}' must keep their order ({}); move a scene to another event to reorder it",
+                timeline[*index].title,
+                current.join(", ")
+            )));
+        }
+    }
+
+    // Listed events trade their current slots; the rest keep theirs
+    let mut slots: Vec<usize> = order.clone();
+    slots.sort_unstable();
+    let mut sequences: Vec<i64> = timeline.iter().map(|e| e.sequence).collect();
+    let mut changes = Vec::new();
+    for (&slot, &index) in slots.iter().zip(&order) {
+        let event = &timeline[index];
+        let to = timeline[slot].sequence;
+        sequences[index] = to;
+        if to != event.sequence {
+            changes.push(SequenceChange {
+                event_id: event.id.clone(),
+                title: event.title.clone(),
+                from: event.sequence,
+                to,
+            });
+        }
+    }
+
+    // Unlisted events keep their slots between the moved ones
+    let mut placement: Vec<usize> = (0..timeline.len()).collect();
+    for (&slot, &index) in slots.iter().zip(&order) {
+        placement[slot] = index;
+    }
+    let ties = (1..placement.len())
+        .filter(|&slot| {
+            let (before, after) = (placement[slot - 1], placement[slot]);
+            let moved = before != slot - 1 || after != slot;
+            moved && sequences[before] == sequences[after]
+        })
+        .count();
+    let reading_order = placement
+        .into_iter()
+        .flat_map(|index| timeline[index].scenes.iter().cloned())
+        .collect();
+
+    Ok(SequencePlan {
+        changes,
+        reading_order,
+        ties,
+    })
+}
+
+/// The `(seq ...)` suffix generated phase labels end with.
+fn sequence_suffix((min, max): (i64, i64)) -> String {
+    if min == max {
+        format!("(seq {})", min)
+    } else {
+        format!("(seq {}-{})", min, max)
+    }
+}
+
+/// `label` with its generated sequence suffix moved from `old` to `new`.
+/// Labels without the suffix are returned unchanged.
+pub fn relabel(label: &str, old: (i64, i64), new: (i64, i64)) -> String {
+    match label.strip_suffix(&sequence_suffix(old)) {
+        Some(base) => format!("{}{}", base, sequence_suffix(new)),
+        None => label.to_string(),
+    }
+}
+
+/// Drift of each snapshot from the one before it, in the order given. The
+/// first has nothing to compare with and is left out.
+pub fn chained_drift(embeddings: &[&[f32]]) -> Vec<f32> {
+    embeddings
+        .windows(2)
+        .map(|pair| 1.0 - cosine_similarity(pair[0], pair[1]))
+        .collect()
+}
+
+pub struct ReorderService {
+    db: Arc<NarraDb>,
+}
+
+impl ReorderService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Scenes in reading order.
+    pub async fn scene_order(&self) -> Result<Vec<OrderedScene>, NarraError> {
+        #[derive(Deserialize)]
+        struct Row {
+            id: RecordId,
+            title: String,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, title, event.sequence AS sequence, created_at FROM scene \
+                 ORDER BY sequence ASC, created_at ASC",
+            )
+            .await?;
+        let rows: Vec<Row> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|r| OrderedScene {
+                id: r.id.to_string(),
+                title: r.title,
+            })
+            .collect())
+    }
+
+    /// Explicit `requires` edges plus dependencies inferred from premises.
+    pub async fn dependencies(&self) -> Result<Vec<Dependency>, NarraError> {
+        #[derive(Deserialize)]
+        struct PremiseRow {
+            scenes: Vec<RecordId>,
+            premise_scenes: Vec<RecordId>,
+            fact: Option<String>,
+        }
+
+        let mut dependencies: Vec<Dependency> = requires::list_requirements(&self.db)
+            .await?
+            .into_iter()
+            .map(|edge| Dependency {
+                scene: edge.scene.to_string(),
+                prerequisite: edge.prerequisite.to_string(),
+                source: "explicit".to_string(),
+                reason: edge.note,
+            })
+            .collect();
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT \
+                 (SELECT VALUE id FROM scene WHERE event = $parent.event) AS scenes, \
+                 array::flatten((SELECT VALUE (SELECT VALUE id FROM scene WHERE event = $parent.event) \
+                 FROM $parent.premises)) AS premise_scenes, \
+                 out.fact AS fact \
+                 FROM knows WHERE event != NONE AND premises != NONE",
+            )
+            .await?;
+        let rows: Vec<PremiseRow> = response.take(0)?;
+
+        let mut seen: HashSet<(String, String)> = dependencies
+            .iter()
+            .map(|d| (d.scene.clone(), d.prerequisite.clone()))
+            .collect();
+        for row in rows {
+            for scene in &row.scenes {
+                for prerequisite in &row.premise_scenes {
+                    let pair = (scene.to_string(), prerequisite.to_string());
+                    if pair.0 == pair.1 || !seen.insert(pair.clone()) {
+                        continue;
+                    }
+                    dependencies.push(Dependency {
+                        scene: pair.0,
+                        prerequisite: pair.1,
+                        source: "inferred".to_string(),
+                        reason: row
+                            .fact
+                            .as_ref()
+                            .map(|f| format!("deduction rests on: {}", f)),
+                    });
+                }
+            }
+        }
+        Ok(dependencies)
+    }
+
+    pub async fn check_move(&self, scene_id: &str, to: usize) -> Result<ReorderReport, NarraError> {
+        let order = self.scene_order().await?;
+        let dependencies = self.dependencies().await?;
+        check_move(&order, &dependencies, scene_id, to)
+    }
+
+    /// Events in timeline order, each with its scenes.
+    pub async fn timeline(&self) -> Result<Vec<TimelineEvent>, NarraError> {
+        #[derive(Deserialize)]
+        struct EventRow {
+            id: RecordId,
+            title: String,
+            sequence: i64,
+            narrative_status: Option<String>,
+        }
+        #[derive(Deserialize)]
+        struct SceneRow {
+            id: RecordId,
+            title: String,
+            event: RecordId,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, title, sequence, narrative_status, created_at FROM event \
+                 ORDER BY sequence ASC, created_at ASC; \
+                 SELECT id, title, event, created_at FROM scene ORDER BY created_at ASC",
+            )
+            .await?;
+        let events: Vec<EventRow> = response.take(0)?;
+        let scenes: Vec<SceneRow> = response.take(1)?;
+
+        let mut by_event: HashMap<String, Vec<OrderedScene>> = HashMap::new();
+        for scene in scenes {
+            by_event
+                .entry(scene.event.to_string())
+                .or_default()
+                .push(OrderedScene {
+                    id: scene.id.to_string(),
+                    title: scene.title,
+                });
+        }
+        Ok(events
+            .into_iter()
+            .map(|e| {
+                let id = e.id.to_string();
+                TimelineEvent {
+                    scenes: by_event.remove(&id).unwrap_or_default(),
+                    id,
+                    title: e.title,
+                    sequence: e.sequence,
+                    narrative_status: e.narrative_status.unwrap_or_else(|| "on_page".to_string()),
+                }
+            })
+            .collect())
+    }
+
+    /// Plan the reading order given by `entries` (scene and event IDs).
+    pub async fn plan(&self, entries: &[String]) -> Result<ReorderPlan, NarraError> {
+        let timeline = self.timeline().await?;
+        let plan = plan_sequences(&timeline, entries)?;
+        let dependencies = self.dependencies().await?;
+        Ok(ReorderPlan {
+            broken: broken_dependencies(&plan.reading_order, &dependencies),
+            changes: plan.changes,
+            ties: plan.ties,
+        })
+    }
+
+    /// Write the planned sequences, then recompute drift for event-anchored
+    /// arc snapshots and sequence data for saved phases.
+    pub async fn apply(&self, plan: &ReorderPlan) -> Result<ReorderOutcome, NarraError> {
+        if plan.changes.is_empty() {
+            return Ok(ReorderOutcome::default());
+        }
+        let mut statements = String::from("BEGIN TRANSACTION;\n");
+        for i in 0..plan.changes.len() {
+            statements.push_str(&format!(
+                "UPDATE $event_{i} SET sequence = $sequence_{i} RETURN NONE;\n"
+            ));
+        }
+        statements.push_str("COMMIT TRANSACTION;");
+        let mut query = self.db.query(statements);
+        let mut events = Vec::with_capacity(plan.changes.len());
+        for (i, change) in plan.changes.iter().enumerate() {
+            let event = record_id("event", &change.event_id)?;
+            events.push(event.clone());
+            query = query
+                .bind((format!("event_{i}"), event))
+                .bind((format!("sequence_{i}"), change.to));
+        }
+        query.await?.check()?;
+
+        Ok(ReorderOutcome {
+            events: plan.changes.len(),
+            arc_snapshots: self.refresh_arc_snapshots(&events).await?,
+            phases: self.refresh_phases(&events).await?,
+        })
+    }
+
+    /// Recompute drift along the event order for entities with snapshots
+    /// anchored to the moved events.
+    async fn refresh_arc_snapshots(&self, events: &[RecordId]) -> Result<usize, NarraError> {
+        #[derive(Deserialize)]
+        struct SnapshotRow {
+            id: RecordId,
+            entity_id: RecordId,
+            facet: Option<String>,
+            embedding: Vec<f32>,
+            delta_magnitude: Option<f32>,
+            sequence: Option<i64>,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, entity_id, facet, embedding, delta_magnitude, \
+                 event_id.sequence AS sequence, created_at FROM arc_snapshot \
+                 WHERE event_id != NONE AND entity_id IN \
+                 (SELECT VALUE entity_id FROM arc_snapshot WHERE event_id IN $events) \
+                 ORDER BY created_at ASC",
+            )
+            .bind(("events", events.to_vec()))
+            .await?;
+        let rows: Vec<SnapshotRow> = response.take(0)?;
+
+        let mut chains: HashMap<(String, Option<String>), Vec<SnapshotRow>> = HashMap::new();
+        for row in rows {
+            chains
+                .entry((row.entity_id.to_string(), row.facet.clone()))
+                .or_default()
+                .push(row);
+        }
+
+        let mut updated = 0;
+        for chain in chains.values_mut() {
+            // Stable, so snapshots at one event keep their creation order
+            chain.sort_by_key(|r| r.sequence.unwrap_or(i64::MAX));
+            let embeddings: Vec<&[f32]> = chain.iter().map(|r| r.embedding.as_slice()).collect();
+            for (row, drift) in chain.iter().skip(1).zip(chained_drift(&embeddings)) {
+                if row
+                    .delta_magnitude
+                    .is_some_and(|d| (d - drift).abs() < 1e-6)
+                {
+                    continue;
+                }
+                self.db
+                    .query("UPDATE $id SET delta_magnitude = $drift RETURN NONE")
+                    .bind(("id", row.id.clone()))
+                    .bind(("drift", drift))
+                    .await?
+                    .check()?;
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Update sequence positions of event and scene members of saved phases,
+    /// then the sequence range (and generated label) of phases they are in.
+    async fn refresh_phases(&self, events: &[RecordId]) -> Result<usize, NarraError> {
+        #[derive(Deserialize)]
+        struct MemberRow {
+            phase: RecordId,
+            event_sequence: Option<i64>,
+            scene_sequence: Option<i64>,
+            moved: bool,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "LET $max = math::max((SELECT VALUE sequence FROM event)) ?? 1; \
+                 UPDATE belongs_to_phase SET sequence_position = <float> in.sequence / $max \
+                 WHERE entity_type = 'event' AND in IN $events RETURN NONE; \
+                 UPDATE belongs_to_phase SET sequence_position = <float> in.event.sequence / $max \
+                 WHERE entity_type = 'scene' AND in.event IN $events RETURN NONE; \
+                 SELECT out AS phase, in.sequence AS event_sequence, \
+                 in.event.sequence AS scene_sequence, \
+                 (in IN $events OR in.event IN $events) AS moved \
+                 FROM belongs_to_phase WHERE entity_type IN ['event', 'scene']",
+            )
+            .bind(("events", events.to_vec()))
+            .await?;
+        let members: Vec<MemberRow> = response.take(3)?;
+
+        let mut ranges: HashMap<String, (i64, i64)> = HashMap::new();
+        let mut affected: HashSet<String> = HashSet::new();
+        for member in members {
+            let Some(sequence) = member.event_sequence.or(member.scene_sequence) else {
+                continue;
+            };
+            let phase = member.phase.key().to_string();
+            if member.moved {
+                affected.insert(phase.clone());
+            }
+            let range = ranges.entry(phase).or_insert((sequence, sequence));
+            range.0 = range.0.min(sequence);
+            range.1 = range.1.max(sequence);
+        }
+
+        let mut updated = 0;
+        for phase in list_phases(&self.db).await? {
+            let key = phase.id.key().to_string();
+            let Some(&range) = ranges.get(&key).filter(|_| affected.contains(&key)) else {
+                continue;
+            };
+            let old = phase.sequence_range_min.zip(phase.sequence_range_max);
+            if old == Some(range) {
+                continue;
+            }
+            let relabelled = |label: &str| match old {
+                Some(old) => relabel(label, old, range),
+                None => label.to_string(),
+            };
+            self.db
+                .query(
+                    "UPDATE $phase SET sequence_range_min = $min, sequence_range_max = $max, \
+                     label = $label, auto_label = $auto_label RETURN NONE",
+                )
+                .bind(("phase", phase.id.clone()))
+                .bind(("min", range.0))
+                .bind(("max", range.1))
+                .bind(("label", relabelled(&phase.label)))
+                .bind(("auto_label", phase.auto_label.as_deref().map(relabelled)))
+                .await?
+                .check()?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(id: &str) -> OrderedScene {
+        OrderedScene {
+            id: id.to_string(),
+            title: id.to_string(),
+        }
+    }
+
+    fn dep(scene: &str, prerequisite: &str, source: &str) -> Dependency {
+        Dependency {
+            scene: scene.to_string(),
+            prerequisite: prerequisite.to_string(),
+            source: source.to_string(),
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_moving_a_scene_reports_broken_dependencies() {
+        let order: Vec<OrderedScene> = ["scene:a", "scene:b", "scene:c", "scene:d"]
+            .into_iter()
+            .map(scene)
+            .collect();
+        let deps = vec![
+            dep("scene:c", "scene:a", "explicit"),
+            dep("scene:d", "scene:c", "inferred"),
+            dep("scene:b", "scene:a", "explicit"),
+        ];
+
+        // c to the front: it now precedes a; d still follows c
+        let report = check_move(&order, &deps, "scene:c", 1).unwrap();
+        assert_eq!((report.from, report.to), (3, 1));
+        assert_eq!(report.dependencies, 2);
+        assert_eq!(report.broken.len(), 1);
+        assert_eq!(report.broken[0].prerequisite.id, "scene:a");
+
+        // c to the end: d now precedes it
+        let report = check_move(&order, &deps, "scene:c", 4).unwrap();
+        assert_eq!(report.broken.len(), 1);
+        assert_eq!(report.broken[0].scene.id, "scene:d");
+        assert_eq!(report.broken[0].source, "inferred");
+
+        assert!(check_move(&order, &deps, "scene:b", 3)
+            .unwrap()
+            .broken
+            .is_empty());
+        assert!(check_move(&order, &deps, "scene:b", 9).is_err());
+        assert!(check_move(&order, &deps, "scene:z", 1).is_err());
+    }
+
+    fn event(id: &str, sequence: i64, scenes: &[&str]) -> TimelineEvent {
+        TimelineEvent {
+            id: id.to_string(),
+            title: id.to_string(),
+            sequence,
+            narrative_status: "on_page".to_string(),
+            scenes: scenes.iter().map(|s| scene(s)).collect(),
+        }
+    }
+
+    fn entries(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_plan_sequences_swaps_slots_and_keeps_unlisted_events() {
+        let timeline = vec![
+            event("event:a", 10, &["scene:a1", "scene:a2"]),
+            event("event:gap", 15, &[]),
+            event("event:b", 20, &["scene:b1"]),
+            event("event:c", 40, &["scene:c1"]),
+        ];
+
+        let plan = plan_sequences(
+            &timeline,
+            &entries(&["scene:c1", "scene:a1", "scene:a2", "scene:b1"]),
+        )
+        .unwrap();
+        let moves: Vec<(&str, i64, i64)> = plan
+            .changes
+            .iter()
+            .map(|c| (c.event_id.as_str(), c.from, c.to))
+            .collect();
+        assert_eq!(
+            moves,
+            vec![
+                ("event:c", 40, 10),
+                ("event:a", 10, 20),
+                ("event:b", 20, 40)
+            ]
+        );
+        let order: Vec<&str> = plan.reading_order.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(order, vec!["scene:c1", "scene:a1", "scene:a2", "scene:b1"]);
+        assert_eq!(plan.ties, 0);
+
+        // An event listed on its own moves too
+        let plan = plan_sequences(&timeline, &entries(&["event:gap", "scene:a1"])).unwrap();
+        assert_eq!(plan.changes.len(), 2);
+        assert_eq!(plan.changes[0].to, 10);
+
+        assert!(
+            plan_sequences(&timeline, &entries(&["scene:a1", "scene:b1", "scene:a2"])).is_err()
+        );
+        assert!(plan_sequences(&timeline, &entries(&["scene:a2", "scene:a1"])).is_err());
+        assert!(plan_sequences(&timeline, &entries(&["scene:b1", "scene:b1"])).is_err());
+        assert!(plan_sequences(&timeline, &entries(&["scene:zz"])).is_err());
+    }
+
+    #[test]
+    fn test_order_file_round_trip_and_relabel() {
+        let mut timeline = vec![event("event:a", 1, &["scene:a1"]), event("event:b", 2, &[])];
+        timeline[0].narrative_status = "backstory".to_string();
+        let text = format_order(&timeline);
+        assert!(text.contains("[1] event:a (backstory) / scene:a1"));
+        assert!(text.contains("[2] event:b (no scenes)"));
+        assert_eq!(parse_order(&text), entries(&["scene:a1", "event:b"]));
+
+        assert_eq!(
+            relabel("Alice, Bob (seq 3-9)", (3, 9), (2, 9)),
+            "Alice, Bob (seq 2-9)"
+        );
+        assert_eq!(relabel("Act II", (3, 9), (2, 9)), "Act II");
+        assert_eq!(
+            relabel("Unnamed Phase (seq 4)", (4, 4), (5, 7)),
+            "Unnamed Phase (seq 5-7)"
+        );
+    }
+}
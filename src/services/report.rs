@@ -0,0 +1,276 @@
+//! Standalone HTML reports for composite analyses.
+//!
+//! A report is one self-contained file: the `<name>-html` output template
+//! (see [`templates`](crate::services::templates)) rendered with the
+//! analysis data under `report`, the stylesheet under `style`, and inline
+//! SVG bar charts under `charts`. Nothing is loaded from the network, so a
+//! report opens the same way when archived or mailed.
+
+use serde::Serialize;
+
+use crate::services::composite::{CharacterDossier, SituationReport};
+use crate::services::templates::Templates;
+use crate::NarraError;
+
+const STYLE: &str = include_str!("templates/report.css");
+
+const BAR_HEIGHT: usize = 22;
+const LABEL_WIDTH: usize = 220;
+const BAR_WIDTH: usize = 360;
+
+/// A horizontal bar chart.
+#[derive(Debug, Clone)]
+pub struct Chart {
+    pub title: String,
+    pub bars: Vec<(String, f64)>,
+    /// Value of a full-width bar; the largest value when None
+    pub max: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct RenderedChart {
+    title: String,
+    svg: String,
+}
+
+#[derive(Serialize)]
+struct ReportContext<'a, T: Serialize> {
+    title: &'a str,
+    generated_at: String,
+    style: &'static str,
+    charts: Vec<RenderedChart>,
+    report: &'a T,
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+impl Chart {
+    pub fn new(title: impl Into<String>, bars: Vec<(String, f64)>) -> Self {
+        Self {
+            title: title.into(),
+            bars,
+            max: None,
+        }
+    }
+
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// The chart as an inline `<svg>` element.
+    pub fn svg(&self) -> String {
+        let max = self
+            .max
+            .unwrap_or_else(|| self.bars.iter().map(|(_, v)| *v).fold(0.0, f64::max));
+        let width = LABEL_WIDTH + BAR_WIDTH + 60;
+        let height = self.bars.len() * BAR_HEIGHT + 4;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"chart\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" role=\"img\" aria-label=\"{t}\">",
+            w = width,
+            h = height,
+            t = escape(&self.title)
+        );
+        for (i, (label, value)) in self.bars.iter().enumerate() {
+            let y = i * BAR_HEIGHT + 2;
+            let length = if max > 0.0 {
+                (value.max(0.0) / max * BAR_WIDTH as f64).round() as usize
+            } else {
+                0
+            };
+            svg.push_str(&format!(
+                "<text x=\"{lx}\" y=\"{ty}\" text-anchor=\"end\">{label}</text>\
+                 <rect x=\"{bx}\" y=\"{y}\" width=\"{len}\" height=\"{bh}\"/>\
+                 <text x=\"{vx}\" y=\"{ty}\">{value}</text>",
+                lx = LABEL_WIDTH - 8,
+                ty = y + BAR_HEIGHT / 2 + 4,
+                label = escape(label),
+                bx = LABEL_WIDTH,
+                y = y,
+                len = length,
+                bh = BAR_HEIGHT - 6,
+                vx = LABEL_WIDTH + length + 6,
+                value = format_value(*value),
+            ));
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+/// Render the `<name>-html` template as a complete HTML document.
+pub fn render_html<T: Serialize>(
+    templates: &Templates,
+    name: &str,
+    title: &str,
+    report: &T,
+    charts: &[Chart],
+) -> Result<String, NarraError> {
+    let context = ReportContext {
+        title,
+        generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        style: STYLE,
+        charts: charts
+            .iter()
+            .filter(|c| !c.bars.is_empty())
+            .map(|c| RenderedChart {
+                title: c.title.clone(),
+                svg: c.svg(),
+            })
+            .collect(),
+        report,
+    };
+    templates.render(&format!("{}-html", name), &context)
+}
+
+/// Tension between perception pairs, severity of structural tensions, and
+/// recent arc drift.
+pub fn situation_charts(report: &SituationReport) -> Vec<Chart> {
+    vec![
+        Chart::new(
+            "High-tension pairs",
+            report
+                .high_tension_pairs
+                .iter()
+                .map(|t| {
+                    (
+                        format!("{} → {}", t.observer, t.target),
+                        t.tension_level as f64,
+                    )
+                })
+                .collect(),
+        )
+        .with_max(10.0),
+        Chart::new(
+            "Narrative tension severity",
+            report
+                .narrative_tensions
+                .iter()
+                .map(|t| {
+                    (
+                        format!("{} / {}", t.character_a_name, t.character_b_name),
+                        t.severity as f64,
+                    )
+                })
+                .collect(),
+        )
+        .with_max(1.0),
+        Chart::new(
+            "Recent arc drift",
+            report
+                .character_arc_summaries
+                .iter()
+                .filter_map(|a| Some((a.character_name.clone(), a.recent_drift? as f64)))
+                .collect(),
+        ),
+    ]
+}
+
+/// Knowledge by certainty and tension in each relationship.
+pub fn dossier_charts(dossier: &CharacterDossier) -> Vec<Chart> {
+    let k = &dossier.knowledge_inventory;
+    let knowledge = [
+        ("Knows", k.knows),
+        ("Suspects", k.suspects),
+        ("Believes wrongly", k.believes_wrongly),
+        ("Uncertain", k.uncertain),
+        ("Other", k.other),
+    ]
+    .into_iter()
+    .filter(|(_, n)| *n > 0)
+    .map(|(label, n)| (label.to_string(), n as f64))
+    .collect();
+    vec![
+        Chart::new("Knowledge", knowledge),
+        Chart::new(
+            "Relationship tension",
+            dossier
+                .relationship_map
+                .iter()
+                .filter_map(|r| Some((r.other_name.clone(), r.tension? as f64)))
+                .collect(),
+        )
+        .with_max(10.0),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_svg_scales_and_escapes() {
+        let chart = Chart::new(
+            "Tension <pairs>",
+            vec![("Alice & Bob".to_string(), 4.0), ("Carol".to_string(), 8.0)],
+        );
+        let svg = chart.svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert!(svg.contains("Alice &amp; Bob"));
+        assert!(svg.contains("aria-label=\"Tension &lt;pairs&gt;\""));
+        // The largest value fills the bar area; the other is half of it
+        assert!(svg.contains(&format!("width=\"{}\"", BAR_WIDTH)));
+        assert!(svg.contains(&format!("width=\"{}\"", BAR_WIDTH / 2)));
+    }
+
+    #[test]
+    fn test_dossier_report_is_standalone_html() {
+        use crate::services::composite::{KnowledgeInventory, RelationshipBrief};
+
+        let dossier = CharacterDossier {
+            name: "Alice <the Bold>".to_string(),
+            roles: vec!["detective".to_string()],
+            inferred_roles: None,
+            centrality_rank: Some(1),
+            influence_reach: 3,
+            knowledge_advantages: 2,
+            knowledge_blind_spots: 1,
+            false_beliefs: 0,
+            avg_tension_toward_them: None,
+            key_perceptions: vec![],
+            suggestions: vec!["Give Bob a reason to lie".to_string()],
+            arc_trajectory: None,
+            relationship_map: vec![RelationshipBrief {
+                other_character: "character:bob".to_string(),
+                other_name: "Bob".to_string(),
+                rel_type: "rivalry".to_string(),
+                tension: Some(7),
+            }],
+            knowledge_inventory: KnowledgeInventory {
+                knows: 4,
+                ..Default::default()
+            },
+            narrative_tensions: vec![],
+            goals: vec![],
+            goal_conflicts: vec![],
+            emotion_profile: None,
+            theme_tags: None,
+        };
+        let html = render_html(
+            &Templates::builtin(),
+            "dossier",
+            &format!("Character Dossier: {}", dossier.name),
+            &dossier,
+            &dossier_charts(&dossier),
+        )
+        .unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Character Dossier: Alice &lt;the Bold&gt;</title>"));
+        assert!(html.contains("<h2>Relationship tension</h2>"));
+        assert_eq!(html.matches("<svg").count(), 2);
+        assert!(!html.contains("<script"));
+    }
+}
@@ -9,7 +9,7 @@
 //! Produces richer role classifications than basic hub/bridge/peripheral.
 
 use async_trait::async_trait;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -17,7 +17,7 @@ use crate::db::connection::NarraDb;
 use crate::NarraError;
 
 /// An inferred narrative role for a character.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InferredRole {
     pub character_id: String,
     pub character_name: String,
@@ -32,7 +32,7 @@ pub struct InferredRole {
 }
 
 /// A single piece of evidence for a role inference.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoleEvidence {
     pub signal: String,
     pub detail: String,
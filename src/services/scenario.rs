@@ -0,0 +1,734 @@
+//! What-if scenarios: multi-step hypothetical mutations.
+//!
+//! A scenario script lists mutations (a character learns a fact, a
+//! relationship forms, an entity is deleted, ...). The sandbox applies them
+//! to an in-memory copy of the world, analyzes the result, and discards the
+//! copy, so the real world is never written.
+//!
+//! ```yaml
+//! name: Alice finds out
+//! steps:
+//!   - op: learns
+//!     character: alice
+//!     fact: knowledge:affair
+//!     certainty: suspects
+//!   - op: perceives
+//!     observer: alice
+//!     target: bob
+//!     tension_level: 9
+//! ```
+//!
+//! `WorldMetrics` and `diff` compare a scenario against the baseline world
+//! (centrality, tensions, knowledge asymmetries, phase membership), so
+//! candidate plot directions can be weighed against each other.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::{fork_in_memory, NarraDb};
+use crate::models::knowledge::{create_knowledge_state, CertaintyLevel, KnowledgeStateCreate};
+use crate::models::perception::{create_perception, PerceptionCreate};
+use crate::models::relationship::{create_relationship, RelationshipCreate};
+use crate::services::graph_analytics::{CentralityMetric, CentralityResult, GraphAnalyticsService};
+use crate::services::impact::{ImpactAnalysis, ImpactAnalyzer, ImpactService};
+use crate::services::irony::{IronyReport, IronyService, KnowledgeAsymmetry};
+use crate::services::search::EntityType;
+use crate::services::temporal::{PhaseDetectionResult, TemporalService};
+use crate::services::tension::{NarrativeTension, TensionReport, TensionService};
+use crate::NarraError;
+
+/// Graph depth for per-step impact analysis.
+const IMPACT_DEPTH: usize = 2;
+/// Tensions reported per scenario.
+const TENSION_LIMIT: usize = 20;
+/// Effectively "every character" for metric snapshots.
+const METRIC_LIMIT: usize = 10_000;
+/// Centrality changes smaller than this are noise.
+const CENTRALITY_EPSILON: f64 = 1e-3;
+
+/// A scenario script.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// One hypothetical mutation. Character fields accept a bare key
+/// (`alice`) or a full ID; `fact` defaults to the knowledge table.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ScenarioStep {
+    /// A character comes to know (or suspect, or wrongly believe) something.
+    Learns {
+        character: String,
+        fact: String,
+        #[serde(default)]
+        certainty: CertaintyLevel,
+        #[serde(default)]
+        truth_value: Option<String>,
+    },
+    /// A character's knowledge of something is removed.
+    Forgets { character: String, fact: String },
+    /// Set how one character sees another (creates the perception if missing).
+    Perceives {
+        observer: String,
+        target: String,
+        #[serde(default)]
+        tension_level: Option<i32>,
+        #[serde(default)]
+        feelings: Option<String>,
+        #[serde(default)]
+        rel_types: Option<Vec<String>>,
+    },
+    /// A new relationship between two characters.
+    Relates {
+        from: String,
+        to: String,
+        rel_type: String,
+    },
+    /// Remove every relationship from one character to another.
+    Unrelates { from: String, to: String },
+    /// Merge fields into any entity.
+    Update {
+        entity: String,
+        set: serde_json::Map<String, serde_json::Value>,
+    },
+    /// Delete any entity.
+    Delete { entity: String },
+}
+
+impl ScenarioStep {
+    /// One-line description for reports.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Learns {
+                character,
+                fact,
+                certainty,
+                ..
+            } => {
+                let certainty = serde_json::to_value(certainty)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                format!("{} learns {} ({})", character, fact, certainty)
+            }
+            Self::Forgets { character, fact } => format!("{} forgets {}", character, fact),
+            Self::Perceives {
+                observer, target, ..
+            } => format!("{} now perceives {} differently", observer, target),
+            Self::Relates { from, to, rel_type } => {
+                format!("{} -[{}]-> {}", from, rel_type, to)
+            }
+            Self::Unrelates { from, to } => format!("{} no longer relates to {}", from, to),
+            Self::Update { entity, set } => {
+                let fields: Vec<&str> = set.keys().map(String::as_str).collect();
+                format!("update {} ({})", entity, fields.join(", "))
+            }
+            Self::Delete { entity } => format!("delete {}", entity),
+        }
+    }
+
+    /// Entity whose neighbourhood the step disturbs, for impact analysis.
+    fn subject(&self) -> String {
+        match self {
+            Self::Learns { character, .. } | Self::Forgets { character, .. } => {
+                full_id(character, "character")
+            }
+            Self::Perceives { observer, .. } => full_id(observer, "character"),
+            Self::Relates { from, .. } | Self::Unrelates { from, .. } => full_id(from, "character"),
+            Self::Update { entity, .. } | Self::Delete { entity } => entity.clone(),
+        }
+    }
+}
+
+impl Scenario {
+    pub fn from_yaml(source: &str) -> Result<Self, NarraError> {
+        let scenario: Self = serde_yaml_ng::from_str(source)
+            .map_err(|e| NarraError::Validation(format!("Invalid scenario: {}", e)))?;
+        if scenario.steps.is_empty() {
+            return Err(NarraError::Validation("Scenario has no steps".into()));
+        }
+        Ok(scenario)
+    }
+}
+
+/// Analyses of a world after a scenario was applied.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub steps: Vec<String>,
+    /// Impact of each step, measured just before it was applied.
+    pub impacts: Vec<ImpactAnalysis>,
+    pub irony: IronyReport,
+    pub tensions: TensionReport,
+}
+
+fn full_id(id: &str, default_table: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("{}:{}", default_table, id)
+    }
+}
+
+fn record(id: &str, default_table: &str) -> Result<RecordId, NarraError> {
+    let id = full_id(id, default_table);
+    let (table, key) = id
+        .split_once(':')
+        .ok_or_else(|| NarraError::Validation(format!("Invalid entity ID: {}", id)))?;
+    Ok(RecordId::from((table, key)))
+}
+
+fn character_key(id: &str) -> &str {
+    id.strip_prefix("character:").unwrap_or(id)
+}
+
+/// An in-memory copy of the world that scenarios run against.
+pub struct ScenarioSandbox {
+    overlay: Arc<NarraDb>,
+}
+
+impl ScenarioSandbox {
+    /// Copy `db` into memory. Nothing done in the sandbox touches `db`.
+    pub async fn fork(db: &NarraDb) -> Result<Self, NarraError> {
+        Ok(Self {
+            overlay: Arc::new(fork_in_memory(db).await?),
+        })
+    }
+
+    /// The sandboxed world, for analyses beyond the standard report.
+    pub fn db(&self) -> Arc<NarraDb> {
+        self.overlay.clone()
+    }
+
+    /// Apply every step, then analyze the resulting world.
+    pub async fn run(&self, scenario: &Scenario) -> Result<ScenarioReport, NarraError> {
+        let impact = ImpactAnalyzer::new(self.overlay.clone());
+        let mut impacts = Vec::with_capacity(scenario.steps.len());
+        for (i, step) in scenario.steps.iter().enumerate() {
+            let description = step.describe();
+            let step_error = |e: NarraError| {
+                NarraError::Validation(format!("Step {} ({}): {}", i + 1, description, e))
+            };
+            impacts.push(
+                impact
+                    .analyze_impact(&step.subject(), &description, IMPACT_DEPTH)
+                    .await
+                    .map_err(step_error)?,
+            );
+            self.apply(step).await.map_err(step_error)?;
+        }
+
+        let irony = IronyService::new(self.overlay.clone())
+            .generate_report(None, 0)
+            .await?;
+        let tensions = TensionService::new(self.overlay.clone())
+            .detect_tensions(TENSION_LIMIT, 0.0)
+            .await?;
+
+        Ok(ScenarioReport {
+            name: scenario
+                .name
+                .clone()
+                .unwrap_or_else(|| "Scenario".to_string()),
+            steps: scenario.steps.iter().map(ScenarioStep::describe).collect(),
+            impacts,
+            irony,
+            tensions,
+        })
+    }
+
+    pub async fn apply(&self, step: &ScenarioStep) -> Result<(), NarraError> {
+        let db = &self.overlay;
+        match step {
+            ScenarioStep::Learns {
+                character,
+                fact,
+                certainty,
+                truth_value,
+            } => {
+                create_knowledge_state(
+                    db,
+                    character_key(character),
+                    &full_id(fact, "knowledge"),
+                    KnowledgeStateCreate {
+                        certainty: *certainty,
+                        truth_value: truth_value.clone(),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            }
+            ScenarioStep::Forgets { character, fact } => {
+                db.query("DELETE knows WHERE in = $character AND out = $fact")
+                    .bind(("character", record(character, "character")?))
+                    .bind(("fact", record(fact, "knowledge")?))
+                    .await?
+                    .check()?;
+            }
+            ScenarioStep::Perceives {
+                observer,
+                target,
+                tension_level,
+                feelings,
+                rel_types,
+            } => {
+                let mut patch = serde_json::Map::new();
+                if let Some(t) = tension_level {
+                    patch.insert("tension_level".into(), (*t).into());
+                }
+                if let Some(f) = feelings {
+                    patch.insert("feelings".into(), f.clone().into());
+                }
+                if let Some(r) = rel_types {
+                    patch.insert("rel_types".into(), r.clone().into());
+                }
+                let mut response = db
+                    .query(
+                        "UPDATE perceives MERGE $patch \
+                         WHERE in = $observer AND out = $target RETURN VALUE id",
+                    )
+                    .bind(("patch", patch))
+                    .bind(("observer", record(observer, "character")?))
+                    .bind(("target", record(target, "character")?))
+                    .await?;
+                let updated: Vec<RecordId> = response.take(0)?;
+                if updated.is_empty() {
+                    create_perception(
+                        db,
+                        character_key(observer),
+                        character_key(target),
+                        PerceptionCreate {
+                            rel_types: rel_types.clone().unwrap_or_default(),
+                            subtype: None,
+                            feelings: feelings.clone(),
+                            perception: None,
+                            tension_level: *tension_level,
+                            history_notes: None,
+                        },
+                    )
+                    .await?;
+                }
+            }
+            ScenarioStep::Relates { from, to, rel_type } => {
+                create_relationship(
+                    db,
+                    RelationshipCreate {
+                        from_character_id: character_key(from).to_string(),
+                        to_character_id: character_key(to).to_string(),
+                        rel_type: rel_type.clone(),
+                        subtype: None,
+                        label: None,
+                    },
+                )
+                .await?;
+            }
+            ScenarioStep::Unrelates { from, to } => {
+                db.query("DELETE relates_to WHERE in = $from AND out = $to")
+                    .bind(("from", record(from, "character")?))
+                    .bind(("to", record(to, "character")?))
+                    .await?
+                    .check()?;
+            }
+            ScenarioStep::Update { entity, set } => {
+                let mut response = db
+                    .query("UPDATE $id MERGE $set RETURN VALUE id")
+                    .bind(("id", record(entity, "character")?))
+                    .bind(("set", set.clone()))
+                    .await?;
+                let updated: Vec<RecordId> = response.take(0)?;
+                if updated.is_empty() {
+                    return Err(NarraError::NotFound {
+                        entity_type: "entity".into(),
+                        id: entity.clone(),
+                    });
+                }
+            }
+            ScenarioStep::Delete { entity } => {
+                db.query("DELETE $id")
+                    .bind(("id", record(entity, "character")?))
+                    .await?
+                    .check()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Counterfactual diff
+// ---------------------------------------------------------------------------
+
+/// Metrics compared between the baseline world and a scenario.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldMetrics {
+    pub centrality: Vec<CentralityResult>,
+    pub tensions: Vec<NarrativeTension>,
+    pub asymmetries: Vec<KnowledgeAsymmetry>,
+    /// `None` when phases can't be detected (e.g. no embeddings).
+    pub phases: Option<PhaseDetectionResult>,
+}
+
+impl WorldMetrics {
+    /// Measure a world. Pass the baseline's phase count as `num_phases` so
+    /// phase membership is comparable.
+    pub async fn measure(db: Arc<NarraDb>, num_phases: Option<usize>) -> Result<Self, NarraError> {
+        let centrality = GraphAnalyticsService::new(db.clone())
+            .compute_centrality(
+                None,
+                vec![CentralityMetric::Degree, CentralityMetric::Betweenness],
+                METRIC_LIMIT,
+            )
+            .await?;
+        let tensions = TensionService::new(db.clone())
+            .detect_tensions(METRIC_LIMIT, 0.0)
+            .await?
+            .tensions;
+        let asymmetries = IronyService::new(db.clone())
+            .generate_report(None, 0)
+            .await?
+            .asymmetries;
+        let phases = match TemporalService::new(db)
+            .detect_phases(EntityType::embeddable(), num_phases, None)
+            .await
+        {
+            Ok(result) if !result.phases.is_empty() => Some(result),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::debug!("Phase detection skipped in scenario diff: {}", e);
+                None
+            }
+        };
+        Ok(Self {
+            centrality,
+            tensions,
+            asymmetries,
+            phases,
+        })
+    }
+
+    pub fn phase_count(&self) -> Option<usize> {
+        self.phases.as_ref().map(|p| p.phases.len())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CentralityChange {
+    pub character_id: String,
+    pub character_name: String,
+    pub baseline_degree: f64,
+    pub scenario_degree: f64,
+    pub baseline_betweenness: f64,
+    pub scenario_betweenness: f64,
+}
+
+/// A tension present on either side. `None` severity means absent there.
+#[derive(Debug, Clone, Serialize)]
+pub struct TensionChange {
+    pub character_a: String,
+    pub character_b: String,
+    pub tension_type: String,
+    pub baseline_severity: Option<f32>,
+    pub scenario_severity: Option<f32>,
+}
+
+/// A knowledge asymmetry that exists in only one of the two worlds.
+#[derive(Debug, Clone, Serialize)]
+pub struct AsymmetryChange {
+    pub knowing: String,
+    pub unknowing: String,
+    pub fact: String,
+    /// "created" (only in the scenario) or "resolved" (only in the baseline)
+    pub change: String,
+}
+
+/// An entity whose phase changed. Scenario phases are matched to the
+/// baseline phase they share the most members with.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseMove {
+    pub entity_id: String,
+    pub name: String,
+    pub baseline_phase: Option<String>,
+    pub scenario_phase: Option<String>,
+}
+
+/// How a scenario differs from the baseline.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioDiff {
+    pub scenario: String,
+    pub centrality: Vec<CentralityChange>,
+    pub tensions: Vec<TensionChange>,
+    pub asymmetries: Vec<AsymmetryChange>,
+    /// `None` when either side has no phases.
+    pub phase_moves: Option<Vec<PhaseMove>>,
+}
+
+fn tension_key(t: &NarrativeTension) -> (String, String, String) {
+    // Tensions are symmetric; order the pair so both sides agree
+    let (a, b) = if t.character_a_id <= t.character_b_id {
+        (&t.character_a_name, &t.character_b_name)
+    } else {
+        (&t.character_b_name, &t.character_a_name)
+    };
+    (a.clone(), b.clone(), t.tension_type.clone())
+}
+
+fn asymmetry_key(a: &KnowledgeAsymmetry) -> (String, String, String) {
+    (
+        a.knowing_character_name.clone(),
+        a.unknowing_character_name.clone(),
+        a.fact.clone(),
+    )
+}
+
+/// entity_id -> (phase label, phase index) for every member.
+fn phase_of(result: &PhaseDetectionResult) -> HashMap<&str, (usize, &str)> {
+    result
+        .phases
+        .iter()
+        .enumerate()
+        .flat_map(|(i, p)| {
+            p.members
+                .iter()
+                .map(move |m| (m.entity_id.as_str(), (i, p.label.as_str())))
+        })
+        .collect()
+}
+
+fn phase_moves(baseline: &PhaseDetectionResult, scenario: &PhaseDetectionResult) -> Vec<PhaseMove> {
+    let before = phase_of(baseline);
+    let after = phase_of(scenario);
+
+    // Match each scenario phase to the baseline phase sharing most members
+    let mut overlap: HashMap<(usize, usize), usize> = HashMap::new();
+    for (id, (j, _)) in &after {
+        if let Some((i, _)) = before.get(id) {
+            *overlap.entry((*j, *i)).or_default() += 1;
+        }
+    }
+    let matched = |j: usize| -> Option<usize> {
+        overlap
+            .iter()
+            .filter(|((sj, _), _)| *sj == j)
+            .max_by_key(|((_, i), n)| (**n, std::cmp::Reverse(*i)))
+            .map(|((_, i), _)| *i)
+    };
+
+    let mut names: BTreeMap<&str, &str> = BTreeMap::new();
+    for phase in baseline.phases.iter().chain(&scenario.phases) {
+        for m in &phase.members {
+            names.insert(m.entity_id.as_str(), m.name.as_str());
+        }
+    }
+
+    names
+        .into_iter()
+        .filter_map(|(id, name)| {
+            let was = before.get(id);
+            let now = after.get(id);
+            let same = match (was, now) {
+                (Some((i, _)), Some((j, _))) => matched(*j) == Some(*i),
+                (None, None) => true,
+                _ => false,
+            };
+            (!same).then(|| PhaseMove {
+                entity_id: id.to_string(),
+                name: name.to_string(),
+                baseline_phase: was.map(|(_, label)| label.to_string()),
+                scenario_phase: now.map(|(_, label)| label.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Compare a scenario's metrics against the baseline's.
+pub fn diff(name: &str, baseline: &WorldMetrics, scenario: &WorldMetrics) -> ScenarioDiff {
+    // Centrality: characters on either side, changed beyond noise
+    let mut by_id: BTreeMap<&str, (Option<&CentralityResult>, Option<&CentralityResult>)> =
+        BTreeMap::new();
+    for c in &baseline.centrality {
+        by_id.entry(c.character_id.as_str()).or_default().0 = Some(c);
+    }
+    for c in &scenario.centrality {
+        by_id.entry(c.character_id.as_str()).or_default().1 = Some(c);
+    }
+    let mut centrality: Vec<CentralityChange> = by_id
+        .into_iter()
+        .filter_map(|(id, (before, after))| {
+            let change = CentralityChange {
+                character_id: id.to_string(),
+                character_name: before
+                    .or(after)
+                    .map(|c| c.character_name.clone())
+                    .unwrap_or_default(),
+                baseline_degree: before.map_or(0.0, |c| c.degree),
+                scenario_degree: after.map_or(0.0, |c| c.degree),
+                baseline_betweenness: before.map_or(0.0, |c| c.betweenness),
+                scenario_betweenness: after.map_or(0.0, |c| c.betweenness),
+            };
+            let moved = (change.scenario_degree - change.baseline_degree).abs()
+                + (change.scenario_betweenness - change.baseline_betweenness).abs();
+            (moved > CENTRALITY_EPSILON).then_some(change)
+        })
+        .collect();
+    let shift = |c: &CentralityChange| {
+        (c.scenario_degree - c.baseline_degree).abs()
+            + (c.scenario_betweenness - c.baseline_betweenness).abs()
+    };
+    centrality.sort_by(|a, b| shift(b).total_cmp(&shift(a)));
+
+    // Tensions: new, gone, or changed severity
+    let mut tensions: BTreeMap<(String, String, String), (Option<f32>, Option<f32>)> =
+        BTreeMap::new();
+    for t in &baseline.tensions {
+        tensions.entry(tension_key(t)).or_default().0 = Some(t.severity);
+    }
+    for t in &scenario.tensions {
+        tensions.entry(tension_key(t)).or_default().1 = Some(t.severity);
+    }
+    let tensions = tensions
+        .into_iter()
+        .filter(|(_, (before, after))| match (before, after) {
+            (Some(b), Some(a)) => (a - b).abs() > f32::EPSILON,
+            _ => true,
+        })
+        .map(|((a, b, kind), (before, after))| TensionChange {
+            character_a: a,
+            character_b: b,
+            tension_type: kind,
+            baseline_severity: before,
+            scenario_severity: after,
+        })
+        .collect();
+
+    // Asymmetries: created or resolved
+    let before: BTreeMap<_, _> = baseline
+        .asymmetries
+        .iter()
+        .map(|a| (asymmetry_key(a), a))
+        .collect();
+    let after: BTreeMap<_, _> = scenario
+        .asymmetries
+        .iter()
+        .map(|a| (asymmetry_key(a), a))
+        .collect();
+    let asymmetries = after
+        .keys()
+        .filter(|k| !before.contains_key(*k))
+        .map(|k| (k, "created"))
+        .chain(
+            before
+                .keys()
+                .filter(|k| !after.contains_key(*k))
+                .map(|k| (k, "resolved")),
+        )
+        .map(|((knowing, unknowing, fact), change)| AsymmetryChange {
+            knowing: knowing.clone(),
+            unknowing: unknowing.clone(),
+            fact: fact.clone(),
+            change: change.to_string(),
+        })
+        .collect();
+
+    let phase_moves = match (&baseline.phases, &scenario.phases) {
+        (Some(b), Some(s)) => Some(phase_moves(b, s)),
+        _ => None,
+    };
+
+    ScenarioDiff {
+        scenario: name.to_string(),
+        centrality,
+        tensions,
+        asymmetries,
+        phase_moves,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenario_parses_steps() {
+        let scenario = Scenario::from_yaml(
+            r#"
+name: Betrayal
+steps:
+  - op: learns
+    character: alice
+    fact: affair
+    certainty: suspects
+  - op: relates
+    from: character:alice
+    to: bob
+    rel_type: rivalry
+  - op: update
+    entity: location:docks
+    set: { description: "Burned down" }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(scenario.steps.len(), 3);
+        assert_eq!(
+            scenario.steps[0].describe(),
+            "alice learns affair (suspects)"
+        );
+        assert_eq!(scenario.steps[0].subject(), "character:alice");
+        assert_eq!(scenario.steps[2].subject(), "location:docks");
+        assert!(Scenario::from_yaml("steps: []").is_err());
+        assert!(Scenario::from_yaml("steps:\n  - op: teleports\n").is_err());
+    }
+
+    fn tension(a: &str, b: &str, severity: f32) -> NarrativeTension {
+        NarrativeTension {
+            character_a_id: format!("character:{}", a),
+            character_a_name: a.to_string(),
+            character_b_id: format!("character:{}", b),
+            character_b_name: b.to_string(),
+            tension_type: "emotional_conflict".to_string(),
+            description: String::new(),
+            severity,
+            signals: vec![],
+        }
+    }
+
+    fn metrics(tensions: Vec<NarrativeTension>) -> WorldMetrics {
+        WorldMetrics {
+            centrality: vec![],
+            tensions,
+            asymmetries: vec![],
+            phases: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_tension_changes() {
+        let baseline = metrics(vec![
+            tension("alice", "bob", 0.4),
+            tension("bob", "gray", 0.5),
+        ]);
+        // Same pair listed the other way round is the same tension
+        let scenario = metrics(vec![
+            tension("bob", "alice", 0.9),
+            tension("bob", "gray", 0.5),
+        ]);
+
+        let d = diff("Betrayal", &baseline, &scenario);
+        assert_eq!(d.scenario, "Betrayal");
+        assert_eq!(d.tensions.len(), 1);
+        assert_eq!(d.tensions[0].character_a, "alice");
+        assert_eq!(d.tensions[0].baseline_severity, Some(0.4));
+        assert_eq!(d.tensions[0].scenario_severity, Some(0.9));
+        assert!(d.phase_moves.is_none());
+
+        let d = diff("Quiet", &baseline, &metrics(vec![]));
+        assert!(d.tensions.iter().all(|t| t.scenario_severity.is_none()));
+        assert_eq!(d.tensions.len(), 2);
+    }
+}
@@ -0,0 +1,266 @@
+//! Background annotation for the long-running MCP server.
+//!
+//! `[annotate] schedule` picks when the annotation pipeline and baseline arc
+//! snapshotting run on their own:
+//!
+//! - `manual` (default): never; run `narra world annotate` or `AnnotateEntities`
+//! - `on_mutation`: once the world has been idle for `idle_secs` after a change
+//! - `nightly`: once a day, at or after `nightly_hour` (local time), when idle
+//!
+//! Runs are incremental, so only models whose input text changed re-run.
+//! Mutations are seen on the [`EventBus`], which means changes made by other
+//! processes (the CLI) are only picked up by the nightly run. The outcome of
+//! the latest run is kept in session state and shown in session context.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::db::connection::NarraDb;
+use crate::services::arc::baseline_arc_snapshots;
+use crate::services::{noop_progress, AnnotationPipeline, EventBus, PipelineConfig};
+use crate::session::{ScheduledRun, SessionStateManager};
+use crate::NarraError;
+
+/// How often the scheduler checks whether a run is due.
+const TICK: Duration = Duration::from_secs(15);
+/// Entity types run through the annotation pipeline.
+const ANNOTATED_TYPES: &[&str] = &["character", "event", "scene"];
+/// Entity types given baseline arc snapshots.
+const SNAPSHOT_TYPES: &[&str] = &["character", "knowledge"];
+
+/// When background annotation runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationSchedule {
+    #[default]
+    Manual,
+    OnMutation,
+    Nightly,
+}
+
+impl std::fmt::Display for AnnotationSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AnnotationSchedule::Manual => "manual",
+            AnnotationSchedule::OnMutation => "on_mutation",
+            AnnotationSchedule::Nightly => "nightly",
+        })
+    }
+}
+
+impl std::str::FromStr for AnnotationSchedule {
+    type Err = NarraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "manual" => Ok(AnnotationSchedule::Manual),
+            "on_mutation" => Ok(AnnotationSchedule::OnMutation),
+            "nightly" => Ok(AnnotationSchedule::Nightly),
+            _ => Err(NarraError::Validation(format!(
+                "Annotation schedule must be manual, on_mutation, or nightly (got '{}')",
+                s
+            ))),
+        }
+    }
+}
+
+/// Schedule plus what counts as idle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SchedulePolicy {
+    pub schedule: AnnotationSchedule,
+    /// Quiet time after the last mutation before a run may start
+    pub idle: Duration,
+    /// Local hour (0-23) from which the nightly run is due
+    pub nightly_hour: u32,
+}
+
+impl Default for SchedulePolicy {
+    fn default() -> Self {
+        Self {
+            schedule: AnnotationSchedule::Manual,
+            idle: Duration::from_secs(60),
+            nightly_hour: 3,
+        }
+    }
+}
+
+impl SchedulePolicy {
+    /// Whether a run is due. `changed` is whether a mutation arrived since the
+    /// last run, `idle` the time since the last mutation, and `last_run` when
+    /// the latest run finished.
+    pub fn due(
+        &self,
+        changed: bool,
+        idle: Duration,
+        now: DateTime<Local>,
+        last_run: Option<DateTime<Local>>,
+    ) -> bool {
+        if idle < self.idle {
+            return false;
+        }
+        match self.schedule {
+            AnnotationSchedule::Manual => false,
+            AnnotationSchedule::OnMutation => changed,
+            AnnotationSchedule::Nightly => {
+                now.hour() >= self.nightly_hour
+                    && last_run.is_none_or(|last| last.date_naive() < now.date_naive())
+            }
+        }
+    }
+}
+
+/// Run incremental annotation and baseline arc snapshotting once.
+pub async fn run_scheduled(
+    db: &NarraDb,
+    pipeline: &AnnotationPipeline,
+    trigger: AnnotationSchedule,
+) -> Result<ScheduledRun, NarraError> {
+    let config = PipelineConfig {
+        incremental: true,
+        ..Default::default()
+    };
+    let report = pipeline
+        .annotate_all(ANNOTATED_TYPES, config, noop_progress())
+        .await?;
+    let snapshots = baseline_arc_snapshots(db, SNAPSHOT_TYPES).await?;
+    Ok(ScheduledRun {
+        finished_at: Utc::now(),
+        trigger: trigger.to_string(),
+        annotated: report.total_processed,
+        unchanged: report.unchanged,
+        errors: report.errors,
+        arc_snapshots: snapshots.created,
+    })
+}
+
+/// Background task that runs annotation on the configured schedule.
+pub struct AnnotationScheduler {
+    handle: JoinHandle<()>,
+}
+
+impl AnnotationScheduler {
+    /// Start scheduling. Returns `None` for the manual schedule.
+    pub fn start(
+        policy: SchedulePolicy,
+        bus: &EventBus,
+        db: Arc<NarraDb>,
+        pipeline: AnnotationPipeline,
+        session: Arc<SessionStateManager>,
+    ) -> Option<Self> {
+        if policy.schedule == AnnotationSchedule::Manual {
+            return None;
+        }
+
+        let mut rx = bus.subscribe();
+        let handle = tokio::spawn(async move {
+            let mut changed = false;
+            let mut last_mutation = Instant::now();
+            let mut ticker = tokio::time::interval(TICK);
+            loop {
+                tokio::select! {
+                    received = rx.recv() => match received {
+                        Ok(event) if event.event.starts_with("entity.") => {
+                            changed = true;
+                            last_mutation = Instant::now();
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(_)) => {
+                            changed = true;
+                            last_mutation = Instant::now();
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    },
+                    _ = ticker.tick() => {
+                        let last_run = session
+                            .get_last_scheduled_run()
+                            .await
+                            .map(|run| run.finished_at.with_timezone(&Local));
+                        if !policy.due(changed, last_mutation.elapsed(), Local::now(), last_run) {
+                            continue;
+                        }
+                        changed = false;
+                        match run_scheduled(&db, &pipeline, policy.schedule).await {
+                            Ok(run) => {
+                                tracing::info!(
+                                    "Scheduled annotation: {} entities, {} unchanged, {} arc snapshots",
+                                    run.annotated,
+                                    run.unchanged,
+                                    run.arc_snapshots
+                                );
+                                session.record_scheduled_run(run).await;
+                                if let Err(e) = session.save().await {
+                                    tracing::warn!("Failed to save session state: {}", e);
+                                }
+                            }
+                            Err(e) => tracing::warn!("Scheduled annotation failed: {}", e),
+                        }
+                    }
+                }
+            }
+        });
+
+        Some(Self { handle })
+    }
+
+    /// Stop scheduling; a run in progress is abandoned.
+    pub fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        assert_eq!(
+            "on-mutation".parse::<AnnotationSchedule>().unwrap(),
+            AnnotationSchedule::OnMutation
+        );
+        assert_eq!(
+            "Nightly".parse::<AnnotationSchedule>().unwrap(),
+            AnnotationSchedule::Nightly
+        );
+        assert!("hourly".parse::<AnnotationSchedule>().is_err());
+    }
+
+    #[test]
+    fn test_on_mutation_waits_for_idle_change() {
+        let policy = SchedulePolicy {
+            schedule: AnnotationSchedule::OnMutation,
+            ..Default::default()
+        };
+        let quiet = Duration::from_secs(90);
+        assert!(policy.due(true, quiet, at(2, 14), None));
+        assert!(!policy.due(false, quiet, at(2, 14), None));
+        assert!(!policy.due(true, Duration::from_secs(5), at(2, 14), None));
+        let manual = SchedulePolicy::default();
+        assert!(!manual.due(true, quiet, at(2, 14), None));
+    }
+
+    #[test]
+    fn test_nightly_runs_once_a_day() {
+        let policy = SchedulePolicy {
+            schedule: AnnotationSchedule::Nightly,
+            ..Default::default()
+        };
+        let quiet = Duration::from_secs(90);
+        assert!(!policy.due(false, quiet, at(2, 1), Some(at(1, 4))));
+        assert!(policy.due(false, quiet, at(2, 3), Some(at(1, 4))));
+        assert!(!policy.due(false, quiet, at(2, 9), Some(at(2, 3))));
+        assert!(policy.due(true, quiet, at(2, 9), None));
+        assert!(!policy.due(true, Duration::ZERO, at(2, 9), None));
+    }
+}
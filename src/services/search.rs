@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use crate::embedding::reranker::RerankerService;
 use crate::embedding::EmbeddingService;
+use crate::services::vector_index::VectorIndexSettings;
 use crate::NarraError;
 
 /// Entity types for search filtering.
@@ -19,6 +20,7 @@ pub enum EntityType {
     Knowledge,
     Note,
     Fact,
+    Term,
 }
 
 impl EntityType {
@@ -32,6 +34,7 @@ impl EntityType {
             EntityType::Knowledge => "knowledge",
             EntityType::Note => "note",
             EntityType::Fact => "fact",
+            EntityType::Term => "term",
         }
     }
 
@@ -45,6 +48,7 @@ impl EntityType {
             EntityType::Knowledge,
             EntityType::Note,
             EntityType::Fact,
+            EntityType::Term,
         ]
     }
 
@@ -58,6 +62,7 @@ impl EntityType {
             EntityType::Knowledge,
             EntityType::Note,
             EntityType::Fact,
+            EntityType::Term,
         ]
     }
 
@@ -72,6 +77,7 @@ impl EntityType {
                 | EntityType::Knowledge
                 | EntityType::Note
                 | EntityType::Fact
+                | EntityType::Term
         )
     }
 }
@@ -244,6 +250,7 @@ pub struct SurrealSearchService {
     db: Arc<NarraDb>,
     embedding_service: Arc<dyn EmbeddingService + Send + Sync>,
     reranker: Option<Arc<dyn RerankerService + Send + Sync>>,
+    vector_index: VectorIndexSettings,
 }
 
 /// Build SQL WHERE clause fragment and bindings from metadata filters for a given entity type.
@@ -265,6 +272,7 @@ fn build_filter_clause(
                 | (EntityType::Event, "sequence_min")
                 | (EntityType::Event, "sequence_max")
                 | (EntityType::Location, "loc_type")
+                | (EntityType::Note, "kind")
         );
 
         if !allowed {
@@ -290,6 +298,9 @@ fn build_filter_clause(
             ("loc_type", FilterOp::Eq) => {
                 format!(" AND loc_type = ${}", filter.bind_key)
             }
+            ("kind", FilterOp::Eq) => {
+                format!(" AND kind = ${}", filter.bind_key)
+            }
             _ => continue,
         };
 
@@ -355,6 +366,7 @@ impl SurrealSearchService {
             db,
             embedding_service,
             reranker: None,
+            vector_index: VectorIndexSettings::default(),
         }
     }
 
@@ -363,6 +375,20 @@ impl SurrealSearchService {
         self
     }
 
+    /// Query embeddings through a built ANN index instead of a full scan.
+    /// Only pass settings whose index exists with the model's dimensions.
+    pub fn with_vector_index(mut self, settings: VectorIndexSettings) -> Self {
+        self.vector_index = settings;
+        self
+    }
+
+    /// WHERE condition picking the `k` embedding candidates for a query.
+    fn vector_condition(&self, k: usize) -> String {
+        self.vector_index
+            .knn_condition(k)
+            .unwrap_or_else(|| "embedding IS NOT NONE".to_string())
+    }
+
     /// Build a full-text search query for a specific entity type.
     fn build_search_query(entity_type: EntityType, limit: usize) -> String {
         let table = entity_type.table_name();
@@ -664,6 +690,7 @@ impl SearchService for SurrealSearchService {
 
         let limit = filter.limit.unwrap_or(20);
         let min_score = filter.min_score.unwrap_or(0.0);
+        let vector_condition = self.vector_condition(limit * 2);
 
         // Launch all entity type queries in parallel
         let futures: Vec<_> = entity_types
@@ -672,6 +699,7 @@ impl SearchService for SurrealSearchService {
                 let db = self.db.clone();
                 let qv = Arc::clone(&query_vector);
                 let metadata = filter.metadata.clone();
+                let vector_condition = vector_condition.clone();
                 async move {
                     let table = entity_type.table_name();
                     let name_field = match entity_type {
@@ -684,13 +712,13 @@ impl SearchService for SurrealSearchService {
                     let (filter_clause, filter_bindings) =
                         build_filter_clause(entity_type, &metadata);
 
-                    // Brute-force cosine similarity (HNSW unreliable in embedded RocksDB mode)
+                    // Brute-force cosine similarity unless an ANN index was built
                     let k = limit * 2;
                     let query_str = format!(
                         r#"SELECT id, '{table}' AS entity_type, {name_field} AS name,
                                   vector::similarity::cosine(embedding, $query_vector) AS score
                            FROM {table}
-                           WHERE embedding IS NOT NONE{filter_clause}
+                           WHERE {vector_condition}{filter_clause}
                            ORDER BY score DESC
                            LIMIT {k}"#,
                         table = table,
@@ -769,6 +797,7 @@ impl SearchService for SurrealSearchService {
 
         let limit = filter.limit.unwrap_or(20);
         let min_score = filter.min_score;
+        let vector_condition = self.vector_condition(limit * 2);
 
         // Launch keyword + semantic queries for all types in parallel
         let futures: Vec<_> = entity_types
@@ -778,6 +807,7 @@ impl SearchService for SurrealSearchService {
                 let query_text = query.to_string();
                 let qv = Arc::clone(&query_vector);
                 let metadata = filter.metadata.clone();
+                let vector_condition = vector_condition.clone();
                 async move {
                     let table = entity_type.table_name();
                     let name_field = match entity_type {
@@ -808,7 +838,7 @@ impl SearchService for SurrealSearchService {
                         r#"SELECT id, '{table}' AS entity_type, {name_field} AS name,
                                   vector::similarity::cosine(embedding, $query_vector) AS score
                            FROM {table}
-                           WHERE embedding IS NOT NONE{filter_clause}
+                           WHERE {vector_condition}{filter_clause}
                            ORDER BY score DESC
                            LIMIT {k}"#,
                         table = table,
@@ -0,0 +1,437 @@
+//! Relationship sentiment over time: how one character feels about another,
+//! scene by scene.
+//!
+//! Every perspective arc snapshot of A's perception of B is a point on the
+//! curve. A point's valence (-1 hostile to +1 warm) blends two readings:
+//!
+//! - the snapshot's embedding projected onto an axis between a warm and a
+//!   hostile reference text, when an embedding model is loaded
+//! - the emotion annotation of the event the snapshot is tied to, as the
+//!   share of positive minus negative GoEmotions scores
+//!
+//! Points tied to events are ordered by event sequence; snapshots that aren't
+//! tied to an event can't be placed in story order and are left out (unless
+//! none are tied, in which case creation order is used). The present
+//! perception closes the curve, with its feelings text classified by the
+//! emotion model. Inflections are sharp swings and turning points.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
+use crate::embedding::EmbeddingService;
+use crate::models::annotation::{get_annotation, EmotionOutput};
+use crate::services::EmotionService;
+use crate::utils::math::cosine_similarity;
+use crate::NarraError;
+
+/// GoEmotions labels that read as warmth towards someone.
+const POSITIVE_EMOTIONS: &[&str] = &[
+    "admiration",
+    "amusement",
+    "approval",
+    "caring",
+    "desire",
+    "excitement",
+    "gratitude",
+    "joy",
+    "love",
+    "optimism",
+    "pride",
+    "relief",
+];
+
+/// GoEmotions labels that read as hostility or distress.
+const NEGATIVE_EMOTIONS: &[&str] = &[
+    "anger",
+    "annoyance",
+    "disappointment",
+    "disapproval",
+    "disgust",
+    "embarrassment",
+    "fear",
+    "grief",
+    "nervousness",
+    "remorse",
+    "sadness",
+];
+
+const WARM_REFERENCE: &str =
+    "They admire, trust and love the other person. Warm, loyal, affectionate feelings.";
+const HOSTILE_REFERENCE: &str =
+    "They resent, distrust and fear the other person. Hostile, bitter, contemptuous feelings.";
+
+/// Cosine differences between the reference texts are small; this spreads
+/// them over the -1..1 range.
+const AXIS_SCALE: f32 = 10.0;
+
+/// Change in valence between neighbouring points that counts as a swing.
+pub const DEFAULT_MIN_CHANGE: f32 = 0.2;
+
+/// Overall change from first to last point below which the trend is steady.
+const STEADY_BAND: f32 = 0.1;
+
+/// One point on the sentiment curve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentimentPoint {
+    /// Event title, "baseline" for an untied first snapshot, or "now"
+    pub label: String,
+    pub event_id: Option<String>,
+    pub sequence: Option<i64>,
+    /// -1.0 (hostile) to 1.0 (warm)
+    pub valence: f32,
+    /// Dominant emotion of the event, or of the present feelings
+    pub emotion: Option<String>,
+}
+
+/// A point where the curve swings or turns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentimentInflection {
+    /// Index into the curve's points
+    pub index: usize,
+    pub label: String,
+    pub event_id: Option<String>,
+    /// "warmed" or "cooled" (a swing), "peak" or "trough" (a turn)
+    pub kind: String,
+    /// Valence change from the previous point
+    pub change: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipSentiment {
+    pub observer: String,
+    pub observer_name: String,
+    pub target: String,
+    pub target_name: String,
+    pub points: Vec<SentimentPoint>,
+    pub inflections: Vec<SentimentInflection>,
+    /// "warming", "cooling", or "steady" from the first to the last point
+    pub trend: String,
+    /// Snapshots left out because they aren't tied to an event
+    pub unanchored: usize,
+}
+
+/// Positive minus negative emotion scores, as a share of both (-1.0 to 1.0).
+pub fn emotion_valence(output: &EmotionOutput) -> f32 {
+    let sum = |labels: &[&str]| -> f32 {
+        output
+            .scores
+            .iter()
+            .filter(|s| labels.contains(&s.label.as_str()))
+            .map(|s| s.score)
+            .sum()
+    };
+    let positive = sum(POSITIVE_EMOTIONS);
+    let negative = sum(NEGATIVE_EMOTIONS);
+    if positive + negative <= f32::EPSILON {
+        0.0
+    } else {
+        (positive - negative) / (positive + negative)
+    }
+}
+
+/// Where `embedding` sits between the warm and hostile references (-1.0 to 1.0).
+pub fn axis_valence(embedding: &[f32], warm: &[f32], hostile: &[f32]) -> f32 {
+    let raw = cosine_similarity(embedding, warm) - cosine_similarity(embedding, hostile);
+    (raw * AXIS_SCALE).clamp(-1.0, 1.0)
+}
+
+/// Swings of at least `min_change` between neighbouring points, and turns
+/// where the curve changes direction by at least half that on one side.
+pub fn detect_inflections(points: &[SentimentPoint], min_change: f32) -> Vec<SentimentInflection> {
+    let mut inflections = Vec::new();
+    for i in 1..points.len() {
+        let change = points[i].valence - points[i - 1].valence;
+        let next = points
+            .get(i + 1)
+            .map(|p| p.valence - points[i].valence)
+            .unwrap_or(0.0);
+        let turns = change * next < 0.0 && change.abs().max(next.abs()) >= min_change / 2.0;
+        let kind = if turns {
+            if change > 0.0 {
+                "peak"
+            } else {
+                "trough"
+            }
+        } else if change.abs() >= min_change {
+            if change > 0.0 {
+                "warmed"
+            } else {
+                "cooled"
+            }
+        } else {
+            continue;
+        };
+        inflections.push(SentimentInflection {
+            index: i,
+            label: points[i].label.clone(),
+            event_id: points[i].event_id.clone(),
+            kind: kind.to_string(),
+            change,
+        });
+    }
+    inflections
+}
+
+fn trend(points: &[SentimentPoint]) -> &'static str {
+    match (points.first(), points.last()) {
+        (Some(first), Some(last)) if last.valence - first.valence > STEADY_BAND => "warming",
+        (Some(first), Some(last)) if first.valence - last.valence > STEADY_BAND => "cooling",
+        _ => "steady",
+    }
+}
+
+fn mean(values: &[f32]) -> Option<f32> {
+    (!values.is_empty()).then(|| values.iter().sum::<f32>() / values.len() as f32)
+}
+
+pub struct RelationshipSentimentService {
+    db: Arc<NarraDb>,
+    embedding: Arc<dyn EmbeddingService + Send + Sync>,
+    emotion: Arc<dyn EmotionService + Send + Sync>,
+}
+
+impl RelationshipSentimentService {
+    pub fn new(
+        db: Arc<NarraDb>,
+        embedding: Arc<dyn EmbeddingService + Send + Sync>,
+        emotion: Arc<dyn EmotionService + Send + Sync>,
+    ) -> Self {
+        Self {
+            db,
+            embedding,
+            emotion,
+        }
+    }
+
+    /// Sentiment curve of `observer_id`'s feelings about `target_id`.
+    pub async fn analyze(
+        &self,
+        observer_id: &str,
+        target_id: &str,
+        min_change: f32,
+    ) -> Result<RelationshipSentiment, NarraError> {
+        #[derive(Deserialize)]
+        struct Edge {
+            id: RecordId,
+            embedding: Option<Vec<f32>>,
+            feelings: Option<String>,
+            perception: Option<String>,
+            observer_name: Option<String>,
+            target_name: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        struct Snapshot {
+            embedding: Vec<f32>,
+            event_id: Option<RecordId>,
+            event_title: Option<String>,
+            sequence: Option<i64>,
+        }
+
+        let mut resp = self
+            .db
+            .query(
+                "SELECT id, embedding, feelings, perception, in.name AS observer_name, \
+                 out.name AS target_name FROM perceives WHERE in = $observer AND out = $target",
+            )
+            .bind(("observer", parse_record_id(observer_id)?))
+            .bind(("target", parse_record_id(target_id)?))
+            .await?;
+        let edge: Edge = resp
+            .take::<Vec<Edge>>(0)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| NarraError::NotFound {
+                entity_type: "perceives_edge".to_string(),
+                id: format!("{} -> {}", observer_id, target_id),
+            })?;
+
+        let mut resp = self
+            .db
+            .query(
+                "SELECT embedding, created_at, event_id, event_id.title AS event_title, \
+                 event_id.sequence AS sequence FROM arc_snapshot \
+                 WHERE entity_id = $edge AND entity_type = 'perspective' \
+                 ORDER BY created_at ASC",
+            )
+            .bind(("edge", edge.id.clone()))
+            .await?;
+        let snapshots: Vec<Snapshot> = resp.take(0)?;
+
+        // Story order where snapshots are tied to events; creation order otherwise
+        let total = snapshots.len();
+        let tied = snapshots.iter().filter(|s| s.sequence.is_some()).count();
+        let (mut placed, unanchored) = if tied > 0 {
+            let placed: Vec<Snapshot> = snapshots
+                .into_iter()
+                .filter(|s| s.sequence.is_some())
+                .collect();
+            (placed, total - tied)
+        } else {
+            (snapshots, 0)
+        };
+        placed.sort_by_key(|s| s.sequence);
+
+        let references = if self.embedding.is_available() {
+            let texts = vec![WARM_REFERENCE.to_string(), HOSTILE_REFERENCE.to_string()];
+            let mut embedded = self.embedding.embed_batch(&texts).await?;
+            let hostile = embedded.pop();
+            embedded.pop().zip(hostile)
+        } else {
+            None
+        };
+        let axis = |embedding: &[f32]| {
+            references
+                .as_ref()
+                .filter(|(warm, _)| warm.len() == embedding.len())
+                .map(|(warm, hostile)| axis_valence(embedding, warm, hostile))
+        };
+
+        let mut points = Vec::new();
+        for (i, snapshot) in placed.iter().enumerate() {
+            let event_id = snapshot.event_id.as_ref().map(|id| id.to_string());
+            let event_emotion = match &event_id {
+                Some(id) => get_annotation(&self.db, id, "emotion")
+                    .await?
+                    .and_then(|a| serde_json::from_value::<EmotionOutput>(a.output).ok()),
+                None => None,
+            };
+            let readings: Vec<f32> = axis(&snapshot.embedding)
+                .into_iter()
+                .chain(event_emotion.as_ref().map(emotion_valence))
+                .collect();
+            let Some(valence) = mean(&readings) else {
+                continue;
+            };
+            let label = match (&snapshot.event_title, i) {
+                (Some(title), _) => title.clone(),
+                (None, 0) => "baseline".to_string(),
+                (None, _) => format!("snapshot {}", i + 1),
+            };
+            points.push(SentimentPoint {
+                label,
+                event_id,
+                sequence: snapshot.sequence,
+                valence,
+                emotion: event_emotion.map(|e| e.dominant),
+            });
+        }
+
+        // The present perception closes the curve
+        let text = [edge.feelings.as_deref(), edge.perception.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let present_emotion = if self.emotion.is_available() && !text.trim().is_empty() {
+            self.emotion.classify_text(&text).await.ok()
+        } else {
+            None
+        };
+        let readings: Vec<f32> = edge
+            .embedding
+            .as_deref()
+            .and_then(axis)
+            .into_iter()
+            .chain(present_emotion.as_ref().map(emotion_valence))
+            .collect();
+        if let Some(valence) = mean(&readings) {
+            points.push(SentimentPoint {
+                label: "now".to_string(),
+                event_id: None,
+                sequence: None,
+                valence,
+                emotion: present_emotion.map(|e| e.dominant),
+            });
+        }
+
+        Ok(RelationshipSentiment {
+            observer: observer_id.to_string(),
+            observer_name: edge
+                .observer_name
+                .unwrap_or_else(|| observer_id.to_string()),
+            target: target_id.to_string(),
+            target_name: edge.target_name.unwrap_or_else(|| target_id.to_string()),
+            inflections: detect_inflections(&points, min_change),
+            trend: trend(&points).to_string(),
+            points,
+            unanchored,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::annotation::EmotionScore;
+
+    fn point(label: &str, valence: f32) -> SentimentPoint {
+        SentimentPoint {
+            label: label.to_string(),
+            event_id: None,
+            sequence: None,
+            valence,
+            emotion: None,
+        }
+    }
+
+    #[test]
+    fn test_emotion_valence() {
+        let output = EmotionOutput {
+            scores: vec![
+                EmotionScore {
+                    label: "anger".to_string(),
+                    score: 0.6,
+                },
+                EmotionScore {
+                    label: "love".to_string(),
+                    score: 0.2,
+                },
+                EmotionScore {
+                    label: "neutral".to_string(),
+                    score: 0.9,
+                },
+            ],
+            dominant: "neutral".to_string(),
+            active_count: 2,
+        };
+        assert!((emotion_valence(&output) + 0.5).abs() < 1e-6);
+        let neutral = EmotionOutput {
+            scores: vec![],
+            dominant: "neutral".to_string(),
+            active_count: 0,
+        };
+        assert_eq!(emotion_valence(&neutral), 0.0);
+    }
+
+    #[test]
+    fn test_axis_valence_leans_towards_nearer_reference() {
+        let warm = [1.0, 0.0];
+        let hostile = [0.0, 1.0];
+        assert!(axis_valence(&[0.9, 0.1], &warm, &hostile) > 0.0);
+        assert_eq!(axis_valence(&[0.0, 1.0], &warm, &hostile), -1.0);
+    }
+
+    #[test]
+    fn test_detect_inflections() {
+        let points = vec![
+            point("meet", 0.2),
+            point("dance", 0.5),
+            point("betrayal", -0.4),
+            point("exile", -0.5),
+            point("now", -0.5),
+        ];
+        let inflections = detect_inflections(&points, DEFAULT_MIN_CHANGE);
+        let kinds: Vec<(&str, &str)> = inflections
+            .iter()
+            .map(|i| (i.label.as_str(), i.kind.as_str()))
+            .collect();
+        assert_eq!(kinds, vec![("dance", "peak"), ("betrayal", "cooled")]);
+        assert_eq!(trend(&points), "cooling");
+        assert_eq!(trend(&points[..2]), "warming");
+    }
+}
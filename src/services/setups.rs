@@ -0,0 +1,339 @@
+//! Chekhov's gun tracker: setups that never fire and payoffs never set up.
+//!
+//! Knowledge is placed on the timeline by the events characters learn it at
+//! (`knows.event`), universe facts by the events and scenes they apply to.
+//! An element's first placement introduces it; it fires when it is referenced
+//! again later, or when a thread it belongs to pays off after it. Threads
+//! from the registry are paired setup → payoff by sequence, falling back to
+//! the earliest introduction of their elements when no setup is recorded.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::models::thread;
+use crate::NarraError;
+
+/// A knowledge or fact record and the sequences it is placed at.
+#[derive(Debug, Clone)]
+pub struct Element {
+    pub id: String,
+    /// "knowledge" or "fact"
+    pub kind: String,
+    pub label: String,
+    pub placements: Vec<i64>,
+}
+
+/// A registered thread resolved to sequence positions.
+#[derive(Debug, Clone)]
+pub struct ThreadPlacement {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub setup: Option<i64>,
+    pub payoff: Option<i64>,
+    pub elements: Vec<String>,
+}
+
+/// Something introduced and never referenced again.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnfiredSetup {
+    /// "thread", "knowledge" or "fact"
+    pub kind: String,
+    pub id: String,
+    pub label: String,
+    /// Thread the element belongs to, if any.
+    pub thread: Option<String>,
+    pub introduced_at: i64,
+    /// Sequences between the setup and the latest event.
+    pub distance: i64,
+}
+
+/// A thread payoff with no setup before it.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedPayoff {
+    pub thread_id: String,
+    pub name: String,
+    pub payoff_at: i64,
+    /// Present when the setup comes at or after the payoff.
+    pub setup_at: Option<i64>,
+    pub distance: Option<i64>,
+}
+
+/// A thread whose payoff follows its setup.
+#[derive(Debug, Clone, Serialize)]
+pub struct PairedThread {
+    pub thread_id: String,
+    pub name: String,
+    pub setup_at: i64,
+    pub payoff_at: i64,
+    pub distance: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupsReport {
+    pub latest_sequence: Option<i64>,
+    pub unfired: Vec<UnfiredSetup>,
+    pub orphaned_payoffs: Vec<OrphanedPayoff>,
+    pub paired: Vec<PairedThread>,
+}
+
+/// Pair setups with payoffs. `latest` is the last event sequence.
+pub fn track_setups(
+    latest: Option<i64>,
+    elements: &[Element],
+    threads: &[ThreadPlacement],
+) -> SetupsReport {
+    let introduced: HashMap<&str, i64> = elements
+        .iter()
+        .filter_map(|e| e.placements.iter().min().map(|&s| (e.id.as_str(), s)))
+        .collect();
+    let since = |s: i64| latest.map(|l| (l - s).max(0)).unwrap_or(0);
+
+    let mut unfired = Vec::new();
+    let mut orphaned_payoffs = Vec::new();
+    let mut paired = Vec::new();
+
+    for t in threads.iter().filter(|t| t.status != "abandoned") {
+        let setup = t.setup.or_else(|| {
+            t.elements
+                .iter()
+                .filter_map(|e| introduced.get(e.as_str()).copied())
+                .min()
+        });
+        match (setup, t.payoff) {
+            (Some(s), Some(p)) if p > s => paired.push(PairedThread {
+                thread_id: t.id.clone(),
+                name: t.name.clone(),
+                setup_at: s,
+                payoff_at: p,
+                distance: p - s,
+            }),
+            (s, Some(p)) => orphaned_payoffs.push(OrphanedPayoff {
+                thread_id: t.id.clone(),
+                name: t.name.clone(),
+                payoff_at: p,
+                setup_at: s,
+                distance: s.map(|s| p - s),
+            }),
+            (Some(s), None) if t.status == "open" => unfired.push(UnfiredSetup {
+                kind: "thread".to_string(),
+                id: t.id.clone(),
+                label: t.name.clone(),
+                thread: None,
+                introduced_at: s,
+                distance: since(s),
+            }),
+            _ => {}
+        }
+    }
+
+    for e in elements {
+        let Some(&intro) = introduced.get(e.id.as_str()) else {
+            continue;
+        };
+        if e.placements.iter().any(|&s| s > intro) {
+            continue;
+        }
+        let owners: Vec<&ThreadPlacement> = threads
+            .iter()
+            .filter(|t| t.elements.contains(&e.id))
+            .collect();
+        if owners
+            .iter()
+            .any(|t| t.status == "abandoned" || t.payoff.is_some_and(|p| p > intro))
+        {
+            continue;
+        }
+        unfired.push(UnfiredSetup {
+            kind: e.kind.clone(),
+            id: e.id.clone(),
+            label: e.label.clone(),
+            thread: owners.first().map(|t| t.id.clone()),
+            introduced_at: intro,
+            distance: since(intro),
+        });
+    }
+
+    unfired.sort_by(|a, b| b.distance.cmp(&a.distance).then(a.id.cmp(&b.id)));
+    paired.sort_by_key(|p| p.setup_at);
+    orphaned_payoffs.sort_by_key(|o| o.payoff_at);
+
+    SetupsReport {
+        latest_sequence: latest,
+        unfired,
+        orphaned_payoffs,
+        paired,
+    }
+}
+
+pub struct SetupsService {
+    db: Arc<NarraDb>,
+}
+
+impl SetupsService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    pub async fn report(&self) -> Result<SetupsReport, NarraError> {
+        #[derive(Deserialize)]
+        struct Positioned {
+            id: RecordId,
+            sequence: Option<i64>,
+        }
+        #[derive(Deserialize)]
+        struct Labelled {
+            id: RecordId,
+            label: String,
+        }
+        #[derive(Deserialize)]
+        struct Placement {
+            element: RecordId,
+            anchor: RecordId,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, sequence FROM event; \
+                 SELECT id, event.sequence AS sequence FROM scene; \
+                 SELECT id, fact AS label FROM knowledge; \
+                 SELECT id, title AS label FROM universe_fact; \
+                 SELECT out AS element, event AS anchor FROM knows \
+                 WHERE event != NONE AND record::tb(out) = 'knowledge'; \
+                 SELECT in AS element, out AS anchor FROM applies_to \
+                 WHERE record::tb(out) IN ['event', 'scene']",
+            )
+            .await?;
+        let events: Vec<Positioned> = response.take(0)?;
+        let scenes: Vec<Positioned> = response.take(1)?;
+        let knowledge: Vec<Labelled> = response.take(2)?;
+        let facts: Vec<Labelled> = response.take(3)?;
+        let mut placements: Vec<Placement> = response.take(4)?;
+        let applications: Vec<Placement> = response.take(5)?;
+        placements.extend(applications);
+
+        let latest = events.iter().filter_map(|e| e.sequence).max();
+        let sequence: HashMap<String, i64> = events
+            .into_iter()
+            .chain(scenes)
+            .filter_map(|p| p.sequence.map(|s| (p.id.to_string(), s)))
+            .collect();
+
+        let mut placed: HashMap<String, Vec<i64>> = HashMap::new();
+        for p in placements {
+            if let Some(&s) = sequence.get(&p.anchor.to_string()) {
+                placed.entry(p.element.to_string()).or_default().push(s);
+            }
+        }
+
+        let elements: Vec<Element> = knowledge
+            .into_iter()
+            .map(|k| ("knowledge", k))
+            .chain(facts.into_iter().map(|f| ("fact", f)))
+            .map(|(kind, row)| {
+                let id = row.id.to_string();
+                Element {
+                    placements: placed.remove(&id).unwrap_or_default(),
+                    id,
+                    kind: kind.to_string(),
+                    label: row.label,
+                }
+            })
+            .collect();
+
+        let position = |anchor: &Option<RecordId>| {
+            anchor
+                .as_ref()
+                .and_then(|a| sequence.get(&a.to_string()).copied())
+        };
+        let threads: Vec<ThreadPlacement> = thread::list_threads(&self.db, None)
+            .await?
+            .into_iter()
+            .map(|t| ThreadPlacement {
+                id: t.id.to_string(),
+                setup: position(&t.setup),
+                payoff: position(&t.payoff),
+                name: t.name,
+                status: t.status,
+                elements: t.elements.iter().map(|e| e.to_string()).collect(),
+            })
+            .collect();
+
+        Ok(track_setups(latest, &elements, &threads))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(id: &str, placements: &[i64]) -> Element {
+        Element {
+            id: id.to_string(),
+            kind: "knowledge".to_string(),
+            label: id.to_string(),
+            placements: placements.to_vec(),
+        }
+    }
+
+    fn thread(
+        id: &str,
+        setup: Option<i64>,
+        payoff: Option<i64>,
+        elements: &[&str],
+    ) -> ThreadPlacement {
+        ThreadPlacement {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: "open".to_string(),
+            setup,
+            payoff,
+            elements: elements.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_setups_pair_threads_and_flag_unfired() {
+        let elements = vec![
+            element("knowledge:gun", &[10]),
+            element("knowledge:letter", &[20, 60]),
+            element("knowledge:map", &[30]),
+            element("knowledge:unplaced", &[]),
+        ];
+        let threads = vec![
+            thread("thread:heist", None, Some(80), &["knowledge:map"]),
+            thread("thread:curse", Some(15), None, &[]),
+            thread("thread:twist", Some(70), Some(40), &[]),
+            thread("thread:cameo", None, Some(50), &[]),
+        ];
+
+        let report = track_setups(Some(100), &elements, &threads);
+
+        let unfired: Vec<(&str, i64)> = report
+            .unfired
+            .iter()
+            .map(|u| (u.id.as_str(), u.distance))
+            .collect();
+        assert_eq!(unfired, vec![("knowledge:gun", 90), ("thread:curse", 85)]);
+
+        assert_eq!(report.paired.len(), 1);
+        assert_eq!(report.paired[0].thread_id, "thread:heist");
+        assert_eq!(report.paired[0].setup_at, 30);
+        assert_eq!(report.paired[0].distance, 50);
+
+        let orphans: Vec<(&str, Option<i64>)> = report
+            .orphaned_payoffs
+            .iter()
+            .map(|o| (o.thread_id.as_str(), o.distance))
+            .collect();
+        assert_eq!(
+            orphans,
+            vec![("thread:twist", Some(-30)), ("thread:cameo", None)]
+        );
+    }
+}
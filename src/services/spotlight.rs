@@ -0,0 +1,278 @@
+//! Spotlight: who gets page time, and who has gone missing.
+//!
+//! Scenes are ordered by their event's sequence; scenes at planned events are
+//! not on the page yet and are left out. For each character we count
+//! scene appearances, measure the longest absence, and compute an importance
+//! score that decays with distance from the latest scene. Major characters
+//! (by declared role) who vanish for long stretches and minor characters who
+//! appear in an outsized share of scenes are flagged.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::NarraError;
+
+/// Declared roles that make a character "major".
+pub const MAJOR_ROLES: &[&str] = &["protagonist", "antagonist", "deuteragonist", "main", "lead"];
+
+/// Thresholds for spotlight flags.
+#[derive(Debug, Clone, Copy)]
+pub struct SpotlightOptions {
+    /// A major character absent for this many consecutive scenes is flagged.
+    pub max_gap: usize,
+    /// A minor character in more than this share of scenes is flagged.
+    pub minor_share: f32,
+    /// Scenes after which an appearance counts half as much.
+    pub half_life: f32,
+}
+
+impl Default for SpotlightOptions {
+    fn default() -> Self {
+        Self {
+            max_gap: 8,
+            minor_share: 0.3,
+            half_life: 10.0,
+        }
+    }
+}
+
+/// One character's page time.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpotlightEntry {
+    pub character_id: String,
+    pub name: String,
+    /// "major" or "minor"
+    pub tier: String,
+    pub appearances: usize,
+    /// Fraction of all scenes the character appears in.
+    pub share: f32,
+    /// 1-based position of the first and latest appearance.
+    pub first_scene: Option<usize>,
+    pub last_scene: Option<usize>,
+    /// Scenes since the latest appearance.
+    pub scenes_since: usize,
+    /// Longest run of consecutive scenes without the character, including
+    /// the run up to the latest scene.
+    pub longest_gap: usize,
+    /// Appearances weighted by recency (halving every `half_life` scenes).
+    pub importance: f32,
+    /// "vanished" (major, long absence) or "hogging" (minor, large share).
+    pub flag: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpotlightReport {
+    pub total_scenes: usize,
+    pub characters: Vec<SpotlightEntry>,
+}
+
+/// A character and the 0-based positions of the scenes they appear in.
+pub struct Presence {
+    pub character_id: String,
+    pub name: String,
+    pub roles: Vec<String>,
+    pub scenes: Vec<usize>,
+}
+
+fn is_major(roles: &[String]) -> bool {
+    roles
+        .iter()
+        .any(|r| MAJOR_ROLES.contains(&r.to_lowercase().as_str()))
+}
+
+/// Score every character against `total_scenes` ordered scenes.
+pub fn spotlight(
+    total_scenes: usize,
+    presence: Vec<Presence>,
+    options: &SpotlightOptions,
+) -> SpotlightReport {
+    let mut characters: Vec<SpotlightEntry> = presence
+        .into_iter()
+        .map(|p| {
+            let mut scenes = p.scenes;
+            scenes.sort_unstable();
+            scenes.dedup();
+
+            let mut longest_gap = 0;
+            let mut previous: Option<usize> = None;
+            for &s in &scenes {
+                let gap = match previous {
+                    Some(prev) => s - prev - 1,
+                    None => s,
+                };
+                longest_gap = longest_gap.max(gap);
+                previous = Some(s);
+            }
+            let scenes_since = match scenes.last() {
+                Some(&last) => total_scenes - last - 1,
+                None => total_scenes,
+            };
+            longest_gap = longest_gap.max(scenes_since);
+
+            let importance = scenes
+                .iter()
+                .map(|&s| 0.5f32.powf((total_scenes - s - 1) as f32 / options.half_life))
+                .sum();
+            let share = if total_scenes == 0 {
+                0.0
+            } else {
+                scenes.len() as f32 / total_scenes as f32
+            };
+
+            let major = is_major(&p.roles);
+            let flag = if major && longest_gap >= options.max_gap {
+                Some("vanished".to_string())
+            } else if !major && share > options.minor_share {
+                Some("hogging".to_string())
+            } else {
+                None
+            };
+
+            SpotlightEntry {
+                character_id: p.character_id,
+                name: p.name,
+                tier: if major { "major" } else { "minor" }.to_string(),
+                appearances: scenes.len(),
+                share,
+                first_scene: scenes.first().map(|s| s + 1),
+                last_scene: scenes.last().map(|s| s + 1),
+                scenes_since,
+                longest_gap,
+                importance,
+                flag,
+            }
+        })
+        .collect();
+
+    characters.sort_by(|a, b| {
+        b.flag
+            .is_some()
+            .cmp(&a.flag.is_some())
+            .then(b.importance.total_cmp(&a.importance))
+    });
+    SpotlightReport {
+        total_scenes,
+        characters,
+    }
+}
+
+pub struct SpotlightService {
+    db: Arc<NarraDb>,
+}
+
+impl SpotlightService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    pub async fn report(&self, options: &SpotlightOptions) -> Result<SpotlightReport, NarraError> {
+        #[derive(Deserialize)]
+        struct CharacterRow {
+            id: RecordId,
+            name: String,
+            roles: Vec<String>,
+        }
+        #[derive(Deserialize)]
+        struct ParticipationRow {
+            character: RecordId,
+            scene: RecordId,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT VALUE id FROM (SELECT id, event.sequence AS sequence, created_at \
+                 FROM scene WHERE event.narrative_status != 'planned' \
+                 ORDER BY sequence ASC, created_at ASC); \
+                 SELECT id, name, roles FROM character; \
+                 SELECT in AS character, out AS scene FROM participates_in",
+            )
+            .await?;
+        let scenes: Vec<RecordId> = response.take(0)?;
+        let characters: Vec<CharacterRow> = response.take(1)?;
+        let participation: Vec<ParticipationRow> = response.take(2)?;
+
+        let position: HashMap<String, usize> = scenes
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.to_string(), i))
+            .collect();
+        let mut appearances: HashMap<String, Vec<usize>> = HashMap::new();
+        for row in participation {
+            if let Some(&i) = position.get(&row.scene.to_string()) {
+                appearances
+                    .entry(row.character.to_string())
+                    .or_default()
+                    .push(i);
+            }
+        }
+
+        let presence = characters
+            .into_iter()
+            .map(|c| {
+                let character_id = c.id.to_string();
+                Presence {
+                    scenes: appearances.remove(&character_id).unwrap_or_default(),
+                    character_id,
+                    name: c.name,
+                    roles: c.roles,
+                }
+            })
+            .collect();
+        Ok(spotlight(position.len(), presence, options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn presence(name: &str, roles: &[&str], scenes: &[usize]) -> Presence {
+        Presence {
+            character_id: format!("character:{}", name),
+            name: name.to_string(),
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+            scenes: scenes.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_spotlight_flags_vanished_and_hogging() {
+        let options = SpotlightOptions {
+            max_gap: 5,
+            minor_share: 0.5,
+            half_life: 10.0,
+        };
+        let report = spotlight(
+            12,
+            vec![
+                presence("alice", &["Protagonist"], &[0, 1, 2, 3]),
+                presence("bob", &["antagonist"], &[0, 3, 6, 9, 11]),
+                presence("gray", &["bartender"], &[0, 1, 2, 4, 5, 6, 8, 10]),
+                presence("nobody", &[], &[]),
+            ],
+            &options,
+        );
+
+        let get = |name: &str| report.characters.iter().find(|c| c.name == name).unwrap();
+        let alice = get("alice");
+        assert_eq!(alice.tier, "major");
+        assert_eq!(alice.scenes_since, 8);
+        assert_eq!(alice.longest_gap, 8);
+        assert_eq!(alice.flag.as_deref(), Some("vanished"));
+
+        let bob = get("bob");
+        assert_eq!(bob.longest_gap, 2);
+        assert_eq!(bob.last_scene, Some(12));
+        assert!(bob.flag.is_none());
+        assert!(bob.importance > alice.importance);
+
+        assert_eq!(get("gray").flag.as_deref(), Some("hogging"));
+        assert_eq!(get("nobody").longest_gap, 12);
+        assert!(get("nobody").flag.is_none());
+    }
+}
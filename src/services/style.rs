@@ -0,0 +1,232 @@
+//! Manuscript linting against the project style sheet.
+//!
+//! Text is checked line by line, skipping fenced code blocks and inline
+//! code. Spelling and capitalization rules match whole words, so multi-word
+//! forms ("all right") work and "grey" never matches inside "greyhound".
+//! A capitalized first letter is accepted for capitalization rules, since
+//! the term may open a sentence.
+
+use serde::Serialize;
+
+use crate::models::style::{StyleRule, StyleRuleKind};
+
+/// Something in the text the style sheet disagrees with.
+#[derive(Debug, Clone, Serialize)]
+pub struct LintFinding {
+    pub rule_id: String,
+    pub kind: StyleRuleKind,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    pub found: String,
+    pub suggestion: String,
+    pub note: Option<String>,
+}
+
+/// Byte ranges of the words in `line` (letters, digits, inner apostrophes).
+fn word_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        let in_word = c.is_alphanumeric() || (c == '\'' && start.is_some());
+        match (in_word, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, line.len()));
+    }
+    // A trailing apostrophe closes a quote, not a word
+    spans
+        .into_iter()
+        .map(|(s, e)| (s, s + line[s..e].trim_end_matches('\'').len()))
+        .collect()
+}
+
+/// Where `phrase` occurs in `line` as whole words, ignoring case.
+fn phrase_matches(line: &str, spans: &[(usize, usize)], phrase: &str) -> Vec<(usize, usize)> {
+    let words: Vec<String> = word_spans(phrase)
+        .into_iter()
+        .map(|(s, e)| phrase[s..e].to_lowercase())
+        .collect();
+    if words.is_empty() || words.len() > spans.len() {
+        return Vec::new();
+    }
+    spans
+        .windows(words.len())
+        .filter(|window| {
+            window
+                .iter()
+                .zip(&words)
+                .all(|((s, e), w)| line[*s..*e].to_lowercase() == *w)
+        })
+        .map(|window| (window[0].0, window[window.len() - 1].1))
+        .collect()
+}
+
+/// `preferred` cased like `found`: all caps stays all caps, and a
+/// capitalized first letter carries over.
+fn match_case(found: &str, preferred: &str) -> String {
+    let letters: Vec<char> = found.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()) {
+        return preferred.to_uppercase();
+    }
+    let mut chars = preferred.chars();
+    match (found.chars().next(), chars.next()) {
+        (Some(f), Some(p)) if f.is_uppercase() && p.is_lowercase() => {
+            p.to_uppercase().chain(chars).collect()
+        }
+        _ => preferred.to_string(),
+    }
+}
+
+/// Whether two spellings differ in more than the case of the first letter.
+fn differs_past_first_letter(found: &str, preferred: &str) -> bool {
+    let mut f = found.chars();
+    let mut p = preferred.chars();
+    match (f.next(), p.next()) {
+        (Some(a), Some(b)) if a.to_lowercase().eq(b.to_lowercase()) => f.as_str() != p.as_str(),
+        _ => true,
+    }
+}
+
+/// Blank out inline code spans, keeping byte offsets.
+fn mask_inline_code(line: &str) -> String {
+    let mut in_code = false;
+    let mut masked = String::with_capacity(line.len());
+    for c in line.chars() {
+        if c == '`' {
+            in_code = !in_code;
+            masked.push(' ');
+        } else if in_code {
+            masked.push_str(&" ".repeat(c.len_utf8()));
+        } else {
+            masked.push(c);
+        }
+    }
+    masked
+}
+
+/// Check `text` against the style sheet.
+pub fn lint_text(text: &str, rules: &[StyleRule]) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let mut in_fence = false;
+
+    for (index, raw) in text.lines().enumerate() {
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let line = mask_inline_code(raw);
+        let spans = word_spans(&line);
+
+        for rule in rules {
+            let mut flag = |start: usize, end: usize, suggestion: String| {
+                findings.push(LintFinding {
+                    rule_id: rule.id.to_string(),
+                    kind: rule.kind,
+                    line: index + 1,
+                    column: raw[..start].chars().count() + 1,
+                    found: raw[start..end].to_string(),
+                    suggestion,
+                    note: rule.note.clone(),
+                });
+            };
+
+            match rule.kind {
+                StyleRuleKind::Punctuation => {
+                    for avoid in rule.avoid.iter().filter(|a| !a.is_empty()) {
+                        for (start, m) in line.match_indices(avoid.as_str()) {
+                            flag(start, start + m.len(), rule.preferred.clone());
+                        }
+                    }
+                }
+                StyleRuleKind::Spelling | StyleRuleKind::Capitalization => {
+                    for avoid in &rule.avoid {
+                        for (start, end) in phrase_matches(&line, &spans, avoid) {
+                            let suggestion = if rule.kind == StyleRuleKind::Spelling {
+                                match_case(&line[start..end], &rule.preferred)
+                            } else {
+                                rule.preferred.clone()
+                            };
+                            flag(start, end, suggestion);
+                        }
+                    }
+                    if rule.kind == StyleRuleKind::Capitalization {
+                        for (start, end) in phrase_matches(&line, &spans, &rule.preferred) {
+                            if differs_past_first_letter(&line[start..end], &rule.preferred) {
+                                flag(start, end, rule.preferred.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    findings.sort_by_key(|f| (f.line, f.column));
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(key: &str, kind: StyleRuleKind, preferred: &str, avoid: &[&str]) -> StyleRule {
+        StyleRule {
+            id: surrealdb::RecordId::from(("style_rule", key)),
+            kind,
+            preferred: preferred.to_string(),
+            avoid: avoid.iter().map(|a| a.to_string()).collect(),
+            note: None,
+            created_at: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_lint_flags_spelling_capitalization_and_punctuation() {
+        let rules = vec![
+            rule("grey", StyleRuleKind::Spelling, "grey", &["gray"]),
+            rule(
+                "allright",
+                StyleRuleKind::Spelling,
+                "all right",
+                &["alright"],
+            ),
+            rule("weave", StyleRuleKind::Capitalization, "the Weave", &[]),
+            rule("ellipsis", StyleRuleKind::Punctuation, "…", &["..."]),
+        ];
+        let text = "Gray skies over the weave...\n\
+                    The Weave is alright. A greyhound ran.\n\
+                    ```\n\
+                    gray in code\n\
+                    ```\n\
+                    Use `gray` sparingly; GRAY is loud.";
+
+        let findings = lint_text(text, &rules);
+        let summary: Vec<(usize, usize, &str, &str)> = findings
+            .iter()
+            .map(|f| (f.line, f.column, f.found.as_str(), f.suggestion.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, 1, "Gray", "Grey"),
+                (1, 17, "the weave", "the Weave"),
+                (1, 26, "...", "…"),
+                (2, 14, "alright", "all right"),
+                (6, 23, "GRAY", "GREY"),
+            ]
+        );
+    }
+}
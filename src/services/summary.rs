@@ -8,6 +8,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::repository::{EntityRepository, SurrealEntityRepository};
+use crate::services::token_counter::{HeuristicTokenCounter, TokenCounter};
 use crate::NarraError;
 
 /// Detail level for entity retrieval.
@@ -111,6 +112,10 @@ pub trait SummaryService: Send + Sync {
 
     /// Get estimated token count for an entity.
     async fn estimate_tokens(&self, entity_id: &str) -> Result<usize, NarraError>;
+
+    /// Compress arbitrary text to roughly `target_tokens` tokens.
+    /// Used by budget enforcement to shorten results instead of dropping them.
+    fn summarize_text(&self, text: &str, target_tokens: usize) -> String;
 }
 
 /// Cached implementation of SummaryService.
@@ -119,6 +124,7 @@ pub struct CachedSummaryService {
     /// Cache for entity summaries (id -> EntitySummary)
     summary_cache: Cache<String, EntitySummary>,
     config: SummaryConfig,
+    token_counter: Arc<dyn TokenCounter>,
 }
 
 impl CachedSummaryService {
@@ -133,6 +139,7 @@ impl CachedSummaryService {
             entity_repo: Arc::new(SurrealEntityRepository::new(db)),
             summary_cache,
             config,
+            token_counter: Arc::new(HeuristicTokenCounter),
         }
     }
 
@@ -141,6 +148,12 @@ impl CachedSummaryService {
         Self::new(db, SummaryConfig::default())
     }
 
+    /// Count and truncate tokens with `counter` instead of the ~4 chars/token heuristic.
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.token_counter = counter;
+        self
+    }
+
     /// Compute a version hash from entity content for cache invalidation.
     fn compute_version(content: &str) -> String {
         let mut hasher = DefaultHasher::new();
@@ -148,22 +161,37 @@ impl CachedSummaryService {
         format!("{:x}", hasher.finish())
     }
 
-    /// Estimate tokens for text (simple heuristic: ~4 chars per token).
-    fn estimate_tokens_for_text(text: &str) -> usize {
-        text.len().div_ceil(4)
+    /// Estimate tokens for text with the configured counter.
+    fn estimate_tokens_for_text(&self, text: &str) -> usize {
+        self.token_counter.count(text)
     }
 
     /// Generate a summary for entity content.
     /// Uses simple truncation with ellipsis - could be enhanced with LLM summarization.
     fn generate_summary(&self, full_content: &str) -> String {
-        let target_chars = self.config.summary_target * 4; // ~4 chars per token
+        self.truncate_to_tokens(full_content, self.config.summary_target)
+    }
 
-        if full_content.len() <= target_chars {
+    /// Truncate text to roughly `target_tokens`, preferring sentence then word boundaries.
+    fn truncate_to_tokens(&self, full_content: &str, target_tokens: usize) -> String {
+        if self.estimate_tokens_for_text(full_content) <= target_tokens {
             return full_content.to_string();
         }
 
+        // Longest prefix within budget, cut only at char boundaries
+        let boundaries: Vec<usize> = full_content.char_indices().map(|(i, _)| i).collect();
+        let (mut lo, mut hi) = (0, boundaries.len() - 1);
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if self.estimate_tokens_for_text(&full_content[..boundaries[mid]]) <= target_tokens {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
         // Find a good break point (sentence or word boundary)
-        let truncated = &full_content[..target_chars];
+        let truncated = &full_content[..boundaries[lo]];
 
         // Try to end at sentence boundary
         if let Some(pos) = truncated.rfind(". ") {
@@ -350,13 +378,13 @@ impl SummaryService for CachedSummaryService {
                 None => return Ok(None),
             };
 
-        let full_tokens = Self::estimate_tokens_for_text(&full_content);
+        let full_tokens = self.estimate_tokens_for_text(&full_content);
         let version = Self::compute_version(&full_content);
 
         let summary = if full_tokens > self.config.summary_threshold {
             // Needs summarization
             let summarized_content = self.generate_summary(&full_content);
-            let summary_tokens = Self::estimate_tokens_for_text(&summarized_content);
+            let summary_tokens = self.estimate_tokens_for_text(&summarized_content);
 
             EntitySummary {
                 id: entity_id.to_string(),
@@ -395,7 +423,7 @@ impl SummaryService for CachedSummaryService {
             None => return Ok(None),
         };
 
-        let estimated_tokens = Self::estimate_tokens_for_text(&content);
+        let estimated_tokens = self.estimate_tokens_for_text(&content);
 
         Ok(Some(EntityFullContent {
             id: entity_id.to_string(),
@@ -426,7 +454,11 @@ impl SummaryService for CachedSummaryService {
             None => return Ok(0),
         };
 
-        Ok(Self::estimate_tokens_for_text(&content))
+        Ok(self.estimate_tokens_for_text(&content))
+    }
+
+    fn summarize_text(&self, text: &str, target_tokens: usize) -> String {
+        self.truncate_to_tokens(text, target_tokens)
     }
 }
 
@@ -434,27 +466,42 @@ impl SummaryService for CachedSummaryService {
 mod tests {
     use super::*;
 
+    /// Helper to create a CachedSummaryService with custom config for testing generate_summary.
+    /// Uses a dummy DB that won't be called (we only test the pure generate_summary method).
+    fn make_summary_service(summary_target: usize) -> CachedSummaryService {
+        CachedSummaryService {
+            entity_repo: Arc::new(SurrealEntityRepository::new(Arc::new(
+                // We need a NarraDb but won't use it. Use a disconnected client.
+                surrealdb::Surreal::<surrealdb::engine::any::Any>::init(),
+            ))),
+            summary_cache: Cache::builder().max_capacity(1).build(),
+            config: SummaryConfig {
+                summary_threshold: 200,
+                summary_target,
+                cache_ttl_secs: 60,
+            },
+            token_counter: Arc::new(HeuristicTokenCounter),
+        }
+    }
+
     #[test]
     fn test_estimate_tokens_empty() {
-        assert_eq!(CachedSummaryService::estimate_tokens_for_text(""), 0);
+        let svc = make_summary_service(50);
+        assert_eq!(svc.estimate_tokens_for_text(""), 0);
     }
 
     #[test]
     fn test_estimate_tokens_short_text() {
+        let svc = make_summary_service(50);
         // "hello world" = 11 chars, ceil(11/4) = 3
-        assert_eq!(
-            CachedSummaryService::estimate_tokens_for_text("hello world"),
-            3
-        );
+        assert_eq!(svc.estimate_tokens_for_text("hello world"), 3);
     }
 
     #[test]
     fn test_estimate_tokens_exact_multiple() {
+        let svc = make_summary_service(50);
         // 8 chars, ceil(8/4) = 2
-        assert_eq!(
-            CachedSummaryService::estimate_tokens_for_text("abcdefgh"),
-            2
-        );
+        assert_eq!(svc.estimate_tokens_for_text("abcdefgh"), 2);
     }
 
     #[test]
@@ -471,23 +518,6 @@ mod tests {
         assert_ne!(v1, v2);
     }
 
-    /// Helper to create a CachedSummaryService with custom config for testing generate_summary.
-    /// Uses a dummy DB that won't be called (we only test the pure generate_summary method).
-    fn make_summary_service(summary_target: usize) -> CachedSummaryService {
-        CachedSummaryService {
-            entity_repo: Arc::new(SurrealEntityRepository::new(Arc::new(
-                // We need a NarraDb but won't use it. Use a disconnected client.
-                surrealdb::Surreal::<surrealdb::engine::any::Any>::init(),
-            ))),
-            summary_cache: Cache::builder().max_capacity(1).build(),
-            config: SummaryConfig {
-                summary_threshold: 200,
-                summary_target,
-                cache_ttl_secs: 60,
-            },
-        }
-    }
-
     #[test]
     fn test_generate_summary_under_target_returns_unchanged() {
         let svc = make_summary_service(50); // target_chars = 50 * 4 = 200
@@ -514,4 +544,45 @@ mod tests {
         // Should not cut mid-word
         assert!(!result.contains("threewo"));
     }
+
+    #[test]
+    fn test_summarize_text_respects_target() {
+        let svc = make_summary_service(50);
+        let text = "Alpha beta gamma. ".repeat(40);
+        let result = svc.summarize_text(&text, 20);
+        assert!(result.ends_with("..."));
+        assert!(svc.estimate_tokens_for_text(&result) <= 21);
+    }
+
+    #[test]
+    fn test_summarize_text_multibyte_boundary() {
+        let svc = make_summary_service(50);
+        // 'Ö' is two bytes, so byte 8 falls mid-character; a naive slice would panic
+        let text = "aÖÖÖÖÖÖÖÖÖÖÖÖÖÖÖÖ";
+        let result = svc.summarize_text(text, 2);
+        assert!(result.ends_with("..."));
+    }
+
+    /// One token per whitespace-separated word.
+    struct WordCounter;
+
+    impl TokenCounter for WordCounter {
+        fn count(&self, text: &str) -> usize {
+            text.split_whitespace().count()
+        }
+
+        fn name(&self) -> &str {
+            "words"
+        }
+    }
+
+    #[test]
+    fn test_summarize_text_uses_token_counter() {
+        let svc = make_summary_service(50).with_token_counter(Arc::new(WordCounter));
+        let text = "a bb ccc dddd eeeee ffffff";
+        // 26 chars would fit 10 heuristic tokens, but it is 6 words
+        assert_eq!(svc.summarize_text(text, 6), text);
+        assert_eq!(svc.summarize_text(text, 3), "a bb ccc...");
+        assert_eq!(svc.estimate_tokens_for_text(text), 6);
+    }
 }
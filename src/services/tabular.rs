@@ -0,0 +1,411 @@
+//! CSV import/export for the flat entity types (characters, locations, events).
+//!
+//! Columns are matched to spec fields by a mapping DSL such as
+//! `name=Name,role=Role,profile.wound=Backstory`. Fields without a mapping
+//! fall back to a header with the same name (case-insensitive), so a CSV
+//! written by `export-csv` reads back without any `--map`.
+//!
+//! List-valued fields (`aliases`, `profile.<key>`) are `;`-separated.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crate::mcp::types::{CharacterSpec, EventSpec, LocationSpec, NarraImport};
+use crate::NarraError;
+
+const LIST_SEPARATOR: char = ';';
+pub(crate) const PROFILE_PREFIX: &str = "profile.";
+
+/// Entity types that fit in a single table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabularType {
+    Character,
+    Location,
+    Event,
+}
+
+impl TabularType {
+    pub fn parse(s: &str) -> Result<Self, NarraError> {
+        match s.to_lowercase().trim_end_matches('s') {
+            "character" => Ok(Self::Character),
+            "location" => Ok(Self::Location),
+            "event" => Ok(Self::Event),
+            _ => Err(NarraError::Validation(format!(
+                "CSV supports characters, locations and events, not '{}'",
+                s
+            ))),
+        }
+    }
+
+    /// Guess the type from a file name like `characters.csv` or `cast-locations.csv`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let stem = path.file_stem()?.to_string_lossy().to_lowercase();
+        [
+            ("character", Self::Character),
+            ("location", Self::Location),
+            ("event", Self::Event),
+        ]
+        .into_iter()
+        .find(|(word, _)| stem.contains(word))
+        .map(|(_, kind)| kind)
+    }
+
+    pub fn plural(self) -> &'static str {
+        match self {
+            Self::Character => "characters",
+            Self::Location => "locations",
+            Self::Event => "events",
+        }
+    }
+
+    /// Spec fields in export column order; `profile.<key>` is handled separately.
+    pub fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Character => &["id", "name", "role", "aliases", "description"],
+            Self::Location => &["id", "name", "description", "parent_id", "loc_type"],
+            Self::Event => &[
+                "id",
+                "title",
+                "description",
+                "sequence",
+                "date",
+                "date_precision",
+            ],
+        }
+    }
+
+    fn required(self) -> &'static str {
+        match self {
+            Self::Event => "title",
+            _ => "name",
+        }
+    }
+}
+
+/// Spec field → CSV column header.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnMap {
+    columns: HashMap<String, String>,
+}
+
+impl ColumnMap {
+    /// Parse `field=Column,field=Column`. Unknown fields are rejected.
+    pub fn parse(dsl: &str, kind: TabularType) -> Result<Self, NarraError> {
+        let mut columns = HashMap::new();
+        for pair in dsl.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (field, column) = pair.split_once('=').ok_or_else(|| {
+                NarraError::Validation(format!("Expected field=Column in --map, got '{}'", pair))
+            })?;
+            let field = field.trim();
+            let known = kind.fields().contains(&field)
+                || (kind == TabularType::Character && field.starts_with(PROFILE_PREFIX));
+            if !known {
+                return Err(NarraError::Validation(format!(
+                    "Unknown {} field '{}' in --map (expected one of: {})",
+                    kind.plural(),
+                    field,
+                    kind.fields().join(", ")
+                )));
+            }
+            columns.insert(field.to_string(), column.trim().to_string());
+        }
+        Ok(Self { columns })
+    }
+
+    fn column<'a>(&'a self, field: &'a str) -> &'a str {
+        self.columns.get(field).map(String::as_str).unwrap_or(field)
+    }
+}
+
+fn csv_error(e: csv::Error) -> NarraError {
+    NarraError::Validation(format!("CSV: {}", e))
+}
+
+pub(crate) fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(LIST_SEPARATOR)
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Read rows into an import document of the given type.
+pub fn read_csv<R: Read>(
+    reader: R,
+    kind: TabularType,
+    map: &ColumnMap,
+) -> Result<NarraImport, NarraError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(String::from)
+        .collect();
+    let index_of = |column: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(column));
+
+    let mut fields: HashMap<&str, usize> = HashMap::new();
+    for field in kind.fields() {
+        if let Some(i) = index_of(map.column(field)) {
+            fields.insert(*field, i);
+        } else if map.columns.contains_key(*field) {
+            return Err(NarraError::Validation(format!(
+                "Column '{}' (mapped to {}) not found; headers are: {}",
+                map.column(field),
+                field,
+                headers.join(", ")
+            )));
+        }
+    }
+    if !fields.contains_key(kind.required()) {
+        return Err(NarraError::Validation(format!(
+            "No '{}' column; map one with --map {}=<Column>",
+            kind.required(),
+            kind.required()
+        )));
+    }
+
+    // Profile columns: explicit mappings plus any `profile.<key>` header
+    let mut profile: Vec<(String, usize)> = Vec::new();
+    if kind == TabularType::Character {
+        for (field, column) in &map.columns {
+            if let Some(key) = field.strip_prefix(PROFILE_PREFIX) {
+                let i = index_of(column).ok_or_else(|| {
+                    NarraError::Validation(format!("Column '{}' not found", column))
+                })?;
+                profile.push((key.to_string(), i));
+            }
+        }
+        for (i, header) in headers.iter().enumerate() {
+            if let Some(key) = header.strip_prefix(PROFILE_PREFIX) {
+                if !profile.iter().any(|(k, _)| k == key) {
+                    profile.push((key.to_string(), i));
+                }
+            }
+        }
+    }
+
+    let mut import = NarraImport::default();
+    for (row, record) in reader.records().enumerate() {
+        // Header is line 1
+        let line = row + 2;
+        let record = record.map_err(csv_error)?;
+        let get = |field: &str| {
+            fields
+                .get(field)
+                .and_then(|i| record.get(*i))
+                .filter(|v| !v.is_empty())
+                .map(String::from)
+        };
+        let required = get(kind.required()).ok_or_else(|| {
+            NarraError::Validation(format!("Line {}: empty {}", line, kind.required()))
+        })?;
+
+        match kind {
+            TabularType::Character => {
+                let profile: HashMap<String, Vec<String>> = profile
+                    .iter()
+                    .filter_map(|(key, i)| {
+                        let values = split_list(record.get(*i)?);
+                        (!values.is_empty()).then(|| (key.clone(), values))
+                    })
+                    .collect();
+                import.characters.push(CharacterSpec {
+                    id: get("id"),
+                    name: required,
+                    role: get("role"),
+                    aliases: get("aliases").map(|a| split_list(&a)),
+                    description: get("description"),
+                    profile: (!profile.is_empty()).then_some(profile),
+                });
+            }
+            TabularType::Location => import.locations.push(LocationSpec {
+                id: get("id"),
+                name: required,
+                description: get("description"),
+                parent_id: get("parent_id"),
+                loc_type: get("loc_type"),
+            }),
+            TabularType::Event => {
+                let sequence = get("sequence")
+                    .map(|s| {
+                        s.parse::<i32>().map_err(|_| {
+                            NarraError::Validation(format!(
+                                "Line {}: sequence '{}' is not a whole number",
+                                line, s
+                            ))
+                        })
+                    })
+                    .transpose()?;
+                import.events.push(EventSpec {
+                    id: get("id"),
+                    title: required,
+                    description: get("description"),
+                    sequence,
+                    date: get("date"),
+                    date_precision: get("date_precision"),
+                    narrative_status: None,
+                });
+            }
+        }
+    }
+    Ok(import)
+}
+
+/// Write the given type from an import document. Headers honour `map`, so
+/// the same mapping round-trips through a spreadsheet.
+pub fn write_csv<W: Write>(
+    writer: W,
+    kind: TabularType,
+    import: &NarraImport,
+    map: &ColumnMap,
+) -> Result<usize, NarraError> {
+    let mut writer = csv::Writer::from_writer(writer);
+    let opt = |v: &Option<String>| v.clone().unwrap_or_default();
+
+    let profile_keys: Vec<String> = match kind {
+        TabularType::Character => import
+            .characters
+            .iter()
+            .flat_map(|c| c.profile.iter().flat_map(|p| p.keys().cloned()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    };
+    let mut header: Vec<String> = kind
+        .fields()
+        .iter()
+        .map(|f| map.column(f).to_string())
+        .collect();
+    header.extend(profile_keys.iter().map(|key| {
+        let field = format!("{}{}", PROFILE_PREFIX, key);
+        map.columns.get(&field).cloned().unwrap_or(field)
+    }));
+    writer.write_record(&header).map_err(csv_error)?;
+
+    let separator = format!("{} ", LIST_SEPARATOR);
+    let rows: Vec<Vec<String>> = match kind {
+        TabularType::Character => import
+            .characters
+            .iter()
+            .map(|c| {
+                let mut row = vec![
+                    opt(&c.id),
+                    c.name.clone(),
+                    opt(&c.role),
+                    c.aliases.clone().unwrap_or_default().join(&separator),
+                    opt(&c.description),
+                ];
+                row.extend(profile_keys.iter().map(|key| {
+                    c.profile
+                        .as_ref()
+                        .and_then(|p| p.get(key))
+                        .map(|v| v.join(&separator))
+                        .unwrap_or_default()
+                }));
+                row
+            })
+            .collect(),
+        TabularType::Location => import
+            .locations
+            .iter()
+            .map(|l| {
+                vec![
+                    opt(&l.id),
+                    l.name.clone(),
+                    opt(&l.description),
+                    opt(&l.parent_id),
+                    opt(&l.loc_type),
+                ]
+            })
+            .collect(),
+        TabularType::Event => import
+            .events
+            .iter()
+            .map(|e| {
+                vec![
+                    opt(&e.id),
+                    e.title.clone(),
+                    opt(&e.description),
+                    e.sequence.map(|s| s.to_string()).unwrap_or_default(),
+                    opt(&e.date),
+                    opt(&e.date_precision),
+                ]
+            })
+            .collect(),
+    };
+    for row in &rows {
+        writer.write_record(row).map_err(csv_error)?;
+    }
+    writer.flush()?;
+    Ok(rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_with_map_and_roundtrip() {
+        let data = "Name,Role,Also Known As,Wound\n\
+                   Alice,protagonist,Al; Ally,lost her brother\n\
+                   Bob,,,\n";
+        let map = ColumnMap::parse(
+            "name=Name,role=Role,aliases=Also Known As,profile.wound=Wound",
+            TabularType::Character,
+        )
+        .unwrap();
+        let import = read_csv(data.as_bytes(), TabularType::Character, &map).unwrap();
+
+        assert_eq!(import.characters.len(), 2);
+        let alice = &import.characters[0];
+        assert_eq!(alice.role.as_deref(), Some("protagonist"));
+        assert_eq!(
+            alice.aliases.as_deref(),
+            Some(&["Al".into(), "Ally".into()][..])
+        );
+        assert_eq!(
+            alice.profile.as_ref().unwrap()["wound"],
+            vec!["lost her brother".to_string()]
+        );
+        assert!(import.characters[1].role.is_none());
+        assert!(import.characters[1].profile.is_none());
+
+        // Export without a map reads back without one
+        let mut out = Vec::new();
+        write_csv(
+            &mut out,
+            TabularType::Character,
+            &import,
+            &ColumnMap::default(),
+        )
+        .unwrap();
+        let again = read_csv(&out[..], TabularType::Character, &ColumnMap::default()).unwrap();
+        assert_eq!(again.characters[0].aliases, alice.aliases);
+        assert_eq!(again.characters[0].profile, alice.profile);
+    }
+
+    #[test]
+    fn test_read_errors_name_the_line() {
+        let map = ColumnMap::default();
+        let err = read_csv(
+            "title,sequence\nDuel,soon\n".as_bytes(),
+            TabularType::Event,
+            &map,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Line 2"));
+
+        assert!(read_csv("role\nhero\n".as_bytes(), TabularType::Character, &map).is_err());
+        assert!(ColumnMap::parse("colour=Colour", TabularType::Character).is_err());
+        assert_eq!(
+            TabularType::from_path(Path::new("cast/main-characters.csv")),
+            Some(TabularType::Character)
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! User-overridable output templates (Tera).
+//!
+//! Each template has a built-in version compiled into the binary. A
+//! `<name>.tera` file in `<data_path>/templates/` (this world) or
+//! `~/.narra/templates/` (every world) replaces it; the world directory wins.
+//!
+//! Templates receive the same data the command prints with `--json`, with
+//! record IDs flattened to `table:key` strings.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use tera::Tera;
+
+use crate::NarraError;
+
+pub const TEMPLATE_EXTENSION: &str = "tera";
+
+/// A template compiled into the binary.
+pub struct Builtin {
+    pub name: &'static str,
+    /// What renders with it
+    pub used_by: &'static str,
+    pub source: &'static str,
+}
+
+pub const BUILTINS: &[Builtin] = &[
+    Builtin {
+        name: "character",
+        used_by: "narra get character:<id>",
+        source: include_str!("templates/character.tera"),
+    },
+    Builtin {
+        name: "location",
+        used_by: "narra get location:<id>",
+        source: include_str!("templates/location.tera"),
+    },
+    Builtin {
+        name: "event",
+        used_by: "narra get event:<id>",
+        source: include_str!("templates/event.tera"),
+    },
+    Builtin {
+        name: "scene",
+        used_by: "narra get scene:<id>",
+        source: include_str!("templates/scene.tera"),
+    },
+    Builtin {
+        name: "dossier",
+        used_by: "narra analyze dossier",
+        source: include_str!("templates/dossier.tera"),
+    },
+    Builtin {
+        name: "situation-report-html",
+        used_by: "narra analyze situation-report --output <file>.html",
+        source: include_str!("templates/situation-report-html.tera"),
+    },
+    Builtin {
+        name: "dossier-html",
+        used_by: "narra analyze dossier --output <file>.html",
+        source: include_str!("templates/dossier-html.tera"),
+    },
+    Builtin {
+        name: "bible",
+        used_by: "narra world compile (Markdown before conversion)",
+        source: include_str!("templates/bible.tera"),
+    },
+];
+
+/// Where a template's effective version comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    Builtin,
+    User(PathBuf),
+    World(PathBuf),
+}
+
+impl TemplateSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Builtin => "builtin",
+            Self::User(_) => "user",
+            Self::World(_) => "world",
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Builtin => None,
+            Self::User(p) | Self::World(p) => Some(p),
+        }
+    }
+}
+
+pub struct TemplateInfo {
+    pub name: &'static str,
+    pub used_by: &'static str,
+    pub source: TemplateSource,
+}
+
+/// Templates for this world, built-ins overlaid with overrides.
+pub struct Templates {
+    tera: Tera,
+}
+
+fn tera_error(e: tera::Error) -> NarraError {
+    // Tera nests the useful message (line, column, cause) in the source chain
+    let mut message = e.to_string();
+    let mut source = std::error::Error::source(&e);
+    while let Some(cause) = source {
+        message.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
+    NarraError::Validation(format!("Template: {}", message))
+}
+
+pub fn world_dir(data_path: &Path) -> PathBuf {
+    data_path.join("templates")
+}
+
+pub fn user_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".narra").join("templates"))
+}
+
+pub fn builtin(name: &str) -> Option<&'static Builtin> {
+    BUILTINS.iter().find(|b| b.name == name)
+}
+
+fn file_name(name: &str) -> String {
+    format!("{}.{}", name, TEMPLATE_EXTENSION)
+}
+
+/// Effective source of every known template.
+pub fn list(data_path: &Path) -> Vec<TemplateInfo> {
+    BUILTINS
+        .iter()
+        .map(|b| TemplateInfo {
+            name: b.name,
+            used_by: b.used_by,
+            source: resolve(data_path, b.name),
+        })
+        .collect()
+}
+
+/// World override, then user override, then the built-in.
+pub fn resolve(data_path: &Path, name: &str) -> TemplateSource {
+    let world = world_dir(data_path).join(file_name(name));
+    if world.is_file() {
+        return TemplateSource::World(world);
+    }
+    if let Some(user) = user_dir().map(|d| d.join(file_name(name))) {
+        if user.is_file() {
+            return TemplateSource::User(user);
+        }
+    }
+    TemplateSource::Builtin
+}
+
+/// Replace RecordId objects (`{"tb": .., "id": {"String": ..}}`) with
+/// `table:key` strings so templates can print them directly.
+fn flatten_record_ids(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            if map.len() == 2 {
+                if let (Some(Value::String(tb)), Some(id)) = (map.get("tb"), map.get("id")) {
+                    let key = match id {
+                        Value::Object(inner) if inner.len() == 1 => {
+                            inner.values().next().cloned().unwrap_or(Value::Null)
+                        }
+                        other => other.clone(),
+                    };
+                    let key = match key {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    *value = Value::String(format!("{}:{}", tb, key));
+                    return;
+                }
+            }
+            map.values_mut().for_each(flatten_record_ids);
+        }
+        Value::Array(items) => items.iter_mut().for_each(flatten_record_ids),
+        _ => {}
+    }
+}
+
+impl Templates {
+    /// Built-in templates only.
+    pub fn builtin() -> Self {
+        let mut tera = Tera::default();
+        for b in BUILTINS {
+            tera.add_raw_template(b.name, b.source)
+                .expect("built-in templates parse");
+        }
+        Self { tera }
+    }
+
+    /// Built-ins with this world's and the user's overrides applied.
+    pub fn load(data_path: &Path) -> Result<Self, NarraError> {
+        let mut templates = Self::builtin();
+        for b in BUILTINS {
+            if let Some(path) = resolve(data_path, b.name).path() {
+                let source = std::fs::read_to_string(path)?;
+                templates
+                    .tera
+                    .add_raw_template(b.name, &source)
+                    .map_err(|e| {
+                        NarraError::Validation(format!("{}: {}", path.display(), tera_error(e)))
+                    })?;
+            }
+        }
+        Ok(templates)
+    }
+
+    pub fn render<T: Serialize>(&self, name: &str, data: &T) -> Result<String, NarraError> {
+        let mut value = serde_json::to_value(data)?;
+        flatten_record_ids(&mut value);
+        let context = tera::Context::from_value(value).map_err(tera_error)?;
+        self.tera.render(name, &context).map_err(tera_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_world_override_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = serde_json::json!({
+            "id": {"tb": "character", "id": {"String": "alice"}},
+            "name": "Alice",
+            "aliases": [],
+            "roles": ["detective"],
+            "profile": {},
+        });
+
+        let builtin = Templates::load(dir.path()).unwrap();
+        let out = builtin.render("character", &data).unwrap();
+        assert!(out.contains("Alice (character:alice)"));
+
+        std::fs::create_dir_all(world_dir(dir.path())).unwrap();
+        std::fs::write(
+            world_dir(dir.path()).join("character.tera"),
+            "{{ name | upper }} / {{ roles | join(sep=\",\") }}",
+        )
+        .unwrap();
+        assert!(matches!(
+            resolve(dir.path(), "character"),
+            TemplateSource::World(_)
+        ));
+        let custom = Templates::load(dir.path()).unwrap();
+        assert_eq!(
+            custom.render("character", &data).unwrap(),
+            "ALICE / detective"
+        );
+
+        std::fs::write(world_dir(dir.path()).join("character.tera"), "{{ name").unwrap();
+        assert!(Templates::load(dir.path()).is_err());
+    }
+}
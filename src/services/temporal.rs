@@ -3,19 +3,25 @@
 //! Clusters entities using a composite narrative distance metric that blends
 //! embedding similarity, event sequence proximity, and scene co-occurrence
 //! to detect "acts" or "arcs" in the story automatically.
+//!
+//! Backstory events give no sequence position: they happened before the
+//! story starts and would otherwise stretch the timeline phases are cut from.
 
 use crate::db::connection::NarraDb;
-use crate::models::phase::{self, PhaseCreate};
+use crate::db::query::parse_record_id;
+use crate::models::phase::{self, PhaseCreate, PhaseLabel};
+use crate::services::kmeans::{self, KMeansOptions};
 use crate::services::EntityType;
 use crate::NarraError;
 use async_trait::async_trait;
-use linfa::prelude::*;
-use linfa_clustering::KMeans;
-use ndarray::{Array1, Array2};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// Share of members (Jaccard) a re-detected phase must have in common with
+/// a custom label's phase to inherit the label.
+const LABEL_MIN_OVERLAP: f64 = 0.5;
+
 // ---------------------------------------------------------------------------
 // Types
 // ---------------------------------------------------------------------------
@@ -60,12 +66,22 @@ impl Default for PhaseWeights {
 pub struct NarrativePhase {
     pub phase_id: usize,
     pub label: String,
+    /// Generated label, set when `label` is a custom name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_label: Option<String>,
     pub members: Vec<PhaseMember>,
     pub member_count: usize,
     pub sequence_range: Option<(i64, i64)>,
     pub entity_type_counts: HashMap<String, usize>,
 }
 
+impl NarrativePhase {
+    /// Entity IDs of the phase's members.
+    pub fn member_ids(&self) -> HashSet<String> {
+        self.members.iter().map(|m| m.entity_id.clone()).collect()
+    }
+}
+
 /// A member within a narrative phase.
 #[derive(Debug, Clone, Serialize)]
 pub struct PhaseMember {
@@ -159,6 +175,20 @@ pub trait TemporalDataProvider: Send + Sync {
 
     /// Delete all saved phases. Returns count of deleted phases.
     async fn delete_all_phases(&self) -> Result<usize, NarraError>;
+
+    /// Custom phase labels, with the members of the phase each was given to.
+    async fn load_phase_labels(&self) -> Result<Vec<PhaseLabel>, NarraError>;
+
+    /// Replace all custom phase labels.
+    async fn save_phase_labels(&self, labels: &[PhaseLabel]) -> Result<(), NarraError>;
+
+    /// Rename a saved phase, keeping its generated label.
+    async fn set_phase_label(
+        &self,
+        phase_id: usize,
+        label: &str,
+        auto_label: &str,
+    ) -> Result<(), NarraError>;
 }
 
 // ---------------------------------------------------------------------------
@@ -318,6 +348,7 @@ impl TemporalDataProvider for SurrealTemporalDataProvider {
                 weights_neighborhood: result.weights_used.neighborhood as f64,
                 weights_temporal: result.weights_used.temporal as f64,
                 member_count: p.member_count as i64,
+                auto_label: p.auto_label.clone(),
             };
 
             phase::create_phase_with_id(&self.db, &phase_id, data).await?;
@@ -381,6 +412,7 @@ impl TemporalDataProvider for SurrealTemporalDataProvider {
             narrative_phases.push(NarrativePhase {
                 phase_id: p.phase_order as usize,
                 label: p.label.clone(),
+                auto_label: p.auto_label.clone(),
                 member_count: members.len(),
                 members,
                 sequence_range,
@@ -420,13 +452,34 @@ impl TemporalDataProvider for SurrealTemporalDataProvider {
     async fn delete_all_phases(&self) -> Result<usize, NarraError> {
         phase::delete_all_phases(&self.db).await
     }
+
+    async fn load_phase_labels(&self) -> Result<Vec<PhaseLabel>, NarraError> {
+        phase::list_phase_labels(&self.db).await
+    }
+
+    async fn save_phase_labels(&self, labels: &[PhaseLabel]) -> Result<(), NarraError> {
+        phase::replace_phase_labels(&self.db, labels).await
+    }
+
+    async fn set_phase_label(
+        &self,
+        phase_id: usize,
+        label: &str,
+        auto_label: &str,
+    ) -> Result<(), NarraError> {
+        phase::set_phase_label(&self.db, &format!("phase_{}", phase_id), label, auto_label).await?;
+        Ok(())
+    }
 }
 
 impl SurrealTemporalDataProvider {
     async fn get_max_sequence(&self) -> Result<Option<i64>, NarraError> {
         let mut resp = self
             .db
-            .query("SELECT math::max(sequence) AS max_seq FROM event GROUP ALL")
+            .query(
+                "SELECT math::max(sequence) AS max_seq FROM event \
+                 WHERE narrative_status != 'backstory' GROUP ALL",
+            )
             .await?;
 
         #[derive(serde::Deserialize)]
@@ -448,11 +501,15 @@ impl SurrealTemporalDataProvider {
             return Ok((vec![], vec![]));
         }
 
+        let entity = parse_record_id(entity_id)?;
         let (normalized, original) = match entity_type {
             EntityType::Event => {
                 // Events have direct sequence
-                let q = format!("SELECT sequence FROM {}", entity_id);
-                let mut resp = self.db.query(&q).await?;
+                let mut resp = self
+                    .db
+                    .query("SELECT sequence FROM $entity WHERE narrative_status != 'backstory'")
+                    .bind(("entity", entity))
+                    .await?;
 
                 #[derive(serde::Deserialize)]
                 struct SeqRow {
@@ -466,8 +523,14 @@ impl SurrealTemporalDataProvider {
             }
             EntityType::Scene => {
                 // Scenes link to events via event field
-                let q = format!("SELECT event.sequence AS seq FROM {}", entity_id);
-                let mut resp = self.db.query(&q).await?;
+                let mut resp = self
+                    .db
+                    .query(
+                        "SELECT event.sequence AS seq FROM $entity \
+                         WHERE event.narrative_status != 'backstory'",
+                    )
+                    .bind(("entity", entity))
+                    .await?;
 
                 #[derive(serde::Deserialize)]
                 struct SeqRow {
@@ -480,30 +543,40 @@ impl SurrealTemporalDataProvider {
                 (normalized, seqs)
             }
             EntityType::Character => {
-                // Characters participate in scenes which link to events
-                let q = format!(
-                    "SELECT out.event.sequence AS seq FROM participates_in WHERE in = {}",
-                    entity_id
-                );
-                let mut resp = self.db.query(&q).await?;
+                // Characters participate in scenes which link to events, and
+                // take part in events directly
+                let mut resp = self
+                    .db
+                    .query(
+                        "SELECT out.event.sequence AS seq FROM participates_in \
+                         WHERE in = $entity AND out.event.narrative_status != 'backstory'; \
+                         SELECT out.sequence AS seq FROM involved_in \
+                         WHERE in = $entity AND out.narrative_status != 'backstory'",
+                    )
+                    .bind(("entity", entity))
+                    .await?;
 
                 #[derive(serde::Deserialize)]
                 struct SeqRow {
                     seq: Option<i64>,
                 }
 
-                let rows: Vec<SeqRow> = resp.take(0).unwrap_or_default();
+                let mut rows: Vec<SeqRow> = resp.take(0).unwrap_or_default();
+                rows.extend(resp.take::<Vec<SeqRow>>(1).unwrap_or_default());
                 let seqs: Vec<i64> = rows.into_iter().filter_map(|r| r.seq).collect();
                 let normalized: Vec<f32> = seqs.iter().map(|&s| s as f32 / max_seq).collect();
                 (normalized, seqs)
             }
             EntityType::Location => {
                 // Locations appear in scenes which link to events
-                let q = format!(
-                    "SELECT event.sequence AS seq FROM scene WHERE location = {}",
-                    entity_id
-                );
-                let mut resp = self.db.query(&q).await?;
+                let mut resp = self
+                    .db
+                    .query(
+                        "SELECT event.sequence AS seq FROM scene \
+                         WHERE location = $entity AND event.narrative_status != 'backstory'",
+                    )
+                    .bind(("entity", entity))
+                    .await?;
 
                 #[derive(serde::Deserialize)]
                 struct SeqRow {
@@ -530,22 +603,94 @@ const POSITIONAL_DIMS: usize = 8;
 
 pub struct TemporalService {
     data: Arc<dyn TemporalDataProvider>,
+    options: KMeansOptions,
 }
 
 impl TemporalService {
     pub fn new(db: Arc<NarraDb>) -> Self {
+        Self::with_provider(Arc::new(SurrealTemporalDataProvider::new(db)))
+    }
+
+    pub fn with_provider(data: Arc<dyn TemporalDataProvider>) -> Self {
         Self {
-            data: Arc::new(SurrealTemporalDataProvider::new(db)),
+            data,
+            options: kmeans::configured(),
         }
     }
 
-    pub fn with_provider(data: Arc<dyn TemporalDataProvider>) -> Self {
-        Self { data }
+    /// Override the configured memory ceiling and seed.
+    pub fn with_options(mut self, options: KMeansOptions) -> Self {
+        self.options = options;
+        self
     }
 
     /// Save detected phases to persistent storage.
+    ///
+    /// Custom labels move to the members of the phase that now carries them.
     pub async fn save_phases(&self, result: &PhaseDetectionResult) -> Result<(), NarraError> {
-        self.data.save_phases(result).await
+        self.data.save_phases(result).await?;
+        let stored = self.data.load_phase_labels().await?;
+        let labels = refresh_custom_labels(&result.phases, stored);
+        self.data.save_phase_labels(&labels).await
+    }
+
+    /// Give a saved phase a custom label.
+    ///
+    /// `phase` is a phase ID (`phase:phase_2`, `phase_2`) or its number.
+    /// The label is kept across re-detection by the phase that overlaps the
+    /// renamed phase's members most.
+    pub async fn rename_phase(
+        &self,
+        phase: &str,
+        label: &str,
+    ) -> Result<NarrativePhase, NarraError> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(NarraError::Validation("Phase label cannot be empty".into()));
+        }
+        let saved = self.data.load_phases().await?.ok_or_else(|| {
+            NarraError::Validation(
+                "No saved phases; run `narra analyze phases --save` first".into(),
+            )
+        })?;
+
+        let key = phase.strip_prefix("phase:").unwrap_or(phase);
+        let number = key.strip_prefix("phase_").unwrap_or(key);
+        let mut target = saved
+            .phases
+            .into_iter()
+            .find(|p| p.phase_id.to_string() == number)
+            .ok_or_else(|| NarraError::NotFound {
+                entity_type: "phase".into(),
+                id: phase.to_string(),
+            })?;
+
+        let auto_label = target
+            .auto_label
+            .take()
+            .unwrap_or_else(|| target.label.clone());
+        self.data
+            .set_phase_label(target.phase_id, label, &auto_label)
+            .await?;
+
+        // Replace any label this phase carried before
+        let previous = (target.label != auto_label).then(|| target.label.clone());
+        let mut labels: Vec<PhaseLabel> = self
+            .data
+            .load_phase_labels()
+            .await?
+            .into_iter()
+            .filter(|l| Some(&l.label) != previous.as_ref() && l.label != label)
+            .collect();
+        labels.push(PhaseLabel {
+            label: label.to_string(),
+            members: target.members.iter().map(|m| m.entity_id.clone()).collect(),
+        });
+        self.data.save_phase_labels(&labels).await?;
+
+        target.label = label.to_string();
+        target.auto_label = Some(auto_label);
+        Ok(target)
     }
 
     /// Load previously saved phases.
@@ -576,6 +721,29 @@ impl TemporalService {
         self.detect_phases(entity_types, num_phases, weights).await
     }
 
+    /// One phase by number, from saved phases or a fresh detection.
+    pub async fn find_phase(&self, phase_id: usize) -> Result<NarrativePhase, NarraError> {
+        let result = self
+            .load_or_detect_phases(EntityType::embeddable(), None, None)
+            .await?;
+        let available: Vec<String> = result
+            .phases
+            .iter()
+            .map(|p| format!("{}: {}", p.phase_id, p.label))
+            .collect();
+        result
+            .phases
+            .into_iter()
+            .find(|p| p.phase_id == phase_id)
+            .ok_or_else(|| {
+                NarraError::Validation(format!(
+                    "Phase {} not found. Available: {}",
+                    phase_id,
+                    available.join(", ")
+                ))
+            })
+    }
+
     /// Auto-detect narrative phases via composite clustering.
     pub async fn detect_phases(
         &self,
@@ -620,8 +788,8 @@ impl TemporalService {
         let emb_dims = entity_data[0].embedding.len();
         let narrative_dims = emb_dims + emb_dims + POSITIONAL_DIMS; // content + neighborhood + temporal
 
-        // Compute narrative vectors
-        let mut narrative_vectors: Vec<Vec<f64>> = Vec::with_capacity(entities_with_embeddings);
+        // Compute narrative vectors (f32: half the memory of the f64 matrix)
+        let mut narrative_vectors: Vec<Vec<f32>> = Vec::with_capacity(entities_with_embeddings);
 
         for entity in &entity_data {
             let mut vec = Vec::with_capacity(narrative_dims);
@@ -630,7 +798,7 @@ impl TemporalService {
             let norm = l2_norm(&entity.embedding);
             for &v in &entity.embedding {
                 let normalized = if norm > 0.0 { v / norm } else { 0.0 };
-                vec.push((normalized * weights.content) as f64);
+                vec.push(normalized * weights.content);
             }
 
             // b) Neighborhood: average embedding of co-occurring entities
@@ -642,7 +810,7 @@ impl TemporalService {
                 emb_dims,
             );
             for v in &neighborhood {
-                vec.push((*v * weights.neighborhood) as f64);
+                vec.push(*v * weights.neighborhood);
             }
 
             // c) Temporal: positional encoding of median sequence position
@@ -653,22 +821,12 @@ impl TemporalService {
                 positional_encoding(median)
             };
             for v in &temporal {
-                vec.push((*v * weights.temporal) as f64);
+                vec.push(*v * weights.temporal);
             }
 
             narrative_vectors.push(vec);
         }
 
-        // Build matrix for k-means
-        let mut matrix_data = Vec::with_capacity(entities_with_embeddings * narrative_dims);
-        for nv in &narrative_vectors {
-            matrix_data.extend(nv);
-        }
-
-        let embedding_matrix =
-            Array2::from_shape_vec((entities_with_embeddings, narrative_dims), matrix_data)
-                .map_err(|e| NarraError::Database(format!("Failed to create matrix: {}", e)))?;
-
         let num_clusters = if let Some(n) = num_phases {
             n.max(2).min(entities_with_embeddings - 1)
         } else {
@@ -676,20 +834,9 @@ impl TemporalService {
             auto.max(2).min(entities_with_embeddings - 1)
         };
 
-        let dataset = DatasetBase::new(
-            embedding_matrix.clone(),
-            Array1::from_elem(entities_with_embeddings, ()),
-        );
-
-        let model = KMeans::params(num_clusters)
-            .max_n_iterations(300)
-            .tolerance(1e-4)
-            .fit(&dataset)
-            .map_err(|e| NarraError::Database(format!("K-means clustering failed: {}", e)))?;
-
-        let predictions = model.predict(&dataset);
-        let cluster_assignments: Vec<usize> = predictions.iter().cloned().collect();
-        let centroids = model.centroids();
+        let fit = kmeans::fit(&narrative_vectors, num_clusters, &self.options)?;
+        let cluster_assignments = fit.assignments;
+        let centroids = fit.centroids;
 
         // Group entities by cluster with soft multi-membership:
         // An entity belongs to its primary cluster, but also to any other cluster
@@ -701,14 +848,8 @@ impl TemporalService {
 
             // Compute distances to all centroids
             let mut distances: Vec<(usize, f64, f32)> = Vec::new();
-            for cluster_id in 0..centroids.nrows() {
-                let centroid = centroids.row(cluster_id);
-                let mut distance_sq = 0.0_f64;
-                for (i, &v) in entity_vec.iter().enumerate() {
-                    let diff = v - centroid[i];
-                    distance_sq += diff * diff;
-                }
-                let distance = distance_sq.sqrt();
+            for (cluster_id, centroid) in centroids.iter().enumerate() {
+                let distance = kmeans::distance_sq(entity_vec, centroid).sqrt();
                 let centrality = 1.0 / (1.0 + distance);
                 distances.push((cluster_id, distance, centrality as f32));
             }
@@ -833,6 +974,7 @@ impl TemporalService {
                 NarrativePhase {
                     phase_id: cluster_id,
                     label,
+                    auto_label: None,
                     members,
                     member_count,
                     sequence_range,
@@ -855,6 +997,9 @@ impl TemporalService {
             phase.phase_id = i;
         }
 
+        let labels = self.data.load_phase_labels().await?;
+        apply_custom_labels(&mut phases, &labels);
+
         Ok(PhaseDetectionResult {
             phases,
             total_entities,
@@ -1159,6 +1304,66 @@ fn phase_median_position(phase: &NarrativePhase) -> f32 {
     }
 }
 
+/// Jaccard overlap between a phase's members and a label's members.
+fn member_overlap(phase: &NarrativePhase, members: &HashSet<&str>) -> f64 {
+    let phase_members: HashSet<&str> = phase.members.iter().map(|m| m.entity_id.as_str()).collect();
+    let union = phase_members.union(members).count();
+    if union == 0 {
+        return 0.0;
+    }
+    phase_members.intersection(members).count() as f64 / union as f64
+}
+
+/// Give each custom label to the phase that overlaps its members most.
+///
+/// Pairs are matched greedily by overlap so each label goes to one phase
+/// and each phase gets at most one label.
+fn apply_custom_labels(phases: &mut [NarrativePhase], labels: &[PhaseLabel]) {
+    let label_members: Vec<HashSet<&str>> = labels
+        .iter()
+        .map(|l| l.members.iter().map(String::as_str).collect())
+        .collect();
+    let mut pairs: Vec<(f64, usize, usize)> = Vec::new();
+    for (p, phase) in phases.iter().enumerate() {
+        for (l, members) in label_members.iter().enumerate() {
+            let overlap = member_overlap(phase, members);
+            if overlap >= LABEL_MIN_OVERLAP {
+                pairs.push((overlap, p, l));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut phase_taken = vec![false; phases.len()];
+    let mut label_taken = vec![false; labels.len()];
+    for (_, p, l) in pairs {
+        if phase_taken[p] || label_taken[l] {
+            continue;
+        }
+        phase_taken[p] = true;
+        label_taken[l] = true;
+        let phase = &mut phases[p];
+        let auto_label = std::mem::replace(&mut phase.label, labels[l].label.clone());
+        phase.auto_label.get_or_insert(auto_label);
+    }
+}
+
+/// Labels after saving `phases`: a labelled phase's label now points at its
+/// current members; labels no phase carries are kept for later detections.
+fn refresh_custom_labels(phases: &[NarrativePhase], stored: Vec<PhaseLabel>) -> Vec<PhaseLabel> {
+    let mut labels: Vec<PhaseLabel> = phases
+        .iter()
+        .filter(|p| p.auto_label.is_some())
+        .map(|p| PhaseLabel {
+            label: p.label.clone(),
+            members: p.members.iter().map(|m| m.entity_id.clone()).collect(),
+        })
+        .collect();
+    let carried: HashSet<String> = labels.iter().map(|l| l.label.clone()).collect();
+    labels.extend(stored.into_iter().filter(|l| !carried.contains(&l.label)));
+    labels
+}
+
 type CooccurrenceMap = HashMap<String, Vec<String>>;
 
 fn build_cooccurrence_map(cooccurrences: &[(String, String, usize)]) -> CooccurrenceMap {
@@ -1290,6 +1495,7 @@ mod tests {
         total_count: usize,
         cooccurrences: Vec<(String, String, usize)>,
         saved_phases: Mutex<Option<PhaseDetectionResult>>,
+        labels: Mutex<Vec<PhaseLabel>>,
     }
 
     impl MockTemporalDataProvider {
@@ -1303,6 +1509,7 @@ mod tests {
                 total_count,
                 cooccurrences,
                 saved_phases: Mutex::new(None),
+                labels: Mutex::new(Vec::new()),
             }
         }
     }
@@ -1345,6 +1552,30 @@ mod tests {
             let had = self.saved_phases.lock().unwrap().take();
             Ok(had.map(|r| r.phases.len()).unwrap_or(0))
         }
+
+        async fn load_phase_labels(&self) -> Result<Vec<PhaseLabel>, NarraError> {
+            Ok(self.labels.lock().unwrap().clone())
+        }
+
+        async fn save_phase_labels(&self, labels: &[PhaseLabel]) -> Result<(), NarraError> {
+            *self.labels.lock().unwrap() = labels.to_vec();
+            Ok(())
+        }
+
+        async fn set_phase_label(
+            &self,
+            phase_id: usize,
+            label: &str,
+            auto_label: &str,
+        ) -> Result<(), NarraError> {
+            if let Some(saved) = self.saved_phases.lock().unwrap().as_mut() {
+                for p in saved.phases.iter_mut().filter(|p| p.phase_id == phase_id) {
+                    p.label = label.to_string();
+                    p.auto_label = Some(auto_label.to_string());
+                }
+            }
+            Ok(())
+        }
     }
 
     fn make_entity(
@@ -1972,4 +2203,105 @@ mod tests {
         let loaded = service.load_phases().await.unwrap();
         assert!(loaded.is_none());
     }
+
+    #[tokio::test]
+    async fn test_rename_survives_redetection() {
+        let provider = MockTemporalDataProvider::new(
+            vec![
+                make_entity("e1", "Early1", vec![1.0, 0.0, 0.0, 0.0], vec![0.0], vec![0]),
+                make_entity(
+                    "e2",
+                    "Early2",
+                    vec![0.9, 0.1, 0.0, 0.0],
+                    vec![0.1],
+                    vec![10],
+                ),
+                make_entity(
+                    "e3",
+                    "Early3",
+                    vec![0.95, 0.05, 0.0, 0.0],
+                    vec![0.2],
+                    vec![20],
+                ),
+                make_entity("l1", "Late1", vec![0.0, 0.0, 0.0, 1.0], vec![0.8], vec![80]),
+                make_entity("l2", "Late2", vec![0.0, 0.0, 0.1, 0.9], vec![0.9], vec![90]),
+                make_entity(
+                    "l3",
+                    "Late3",
+                    vec![0.0, 0.0, 0.05, 0.95],
+                    vec![1.0],
+                    vec![100],
+                ),
+            ],
+            6,
+            vec![],
+        );
+        let service = TemporalService::with_provider(Arc::new(provider));
+
+        // Renaming needs saved phases
+        assert!(service.rename_phase("1", "Act II").await.is_err());
+
+        let detected = service
+            .detect_phases(vec![EntityType::Character], Some(2), None)
+            .await
+            .unwrap();
+        service.save_phases(&detected).await.unwrap();
+        let generated = detected.phases[1].label.clone();
+
+        let renamed = service
+            .rename_phase("phase:phase_1", "Act II: The Siege")
+            .await
+            .unwrap();
+        assert_eq!(renamed.label, "Act II: The Siege");
+        assert_eq!(renamed.auto_label.as_deref(), Some(generated.as_str()));
+        assert!(matches!(
+            service.rename_phase("phase_9", "Nowhere").await,
+            Err(NarraError::NotFound { .. })
+        ));
+
+        let loaded = service.load_phases().await.unwrap().unwrap();
+        assert_eq!(loaded.phases[1].label, "Act II: The Siege");
+
+        // Re-detection finds the same members and keeps the name
+        let redetected = service
+            .detect_phases(vec![EntityType::Character], Some(2), None)
+            .await
+            .unwrap();
+        assert_eq!(redetected.phases[1].label, "Act II: The Siege");
+        assert_eq!(redetected.phases[0].auto_label, None);
+        service.save_phases(&redetected).await.unwrap();
+        let loaded = service.load_phases().await.unwrap().unwrap();
+        assert_eq!(loaded.phases[1].label, "Act II: The Siege");
+    }
+
+    #[test]
+    fn test_custom_label_needs_majority_overlap() {
+        let member = |id: &str| PhaseMember {
+            entity_id: id.to_string(),
+            entity_type: "character".to_string(),
+            name: id.to_string(),
+            centrality: 1.0,
+            sequence_position: None,
+        };
+        let phase = |ids: &[&str]| NarrativePhase {
+            phase_id: 0,
+            label: "generated".to_string(),
+            auto_label: None,
+            members: ids.iter().map(|id| member(id)).collect(),
+            member_count: ids.len(),
+            sequence_range: None,
+            entity_type_counts: HashMap::new(),
+        };
+        let labels = vec![PhaseLabel {
+            label: "Act I".to_string(),
+            members: vec!["a".into(), "b".into(), "c".into()],
+        }];
+
+        // 1 of 4 shared: below the threshold; 3 of 4: above
+        let mut phases = vec![phase(&["a", "d"]), phase(&["a", "b", "c", "e"])];
+        apply_custom_labels(&mut phases, &labels);
+        assert_eq!(phases[0].label, "generated");
+        assert_eq!(phases[1].label, "Act I");
+        assert_eq!(phases[1].auto_label.as_deref(), Some("generated"));
+    }
 }
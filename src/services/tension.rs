@@ -9,7 +9,7 @@
 //! Enriches detected tensions with emotion and theme annotations for severity weighting.
 
 use async_trait::async_trait;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
@@ -19,7 +19,7 @@ use crate::utils::sanitize::validate_key;
 use crate::NarraError;
 
 /// A detected narrative tension between two characters.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NarrativeTension {
     pub character_a_id: String,
     pub character_a_name: String,
@@ -37,7 +37,7 @@ pub struct NarrativeTension {
 }
 
 /// A single signal contributing to a tension.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TensionSignal {
     pub signal_type: String,
     pub detail: String,
@@ -436,17 +436,26 @@ impl TensionDataProvider for SurrealTensionDataProvider {
 /// Service for detecting narrative tensions between characters.
 pub struct TensionService {
     data: Arc<dyn TensionDataProvider>,
+    members: Option<HashSet<String>>,
 }
 
 impl TensionService {
     pub fn new(db: Arc<NarraDb>) -> Self {
+        Self::with_provider(Arc::new(SurrealTensionDataProvider::new(db)))
+    }
+
+    pub fn with_provider(data: Arc<dyn TensionDataProvider>) -> Self {
         Self {
-            data: Arc::new(SurrealTensionDataProvider::new(db)),
+            data,
+            members: None,
         }
     }
 
-    pub fn with_provider(data: Arc<dyn TensionDataProvider>) -> Self {
-        Self { data }
+    /// Restrict the analysis to these entity IDs (a phase's members);
+    /// `None` covers the whole world.
+    pub fn with_members(mut self, members: Option<HashSet<String>>) -> Self {
+        self.members = members;
+        self
     }
 
     /// Detect all narrative tensions across the character network.
@@ -455,7 +464,10 @@ impl TensionService {
         limit: usize,
         min_severity: f32,
     ) -> Result<TensionReport, NarraError> {
-        let characters = self.data.get_all_characters().await?;
+        let mut characters = self.data.get_all_characters().await?;
+        if let Some(members) = &self.members {
+            characters.retain(|c| members.contains(&c.id));
+        }
         let relationship_edges = self.data.get_all_relationship_edges().await?;
 
         // Build per-character ally/rival sets
@@ -661,6 +673,8 @@ mod tests {
             event: None,
             premises: None,
             truth_value: None,
+            derived_from: None,
+            derivation: None,
             learned_at: surrealdb::Datetime::from(past),
             created_at: Default::default(),
             updated_at: Default::default(),
@@ -0,0 +1,267 @@
+//! Glossary terms used before the reader meets them.
+//!
+//! A term's `first_use` event places its introduction on the timeline. Any
+//! scene ordered before that event whose summary mentions the term (by name
+//! or alias, as whole words, ignoring case) is flagged. Terms without a
+//! first use are listed with where they first turn up, so the author can
+//! anchor them.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::NarraError;
+
+/// A term and the sequence it is introduced at.
+#[derive(Debug, Clone)]
+pub struct TermPlacement {
+    pub id: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub introduced_at: Option<i64>,
+}
+
+/// A scene summary on the timeline.
+#[derive(Debug, Clone)]
+pub struct SceneText {
+    pub id: String,
+    pub title: String,
+    pub sequence: Option<i64>,
+    pub summary: String,
+}
+
+/// A scene that uses a term before its introduction.
+#[derive(Debug, Clone, Serialize)]
+pub struct EarlyUse {
+    pub term_id: String,
+    pub term: String,
+    /// The name or alias found in the summary
+    pub matched: String,
+    pub scene_id: String,
+    pub scene_title: String,
+    pub scene_sequence: i64,
+    pub introduced_at: i64,
+}
+
+/// A term used in scenes but never given a first use.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnanchoredTerm {
+    pub term_id: String,
+    pub term: String,
+    pub earliest_scene_id: String,
+    pub earliest_sequence: Option<i64>,
+    pub uses: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TerminologyReport {
+    pub terms: usize,
+    pub scenes: usize,
+    pub early_uses: Vec<EarlyUse>,
+    pub unanchored: Vec<UnanchoredTerm>,
+}
+
+/// Lowercased words, padded so phrases match on word boundaries.
+fn padded_words(text: &str) -> String {
+    let words: Vec<String> = text
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+/// The first of `names` that appears in `text` as whole words.
+fn mention<'a>(text: &str, names: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    names.into_iter().find(|name| {
+        let phrase = padded_words(name);
+        !phrase.trim().is_empty() && text.contains(&phrase)
+    })
+}
+
+/// Flag scenes that use terms before they are introduced.
+pub fn check_terminology(terms: &[TermPlacement], scenes: &[SceneText]) -> TerminologyReport {
+    let mut scenes: Vec<&SceneText> = scenes.iter().collect();
+    scenes.sort_by_key(|s| (s.sequence.is_none(), s.sequence));
+    let texts: Vec<String> = scenes.iter().map(|s| padded_words(&s.summary)).collect();
+
+    let mut early_uses = Vec::new();
+    let mut unanchored = Vec::new();
+    for term in terms {
+        let names: Vec<&str> = std::iter::once(term.name.as_str())
+            .chain(term.aliases.iter().map(|a| a.as_str()))
+            .collect();
+        let uses: Vec<(&SceneText, &str)> = scenes
+            .iter()
+            .zip(&texts)
+            .filter_map(|(scene, text)| mention(text, names.iter().copied()).map(|m| (*scene, m)))
+            .collect();
+
+        match term.introduced_at {
+            Some(intro) => {
+                for (scene, matched) in uses {
+                    let Some(seq) = scene.sequence.filter(|&s| s < intro) else {
+                        continue;
+                    };
+                    early_uses.push(EarlyUse {
+                        term_id: term.id.clone(),
+                        term: term.name.clone(),
+                        matched: matched.to_string(),
+                        scene_id: scene.id.clone(),
+                        scene_title: scene.title.clone(),
+                        scene_sequence: seq,
+                        introduced_at: intro,
+                    });
+                }
+            }
+            None => {
+                if let Some((first, _)) = uses.first() {
+                    unanchored.push(UnanchoredTerm {
+                        term_id: term.id.clone(),
+                        term: term.name.clone(),
+                        earliest_scene_id: first.id.clone(),
+                        earliest_sequence: first.sequence,
+                        uses: uses.len(),
+                    });
+                }
+            }
+        }
+    }
+
+    early_uses.sort_by(|a, b| {
+        a.scene_sequence
+            .cmp(&b.scene_sequence)
+            .then_with(|| a.term.cmp(&b.term))
+    });
+
+    TerminologyReport {
+        terms: terms.len(),
+        scenes: scenes.len(),
+        early_uses,
+        unanchored,
+    }
+}
+
+pub struct TerminologyService {
+    db: Arc<NarraDb>,
+}
+
+impl TerminologyService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    pub async fn report(&self) -> Result<TerminologyReport, NarraError> {
+        #[derive(Deserialize)]
+        struct TermRow {
+            id: RecordId,
+            name: String,
+            #[serde(default)]
+            aliases: Vec<String>,
+            sequence: Option<i64>,
+        }
+        #[derive(Deserialize)]
+        struct SceneRow {
+            id: RecordId,
+            title: String,
+            sequence: Option<i64>,
+            summary: Option<String>,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, name, aliases, first_use.sequence AS sequence FROM term; \
+                 SELECT id, title, event.sequence AS sequence, summary FROM scene",
+            )
+            .await?;
+        let terms: Vec<TermRow> = response.take(0)?;
+        let scenes: Vec<SceneRow> = response.take(1)?;
+
+        let terms: Vec<TermPlacement> = terms
+            .into_iter()
+            .map(|t| TermPlacement {
+                id: t.id.to_string(),
+                name: t.name,
+                aliases: t.aliases,
+                introduced_at: t.sequence,
+            })
+            .collect();
+        let scenes: Vec<SceneText> = scenes
+            .into_iter()
+            .filter_map(|s| {
+                Some(SceneText {
+                    id: s.id.to_string(),
+                    title: s.title,
+                    sequence: s.sequence,
+                    summary: s.summary?,
+                })
+            })
+            .collect();
+
+        Ok(check_terminology(&terms, &scenes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(id: &str, name: &str, aliases: &[&str], introduced_at: Option<i64>) -> TermPlacement {
+        TermPlacement {
+            id: id.to_string(),
+            name: name.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            introduced_at,
+        }
+    }
+
+    fn scene(id: &str, sequence: i64, summary: &str) -> SceneText {
+        SceneText {
+            id: id.to_string(),
+            title: id.to_string(),
+            sequence: Some(sequence),
+            summary: summary.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_flags_terms_used_before_introduction() {
+        let terms = vec![
+            term("term:weave", "the Weave", &["weaving"], Some(30)),
+            term("term:drift", "Drift", &[], None),
+            term("term:ash", "Ashcall", &[], Some(5)),
+        ];
+        let scenes = vec![
+            scene("scene:a", 10, "Mara feels THE WEAVE tremble."),
+            scene("scene:b", 20, "Old women talk of weaving storms."),
+            scene("scene:c", 30, "The Weave is revealed."),
+            scene("scene:d", 15, "A drifting boat; the Drift takes it."),
+            scene("scene:e", 25, "Nobody says Ashcall'd words."),
+            scene("scene:f", 35, "Drift again."),
+        ];
+
+        let report = check_terminology(&terms, &scenes);
+        let early: Vec<(&str, &str, &str)> = report
+            .early_uses
+            .iter()
+            .map(|e| (e.term_id.as_str(), e.scene_id.as_str(), e.matched.as_str()))
+            .collect();
+        assert_eq!(
+            early,
+            vec![
+                ("term:weave", "scene:a", "the Weave"),
+                ("term:weave", "scene:b", "weaving"),
+            ]
+        );
+
+        assert_eq!(report.unanchored.len(), 1);
+        let drift = &report.unanchored[0];
+        // "drifting" is a different word
+        assert_eq!(drift.earliest_scene_id, "scene:d");
+        assert_eq!(drift.uses, 2);
+    }
+}
@@ -20,9 +20,9 @@ use crate::NarraError;
 /// Default entailment threshold for considering a theme active.
 const THEME_THRESHOLD: f32 = 0.5;
 
-const THEME_MODEL_REPO: &str = "cross-encoder/nli-roberta-base";
+pub(crate) const THEME_MODEL_REPO: &str = "cross-encoder/nli-roberta-base";
 const THEME_MODEL_VERSION: &str = "nli-roberta-base-v1";
-const THEME_MODEL_TYPE: &str = "theme";
+pub(crate) const THEME_MODEL_TYPE: &str = "theme";
 
 /// Default narrative themes for fiction analysis.
 pub const DEFAULT_NARRATIVE_THEMES: &[&str] = &[
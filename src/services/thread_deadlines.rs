@@ -0,0 +1,250 @@
+//! Thread deadlines: threads that should have resolved by now.
+//!
+//! A thread's deadline is its `resolve_by` sequence, or the last sequence of
+//! its `resolve_by_phase`. The story's current position is the latest event
+//! that has a scene (what has been written), falling back to the latest
+//! event; planned events and their scenes are not written yet and don't
+//! count. Open threads past their deadline are overdue; threads whose payoff
+//! is placed after the deadline resolve late.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::models::thread;
+use crate::NarraError;
+
+/// A thread with its deadline resolved to a sequence.
+#[derive(Debug, Clone)]
+pub struct DeadlineThread {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    /// As entered: "40" or "Act 3"
+    pub label: String,
+    /// None when the phase is unknown
+    pub deadline: Option<i64>,
+    pub payoff_sequence: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadlineCheck {
+    pub thread_id: String,
+    pub name: String,
+    pub status: String,
+    pub resolve_by: String,
+    pub deadline: Option<i64>,
+    pub payoff_sequence: Option<i64>,
+    /// Deadline minus current position; negative when past
+    pub remaining: Option<i64>,
+    /// "overdue", "payoff_after_deadline", "due_soon", "unknown_phase" or "on_track"
+    pub flag: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadlineReport {
+    pub current_sequence: Option<i64>,
+    pub threads: Vec<DeadlineCheck>,
+}
+
+impl DeadlineReport {
+    pub fn flagged(&self) -> impl Iterator<Item = &DeadlineCheck> {
+        self.threads.iter().filter(|t| t.flag != "on_track")
+    }
+}
+
+/// Check each thread's deadline against the current position. Threads due
+/// within `window` sequence steps are "due_soon".
+pub fn check_deadlines(
+    threads: Vec<DeadlineThread>,
+    current: Option<i64>,
+    window: i64,
+) -> DeadlineReport {
+    let mut checks: Vec<DeadlineCheck> = threads
+        .into_iter()
+        .filter(|t| t.status != "abandoned")
+        .map(|t| {
+            let remaining = t.deadline.zip(current).map(|(d, c)| d - c);
+            let open = t.status == "open";
+            let flag = match (t.deadline, remaining) {
+                (None, _) => "unknown_phase",
+                (Some(d), _) if t.payoff_sequence.is_some_and(|p| p > d) => "payoff_after_deadline",
+                (_, Some(r)) if open && r < 0 => "overdue",
+                (_, Some(r)) if open && r <= window => "due_soon",
+                _ => "on_track",
+            };
+            DeadlineCheck {
+                thread_id: t.id,
+                name: t.name,
+                status: t.status,
+                resolve_by: t.label,
+                deadline: t.deadline,
+                payoff_sequence: t.payoff_sequence,
+                remaining,
+                flag: flag.to_string(),
+            }
+        })
+        .collect();
+
+    let severity = |flag: &str| match flag {
+        "overdue" => 0,
+        "payoff_after_deadline" => 1,
+        "due_soon" => 2,
+        "unknown_phase" => 3,
+        _ => 4,
+    };
+    checks.sort_by(|a, b| {
+        severity(&a.flag)
+            .cmp(&severity(&b.flag))
+            .then(a.deadline.cmp(&b.deadline))
+    });
+    DeadlineReport {
+        current_sequence: current,
+        threads: checks,
+    }
+}
+
+pub struct ThreadDeadlineService {
+    db: Arc<NarraDb>,
+}
+
+impl ThreadDeadlineService {
+    pub fn new(db: Arc<NarraDb>) -> Self {
+        Self { db }
+    }
+
+    /// Check deadlines at `at`, or at the current writing position.
+    pub async fn report(&self, at: Option<i64>, window: i64) -> Result<DeadlineReport, NarraError> {
+        #[derive(Deserialize)]
+        struct PhaseRow {
+            name: String,
+            label: String,
+            sequence_range_max: Option<i64>,
+        }
+        #[derive(Deserialize)]
+        struct Positioned {
+            id: RecordId,
+            sequence: Option<i64>,
+            planned: bool,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT name, label, sequence_range_max FROM phase; \
+                 SELECT id, sequence, narrative_status = 'planned' AS planned FROM event; \
+                 SELECT id, event.sequence AS sequence, \
+                 event.narrative_status = 'planned' AS planned FROM scene",
+            )
+            .await?;
+        let phases: Vec<PhaseRow> = response.take(0)?;
+        let events: Vec<Positioned> = response.take(1)?;
+        let scenes: Vec<Positioned> = response.take(2)?;
+
+        let written = |rows: &[Positioned]| {
+            rows.iter()
+                .filter(|p| !p.planned)
+                .filter_map(|p| p.sequence)
+                .max()
+        };
+        let current = at.or_else(|| written(&scenes).or_else(|| written(&events)));
+        let sequence: HashMap<String, i64> = events
+            .into_iter()
+            .chain(scenes)
+            .filter_map(|p| p.sequence.map(|s| (p.id.to_string(), s)))
+            .collect();
+        let phase_end = |wanted: &str| {
+            phases
+                .iter()
+                .find(|p| {
+                    p.label.eq_ignore_ascii_case(wanted) || p.name.eq_ignore_ascii_case(wanted)
+                })
+                .and_then(|p| p.sequence_range_max)
+        };
+
+        let threads = thread::list_threads(&self.db, None)
+            .await?
+            .into_iter()
+            .filter_map(|t| {
+                let (label, deadline) = match (t.resolve_by, &t.resolve_by_phase) {
+                    (Some(s), _) => (s.to_string(), Some(s)),
+                    (None, Some(phase)) => (phase.clone(), phase_end(phase)),
+                    (None, None) => return None,
+                };
+                Some(DeadlineThread {
+                    id: t.id.to_string(),
+                    payoff_sequence: t
+                        .payoff
+                        .as_ref()
+                        .and_then(|p| sequence.get(&p.to_string()).copied()),
+                    name: t.name,
+                    status: t.status,
+                    label,
+                    deadline,
+                })
+            })
+            .collect();
+
+        Ok(check_deadlines(threads, current, window))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread(
+        name: &str,
+        status: &str,
+        deadline: Option<i64>,
+        payoff: Option<i64>,
+    ) -> DeadlineThread {
+        DeadlineThread {
+            id: format!("thread:{}", name),
+            name: name.to_string(),
+            status: status.to_string(),
+            label: deadline
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "Act 9".to_string()),
+            deadline,
+            payoff_sequence: payoff,
+        }
+    }
+
+    #[test]
+    fn test_deadlines_against_current_position() {
+        let report = check_deadlines(
+            vec![
+                thread("heist", "open", Some(30), None),
+                thread("romance", "open", Some(55), None),
+                thread("feud", "resolved", Some(20), Some(25)),
+                thread("quest", "open", Some(90), Some(80)),
+                thread("ghost", "open", None, None),
+                thread("dropped", "abandoned", Some(10), None),
+            ],
+            Some(50),
+            10,
+        );
+
+        let flags: Vec<(&str, &str)> = report
+            .threads
+            .iter()
+            .map(|t| (t.name.as_str(), t.flag.as_str()))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("heist", "overdue"),
+                ("feud", "payoff_after_deadline"),
+                ("romance", "due_soon"),
+                ("ghost", "unknown_phase"),
+                ("quest", "on_track"),
+            ]
+        );
+        assert_eq!(report.threads[0].remaining, Some(-20));
+        assert_eq!(report.flagged().count(), 4);
+    }
+}
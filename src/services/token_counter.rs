@@ -0,0 +1,140 @@
+//! Token counting for budget enforcement.
+//!
+//! Budgets are expressed in model tokens, so counting characters / 4 under- or
+//! over-shoots depending on language and punctuation. `TokenCounter` abstracts
+//! the count so budget enforcement and the context packer can use a real BPE
+//! tokenizer when one is available, and fall back to the heuristic otherwise.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use tokenizers::Tokenizer;
+
+/// Default HuggingFace tokenizer used for counting (Claude-compatible BPE vocabulary).
+pub const DEFAULT_TOKENIZER_REPO: &str = "Xenova/claude-tokenizer";
+
+/// Counts tokens in text.
+pub trait TokenCounter: Send + Sync {
+    /// Number of tokens `text` occupies.
+    fn count(&self, text: &str) -> usize;
+
+    /// Short identifier for diagnostics (e.g., "heuristic", "Xenova/claude-tokenizer").
+    fn name(&self) -> &str;
+}
+
+/// Character-based estimate: ~4 chars per token.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicTokenCounter;
+
+impl TokenCounter for HeuristicTokenCounter {
+    fn count(&self, text: &str) -> usize {
+        text.len().div_ceil(4)
+    }
+
+    fn name(&self) -> &str {
+        "heuristic"
+    }
+}
+
+/// Exact counts via a HuggingFace `tokenizer.json`.
+pub struct HfTokenCounter {
+    tokenizer: Tokenizer,
+    name: String,
+}
+
+impl HfTokenCounter {
+    /// Load a tokenizer from a local `tokenizer.json` file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let mut tokenizer = Tokenizer::from_file(path)
+            .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?;
+        // Counting must see the whole text — never truncate or pad.
+        let _ = tokenizer.with_truncation(None);
+        tokenizer.with_padding(None);
+
+        Ok(Self {
+            tokenizer,
+            name: path.display().to_string(),
+        })
+    }
+
+    /// Download (or reuse the cached) `tokenizer.json` from a HuggingFace repo.
+    pub fn from_pretrained(repo_id: &str) -> Result<Self> {
+        let path = crate::embedding::hub::fetch_file(repo_id, "tokenizer.json")
+            .context("Failed to get tokenizer.json")?;
+
+        let mut counter = Self::from_file(&path)?;
+        counter.name = repo_id.to_string();
+        Ok(counter)
+    }
+}
+
+impl TokenCounter for HfTokenCounter {
+    fn count(&self, text: &str) -> usize {
+        match self.tokenizer.encode(text, false) {
+            Ok(encoding) => encoding.len(),
+            // Never fail a budget check over a tokenizer hiccup
+            Err(_) => HeuristicTokenCounter.count(text),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Build the token counter selected by `NARRA_TOKENIZER`.
+///
+/// - unset: `DEFAULT_TOKENIZER_REPO`
+/// - `heuristic`: character-based estimate, no model download
+/// - a path to an existing `tokenizer.json`: load it directly
+/// - anything else: treated as a HuggingFace repo ID
+///
+/// Degrades gracefully to the heuristic if the tokenizer can't be loaded.
+pub fn create_token_counter() -> Arc<dyn TokenCounter> {
+    let spec = std::env::var("NARRA_TOKENIZER").unwrap_or_else(|_| DEFAULT_TOKENIZER_REPO.into());
+
+    if spec.eq_ignore_ascii_case("heuristic") {
+        return Arc::new(HeuristicTokenCounter);
+    }
+
+    let path = Path::new(&spec);
+    let loaded = if path.is_file() {
+        HfTokenCounter::from_file(path)
+    } else {
+        HfTokenCounter::from_pretrained(&spec)
+    };
+
+    match loaded {
+        Ok(counter) => {
+            tracing::info!("Token counter loaded: {}", counter.name());
+            Arc::new(counter)
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Tokenizer '{}' unavailable ({}), using ~4 chars/token estimate",
+                spec,
+                e
+            );
+            Arc::new(HeuristicTokenCounter)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_counts() {
+        assert_eq!(HeuristicTokenCounter.count(""), 0);
+        assert_eq!(HeuristicTokenCounter.count("hello world"), 3);
+        assert_eq!(HeuristicTokenCounter.count("abcdefgh"), 2);
+    }
+
+    #[test]
+    fn test_missing_tokenizer_file_errors() {
+        let result = HfTokenCounter::from_file(Path::new("/nonexistent/tokenizer.json"));
+        assert!(result.is_err());
+    }
+}
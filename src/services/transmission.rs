@@ -0,0 +1,257 @@
+//! Certainty propagation when knowledge is passed on.
+//!
+//! A character told something by another character believes it as much as
+//! the teller did, tempered by trust: a friend's certainty carries over, a
+//! stranger's is weakened, an enemy's is doubted. Trust is the recipient's
+//! trust in the source, set on their perception or inferred from their
+//! relationships and history (see `services::trust`). The table mapping
+//! (trust, source certainty) to the recipient's certainty has built-in
+//! defaults that `[transmission.<trust>]` config entries override.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
+use crate::models::knowledge::KnowledgeState;
+use crate::models::CertaintyLevel;
+use crate::services::ontology::RelationshipOntology;
+use crate::services::trust::estimate_trust;
+use crate::NarraError;
+
+/// How far a recipient trusts the character passing knowledge on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trust {
+    /// Trust at or above `services::trust::TRUSTED_AT`
+    Trusted,
+    /// Trust between the two thresholds
+    Neutral,
+    /// Trust at or below `services::trust::DISTRUSTED_AT`
+    Distrusted,
+}
+
+impl std::fmt::Display for Trust {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Trust::Trusted => "trusted",
+            Trust::Neutral => "neutral",
+            Trust::Distrusted => "distrusted",
+        })
+    }
+}
+
+/// Built-in rules: source certainty -> (trusted, neutral, distrusted).
+/// Forgotten knowledge can't be passed on.
+const DEFAULT_RULES: &[(CertaintyLevel, [CertaintyLevel; 3])] = {
+    use CertaintyLevel::*;
+    &[
+        (Knows, [Knows, Suspects, Uncertain]),
+        (
+            BelievesWrongly,
+            [BelievesWrongly, BelievesWrongly, Uncertain],
+        ),
+        (Suspects, [Suspects, Uncertain, Uncertain]),
+        (Assumes, [Assumes, Uncertain, Uncertain]),
+        (Uncertain, [Uncertain, Uncertain, Uncertain]),
+        (Denies, [Denies, Denies, Uncertain]),
+    ]
+};
+
+/// Parse a snake_case certainty level ("believes_wrongly").
+pub fn parse_certainty(value: &str) -> Option<CertaintyLevel> {
+    serde_json::from_value(serde_json::Value::String(value.trim().to_lowercase())).ok()
+}
+
+fn certainty_name(certainty: CertaintyLevel) -> String {
+    serde_json::to_value(certainty)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// The recipient's certainty for each (trust, source certainty).
+#[derive(Debug, Clone)]
+pub struct TransmissionRules {
+    rules: Vec<(Trust, CertaintyLevel, CertaintyLevel)>,
+}
+
+impl Default for TransmissionRules {
+    fn default() -> Self {
+        let trusts = [Trust::Trusted, Trust::Neutral, Trust::Distrusted];
+        let rules = DEFAULT_RULES
+            .iter()
+            .flat_map(|(source, outcomes)| {
+                trusts
+                    .iter()
+                    .zip(outcomes)
+                    .map(|(trust, outcome)| (*trust, *source, *outcome))
+            })
+            .collect();
+        Self { rules }
+    }
+}
+
+impl TransmissionRules {
+    /// Built-in rules with overrides, given as source certainty -> recipient
+    /// certainty names per trust level.
+    pub fn with_overrides(
+        overrides: &[(Trust, &BTreeMap<String, String>)],
+    ) -> Result<Self, NarraError> {
+        let mut rules = Self::default();
+        for (trust, entries) in overrides {
+            for (source, outcome) in entries.iter() {
+                let invalid = |name: &str| {
+                    NarraError::Validation(format!(
+                        "transmission.{}: '{}' is not a certainty level that can be passed on",
+                        trust, name
+                    ))
+                };
+                let source = parse_certainty(source)
+                    .filter(|c| *c != CertaintyLevel::Forgotten)
+                    .ok_or_else(|| invalid(source))?;
+                let outcome = parse_certainty(outcome).ok_or_else(|| invalid(outcome))?;
+                rules
+                    .rules
+                    .retain(|(t, s, _)| !(t == trust && *s == source));
+                rules.rules.push((*trust, source, outcome));
+            }
+        }
+        Ok(rules)
+    }
+
+    /// The recipient's certainty, or None when the source's can't be passed on.
+    pub fn apply(&self, trust: Trust, source: CertaintyLevel) -> Option<CertaintyLevel> {
+        self.rules
+            .iter()
+            .find(|(t, s, _)| *t == trust && *s == source)
+            .map(|(_, _, outcome)| *outcome)
+    }
+}
+
+/// How far `recipient` trusts `source`, and what decided it.
+pub async fn trust_between(
+    db: &NarraDb,
+    ontology: &RelationshipOntology,
+    recipient: &str,
+    source: &str,
+) -> Result<(Trust, Option<String>), NarraError> {
+    let estimate = estimate_trust(db, ontology, recipient, source).await?;
+    Ok((estimate.level(), estimate.reason()))
+}
+
+/// A recipient's certainty derived from the source's.
+#[derive(Debug, Clone, Serialize)]
+pub struct Derivation {
+    pub certainty: CertaintyLevel,
+    /// Carried over from the source when the belief is wrong
+    pub truth_value: Option<String>,
+    /// The source's knowledge state it was derived from
+    pub source_state: RecordId,
+    pub trust: Trust,
+    /// Human-readable reason, stored on the recipient's state
+    pub explanation: String,
+}
+
+/// The source's latest state about a fact, matched on the fact text.
+async fn source_state(
+    db: &NarraDb,
+    source: &str,
+    fact: &str,
+) -> Result<Option<KnowledgeState>, NarraError> {
+    let mut result = db
+        .query(
+            "SELECT * FROM knows WHERE in = $source \
+             AND string::lowercase(string::trim(out.fact ?? '')) = $fact \
+             ORDER BY learned_at DESC LIMIT 1",
+        )
+        .bind(("source", record_id("character", source)?))
+        .bind(("fact", fact.trim().to_lowercase()))
+        .await?;
+    let states: Vec<KnowledgeState> = result.take(0)?;
+    Ok(states.into_iter().next())
+}
+
+/// Derive what `recipient` makes of `fact` when told by `source`.
+pub async fn derive_certainty(
+    db: &NarraDb,
+    ontology: &RelationshipOntology,
+    rules: &TransmissionRules,
+    recipient: &str,
+    source: &str,
+    fact: &str,
+) -> Result<Derivation, NarraError> {
+    let state = source_state(db, source, fact).await?.ok_or_else(|| {
+        NarraError::Validation(format!(
+            "character:{} has no recorded knowledge of '{}' to pass on; give a certainty instead",
+            source, fact
+        ))
+    })?;
+    let (trust, because) = trust_between(db, ontology, recipient, source).await?;
+    let certainty = rules.apply(trust, state.certainty).ok_or_else(|| {
+        NarraError::Validation(format!(
+            "character:{} {} '{}', so it can't be passed on",
+            source,
+            certainty_name(state.certainty),
+            fact
+        ))
+    })?;
+
+    let truth_value = match certainty {
+        CertaintyLevel::BelievesWrongly => state.truth_value.clone().or(Some(fact.to_string())),
+        _ => None,
+    };
+    let trust_reason = match &because {
+        Some(role) => format!("{} ({})", trust, role),
+        None => trust.to_string(),
+    };
+    let explanation = format!(
+        "told by character:{}, who {}; {} -> {}",
+        source,
+        certainty_name(state.certainty),
+        trust_reason,
+        certainty_name(certainty)
+    );
+    Ok(Derivation {
+        certainty,
+        truth_value,
+        source_state: state.id,
+        trust,
+        explanation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules_and_overrides() {
+        let rules = TransmissionRules::default();
+        assert_eq!(
+            rules.apply(Trust::Trusted, CertaintyLevel::BelievesWrongly),
+            Some(CertaintyLevel::BelievesWrongly)
+        );
+        assert_eq!(
+            rules.apply(Trust::Neutral, CertaintyLevel::Knows),
+            Some(CertaintyLevel::Suspects)
+        );
+        assert_eq!(rules.apply(Trust::Trusted, CertaintyLevel::Forgotten), None);
+
+        let neutral: BTreeMap<String, String> = [("knows".to_string(), "knows".to_string())].into();
+        let rules = TransmissionRules::with_overrides(&[(Trust::Neutral, &neutral)]).unwrap();
+        assert_eq!(
+            rules.apply(Trust::Neutral, CertaintyLevel::Knows),
+            Some(CertaintyLevel::Knows)
+        );
+
+        let bad: BTreeMap<String, String> = [("knows".to_string(), "sure".to_string())].into();
+        assert!(TransmissionRules::with_overrides(&[(Trust::Trusted, &bad)]).is_err());
+        assert_eq!(
+            parse_certainty("Believes_Wrongly"),
+            Some(CertaintyLevel::BelievesWrongly)
+        );
+    }
+}
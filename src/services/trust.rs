@@ -0,0 +1,227 @@
+//! Trust between characters.
+//!
+//! Trust is how far one character relies on another, from 0.0 (not at all)
+//! to 1.0 (completely). It lives on the `perceives` edge: A's trust in B is
+//! on A -> B, and B may trust A quite differently. When the author hasn't
+//! set it, it is inferred from A's relationships towards B (through the
+//! ontology's polarity), the tension A feels, and how reliable what B told
+//! A turned out to be. Influence propagation and certainty derivation
+//! (`services::transmission`) both read it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::connection::NarraDb;
+use crate::db::query::record_id;
+use crate::models::{CertaintyLevel, Perception, Relationship};
+use crate::services::ontology::{Polarity, RelationshipOntology};
+use crate::services::transmission::Trust;
+use crate::NarraError;
+
+/// Trust with nothing to go on.
+pub const NEUTRAL_TRUST: f32 = 0.5;
+/// At or above this, knowledge is passed on as trusted.
+pub const TRUSTED_AT: f32 = 0.65;
+/// At or below this, knowledge is passed on as distrusted.
+pub const DISTRUSTED_AT: f32 = 0.35;
+
+/// The trust level a value falls into.
+pub fn trust_level(value: f32) -> Trust {
+    if value >= TRUSTED_AT {
+        Trust::Trusted
+    } else if value <= DISTRUSTED_AT {
+        Trust::Distrusted
+    } else {
+        Trust::Neutral
+    }
+}
+
+/// What trust is inferred from.
+#[derive(Debug, Clone, Default)]
+pub struct TrustSignals {
+    /// A positive relationship towards the other character, by role
+    pub positive: Option<String>,
+    /// A negative relationship towards the other character, by role
+    pub negative: Option<String>,
+    /// Tension felt towards the other character (0-10)
+    pub tension: Option<i32>,
+    /// Things the other character told them that they know to be so
+    pub reliable: usize,
+    /// Things the other character told them that they believe wrongly
+    pub misleading: usize,
+}
+
+/// Infer trust from signals, with the reasons that moved it.
+pub fn infer_trust(signals: &TrustSignals) -> (f32, Vec<String>) {
+    let mut value = NEUTRAL_TRUST;
+    let mut reasons = Vec::new();
+
+    // A hostile relationship outweighs a friendly one
+    if let Some(role) = &signals.negative {
+        value -= 0.3;
+        reasons.push(role.clone());
+    } else if let Some(role) = &signals.positive {
+        value += 0.2;
+        reasons.push(role.clone());
+    }
+    if let Some(tension) = signals.tension.filter(|t| *t >= 7) {
+        value -= 0.15;
+        reasons.push(format!("tension {}", tension));
+    }
+    if signals.reliable > 0 {
+        value += (0.05 * signals.reliable as f32).min(0.2);
+        reasons.push(format!("{} reliable", signals.reliable));
+    }
+    if signals.misleading > 0 {
+        value -= (0.1 * signals.misleading as f32).min(0.3);
+        reasons.push(format!("{} misleading", signals.misleading));
+    }
+
+    (value.clamp(0.0, 1.0), reasons)
+}
+
+/// One character's trust in another.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrustEstimate {
+    pub from: String,
+    pub to: String,
+    /// Explicit trust if set, else the inferred value
+    pub value: f32,
+    /// Whether `value` was set by the author
+    pub explicit: bool,
+    /// What the history alone suggests
+    pub inferred: f32,
+    /// Signals behind `inferred`
+    pub reasons: Vec<String>,
+}
+
+impl TrustEstimate {
+    pub fn level(&self) -> Trust {
+        trust_level(self.value)
+    }
+
+    /// Whether anything beyond the neutral default backs the value.
+    pub fn is_informed(&self) -> bool {
+        self.explicit || !self.reasons.is_empty()
+    }
+
+    /// Short reason for the value: "trust 0.80" when explicit, else the signals.
+    pub fn reason(&self) -> Option<String> {
+        if self.explicit {
+            Some(format!("trust {:.2}", self.value))
+        } else if self.reasons.is_empty() {
+            None
+        } else {
+            Some(self.reasons.join(", "))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CertaintyCount {
+    certainty: CertaintyLevel,
+    n: usize,
+}
+
+/// How far `from` trusts `to` (character keys).
+pub async fn estimate_trust(
+    db: &NarraDb,
+    ontology: &RelationshipOntology,
+    from: &str,
+    to: &str,
+) -> Result<TrustEstimate, NarraError> {
+    let mut result = db
+        .query("SELECT * FROM perceives WHERE in = $from AND out = $to")
+        .query("SELECT * FROM relates_to WHERE in = $from AND out = $to")
+        .query(
+            "SELECT certainty, count() AS n FROM knows \
+             WHERE in = $from AND source_character = $to GROUP BY certainty",
+        )
+        .bind(("from", record_id("character", from)?))
+        .bind(("to", record_id("character", to)?))
+        .await?;
+    let perceptions: Vec<Perception> = result.take(0)?;
+    let relationships: Vec<Relationship> = result.take(1)?;
+    let counts: Vec<CertaintyCount> = result.take(2)?;
+
+    let mut signals = TrustSignals::default();
+    let mut explicit = None;
+    let mut roles: Vec<(String, String)> = relationships
+        .iter()
+        .map(|r| {
+            let role = r.subtype.clone().unwrap_or_else(|| r.rel_type.clone());
+            (role, r.rel_type.clone())
+        })
+        .collect();
+    for perception in &perceptions {
+        explicit = explicit.or(perception.trust);
+        signals.tension = signals.tension.max(perception.tension_level);
+        roles.extend(perception.rel_types.iter().map(|t| {
+            let role = perception.subtype.clone().unwrap_or_else(|| t.clone());
+            (role, t.clone())
+        }));
+    }
+    for (role, rel_type) in roles {
+        let polarity = ontology
+            .get(&role)
+            .or_else(|| ontology.get(&rel_type))
+            .map(|t| t.polarity)
+            .unwrap_or_default();
+        match polarity {
+            Polarity::Negative => signals.negative = signals.negative.or(Some(role)),
+            Polarity::Positive => signals.positive = signals.positive.or(Some(role)),
+            Polarity::Neutral => {}
+        }
+    }
+    for count in counts {
+        match count.certainty {
+            CertaintyLevel::Knows => signals.reliable += count.n,
+            CertaintyLevel::BelievesWrongly => signals.misleading += count.n,
+            _ => {}
+        }
+    }
+
+    let (inferred, reasons) = infer_trust(&signals);
+    Ok(TrustEstimate {
+        from: format!("character:{}", from),
+        to: format!("character:{}", to),
+        value: explicit.unwrap_or(inferred),
+        explicit: explicit.is_some(),
+        inferred,
+        reasons,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_trust() {
+        let (value, reasons) = infer_trust(&TrustSignals::default());
+        assert_eq!(value, NEUTRAL_TRUST);
+        assert!(reasons.is_empty());
+        assert_eq!(trust_level(value), Trust::Neutral);
+
+        let friend = TrustSignals {
+            positive: Some("friend".into()),
+            reliable: 2,
+            ..Default::default()
+        };
+        let (value, reasons) = infer_trust(&friend);
+        assert!((value - 0.8).abs() < 0.001);
+        assert_eq!(reasons, vec!["friend", "2 reliable"]);
+        assert_eq!(trust_level(value), Trust::Trusted);
+
+        // Hostility wins over friendship, and lies compound it
+        let betrayed = TrustSignals {
+            positive: Some("friend".into()),
+            negative: Some("rival".into()),
+            tension: Some(8),
+            misleading: 5,
+            ..Default::default()
+        };
+        let (value, _) = infer_trust(&betrayed);
+        assert_eq!(value, 0.0);
+        assert_eq!(trust_level(value), Trust::Distrusted);
+    }
+}
@@ -0,0 +1,347 @@
+//! Approximate nearest-neighbour indexes for embedding search.
+//!
+//! Semantic search scans every embedding with brute-force cosine by default,
+//! which is exact and fast at narrative scale. Very large worlds can opt into
+//! an HNSW or MTREE index per embeddable table (`[vector_index]` in config),
+//! built by `narra world reindex-vectors`. Index dimensions must match the
+//! embedding model, so the built kind and dimensions are recorded in
+//! `world_meta` and search only uses an index that matches both.
+
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::connection::NarraDb;
+use crate::services::EntityType;
+use crate::NarraError;
+
+/// Index kind for embedding fields.
+///
+/// Set via `[vector_index] kind` in config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorIndexKind {
+    /// No index: exact brute-force cosine scan.
+    #[default]
+    None,
+    /// Hierarchical navigable small world graph (in-memory, fastest).
+    Hnsw,
+    /// Metric tree (persisted, lower memory).
+    Mtree,
+}
+
+impl std::fmt::Display for VectorIndexKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Hnsw => "hnsw",
+            Self::Mtree => "mtree",
+        })
+    }
+}
+
+impl std::str::FromStr for VectorIndexKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "hnsw" => Ok(Self::Hnsw),
+            "mtree" => Ok(Self::Mtree),
+            other => Err(format!(
+                "Unknown vector index '{}'. Expected: none, hnsw, mtree",
+                other
+            )),
+        }
+    }
+}
+
+/// Resolved index parameters (config values over SurrealDB's defaults).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct VectorIndexSettings {
+    pub kind: VectorIndexKind,
+    /// HNSW: neighbours per node
+    pub m: u8,
+    /// HNSW: candidate list size while building
+    pub efc: u16,
+    /// HNSW: candidate list size while searching
+    pub ef: u16,
+    /// MTREE: node capacity
+    pub capacity: u16,
+}
+
+impl Default for VectorIndexSettings {
+    fn default() -> Self {
+        Self {
+            kind: VectorIndexKind::None,
+            m: 12,
+            efc: 150,
+            ef: 40,
+            capacity: 40,
+        }
+    }
+}
+
+impl VectorIndexSettings {
+    /// `DEFINE INDEX` for `table`'s embedding field, or `None` without an index.
+    pub fn define_statement(&self, table: &str, dimensions: usize) -> Option<String> {
+        let spec = match self.kind {
+            VectorIndexKind::None => return None,
+            VectorIndexKind::Hnsw => format!(
+                "HNSW DIMENSION {} DIST COSINE TYPE F32 EFC {} M {}",
+                dimensions, self.efc, self.m
+            ),
+            VectorIndexKind::Mtree => format!(
+                "MTREE DIMENSION {} DIST COSINE TYPE F32 CAPACITY {}",
+                dimensions, self.capacity
+            ),
+        };
+        Some(format!(
+            "DEFINE INDEX OVERWRITE {} ON {} FIELDS embedding {};",
+            index_name(table),
+            table,
+            spec
+        ))
+    }
+
+    /// KNN condition selecting the `k` nearest neighbours of `$query_vector`
+    /// through the index, or `None` without one.
+    pub fn knn_condition(&self, k: usize) -> Option<String> {
+        match self.kind {
+            VectorIndexKind::None => None,
+            VectorIndexKind::Hnsw => Some(format!(
+                "embedding <|{},{}|> $query_vector",
+                k,
+                usize::from(self.ef).max(k)
+            )),
+            VectorIndexKind::Mtree => Some(format!("embedding <|{}|> $query_vector", k)),
+        }
+    }
+}
+
+/// Name of the ANN index on `table`'s embedding field.
+pub fn index_name(table: &str) -> String {
+    format!("{}_embedding_ann", table)
+}
+
+/// Index recorded in `world_meta` by the last reindex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltVectorIndex {
+    pub kind: VectorIndexKind,
+    pub dimensions: usize,
+}
+
+/// Read the index recorded by the last `reindex`, if any.
+pub async fn built_index(db: &NarraDb) -> Result<Option<BuiltVectorIndex>, NarraError> {
+    #[derive(Deserialize)]
+    struct Meta {
+        vector_index: Option<String>,
+        vector_index_dimensions: Option<i64>,
+    }
+
+    let mut response = db
+        .query("SELECT vector_index, vector_index_dimensions FROM ONLY world_meta:default")
+        .await?;
+    let meta: Option<Meta> = response.take(0).unwrap_or(None);
+    Ok(meta.and_then(|m| {
+        let kind: VectorIndexKind = m.vector_index?.parse().ok()?;
+        (kind != VectorIndexKind::None).then_some(BuiltVectorIndex {
+            kind,
+            dimensions: m.vector_index_dimensions.unwrap_or(0) as usize,
+        })
+    }))
+}
+
+/// Remove every ANN index and forget the recorded one.
+///
+/// Called when the embedding model's dimensions no longer match the index,
+/// since SurrealDB rejects writes of vectors with the wrong dimension.
+pub async fn drop_indexes(db: &NarraDb) -> Result<(), NarraError> {
+    let mut sql: String = EntityType::embeddable()
+        .iter()
+        .map(|t| {
+            let table = t.table_name();
+            format!(
+                "REMOVE INDEX IF EXISTS {} ON TABLE {};\n",
+                index_name(table),
+                table
+            )
+        })
+        .collect();
+    sql.push_str(
+        "UPSERT world_meta:default SET vector_index = NONE, vector_index_dimensions = NONE, \
+         updated_at = time::now();",
+    );
+    db.query(sql).await?.check()?;
+    Ok(())
+}
+
+/// Probe latency for one table, before and after a reindex.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexTiming {
+    pub table: String,
+    /// Rows with an embedding
+    pub rows: usize,
+    /// Median milliseconds for a top-10 query before reindexing
+    pub before_ms: Option<f64>,
+    /// Median milliseconds for the same query afterwards
+    pub after_ms: Option<f64>,
+}
+
+/// Rebuild the ANN index of every embeddable table with `settings`,
+/// timing a top-10 query against one of the table's own vectors before and
+/// after. `previous` is the index queries used before (from [`built_index`]).
+pub async fn reindex(
+    db: &NarraDb,
+    settings: &VectorIndexSettings,
+    previous: Option<BuiltVectorIndex>,
+    dimensions: usize,
+) -> Result<Vec<ReindexTiming>, NarraError> {
+    let before = match previous {
+        Some(built) if built.dimensions == dimensions => VectorIndexSettings {
+            kind: built.kind,
+            ..*settings
+        },
+        _ => VectorIndexSettings::default(),
+    };
+
+    let mut timings = Vec::new();
+    for entity_type in EntityType::embeddable() {
+        let table = entity_type.table_name();
+
+        #[derive(Deserialize)]
+        struct RowCount {
+            rows: usize,
+        }
+        let mut response = db
+            .query(format!(
+                "SELECT count() AS rows FROM {table} WHERE embedding IS NOT NONE GROUP ALL;
+                 SELECT VALUE embedding FROM {table} WHERE embedding IS NOT NONE LIMIT 1;",
+                table = table
+            ))
+            .await?;
+        let counts: Vec<RowCount> = response.take(0)?;
+        let rows = counts.first().map_or(0, |c| c.rows);
+        let vectors: Vec<Vec<f32>> = response.take(1)?;
+        let vector = vectors.into_iter().next();
+        // A probe from another model would fail against the new index
+        let vector = vector.filter(|v| v.len() == dimensions);
+
+        let before_ms = match &vector {
+            Some(v) => Some(probe_latency(db, table, &before, v).await?),
+            None => None,
+        };
+
+        let mut sql = format!(
+            "REMOVE INDEX IF EXISTS {} ON TABLE {};\n",
+            index_name(table),
+            table
+        );
+        if let Some(define) = settings.define_statement(table, dimensions) {
+            sql.push_str(&define);
+        }
+        db.query(sql).await?.check()?;
+
+        let after_ms = match &vector {
+            Some(v) => Some(probe_latency(db, table, settings, v).await?),
+            None => None,
+        };
+
+        timings.push(ReindexTiming {
+            table: table.to_string(),
+            rows,
+            before_ms,
+            after_ms,
+        });
+    }
+
+    let (kind, dims) = match settings.kind {
+        VectorIndexKind::None => (None, None),
+        kind => (Some(kind.to_string()), Some(dimensions as i64)),
+    };
+    db.query(
+        "UPSERT world_meta:default SET vector_index = $kind, \
+         vector_index_dimensions = $dims, updated_at = time::now()",
+    )
+    .bind(("kind", kind))
+    .bind(("dims", dims))
+    .await?
+    .check()?;
+
+    Ok(timings)
+}
+
+/// Median of a few top-10 queries, in milliseconds.
+async fn probe_latency(
+    db: &NarraDb,
+    table: &str,
+    settings: &VectorIndexSettings,
+    vector: &[f32],
+) -> Result<f64, NarraError> {
+    const RUNS: usize = 5;
+    const K: usize = 10;
+
+    let condition = settings
+        .knn_condition(K)
+        .unwrap_or_else(|| "embedding IS NOT NONE".to_string());
+    let sql = format!(
+        "SELECT id, vector::similarity::cosine(embedding, $query_vector) AS score \
+         FROM {} WHERE {} ORDER BY score DESC LIMIT {}",
+        table, condition, K
+    );
+
+    let mut samples = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        db.query(&sql)
+            .bind(("query_vector", vector.to_vec()))
+            .await?
+            .check()?;
+        samples.push(start.elapsed().as_secs_f64() * 1000.0);
+    }
+    samples.sort_by(|a, b| a.total_cmp(b));
+    Ok(samples[RUNS / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_statements_follow_settings() {
+        let none = VectorIndexSettings::default();
+        assert_eq!(none.define_statement("character", 384), None);
+        assert_eq!(none.knn_condition(10), None);
+
+        let hnsw = VectorIndexSettings {
+            kind: VectorIndexKind::Hnsw,
+            m: 16,
+            ..Default::default()
+        };
+        assert_eq!(
+            hnsw.define_statement("scene", 768).unwrap(),
+            "DEFINE INDEX OVERWRITE scene_embedding_ann ON scene FIELDS embedding \
+             HNSW DIMENSION 768 DIST COSINE TYPE F32 EFC 150 M 16;"
+        );
+        assert_eq!(
+            hnsw.knn_condition(10).unwrap(),
+            "embedding <|10,40|> $query_vector"
+        );
+        // ef never drops below k
+        assert_eq!(
+            hnsw.knn_condition(100).unwrap(),
+            "embedding <|100,100|> $query_vector"
+        );
+
+        let mtree = VectorIndexSettings {
+            kind: VectorIndexKind::Mtree,
+            ..Default::default()
+        };
+        assert!(mtree
+            .define_statement("note", 384)
+            .unwrap()
+            .contains("MTREE DIMENSION 384 DIST COSINE TYPE F32 CAPACITY 40"));
+        assert_eq!("HNSW".parse::<VectorIndexKind>(), Ok(VectorIndexKind::Hnsw));
+        assert!("ivf".parse::<VectorIndexKind>().is_err());
+    }
+}
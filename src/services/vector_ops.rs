@@ -9,6 +9,7 @@
 use std::sync::Arc;
 
 use crate::db::connection::NarraDb;
+use crate::db::query::parse_record_id;
 use serde::{Deserialize, Serialize};
 
 use crate::utils::math::{cosine_similarity, vector_midpoint, vector_normalize, vector_subtract};
@@ -176,8 +177,11 @@ impl VectorOpsService {
             embedding: Option<Vec<f32>>,
         }
 
-        let query = format!("SELECT embedding FROM {} LIMIT 1", target_id);
-        let mut resp = self.db.query(&query).await?;
+        let mut resp = self
+            .db
+            .query("SELECT embedding FROM $ref LIMIT 1")
+            .bind(("ref", parse_record_id(target_id)?))
+            .await?;
         let target_row: Option<EmbeddingRow> = resp.take(0)?;
 
         let target_embedding = target_row.and_then(|r| r.embedding).ok_or_else(|| {
@@ -315,8 +319,11 @@ impl VectorOpsService {
             title: Option<String>,
         }
 
-        let query = format!("SELECT name, title FROM {} LIMIT 1", entity_id);
-        let mut resp = self.db.query(&query).await?;
+        let mut resp = self
+            .db
+            .query("SELECT name, title FROM $ref LIMIT 1")
+            .bind(("ref", parse_record_id(entity_id)?))
+            .await?;
         let row: Option<NameRow> = resp.take(0)?;
 
         Ok(row
@@ -330,8 +337,11 @@ impl VectorOpsService {
             embedding: Option<Vec<f32>>,
         }
 
-        let query = format!("SELECT embedding FROM {} LIMIT 1", entity_id);
-        let mut resp = self.db.query(&query).await?;
+        let mut resp = self
+            .db
+            .query("SELECT embedding FROM $ref LIMIT 1")
+            .bind(("ref", parse_record_id(entity_id)?))
+            .await?;
         let row: Option<EmbRow> = resp.take(0)?;
 
         row.and_then(|r| r.embedding).ok_or_else(|| {
@@ -0,0 +1,307 @@
+//! Outbound webhooks for mutation events.
+//!
+//! Webhooks are stored in `<data_path>/webhooks.toml` and managed with
+//! `narra config webhook`. A `WebhookDispatcher` subscribes to the `EventBus`
+//! and POSTs each matching `NarraEvent` as JSON, retrying transient failures
+//! with exponential backoff. When a secret is set the body is signed with
+//! HMAC-SHA256 in the `X-Narra-Signature: sha256=<hex>` header.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::services::events::{EventBus, EventListener, NarraEvent};
+
+/// Delivery attempts per event (first try plus retries).
+const MAX_ATTEMPTS: u32 = 4;
+/// Delay before the first retry; doubled on each subsequent one.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// Per-request timeout.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const SIGNATURE_HEADER: &str = "X-Narra-Signature";
+pub const EVENT_HEADER: &str = "X-Narra-Event";
+
+/// A registered webhook.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Webhook {
+    /// Short identifier used by `narra config webhook remove`.
+    pub id: String,
+    pub url: String,
+    /// HMAC-SHA256 signing secret.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Event names to deliver (e.g. "entity.created"). Empty = all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+    /// Entity types to deliver (e.g. "character"). Empty = all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entity_types: Vec<String>,
+}
+
+impl Webhook {
+    /// Whether this webhook should receive `event`.
+    ///
+    /// An entity-type filter only admits events that carry an `entity_type`.
+    pub fn matches(&self, event: &NarraEvent) -> bool {
+        if !self.events.is_empty() && !self.events.iter().any(|e| e == &event.event) {
+            return false;
+        }
+        if self.entity_types.is_empty() {
+            return true;
+        }
+        event
+            .data
+            .get("entity_type")
+            .and_then(|t| t.as_str())
+            .is_some_and(|t| self.entity_types.iter().any(|f| f == t))
+    }
+}
+
+/// On-disk webhook list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    #[serde(default, rename = "webhook")]
+    pub webhooks: Vec<Webhook>,
+}
+
+impl WebhookConfig {
+    pub fn path(data_path: &Path) -> PathBuf {
+        data_path.join("webhooks.toml")
+    }
+
+    /// Load `webhooks.toml`, or an empty config if missing or unreadable.
+    pub fn load(data_path: &Path) -> Self {
+        let path = Self::path(data_path);
+        if !path.exists() {
+            return Self::default();
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => match toml::from_str(&contents) {
+                Ok(config) => config,
+                Err(e) => {
+                    tracing::warn!("Failed to parse {}: {}. Ignoring.", path.display(), e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                tracing::warn!("Failed to read {}: {}. Ignoring.", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, data_path: &Path) -> Result<()> {
+        let path = Self::path(data_path);
+        let contents = toml::to_string_pretty(self)?;
+        std::fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Register a webhook and return its generated ID.
+    pub fn add(
+        &mut self,
+        url: String,
+        secret: Option<String>,
+        events: Vec<String>,
+        entity_types: Vec<String>,
+    ) -> String {
+        let id = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        self.webhooks.push(Webhook {
+            id: id.clone(),
+            url,
+            secret,
+            events,
+            entity_types,
+        });
+        id
+    }
+
+    /// Remove by ID or URL. Returns the removed webhook.
+    pub fn remove(&mut self, id_or_url: &str) -> Option<Webhook> {
+        let pos = self
+            .webhooks
+            .iter()
+            .position(|w| w.id == id_or_url || w.url == id_or_url)?;
+        Some(self.webhooks.remove(pos))
+    }
+}
+
+/// Hex-encoded HMAC-SHA256 of `body` under `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Background task that delivers bus events to webhooks.
+pub struct WebhookDispatcher {
+    listener: EventListener,
+}
+
+impl WebhookDispatcher {
+    /// Subscribe to `bus` and start delivering. Returns `None` when no webhooks
+    /// are configured.
+    pub fn start(bus: &EventBus, config: WebhookConfig) -> Option<Self> {
+        if config.webhooks.is_empty() {
+            return None;
+        }
+
+        let webhooks = Arc::new(config.webhooks);
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        let listener = EventListener::spawn(bus, move |event| {
+            let webhooks = webhooks.clone();
+            let client = client.clone();
+            async move { dispatch(&client, &webhooks, &event).await }
+        });
+
+        Some(Self { listener })
+    }
+
+    /// Deliver events already emitted, then stop.
+    pub async fn finish(self) {
+        self.listener.finish().await;
+    }
+}
+
+async fn dispatch(client: &reqwest::Client, webhooks: &[Webhook], event: &NarraEvent) {
+    let body = match serde_json::to_vec(event) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!("Failed to serialize event for webhook: {}", e);
+            return;
+        }
+    };
+
+    let deliveries = webhooks
+        .iter()
+        .filter(|w| w.matches(event))
+        .map(|w| deliver(client, w, &event.event, &body));
+    futures::future::join_all(deliveries).await;
+}
+
+async fn deliver(client: &reqwest::Client, webhook: &Webhook, event_name: &str, body: &[u8]) {
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let mut request = client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event_name)
+            .body(body.to_vec());
+        if let Some(secret) = &webhook.secret {
+            request = request.header(SIGNATURE_HEADER, format!("sha256={}", sign(secret, body)));
+        }
+
+        let retryable = match request.send().await {
+            Ok(resp) if resp.status().is_success() => return,
+            Ok(resp) => {
+                let status = resp.status();
+                tracing::warn!(
+                    "Webhook {} returned {} (attempt {}/{})",
+                    webhook.id,
+                    status,
+                    attempt,
+                    MAX_ATTEMPTS
+                );
+                // 4xx other than 408/429 will not succeed on retry
+                status.is_server_error()
+                    || status == reqwest::StatusCode::REQUEST_TIMEOUT
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Webhook {} delivery failed: {} (attempt {}/{})",
+                    webhook.id,
+                    e,
+                    attempt,
+                    MAX_ATTEMPTS
+                );
+                true
+            }
+        };
+
+        if !retryable || attempt == MAX_ATTEMPTS {
+            break;
+        }
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+
+    tracing::error!(
+        "Giving up on webhook {} ({}) for {}",
+        webhook.id,
+        webhook.url,
+        event_name
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, data: serde_json::Value) -> NarraEvent {
+        NarraEvent {
+            event: name.to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut config = WebhookConfig::default();
+        config.add(
+            "http://localhost/hook".to_string(),
+            None,
+            vec!["entity.created".to_string()],
+            vec!["character".to_string()],
+        );
+        let hook = &config.webhooks[0];
+
+        let created = serde_json::json!({"entity_type": "character"});
+        assert!(hook.matches(&event("entity.created", created.clone())));
+        assert!(!hook.matches(&event("entity.deleted", created)));
+        assert!(!hook.matches(&event(
+            "entity.created",
+            serde_json::json!({"entity_type": "location"})
+        )));
+        assert!(!hook.matches(&event("entity.created", serde_json::json!({}))));
+    }
+
+    #[test]
+    fn test_sign_matches_known_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_config_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = WebhookConfig::default();
+        let id = config.add(
+            "https://example.com/narra".to_string(),
+            Some("s3cret".to_string()),
+            vec![],
+            vec![],
+        );
+        config.save(dir.path()).unwrap();
+
+        let mut loaded = WebhookConfig::load(dir.path());
+        assert_eq!(loaded.webhooks, config.webhooks);
+        assert!(loaded.remove(&id).is_some());
+        assert!(loaded.webhooks.is_empty());
+    }
+}
@@ -1,8 +1,14 @@
 mod startup;
 mod state;
+mod stats;
 
 pub use startup::{
-    generate_startup_context, HotEntity, PendingDecisionInfo, SessionStartupInfo, StartupVerbosity,
-    WorldOverview,
+    expire_pins, generate_startup_context, HotEntity, JournalEntryInfo, OpenTodoInfo,
+    PendingDecisionInfo, PinnedEntity, ScheduledRunInfo, SessionStartupInfo, StartupOptions,
+    StartupSection, StartupVerbosity, WorldOverview,
 };
-pub use state::{PendingDecision, SessionState, SessionStateManager};
+pub use state::{
+    EntityHeat, Heat, HotDecay, JournalEntry, PendingDecision, Pin, PinPriority, ScheduledRun,
+    SessionState, SessionStateManager, HOT_FLOOR, PIN_SUGGESTION_ACCESSES,
+};
+pub use stats::{session_stats, FocusEntity, SessionStats, NEGLECTED_BELOW};
@@ -1,7 +1,10 @@
 use crate::db::connection::NarraDb;
+use crate::db::query::{parse_record_id, record_id};
 use crate::error::NarraError;
-use crate::session::SessionStateManager;
+use crate::services::TokenCounter;
+use crate::session::{session_stats, FocusEntity, PinPriority, SessionStateManager};
 use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Determines how verbose the session startup context should be.
@@ -17,6 +20,8 @@ pub enum StartupVerbosity {
     NewWorld,
     /// No data exists yet
     EmptyWorld,
+    /// Summary line, pins and always-included entities only (set by config)
+    Minimal,
 }
 
 impl std::fmt::Display for StartupVerbosity {
@@ -27,10 +32,102 @@ impl std::fmt::Display for StartupVerbosity {
             StartupVerbosity::Full => write!(f, "full"),
             StartupVerbosity::NewWorld => write!(f, "new_world"),
             StartupVerbosity::EmptyWorld => write!(f, "empty_world"),
+            StartupVerbosity::Minimal => write!(f, "minimal"),
         }
     }
 }
 
+impl std::str::FromStr for StartupVerbosity {
+    type Err = NarraError;
+
+    /// Verbosities an author can ask for; new_world and empty_world follow
+    /// from the world itself.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "minimal" => Ok(StartupVerbosity::Minimal),
+            "brief" => Ok(StartupVerbosity::Brief),
+            "standard" => Ok(StartupVerbosity::Standard),
+            "full" => Ok(StartupVerbosity::Full),
+            _ => Err(NarraError::Validation(format!(
+                "Startup verbosity must be minimal, brief, standard, or full (got '{}')",
+                s
+            ))),
+        }
+    }
+}
+
+/// A part of the startup context that can be left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupSection {
+    HotEntities,
+    Pinned,
+    PinSuggestions,
+    Decisions,
+    Journal,
+    Todos,
+    Overview,
+    /// Entities that had attention and cooled off (see `session stats`)
+    Neglected,
+}
+
+impl StartupSection {
+    /// Sections included unless configured otherwise.
+    pub const DEFAULT: &[StartupSection] = &[
+        StartupSection::HotEntities,
+        StartupSection::Pinned,
+        StartupSection::PinSuggestions,
+        StartupSection::Decisions,
+        StartupSection::Journal,
+        StartupSection::Todos,
+        StartupSection::Overview,
+    ];
+}
+
+impl std::str::FromStr for StartupSection {
+    type Err = NarraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.trim().to_lowercase())).map_err(|_| {
+            NarraError::Validation(format!(
+                "Unknown startup section '{}' (expected hot_entities, pinned, pin_suggestions, \
+                 decisions, journal, todos, overview, or neglected)",
+                s
+            ))
+        })
+    }
+}
+
+/// How the startup context is put together for a project.
+#[derive(Debug, Clone)]
+pub struct StartupOptions {
+    /// Fixed verbosity instead of one picked from the time since the last session
+    pub verbosity: Option<StartupVerbosity>,
+    /// Sections to include
+    pub sections: Vec<StartupSection>,
+    /// Approximate token ceiling; lists are trimmed to fit
+    pub token_ceiling: Option<usize>,
+    /// Entities always included, whatever was worked on lately
+    pub always_include: Vec<String>,
+}
+
+impl Default for StartupOptions {
+    fn default() -> Self {
+        Self {
+            verbosity: None,
+            sections: StartupSection::DEFAULT.to_vec(),
+            token_ceiling: None,
+            always_include: Vec::new(),
+        }
+    }
+}
+
+impl StartupOptions {
+    pub fn includes(&self, section: StartupSection) -> bool {
+        self.sections.contains(&section)
+    }
+}
+
 /// A hot (recently/frequently accessed) entity.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotEntity {
@@ -40,6 +137,17 @@ pub struct HotEntity {
     pub last_accessed: Option<String>,
 }
 
+/// A pinned entity, as shown in session context.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedEntity {
+    pub id: String,
+    /// Entity name, when it could be looked up
+    pub name: Option<String>,
+    pub priority: PinPriority,
+    /// Event the pin lasts until
+    pub until: Option<String>,
+}
+
 /// Information about a pending decision.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PendingDecisionInfo {
@@ -49,6 +157,33 @@ pub struct PendingDecisionInfo {
     pub affected_count: usize,
 }
 
+/// A recent session journal entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntryInfo {
+    pub message: String,
+    pub age: String,
+    pub entities: Vec<String>,
+}
+
+/// A todo note that is still open.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenTodoInfo {
+    pub id: String,
+    pub title: String,
+    pub age: String,
+}
+
+/// The latest background annotation run (see `[annotate] schedule`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduledRunInfo {
+    pub trigger: String,
+    pub age: String,
+    pub annotated: usize,
+    pub unchanged: usize,
+    pub errors: usize,
+    pub arc_snapshots: usize,
+}
+
 /// Overview of world entity counts.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorldOverview {
@@ -68,10 +203,62 @@ pub struct SessionStartupInfo {
     pub hot_entities: Vec<HotEntity>,
     pub pending_decisions: Vec<PendingDecisionInfo>,
     pub world_overview: Option<WorldOverview>,
+    /// Latest journal entries, most recent first
+    #[serde(default)]
+    pub recent_journal: Vec<JournalEntryInfo>,
+    /// Todo notes not yet resolved, oldest first
+    #[serde(default)]
+    pub open_todos: Vec<OpenTodoInfo>,
+    /// Pinned entities, highest priority first
+    #[serde(default)]
+    pub pinned: Vec<PinnedEntity>,
+    /// Pins that expired since last time (work moved past their event)
+    #[serde(default)]
+    pub expired_pins: Vec<String>,
+    /// Frequently used entities worth pinning
+    #[serde(default)]
+    pub suggested_pins: Vec<HotEntity>,
+    /// Entities the project always includes
+    #[serde(default)]
+    pub always_included: Vec<HotEntity>,
+    /// Entities that had attention and cooled off
+    #[serde(default)]
+    pub neglected: Vec<FocusEntity>,
+    /// Latest background annotation run, when the MCP server schedules them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_run: Option<ScheduledRunInfo>,
+    /// List items dropped to stay under the token ceiling
+    #[serde(default)]
+    pub trimmed: usize,
+}
+
+/// Token count of the context as JSON.
+fn estimate_tokens(info: &SessionStartupInfo, counter: &dyn TokenCounter) -> usize {
+    serde_json::to_string(info)
+        .map(|json| counter.count(&json))
+        .unwrap_or(0)
+}
+
+/// Drop list items, least essential first, until the context fits under
+/// `ceiling`. Pins and always-included entities are never dropped.
+fn fit_to_ceiling(info: &mut SessionStartupInfo, ceiling: usize, counter: &dyn TokenCounter) {
+    while estimate_tokens(info, counter) > ceiling {
+        let dropped = info.neglected.pop().is_some()
+            || info.suggested_pins.pop().is_some()
+            || info.world_overview.take().is_some()
+            || info.hot_entities.pop().is_some()
+            || info.recent_journal.pop().is_some()
+            || info.open_todos.pop().is_some()
+            || info.pending_decisions.pop().is_some();
+        if !dropped {
+            break;
+        }
+        info.trimmed += 1;
+    }
 }
 
 /// Generate a human-readable time ago string.
-fn format_time_ago(dt: DateTime<Utc>) -> String {
+pub(super) fn format_time_ago(dt: DateTime<Utc>) -> String {
     let now = Utc::now();
     let duration = now.signed_duration_since(dt);
 
@@ -151,8 +338,37 @@ async fn query_world_overview(db: &NarraDb) -> Result<WorldOverview, NarraError>
     })
 }
 
+/// Todo notes that are still open, oldest first.
+async fn query_open_todos(db: &NarraDb) -> Result<Vec<OpenTodoInfo>, NarraError> {
+    #[derive(Deserialize)]
+    struct TodoRow {
+        id: surrealdb::RecordId,
+        title: String,
+        created: String,
+    }
+
+    let rows: Vec<TodoRow> = db
+        .query(
+            "SELECT id, title, <string> created_at AS created FROM note \
+             WHERE kind = 'todo' AND status = 'open' ORDER BY created ASC",
+        )
+        .await?
+        .take(0)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| OpenTodoInfo {
+            id: row.id.to_string(),
+            title: row.title,
+            age: DateTime::parse_from_rfc3339(&row.created)
+                .map(|t| format_time_ago(t.with_timezone(&Utc)))
+                .unwrap_or_default(),
+        })
+        .collect())
+}
+
 /// Get entity details for hot entities.
-async fn get_hot_entity_details(db: &NarraDb, entity_ids: &[String]) -> Vec<HotEntity> {
+pub(super) async fn get_hot_entity_details(db: &NarraDb, entity_ids: &[String]) -> Vec<HotEntity> {
     use serde::Deserialize;
 
     let mut hot_entities = Vec::new();
@@ -176,7 +392,13 @@ async fn get_hot_entity_details(db: &NarraDb, entity_ids: &[String]) -> Vec<HotE
         }
 
         // Use direct access (not WHERE id =) per SurrealDB best practices
-        let query_result = db.query(format!("SELECT name FROM {}", entity_id)).await;
+        let Ok(entity) = parse_record_id(entity_id) else {
+            continue;
+        };
+        let query_result = db
+            .query("SELECT name FROM $ref")
+            .bind(("ref", entity))
+            .await;
 
         if let Ok(mut response) = query_result {
             if let Ok(Some(name_data)) = response.take::<Option<NameOnly>>(0) {
@@ -184,7 +406,7 @@ async fn get_hot_entity_details(db: &NarraDb, entity_ids: &[String]) -> Vec<HotE
                     id: entity_id.clone(),
                     name: name_data.name,
                     entity_type: table.to_string(),
-                    last_accessed: None, // We don't track timestamp per access, just order
+                    last_accessed: None,
                 });
             }
         }
@@ -193,11 +415,76 @@ async fn get_hot_entity_details(db: &NarraDb, entity_ids: &[String]) -> Vec<HotE
     hot_entities
 }
 
-/// Generate session startup context.
+/// The furthest point in the story that recent work touched: the highest
+/// sequence among recently accessed events and the events of recent scenes.
+async fn story_position(db: &NarraDb, recent: &[String]) -> Result<Option<i64>, NarraError> {
+    let ids_in = |table: &str| -> Vec<surrealdb::RecordId> {
+        recent
+            .iter()
+            .filter(|id| id.split_once(':').is_some_and(|(t, _)| t == table))
+            .filter_map(|id| record_id(table, id).ok())
+            .collect()
+    };
+    let mut result = db
+        .query("SELECT VALUE sequence FROM $events")
+        .query("SELECT VALUE event.sequence FROM $scenes")
+        .bind(("events", ids_in("event")))
+        .bind(("scenes", ids_in("scene")))
+        .await?;
+    let events: Vec<Option<i64>> = result.take(0)?;
+    let scenes: Vec<Option<i64>> = result.take(1)?;
+    Ok(events.into_iter().chain(scenes).flatten().max())
+}
+
+/// Remove pins whose `until` event work has moved past, or that no longer
+/// exists. Returns the expired entity IDs; the state is saved if any expired.
+pub async fn expire_pins(
+    session_manager: &SessionStateManager,
+    db: &NarraDb,
+) -> Result<Vec<String>, NarraError> {
+    let pins = session_manager.get_pins().await;
+    if pins.iter().all(|p| p.until.is_none()) {
+        return Ok(Vec::new());
+    }
+    let position = story_position(db, &session_manager.get_recent(100).await).await?;
+
+    let mut expired = Vec::new();
+    for pin in pins {
+        let Some(until) = &pin.until else {
+            continue;
+        };
+        let sequence: Option<i64> = match record_id("event", until) {
+            Ok(event) => db
+                .query("SELECT VALUE sequence FROM ONLY $event")
+                .bind(("event", event))
+                .await?
+                .take(0)
+                .unwrap_or(None),
+            Err(_) => None,
+        };
+        let past = match sequence {
+            Some(sequence) => position.is_some_and(|p| p > sequence),
+            None => true,
+        };
+        if past {
+            session_manager.unpin_entity(&pin.entity_id).await;
+            expired.push(pin.entity_id);
+        }
+    }
+    if !expired.is_empty() {
+        session_manager.save().await?;
+    }
+    Ok(expired)
+}
+
+/// Generate session startup context, shaped by the manager's startup options.
+/// `token_counter` measures the context against the token ceiling.
 pub async fn generate_startup_context(
     session_manager: &SessionStateManager,
     db: &NarraDb,
+    token_counter: &dyn TokenCounter,
 ) -> Result<SessionStartupInfo, NarraError> {
+    let options = session_manager.startup_options();
     let last_session = session_manager.get_last_session().await;
 
     // Get world overview to determine if world has data
@@ -226,9 +513,16 @@ pub async fn generate_startup_context(
             }
         }
     };
+    // A configured verbosity applies once there is something to show
+    let verbosity = match options.verbosity {
+        Some(fixed) if verbosity != StartupVerbosity::EmptyWorld => fixed,
+        _ => verbosity,
+    };
 
     // Get recent entity accesses
     let recent_limit = match verbosity {
+        _ if !options.includes(StartupSection::HotEntities) => 0,
+        StartupVerbosity::Minimal => 0,
         StartupVerbosity::Brief => 3,
         StartupVerbosity::Standard => 10,
         StartupVerbosity::Full => 20,
@@ -236,11 +530,59 @@ pub async fn generate_startup_context(
         StartupVerbosity::EmptyWorld => 0,
     };
 
-    let recent_ids = session_manager.get_recent(recent_limit).await;
-    let hot_entities = get_hot_entity_details(db, &recent_ids).await;
+    let hot_ids = session_manager.get_hot(recent_limit).await;
+    let heat = session_manager.get_heat().await;
+    let mut hot_entities = get_hot_entity_details(db, &hot_ids).await;
+    for entity in &mut hot_entities {
+        entity.last_accessed = heat
+            .iter()
+            .find(|h| h.entity_id == entity.id)
+            .and_then(|h| h.last_access)
+            .map(format_time_ago);
+    }
+
+    // Pins: drop the expired ones, then list the rest by priority
+    let expired_pins = expire_pins(session_manager, db).await?;
+    let pins = if options.includes(StartupSection::Pinned) {
+        session_manager.get_pins().await
+    } else {
+        Vec::new()
+    };
+    let pin_ids: Vec<String> = pins.iter().map(|p| p.entity_id.clone()).collect();
+    let pin_names = get_hot_entity_details(db, &pin_ids).await;
+    let pinned: Vec<PinnedEntity> = pins
+        .into_iter()
+        .map(|p| PinnedEntity {
+            name: pin_names
+                .iter()
+                .find(|e| e.id == p.entity_id)
+                .map(|e| e.name.clone()),
+            id: p.entity_id,
+            priority: p.priority,
+            until: p.until,
+        })
+        .collect();
+    let suggested_pins = if options.includes(StartupSection::PinSuggestions)
+        && verbosity != StartupVerbosity::Minimal
+    {
+        let suggested_ids = session_manager.suggest_pins(3).await;
+        get_hot_entity_details(db, &suggested_ids).await
+    } else {
+        Vec::new()
+    };
+    let always_included = get_hot_entity_details(db, &options.always_include).await;
+    let neglected = if options.includes(StartupSection::Neglected) {
+        session_stats(session_manager, db, 5).await?.neglected
+    } else {
+        Vec::new()
+    };
 
     // Get pending decisions
-    let pending_decisions_raw = session_manager.get_pending_decisions().await;
+    let pending_decisions_raw = if options.includes(StartupSection::Decisions) {
+        session_manager.get_pending_decisions().await
+    } else {
+        Vec::new()
+    };
     let pending_decisions: Vec<PendingDecisionInfo> = pending_decisions_raw
         .into_iter()
         .map(|d| PendingDecisionInfo {
@@ -251,8 +593,40 @@ pub async fn generate_startup_context(
         })
         .collect();
 
+    // Recent journal entries: what happened last time
+    let journal_limit = match verbosity {
+        _ if !options.includes(StartupSection::Journal) => 0,
+        StartupVerbosity::Minimal => 1,
+        StartupVerbosity::Brief => 3,
+        StartupVerbosity::Standard => 5,
+        StartupVerbosity::Full => 10,
+        StartupVerbosity::NewWorld => 5,
+        StartupVerbosity::EmptyWorld => 3,
+    };
+    let recent_journal: Vec<JournalEntryInfo> = session_manager
+        .get_journal(journal_limit)
+        .await
+        .into_iter()
+        .map(|e| JournalEntryInfo {
+            message: e.message,
+            age: format_time_ago(e.created_at),
+            entities: e.entities,
+        })
+        .collect();
+
+    // Todos stay in view until resolved
+    let open_todos = if options.includes(StartupSection::Todos) {
+        query_open_todos(db).await?
+    } else {
+        Vec::new()
+    };
+
     // Generate summary based on verbosity
     let summary = match verbosity {
+        StartupVerbosity::Minimal => match recent_journal.first() {
+            Some(entry) => format!("Welcome back. You last logged \"{}\".", entry.message),
+            None => "Welcome back.".to_string(),
+        },
         StartupVerbosity::EmptyWorld => {
             "Your Narra world is empty. Ready to start building? Try: 'Create a character named...' or 'Let's establish the setting first.'".to_string()
         }
@@ -287,6 +661,10 @@ pub async fn generate_startup_context(
 
             let mut summary_parts = vec![format!("Last session {}", time_ago)];
 
+            if let Some(entry) = recent_journal.first() {
+                summary_parts.push(format!("last logged \"{}\"", entry.message));
+            }
+
             if !hot_entities.is_empty() {
                 let names: Vec<&str> = hot_entities.iter().take(5).map(|e| e.name.as_str()).collect();
                 summary_parts.push(format!("you were working on {}", names.join(", ")));
@@ -298,6 +676,12 @@ pub async fn generate_startup_context(
                     if pending_decisions.len() == 1 { "" } else { "s" }));
             }
 
+            if !open_todos.is_empty() {
+                summary_parts.push(format!("{} open todo{}",
+                    open_todos.len(),
+                    if open_todos.len() == 1 { "" } else { "s" }));
+            }
+
             format!("{}.", summary_parts.join("; "))
         }
         StartupVerbosity::Full => {
@@ -305,6 +689,10 @@ pub async fn generate_startup_context(
 
             let mut summary = format!("It's been {} since your last session. ", time_ago);
 
+            if let Some(entry) = recent_journal.first() {
+                summary.push_str(&format!("You last logged \"{}\" ({}). ", entry.message, entry.age));
+            }
+
             if !hot_entities.is_empty() {
                 summary.push_str("Here's what you were working on: ");
                 let names: Vec<String> = hot_entities.iter()
@@ -321,24 +709,57 @@ pub async fn generate_startup_context(
                     if pending_decisions.len() == 1 { "" } else { "s" }));
             }
 
+            if !open_todos.is_empty() {
+                summary.push_str(&format!("{} todo{} still open. ",
+                    open_todos.len(),
+                    if open_todos.len() == 1 { " is" } else { "s are" }));
+            }
+
             summary.push_str("Ready to continue?");
             summary
         }
     };
 
+    let scheduled_run =
+        session_manager
+            .get_last_scheduled_run()
+            .await
+            .map(|run| ScheduledRunInfo {
+                trigger: run.trigger,
+                age: format_time_ago(run.finished_at),
+                annotated: run.annotated,
+                unchanged: run.unchanged,
+                errors: run.errors,
+                arc_snapshots: run.arc_snapshots,
+            });
+
     let last_session_ago = last_session.map(format_time_ago);
-    let overview = if verbosity == StartupVerbosity::NewWorld {
-        Some(world_overview)
-    } else {
-        None
-    };
+    let overview =
+        if verbosity == StartupVerbosity::NewWorld && options.includes(StartupSection::Overview) {
+            Some(world_overview)
+        } else {
+            None
+        };
 
-    Ok(SessionStartupInfo {
+    let mut info = SessionStartupInfo {
         verbosity,
         last_session_ago,
         summary,
         hot_entities,
         pending_decisions,
         world_overview: overview,
-    })
+        recent_journal,
+        open_todos,
+        pinned,
+        expired_pins,
+        suggested_pins,
+        always_included,
+        neglected,
+        scheduled_run,
+        trimmed: 0,
+    };
+    if let Some(ceiling) = options.token_ceiling {
+        fit_to_ceiling(&mut info, ceiling, token_counter);
+    }
+    Ok(info)
 }
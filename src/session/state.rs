@@ -1,7 +1,12 @@
 use crate::error::NarraError;
+use crate::session::StartupOptions;
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -18,17 +23,195 @@ pub struct PendingDecision {
     pub entity_ids: Vec<String>,
 }
 
+/// A writing session journal entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// What happened ("drafted ch 7 confrontation")
+    pub message: String,
+    /// When it was logged
+    pub created_at: DateTime<Utc>,
+    /// Entity IDs the work touched
+    #[serde(default)]
+    pub entities: Vec<String>,
+}
+
+/// Outcome of a background annotation run in the MCP server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledRun {
+    /// When the run finished
+    pub finished_at: DateTime<Utc>,
+    /// Schedule that started it ("on_mutation" or "nightly")
+    pub trigger: String,
+    /// Entities run through the annotation pipeline
+    pub annotated: usize,
+    /// Model runs skipped because their input had not changed
+    pub unchanged: usize,
+    /// Entities with classifier errors
+    pub errors: usize,
+    /// Baseline arc snapshots recorded
+    pub arc_snapshots: usize,
+}
+
+/// How prominently a pinned entity shows in session context.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PinPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl fmt::Display for PinPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PinPriority::Low => "low",
+            PinPriority::Normal => "normal",
+            PinPriority::High => "high",
+        })
+    }
+}
+
+impl FromStr for PinPriority {
+    type Err = NarraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(PinPriority::Low),
+            "normal" => Ok(PinPriority::Normal),
+            "high" => Ok(PinPriority::High),
+            _ => Err(NarraError::Validation(format!(
+                "Pin priority must be low, normal, or high (got '{}')",
+                s
+            ))),
+        }
+    }
+}
+
+/// A pinned entity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "StoredPin")]
+pub struct Pin {
+    pub entity_id: String,
+    pub priority: PinPriority,
+    /// Event the pin lasts until: it expires once work moves past it
+    pub until: Option<String>,
+}
+
+/// Pins as stored: plain entity IDs in state files from before priorities.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredPin {
+    Plain(String),
+    Full {
+        entity_id: String,
+        #[serde(default)]
+        priority: PinPriority,
+        #[serde(default)]
+        until: Option<String>,
+    },
+}
+
+impl From<StoredPin> for Pin {
+    fn from(stored: StoredPin) -> Self {
+        match stored {
+            StoredPin::Plain(entity_id) => Pin {
+                entity_id,
+                priority: PinPriority::default(),
+                until: None,
+            },
+            StoredPin::Full {
+                entity_id,
+                priority,
+                until,
+            } => Pin {
+                entity_id,
+                priority,
+                until,
+            },
+        }
+    }
+}
+
+/// Accesses before an unpinned entity is suggested for pinning.
+pub const PIN_SUGGESTION_ACCESSES: u32 = 5;
+
+/// Heat below which an entity drops out of the hot list.
+pub const HOT_FLOOR: f64 = 0.1;
+
+/// How quickly hot entities cool down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HotDecay {
+    /// Days for an entity's heat to halve without access
+    pub half_life_days: f64,
+    /// Share of heat kept when a session ends (1.0 keeps it all)
+    pub per_session: f64,
+}
+
+impl Default for HotDecay {
+    fn default() -> Self {
+        Self {
+            half_life_days: 7.0,
+            per_session: 0.8,
+        }
+    }
+}
+
+impl HotDecay {
+    /// `heat` as of `now`, given it was last touched at `since`.
+    pub fn decayed(&self, heat: f64, since: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let days = (now - since).num_seconds().max(0) as f64 / 86_400.0;
+        heat * 0.5f64.powf(days / self.half_life_days)
+    }
+}
+
+/// Stored heat for one entity: each access adds 1.0, which then decays.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Heat {
+    /// Heat as of `last_access`
+    pub score: f64,
+    pub last_access: DateTime<Utc>,
+}
+
+/// How much attention an entity has had.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityHeat {
+    pub entity_id: String,
+    /// Heat now, after decay
+    pub heat: f64,
+    /// Accesses over the lifetime of the session state
+    pub accesses: u32,
+    pub last_access: Option<DateTime<Utc>>,
+}
+
 /// Session state that persists across process restarts.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SessionState {
     /// When the last session ended (None for new session)
     pub last_session: Option<DateTime<Utc>>,
-    /// Explicitly pinned entity IDs
-    pub pinned_entities: Vec<String>,
+    /// Explicitly pinned entities, in the order they were pinned
+    pub pinned_entities: Vec<Pin>,
     /// Recent entity accesses (most recent first, max 100)
     pub recent_accesses: Vec<String>,
+    /// How often each entity was accessed, for pin suggestions
+    #[serde(default)]
+    pub access_counts: HashMap<String, u32>,
+    /// Decaying attention per entity, for the hot list
+    #[serde(default)]
+    pub heat: HashMap<String, Heat>,
+    /// Sessions ended so far
+    #[serde(default)]
+    pub session_count: u32,
     /// Pending decisions from impact analysis
     pub pending_decisions: Vec<PendingDecision>,
+    /// Writing session journal (oldest first)
+    #[serde(default)]
+    pub journal: Vec<JournalEntry>,
+    /// Latest scheduled annotation run
+    #[serde(default)]
+    pub last_scheduled_run: Option<ScheduledRun>,
 }
 
 /// Manages session state persistence to disk.
@@ -37,6 +220,10 @@ pub struct SessionStateManager {
     state_path: PathBuf,
     /// Current session state
     state: Arc<RwLock<SessionState>>,
+    /// How quickly hot entities cool down
+    decay: HotDecay,
+    /// How startup context is put together
+    startup: StartupOptions,
 }
 
 impl SessionStateManager {
@@ -59,9 +246,28 @@ impl SessionStateManager {
         Ok(Self {
             state_path: path.to_path_buf(),
             state: Arc::new(RwLock::new(state)),
+            decay: HotDecay::default(),
+            startup: StartupOptions::default(),
         })
     }
 
+    /// Use `decay` instead of the default cooling rate.
+    pub fn with_decay(mut self, decay: HotDecay) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Shape startup context with `options` instead of the defaults.
+    pub fn with_startup_options(mut self, options: StartupOptions) -> Self {
+        self.startup = options;
+        self
+    }
+
+    /// Options startup context is generated with.
+    pub fn startup_options(&self) -> &StartupOptions {
+        &self.startup
+    }
+
     /// Persist current state to disk.
     pub async fn save(&self) -> Result<(), NarraError> {
         let state = self.state.read().await;
@@ -99,28 +305,154 @@ impl SessionStateManager {
         if state.recent_accesses.len() > 100 {
             state.recent_accesses.truncate(100);
         }
+
+        *state
+            .access_counts
+            .entry(entity_id.to_string())
+            .or_default() += 1;
+
+        let now = Utc::now();
+        let score = state
+            .heat
+            .get(entity_id)
+            .map(|h| self.decay.decayed(h.score, h.last_access, now))
+            .unwrap_or(0.0);
+        state.heat.insert(
+            entity_id.to_string(),
+            Heat {
+                score: score + 1.0,
+                last_access: now,
+            },
+        );
     }
 
-    /// Pin an entity.
+    /// Hot entities: recent accesses ordered by decayed heat, dropping the
+    /// ones that have cooled below `HOT_FLOOR`. Entities recorded before heat
+    /// was tracked follow, in recency order.
+    pub async fn get_hot(&self, limit: usize) -> Vec<String> {
+        let state = self.state.read().await;
+        let now = Utc::now();
+        let mut hot: Vec<(&String, Option<f64>)> = state
+            .recent_accesses
+            .iter()
+            .map(|id| {
+                let heat = state
+                    .heat
+                    .get(id)
+                    .map(|h| self.decay.decayed(h.score, h.last_access, now));
+                (id, heat)
+            })
+            .filter(|(_, heat)| heat.is_none_or(|h| h >= HOT_FLOOR))
+            .collect();
+        // Stable: equal heat keeps recency order, untracked entities go last
+        hot.sort_by(|a, b| b.1.unwrap_or(0.0).total_cmp(&a.1.unwrap_or(0.0)));
+        hot.into_iter()
+            .take(limit)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Attention per entity ever accessed, hottest first.
+    pub async fn get_heat(&self) -> Vec<EntityHeat> {
+        let state = self.state.read().await;
+        let now = Utc::now();
+        let mut heat: Vec<EntityHeat> = state
+            .access_counts
+            .iter()
+            .map(|(id, accesses)| {
+                let stored = state.heat.get(id);
+                EntityHeat {
+                    entity_id: id.clone(),
+                    heat: stored
+                        .map(|h| self.decay.decayed(h.score, h.last_access, now))
+                        .unwrap_or(0.0),
+                    accesses: *accesses,
+                    last_access: stored.map(|h| h.last_access),
+                }
+            })
+            .collect();
+        heat.sort_by(|a, b| {
+            b.heat
+                .total_cmp(&a.heat)
+                .then_with(|| a.entity_id.cmp(&b.entity_id))
+        });
+        heat
+    }
+
+    /// Pin an entity at normal priority, with no expiry.
     pub async fn pin_entity(&self, entity_id: &str) {
-        let mut state = self.state.write().await;
+        self.pin_entity_with(entity_id, PinPriority::default(), None)
+            .await;
+    }
 
-        // Only add if not already pinned
-        if !state.pinned_entities.contains(&entity_id.to_string()) {
-            state.pinned_entities.push(entity_id.to_string());
+    /// Pin an entity, or change an existing pin's priority and expiry.
+    pub async fn pin_entity_with(
+        &self,
+        entity_id: &str,
+        priority: PinPriority,
+        until: Option<String>,
+    ) {
+        let mut state = self.state.write().await;
+        match state
+            .pinned_entities
+            .iter_mut()
+            .find(|p| p.entity_id == entity_id)
+        {
+            Some(pin) => {
+                pin.priority = priority;
+                pin.until = until;
+            }
+            None => state.pinned_entities.push(Pin {
+                entity_id: entity_id.to_string(),
+                priority,
+                until,
+            }),
         }
     }
 
     /// Unpin an entity.
     pub async fn unpin_entity(&self, entity_id: &str) {
         let mut state = self.state.write().await;
-        state.pinned_entities.retain(|e| e != entity_id);
+        state.pinned_entities.retain(|p| p.entity_id != entity_id);
     }
 
-    /// Get list of pinned entities.
+    /// Get pinned entity IDs, highest priority first.
     pub async fn get_pinned(&self) -> Vec<String> {
+        self.get_pins()
+            .await
+            .into_iter()
+            .map(|p| p.entity_id)
+            .collect()
+    }
+
+    /// Get pins, highest priority first, then in the order they were pinned.
+    pub async fn get_pins(&self) -> Vec<Pin> {
+        let state = self.state.read().await;
+        let mut pins = state.pinned_entities.clone();
+        pins.sort_by(|a, b| b.priority.cmp(&a.priority));
+        pins
+    }
+
+    /// Frequently accessed entities that aren't pinned, most accessed first.
+    ///
+    /// Returns up to `limit` entities accessed at least
+    /// `PIN_SUGGESTION_ACCESSES` times.
+    pub async fn suggest_pins(&self, limit: usize) -> Vec<String> {
         let state = self.state.read().await;
-        state.pinned_entities.clone()
+        let mut hot: Vec<(&String, u32)> = state
+            .access_counts
+            .iter()
+            .filter(|(id, count)| {
+                **count >= PIN_SUGGESTION_ACCESSES
+                    && !state.pinned_entities.iter().any(|p| &p.entity_id == *id)
+            })
+            .map(|(id, count)| (id, *count))
+            .collect();
+        hot.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        hot.into_iter()
+            .take(limit)
+            .map(|(id, _)| id.clone())
+            .collect()
     }
 
     /// Get recent entity accesses.
@@ -149,12 +481,55 @@ impl SessionStateManager {
         state.pending_decisions.clone()
     }
 
+    /// Add a journal entry.
+    pub async fn log_entry(&self, message: &str, entities: Vec<String>) -> JournalEntry {
+        let entry = JournalEntry {
+            message: message.to_string(),
+            created_at: Utc::now(),
+            entities,
+        };
+        let mut state = self.state.write().await;
+        state.journal.push(entry.clone());
+        entry
+    }
+
+    /// Get journal entries, most recent first.
+    ///
+    /// Returns up to `limit` entries.
+    pub async fn get_journal(&self, limit: usize) -> Vec<JournalEntry> {
+        let state = self.state.read().await;
+        state.journal.iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Record the outcome of a scheduled annotation run.
+    pub async fn record_scheduled_run(&self, run: ScheduledRun) {
+        let mut state = self.state.write().await;
+        state.last_scheduled_run = Some(run);
+    }
+
+    /// Latest scheduled annotation run, if any.
+    pub async fn get_last_scheduled_run(&self) -> Option<ScheduledRun> {
+        let state = self.state.read().await;
+        state.last_scheduled_run.clone()
+    }
+
     /// Mark the end of a session.
     ///
-    /// Sets last_session to current time.
+    /// Sets last_session to current time and cools every entity by the
+    /// per-session decay.
     pub async fn mark_session_end(&self) {
         let mut state = self.state.write().await;
         state.last_session = Some(Utc::now());
+        state.session_count += 1;
+        for heat in state.heat.values_mut() {
+            heat.score *= self.decay.per_session;
+        }
+    }
+
+    /// Number of sessions ended so far.
+    pub async fn get_session_count(&self) -> u32 {
+        let state = self.state.read().await;
+        state.session_count
     }
 
     /// Get the last session timestamp.
@@ -0,0 +1,122 @@
+//! Session analytics: where the author's attention has gone.
+//!
+//! Built on the decaying heat the session state keeps per entity: hot
+//! entities are the current focus, entities that had attention and cooled
+//! off are neglected, and characters and locations never opened at all are
+//! untouched.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::db::connection::NarraDb;
+use crate::error::NarraError;
+use crate::session::startup::{format_time_ago, get_hot_entity_details};
+use crate::session::{EntityHeat, HotEntity, SessionStateManager, HOT_FLOOR};
+
+/// Heat below which an entity with repeated accesses counts as neglected.
+pub const NEGLECTED_BELOW: f64 = 0.25;
+
+/// Accesses before a cooled-off entity counts as neglected rather than a
+/// one-off look.
+const NEGLECTED_MIN_ACCESSES: u32 = 2;
+
+/// One entity's share of attention.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusEntity {
+    pub id: String,
+    /// Entity name, when it could be looked up
+    pub name: Option<String>,
+    pub heat: f64,
+    pub accesses: u32,
+    pub last_accessed: Option<String>,
+}
+
+/// What the author has focused on and what they've let slide.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionStats {
+    /// Sessions ended so far
+    pub session_count: u32,
+    pub last_session_ago: Option<String>,
+    pub journal_entries: usize,
+    /// Entities accessed at least once
+    pub tracked_entities: usize,
+    /// Hottest entities, most attention first
+    pub focus: Vec<FocusEntity>,
+    /// Entities that had repeated attention and have cooled off, most
+    /// accessed first
+    pub neglected: Vec<FocusEntity>,
+    /// Characters and locations never accessed
+    pub untouched: Vec<HotEntity>,
+}
+
+/// Attention statistics, with up to `limit` entities per list.
+pub async fn session_stats(
+    session_manager: &SessionStateManager,
+    db: &NarraDb,
+    limit: usize,
+) -> Result<SessionStats, NarraError> {
+    let heat = session_manager.get_heat().await;
+
+    let focus: Vec<&EntityHeat> = heat
+        .iter()
+        .filter(|h| h.heat >= HOT_FLOOR)
+        .take(limit)
+        .collect();
+    let mut neglected: Vec<&EntityHeat> = heat
+        .iter()
+        .filter(|h| h.heat < NEGLECTED_BELOW && h.accesses >= NEGLECTED_MIN_ACCESSES)
+        .collect();
+    neglected.sort_by(|a, b| {
+        b.accesses
+            .cmp(&a.accesses)
+            .then_with(|| a.last_access.cmp(&b.last_access))
+    });
+    neglected.truncate(limit);
+
+    let ids: Vec<String> = focus
+        .iter()
+        .chain(&neglected)
+        .map(|h| h.entity_id.clone())
+        .collect();
+    let details = get_hot_entity_details(db, &ids).await;
+    let describe = |h: &EntityHeat| FocusEntity {
+        id: h.entity_id.clone(),
+        name: details
+            .iter()
+            .find(|e| e.id == h.entity_id)
+            .map(|e| e.name.clone()),
+        heat: h.heat,
+        accesses: h.accesses,
+        last_accessed: h.last_access.map(format_time_ago),
+    };
+
+    let tracked: HashSet<&str> = heat.iter().map(|h| h.entity_id.as_str()).collect();
+    let mut result = db
+        .query("SELECT VALUE id FROM character ORDER BY name")
+        .query("SELECT VALUE id FROM location ORDER BY name")
+        .await?;
+    let characters: Vec<RecordId> = result.take(0)?;
+    let locations: Vec<RecordId> = result.take(1)?;
+    let untouched_ids: Vec<String> = characters
+        .iter()
+        .chain(&locations)
+        .map(|id| id.to_string())
+        .filter(|id| !tracked.contains(id.as_str()))
+        .take(limit)
+        .collect();
+
+    Ok(SessionStats {
+        session_count: session_manager.get_session_count().await,
+        last_session_ago: session_manager
+            .get_last_session()
+            .await
+            .map(format_time_ago),
+        journal_entries: session_manager.get_journal(usize::MAX).await.len(),
+        tracked_entities: heat.len(),
+        focus: focus.into_iter().map(describe).collect(),
+        neglected: neglected.into_iter().map(describe).collect(),
+        untouched: get_hot_entity_details(db, &untouched_ids).await,
+    })
+}
@@ -15,6 +15,7 @@ async fn test_per_request_budget_low() {
     let input = QueryInput {
         operation: "situation_report".to_string(),
         token_budget: Some(500), // Should trigger "summary" mode
+        truncation_strategy: None,
         params: serde_json::Map::new(),
     };
 
@@ -39,6 +40,7 @@ async fn test_per_request_budget_high() {
     let input = QueryInput {
         operation: "situation_report".to_string(),
         token_budget: Some(6000), // Should trigger "full" mode
+        truncation_strategy: None,
         params: serde_json::Map::new(),
     };
 
@@ -63,6 +65,7 @@ async fn test_explicit_detail_level_wins() {
     let input = QueryInput {
         operation: "situation_report".to_string(),
         token_budget: Some(500), // Low budget, but explicit detail_level should win
+        truncation_strategy: None,
         params,
     };
 
@@ -85,6 +88,7 @@ async fn test_tool_type_defaults() {
     let composite_input = QueryInput {
         operation: "situation_report".to_string(),
         token_budget: None, // Use per-tool-type default
+        truncation_strategy: None,
         params: serde_json::Map::new(),
     };
 
@@ -119,6 +123,7 @@ async fn test_env_var_override() {
     let input = QueryInput {
         operation: "situation_report".to_string(),
         token_budget: None, // Should use env var (1500) instead of tool-type default (4000)
+        truncation_strategy: None,
         params: serde_json::Map::new(),
     };
 
@@ -142,6 +147,7 @@ async fn test_max_budget_cap() {
     let input = QueryInput {
         operation: "situation_report".to_string(),
         token_budget: Some(999999), // Should be capped at MAX_TOKEN_BUDGET (8000)
+        truncation_strategy: None,
         params: serde_json::Map::new(),
     };
 
@@ -192,6 +198,7 @@ async fn test_token_budget_triggers_truncation() {
     let input = QueryInput {
         operation: "overview".to_string(),
         token_budget: Some(100), // Very small — should truncate 15 results
+        truncation_strategy: None,
         params,
     };
 
@@ -222,3 +229,66 @@ async fn test_token_budget_triggers_truncation() {
         "Should return at least 1 result even when budget is tiny"
     );
 }
+
+/// Test that the "summarize" strategy keeps every result but shortens each one.
+#[tokio::test]
+async fn test_summarize_strategy_keeps_all_results() {
+    let harness = TestHarness::new().await;
+    let server = create_test_server(&harness).await;
+
+    // Six locations whose descriptions together far exceed the budget
+    for i in 0..6 {
+        let name = format!("Summarized_{}", i);
+        let desc = "A sprawling account of a crumbling keep. ".repeat(20);
+        harness
+            .db
+            .query(format!(
+                "CREATE location:{} SET name = '{}', loc_type = 'place', description = '{}'",
+                name.to_lowercase(),
+                name,
+                desc
+            ))
+            .await
+            .unwrap();
+    }
+
+    let mut params = serde_json::Map::new();
+    params.insert("entity_type".to_string(), json!("location"));
+
+    let input = QueryInput {
+        operation: "overview".to_string(),
+        token_budget: Some(600),
+        truncation_strategy: Some("summarize".to_string()),
+        params,
+    };
+
+    let response = server
+        .handle_query(Parameters(input))
+        .await
+        .expect("Query should succeed with summarize strategy");
+
+    let truncation = response
+        .truncated
+        .expect("Over-budget response should report summarization");
+    assert_eq!(truncation.reason, "summarized");
+    assert_eq!(truncation.returned_count, truncation.original_count);
+    assert_eq!(response.results.len(), 6, "No results should be dropped");
+    assert!(response.token_estimate <= 600);
+}
+
+/// Test that an unknown truncation strategy is rejected.
+#[tokio::test]
+async fn test_unknown_truncation_strategy_errors() {
+    let harness = TestHarness::new().await;
+    let server = create_test_server(&harness).await;
+
+    let input = QueryInput {
+        operation: "situation_report".to_string(),
+        token_budget: None,
+        truncation_strategy: Some("squash".to_string()),
+        params: serde_json::Map::new(),
+    };
+
+    let result = server.handle_query(Parameters(input)).await;
+    assert!(result.is_err());
+}
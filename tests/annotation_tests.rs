@@ -26,6 +26,7 @@ async fn create_test_character(server: &NarraServer, name: &str) -> String {
         aliases: None,
         description: Some("A test character".to_string()),
         profile: None,
+        allow_similar: None,
     };
     let result = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -341,6 +342,7 @@ async fn test_entity_update_marks_annotations_stale() {
     let update = MutationRequest::Update {
         entity_id: char_id.clone(),
         fields: serde_json::json!({"description": "Updated description"}),
+        force: None,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(update)))
@@ -376,6 +378,7 @@ async fn test_entity_delete_removes_annotations() {
     let delete = MutationRequest::Delete {
         entity_id: char_id.clone(),
         hard: None,
+        force: None,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(delete)))
@@ -715,6 +718,7 @@ async fn test_entity_update_marks_both_annotation_types_stale() {
     let update = MutationRequest::Update {
         entity_id: char_id.clone(),
         fields: serde_json::json!({"description": "Changed description"}),
+        force: None,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(update)))
@@ -769,6 +773,7 @@ async fn test_entity_delete_removes_all_annotation_types() {
     let delete = MutationRequest::Delete {
         entity_id: char_id.clone(),
         hard: None,
+        force: None,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(delete)))
@@ -1092,6 +1097,7 @@ async fn test_entity_update_marks_all_three_annotation_types_stale() {
     let update = MutationRequest::Update {
         entity_id: char_id.clone(),
         fields: serde_json::json!({"description": "Changed again"}),
+        force: None,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(update)))
@@ -1155,6 +1161,7 @@ async fn test_entity_delete_removes_all_three_annotation_types() {
     let delete = MutationRequest::Delete {
         entity_id: char_id.clone(),
         hard: None,
+        force: None,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(delete)))
@@ -1303,6 +1310,7 @@ async fn test_narrative_tensions_query_serialization() {
     let request = QueryRequest::NarrativeTensions {
         limit: None,
         min_severity: None,
+        phase: None,
     };
     let json = serde_json::to_value(&request).expect("serialize");
     assert_eq!(json["operation"], "narrative_tensions");
@@ -1312,6 +1320,7 @@ async fn test_narrative_tensions_query_serialization() {
         QueryRequest::NarrativeTensions {
             limit,
             min_severity,
+            ..
         } => {
             assert!(limit.is_none());
             assert!(min_severity.is_none());
@@ -1325,6 +1334,7 @@ async fn test_narrative_tensions_query_serialization_with_params() {
     let request = QueryRequest::NarrativeTensions {
         limit: Some(5),
         min_severity: Some(0.3),
+        phase: None,
     };
     let json = serde_json::to_value(&request).expect("serialize");
     assert_eq!(json["operation"], "narrative_tensions");
@@ -1335,6 +1345,7 @@ async fn test_narrative_tensions_query_serialization_with_params() {
         QueryRequest::NarrativeTensions {
             limit,
             min_severity,
+            ..
         } => {
             assert_eq!(limit, Some(5));
             assert!((min_severity.unwrap() - 0.3).abs() < 0.001);
@@ -1351,6 +1362,7 @@ async fn test_narrative_tensions_via_mcp_empty_world() {
     let request = QueryRequest::NarrativeTensions {
         limit: Some(10),
         min_severity: None,
+        phase: None,
     };
     let result = server
         .handle_query(Parameters(to_query_input(request)))
@@ -1438,6 +1450,7 @@ async fn test_narrative_tensions_via_mcp_with_conflicting_loyalties() {
     let request = QueryRequest::NarrativeTensions {
         limit: Some(20),
         min_severity: Some(0.0),
+        phase: None,
     };
     let result = server
         .handle_query(Parameters(to_query_input(request)))
@@ -1569,6 +1582,7 @@ async fn test_annotate_entities_mutation_serialization() {
         run_themes: true,
         run_ner: true,
         concurrency: None,
+        incremental: false,
     };
     let json = serde_json::to_value(&request).expect("serialize");
     assert_eq!(json["operation"], "annotate_entities");
@@ -1584,6 +1598,7 @@ async fn test_annotate_entities_mutation_serialization() {
             run_themes,
             run_ner,
             concurrency,
+            ..
         } => {
             assert!(entity_types.is_none());
             assert!(run_emotions);
@@ -1603,6 +1618,7 @@ async fn test_annotate_entities_mutation_serialization_with_params() {
         run_themes: false,
         run_ner: true,
         concurrency: Some(2),
+        incremental: false,
     };
     let json = serde_json::to_value(&request).expect("serialize");
     assert_eq!(json["operation"], "annotate_entities");
@@ -1619,6 +1635,7 @@ async fn test_annotate_entities_mutation_serialization_with_params() {
             run_themes,
             run_ner,
             concurrency,
+            ..
         } => {
             assert_eq!(
                 entity_types,
@@ -1645,6 +1662,7 @@ async fn test_annotate_entities_via_mcp_empty_world() {
         run_themes: true,
         run_ner: true,
         concurrency: None,
+        incremental: false,
     };
     let result = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -1681,6 +1699,7 @@ async fn test_annotate_entities_via_mcp_with_characters() {
         run_themes: true,
         run_ner: true,
         concurrency: Some(1),
+        incremental: false,
     };
     let result = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -1705,6 +1724,7 @@ async fn test_annotate_entities_via_mcp_custom_types() {
         run_themes: true,
         run_ner: false,
         concurrency: Some(2),
+        incremental: false,
     };
     let result = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -2004,3 +2024,166 @@ async fn test_extract_entities_happy_path_via_mcp() {
         content
     );
 }
+
+// =============================================================================
+// Incremental re-annotation
+// =============================================================================
+
+/// Emotion service that caches like the real one and counts classifications.
+struct CountingEmotionService {
+    db: Arc<narra::db::connection::NarraDb>,
+    runs: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait]
+impl EmotionService for CountingEmotionService {
+    async fn get_emotions(&self, entity_id: &str, text: &str) -> Result<EmotionOutput, NarraError> {
+        if let Some(cached) = get_annotation(&self.db, entity_id, "emotion").await? {
+            if !cached.stale {
+                return Ok(serde_json::from_value(cached.output)?);
+            }
+        }
+        let output = self.classify_text(text).await?;
+        upsert_annotation(
+            &self.db,
+            AnnotationCreate {
+                entity_id: entity_id.to_string(),
+                model_type: "emotion".to_string(),
+                model_version: "counting".to_string(),
+                output: serde_json::to_value(&output)?,
+            },
+        )
+        .await?;
+        Ok(output)
+    }
+
+    async fn classify_text(&self, _text: &str) -> Result<EmotionOutput, NarraError> {
+        self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        MockEmotionService.classify_text("").await
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+#[tokio::test]
+async fn test_incremental_annotation_reruns_only_changed_input() {
+    use narra::services::{
+        noop_progress, AnnotationPipeline, NoopNerService, NoopThemeService, PipelineConfig,
+    };
+    use std::sync::atomic::Ordering;
+
+    let harness = TestHarness::new().await;
+    let server = common::create_test_server(&harness).await;
+    let alice = create_test_character(&server, "Alice").await;
+
+    let emotions = Arc::new(CountingEmotionService {
+        db: harness.db.clone(),
+        runs: Default::default(),
+    });
+    let pipeline = AnnotationPipeline::new(
+        harness.db.clone(),
+        emotions.clone(),
+        Arc::new(NoopThemeService::new()),
+        Arc::new(NoopNerService::new()),
+    );
+    let config = PipelineConfig {
+        incremental: true,
+        ..Default::default()
+    };
+    let run = || pipeline.annotate_all(&["character"], config.clone(), noop_progress());
+
+    let first = run().await.unwrap();
+    assert_eq!(emotions.runs.load(Ordering::SeqCst), 1);
+    assert_eq!(first.unchanged, 0);
+    let annotation = get_annotation(&harness.db, &alice, "emotion")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(annotation.input_hash.is_some());
+
+    // Stale, but the text the model reads is the same: nothing re-runs
+    mark_annotations_stale(&harness.db, &alice).await.unwrap();
+    let second = run().await.unwrap();
+    assert_eq!(emotions.runs.load(Ordering::SeqCst), 1);
+    assert_eq!(second.unchanged, 1);
+    assert_eq!(second.emotion_successes, 1);
+    let annotation = get_annotation(&harness.db, &alice, "emotion")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!annotation.stale);
+
+    // New text re-runs the model even though nothing marked it stale
+    harness
+        .db
+        .query("UPDATE type::thing($id) SET composite_text = 'Alice lost everything.'")
+        .bind(("id", alice.clone()))
+        .await
+        .unwrap();
+    let third = run().await.unwrap();
+    assert_eq!(emotions.runs.load(Ordering::SeqCst), 2);
+    assert_eq!(third.unchanged, 0);
+}
+
+#[tokio::test]
+async fn test_scheduled_run_is_incremental_and_shows_in_session_context() {
+    use narra::services::scheduler::{run_scheduled, AnnotationSchedule};
+    use narra::services::{
+        AnnotationPipeline, HeuristicTokenCounter, NoopNerService, NoopThemeService,
+    };
+    use narra::session::{generate_startup_context, SessionStateManager};
+    use std::sync::atomic::Ordering;
+
+    let harness = TestHarness::new().await;
+    let server = common::create_test_server(&harness).await;
+    let alice = create_test_character(&server, "Alice").await;
+    harness
+        .db
+        .query("UPDATE type::thing($id) SET embedding = [0.1, 0.2, 0.3]")
+        .bind(("id", alice.clone()))
+        .await
+        .unwrap();
+
+    let emotions = Arc::new(CountingEmotionService {
+        db: harness.db.clone(),
+        runs: Default::default(),
+    });
+    let pipeline = AnnotationPipeline::new(
+        harness.db.clone(),
+        emotions.clone(),
+        Arc::new(NoopThemeService::new()),
+        Arc::new(NoopNerService::new()),
+    );
+
+    let first = run_scheduled(&harness.db, &pipeline, AnnotationSchedule::OnMutation)
+        .await
+        .unwrap();
+    assert_eq!(first.trigger, "on_mutation");
+    assert_eq!(first.arc_snapshots, 1);
+    assert_eq!(emotions.runs.load(Ordering::SeqCst), 1);
+
+    // Nothing changed: no model re-runs and no new baseline
+    let second = run_scheduled(&harness.db, &pipeline, AnnotationSchedule::OnMutation)
+        .await
+        .unwrap();
+    assert_eq!(emotions.runs.load(Ordering::SeqCst), 1);
+    assert_eq!(second.unchanged, 1);
+    assert_eq!(second.arc_snapshots, 0);
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let session_path = temp_dir.path().join("session.json");
+    {
+        let manager = SessionStateManager::load_or_create(&session_path).unwrap();
+        manager.record_scheduled_run(second).await;
+        manager.save().await.unwrap();
+    }
+    let manager = SessionStateManager::load_or_create(&session_path).unwrap();
+    let info = generate_startup_context(&manager, &harness.db, &HeuristicTokenCounter)
+        .await
+        .unwrap();
+    let run = info.scheduled_run.expect("scheduled run in context");
+    assert_eq!(run.trigger, "on_mutation");
+    assert_eq!(run.unchanged, 1);
+}
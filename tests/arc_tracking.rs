@@ -12,11 +12,13 @@ mod common;
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use common::{harness::TestHarness, to_mutation_input, to_query_input};
 use narra::embedding::backfill::BackfillService;
 use narra::embedding::{
-    EmbeddingConfig, LocalEmbeddingService, NoopEmbeddingService, StalenessManager,
+    ArcSnapshotPolicy, EmbeddingConfig, LocalEmbeddingService, NoopEmbeddingService,
+    StalenessManager,
 };
 use narra::mcp::{MutationRequest, NarraServer, QueryRequest};
 use narra::models::character::{
@@ -27,6 +29,14 @@ use narra::session::SessionStateManager;
 use rmcp::handler::server::wrapper::Parameters;
 use surrealdb::Datetime;
 
+/// Snapshot on every re-embedding, so tests don't depend on drift or timing.
+fn every_change() -> ArcSnapshotPolicy {
+    ArcSnapshotPolicy {
+        drift_threshold: 0.0,
+        debounce: Duration::ZERO,
+    }
+}
+
 /// Helper to create a 384-dimensional test embedding vector.
 /// HNSW indexes on character/knowledge tables require 384-dim vectors.
 fn test_embedding(seed: f32) -> Vec<f32> {
@@ -1025,6 +1035,7 @@ async fn test_arc_moment_at_event() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -1069,10 +1080,10 @@ async fn test_regeneration_creates_arc_snapshot() {
     let harness = TestHarness::new().await;
     let embedding_service =
         Arc::new(LocalEmbeddingService::new(EmbeddingConfig::default()).unwrap());
-    let staleness_manager = Arc::new(StalenessManager::new(
-        harness.db.clone(),
-        embedding_service.clone(),
-    ));
+    let staleness_manager = Arc::new(
+        StalenessManager::new(harness.db.clone(), embedding_service.clone())
+            .with_arc_policy(every_change()),
+    );
 
     let alice = create_character(
         &harness.db,
@@ -1181,10 +1192,10 @@ async fn test_snapshot_captures_event_id() {
     let harness = TestHarness::new().await;
     let embedding_service =
         Arc::new(LocalEmbeddingService::new(EmbeddingConfig::default()).unwrap());
-    let staleness_manager = Arc::new(StalenessManager::new(
-        harness.db.clone(),
-        embedding_service.clone(),
-    ));
+    let staleness_manager = Arc::new(
+        StalenessManager::new(harness.db.clone(), embedding_service.clone())
+            .with_arc_policy(every_change()),
+    );
     let server = create_test_server_with_embeddings(&harness, embedding_service.clone()).await;
 
     let alice = create_character(
@@ -1208,6 +1219,7 @@ async fn test_snapshot_captures_event_id() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -1326,10 +1338,10 @@ async fn test_full_arc_tracking_workflow() {
     let harness = TestHarness::new().await;
     let embedding_service =
         Arc::new(LocalEmbeddingService::new(EmbeddingConfig::default()).unwrap());
-    let staleness_manager = Arc::new(StalenessManager::new(
-        harness.db.clone(),
-        embedding_service.clone(),
-    ));
+    let staleness_manager = Arc::new(
+        StalenessManager::new(harness.db.clone(), embedding_service.clone())
+            .with_arc_policy(every_change()),
+    );
     let backfill_service = BackfillService::new(harness.db.clone(), embedding_service.clone());
     let server = create_test_server_with_embeddings(&harness, embedding_service.clone()).await;
 
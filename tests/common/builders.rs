@@ -131,6 +131,7 @@ impl EventBuilder {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         }
     }
 }
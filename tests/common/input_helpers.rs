@@ -23,6 +23,7 @@ pub fn to_query_input(request: QueryRequest) -> QueryInput {
     QueryInput {
         operation,
         token_budget: None,
+        truncation_strategy: None,
         params: obj,
     }
 }
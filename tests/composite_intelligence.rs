@@ -1144,3 +1144,51 @@ async fn test_scene_plan_annotation_fields_none() {
         "scene_themes should be None when populated at handler level"
     );
 }
+
+/// Test cached reports are reused until the world changes.
+#[tokio::test]
+async fn test_situation_report_cache_follows_revision() {
+    let harness = TestHarness::new().await;
+    let service = CompositeIntelligenceService::new(harness.db.clone());
+
+    let before = service.world_revision().await.unwrap();
+    assert_eq!(before, service.world_revision().await.unwrap());
+    service.situation_report().await.unwrap();
+
+    let mut response = harness
+        .db
+        .query("SELECT VALUE revision FROM composite_cache")
+        .await
+        .unwrap();
+    let cached: Vec<String> = response.take(0).unwrap();
+    assert_eq!(cached, vec![before.clone()]);
+
+    create_character(
+        &harness.db,
+        CharacterCreate {
+            name: "Alice".into(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let after = service.world_revision().await.unwrap();
+    assert_ne!(
+        before, after,
+        "Creating a character should change the revision"
+    );
+
+    // A fresh run recomputes and replaces the stale entry
+    CompositeIntelligenceService::new(harness.db.clone())
+        .fresh(true)
+        .situation_report()
+        .await
+        .unwrap();
+    let mut response = harness
+        .db
+        .query("SELECT VALUE revision FROM composite_cache")
+        .await
+        .unwrap();
+    let cached: Vec<String> = response.take(0).unwrap();
+    assert_eq!(cached, vec![after]);
+}
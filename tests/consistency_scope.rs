@@ -66,6 +66,7 @@ async fn test_pov_scope_character_applies_to_target_only() {
             categories: vec![],
             enforcement_level: EnforcementLevel::Strict,
             scope: Some(FactScope {
+                location: None,
                 pov: Some(PovScope::Character(format!("character:{}", alice_key))),
                 temporal: None,
             }),
@@ -158,6 +159,7 @@ async fn test_pov_scope_group_applies_to_role_members() {
             categories: vec![],
             enforcement_level: EnforcementLevel::Strict,
             scope: Some(FactScope {
+                location: None,
                 pov: Some(PovScope::Group("warriors".to_string())),
                 temporal: None,
             }),
@@ -254,6 +256,7 @@ async fn test_pov_scope_except_excludes_listed_characters() {
             categories: vec![],
             enforcement_level: EnforcementLevel::Strict,
             scope: Some(FactScope {
+                location: None,
                 pov: Some(PovScope::ExceptCharacters(vec![format!(
                     "character:{}",
                     alice_key
@@ -320,6 +323,7 @@ async fn test_temporal_scope_before_valid_from_skips_fact() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -334,6 +338,7 @@ async fn test_temporal_scope_before_valid_from_skips_fact() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -362,8 +367,11 @@ async fn test_temporal_scope_before_valid_from_skips_fact() {
             categories: vec![],
             enforcement_level: EnforcementLevel::Strict,
             scope: Some(FactScope {
+                location: None,
                 pov: None,
                 temporal: Some(TemporalScope {
+                    from_sequence: None,
+                    until_sequence: None,
                     valid_from_event: Some(later_event_id),
                     valid_until_event: None,
                     freeform_description: None,
@@ -419,6 +427,7 @@ async fn test_temporal_scope_after_valid_until_skips_fact() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -434,6 +443,7 @@ async fn test_temporal_scope_after_valid_until_skips_fact() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -462,8 +472,11 @@ async fn test_temporal_scope_after_valid_until_skips_fact() {
             categories: vec![],
             enforcement_level: EnforcementLevel::Strict,
             scope: Some(FactScope {
+                location: None,
                 pov: None,
                 temporal: Some(TemporalScope {
+                    from_sequence: None,
+                    until_sequence: None,
                     valid_from_event: None,
                     valid_until_event: Some(seal_event_id),
                     freeform_description: None,
@@ -533,6 +546,7 @@ async fn test_combined_pov_and_temporal_scope_intersection() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -547,6 +561,7 @@ async fn test_combined_pov_and_temporal_scope_intersection() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -562,6 +577,7 @@ async fn test_combined_pov_and_temporal_scope_intersection() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -579,8 +595,11 @@ async fn test_combined_pov_and_temporal_scope_intersection() {
             categories: vec![],
             enforcement_level: EnforcementLevel::Strict,
             scope: Some(FactScope {
+                location: None,
                 pov: Some(PovScope::Character(format!("character:{}", alice_key))),
                 temporal: Some(TemporalScope {
+                    from_sequence: None,
+                    until_sequence: None,
                     valid_from_event: None,
                     valid_until_event: Some(expiry_id),
                     freeform_description: None,
@@ -676,3 +695,243 @@ async fn test_no_scope_fact_applies_globally() {
         "Unscopeed fact should apply globally and trigger violations"
     );
 }
+
+// =============================================================================
+// LOCATION AND SEQUENCE SCOPE
+// =============================================================================
+
+/// A fact scoped to a location subtree holds for entities placed anywhere in
+/// it, without being linked, and only within its sequence range.
+#[tokio::test]
+async fn test_location_subtree_and_sequence_range_scope() {
+    use narra::models::event::create_event_with_id;
+    use narra::models::fact::apply_scope_bounds;
+    use narra::models::location::{create_location_with_id, LocationCreate};
+    use narra::models::scene::{
+        add_scene_participant, create_scene_with_id, SceneCreate, SceneParticipantCreate,
+    };
+    use surrealdb::RecordId;
+
+    let harness = TestHarness::new().await;
+    let db = &harness.db;
+
+    for (key, parent) in [
+        ("citadel", None),
+        ("tower", Some("citadel")),
+        ("market", None),
+    ] {
+        create_location_with_id(
+            db,
+            key,
+            LocationCreate {
+                name: key.to_string(),
+                description: None,
+                loc_type: "place".to_string(),
+                parent: parent.map(|p| RecordId::from(("location", p))),
+            },
+        )
+        .await
+        .expect("Create location");
+    }
+    for (key, sequence) in [("dawn", 10), ("dusk", 30)] {
+        create_event_with_id(
+            db,
+            key,
+            EventCreate {
+                title: key.to_string(),
+                sequence,
+                description: None,
+                date: None,
+                date_precision: None,
+                duration_end: None,
+                narrative_status: None,
+            },
+        )
+        .await
+        .expect("Create event");
+    }
+    for (key, event, location) in [
+        ("tower_dawn", "dawn", "tower"),
+        ("tower_dusk", "dusk", "tower"),
+        ("market_dusk", "dusk", "market"),
+    ] {
+        create_scene_with_id(
+            db,
+            key,
+            SceneCreate {
+                title: key.to_string(),
+                summary: None,
+                event: RecordId::from(("event", event)),
+                primary_location: RecordId::from(("location", location)),
+                secondary_locations: vec![],
+            },
+        )
+        .await
+        .expect("Create scene");
+    }
+    for (name, scene) in [("Alice", "tower_dawn"), ("Bob", "market_dusk")] {
+        let character = create_character(
+            db,
+            CharacterCreate {
+                name: name.to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Create character");
+        add_scene_participant(
+            db,
+            SceneParticipantCreate {
+                character_id: character.id.key().to_string(),
+                scene_id: scene.to_string(),
+                role: "present".to_string(),
+                notes: None,
+            },
+        )
+        .await
+        .expect("Add participant");
+    }
+
+    let scope = apply_scope_bounds(db, None, Some("citadel"), Some("20"), None)
+        .await
+        .expect("Build scope");
+    assert_eq!(scope.location.as_deref(), Some("location:citadel"));
+    create_fact_with_id(
+        db,
+        "no_flight",
+        FactCreate {
+            title: "No flight".to_string(),
+            description: "Flight is prohibited inside the citadel after the curfew.".to_string(),
+            categories: vec![],
+            enforcement_level: EnforcementLevel::Warning,
+            scope: Some(scope.clone()),
+        },
+    )
+    .await
+    .expect("Create fact");
+
+    let checker = ConsistencyChecker::new(db.clone());
+    let flying = serde_json::json!({ "summary": "everyone takes flight" });
+    let violations = |id: &'static str| {
+        let checker = &checker;
+        let flying = &flying;
+        async move {
+            checker
+                .check_entity_mutation(id, flying)
+                .await
+                .expect("Check should succeed")
+                .total_violations
+        }
+    };
+
+    // Inside the subtree and after sequence 20: enforced without a link
+    assert_eq!(violations("scene:tower_dusk").await, 1);
+    // Inside the subtree but before the range starts
+    assert_eq!(violations("scene:tower_dawn").await, 0);
+    // Outside the subtree
+    assert_eq!(violations("scene:market_dusk").await, 0);
+    assert_eq!(violations("location:tower").await, 1);
+    assert_eq!(violations("location:market").await, 0);
+
+    // Bounds not given are kept; "none" clears one
+    let widened = apply_scope_bounds(db, Some(scope), None, Some("none"), Some("event:dawn"))
+        .await
+        .expect("Update scope");
+    let temporal = widened.temporal.expect("Temporal scope kept");
+    assert_eq!(widened.location.as_deref(), Some("location:citadel"));
+    assert_eq!(temporal.from_sequence, None);
+    assert_eq!(temporal.valid_until_event.as_deref(), Some("event:dawn"));
+    assert!(apply_scope_bounds(db, None, Some("atlantis"), None, None)
+        .await
+        .is_err());
+}
+
+// =============================================================================
+// EXCEPTIONS
+// =============================================================================
+
+/// An entity listed as an exception to a fact is skipped by consistency
+/// checks until the exception is removed.
+#[tokio::test]
+async fn test_fact_exception_skips_excepted_entity() {
+    use narra::models::character::create_character_with_id;
+    use narra::models::fact::{add_fact_exception, remove_fact_exception};
+
+    let harness = TestHarness::new().await;
+    let db = &harness.db;
+
+    for (key, name) in [("lyra", "Lyra"), ("bob", "Bob")] {
+        create_character_with_id(
+            db,
+            key,
+            CharacterCreate {
+                name: name.to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Create character");
+    }
+    let (lyra, bob) = ("character:lyra", "character:bob");
+
+    create_fact_with_id(
+        db,
+        "no_flight",
+        FactCreate {
+            title: "No flight".to_string(),
+            description: "Flight is prohibited for everyone in this world.".to_string(),
+            categories: vec![],
+            enforcement_level: EnforcementLevel::Strict,
+            scope: None,
+        },
+    )
+    .await
+    .expect("Create fact");
+    for id in [lyra, bob] {
+        link_fact_to_entity(db, "no_flight", id, "manual", None)
+            .await
+            .expect("Link fact");
+    }
+
+    let fact = add_fact_exception(db, "no_flight", lyra, "Born of the wind spirits")
+        .await
+        .expect("Add exception")
+        .expect("Fact exists");
+    assert_eq!(fact.exceptions.len(), 1);
+    // Re-adding replaces the justification
+    let fact = add_fact_exception(db, "no_flight", lyra, "Blessed by the storm")
+        .await
+        .expect("Replace exception")
+        .expect("Fact exists");
+    assert_eq!(
+        fact.exception_for(lyra).map(|e| e.reason.as_str()),
+        Some("Blessed by the storm")
+    );
+    assert!(add_fact_exception(db, "no_flight", bob, "  ")
+        .await
+        .is_err());
+
+    let checker = ConsistencyChecker::new(db.clone());
+    let flying = serde_json::json!({ "summary": "takes flight over the city" });
+    let violations = |id: &'static str| {
+        let checker = &checker;
+        let flying = &flying;
+        async move {
+            checker
+                .check_entity_mutation(id, flying)
+                .await
+                .expect("Check should succeed")
+                .total_violations
+        }
+    };
+
+    assert_eq!(violations("character:lyra").await, 0);
+    assert_eq!(violations("character:bob").await, 1);
+
+    let fact = remove_fact_exception(db, "no_flight", lyra)
+        .await
+        .expect("Remove exception")
+        .expect("Fact exists");
+    assert!(fact.exceptions.is_empty());
+    assert_eq!(violations("character:lyra").await, 1);
+}
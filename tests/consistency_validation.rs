@@ -376,3 +376,145 @@ async fn test_validate_character_no_relationships() {
     let rel_result = checker.check_relationship_violations(&alice_key).await;
     assert!(rel_result.is_ok(), "Relationship check should succeed");
 }
+
+/// Test reciprocal relationship checks.
+///
+/// Scenario: Alice mentors Bob with no reverse edge (missing), Alice is
+/// Carol's ally while Carol is Alice's enemy (contradictory).
+#[tokio::test]
+async fn test_reciprocal_relationship_checks() {
+    use narra::models::character::create_character_with_id;
+    use narra::models::relationship::create_relationship;
+    use narra::models::RelationshipCreate;
+    use narra::services::reciprocity::{
+        check_reciprocity, create_missing_reciprocals, set_intentional,
+    };
+
+    let harness = TestHarness::new().await;
+    let db = &harness.db;
+
+    for (key, name) in [("alice", "Alice"), ("bob", "Bob"), ("carol", "Carol")] {
+        create_character_with_id(
+            db,
+            key,
+            CharacterCreate {
+                name: name.to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create character");
+    }
+    let relate = |from: &str, to: &str, rel_type: &str, subtype: Option<&str>| RelationshipCreate {
+        from_character_id: from.to_string(),
+        to_character_id: to.to_string(),
+        rel_type: rel_type.to_string(),
+        subtype: subtype.map(str::to_string),
+        label: None,
+    };
+    create_relationship(db, relate("alice", "bob", "mentorship", Some("mentor")))
+        .await
+        .unwrap();
+    create_relationship(db, relate("alice", "carol", "ally", None))
+        .await
+        .unwrap();
+    let enemy = create_relationship(db, relate("carol", "alice", "enemy", None))
+        .await
+        .unwrap();
+
+    let checker = ConsistencyChecker::new(db.clone());
+    let violations = checker
+        .check_relationship_violations("alice")
+        .await
+        .expect("Relationship check should succeed");
+    assert!(violations
+        .iter()
+        .any(|v| v.fact_title == "Missing reciprocal relationship"
+            && v.severity == ConsistencySeverity::Info));
+    assert!(violations
+        .iter()
+        .any(|v| v.fact_title == "Contradictory relationship"
+            && v.severity == ConsistencySeverity::Warning));
+
+    // World-wide: one missing edge, one contradictory pair reported once
+    let issues = check_reciprocity(db, None).await.unwrap();
+    assert_eq!(issues.len(), 2, "{:?}", issues);
+
+    // Fixing creates Bob -> Alice as mentorship/student
+    assert_eq!(create_missing_reciprocals(db, &issues).await.unwrap(), 1);
+    let mut resp = db
+        .query("SELECT VALUE subtype FROM relates_to WHERE in = character:bob AND out = character:alice")
+        .await
+        .unwrap();
+    let subtypes: Vec<Option<String>> = resp.take(0).unwrap();
+    assert_eq!(subtypes, vec![Some("student".to_string())]);
+
+    // Marking the enemy edge intentional silences the contradiction
+    set_intentional(db, &enemy.id.to_string(), true)
+        .await
+        .unwrap();
+    assert!(check_reciprocity(db, None).await.unwrap().is_empty());
+}
+
+/// The world audit runs the checks for every entity and groups the results.
+#[tokio::test]
+async fn test_world_audit_groups_violations() {
+    use narra::models::character::create_character_with_id;
+    use narra::services::audit::{audit_world, AuditCheck};
+
+    let harness = TestHarness::new().await;
+    let db = &harness.db;
+
+    for (key, name) in [("alice", "Alice"), ("bob", "Bob"), ("carol", "Carol")] {
+        create_character_with_id(
+            db,
+            key,
+            CharacterCreate {
+                name: name.to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create character");
+    }
+    let parent = || PerceptionCreate {
+        rel_types: vec!["family".to_string(), "parent".to_string()],
+        feelings: Some("protective".to_string()),
+        subtype: None,
+        perception: None,
+        tension_level: None,
+        history_notes: None,
+    };
+    create_perception(db, "alice", "bob", parent())
+        .await
+        .unwrap();
+    create_perception(db, "bob", "alice", parent())
+        .await
+        .unwrap();
+
+    let checker = ConsistencyChecker::new(db.clone());
+    let report = audit_world(db, &checker, &AuditCheck::ALL)
+        .await
+        .expect("Audit should not error");
+
+    assert_eq!(report.checked, 3);
+    assert_eq!(report.worst(), Some(ConsistencySeverity::Critical));
+    let circular = report
+        .groups
+        .iter()
+        .find(|g| g.fact_title == "Impossible relationship state")
+        .expect("Circular parents should be reported");
+    assert_eq!(circular.severity, ConsistencySeverity::Critical);
+    assert!(circular
+        .findings
+        .iter()
+        .all(|f| f.check == AuditCheck::Relationships));
+    assert_eq!(report.critical, report.groups[0].findings.len());
+
+    // Only the checks asked for run
+    let report = audit_world(db, &checker, &[AuditCheck::Timeline])
+        .await
+        .unwrap();
+    assert_eq!(report.total_violations, 0);
+    assert_eq!(report.worst(), None);
+}
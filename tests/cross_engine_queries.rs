@@ -478,6 +478,7 @@ async fn test_semantic_graph_search_ranks_connected_entities() {
         rel_type: "professional".to_string(),
         subtype: Some("alliance".to_string()),
         label: Some("spy network".to_string()),
+        with_perceptions: false,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(create_rel_ab)))
@@ -490,6 +491,7 @@ async fn test_semantic_graph_search_ranks_connected_entities() {
         rel_type: "professional".to_string(),
         subtype: Some("comrades".to_string()),
         label: Some("battle companions".to_string()),
+        with_perceptions: false,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(create_rel_ac)))
@@ -583,6 +585,7 @@ async fn test_semantic_graph_search_type_filter() {
         rel_type: "friendship".to_string(),
         subtype: None,
         label: None,
+        with_perceptions: false,
     };
     server
         .handle_mutate(Parameters(to_mutation_input(create_rel)))
@@ -689,6 +692,7 @@ async fn test_record_knowledge_generates_embedding() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -0,0 +1,45 @@
+//! Integration tests for the database checks behind `narra doctor`.
+
+mod common;
+
+use common::builders::CharacterBuilder;
+use common::harness::TestHarness;
+use narra::models::character::create_character;
+use narra::services::doctor::{
+    check_embedding_coverage, check_indexes, check_integrity, CheckStatus,
+};
+use narra::services::vector_index::{VectorIndexKind, VectorIndexSettings};
+
+#[tokio::test]
+async fn test_doctor_checks_on_fresh_world() {
+    let harness = TestHarness::new().await;
+    create_character(&harness.db, CharacterBuilder::new("Alice").build())
+        .await
+        .expect("Should create Alice");
+
+    let integrity = check_integrity(&harness.db).await.unwrap();
+    assert_eq!(integrity.status, CheckStatus::Ok, "{}", integrity.detail);
+
+    // Nothing is embedded yet: a warning that points at backfill
+    let coverage = check_embedding_coverage(&harness.db).await.unwrap();
+    assert_eq!(coverage.status, CheckStatus::Warn);
+    assert_eq!(coverage.fix.as_deref(), Some("narra world backfill"));
+
+    let indexes = check_indexes(&harness.db, &VectorIndexSettings::default(), 384)
+        .await
+        .unwrap();
+    assert_eq!(indexes[0].status, CheckStatus::Ok, "{}", indexes[0].detail);
+    assert_eq!(indexes[1].status, CheckStatus::Ok);
+
+    // An index configured but never built needs a reindex
+    let hnsw = VectorIndexSettings {
+        kind: VectorIndexKind::Hnsw,
+        ..Default::default()
+    };
+    let indexes = check_indexes(&harness.db, &hnsw, 384).await.unwrap();
+    assert_eq!(indexes[1].status, CheckStatus::Warn);
+    assert_eq!(
+        indexes[1].fix.as_deref(),
+        Some("narra world reindex-vectors")
+    );
+}
@@ -80,6 +80,7 @@ async fn test_backfill_generates_embeddings_for_all_types() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -498,6 +499,7 @@ async fn test_backfill_single_type() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
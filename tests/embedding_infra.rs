@@ -283,6 +283,7 @@ async fn test_create_relationship_marks_character_embeddings_stale() {
                 rel_type: "ally".to_string(),
                 subtype: None,
                 label: None,
+                with_perceptions: false,
             },
         )))
         .await;
@@ -368,6 +369,7 @@ async fn test_character_name_change_marks_relates_to_stale() {
         .handle_mutate(Parameters(to_mutation_input(MutationRequest::Update {
             entity_id: "character:alice".to_string(),
             fields: serde_json::json!({"name": "Alice the Great"}),
+            force: None,
         })))
         .await;
 
@@ -350,6 +350,7 @@ async fn test_event_crud_workflow() {
         date: None,
         date_precision: None,
         duration_end: None,
+        narrative_status: None,
         updated_at: Datetime::default(),
     };
 
@@ -638,3 +639,97 @@ async fn test_scene_list_multiple_at_event() {
     repo.delete_location(&location1_id).await.ok();
     repo.delete_location(&location2_id).await.ok();
 }
+
+/// Test bulk draft-status transitions and the status filters built on them.
+///
+/// Verifies:
+/// - New scenes start as outline
+/// - Several scenes move at once, and only changed scenes are returned
+/// - Re-running a transition changes nothing
+/// - Unknown statuses are rejected by both the transition and the filter
+/// - `scene_ids_with_status` (used by `find --status`) and the listed
+///   `draft_status` (used by `list scenes --status`) agree
+#[tokio::test]
+async fn test_scene_draft_status_transitions() {
+    use narra::models::scene::{scene_ids_with_status, set_draft_status};
+    use narra::NarraError;
+
+    let harness = TestHarness::new().await;
+    let repo = SurrealEntityRepository::new(harness.db.clone());
+
+    let event = repo
+        .create_event(EventBuilder::new("Act One").sequence(1).build())
+        .await
+        .expect("Failed to create event");
+    let event_id = event.id.key().to_string();
+    let location = repo
+        .create_location(LocationBuilder::new("The Docks").build())
+        .await
+        .expect("Failed to create location");
+    let location_id = location.id.key().to_string();
+
+    let mut scene_ids = Vec::new();
+    for title in ["Arrival", "Ambush", "Escape"] {
+        let scene = repo
+            .create_scene(SceneBuilder::new(title, &event_id, &location_id).build())
+            .await
+            .expect("Failed to create scene");
+        assert_eq!(scene.draft_status, "outline");
+        scene_ids.push(scene.id.to_string());
+    }
+
+    // Bulk transition: two scenes move, the third stays outline
+    let moved = set_draft_status(&harness.db, &scene_ids[..2], "drafted")
+        .await
+        .expect("Failed to set draft status");
+    assert_eq!(moved.len(), 2);
+    assert!(moved.iter().all(|s| s.draft_status == "drafted"));
+
+    // Already at the target status: nothing changes
+    let again = set_draft_status(&harness.db, &scene_ids[..2], "drafted")
+        .await
+        .expect("Failed to set draft status");
+    assert!(again.is_empty());
+
+    // Rejected transition and filter
+    let err = set_draft_status(&harness.db, &scene_ids, "published")
+        .await
+        .expect_err("Unknown status should be rejected");
+    assert!(matches!(err, NarraError::Validation(_)));
+    let err = scene_ids_with_status(&harness.db, "published")
+        .await
+        .expect_err("Unknown status should be rejected");
+    assert!(matches!(err, NarraError::Validation(_)));
+
+    // Filtering by status
+    let drafted = scene_ids_with_status(&harness.db, "drafted")
+        .await
+        .expect("Failed to filter by status");
+    assert_eq!(
+        drafted,
+        scene_ids[..2]
+            .iter()
+            .cloned()
+            .collect::<std::collections::HashSet<_>>()
+    );
+    let outline = scene_ids_with_status(&harness.db, "outline")
+        .await
+        .expect("Failed to filter by status");
+    assert_eq!(outline.len(), 1);
+    assert!(outline.contains(&scene_ids[2]));
+    assert!(scene_ids_with_status(&harness.db, "final")
+        .await
+        .expect("Failed to filter by status")
+        .is_empty());
+
+    let listed: Vec<String> = repo
+        .list_scenes()
+        .await
+        .expect("Failed to list scenes")
+        .into_iter()
+        .filter(|s| s.draft_status == "drafted")
+        .map(|s| s.id.to_string())
+        .collect();
+    assert_eq!(listed.len(), 2);
+    assert!(listed.iter().all(|id| drafted.contains(id)));
+}
@@ -84,6 +84,7 @@ async fn test_export_with_data() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         })
         .await
         .unwrap();
@@ -160,6 +161,7 @@ async fn test_export_round_trip() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         })
         .await
         .unwrap();
@@ -246,6 +248,7 @@ async fn test_export_all_entity_types() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         })
         .await
         .unwrap();
@@ -318,6 +321,7 @@ async fn test_export_all_entity_types() {
         narra::models::note::NoteCreate {
             title: "Plot Notes".to_string(),
             body: "Remember to foreshadow the betrayal".to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -402,6 +406,7 @@ async fn test_export_scene_with_participants() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         })
         .await
         .unwrap();
@@ -513,6 +518,7 @@ async fn test_export_notes_with_attachments() {
         narra::models::note::NoteCreate {
             title: "Plot Idea".to_string(),
             body: "What if Alice can fly?".to_string(),
+            ..Default::default()
         },
     )
     .await
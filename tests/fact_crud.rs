@@ -121,7 +121,10 @@ async fn test_create_fact_with_scope() {
             categories: vec![FactCategory::SocialCultural],
             enforcement_level: EnforcementLevel::Informational,
             scope: Some(FactScope {
+                location: None,
                 temporal: Some(TemporalScope {
+                    from_sequence: None,
+                    until_sequence: None,
                     valid_from_event: Some("event:council_meeting".into()),
                     valid_until_event: None,
                     freeform_description: Some("After the Council reveals the prophecy".into()),
@@ -174,6 +177,7 @@ async fn test_create_fact_with_character_pov_scope() {
             categories: vec![],
             enforcement_level: EnforcementLevel::Warning,
             scope: Some(FactScope {
+                location: None,
                 temporal: None,
                 pov: Some(PovScope::Character("character:alice".into())),
             }),
@@ -211,6 +215,7 @@ async fn test_create_fact_with_except_characters_scope() {
             categories: vec![],
             enforcement_level: EnforcementLevel::Warning,
             scope: Some(FactScope {
+                location: None,
                 temporal: None,
                 pov: Some(PovScope::ExceptCharacters(vec![
                     "character:villain1".into(),
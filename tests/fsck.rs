@@ -0,0 +1,68 @@
+//! Integration tests for `narra world fsck`.
+
+mod common;
+
+use common::builders::CharacterBuilder;
+use common::harness::TestHarness;
+use narra::models::annotation::{upsert_annotation, AnnotationCreate};
+use narra::models::character::create_character;
+use narra::services::fsck::fsck;
+
+fn annotation(entity_id: &str) -> AnnotationCreate {
+    AnnotationCreate {
+        entity_id: entity_id.to_string(),
+        model_type: "emotion".to_string(),
+        model_version: "test".to_string(),
+        output: serde_json::json!({"dominant": "joy"}),
+    }
+}
+
+#[tokio::test]
+async fn test_fsck_reports_and_repairs_derived_data() {
+    let harness = TestHarness::new().await;
+    let alice = create_character(&harness.db, CharacterBuilder::new("Alice").build())
+        .await
+        .expect("Should create Alice");
+
+    let clean = fsck(&harness.db, 384, false).await.unwrap();
+    assert!(clean.findings.is_empty(), "{:?}", clean.findings);
+
+    // Annotations of a live entity, a deleted one, and a malformed ID
+    let alice_id = alice.id.to_string();
+    for entity_id in [alice_id.as_str(), "character:ghost", "ghost"] {
+        upsert_annotation(&harness.db, annotation(entity_id))
+            .await
+            .unwrap();
+    }
+    // An embedding from a 3-dimensional model
+    harness
+        .db
+        .query("UPDATE $id SET embedding = [0.1, 0.2, 0.3], embedding_stale = false")
+        .bind(("id", alice.id.clone()))
+        .await
+        .unwrap();
+
+    let report = fsck(&harness.db, 384, false).await.unwrap();
+    assert_eq!(report.findings.len(), 3, "{:?}", report.findings);
+    assert_eq!(report.fixable(), 3);
+    assert!(report.findings.iter().all(|f| !f.repaired));
+
+    let fixed = fsck(&harness.db, 384, true).await.unwrap();
+    assert_eq!(fixed.unresolved(), 0);
+    assert!(fixed.reclaimed_bytes > 0);
+
+    let again = fsck(&harness.db, 384, false).await.unwrap();
+    assert!(again.findings.is_empty(), "{:?}", again.findings);
+
+    // The live entity's annotation survives; the bad vector awaits backfill
+    let mut response = harness
+        .db
+        .query("SELECT VALUE entity_id FROM annotation; SELECT VALUE embedding_stale FROM $id")
+        .bind(("id", alice.id.clone()))
+        .await
+        .unwrap();
+    let remaining: Vec<String> = response.take(0).unwrap();
+    assert_eq!(remaining, vec![alice_id]);
+    let stale: Vec<bool> = response.take(1).unwrap();
+    assert_eq!(stale, vec![true]);
+}
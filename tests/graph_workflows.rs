@@ -527,3 +527,220 @@ async fn test_mermaid_with_roles() {
         "Diagram with roles should include role labels"
     );
 }
+
+// ============================================================================
+// FAMILY TEMPLATE TESTS
+// ============================================================================
+
+/// A family template plans reciprocal kinship edges and in-laws from
+/// spouses already in the world, skipping edges that exist.
+#[tokio::test]
+async fn test_family_template_plan() {
+    use narra::models::character::create_character_with_id;
+    use narra::models::relationship::create_relationship;
+    use narra::models::RelationshipCreate;
+    use narra::services::family::plan_family;
+
+    let harness = TestHarness::new().await;
+    for key in ["alice", "bob", "carol", "dave", "erin"] {
+        create_character_with_id(&harness.db, key, CharacterBuilder::new(key).build())
+            .await
+            .expect("character");
+    }
+    // Carol is already married to Erin
+    for (from, to) in [("carol", "erin"), ("erin", "carol")] {
+        create_relationship(
+            &harness.db,
+            RelationshipCreate {
+                from_character_id: from.to_string(),
+                to_character_id: to.to_string(),
+                rel_type: "family".to_string(),
+                subtype: Some("spouse".to_string()),
+                label: None,
+            },
+        )
+        .await
+        .expect("spouse");
+    }
+
+    let keys = |ks: &[&str]| ks.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+    let edges = plan_family(
+        &harness.db,
+        &keys(&["alice", "bob"]),
+        &keys(&["carol", "dave"]),
+        true,
+    )
+    .await
+    .expect("plan");
+    let has = |from: &str, subtype: &str, to: &str| {
+        edges
+            .iter()
+            .any(|e| e.from == from && e.subtype == subtype && e.to == to)
+    };
+
+    assert!(has("alice", "spouse", "bob") && has("bob", "spouse", "alice"));
+    assert!(has("alice", "parent", "carol") && has("carol", "child", "alice"));
+    assert!(has("dave", "sibling", "carol") && has("carol", "sibling", "dave"));
+    assert!(has("bob", "parent-in-law", "erin") && has("erin", "child-in-law", "bob"));
+    assert!(has("dave", "sibling-in-law", "erin") && has("erin", "sibling-in-law", "dave"));
+    assert!(
+        !has("carol", "spouse", "erin"),
+        "Existing edges are skipped"
+    );
+    // 2 spouse + 8 parent/child + 2 sibling + 4 parent-in-law + 2 sibling-in-law
+    assert_eq!(edges.len(), 18);
+
+    assert!(
+        plan_family(&harness.db, &keys(&["alice"]), &keys(&["alice"]), true)
+            .await
+            .is_err()
+    );
+}
+
+/// Normalizing rewrites synonyms and stray case to canonical types and
+/// reports types the ontology doesn't know.
+#[tokio::test]
+async fn test_normalize_relationship_types() {
+    use narra::models::character::create_character_with_id;
+    use narra::models::relationship::create_relationship;
+    use narra::models::RelationshipCreate;
+    use narra::services::ontology::{normalize_relationship_types, RelationshipOntology};
+
+    let harness = TestHarness::new().await;
+    for key in ["alice", "bob", "carol"] {
+        create_character_with_id(&harness.db, key, CharacterBuilder::new(key).build())
+            .await
+            .expect("character");
+    }
+    for (from, to, rel_type) in [
+        ("alice", "bob", "friends"),
+        ("bob", "alice", "Buddy"),
+        ("alice", "carol", "Ally"),
+        ("carol", "bob", "friend"),
+        ("bob", "carol", "sworn-brother"),
+    ] {
+        create_relationship(
+            &harness.db,
+            RelationshipCreate {
+                from_character_id: from.to_string(),
+                to_character_id: to.to_string(),
+                rel_type: rel_type.to_string(),
+                subtype: None,
+                label: None,
+            },
+        )
+        .await
+        .expect("relationship");
+    }
+
+    let ontology = RelationshipOntology::builtin();
+    let preview = normalize_relationship_types(&harness.db, &ontology, true)
+        .await
+        .expect("dry run");
+    assert_eq!(preview.rewrites.len(), 3);
+    assert_eq!(preview.unknown.len(), 1);
+    assert_eq!(preview.unknown[0].rel_type, "sworn-brother");
+
+    normalize_relationship_types(&harness.db, &ontology, false)
+        .await
+        .expect("normalize");
+    let repo = SurrealRelationshipRepository::new(harness.db.clone());
+    let mut types: Vec<String> = repo
+        .get_character_relationships("bob")
+        .await
+        .expect("relationships")
+        .into_iter()
+        .map(|r| r.rel_type)
+        .collect();
+    types.sort();
+    assert_eq!(types, ["friend", "friend", "friend", "sworn-brother"]);
+
+    let again = normalize_relationship_types(&harness.db, &ontology, false)
+        .await
+        .expect("normalize again");
+    assert!(again.rewrites.is_empty());
+}
+
+/// Test trust: inferred from perceptions, overridden explicitly, and
+/// weighing influence paths.
+#[tokio::test]
+async fn test_trust_weighs_influence() {
+    use narra::models::character::create_character_with_id;
+    use narra::models::perception::set_trust;
+    use narra::services::ontology::RelationshipOntology;
+    use narra::services::transmission::Trust;
+    use narra::services::trust::estimate_trust;
+    use narra::services::InfluenceService;
+    use std::sync::Arc;
+
+    let harness = TestHarness::new().await;
+    for key in ["alice", "bob", "carol"] {
+        create_character_with_id(&harness.db, key, CharacterBuilder::new(key).build())
+            .await
+            .expect("character");
+    }
+    for (to, rel_type) in [("bob", "friendship"), ("carol", "professional")] {
+        create_perception(
+            &harness.db,
+            "alice",
+            to,
+            PerceptionCreate {
+                rel_types: vec![rel_type.to_string()],
+                subtype: None,
+                feelings: None,
+                perception: None,
+                tension_level: None,
+                history_notes: None,
+            },
+        )
+        .await
+        .expect("perception");
+    }
+
+    let ontology = RelationshipOntology::builtin();
+    let bob = estimate_trust(&harness.db, &ontology, "alice", "bob")
+        .await
+        .expect("estimate");
+    assert!(!bob.explicit);
+    assert_eq!(bob.level(), Trust::Trusted);
+    assert_eq!(bob.reasons, vec!["friendship"]);
+
+    // Alice has stopped trusting her friend
+    let updated = set_trust(&harness.db, "alice", "bob", Some(0.1))
+        .await
+        .expect("set trust")
+        .expect("perception exists");
+    assert_eq!(updated.trust, Some(0.1));
+    assert!(set_trust(&harness.db, "bob", "alice", Some(0.5))
+        .await
+        .expect("set trust")
+        .is_none());
+    assert!(set_trust(&harness.db, "alice", "bob", Some(1.5))
+        .await
+        .is_err());
+
+    let service = InfluenceService::with_ontology(harness.db.clone(), Arc::new(ontology.clone()));
+    let result = service
+        .trace_propagation("character:alice", 1)
+        .await
+        .expect("propagation");
+    let strength = |name: &str| {
+        result
+            .reachable_characters
+            .iter()
+            .find(|p| p.steps.last().map(|s| s.character_name.as_str()) == Some(name))
+            .map(|p| p.numeric_strength)
+            .expect("reachable")
+    };
+    assert!((strength("bob") - 0.2).abs() < 0.001);
+    assert!(strength("carol") > strength("bob"));
+
+    set_trust(&harness.db, "alice", "bob", None)
+        .await
+        .expect("clear trust");
+    let cleared = estimate_trust(&harness.db, &ontology, "alice", "bob")
+        .await
+        .expect("estimate");
+    assert!(!cleared.explicit);
+    assert_eq!(cleared.value, cleared.inferred);
+}
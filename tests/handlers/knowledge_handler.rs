@@ -146,6 +146,131 @@ async fn test_record_knowledge_with_source_character() {
     );
 }
 
+/// Test certainty "derive" passes knowledge on through trust.
+///
+/// Verifies:
+/// - A friend inherits the teller's wrong belief
+/// - A stranger keeps a wrong belief, an enemy only ends up uncertain
+/// - Conflicts show the chain the belief travelled
+#[tokio::test]
+async fn test_record_knowledge_derived_certainty() {
+    let harness = TestHarness::new().await;
+    let server = crate::common::create_test_server(&harness).await;
+
+    let repo = SurrealEntityRepository::new(harness.db.clone());
+    let mut characters = Vec::new();
+    for name in ["Alice", "Bob", "Carol", "Dave"] {
+        let character = repo
+            .create_character(CharacterBuilder::new(name).build())
+            .await
+            .expect("Failed to create character");
+        characters.push(character);
+    }
+    let [alice, bob, carol, dave] = characters.try_into().ok().unwrap();
+    let event = repo
+        .create_event(EventBuilder::new("The Rumour").sequence(1).build())
+        .await
+        .expect("Failed to create event");
+
+    for (from, to, rel_type) in [(&bob, &carol, "friend"), (&alice, &bob, "enemy")] {
+        narra::models::relationship::create_relationship(
+            &harness.db,
+            narra::models::relationship::RelationshipCreate {
+                from_character_id: from.id.key().to_string(),
+                to_character_id: to.id.key().to_string(),
+                rel_type: rel_type.to_string(),
+                subtype: None,
+                label: None,
+            },
+        )
+        .await
+        .expect("Failed to create relationship");
+    }
+
+    let fact = "The king is dead";
+    let record = |who: &narra::models::Character, certainty: &str, source: Option<String>| {
+        MutationRequest::RecordKnowledge {
+            character_id: who.id.to_string(),
+            target_id: carol.id.to_string(),
+            fact: fact.to_string(),
+            certainty: certainty.to_string(),
+            method: None,
+            source_character_id: source,
+            event_id: Some(event.id.key().to_string()),
+        }
+    };
+
+    // Deriving needs a source who knows something about the fact
+    let response = server
+        .handle_mutate(Parameters(to_mutation_input(record(&bob, "derive", None))))
+        .await;
+    assert!(response.is_err(), "derive without a source should fail");
+    let response = server
+        .handle_mutate(Parameters(to_mutation_input(record(
+            &bob,
+            "derive",
+            Some(carol.id.key().to_string()),
+        ))))
+        .await;
+    assert!(
+        response.is_err(),
+        "derive from an unaware source should fail"
+    );
+
+    server
+        .handle_mutate(Parameters(to_mutation_input(record(
+            &carol,
+            "believes_wrongly",
+            None,
+        ))))
+        .await
+        .expect("Carol's belief should be recorded");
+    for (who, source) in [(&bob, &carol), (&dave, &bob), (&alice, &bob)] {
+        let response = server
+            .handle_mutate(Parameters(to_mutation_input(record(
+                who,
+                "derive",
+                Some(source.id.key().to_string()),
+            ))))
+            .await;
+        assert!(
+            response.is_ok(),
+            "Derive should succeed: {:?}",
+            response.err()
+        );
+        assert!(response
+            .unwrap()
+            .hints
+            .iter()
+            .any(|h| h.starts_with("Certainty derived")));
+    }
+
+    let knowledge_repo = SurrealKnowledgeRepository::new(harness.db.clone());
+    for (who, expected) in [
+        (&bob, CertaintyLevel::BelievesWrongly),
+        (&dave, CertaintyLevel::BelievesWrongly),
+        (&alice, CertaintyLevel::Uncertain),
+    ] {
+        let states = knowledge_repo
+            .get_character_knowledge_states(who.id.key().to_string().as_str())
+            .await
+            .expect("Failed to get knowledge states");
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].certainty, expected, "{}", who.name);
+        assert!(states[0].derived_from.is_some());
+    }
+
+    let conflicts = narra::models::knowledge::find_knowledge_conflicts(&harness.db)
+        .await
+        .expect("Failed to find conflicts");
+    let dave_state = conflicts
+        .iter()
+        .flat_map(|c| &c.conflicting_states)
+        .find(|s| s.character_id.ends_with(&dave.id.key().to_string()))
+        .expect("Dave's wrong belief should be a conflict");
+    assert_eq!(dave_state.derivation.len(), 2);
+}
+
 /// Test knowledge recording with event tracks temporal link.
 ///
 /// Verifies:
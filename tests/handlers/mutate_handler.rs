@@ -14,7 +14,7 @@ use pretty_assertions::assert_eq;
 use rmcp::handler::server::wrapper::Parameters;
 
 use crate::common::{
-    builders::{CharacterBuilder, EventBuilder, LocationBuilder},
+    builders::{CharacterBuilder, EventBuilder, KnowledgeBuilder, LocationBuilder, SceneBuilder},
     harness::TestHarness,
     to_mutation_input, to_query_input,
 };
@@ -41,6 +41,7 @@ async fn test_create_character_success() {
         aliases: None,
         description: None,
         profile: None,
+        allow_similar: None,
     };
     let response = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -80,6 +81,7 @@ async fn test_create_character_with_aliases() {
         aliases: Some(vec!["Dark One".to_string(), "The Nameless".to_string()]),
         description: None,
         profile: None,
+        allow_similar: None,
     };
     let response = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -122,6 +124,7 @@ async fn test_delete_character_hard() {
         aliases: None,
         description: None,
         profile: None,
+        allow_similar: None,
     };
     let create_response = server
         .handle_mutate(Parameters(to_mutation_input(create_request)))
@@ -133,6 +136,7 @@ async fn test_delete_character_hard() {
     let delete_request = MutationRequest::Delete {
         entity_id: char_id.clone(),
         hard: Some(true),
+        force: None,
     };
     let delete_response = server
         .handle_mutate(Parameters(to_mutation_input(delete_request)))
@@ -261,6 +265,7 @@ async fn test_delete_location_hard() {
     let delete_request = MutationRequest::Delete {
         entity_id: loc_id.clone(),
         hard: Some(true),
+        force: None,
     };
     let delete_response = server
         .handle_mutate(Parameters(to_mutation_input(delete_request)))
@@ -296,6 +301,7 @@ async fn test_create_event_success() {
         sequence: Some(100),
         date: None,
         date_precision: None,
+        narrative_status: None,
     };
     let response = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -322,6 +328,7 @@ async fn test_create_event_with_date() {
         sequence: Some(1),
         date: Some("2023-06-15T14:30:00Z".to_string()),
         date_precision: Some("day".to_string()),
+        narrative_status: None,
     };
     let response = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -356,6 +363,7 @@ async fn test_delete_event_hard() {
         sequence: None,
         date: None,
         date_precision: None,
+        narrative_status: None,
     };
     let create_response = server
         .handle_mutate(Parameters(to_mutation_input(create_request)))
@@ -367,6 +375,7 @@ async fn test_delete_event_hard() {
     let delete_request = MutationRequest::Delete {
         entity_id: event_id.clone(),
         hard: Some(true),
+        force: None,
     };
     let delete_response = server
         .handle_mutate(Parameters(to_mutation_input(delete_request)))
@@ -461,6 +470,139 @@ async fn test_create_scene_malformed_event_id() {
     assert_snapshot!("create_scene_malformed_event_id", error_message);
 }
 
+/// Test that entities referenced by a final scene need force to change.
+///
+/// Verifies:
+/// - Updating the scene's event without force is refused
+/// - The same update with force succeeds
+#[tokio::test]
+async fn test_update_locked_by_final_scene() {
+    let harness = TestHarness::new().await;
+    let server = crate::common::create_test_server(&harness).await;
+
+    let repo = SurrealEntityRepository::new(harness.db.clone());
+    let event = repo
+        .create_event(EventBuilder::new("Coronation").build())
+        .await
+        .expect("Create event");
+    let location = repo
+        .create_location(LocationBuilder::new("Throne Room").build())
+        .await
+        .expect("Create location");
+    let scene = repo
+        .create_scene(
+            SceneBuilder::new(
+                "The Crown",
+                event.id.key().to_string(),
+                location.id.key().to_string(),
+            )
+            .build(),
+        )
+        .await
+        .expect("Create scene");
+    narra::models::scene::set_draft_status(&harness.db, &[scene.id.to_string()], "final")
+        .await
+        .expect("Mark final");
+
+    let update = |force| MutationRequest::Update {
+        entity_id: event.id.to_string(),
+        fields: serde_json::json!({"title": "Abdication"}),
+        force,
+    };
+    let refused = server
+        .handle_mutate(Parameters(to_mutation_input(update(None))))
+        .await;
+    let error_message = refused.expect_err("Update should be refused without force");
+    assert!(error_message.contains("REVISION LOCK"));
+    assert!(error_message.contains(&scene.id.to_string()));
+
+    let forced = server
+        .handle_mutate(Parameters(to_mutation_input(update(Some(true)))))
+        .await
+        .expect("Forced update should succeed");
+    assert_eq!(
+        forced.impact.map(|i| i.severity),
+        Some("Critical".to_string())
+    );
+}
+
+/// Test that participant and tag changes to entities a final scene
+/// references need force too.
+#[tokio::test]
+async fn test_participants_and_tags_locked_by_final_scene() {
+    let harness = TestHarness::new().await;
+    let server = crate::common::create_test_server(&harness).await;
+
+    let repo = SurrealEntityRepository::new(harness.db.clone());
+    let character = repo
+        .create_character(CharacterBuilder::new("Regent").build())
+        .await
+        .expect("Create character");
+    let event = repo
+        .create_event(EventBuilder::new("Coronation").build())
+        .await
+        .expect("Create event");
+    let location = repo
+        .create_location(LocationBuilder::new("Throne Room").build())
+        .await
+        .expect("Create location");
+    let scene = repo
+        .create_scene(
+            SceneBuilder::new(
+                "The Crown",
+                event.id.key().to_string(),
+                location.id.key().to_string(),
+            )
+            .build(),
+        )
+        .await
+        .expect("Create scene");
+    narra::models::scene::set_draft_status(&harness.db, &[scene.id.to_string()], "final")
+        .await
+        .expect("Mark final");
+
+    let add = |force| MutationRequest::AddEventParticipant {
+        event_id: event.id.to_string(),
+        character_id: character.id.to_string(),
+        role: None,
+        impact: None,
+        force,
+    };
+    let remove = |force| MutationRequest::RemoveEventParticipant {
+        event_id: event.id.to_string(),
+        character_id: character.id.to_string(),
+        force,
+    };
+    let tag = |force| MutationRequest::TagEntity {
+        entity_id: scene.id.to_string(),
+        tags: vec!["climax".to_string()],
+        force,
+    };
+    let untag = |force| MutationRequest::UntagEntity {
+        entity_id: scene.id.to_string(),
+        tags: vec!["climax".to_string()],
+        force,
+    };
+
+    for (refused, forced) in [
+        (add(None), add(Some(true))),
+        (remove(None), remove(Some(true))),
+        (tag(None), tag(Some(true))),
+        (untag(None), untag(Some(true))),
+    ] {
+        let error_message = server
+            .handle_mutate(Parameters(to_mutation_input(refused)))
+            .await
+            .expect_err("Change should be refused without force");
+        assert!(error_message.contains("REVISION LOCK"));
+        assert!(error_message.contains(&scene.id.to_string()));
+        server
+            .handle_mutate(Parameters(to_mutation_input(forced)))
+            .await
+            .expect("Forced change should succeed");
+    }
+}
+
 // =============================================================================
 // DELETE EDGE CASES
 // =============================================================================
@@ -476,6 +618,7 @@ async fn test_delete_nonexistent_entity() {
     let request = MutationRequest::Delete {
         entity_id: "character:nonexistent123".to_string(),
         hard: Some(true),
+        force: None,
     };
     let response = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -505,6 +648,7 @@ async fn test_delete_soft_not_implemented() {
         aliases: None,
         description: None,
         profile: None,
+        allow_similar: None,
     };
     let create_response = server
         .handle_mutate(Parameters(to_mutation_input(create_request)))
@@ -516,6 +660,7 @@ async fn test_delete_soft_not_implemented() {
     let delete_request = MutationRequest::Delete {
         entity_id: char_id,
         hard: Some(false),
+        force: None,
     };
     let response = server
         .handle_mutate(Parameters(to_mutation_input(delete_request)))
@@ -551,6 +696,7 @@ async fn test_created_entity_queryable() {
         aliases: None,
         description: None,
         profile: None,
+        allow_similar: None,
     };
     let create_response = server
         .handle_mutate(Parameters(to_mutation_input(create_request)))
@@ -695,6 +841,7 @@ async fn test_create_character_with_id() {
         aliases: None,
         description: None,
         profile: None,
+        allow_similar: None,
     };
     let response = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -705,6 +852,44 @@ async fn test_create_character_with_id() {
     assert_eq!(response.entity.name, "The Hero");
 }
 
+/// Test that a likely duplicate character is refused unless allowed.
+#[tokio::test]
+async fn test_create_similar_character_needs_allow() {
+    let harness = TestHarness::new().await;
+    let server = crate::common::create_test_server(&harness).await;
+
+    let create = |name: &str, allow_similar| MutationRequest::CreateCharacter {
+        id: None,
+        name: name.to_string(),
+        role: None,
+        aliases: None,
+        description: None,
+        profile: None,
+        allow_similar,
+    };
+    server
+        .handle_mutate(Parameters(to_mutation_input(create("Bran", None))))
+        .await
+        .expect("First character should be created");
+
+    let refused = server
+        .handle_mutate(Parameters(to_mutation_input(create("Bram", None))))
+        .await;
+    let error_message = refused.expect_err("Similar character should be refused");
+    assert!(error_message.contains("Bram is 75% similar to Bran"));
+    assert!(error_message.contains("allow_similar"));
+
+    server
+        .handle_mutate(Parameters(to_mutation_input(create("Osric", None))))
+        .await
+        .expect("Distinct name should be created");
+    let allowed = server
+        .handle_mutate(Parameters(to_mutation_input(create("Bram", Some(true)))))
+        .await
+        .expect("Allowed similar character should be created");
+    assert_eq!(allowed.entity.name, "Bram");
+}
+
 /// Test batch location creation with caller-specified IDs.
 #[tokio::test]
 async fn test_batch_create_locations() {
@@ -756,6 +941,7 @@ async fn test_batch_create_events() {
                 sequence: Some(100),
                 date: None,
                 date_precision: None,
+                narrative_status: None,
             },
             EventSpec {
                 id: Some("coronation".to_string()),
@@ -764,6 +950,7 @@ async fn test_batch_create_events() {
                 sequence: Some(200),
                 date: None,
                 date_precision: None,
+                narrative_status: None,
             },
         ],
     };
@@ -805,6 +992,7 @@ async fn test_batch_create_relationships() {
                 rel_type: "friendship".to_string(),
                 subtype: None,
                 label: Some("Best friends".to_string()),
+                intentional: None,
             },
             RelationshipSpec {
                 from_character_id: "bob".to_string(),
@@ -812,6 +1000,7 @@ async fn test_batch_create_relationships() {
                 rel_type: "rivalry".to_string(),
                 subtype: None,
                 label: None,
+                intentional: None,
             },
         ],
     };
@@ -828,6 +1017,188 @@ async fn test_batch_create_relationships() {
     }
 }
 
+/// Test relationship creation seeding baseline perceptions.
+///
+/// Verifies:
+/// - The missing direction gets the type's baseline perception
+/// - An existing perception in the other direction is left alone
+#[tokio::test]
+async fn test_create_relationship_with_perceptions() {
+    use narra::models::character::create_character_with_id;
+    use narra::models::perception::{create_perception, get_perception};
+    use narra::models::PerceptionCreate;
+
+    let harness = TestHarness::new().await;
+    let server = crate::common::create_test_server(&harness).await;
+
+    for (id, name) in [("alice", "Alice"), ("bob", "Bob")] {
+        create_character_with_id(&harness.db, id, CharacterBuilder::new(name).build())
+            .await
+            .expect("Create character");
+    }
+    create_perception(
+        &harness.db,
+        "bob",
+        "alice",
+        PerceptionCreate {
+            rel_types: vec!["mentor".to_string()],
+            subtype: None,
+            feelings: Some("Grateful".to_string()),
+            perception: Some("Alice saved my career".to_string()),
+            tension_level: None,
+            history_notes: None,
+        },
+    )
+    .await
+    .expect("Create Bob's perception");
+
+    let response = server
+        .handle_mutate(Parameters(to_mutation_input(
+            MutationRequest::CreateRelationship {
+                from_character_id: "alice".to_string(),
+                to_character_id: "bob".to_string(),
+                rel_type: "mentor".to_string(),
+                subtype: None,
+                label: None,
+                with_perceptions: true,
+            },
+        )))
+        .await
+        .expect("Create relationship should succeed");
+    assert!(response
+        .hints
+        .iter()
+        .any(|h| h.contains("Seeded 1 baseline perception(s), kept 1 existing")));
+
+    let seeded = get_perception(&harness.db, "alice", "bob")
+        .await
+        .unwrap()
+        .expect("Alice's perception is seeded");
+    assert_eq!(
+        seeded.perception.as_deref(),
+        Some("Alice sees Bob as a student in their care.")
+    );
+    assert_eq!(seeded.rel_types, vec!["mentor".to_string()]);
+
+    let kept = get_perception(&harness.db, "bob", "alice")
+        .await
+        .unwrap()
+        .expect("Bob's perception still exists");
+    assert_eq!(kept.perception.as_deref(), Some("Alice saved my career"));
+}
+
+/// Test adding, re-adding and removing event participants, and that
+/// witnessing an event without taking part in it is flagged.
+#[tokio::test]
+async fn test_event_participants() {
+    use narra::models::character::create_character_with_id;
+    use narra::models::event::create_event_with_id;
+    use narra::models::knowledge::{
+        create_knowledge, create_knowledge_state, KnowledgeStateCreate, LearningMethod,
+    };
+    use narra::models::scene::get_event_characters;
+    use narra::services::ConsistencyChecker;
+
+    let harness = TestHarness::new().await;
+    let server = crate::common::create_test_server(&harness).await;
+
+    for (id, name) in [("alice", "Alice"), ("bob", "Bob"), ("carol", "Carol")] {
+        create_character_with_id(&harness.db, id, CharacterBuilder::new(name).build())
+            .await
+            .expect("Create character");
+    }
+    create_event_with_id(
+        &harness.db,
+        "duel",
+        EventBuilder::new("The duel").sequence(1).build(),
+    )
+    .await
+    .expect("Create event");
+
+    let add = |character: &str, role: &str| {
+        to_mutation_input(MutationRequest::AddEventParticipant {
+            event_id: "event:duel".to_string(),
+            character_id: format!("character:{}", character),
+            role: Some(role.to_string()),
+            impact: None,
+            force: None,
+        })
+    };
+    server
+        .handle_mutate(Parameters(add("alice", "duelist")))
+        .await
+        .expect("Add participant");
+    let response = server
+        .handle_mutate(Parameters(add("alice", "victor")))
+        .await
+        .expect("Re-add participant");
+    assert_eq!(response.entity.entity_type, "involved_in");
+    server
+        .handle_mutate(Parameters(add("bob", "witness")))
+        .await
+        .expect("Add participant");
+
+    let participants = get_event_characters(&harness.db, "duel").await.unwrap();
+    assert_eq!(participants.len(), 2);
+    let alice = participants
+        .iter()
+        .find(|p| p.character.key().to_string() == "alice")
+        .expect("Alice takes part");
+    assert_eq!(alice.role.as_deref(), Some("victor"));
+
+    // Carol saw the duel without taking part in it
+    let secret = create_knowledge(
+        &harness.db,
+        KnowledgeBuilder::new("Alice cheated")
+            .for_character("carol")
+            .build(),
+    )
+    .await
+    .unwrap();
+    create_knowledge_state(
+        &harness.db,
+        "carol",
+        &secret.id.to_string(),
+        KnowledgeStateCreate {
+            learning_method: LearningMethod::Witnessed,
+            event: Some("duel".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let checker = ConsistencyChecker::new(harness.db.clone());
+    let violations = checker.check_timeline_violations("carol").await.unwrap();
+    assert!(violations
+        .iter()
+        .any(|v| v.fact_title == "Timeline: Witnessed without being present"));
+
+    server
+        .handle_mutate(Parameters(add("carol", "witness")))
+        .await
+        .expect("Add participant");
+    let violations = checker.check_timeline_violations("carol").await.unwrap();
+    assert!(violations.is_empty());
+
+    let remove = to_mutation_input(MutationRequest::RemoveEventParticipant {
+        event_id: "event:duel".to_string(),
+        character_id: "character:bob".to_string(),
+        force: None,
+    });
+    server
+        .handle_mutate(Parameters(remove.clone()))
+        .await
+        .expect("Remove participant");
+    assert_eq!(
+        get_event_characters(&harness.db, "duel")
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(server.handle_mutate(Parameters(remove)).await.is_err());
+}
+
 /// Test batch with mixed auto-generated and caller-specified IDs.
 #[tokio::test]
 async fn test_batch_mixed_ids() {
@@ -4,6 +4,7 @@
 //! persistence, and temporal queries:
 //! - SavePhases / LoadPhases roundtrip
 //! - ClearPhases empties saved state
+//! - RenamePhase labels survive re-detection
 //! - DetectPhases with save flag
 //! - QueryAround via MCP
 //! - DetectTransitions via MCP
@@ -183,6 +184,7 @@ async fn test_save_phases_then_load_roundtrip() {
             content_weight: None,
             neighborhood_weight: None,
             temporal_weight: None,
+            seed: None,
         })))
         .await
         .expect("SavePhases should succeed");
@@ -208,6 +210,67 @@ async fn test_save_phases_then_load_roundtrip() {
     }
 }
 
+/// RenamePhase sets a custom label that survives re-saving phases.
+#[tokio::test]
+async fn test_rename_phase_survives_resave() {
+    let harness = TestHarness::new().await;
+    let _world = create_phase_world(&harness).await;
+    let server = create_test_server(&harness).await;
+    let save = || {
+        to_mutation_input(MutationRequest::SavePhases {
+            entity_types: None,
+            num_phases: Some(2),
+            content_weight: None,
+            neighborhood_weight: None,
+            temporal_weight: None,
+            seed: Some(3),
+        })
+    };
+
+    server
+        .handle_mutate(Parameters(save()))
+        .await
+        .expect("SavePhases should succeed");
+    let renamed = server
+        .handle_mutate(Parameters(to_mutation_input(
+            MutationRequest::RenamePhase {
+                phase_id: "phase:phase_1".to_string(),
+                label: "Act II: The Siege".to_string(),
+            },
+        )))
+        .await
+        .expect("RenamePhase should succeed");
+    assert_eq!(renamed.entity.id, "phase:phase_1");
+    assert_eq!(renamed.entity.name, "Act II: The Siege");
+
+    // Re-detect and save: the label follows the phase's members
+    server
+        .handle_mutate(Parameters(save()))
+        .await
+        .expect("SavePhases should succeed");
+    let load_response = server
+        .handle_query(Parameters(to_query_input(QueryRequest::LoadPhases)))
+        .await
+        .expect("LoadPhases should succeed");
+    assert!(
+        load_response
+            .results
+            .iter()
+            .any(|r| r.name.ends_with("Act II: The Siege")),
+        "Custom label should survive re-detection"
+    );
+
+    let missing = server
+        .handle_mutate(Parameters(to_mutation_input(
+            MutationRequest::RenamePhase {
+                phase_id: "phase_9".to_string(),
+                label: "Nowhere".to_string(),
+            },
+        )))
+        .await;
+    assert!(missing.is_err(), "Unknown phase should fail");
+}
+
 /// Save → ClearPhases → Load returns empty.
 #[tokio::test]
 async fn test_clear_phases_empties_saved() {
@@ -223,6 +286,7 @@ async fn test_clear_phases_empties_saved() {
             content_weight: None,
             neighborhood_weight: None,
             temporal_weight: None,
+            seed: None,
         })))
         .await
         .expect("SavePhases should succeed");
@@ -269,6 +333,7 @@ async fn test_detect_phases_save_flag() {
             neighborhood_weight: None,
             temporal_weight: None,
             save: Some(false),
+            seed: None,
         })))
         .await
         .expect("DetectPhases should succeed");
@@ -294,6 +359,7 @@ async fn test_detect_phases_save_flag() {
             neighborhood_weight: None,
             temporal_weight: None,
             save: Some(true),
+            seed: None,
         })))
         .await
         .expect("DetectPhases with save should succeed");
@@ -311,6 +377,34 @@ async fn test_detect_phases_save_flag() {
     );
 }
 
+/// DetectPhases with the same seed returns the same phases.
+#[tokio::test]
+async fn test_detect_phases_seed_is_reproducible() {
+    let harness = TestHarness::new().await;
+    let _world = create_phase_world(&harness).await;
+    let server = create_test_server(&harness).await;
+
+    let mut runs = Vec::new();
+    for _ in 0..2 {
+        let response = server
+            .handle_query(Parameters(to_query_input(QueryRequest::DetectPhases {
+                entity_types: None,
+                num_phases: Some(2),
+                content_weight: None,
+                neighborhood_weight: None,
+                temporal_weight: None,
+                save: None,
+                seed: Some(7),
+            })))
+            .await
+            .expect("DetectPhases should succeed");
+        let phases: Vec<String> = response.results.iter().map(|r| r.content.clone()).collect();
+        runs.push(phases);
+    }
+
+    assert_eq!(runs[0], runs[1], "Same seed should give the same phases");
+}
+
 // =============================================================================
 // QUERY AROUND
 // =============================================================================
@@ -373,6 +467,7 @@ async fn test_detect_transitions_via_mcp() {
                 content_weight: None,
                 neighborhood_weight: None,
                 temporal_weight: None,
+                seed: None,
             },
         )))
         .await
@@ -414,6 +509,7 @@ async fn test_unified_search_phase_filter() {
             content_weight: None,
             neighborhood_weight: None,
             temporal_weight: None,
+            seed: None,
         })))
         .await
         .expect("SavePhases should succeed");
@@ -516,6 +612,7 @@ async fn test_detect_phases_insufficient_entities() {
             neighborhood_weight: None,
             temporal_weight: None,
             save: None,
+            seed: None,
         })))
         .await;
 
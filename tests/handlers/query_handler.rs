@@ -416,6 +416,7 @@ async fn test_centrality_metrics() {
         scope: None,
         metrics: None,
         limit: Some(10),
+        phase: None,
     };
     let response = server
         .handle_query(Parameters(to_query_input(request)))
@@ -577,6 +578,7 @@ async fn test_dramatic_irony_report() {
     let request = QueryRequest::DramaticIronyReport {
         character_id: None,
         min_scene_threshold: Some(0),
+        phase: None,
     };
     let response = server
         .handle_query(Parameters(to_query_input(request)))
@@ -731,6 +733,8 @@ async fn test_thematic_clustering() {
     let request = QueryRequest::ThematicClustering {
         entity_types: Some(vec!["character".to_string()]),
         num_themes: Some(2),
+        seed: None,
+        phase: None,
     };
     let response = server
         .handle_query(Parameters(to_query_input(request)))
@@ -149,6 +149,7 @@ async fn test_mutation_update_malformed_entity_id() {
     let request = MutationRequest::Update {
         entity_id: "###invalid###".to_string(),
         fields: serde_json::json!({"name": "New Name"}),
+        force: None,
     };
 
     let response = server
@@ -229,6 +230,7 @@ async fn test_mutation_delete_nonexistent_entity() {
     let request = MutationRequest::Delete {
         entity_id: "character:nonexistent_xyz789".to_string(),
         hard: Some(false),
+        force: None,
     };
 
     let response = server
@@ -5,7 +5,7 @@ use narra::mcp::types::{
     CharacterSpec, ConflictMode, EventSpec, FactLinkSpec, FactSpec, KnowledgeSpec, LocationSpec,
     NarraImport, NoteSpec, ParticipantSpec, RelationshipSpec, SceneSpec,
 };
-use narra::services::import::ImportService;
+use narra::services::import::{ConflictPolicy, ImportService};
 use std::sync::Arc;
 
 use narra::embedding::{NoopEmbeddingService, StalenessManager};
@@ -59,6 +59,7 @@ fn full_import() -> NarraImport {
             sequence: Some(10),
             date: None,
             date_precision: None,
+            narrative_status: None,
         }],
         scenes: vec![SceneSpec {
             id: Some("homecoming".to_string()),
@@ -79,6 +80,7 @@ fn full_import() -> NarraImport {
             rel_type: "rivalry".to_string(),
             subtype: None,
             label: Some("Political rivals".to_string()),
+            intentional: None,
         }],
         knowledge: vec![KnowledgeSpec {
             character_id: "character:alice".to_string(),
@@ -93,6 +95,8 @@ fn full_import() -> NarraImport {
             id: Some("worldnote".to_string()),
             title: "World Notes".to_string(),
             body: "The kingdom is at war.".to_string(),
+            kind: None,
+            status: None,
             attach_to: vec!["character:alice".to_string()],
         }],
         facts: vec![FactSpec {
@@ -186,6 +190,7 @@ async fn test_import_dependency_ordering() {
             sequence: Some(1),
             date: None,
             date_precision: None,
+            narrative_status: None,
         }],
         scenes: vec![SceneSpec {
             id: Some("intro".to_string()),
@@ -311,6 +316,54 @@ async fn test_import_conflict_skip() {
     assert_eq!(r2.total_errors, 0);
 }
 
+#[tokio::test]
+async fn test_import_matches_transliterated_names() {
+    let harness = TestHarness::new().await;
+    let staleness = test_staleness(&harness);
+    let service = ImportService::new(harness.db.clone(), staleness);
+
+    let character = |id: Option<&str>, name: &str, aliases: Option<Vec<String>>| CharacterSpec {
+        id: id.map(str::to_string),
+        name: name.to_string(),
+        role: None,
+        aliases,
+        description: None,
+        profile: None,
+    };
+    let world = NarraImport {
+        characters: vec![
+            character(Some("osten"), "Östen", None),
+            character(Some("dmitri"), "Dmitri", Some(vec!["Дмитрий".to_string()])),
+        ],
+        ..Default::default()
+    };
+    service
+        .execute_import(world, ConflictMode::Error)
+        .await
+        .unwrap();
+
+    // Unidentified specs land on the existing characters instead of duplicating them
+    let manuscript = NarraImport {
+        characters: vec![
+            character(None, "Osten", None),
+            character(None, "дмитрий", None),
+            character(None, "Vera", None),
+        ],
+        ..Default::default()
+    };
+    let result = service
+        .execute_import(manuscript, ConflictMode::Skip)
+        .await
+        .unwrap();
+    assert_eq!(result.total_skipped, 2);
+    assert_eq!(result.total_created, 1);
+    assert_eq!(result.conflicts.len(), 2);
+    assert!(result
+        .conflicts
+        .iter()
+        .any(|c| c.entity_id == "character:osten"));
+}
+
 #[tokio::test]
 async fn test_import_conflict_update() {
     let harness = TestHarness::new().await;
@@ -363,6 +416,72 @@ async fn test_import_conflict_update() {
     assert_eq!(alice.name, "Alice Updated");
 }
 
+#[tokio::test]
+async fn test_import_conflict_report_with_overrides() {
+    let harness = TestHarness::new().await;
+    let staleness = test_staleness(&harness);
+    let service = ImportService::new(harness.db.clone(), staleness);
+
+    let character = |id: &str, name: &str| CharacterSpec {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+        role: None,
+        aliases: None,
+        description: None,
+        profile: None,
+    };
+    let original = NarraImport {
+        characters: vec![character("alice", "Alice"), character("bob", "Bob")],
+        ..Default::default()
+    };
+    service
+        .execute_import(original, ConflictMode::Error)
+        .await
+        .unwrap();
+
+    let incoming = NarraImport {
+        characters: vec![
+            character("alice", "Alice Renamed"),
+            character("bob", "Robert"),
+            character("carol", "Carol"),
+        ],
+        ..Default::default()
+    };
+    let conflicts = service.conflicts(&incoming).await.unwrap();
+    assert_eq!(conflicts.len(), 2);
+    let alice = conflicts
+        .iter()
+        .find(|c| c.entity_id == "character:alice")
+        .unwrap();
+    assert_eq!(alice.fields.len(), 1);
+    assert_eq!(alice.fields[0].field, "name");
+    assert_eq!(alice.fields[0].old, "Alice");
+    assert_eq!(alice.fields[0].new, "Alice Renamed");
+
+    // Update Alice only; Bob falls back to skip
+    let mut policy = ConflictPolicy::uniform(ConflictMode::Skip);
+    policy
+        .overrides
+        .insert("character:alice".to_string(), ConflictMode::Update);
+    let result = service
+        .execute_import_with(incoming, &policy)
+        .await
+        .unwrap();
+    assert_eq!(result.total_updated, 1);
+    assert_eq!(result.total_skipped, 1);
+    assert_eq!(result.total_created, 1);
+    assert!(result
+        .conflicts
+        .iter()
+        .any(|c| c.entity_id == "character:bob" && c.resolution == Some(ConflictMode::Skip)));
+
+    let bob = narra::models::character::get_character(&harness.db, "bob")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(bob.name, "Bob");
+}
+
 #[tokio::test]
 async fn test_import_scene_with_participants() {
     let harness = TestHarness::new().await;
@@ -402,6 +521,7 @@ async fn test_import_scene_with_participants() {
             sequence: Some(1),
             date: None,
             date_precision: None,
+            narrative_status: None,
         }],
         scenes: vec![SceneSpec {
             id: Some("dinner".to_string()),
@@ -521,6 +641,8 @@ async fn test_import_notes_with_attachments() {
             id: Some("mynote".to_string()),
             title: "Research Notes".to_string(),
             body: "Some worldbuilding ideas".to_string(),
+            kind: None,
+            status: None,
             attach_to: vec!["character:alice".to_string()],
         }],
         ..Default::default()
@@ -0,0 +1,137 @@
+//! Integration tests for Lua plugin hooks.
+
+mod common;
+
+use std::sync::Arc;
+
+use common::builders::CharacterBuilder;
+use common::harness::TestHarness;
+use narra::models::character::{create_character, list_characters};
+use narra::plugins::{PluginConsistencyService, PluginHost};
+use narra::services::{ConsistencyChecker, ConsistencyService, ConsistencySeverity};
+
+fn write_plugin(harness: &TestHarness, name: &str, source: &str) {
+    let dir = harness.temp_path().join("plugins");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(format!("{}.lua", name)), source).unwrap();
+}
+
+#[tokio::test]
+async fn test_on_validate_adds_violations() {
+    let harness = TestHarness::new().await;
+    write_plugin(
+        &harness,
+        "hard_sf",
+        r#"
+        narra.on_validate(function(entity)
+            if entity.entity_type == "character" and entity.name == "Zed" then
+                return { severity = "critical", message = "Zed cannot exceed light speed" }
+            end
+        end)
+        "#,
+    );
+
+    let character = create_character(&harness.db, CharacterBuilder::new("Zed").build())
+        .await
+        .unwrap();
+    let plugins = Arc::new(PluginHost::load(harness.temp_path(), harness.db.clone()).await);
+    assert!(plugins.has_hook("on_validate"));
+
+    let service = PluginConsistencyService::new(
+        Arc::new(ConsistencyChecker::new(harness.db.clone())),
+        plugins,
+        harness.db.clone(),
+    );
+    let result = service
+        .check_entity_mutation(&character.id.to_string(), &serde_json::json!({}))
+        .await
+        .unwrap();
+
+    assert!(result.has_blocking_violations);
+    let critical = &result.violations_by_severity[&ConsistencySeverity::Critical];
+    assert_eq!(critical[0].fact_id, "plugin:hard_sf");
+}
+
+#[tokio::test]
+async fn test_commands_can_query_and_sandbox_blocks_os() {
+    let harness = TestHarness::new().await;
+    write_plugin(
+        &harness,
+        "report",
+        r#"
+        narra.command("count", "Count characters", function(args)
+            local rows = narra.query("SELECT count() AS n FROM character GROUP ALL")
+            return { n = rows[1].n, sandboxed = (os == nil and io == nil), args = args }
+        end)
+        "#,
+    );
+    write_plugin(&harness, "broken", "this is not lua");
+
+    create_character(&harness.db, CharacterBuilder::new("Alice").build())
+        .await
+        .unwrap();
+    let plugins = PluginHost::load(harness.temp_path(), harness.db.clone()).await;
+
+    let commands = plugins.commands();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].name, "count");
+
+    let result = plugins
+        .run_command("count", &["--verbose".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(result["n"], 1);
+    assert_eq!(result["sandboxed"], true);
+    assert_eq!(result["args"][0], "--verbose");
+
+    assert!(plugins.run_command("missing", &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn test_runaway_hook_is_stopped() {
+    let harness = TestHarness::new().await;
+    write_plugin(
+        &harness,
+        "spin",
+        r#"narra.command("spin", "", function() while true do end end)"#,
+    );
+
+    let plugins = PluginHost::load(harness.temp_path(), harness.db.clone()).await;
+    let err = plugins.run_command("spin", &[]).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("time budget"));
+}
+
+#[tokio::test]
+async fn test_query_refuses_write_subqueries() {
+    let harness = TestHarness::new().await;
+    write_plugin(
+        &harness,
+        "sneaky",
+        r#"
+        narra.command("wipe", "", function(args)
+            return narra.query(args[1])
+        end)
+        "#,
+    );
+
+    create_character(&harness.db, CharacterBuilder::new("Alice").build())
+        .await
+        .unwrap();
+    let plugins = PluginHost::load(harness.temp_path(), harness.db.clone()).await;
+
+    for sql in [
+        "SELECT * FROM (DELETE character RETURN BEFORE)",
+        "SELECT * FROM (UPDATE character SET name = 'Mallory')",
+        "DELETE character",
+    ] {
+        let err = plugins
+            .run_command("wipe", &[sql.to_string()])
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("narra.query"), "{}", sql);
+    }
+
+    let characters = list_characters(&harness.db).await.unwrap();
+    assert_eq!(characters.len(), 1);
+    assert_eq!(characters[0].name, "Alice");
+}
@@ -25,6 +25,7 @@ async fn test_create_and_lookup_character() {
         aliases: None,
         description: Some("A mysterious figure with a troubled past".to_string()),
         profile: None,
+        allow_similar: None,
     };
 
     let create_result = server
@@ -66,6 +67,7 @@ async fn test_search_with_fuzzy_matching() {
             aliases: None,
             description: None,
             profile: None,
+            allow_similar: Some(true),
         };
         server
             .handle_mutate(Parameters(to_mutation_input(request)))
@@ -108,6 +110,7 @@ async fn test_record_and_query_knowledge() {
         aliases: None,
         description: None,
         profile: None,
+        allow_similar: None,
     };
     let char_result = server
         .handle_mutate(Parameters(to_mutation_input(char_request)))
@@ -179,6 +182,7 @@ async fn test_impact_analysis() {
                 aliases: None,
                 description: None,
                 profile: None,
+                allow_similar: None,
             },
         )))
         .await
@@ -193,6 +197,7 @@ async fn test_impact_analysis() {
                 aliases: None,
                 description: None,
                 profile: None,
+                allow_similar: None,
             },
         )))
         .await
@@ -254,6 +259,7 @@ async fn test_pagination_with_cursors() {
                     aliases: None,
                     description: None,
                     profile: None,
+                    allow_similar: None,
                 },
             )))
             .await
@@ -290,6 +296,7 @@ async fn test_update_with_impact() {
                 aliases: None,
                 description: None,
                 profile: None,
+                allow_similar: None,
             },
         )))
         .await
@@ -304,6 +311,7 @@ async fn test_update_with_impact() {
             "name": "Updated Name",
             "roles": ["Villain"]
         }),
+        force: None,
     };
 
     let update_result = server
@@ -334,6 +342,7 @@ async fn test_graph_traversal() {
                 aliases: None,
                 description: None,
                 profile: None,
+                allow_similar: None,
             },
         )))
         .await
@@ -348,6 +357,7 @@ async fn test_graph_traversal() {
                 aliases: None,
                 description: None,
                 profile: None,
+                allow_similar: None,
             },
         )))
         .await
@@ -384,3 +394,79 @@ async fn test_graph_traversal() {
     // Should find connected entities
     assert!(!traversal_result.hints.is_empty());
 }
+
+#[tokio::test]
+async fn test_foreshadow_mutations_and_validation() {
+    use narra::services::foreshadowing::ForeshadowingService;
+
+    let harness = TestHarness::new().await;
+    let server = common::create_test_server(&harness).await;
+
+    for (id, sequence) in [("dream", 1), ("fall", 5)] {
+        server
+            .handle_mutate(Parameters(to_mutation_input(
+                MutationRequest::CreateEvent {
+                    id: Some(id.to_string()),
+                    title: id.to_string(),
+                    description: None,
+                    sequence: Some(sequence),
+                    date: None,
+                    date_precision: None,
+                    narrative_status: None,
+                },
+            )))
+            .await
+            .unwrap();
+    }
+
+    let link = |setup: &str, payoff: &str| MutationRequest::CreateForeshadow {
+        setup_id: setup.to_string(),
+        payoff_id: payoff.to_string(),
+        note: None,
+    };
+    server
+        .handle_mutate(Parameters(to_mutation_input(link(
+            "event:dream",
+            "event:fall",
+        ))))
+        .await
+        .expect("Create foreshadow failed");
+    server
+        .handle_mutate(Parameters(to_mutation_input(link(
+            "event:fall",
+            "event:dream",
+        ))))
+        .await
+        .expect("Create reversed foreshadow failed");
+    assert!(server
+        .handle_mutate(Parameters(to_mutation_input(link(
+            "character:x",
+            "event:fall"
+        ))))
+        .await
+        .is_err());
+
+    let report = ForeshadowingService::new(harness.db.clone())
+        .report()
+        .await
+        .unwrap();
+    assert_eq!(report.links, 2);
+    let problems: Vec<&str> = report.problems().map(|p| p.payoff.id.as_str()).collect();
+    assert_eq!(problems, vec!["event:dream"]);
+
+    server
+        .handle_mutate(Parameters(to_mutation_input(
+            MutationRequest::RemoveForeshadow {
+                setup_id: "event:fall".to_string(),
+                payoff_id: "event:dream".to_string(),
+            },
+        )))
+        .await
+        .expect("Remove foreshadow failed");
+    let report = ForeshadowingService::new(harness.db.clone())
+        .report()
+        .await
+        .unwrap();
+    assert_eq!(report.links, 1);
+    assert_eq!(report.problems().count(), 0);
+}
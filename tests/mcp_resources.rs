@@ -7,6 +7,7 @@ use std::sync::Arc;
 use common::harness::TestHarness;
 use narra::models::character::create_character;
 use narra::models::CharacterCreate;
+use narra::services::HeuristicTokenCounter;
 use narra::session::SessionStateManager;
 
 #[tokio::test]
@@ -18,9 +19,13 @@ async fn test_session_context_resource_empty_world() {
             .expect("Failed to create session manager"),
     );
 
-    let result = narra::mcp::resources::get_session_context_resource(&session_manager, &harness.db)
-        .await
-        .expect("Should return session context");
+    let result = narra::mcp::resources::get_session_context_resource(
+        &session_manager,
+        &harness.db,
+        &HeuristicTokenCounter,
+    )
+    .await
+    .expect("Should return session context");
 
     // Parse and verify structure
     let info: narra::session::SessionStartupInfo =
@@ -31,6 +36,42 @@ async fn test_session_context_resource_empty_world() {
     assert!(info.hot_entities.is_empty());
 }
 
+#[tokio::test]
+async fn test_session_stats_resource() {
+    let harness = TestHarness::new().await;
+    let session_path = harness.temp_path().join("session.json");
+    let session_manager = Arc::new(
+        SessionStateManager::load_or_create(&session_path)
+            .expect("Failed to create session manager"),
+    );
+    let character = create_character(
+        &harness.db,
+        CharacterCreate {
+            name: "Alice".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create character");
+    let alice_id = format!("character:{}", character.id.key());
+
+    let result = narra::mcp::resources::get_session_stats_resource(&session_manager, &harness.db)
+        .await
+        .expect("Should return session stats");
+    let stats: narra::session::SessionStats =
+        serde_json::from_str(&result).expect("Should be valid SessionStats JSON");
+    assert!(stats.focus.is_empty());
+    assert_eq!(stats.untouched.len(), 1);
+
+    session_manager.record_access(&alice_id).await;
+    let result = narra::mcp::resources::get_session_stats_resource(&session_manager, &harness.db)
+        .await
+        .expect("Should return session stats");
+    let stats: narra::session::SessionStats = serde_json::from_str(&result).unwrap();
+    assert_eq!(stats.focus[0].id, alice_id);
+    assert!(stats.untouched.is_empty());
+}
+
 #[tokio::test]
 async fn test_entity_resource_not_found() {
     let harness = TestHarness::new().await;
@@ -110,3 +151,44 @@ async fn test_consistency_issues_resource_empty() {
     assert_eq!(report["total_issues"], 0);
     assert_eq!(report["critical_count"], 0);
 }
+
+#[tokio::test]
+async fn test_asset_resources() {
+    use narra::mcp::resources::{get_asset_resource, get_assets_resource, AssetContent};
+    use narra::services::AssetStore;
+
+    let harness = TestHarness::new().await;
+    let store = AssetStore::new(harness.db.clone(), harness.temp_path(), None);
+    let source = harness.temp_path().join("harbor.png");
+    std::fs::write(&source, b"\x89PNG harbor map").unwrap();
+
+    let asset = store
+        .add(&source, Some("location:harbor"), Some("The harbor".into()))
+        .await
+        .expect("Should store asset");
+
+    let listing: serde_json::Value =
+        serde_json::from_str(&get_assets_resource(&store).await.unwrap()).unwrap();
+    let entry = &listing[0];
+    assert_eq!(entry["entity"], "location:harbor");
+    assert_eq!(entry["uri"], format!("narra://asset/{}", asset.id.key()));
+    assert!(entry["file_uri"].as_str().unwrap().starts_with("file://"));
+
+    match get_asset_resource(&store, &asset.id.key().to_string())
+        .await
+        .unwrap()
+    {
+        AssetContent::Blob { mime_type, blob } => {
+            assert_eq!(mime_type, "image/png");
+            use base64::Engine as _;
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(blob)
+                .unwrap();
+            assert_eq!(bytes, b"\x89PNG harbor map");
+        }
+        AssetContent::Metadata(_) => panic!("Small images should be inlined"),
+    }
+
+    let missing = get_asset_resource(&store, "nope").await;
+    assert!(missing.is_err_and(|e| e.starts_with("Not found")));
+}
@@ -459,6 +459,7 @@ async fn test_unresolved_tensions_max_shared_scenes() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -45,6 +45,7 @@ async fn test_note_crud_operations() {
             title: "Research: Magic System".to_string(),
             body: "The magic system is based on elemental forces. Fire, water, earth, air."
                 .to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -71,6 +72,7 @@ async fn test_note_crud_operations() {
         NoteUpdate {
             title: Some("Research: Elemental Magic System".to_string()),
             body: None,
+            ..Default::default()
         },
     )
     .await
@@ -124,6 +126,7 @@ async fn test_note_attachment_to_entity() {
         NoteCreate {
             title: "Elena's Backstory Ideas".to_string(),
             body: "Consider: orphaned at age 10, raised by grandmother".to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -184,6 +187,7 @@ async fn test_note_standalone_no_attachments() {
         NoteCreate {
             title: "World Building: Climate".to_string(),
             body: "The world has three distinct seasons...".to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -194,6 +198,7 @@ async fn test_note_standalone_no_attachments() {
         NoteCreate {
             title: "Plot Idea: Betrayal Arc".to_string(),
             body: "What if the mentor character is secretly working for the enemy?".to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -238,6 +243,7 @@ async fn test_note_fulltext_search() {
         NoteCreate {
             title: "Dragon Mythology".to_string(),
             body: "Dragons in this world are elemental creatures tied to volcanoes".to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -248,6 +254,7 @@ async fn test_note_fulltext_search() {
         NoteCreate {
             title: "Character Arc: Redemption".to_string(),
             body: "The villain seeks redemption after betraying the kingdom".to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -309,6 +316,7 @@ async fn test_note_search_filter_by_type() {
         NoteCreate {
             title: "Magic System Design".to_string(),
             body: "Notes about the magic system".to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -844,6 +852,7 @@ async fn test_phase7_full_workflow() {
                    This trauma drove her to become a detective, seeking justice for those who \
                    cannot seek it themselves."
                 .to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -856,6 +865,7 @@ async fn test_phase7_full_workflow() {
             body: "In Act 2, Elena discovers Marcus was responsible for her father's death. \
                    This revelation changes her motivation from professional to personal."
                 .to_string(),
+            ..Default::default()
         },
     )
     .await
@@ -944,3 +954,226 @@ async fn test_phase7_full_workflow() {
 
     println!("Phase 7 full workflow test passed - all features verified");
 }
+
+// ============================================================================
+// Tags on scenes, events, and notes
+// ============================================================================
+
+/// Tags are normalized, deduplicated, counted per table, and filterable.
+#[tokio::test]
+async fn test_tag_crud_and_counts() {
+    use narra::models::event::create_event;
+    use narra::models::tag::{add_tags, get_tags, remove_tags, tag_counts, tagged_ids};
+    use narra::models::EventCreate;
+
+    let harness = TestHarness::new().await;
+    let db = &harness.db;
+
+    let note = create_note(
+        db,
+        NoteCreate {
+            title: "Siege logistics".to_string(),
+            body: "Grain lasts eleven days.".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let event = create_event(
+        db,
+        EventCreate {
+            title: "The walls fall".to_string(),
+            description: None,
+            sequence: 10,
+            date: None,
+            date_precision: None,
+            duration_end: None,
+            narrative_status: None,
+        },
+    )
+    .await
+    .unwrap();
+    let note_id = note.id.to_string();
+    let event_id = event.id.to_string();
+
+    let tags = add_tags(db, &note_id, &["B-Plot".to_string(), " siege ".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(tags, vec!["b-plot", "siege"]);
+    add_tags(db, &note_id, &["siege".to_string()])
+        .await
+        .unwrap();
+    add_tags(db, &event_id, &["siege".to_string(), "action".to_string()])
+        .await
+        .unwrap();
+
+    let fetched = get_note(db, &note.id.key().to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fetched.tags, vec!["b-plot", "siege"]);
+
+    let counts = tag_counts(db).await.unwrap();
+    assert_eq!(counts[0].tag, "siege");
+    assert_eq!((counts[0].events, counts[0].notes), (1, 1));
+    assert_eq!(counts.len(), 3);
+
+    let siege = tagged_ids(db, "SIEGE").await.unwrap();
+    assert!(siege.contains(&note_id) && siege.contains(&event_id));
+
+    let remaining = remove_tags(db, &note_id, &["siege".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(remaining, vec!["b-plot"]);
+    assert_eq!(
+        get_tags(db, &event_id).await.unwrap(),
+        vec!["action", "siege"]
+    );
+
+    assert!(add_tags(db, "character:alice", &["x".to_string()])
+        .await
+        .is_err());
+    assert!(add_tags(db, "note:missing", &["x".to_string()])
+        .await
+        .is_err());
+}
+
+/// `[[Name]]` links attach notes, follow body edits, and survive renames.
+#[tokio::test]
+async fn test_note_wiki_links() {
+    use narra::models::character::{update_character, CharacterUpdate};
+    use narra::services::note_links::{backlinks, resync_all_note_links, sync_note_links};
+
+    let harness = TestHarness::new().await;
+    let db = &harness.db;
+    let entity_repo = SurrealEntityRepository::new(db.clone());
+
+    let osten = entity_repo
+        .create_character(CharacterCreate {
+            name: "Östen".to_string(),
+            aliases: vec![],
+            roles: vec![],
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let osten_id = osten.id.to_string();
+    let osten_key = osten.id.key().to_string();
+
+    let note = create_note(
+        db,
+        NoteCreate {
+            title: "Siege".to_string(),
+            body: "Ask [[Osten|the miller]] about [[Nobody]].".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let note_key = note.id.key().to_string();
+
+    let sync = sync_note_links(db, &note_key).await.unwrap();
+    assert_eq!(sync.linked, vec![osten_id.clone()]);
+    assert_eq!(sync.unresolved, vec!["Nobody"]);
+
+    let links = backlinks(db, &osten_id).await.unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].link, "Osten");
+
+    // Renaming the target keeps the link while it stays in the body
+    update_character(
+        db,
+        &osten_key,
+        CharacterUpdate {
+            name: Some("Erik".to_string()),
+            aliases: None,
+            roles: None,
+            profile: None,
+            updated_at: chrono::Utc::now().into(),
+        },
+    )
+    .await
+    .unwrap();
+    resync_all_note_links(db).await.unwrap();
+    assert_eq!(backlinks(db, &osten_id).await.unwrap().len(), 1);
+
+    // Dropping the link from the body removes the link-only attachment
+    update_note(
+        db,
+        &note_key,
+        NoteUpdate {
+            title: None,
+            body: Some("Nothing to ask.".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let sync = sync_note_links(db, &note_key).await.unwrap();
+    assert!(sync.linked.is_empty());
+    assert!(get_entity_notes(db, &osten_id).await.unwrap().is_empty());
+
+    // A manual attachment outlives its link
+    attach_note(db, &note_key, &osten_id).await.unwrap();
+    sync_note_links(db, &note_key).await.unwrap();
+    assert_eq!(get_entity_notes(db, &osten_id).await.unwrap().len(), 1);
+    assert!(backlinks(db, &osten_id).await.unwrap().is_empty());
+}
+
+/// Note kinds round-trip; only todos and critiques carry a status, and
+/// critiques need a scene.
+#[tokio::test]
+async fn test_note_kinds_and_status() {
+    use narra::models::note::{check_critique_targets, set_note_status, NoteKind, NoteStatus};
+
+    let harness = TestHarness::new().await;
+    let db = &harness.db;
+
+    let critique = create_note(
+        db,
+        NoteCreate {
+            title: "Opening drags".to_string(),
+            body: "Cut the weather".to_string(),
+            kind: NoteKind::Critique,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(critique.kind, NoteKind::Critique);
+    assert_eq!(critique.status, NoteStatus::Open);
+
+    let key = critique.id.key().to_string();
+    let dismissed = set_note_status(db, &key, NoteStatus::Dismissed)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(dismissed.status, NoteStatus::Dismissed);
+
+    let general = create_note(
+        db,
+        NoteCreate {
+            title: "Loose idea".to_string(),
+            body: String::new(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(general.kind, NoteKind::General);
+    assert!(
+        set_note_status(db, &general.id.key().to_string(), NoteStatus::Resolved)
+            .await
+            .is_err()
+    );
+    assert!(set_note_status(db, "missing", NoteStatus::Resolved)
+        .await
+        .unwrap()
+        .is_none());
+
+    assert!(check_critique_targets(NoteKind::Critique, &["character:alice".to_string()]).is_err());
+    assert!(check_critique_targets(NoteKind::Critique, &["scene:opening".to_string()]).is_ok());
+    assert!(check_critique_targets(NoteKind::Todo, &[]).is_ok());
+    assert_eq!("TODO".parse::<NoteKind>().unwrap(), NoteKind::Todo);
+    assert!("idea".parse::<NoteKind>().is_err());
+}
@@ -14,6 +14,7 @@ use narra::mcp::tools::SessionContextRequest;
 use narra::mcp::NarraServer;
 use narra::models::{CharacterCreate, LocationCreate};
 use narra::repository::{EntityRepository, SurrealEntityRepository};
+use narra::services::HeuristicTokenCounter;
 use narra::session::{
     generate_startup_context, PendingDecision, SessionStateManager, StartupVerbosity,
 };
@@ -136,7 +137,9 @@ async fn test_startup_context_knows_last_work() {
     manager.mark_session_end().await;
 
     // Generate startup context
-    let startup_info = generate_startup_context(&manager, &db).await.unwrap();
+    let startup_info = generate_startup_context(&manager, &db, &HeuristicTokenCounter)
+        .await
+        .unwrap();
 
     // Verify summary mentions recent work
     assert!(!startup_info.summary.is_empty());
@@ -210,7 +213,9 @@ async fn test_startup_surfaces_recent_context() {
     manager.mark_session_end().await;
 
     // Generate startup context
-    let startup_info = generate_startup_context(&manager, &db).await.unwrap();
+    let startup_info = generate_startup_context(&manager, &db, &HeuristicTokenCounter)
+        .await
+        .unwrap();
 
     // Verify "Last session" information present
     assert!(startup_info.last_session_ago.is_some());
@@ -304,7 +309,9 @@ async fn test_hot_entity_tracking() {
     assert_eq!(recent[2], c_id); // C least recent
 
     // Verify that hot entities from startup context match
-    let startup_info = generate_startup_context(&manager, &db).await.unwrap();
+    let startup_info = generate_startup_context(&manager, &db, &HeuristicTokenCounter)
+        .await
+        .unwrap();
     assert_eq!(startup_info.hot_entities.len(), 3);
     assert_eq!(startup_info.hot_entities[0].name, "A");
     assert_eq!(startup_info.hot_entities[1].name, "B");
@@ -351,7 +358,9 @@ async fn test_startup_verbosity_by_time() {
         manager.mark_session_end().await;
         manager.save().await.unwrap(); // Save before generating context
 
-        let startup_info = generate_startup_context(&manager, &db).await.unwrap();
+        let startup_info = generate_startup_context(&manager, &db, &HeuristicTokenCounter)
+            .await
+            .unwrap();
         assert_eq!(startup_info.verbosity, StartupVerbosity::Brief);
         println!("✓ Brief verbosity: {}", startup_info.summary);
     }
@@ -369,7 +378,9 @@ async fn test_startup_verbosity_by_time() {
 
         // Reload manager
         let manager = SessionStateManager::load_or_create(&session_path).unwrap();
-        let startup_info = generate_startup_context(&manager, &db).await.unwrap();
+        let startup_info = generate_startup_context(&manager, &db, &HeuristicTokenCounter)
+            .await
+            .unwrap();
         assert_eq!(startup_info.verbosity, StartupVerbosity::Standard);
         println!("✓ Standard verbosity: {}", startup_info.summary);
     }
@@ -385,7 +396,9 @@ async fn test_startup_verbosity_by_time() {
         fs::write(&session_path, serde_json::to_string_pretty(&state).unwrap()).unwrap();
 
         let manager = SessionStateManager::load_or_create(&session_path).unwrap();
-        let startup_info = generate_startup_context(&manager, &db).await.unwrap();
+        let startup_info = generate_startup_context(&manager, &db, &HeuristicTokenCounter)
+            .await
+            .unwrap();
         assert_eq!(startup_info.verbosity, StartupVerbosity::Full);
         println!("✓ Full verbosity: {}", startup_info.summary);
     }
@@ -395,7 +408,9 @@ async fn test_startup_verbosity_by_time() {
         let fresh_path = temp_dir.path().join("fresh_session.json");
         let manager = SessionStateManager::load_or_create(&fresh_path).unwrap();
 
-        let startup_info = generate_startup_context(&manager, &db).await.unwrap();
+        let startup_info = generate_startup_context(&manager, &db, &HeuristicTokenCounter)
+            .await
+            .unwrap();
         assert_eq!(startup_info.verbosity, StartupVerbosity::NewWorld);
         assert!(startup_info.world_overview.is_some());
         println!("✓ NewWorld verbosity: {}", startup_info.summary);
@@ -419,7 +434,9 @@ async fn test_startup_verbosity_by_time() {
         let empty_session_path = temp_dir.path().join("empty_session.json");
         let manager = SessionStateManager::load_or_create(&empty_session_path).unwrap();
 
-        let startup_info = generate_startup_context(&manager, &empty_db).await.unwrap();
+        let startup_info = generate_startup_context(&manager, &empty_db, &HeuristicTokenCounter)
+            .await
+            .unwrap();
         assert_eq!(startup_info.verbosity, StartupVerbosity::EmptyWorld);
         assert!(startup_info.summary.contains("empty"));
         println!("✓ EmptyWorld verbosity: {}", startup_info.summary);
@@ -467,7 +484,9 @@ async fn test_pending_decisions_surfaced() {
     manager.mark_session_end().await;
 
     // Generate startup context
-    let startup_info = generate_startup_context(&manager, &db).await.unwrap();
+    let startup_info = generate_startup_context(&manager, &db, &HeuristicTokenCounter)
+        .await
+        .unwrap();
 
     // Verify pending decisions in response
     assert_eq!(startup_info.pending_decisions.len(), 2);
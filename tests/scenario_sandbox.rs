@@ -0,0 +1,145 @@
+//! Integration tests for the what-if scenario sandbox.
+
+mod common;
+
+use common::harness::TestHarness;
+use narra::models::character::{create_character, CharacterCreate};
+use narra::models::knowledge::{create_knowledge, KnowledgeCreate};
+use narra::services::scenario::{self, Scenario, ScenarioSandbox, WorldMetrics};
+
+async fn count_knows(db: &narra::db::connection::NarraDb) -> usize {
+    let mut response = db.query("SELECT VALUE id FROM knows").await.unwrap();
+    let ids: Vec<surrealdb::RecordId> = response.take(0).unwrap();
+    ids.len()
+}
+
+/// Scenario steps land in the sandbox only; the real world is untouched.
+#[tokio::test]
+async fn test_scenario_runs_without_writing_world() {
+    let harness = TestHarness::new().await;
+    let alice = create_character(
+        &harness.db,
+        CharacterCreate {
+            name: "Alice".into(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let bob = create_character(
+        &harness.db,
+        CharacterCreate {
+            name: "Bob".into(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let secret = create_knowledge(
+        &harness.db,
+        KnowledgeCreate {
+            character: bob.id.clone(),
+            fact: "Bob embezzled the pension fund".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let scenario = Scenario::from_yaml(&format!(
+        r#"
+name: Alice finds out
+steps:
+  - op: learns
+    character: {alice}
+    fact: {secret}
+    certainty: suspects
+  - op: perceives
+    observer: {alice}
+    target: {bob}
+    tension_level: 9
+  - op: relates
+    from: {alice}
+    to: {bob}
+    rel_type: rivalry
+"#,
+        alice = alice.id,
+        bob = bob.id,
+        secret = secret.id,
+    ))
+    .unwrap();
+
+    let sandbox = ScenarioSandbox::fork(&harness.db).await.unwrap();
+    let report = sandbox.run(&scenario).await.expect("Scenario should run");
+
+    assert_eq!(report.name, "Alice finds out");
+    assert_eq!(report.steps.len(), 3);
+    assert_eq!(report.impacts.len(), 3);
+    assert_eq!(count_knows(&sandbox.db()).await, 1);
+    assert_eq!(
+        count_knows(&harness.db).await,
+        0,
+        "Scenario must not write to the world"
+    );
+
+    // Invalid steps name the step that failed
+    let broken = Scenario::from_yaml(
+        "steps:\n  - op: update\n    entity: character:nobody\n    set: { name: X }\n",
+    )
+    .unwrap();
+    let err = sandbox.run(&broken).await.unwrap_err().to_string();
+    assert!(err.contains("Step 1"), "got: {}", err);
+}
+
+/// A scenario that connects two characters shows up as a centrality shift.
+#[tokio::test]
+async fn test_scenario_diff_against_baseline() {
+    let harness = TestHarness::new().await;
+    for name in ["Alice", "Bob"] {
+        create_character(
+            &harness.db,
+            CharacterCreate {
+                name: name.into(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let mut response = harness
+        .db
+        .query("SELECT VALUE id FROM character ORDER BY name")
+        .await
+        .unwrap();
+    let ids: Vec<surrealdb::RecordId> = response.take(0).unwrap();
+
+    let scenario = Scenario::from_yaml(&format!(
+        "name: Allies\nsteps:\n  - op: relates\n    from: {}\n    to: {}\n    rel_type: alliance\n",
+        ids[0], ids[1]
+    ))
+    .unwrap();
+
+    let baseline = WorldMetrics::measure(harness.db.clone(), None)
+        .await
+        .unwrap();
+    let sandbox = ScenarioSandbox::fork(&harness.db).await.unwrap();
+    let report = sandbox.run(&scenario).await.unwrap();
+    let metrics = WorldMetrics::measure(sandbox.db(), baseline.phase_count())
+        .await
+        .unwrap();
+    let diff = scenario::diff(&report.name, &baseline, &metrics);
+
+    assert_eq!(diff.scenario, "Allies");
+    assert!(
+        diff.centrality
+            .iter()
+            .any(|c| c.scenario_degree > c.baseline_degree),
+        "New relationship should raise degree centrality: {:?}",
+        diff.centrality
+    );
+
+    // Identical worlds have nothing to report
+    let unchanged = scenario::diff("Same", &baseline, &baseline);
+    assert!(unchanged.centrality.is_empty());
+    assert!(unchanged.tensions.is_empty());
+    assert!(unchanged.asymmetries.is_empty());
+}
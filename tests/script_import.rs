@@ -0,0 +1,81 @@
+//! Integration tests for screenplay import (`narra world import-script`).
+
+mod common;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common::builders::CharacterBuilder;
+use common::harness::TestHarness;
+use narra::embedding::{NoopEmbeddingService, StalenessManager};
+use narra::mcp::types::ConflictMode;
+use narra::models::character::create_character;
+use narra::models::dialogue::{get_character_dialogue, get_scene_dialogue};
+use narra::services::export::ExportService;
+use narra::services::import::ImportService;
+use narra::services::ingest::{fountain, plan_import, store_dialogue};
+
+const SCRIPT: &str = "\
+INT. DINER - NIGHT
+
+ALICE
+Coffee. Black.
+
+BOB
+(grinning)
+Same.
+
+EXT. PARKING LOT - NIGHT
+
+ALICE
+Don't follow me.
+";
+
+#[tokio::test]
+async fn test_fountain_import_creates_scenes_and_dialogue() {
+    let harness = TestHarness::new().await;
+    let alice = create_character(&harness.db, CharacterBuilder::new("Alice").build())
+        .await
+        .expect("Should create Alice");
+
+    let script = fountain::parse(SCRIPT);
+    let world = ExportService::new(harness.db.clone())
+        .export_world()
+        .await
+        .unwrap();
+    let plan = plan_import(&script, "pilot", &world);
+
+    let noop: Arc<dyn narra::embedding::EmbeddingService + Send + Sync> =
+        Arc::new(NoopEmbeddingService::new());
+    let staleness = Arc::new(StalenessManager::new(harness.db.clone(), noop));
+    let result = ImportService::new(harness.db.clone(), staleness)
+        .execute_import(plan.import.clone(), ConflictMode::Error)
+        .await
+        .expect("import should succeed");
+    // Bob, two locations, two events, two scenes; Alice is reused
+    assert_eq!(result.total_created, 7);
+    assert_eq!(result.total_errors, 0);
+
+    let stored = store_dialogue(&harness.db, &script, &plan, &HashSet::new())
+        .await
+        .unwrap();
+    assert_eq!(stored, 3);
+
+    let diner = get_scene_dialogue(&harness.db, "pilot-001").await.unwrap();
+    assert_eq!(diner.len(), 2);
+    assert_eq!(diner[0].character, alice.id);
+    assert_eq!(diner[1].parenthetical.as_deref(), Some("(grinning)"));
+
+    let lines = get_character_dialogue(&harness.db, &alice.id.key().to_string())
+        .await
+        .unwrap();
+    let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+    assert_eq!(texts, vec!["Coffee. Black.", "Don't follow me."]);
+
+    // Storing again replaces rather than duplicates
+    store_dialogue(&harness.db, &script, &plan, &HashSet::new())
+        .await
+        .unwrap();
+    let diner = get_scene_dialogue(&harness.db, "pilot-001").await.unwrap();
+    assert_eq!(diner.len(), 2);
+}
@@ -168,6 +168,7 @@ async fn test_semantic_search_scene_by_mood() {
             date: None,
             date_precision: None,
             duration_end: None,
+            narrative_status: None,
         },
     )
     .await
@@ -433,3 +434,53 @@ async fn test_semantic_search_empty_embeddings() {
 
     println!("✓ Semantic search handles entities with missing embeddings");
 }
+
+/// `reindex` builds the configured ANN index, records it, and can be dropped.
+#[tokio::test]
+async fn test_reindex_vectors_records_built_index() {
+    use narra::services::vector_index::{
+        built_index, drop_indexes, reindex, BuiltVectorIndex, VectorIndexKind, VectorIndexSettings,
+    };
+
+    let harness = TestHarness::new().await;
+    for (name, embedding) in [
+        ("Alice", [1.0f32, 0.0, 0.0, 0.0]),
+        ("Bob", [0.0, 1.0, 0.0, 0.0]),
+    ] {
+        let character = create_character(
+            &harness.db,
+            CharacterCreate {
+                name: name.into(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        harness
+            .db
+            .query("UPDATE $id SET embedding = $embedding")
+            .bind(("id", character.id))
+            .bind(("embedding", embedding.to_vec()))
+            .await
+            .unwrap();
+    }
+
+    let settings = VectorIndexSettings {
+        kind: VectorIndexKind::Hnsw,
+        ..Default::default()
+    };
+    let timings = reindex(&harness.db, &settings, None, 4).await.unwrap();
+    let characters = timings.iter().find(|t| t.table == "character").unwrap();
+    assert_eq!(characters.rows, 2);
+    assert!(characters.before_ms.is_some() && characters.after_ms.is_some());
+    assert_eq!(
+        built_index(&harness.db).await.unwrap(),
+        Some(BuiltVectorIndex {
+            kind: VectorIndexKind::Hnsw,
+            dimensions: 4
+        })
+    );
+
+    drop_indexes(&harness.db).await.unwrap();
+    assert_eq!(built_index(&harness.db).await.unwrap(), None);
+}
@@ -5,13 +5,18 @@
 mod common;
 
 use narra::repository::{EntityRepository, SurrealEntityRepository};
-use narra::services::{CachedContextService, ContextConfig, ContextService};
-use narra::session::{PendingDecision, SessionStateManager};
+use narra::services::{
+    CachedContextService, ContextConfig, ContextService, HeuristicTokenCounter, TokenCounter,
+};
+use narra::session::{
+    expire_pins, generate_startup_context, session_stats, HotDecay, PendingDecision, PinPriority,
+    SessionStateManager, StartupOptions, StartupSection, StartupVerbosity, PIN_SUGGESTION_ACCESSES,
+};
 use pretty_assertions::assert_eq;
 use std::sync::Arc;
 use tempfile::TempDir;
 
-use common::builders::CharacterBuilder;
+use common::builders::{CharacterBuilder, EventBuilder};
 use common::harness::TestHarness;
 
 // ============================================================================
@@ -122,6 +127,64 @@ async fn test_hot_entity_limit() {
     assert_eq!(all_recent[0], "character:149");
 }
 
+/// Test that hot entities cool off over time and across sessions, and that
+/// stats separate focus from neglect.
+#[tokio::test]
+async fn test_hot_entity_decay_and_stats() {
+    let harness = TestHarness::new().await;
+    let temp_dir = TempDir::new().expect("Temp dir");
+    let manager = SessionStateManager::load_or_create(&temp_dir.path().join("session.json"))
+        .expect("Should create session manager")
+        .with_decay(HotDecay {
+            half_life_days: 7.0,
+            per_session: 0.1,
+        });
+    let entity_repo = SurrealEntityRepository::new(harness.db.clone());
+
+    let mut ids = Vec::new();
+    for name in ["Alice", "Bob", "Carol"] {
+        let character = entity_repo
+            .create_character(CharacterBuilder::new(name).build())
+            .await
+            .expect("Character");
+        ids.push(format!("character:{}", character.id.key()));
+    }
+    let (alice, bob, carol) = (&ids[0], &ids[1], &ids[2]);
+
+    // Alice was the focus two sessions ago, Bob is now
+    manager.record_access(alice).await;
+    manager.record_access(alice).await;
+    manager.mark_session_end().await;
+    manager.mark_session_end().await;
+    manager.record_access(bob).await;
+
+    assert_eq!(manager.get_hot(10).await, vec![bob.clone()]);
+    assert!(
+        manager.get_recent(10).await.contains(alice),
+        "Recency is kept even when heat has decayed"
+    );
+
+    let stats = session_stats(&manager, &harness.db, 10)
+        .await
+        .expect("Should compute stats");
+    assert_eq!(stats.session_count, 2);
+    assert_eq!(stats.tracked_entities, 2);
+    assert_eq!(stats.focus.len(), 1);
+    assert_eq!(stats.focus[0].id, *bob);
+    assert_eq!(stats.focus[0].name.as_deref(), Some("Bob"));
+    assert_eq!(stats.neglected.len(), 1);
+    assert_eq!(stats.neglected[0].id, *alice);
+    assert_eq!(stats.neglected[0].accesses, 2);
+    let untouched: Vec<&str> = stats.untouched.iter().map(|e| e.id.as_str()).collect();
+    assert_eq!(untouched, vec![carol.as_str()]);
+
+    // Time decay halves heat every half-life
+    let now = chrono::Utc::now();
+    let week_ago = now - chrono::Duration::days(7);
+    let heat = HotDecay::default().decayed(1.0, week_ago, now);
+    assert!((heat - 0.5).abs() < 1e-9);
+}
+
 // ============================================================================
 // PIN/UNPIN TESTS
 // ============================================================================
@@ -162,6 +225,180 @@ async fn test_pin_unpin_entity() {
     );
 }
 
+/// Test pin priorities, state files from before priorities, and pin suggestions.
+#[tokio::test]
+async fn test_pin_priorities_and_suggestions() {
+    let temp_dir = TempDir::new().expect("Temp dir");
+    let session_path = temp_dir.path().join("session.json");
+
+    // Old state files store pins as plain IDs
+    std::fs::write(
+        &session_path,
+        r#"{"last_session":null,"pinned_entities":["character:old"],"recent_accesses":[],"pending_decisions":[]}"#,
+    )
+    .expect("Should write old state");
+    let manager =
+        SessionStateManager::load_or_create(&session_path).expect("Should load old state");
+    assert_eq!(manager.get_pins().await[0].priority, PinPriority::Normal);
+
+    manager
+        .pin_entity_with("character:minor", PinPriority::Low, None)
+        .await;
+    manager
+        .pin_entity_with("character:hero", PinPriority::High, None)
+        .await;
+    assert_eq!(
+        manager.get_pinned().await,
+        vec!["character:hero", "character:old", "character:minor"]
+    );
+
+    // Re-pinning changes priority without duplicating
+    manager
+        .pin_entity_with("character:minor", PinPriority::High, None)
+        .await;
+    assert_eq!(
+        manager.get_pinned().await,
+        vec!["character:hero", "character:minor", "character:old"]
+    );
+
+    // Frequently accessed, unpinned entities are suggested
+    for _ in 0..PIN_SUGGESTION_ACCESSES {
+        manager.record_access("character:sidekick").await;
+        manager.record_access("character:hero").await;
+    }
+    manager.record_access("character:extra").await;
+    assert_eq!(manager.suggest_pins(3).await, vec!["character:sidekick"]);
+
+    // Pins round-trip in the new format
+    manager.save().await.expect("Should save");
+    let reloaded = SessionStateManager::load_or_create(&session_path).expect("Should reload");
+    assert_eq!(reloaded.get_pins().await[0].priority, PinPriority::High);
+}
+
+/// Test that a pin with an `until` event expires once work moves past it.
+#[tokio::test]
+async fn test_pin_until_event_expires() {
+    let harness = TestHarness::new().await;
+    let temp_dir = TempDir::new().expect("Temp dir");
+    let manager = SessionStateManager::load_or_create(&temp_dir.path().join("session.json"))
+        .expect("Should create session manager");
+    let entity_repo = SurrealEntityRepository::new(harness.db.clone());
+
+    let duel = entity_repo
+        .create_event(EventBuilder::new("The Duel").sequence(5).build())
+        .await
+        .expect("Duel");
+    let aftermath = entity_repo
+        .create_event(EventBuilder::new("Aftermath").sequence(6).build())
+        .await
+        .expect("Aftermath");
+    let duel_id = format!("event:{}", duel.id.key());
+    let aftermath_id = format!("event:{}", aftermath.id.key());
+
+    manager
+        .pin_entity_with("character:rival", PinPriority::High, Some(duel_id.clone()))
+        .await;
+    manager.pin_entity("character:hero").await;
+
+    // Still working on the duel: the pin holds
+    manager.record_access(&duel_id).await;
+    let info = generate_startup_context(&manager, &harness.db, &HeuristicTokenCounter)
+        .await
+        .expect("Should generate context");
+    assert!(info.expired_pins.is_empty());
+    assert_eq!(info.pinned[0].id, "character:rival");
+    assert_eq!(info.pinned[0].until.as_deref(), Some(duel_id.as_str()));
+
+    // Moving on to a later event expires it
+    manager.record_access(&aftermath_id).await;
+    let expired = expire_pins(&manager, &harness.db)
+        .await
+        .expect("Should expire pins");
+    assert_eq!(expired, vec!["character:rival"]);
+    assert_eq!(manager.get_pinned().await, vec!["character:hero"]);
+}
+
+/// Test that startup context follows the project's startup options.
+#[tokio::test]
+async fn test_startup_context_personalization() {
+    let harness = TestHarness::new().await;
+    let temp_dir = TempDir::new().expect("Temp dir");
+    let session_path = temp_dir.path().join("session.json");
+    let entity_repo = SurrealEntityRepository::new(harness.db.clone());
+
+    let mut ids = Vec::new();
+    for name in ["Alice", "Bob", "Carol", "Dave", "Eve"] {
+        let character = entity_repo
+            .create_character(CharacterBuilder::new(name).build())
+            .await
+            .expect("Character");
+        ids.push(format!("character:{}", character.id.key()));
+    }
+    let alice = ids[0].clone();
+
+    // Minimal, pins only, Alice always in view
+    let manager = SessionStateManager::load_or_create(&session_path)
+        .expect("Should create session manager")
+        .with_startup_options(StartupOptions {
+            verbosity: Some(StartupVerbosity::Minimal),
+            sections: vec![StartupSection::Pinned],
+            token_ceiling: None,
+            always_include: vec![alice.clone()],
+        });
+    for id in &ids[1..] {
+        manager.record_access(id).await;
+    }
+    manager.pin_entity(&ids[1]).await;
+    manager.log_entry("drafted the heist", vec![]).await;
+
+    let info = generate_startup_context(&manager, &harness.db, &HeuristicTokenCounter)
+        .await
+        .expect("Should generate context");
+    assert_eq!(info.verbosity, StartupVerbosity::Minimal);
+    assert_eq!(info.summary, "Welcome back.");
+    assert!(info.hot_entities.is_empty());
+    assert!(info.recent_journal.is_empty());
+    assert!(info.world_overview.is_none());
+    assert_eq!(info.pinned.len(), 1);
+    assert_eq!(info.always_included.len(), 1);
+    assert_eq!(info.always_included[0].id, alice);
+
+    // A tight ceiling trims lists but keeps pins and always-included entities
+    let manager = SessionStateManager::load_or_create(&session_path)
+        .expect("Should create session manager")
+        .with_startup_options(StartupOptions {
+            token_ceiling: Some(100),
+            always_include: vec![alice.clone()],
+            ..Default::default()
+        });
+    for id in &ids[1..] {
+        manager.record_access(id).await;
+    }
+    manager.pin_entity(&ids[1]).await;
+    let info = generate_startup_context(&manager, &harness.db, &HeuristicTokenCounter)
+        .await
+        .expect("Should generate context");
+    assert!(info.trimmed > 0);
+    assert!(info.hot_entities.len() < 4);
+    assert_eq!(info.pinned.len(), 1);
+    assert_eq!(info.always_included.len(), 1);
+
+    // The ceiling is measured with the given token counter
+    struct Tiny;
+    impl TokenCounter for Tiny {
+        fn count(&self, text: &str) -> usize {
+            text.len() / 100
+        }
+        fn name(&self) -> &str {
+            "tiny"
+        }
+    }
+    let info = generate_startup_context(&manager, &harness.db, &Tiny)
+        .await
+        .expect("Should generate context");
+    assert_eq!(info.trimmed, 0);
+}
+
 // ============================================================================
 // CONTEXT RESTORATION TESTS
 // ============================================================================
@@ -432,3 +669,95 @@ async fn test_context_hot_entities() {
         "Recently accessed entity should be in hot list"
     );
 }
+
+// ============================================================================
+// SESSION JOURNAL TESTS
+// ============================================================================
+
+/// Test journal entries persist and show up in the startup context.
+#[tokio::test]
+async fn test_session_journal() {
+    let harness = TestHarness::new().await;
+    let temp_dir = TempDir::new().expect("Temp dir");
+    let session_path = temp_dir.path().join("session.json");
+
+    {
+        let manager = SessionStateManager::load_or_create(&session_path)
+            .expect("Should create session manager");
+        manager
+            .log_entry("outlined act 2", vec!["character:alice".to_string()])
+            .await;
+        manager
+            .log_entry("drafted ch 7 confrontation", vec![])
+            .await;
+        manager.save().await.expect("Should save session");
+    }
+
+    let manager =
+        SessionStateManager::load_or_create(&session_path).expect("Should load session manager");
+    let journal = manager.get_journal(10).await;
+    assert_eq!(journal.len(), 2);
+    assert_eq!(journal[0].message, "drafted ch 7 confrontation");
+    assert_eq!(journal[1].entities, vec!["character:alice".to_string()]);
+    assert_eq!(manager.get_journal(1).await.len(), 1);
+
+    let info = generate_startup_context(&manager, &harness.db, &HeuristicTokenCounter)
+        .await
+        .expect("Should generate context");
+    assert_eq!(
+        info.recent_journal.first().map(|e| e.message.as_str()),
+        Some("drafted ch 7 confrontation")
+    );
+}
+
+/// Open todo notes show up in the startup context until resolved.
+#[tokio::test]
+async fn test_session_open_todos() {
+    use narra::models::note::{create_note, set_note_status, NoteCreate, NoteKind, NoteStatus};
+
+    let harness = TestHarness::new().await;
+    let temp_dir = TempDir::new().expect("Temp dir");
+    let manager = SessionStateManager::load_or_create(&temp_dir.path().join("session.json"))
+        .expect("Should create session manager");
+
+    let todo = create_note(
+        &harness.db,
+        NoteCreate {
+            title: "Fix the timeline in act 2".to_string(),
+            body: String::new(),
+            kind: NoteKind::Todo,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create todo");
+    create_note(
+        &harness.db,
+        NoteCreate {
+            title: "Glyph etymology".to_string(),
+            body: String::new(),
+            kind: NoteKind::Research,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create research note");
+
+    let info = generate_startup_context(&manager, &harness.db, &HeuristicTokenCounter)
+        .await
+        .expect("Should generate context");
+    assert_eq!(info.open_todos.len(), 1);
+    assert_eq!(info.open_todos[0].title, "Fix the timeline in act 2");
+
+    set_note_status(
+        &harness.db,
+        &todo.id.key().to_string(),
+        NoteStatus::Resolved,
+    )
+    .await
+    .expect("Should resolve todo");
+    let info = generate_startup_context(&manager, &harness.db, &HeuristicTokenCounter)
+        .await
+        .expect("Should generate context");
+    assert!(info.open_todos.is_empty());
+}
@@ -10,6 +10,7 @@ use common::{to_mutation_input, to_query_input, to_session_input};
 use narra::mcp::tools::export::ExportRequest;
 use narra::mcp::tools::graph::GraphRequest;
 use narra::mcp::{KnowledgeSpec, MutationRequest, NarraServer, QueryRequest, SessionRequest};
+use narra::session::PinPriority;
 use rmcp::handler::server::wrapper::Parameters;
 
 /// Helper to create a character and return its ID.
@@ -21,6 +22,7 @@ async fn create_test_character(server: &NarraServer, name: &str) -> String {
         aliases: None,
         description: Some("Test character for smoke tests".to_string()),
         profile: None,
+        allow_similar: Some(true),
     };
     let result = server
         .handle_mutate(Parameters(to_mutation_input(request)))
@@ -68,6 +70,7 @@ async fn smoke_test_mutate() {
         aliases: None,
         description: Some("Created in smoke test".to_string()),
         profile: None,
+        allow_similar: None,
     };
 
     let result = server
@@ -175,6 +178,8 @@ async fn smoke_test_pin_entity_via_session() {
 
     let request = SessionRequest::PinEntity {
         entity_id: character_id.clone(),
+        priority: PinPriority::High,
+        until: None,
     };
 
     let result = server
@@ -186,6 +191,7 @@ async fn smoke_test_pin_entity_via_session() {
     let pin_result = result.0.pin_result.expect("Should have pin_result");
     assert!(pin_result.success, "Pin should succeed");
     assert_eq!(pin_result.entity_id, character_id);
+    assert_eq!(pin_result.priority, Some(PinPriority::High));
 }
 
 #[tokio::test]
@@ -198,6 +204,8 @@ async fn smoke_test_unpin_entity_via_session() {
     server
         .session(Parameters(to_session_input(SessionRequest::PinEntity {
             entity_id: character_id.clone(),
+            priority: PinPriority::Normal,
+            until: None,
         })))
         .await
         .expect("pin should succeed");
@@ -401,6 +409,7 @@ async fn smoke_test_structured_error_from_mutate() {
     let request = MutationRequest::Delete {
         entity_id: char_id,
         hard: Some(false),
+        force: None,
     };
 
     let result = server